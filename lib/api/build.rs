@@ -122,6 +122,8 @@ fn configure_validation(builder: Builder) -> Builder {
             ("CreateCollection.optimizers_config", ""),
             ("CreateCollection.vectors_config", ""),
             ("CreateCollection.quantization_config", ""),
+            ("CreateCollection.tiering_config", ""),
+            ("CreateCollection.ttl_config", ""),
             ("UpdateCollection.collection_name", "length(min = 1, max = 255)"),
             ("UpdateCollection.optimizers_config", ""),
             ("UpdateCollection.params", ""),
@@ -129,12 +131,16 @@ fn configure_validation(builder: Builder) -> Builder {
             ("UpdateCollection.hnsw_config", ""),
             ("UpdateCollection.vectors_config", ""),
             ("UpdateCollection.quantization_config", ""),
+            ("UpdateCollection.tiering_config", ""),
+            ("UpdateCollection.ttl_config", ""),
             ("DeleteCollection.collection_name", "length(min = 1, max = 255)"),
             ("DeleteCollection.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("CollectionConfig.params", ""),
             ("CollectionConfig.hnsw_config", ""),
             ("CollectionConfig.optimizers_config", ""),
             ("CollectionConfig.quantization_config", ""),
+            ("CollectionConfig.tiering_config", ""),
+            ("CollectionConfig.ttl_config", ""),
             ("CollectionParams.vectors_config", ""),
             ("ChangeAliases.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("ListCollectionAliasesRequest.collection_name", "length(min = 1, max = 255)"),
@@ -154,6 +160,10 @@ fn configure_validation(builder: Builder) -> Builder {
             ("QuantizationConfig.quantization", ""),
             ("QuantizationConfigDiff.quantization", ""),
             ("ScalarQuantization.quantile", "custom = \"crate::grpc::validate::validate_f32_range_min_0_5_max_1\""),
+            ("TieringConfig.cold_after_sec", "range(min = 1)"),
+            ("TtlConfig.field", "length(min = 1)"),
+            ("TtlConfig.ttl_sec", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
+            ("TtlConfig.check_interval_sec", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("UpdateCollectionClusterSetupRequest.timeout", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("UpdateCollectionClusterSetupRequest.operation", ""),
         ], &[
@@ -182,6 +192,7 @@ fn configure_validation(builder: Builder) -> Builder {
         .validates(&[
             ("UpsertPoints.collection_name", "length(min = 1, max = 255)"),
             ("UpsertPoints.points", ""),
+            ("UpsertPoints.update_filter", ""),
             ("DeletePoints.collection_name", "length(min = 1, max = 255)"),
             ("UpdatePointVectors.collection_name", "length(min = 1, max = 255)"),
             ("UpdatePointVectors.vectors", "custom(function = \"crate::grpc::validate::validate_named_vectors_not_empty\", message = \"must specify vectors to update\")"),
@@ -191,6 +202,13 @@ fn configure_validation(builder: Builder) -> Builder {
             ("SetPayloadPoints.collection_name", "length(min = 1, max = 255)"),
             ("DeletePayloadPoints.collection_name", "length(min = 1, max = 255)"),
             ("ClearPayloadPoints.collection_name", "length(min = 1, max = 255)"),
+            ("PatchPayloadPoints.collection_name", "length(min = 1, max = 255)"),
+            ("PatchPayloadPoints.patch", "length(min = 1)"),
+            ("SetBlobPoint.collection_name", "length(min = 1, max = 255)"),
+            ("SetBlobPoint.blob", "length(min = 1)"),
+            ("DeleteBlobPoint.collection_name", "length(min = 1, max = 255)"),
+            ("GetBlobPoint.collection_name", "length(min = 1, max = 255)"),
+            ("GetBlobPoint.limit", "custom = \"crate::grpc::validate::validate_u64_range_min_1\""),
             ("UpdateBatchPoints.collection_name", "length(min = 1, max = 255)"),
             ("UpdateBatchPoints.operations", "length(min = 1)"),
             ("CreateFieldIndexCollection.collection_name", "length(min = 1, max = 255)"),
@@ -270,6 +288,9 @@ fn configure_validation(builder: Builder) -> Builder {
             ("SetPayloadPointsInternal.set_payload_points", ""),
             ("DeletePayloadPointsInternal.delete_payload_points", ""),
             ("ClearPayloadPointsInternal.clear_payload_points", ""),
+            ("PatchPayloadPointsInternal.patch_payload_points", ""),
+            ("SetBlobPointInternal.set_blob_point", ""),
+            ("DeleteBlobPointInternal.delete_blob_point", ""),
             ("CreateFieldIndexCollectionInternal.create_field_index_collection", ""),
             ("DeleteFieldIndexCollectionInternal.delete_field_index_collection", ""),
             ("SearchPointsInternal.search_points", ""),
@@ -291,6 +312,7 @@ fn configure_validation(builder: Builder) -> Builder {
             ("RecommendPointsInternal.recommend_points", ""),
             ("ScrollPointsInternal.scroll_points", ""),
             ("GetPointsInternal.get_points", ""),
+            ("GetBlobPointInternal.get_blob_point", ""),
             ("CountPointsInternal.count_points", ""),
             ("SyncPointsInternal.sync_points", ""),
             ("SyncPoints.collection_name", "length(min = 1, max = 255)"),
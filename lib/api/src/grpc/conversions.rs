@@ -16,8 +16,9 @@ use uuid::Uuid;
 use super::qdrant::raw_query::RawContextPair;
 use super::qdrant::{
     raw_query, start_from, BinaryQuantization, CompressionRatio, DatetimeRange, Direction,
-    GeoLineString, GroupId, MultiVectorComparator, MultiVectorConfig, OrderBy, OrderValue, Range,
-    RawVector, RecommendStrategy, ShardKeySelector, SparseIndices, StartFrom,
+    GeoLineString, GroupId, MultiVectorComparator, MultiVectorConfig, OrderBy, OrderValue,
+    PayloadPatchOperation, PayloadPatchOperationType, Range, RawVector, RecommendStrategy,
+    ShardKeySelector, SparseIndices, StartFrom,
 };
 use crate::grpc::models::{CollectionsResponse, VersionInfo};
 use crate::grpc::qdrant::condition::ConditionOneOf;
@@ -29,16 +30,17 @@ use crate::grpc::qdrant::vectors::VectorsOptions;
 use crate::grpc::qdrant::with_payload_selector::SelectorOptions;
 use crate::grpc::qdrant::{
     shard_key, with_vectors_selector, CollectionDescription, CollectionOperationResponse,
-    Condition, DenseVector, Distance, FieldCondition, Filter, GeoBoundingBox, GeoPoint, GeoPolygon,
-    GeoRadius, HasIdCondition, HealthCheckReply, HnswConfigDiff, IntegerIndexParams,
-    IsEmptyCondition, IsNullCondition, ListCollectionsResponse, ListValue, Match, MinShould,
-    MultiDenseVector, NamedVectors, NestedCondition, PayloadExcludeSelector,
-    PayloadIncludeSelector, PayloadIndexParams, PayloadSchemaInfo, PayloadSchemaType, PointId,
-    PointsOperationResponse, PointsOperationResponseInternal, ProductQuantization,
-    QuantizationConfig, QuantizationSearchParams, QuantizationType, RepeatedIntegers,
-    RepeatedStrings, ScalarQuantization, ScoredPoint, SearchParams, ShardKey, SparseVector, Struct,
-    TextIndexParams, TokenizerType, UpdateResult, UpdateResultInternal, Value, ValuesCount, Vector,
-    Vectors, VectorsSelector, WithPayloadSelector, WithVectorsSelector,
+    Condition, DenseDoubleVector, DenseVector, Distance, FieldCondition, Filter, GeoBoundingBox,
+    GeoPoint, GeoPolygon, GeoRadius, HasIdCondition, HasIdRangeCondition, HealthCheckReply,
+    HnswConfigDiff, IntegerIndexParams, IsEmptyCondition, IsNullCondition, ListCollectionsResponse,
+    ListValue, Match, MinShould, MultiDenseVector, NamedVectors, NestedCondition,
+    PayloadExcludeSelector, PayloadIncludeSelector, PayloadIndexParams, PayloadPatternsSelector,
+    PayloadSchemaInfo, PayloadSchemaType, PointId, PointsOperationResponse,
+    PointsOperationResponseInternal, ProductQuantization, QuantizationConfig,
+    QuantizationSearchParams, QuantizationType, RepeatedIntegers, RepeatedStrings,
+    ScalarQuantization, ScoredPoint, SearchParams, ShardKey, SparseVector, Struct, TextIndexParams,
+    TokenizerType, UpdateResult, UpdateResultInternal, Value, ValuesCount, Vector, Vectors,
+    VectorsSelector, WithPayloadSelector, WithVectorsSelector,
 };
 use crate::rest::schema as rest;
 
@@ -408,6 +410,22 @@ impl TryFrom<WithPayloadSelector> for segment::types::WithPayloadInterface {
                         .collect::<Result<_, _>>()?,
                 )
                 .into(),
+                SelectorOptions::Patterns(s) => {
+                    let parse = |patterns: Vec<String>| {
+                        patterns
+                            .iter()
+                            .map(|pattern| pattern.parse().map_err(Status::invalid_argument))
+                            .collect::<Result<_, _>>()
+                    };
+                    segment::types::WithPayloadInterface::Selector(
+                        segment::types::PayloadSelector::Patterns(
+                            segment::types::PayloadSelectorPatterns {
+                                include: parse(s.include)?,
+                                exclude: parse(s.exclude)?,
+                            },
+                        ),
+                    )
+                }
             }),
             _ => Err(Status::invalid_argument("No PayloadSelector".to_string())),
         }
@@ -434,6 +452,12 @@ impl From<segment::types::WithPayloadInterface> for WithPayloadSelector {
                         fields: s.exclude.iter().map(|f| f.to_string()).collect(),
                     })
                 }
+                segment::types::PayloadSelector::Patterns(s) => {
+                    SelectorOptions::Patterns(PayloadPatternsSelector {
+                        include: s.include.iter().map(|p| p.to_string()).collect(),
+                        exclude: s.exclude.iter().map(|p| p.to_string()).collect(),
+                    })
+                }
             },
         };
         WithPayloadSelector {
@@ -469,6 +493,7 @@ impl From<SearchParams> for segment::types::SearchParams {
             exact: params.exact.unwrap_or(false),
             quantization: params.quantization.map(|q| q.into()),
             indexed_only: params.indexed_only.unwrap_or(false),
+            distance: None, // Not part of the gRPC API
         }
     }
 }
@@ -502,6 +527,13 @@ impl From<segment_vectors::Vector> for Vector {
                 data: vector,
                 indices: None,
                 vectors_count: None,
+                data_double: Vec::new(),
+            },
+            segment_vectors::Vector::DenseDouble(vector) => Self {
+                data: Vec::new(),
+                indices: None,
+                vectors_count: None,
+                data_double: vector,
             },
             segment_vectors::Vector::Sparse(vector) => Self {
                 data: vector.values,
@@ -509,6 +541,7 @@ impl From<segment_vectors::Vector> for Vector {
                     data: vector.indices,
                 }),
                 vectors_count: None,
+                data_double: Vec::new(),
             },
             segment_vectors::Vector::MultiDense(vector) => {
                 let vector_count = vector.multi_vectors().count() as u32;
@@ -516,6 +549,7 @@ impl From<segment_vectors::Vector> for Vector {
                     data: vector.flattened_vectors,
                     indices: None,
                     vectors_count: Some(vector_count),
+                    data_double: Vec::new(),
                 }
             }
         }
@@ -526,6 +560,17 @@ impl TryFrom<Vector> for segment_vectors::Vector {
     type Error = Status;
 
     fn try_from(vector: Vector) -> Result<Self, Self::Error> {
+        // double precision vector
+        if !vector.data_double.is_empty() {
+            if !vector.data.is_empty() || vector.indices.is_some() || vector.vectors_count.is_some()
+            {
+                return Err(Status::invalid_argument(
+                    "Double precision data can't be combined with other vector fields",
+                ));
+            }
+            return Ok(segment_vectors::Vector::DenseDouble(vector.data_double));
+        }
+
         // sparse vector
         if let Some(indices) = vector.indices {
             return Ok(segment_vectors::Vector::Sparse(
@@ -626,6 +671,92 @@ impl TryFrom<OrderValue> for segment::data_types::order_by::OrderValue {
     }
 }
 
+impl From<segment::json_patch::PatchOperation> for PayloadPatchOperation {
+    fn from(value: segment::json_patch::PatchOperation) -> Self {
+        use segment::json_patch::PatchOperation;
+
+        let (op, path, from, value) = match value {
+            PatchOperation::Add { path, value } => {
+                (PayloadPatchOperationType::PatchAdd, path, None, Some(value))
+            }
+            PatchOperation::Remove { path } => {
+                (PayloadPatchOperationType::PatchRemove, path, None, None)
+            }
+            PatchOperation::Replace { path, value } => (
+                PayloadPatchOperationType::PatchReplace,
+                path,
+                None,
+                Some(value),
+            ),
+            PatchOperation::Move { from, path } => {
+                (PayloadPatchOperationType::PatchMove, path, Some(from), None)
+            }
+            PatchOperation::Copy { from, path } => {
+                (PayloadPatchOperationType::PatchCopy, path, Some(from), None)
+            }
+            PatchOperation::Test { path, value } => (
+                PayloadPatchOperationType::PatchTest,
+                path,
+                None,
+                Some(value),
+            ),
+        };
+        Self {
+            op: op as i32,
+            path,
+            from,
+            value: value.map(json_to_proto),
+        }
+    }
+}
+
+impl TryFrom<PayloadPatchOperation> for segment::json_patch::PatchOperation {
+    type Error = Status;
+
+    fn try_from(value: PayloadPatchOperation) -> Result<Self, Self::Error> {
+        let PayloadPatchOperation {
+            op,
+            path,
+            from,
+            value,
+        } = value;
+        let op = PayloadPatchOperationType::from_i32(op)
+            .ok_or_else(|| Status::invalid_argument(format!("unknown patch operation: {op}")))?;
+        let value = || {
+            value
+                .map(proto_to_json)
+                .transpose()?
+                .ok_or_else(|| Status::invalid_argument("patch operation requires a value"))
+        };
+        let from = || {
+            from.ok_or_else(|| Status::invalid_argument("patch operation requires a source path"))
+        };
+        Ok(match op {
+            PayloadPatchOperationType::PatchAdd => Self::Add {
+                path,
+                value: value()?,
+            },
+            PayloadPatchOperationType::PatchRemove => Self::Remove { path },
+            PayloadPatchOperationType::PatchReplace => Self::Replace {
+                path,
+                value: value()?,
+            },
+            PayloadPatchOperationType::PatchMove => Self::Move {
+                from: from()?,
+                path,
+            },
+            PayloadPatchOperationType::PatchCopy => Self::Copy {
+                from: from()?,
+                path,
+            },
+            PayloadPatchOperationType::PatchTest => Self::Test {
+                path,
+                value: value()?,
+            },
+        })
+    }
+}
+
 impl From<segment::types::ScoredPoint> for ScoredPoint {
     fn from(point: segment::types::ScoredPoint) -> Self {
         Self {
@@ -705,6 +836,11 @@ impl From<segment::types::WithVector> for WithVectorsSelector {
             segment::types::WithVector::Selector(include) => {
                 with_vectors_selector::SelectorOptions::Include(VectorsSelector { names: include })
             }
+            segment::types::WithVector::Exclude(selector) => {
+                with_vectors_selector::SelectorOptions::Exclude(VectorsSelector {
+                    names: selector.exclude,
+                })
+            }
         };
         Self {
             selector_options: Some(selector_options),
@@ -720,6 +856,11 @@ impl From<WithVectorsSelector> for segment::types::WithVector {
             Some(with_vectors_selector::SelectorOptions::Include(include)) => {
                 Self::Selector(include.names)
             }
+            Some(with_vectors_selector::SelectorOptions::Exclude(exclude)) => {
+                Self::Exclude(segment::types::WithVectorExclude {
+                    exclude: exclude.names,
+                })
+            }
         }
     }
 }
@@ -892,6 +1033,8 @@ impl From<segment::types::MultiVectorComparator> for MultiVectorComparator {
     fn from(value: segment::types::MultiVectorComparator) -> Self {
         match value {
             segment::types::MultiVectorComparator::MaxSim => MultiVectorComparator::MaxSim,
+            segment::types::MultiVectorComparator::MeanSim => MultiVectorComparator::MeanSim,
+            segment::types::MultiVectorComparator::Sum => MultiVectorComparator::Sum,
         }
     }
 }
@@ -912,6 +1055,8 @@ impl From<MultiVectorComparator> for segment::types::MultiVectorComparator {
     fn from(value: MultiVectorComparator) -> Self {
         match value {
             MultiVectorComparator::MaxSim => segment::types::MultiVectorComparator::MaxSim,
+            MultiVectorComparator::MeanSim => segment::types::MultiVectorComparator::MeanSim,
+            MultiVectorComparator::Sum => segment::types::MultiVectorComparator::Sum,
         }
     }
 }
@@ -1016,6 +1161,9 @@ impl TryFrom<Condition> for segment::types::Condition {
                 ConditionOneOf::Nested(nested) => Ok(segment::types::Condition::Nested(
                     segment::types::NestedCondition::new(nested.try_into()?),
                 )),
+                ConditionOneOf::HasIdRange(has_id_range) => {
+                    Ok(segment::types::Condition::HasIdRange(has_id_range.into()))
+                }
             };
         }
         Err(Status::invalid_argument("Malformed Condition type"))
@@ -1031,6 +1179,9 @@ impl From<segment::types::Condition> for Condition {
             }
             segment::types::Condition::IsNull(is_null) => ConditionOneOf::IsNull(is_null.into()),
             segment::types::Condition::HasId(has_id) => ConditionOneOf::HasId(has_id.into()),
+            segment::types::Condition::HasIdRange(has_id_range) => {
+                ConditionOneOf::HasIdRange(has_id_range.into())
+            }
             segment::types::Condition::Filter(filter) => ConditionOneOf::Filter(filter.into()),
             segment::types::Condition::Nested(nested) => {
                 ConditionOneOf::Nested(nested.nested.into())
@@ -1128,6 +1279,20 @@ impl From<segment::types::HasIdCondition> for HasIdCondition {
     }
 }
 
+impl From<HasIdRangeCondition> for segment::types::HasIdRangeCondition {
+    fn from(value: HasIdRangeCondition) -> Self {
+        let HasIdRangeCondition { gte, lt } = value;
+        segment::types::PointIdRange { gte, lt }.into()
+    }
+}
+
+impl From<segment::types::HasIdRangeCondition> for HasIdRangeCondition {
+    fn from(value: segment::types::HasIdRangeCondition) -> Self {
+        let segment::types::PointIdRange { gte, lt } = value.has_id_range;
+        Self { gte, lt }
+    }
+}
+
 impl TryFrom<FieldCondition> for segment::types::FieldCondition {
     type Error = Status;
 
@@ -1663,6 +1828,18 @@ impl From<segment_vectors::DenseVector> for DenseVector {
     }
 }
 
+impl From<DenseDoubleVector> for segment_vectors::DenseDoubleVector {
+    fn from(value: DenseDoubleVector) -> Self {
+        value.data
+    }
+}
+
+impl From<segment_vectors::DenseDoubleVector> for DenseDoubleVector {
+    fn from(value: segment_vectors::DenseDoubleVector) -> Self {
+        Self { data: value }
+    }
+}
+
 impl From<sparse::common::sparse_vector::SparseVector> for SparseVector {
     fn from(value: sparse::common::sparse_vector::SparseVector) -> Self {
         let sparse::common::sparse_vector::SparseVector { indices, values } = value;
@@ -1717,6 +1894,7 @@ impl From<segment_vectors::Vector> for RawVector {
 
         let variant = match value {
             Vector::Dense(vector) => Variant::Dense(DenseVector::from(vector)),
+            Vector::DenseDouble(vector) => Variant::DenseDouble(DenseDoubleVector::from(vector)),
             Vector::Sparse(vector) => Variant::Sparse(SparseVector::from(vector)),
             Vector::MultiDense(vector) => Variant::MultiDense(MultiDenseVector::from(vector)),
         };
@@ -1747,6 +1925,9 @@ impl TryFrom<RawVector> for segment_vectors::Vector {
             Variant::MultiDense(multi_dense) => segment_vectors::Vector::MultiDense(
                 segment_vectors::MultiDenseVector::from(multi_dense),
             ),
+            Variant::DenseDouble(dense_double) => segment_vectors::Vector::DenseDouble(
+                segment_vectors::DenseDoubleVector::from(dense_double),
+            ),
         };
 
         Ok(vector)
@@ -1,6 +1,7 @@
 use std::fmt::Debug;
 
 use schemars::JsonSchema;
+use segment::common::request_usage::RequestUsageReport;
 use serde;
 use serde::Serialize;
 
@@ -43,6 +44,9 @@ pub struct ApiResponse<D> {
     pub result: Option<D>,
     pub status: ApiStatus,
     pub time: f64,
+    /// Resources used by the request, if requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usage: Option<RequestUsageReport>,
 }
 
 #[derive(Debug, Serialize, JsonSchema)]
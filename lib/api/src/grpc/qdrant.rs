@@ -278,6 +278,12 @@ pub struct WalConfigDiff {
     /// Number of segments to create in advance
     #[prost(uint64, optional, tag = "2")]
     pub wal_segments_ahead: ::core::option::Option<u64>,
+    /// Compression of WAL entries, applies to new entries only
+    #[prost(enumeration = "WalCompression", optional, tag = "3")]
+    pub wal_compression: ::core::option::Option<i32>,
+    /// When write operations are persisted to disk
+    #[prost(enumeration = "DurabilityPolicy", optional, tag = "4")]
+    pub wal_durability: ::core::option::Option<i32>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -348,6 +354,10 @@ pub struct OptimizersConfigDiff {
     /// If 0 - no optimization threads, optimizations will be disabled.
     #[prost(uint64, optional, tag = "8")]
     pub max_optimization_threads: ::core::option::Option<u64>,
+    ///
+    /// Strategy of selecting segments to merge, when there are more segments than `default_segment_number`
+    #[prost(enumeration = "MergePolicy", optional, tag = "9")]
+    pub merge_policy: ::core::option::Option<i32>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -443,6 +453,46 @@ pub mod quantization_config_diff {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TieringConfig {
+    /// If false - segments are never moved to the cold tier, default = true
+    #[prost(bool, optional, tag = "1")]
+    pub enabled: ::core::option::Option<bool>,
+    /// Number of seconds without any read or write touching a segment, after which the segment is moved to the cold tier
+    #[prost(uint64, tag = "2")]
+    #[validate(range(min = 1))]
+    pub cold_after_sec: u64,
+    /// Segments with fewer points are kept in the hot tier, default = 0
+    #[prost(uint64, optional, tag = "3")]
+    pub min_segment_points: ::core::option::Option<u64>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TtlConfig {
+    /// If false - points are never deleted by TTL, default = true
+    #[prost(bool, optional, tag = "1")]
+    pub enabled: ::core::option::Option<bool>,
+    /// Payload field with the datetime, from which the lifetime of the point is counted, must be set together with `ttl_sec`
+    #[prost(string, optional, tag = "2")]
+    #[validate(length(min = 1))]
+    pub field: ::core::option::Option<::prost::alloc::string::String>,
+    /// Number of seconds since the datetime in `field`, after which the point is deleted
+    #[prost(uint64, optional, tag = "3")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub ttl_sec: ::core::option::Option<u64>,
+    /// Interval between checks for expired points, in seconds, default = 60
+    #[prost(uint64, optional, tag = "4")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub check_interval_sec: ::core::option::Option<u64>,
+    /// If true - points may have own expiration moment, set with `expires_at`, default = false
+    #[prost(bool, optional, tag = "5")]
+    pub point_expiry: ::core::option::Option<bool>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CreateCollection {
     /// Name of the collection
     #[prost(string, tag = "1")]
@@ -495,6 +545,14 @@ pub struct CreateCollection {
     /// Configuration for sparse vectors
     #[prost(message, optional, tag = "16")]
     pub sparse_vectors_config: ::core::option::Option<SparseVectorConfig>,
+    /// Hot/cold storage tiering, if not specified - all segments are kept in the hot tier
+    #[prost(message, optional, tag = "17")]
+    #[validate]
+    pub tiering_config: ::core::option::Option<TieringConfig>,
+    /// Automatic expiry of points, if not specified - points never expire
+    #[prost(message, optional, tag = "18")]
+    #[validate]
+    pub ttl_config: ::core::option::Option<TtlConfig>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -532,6 +590,14 @@ pub struct UpdateCollection {
     /// New sparse vector parameters
     #[prost(message, optional, tag = "8")]
     pub sparse_vectors_config: ::core::option::Option<SparseVectorConfig>,
+    /// New hot/cold storage tiering parameters
+    #[prost(message, optional, tag = "9")]
+    #[validate]
+    pub tiering_config: ::core::option::Option<TieringConfig>,
+    /// New points expiry parameters
+    #[prost(message, optional, tag = "10")]
+    #[validate]
+    pub ttl_config: ::core::option::Option<TtlConfig>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -630,6 +696,14 @@ pub struct CollectionConfig {
     #[prost(message, optional, tag = "5")]
     #[validate]
     pub quantization_config: ::core::option::Option<QuantizationConfig>,
+    /// Configuration of the hot/cold storage tiering
+    #[prost(message, optional, tag = "6")]
+    #[validate]
+    pub tiering_config: ::core::option::Option<TieringConfig>,
+    /// Configuration of the automatic expiry of points
+    #[prost(message, optional, tag = "7")]
+    #[validate]
+    pub ttl_config: ::core::option::Option<TtlConfig>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -1114,6 +1188,7 @@ pub enum Datatype {
     Float32 = 1,
     Uint8 = 2,
     Float16 = 3,
+    Float64 = 4,
 }
 impl Datatype {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -1126,6 +1201,7 @@ impl Datatype {
             Datatype::Float32 => "Float32",
             Datatype::Uint8 => "Uint8",
             Datatype::Float16 => "Float16",
+            Datatype::Float64 => "Float64",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -1135,6 +1211,7 @@ impl Datatype {
             "Float32" => Some(Self::Float32),
             "Uint8" => Some(Self::Uint8),
             "Float16" => Some(Self::Float16),
+            "Float64" => Some(Self::Float64),
             _ => None,
         }
     }
@@ -1172,6 +1249,8 @@ impl Modifier {
 #[repr(i32)]
 pub enum MultiVectorComparator {
     MaxSim = 0,
+    MeanSim = 1,
+    Sum = 2,
 }
 impl MultiVectorComparator {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -1181,12 +1260,16 @@ impl MultiVectorComparator {
     pub fn as_str_name(&self) -> &'static str {
         match self {
             MultiVectorComparator::MaxSim => "MaxSim",
+            MultiVectorComparator::MeanSim => "MeanSim",
+            MultiVectorComparator::Sum => "Sum",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
     pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
         match value {
             "MaxSim" => Some(Self::MaxSim),
+            "MeanSim" => Some(Self::MeanSim),
+            "Sum" => Some(Self::Sum),
             _ => None,
         }
     }
@@ -1378,6 +1461,101 @@ impl CompressionRatio {
 #[derive(serde::Serialize)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
+pub enum WalCompression {
+    /// Store entries as is
+    NoCompression = 0,
+    /// Fast compression with moderate ratio
+    Lz4 = 1,
+    /// Better compression ratio at the cost of more CPU
+    Zstd = 2,
+}
+impl WalCompression {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            WalCompression::NoCompression => "NoCompression",
+            WalCompression::Lz4 => "Lz4",
+            WalCompression::Zstd => "Zstd",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "NoCompression" => Some(Self::NoCompression),
+            "Lz4" => Some(Self::Lz4),
+            "Zstd" => Some(Self::Zstd),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum DurabilityPolicy {
+    /// Fsync WAL after every write operation, acknowledged operations survive power loss
+    Fsync = 0,
+    /// Fsync WAL for operations with `wait=true` and every `flush_interval_sec` otherwise
+    Interval = 1,
+    /// Never fsync WAL explicitly, recent operations might be lost on power loss
+    OsBuffered = 2,
+}
+impl DurabilityPolicy {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            DurabilityPolicy::Fsync => "Fsync",
+            DurabilityPolicy::Interval => "Interval",
+            DurabilityPolicy::OsBuffered => "OsBuffered",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Fsync" => Some(Self::Fsync),
+            "Interval" => Some(Self::Interval),
+            "OsBuffered" => Some(Self::OsBuffered),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum MergePolicy {
+    /// Merge smallest segments together
+    SizeTiered = 0,
+    /// Merge segments of a similar size only
+    Leveled = 1,
+}
+impl MergePolicy {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            MergePolicy::SizeTiered => "SizeTiered",
+            MergePolicy::Leveled => "Leveled",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "SizeTiered" => Some(Self::SizeTiered),
+            "Leveled" => Some(Self::Leveled),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
 pub enum ShardingMethod {
     /// Auto-sharding based on record ids
     Auto = 0,
@@ -3736,6 +3914,9 @@ pub struct Vector {
     /// Number of vectors per multi vector
     #[prost(uint32, optional, tag = "3")]
     pub vectors_count: ::core::option::Option<u32>,
+    /// Vector data in double precision, for float64 vectors
+    #[prost(double, repeated, tag = "4")]
+    pub data_double: ::prost::alloc::vec::Vec<f64>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -3747,6 +3928,13 @@ pub struct DenseVector {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DenseDoubleVector {
+    #[prost(double, repeated, tag = "1")]
+    pub data: ::prost::alloc::vec::Vec<f64>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SparseVector {
     #[prost(float, repeated, tag = "1")]
     pub values: ::prost::alloc::vec::Vec<f32>,
@@ -3765,7 +3953,7 @@ pub struct MultiDenseVector {
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct VectorInput {
-    #[prost(oneof = "vector_input::Variant", tags = "1, 2, 3, 4")]
+    #[prost(oneof = "vector_input::Variant", tags = "1, 2, 3, 4, 5")]
     pub variant: ::core::option::Option<vector_input::Variant>,
 }
 /// Nested message and enum types in `VectorInput`.
@@ -3782,6 +3970,8 @@ pub mod vector_input {
         Sparse(super::SparseVector),
         #[prost(message, tag = "4")]
         MultiDense(super::MultiDenseVector),
+        #[prost(message, tag = "5")]
+        DenseDouble(super::DenseDoubleVector),
     }
 }
 /// ---------------------------------------------
@@ -3816,6 +4006,19 @@ pub struct UpsertPoints {
     /// Option for custom sharding to specify used shard keys
     #[prost(message, optional, tag = "5")]
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// Defines whether new points are inserted and existing points are updated
+    #[prost(enumeration = "UpdateMode", optional, tag = "6")]
+    pub update_mode: ::core::option::Option<i32>,
+    /// If specified, existing points are updated only if they satisfy this filter
+    #[prost(message, optional, tag = "7")]
+    #[validate]
+    pub update_filter: ::core::option::Option<Filter>,
+    /// If specified, the operation is rejected unless all points exist and have this version on the shard leader
+    #[prost(uint64, optional, tag = "8")]
+    pub if_version: ::core::option::Option<u64>,
+    /// If specified, this payload field is used as a logical key, existing point with the same key is replaced instead of duplicated
+    #[prost(string, optional, tag = "9")]
+    pub dedup_key: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -3838,6 +4041,9 @@ pub struct DeletePoints {
     /// Option for custom sharding to specify used shard keys
     #[prost(message, optional, tag = "5")]
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// If specified, the operation is rejected unless all points exist and have this version on the shard leader
+    #[prost(uint64, optional, tag = "6")]
+    pub if_version: ::core::option::Option<u64>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -3997,6 +4203,120 @@ pub struct ClearPayloadPoints {
     #[prost(message, optional, tag = "5")]
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
 }
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PayloadPatchOperation {
+    /// Operation type
+    #[prost(enumeration = "PayloadPatchOperationType", tag = "1")]
+    pub op: i32,
+    /// JSON Pointer to the target location
+    #[prost(string, tag = "2")]
+    pub path: ::prost::alloc::string::String,
+    /// JSON Pointer to the source location, required for move and copy
+    #[prost(string, optional, tag = "3")]
+    pub from: ::core::option::Option<::prost::alloc::string::String>,
+    /// Value to add, to replace with or to test against
+    #[prost(message, optional, tag = "4")]
+    pub value: ::core::option::Option<Value>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PatchPayloadPoints {
+    /// name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Wait until the changes have been applied?
+    #[prost(bool, optional, tag = "2")]
+    pub wait: ::core::option::Option<bool>,
+    /// JSON Patch (RFC 6902) operations, applied to the payload of each point in order
+    #[prost(message, repeated, tag = "3")]
+    #[validate(length(min = 1))]
+    pub patch: ::prost::alloc::vec::Vec<PayloadPatchOperation>,
+    /// Affected points
+    #[prost(message, optional, tag = "4")]
+    pub points_selector: ::core::option::Option<PointsSelector>,
+    /// Write ordering guarantees
+    #[prost(message, optional, tag = "5")]
+    pub ordering: ::core::option::Option<WriteOrdering>,
+    /// Option for custom sharding to specify used shard keys
+    #[prost(message, optional, tag = "6")]
+    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetBlobPoint {
+    /// name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Wait until the changes have been applied?
+    #[prost(bool, optional, tag = "2")]
+    pub wait: ::core::option::Option<bool>,
+    /// Point to attach the blob to
+    #[prost(message, optional, tag = "3")]
+    pub id: ::core::option::Option<PointId>,
+    /// Opaque binary data, replaces the existing blob of the point
+    #[prost(bytes = "vec", tag = "4")]
+    #[validate(length(min = 1))]
+    pub blob: ::prost::alloc::vec::Vec<u8>,
+    /// Write ordering guarantees
+    #[prost(message, optional, tag = "5")]
+    pub ordering: ::core::option::Option<WriteOrdering>,
+    /// Option for custom sharding to specify used shard keys
+    #[prost(message, optional, tag = "6")]
+    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeleteBlobPoint {
+    /// name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Wait until the changes have been applied?
+    #[prost(bool, optional, tag = "2")]
+    pub wait: ::core::option::Option<bool>,
+    /// Point to remove the blob from
+    #[prost(message, optional, tag = "3")]
+    pub id: ::core::option::Option<PointId>,
+    /// Write ordering guarantees
+    #[prost(message, optional, tag = "4")]
+    pub ordering: ::core::option::Option<WriteOrdering>,
+    /// Option for custom sharding to specify used shard keys
+    #[prost(message, optional, tag = "5")]
+    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetBlobPoint {
+    /// name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Point to read the blob of
+    #[prost(message, optional, tag = "2")]
+    pub id: ::core::option::Option<PointId>,
+    /// Position of the first byte to read, default is 0
+    #[prost(uint64, optional, tag = "3")]
+    pub offset: ::core::option::Option<u64>,
+    /// Maximal number of bytes to read, default is the whole blob
+    #[prost(uint64, optional, tag = "4")]
+    #[validate(custom = "crate::grpc::validate::validate_u64_range_min_1")]
+    pub limit: ::core::option::Option<u64>,
+    /// Specify in which shards to look for the point, if not specified - look in all shards
+    #[prost(message, optional, tag = "5")]
+    pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+}
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -4062,8 +4382,19 @@ pub struct PayloadExcludeSelector {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PayloadPatternsSelector {
+    /// Only include keys matching any of these patterns, e.g. `metadata.*`. All keys are included, if empty
+    #[prost(string, repeated, tag = "1")]
+    pub include: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Exclude keys matching any of these patterns, even if they are included
+    #[prost(string, repeated, tag = "2")]
+    pub exclude: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct WithPayloadSelector {
-    #[prost(oneof = "with_payload_selector::SelectorOptions", tags = "1, 2, 3")]
+    #[prost(oneof = "with_payload_selector::SelectorOptions", tags = "1, 2, 3, 4")]
     pub selector_options: ::core::option::Option<with_payload_selector::SelectorOptions>,
 }
 /// Nested message and enum types in `WithPayloadSelector`.
@@ -4079,6 +4410,8 @@ pub mod with_payload_selector {
         Include(super::PayloadIncludeSelector),
         #[prost(message, tag = "3")]
         Exclude(super::PayloadExcludeSelector),
+        #[prost(message, tag = "4")]
+        Patterns(super::PayloadPatternsSelector),
     }
 }
 #[derive(validator::Validate)]
@@ -4123,7 +4456,7 @@ pub struct VectorsSelector {
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct WithVectorsSelector {
-    #[prost(oneof = "with_vectors_selector::SelectorOptions", tags = "1, 2")]
+    #[prost(oneof = "with_vectors_selector::SelectorOptions", tags = "1, 2, 3")]
     pub selector_options: ::core::option::Option<with_vectors_selector::SelectorOptions>,
 }
 /// Nested message and enum types in `WithVectorsSelector`.
@@ -4138,6 +4471,9 @@ pub mod with_vectors_selector {
         /// List of payload keys to include into result
         #[prost(message, tag = "2")]
         Include(super::VectorsSelector),
+        /// List of vectors to exclude from the result, all other vectors are returned
+        #[prost(message, tag = "3")]
+        Exclude(super::VectorsSelector),
     }
 }
 #[derive(validator::Validate)]
@@ -4892,6 +5228,15 @@ pub mod points_update_operation {
         /// Option for custom sharding to specify used shard keys
         #[prost(message, optional, tag = "2")]
         pub shard_key_selector: ::core::option::Option<super::ShardKeySelector>,
+        /// Defines whether new points are inserted and existing points are updated
+        #[prost(enumeration = "super::UpdateMode", optional, tag = "3")]
+        pub update_mode: ::core::option::Option<i32>,
+        /// If specified, existing points are updated only if they satisfy this filter
+        #[prost(message, optional, tag = "4")]
+        pub update_filter: ::core::option::Option<super::Filter>,
+        /// If specified, this payload field is used as a logical key, existing point with the same key is replaced instead of duplicated
+        #[prost(string, optional, tag = "5")]
+        pub dedup_key: ::core::option::Option<::prost::alloc::string::String>,
     }
     #[derive(serde::Serialize)]
     #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5229,6 +5574,9 @@ pub struct RetrievedPoint {
     /// Shard key
     #[prost(message, optional, tag = "5")]
     pub shard_key: ::core::option::Option<ShardKey>,
+    /// Last update operation applied to this point. Versions are local to the replica, which served the request
+    #[prost(uint64, optional, tag = "6")]
+    pub version: ::core::option::Option<u64>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5243,6 +5591,28 @@ pub struct GetResponse {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BlobChunk {
+    /// Requested bytes of the blob
+    #[prost(bytes = "vec", tag = "1")]
+    pub data: ::prost::alloc::vec::Vec<u8>,
+    /// Size of the whole blob in bytes
+    #[prost(uint64, tag = "2")]
+    pub total_size: u64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetBlobResponse {
+    /// Requested part of the blob, absent if the point has no blob
+    #[prost(message, optional, tag = "1")]
+    pub result: ::core::option::Option<BlobChunk>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct RecommendResponse {
     #[prost(message, repeated, tag = "1")]
     pub result: ::prost::alloc::vec::Vec<ScoredPoint>,
@@ -5293,6 +5663,23 @@ pub struct RecommendGroupsResponse {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpsertStreamResponse {
+    /// Number of applied batches
+    #[prost(uint64, tag = "1")]
+    pub batches_count: u64,
+    /// Number of upserted points
+    #[prost(uint64, tag = "2")]
+    pub points_count: u64,
+    /// Result of the last applied batch
+    #[prost(message, optional, tag = "3")]
+    pub result: ::core::option::Option<UpdateResult>,
+    /// Time spent to process
+    #[prost(double, tag = "4")]
+    pub time: f64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct UpdateBatchResponse {
     #[prost(message, repeated, tag = "1")]
     pub result: ::prost::alloc::vec::Vec<UpdateResult>,
@@ -5335,7 +5722,7 @@ pub struct MinShould {
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Condition {
-    #[prost(oneof = "condition::ConditionOneOf", tags = "1, 2, 3, 4, 5, 6")]
+    #[prost(oneof = "condition::ConditionOneOf", tags = "1, 2, 3, 4, 5, 6, 7")]
     #[validate]
     pub condition_one_of: ::core::option::Option<condition::ConditionOneOf>,
 }
@@ -5357,6 +5744,8 @@ pub mod condition {
         IsNull(super::IsNullCondition),
         #[prost(message, tag = "6")]
         Nested(super::NestedCondition),
+        #[prost(message, tag = "7")]
+        HasIdRange(super::HasIdRangeCondition),
     }
 }
 #[derive(serde::Serialize)]
@@ -5380,6 +5769,17 @@ pub struct HasIdCondition {
     #[prost(message, repeated, tag = "1")]
     pub has_id: ::prost::alloc::vec::Vec<PointId>,
 }
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HasIdRangeCondition {
+    /// Point ID greater than or equal
+    #[prost(uint64, optional, tag = "1")]
+    pub gte: ::core::option::Option<u64>,
+    /// Point ID less than
+    #[prost(uint64, optional, tag = "2")]
+    pub lt: ::core::option::Option<u64>,
+}
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5604,6 +6004,9 @@ pub struct PointStruct {
     #[prost(message, optional, tag = "4")]
     #[validate]
     pub vectors: ::core::option::Option<Vectors>,
+    /// Moment after which the point is deleted, requires `point_expiry` in the collection TTL config
+    #[prost(message, optional, tag = "5")]
+    pub expires_at: ::core::option::Option<::prost_wkt_types::Timestamp>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5650,6 +6053,39 @@ impl WriteOrderingType {
 #[derive(serde::Serialize)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
+pub enum UpdateMode {
+    /// Insert new points and update existing ones, default
+    Upsert = 0,
+    /// Only insert new points, existing points are left untouched
+    InsertOnly = 1,
+    /// Only update existing points, new points are ignored
+    UpdateOnly = 2,
+}
+impl UpdateMode {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            UpdateMode::Upsert => "Upsert",
+            UpdateMode::InsertOnly => "InsertOnly",
+            UpdateMode::UpdateOnly => "UpdateOnly",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Upsert" => Some(Self::Upsert),
+            "InsertOnly" => Some(Self::InsertOnly),
+            "UpdateOnly" => Some(Self::UpdateOnly),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
 pub enum ReadConsistencyType {
     /// Send request to all nodes and return points which are present on all of them
     All = 0,
@@ -5683,13 +6119,58 @@ impl ReadConsistencyType {
 #[derive(serde::Serialize)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
-pub enum FieldType {
-    Keyword = 0,
-    Integer = 1,
-    Float = 2,
-    Geo = 3,
-    Text = 4,
-    Bool = 5,
+pub enum PayloadPatchOperationType {
+    /// Add a value to an object or insert it into an array
+    PatchAdd = 0,
+    /// Remove a value from an object or an array
+    PatchRemove = 1,
+    /// Replace an existing value
+    PatchReplace = 2,
+    /// Remove a value and add it at another location
+    PatchMove = 3,
+    /// Copy a value to another location
+    PatchCopy = 4,
+    /// Check that the value at the location is equal to the given one
+    PatchTest = 5,
+}
+impl PayloadPatchOperationType {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            PayloadPatchOperationType::PatchAdd => "PatchAdd",
+            PayloadPatchOperationType::PatchRemove => "PatchRemove",
+            PayloadPatchOperationType::PatchReplace => "PatchReplace",
+            PayloadPatchOperationType::PatchMove => "PatchMove",
+            PayloadPatchOperationType::PatchCopy => "PatchCopy",
+            PayloadPatchOperationType::PatchTest => "PatchTest",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "PatchAdd" => Some(Self::PatchAdd),
+            "PatchRemove" => Some(Self::PatchRemove),
+            "PatchReplace" => Some(Self::PatchReplace),
+            "PatchMove" => Some(Self::PatchMove),
+            "PatchCopy" => Some(Self::PatchCopy),
+            "PatchTest" => Some(Self::PatchTest),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum FieldType {
+    Keyword = 0,
+    Integer = 1,
+    Float = 2,
+    Geo = 3,
+    Text = 4,
+    Bool = 5,
     Datetime = 6,
 }
 impl FieldType {
@@ -6495,6 +6976,141 @@ pub mod points_client {
             req.extensions_mut().insert(GrpcMethod::new("qdrant.Points", "UpdateBatch"));
             self.inner.unary(req, path, codec).await
         }
+        ///
+        /// Perform insert + updates on points from a stream of batches. Batches are applied one by one in the order they are received.
+        pub async fn upsert_stream(
+            &mut self,
+            request: impl tonic::IntoStreamingRequest<Message = super::UpsertPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::UpsertStreamResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Points/UpsertStream",
+            );
+            let mut req = request.into_streaming_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Points", "UpsertStream"));
+            self.inner.client_streaming(req, path, codec).await
+        }
+        ///
+        /// Modify payload of points with JSON Patch (RFC 6902) operations
+        pub async fn patch_payload(
+            &mut self,
+            request: impl tonic::IntoRequest<super::PatchPayloadPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointsOperationResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Points/PatchPayload",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Points", "PatchPayload"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Attach a binary blob to a point, replacing the existing one
+        pub async fn set_blob(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetBlobPoint>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointsOperationResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Points/SetBlob",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Points", "SetBlob"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Remove the binary blob of a point
+        pub async fn delete_blob(
+            &mut self,
+            request: impl tonic::IntoRequest<super::DeleteBlobPoint>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointsOperationResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Points/DeleteBlob",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Points", "DeleteBlob"));
+            self.inner.unary(req, path, codec).await
+        }
+        ///
+        /// Read the binary blob of a point. Large blobs can be read in parts with `offset` and `limit`
+        pub async fn get_blob(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetBlobPoint>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetBlobResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Points/GetBlob",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Points", "GetBlob"));
+            self.inner.unary(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -6704,6 +7320,51 @@ pub mod points_server {
             tonic::Response<super::UpdateBatchResponse>,
             tonic::Status,
         >;
+        ///
+        /// Perform insert + updates on points from a stream of batches. Batches are applied one by one in the order they are received.
+        async fn upsert_stream(
+            &self,
+            request: tonic::Request<tonic::Streaming<super::UpsertPoints>>,
+        ) -> std::result::Result<
+            tonic::Response<super::UpsertStreamResponse>,
+            tonic::Status,
+        >;
+        ///
+        /// Modify payload of points with JSON Patch (RFC 6902) operations
+        async fn patch_payload(
+            &self,
+            request: tonic::Request<super::PatchPayloadPoints>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointsOperationResponse>,
+            tonic::Status,
+        >;
+        ///
+        /// Attach a binary blob to a point, replacing the existing one
+        async fn set_blob(
+            &self,
+            request: tonic::Request<super::SetBlobPoint>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointsOperationResponse>,
+            tonic::Status,
+        >;
+        ///
+        /// Remove the binary blob of a point
+        async fn delete_blob(
+            &self,
+            request: tonic::Request<super::DeleteBlobPoint>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointsOperationResponse>,
+            tonic::Status,
+        >;
+        ///
+        /// Read the binary blob of a point. Large blobs can be read in parts with `offset` and `limit`
+        async fn get_blob(
+            &self,
+            request: tonic::Request<super::GetBlobPoint>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetBlobResponse>,
+            tonic::Status,
+        >;
     }
     #[derive(Debug)]
     pub struct PointsServer<T: Points> {
@@ -7770,6 +8431,230 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
+                "/qdrant.Points/UpsertStream" => {
+                    #[allow(non_camel_case_types)]
+                    struct UpsertStreamSvc<T: Points>(pub Arc<T>);
+                    impl<
+                        T: Points,
+                    > tonic::server::ClientStreamingService<super::UpsertPoints>
+                    for UpsertStreamSvc<T> {
+                        type Response = super::UpsertStreamResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                tonic::Streaming<super::UpsertPoints>,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::upsert_stream(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = UpsertStreamSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.client_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Points/PatchPayload" => {
+                    #[allow(non_camel_case_types)]
+                    struct PatchPayloadSvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::PatchPayloadPoints>
+                    for PatchPayloadSvc<T> {
+                        type Response = super::PointsOperationResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::PatchPayloadPoints>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::patch_payload(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = PatchPayloadSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Points/SetBlob" => {
+                    #[allow(non_camel_case_types)]
+                    struct SetBlobSvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::SetBlobPoint>
+                    for SetBlobSvc<T> {
+                        type Response = super::PointsOperationResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SetBlobPoint>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::set_blob(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = SetBlobSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Points/DeleteBlob" => {
+                    #[allow(non_camel_case_types)]
+                    struct DeleteBlobSvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::DeleteBlobPoint>
+                    for DeleteBlobSvc<T> {
+                        type Response = super::PointsOperationResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DeleteBlobPoint>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::delete_blob(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = DeleteBlobSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Points/GetBlob" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetBlobSvc<T: Points>(pub Arc<T>);
+                    impl<T: Points> tonic::server::UnaryService<super::GetBlobPoint>
+                    for GetBlobSvc<T> {
+                        type Response = super::GetBlobResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetBlobPoint>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::get_blob(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetBlobSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         Ok(
@@ -7942,6 +8827,45 @@ pub struct ClearPayloadPointsInternal {
 #[derive(validator::Validate)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PatchPayloadPointsInternal {
+    #[prost(message, optional, tag = "1")]
+    #[validate]
+    pub patch_payload_points: ::core::option::Option<PatchPayloadPoints>,
+    #[prost(uint32, optional, tag = "2")]
+    pub shard_id: ::core::option::Option<u32>,
+    #[prost(message, optional, tag = "3")]
+    pub clock_tag: ::core::option::Option<ClockTag>,
+}
+#[derive(serde::Serialize)]
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetBlobPointInternal {
+    #[prost(message, optional, tag = "1")]
+    #[validate]
+    pub set_blob_point: ::core::option::Option<SetBlobPoint>,
+    #[prost(uint32, optional, tag = "2")]
+    pub shard_id: ::core::option::Option<u32>,
+    #[prost(message, optional, tag = "3")]
+    pub clock_tag: ::core::option::Option<ClockTag>,
+}
+#[derive(serde::Serialize)]
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeleteBlobPointInternal {
+    #[prost(message, optional, tag = "1")]
+    #[validate]
+    pub delete_blob_point: ::core::option::Option<DeleteBlobPoint>,
+    #[prost(uint32, optional, tag = "2")]
+    pub shard_id: ::core::option::Option<u32>,
+    #[prost(message, optional, tag = "3")]
+    pub clock_tag: ::core::option::Option<ClockTag>,
+}
+#[derive(serde::Serialize)]
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CreateFieldIndexCollectionInternal {
     #[prost(message, optional, tag = "1")]
     #[validate]
@@ -8193,6 +9117,17 @@ pub struct GetPointsInternal {
 #[derive(validator::Validate)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetBlobPointInternal {
+    #[prost(message, optional, tag = "1")]
+    #[validate]
+    pub get_blob_point: ::core::option::Option<GetBlobPoint>,
+    #[prost(uint32, optional, tag = "2")]
+    pub shard_id: ::core::option::Option<u32>,
+}
+#[derive(serde::Serialize)]
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CountPointsInternal {
     #[prost(message, optional, tag = "1")]
     #[validate]
@@ -8205,7 +9140,7 @@ pub struct CountPointsInternal {
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct RawVector {
-    #[prost(oneof = "raw_vector::Variant", tags = "1, 2, 3")]
+    #[prost(oneof = "raw_vector::Variant", tags = "1, 2, 3, 4")]
     pub variant: ::core::option::Option<raw_vector::Variant>,
 }
 /// Nested message and enum types in `RawVector`.
@@ -8220,6 +9155,8 @@ pub mod raw_vector {
         Sparse(super::SparseVector),
         #[prost(message, tag = "3")]
         MultiDense(super::MultiDenseVector),
+        #[prost(message, tag = "4")]
+        DenseDouble(super::DenseDoubleVector),
     }
 }
 /// Query variants for raw vectors (ids have been substituted with vectors)
@@ -8693,13 +9630,107 @@ pub mod points_internal_client {
                 .insert(GrpcMethod::new("qdrant.PointsInternal", "ClearPayload"));
             self.inner.unary(req, path, codec).await
         }
-        pub async fn create_field_index(
+        pub async fn create_field_index(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CreateFieldIndexCollectionInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointsOperationResponseInternal>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.PointsInternal/CreateFieldIndex",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "CreateFieldIndex"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn delete_field_index(
+            &mut self,
+            request: impl tonic::IntoRequest<super::DeleteFieldIndexCollectionInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointsOperationResponseInternal>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.PointsInternal/DeleteFieldIndex",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "DeleteFieldIndex"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn core_search_batch(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CoreSearchBatchPointsInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::SearchBatchResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.PointsInternal/CoreSearchBatch",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "CoreSearchBatch"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn scroll(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ScrollPointsInternal>,
+        ) -> std::result::Result<tonic::Response<super::ScrollResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.PointsInternal/Scroll",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "Scroll"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn count(
             &mut self,
-            request: impl tonic::IntoRequest<super::CreateFieldIndexCollectionInternal>,
-        ) -> std::result::Result<
-            tonic::Response<super::PointsOperationResponseInternal>,
-            tonic::Status,
-        > {
+            request: impl tonic::IntoRequest<super::CountPointsInternal>,
+        ) -> std::result::Result<tonic::Response<super::CountResponse>, tonic::Status> {
             self.inner
                 .ready()
                 .await
@@ -8711,18 +9742,18 @@ pub mod points_internal_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/qdrant.PointsInternal/CreateFieldIndex",
+                "/qdrant.PointsInternal/Count",
             );
             let mut req = request.into_request();
             req.extensions_mut()
-                .insert(GrpcMethod::new("qdrant.PointsInternal", "CreateFieldIndex"));
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "Count"));
             self.inner.unary(req, path, codec).await
         }
-        pub async fn delete_field_index(
+        pub async fn recommend(
             &mut self,
-            request: impl tonic::IntoRequest<super::DeleteFieldIndexCollectionInternal>,
+            request: impl tonic::IntoRequest<super::RecommendPointsInternal>,
         ) -> std::result::Result<
-            tonic::Response<super::PointsOperationResponseInternal>,
+            tonic::Response<super::RecommendResponse>,
             tonic::Status,
         > {
             self.inner
@@ -8736,20 +9767,17 @@ pub mod points_internal_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/qdrant.PointsInternal/DeleteFieldIndex",
+                "/qdrant.PointsInternal/Recommend",
             );
             let mut req = request.into_request();
             req.extensions_mut()
-                .insert(GrpcMethod::new("qdrant.PointsInternal", "DeleteFieldIndex"));
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "Recommend"));
             self.inner.unary(req, path, codec).await
         }
-        pub async fn core_search_batch(
+        pub async fn get(
             &mut self,
-            request: impl tonic::IntoRequest<super::CoreSearchBatchPointsInternal>,
-        ) -> std::result::Result<
-            tonic::Response<super::SearchBatchResponse>,
-            tonic::Status,
-        > {
+            request: impl tonic::IntoRequest<super::GetPointsInternal>,
+        ) -> std::result::Result<tonic::Response<super::GetResponse>, tonic::Status> {
             self.inner
                 .ready()
                 .await
@@ -8761,17 +9789,16 @@ pub mod points_internal_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/qdrant.PointsInternal/CoreSearchBatch",
+                "/qdrant.PointsInternal/Get",
             );
             let mut req = request.into_request();
-            req.extensions_mut()
-                .insert(GrpcMethod::new("qdrant.PointsInternal", "CoreSearchBatch"));
+            req.extensions_mut().insert(GrpcMethod::new("qdrant.PointsInternal", "Get"));
             self.inner.unary(req, path, codec).await
         }
-        pub async fn scroll(
+        pub async fn query(
             &mut self,
-            request: impl tonic::IntoRequest<super::ScrollPointsInternal>,
-        ) -> std::result::Result<tonic::Response<super::ScrollResponse>, tonic::Status> {
+            request: impl tonic::IntoRequest<super::QueryPointsInternal>,
+        ) -> std::result::Result<tonic::Response<super::QueryResponse>, tonic::Status> {
             self.inner
                 .ready()
                 .await
@@ -8783,17 +9810,20 @@ pub mod points_internal_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/qdrant.PointsInternal/Scroll",
+                "/qdrant.PointsInternal/Query",
             );
             let mut req = request.into_request();
             req.extensions_mut()
-                .insert(GrpcMethod::new("qdrant.PointsInternal", "Scroll"));
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "Query"));
             self.inner.unary(req, path, codec).await
         }
-        pub async fn count(
+        pub async fn patch_payload(
             &mut self,
-            request: impl tonic::IntoRequest<super::CountPointsInternal>,
-        ) -> std::result::Result<tonic::Response<super::CountResponse>, tonic::Status> {
+            request: impl tonic::IntoRequest<super::PatchPayloadPointsInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointsOperationResponseInternal>,
+            tonic::Status,
+        > {
             self.inner
                 .ready()
                 .await
@@ -8805,18 +9835,18 @@ pub mod points_internal_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/qdrant.PointsInternal/Count",
+                "/qdrant.PointsInternal/PatchPayload",
             );
             let mut req = request.into_request();
             req.extensions_mut()
-                .insert(GrpcMethod::new("qdrant.PointsInternal", "Count"));
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "PatchPayload"));
             self.inner.unary(req, path, codec).await
         }
-        pub async fn recommend(
+        pub async fn set_blob(
             &mut self,
-            request: impl tonic::IntoRequest<super::RecommendPointsInternal>,
+            request: impl tonic::IntoRequest<super::SetBlobPointInternal>,
         ) -> std::result::Result<
-            tonic::Response<super::RecommendResponse>,
+            tonic::Response<super::PointsOperationResponseInternal>,
             tonic::Status,
         > {
             self.inner
@@ -8830,17 +9860,20 @@ pub mod points_internal_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/qdrant.PointsInternal/Recommend",
+                "/qdrant.PointsInternal/SetBlob",
             );
             let mut req = request.into_request();
             req.extensions_mut()
-                .insert(GrpcMethod::new("qdrant.PointsInternal", "Recommend"));
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "SetBlob"));
             self.inner.unary(req, path, codec).await
         }
-        pub async fn get(
+        pub async fn delete_blob(
             &mut self,
-            request: impl tonic::IntoRequest<super::GetPointsInternal>,
-        ) -> std::result::Result<tonic::Response<super::GetResponse>, tonic::Status> {
+            request: impl tonic::IntoRequest<super::DeleteBlobPointInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointsOperationResponseInternal>,
+            tonic::Status,
+        > {
             self.inner
                 .ready()
                 .await
@@ -8852,16 +9885,20 @@ pub mod points_internal_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/qdrant.PointsInternal/Get",
+                "/qdrant.PointsInternal/DeleteBlob",
             );
             let mut req = request.into_request();
-            req.extensions_mut().insert(GrpcMethod::new("qdrant.PointsInternal", "Get"));
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "DeleteBlob"));
             self.inner.unary(req, path, codec).await
         }
-        pub async fn query(
+        pub async fn get_blob(
             &mut self,
-            request: impl tonic::IntoRequest<super::QueryPointsInternal>,
-        ) -> std::result::Result<tonic::Response<super::QueryResponse>, tonic::Status> {
+            request: impl tonic::IntoRequest<super::GetBlobPointInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetBlobResponse>,
+            tonic::Status,
+        > {
             self.inner
                 .ready()
                 .await
@@ -8873,11 +9910,11 @@ pub mod points_internal_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/qdrant.PointsInternal/Query",
+                "/qdrant.PointsInternal/GetBlob",
             );
             let mut req = request.into_request();
             req.extensions_mut()
-                .insert(GrpcMethod::new("qdrant.PointsInternal", "Query"));
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "GetBlob"));
             self.inner.unary(req, path, codec).await
         }
     }
@@ -8996,6 +10033,34 @@ pub mod points_internal_server {
             &self,
             request: tonic::Request<super::QueryPointsInternal>,
         ) -> std::result::Result<tonic::Response<super::QueryResponse>, tonic::Status>;
+        async fn patch_payload(
+            &self,
+            request: tonic::Request<super::PatchPayloadPointsInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointsOperationResponseInternal>,
+            tonic::Status,
+        >;
+        async fn set_blob(
+            &self,
+            request: tonic::Request<super::SetBlobPointInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointsOperationResponseInternal>,
+            tonic::Status,
+        >;
+        async fn delete_blob(
+            &self,
+            request: tonic::Request<super::DeleteBlobPointInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::PointsOperationResponseInternal>,
+            tonic::Status,
+        >;
+        async fn get_blob(
+            &self,
+            request: tonic::Request<super::GetBlobPointInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetBlobResponse>,
+            tonic::Status,
+        >;
     }
     #[derive(Debug)]
     pub struct PointsInternalServer<T: PointsInternal> {
@@ -9868,6 +10933,190 @@ pub mod points_internal_server {
                     };
                     Box::pin(fut)
                 }
+                "/qdrant.PointsInternal/PatchPayload" => {
+                    #[allow(non_camel_case_types)]
+                    struct PatchPayloadSvc<T: PointsInternal>(pub Arc<T>);
+                    impl<
+                        T: PointsInternal,
+                    > tonic::server::UnaryService<super::PatchPayloadPointsInternal>
+                    for PatchPayloadSvc<T> {
+                        type Response = super::PointsOperationResponseInternal;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::PatchPayloadPointsInternal>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PointsInternal>::patch_payload(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = PatchPayloadSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.PointsInternal/SetBlob" => {
+                    #[allow(non_camel_case_types)]
+                    struct SetBlobSvc<T: PointsInternal>(pub Arc<T>);
+                    impl<
+                        T: PointsInternal,
+                    > tonic::server::UnaryService<super::SetBlobPointInternal>
+                    for SetBlobSvc<T> {
+                        type Response = super::PointsOperationResponseInternal;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SetBlobPointInternal>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PointsInternal>::set_blob(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = SetBlobSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.PointsInternal/DeleteBlob" => {
+                    #[allow(non_camel_case_types)]
+                    struct DeleteBlobSvc<T: PointsInternal>(pub Arc<T>);
+                    impl<
+                        T: PointsInternal,
+                    > tonic::server::UnaryService<super::DeleteBlobPointInternal>
+                    for DeleteBlobSvc<T> {
+                        type Response = super::PointsOperationResponseInternal;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DeleteBlobPointInternal>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PointsInternal>::delete_blob(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = DeleteBlobSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.PointsInternal/GetBlob" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetBlobSvc<T: PointsInternal>(pub Arc<T>);
+                    impl<
+                        T: PointsInternal,
+                    > tonic::server::UnaryService<super::GetBlobPointInternal>
+                    for GetBlobSvc<T> {
+                        type Response = super::GetBlobResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetBlobPointInternal>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PointsInternal>::get_blob(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetBlobSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         Ok(
@@ -10370,6 +11619,9 @@ pub struct AddPeerToKnownMessage {
     pub port: ::core::option::Option<u32>,
     #[prost(uint64, tag = "3")]
     pub id: u64,
+    /// Join as a learner, which never votes
+    #[prost(bool, optional, tag = "4")]
+    pub learner: ::core::option::Option<bool>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -10927,6 +12179,9 @@ pub struct CreateSnapshotRequest {
     #[prost(string, tag = "1")]
     #[validate(length(min = 1, max = 255))]
     pub collection_name: ::prost::alloc::string::String,
+    /// Hex-encoded 256-bit key to encrypt the snapshot with, overrides configured key
+    #[prost(string, optional, tag = "2")]
+    pub encryption_key: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(serde::Serialize)]
 #[derive(validator::Validate)]
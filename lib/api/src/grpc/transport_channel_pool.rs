@@ -3,9 +3,12 @@ use std::future::Future;
 use std::num::NonZeroUsize;
 use std::time::Duration;
 
+use common::request_id;
+use parking_lot::RwLock;
 use rand::{thread_rng, Rng};
 use tokio::select;
 use tonic::codegen::InterceptedService;
+use tonic::metadata::MetadataValue;
 use tonic::service::Interceptor;
 use tonic::transport::{Channel, ClientTlsConfig, Error as TonicError, Uri};
 use tonic::{Code, Request, Status};
@@ -73,6 +76,8 @@ enum RequestFailure {
 }
 
 /// Intercepts gRPC requests and adds a default timeout if it wasn't already set.
+///
+/// Also passes on the ID of the request served by the current task, if any.
 pub struct AddTimeout {
     default_timeout: Duration,
 }
@@ -88,6 +93,13 @@ impl Interceptor for AddTimeout {
         if request.metadata().get("grpc-timeout").is_none() {
             request.set_timeout(self.default_timeout);
         }
+        if let Some(request_id) = request_id::current() {
+            if let Ok(request_id) = MetadataValue::try_from(request_id.as_ref()) {
+                request
+                    .metadata_mut()
+                    .insert(request_id::REQUEST_ID_HEADER, request_id);
+            }
+        }
         Ok(request)
     }
 }
@@ -100,7 +112,7 @@ pub struct TransportChannelPool {
     pool_size: NonZeroUsize,
     grpc_timeout: Duration,
     connection_timeout: Duration,
-    tls_config: Option<ClientTlsConfig>,
+    tls_config: RwLock<Option<ClientTlsConfig>>,
 }
 
 impl Default for TransportChannelPool {
@@ -110,7 +122,7 @@ impl Default for TransportChannelPool {
             pool_size: NonZeroUsize::new(DEFAULT_POOL_SIZE).unwrap(),
             grpc_timeout: DEFAULT_GRPC_TIMEOUT,
             connection_timeout: DEFAULT_CONNECT_TIMEOUT,
-            tls_config: None,
+            tls_config: RwLock::new(None),
         }
     }
 }
@@ -127,16 +139,29 @@ impl TransportChannelPool {
             grpc_timeout: p2p_grpc_timeout,
             connection_timeout,
             pool_size: NonZeroUsize::new(pool_size).unwrap(),
-            tls_config,
+            tls_config: RwLock::new(tls_config),
         }
     }
 
+    pub fn tls_config(&self) -> Option<ClientTlsConfig> {
+        self.tls_config.read().clone()
+    }
+
+    /// Replace TLS configuration, e.g. after rotation of the certificates.
+    ///
+    /// All pools are dropped, so that new connections are established with the new certificates.
+    /// Requests in flight keep using their channels until they are finished.
+    pub async fn update_tls_config(&self, tls_config: Option<ClientTlsConfig>) {
+        *self.tls_config.write() = tls_config;
+        self.uri_to_pool.write().await.clear();
+    }
+
     async fn _init_pool_for_uri(&self, uri: Uri) -> Result<DynamicChannelPool, TonicError> {
         DynamicChannelPool::new(
             uri,
             MAX_GRPC_CHANNEL_TIMEOUT,
             self.connection_timeout,
-            self.tls_config.clone(),
+            self.tls_config(),
             MAX_CONNECTIONS_PER_CHANNEL,
             self.pool_size.get(),
         )
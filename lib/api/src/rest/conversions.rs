@@ -1,13 +1,38 @@
+use segment::common::operation_error::OperationError;
 use segment::data_types::order_by::OrderBy;
 
-use super::schema::{BatchVectorStruct, ScoredPoint, Vector, VectorStruct};
+use super::schema::{BatchVectorStruct, Float64Vector, ScoredPoint, Vector, VectorStruct};
 use super::{OrderByInterface, Query, QueryInterface};
 use crate::rest::{DenseVector, NamedVectorStruct};
 
+/// Document or image, which was not replaced with a vector before the conversion.
+///
+/// Inputs are embedded before the request is processed, only in APIs which support inference.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Documents and images are not supported in this request, provide vectors instead")]
+pub struct InferenceInputError;
+
+impl From<InferenceInputError> for tonic::Status {
+    fn from(err: InferenceInputError) -> Self {
+        tonic::Status::invalid_argument(err.to_string())
+    }
+}
+
+impl From<InferenceInputError> for OperationError {
+    fn from(err: InferenceInputError) -> Self {
+        OperationError::ValidationError {
+            description: err.to_string(),
+        }
+    }
+}
+
 impl From<segment::data_types::vectors::Vector> for Vector {
     fn from(value: segment::data_types::vectors::Vector) -> Self {
         match value {
             segment::data_types::vectors::Vector::Dense(vector) => Vector::Dense(vector),
+            segment::data_types::vectors::Vector::DenseDouble(vector) => {
+                Vector::DenseDouble(Float64Vector { float64: vector })
+            }
             segment::data_types::vectors::Vector::Sparse(vector) => Vector::Sparse(vector),
             segment::data_types::vectors::Vector::MultiDense(vector) => {
                 Vector::MultiDense(vector.into_multi_vectors())
@@ -16,10 +41,15 @@ impl From<segment::data_types::vectors::Vector> for Vector {
     }
 }
 
-impl From<Vector> for segment::data_types::vectors::Vector {
-    fn from(value: Vector) -> Self {
-        match value {
+impl TryFrom<Vector> for segment::data_types::vectors::Vector {
+    type Error = InferenceInputError;
+
+    fn try_from(value: Vector) -> Result<Self, Self::Error> {
+        Ok(match value {
             Vector::Dense(vector) => segment::data_types::vectors::Vector::Dense(vector),
+            Vector::DenseDouble(vector) => {
+                segment::data_types::vectors::Vector::DenseDouble(vector.float64)
+            }
             Vector::Sparse(vector) => segment::data_types::vectors::Vector::Sparse(vector),
             Vector::MultiDense(vector) => {
                 // the REST vectors have been validated already
@@ -28,7 +58,8 @@ impl From<Vector> for segment::data_types::vectors::Vector {
                     segment::data_types::vectors::MultiDenseVector::new_unchecked(vector),
                 )
             }
-        }
+            Vector::Inference(_) => return Err(InferenceInputError),
+        })
     }
 }
 
@@ -45,16 +76,21 @@ impl From<segment::data_types::vectors::VectorStruct> for VectorStruct {
     }
 }
 
-impl From<VectorStruct> for segment::data_types::vectors::VectorStruct {
-    fn from(value: VectorStruct) -> Self {
-        match value {
+impl TryFrom<VectorStruct> for segment::data_types::vectors::VectorStruct {
+    type Error = InferenceInputError;
+
+    fn try_from(value: VectorStruct) -> Result<Self, Self::Error> {
+        Ok(match value {
             VectorStruct::Single(vector) => {
                 segment::data_types::vectors::VectorStruct::Single(vector)
             }
             VectorStruct::Multi(vectors) => segment::data_types::vectors::VectorStruct::Multi(
-                vectors.into_iter().map(|(k, v)| (k, v.into())).collect(),
+                vectors
+                    .into_iter()
+                    .map(|(k, v)| Ok((k, v.try_into()?)))
+                    .collect::<Result<_, InferenceInputError>>()?,
             ),
-        }
+        })
     }
 }
 
@@ -76,9 +112,11 @@ impl From<segment::data_types::vectors::BatchVectorStruct> for BatchVectorStruct
     }
 }
 
-impl From<BatchVectorStruct> for segment::data_types::vectors::BatchVectorStruct {
-    fn from(value: BatchVectorStruct) -> Self {
-        match value {
+impl TryFrom<BatchVectorStruct> for segment::data_types::vectors::BatchVectorStruct {
+    type Error = InferenceInputError;
+
+    fn try_from(value: BatchVectorStruct) -> Result<Self, Self::Error> {
+        Ok(match value {
             BatchVectorStruct::Single(vector) => {
                 segment::data_types::vectors::BatchVectorStruct::Single(vector)
             }
@@ -86,11 +124,17 @@ impl From<BatchVectorStruct> for segment::data_types::vectors::BatchVectorStruct
                 segment::data_types::vectors::BatchVectorStruct::Multi(
                     vectors
                         .into_iter()
-                        .map(|(k, v)| (k, v.into_iter().map(|v| v.into()).collect()))
-                        .collect(),
+                        .map(|(k, v)| {
+                            let v = v
+                                .into_iter()
+                                .map(TryInto::try_into)
+                                .collect::<Result<_, _>>()?;
+                            Ok((k, v))
+                        })
+                        .collect::<Result<_, InferenceInputError>>()?,
                 )
             }
-        }
+        })
     }
 }
 
@@ -108,23 +152,27 @@ impl From<segment::types::ScoredPoint> for ScoredPoint {
     }
 }
 
-impl From<ScoredPoint> for segment::types::ScoredPoint {
-    fn from(value: ScoredPoint) -> Self {
-        segment::types::ScoredPoint {
+impl TryFrom<ScoredPoint> for segment::types::ScoredPoint {
+    type Error = InferenceInputError;
+
+    fn try_from(value: ScoredPoint) -> Result<Self, Self::Error> {
+        Ok(segment::types::ScoredPoint {
             id: value.id,
             version: value.version,
             score: value.score,
             payload: value.payload,
-            vector: value.vector.map(From::from),
+            vector: value.vector.map(TryFrom::try_from).transpose()?,
             shard_key: value.shard_key,
             order_value: value.order_value.map(From::from),
-        }
+        })
     }
 }
 
-impl From<NamedVectorStruct> for segment::data_types::vectors::NamedVectorStruct {
-    fn from(value: NamedVectorStruct) -> Self {
-        match value {
+impl TryFrom<NamedVectorStruct> for segment::data_types::vectors::NamedVectorStruct {
+    type Error = InferenceInputError;
+
+    fn try_from(value: NamedVectorStruct) -> Result<Self, Self::Error> {
+        Ok(match value {
             NamedVectorStruct::Default(vector) => {
                 segment::data_types::vectors::NamedVectorStruct::Default(vector)
             }
@@ -134,7 +182,10 @@ impl From<NamedVectorStruct> for segment::data_types::vectors::NamedVectorStruct
             NamedVectorStruct::Sparse(vector) => {
                 segment::data_types::vectors::NamedVectorStruct::Sparse(vector)
             }
-        }
+            NamedVectorStruct::NamedInference(_) | NamedVectorStruct::Inference(_) => {
+                return Err(InferenceInputError);
+            }
+        })
     }
 }
 
@@ -150,6 +201,17 @@ impl From<segment::data_types::vectors::NamedVectorStruct> for NamedVectorStruct
             segment::data_types::vectors::NamedVectorStruct::Sparse(vector) => {
                 NamedVectorStruct::Sparse(vector)
             }
+            // Search requests take single precision vectors
+            segment::data_types::vectors::NamedVectorStruct::DenseDouble(vector) => {
+                NamedVectorStruct::Dense(segment::data_types::vectors::NamedVector {
+                    name: vector.name,
+                    vector: vector
+                        .vector
+                        .into_iter()
+                        .map(|value| value as f32)
+                        .collect(),
+                })
+            }
             segment::data_types::vectors::NamedVectorStruct::MultiDense(_vector) => {
                 // TODO(colbert)
                 unimplemented!("MultiDense is not available in the API yet")
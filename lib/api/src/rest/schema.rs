@@ -15,12 +15,71 @@ pub type DenseVector = Vec<segment::data_types::vectors::VectorElementType>;
 /// Type for multi dense vector
 pub type MultiDenseVector = Vec<DenseVector>;
 
+/// Dense vector for `float64` storages, values are kept in double precision
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct Float64Vector {
+    pub float64: Vec<segment::data_types::vectors::VectorElementTypeDouble>,
+}
+
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize, JsonSchema)]
 #[serde(untagged, rename_all = "snake_case")]
 pub enum Vector {
     Dense(DenseVector),
     Sparse(sparse::common::sparse_vector::SparseVector),
     MultiDense(MultiDenseVector),
+    DenseDouble(Float64Vector),
+    Inference(InferenceInput),
+}
+
+/// Text, converted into a dense vector by the embedding service of the collection
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct Document {
+    /// Text to embed
+    pub text: String,
+    /// Name of the embedding model. Default: model from the inference config of the collection
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+}
+
+/// Image, converted into a dense vector by the embedding service of the collection
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct Image {
+    /// URL of the image or base64 encoded image data
+    pub image: String,
+    /// Name of the embedding model. Default: model from the inference config of the collection
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+}
+
+/// Raw input, which is embedded by the server instead of providing a vector
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, JsonSchema)]
+#[serde(untagged, rename_all = "snake_case")]
+pub enum InferenceInput {
+    Document(Document),
+    Image(Image),
+}
+
+impl InferenceInput {
+    /// Model requested for this input, if any
+    pub fn model(&self) -> Option<&str> {
+        match self {
+            InferenceInput::Document(document) => document.model.as_deref(),
+            InferenceInput::Image(image) => image.model.as_deref(),
+        }
+    }
+}
+
+/// Raw input to search in the named vector
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct NamedInferenceInput {
+    /// Name of vector data
+    pub name: String,
+    /// Input to embed
+    pub vector: InferenceInput,
 }
 
 /// Full vector data per point separator with single and multiple vector modes
@@ -40,6 +99,8 @@ impl VectorStruct {
                 Vector::Dense(vector) => vector.is_empty(),
                 Vector::Sparse(vector) => vector.indices.is_empty(),
                 Vector::MultiDense(vector) => vector.is_empty(),
+                Vector::DenseDouble(vector) => vector.float64.is_empty(),
+                Vector::Inference(_) => false,
             }),
         }
     }
@@ -120,6 +181,10 @@ pub struct Record {
     /// Shard Key
     #[serde(skip_serializing_if = "Option::is_none")]
     pub shard_key: Option<segment::types::ShardKey>,
+    /// Point version, the last update operation applied to this point.
+    /// Versions are local to the replica, which served the request
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<segment::types::SeqNumberType>,
 }
 
 /// Vector data separator for named and unnamed modes
@@ -137,6 +202,14 @@ pub struct Record {
 ///     "name": "image-embeddings"
 ///   }
 /// }
+///
+/// or raw input, embedded by the inference service of the collection:
+///
+/// {
+///   "vector": {
+///     "text": "Hello, world!"
+///   }
+/// }
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
 #[serde(rename_all = "snake_case")]
 #[serde(untagged)]
@@ -145,6 +218,8 @@ pub enum NamedVectorStruct {
     Dense(segment::data_types::vectors::NamedVector),
     Sparse(segment::data_types::vectors::NamedSparseVector),
     // No support for multi-dense vectors in search
+    NamedInference(NamedInferenceInput),
+    Inference(InferenceInput),
 }
 
 #[derive(Deserialize, Serialize, JsonSchema, Clone, Debug, PartialEq)]
@@ -168,6 +243,7 @@ pub enum VectorInput {
     DenseVector(DenseVector),
     SparseVector(SparseVector),
     MultiDenseVector(MultiDenseVector),
+    DenseDoubleVector(Float64Vector),
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -29,6 +29,8 @@ impl Validate for Vector {
             Vector::Dense(_) => Ok(()),
             Vector::Sparse(v) => v.validate(),
             Vector::MultiDense(m) => common::validation::validate_multi_vector(m),
+            Vector::DenseDouble(_) => Ok(()),
+            Vector::Inference(_) => Ok(()),
         }
     }
 }
@@ -39,6 +41,8 @@ impl Validate for NamedVectorStruct {
             NamedVectorStruct::Default(_) => Ok(()),
             NamedVectorStruct::Dense(_) => Ok(()),
             NamedVectorStruct::Sparse(v) => v.validate(),
+            NamedVectorStruct::NamedInference(_) => Ok(()),
+            NamedVectorStruct::Inference(_) => Ok(()),
         }
     }
 }
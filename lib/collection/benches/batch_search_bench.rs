@@ -7,7 +7,7 @@ use collection::operations::point_ops::{
 use collection::operations::types::{CoreSearchRequestBatch, SearchRequestInternal};
 use collection::operations::vector_params_builder::VectorParamsBuilder;
 use collection::operations::CollectionUpdateOperations;
-use collection::optimizers_builder::OptimizersConfig;
+use collection::optimizers_builder::{MergePolicy, OptimizersConfig};
 use collection::shards::local_shard::LocalShard;
 use collection::shards::shard_trait::ShardOperation;
 use common::cpu::CpuBudget;
@@ -38,6 +38,7 @@ fn create_rnd_batch() -> CollectionUpdateOperations {
             id: (i as u64).into(),
             vector: VectorStruct::from(vectors).into(),
             payload: Some(Payload(payload_map)),
+            expires_at: None,
         };
         points.push(point);
     }
@@ -57,10 +58,13 @@ fn batch_search_bench(c: &mut Criterion) {
     let wal_config = WalConfig {
         wal_capacity_mb: 1,
         wal_segments_ahead: 0,
+        wal_compression: Default::default(),
+        wal_durability: Default::default(),
     };
 
     let collection_params = CollectionParams {
         vectors: VectorParamsBuilder::new(100, Distance::Dot).build().into(),
+        storage_engine: None,
         ..CollectionParams::empty()
     };
 
@@ -75,10 +79,18 @@ fn batch_search_bench(c: &mut Criterion) {
             indexing_threshold: Some(50_000),
             flush_interval_sec: 30,
             max_optimization_threads: Some(2),
+            merge_policy: MergePolicy::SizeTiered,
+            cpu_budget: None,
         },
         wal_config,
         hnsw_config: Default::default(),
         quantization_config: Default::default(),
+        tiering_config: None,
+        ttl_config: None,
+        async_replication_config: None,
+        inference_config: None,
+        quota_config: None,
+        multitenancy_config: None,
     };
 
     let shared_config = Arc::new(RwLock::new(collection_config));
@@ -143,7 +155,7 @@ fn batch_search_bench(c: &mut Criterion) {
                         let result = shard
                             .core_search(
                                 Arc::new(CoreSearchRequestBatch {
-                                    searches: vec![search_query.into()],
+                                    searches: vec![search_query.try_into().unwrap()],
                                 }),
                                 search_runtime_handle,
                                 None,
@@ -173,7 +185,7 @@ fn batch_search_bench(c: &mut Criterion) {
                             with_vector: None,
                             score_threshold: None,
                         };
-                        searches.push(search_query.into());
+                        searches.push(search_query.try_into().unwrap());
                     }
 
                     let search_query = CoreSearchRequestBatch { searches };
@@ -0,0 +1,689 @@
+//! Asynchronous replication of the collection to a remote Qdrant cluster.
+//!
+//! Operations of every shard are read from the WAL of its source replica and replayed, in order,
+//! to the remote collection through the public gRPC API. Offsets of replayed operations are
+//! persisted on the node, so replication resumes where it stopped after a restart or an outage
+//! of the remote cluster. The WAL is pinned at the offset, so operations are not truncated before
+//! they are replayed.
+//!
+//! Offsets are specific to the WAL of the source replica. If the source moves to another peer,
+//! or the operations can't be replayed without a gap, all points of the shard are copied to the
+//! remote collection first, and the replay continues from the WAL position of the copy.
+//! Points, deleted while replication could not keep up, are not removed from the remote
+//! collection by the copy.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+
+use api::grpc::qdrant::points_client::PointsClient;
+use api::grpc::transport_channel_pool::{AddTimeout, TransportChannelPool};
+use schemars::JsonSchema;
+use segment::types::{WithPayloadInterface, WithVector};
+use serde::{Deserialize, Serialize};
+use tonic::codegen::InterceptedService;
+use tonic::metadata::AsciiMetadataValue;
+use tonic::transport::{Channel, Uri};
+use tonic::Status;
+
+use super::Collection;
+use crate::config::AsyncReplicationConfig;
+use crate::operations::blob_ops::BlobOperations;
+use crate::operations::payload_ops::PayloadOps;
+use crate::operations::point_ops::{PointInsertOperationsInternal, PointOperations, PointStruct};
+use crate::operations::types::{CollectionError, CollectionResult};
+use crate::operations::vector_ops::VectorOperations;
+use crate::operations::{CollectionUpdateOperations, FieldIndexOperations};
+use crate::save_on_disk::SaveOnDisk;
+use crate::shards::conversions::{
+    internal_clear_payload, internal_clear_payload_by_filter, internal_conditional_upsert_points,
+    internal_create_index, internal_delete_blob, internal_delete_index, internal_delete_payload,
+    internal_delete_points, internal_delete_points_by_filter, internal_delete_vectors,
+    internal_delete_vectors_by_filter, internal_patch_payload, internal_set_blob,
+    internal_set_payload, internal_update_vectors, internal_upsert_points,
+};
+use crate::shards::local_shard::wal_tail::WalTail;
+use crate::shards::replica_set::ShardReplicaSet;
+use crate::shards::shard::ShardId;
+
+pub const ASYNC_REPLICATION_STATE_FILE: &str = "async_replication.json";
+
+/// Header with the API key of the remote cluster
+const API_KEY_HEADER: &str = "api-key";
+
+/// Limit of batches replayed per shard in one round, to not hold the shards for too long
+const MAX_BATCHES_PER_ROUND: usize = 10;
+
+/// Limit of points copied at once, while the shard is resynchronized
+const RESYNC_BATCH_SIZE: usize = 100;
+
+/// Replication progress of a single shard on this node, while this node is its source
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+pub struct ShardReplicationProgress {
+    /// Index of the next WAL operation to replay.
+    /// `None` if all points of the shard have to be copied first.
+    pub offset: Option<u64>,
+    /// Number of times all points of the shard were copied, because operations could not be
+    /// replayed from the WAL
+    #[serde(default)]
+    pub resyncs: u64,
+    /// Number of operations rejected by the remote cluster
+    pub failed_operations: u64,
+    #[serde(skip)]
+    pub last_error: Option<String>,
+}
+
+pub type AsyncReplicationState = HashMap<ShardId, ShardReplicationProgress>;
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct AsyncReplicationStatus {
+    /// gRPC URI of the remote cluster
+    pub uri: String,
+    /// Name of the collection in the remote cluster
+    pub collection_name: String,
+    pub enabled: bool,
+    /// Shards of the collection, replicated by this peer
+    pub shards: Vec<ShardAsyncReplicationStatus>,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ShardAsyncReplicationStatus {
+    pub shard_id: ShardId,
+    /// Index of the next local WAL operation to replay
+    pub offset: u64,
+    /// Number of operations in the local WAL, which are not replayed yet
+    pub lag: u64,
+    /// Whether all points of the shard have to be copied, before operations can be replayed
+    pub resync_pending: bool,
+    /// Number of times all points of the shard were copied, because operations could not be
+    /// replayed from the WAL
+    pub resyncs: u64,
+    /// Number of operations rejected by the remote cluster
+    pub failed_operations: u64,
+    /// Last error of the replication, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+}
+
+/// Collection in the remote cluster, which receives replayed operations
+struct RemoteCollection<'a> {
+    channel_pool: &'a TransportChannelPool,
+    uri: Uri,
+    api_key: Option<AsciiMetadataValue>,
+    collection_name: String,
+}
+
+impl RemoteCollection<'_> {
+    async fn call<R, T, O>(
+        &self,
+        request: R,
+        f: impl Fn(PointsClient<InterceptedService<Channel, AddTimeout>>, tonic::Request<R>) -> O,
+    ) -> CollectionResult<()>
+    where
+        R: Clone,
+        O: Future<Output = Result<tonic::Response<T>, Status>>,
+    {
+        self.channel_pool
+            .with_channel(&self.uri, |channel| {
+                let client = PointsClient::new(channel).max_decoding_message_size(usize::MAX);
+                let mut request = tonic::Request::new(request.clone());
+                if let Some(api_key) = &self.api_key {
+                    request
+                        .metadata_mut()
+                        .insert(API_KEY_HEADER, api_key.clone());
+                }
+                f(client, request)
+            })
+            .await?;
+        Ok(())
+    }
+
+    /// Replay a single operation through the public API of the remote cluster
+    async fn replay(&self, operation: CollectionUpdateOperations) -> CollectionResult<()> {
+        let name = self.collection_name.clone();
+        // Shard and clock are specific to this cluster, operations are routed by the remote one
+        let (shard_id, clock_tag, wait, ordering) = (None, None, true, None);
+
+        match operation {
+            CollectionUpdateOperations::PointOperation(point_ops) => match point_ops {
+                PointOperations::UpsertPoints(operation) => {
+                    let request = internal_upsert_points(
+                        shard_id, clock_tag, name, operation, wait, ordering,
+                    )?
+                    .upsert_points;
+                    self.call(required(request)?, |mut client, request| async move {
+                        client.upsert(request).await
+                    })
+                    .await
+                }
+                PointOperations::UpsertPointsConditional(operation) => {
+                    let request = internal_conditional_upsert_points(
+                        shard_id, clock_tag, name, operation, wait, ordering,
+                    )?
+                    .upsert_points;
+                    self.call(required(request)?, |mut client, request| async move {
+                        client.upsert(request).await
+                    })
+                    .await
+                }
+                PointOperations::DeletePoints { ids, if_version } => {
+                    let request = internal_delete_points(
+                        shard_id, clock_tag, name, ids, if_version, wait, ordering,
+                    )
+                    .delete_points;
+                    self.call(required(request)?, |mut client, request| async move {
+                        client.delete(request).await
+                    })
+                    .await
+                }
+                PointOperations::DeletePointsByFilter(filter) => {
+                    let request = internal_delete_points_by_filter(
+                        shard_id, clock_tag, name, filter, wait, ordering,
+                    )
+                    .delete_points;
+                    self.call(required(request)?, |mut client, request| async move {
+                        client.delete(request).await
+                    })
+                    .await
+                }
+                PointOperations::SyncPoints(_) => Err(CollectionError::bad_input(
+                    "Sync of points can't be replayed to a remote cluster",
+                )),
+            },
+            CollectionUpdateOperations::VectorOperation(vector_ops) => match vector_ops {
+                VectorOperations::UpdateVectors(operation) => {
+                    let request = internal_update_vectors(
+                        shard_id, clock_tag, name, operation, wait, ordering,
+                    )?
+                    .update_vectors;
+                    self.call(required(request)?, |mut client, request| async move {
+                        client.update_vectors(request).await
+                    })
+                    .await
+                }
+                VectorOperations::DeleteVectors(ids, vector_names) => {
+                    let request = internal_delete_vectors(
+                        shard_id,
+                        clock_tag,
+                        name,
+                        ids.points,
+                        vector_names,
+                        wait,
+                        ordering,
+                    )
+                    .delete_vectors;
+                    self.call(required(request)?, |mut client, request| async move {
+                        client.delete_vectors(request).await
+                    })
+                    .await
+                }
+                VectorOperations::DeleteVectorsByFilter(filter, vector_names) => {
+                    let request = internal_delete_vectors_by_filter(
+                        shard_id,
+                        clock_tag,
+                        name,
+                        filter,
+                        vector_names,
+                        wait,
+                        ordering,
+                    )
+                    .delete_vectors;
+                    self.call(required(request)?, |mut client, request| async move {
+                        client.delete_vectors(request).await
+                    })
+                    .await
+                }
+            },
+            CollectionUpdateOperations::PayloadOperation(payload_ops) => match payload_ops {
+                PayloadOps::SetPayload(operation) => {
+                    let request =
+                        internal_set_payload(shard_id, clock_tag, name, operation, wait, ordering)
+                            .set_payload_points;
+                    self.call(required(request)?, |mut client, request| async move {
+                        client.set_payload(request).await
+                    })
+                    .await
+                }
+                PayloadOps::OverwritePayload(operation) => {
+                    let request =
+                        internal_set_payload(shard_id, clock_tag, name, operation, wait, ordering)
+                            .set_payload_points;
+                    self.call(required(request)?, |mut client, request| async move {
+                        client.overwrite_payload(request).await
+                    })
+                    .await
+                }
+                PayloadOps::DeletePayload(operation) => {
+                    let request = internal_delete_payload(
+                        shard_id, clock_tag, name, operation, wait, ordering,
+                    )
+                    .delete_payload_points;
+                    self.call(required(request)?, |mut client, request| async move {
+                        client.delete_payload(request).await
+                    })
+                    .await
+                }
+                PayloadOps::ClearPayload { points } => {
+                    let request =
+                        internal_clear_payload(shard_id, clock_tag, name, points, wait, ordering)
+                            .clear_payload_points;
+                    self.call(required(request)?, |mut client, request| async move {
+                        client.clear_payload(request).await
+                    })
+                    .await
+                }
+                PayloadOps::ClearPayloadByFilter(filter) => {
+                    let request = internal_clear_payload_by_filter(
+                        shard_id, clock_tag, name, filter, wait, ordering,
+                    )
+                    .clear_payload_points;
+                    self.call(required(request)?, |mut client, request| async move {
+                        client.clear_payload(request).await
+                    })
+                    .await
+                }
+                PayloadOps::PatchPayload(operation) => {
+                    let request = internal_patch_payload(
+                        shard_id, clock_tag, name, operation, wait, ordering,
+                    )
+                    .patch_payload_points;
+                    self.call(required(request)?, |mut client, request| async move {
+                        client.patch_payload(request).await
+                    })
+                    .await
+                }
+            },
+            CollectionUpdateOperations::FieldIndexOperation(index_ops) => match index_ops {
+                FieldIndexOperations::CreateIndex(operation) => {
+                    let request =
+                        internal_create_index(shard_id, clock_tag, name, operation, wait, ordering)
+                            .create_field_index_collection;
+                    self.call(required(request)?, |mut client, request| async move {
+                        client.create_field_index(request).await
+                    })
+                    .await
+                }
+                FieldIndexOperations::DeleteIndex(field_name) => {
+                    let request = internal_delete_index(
+                        shard_id, clock_tag, name, field_name, wait, ordering,
+                    )
+                    .delete_field_index_collection;
+                    self.call(required(request)?, |mut client, request| async move {
+                        client.delete_field_index(request).await
+                    })
+                    .await
+                }
+            },
+            CollectionUpdateOperations::BlobOperation(blob_ops) => match blob_ops {
+                BlobOperations::SetBlob(operation) => {
+                    let request =
+                        internal_set_blob(shard_id, clock_tag, name, operation, wait, ordering)
+                            .set_blob_point;
+                    self.call(required(request)?, |mut client, request| async move {
+                        client.set_blob(request).await
+                    })
+                    .await
+                }
+                BlobOperations::DeleteBlob(operation) => {
+                    let request =
+                        internal_delete_blob(shard_id, clock_tag, name, operation, wait, ordering)
+                            .delete_blob_point;
+                    self.call(required(request)?, |mut client, request| async move {
+                        client.delete_blob(request).await
+                    })
+                    .await
+                }
+            },
+        }
+    }
+}
+
+fn required<T>(request: Option<T>) -> CollectionResult<T> {
+    request.ok_or_else(|| CollectionError::service_error("Malformed request to remote cluster"))
+}
+
+/// Whether the operation is rejected by the remote cluster, so retrying it makes no sense
+fn is_rejected(err: &CollectionError) -> bool {
+    matches!(
+        err,
+        CollectionError::BadInput { .. } | CollectionError::NotFound { .. },
+    )
+}
+
+impl Collection {
+    fn async_replication_state_file(collection_path: &Path) -> PathBuf {
+        collection_path.join(ASYNC_REPLICATION_STATE_FILE)
+    }
+
+    pub(crate) fn load_async_replication_state(
+        collection_path: &Path,
+    ) -> CollectionResult<SaveOnDisk<AsyncReplicationState>> {
+        let state_file = Self::async_replication_state_file(collection_path);
+        Ok(SaveOnDisk::load_or_init(state_file)?)
+    }
+
+    /// Replay new operations of the shards, replicated by this peer, to the remote cluster
+    ///
+    /// `api_key` is used to authenticate in the remote cluster.
+    /// Returns the number of replayed operations.
+    pub async fn replicate_async(&self, api_key: Option<&str>) -> CollectionResult<usize> {
+        let config = self
+            .collection_config
+            .read()
+            .await
+            .async_replication_config
+            .clone();
+        let Some(config) = config.filter(|config| config.enabled) else {
+            // Replication is paused, offsets are kept, but the WAL is not held back.
+            // If operations are truncated in the meantime, shards are copied on resume.
+            let shards_holder = self.shards_holder.read().await;
+            for replica_set in shards_holder.all_shards() {
+                replica_set.pin_local_wal(None).await;
+            }
+            return Ok(0);
+        };
+
+        let remote = RemoteCollection {
+            channel_pool: &self.channel_service.channel_pool,
+            uri: config.uri.parse().map_err(|err| {
+                CollectionError::bad_input(format!(
+                    "Invalid URI of the remote cluster {}: {err}",
+                    config.uri,
+                ))
+            })?,
+            api_key: api_key
+                .map(|api_key| api_key.parse::<AsciiMetadataValue>())
+                .transpose()
+                .map_err(|_| CollectionError::bad_input("Invalid API key of the remote cluster"))?,
+            collection_name: config
+                .collection_name
+                .clone()
+                .unwrap_or_else(|| self.name()),
+        };
+
+        let shards_holder = self.shards_holder.read().await;
+        let mut replayed = 0;
+        for (&shard_id, replica_set) in shards_holder.get_shards() {
+            if !replica_set.is_async_replication_source() {
+                // Offsets are specific to the WAL of this replica. If this peer becomes the
+                // source again, the remote cluster might have received operations from another
+                // replica in the meantime, so the shard is copied from scratch.
+                replica_set.pin_local_wal(None).await;
+                if self.async_replication_state.read().contains_key(&shard_id) {
+                    self.async_replication_state.write(|state| {
+                        state.remove(&shard_id);
+                    })?;
+                }
+                continue;
+            }
+            match self
+                .replicate_shard_async(shard_id, replica_set, &remote, &config)
+                .await
+            {
+                Ok(count) => replayed += count,
+                Err(err) => {
+                    log::warn!(
+                        "Failed to replicate shard {}:{shard_id} to {}: {err}",
+                        self.name(),
+                        config.uri,
+                    );
+                    self.async_replication_state.write(|state| {
+                        state.entry(shard_id).or_default().last_error = Some(err.to_string());
+                    })?;
+                }
+            }
+        }
+
+        Ok(replayed)
+    }
+
+    /// Pin the WAL of local shards at the offsets of not yet replayed operations
+    pub(super) async fn pin_async_replication_offsets(&self) {
+        let offsets = self
+            .async_replication_state
+            .read()
+            .iter()
+            .filter_map(|(&shard_id, progress)| Some((shard_id, progress.offset?)))
+            .collect::<Vec<_>>();
+        if offsets.is_empty() {
+            return;
+        }
+
+        let shards_holder = self.shards_holder.read().await;
+        for (shard_id, offset) in offsets {
+            if let Some(replica_set) = shards_holder.get_shard(&shard_id) {
+                replica_set.pin_local_wal(Some(offset)).await;
+            }
+        }
+    }
+
+    async fn replicate_shard_async(
+        &self,
+        shard_id: ShardId,
+        replica_set: &ShardReplicaSet,
+        remote: &RemoteCollection<'_>,
+        config: &AsyncReplicationConfig,
+    ) -> CollectionResult<usize> {
+        let mut replayed = 0;
+        for _ in 0..MAX_BATCHES_PER_ROUND {
+            let offset = self
+                .async_replication_state
+                .read()
+                .get(&shard_id)
+                .and_then(|progress| progress.offset);
+            let Some(offset) = offset else {
+                // First replication from this replica, copy the whole shard
+                match self
+                    .resync_shard_async(shard_id, replica_set, remote)
+                    .await?
+                {
+                    Some(copied) => replayed += copied,
+                    // No plain local shard, e.g. while it is being transferred
+                    None => break,
+                }
+                continue;
+            };
+
+            // Offset is persisted before the WAL is read, so it is safe to keep operations from it
+            replica_set.pin_local_wal(Some(offset)).await;
+            let batch = match replica_set
+                .read_local_wal(offset, config.batch_size)
+                .await?
+            {
+                Some(WalTail::Batch(batch)) => batch,
+                Some(WalTail::Gap) => {
+                    log::warn!(
+                        "Operations of shard {}:{shard_id} from {offset} are no longer stored in \
+                         WAL, copying the whole shard to the remote cluster",
+                        self.name(),
+                    );
+                    self.async_replication_state.write(|state| {
+                        state.entry(shard_id).or_default().offset = None;
+                    })?;
+                    continue;
+                }
+                // No plain local shard, e.g. while it is being transferred
+                None => break,
+            };
+
+            if batch.operations.is_empty() {
+                break;
+            }
+
+            // Progress is persisted once per batch, or once the replay is interrupted
+            let mut next_offset = batch.from;
+            let mut failed = 0;
+            let mut last_error = None;
+            let mut result = Ok(());
+            for (index, operation) in batch.operations {
+                match remote.replay(operation.operation).await {
+                    Ok(()) => last_error = None,
+                    Err(err) if is_rejected(&err) => {
+                        log::warn!(
+                            "Operation {index} of shard {}:{shard_id} rejected by the remote cluster: {err}",
+                            self.name(),
+                        );
+                        failed += 1;
+                        last_error = Some(err.to_string());
+                    }
+                    Err(err) => {
+                        result = Err(err);
+                        break;
+                    }
+                }
+                next_offset = index + 1;
+                replayed += 1;
+            }
+
+            self.async_replication_state.write(|state| {
+                let progress = state.entry(shard_id).or_default();
+                progress.offset = Some(next_offset);
+                progress.failed_operations += failed;
+                progress.last_error = last_error;
+            })?;
+            replica_set.pin_local_wal(Some(next_offset)).await;
+            result?;
+        }
+        Ok(replayed)
+    }
+
+    /// Copy all points of the local shard to the remote cluster, and set the offset to continue
+    /// the replay from.
+    ///
+    /// Returns the number of copied points, `None` if there is no plain local shard.
+    async fn resync_shard_async(
+        &self,
+        shard_id: ShardId,
+        replica_set: &ShardReplicaSet,
+        remote: &RemoteCollection<'_>,
+    ) -> CollectionResult<Option<usize>> {
+        let Some(offset) = replica_set.pin_local_wal_for_copy().await else {
+            return Ok(None);
+        };
+
+        let with_payload = WithPayloadInterface::Bool(true);
+        let with_vector = WithVector::Bool(true);
+        let mut copied = 0;
+        let mut failed = 0;
+        let mut next_page = None;
+        loop {
+            let mut records = replica_set
+                .scroll_by(
+                    next_page,
+                    RESYNC_BATCH_SIZE + 1,
+                    &with_payload,
+                    &with_vector,
+                    None,
+                    None,
+                    true,
+                    None,
+                )
+                .await?;
+            next_page = if records.len() > RESYNC_BATCH_SIZE {
+                records.pop().map(|record| record.id)
+            } else {
+                None
+            };
+
+            if !records.is_empty() {
+                let points = records
+                    .into_iter()
+                    .filter(|record| record.vector.is_some())
+                    .map(PointStruct::try_from)
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(CollectionError::service_error)?;
+                let count = points.len();
+                let operation = CollectionUpdateOperations::PointOperation(
+                    PointOperations::UpsertPoints(PointInsertOperationsInternal::from(points)),
+                );
+                match remote.replay(operation).await {
+                    Ok(()) => copied += count,
+                    Err(err) if is_rejected(&err) => {
+                        log::warn!(
+                            "Points of shard {}:{shard_id} rejected by the remote cluster: {err}",
+                            self.name(),
+                        );
+                        failed += count as u64;
+                    }
+                    Err(err) => return Err(err),
+                }
+            }
+
+            if next_page.is_none() {
+                break;
+            }
+        }
+
+        log::info!(
+            "Copied {copied} points of shard {}:{shard_id} to the remote cluster, \
+             replaying operations from {offset}",
+            self.name(),
+        );
+        self.async_replication_state.write(|state| {
+            let progress = state.entry(shard_id).or_default();
+            progress.offset = Some(offset);
+            progress.resyncs += 1;
+            progress.failed_operations += failed;
+        })?;
+        Ok(Some(copied))
+    }
+
+    /// Progress of the asynchronous replication of shards, replicated by this peer
+    ///
+    /// Returns `None` if asynchronous replication is not configured for the collection.
+    pub async fn async_replication_status(&self) -> Option<AsyncReplicationStatus> {
+        let config = self
+            .collection_config
+            .read()
+            .await
+            .async_replication_config
+            .clone()?;
+
+        let shards_holder = self.shards_holder.read().await;
+        let mut shards = Vec::new();
+        for (&shard_id, replica_set) in shards_holder.get_shards() {
+            if !replica_set.is_async_replication_source() {
+                continue;
+            }
+            let progress = self
+                .async_replication_state
+                .read()
+                .get(&shard_id)
+                .cloned()
+                .unwrap_or_default();
+            let batch = match progress.offset {
+                Some(offset) => replica_set.read_local_wal(offset, 0).await,
+                None => Ok(Some(WalTail::Gap)),
+            };
+            let (offset, lag, resync_pending) = match batch {
+                Ok(Some(WalTail::Batch(batch))) => (batch.from, batch.end - batch.from, false),
+                Ok(Some(WalTail::Gap)) => (progress.offset.unwrap_or_default(), 0, true),
+                Ok(None) => continue,
+                Err(err) => {
+                    log::error!(
+                        "Failed to read WAL of shard {}:{shard_id}: {err}",
+                        self.name()
+                    );
+                    continue;
+                }
+            };
+            shards.push(ShardAsyncReplicationStatus {
+                shard_id,
+                offset,
+                lag,
+                resync_pending,
+                resyncs: progress.resyncs,
+                failed_operations: progress.failed_operations,
+                last_error: progress.last_error,
+            });
+        }
+        shards.sort_by_key(|shard| shard.shard_id);
+
+        Some(AsyncReplicationStatus {
+            collection_name: config.collection_name.unwrap_or_else(|| self.name()),
+            uri: config.uri,
+            enabled: config.enabled,
+            shards,
+        })
+    }
+}
@@ -1,4 +1,5 @@
 use std::cmp;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use futures::{future, TryStreamExt as _};
@@ -7,12 +8,20 @@ use segment::types::QuantizationConfig;
 use semver::Version;
 
 use super::Collection;
+use crate::common::disk_usage;
+use crate::config::{
+    AsyncReplicationConfig, InferenceConfig, QuotaConfig, ShardPin, TieringConfig, TtlConfig,
+};
 use crate::operations::config_diff::*;
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::types::*;
 use crate::optimizers_builder::OptimizersConfig;
+use crate::shards::local_shard::force_optimize::ForcedOptimization;
+use crate::shards::local_shard::memory_spill::MemorySpill;
+use crate::shards::local_shard::vacuum::Vacuum;
 use crate::shards::replica_set::{Change, ReplicaState};
-use crate::shards::shard::PeerId;
+use crate::shards::shard::{PeerId, ShardId};
+use crate::shards::shard_holder::shard_not_found_error;
 
 lazy_static! {
     /// When dropping a shard, only cancel all related shard transfers to and from it when all nodes
@@ -33,7 +42,13 @@ impl Collection {
     ) -> CollectionResult<()> {
         {
             let mut config = self.collection_config.write().await;
-            config.params = params_diff.update(&config.params)?;
+            let params = params_diff.update(&config.params)?;
+            self.check_storage_encryption(
+                &params,
+                &config.optimizer_config,
+                config.quantization_config.as_ref(),
+            )?;
+            config.params = params;
         }
         self.collection_config.read().await.save(&self.path)?;
         Ok(())
@@ -67,9 +82,14 @@ impl Collection {
     ) -> CollectionResult<()> {
         let mut config = self.collection_config.write().await;
         update_vectors_diff.check_vector_names(&config.params)?;
-        config
-            .params
-            .update_vectors_from_diff(update_vectors_diff)?;
+        let mut params = config.params.clone();
+        params.update_vectors_from_diff(update_vectors_diff)?;
+        self.check_storage_encryption(
+            &params,
+            &config.optimizer_config,
+            config.quantization_config.as_ref(),
+        )?;
+        config.params = params;
         config.save(&self.path)?;
         Ok(())
     }
@@ -85,9 +105,14 @@ impl Collection {
     ) -> CollectionResult<()> {
         let mut config = self.collection_config.write().await;
         update_vectors_diff.check_vector_names(&config.params)?;
-        config
-            .params
-            .update_sparse_vectors_from_other(update_vectors_diff)?;
+        let mut params = config.params.clone();
+        params.update_sparse_vectors_from_other(update_vectors_diff)?;
+        self.check_storage_encryption(
+            &params,
+            &config.optimizer_config,
+            config.quantization_config.as_ref(),
+        )?;
+        config.params = params;
         config.save(&self.path)?;
         Ok(())
     }
@@ -103,8 +128,16 @@ impl Collection {
     ) -> CollectionResult<()> {
         {
             let mut config = self.collection_config.write().await;
-            config.optimizer_config =
+            let optimizer_config =
                 DiffConfig::update(optimizer_config_diff, &config.optimizer_config)?;
+            self.check_storage_encryption(
+                &config.params,
+                &optimizer_config,
+                config.quantization_config.as_ref(),
+            )?;
+            config.optimizer_config = optimizer_config;
+            self.optimizer_cpu_budget
+                .set_limit(config.optimizer_config.cpu_budget);
         }
         self.collection_config.read().await.save(&self.path)?;
         Ok(())
@@ -120,12 +153,123 @@ impl Collection {
     ) -> CollectionResult<()> {
         {
             let mut config = self.collection_config.write().await;
+            self.check_storage_encryption(
+                &config.params,
+                &optimizer_config,
+                config.quantization_config.as_ref(),
+            )?;
+            self.optimizer_cpu_budget
+                .set_limit(optimizer_config.cpu_budget);
             config.optimizer_config = optimizer_config;
         }
         self.collection_config.read().await.save(&self.path)?;
         Ok(())
     }
 
+    /// Updates hot/cold storage tiering config:
+    /// Saves new params on disk
+    ///
+    /// Shards pick up the new configuration on the next tiering check, no need to recreate
+    /// optimizers.
+    pub async fn update_tiering_config(
+        &self,
+        tiering_config: TieringConfig,
+    ) -> CollectionResult<()> {
+        {
+            let mut config = self.collection_config.write().await;
+            config.tiering_config = Some(tiering_config);
+        }
+        self.collection_config.read().await.save(&self.path)?;
+        Ok(())
+    }
+
+    /// Updates points expiry config:
+    /// Saves new params on disk
+    ///
+    /// Shards pick up the new configuration on the next expiry check.
+    pub async fn update_ttl_config(&self, ttl_config: TtlConfig) -> CollectionResult<()> {
+        {
+            let mut config = self.collection_config.write().await;
+            config.ttl_config = Some(ttl_config);
+        }
+        self.collection_config.read().await.save(&self.path)?;
+        Ok(())
+    }
+
+    /// Updates asynchronous replication config:
+    /// Saves new params on disk
+    ///
+    /// Replication picks up the new configuration on the next round.
+    pub async fn update_async_replication_config(
+        &self,
+        async_replication_config: AsyncReplicationConfig,
+    ) -> CollectionResult<()> {
+        {
+            let mut config = self.collection_config.write().await;
+            config.async_replication_config = Some(async_replication_config);
+        }
+        self.collection_config.read().await.save(&self.path)?;
+        Ok(())
+    }
+
+    /// Updates embedding service config:
+    /// Saves new params on disk
+    pub async fn update_inference_config(
+        &self,
+        inference_config: InferenceConfig,
+    ) -> CollectionResult<()> {
+        {
+            let mut config = self.collection_config.write().await;
+            config.inference_config = Some(inference_config);
+        }
+        self.collection_config.read().await.save(&self.path)?;
+        Ok(())
+    }
+
+    /// Embedding service config of the collection, if any
+    pub async fn inference_config(&self) -> Option<InferenceConfig> {
+        self.collection_config.read().await.inference_config.clone()
+    }
+
+    /// Text conversion params of sparse vectors, which are produced from text by the server
+    pub async fn sparse_text_params(&self) -> HashMap<String, SparseTextParams> {
+        let config = self.collection_config.read().await;
+        config
+            .params
+            .sparse_vectors
+            .iter()
+            .flatten()
+            .filter_map(|(name, params)| Some((name.clone(), params.text.clone()?)))
+            .collect()
+    }
+
+    /// Updates tenant quotas:
+    /// Saves new params on disk
+    ///
+    /// Measured usage of tenants is kept, new limits apply to the next upsert.
+    pub async fn update_quota_config(&self, quota_config: QuotaConfig) -> CollectionResult<()> {
+        {
+            let mut config = self.collection_config.write().await;
+            config.quota_config = Some(quota_config);
+        }
+        self.collection_config.read().await.save(&self.path)?;
+        Ok(())
+    }
+
+    /// Updates shard pinning policy:
+    /// Saves new params on disk
+    ///
+    /// Empty list of pins removes the policy. Existing replicas are not moved, pins are
+    /// respected by the following placements and transfers.
+    pub async fn update_shard_pinning(&self, shard_pinning: Vec<ShardPin>) -> CollectionResult<()> {
+        {
+            let mut config = self.collection_config.write().await;
+            config.params.shard_pinning = (!shard_pinning.is_empty()).then_some(shard_pinning);
+        }
+        self.collection_config.read().await.save(&self.path)?;
+        Ok(())
+    }
+
     /// Updates quantization config:
     /// Saves new params on disk
     ///
@@ -137,26 +281,20 @@ impl Collection {
     ) -> CollectionResult<()> {
         {
             let mut config = self.collection_config.write().await;
-            match quantization_config_diff {
-                QuantizationConfigDiff::Scalar(scalar) => {
-                    config
-                        .quantization_config
-                        .replace(QuantizationConfig::Scalar(scalar));
-                }
+            let quantization_config = match quantization_config_diff {
+                QuantizationConfigDiff::Scalar(scalar) => Some(QuantizationConfig::Scalar(scalar)),
                 QuantizationConfigDiff::Product(product) => {
-                    config
-                        .quantization_config
-                        .replace(QuantizationConfig::Product(product));
-                }
-                QuantizationConfigDiff::Binary(binary) => {
-                    config
-                        .quantization_config
-                        .replace(QuantizationConfig::Binary(binary));
+                    Some(QuantizationConfig::Product(product))
                 }
-                QuantizationConfigDiff::Disabled(_) => {
-                    config.quantization_config = None;
-                }
-            }
+                QuantizationConfigDiff::Binary(binary) => Some(QuantizationConfig::Binary(binary)),
+                QuantizationConfigDiff::Disabled(_) => None,
+            };
+            self.check_storage_encryption(
+                &config.params,
+                &config.optimizer_config,
+                quantization_config.as_ref(),
+            )?;
+            config.quantization_config = quantization_config;
         }
         self.collection_config.read().await.save(&self.path)?;
         Ok(())
@@ -277,6 +415,13 @@ impl Collection {
                     .and_modify(|info_schema| info_schema.points += response_schema.points)
                     .or_insert(response_schema);
             }
+
+            for (vector_name, response_stats) in response.sparse_vectors_stats {
+                info.sparse_vectors_stats
+                    .entry(vector_name)
+                    .or_default()
+                    .merge(&response_stats);
+            }
         }
 
         // Do not display vectors count, as it is an approximate number
@@ -347,4 +492,190 @@ impl Collection {
         };
         Ok(info)
     }
+
+    /// Point counts per local shard and per segment
+    pub async fn points_breakdown(
+        &self,
+        peer_id: PeerId,
+    ) -> CollectionResult<CollectionPointsBreakdown> {
+        let shards_holder = self.shards_holder.read().await;
+        let shard_to_key = shards_holder.get_shard_id_to_key_mapping();
+        let mut shards = Vec::new();
+
+        for (shard_id, replica_set) in shards_holder.get_shards() {
+            let Some(breakdown) = replica_set.local_points_breakdown().await else {
+                continue;
+            };
+            let state = replica_set
+                .peers()
+                .get(&replica_set.this_peer_id())
+                .copied()
+                .unwrap_or(ReplicaState::Dead);
+            shards.push(ShardPointsBreakdown::new(
+                *shard_id,
+                shard_to_key.get(shard_id).cloned(),
+                state,
+                breakdown,
+            ));
+        }
+
+        shards.sort_by_key(|shard| shard.shard_id);
+
+        Ok(CollectionPointsBreakdown { peer_id, shards })
+    }
+
+    pub async fn index_info(&self, peer_id: PeerId) -> CollectionResult<CollectionIndexInfo> {
+        let shards_holder = self.shards_holder.read().await;
+        let shard_to_key = shards_holder.get_shard_id_to_key_mapping();
+        let mut shards = Vec::new();
+
+        for (shard_id, replica_set) in shards_holder.get_shards() {
+            let Some(segments) = replica_set.local_index_info().await? else {
+                continue;
+            };
+            let state = replica_set
+                .peers()
+                .get(&replica_set.this_peer_id())
+                .copied()
+                .unwrap_or(ReplicaState::Dead);
+            shards.push(ShardIndexInfo {
+                shard_id: *shard_id,
+                shard_key: shard_to_key.get(shard_id).cloned(),
+                state,
+                segments,
+            });
+        }
+
+        shards.sort_by_key(|shard| shard.shard_id);
+
+        Ok(CollectionIndexInfo { peer_id, shards })
+    }
+
+    /// Plan optimization of segments of local shards, which are not indexed yet, regardless of
+    /// optimizer thresholds. Plans all local shards, or only the given one.
+    ///
+    /// Planned optimizations do not borrow the collection, so that it's not locked while they run.
+    pub async fn forced_optimizations(
+        &self,
+        shard_id: Option<ShardId>,
+    ) -> CollectionResult<Vec<(ShardId, ForcedOptimization)>> {
+        let shards_holder = self.shards_holder.read().await;
+
+        let Some(shard_id) = shard_id else {
+            let mut optimizations = Vec::new();
+            for (shard_id, replica_set) in shards_holder.get_shards() {
+                if let Some(optimization) = replica_set.local_forced_optimization().await {
+                    optimizations.push((*shard_id, optimization));
+                }
+            }
+            optimizations.sort_by_key(|(shard_id, _)| *shard_id);
+            return Ok(optimizations);
+        };
+
+        let replica_set = shards_holder
+            .get_shard(&shard_id)
+            .ok_or_else(|| shard_not_found_error(shard_id))?;
+        let optimization = replica_set
+            .local_forced_optimization()
+            .await
+            .ok_or_else(|| {
+                CollectionError::bad_request(format!(
+                    "Shard {shard_id} has no replica on this peer"
+                ))
+            })?;
+        Ok(vec![(shard_id, optimization)])
+    }
+
+    /// Plan compaction of segments of all local shards, in which the fraction of deleted points is
+    /// above `deleted_threshold`, or above the threshold of the optimizers config if not set.
+    ///
+    /// Planned compactions do not borrow the collection, so that it's not locked while they run.
+    pub async fn vacuums(&self, deleted_threshold: Option<f64>) -> Vec<Vacuum> {
+        let shards_holder = self.shards_holder.read().await;
+
+        let mut vacuums = Vec::new();
+        for replica_set in shards_holder.all_shards() {
+            if let Some(vacuum) = replica_set.local_vacuum(deleted_threshold).await {
+                vacuums.push(vacuum);
+            }
+        }
+        vacuums
+    }
+
+    /// Plan moving vectors of segments of all local shards to disk, with RAM usage of the shards.
+    ///
+    /// Planned spills do not borrow the collection, so that it's not locked while they run.
+    pub async fn memory_spills(&self) -> Vec<(ShardId, MemorySpill)> {
+        let shards_holder = self.shards_holder.read().await;
+
+        let mut spills = Vec::new();
+        for (shard_id, replica_set) in shards_holder.get_shards() {
+            if let Some(spill) = replica_set.local_memory_spill().await {
+                spills.push((*shard_id, spill));
+            }
+        }
+        spills
+    }
+
+    /// Bytes used on disk by the collection on this peer, walking directories of local shards
+    pub async fn disk_usage(&self, peer_id: PeerId) -> CollectionResult<CollectionDiskUsage> {
+        let mut local_shards = Vec::new();
+        {
+            let shards_holder = self.shards_holder.read().await;
+            let shard_to_key = shards_holder.get_shard_id_to_key_mapping();
+            for (shard_id, replica_set) in shards_holder.get_shards() {
+                if !replica_set.has_local_shard().await {
+                    continue;
+                }
+                let state = replica_set
+                    .peers()
+                    .get(&replica_set.this_peer_id())
+                    .copied()
+                    .unwrap_or(ReplicaState::Dead);
+                local_shards.push((
+                    *shard_id,
+                    shard_to_key.get(shard_id).cloned(),
+                    state,
+                    replica_set.shard_path.clone(),
+                ));
+            }
+        }
+
+        let collection_path = self.path.clone();
+        let snapshots_path = self.snapshots_path.clone();
+
+        let usage = tokio::task::spawn_blocking(move || {
+            let mut shards: Vec<_> = local_shards
+                .into_iter()
+                .map(|(shard_id, shard_key, state, shard_path)| {
+                    ShardDiskUsage::new(
+                        shard_id,
+                        shard_key,
+                        state,
+                        disk_usage::shard_disk_usage(&shard_path),
+                    )
+                })
+                .collect();
+            shards.sort_by_key(|shard| shard.shard_id);
+
+            let mut segments_data = SegmentDataDiskUsage::default();
+            for shard in &shards {
+                segments_data.add(&shard.segments_data);
+            }
+
+            let snapshots_bytes = disk_usage::dir_disk_usage(&snapshots_path);
+
+            CollectionDiskUsage {
+                peer_id,
+                total_bytes: disk_usage::dir_disk_usage(&collection_path) + snapshots_bytes,
+                wal_bytes: shards.iter().map(|shard| shard.wal_bytes).sum(),
+                snapshots_bytes,
+                segments_data,
+                shards,
+            }
+        })
+        .await?;
+
+        Ok(usage)
+    }
 }
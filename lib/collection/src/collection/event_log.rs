@@ -0,0 +1,228 @@
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use segment::json_path::JsonPath;
+use serde::{Deserialize, Serialize};
+
+use super::Collection;
+use crate::collection_manager::holders::segment_holder::SegmentId;
+use crate::collection_manager::optimizers::{TrackerStatus, TrackerTelemetry};
+use crate::operations::types::CollectionResult;
+use crate::save_on_disk::SaveOnDisk;
+use crate::shards::replica_set::ReplicaState;
+use crate::shards::shard::{PeerId, ShardId};
+
+const EVENT_LOG_FILE: &str = "event_log.json";
+
+/// Number of last events kept in the log of the collection
+const KEEP_LAST_EVENTS: usize = 256;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CollectionEventKind {
+    /// Collection was created
+    Created,
+    /// Collection was loaded on start of this peer
+    Loaded,
+    /// Configuration of the collection was updated
+    ConfigChanged {
+        /// Applied update, as in the update collection request
+        update: serde_json::Value,
+    },
+    /// Payload index was created, it's built by local shards in background
+    PayloadIndexCreated {
+        field_name: JsonPath,
+    },
+    PayloadIndexDropped {
+        field_name: JsonPath,
+    },
+    /// State of a replica was changed through consensus
+    ReplicaStateChanged {
+        shard_id: ShardId,
+        peer_id: PeerId,
+        state: ReplicaState,
+    },
+    /// Optimizer of a local shard replaced segments with optimized ones, with indexes built
+    OptimizationFinished {
+        shard_id: ShardId,
+        optimizer: String,
+        segment_ids: Vec<SegmentId>,
+    },
+    /// Optimizer of a local shard failed, optimizations of the shard are stopped until restart
+    OptimizerError {
+        shard_id: ShardId,
+        optimizer: String,
+        error: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct CollectionEvent {
+    /// Time of the event, in UTC
+    pub time: DateTime<Utc>,
+    pub event: CollectionEventKind,
+}
+
+impl CollectionEvent {
+    pub fn now(event: CollectionEventKind) -> Self {
+        Self {
+            time: Utc::now(),
+            event,
+        }
+    }
+
+    /// Event of the finished or failed optimization, if any
+    fn from_optimizer_tracker(shard_id: ShardId, tracker: TrackerTelemetry) -> Option<Self> {
+        let time = tracker.end_at?;
+        let event = match tracker.status {
+            TrackerStatus::Done => CollectionEventKind::OptimizationFinished {
+                shard_id,
+                optimizer: tracker.name,
+                segment_ids: tracker.segment_ids,
+            },
+            TrackerStatus::Error(error) => CollectionEventKind::OptimizerError {
+                shard_id,
+                optimizer: tracker.name,
+                error,
+            },
+            TrackerStatus::Optimizing | TrackerStatus::Cancelled(_) => return None,
+        };
+        Some(Self { time, event })
+    }
+}
+
+/// Last events of the collection on this peer, persisted across restarts
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CollectionEventLog {
+    events: VecDeque<CollectionEvent>,
+}
+
+impl CollectionEventLog {
+    fn push(&mut self, event: CollectionEvent) {
+        self.events.push_back(event);
+        while self.events.len() > KEEP_LAST_EVENTS {
+            self.events.pop_front();
+        }
+    }
+}
+
+/// Events of the collection on this peer
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct CollectionEvents {
+    /// ID of this peer
+    pub peer_id: PeerId,
+    /// Events, latest first
+    pub events: Vec<CollectionEvent>,
+}
+
+impl Collection {
+    fn event_log_file(collection_path: &Path) -> PathBuf {
+        collection_path.join(EVENT_LOG_FILE)
+    }
+
+    pub(crate) fn load_event_log(
+        collection_path: &Path,
+    ) -> CollectionResult<SaveOnDisk<CollectionEventLog>> {
+        let event_log_file = Self::event_log_file(collection_path);
+        let event_log = SaveOnDisk::load_or_init(event_log_file)?;
+        Ok(event_log)
+    }
+
+    /// Add the event to the log of the collection.
+    ///
+    /// Failure to persist the log is not an error of the operation, which caused the event.
+    pub fn record_event(&self, event: CollectionEventKind) {
+        let event = CollectionEvent::now(event);
+        if let Err(err) = self.event_log.write(|log| log.push(event)) {
+            log::warn!("Failed to save event log of collection {}: {err}", self.id);
+        }
+    }
+
+    /// Last events of the collection on this peer, latest first.
+    ///
+    /// Optimizer events are taken from logs of optimizers of local shards, which keep the last
+    /// optimizations since the start of this peer.
+    pub async fn events(&self, limit: Option<usize>) -> CollectionEvents {
+        let mut events: Vec<_> = self.event_log.read().events.iter().cloned().collect();
+
+        let shards_holder = self.shards_holder.read().await;
+        for (shard_id, replica_set) in shards_holder.get_shards() {
+            let Some(trackers) = replica_set.local_optimizations_log().await else {
+                continue;
+            };
+            events.extend(
+                trackers.into_iter().filter_map(|tracker| {
+                    CollectionEvent::from_optimizer_tracker(*shard_id, tracker)
+                }),
+            );
+        }
+
+        events.sort_by(|a, b| b.time.cmp(&a.time));
+        if let Some(limit) = limit {
+            events.truncate(limit);
+        }
+
+        CollectionEvents {
+            peer_id: self.this_peer_id,
+            events,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_log_is_bounded() {
+        let mut log = CollectionEventLog::default();
+        for shard_id in 0..KEEP_LAST_EVENTS as ShardId + 10 {
+            log.push(CollectionEvent::now(
+                CollectionEventKind::ReplicaStateChanged {
+                    shard_id,
+                    peer_id: 1,
+                    state: ReplicaState::Active,
+                },
+            ));
+        }
+        assert_eq!(log.events.len(), KEEP_LAST_EVENTS);
+        assert_eq!(
+            log.events.front().unwrap().event,
+            CollectionEventKind::ReplicaStateChanged {
+                shard_id: 10,
+                peer_id: 1,
+                state: ReplicaState::Active,
+            },
+        );
+    }
+
+    #[test]
+    fn test_events_from_optimizer_trackers() {
+        let tracker = TrackerTelemetry {
+            name: "indexing".to_string(),
+            segment_ids: vec![1, 2],
+            status: TrackerStatus::Done,
+            start_at: Utc::now(),
+            end_at: Some(Utc::now()),
+        };
+        let event = CollectionEvent::from_optimizer_tracker(3, tracker.clone()).unwrap();
+        assert_eq!(
+            serde_json::to_value(&event.event).unwrap(),
+            serde_json::json!({
+                "type": "optimization_finished",
+                "shard_id": 3,
+                "optimizer": "indexing",
+                "segment_ids": [1, 2],
+            }),
+        );
+
+        let running = TrackerTelemetry {
+            status: TrackerStatus::Optimizing,
+            end_at: None,
+            ..tracker
+        };
+        assert!(CollectionEvent::from_optimizer_tracker(3, running).is_none());
+    }
+}
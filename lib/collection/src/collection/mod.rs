@@ -1,12 +1,20 @@
+pub mod async_replication;
 mod collection_ops;
+pub mod event_log;
+mod multitenancy;
 pub mod payload_index_schema;
 mod point_ops;
+mod points_expiry;
+mod projection;
+mod quota;
 pub mod resharding;
 mod search;
 mod shard_transfer;
 mod sharding_keys;
+mod snapshot_schedule;
 mod snapshots;
 mod state_management;
+mod storage_encryption;
 
 use std::collections::{HashMap, HashSet};
 use std::ops::Deref;
@@ -22,12 +30,17 @@ use semver::Version;
 use tokio::runtime::Handle;
 use tokio::sync::{Mutex, RwLock, RwLockWriteGuard};
 
+use self::async_replication::AsyncReplicationState;
+use self::event_log::{CollectionEventKind, CollectionEventLog};
+use self::projection::ProjectionCache;
+use self::quota::QuotaTracker;
 use self::resharding::ReshardingState;
 use crate::collection::payload_index_schema::PayloadIndexSchema;
 use crate::collection_state::{ShardInfo, State};
 use crate::common::is_ready::IsReady;
 use crate::config::CollectionConfig;
 use crate::operations::shared_storage_config::SharedStorageConfig;
+use crate::operations::snapshot_ops::SnapshotSchedule;
 use crate::operations::types::{CollectionError, CollectionResult, NodeType};
 use crate::save_on_disk::SaveOnDisk;
 use crate::shards::channel_service::ChannelService;
@@ -41,7 +54,7 @@ use crate::shards::transfer::helpers::check_transfer_conflicts_strict;
 use crate::shards::transfer::transfer_tasks_pool::{TaskResult, TransferTasksPool};
 use crate::shards::transfer::{ShardTransfer, ShardTransferMethod};
 use crate::shards::{replica_set, CollectionId};
-use crate::telemetry::CollectionTelemetry;
+use crate::telemetry::{CollectionRequestsAggregators, CollectionTelemetry};
 
 const RESHARDING_STATE_FILE: &str = "resharding_state.json";
 
@@ -54,6 +67,14 @@ pub struct Collection {
     pub(crate) shared_storage_config: Arc<SharedStorageConfig>,
     pub(crate) payload_index_schema: SaveOnDisk<PayloadIndexSchema>,
     resharding_state: SaveOnDisk<Option<ReshardingState>>,
+    snapshot_schedule: SaveOnDisk<Option<SnapshotSchedule>>,
+    async_replication_state: SaveOnDisk<AsyncReplicationState>,
+    /// Last lifecycle events of the collection on this peer
+    event_log: SaveOnDisk<CollectionEventLog>,
+    quota_tracker: QuotaTracker,
+    projection_cache: ProjectionCache,
+    /// Durations of searches, scrolls and updates of the collection
+    pub(crate) requests_telemetry: CollectionRequestsAggregators,
     this_peer_id: PeerId,
     path: PathBuf,
     snapshots_path: PathBuf,
@@ -104,6 +125,10 @@ impl Collection {
 
         let mut shard_holder = ShardHolder::new(path, None)?;
 
+        // Optimizations of all shards of the collection share the limit of CPUs
+        let optimizer_cpu_budget =
+            optimizer_cpu_budget.with_limit(collection_config.optimizer_config.cpu_budget);
+
         let shared_collection_config = Arc::new(RwLock::new(collection_config.clone()));
         for (shard_id, mut peers) in shard_distribution.shards {
             let is_local = peers.remove(&this_peer_id);
@@ -138,14 +163,23 @@ impl Collection {
 
         let payload_index_schema = Self::load_payload_index_schema(path)?;
         let resharding_state = Self::load_resharding_state(path)?;
+        let snapshot_schedule = Self::load_snapshot_schedule(path)?;
+        let async_replication_state = Self::load_async_replication_state(path)?;
+        let event_log = Self::load_event_log(path)?;
 
-        Ok(Self {
+        let collection = Self {
             id: name.clone(),
             shards_holder: locked_shard_holder,
             collection_config: shared_collection_config,
             payload_index_schema,
             shared_storage_config,
             resharding_state,
+            snapshot_schedule,
+            async_replication_state,
+            event_log,
+            quota_tracker: Default::default(),
+            projection_cache: Default::default(),
+            requests_telemetry: Default::default(),
             this_peer_id,
             path: path.to_owned(),
             snapshots_path: snapshots_path.to_owned(),
@@ -160,7 +194,10 @@ impl Collection {
             update_runtime: update_runtime.unwrap_or_else(Handle::current),
             search_runtime: search_runtime.unwrap_or_else(Handle::current),
             optimizer_cpu_budget,
-        })
+        };
+        collection.record_event(CollectionEventKind::Created);
+
+        Ok(collection)
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -209,6 +246,10 @@ impl Collection {
         });
         collection_config.validate_and_warn();
 
+        // Optimizations of all shards of the collection share the limit of CPUs
+        let optimizer_cpu_budget =
+            optimizer_cpu_budget.with_limit(collection_config.optimizer_config.cpu_budget);
+
         let resharding_state = Self::load_resharding_state(path)
             .expect("Can't load or initialize resharding progress");
 
@@ -241,13 +282,28 @@ impl Collection {
         let payload_index_schema = Self::load_payload_index_schema(path)
             .expect("Can't load or initialize payload index schema");
 
-        Self {
+        let snapshot_schedule =
+            Self::load_snapshot_schedule(path).expect("Can't load or initialize snapshot schedule");
+
+        let async_replication_state = Self::load_async_replication_state(path)
+            .expect("Can't load or initialize asynchronous replication state");
+
+        let event_log =
+            Self::load_event_log(path).expect("Can't load or initialize collection event log");
+
+        let collection = Self {
             id: collection_id.clone(),
             shards_holder: locked_shard_holder,
             collection_config: shared_collection_config,
             payload_index_schema,
             shared_storage_config,
             resharding_state,
+            snapshot_schedule,
+            async_replication_state,
+            event_log,
+            quota_tracker: Default::default(),
+            projection_cache: Default::default(),
+            requests_telemetry: Default::default(),
             this_peer_id,
             path: path.to_owned(),
             snapshots_path: snapshots_path.to_owned(),
@@ -262,7 +318,12 @@ impl Collection {
             update_runtime: update_runtime.unwrap_or_else(Handle::current),
             search_runtime: search_runtime.unwrap_or_else(Handle::current),
             optimizer_cpu_budget,
-        }
+        };
+        // Keep operations, which are not replayed to the remote cluster yet, from the start
+        collection.pin_async_replication_offsets().await;
+        collection.record_event(CollectionEventKind::Loaded);
+
+        collection
     }
 
     fn resharding_state_file(collection_path: &Path) -> PathBuf {
@@ -396,6 +457,12 @@ impl Collection {
             .ensure_replica_with_state(&peer_id, state)
             .await?;
 
+        self.record_event(CollectionEventKind::ReplicaStateChanged {
+            shard_id,
+            peer_id,
+            state,
+        });
+
         if state == ReplicaState::Dead {
             // Terminate transfer if source or target replicas are now dead
             let related_transfers = shard_holder.get_related_transfers(&shard_id, &peer_id);
@@ -486,6 +553,13 @@ impl Collection {
             .await
     }
 
+    /// Disable replicas of an unreachable peer, see [`ShardReplicaSet::report_unreachable_peer`]
+    pub async fn report_unreachable_peer(&self, peer_id: PeerId) {
+        for replica_set in self.shards_holder.read().await.all_shards() {
+            replica_set.report_unreachable_peer(peer_id);
+        }
+    }
+
     pub async fn sync_local_state(
         &self,
         on_transfer_failure: OnTransferFailure,
@@ -775,6 +849,8 @@ impl Collection {
             config: self.collection_config.read().await.clone(),
             shards: shards_telemetry,
             transfers,
+            async_replication: self.async_replication_status().await,
+            requests: self.requests_telemetry.get_telemetry_data(detail),
         }
     }
 
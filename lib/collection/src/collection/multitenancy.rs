@@ -0,0 +1,195 @@
+//! Validation of writes to a collection in multitenancy mode, see
+//! [`MultitenancyConfig`](crate::config::MultitenancyConfig).
+
+use segment::json_patch::PatchOperation;
+use segment::json_path::{JsonPath, JsonPathInterface as _};
+use segment::types::Payload;
+use serde_json::Value;
+
+use super::Collection;
+use crate::operations::payload_ops::{DeletePayloadOp, PatchPayloadOp, PayloadOps, SetPayloadOp};
+use crate::operations::point_ops::PointOperations;
+use crate::operations::types::{CollectionError, CollectionResult};
+use crate::operations::CollectionUpdateOperations;
+
+impl Collection {
+    /// Payload field, which holds the tenant of a point, if the collection is multitenant
+    pub async fn tenant_key(&self) -> Option<JsonPath> {
+        self.collection_config
+            .read()
+            .await
+            .multitenancy_config
+            .as_ref()
+            .map(|config| config.tenant_key.clone())
+    }
+
+    /// Reject the write, if it would leave a point without a tenant
+    pub(super) async fn check_tenant_key(
+        &self,
+        operation: &CollectionUpdateOperations,
+    ) -> CollectionResult<()> {
+        match self.tenant_key().await {
+            Some(tenant_key) => check_tenant_key(&tenant_key, operation),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Check that every point keeps a single string value of the tenant field
+fn check_tenant_key(
+    tenant_key: &JsonPath,
+    operation: &CollectionUpdateOperations,
+) -> CollectionResult<()> {
+    match operation {
+        CollectionUpdateOperations::PointOperation(point_operation) => {
+            let points_op = match point_operation {
+                PointOperations::UpsertPoints(points_op) => points_op,
+                PointOperations::UpsertPointsConditional(operation) => &operation.points_op,
+                PointOperations::DeletePoints { .. }
+                | PointOperations::DeletePointsByFilter(_)
+                | PointOperations::SyncPoints(_) => return Ok(()),
+            };
+            for payload in points_op.payloads() {
+                check_tenant_value(tenant_key, payload)?;
+            }
+        }
+        CollectionUpdateOperations::PayloadOperation(payload_operation) => {
+            match payload_operation {
+                PayloadOps::SetPayload(SetPayloadOp { payload, key, .. }) => {
+                    if tenant_key.is_affected_by_value_set(&payload.0, key.as_ref()) {
+                        if key.is_some() {
+                            return Err(tenant_key_modified(tenant_key));
+                        }
+                        check_tenant_value(tenant_key, Some(payload))?;
+                    }
+                }
+                PayloadOps::OverwritePayload(SetPayloadOp { payload, key, .. }) => {
+                    if key.is_some() {
+                        if tenant_key.is_affected_by_value_set(&payload.0, key.as_ref()) {
+                            return Err(tenant_key_modified(tenant_key));
+                        }
+                    } else {
+                        check_tenant_value(tenant_key, Some(payload))?;
+                    }
+                }
+                PayloadOps::DeletePayload(DeletePayloadOp { keys, .. }) => {
+                    if keys
+                        .iter()
+                        .any(|key| tenant_key.is_affected_by_value_remove(key))
+                    {
+                        return Err(tenant_key_modified(tenant_key));
+                    }
+                }
+                PayloadOps::ClearPayload { .. } | PayloadOps::ClearPayloadByFilter(_) => {
+                    return Err(tenant_key_modified(tenant_key));
+                }
+                PayloadOps::PatchPayload(PatchPayloadOp { patch, .. }) => {
+                    if patch
+                        .iter()
+                        .any(|operation| patch_affects_tenant_key(tenant_key, operation))
+                    {
+                        return Err(tenant_key_modified(tenant_key));
+                    }
+                }
+            }
+        }
+        CollectionUpdateOperations::VectorOperation(_)
+        | CollectionUpdateOperations::FieldIndexOperation(_)
+        | CollectionUpdateOperations::BlobOperation(_) => (),
+    }
+    Ok(())
+}
+
+fn check_tenant_value(tenant_key: &JsonPath, payload: Option<&Payload>) -> CollectionResult<()> {
+    let is_valid = payload.map_or(false, |payload| {
+        match tenant_key.value_get(&payload.0).as_slice() {
+            [Value::String(tenant)] => !tenant.is_empty(),
+            _ => false,
+        }
+    });
+    if is_valid {
+        Ok(())
+    } else {
+        Err(CollectionError::bad_input(format!(
+            "Payload of every point must have a single string tenant in field {tenant_key}",
+        )))
+    }
+}
+
+fn tenant_key_modified(tenant_key: &JsonPath) -> CollectionError {
+    CollectionError::bad_input(format!(
+        "Tenant field {tenant_key} can only be changed by setting a new tenant",
+    ))
+}
+
+/// Check if the JSON patch operation writes or removes the top level key of the tenant field
+fn patch_affects_tenant_key(tenant_key: &JsonPath, operation: &PatchOperation) -> bool {
+    let affects = |pointer: &str| match pointer.split('/').nth(1) {
+        Some(key) => key.replace("~1", "/").replace("~0", "~") == tenant_key.first_key,
+        // Whole document is replaced
+        None => true,
+    };
+    match operation {
+        PatchOperation::Add { path, .. }
+        | PatchOperation::Remove { path }
+        | PatchOperation::Replace { path, .. }
+        | PatchOperation::Copy { path, .. } => affects(path),
+        PatchOperation::Move { from, path } => affects(from) || affects(path),
+        PatchOperation::Test { .. } => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::json_path::path;
+    use serde_json::json;
+
+    use super::*;
+    use crate::operations::point_ops::{PointInsertOperationsInternal, PointStruct};
+
+    fn upsert(payload: Value) -> CollectionUpdateOperations {
+        let point: PointStruct = serde_json::from_value(json!({
+            "id": 1,
+            "vector": [1.0, 0.0],
+            "payload": payload,
+        }))
+        .unwrap();
+        CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+            PointInsertOperationsInternal::PointsList(vec![point]),
+        ))
+    }
+
+    #[test]
+    fn test_check_tenant_key() {
+        let tenant_key: JsonPath = path("tenant");
+
+        assert!(check_tenant_key(&tenant_key, &upsert(json!({"tenant": "acme"}))).is_ok());
+        assert!(check_tenant_key(&tenant_key, &upsert(json!({"tenant": ""}))).is_err());
+        assert!(check_tenant_key(&tenant_key, &upsert(json!({"tenant": ["a", "b"]}))).is_err());
+        assert!(check_tenant_key(&tenant_key, &upsert(json!({"city": "Berlin"}))).is_err());
+
+        let delete = |key: &str| {
+            CollectionUpdateOperations::PayloadOperation(PayloadOps::DeletePayload(
+                DeletePayloadOp {
+                    keys: vec![path(key)],
+                    points: Some(vec![1.into()]),
+                    filter: None,
+                },
+            ))
+        };
+        assert!(check_tenant_key(&tenant_key, &delete("city")).is_ok());
+        assert!(check_tenant_key(&tenant_key, &delete("tenant")).is_err());
+
+        let patch = |pointer: &str| {
+            CollectionUpdateOperations::PayloadOperation(PayloadOps::PatchPayload(PatchPayloadOp {
+                patch: vec![PatchOperation::Remove {
+                    path: pointer.to_string(),
+                }],
+                points: Some(vec![1.into()]),
+                filter: None,
+            }))
+        };
+        assert!(check_tenant_key(&tenant_key, &patch("/city")).is_ok());
+        assert!(check_tenant_key(&tenant_key, &patch("/tenant")).is_err());
+    }
+}
@@ -5,6 +5,7 @@ use segment::json_path::JsonPath;
 use segment::types::{PayloadFieldSchema, PayloadKeyType};
 use serde::{Deserialize, Serialize};
 
+use crate::collection::event_log::CollectionEventKind;
 use crate::collection::Collection;
 use crate::operations::types::{CollectionResult, UpdateResult};
 use crate::operations::{CollectionUpdateOperations, CreateIndex, FieldIndexOperations};
@@ -47,24 +48,27 @@ impl Collection {
         field_schema: PayloadFieldSchema,
         wait: bool,
     ) -> CollectionResult<Option<UpdateResult>> {
-        self.payload_index_schema.write(|schema| {
-            schema
-                .schema
-                .insert(field_name.clone(), field_schema.clone());
-        })?;
-
         // This operation might be redundant, if we also create index as a regular collection op,
         // but it looks better in long term to also have it here, so
         // the creation of payload index may be eventually completely converted
         // into the consensus operation
         let create_index_operation = CollectionUpdateOperations::FieldIndexOperation(
             FieldIndexOperations::CreateIndex(CreateIndex {
-                field_name,
-                field_schema: Some(field_schema),
+                field_name: field_name.clone(),
+                field_schema: Some(field_schema.clone()),
             }),
         );
+        self.check_storage_encryption_operation(&create_index_operation)?;
+
+        self.payload_index_schema.write(|schema| {
+            schema.schema.insert(field_name.clone(), field_schema);
+        })?;
+
+        let result = self.update_all_local(create_index_operation, wait).await?;
+
+        self.record_event(CollectionEventKind::PayloadIndexCreated { field_name });
 
-        self.update_all_local(create_index_operation, wait).await
+        Ok(result)
     }
 
     pub async fn drop_payload_index(
@@ -76,11 +80,13 @@ impl Collection {
         })?;
 
         let delete_index_operation = CollectionUpdateOperations::FieldIndexOperation(
-            FieldIndexOperations::DeleteIndex(field_name),
+            FieldIndexOperations::DeleteIndex(field_name.clone()),
         );
 
         let result = self.update_all_local(delete_index_operation, false).await?;
 
+        self.record_event(CollectionEventKind::PayloadIndexDropped { field_name });
+
         Ok(result)
     }
 }
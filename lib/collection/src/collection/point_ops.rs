@@ -1,14 +1,19 @@
 use std::collections::HashSet;
 use std::sync::Arc;
 
+use common::request_id;
 use futures::stream::FuturesUnordered;
 use futures::{future, StreamExt as _, TryFutureExt, TryStreamExt as _};
 use itertools::Itertools;
+use segment::blob_storage::BlobChunk;
 use segment::data_types::order_by::{Direction, OrderBy};
-use segment::types::{ShardKey, WithPayload, WithPayloadInterface};
+use segment::types::{PointIdType, ShardKey, WithPayload, WithPayloadInterface};
 use validator::Validate as _;
 
+use super::quota::QuotaWrite;
 use super::Collection;
+use crate::common::latency_histograms::OperationType;
+use crate::common::slow_log::{OperationDetails, OperationTimer};
 use crate::operations::consistency_params::ReadConsistency;
 use crate::operations::point_ops::WriteOrdering;
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
@@ -88,7 +93,7 @@ impl Collection {
         let update_lock = self.updates_lock.clone().read_owned().await;
         let shard_holder = self.shards_holder.clone().read_owned().await;
 
-        let result = tokio::task::spawn(async move {
+        let result = request_id::spawn(async move {
             let _update_lock = update_lock;
 
             let Some(shard) = shard_holder.get_shard(&shard_selection) else {
@@ -131,24 +136,46 @@ impl Collection {
     /// This method is cancel safe.
     pub async fn update_from_client(
         &self,
-        operation: CollectionUpdateOperations,
+        mut operation: CollectionUpdateOperations,
         wait: bool,
         ordering: WriteOrdering,
         shard_keys_selection: Option<ShardKey>,
     ) -> CollectionResult<UpdateResult> {
         operation.validate()?;
+        self.check_storage_encryption_operation(&operation)?;
+        self.check_tenant_key(&operation).await?;
+        self.check_point_expiry(&operation).await?;
+        self.project_operation(&mut operation).await?;
+
+        if let Some(write) = QuotaWrite::from_operation(&operation) {
+            self.check_quota(shard_keys_selection.as_ref(), write)
+                .await?;
+        }
+
+        let timer = OperationTimer::start(
+            &self.requests_telemetry.updates,
+            OperationType::from(&operation),
+        );
+        // Operation is moved into the update task, so details are taken beforehand
+        let details = timer
+            .is_reported()
+            .then(|| OperationDetails::update(&operation));
 
         let update_lock = self.updates_lock.clone().read_owned().await;
         let shard_holder = self.shards_holder.clone().read_owned().await;
 
-        let mut results = tokio::task::spawn(async move {
+        let shard_timer = timer.shards().clone();
+        let mut results = request_id::spawn(async move {
             let _update_lock = update_lock;
 
             let updates: FuturesUnordered<_> = shard_holder
                 .split_by_shard(operation, &shard_keys_selection)?
                 .into_iter()
                 .map(move |(shard, operation)| {
-                    shard.update_with_consistency(operation, wait, ordering)
+                    shard_timer.measure(
+                        shard.shard_id,
+                        shard.update_with_consistency(operation, wait, ordering),
+                    )
                 })
                 .collect();
 
@@ -169,6 +196,10 @@ impl Collection {
         // one request per shard
         let result_len = results.len();
 
+        if with_error == 0 {
+            timer.finish(&self.id, "update", || details.unwrap_or_default());
+        }
+
         if with_error > 0 {
             let first_err = results.into_iter().find(|result| result.is_err()).unwrap();
             // inconsistent if only a subset of the requests fail - one request per shard.
@@ -221,9 +252,9 @@ impl Collection {
             .with_payload
             .clone()
             .unwrap_or_else(|| default_request.with_payload.clone().unwrap());
-        let with_vector = request.with_vector;
+        let with_vector = request.with_vector.clone();
 
-        let order_by = request.order_by.map(OrderBy::from);
+        let order_by = request.order_by.clone().map(OrderBy::from);
 
         // Handle case of order_by
         if let Some(order_by) = &order_by {
@@ -261,13 +292,14 @@ impl Collection {
         };
 
         let local_only = shard_selection.is_shard_id();
+        let timer = OperationTimer::start(&self.requests_telemetry.scrolls, OperationType::Scroll);
 
         let retrieved_points: Vec<_> = {
             let shards_holder = self.shards_holder.read().await;
             let target_shards = shards_holder.select_shards(shard_selection)?;
             let scroll_futures = target_shards.into_iter().map(|(shard, shard_key)| {
                 let shard_key = shard_key.cloned();
-                shard
+                let scroll = shard
                     .scroll_by(
                         id_offset,
                         limit,
@@ -286,11 +318,13 @@ impl Collection {
                             point.shard_key.clone_from(&shard_key);
                         }
                         Ok(records)
-                    })
+                    });
+                timer.shards().measure(shard.shard_id, scroll)
             });
 
             future::try_join_all(scroll_futures).await?
         };
+        timer.finish(&self.id, "scroll", || OperationDetails::scroll(&request));
 
         let retrieved_iter = retrieved_points.into_iter();
 
@@ -426,4 +460,21 @@ impl Collection {
             .collect();
         Ok(points)
     }
+
+    /// Read up to `limit` bytes of the point blob, starting from `offset`
+    pub async fn read_blob(
+        &self,
+        point_id: PointIdType,
+        offset: usize,
+        limit: usize,
+        shard_selection: &ShardSelectorInternal,
+    ) -> CollectionResult<Option<BlobChunk>> {
+        let shard_holder = self.shards_holder.read().await;
+        let target_shards = shard_holder.select_shards(shard_selection)?;
+        let read_futures = target_shards.into_iter().map(|(shard, _shard_key)| {
+            shard.read_blob(point_id, offset, limit, shard_selection.is_shard_id())
+        });
+        let blobs = future::try_join_all(read_futures).await?;
+        Ok(blobs.into_iter().flatten().next())
+    }
 }
@@ -0,0 +1,77 @@
+use std::time::Duration;
+
+use super::Collection;
+use crate::operations::point_ops::EXPIRES_AT_KEY;
+use crate::operations::types::{CollectionError, CollectionResult};
+use crate::operations::CollectionUpdateOperations;
+
+/// How often expired points are checked, if collection TTL is not configured
+const DEFAULT_EXPIRY_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+impl Collection {
+    /// How often expired points of this collection should be checked
+    pub async fn expiry_check_interval(&self) -> Duration {
+        self.collection_config
+            .read()
+            .await
+            .ttl_config
+            .as_ref()
+            .filter(|ttl_config| ttl_config.enabled)
+            .map(|ttl_config| Duration::from_secs(ttl_config.check_interval_sec))
+            .unwrap_or(DEFAULT_EXPIRY_CHECK_INTERVAL)
+    }
+
+    /// Check that points get own expiration moment only if `point_expiry` is enabled for the
+    /// collection, and only through `expires_at`, not by writing the reserved payload key.
+    pub(super) async fn check_point_expiry(
+        &self,
+        operation: &CollectionUpdateOperations,
+    ) -> CollectionResult<()> {
+        if operation.writes_expiry_payload() {
+            return Err(CollectionError::bad_input(format!(
+                "Payload key `{EXPIRES_AT_KEY}` is reserved, use `expires_at` of the point to set its expiration moment",
+            )));
+        }
+
+        if !operation.has_expiring_points() {
+            return Ok(());
+        }
+        let point_expiry = self
+            .collection_config
+            .read()
+            .await
+            .ttl_config
+            .as_ref()
+            .map_or(false, |ttl_config| ttl_config.point_expiry);
+        if !point_expiry {
+            return Err(CollectionError::bad_input(format!(
+                "Expiration moment of points is not enabled for collection {}, enable `point_expiry` in its TTL config",
+                self.name(),
+            )));
+        }
+        Ok(())
+    }
+
+    /// Delete expired points of the shards, for which this peer is the update leader.
+    ///
+    /// Deletions go through the regular update path of the replica sets, so they are
+    /// clock-tagged and replicated to all replicas like any other update.
+    /// Returns the number of shards, in which deletion was issued.
+    pub async fn delete_expired_points(&self) -> CollectionResult<usize> {
+        let _update_lock = self.updates_lock.read().await;
+        let shards_holder = self.shards_holder.read().await;
+
+        let mut deleted = 0;
+        for (&shard_id, replica_set) in shards_holder.get_shards() {
+            match replica_set.delete_expired_points().await {
+                Ok(true) => deleted += 1,
+                Ok(false) => {}
+                Err(err) => log::warn!(
+                    "Failed to delete expired points of shard {}:{shard_id}: {err}",
+                    self.name(),
+                ),
+            }
+        }
+        Ok(deleted)
+    }
+}
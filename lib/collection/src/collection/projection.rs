@@ -0,0 +1,560 @@
+//! Projection of vectors to a lower number of dimensions on upsert and in queries, see
+//! [`ProjectionConfig`].
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use api::rest::{BatchVectorStruct, Vector, VectorStruct};
+use common::types::ScoreType;
+use itertools::Itertools;
+use parking_lot::Mutex;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use segment::common::operation_error::{OperationError, OperationResult};
+use segment::data_types::vectors::{
+    DenseVector, NamedVectorStruct, VectorElementType, VectorRef, DEFAULT_VECTOR_NAME,
+};
+use segment::spaces::metric::Metric;
+use segment::spaces::simple::{CosineMetric, DotProductMetric, EuclidMetric, ManhattanMetric};
+use segment::types::{Distance, Order, ScoredPoint, WithPayloadInterface, WithVector};
+
+use super::Collection;
+use crate::operations::consistency_params::ReadConsistency;
+use crate::operations::point_ops::{PointInsertOperationsInternal, PointOperations};
+use crate::operations::query_enum::QueryEnum;
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::{
+    CollectionResult, CoreSearchRequestBatch, PointRequestInternal, ProjectionConfig,
+    ProjectionMethod,
+};
+use crate::operations::vector_ops::VectorOperations;
+use crate::operations::CollectionUpdateOperations;
+
+/// Number of candidates per requested result, which are rescored with original vectors
+const RESCORE_OVERSAMPLING: usize = 4;
+
+/// Projection of a vector, materialized from its config
+pub(super) struct Projection {
+    kind: ProjectionKind,
+    input_size: usize,
+    original_vector: Option<String>,
+}
+
+enum ProjectionKind {
+    /// First `size` dimensions
+    Prefix { size: usize },
+    Matrix {
+        /// One row of `input_size` values per dimension of the projected vectors
+        matrix: Vec<DenseVector>,
+        offset: Option<DenseVector>,
+    },
+}
+
+impl Projection {
+    fn new(config: &ProjectionConfig, size: usize) -> Self {
+        let input_size = config.input_size.get() as usize;
+        let kind = match &config.method {
+            ProjectionMethod::Prefix => ProjectionKind::Prefix { size },
+            ProjectionMethod::Random { seed } => ProjectionKind::Matrix {
+                matrix: random_matrix(*seed, size, input_size),
+                offset: None,
+            },
+            ProjectionMethod::Matrix { matrix, offset } => ProjectionKind::Matrix {
+                matrix: matrix.clone(),
+                offset: offset.clone(),
+            },
+        };
+        Self {
+            kind,
+            input_size,
+            original_vector: config.original_vector.clone(),
+        }
+    }
+
+    fn project(&self, vector: &[VectorElementType]) -> OperationResult<DenseVector> {
+        if vector.len() != self.input_size {
+            return Err(OperationError::WrongVectorDimension {
+                expected_dim: self.input_size,
+                received_dim: vector.len(),
+            });
+        }
+
+        let (matrix, offset) = match &self.kind {
+            ProjectionKind::Prefix { size } => return Ok(vector[..*size].to_vec()),
+            ProjectionKind::Matrix { matrix, offset } => (matrix, offset),
+        };
+        let centered;
+        let vector = match offset {
+            Some(offset) => {
+                centered = vector
+                    .iter()
+                    .zip(offset)
+                    .map(|(value, offset)| value - offset)
+                    .collect_vec();
+                &centered
+            }
+            None => vector,
+        };
+        Ok(matrix
+            .iter()
+            .map(|row| row.iter().zip(vector).map(|(a, b)| a * b).sum())
+            .collect())
+    }
+}
+
+/// Sparse random projection: values are `±sqrt(3 / size)` with probability 1/6 each and zero
+/// otherwise, which preserves distances between vectors approximately
+fn random_matrix(seed: u64, size: usize, input_size: usize) -> Vec<DenseVector> {
+    let scale = (3.0 / size as VectorElementType).sqrt();
+    let mut rng = StdRng::seed_from_u64(seed);
+    (0..size)
+        .map(|_| {
+            (0..input_size)
+                .map(|_| match rng.gen_range(0..6) {
+                    0 => scale,
+                    1 => -scale,
+                    _ => 0.0,
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Projections of vectors of the collection, materialized on first use.
+///
+/// Projections can't be changed after the collection is created, so they are never invalidated.
+#[derive(Default)]
+pub(super) struct ProjectionCache {
+    projections: Mutex<HashMap<String, Arc<Projection>>>,
+}
+
+/// Search request, which results are rescored with original vectors
+pub(super) struct Rescore {
+    original_vector: String,
+    distance: Distance,
+    /// Query before the projection
+    query: DenseVector,
+    limit: usize,
+    offset: usize,
+    score_threshold: Option<ScoreType>,
+    with_payload: Option<WithPayloadInterface>,
+    with_vector: Option<WithVector>,
+}
+
+impl Collection {
+    /// Projections of vectors, which have one, by vector name
+    async fn projections(&self) -> HashMap<String, Arc<Projection>> {
+        let config = self.collection_config.read().await;
+        let mut cache = self.projection_cache.projections.lock();
+        config
+            .params
+            .vectors
+            .params_iter()
+            .filter_map(|(name, params)| {
+                let config = params.projection.as_ref()?;
+                let projection = cache
+                    .entry(name.to_string())
+                    .or_insert_with(|| {
+                        Arc::new(Projection::new(config, params.size.get() as usize))
+                    })
+                    .clone();
+                Some((name.to_string(), projection))
+            })
+            .collect()
+    }
+
+    /// Project vectors of inserted and updated points, keeping original vectors if configured
+    pub(super) async fn project_operation(
+        &self,
+        operation: &mut CollectionUpdateOperations,
+    ) -> CollectionResult<()> {
+        let is_affected = matches!(
+            operation,
+            CollectionUpdateOperations::PointOperation(
+                PointOperations::UpsertPoints(_) | PointOperations::UpsertPointsConditional(_)
+            ) | CollectionUpdateOperations::VectorOperation(VectorOperations::UpdateVectors(_))
+        );
+        if !is_affected {
+            return Ok(());
+        }
+        let projections = self.projections().await;
+        if projections.is_empty() {
+            return Ok(());
+        }
+
+        match operation {
+            CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+                points_op,
+            )) => project_points(&projections, points_op)?,
+            CollectionUpdateOperations::PointOperation(
+                PointOperations::UpsertPointsConditional(operation),
+            ) => project_points(&projections, &mut operation.points_op)?,
+            CollectionUpdateOperations::VectorOperation(VectorOperations::UpdateVectors(
+                update_op,
+            )) => {
+                for point in &mut update_op.points {
+                    project_vector_struct(&projections, &mut point.vector)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Project query vectors of the searches.
+    ///
+    /// Searches of vectors with originals are oversampled, to be rescored after the search.
+    /// Returns `None` if none of the searches uses a projected vector.
+    pub(super) async fn project_search_batch(
+        &self,
+        request: &CoreSearchRequestBatch,
+    ) -> CollectionResult<Option<(CoreSearchRequestBatch, Vec<Option<Rescore>>)>> {
+        let projections = self.projections().await;
+        let is_affected = request
+            .searches
+            .iter()
+            .any(|search| projections.contains_key(search.query.get_vector_name()));
+        if !is_affected {
+            return Ok(None);
+        }
+
+        let collection_params = self.collection_config.read().await.params.clone();
+        let mut searches = Vec::with_capacity(request.searches.len());
+        let mut rescores = Vec::with_capacity(request.searches.len());
+        for search in &request.searches {
+            let mut search = search.clone();
+            let Some(projection) = projections.get(search.query.get_vector_name()) else {
+                searches.push(search);
+                rescores.push(None);
+                continue;
+            };
+
+            let rescore = match (&projection.original_vector, &search.query) {
+                (Some(original_vector), QueryEnum::Nearest(vector)) => {
+                    let query = match vector {
+                        NamedVectorStruct::Default(vector) => Some(vector.clone()),
+                        NamedVectorStruct::Dense(vector) => Some(vector.vector.clone()),
+                        NamedVectorStruct::DenseDouble(vector) => Some(
+                            vector
+                                .vector
+                                .iter()
+                                .map(|&x| x as VectorElementType)
+                                .collect(),
+                        ),
+                        NamedVectorStruct::Sparse(_) | NamedVectorStruct::MultiDense(_) => None,
+                    };
+                    let distance = collection_params.get_search_distance(
+                        search.query.get_vector_name(),
+                        search.params.as_ref(),
+                    )?;
+                    query.map(|query| Rescore {
+                        original_vector: original_vector.clone(),
+                        distance,
+                        query,
+                        limit: search.limit,
+                        offset: search.offset,
+                        score_threshold: search.score_threshold,
+                        with_payload: search.with_payload.clone(),
+                        with_vector: search.with_vector.clone(),
+                    })
+                }
+                _ => None,
+            };
+
+            search.query = search
+                .query
+                .transform_dense(|vector| projection.project(&vector))?;
+            if rescore.is_some() {
+                // Payloads and vectors are fetched only for the results left after rescoring
+                search.limit = (search.limit + search.offset) * RESCORE_OVERSAMPLING;
+                search.offset = 0;
+                search.score_threshold = None;
+                search.with_payload = None;
+                search.with_vector = None;
+            }
+            searches.push(search);
+            rescores.push(rescore);
+        }
+        Ok(Some((CoreSearchRequestBatch { searches }, rescores)))
+    }
+
+    /// Rescore results of oversampled searches with original vectors
+    pub(super) async fn rescore_with_originals(
+        &self,
+        results: Vec<Vec<ScoredPoint>>,
+        rescores: Vec<Option<Rescore>>,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: &ShardSelectorInternal,
+    ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        let mut rescored_results = Vec::with_capacity(results.len());
+        for (result, rescore) in results.into_iter().zip(rescores) {
+            let Some(rescore) = rescore else {
+                rescored_results.push(result);
+                continue;
+            };
+
+            let retrieve_request = PointRequestInternal {
+                ids: result.iter().map(|point| point.id).collect(),
+                with_payload: Some(WithPayloadInterface::Bool(false)),
+                with_vector: WithVector::Selector(vec![rescore.original_vector.clone()]),
+            };
+            let originals: HashMap<_, _> = self
+                .retrieve(retrieve_request, read_consistency, shard_selection)
+                .await?
+                .into_iter()
+                .filter_map(|record| Some((record.id, record.vector?)))
+                .collect();
+
+            let query = preprocess(rescore.distance, rescore.query);
+            let mut rescored = result
+                .into_iter()
+                .filter_map(|mut point| {
+                    // Points might get deleted between search and retrieve
+                    let original = originals.get(&point.id)?.get(&rescore.original_vector)?;
+                    let original = match original {
+                        VectorRef::Dense(original) => Cow::Borrowed(original),
+                        VectorRef::DenseDouble(original) => {
+                            Cow::Owned(original.iter().map(|&x| x as VectorElementType).collect())
+                        }
+                        VectorRef::Sparse(_) | VectorRef::MultiDense(_) => return None,
+                    };
+                    point.score = score(rescore.distance, &query, &original);
+                    Some(point)
+                })
+                .filter(|point| {
+                    rescore.score_threshold.map_or(true, |threshold| {
+                        rescore.distance.check_threshold(point.score, threshold)
+                    })
+                })
+                .collect_vec();
+            match rescore.distance.distance_order() {
+                Order::LargeBetter => rescored.sort_unstable_by(|a, b| b.cmp(a)),
+                Order::SmallBetter => rescored.sort_unstable(),
+            }
+            let rescored = rescored
+                .into_iter()
+                .skip(rescore.offset)
+                .take(rescore.limit)
+                .collect_vec();
+
+            let with_payload = rescore
+                .with_payload
+                .filter(|with_payload| !matches!(with_payload, WithPayloadInterface::Bool(false)));
+            let with_vector = rescore.with_vector.filter(WithVector::is_enabled);
+            let rescored = if with_payload.is_some() || with_vector.is_some() {
+                self.fill_search_result_with_payload(
+                    rescored,
+                    Some(with_payload.unwrap_or(WithPayloadInterface::Bool(false))),
+                    with_vector.unwrap_or_default(),
+                    read_consistency,
+                    shard_selection,
+                )
+                .await?
+            } else {
+                rescored
+            };
+            rescored_results.push(rescored);
+        }
+        Ok(rescored_results)
+    }
+}
+
+fn project_points(
+    projections: &HashMap<String, Arc<Projection>>,
+    points_op: &mut PointInsertOperationsInternal,
+) -> OperationResult<()> {
+    match points_op {
+        PointInsertOperationsInternal::PointsBatch(batch) => {
+            if let BatchVectorStruct::Single(vectors) = &mut batch.vectors {
+                if projections.contains_key(DEFAULT_VECTOR_NAME) {
+                    let vectors = std::mem::take(vectors)
+                        .into_iter()
+                        .map(Vector::Dense)
+                        .collect();
+                    batch.vectors = BatchVectorStruct::Multi(HashMap::from([(
+                        DEFAULT_VECTOR_NAME.to_string(),
+                        vectors,
+                    )]));
+                }
+            }
+            let BatchVectorStruct::Multi(named_vectors) = &mut batch.vectors else {
+                return Ok(());
+            };
+            for (name, projection) in projections {
+                let Some(vectors) = named_vectors.get_mut(name) else {
+                    continue;
+                };
+                let originals = projection
+                    .original_vector
+                    .is_some()
+                    .then(|| vectors.clone());
+                for vector in vectors.iter_mut() {
+                    if let Vector::Dense(dense) = vector {
+                        *dense = projection.project(dense)?;
+                    }
+                }
+                if let (Some(original_vector), Some(originals)) =
+                    (&projection.original_vector, originals)
+                {
+                    named_vectors.insert(original_vector.clone(), originals);
+                }
+            }
+        }
+        PointInsertOperationsInternal::PointsList(points) => {
+            for point in points {
+                project_vector_struct(projections, &mut point.vector)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn project_vector_struct(
+    projections: &HashMap<String, Arc<Projection>>,
+    vector: &mut VectorStruct,
+) -> OperationResult<()> {
+    if let VectorStruct::Single(dense) = vector {
+        if projections.contains_key(DEFAULT_VECTOR_NAME) {
+            *vector = VectorStruct::Multi(HashMap::from([(
+                DEFAULT_VECTOR_NAME.to_string(),
+                Vector::Dense(std::mem::take(dense)),
+            )]));
+        }
+    }
+    let VectorStruct::Multi(named_vectors) = vector else {
+        return Ok(());
+    };
+    for (name, projection) in projections {
+        let Some(Vector::Dense(dense)) = named_vectors.get_mut(name) else {
+            continue;
+        };
+        let projected = projection.project(dense)?;
+        let original = std::mem::replace(dense, projected);
+        if let Some(original_vector) = &projection.original_vector {
+            named_vectors.insert(original_vector.clone(), Vector::Dense(original));
+        }
+    }
+    Ok(())
+}
+
+fn preprocess(distance: Distance, query: DenseVector) -> DenseVector {
+    match distance {
+        Distance::Cosine => <CosineMetric as Metric<VectorElementType>>::preprocess(query),
+        Distance::Euclid | Distance::Dot | Distance::Manhattan => query,
+    }
+}
+
+/// Score of a stored vector, as it is reported in search results
+fn score(
+    distance: Distance,
+    query: &[VectorElementType],
+    stored: &[VectorElementType],
+) -> ScoreType {
+    let similarity = match distance {
+        Distance::Cosine => <CosineMetric as Metric<VectorElementType>>::similarity(query, stored),
+        Distance::Euclid => <EuclidMetric as Metric<VectorElementType>>::similarity(query, stored),
+        Distance::Dot => <DotProductMetric as Metric<VectorElementType>>::similarity(query, stored),
+        Distance::Manhattan => {
+            <ManhattanMetric as Metric<VectorElementType>>::similarity(query, stored)
+        }
+    };
+    distance.postprocess_score(similarity)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU64;
+
+    use super::*;
+
+    fn norm(vector: &[VectorElementType]) -> VectorElementType {
+        vector.iter().map(|value| value * value).sum::<f32>().sqrt()
+    }
+
+    #[test]
+    fn test_random_projection() {
+        let config = ProjectionConfig {
+            input_size: NonZeroU64::new(512).unwrap(),
+            method: ProjectionMethod::Random { seed: 42 },
+            original_vector: None,
+        };
+        let projection = Projection::new(&config, 128);
+
+        let mut rng = StdRng::seed_from_u64(0);
+        let vector: DenseVector = (0..512).map(|_| rng.gen_range(-1.0..1.0)).collect();
+        let projected = projection.project(&vector).unwrap();
+        assert_eq!(projected.len(), 128);
+        // Norm is preserved approximately
+        let ratio = norm(&projected) / norm(&vector);
+        assert!((0.7..1.3).contains(&ratio), "ratio {ratio}");
+
+        // Same seed gives the same projection
+        let same = Projection::new(&config, 128);
+        assert_eq!(same.project(&vector).unwrap(), projected);
+
+        assert!(projection.project(&vector[..128]).is_err());
+    }
+
+    #[test]
+    fn test_matrix_projection() {
+        let config = ProjectionConfig {
+            input_size: NonZeroU64::new(3).unwrap(),
+            method: ProjectionMethod::Matrix {
+                matrix: vec![vec![1.0, 0.0, 0.0], vec![0.0, 0.5, 0.5]],
+                offset: Some(vec![1.0, 1.0, 1.0]),
+            },
+            original_vector: Some("original".to_string()),
+        };
+        let projections = HashMap::from([(
+            DEFAULT_VECTOR_NAME.to_string(),
+            Arc::new(Projection::new(&config, 2)),
+        )]);
+
+        let mut vector = VectorStruct::Single(vec![2.0, 3.0, 5.0]);
+        project_vector_struct(&projections, &mut vector).unwrap();
+        assert_eq!(
+            vector,
+            VectorStruct::Multi(HashMap::from([
+                (
+                    DEFAULT_VECTOR_NAME.to_string(),
+                    Vector::Dense(vec![1.0, 3.0])
+                ),
+                ("original".to_string(), Vector::Dense(vec![2.0, 3.0, 5.0])),
+            ])),
+        );
+    }
+
+    #[test]
+    fn test_prefix_projection() {
+        let config = ProjectionConfig {
+            input_size: NonZeroU64::new(4).unwrap(),
+            method: ProjectionMethod::Prefix,
+            original_vector: Some("full".to_string()),
+        };
+        let projections =
+            HashMap::from([("prefix".to_string(), Arc::new(Projection::new(&config, 2)))]);
+
+        let mut vector = VectorStruct::Multi(HashMap::from([(
+            "prefix".to_string(),
+            Vector::Dense(vec![1.0, 2.0, 3.0, 4.0]),
+        )]));
+        project_vector_struct(&projections, &mut vector).unwrap();
+        assert_eq!(
+            vector,
+            VectorStruct::Multi(HashMap::from([
+                ("prefix".to_string(), Vector::Dense(vec![1.0, 2.0])),
+                ("full".to_string(), Vector::Dense(vec![1.0, 2.0, 3.0, 4.0])),
+            ])),
+        );
+    }
+
+    #[test]
+    fn test_score() {
+        let query = preprocess(Distance::Cosine, vec![3.0, 4.0]);
+        assert!((score(Distance::Cosine, &query, &[0.6, 0.8]) - 1.0).abs() < 1e-6);
+        assert_eq!(score(Distance::Dot, &[1.0, 2.0], &[3.0, 4.0]), 11.0);
+        assert_eq!(score(Distance::Euclid, &[0.0, 0.0], &[3.0, 4.0]), 5.0);
+        assert_eq!(score(Distance::Manhattan, &[0.0, 0.0], &[3.0, 4.0]), 7.0);
+    }
+}
@@ -0,0 +1,404 @@
+//! Quotas of tenants of a shared collection, see [`QuotaConfig`].
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use segment::types::{ShardKey, WithPayloadInterface, WithVector};
+
+use super::Collection;
+use crate::config::{QuotaConfig, ShardingMethod};
+use crate::operations::point_ops::{payload_size_bytes, PointOperations};
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::{
+    CollectionError, CollectionQuotaUsage, CollectionResult, CountRequestInternal,
+    ScrollRequestInternal, TenantUsage,
+};
+use crate::operations::CollectionUpdateOperations;
+
+/// Page size of the scroll, which measures size of payloads
+const PAYLOAD_SCROLL_LIMIT: usize = 1000;
+
+/// Points and payloads inserted by an update operation
+#[derive(Debug, Clone, Copy, Default)]
+pub(super) struct QuotaWrite {
+    points_count: usize,
+    payload_bytes: usize,
+}
+
+impl QuotaWrite {
+    /// Returns `None` if the operation doesn't insert points
+    pub(super) fn from_operation(operation: &CollectionUpdateOperations) -> Option<Self> {
+        let points_op = match operation {
+            CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+                points_op,
+            )) => points_op,
+            CollectionUpdateOperations::PointOperation(
+                PointOperations::UpsertPointsConditional(operation),
+            ) => &operation.points_op,
+            _ => return None,
+        };
+        Some(Self {
+            points_count: points_op.points_count(),
+            payload_bytes: points_op.payload_bytes(),
+        })
+    }
+}
+
+struct TenantState {
+    /// Last measured usage, with writes accepted since
+    usage: TenantUsage,
+    /// Writes accepted since the start of the ongoing measurement, which might miss them
+    unmeasured: QuotaWrite,
+    /// Start of the current one second window of the write rate
+    window_start: Instant,
+    /// Number of points upserted within the current window
+    window_points: usize,
+}
+
+impl TenantState {
+    fn new(tenant: &Option<ShardKey>, now: Instant) -> Self {
+        Self {
+            usage: TenantUsage {
+                shard_key: tenant.clone(),
+                points_count: 0,
+                payload_bytes: 0,
+            },
+            unmeasured: QuotaWrite::default(),
+            window_start: now,
+            window_points: 0,
+        }
+    }
+}
+
+/// Usage of tenants, which is accounted with every accepted write.
+///
+/// Writes are never blocked on measurement, usage is measured in background, see
+/// [`Collection::measure_quota_usage`]. Until a tenant is measured for the first time, e.g. right
+/// after restart, only writes accepted since are accounted.
+///
+/// Upserts of existing points are accounted as new points until the next measurement,
+/// so that the limits are never exceeded by measured tenants.
+#[derive(Default)]
+pub(super) struct QuotaTracker {
+    tenants: Mutex<HashMap<Option<ShardKey>, TenantState>>,
+    /// Held during measurement, so that writes since its start are accounted on top of it
+    measurement: tokio::sync::Mutex<()>,
+}
+
+impl QuotaTracker {
+    /// Writes accepted from now on are accounted on top of the measurement, which is starting
+    fn start_measurement(&self, tenant: &Option<ShardKey>, now: Instant) {
+        let mut tenants = self.tenants.lock();
+        let state = tenants
+            .entry(tenant.clone())
+            .or_insert_with(|| TenantState::new(tenant, now));
+        state.unmeasured = QuotaWrite::default();
+    }
+
+    /// Replace accounted usage with the measured one, keeping writes accepted during measurement
+    fn finish_measurement(&self, measured: TenantUsage, now: Instant) -> TenantUsage {
+        let mut tenants = self.tenants.lock();
+        let state = tenants
+            .entry(measured.shard_key.clone())
+            .or_insert_with(|| TenantState::new(&measured.shard_key, now));
+        state.usage = TenantUsage {
+            points_count: measured.points_count + state.unmeasured.points_count,
+            payload_bytes: measured.payload_bytes + state.unmeasured.payload_bytes,
+            ..measured
+        };
+        state.unmeasured = QuotaWrite::default();
+        state.usage.clone()
+    }
+
+    /// Account the write, if it doesn't exceed any of the limits of the tenant
+    fn try_write(
+        &self,
+        tenant: &Option<ShardKey>,
+        quota: &QuotaConfig,
+        write: QuotaWrite,
+        now: Instant,
+    ) -> CollectionResult<()> {
+        let mut tenants = self.tenants.lock();
+        let state = tenants
+            .entry(tenant.clone())
+            .or_insert_with(|| TenantState::new(tenant, now));
+
+        let tenant_name = match tenant {
+            Some(shard_key) => format!("Tenant {shard_key}"),
+            None => "Collection".to_string(),
+        };
+
+        if let Some(max_points) = quota.max_points {
+            let points_count = state.usage.points_count + write.points_count;
+            if points_count > max_points {
+                return Err(CollectionError::quota_exceeded(format!(
+                    "{tenant_name} would have {points_count} points, limit is {max_points}",
+                )));
+            }
+        }
+
+        if let Some(max_payload_bytes) = quota.max_payload_bytes {
+            let payload_bytes = state.usage.payload_bytes + write.payload_bytes;
+            if payload_bytes > max_payload_bytes {
+                return Err(CollectionError::quota_exceeded(format!(
+                    "{tenant_name} would have {payload_bytes} bytes of payload, limit is {max_payload_bytes}",
+                )));
+            }
+        }
+
+        if now.saturating_duration_since(state.window_start) >= Duration::from_secs(1) {
+            state.window_start = now;
+            state.window_points = 0;
+        }
+
+        if let Some(max_writes_per_second) = quota.max_writes_per_second {
+            // A single upsert larger than the limit is allowed at the start of the window
+            let window_points = state.window_points + write.points_count;
+            if state.window_points > 0 && window_points > max_writes_per_second {
+                return Err(CollectionError::quota_exceeded(format!(
+                    "{tenant_name} upserts more than {max_writes_per_second} points per second",
+                )));
+            }
+        }
+
+        state.usage.points_count += write.points_count;
+        state.usage.payload_bytes += write.payload_bytes;
+        state.unmeasured.points_count += write.points_count;
+        state.unmeasured.payload_bytes += write.payload_bytes;
+        state.window_points += write.points_count;
+        Ok(())
+    }
+}
+
+impl Collection {
+    /// Reject the write, if it would exceed the quota of the tenant
+    ///
+    /// Checked against accounted usage, the write never waits for measurement.
+    pub(super) async fn check_quota(
+        &self,
+        shard_key: Option<&ShardKey>,
+        write: QuotaWrite,
+    ) -> CollectionResult<()> {
+        let Some(quota) = self.collection_config.read().await.quota_config.clone() else {
+            return Ok(());
+        };
+
+        self.quota_tracker
+            .try_write(&shard_key.cloned(), &quota, write, Instant::now())
+    }
+
+    /// Measure usage of all tenants of the collection, if quotas are configured
+    ///
+    /// Called periodically in background, to correct accounted usage for overwritten and deleted
+    /// points.
+    pub async fn measure_quota_usage(&self) -> CollectionResult<()> {
+        let Some(quota) = self.collection_config.read().await.quota_config.clone() else {
+            return Ok(());
+        };
+
+        self.measure_tenants_usage(quota.max_payload_bytes.is_some())
+            .await?;
+        Ok(())
+    }
+
+    /// Measure usage of all tenants of the collection
+    pub async fn quota_usage(&self) -> CollectionResult<CollectionQuotaUsage> {
+        let quota = self.collection_config.read().await.quota_config.clone();
+        let tenants = self.measure_tenants_usage(true).await?;
+        Ok(CollectionQuotaUsage { quota, tenants })
+    }
+
+    /// Measure usage of all tenants and update accounted usage with it
+    async fn measure_tenants_usage(
+        &self,
+        with_payload: bool,
+    ) -> CollectionResult<Vec<TenantUsage>> {
+        let _measurement = self.quota_tracker.measurement.lock().await;
+
+        let sharding_method = self
+            .collection_config
+            .read()
+            .await
+            .params
+            .sharding_method
+            .unwrap_or_default();
+
+        let tenants = match sharding_method {
+            ShardingMethod::Auto => vec![None],
+            ShardingMethod::Custom => {
+                let mut shard_keys: Vec<_> = self
+                    .shards_holder
+                    .read()
+                    .await
+                    .get_shard_key_to_ids_mapping()
+                    .into_keys()
+                    .collect();
+                shard_keys.sort_by_cached_key(ToString::to_string);
+                shard_keys.into_iter().map(Some).collect()
+            }
+        };
+
+        let mut usages = Vec::with_capacity(tenants.len());
+        for tenant in tenants {
+            self.quota_tracker
+                .start_measurement(&tenant, Instant::now());
+            let measured = self.measure_tenant_usage(tenant, with_payload).await?;
+            let usage = self
+                .quota_tracker
+                .finish_measurement(measured, Instant::now());
+            usages.push(usage);
+        }
+
+        Ok(usages)
+    }
+
+    /// Count points of the tenant, and size of their payloads if `with_payload`.
+    ///
+    /// Size of payloads is measured by reading all payloads of the tenant.
+    async fn measure_tenant_usage(
+        &self,
+        shard_key: Option<ShardKey>,
+        with_payload: bool,
+    ) -> CollectionResult<TenantUsage> {
+        let shard_selection = match &shard_key {
+            Some(shard_key) => ShardSelectorInternal::ShardKey(shard_key.clone()),
+            None => ShardSelectorInternal::All,
+        };
+
+        let count_request = CountRequestInternal {
+            filter: None,
+            exact: true,
+        };
+        let points_count = self
+            .count(count_request, None, &shard_selection)
+            .await?
+            .count;
+
+        let mut payload_bytes = 0;
+        if with_payload {
+            let mut offset = None;
+            loop {
+                let scroll_request = ScrollRequestInternal {
+                    offset,
+                    limit: Some(PAYLOAD_SCROLL_LIMIT),
+                    filter: None,
+                    with_payload: Some(WithPayloadInterface::Bool(true)),
+                    with_vector: WithVector::Bool(false),
+                    order_by: None,
+                };
+                let page = self
+                    .scroll_by(scroll_request, None, &shard_selection)
+                    .await?;
+                payload_bytes += page
+                    .points
+                    .iter()
+                    .filter_map(|point| point.payload.as_ref())
+                    .map(payload_size_bytes)
+                    .sum::<usize>();
+
+                offset = page.next_page_offset;
+                if offset.is_none() {
+                    break;
+                }
+            }
+        }
+
+        Ok(TenantUsage {
+            shard_key,
+            points_count,
+            payload_bytes,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quota_tracker() {
+        let quota = QuotaConfig {
+            max_points: Some(100),
+            max_payload_bytes: None,
+            max_writes_per_second: Some(20),
+        };
+        let tenant = Some(ShardKey::from("tenant"));
+        let tracker = QuotaTracker::default();
+        let start = Instant::now();
+
+        tracker.start_measurement(&tenant, start);
+        tracker.finish_measurement(
+            TenantUsage {
+                shard_key: tenant.clone(),
+                points_count: 70,
+                payload_bytes: 0,
+            },
+            start,
+        );
+
+        let write = |points_count| QuotaWrite {
+            points_count,
+            payload_bytes: 0,
+        };
+
+        // Write rate
+        tracker
+            .try_write(&tenant, &quota, write(15), start)
+            .unwrap();
+        assert!(tracker
+            .try_write(&tenant, &quota, write(10), start)
+            .is_err());
+        let later = start + Duration::from_secs(1);
+        tracker
+            .try_write(&tenant, &quota, write(10), later)
+            .unwrap();
+
+        // Number of points, accepted writes are accounted
+        let later = later + Duration::from_secs(1);
+        assert!(tracker
+            .try_write(&tenant, &quota, write(10), later)
+            .is_err());
+        tracker.try_write(&tenant, &quota, write(5), later).unwrap();
+
+        // Measurement replaces accounted usage, e.g. some points were overwritten
+        let usage = |points_count| TenantUsage {
+            shard_key: tenant.clone(),
+            points_count,
+            payload_bytes: 0,
+        };
+        tracker.start_measurement(&tenant, later);
+        let usage_after = tracker.finish_measurement(usage(80), later);
+        assert_eq!(usage_after.points_count, 80);
+
+        // Writes during measurement are accounted on top of it
+        let later = later + Duration::from_secs(1);
+        tracker.start_measurement(&tenant, later);
+        tracker.try_write(&tenant, &quota, write(3), later).unwrap();
+        let usage_after = tracker.finish_measurement(usage(85), later);
+        assert_eq!(usage_after.points_count, 88);
+
+        tracker.try_write(&tenant, &quota, write(7), later).unwrap();
+        assert!(tracker.try_write(&tenant, &quota, write(6), later).is_err());
+    }
+
+    #[test]
+    fn test_quota_tracker_unmeasured_tenant() {
+        let quota = QuotaConfig {
+            max_points: Some(10),
+            max_payload_bytes: None,
+            max_writes_per_second: None,
+        };
+        let tenant = Some(ShardKey::from("tenant"));
+        let tracker = QuotaTracker::default();
+        let now = Instant::now();
+
+        // Accepted writes are accounted before the first measurement
+        let write = QuotaWrite {
+            points_count: 10,
+            payload_bytes: 0,
+        };
+        tracker.try_write(&tenant, &quota, write, now).unwrap();
+        assert!(tracker.try_write(&tenant, &quota, write, now).is_err());
+    }
+}
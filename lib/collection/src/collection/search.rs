@@ -12,6 +12,8 @@ use segment::types::{
 use tokio::time::Instant;
 
 use super::Collection;
+use crate::common::latency_histograms::OperationType;
+use crate::common::slow_log::{OperationDetails, OperationTimer, ShardTimer};
 use crate::events::SlowQueryEvent;
 use crate::operations::consistency_params::ReadConsistency;
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
@@ -28,13 +30,23 @@ impl Collection {
         if request.limit == 0 {
             return Ok(vec![]);
         }
+        let timer = OperationTimer::start(&self.requests_telemetry.searches, OperationType::Search);
         // search is a special case of search_batch with a single batch
-        let request_batch = CoreSearchRequestBatch {
+        let request_batch = Arc::new(CoreSearchRequestBatch {
             searches: vec![request],
-        };
+        });
         let results = self
-            .do_core_search_batch(request_batch, read_consistency, shard_selection, timeout)
+            .do_core_search_batch(
+                Arc::clone(&request_batch),
+                read_consistency,
+                shard_selection,
+                timeout,
+                timer.shards(),
+            )
             .await?;
+        timer.finish(&self.id, "search", || {
+            OperationDetails::search(&request_batch)
+        });
         Ok(results.into_iter().next().unwrap())
     }
 
@@ -79,6 +91,8 @@ impl Collection {
         let is_required_transfer_large_enough =
             require_transfers > used_transfers * PAYLOAD_TRANSFERS_FACTOR_THRESHOLD;
 
+        let timer = OperationTimer::start(&self.requests_telemetry.searches, OperationType::Search);
+
         if metadata_required && is_required_transfer_large_enough {
             // If there is a significant offset, we need to retrieve the whole result
             // set without payload first and then retrieve the payload.
@@ -91,15 +105,16 @@ impl Collection {
                 without_payload_request.with_vector = None;
                 without_payload_requests.push(without_payload_request);
             }
-            let without_payload_batch = CoreSearchRequestBatch {
+            let without_payload_batch = Arc::new(CoreSearchRequestBatch {
                 searches: without_payload_requests,
-            };
+            });
             let without_payload_results = self
                 .do_core_search_batch(
                     without_payload_batch,
                     read_consistency,
                     &shard_selection,
                     timeout,
+                    timer.shards(),
                 )
                 .await?;
             let filled_results = without_payload_results
@@ -114,33 +129,53 @@ impl Collection {
                         &shard_selection,
                     )
                 });
-            future::try_join_all(filled_results).await
+            let result = future::try_join_all(filled_results).await?;
+            timer.finish(&self.id, "search", || OperationDetails::search(&request));
+            Ok(result)
         } else {
+            let request = Arc::new(request);
             let result = self
-                .do_core_search_batch(request, read_consistency, &shard_selection, timeout)
+                .do_core_search_batch(
+                    Arc::clone(&request),
+                    read_consistency,
+                    &shard_selection,
+                    timeout,
+                    timer.shards(),
+                )
                 .await?;
+            timer.finish(&self.id, "search", || OperationDetails::search(&request));
             Ok(result)
         }
     }
 
     async fn do_core_search_batch(
         &self,
-        request: CoreSearchRequestBatch,
+        request: Arc<CoreSearchRequestBatch>,
         read_consistency: Option<ReadConsistency>,
         shard_selection: &ShardSelectorInternal,
         timeout: Option<Duration>,
+        shard_timer: &ShardTimer,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
-        let request = Arc::new(request);
-
         let instant = Instant::now();
 
+        // Query vectors are projected once, by the peer which received the request from a client
+        let projected = if shard_selection.is_shard_id() {
+            None
+        } else {
+            self.project_search_batch(&request).await?
+        };
+        let (request, rescores) = match projected {
+            Some((request, rescores)) => (Arc::new(request), Some(rescores)),
+            None => (request, None),
+        };
+
         // query all shards concurrently
         let all_searches_res = {
             let shard_holder = self.shards_holder.read().await;
             let target_shards = shard_holder.select_shards(shard_selection)?;
             let all_searches = target_shards.iter().map(|(shard, shard_key)| {
                 let shard_key = shard_key.cloned();
-                shard
+                let search = shard
                     .core_search(
                         Arc::clone(&request),
                         read_consistency,
@@ -157,7 +192,8 @@ impl Collection {
                             }
                         }
                         Ok(records)
-                    })
+                    });
+                shard_timer.measure(shard.shard_id, search)
             });
             future::try_join_all(all_searches).await?
         };
@@ -174,7 +210,13 @@ impl Collection {
 
         self.post_process_if_slow_request(instant.elapsed(), filters_refs);
 
-        result
+        match rescores {
+            Some(rescores) => {
+                self.rescore_with_originals(result?, rescores, read_consistency, shard_selection)
+                    .await
+            }
+            None => result,
+        }
     }
 
     pub(crate) async fn fill_search_result_with_payload(
@@ -244,7 +286,7 @@ impl Collection {
         for (batch_index, request) in request.searches.iter().enumerate() {
             let order = if request.query.is_distance_scored() {
                 collection_params
-                    .get_distance(request.query.get_vector_name())?
+                    .get_search_distance(request.query.get_vector_name(), request.params.as_ref())?
                     .distance_order()
             } else {
                 // Score comes from special handling of the distances in a way that it doesn't
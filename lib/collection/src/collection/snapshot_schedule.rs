@@ -0,0 +1,72 @@
+use std::path::{Path, PathBuf};
+
+use super::Collection;
+use crate::operations::snapshot_ops::{SnapshotRetention, SnapshotSchedule};
+use crate::operations::types::CollectionResult;
+use crate::save_on_disk::SaveOnDisk;
+
+pub const SNAPSHOT_SCHEDULE_FILE: &str = "snapshot_schedule.json";
+
+impl Collection {
+    fn snapshot_schedule_file(collection_path: &Path) -> PathBuf {
+        collection_path.join(SNAPSHOT_SCHEDULE_FILE)
+    }
+
+    pub(crate) fn load_snapshot_schedule(
+        collection_path: &Path,
+    ) -> CollectionResult<SaveOnDisk<Option<SnapshotSchedule>>> {
+        let snapshot_schedule_file = Self::snapshot_schedule_file(collection_path);
+        let snapshot_schedule = SaveOnDisk::load_or_init(snapshot_schedule_file)?;
+        Ok(snapshot_schedule)
+    }
+
+    /// Schedule of automatic snapshots of this collection on this node, if any
+    pub fn snapshot_schedule(&self) -> Option<SnapshotSchedule> {
+        self.snapshot_schedule.read().clone()
+    }
+
+    /// Set or remove the schedule of automatic snapshots.
+    ///
+    /// Schedule is local to this node and is not included into collection snapshots.
+    pub fn set_snapshot_schedule(
+        &self,
+        schedule: Option<SnapshotSchedule>,
+    ) -> CollectionResult<()> {
+        if let Some(schedule) = &schedule {
+            schedule.cron_schedule()?;
+        }
+        self.snapshot_schedule
+            .write(|current| *current = schedule)?;
+        Ok(())
+    }
+
+    /// Remove snapshots of the collection, which are expired according to the retention policy
+    ///
+    /// Returns names of the removed snapshots.
+    pub async fn apply_snapshot_retention(
+        &self,
+        retention: &SnapshotRetention,
+    ) -> CollectionResult<Vec<String>> {
+        let snapshot_manager = self.get_snapshots_storage_manager()?;
+        let snapshots = snapshot_manager
+            .list_snapshots(&self.snapshots_path)
+            .await?;
+        let expired = retention.expired_snapshots(snapshots, chrono::Utc::now().naive_utc());
+
+        let mut removed = Vec::with_capacity(expired.len());
+        for snapshot in expired {
+            let snapshot_path = snapshot_manager
+                .get_snapshot_path(&self.snapshots_path, &snapshot.name)
+                .await?;
+            log::info!(
+                "Removing snapshot {:?} of collection {} due to retention policy",
+                snapshot_path,
+                self.name(),
+            );
+            snapshot_manager.delete_snapshot(&snapshot_path).await?;
+            removed.push(snapshot.name);
+        }
+
+        Ok(removed)
+    }
+}
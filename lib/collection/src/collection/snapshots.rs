@@ -3,10 +3,12 @@ use std::path::Path;
 
 use io::file_operations::read_json;
 use io::storage_version::StorageVersion as _;
+use tempfile::TempDir;
 use tokio::fs;
 
 use super::Collection;
 use crate::collection::CollectionVersion;
+use crate::common::archive_stream::{archive_dir, ArchiveStream};
 use crate::common::snapshots_manager::SnapshotStorageManager;
 use crate::common::validate_snapshot_archive::validate_open_snapshot_archive;
 use crate::config::{CollectionConfig, ShardingMethod};
@@ -34,25 +36,24 @@ impl Collection {
     ///
     /// The snapshot is created in three steps:
     /// 1. Create a temporary directory and create a snapshot of each shard in it.
-    /// 2. Archive the temporary directory into a single file.
-    /// 3. Move the archive to the final location.
+    /// 2. Archive the temporary directory into a stream, without materializing the archive.
+    ///    The archive is encrypted, if an encryption key is given or configured.
+    /// 3. Write the stream to the final location, computing the checksum on the fly.
     ///
     /// # Arguments
     ///
     /// * `global_temp_dir`: directory used to host snapshots while they are being created
     /// * `this_peer_id`: current peer id
+    /// * `encryption_key`: hex-encoded key to encrypt the snapshot with, overrides configured key
     ///
     /// returns: Result<SnapshotDescription, CollectionError>
     pub async fn create_snapshot(
         &self,
         global_temp_dir: &Path,
         this_peer_id: PeerId,
+        encryption_key: Option<&str>,
     ) -> CollectionResult<SnapshotDescription> {
-        let snapshot_name = format!(
-            "{}-{this_peer_id}-{}.snapshot",
-            self.name(),
-            chrono::Utc::now().format("%Y-%m-%d-%H-%M-%S"),
-        );
+        let snapshot_name = self.snapshot_name(this_peer_id);
 
         // Final location of snapshot
         let snapshot_path = self.snapshots_path.join(&snapshot_name);
@@ -62,6 +63,81 @@ impl Collection {
             snapshot_path
         );
 
+        let snapshot_temp_target_dir = self
+            .prepare_snapshot_dir(global_temp_dir, &snapshot_name)
+            .await?;
+
+        // Archive snapshot folder, temporary directory is kept until archiving is finished
+        log::debug!("Archiving snapshot {:?}", snapshot_temp_target_dir.path());
+        let archive = self.archive_snapshot_dir(snapshot_temp_target_dir, encryption_key)?;
+
+        let snapshot_manager = self.get_snapshots_storage_manager()?;
+        snapshot_manager
+            .store_stream(archive, snapshot_path.as_path())
+            .await
+            .map_err(|err| {
+                CollectionError::service_error(format!(
+                    "failed to store snapshot archive to {}: {err}",
+                    snapshot_path.display()
+                ))
+            })
+    }
+
+    /// Creates a snapshot of the collection and streams the archive as it is being built.
+    ///
+    /// Snapshot is not stored, returns the name of the snapshot along with the archive.
+    pub async fn stream_snapshot(
+        &self,
+        global_temp_dir: &Path,
+        this_peer_id: PeerId,
+        encryption_key: Option<&str>,
+    ) -> CollectionResult<(String, ArchiveStream)> {
+        let snapshot_name = self.snapshot_name(this_peer_id);
+        log::info!("Streaming collection snapshot {snapshot_name}");
+
+        let snapshot_temp_target_dir = self
+            .prepare_snapshot_dir(global_temp_dir, &snapshot_name)
+            .await?;
+
+        let archive = self.archive_snapshot_dir(snapshot_temp_target_dir, encryption_key)?;
+        Ok((snapshot_name, archive))
+    }
+
+    /// Archive prepared snapshot directory, encrypting it if a key is given or configured
+    fn archive_snapshot_dir(
+        &self,
+        snapshot_dir: TempDir,
+        encryption_key: Option<&str>,
+    ) -> CollectionResult<ArchiveStream> {
+        let cipher = self
+            .shared_storage_config
+            .snapshots_config
+            .encryption
+            .cipher(encryption_key)?;
+
+        let archive = archive_dir(snapshot_dir.path().to_path_buf(), snapshot_dir);
+        match cipher {
+            Some(cipher) => cipher.encrypt_stream(archive),
+            None => Ok(archive),
+        }
+    }
+
+    fn snapshot_name(&self, this_peer_id: PeerId) -> String {
+        format!(
+            "{}-{this_peer_id}-{}.snapshot",
+            self.name(),
+            chrono::Utc::now().format("%Y-%m-%d-%H-%M-%S"),
+        )
+    }
+
+    /// Create a temporary directory with the snapshot of each shard and collection metadata.
+    ///
+    /// Directory is deleted on drop.
+    async fn prepare_snapshot_dir(
+        &self,
+        global_temp_dir: &Path,
+        snapshot_name: &str,
+    ) -> CollectionResult<TempDir> {
         // Dedicated temporary directory for this snapshot (deleted on drop)
         let snapshot_temp_target_dir = tempfile::Builder::new()
             .prefix(&format!("{snapshot_name}-target-"))
@@ -139,43 +215,7 @@ impl Collection {
         self.payload_index_schema
             .save_to(&payload_index_schema_tmp_path)?;
 
-        // Dedicated temporary file for archiving this snapshot (deleted on drop)
-        let mut snapshot_temp_arc_file = tempfile::Builder::new()
-            .prefix(&format!("{snapshot_name}-arc-"))
-            .tempfile_in(global_temp_dir)
-            .map_err(|err| {
-                CollectionError::service_error(format!(
-                    "failed to create temporary snapshot directory {}/{snapshot_name}-arc-XXXX: \
-                     {err}",
-                    global_temp_dir.display(),
-                ))
-            })?;
-
-        // Archive snapshot folder into a single file
-        log::debug!("Archiving snapshot {snapshot_temp_target_dir_path:?}");
-        let archiving = tokio::task::spawn_blocking(move || -> CollectionResult<_> {
-            let mut builder = tar::Builder::new(snapshot_temp_arc_file.as_file_mut());
-            // archive recursively collection directory `snapshot_path_with_arc_extension` into `snapshot_path`
-            builder.append_dir_all(".", &snapshot_temp_target_dir_path)?;
-            builder.finish()?;
-            drop(builder);
-            // return ownership of the file
-            Ok(snapshot_temp_arc_file)
-        });
-        snapshot_temp_arc_file = archiving.await?.map_err(|err| {
-            CollectionError::service_error(format!("failed to create snapshot archive: {err}"))
-        })?;
-
-        let snapshot_manager = self.get_snapshots_storage_manager()?;
-        snapshot_manager
-            .store_file(snapshot_temp_arc_file.path(), snapshot_path.as_path())
-            .await
-            .map_err(|err| {
-                CollectionError::service_error(format!(
-                    "failed to store snapshot archive to {}: {err}",
-                    snapshot_temp_arc_file.path().display()
-                ))
-            })
+        Ok(snapshot_temp_target_dir)
     }
 
     /// Restore collection from snapshot
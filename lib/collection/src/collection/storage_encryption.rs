@@ -0,0 +1,62 @@
+//! Refusal of configurations and writes, which would store data of an encrypted collection in
+//! plain text, see [`StorageEncryptionConfig`](crate::common::storage_encryption::StorageEncryptionConfig).
+
+use segment::types::QuantizationConfig;
+
+use super::Collection;
+use crate::common::storage_encryption::check_supported;
+use crate::config::CollectionParams;
+use crate::operations::blob_ops::BlobOperations;
+use crate::operations::types::{CollectionError, CollectionResult};
+use crate::operations::{CollectionUpdateOperations, FieldIndexOperations};
+use crate::optimizers_builder::OptimizersConfig;
+
+impl Collection {
+    /// Whether storages of this collection are encrypted at rest
+    pub fn is_storage_encrypted(&self) -> bool {
+        self.shared_storage_config
+            .storage_encryption_config
+            .is_encrypted(&self.id)
+    }
+
+    /// Reject the configuration, if storages of the collection are encrypted, but the
+    /// configuration needs storages, which are not
+    pub(super) fn check_storage_encryption(
+        &self,
+        collection_params: &CollectionParams,
+        optimizer_config: &OptimizersConfig,
+        quantization_config: Option<&QuantizationConfig>,
+    ) -> CollectionResult<()> {
+        if !self.is_storage_encrypted() {
+            return Ok(());
+        }
+        check_supported(collection_params, optimizer_config, quantization_config)
+    }
+
+    /// Reject payload indexes and blobs in an encrypted collection, they are stored in plain text
+    pub(super) fn check_storage_encryption_operation(
+        &self,
+        operation: &CollectionUpdateOperations,
+    ) -> CollectionResult<()> {
+        if !self.is_storage_encrypted() {
+            return Ok(());
+        }
+        match operation {
+            CollectionUpdateOperations::FieldIndexOperation(operation) => match operation {
+                FieldIndexOperations::CreateIndex(_) => Err(CollectionError::bad_input(
+                    "Storage encryption is not supported with payload indexes",
+                )),
+                FieldIndexOperations::DeleteIndex(_) => Ok(()),
+            },
+            CollectionUpdateOperations::BlobOperation(operation) => match operation {
+                BlobOperations::SetBlob(_) => Err(CollectionError::bad_input(
+                    "Storage encryption is not supported with blobs",
+                )),
+                BlobOperations::DeleteBlob(_) => Ok(()),
+            },
+            CollectionUpdateOperations::PointOperation(_)
+            | CollectionUpdateOperations::VectorOperation(_)
+            | CollectionUpdateOperations::PayloadOperation(_) => Ok(()),
+        }
+    }
+}
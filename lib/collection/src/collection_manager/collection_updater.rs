@@ -62,6 +62,9 @@ impl CollectionUpdater {
             CollectionUpdateOperations::FieldIndexOperation(index_operation) => {
                 process_field_index_operation(segments, op_num, &index_operation)
             }
+            CollectionUpdateOperations::BlobOperation(blob_operation) => {
+                process_blob_operation(segments, op_num, blob_operation)
+            }
         };
 
         CollectionUpdater::handle_update_result(segments, op_num, &operation_result);
@@ -73,7 +76,7 @@ impl CollectionUpdater {
 #[cfg(test)]
 mod tests {
     use segment::data_types::vectors::{only_default_vector, VectorStruct, DEFAULT_VECTOR_NAME};
-    use segment::types::{Payload, WithPayload};
+    use segment::types::{Condition, FieldCondition, Filter, Payload, WithPayload};
     use serde_json::json;
     use tempfile::Builder;
 
@@ -81,8 +84,12 @@ mod tests {
     use crate::collection_manager::fixtures::build_test_holder;
     use crate::collection_manager::segments_searcher::SegmentsSearcher;
     use crate::collection_manager::segments_updater::upsert_points;
+    use crate::operations::blob_ops::{BlobOperations, DeleteBlobOp, SetBlobOp};
     use crate::operations::payload_ops::{DeletePayloadOp, PayloadOps, SetPayloadOp};
-    use crate::operations::point_ops::{PointOperations, PointStruct};
+    use crate::operations::point_ops::{
+        ConditionalInsertOperationInternal, PointOperations, PointStruct, UpdateMode,
+    };
+    use crate::operations::types::CollectionError;
 
     #[test]
     fn test_sync_ops() {
@@ -99,26 +106,31 @@ mod tests {
                 id: 11.into(),
                 vector: VectorStruct::from(vec11).into(),
                 payload: None,
+                expires_at: None,
             },
             PointStruct {
                 id: 12.into(),
                 vector: VectorStruct::from(vec12).into(),
                 payload: None,
+                expires_at: None,
             },
             PointStruct {
                 id: 13.into(),
                 vector: VectorStruct::from(vec13).into(),
                 payload: Some(json!({ "color": "red" }).into()),
+                expires_at: None,
             },
             PointStruct {
                 id: 14.into(),
                 vector: VectorStruct::from(vec![0., 0., 0., 0.]).into(),
                 payload: None,
+                expires_at: None,
             },
             PointStruct {
                 id: 500.into(),
                 vector: VectorStruct::from(vec![2., 0., 2., 0.]).into(),
                 payload: None,
+                expires_at: None,
             },
         ];
 
@@ -141,11 +153,13 @@ mod tests {
                 id: 1.into(),
                 vector: VectorStruct::from(vec![2., 2., 2., 2.]).into(),
                 payload: None,
+                expires_at: None,
             },
             PointStruct {
                 id: 500.into(),
                 vector: VectorStruct::from(vec![2., 0., 2., 0.]).into(),
                 payload: None,
+                expires_at: None,
             },
         ];
 
@@ -180,6 +194,7 @@ mod tests {
             101,
             PointOperations::DeletePoints {
                 ids: vec![500.into()],
+                if_version: None,
             },
         )
         .unwrap();
@@ -198,6 +213,166 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_conditional_upsert() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let segments = build_test_holder(dir.path());
+
+        let new_vector = vec![3., 3., 3., 3.];
+        let point = |id: u64| PointStruct {
+            id: id.into(),
+            vector: VectorStruct::from(new_vector.clone()).into(),
+            payload: None,
+            expires_at: None,
+        };
+        let upsert = |op_num, ids: &[u64], update_mode, update_filter| {
+            process_point_operation(
+                &segments,
+                op_num,
+                PointOperations::UpsertPointsConditional(ConditionalInsertOperationInternal {
+                    points_op: ids.iter().copied().map(&point).collect::<Vec<_>>().into(),
+                    update_mode,
+                    update_filter,
+                    if_version: None,
+                    dedup_key: None,
+                }),
+            )
+            .unwrap();
+        };
+        let updated_ids = || {
+            let mut ids = SegmentsSearcher::retrieve(
+                &segments,
+                &[1.into(), 2.into(), 3.into(), 500.into(), 501.into()],
+                &WithPayload::from(false),
+                &true.into(),
+            )
+            .unwrap()
+            .into_iter()
+            .filter(|record| {
+                record.vector.as_ref().unwrap().get(DEFAULT_VECTOR_NAME)
+                    == Some((&new_vector).into())
+            })
+            .map(|record| record.id)
+            .collect::<Vec<_>>();
+            ids.sort();
+            ids
+        };
+
+        // Point 1 is red and gets updated, point 3 is blue, point 500 doesn't exist
+        let red = Filter::new_must(Condition::Field(FieldCondition::new_match(
+            "color".parse().unwrap(),
+            "red".to_string().into(),
+        )));
+        upsert(100, &[1, 3, 500], UpdateMode::UpdateOnly, Some(red));
+        assert_eq!(updated_ids(), vec![1.into()]);
+
+        // Point 2 exists and stays untouched, point 501 is new
+        upsert(101, &[2, 501], UpdateMode::InsertOnly, None);
+        assert_eq!(updated_ids(), vec![1.into(), 501.into()]);
+    }
+
+    #[test]
+    fn test_dedup_by_key() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let segments = build_test_holder(dir.path());
+
+        let point = |id: u64, payload: serde_json::Value| PointStruct {
+            id: id.into(),
+            vector: VectorStruct::from(vec![3., 3., 3., 3.]).into(),
+            payload: Some(payload.into()),
+            expires_at: None,
+        };
+
+        let points = vec![point(100, json!({"sku": "x"}))];
+        process_point_operation(&segments, 100, PointOperations::UpsertPoints(points.into()))
+            .unwrap();
+
+        // Point 200 replaces point 100, point 201 is superseded by point 202 within the operation
+        let points = vec![
+            point(200, json!({"sku": "x", "n": 1})),
+            point(201, json!({"sku": "y"})),
+            point(202, json!({"sku": "y", "n": 2})),
+        ];
+        process_point_operation(
+            &segments,
+            101,
+            PointOperations::UpsertPointsConditional(ConditionalInsertOperationInternal {
+                points_op: points.into(),
+                update_mode: UpdateMode::Upsert,
+                update_filter: None,
+                if_version: None,
+                dedup_key: Some("sku".parse().unwrap()),
+            }),
+        )
+        .unwrap();
+
+        let records = SegmentsSearcher::retrieve(
+            &segments,
+            &[100.into(), 200.into(), 201.into(), 202.into()],
+            &WithPayload::from(true),
+            &false.into(),
+        )
+        .unwrap();
+        let ids: Vec<_> = records.iter().map(|record| record.id).collect();
+        assert_eq!(ids, vec![100.into(), 202.into()]);
+        assert_eq!(records[0].payload, Some(json!({"sku": "x", "n": 1}).into()));
+    }
+
+    #[test]
+    fn test_version_guard() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let segments = build_test_holder(dir.path());
+
+        let points = vec![PointStruct {
+            id: 1.into(),
+            vector: VectorStruct::from(vec![3., 3., 3., 3.]).into(),
+            payload: None,
+            expires_at: None,
+        }];
+        process_point_operation(&segments, 100, PointOperations::UpsertPoints(points.into()))
+            .unwrap();
+
+        let records = SegmentsSearcher::retrieve(
+            &segments,
+            &[1.into()],
+            &WithPayload::from(false),
+            &false.into(),
+        )
+        .unwrap();
+        assert_eq!(records[0].version, Some(100));
+
+        // Stale version is rejected
+        let res = check_point_versions(&segments.read(), &[1.into()], 99);
+        assert!(matches!(res, Err(CollectionError::Conflict { .. })));
+
+        // Missing point can't match any version
+        let res = check_point_versions(&segments.read(), &[1.into(), 500.into()], 100);
+        assert!(matches!(res, Err(CollectionError::PointNotFound { .. })));
+
+        check_point_versions(&segments.read(), &[1.into()], 100).unwrap();
+
+        // Guard is resolved before the operation is replicated, so it is not checked again when
+        // the operation is applied to segments
+        let mut operation =
+            CollectionUpdateOperations::PointOperation(PointOperations::DeletePoints {
+                ids: vec![1.into()],
+                if_version: Some(100),
+            });
+        assert_eq!(operation.take_version_guard(), Some((vec![1.into()], 100)),);
+        assert!(!operation.has_version_guard());
+
+        let deleted = process_point_operation(
+            &segments,
+            101,
+            PointOperations::DeletePoints {
+                ids: vec![1.into()],
+                if_version: Some(99),
+            },
+        )
+        .unwrap();
+        assert_eq!(deleted, 1);
+    }
+
     #[test]
     fn test_payload_ops() {
         let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
@@ -287,4 +462,60 @@ mod tests {
         assert_eq!(res.len(), 1);
         assert!(!res[0].payload.as_ref().unwrap().contains_key("color"));
     }
+
+    #[test]
+    fn test_blob_ops() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let segments = build_test_holder(dir.path());
+
+        let set_blob = |op_num, point_id: u64, blob: &[u8]| {
+            process_blob_operation(
+                &segments,
+                op_num,
+                BlobOperations::SetBlob(SetBlobOp {
+                    point_id: point_id.into(),
+                    blob: blob.to_vec(),
+                }),
+            )
+        };
+        let read_blob = |point_id: u64, offset, limit| {
+            SegmentsSearcher::read_blob(&segments, point_id.into(), offset, limit).unwrap()
+        };
+
+        set_blob(100, 1, b"thumbnail").unwrap();
+        let blob = read_blob(1, 0, usize::MAX).unwrap();
+        assert_eq!(blob.data, b"thumbnail");
+        assert_eq!(blob.total_len, 9);
+
+        // Blob is replaced and can be read in parts
+        set_blob(101, 1, b"another thumbnail").unwrap();
+        let blob = read_blob(1, 8, 5).unwrap();
+        assert_eq!(blob.data, b"thumb");
+        assert_eq!(blob.total_len, 17);
+        assert_eq!(read_blob(2, 0, usize::MAX), None);
+
+        let res = set_blob(102, 1000, b"missing");
+        assert!(matches!(res, Err(CollectionError::PointNotFound { .. })));
+
+        process_blob_operation(
+            &segments,
+            103,
+            BlobOperations::DeleteBlob(DeleteBlobOp { point_id: 1.into() }),
+        )
+        .unwrap();
+        assert_eq!(read_blob(1, 0, usize::MAX), None);
+
+        // Blob is removed together with the point
+        set_blob(104, 2, b"thumbnail").unwrap();
+        process_point_operation(
+            &segments,
+            105,
+            PointOperations::DeletePoints {
+                ids: vec![2.into()],
+                if_version: None,
+            },
+        )
+        .unwrap();
+        assert_eq!(read_blob(2, 0, usize::MAX), None);
+    }
 }
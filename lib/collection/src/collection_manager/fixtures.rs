@@ -28,6 +28,7 @@ use crate::collection_manager::optimizers::segment_optimizer::{
 use crate::config::CollectionParams;
 use crate::operations::types::VectorsConfig;
 use crate::operations::vector_params_builder::VectorParamsBuilder;
+use crate::optimizers_builder::MergePolicy;
 
 pub fn empty_segment(path: &Path) -> Segment {
     build_simple_segment(path, 4, Distance::Dot).unwrap()
@@ -220,6 +221,7 @@ pub(crate) fn get_merge_optimizer(
 ) -> MergeOptimizer {
     MergeOptimizer::new(
         5,
+        MergePolicy::SizeTiered,
         OptimizerThresholds {
             max_segment_size: 100_000,
             memmap_threshold: 1000000,
@@ -231,6 +233,7 @@ pub(crate) fn get_merge_optimizer(
             vectors: VectorsConfig::Single(
                 VectorParamsBuilder::new(dim as u64, Distance::Dot).build(),
             ),
+            storage_engine: None,
             ..CollectionParams::empty()
         },
         Default::default(),
@@ -256,6 +259,7 @@ pub(crate) fn get_indexing_optimizer(
             vectors: VectorsConfig::Single(
                 VectorParamsBuilder::new(dim as u64, Distance::Dot).build(),
             ),
+            storage_engine: None,
             ..CollectionParams::empty()
         },
         Default::default(),
@@ -6,6 +6,7 @@ use std::sync::Arc;
 use bitvec::prelude::BitVec;
 use common::types::{PointOffsetType, TelemetryDetail};
 use parking_lot::{RwLock, RwLockUpgradableReadGuard};
+use segment::blob_storage::BlobChunk;
 use segment::common::operation_error::{OperationResult, SegmentFailedState};
 use segment::data_types::named_vectors::NamedVectors;
 use segment::data_types::order_by::OrderValue;
@@ -13,6 +14,7 @@ use segment::data_types::query_context::{QueryContext, SegmentQueryContext};
 use segment::data_types::vectors::{QueryVector, Vector};
 use segment::entry::entry_point::SegmentEntry;
 use segment::index::field_index::CardinalityEstimation;
+use segment::index::vector_index_info::VectorIndexInfo;
 use segment::json_path::JsonPath;
 use segment::telemetry::SegmentTelemetry;
 use segment::types::{
@@ -155,9 +157,10 @@ impl ProxySegment {
                 return Ok(false);
             }
 
-            let (all_vectors, payload) = (
+            let (all_vectors, payload, blob) = (
                 wrapped_segment_guard.all_vectors(point_id)?,
                 wrapped_segment_guard.payload(point_id)?,
+                wrapped_segment_guard.blob(point_id, 0, usize::MAX)?,
             );
 
             {
@@ -168,6 +171,9 @@ impl ProxySegment {
                 if !payload.is_empty() {
                     write_segment.set_full_payload(op_num, point_id, &payload)?;
                 }
+                if let Some(blob) = blob {
+                    write_segment.set_blob(op_num, point_id, &blob.data)?;
+                }
             };
 
             point_offset
@@ -337,6 +343,9 @@ impl SegmentEntry for ProxySegment {
         params: Option<&SearchParams>,
         query_context: SegmentQueryContext,
     ) -> OperationResult<Vec<Vec<ScoredPoint>>> {
+        // Filter plans are bound to a single segment, but we search in two of them
+        let query_context = query_context.without_filter_plans();
+
         let deleted_points = self.deleted_points.read();
 
         // Some point might be deleted after temporary segment creation
@@ -529,6 +538,31 @@ impl SegmentEntry for ProxySegment {
             .clear_payload(op_num, point_id)
     }
 
+    fn set_blob(
+        &mut self,
+        op_num: SeqNumberType,
+        point_id: PointIdType,
+        blob: &[u8],
+    ) -> OperationResult<bool> {
+        self.move_if_exists(op_num, point_id)?;
+        self.write_segment
+            .get()
+            .write()
+            .set_blob(op_num, point_id, blob)
+    }
+
+    fn delete_blob(
+        &mut self,
+        op_num: SeqNumberType,
+        point_id: PointIdType,
+    ) -> OperationResult<bool> {
+        self.move_if_exists(op_num, point_id)?;
+        self.write_segment
+            .get()
+            .write()
+            .delete_blob(op_num, point_id)
+    }
+
     fn vector(&self, vector_name: &str, point_id: PointIdType) -> OperationResult<Option<Vector>> {
         return if self.deleted_points.read().contains(&point_id) {
             self.write_segment
@@ -594,6 +628,32 @@ impl SegmentEntry for ProxySegment {
         };
     }
 
+    fn blob(
+        &self,
+        point_id: PointIdType,
+        offset: usize,
+        limit: usize,
+    ) -> OperationResult<Option<BlobChunk>> {
+        return if self.deleted_points.read().contains(&point_id) {
+            self.write_segment
+                .get()
+                .read()
+                .blob(point_id, offset, limit)
+        } else {
+            {
+                let write_segment = self.write_segment.get();
+                let segment_guard = write_segment.read();
+                if segment_guard.has_point(point_id) {
+                    return segment_guard.blob(point_id, offset, limit);
+                }
+            }
+            self.wrapped_segment
+                .get()
+                .read()
+                .blob(point_id, offset, limit)
+        };
+    }
+
     /// Not implemented for proxy
     fn iter_points(&self) -> Box<dyn Iterator<Item = PointIdType> + '_> {
         // iter_points is not available for Proxy implementation
@@ -764,6 +824,13 @@ impl SegmentEntry for ProxySegment {
                 .entry(key)
                 .and_modify(|wrapped_info| {
                     wrapped_info.num_vectors += info.num_vectors;
+                    match (&mut wrapped_info.sparse_stats, info.sparse_stats) {
+                        (Some(wrapped_stats), Some(write_stats)) => {
+                            wrapped_stats.merge(&write_stats);
+                        }
+                        (wrapped_stats @ None, write_stats) => *wrapped_stats = write_stats,
+                        (Some(_), None) => {}
+                    }
                 })
                 .or_insert(info);
         }
@@ -962,6 +1029,11 @@ impl SegmentEntry for ProxySegment {
         self.wrapped_segment.get().read().get_telemetry_data(detail)
     }
 
+    fn vector_index_info(&self) -> Vec<VectorIndexInfo> {
+        // Temporary write segment is plain and small, only indexes of the wrapped one matter
+        self.wrapped_segment.get().read().vector_index_info()
+    }
+
     fn fill_query_context(&self, query_context: &mut QueryContext) {
         // Information from temporary segment is not too important for query context
         self.wrapped_segment
@@ -1530,6 +1602,8 @@ mod tests {
             ]),
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_columns: vec![],
+            storage_cipher: None,
         };
         let mut original_segment = build_segment(dir.path(), &config, true).unwrap();
         let write_segment = build_segment(dir.path(), &config, true).unwrap();
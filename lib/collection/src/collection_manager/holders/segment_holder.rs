@@ -10,6 +10,7 @@ use io::storage_version::StorageVersion;
 use parking_lot::{RwLock, RwLockReadGuard, RwLockUpgradableReadGuard, RwLockWriteGuard};
 use rand::seq::SliceRandom;
 use rand::{thread_rng, Rng};
+use segment::common::encryption::StorageCipher;
 use segment::common::operation_error::{OperationError, OperationResult};
 use segment::entry::entry_point::SegmentEntry;
 use segment::segment::{Segment, SegmentVersion};
@@ -148,6 +149,9 @@ pub struct SegmentHolder {
 
     /// Holds the first uncorrected error happened with optimizer
     pub optimizer_errors: Option<CollectionError>,
+
+    /// Key of the collection, new segments are encrypted with
+    pub storage_cipher: Option<Arc<StorageCipher>>,
 }
 
 pub type LockedSegmentHolder = Arc<RwLock<SegmentHolder>>;
@@ -478,10 +482,14 @@ impl<'s> SegmentHolder {
                         |_appendable_idx, appendable_write_segment| {
                             let all_vectors = write_segment.all_vectors(point_id)?;
                             let payload = write_segment.payload(point_id)?;
+                            let blob = write_segment.blob(point_id, 0, usize::MAX)?;
 
                             appendable_write_segment.upsert_point(op_num, point_id, all_vectors)?;
                             appendable_write_segment
                                 .set_full_payload(op_num, point_id, &payload)?;
+                            if let Some(blob) = blob {
+                                appendable_write_segment.set_blob(op_num, point_id, &blob.data)?;
+                            }
 
                             write_segment.delete_point(op_num, point_id)?;
 
@@ -525,6 +533,31 @@ impl<'s> SegmentHolder {
         Ok(read_points)
     }
 
+    /// Same as [`Self::read_points`], but calls `f` once per segment, with all of the requested
+    /// points the segment has. Allows segments to read data of multiple points at once.
+    pub fn read_points_batched<F>(&self, ids: &[PointIdType], mut f: F) -> OperationResult<usize>
+    where
+        F: FnMut(&[PointIdType], &RwLockReadGuard<dyn SegmentEntry>) -> OperationResult<usize>,
+    {
+        // Same ordering as in `read_points`, see explanation there
+        let segments = self.non_appendable_then_appendable_segments();
+
+        let mut read_points = 0;
+        for segment in segments {
+            let segment_arc = segment.get();
+            let read_segment = segment_arc.read();
+            let segment_ids = ids
+                .iter()
+                .copied()
+                .filter(|id| read_segment.has_point(*id))
+                .collect::<Vec<_>>();
+            if !segment_ids.is_empty() {
+                read_points += f(&segment_ids, &read_segment)?;
+            }
+        }
+        Ok(read_points)
+    }
+
     /// Defines flush ordering for segments.
     ///
     /// Flush appendable segments first, then non-appendable.
@@ -757,12 +790,14 @@ impl<'s> SegmentHolder {
             // Base config on collection params
             Some(collection_params) => SegmentConfig {
                 vector_data: collection_params
-                    .to_base_vector_data()
+                    .to_base_vector_data(None)
                     .map_err(|err| OperationError::service_error(format!("Failed to source dense vector configuration from collection parameters: {err:?}")))?,
                 sparse_vector_data: collection_params
                     .to_sparse_vector_data()
                     .map_err(|err| OperationError::service_error(format!("Failed to source sparse vector configuration from collection parameters: {err:?}")))?,
                 payload_storage_type: collection_params.payload_storage_type(),
+                payload_columns: collection_params.payload_columns.clone().unwrap_or_default(),
+                storage_cipher: self.storage_cipher.clone(),
             },
             // Fall back: base config on existing appendable segment
             None => {
@@ -4,6 +4,7 @@ pub mod optimizers;
 pub mod segments_searcher;
 
 mod probabilistic_segment_search_sampling;
+mod search_parallelism;
 mod search_result_aggregator;
 mod segments_updater;
 
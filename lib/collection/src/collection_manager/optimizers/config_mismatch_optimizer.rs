@@ -4,9 +4,12 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use parking_lot::Mutex;
+use segment::common::encryption::StorageCipher;
 use segment::common::operation_time_statistics::OperationDurationsAggregator;
 use segment::index::sparse_index::sparse_index_config::SparseIndexType;
-use segment::types::{HnswConfig, Indexes, QuantizationConfig, SegmentType, VECTOR_ELEMENT_SIZE};
+use segment::types::{
+    HnswConfig, Indexes, PayloadKeyType, QuantizationConfig, SegmentType, VECTOR_ELEMENT_SIZE,
+};
 
 use crate::collection_manager::holders::segment_holder::{LockedSegmentHolder, SegmentId};
 use crate::collection_manager::optimizers::segment_optimizer::{
@@ -27,6 +30,8 @@ pub struct ConfigMismatchOptimizer {
     collection_params: CollectionParams,
     hnsw_config: HnswConfig,
     quantization_config: Option<QuantizationConfig>,
+    defragment_key: Option<PayloadKeyType>,
+    storage_cipher: Option<Arc<StorageCipher>>,
     telemetry_durations_aggregator: Arc<Mutex<OperationDurationsAggregator>>,
 }
 
@@ -46,10 +51,24 @@ impl ConfigMismatchOptimizer {
             collection_params,
             hnsw_config,
             quantization_config,
+            defragment_key: None,
+            storage_cipher: None,
             telemetry_durations_aggregator: OperationDurationsAggregator::new(),
         }
     }
 
+    /// Group points by the value of `defragment_key` in optimized segments
+    pub fn with_defragment_key(mut self, defragment_key: Option<PayloadKeyType>) -> Self {
+        self.defragment_key = defragment_key;
+        self
+    }
+
+    /// Encrypt optimized segments with the key of the collection
+    pub fn with_storage_cipher(mut self, storage_cipher: Option<Arc<StorageCipher>>) -> Self {
+        self.storage_cipher = storage_cipher;
+        self
+    }
+
     /// Check if current configuration requires vectors to be stored on disk
     fn check_if_vectors_on_disk(&self, vector_name: &str) -> Option<bool> {
         self.collection_params
@@ -126,8 +145,8 @@ impl ConfigMismatchOptimizer {
                     return None; // Never optimize already optimized segment
                 }
 
-                if self.collection_params.on_disk_payload
-                    != segment_config.payload_storage_type.is_on_disk()
+                if self.collection_params.payload_storage_type()
+                    != segment_config.payload_storage_type
                 {
                     return Some((*idx, vector_size)); // Skip segments with payload mismatch
                 }
@@ -158,6 +177,12 @@ impl ConfigMismatchOptimizer {
                                 }
                             }
 
+                            // Quantization of not indexed segments is maintained in memory, no need to
+                            // rebuild the segment
+                            if !vector_data.index.is_indexed() {
+                                return false;
+                            }
+
                             // Check quantization mismatch
                             let target_quantization_collection = self.quantization_config.as_ref();
                             let target_quantization_vector = self
@@ -176,11 +201,10 @@ impl ConfigMismatchOptimizer {
                                 .zip(target_quantization)
                                 // Rebuild if current parameters differ from target parameters
                                 .map(|(current, target)| current.mismatch_requires_rebuild(target))
-                                // Or rebuild if we now change the enabled state on an indexed segment
+                                // Or rebuild if we now change the enabled state
                                 .unwrap_or_else(|| {
-                                    vector_data.index.is_indexed()
-                                        && (vector_data.quantization_config.is_some()
-                                            != target_quantization.is_some())
+                                    vector_data.quantization_config.is_some()
+                                        != target_quantization.is_some()
                                 });
 
                             quantization_mismatch
@@ -247,6 +271,14 @@ impl SegmentOptimizer for ConfigMismatchOptimizer {
         &self.thresholds_config
     }
 
+    fn defragment_key(&self) -> Option<&PayloadKeyType> {
+        self.defragment_key.as_ref()
+    }
+
+    fn storage_cipher(&self) -> Option<Arc<StorageCipher>> {
+        self.storage_cipher.clone()
+    }
+
     fn check_condition(
         &self,
         segments: LockedSegmentHolder,
@@ -310,6 +342,7 @@ mod tests {
             vectors: VectorsConfig::Single(
                 VectorParamsBuilder::new(dim as u64, Distance::Dot).build(),
             ),
+            storage_engine: None,
             ..CollectionParams::empty()
         };
 
@@ -459,6 +492,7 @@ mod tests {
                     VectorParamsBuilder::new(vector2_dim as u64, Distance::Dot).build(),
                 ),
             ])),
+            storage_engine: None,
             ..CollectionParams::empty()
         };
 
@@ -629,6 +663,7 @@ mod tests {
                     VectorParamsBuilder::new(vector2_dim as u64, Distance::Dot).build(),
                 ),
             ])),
+            storage_engine: None,
             ..CollectionParams::empty()
         };
 
@@ -3,8 +3,11 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use parking_lot::Mutex;
+use segment::common::encryption::StorageCipher;
 use segment::common::operation_time_statistics::OperationDurationsAggregator;
-use segment::types::{HnswConfig, QuantizationConfig, SegmentType, VECTOR_ELEMENT_SIZE};
+use segment::types::{
+    HnswConfig, PayloadKeyType, QuantizationConfig, SegmentType, VECTOR_ELEMENT_SIZE,
+};
 
 use crate::collection_manager::holders::segment_holder::{
     LockedSegmentHolder, SegmentHolder, SegmentId,
@@ -28,6 +31,8 @@ pub struct IndexingOptimizer {
     collection_params: CollectionParams,
     hnsw_config: HnswConfig,
     quantization_config: Option<QuantizationConfig>,
+    defragment_key: Option<PayloadKeyType>,
+    storage_cipher: Option<Arc<StorageCipher>>,
     telemetry_durations_aggregator: Arc<Mutex<OperationDurationsAggregator>>,
 }
 
@@ -49,10 +54,24 @@ impl IndexingOptimizer {
             collection_params,
             hnsw_config,
             quantization_config,
+            defragment_key: None,
+            storage_cipher: None,
             telemetry_durations_aggregator: OperationDurationsAggregator::new(),
         }
     }
 
+    /// Group points by the value of `defragment_key` in optimized segments
+    pub fn with_defragment_key(mut self, defragment_key: Option<PayloadKeyType>) -> Self {
+        self.defragment_key = defragment_key;
+        self
+    }
+
+    /// Encrypt optimized segments with the key of the collection
+    pub fn with_storage_cipher(mut self, storage_cipher: Option<Arc<StorageCipher>>) -> Self {
+        self.storage_cipher = storage_cipher;
+        self
+    }
+
     fn smallest_indexed_segment(
         &self,
         segments: &SegmentHolder,
@@ -276,6 +295,14 @@ impl SegmentOptimizer for IndexingOptimizer {
         &self.thresholds_config
     }
 
+    fn defragment_key(&self) -> Option<&PayloadKeyType> {
+        self.defragment_key.as_ref()
+    }
+
+    fn storage_cipher(&self) -> Option<Arc<StorageCipher>> {
+        self.storage_cipher.clone()
+    }
+
     fn check_condition(
         &self,
         segments: LockedSegmentHolder,
@@ -370,6 +397,7 @@ mod tests {
             segments_temp_dir.path().to_owned(),
             CollectionParams {
                 vectors: VectorsConfig::Multi(vectors_config),
+                storage_engine: None,
                 ..CollectionParams::empty()
             },
             Default::default(),
@@ -477,6 +505,7 @@ mod tests {
                     )
                     .build(),
                 ),
+                storage_engine: None,
                 ..CollectionParams::empty()
             },
             Default::default(),
@@ -770,6 +799,7 @@ mod tests {
                     )
                     .build(),
                 ),
+                storage_engine: None,
                 ..CollectionParams::empty()
             },
             Default::default(),
@@ -843,6 +873,7 @@ mod tests {
                     .with_on_disk(false)
                     .build(),
             ),
+            storage_engine: None,
             ..CollectionParams::empty()
         };
 
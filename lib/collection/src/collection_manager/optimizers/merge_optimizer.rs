@@ -4,8 +4,11 @@ use std::sync::Arc;
 
 use itertools::Itertools;
 use parking_lot::Mutex;
+use segment::common::encryption::StorageCipher;
 use segment::common::operation_time_statistics::OperationDurationsAggregator;
-use segment::types::{HnswConfig, QuantizationConfig, SegmentType, VECTOR_ELEMENT_SIZE};
+use segment::types::{
+    HnswConfig, PayloadKeyType, QuantizationConfig, SegmentType, VECTOR_ELEMENT_SIZE,
+};
 
 use crate::collection_manager::holders::segment_holder::{
     LockedSegment, LockedSegmentHolder, SegmentId,
@@ -14,21 +17,34 @@ use crate::collection_manager::optimizers::segment_optimizer::{
     OptimizerThresholds, SegmentOptimizer,
 };
 use crate::config::CollectionParams;
+use crate::optimizers_builder::MergePolicy;
 
 const BYTES_IN_KB: usize = 1024;
 
+/// Ratio between sizes of segments on the adjacent levels of [`MergePolicy::Leveled`]
+const LEVEL_SIZE_RATIO: usize = 4;
+
+/// Minimal number of segments to merge at once
+const MIN_MERGE_CANDIDATES: usize = 3;
+
 /// Optimizer that tries to reduce number of segments until it fits configured value.
 /// It merges 3 smallest segments into a single large segment.
 /// Merging 3 segments instead of 2 guarantees that after the optimization the number of segments
 /// will be less than before.
+///
+/// With [`MergePolicy::Leveled`] only segments of a similar size are merged, so the number of
+/// segments may stay above the configured value, if there are not enough segments of the same level.
 pub struct MergeOptimizer {
     default_segments_number: usize,
+    merge_policy: MergePolicy,
     thresholds_config: OptimizerThresholds,
     segments_path: PathBuf,
     collection_temp_dir: PathBuf,
     collection_params: CollectionParams,
     hnsw_config: HnswConfig,
     quantization_config: Option<QuantizationConfig>,
+    defragment_key: Option<PayloadKeyType>,
+    storage_cipher: Option<Arc<StorageCipher>>,
     telemetry_durations_aggregator: Arc<Mutex<OperationDurationsAggregator>>,
 }
 
@@ -36,6 +52,7 @@ impl MergeOptimizer {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         default_segments_number: usize,
+        merge_policy: MergePolicy,
         thresholds_config: OptimizerThresholds,
         segments_path: PathBuf,
         collection_temp_dir: PathBuf,
@@ -45,15 +62,78 @@ impl MergeOptimizer {
     ) -> Self {
         MergeOptimizer {
             default_segments_number,
+            merge_policy,
             thresholds_config,
             segments_path,
             collection_temp_dir,
             collection_params,
             hnsw_config,
             quantization_config,
+            defragment_key: None,
+            storage_cipher: None,
             telemetry_durations_aggregator: OperationDurationsAggregator::new(),
         }
     }
+
+    /// Group points by the value of `defragment_key` in optimized segments
+    pub fn with_defragment_key(mut self, defragment_key: Option<PayloadKeyType>) -> Self {
+        self.defragment_key = defragment_key;
+        self
+    }
+
+    /// Encrypt optimized segments with the key of the collection
+    pub fn with_storage_cipher(mut self, storage_cipher: Option<Arc<StorageCipher>>) -> Self {
+        self.storage_cipher = storage_cipher;
+        self
+    }
+
+    /// Take segments, smallest first, while their total size fits into the max segment size
+    fn take_smallest(
+        &self,
+        segments: impl IntoIterator<Item = (SegmentId, usize)>,
+        max_candidates: usize,
+    ) -> Vec<SegmentId> {
+        let max_segment_size = self
+            .thresholds_config
+            .max_segment_size
+            .saturating_mul(BYTES_IN_KB);
+
+        segments
+            .into_iter()
+            .sorted_by_key(|(_, size)| *size)
+            .scan(0, |size_sum, (sid, size)| {
+                *size_sum += size; // produce a cumulative sum of segment sizes starting from smallest
+                Some((sid, *size_sum))
+            })
+            .take_while(|(_, size)| *size < max_segment_size)
+            .take(max_candidates)
+            .map(|x| x.0)
+            .collect()
+    }
+
+    /// Select segments from the lowest level, which has enough segments to merge
+    fn take_same_level(
+        &self,
+        segments: Vec<(SegmentId, usize)>,
+        max_candidates: usize,
+    ) -> Vec<SegmentId> {
+        let levels = segments
+            .into_iter()
+            .into_group_map_by(|(_, size)| segment_level(*size));
+
+        levels
+            .into_iter()
+            .sorted_by_key(|(level, _)| *level)
+            .map(|(_, level_segments)| self.take_smallest(level_segments, max_candidates))
+            .find(|candidates| candidates.len() >= MIN_MERGE_CANDIDATES)
+            .unwrap_or_default()
+    }
+}
+
+/// Level of the segment of the given size in bytes.
+/// Segments of the next level are [`LEVEL_SIZE_RATIO`] times larger.
+fn segment_level(size: usize) -> u32 {
+    size.max(1).ilog(LEVEL_SIZE_RATIO)
 }
 
 impl SegmentOptimizer for MergeOptimizer {
@@ -85,6 +165,14 @@ impl SegmentOptimizer for MergeOptimizer {
         &self.thresholds_config
     }
 
+    fn defragment_key(&self) -> Option<&PayloadKeyType> {
+        self.defragment_key.as_ref()
+    }
+
+    fn storage_cipher(&self) -> Option<Arc<StorageCipher>> {
+        self.storage_cipher.clone()
+    }
+
     fn check_condition(
         &self,
         segments: LockedSegmentHolder,
@@ -107,7 +195,7 @@ impl SegmentOptimizer for MergeOptimizer {
         // Find at least top-3 smallest segments to join.
         // We need 3 segments because in this case we can guarantee that total segments number will be less
 
-        let candidates: Vec<_> = raw_segments
+        let segment_sizes: Vec<_> = raw_segments
             .iter()
             .cloned()
             .filter_map(|(idx, segment)| {
@@ -125,23 +213,14 @@ impl SegmentOptimizer for MergeOptimizer {
                         * VECTOR_ELEMENT_SIZE,
                 ))
             })
-            .sorted_by_key(|(_, size)| *size)
-            .scan(0, |size_sum, (sid, size)| {
-                *size_sum += size; // produce a cumulative sum of segment sizes starting from smallest
-                Some((sid, *size_sum))
-            })
-            .take_while(|(_, size)| {
-                *size
-                    < self
-                        .thresholds_config
-                        .max_segment_size
-                        .saturating_mul(BYTES_IN_KB)
-            })
-            .take(max_candidates)
-            .map(|x| x.0)
             .collect();
 
-        if candidates.len() < 3 {
+        let candidates = match self.merge_policy {
+            MergePolicy::SizeTiered => self.take_smallest(segment_sizes, max_candidates),
+            MergePolicy::Leveled => self.take_same_level(segment_sizes, max_candidates),
+        };
+
+        if candidates.len() < MIN_MERGE_CANDIDATES {
             return vec![];
         }
         log::debug!("Merge candidates: {:?}", candidates);
@@ -201,6 +280,57 @@ mod tests {
         assert_eq!(check_result.len(), 3);
     }
 
+    #[test]
+    fn test_leveled_merge_policy() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let temp_dir = Builder::new().prefix("segment_temp_dir").tempdir().unwrap();
+
+        let mut holder = SegmentHolder::default();
+        let dim = 256;
+
+        let same_level_segments = [
+            holder.add(random_segment(dir.path(), 100, 3, dim)),
+            holder.add(random_segment(dir.path(), 100, 3, dim)),
+            holder.add(random_segment(dir.path(), 100, 3, dim)),
+        ];
+        let larger_segment = holder.add(random_segment(dir.path(), 100, 10, dim));
+
+        let mut merge_optimizer = get_merge_optimizer(dir.path(), temp_dir.path(), dim);
+        merge_optimizer.default_segments_number = 1;
+
+        let locked_holder: Arc<RwLock<_>> = Arc::new(RwLock::new(holder));
+
+        // Size-tiered merges all smallest segments
+        let suggested_for_merge =
+            merge_optimizer.check_condition(locked_holder.clone(), &Default::default());
+        assert_eq!(suggested_for_merge.len(), 4);
+
+        // Leveled merges segments of the same level only
+        merge_optimizer.merge_policy = MergePolicy::Leveled;
+        let suggested_for_merge =
+            merge_optimizer.check_condition(locked_holder.clone(), &Default::default());
+        assert_eq!(suggested_for_merge.len(), 3);
+        assert!(!suggested_for_merge.contains(&larger_segment));
+        for segment_in in &suggested_for_merge {
+            assert!(same_level_segments.contains(segment_in));
+        }
+
+        // Nothing to merge, if there are not enough segments of the same level
+        locked_holder.write().remove(&same_level_segments[..1]);
+        let suggested_for_merge =
+            merge_optimizer.check_condition(locked_holder, &Default::default());
+        assert!(suggested_for_merge.is_empty());
+    }
+
+    #[test]
+    fn test_segment_level() {
+        assert_eq!(segment_level(0), 0);
+        assert_eq!(segment_level(3), 0);
+        assert_eq!(segment_level(4), 1);
+        assert_eq!(segment_level(15), 1);
+        assert_eq!(segment_level(16), 2);
+    }
+
     #[test]
     fn test_merge_optimizer() {
         let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
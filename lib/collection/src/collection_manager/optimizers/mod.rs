@@ -57,6 +57,13 @@ impl TrackerLog {
         });
     }
 
+    /// Whether any of the tracked optimizations is still running
+    pub fn is_optimizing(&self) -> bool {
+        self.descriptions
+            .iter()
+            .any(|tracker| matches!(tracker.state.lock().status, TrackerStatus::Optimizing))
+    }
+
     /// Convert log into list of objects usable in telemetry
     pub fn to_telemetry(&self) -> Vec<TrackerTelemetry> {
         self.descriptions
@@ -7,6 +7,7 @@ use common::cpu::CpuPermit;
 use io::storage_version::StorageVersion;
 use itertools::Itertools;
 use parking_lot::{Mutex, RwLock, RwLockUpgradableReadGuard};
+use segment::common::encryption::StorageCipher;
 use segment::common::operation_error::check_process_stopped;
 use segment::common::operation_time_statistics::{
     OperationDurationsAggregator, ScopeDurationMeasurer,
@@ -17,8 +18,8 @@ use segment::segment::{Segment, SegmentVersion};
 use segment::segment_constructor::build_segment;
 use segment::segment_constructor::segment_builder::SegmentBuilder;
 use segment::types::{
-    HnswConfig, Indexes, PayloadFieldSchema, PayloadKeyType, PayloadStorageType, PointIdType,
-    QuantizationConfig, SegmentConfig, VectorStorageType, VECTOR_ELEMENT_SIZE,
+    HnswConfig, Indexes, PayloadFieldSchema, PayloadKeyType, PointIdType, QuantizationConfig,
+    SegmentConfig, VectorStorageType, VECTOR_ELEMENT_SIZE,
 };
 
 use crate::collection_manager::holders::proxy_segment::ProxySegment;
@@ -68,6 +69,12 @@ pub trait SegmentOptimizer {
     /// Get thresholds configuration for the current optimizer
     fn threshold_config(&self) -> &OptimizerThresholds;
 
+    /// Get payload field, by which points are grouped in optimized segments
+    fn defragment_key(&self) -> Option<&PayloadKeyType>;
+
+    /// Get key of the collection, which optimized segments are encrypted with
+    fn storage_cipher(&self) -> Option<Arc<StorageCipher>>;
+
     /// Checks if segment optimization is required
     fn check_condition(
         &self,
@@ -81,13 +88,15 @@ pub trait SegmentOptimizer {
     fn temp_segment(&self, save_version: bool) -> CollectionResult<LockedSegment> {
         let collection_params = self.collection_params();
         let config = SegmentConfig {
-            vector_data: collection_params.to_base_vector_data()?,
+            vector_data: collection_params
+                .to_base_vector_data(self.quantization_config().as_ref())?,
             sparse_vector_data: collection_params.to_sparse_vector_data()?,
-            payload_storage_type: if collection_params.on_disk_payload {
-                PayloadStorageType::OnDisk
-            } else {
-                PayloadStorageType::InMemory
-            },
+            payload_storage_type: collection_params.payload_storage_type(),
+            payload_columns: collection_params
+                .payload_columns
+                .clone()
+                .unwrap_or_default(),
+            storage_cipher: self.storage_cipher(),
         };
         Ok(LockedSegment::new(build_segment(
             self.segments_path(),
@@ -154,13 +163,14 @@ pub trait SegmentOptimizer {
         let threshold_is_on_disk = maximal_vector_store_size_bytes
             >= thresholds.memmap_threshold.saturating_mul(BYTES_IN_KB);
 
-        let mut vector_data = collection_params.to_base_vector_data()?;
+        let collection_quantization = self.quantization_config();
+        let mut vector_data =
+            collection_params.to_base_vector_data(collection_quantization.as_ref())?;
         let mut sparse_vector_data = collection_params.to_sparse_vector_data()?;
 
-        // If indexing, change to HNSW index and quantization
+        // If indexing, change to HNSW index
         if threshold_is_indexed {
             let collection_hnsw = self.hnsw_config();
-            let collection_quantization = self.quantization_config();
             vector_data.iter_mut().for_each(|(vector_name, config)| {
                 // Assign HNSW index
                 let param_hnsw = collection_params
@@ -171,16 +181,6 @@ pub trait SegmentOptimizer {
                     .and_then(|c| c.update(collection_hnsw).ok())
                     .unwrap_or_else(|| collection_hnsw.clone());
                 config.index = Indexes::Hnsw(vector_hnsw);
-
-                // Assign quantization config
-                let param_quantization = collection_params
-                    .vectors
-                    .get_params(vector_name)
-                    .and_then(|params| params.quantization_config.as_ref());
-                let vector_quantization = param_quantization
-                    .or(collection_quantization.as_ref())
-                    .cloned();
-                config.quantization_config = vector_quantization;
             });
         }
 
@@ -209,6 +209,14 @@ pub trait SegmentOptimizer {
             });
         }
 
+        // Quantization is only maintained in appendable segments, if they are not indexed
+        if !threshold_is_indexed {
+            vector_data
+                .values_mut()
+                .filter(|config| !config.is_appendable())
+                .for_each(|config| config.quantization_config = None);
+        }
+
         sparse_vector_data
             .iter_mut()
             .for_each(|(vector_name, config)| {
@@ -237,11 +245,12 @@ pub trait SegmentOptimizer {
         let optimized_config = SegmentConfig {
             vector_data,
             sparse_vector_data,
-            payload_storage_type: if collection_params.on_disk_payload {
-                PayloadStorageType::OnDisk
-            } else {
-                PayloadStorageType::InMemory
-            },
+            payload_storage_type: collection_params.payload_storage_type(),
+            payload_columns: collection_params
+                .payload_columns
+                .clone()
+                .unwrap_or_default(),
+            storage_cipher: self.storage_cipher(),
         };
 
         Ok(SegmentBuilder::new(
@@ -351,17 +360,20 @@ pub trait SegmentOptimizer {
         stopped: &AtomicBool,
     ) -> CollectionResult<Segment> {
         let mut segment_builder = self.optimized_segment_builder(optimizing_segments)?;
+        segment_builder.defragment_key = self.defragment_key().cloned();
 
         self.check_cancellation(stopped)?;
 
-        for segment in optimizing_segments {
-            match segment {
-                LockedSegment::Original(segment_arc) => {
-                    let segment_guard = segment_arc.read();
-                    segment_builder.update_from(&segment_guard, stopped)?;
-                }
-                LockedSegment::Proxy(_) => panic!("Attempt to optimize segment which is already currently under optimization. Should never happen"),
-            }
+        {
+            let segment_guards: Vec<_> = optimizing_segments
+                .iter()
+                .map(|segment| match segment {
+                    LockedSegment::Original(segment_arc) => segment_arc.read(),
+                    LockedSegment::Proxy(_) => panic!("Attempt to optimize segment which is already currently under optimization. Should never happen"),
+                })
+                .collect();
+            let segments: Vec<&Segment> = segment_guards.iter().map(|guard| &**guard).collect();
+            segment_builder.update(&segments, stopped)?;
         }
 
         for field in proxy_deleted_indexes.read().iter() {
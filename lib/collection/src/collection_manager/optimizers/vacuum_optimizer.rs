@@ -4,10 +4,11 @@ use std::sync::Arc;
 
 use ordered_float::OrderedFloat;
 use parking_lot::Mutex;
+use segment::common::encryption::StorageCipher;
 use segment::common::operation_time_statistics::OperationDurationsAggregator;
 use segment::entry::entry_point::SegmentEntry;
 use segment::index::VectorIndex;
-use segment::types::{HnswConfig, QuantizationConfig, SegmentType};
+use segment::types::{HnswConfig, PayloadKeyType, QuantizationConfig, SegmentType};
 use segment::vector_storage::VectorStorage;
 
 use crate::collection_manager::holders::segment_holder::{
@@ -33,6 +34,8 @@ pub struct VacuumOptimizer {
     collection_params: CollectionParams,
     hnsw_config: HnswConfig,
     quantization_config: Option<QuantizationConfig>,
+    defragment_key: Option<PayloadKeyType>,
+    storage_cipher: Option<Arc<StorageCipher>>,
     telemetry_durations_aggregator: Arc<Mutex<OperationDurationsAggregator>>,
 }
 
@@ -57,10 +60,24 @@ impl VacuumOptimizer {
             collection_params,
             hnsw_config,
             quantization_config,
+            defragment_key: None,
+            storage_cipher: None,
             telemetry_durations_aggregator: OperationDurationsAggregator::new(),
         }
     }
 
+    /// Group points by the value of `defragment_key` in optimized segments
+    pub fn with_defragment_key(mut self, defragment_key: Option<PayloadKeyType>) -> Self {
+        self.defragment_key = defragment_key;
+        self
+    }
+
+    /// Encrypt optimized segments with the key of the collection
+    pub fn with_storage_cipher(mut self, storage_cipher: Option<Arc<StorageCipher>>) -> Self {
+        self.storage_cipher = storage_cipher;
+        self
+    }
+
     fn worst_segment(
         &self,
         segments: LockedSegmentHolder,
@@ -191,6 +208,14 @@ impl SegmentOptimizer for VacuumOptimizer {
         &self.thresholds_config
     }
 
+    fn defragment_key(&self) -> Option<&PayloadKeyType> {
+        self.defragment_key.as_ref()
+    }
+
+    fn storage_cipher(&self) -> Option<Arc<StorageCipher>> {
+        self.storage_cipher.clone()
+    }
+
     fn check_condition(
         &self,
         segments: LockedSegmentHolder,
@@ -301,6 +326,7 @@ mod tests {
             temp_dir.path().to_owned(),
             CollectionParams {
                 vectors: VectorsConfig::Single(VectorParamsBuilder::new(4, Distance::Dot).build()),
+                storage_engine: None,
                 ..CollectionParams::empty()
             },
             Default::default(),
@@ -399,6 +425,7 @@ mod tests {
                     VectorParamsBuilder::new(vector2_dim, Distance::Dot).build(),
                 ),
             ])),
+            storage_engine: None,
             ..CollectionParams::empty()
         };
 
@@ -0,0 +1,119 @@
+//! Decides how many segments of a request are searched in parallel.
+//!
+//! Searching every segment in a separate task is the fastest way to serve a single request on an
+//! idle node, but under load requests compete for the same threads and wait for each other's
+//! tasks. So the number of tasks per request shrinks with the number of tasks already in flight,
+//! and small segments are grouped together, as scheduling them is not worth it.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::collection_manager::holders::segment_holder::LockedSegment;
+
+/// Min number of points searched by a task, smaller segments are searched together
+const MIN_POINTS_PER_TASK: usize = 10_000;
+
+/// Number of search tasks of all requests, which are spawned but not finished yet
+static IN_FLIGHT_SEARCH_TASKS: AtomicUsize = AtomicUsize::new(0);
+
+/// Counts a search task as in flight, from spawning it until it is finished or dropped
+pub struct InFlightSearchTask(());
+
+impl InFlightSearchTask {
+    pub fn start() -> Self {
+        IN_FLIGHT_SEARCH_TASKS.fetch_add(1, Ordering::Relaxed);
+        Self(())
+    }
+}
+
+impl Drop for InFlightSearchTask {
+    fn drop(&mut self) {
+        IN_FLIGHT_SEARCH_TASKS.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Number of points in the segment, used to balance search tasks.
+///
+/// Doesn't wait for the segment lock, to not block the async runtime. A segment which is being
+/// updated is assumed to be worth a separate task.
+pub fn segment_size_hint(segment: &LockedSegment) -> usize {
+    segment
+        .get()
+        .try_read()
+        .map_or(MIN_POINTS_PER_TASK, |segment| {
+            segment.available_point_count()
+        })
+}
+
+/// Distribute segments of a request between search tasks.
+///
+/// Returns offsets of the segments searched by each task.
+pub fn plan_search_tasks(segment_sizes: &[usize], search_threads: usize) -> Vec<Vec<usize>> {
+    let in_flight = IN_FLIGHT_SEARCH_TASKS.load(Ordering::Relaxed);
+    let tasks_count = search_tasks_count(segment_sizes, search_threads, in_flight);
+    distribute_segments(segment_sizes, tasks_count)
+}
+
+/// Use idle threads, but don't spawn more tasks than there are segments worth searching separately
+fn search_tasks_count(segment_sizes: &[usize], search_threads: usize, in_flight: usize) -> usize {
+    let idle_threads = search_threads.saturating_sub(in_flight).max(1);
+    let total_points: usize = segment_sizes.iter().sum();
+    let worth_searching_separately = total_points.div_ceil(MIN_POINTS_PER_TASK).max(1);
+    segment_sizes
+        .len()
+        .min(idle_threads)
+        .min(worth_searching_separately)
+}
+
+/// Assign the largest segments first, each to the task with the least points so far
+fn distribute_segments(segment_sizes: &[usize], tasks_count: usize) -> Vec<Vec<usize>> {
+    if segment_sizes.is_empty() || tasks_count == 0 {
+        return vec![];
+    }
+
+    let mut offsets: Vec<usize> = (0..segment_sizes.len()).collect();
+    offsets.sort_by_key(|&offset| std::cmp::Reverse(segment_sizes[offset]));
+
+    let mut tasks: Vec<(usize, Vec<usize>)> = vec![(0, vec![]); tasks_count];
+    for offset in offsets {
+        let (task_points, task_segments) = tasks
+            .iter_mut()
+            .min_by_key(|(task_points, _)| *task_points)
+            .expect("there is at least one task");
+        *task_points += segment_sizes[offset];
+        task_segments.push(offset);
+    }
+    tasks
+        .into_iter()
+        .map(|(_, mut task_segments)| {
+            task_segments.sort_unstable();
+            task_segments
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_tasks_count() {
+        let sizes = [100_000, 50_000, 20_000, 1_000];
+        // Idle node searches segments in parallel
+        assert_eq!(search_tasks_count(&sizes, 8, 0), 4);
+        // Busy node uses only idle threads, at least one
+        assert_eq!(search_tasks_count(&sizes, 8, 6), 2);
+        assert_eq!(search_tasks_count(&sizes, 8, 100), 1);
+        // Small segments are not worth separate tasks
+        assert_eq!(search_tasks_count(&[1_000, 2_000, 3_000], 8, 0), 1);
+        assert_eq!(search_tasks_count(&[], 8, 0), 0);
+    }
+
+    #[test]
+    fn test_distribute_segments() {
+        let tasks = distribute_segments(&[10, 60, 30, 20, 40], 2);
+        assert_eq!(tasks, vec![vec![1, 3], vec![0, 2, 4]]);
+
+        assert_eq!(distribute_segments(&[10, 20], 1), vec![vec![0, 1]]);
+        assert!(distribute_segments(&[], 1).is_empty());
+    }
+}
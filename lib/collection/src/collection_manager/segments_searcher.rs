@@ -1,18 +1,22 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
+use common::cpu::get_num_cpus;
 use common::types::ScoreType;
 use futures::future::try_join_all;
 use itertools::Itertools;
 use ordered_float::Float;
 use parking_lot::RwLock;
-use segment::common::operation_error::OperationError;
+use segment::blob_storage::BlobChunk;
+use segment::common::operation_error::{OperationError, OperationResult};
+use segment::common::request_usage;
 use segment::data_types::named_vectors::NamedVectors;
-use segment::data_types::query_context::QueryContext;
+use segment::data_types::query_context::{FilterPlans, QueryContext};
 use segment::data_types::vectors::{QueryVector, VectorStruct};
+use segment::entry::entry_point::SegmentEntry;
 use segment::types::{
-    Filter, Indexes, PointIdType, ScoredPoint, SearchParams, SegmentConfig, SeqNumberType,
-    WithPayload, WithPayloadInterface, WithVector,
+    Filter, Indexes, Payload, PayloadSelector, PointIdType, ScoredPoint, SearchParams,
+    SegmentConfig, SeqNumberType, WithPayload, WithPayloadInterface, WithVector,
 };
 use tinyvec::TinyVec;
 use tokio::runtime::Handle;
@@ -21,6 +25,9 @@ use tokio::task::JoinHandle;
 use super::holders::segment_holder::LockedSegmentHolder;
 use crate::collection_manager::holders::segment_holder::{LockedSegment, SegmentHolder};
 use crate::collection_manager::probabilistic_segment_search_sampling::find_search_sampling_over_point_distribution;
+use crate::collection_manager::search_parallelism::{
+    plan_search_tasks, segment_size_hint, InFlightSearchTask,
+};
 use crate::collection_manager::search_result_aggregator::BatchResultAggregator;
 use crate::common::stopping_guard::StoppingGuard;
 use crate::config::CollectionConfig;
@@ -45,15 +52,26 @@ type SegmentSearchExecutedResult = CollectionResult<(SegmentBatchSearchResult, V
 pub struct SegmentsSearcher {}
 
 impl SegmentsSearcher {
+    /// Wait for search tasks, each searching the segments with the given offsets.
+    ///
+    /// Results are returned in the order of segment offsets.
     async fn execute_searches(
-        searches: Vec<JoinHandle<SegmentSearchExecutedResult>>,
+        searches: Vec<JoinHandle<Vec<SegmentSearchExecutedResult>>>,
+        search_tasks: &[Vec<SegmentOffset>],
     ) -> CollectionResult<(BatchSearchResult, Vec<Vec<bool>>)> {
         let searches = try_join_all(searches);
-        let search_results_per_segment_res = searches.await?;
+        let search_results_per_task = searches.await?;
+
+        let mut search_results_per_segment_res: Vec<_> = search_tasks
+            .iter()
+            .flatten()
+            .zip(search_results_per_task.into_iter().flatten())
+            .collect();
+        search_results_per_segment_res.sort_unstable_by_key(|(segment_offset, _)| **segment_offset);
 
         let mut search_results_per_segment = vec![];
         let mut further_searches_per_segment = vec![];
-        for search_result in search_results_per_segment_res {
+        for (_, search_result) in search_results_per_segment_res {
             let (search_results, further_searches) = search_result?;
             debug_assert!(search_results.len() == further_searches.len());
             search_results_per_segment.push(search_results);
@@ -231,11 +249,13 @@ impl SegmentsSearcher {
         let query_context_acr = Arc::new(query_context);
 
         // Using block to ensure `segments` variable is dropped in the end of it
-        let (locked_segments, searches): (Vec<_>, Vec<_>) = {
+        let (locked_segments, search_tasks, searches) = {
             // Unfortunately, we have to do `segments.read()` twice, once in blocking task
             // and once here, due to `Send` bounds :/
             let segments_lock = segments.read();
-            let segments = segments_lock.non_appendable_then_appendable_segments();
+            let locked_segments = segments_lock
+                .non_appendable_then_appendable_segments()
+                .collect_vec();
 
             // Probabilistic sampling for the `limit` parameter avoids over-fetching points from segments.
             // e.g. 10 segments with limit 1000 would fetch 10000 points in total and discard 9000 points.
@@ -248,29 +268,46 @@ impl SegmentsSearcher {
                 && segments_lock.len() > 1
                 && query_context_acr.available_point_count() > 0;
 
-            segments
-                .map(|segment| {
+            // Search segments in as many parallel tasks, as the current load of the node allows.
+            // Search runtime has about as many threads as there are CPUs
+            let segment_sizes = locked_segments.iter().map(segment_size_hint).collect_vec();
+            let search_tasks = plan_search_tasks(&segment_sizes, get_num_cpus());
+
+            let searches = search_tasks
+                .iter()
+                .map(|segment_offsets| {
                     let query_context_arc_segment = query_context_acr.clone();
-                    let search = runtime_handle.spawn_blocking({
-                        let (segment, batch_request) = (segment.clone(), batch_request.clone());
-                        move || {
-                            search_in_segment(
-                                segment,
-                                batch_request,
-                                use_sampling,
-                                query_context_arc_segment,
-                            )
-                        }
-                    });
-                    (segment.clone(), search)
+                    let batch_request = batch_request.clone();
+                    let segments = segment_offsets
+                        .iter()
+                        .map(|&offset| locked_segments[offset].clone())
+                        .collect_vec();
+                    let in_flight = InFlightSearchTask::start();
+                    runtime_handle.spawn_blocking(move || {
+                        let _in_flight = in_flight;
+                        segments
+                            .into_iter()
+                            .map(|segment| {
+                                request_usage::measure(query_context_arc_segment.usage(), || {
+                                    search_in_segment(
+                                        segment,
+                                        batch_request.clone(),
+                                        use_sampling,
+                                        query_context_arc_segment.clone(),
+                                    )
+                                })
+                            })
+                            .collect::<Vec<_>>()
+                    })
                 })
-                .unzip()
+                .collect_vec();
+            (locked_segments, search_tasks, searches)
         };
 
         // perform search on all segments concurrently
-        // the resulting Vec is in the same order as the segment searches were provided.
+        // the resulting Vec is in the same order as the segments were provided.
         let (all_search_results_per_segment, further_results) =
-            Self::execute_searches(searches).await?;
+            Self::execute_searches(searches, &search_tasks).await?;
         debug_assert!(all_search_results_per_segment.len() == locked_segments.len());
 
         let (mut result_aggregator, searches_to_rerun) = Self::process_search_result_step1(
@@ -301,20 +338,31 @@ impl SegmentsSearcher {
                             .map(|batch_id| batch_request.searches[*batch_id].clone())
                             .collect(),
                     });
+                    let in_flight = InFlightSearchTask::start();
                     res.push(runtime_handle.spawn_blocking(move || {
-                        search_in_segment(
-                            segment,
-                            partial_batch_request,
-                            false,
-                            query_context_arc_segment,
-                        )
+                        let _in_flight = in_flight;
+                        vec![request_usage::measure(
+                            query_context_arc_segment.usage(),
+                            || {
+                                search_in_segment(
+                                    segment,
+                                    partial_batch_request,
+                                    false,
+                                    query_context_arc_segment.clone(),
+                                )
+                            },
+                        )]
                     }))
                 }
                 res
             };
+            // Re-run searches are rare, search each segment in a separate task
+            let secondary_search_tasks = (0..secondary_searches.len())
+                .map(|offset| vec![offset])
+                .collect_vec();
 
             let (secondary_search_results_per_segment, _) =
-                Self::execute_searches(secondary_searches).await?;
+                Self::execute_searches(secondary_searches, &secondary_search_tasks).await?;
 
             result_aggregator.update_point_versions(&secondary_search_results_per_segment);
 
@@ -346,29 +394,71 @@ impl SegmentsSearcher {
         points: &[PointIdType],
         with_payload: &WithPayload,
         with_vector: &WithVector,
+    ) -> CollectionResult<Vec<Record>> {
+        let usage = crate::common::request_usage::current();
+        request_usage::measure(usage.as_deref(), || {
+            let records = Self::retrieve_records(segments, points, with_payload, with_vector)?;
+            if let Some(usage) = &usage {
+                if with_payload.enable {
+                    usage.add_payloads_read(records.len());
+                }
+            }
+            Ok(records)
+        })
+    }
+
+    fn retrieve_records(
+        segments: &RwLock<SegmentHolder>,
+        points: &[PointIdType],
+        with_payload: &WithPayload,
+        with_vector: &WithVector,
     ) -> CollectionResult<Vec<Record>> {
         let mut point_version: HashMap<PointIdType, SeqNumberType> = Default::default();
         let mut point_records: HashMap<PointIdType, Record> = Default::default();
 
-        segments.read().read_points(points, |id, segment| {
-            let version = segment.point_version(id).ok_or_else(|| {
-                OperationError::service_error(format!("No version for point {id}"))
-            })?;
-            // If this point was not found yet or this segment have later version
-            if !point_version.contains_key(&id) || point_version[&id] < version {
+        let segments = segments.read();
+        segments.read_points_batched(points, |ids, segment| {
+            // Only read points, which were not found yet or have later version in this segment
+            let mut newer_points = Vec::with_capacity(ids.len());
+            for &id in ids {
+                let version = segment.point_version(id).ok_or_else(|| {
+                    OperationError::service_error(format!("No version for point {id}"))
+                })?;
+                if !point_version.contains_key(&id) || point_version[&id] < version {
+                    newer_points.push((id, version));
+                }
+            }
+
+            // Payloads of all points are read at once, which allows concurrent reads from disk
+            let payloads: Vec<Option<Payload>> = if with_payload.enable {
+                match &with_payload.payload_selector {
+                    Some(PayloadSelector::Patterns(selector)) => newer_points
+                        .iter()
+                        .map(|&(id, _)| segment.payload_projected(id, selector.projection()))
+                        .map(|payload| payload.map(Some))
+                        .collect::<OperationResult<_>>()?,
+                    selector => {
+                        let ids = newer_points.iter().map(|&(id, _)| id).collect::<Vec<_>>();
+                        segment
+                            .payloads(&ids)?
+                            .into_iter()
+                            .map(|payload| match selector {
+                                Some(selector) => Some(selector.process(payload)),
+                                None => Some(payload),
+                            })
+                            .collect()
+                    }
+                }
+            } else {
+                vec![None; newer_points.len()]
+            };
+
+            for ((id, version), payload) in newer_points.iter().copied().zip(payloads) {
                 point_records.insert(
                     id,
                     Record {
                         id,
-                        payload: if with_payload.enable {
-                            if let Some(selector) = &with_payload.payload_selector {
-                                Some(selector.process(segment.payload(id)?))
-                            } else {
-                                Some(segment.payload(id)?)
-                            }
-                        } else {
-                            None
-                        },
+                        payload,
                         vector: {
                             let vector: Option<VectorStruct> = match with_vector {
                                 WithVector::Bool(true) => Some(segment.all_vectors(id)?.into()),
@@ -382,15 +472,31 @@ impl SegmentsSearcher {
                                     }
                                     Some(selected_vectors.into())
                                 }
+                                WithVector::Exclude(_) => {
+                                    let config = segment.config();
+                                    let vector_names = config
+                                        .vector_data
+                                        .keys()
+                                        .chain(config.sparse_vector_data.keys())
+                                        .filter(|name| with_vector.is_selected(name));
+                                    let mut selected_vectors = NamedVectors::default();
+                                    for vector_name in vector_names {
+                                        if let Some(vector) = segment.vector(vector_name, id)? {
+                                            selected_vectors.insert(vector_name.into(), vector);
+                                        }
+                                    }
+                                    Some(selected_vectors.into())
+                                }
                             };
                             vector.map(Into::into)
                         },
                         shard_key: None,
+                        version: Some(version),
                     },
                 );
                 point_version.insert(id, version);
             }
-            Ok(true)
+            Ok(newer_points.len())
         })?;
 
         // Restore the order the ids came in
@@ -401,6 +507,33 @@ impl SegmentsSearcher {
 
         Ok(ordered_records)
     }
+
+    /// Read up to `limit` bytes of the point blob, starting from `offset`
+    ///
+    /// If the point is present in several segments, the blob of its latest version is read.
+    pub fn read_blob(
+        segments: &RwLock<SegmentHolder>,
+        point_id: PointIdType,
+        offset: usize,
+        limit: usize,
+    ) -> CollectionResult<Option<BlobChunk>> {
+        let mut latest: Option<(SeqNumberType, Option<BlobChunk>)> = None;
+
+        segments.read().read_points(&[point_id], |id, segment| {
+            let version = segment.point_version(id).ok_or_else(|| {
+                OperationError::service_error(format!("No version for point {id}"))
+            })?;
+            if latest
+                .as_ref()
+                .map_or(true, |(latest_version, _)| *latest_version < version)
+            {
+                latest = Some((version, segment.blob(id, offset, limit)?));
+            }
+            Ok(true)
+        })?;
+
+        Ok(latest.and_then(|(_, blob)| blob))
+    }
 }
 
 #[derive(PartialEq, Default, Debug)]
@@ -464,17 +597,18 @@ fn effective_limit(limit: usize, ef_limit: usize, poisson_sampling: usize) -> us
     poisson_sampling.max(ef_limit).min(limit)
 }
 
-/// Process sequentially contiguous batches
+/// Process a batch of searches in a single segment
+///
+/// Searches with the same parameters are executed together, regardless of their position in
+/// the batch. All searches share filter planning, so queries with the same filter estimate
+/// cardinality and compute filtered points only once.
 ///
 /// # Arguments
 ///
 /// * `segment` - Locked segment to search in
 /// * `request` - Batch of search requests
-/// * `total_points` - Number of points in all segments combined
 /// * `use_sampling` - If true, try to use probabilistic sampling
-/// * `is_stopped` - Atomic bool to check if search is stopped
-/// * `indexing_threshold` - If `indexed_only` is enabled, the search will skip
-///                          segments with more than this number Kb of un-indexed vectors
+/// * `query_context` - Additional context for the search
 ///
 /// # Returns
 ///
@@ -489,12 +623,10 @@ fn search_in_segment(
 ) -> CollectionResult<(Vec<Vec<ScoredPoint>>, Vec<bool>)> {
     let batch_size = request.searches.len();
 
-    let mut result: Vec<Vec<ScoredPoint>> = Vec::with_capacity(batch_size);
-    let mut further_results: Vec<bool> = Vec::with_capacity(batch_size); // if segment have more points to return
-    let mut vectors_batch: Vec<QueryVector> = vec![];
-    let mut prev_params = BatchSearchParams::default();
+    // Searches grouped by parameters, with their positions in the batch
+    let mut groups: Vec<(BatchSearchParams, Vec<usize>, Vec<QueryVector>)> = vec![];
 
-    for search_query in &request.searches {
+    for (position, search_query) in request.searches.iter().enumerate() {
         let with_payload_interface = search_query
             .with_payload
             .as_ref()
@@ -513,55 +645,52 @@ fn search_in_segment(
         let query = search_query.query.clone().into();
 
         // same params enables batching
-        if params == prev_params {
-            vectors_batch.push(query);
-        } else {
-            // different params means different batches
-            // execute what has been batched so far
-            if !vectors_batch.is_empty() {
-                let (mut res, mut further) = execute_batch_search(
-                    &segment,
-                    &vectors_batch,
-                    &prev_params,
-                    use_sampling,
-                    &query_context,
-                )?;
-                further_results.append(&mut further);
-                result.append(&mut res);
-                vectors_batch.clear()
+        match groups
+            .iter_mut()
+            .find(|(group_params, _, _)| *group_params == params)
+        {
+            Some((_, positions, vectors_batch)) => {
+                positions.push(position);
+                vectors_batch.push(query);
             }
-            // start new batch for current search query
-            vectors_batch.push(query);
-            prev_params = params;
+            None => groups.push((params, vec![position], vec![query])),
         }
     }
 
-    // run last batch if any
-    if !vectors_batch.is_empty() {
-        let (mut res, mut further) = execute_batch_search(
-            &segment,
-            &vectors_batch,
-            &prev_params,
+    // Plans are only valid while the segment can't change, so hold the lock for all groups
+    let locked_segment = segment.get();
+    let read_segment = locked_segment.read();
+    let filter_plans = FilterPlans::default();
+
+    let mut result: Vec<Vec<ScoredPoint>> = vec![vec![]; batch_size];
+    let mut further_results: Vec<bool> = vec![false; batch_size]; // if segment have more points to return
+
+    for (params, positions, vectors_batch) in &groups {
+        let (res, further) = execute_batch_search(
+            &*read_segment,
+            vectors_batch,
+            params,
             use_sampling,
             &query_context,
+            &filter_plans,
         )?;
-        further_results.append(&mut further);
-        result.append(&mut res);
+        for ((&position, res), further) in positions.iter().zip(res).zip(further) {
+            result[position] = res;
+            further_results[position] = further;
+        }
     }
 
     Ok((result, further_results))
 }
 
 fn execute_batch_search(
-    segment: &LockedSegment,
+    read_segment: &dyn SegmentEntry,
     vectors_batch: &[QueryVector],
     search_params: &BatchSearchParams,
     use_sampling: bool,
     query_context: &QueryContext,
+    filter_plans: &FilterPlans,
 ) -> CollectionResult<(Vec<Vec<ScoredPoint>>, Vec<bool>)> {
-    let locked_segment = segment.get();
-    let read_segment = locked_segment.read();
-
     let segment_points = read_segment.available_point_count();
     let segment_config = read_segment.config();
 
@@ -581,7 +710,9 @@ fn execute_batch_search(
     };
 
     let vectors_batch = &vectors_batch.iter().collect_vec();
-    let segment_query_context = query_context.get_segment_query_context();
+    let segment_query_context = query_context
+        .get_segment_query_context()
+        .with_filter_plans(filter_plans);
     let res = read_segment.search_batch(
         search_params.vector_name,
         vectors_batch,
@@ -593,6 +724,12 @@ fn execute_batch_search(
         segment_query_context,
     )?;
 
+    if let Some(usage) = query_context.usage() {
+        if search_params.with_payload.enable {
+            usage.add_payloads_read(res.iter().map(Vec::len).sum());
+        }
+    }
+
     let further_results = res
         .iter()
         .map(|batch_result| batch_result.len() == top)
@@ -702,6 +839,59 @@ mod tests {
         assert!(result[1].id == 3.into() || result[1].id == 11.into());
     }
 
+    #[tokio::test]
+    async fn test_segments_search_interleaved_batch() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+        let segment_holder = Arc::new(build_test_holder(dir.path()));
+
+        let req = |limit| CoreSearchRequest {
+            query: vec![1.0, 1.0, 1.0, 1.0].into(),
+            with_payload: None,
+            with_vector: None,
+            filter: None,
+            params: None,
+            limit,
+            score_threshold: None,
+            offset: 0,
+        };
+
+        // Searches with equal params are grouped, but results keep the order of the batch
+        let limits = [5, 3, 5, 1];
+        let batch_request = CoreSearchRequestBatch {
+            searches: limits.iter().map(|&limit| req(limit)).collect(),
+        };
+
+        let result = SegmentsSearcher::search(
+            segment_holder.clone(),
+            Arc::new(batch_request),
+            &Handle::current(),
+            false,
+            QueryContext::new(DEFAULT_INDEXING_THRESHOLD_KB),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result.len(), limits.len());
+        for (result, &limit) in result.iter().zip(&limits) {
+            let single = SegmentsSearcher::search(
+                segment_holder.clone(),
+                Arc::new(CoreSearchRequestBatch {
+                    searches: vec![req(limit)],
+                }),
+                &Handle::current(),
+                false,
+                QueryContext::new(DEFAULT_INDEXING_THRESHOLD_KB),
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(result.len(), limit);
+            let ids = |points: &[ScoredPoint]| points.iter().map(|p| p.id).collect_vec();
+            assert_eq!(ids(result), ids(&single[0]));
+        }
+    }
+
     #[tokio::test]
     async fn test_segments_search_sampling() {
         let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
@@ -741,7 +931,7 @@ mod tests {
             };
 
             let batch_request = CoreSearchRequestBatch {
-                searches: vec![req1.into(), req2.into()],
+                searches: vec![req1.try_into().unwrap(), req2.try_into().unwrap()],
             };
 
             let batch_request = Arc::new(batch_request);
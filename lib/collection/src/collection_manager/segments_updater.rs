@@ -8,15 +8,22 @@ use segment::common::operation_error::{OperationError, OperationResult};
 use segment::data_types::named_vectors::NamedVectors;
 use segment::data_types::vectors::{BatchVectorStruct, VectorStruct};
 use segment::entry::entry_point::SegmentEntry;
-use segment::json_path::JsonPath;
+use segment::json_patch::{apply_patch, PatchOperation};
+use segment::json_path::{JsonPath, JsonPathInterface as _};
 use segment::types::{
-    Filter, Payload, PayloadFieldSchema, PayloadKeyType, PayloadKeyTypeRef, PointIdType,
-    SeqNumberType,
+    Condition, FieldCondition, Filter, Match, MatchValue, Payload, PayloadFieldSchema,
+    PayloadKeyType, PayloadKeyTypeRef, PayloadSchemaType, PointIdType, SeqNumberType,
+    ValueVariants,
 };
+use serde_json::Value;
 
 use crate::collection_manager::holders::segment_holder::SegmentHolder;
+use crate::operations::blob_ops::BlobOperations;
 use crate::operations::payload_ops::PayloadOps;
-use crate::operations::point_ops::{PointInsertOperationsInternal, PointOperations, PointStruct};
+use crate::operations::point_ops::{
+    expires_at_key, ConditionalInsertOperationInternal, PointInsertOperationsInternal,
+    PointOperations, PointStruct, UpdateMode, EXPIRES_AT_KEY,
+};
 use crate::operations::types::{CollectionError, CollectionResult};
 use crate::operations::vector_ops::{PointVectors, VectorOperations};
 use crate::operations::FieldIndexOperations;
@@ -33,6 +40,34 @@ pub(crate) fn check_unprocessed_points(
     }
 }
 
+/// Checks that all given points exist and were last updated by operation `expected_version`
+pub(crate) fn check_point_versions(
+    segments: &SegmentHolder,
+    ids: &[PointIdType],
+    expected_version: SeqNumberType,
+) -> CollectionResult<()> {
+    for &id in ids {
+        let version = segments
+            .iter()
+            .filter_map(|(_, segment)| segment.get().read().point_version(id))
+            .max();
+        match version {
+            None => {
+                return Err(CollectionError::PointNotFound {
+                    missed_point_id: id,
+                })
+            }
+            Some(version) if version != expected_version => {
+                return Err(CollectionError::conflict(format!(
+                    "point {id} has version {version}, expected {expected_version}"
+                )));
+            }
+            Some(_) => {}
+        }
+    }
+    Ok(())
+}
+
 /// Tries to delete points from all segments, returns number of actually deleted points
 pub(crate) fn delete_points(
     segments: &SegmentHolder,
@@ -70,7 +105,7 @@ pub(crate) fn update_vectors(
         op_num,
         &ids,
         |id, write_segment| {
-            let vectors: VectorStruct = points_map[&id].vector.clone().into();
+            let vectors: VectorStruct = points_map[&id].vector.clone().try_into()?;
             let vectors = vectors.into_all_vectors();
             write_segment.update_vectors(op_num, id, vectors)
         },
@@ -140,6 +175,47 @@ pub(crate) fn overwrite_payload_by_filter(
     overwrite_payload(segments, op_num, payload, &affected_points)
 }
 
+/// Apply JSON Patch to the payload of each point.
+///
+/// Patched payloads are computed for all points before any of them is written, so a patch
+/// which can't be applied to one of the points rejects the whole operation.
+pub(crate) fn patch_payload(
+    segments: &SegmentHolder,
+    op_num: SeqNumberType,
+    patch: &[PatchOperation],
+    points: &[PointIdType],
+) -> CollectionResult<usize> {
+    let mut patched_payloads: HashMap<PointIdType, Payload> = HashMap::with_capacity(points.len());
+    segments.read_points(points, |id, segment| {
+        let mut payload = segment.payload(id)?;
+        apply_patch(&mut payload, patch)?;
+        patched_payloads.insert(id, payload);
+        Ok(true)
+    })?;
+    let found_points: HashSet<PointIdType> = patched_payloads.keys().copied().collect();
+    check_unprocessed_points(points, &found_points)?;
+
+    let updated_points = segments.apply_points_with_conditional_move(
+        op_num,
+        points,
+        |id, write_segment| write_segment.set_full_payload(op_num, id, &patched_payloads[&id]),
+        |segment| segment.get_indexed_fields().is_empty(),
+    )?;
+
+    check_unprocessed_points(points, &updated_points)?;
+    Ok(updated_points.len())
+}
+
+pub(crate) fn patch_payload_by_filter(
+    segments: &SegmentHolder,
+    op_num: SeqNumberType,
+    patch: &[PatchOperation],
+    filter: &Filter,
+) -> CollectionResult<usize> {
+    let affected_points = points_by_filter(segments, filter)?;
+    patch_payload(segments, op_num, patch, &affected_points)
+}
+
 pub(crate) fn set_payload(
     segments: &SegmentHolder,
     op_num: SeqNumberType,
@@ -259,6 +335,42 @@ pub(crate) fn clear_payload_by_filter(
     Ok(updated_points.len())
 }
 
+pub(crate) fn set_blob(
+    segments: &SegmentHolder,
+    op_num: SeqNumberType,
+    point_id: PointIdType,
+    blob: &[u8],
+) -> CollectionResult<usize> {
+    let points = [point_id];
+    let updated_points = segments.apply_points_with_conditional_move(
+        op_num,
+        &points,
+        |id, write_segment| write_segment.set_blob(op_num, id, blob),
+        // Blob storage of any segment can be updated in place
+        |_| true,
+    )?;
+
+    check_unprocessed_points(&points, &updated_points)?;
+    Ok(updated_points.len())
+}
+
+pub(crate) fn delete_blob(
+    segments: &SegmentHolder,
+    op_num: SeqNumberType,
+    point_id: PointIdType,
+) -> CollectionResult<usize> {
+    let points = [point_id];
+    let updated_points = segments.apply_points_with_conditional_move(
+        op_num,
+        &points,
+        |id, write_segment| write_segment.delete_blob(op_num, id),
+        |_| true,
+    )?;
+
+    check_unprocessed_points(&points, &updated_points)?;
+    Ok(updated_points.len())
+}
+
 pub(crate) fn create_field_index(
     segments: &SegmentHolder,
     op_num: SeqNumberType,
@@ -297,10 +409,30 @@ fn upsert_with_payload(
     let mut res = segment.upsert_point(op_num, point_id, vectors)?;
     if let Some(full_payload) = payload {
         res &= segment.set_full_payload(op_num, point_id, full_payload)?;
+        if full_payload.0.contains_key(EXPIRES_AT_KEY) {
+            ensure_expiry_index(segment, op_num)?;
+        }
     }
     Ok(res)
 }
 
+/// Index expiration moments of points under the reserved key, so that expired points can be
+/// excluded from requests without reading the payload
+fn ensure_expiry_index(
+    segment: &mut RwLockWriteGuard<dyn SegmentEntry>,
+    op_num: SeqNumberType,
+) -> OperationResult<()> {
+    let key = expires_at_key();
+    if !segment.get_indexed_fields().contains_key(&key) {
+        segment.create_field_index(
+            op_num,
+            &key,
+            Some(&PayloadFieldSchema::FieldType(PayloadSchemaType::Datetime)),
+        )?;
+    }
+    Ok(())
+}
+
 /// Sync points within a given [from_id; to_id) range
 ///
 /// 1. Retrieve existing points for a range
@@ -346,12 +478,12 @@ pub(crate) fn sync_points(
         };
         let payload = segment.payload(id)?;
         let point = id_to_point.get(&id).unwrap();
-        if point.get_vectors() != all_vectors {
+        if point.get_vectors()? != all_vectors {
             points_to_update.push(*point);
             Ok(true)
         } else {
-            let payload_match = match point.payload {
-                Some(ref p) => p == &payload,
+            let payload_match = match point.full_payload() {
+                Some(p) => *p == payload,
                 None => Payload::default() == payload,
             };
             if !payload_match {
@@ -403,8 +535,8 @@ where
                 write_segment,
                 op_num,
                 id,
-                point.get_vectors(),
-                point.payload.as_ref(),
+                point.get_vectors()?,
+                point.full_payload().as_deref(),
             )
         },
         |_| false,
@@ -433,8 +565,8 @@ where
                 &mut write_segment,
                 op_num,
                 point_id,
-                point.get_vectors(),
-                point.payload.as_ref(),
+                point.get_vectors()?,
+                point.full_payload().as_deref(),
             )? as usize;
         }
         RwLockWriteGuard::unlock_fair(write_segment);
@@ -443,42 +575,198 @@ where
     Ok(res)
 }
 
+/// Upsert points, skipping the ones which the update mode or the update filter don't permit:
+///
+/// - existing points are updated unless mode is `insert_only`, and only if they match the filter
+/// - new points are inserted unless mode is `update_only`
+///
+/// Existence and filter are checked within the same update operation, so the decision
+/// for each point can't be affected by concurrent updates of the shard.
+pub(crate) fn conditional_upsert(
+    segments: &SegmentHolder,
+    op_num: SeqNumberType,
+    points: Vec<PointStruct>,
+    update_mode: UpdateMode,
+    update_filter: Option<&Filter>,
+) -> CollectionResult<usize> {
+    let ids: Vec<PointIdType> = points.iter().map(|point| point.id).collect();
+    let existing_ids: HashSet<PointIdType> = segments
+        .iter()
+        .flat_map(|(_, segment)| {
+            let segment = segment.get();
+            let segment = segment.read();
+            ids.iter()
+                .copied()
+                .filter(|id| segment.has_point(*id))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    let updatable_ids: HashSet<PointIdType> = if update_mode == UpdateMode::InsertOnly {
+        HashSet::new()
+    } else if let Some(update_filter) = update_filter {
+        let filter = update_filter.merge(&Filter::new_must(Condition::HasId(
+            existing_ids.clone().into(),
+        )));
+        segments
+            .iter()
+            .flat_map(|(_, segment)| {
+                segment
+                    .get()
+                    .read()
+                    .read_filtered(None, None, Some(&filter))
+            })
+            .collect()
+    } else {
+        existing_ids.clone()
+    };
+
+    let points = points.iter().filter(|point| {
+        if existing_ids.contains(&point.id) {
+            updatable_ids.contains(&point.id)
+        } else {
+            update_mode != UpdateMode::UpdateOnly
+        }
+    });
+    upsert_points(segments, op_num, points)
+}
+
+/// Use `dedup_key` payload field as a logical key of the points.
+///
+/// Points, which have the same key value as an already stored point, take over the ID
+/// of the stored point, so that it is replaced instead of being duplicated.
+/// Of the points with the same key value within the operation, only the last one is kept.
+/// Only a single keyword, integer or boolean value is considered a key,
+/// points without it keep their own IDs.
+fn dedup_points_by_key(
+    segments: &SegmentHolder,
+    points: Vec<PointStruct>,
+    dedup_key: &JsonPath,
+) -> Vec<PointStruct> {
+    let key_value = |point: &PointStruct| -> Option<ValueVariants> {
+        let payload = point.payload.as_ref()?;
+        match dedup_key.value_get(&payload.0).as_slice() {
+            [Value::String(keyword)] => Some(ValueVariants::Keyword(keyword.clone())),
+            [Value::Number(number)] => number.as_i64().map(ValueVariants::Integer),
+            [Value::Bool(flag)] => Some(ValueVariants::Bool(*flag)),
+            _ => None,
+        }
+    };
+
+    // Key values are compared by their JSON representation, so that `"1"` and `1` differ
+    let mut last_by_key: HashMap<String, (ValueVariants, usize)> = HashMap::new();
+    let mut has_key = vec![false; points.len()];
+    for (index, point) in points.iter().enumerate() {
+        if let Some(value) = key_value(point) {
+            last_by_key.insert(value.to_value().to_string(), (value, index));
+            has_key[index] = true;
+        }
+    }
+
+    let mut replacement_ids: HashMap<usize, PointIdType> = HashMap::new();
+    for (value, index) in last_by_key.into_values() {
+        let filter = Filter::new_must(Condition::Field(FieldCondition::new_match(
+            dedup_key.clone(),
+            Match::Value(MatchValue { value }),
+        )));
+        let existing_id = segments
+            .iter()
+            .filter_map(|(_, segment)| {
+                segment
+                    .get()
+                    .read()
+                    .read_filtered(None, Some(1), Some(&filter))
+                    .into_iter()
+                    .next()
+            })
+            .min();
+        replacement_ids.insert(index, existing_id.unwrap_or(points[index].id));
+    }
+
+    points
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, mut point)| match replacement_ids.get(&index) {
+            Some(id) => {
+                point.id = *id;
+                Some(point)
+            }
+            // Point with a key, which is superseded by a later point of the operation
+            None if has_key[index] => None,
+            None => Some(point),
+        })
+        .collect()
+}
+
+fn points_from_insert_operation(
+    operation: PointInsertOperationsInternal,
+) -> CollectionResult<Vec<PointStruct>> {
+    let points = match operation {
+        PointInsertOperationsInternal::PointsBatch(batch) => {
+            let batch_vectors: BatchVectorStruct = batch.vectors.try_into()?;
+            let all_vectors = batch_vectors.into_all_vectors(batch.ids.len());
+            let vectors_iter = batch.ids.into_iter().zip(all_vectors);
+            match batch.payloads {
+                None => vectors_iter
+                    .map(|(id, vectors)| PointStruct {
+                        id,
+                        vector: VectorStruct::from(vectors).into(),
+                        payload: None,
+                        expires_at: None,
+                    })
+                    .collect(),
+                Some(payloads) => vectors_iter
+                    .zip(payloads)
+                    .map(|((id, vectors), payload)| PointStruct {
+                        id,
+                        vector: VectorStruct::from(vectors).into(),
+                        payload,
+                        expires_at: None,
+                    })
+                    .collect(),
+            }
+        }
+        PointInsertOperationsInternal::PointsList(points) => points,
+    };
+    Ok(points)
+}
+
 pub(crate) fn process_point_operation(
     segments: &RwLock<SegmentHolder>,
     op_num: SeqNumberType,
     point_operation: PointOperations,
 ) -> CollectionResult<usize> {
     match point_operation {
-        PointOperations::DeletePoints { ids, .. } => delete_points(&segments.read(), op_num, &ids),
+        // Version guards are resolved by the shard leader before the operation is replicated,
+        // versions of this replica are not compared, see `ShardReplicaSet::update_with_consistency`
+        PointOperations::DeletePoints { ids, if_version: _ } => {
+            delete_points(&segments.read(), op_num, &ids)
+        }
         PointOperations::UpsertPoints(operation) => {
-            let points: Vec<_> = match operation {
-                PointInsertOperationsInternal::PointsBatch(batch) => {
-                    let batch_vectors: BatchVectorStruct = batch.vectors.into();
-                    let all_vectors = batch_vectors.into_all_vectors(batch.ids.len());
-                    let vectors_iter = batch.ids.into_iter().zip(all_vectors);
-                    match batch.payloads {
-                        None => vectors_iter
-                            .map(|(id, vectors)| PointStruct {
-                                id,
-                                vector: VectorStruct::from(vectors).into(),
-                                payload: None,
-                            })
-                            .collect(),
-                        Some(payloads) => vectors_iter
-                            .zip(payloads)
-                            .map(|((id, vectors), payload)| PointStruct {
-                                id,
-                                vector: VectorStruct::from(vectors).into(),
-                                payload,
-                            })
-                            .collect(),
-                    }
-                }
-                PointInsertOperationsInternal::PointsList(points) => points,
-            };
+            let points = points_from_insert_operation(operation)?;
             let res = upsert_points(&segments.read(), op_num, points.iter())?;
             Ok(res)
         }
+        PointOperations::UpsertPointsConditional(ConditionalInsertOperationInternal {
+            points_op,
+            update_mode,
+            update_filter,
+            if_version: _,
+            dedup_key,
+        }) => {
+            let segments = segments.read();
+            let mut points = points_from_insert_operation(points_op)?;
+            if let Some(dedup_key) = &dedup_key {
+                points = dedup_points_by_key(&segments, points, dedup_key);
+            }
+            conditional_upsert(
+                &segments,
+                op_num,
+                points,
+                update_mode,
+                update_filter.as_ref(),
+            )
+        }
         PointOperations::DeletePointsByFilter(filter) => {
             delete_points_by_filter(&segments.read(), op_num, &filter)
         }
@@ -560,6 +848,28 @@ pub(crate) fn process_payload_operation(
                 })
             }
         }
+        PayloadOps::PatchPayload(pp) => {
+            if let Some(points) = pp.points {
+                patch_payload(&segments.read(), op_num, &pp.patch, &points)
+            } else if let Some(filter) = pp.filter {
+                patch_payload_by_filter(&segments.read(), op_num, &pp.patch, &filter)
+            } else {
+                Err(CollectionError::BadRequest {
+                    description: "No points or filter specified".to_string(),
+                })
+            }
+        }
+    }
+}
+
+pub(crate) fn process_blob_operation(
+    segments: &RwLock<SegmentHolder>,
+    op_num: SeqNumberType,
+    blob_operation: BlobOperations,
+) -> CollectionResult<usize> {
+    match blob_operation {
+        BlobOperations::SetBlob(op) => set_blob(&segments.read(), op_num, op.point_id, &op.blob),
+        BlobOperations::DeleteBlob(op) => delete_blob(&segments.read(), op_num, op.point_id),
     }
 }
 
@@ -71,11 +71,13 @@ fn test_update_proxy_segments() {
                 id: (100 * i + 1).into(),
                 vector: VectorStruct::from(vectors[0].clone()).into(),
                 payload: None,
+                expires_at: None,
             },
             PointStruct {
                 id: (100 * i + 2).into(),
                 vector: VectorStruct::from(vectors[1].clone()).into(),
                 payload: None,
+                expires_at: None,
             },
         ];
         upsert_points(&segments.read(), 1000 + i, &points).unwrap();
@@ -115,11 +117,13 @@ fn test_move_points_to_copy_on_write() {
             id: 1.into(),
             vector: VectorStruct::from(vec![0.0, 0.0, 0.0, 0.0]).into(),
             payload: None,
+            expires_at: None,
         },
         PointStruct {
             id: 2.into(),
             vector: VectorStruct::from(vec![0.0, 0.0, 0.0, 0.0]).into(),
             payload: None,
+            expires_at: None,
         },
     ];
 
@@ -130,11 +134,13 @@ fn test_move_points_to_copy_on_write() {
             id: 2.into(),
             vector: VectorStruct::from(vec![0.0, 0.0, 0.0, 0.0]).into(),
             payload: None,
+            expires_at: None,
         },
         PointStruct {
             id: 3.into(),
             vector: VectorStruct::from(vec![0.0, 0.0, 0.0, 0.0]).into(),
             payload: None,
+            expires_at: None,
         },
     ];
 
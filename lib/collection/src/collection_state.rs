@@ -1,5 +1,6 @@
 use std::collections::{HashMap, HashSet};
 
+use segment::types::ShardKey;
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
@@ -39,4 +40,18 @@ impl State {
             .copied()
             .unwrap_or(0)
     }
+
+    /// Shard key, the shard belongs to, if the collection uses custom sharding
+    pub fn shard_key(&self, shard_id: ShardId) -> Option<&ShardKey> {
+        self.shards_key_mapping
+            .iter()
+            .find_map(|(shard_key, shard_ids)| shard_ids.contains(&shard_id).then_some(shard_key))
+    }
+
+    /// Peers allowed to host replicas of the shard, `None` if the shard is not pinned
+    pub fn pinned_peers(&self, shard_id: ShardId) -> Option<&[PeerId]> {
+        self.config
+            .params
+            .pinned_peers(shard_id, self.shard_key(shard_id))
+    }
 }
@@ -0,0 +1,143 @@
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::pin::Pin;
+
+use bytes::Bytes;
+use futures::Stream;
+use tokio::sync::mpsc;
+
+/// Size of the chunks, the archive is sent in
+const ARCHIVE_CHUNK_SIZE: usize = 1024 * 1024;
+
+/// Number of chunks, which can be built ahead of the consumer
+const ARCHIVE_BUFFERED_CHUNKS: usize = 16;
+
+/// Tar archive, which is built while it is being consumed
+pub type ArchiveStream = Pin<Box<dyn Stream<Item = io::Result<Bytes>> + Send>>;
+
+/// Synchronous writer, which sends written data into the channel in chunks
+struct ChannelWriter {
+    sender: mpsc::Sender<io::Result<Bytes>>,
+    buffer: Vec<u8>,
+}
+
+impl ChannelWriter {
+    fn new(sender: mpsc::Sender<io::Result<Bytes>>) -> Self {
+        Self {
+            sender,
+            buffer: Vec::with_capacity(ARCHIVE_CHUNK_SIZE),
+        }
+    }
+
+    fn send_buffer(&mut self) -> io::Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        let chunk = std::mem::replace(&mut self.buffer, Vec::with_capacity(ARCHIVE_CHUNK_SIZE));
+        self.sender
+            .blocking_send(Ok(Bytes::from(chunk)))
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "archive stream is closed"))
+    }
+}
+
+impl Write for ChannelWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        if self.buffer.len() >= ARCHIVE_CHUNK_SIZE {
+            self.send_buffer()?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.send_buffer()
+    }
+}
+
+/// Archive content of the directory into a tar stream, without writing the archive to disk.
+///
+/// Archive is built in a blocking task, which is paused while the consumer is behind
+/// and stops once the stream is dropped. `guard` is held until archiving is finished,
+/// so it can be used to keep a temporary directory alive.
+///
+/// Failure of archiving is reported as the last item of the stream.
+pub fn archive_dir<G>(dir: PathBuf, guard: G) -> ArchiveStream
+where
+    G: Send + 'static,
+{
+    let (sender, mut receiver) = mpsc::channel(ARCHIVE_BUFFERED_CHUNKS);
+
+    tokio::task::spawn_blocking(move || {
+        let mut builder = tar::Builder::new(ChannelWriter::new(sender.clone()));
+        let result = builder
+            .append_dir_all(".", &dir)
+            .and_then(|()| builder.into_inner())
+            .and_then(|mut writer| writer.flush());
+
+        if let Err(err) = result {
+            if err.kind() != io::ErrorKind::BrokenPipe {
+                log::error!("Failed to archive {}: {err}", dir.display());
+            }
+            // Consumer might be gone already, nothing to report in that case
+            let _ = sender.blocking_send(Err(err));
+        }
+
+        drop(guard);
+    });
+
+    Box::pin(futures::stream::poll_fn(move |cx| receiver.poll_recv(cx)))
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::TryStreamExt as _;
+    use tempfile::Builder;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_archive_dir() {
+        let dir = Builder::new().prefix("archive_dir").tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("nested")).unwrap();
+        std::fs::write(dir.path().join("small.txt"), b"hello").unwrap();
+        let large = vec![42u8; 3 * ARCHIVE_CHUNK_SIZE + 1];
+        std::fs::write(dir.path().join("nested").join("large.bin"), &large).unwrap();
+
+        let dir_path = dir.path().to_path_buf();
+        let chunks: Vec<Bytes> = archive_dir(dir_path.clone(), dir)
+            .try_collect()
+            .await
+            .unwrap();
+        assert!(chunks.len() > 1);
+        // Guard is released once the archive is complete
+        assert!(!dir_path.exists());
+
+        let data: Vec<u8> = chunks.concat();
+        let mut archive = tar::Archive::new(data.as_slice());
+        let mut files: Vec<_> = archive
+            .entries()
+            .unwrap()
+            .map(|entry| {
+                let entry = entry.unwrap();
+                (entry.path().unwrap().into_owned(), entry.size())
+            })
+            .filter(|(_, size)| *size > 0)
+            .collect();
+        files.sort();
+        assert_eq!(
+            files,
+            vec![
+                (PathBuf::from("nested/large.bin"), large.len() as u64),
+                (PathBuf::from("small.txt"), 5),
+            ],
+        );
+    }
+
+    #[tokio::test]
+    async fn test_archive_dir_missing() {
+        let result: io::Result<Vec<Bytes>> = archive_dir(PathBuf::from("/nonexistent/dir"), ())
+            .try_collect()
+            .await;
+        assert!(result.is_err());
+    }
+}
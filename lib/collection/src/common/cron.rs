@@ -0,0 +1,249 @@
+//! Minimal support of cron expressions, used to schedule periodic background tasks.
+//!
+//! Standard 5-field format is supported: `minute hour day-of-month month day-of-week`.
+//! Each field is either `*`, a single value, a range `a-b`, a step `*/n` or `a-b/n`,
+//! or a comma separated list of those. Day of week is `0-7`, both `0` and `7` are Sunday.
+//!
+//! Same as in classic cron, if both day of month and day of week are restricted,
+//! a day matches if it matches either of them.
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+
+/// Schedules are not looked up further than this, to not loop forever on impossible dates like `0 0 31 2 *`
+const MAX_LOOKAHEAD_DAYS: i64 = 366 * 5;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    /// Both day fields are restricted, day matches if any of them matches
+    any_day_field: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronParseError(String);
+
+impl fmt::Display for CronParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid cron expression: {}", self.0)
+    }
+}
+
+impl std::error::Error for CronParseError {}
+
+impl FromStr for CronSchedule {
+    type Err = CronParseError;
+
+    fn from_str(expression: &str) -> Result<Self, Self::Err> {
+        let fields: Vec<_> = expression.split_whitespace().collect();
+        let [minutes, hours, days_of_month, months, days_of_week] = fields[..] else {
+            return Err(CronParseError(format!(
+                "expected 5 fields `minute hour day-of-month month day-of-week`, got {}",
+                fields.len(),
+            )));
+        };
+
+        let mut days_of_week_bits = parse_field(days_of_week, "day of week", 0, 7)?;
+        // Sunday can be either 0 or 7
+        if days_of_week_bits & (1 << 7) != 0 {
+            days_of_week_bits = (days_of_week_bits | 1) & !(1 << 7);
+        }
+
+        Ok(Self {
+            minutes: parse_field(minutes, "minute", 0, 59)?,
+            hours: parse_field(hours, "hour", 0, 23)?,
+            days_of_month: parse_field(days_of_month, "day of month", 1, 31)?,
+            months: parse_field(months, "month", 1, 12)?,
+            days_of_week: days_of_week_bits,
+            any_day_field: !days_of_month.starts_with('*') && !days_of_week.starts_with('*'),
+        })
+    }
+}
+
+fn parse_field(field: &str, name: &str, min: u32, max: u32) -> Result<u64, CronParseError> {
+    let parse_value = |value: &str| {
+        value
+            .parse::<u32>()
+            .map_err(|_| CronParseError(format!("invalid {name} value `{value}`")))
+    };
+
+    let mut bits = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, Some(parse_value(step)?)),
+            None => (part, None),
+        };
+
+        let (start, end) = match range.split_once('-') {
+            _ if range == "*" => (min, max),
+            Some((start, end)) => (parse_value(start)?, parse_value(end)?),
+            // `a/n` means from `a` till the end of the range
+            None if step.is_some() => (parse_value(range)?, max),
+            None => {
+                let value = parse_value(range)?;
+                (value, value)
+            }
+        };
+
+        if start < min || end > max || start > end {
+            return Err(CronParseError(format!(
+                "{name} `{part}` is out of range {min}-{max}",
+            )));
+        }
+
+        let step = step.unwrap_or(1);
+        if step == 0 {
+            return Err(CronParseError(format!("{name} step can't be zero")));
+        }
+
+        for value in (start..=end).step_by(step as usize) {
+            bits |= 1 << value;
+        }
+    }
+
+    Ok(bits)
+}
+
+fn contains(bits: u64, value: u32) -> bool {
+    bits & (1 << value) != 0
+}
+
+impl CronSchedule {
+    fn matches_day(&self, date: NaiveDate) -> bool {
+        let day_of_month = contains(self.days_of_month, date.day());
+        let day_of_week = contains(self.days_of_week, date.weekday().num_days_from_sunday());
+        if self.any_day_field {
+            day_of_month || day_of_week
+        } else {
+            day_of_month && day_of_week
+        }
+    }
+
+    /// Find the first scheduled time strictly after the given one, with a minute precision.
+    ///
+    /// Returns `None` if the schedule never matches in the next few years.
+    pub fn next_after(&self, time: NaiveDateTime) -> Option<NaiveDateTime> {
+        let mut next = time.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        let limit = next + Duration::days(MAX_LOOKAHEAD_DAYS);
+
+        while next < limit {
+            let date = next.date();
+
+            if !contains(self.months, date.month()) {
+                let (year, month) = if date.month() == 12 {
+                    (date.year() + 1, 1)
+                } else {
+                    (date.year(), date.month() + 1)
+                };
+                next = NaiveDate::from_ymd_opt(year, month, 1)?.and_time(NaiveTime::MIN);
+                continue;
+            }
+
+            if !self.matches_day(date) {
+                next = date.succ_opt()?.and_time(NaiveTime::MIN);
+                continue;
+            }
+
+            if !contains(self.hours, next.hour()) {
+                next = next.with_minute(0)? + Duration::hours(1);
+                continue;
+            }
+
+            if !contains(self.minutes, next.minute()) {
+                next += Duration::minutes(1);
+                continue;
+            }
+
+            return Some(next);
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    fn next(expression: &str, after: &str) -> Option<NaiveDateTime> {
+        expression
+            .parse::<CronSchedule>()
+            .unwrap()
+            .next_after(time(after))
+    }
+
+    #[test]
+    fn test_next_after() {
+        assert_eq!(
+            next("* * * * *", "2024-05-01 10:15:30"),
+            Some(time("2024-05-01 10:16:00")),
+        );
+        assert_eq!(
+            next("0 3 * * *", "2024-05-01 03:00:00"),
+            Some(time("2024-05-02 03:00:00")),
+        );
+        assert_eq!(
+            next("*/15 * * * *", "2024-05-01 10:50:00"),
+            Some(time("2024-05-01 11:00:00")),
+        );
+        assert_eq!(
+            next("30 1-5/2 * * *", "2024-05-01 03:30:00"),
+            Some(time("2024-05-01 05:30:00")),
+        );
+        assert_eq!(
+            next("0 0 1 1 *", "2024-05-01 00:00:00"),
+            Some(time("2025-01-01 00:00:00")),
+        );
+        // 2024-05-05 is Sunday
+        assert_eq!(
+            next("0 12 * * 7", "2024-05-01 00:00:00"),
+            Some(time("2024-05-05 12:00:00")),
+        );
+        assert_eq!(
+            next("0 12 * * 0", "2024-05-01 00:00:00"),
+            Some(time("2024-05-05 12:00:00")),
+        );
+        // Either day of month or day of week
+        assert_eq!(
+            next("0 0 3 * 0", "2024-05-01 00:00:00"),
+            Some(time("2024-05-03 00:00:00")),
+        );
+        assert_eq!(
+            next("0 0 29 2 *", "2024-03-01 00:00:00"),
+            Some(time("2028-02-29 00:00:00")),
+        );
+        assert_eq!(next("0 0 31 2 *", "2024-03-01 00:00:00"), None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for expression in [
+            "",
+            "* * * *",
+            "* * * * * *",
+            "60 * * * *",
+            "* 24 * * *",
+            "* * 0 * *",
+            "* * * 13 *",
+            "* * * * 8",
+            "*/0 * * * *",
+            "5-1 * * * *",
+            "a * * * *",
+        ] {
+            assert!(
+                expression.parse::<CronSchedule>().is_err(),
+                "{expression:?} should be invalid",
+            );
+        }
+    }
+}
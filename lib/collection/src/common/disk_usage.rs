@@ -0,0 +1,152 @@
+//! Disk space used by local shards, found by walking their directories.
+//!
+//! Files are classified by their location in the segment directory, so that segments of any
+//! kind, including proxied ones, are reported in the same way.
+
+use std::fs::{self, Metadata};
+use std::path::Path;
+
+use segment::segment_constructor::segment_constructor_base::{
+    PAYLOAD_INDEX_PATH, VECTOR_INDEX_PATH, VECTOR_STORAGE_PATH,
+};
+
+use crate::operations::types::{LocalShardDiskUsage, SegmentDataDiskUsage, SegmentDiskUsage};
+use crate::shards::local_shard::LocalShard;
+
+const PAYLOAD_STORAGE_PATH: &str = "payload_storage";
+
+/// Prefix of files of quantized vectors, which are stored along with original vectors
+const QUANTIZED_FILE_PREFIX: &str = "quantized.";
+
+/// Bytes used by all files in the directory and its subdirectories
+pub fn dir_disk_usage(path: &Path) -> u64 {
+    let mut total_bytes = 0;
+    walk_files(path, &mut |_, bytes| total_bytes += bytes);
+    total_bytes
+}
+
+pub fn shard_disk_usage(shard_path: &Path) -> LocalShardDiskUsage {
+    let segments_path = LocalShard::segments_path(shard_path);
+
+    let mut segments = Vec::new();
+    for entry in fs::read_dir(&segments_path).into_iter().flatten().flatten() {
+        if !entry.file_type().is_ok_and(|file_type| file_type.is_dir()) {
+            continue;
+        }
+        let data = segment_disk_usage(&entry.path());
+        segments.push(SegmentDiskUsage {
+            segment_id: entry.file_name().to_string_lossy().into_owned(),
+            total_bytes: data.total_bytes(),
+            data,
+        });
+    }
+
+    LocalShardDiskUsage {
+        total_bytes: dir_disk_usage(shard_path),
+        wal_bytes: dir_disk_usage(&LocalShard::wal_path(shard_path)),
+        segments,
+    }
+}
+
+fn segment_disk_usage(segment_path: &Path) -> SegmentDataDiskUsage {
+    let mut usage = SegmentDataDiskUsage::default();
+
+    walk_files(segment_path, &mut |path, bytes| {
+        let top_level = path
+            .strip_prefix(segment_path)
+            .ok()
+            .and_then(|relative| relative.iter().next())
+            .and_then(|name| name.to_str())
+            .unwrap_or_default();
+        let file_name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default();
+
+        // Vector directories are suffixed with the vector name
+        let counter = if top_level.starts_with(VECTOR_STORAGE_PATH) {
+            if file_name.starts_with(QUANTIZED_FILE_PREFIX) {
+                &mut usage.quantized_vectors_bytes
+            } else {
+                &mut usage.vector_storage_bytes
+            }
+        } else if top_level.starts_with(VECTOR_INDEX_PATH) {
+            &mut usage.vector_index_bytes
+        } else if top_level == PAYLOAD_STORAGE_PATH {
+            &mut usage.payload_storage_bytes
+        } else if top_level == PAYLOAD_INDEX_PATH {
+            &mut usage.payload_index_bytes
+        } else {
+            &mut usage.other_bytes
+        };
+        *counter += bytes;
+    });
+
+    usage
+}
+
+/// Call `f` with every file in the directory and its subdirectories, and bytes it uses.
+///
+/// Symlinks are not followed. Files removed while walking, e.g. by optimizers, are skipped.
+fn walk_files(path: &Path, f: &mut impl FnMut(&Path, u64)) {
+    let Ok(entries) = fs::read_dir(path) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        let path = entry.path();
+        if file_type.is_dir() {
+            walk_files(&path, f);
+        } else if file_type.is_file() {
+            if let Ok(metadata) = entry.metadata() {
+                f(&path, allocated_bytes(&metadata));
+            }
+        }
+    }
+}
+
+/// Bytes allocated on disk, which may be less than the length of sparse files
+#[cfg(unix)]
+fn allocated_bytes(metadata: &Metadata) -> u64 {
+    use std::os::unix::fs::MetadataExt as _;
+    metadata.blocks() * 512
+}
+
+#[cfg(not(unix))]
+fn allocated_bytes(metadata: &Metadata) -> u64 {
+    metadata.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shard_disk_usage() {
+        let dir = tempfile::Builder::new().prefix("shard").tempdir().unwrap();
+        let segment_path = dir.path().join("segments").join("segment-a");
+        let vectors_path = segment_path.join(format!("{VECTOR_STORAGE_PATH}-image"));
+        fs::create_dir_all(&vectors_path).unwrap();
+        fs::create_dir_all(dir.path().join("wal")).unwrap();
+
+        fs::write(vectors_path.join("vectors.dat"), vec![1; 10_000]).unwrap();
+        fs::write(vectors_path.join("quantized.data"), vec![1; 5_000]).unwrap();
+        fs::write(segment_path.join("segment.json"), "{}").unwrap();
+        fs::write(dir.path().join("wal").join("open-1"), vec![1; 5_000]).unwrap();
+
+        let usage = shard_disk_usage(dir.path());
+        assert_eq!(usage.segments.len(), 1);
+
+        let segment = &usage.segments[0];
+        assert_eq!(segment.segment_id, "segment-a");
+        assert!(segment.data.vector_storage_bytes >= 10_000);
+        assert!(segment.data.quantized_vectors_bytes >= 5_000);
+        assert_eq!(segment.data.payload_index_bytes, 0);
+
+        assert!(usage.wal_bytes >= 5_000);
+        assert_eq!(usage.total_bytes, usage.wal_bytes + segment.total_bytes);
+    }
+}
@@ -0,0 +1,191 @@
+//! Latency histograms of collection operations by operation type, across all collections.
+//!
+//! Unlike `OperationDurationStatistics`, buckets are configurable and always exported in full,
+//! so histograms of consecutive scrapes can be aggregated by Prometheus.
+
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use parking_lot::Mutex;
+
+use crate::operations::point_ops::PointOperations;
+use crate::operations::CollectionUpdateOperations;
+
+/// Upper boundaries of buckets in seconds, used if not configured
+pub const DEFAULT_LATENCY_BUCKETS_SECS: [f64; 14] = [
+    0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0,
+];
+
+static LATENCY_HISTOGRAMS: OnceLock<LatencyHistograms> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperationType {
+    Search,
+    Scroll,
+    Upsert,
+    Delete,
+    Payload,
+    Vectors,
+    FieldIndex,
+}
+
+impl OperationType {
+    pub const ALL: [Self; 7] = [
+        Self::Search,
+        Self::Scroll,
+        Self::Upsert,
+        Self::Delete,
+        Self::Payload,
+        Self::Vectors,
+        Self::FieldIndex,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Search => "search",
+            Self::Scroll => "scroll",
+            Self::Upsert => "upsert",
+            Self::Delete => "delete",
+            Self::Payload => "payload",
+            Self::Vectors => "vectors",
+            Self::FieldIndex => "field_index",
+        }
+    }
+}
+
+impl From<&CollectionUpdateOperations> for OperationType {
+    fn from(operation: &CollectionUpdateOperations) -> Self {
+        match operation {
+            CollectionUpdateOperations::PointOperation(operation) => match operation {
+                PointOperations::UpsertPoints(_)
+                | PointOperations::UpsertPointsConditional(_)
+                | PointOperations::SyncPoints(_) => Self::Upsert,
+                PointOperations::DeletePoints { .. } | PointOperations::DeletePointsByFilter(_) => {
+                    Self::Delete
+                }
+            },
+            CollectionUpdateOperations::VectorOperation(_) => Self::Vectors,
+            CollectionUpdateOperations::PayloadOperation(_)
+            | CollectionUpdateOperations::BlobOperation(_) => Self::Payload,
+            CollectionUpdateOperations::FieldIndexOperation(_) => Self::FieldIndex,
+        }
+    }
+}
+
+/// Cumulative histogram of one operation type
+#[derive(Debug, Clone, Default)]
+pub struct LatencyHistogram {
+    pub count: u64,
+    pub sum_secs: f64,
+    /// `[upper_boundary_secs, cumulative_count]` for each configured bucket
+    pub buckets: Vec<(f64, u64)>,
+}
+
+pub struct LatencyHistograms {
+    boundaries: Vec<f64>,
+    histograms: [Mutex<Histogram>; OperationType::ALL.len()],
+}
+
+#[derive(Default)]
+struct Histogram {
+    count: u64,
+    sum_secs: f64,
+    /// Non-cumulative count of operations in each bucket
+    counts: Vec<u64>,
+}
+
+impl LatencyHistograms {
+    fn new(mut boundaries: Vec<f64>) -> Self {
+        boundaries.retain(|boundary| boundary.is_finite());
+        boundaries.sort_by(f64::total_cmp);
+        boundaries.dedup();
+        let histograms = std::array::from_fn(|_| {
+            Mutex::new(Histogram {
+                counts: vec![0; boundaries.len()],
+                ..Default::default()
+            })
+        });
+        Self {
+            boundaries,
+            histograms,
+        }
+    }
+
+    /// Use the buckets for all operations, must be called before any operation is recorded.
+    ///
+    /// Returns false if histograms are already set up.
+    pub fn setup(boundaries_secs: Vec<f64>) -> bool {
+        LATENCY_HISTOGRAMS.set(Self::new(boundaries_secs)).is_ok()
+    }
+
+    /// Histograms shared by all collections
+    pub fn global() -> &'static Self {
+        LATENCY_HISTOGRAMS.get_or_init(|| Self::new(DEFAULT_LATENCY_BUCKETS_SECS.to_vec()))
+    }
+
+    pub fn record(&self, operation_type: OperationType, duration: Duration) {
+        let duration = duration.as_secs_f64();
+        let bucket = self
+            .boundaries
+            .iter()
+            .position(|&boundary| duration <= boundary);
+
+        let mut histogram = self.histograms[operation_type as usize].lock();
+        histogram.count += 1;
+        histogram.sum_secs += duration;
+        if let Some(bucket) = bucket {
+            histogram.counts[bucket] += 1;
+        }
+    }
+
+    /// Histograms of all operation types
+    pub fn snapshot(&self) -> Vec<(OperationType, LatencyHistogram)> {
+        OperationType::ALL
+            .into_iter()
+            .map(|operation_type| {
+                let histogram = self.histograms[operation_type as usize].lock();
+                let mut cumulative_count = 0;
+                let buckets = self
+                    .boundaries
+                    .iter()
+                    .zip(&histogram.counts)
+                    .map(|(&boundary, &count)| {
+                        cumulative_count += count;
+                        (boundary, cumulative_count)
+                    })
+                    .collect();
+                let histogram = LatencyHistogram {
+                    count: histogram.count,
+                    sum_secs: histogram.sum_secs,
+                    buckets,
+                };
+                (operation_type, histogram)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latency_histograms() {
+        let histograms = LatencyHistograms::new(vec![1.0, 0.1, f64::INFINITY, 0.1]);
+        assert_eq!(histograms.boundaries, vec![0.1, 1.0]);
+
+        histograms.record(OperationType::Payload, Duration::from_millis(50));
+        histograms.record(OperationType::Payload, Duration::from_millis(500));
+        histograms.record(OperationType::Payload, Duration::from_secs(5));
+        histograms.record(OperationType::Search, Duration::from_millis(50));
+
+        let (_, payload) = histograms
+            .snapshot()
+            .into_iter()
+            .find(|(operation_type, _)| *operation_type == OperationType::Payload)
+            .unwrap();
+        assert_eq!(payload.count, 3);
+        assert_eq!(payload.buckets, vec![(0.1, 1), (1.0, 2)]);
+        assert!((payload.sum_secs - 5.55).abs() < 1e-9);
+    }
+}
@@ -1,13 +1,23 @@
+pub mod archive_stream;
 pub mod batching;
+pub mod cron;
+pub mod disk_usage;
 pub mod eta_calculator;
 pub mod fetch_vectors;
 pub mod file_utils;
 pub mod is_ready;
+pub mod latency_histograms;
+pub mod request_usage;
 pub mod retrieve_request_trait;
+pub mod segment_storage;
 pub mod sha_256;
+pub mod slow_log;
+pub mod snapshot_encryption;
 pub mod snapshot_stream;
 pub mod snapshots_manager;
 pub mod stoppable_task;
 pub mod stoppable_task_async;
 pub mod stopping_guard;
+pub mod storage_encryption;
 pub mod validate_snapshot_archive;
+pub mod wal_encryption;
@@ -0,0 +1,49 @@
+//! Resources used by local shards to serve a search or scroll request, reported on demand.
+
+use std::future::Future;
+use std::sync::Arc;
+
+use segment::common::request_usage::{RequestUsage, RequestUsageReport};
+
+tokio::task_local! {
+    /// Usage of the request served by the current task
+    static REQUEST_USAGE: Arc<RequestUsage>;
+}
+
+/// Usage to measure local shards into, if requested for the request of the current task
+pub fn current() -> Option<Arc<RequestUsage>> {
+    REQUEST_USAGE.try_with(Arc::clone).ok()
+}
+
+/// Run the future, and report resources used by local shards, if `enabled`
+pub async fn measure<F: Future>(
+    enabled: bool,
+    future: F,
+) -> (F::Output, Option<RequestUsageReport>) {
+    if !enabled {
+        return (future.await, None);
+    }
+
+    let usage = Arc::new(RequestUsage::default());
+    let output = REQUEST_USAGE.scope(usage.clone(), future).await;
+    (output, Some(usage.report()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_measure() {
+        let (output, report) = measure(true, async {
+            current().unwrap().add_vectors_scored(3);
+            1
+        })
+        .await;
+        assert_eq!(output, 1);
+        assert_eq!(report.unwrap().vectors_scored, 3);
+
+        let (_, report) = measure(false, async { assert!(current().is_none()) }).await;
+        assert!(report.is_none());
+    }
+}
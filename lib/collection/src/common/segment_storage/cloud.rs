@@ -0,0 +1,159 @@
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use async_trait::async_trait;
+use futures::{StreamExt, TryStreamExt};
+
+use super::{AzureConfig, GcsConfig, SegmentStorage};
+use crate::common::snapshots_manager::S3Config;
+use crate::operations::snapshot_storage_ops;
+use crate::operations::types::{CollectionError, CollectionResult};
+
+/// Keeps segment files in an object storage bucket.
+///
+/// Every file of the segment directory is stored as a separate object under `<key>/<relative path>`.
+pub struct CloudSegmentStorage {
+    name: &'static str,
+    client: Box<dyn object_store::ObjectStore>,
+}
+
+impl CloudSegmentStorage {
+    pub fn new_s3(s3_config: &S3Config) -> CollectionResult<Self> {
+        Ok(Self {
+            name: "s3",
+            client: s3_config.build_client()?,
+        })
+    }
+
+    pub fn new_gcs(gcs_config: &GcsConfig) -> CollectionResult<Self> {
+        Ok(Self {
+            name: "gcs",
+            client: gcs_config.build_client()?,
+        })
+    }
+
+    pub fn new_azure(azure_config: &AzureConfig) -> CollectionResult<Self> {
+        Ok(Self {
+            name: "azure",
+            client: azure_config.build_client()?,
+        })
+    }
+
+    fn prefix(key: &str) -> object_store::path::Path {
+        object_store::path::Path::from(key.trim_matches('/'))
+    }
+
+    async fn list_objects(
+        &self,
+        prefix: &object_store::path::Path,
+    ) -> CollectionResult<Vec<object_store::ObjectMeta>> {
+        self.client
+            .list(Some(prefix))
+            .try_collect()
+            .await
+            .map_err(|e| {
+                CollectionError::object_storage_error(format!(
+                    "Failed to list objects under {prefix}: {e}"
+                ))
+            })
+    }
+}
+
+#[async_trait]
+impl SegmentStorage for CloudSegmentStorage {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn is_remote(&self) -> bool {
+        true
+    }
+
+    async fn upload_segment(&self, segment_path: &Path, key: &str) -> CollectionResult<()> {
+        // Remove leftovers of the previous version of the segment, if any
+        self.delete_segment(key).await?;
+
+        let files = super::list_files_recursive(segment_path)?;
+        for relative_path in files {
+            let target_path = Path::new(key.trim_matches('/')).join(&relative_path);
+            snapshot_storage_ops::multipart_upload(
+                self.client.as_ref(),
+                &segment_path.join(&relative_path),
+                &target_path,
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    async fn download_segment(&self, key: &str, target_path: &Path) -> CollectionResult<()> {
+        let prefix = Self::prefix(key);
+        let objects = self.list_objects(&prefix).await?;
+        if objects.is_empty() {
+            return Err(CollectionError::not_found(format!("Segment {key}")));
+        }
+
+        for meta in objects {
+            let Some(relative_path) = meta.location.prefix_match(&prefix) else {
+                continue;
+            };
+            let relative_path = relative_path
+                .map(|part| part.as_ref().to_string())
+                .collect::<Vec<_>>()
+                .join("/");
+            snapshot_storage_ops::download_snapshot(
+                self.client.as_ref(),
+                Path::new(meta.location.as_ref()),
+                &target_path.join(relative_path),
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    async fn delete_segment(&self, key: &str) -> CollectionResult<bool> {
+        let objects = self.list_objects(&Self::prefix(key)).await?;
+        if objects.is_empty() {
+            return Ok(false);
+        }
+        for meta in objects {
+            self.client.delete(&meta.location).await.map_err(|e| {
+                CollectionError::object_storage_error(format!(
+                    "Failed to delete {}: {e}",
+                    meta.location
+                ))
+            })?;
+        }
+        Ok(true)
+    }
+
+    async fn segment_exists(&self, key: &str) -> CollectionResult<bool> {
+        let mut stream = self.client.list(Some(&Self::prefix(key)));
+        Ok(stream
+            .next()
+            .await
+            .transpose()
+            .map_err(|e| {
+                CollectionError::object_storage_error(format!("Failed to check segment {key}: {e}"))
+            })?
+            .is_some())
+    }
+
+    async fn list_segments(&self, prefix: &str) -> CollectionResult<Vec<String>> {
+        let prefix = Self::prefix(prefix);
+        let keys = self
+            .list_objects(&prefix)
+            .await?
+            .into_iter()
+            .filter(|meta| meta.location.filename() == Some("segment.json"))
+            .filter_map(|meta| {
+                let location = meta.location.as_ref();
+                location
+                    .strip_suffix("/segment.json")
+                    .map(|key| key.to_string())
+            })
+            .filter(|key| super::key_has_prefix(key, prefix.as_ref()))
+            .collect::<BTreeSet<_>>();
+        Ok(keys.into_iter().collect())
+    }
+}
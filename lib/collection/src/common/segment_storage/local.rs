@@ -0,0 +1,215 @@
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use fs_extra::dir::CopyOptions;
+
+use super::SegmentStorage;
+use crate::operations::types::{CollectionError, CollectionResult};
+
+/// Keeps segment files in a directory of the local file system.
+///
+/// Useful to move cold segments to a cheaper disk, mounted to the node.
+pub struct LocalSegmentStorage {
+    base_path: PathBuf,
+}
+
+impl LocalSegmentStorage {
+    pub fn new(base_path: impl Into<PathBuf>) -> Self {
+        Self {
+            base_path: base_path.into(),
+        }
+    }
+
+    /// Resolve segment key into a directory path, making sure it stays inside of the base path
+    fn segment_path(&self, key: &str) -> CollectionResult<PathBuf> {
+        let key = key.trim_matches('/');
+        if key.is_empty() || key.split('/').any(|part| part == ".." || part == ".") {
+            return Err(CollectionError::bad_input(format!(
+                "Invalid segment key: {key}"
+            )));
+        }
+        Ok(self.base_path.join(key))
+    }
+}
+
+fn copy_dir(from: &Path, to: &Path) -> CollectionResult<()> {
+    std::fs::create_dir_all(to)?;
+    let options = CopyOptions::new().content_only(true).overwrite(true);
+    fs_extra::dir::copy(from, to, &options).map_err(|err| {
+        CollectionError::service_error(format!(
+            "Can't copy segment from {} to {} due to {}",
+            from.display(),
+            to.display(),
+            err
+        ))
+    })?;
+    Ok(())
+}
+
+#[async_trait]
+impl SegmentStorage for LocalSegmentStorage {
+    fn name(&self) -> &'static str {
+        "local"
+    }
+
+    fn is_remote(&self) -> bool {
+        false
+    }
+
+    async fn upload_segment(&self, segment_path: &Path, key: &str) -> CollectionResult<()> {
+        let target_path = self.segment_path(key)?;
+        let source_path = segment_path.to_path_buf();
+
+        // Copy into a temporary directory first, so that an interrupted upload
+        // never leaves a partially written segment under the final key
+        let tmp_path = target_path.with_extension("tmp");
+
+        tokio::task::spawn_blocking(move || {
+            if tmp_path.exists() {
+                std::fs::remove_dir_all(&tmp_path)?;
+            }
+            copy_dir(&source_path, &tmp_path)?;
+            if target_path.exists() {
+                std::fs::remove_dir_all(&target_path)?;
+            }
+            std::fs::rename(&tmp_path, &target_path)?;
+            Ok::<_, CollectionError>(())
+        })
+        .await??;
+
+        Ok(())
+    }
+
+    async fn download_segment(&self, key: &str, target_path: &Path) -> CollectionResult<()> {
+        let source_path = self.segment_path(key)?;
+        if !source_path.is_dir() {
+            return Err(CollectionError::not_found(format!("Segment {key}")));
+        }
+
+        let target_path = target_path.to_path_buf();
+        tokio::task::spawn_blocking(move || copy_dir(&source_path, &target_path)).await??;
+
+        Ok(())
+    }
+
+    async fn delete_segment(&self, key: &str) -> CollectionResult<bool> {
+        let path = self.segment_path(key)?;
+        if !path.exists() {
+            return Ok(false);
+        }
+        tokio::fs::remove_dir_all(&path).await?;
+        Ok(true)
+    }
+
+    async fn segment_exists(&self, key: &str) -> CollectionResult<bool> {
+        Ok(self.segment_path(key)?.is_dir())
+    }
+
+    async fn list_segments(&self, prefix: &str) -> CollectionResult<Vec<String>> {
+        let base_path = self.base_path.clone();
+        let prefix = prefix.trim_matches('/').to_string();
+
+        let keys = tokio::task::spawn_blocking(move || {
+            if !base_path.exists() {
+                return Ok(Vec::new());
+            }
+            // Segment directories are the parents of the `segment.json` files
+            let keys = super::list_files_recursive(&base_path)?
+                .into_iter()
+                .filter(|path| {
+                    path.file_name()
+                        .map_or(false, |name| name == "segment.json")
+                })
+                .filter_map(|path| path.parent().map(|dir| dir.to_string_lossy().to_string()))
+                .filter(|key| super::key_has_prefix(key, &prefix))
+                .collect::<Vec<_>>();
+            Ok::<_, CollectionError>(keys)
+        })
+        .await??;
+
+        Ok(keys)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::Builder;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_local_segment_storage_roundtrip() {
+        let segment_dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let storage_dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+        let restore_dir = Builder::new().prefix("restore_dir").tempdir().unwrap();
+
+        std::fs::write(segment_dir.path().join("segment.json"), b"{}").unwrap();
+        std::fs::create_dir_all(segment_dir.path().join("vector_storage")).unwrap();
+        std::fs::write(
+            segment_dir
+                .path()
+                .join("vector_storage")
+                .join("vectors.dat"),
+            b"vectors",
+        )
+        .unwrap();
+
+        let storage = LocalSegmentStorage::new(storage_dir.path());
+        let key = "collection/0/segment-a";
+
+        assert!(!storage.segment_exists(key).await.unwrap());
+        storage
+            .upload_segment(segment_dir.path(), key)
+            .await
+            .unwrap();
+        assert!(storage.segment_exists(key).await.unwrap());
+
+        assert_eq!(
+            storage.list_segments("collection/0").await.unwrap(),
+            vec![key.to_string()],
+        );
+        assert!(storage.list_segments("other").await.unwrap().is_empty());
+
+        // Keys under sibling prefixes are not listed
+        let sibling_key = "collection/01/segment-b";
+        storage
+            .upload_segment(segment_dir.path(), sibling_key)
+            .await
+            .unwrap();
+        assert_eq!(
+            storage.list_segments("collection/0").await.unwrap(),
+            vec![key.to_string()],
+        );
+        assert_eq!(
+            storage.list_segments("collection/01/").await.unwrap(),
+            vec![sibling_key.to_string()],
+        );
+        assert_eq!(storage.list_segments("collection").await.unwrap().len(), 2);
+        assert!(storage.delete_segment(sibling_key).await.unwrap());
+
+        storage
+            .download_segment(key, restore_dir.path())
+            .await
+            .unwrap();
+        let restored = std::fs::read(
+            restore_dir
+                .path()
+                .join("vector_storage")
+                .join("vectors.dat"),
+        )
+        .unwrap();
+        assert_eq!(restored, b"vectors");
+
+        assert!(storage.delete_segment(key).await.unwrap());
+        assert!(!storage.delete_segment(key).await.unwrap());
+    }
+
+    #[test]
+    fn test_local_segment_storage_rejects_escaping_keys() {
+        let storage = LocalSegmentStorage::new("/tmp/segments");
+        assert!(storage.segment_path("../etc").is_err());
+        assert!(storage.segment_path("a/./b").is_err());
+        assert!(storage.segment_path("").is_err());
+        assert!(storage.segment_path("a/b").is_ok());
+    }
+}
@@ -0,0 +1,228 @@
+//! Storage backends for segment data.
+//!
+//! Segments are always opened from the local file system, but their files might be kept in a
+//! different place while the segment is not in use. [`SegmentStorage`] abstracts the place where
+//! segment files are persisted, so that cold segments can live in an object storage bucket while
+//! the node only keeps their metadata and a local cache.
+
+mod cloud;
+mod local;
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+pub use cloud::CloudSegmentStorage;
+pub use local::LocalSegmentStorage;
+use object_store::azure::MicrosoftAzureBuilder;
+use object_store::gcp::GoogleCloudStorageBuilder;
+use serde::Deserialize;
+
+use crate::common::snapshots_manager::{retry_config, S3Config};
+use crate::operations::types::{CollectionError, CollectionResult};
+
+/// Default sub-directory of the storage path, used by the local backend
+pub const DEFAULT_SEGMENT_STORAGE_DIR: &str = "segments_storage";
+
+/// Default sub-directory of the storage path, used to cache segments fetched from remote storage
+pub const DEFAULT_SEGMENT_CACHE_DIR: &str = "segments_cache";
+
+#[derive(Clone, Deserialize, Debug, Default)]
+pub struct SegmentStorageConfig {
+    #[serde(default)]
+    pub backend: SegmentStorageBackendType,
+    /// Directory for the local backend.
+    /// If not set, `<storage_path>/segments_storage` is used.
+    #[serde(default)]
+    pub local_path: Option<String>,
+    /// Directory to cache segments fetched from a remote backend.
+    /// If not set, `<storage_path>/segments_cache` is used.
+    #[serde(default)]
+    pub cache_path: Option<String>,
+    #[serde(default)]
+    pub s3_config: Option<S3Config>,
+    #[serde(default)]
+    pub gcs_config: Option<GcsConfig>,
+    #[serde(default)]
+    pub azure_config: Option<AzureConfig>,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SegmentStorageBackendType {
+    #[default]
+    Local,
+    S3,
+    Gcs,
+    Azure,
+}
+
+#[derive(Clone, Deserialize, Debug, Default)]
+pub struct GcsConfig {
+    pub bucket: String,
+    pub service_account_path: Option<String>,
+    /// Number of retries of failed requests, including parts of multipart uploads
+    pub max_retries: Option<usize>,
+}
+
+impl GcsConfig {
+    /// Create object storage client for the configured bucket
+    pub fn build_client(&self) -> CollectionResult<Box<dyn object_store::ObjectStore>> {
+        let mut builder = GoogleCloudStorageBuilder::new()
+            .with_bucket_name(&self.bucket)
+            .with_retry(retry_config(self.max_retries));
+        if let Some(service_account_path) = &self.service_account_path {
+            builder = builder.with_service_account_path(service_account_path);
+        }
+        let client = builder.build().map_err(|e| {
+            CollectionError::service_error(format!("Failed to create GCS client: {}", e))
+        })?;
+        Ok(Box::new(client))
+    }
+}
+
+#[derive(Clone, Deserialize, Debug, Default)]
+pub struct AzureConfig {
+    pub account: String,
+    pub container: String,
+    pub access_key: Option<String>,
+}
+
+impl AzureConfig {
+    /// Create object storage client for the configured container
+    pub fn build_client(&self) -> CollectionResult<Box<dyn object_store::ObjectStore>> {
+        let mut builder = MicrosoftAzureBuilder::new()
+            .with_account(&self.account)
+            .with_container_name(&self.container);
+        if let Some(access_key) = &self.access_key {
+            builder = builder.with_access_key(access_key);
+        }
+        let client = builder.build().map_err(|e| {
+            CollectionError::service_error(format!("Failed to create Azure client: {}", e))
+        })?;
+        Ok(Box::new(client))
+    }
+}
+
+/// Place where segment files are persisted.
+///
+/// Segments are addressed by a string key, which is usually `<collection>/<shard>/<segment uuid>`.
+/// All files of the segment directory are stored under this key, preserving relative paths.
+#[async_trait]
+pub trait SegmentStorage {
+    /// Human-readable name of the backend, used in logs and telemetry
+    fn name(&self) -> &'static str;
+
+    /// Whether segments stored in this backend have to be copied into a local cache before use
+    fn is_remote(&self) -> bool;
+
+    /// Persist all files of the segment directory under the given key.
+    /// Existing segment with the same key is overwritten.
+    async fn upload_segment(&self, segment_path: &Path, key: &str) -> CollectionResult<()>;
+
+    /// Restore files of the segment into `target_path` directory
+    async fn download_segment(&self, key: &str, target_path: &Path) -> CollectionResult<()>;
+
+    /// Remove all files of the segment.
+    /// Returns `false` if there was no segment with the given key.
+    async fn delete_segment(&self, key: &str) -> CollectionResult<bool>;
+
+    async fn segment_exists(&self, key: &str) -> CollectionResult<bool>;
+
+    /// List keys of all stored segments, which start with the given prefix
+    async fn list_segments(&self, prefix: &str) -> CollectionResult<Vec<String>>;
+}
+
+pub type SegmentStorageSS = dyn SegmentStorage + Send + Sync;
+
+impl SegmentStorageConfig {
+    /// Resolve directories, which are not set explicitly, relative to the storage path
+    pub fn with_storage_path(mut self, storage_path: &Path) -> Self {
+        self.local_path.get_or_insert_with(|| {
+            storage_path
+                .join(DEFAULT_SEGMENT_STORAGE_DIR)
+                .to_string_lossy()
+                .into_owned()
+        });
+        self.cache_path.get_or_insert_with(|| {
+            storage_path
+                .join(DEFAULT_SEGMENT_CACHE_DIR)
+                .to_string_lossy()
+                .into_owned()
+        });
+        self
+    }
+
+    /// Directory used to cache segments fetched from a remote backend
+    pub fn cache_path(&self) -> PathBuf {
+        self.cache_path
+            .as_ref()
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_SEGMENT_CACHE_DIR))
+    }
+
+    /// Instantiate the backend described by this config
+    pub fn build(&self) -> CollectionResult<Arc<SegmentStorageSS>> {
+        let storage: Arc<SegmentStorageSS> = match self.backend {
+            SegmentStorageBackendType::Local => {
+                let path = self
+                    .local_path
+                    .as_ref()
+                    .map(PathBuf::from)
+                    .unwrap_or_else(|| PathBuf::from(DEFAULT_SEGMENT_STORAGE_DIR));
+                Arc::new(LocalSegmentStorage::new(path))
+            }
+            SegmentStorageBackendType::S3 => {
+                let s3_config = self.s3_config.as_ref().ok_or_else(|| {
+                    CollectionError::bad_input("`s3_config` is required for S3 segment storage")
+                })?;
+                Arc::new(CloudSegmentStorage::new_s3(s3_config)?)
+            }
+            SegmentStorageBackendType::Gcs => {
+                let gcs_config = self.gcs_config.as_ref().ok_or_else(|| {
+                    CollectionError::bad_input("`gcs_config` is required for GCS segment storage")
+                })?;
+                Arc::new(CloudSegmentStorage::new_gcs(gcs_config)?)
+            }
+            SegmentStorageBackendType::Azure => {
+                let azure_config = self.azure_config.as_ref().ok_or_else(|| {
+                    CollectionError::bad_input(
+                        "`azure_config` is required for Azure segment storage",
+                    )
+                })?;
+                Arc::new(CloudSegmentStorage::new_azure(azure_config)?)
+            }
+        };
+        Ok(storage)
+    }
+}
+
+/// Whether the segment key is under the prefix, whole parts of the path are matched only
+///
+/// So that the prefix `collection/0` doesn't match keys of `collection/01`.
+pub(super) fn key_has_prefix(key: &str, prefix: &str) -> bool {
+    prefix.is_empty()
+        || key
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+/// Collect paths of all files inside of the directory, relative to it
+pub(super) fn list_files_recursive(dir: &Path) -> CollectionResult<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut stack = vec![dir.to_path_buf()];
+
+    while let Some(current) = stack.pop() {
+        for entry in std::fs::read_dir(&current)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                stack.push(path);
+            } else if let Ok(relative) = path.strip_prefix(dir) {
+                files.push(relative.to_path_buf());
+            }
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
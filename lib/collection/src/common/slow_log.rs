@@ -0,0 +1,269 @@
+//! Measuring of searches, scrolls and updates of collections, and reporting of the ones, which
+//! take longer than a configured threshold.
+//!
+//! Slow operations are published as [`SlowOperationEvent`] with their filters, parameters,
+//! timings of each shard and search strategies chosen by local segments.
+
+use std::future::Future;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+
+use common::io_throttle;
+use parking_lot::Mutex;
+use segment::common::operation_time_statistics::OperationDurationsAggregator;
+use segment::data_types::query_context::SearchPlan;
+use segment::types::Filter;
+use serde::Serialize;
+use serde_json::json;
+
+use crate::common::latency_histograms::{LatencyHistograms, OperationType};
+use crate::events::{ShardTiming, SlowOperationEvent};
+use crate::operations::blob_ops::BlobOperationsDiscriminants;
+use crate::operations::payload_ops::{PayloadOps, PayloadOpsDiscriminants};
+use crate::operations::point_ops::{PointOperations, PointOperationsDiscriminants};
+use crate::operations::query_enum::QueryEnum;
+use crate::operations::types::{CoreSearchRequestBatch, ScrollRequestInternal};
+use crate::operations::vector_ops::{VectorOperations, VectorOperationsDiscriminants};
+use crate::operations::{CollectionUpdateOperations, FieldIndexOperationsDiscriminants};
+use crate::shards::shard::ShardId;
+use crate::shards::CollectionId;
+
+/// Operations taking longer are reported, slow log is disabled if not set
+pub static SLOW_OPERATION_THRESHOLD: OnceLock<Duration> = OnceLock::new();
+
+tokio::task_local! {
+    /// Search plan of the shard, which is measured in the current task
+    static SEARCH_PLAN: Arc<SearchPlan>;
+}
+
+/// Plan to record search strategies of local segments into, if the search is measured
+pub fn current_search_plan() -> Option<Arc<SearchPlan>> {
+    SEARCH_PLAN.try_with(Arc::clone).ok()
+}
+
+/// Measures an operation of the collection.
+///
+/// The duration is added to statistics of the collection and to the latency histogram of the
+/// operation type once the operation is finished, an operation dropped before that is counted
+/// as failed. Slow operations are reported.
+pub struct OperationTimer {
+    statistics: Arc<Mutex<OperationDurationsAggregator>>,
+    operation_type: OperationType,
+    started: Instant,
+    shards: ShardTimer,
+    finished: bool,
+}
+
+impl OperationTimer {
+    pub fn start(
+        statistics: &Arc<Mutex<OperationDurationsAggregator>>,
+        operation_type: OperationType,
+    ) -> Self {
+        Self {
+            statistics: statistics.clone(),
+            operation_type,
+            started: Instant::now(),
+            shards: ShardTimer {
+                threshold: SLOW_OPERATION_THRESHOLD.get().copied(),
+                timings: Default::default(),
+            },
+            finished: false,
+        }
+    }
+
+    /// Whether the operation is reported, if it's slow
+    pub fn is_reported(&self) -> bool {
+        self.shards.threshold.is_some()
+    }
+
+    /// Timer of parts of the operation on each shard
+    pub fn shards(&self) -> &ShardTimer {
+        &self.shards
+    }
+
+    /// Record the successful operation, and publish it if it took longer than the threshold.
+    ///
+    /// Details are only collected for slow operations.
+    pub fn finish(
+        mut self,
+        collection_id: &CollectionId,
+        operation: &'static str,
+        details: impl FnOnce() -> OperationDetails,
+    ) {
+        let took = self.started.elapsed();
+        self.finished = true;
+        self.statistics.lock().add_operation_result(true, took);
+        LatencyHistograms::global().record(self.operation_type, took);
+        if self.operation_type == OperationType::Search {
+            io_throttle::record_search_latency(took);
+        }
+
+        let Some(threshold) = self.shards.threshold else {
+            return;
+        };
+        if took <= threshold {
+            return;
+        }
+
+        let OperationDetails { filters, params } = details();
+        let mut shards = std::mem::take(&mut *self.shards.timings.lock());
+        shards.sort_unstable_by_key(|shard| shard.shard_id);
+
+        issues::publish(SlowOperationEvent {
+            collection_id: collection_id.clone(),
+            operation,
+            took: took.as_secs_f64(),
+            filters,
+            params,
+            shards,
+        });
+    }
+}
+
+impl Drop for OperationTimer {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.statistics
+                .lock()
+                .add_operation_result(false, self.started.elapsed());
+        }
+    }
+}
+
+/// Measures parts of an operation on each shard, if slow operations are reported
+#[derive(Clone)]
+pub struct ShardTimer {
+    threshold: Option<Duration>,
+    timings: Arc<Mutex<Vec<ShardTiming>>>,
+}
+
+impl ShardTimer {
+    /// Measure the part of the operation on the shard
+    pub fn measure<F: Future>(
+        &self,
+        shard_id: ShardId,
+        future: F,
+    ) -> impl Future<Output = F::Output> {
+        let timer = self.clone();
+        async move {
+            if timer.threshold.is_none() {
+                return future.await;
+            }
+
+            let search_plan = Arc::new(SearchPlan::default());
+            let started = Instant::now();
+            let output = SEARCH_PLAN.scope(search_plan.clone(), future).await;
+            timer.timings.lock().push(ShardTiming {
+                shard_id,
+                took: started.elapsed().as_secs_f64(),
+                plan: search_plan.strategies(),
+            });
+            output
+        }
+    }
+}
+
+/// What was requested by a slow operation, without vectors and payloads
+#[derive(Debug, Default, Serialize)]
+pub struct OperationDetails {
+    pub filters: Vec<Filter>,
+    pub params: serde_json::Value,
+}
+
+impl OperationDetails {
+    pub fn search(request: &CoreSearchRequestBatch) -> Self {
+        let filters = request
+            .searches
+            .iter()
+            .filter_map(|search| search.filter.clone())
+            .collect();
+        let params = request
+            .searches
+            .iter()
+            .map(|search| {
+                let query = match &search.query {
+                    QueryEnum::Nearest(_) => "nearest",
+                    QueryEnum::RecommendBestScore(_) => "recommend",
+                    QueryEnum::Discover(_) => "discover",
+                    QueryEnum::Context(_) => "context",
+                };
+                json!({
+                    "query": query,
+                    "using": search.query.get_vector_name(),
+                    "params": search.params,
+                    "limit": search.limit,
+                    "offset": search.offset,
+                    "score_threshold": search.score_threshold,
+                    "with_payload": search.with_payload,
+                    "with_vector": search.with_vector,
+                })
+            })
+            .collect();
+        Self { filters, params }
+    }
+
+    pub fn scroll(request: &ScrollRequestInternal) -> Self {
+        Self {
+            filters: request.filter.iter().cloned().collect(),
+            params: json!({
+                "offset": request.offset,
+                "limit": request.limit,
+                "order_by": request.order_by,
+                "with_payload": request.with_payload,
+                "with_vector": request.with_vector,
+            }),
+        }
+    }
+
+    pub fn update(operation: &CollectionUpdateOperations) -> Self {
+        let filter = match operation {
+            CollectionUpdateOperations::PointOperation(operation) => match operation {
+                PointOperations::DeletePointsByFilter(filter) => Some(filter),
+                PointOperations::UpsertPointsConditional(operation) => {
+                    operation.update_filter.as_ref()
+                }
+                PointOperations::UpsertPoints(_)
+                | PointOperations::DeletePoints { .. }
+                | PointOperations::SyncPoints(_) => None,
+            },
+            CollectionUpdateOperations::VectorOperation(operation) => match operation {
+                VectorOperations::DeleteVectorsByFilter(filter, _) => Some(filter),
+                VectorOperations::UpdateVectors(_) | VectorOperations::DeleteVectors(..) => None,
+            },
+            CollectionUpdateOperations::PayloadOperation(operation) => match operation {
+                PayloadOps::SetPayload(operation) | PayloadOps::OverwritePayload(operation) => {
+                    operation.filter.as_ref()
+                }
+                PayloadOps::DeletePayload(operation) => operation.filter.as_ref(),
+                PayloadOps::PatchPayload(operation) => operation.filter.as_ref(),
+                PayloadOps::ClearPayloadByFilter(filter) => Some(filter),
+                PayloadOps::ClearPayload { .. } => None,
+            },
+            CollectionUpdateOperations::FieldIndexOperation(_)
+            | CollectionUpdateOperations::BlobOperation(_) => None,
+        };
+
+        let kind = match operation {
+            CollectionUpdateOperations::PointOperation(operation) => {
+                format!("{:?}", PointOperationsDiscriminants::from(operation))
+            }
+            CollectionUpdateOperations::VectorOperation(operation) => {
+                format!("{:?}", VectorOperationsDiscriminants::from(operation))
+            }
+            CollectionUpdateOperations::PayloadOperation(operation) => {
+                format!("{:?}", PayloadOpsDiscriminants::from(operation))
+            }
+            CollectionUpdateOperations::FieldIndexOperation(operation) => {
+                format!("{:?}", FieldIndexOperationsDiscriminants::from(operation))
+            }
+            CollectionUpdateOperations::BlobOperation(operation) => {
+                format!("{:?}", BlobOperationsDiscriminants::from(operation))
+            }
+        };
+
+        Self {
+            filters: filter.into_iter().cloned().collect(),
+            params: json!({ "operation": kind }),
+        }
+    }
+}
@@ -0,0 +1,375 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use bytes::Bytes;
+use futures::StreamExt as _;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::Deserialize;
+use tempfile::TempPath;
+
+use super::archive_stream::ArchiveStream;
+use super::wal_encryption::decode_hex_key;
+use crate::operations::types::{CollectionError, CollectionResult};
+
+/// Marks encrypted snapshot files, plain snapshots are tar archives and never start with it
+const MAGIC: &[u8; 8] = b"QDRSNPE1";
+
+/// Random part of the nonce, which is shared by all chunks of the snapshot
+const NONCE_PREFIX_LEN: usize = 7;
+
+/// Upper bound of the encrypted chunk size, protects from allocating garbage lengths
+const MAX_CHUNK_LEN: usize = 64 * 1024 * 1024;
+
+/// Keys used to encrypt collection snapshots.
+///
+/// Keys are 256-bit, hex-encoded. A key supplied with the request takes precedence over
+/// the configured one. `key_file` allows to read the key provisioned by a KMS agent or
+/// a secret manager, instead of keeping it in the config.
+#[derive(Clone, Deserialize, Default)]
+pub struct SnapshotEncryptionConfig {
+    #[serde(default)]
+    pub key: Option<String>,
+    #[serde(default)]
+    pub key_file: Option<PathBuf>,
+}
+
+impl fmt::Debug for SnapshotEncryptionConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Never expose key material in logs
+        f.debug_struct("SnapshotEncryptionConfig")
+            .field("key", &self.key.as_ref().map(|_| "<redacted>"))
+            .field("key_file", &self.key_file)
+            .finish()
+    }
+}
+
+impl SnapshotEncryptionConfig {
+    /// Cipher for snapshots, if a key is given in the request or configured
+    pub fn cipher(
+        &self,
+        request_key: Option<&str>,
+    ) -> CollectionResult<Option<Arc<SnapshotCipher>>> {
+        if let Some(key) = request_key.or(self.key.as_deref()) {
+            return SnapshotCipher::from_hex(key).map(|cipher| Some(Arc::new(cipher)));
+        }
+
+        let Some(key_file) = &self.key_file else {
+            return Ok(None);
+        };
+        let key = std::fs::read_to_string(key_file).map_err(|err| {
+            CollectionError::service_error(format!(
+                "Failed to read snapshot encryption key from {}: {err}",
+                key_file.display(),
+            ))
+        })?;
+        SnapshotCipher::from_hex(&key).map(|cipher| Some(Arc::new(cipher)))
+    }
+}
+
+/// Authenticated encryption of snapshot archives with AES-256-GCM.
+///
+/// Archive is encrypted in chunks, so it can be streamed. Nonce of every chunk consists of
+/// the random prefix, the chunk counter and the flag of the last chunk, which protects
+/// from reordering and truncation of chunks. The last chunk is empty and the only empty one.
+///
+/// File layout: `MAGIC | nonce prefix | (chunk length: u32 LE | ciphertext with tag)*`
+pub struct SnapshotCipher {
+    key: LessSafeKey,
+    rng: SystemRandom,
+}
+
+impl fmt::Debug for SnapshotCipher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SnapshotCipher").finish_non_exhaustive()
+    }
+}
+
+impl SnapshotCipher {
+    pub fn from_hex(key: &str) -> CollectionResult<Self> {
+        let key = decode_hex_key(key, "Snapshot encryption key")?;
+        let key = UnboundKey::new(&AES_256_GCM, &key).map_err(|_| {
+            CollectionError::bad_input("Snapshot encryption key has invalid length")
+        })?;
+        Ok(Self {
+            key: LessSafeKey::new(key),
+            rng: SystemRandom::new(),
+        })
+    }
+
+    /// Encrypt the archive as it is being streamed
+    pub fn encrypt_stream(
+        self: Arc<Self>,
+        stream: ArchiveStream,
+    ) -> CollectionResult<ArchiveStream> {
+        let mut nonce_prefix = [0u8; NONCE_PREFIX_LEN];
+        self.rng
+            .fill(&mut nonce_prefix)
+            .map_err(|_| CollectionError::service_error("Failed to generate snapshot nonce"))?;
+
+        let mut header = Vec::with_capacity(MAGIC.len() + NONCE_PREFIX_LEN);
+        header.extend_from_slice(MAGIC);
+        header.extend_from_slice(&nonce_prefix);
+
+        let sealer = ChunkSealer {
+            cipher: self,
+            nonce_prefix,
+            counter: 0,
+        };
+
+        let chunks = futures::stream::unfold(Some((stream, sealer)), |state| async move {
+            let (mut stream, mut sealer) = state?;
+            let next = loop {
+                match stream.next().await {
+                    // Only the last chunk is empty
+                    Some(Ok(chunk)) if chunk.is_empty() => continue,
+                    next => break next,
+                }
+            };
+            match next {
+                Some(Ok(chunk)) => {
+                    let sealed = sealer.seal(chunk.to_vec(), false);
+                    Some((sealed, Some((stream, sealer))))
+                }
+                Some(Err(err)) => Some((Err(err), None)),
+                None => Some((sealer.seal(Vec::new(), true), None)),
+            }
+        });
+
+        let header = futures::stream::once(async move { Ok(Bytes::from(header)) });
+        Ok(Box::pin(header.chain(chunks)))
+    }
+
+    /// Decrypt snapshot file produced by [`SnapshotCipher::encrypt_stream`]
+    ///
+    /// This method performs blocking IO.
+    pub fn decrypt_file(&self, source_path: &Path, target_path: &Path) -> CollectionResult<()> {
+        let mut reader = BufReader::new(File::open(source_path)?);
+        let mut writer = BufWriter::new(File::create(target_path)?);
+
+        let mut magic = [0u8; MAGIC.len()];
+        let mut nonce_prefix = [0u8; NONCE_PREFIX_LEN];
+        reader.read_exact(&mut magic).map_err(truncated)?;
+        reader.read_exact(&mut nonce_prefix).map_err(truncated)?;
+        if &magic != MAGIC {
+            return Err(CollectionError::bad_input("Snapshot is not encrypted"));
+        }
+
+        let mut counter = 0u32;
+        loop {
+            let mut len = [0u8; 4];
+            reader.read_exact(&mut len).map_err(truncated)?;
+            let len = u32::from_le_bytes(len) as usize;
+            if !(AES_256_GCM.tag_len()..=MAX_CHUNK_LEN).contains(&len) {
+                return Err(CollectionError::bad_input(
+                    "Encrypted snapshot is corrupted, invalid chunk length",
+                ));
+            }
+
+            let mut chunk = vec![0u8; len];
+            reader.read_exact(&mut chunk).map_err(truncated)?;
+
+            let is_last = len == AES_256_GCM.tag_len();
+            let nonce = chunk_nonce(&nonce_prefix, counter, is_last);
+            let plaintext = self
+                .key
+                .open_in_place(nonce, Aad::empty(), &mut chunk)
+                .map_err(|_| {
+                    CollectionError::bad_input(
+                        "Failed to decrypt snapshot, probably wrong encryption key",
+                    )
+                })?;
+            writer.write_all(plaintext)?;
+
+            if is_last {
+                if reader.read(&mut [0u8; 1])? != 0 {
+                    return Err(CollectionError::bad_input(
+                        "Encrypted snapshot is corrupted, data after the last chunk",
+                    ));
+                }
+                break;
+            }
+            counter = counter.checked_add(1).ok_or_else(|| {
+                CollectionError::bad_input("Encrypted snapshot has too many chunks")
+            })?;
+        }
+
+        writer.flush()?;
+        writer.get_ref().sync_all()?;
+        Ok(())
+    }
+}
+
+struct ChunkSealer {
+    cipher: Arc<SnapshotCipher>,
+    nonce_prefix: [u8; NONCE_PREFIX_LEN],
+    counter: u32,
+}
+
+impl ChunkSealer {
+    /// Encrypt chunk, returns chunk length followed by ciphertext and authentication tag
+    fn seal(&mut self, mut chunk: Vec<u8>, is_last: bool) -> io::Result<Bytes> {
+        let nonce = chunk_nonce(&self.nonce_prefix, self.counter, is_last);
+        self.counter = self
+            .counter
+            .checked_add(1)
+            .ok_or_else(|| io::Error::other("snapshot has too many chunks to encrypt"))?;
+
+        let tag = self
+            .cipher
+            .key
+            .seal_in_place_separate_tag(nonce, Aad::empty(), &mut chunk)
+            .map_err(|_| io::Error::other("failed to encrypt snapshot chunk"))?;
+
+        let len = (chunk.len() + tag.as_ref().len()) as u32;
+        let mut sealed = Vec::with_capacity(4 + len as usize);
+        sealed.extend_from_slice(&len.to_le_bytes());
+        sealed.extend_from_slice(&chunk);
+        sealed.extend_from_slice(tag.as_ref());
+        Ok(Bytes::from(sealed))
+    }
+}
+
+fn chunk_nonce(nonce_prefix: &[u8; NONCE_PREFIX_LEN], counter: u32, is_last: bool) -> Nonce {
+    let mut nonce = [0u8; NONCE_LEN];
+    nonce[..NONCE_PREFIX_LEN].copy_from_slice(nonce_prefix);
+    nonce[NONCE_PREFIX_LEN..NONCE_LEN - 1].copy_from_slice(&counter.to_be_bytes());
+    nonce[NONCE_LEN - 1] = u8::from(is_last);
+    Nonce::assume_unique_for_key(nonce)
+}
+
+fn truncated(err: io::Error) -> CollectionError {
+    if err.kind() == io::ErrorKind::UnexpectedEof {
+        CollectionError::bad_input("Encrypted snapshot is truncated")
+    } else {
+        err.into()
+    }
+}
+
+/// Check whether the snapshot file is encrypted
+pub fn is_encrypted_snapshot(snapshot_path: &Path) -> CollectionResult<bool> {
+    let mut magic = [0u8; MAGIC.len()];
+    let mut file = File::open(snapshot_path)?;
+    match file.read_exact(&mut magic) {
+        Ok(()) => Ok(&magic == MAGIC),
+        Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => Ok(false),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Decrypt snapshot into `temp_dir`, if it is encrypted.
+///
+/// Returns path of the plain snapshot, along with the `TempPath` of the decrypted file,
+/// which deletes it on drop. Plain snapshots are returned as is.
+///
+/// This method performs blocking IO.
+pub fn decrypt_snapshot(
+    snapshot_path: &Path,
+    cipher: Option<&SnapshotCipher>,
+    temp_dir: &Path,
+) -> CollectionResult<(PathBuf, Option<TempPath>)> {
+    if !is_encrypted_snapshot(snapshot_path)? {
+        return Ok((snapshot_path.to_path_buf(), None));
+    }
+
+    let cipher = cipher.ok_or_else(|| {
+        CollectionError::bad_input("Snapshot is encrypted, but no encryption key is provided")
+    })?;
+
+    let decrypted_path = tempfile::Builder::new()
+        .prefix("decrypted-")
+        .suffix(".snapshot")
+        .tempfile_in(temp_dir)?
+        .into_temp_path();
+    cipher.decrypt_file(snapshot_path, &decrypted_path)?;
+
+    Ok((decrypted_path.to_path_buf(), Some(decrypted_path)))
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::TryStreamExt as _;
+    use tempfile::Builder;
+
+    use super::*;
+
+    const KEY: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+
+    async fn encrypt(cipher: Arc<SnapshotCipher>, chunks: &[&[u8]]) -> Vec<u8> {
+        let chunks: Vec<io::Result<Bytes>> = chunks
+            .iter()
+            .map(|chunk| Ok(Bytes::copy_from_slice(chunk)))
+            .collect();
+        let stream: ArchiveStream = Box::pin(futures::stream::iter(chunks));
+        let encrypted: Vec<Bytes> = cipher
+            .encrypt_stream(stream)
+            .unwrap()
+            .try_collect()
+            .await
+            .unwrap();
+        encrypted.concat()
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_encryption_roundtrip() {
+        let dir = Builder::new().prefix("snapshot_enc").tempdir().unwrap();
+        let cipher = Arc::new(SnapshotCipher::from_hex(KEY).unwrap());
+        let data: &[&[u8]] = &[b"first chunk of the archive", b"", b"second chunk"];
+
+        let encrypted = encrypt(cipher.clone(), data).await;
+        let encrypted_path = dir.path().join("encrypted.snapshot");
+        std::fs::write(&encrypted_path, &encrypted).unwrap();
+        assert!(is_encrypted_snapshot(&encrypted_path).unwrap());
+
+        let (decrypted_path, temp_path) =
+            decrypt_snapshot(&encrypted_path, Some(&cipher), dir.path()).unwrap();
+        assert!(temp_path.is_some());
+        assert_eq!(std::fs::read(&decrypted_path).unwrap(), data.concat());
+
+        // Key is required for encrypted snapshots
+        assert!(decrypt_snapshot(&encrypted_path, None, dir.path()).is_err());
+
+        // Wrong key
+        let other_cipher = SnapshotCipher::from_hex(&KEY.replace("1f", "ff")).unwrap();
+        assert!(decrypt_snapshot(&encrypted_path, Some(&other_cipher), dir.path()).is_err());
+
+        // Truncated after a full chunk
+        let first_chunk_end = MAGIC.len() + NONCE_PREFIX_LEN + 4 + data[0].len() + 16;
+        std::fs::write(&encrypted_path, &encrypted[..first_chunk_end]).unwrap();
+        assert!(decrypt_snapshot(&encrypted_path, Some(&cipher), dir.path()).is_err());
+
+        // Plain snapshots are passed as is
+        let plain_path = dir.path().join("plain.snapshot");
+        std::fs::write(&plain_path, data.concat()).unwrap();
+        let (path, temp_path) = decrypt_snapshot(&plain_path, Some(&cipher), dir.path()).unwrap();
+        assert_eq!(path, plain_path);
+        assert!(temp_path.is_none());
+    }
+
+    #[test]
+    fn test_snapshot_encryption_config() {
+        let dir = Builder::new().prefix("snapshot_enc").tempdir().unwrap();
+        let key_file = dir.path().join("key");
+        std::fs::write(&key_file, format!("{KEY}\n")).unwrap();
+
+        let config = SnapshotEncryptionConfig::default();
+        assert!(config.cipher(None).unwrap().is_none());
+        assert!(config.cipher(Some(KEY)).unwrap().is_some());
+        assert!(config.cipher(Some("abc")).is_err());
+
+        let config = SnapshotEncryptionConfig {
+            key: None,
+            key_file: Some(key_file),
+        };
+        assert!(config.cipher(None).unwrap().is_some());
+
+        let config = SnapshotEncryptionConfig {
+            key: Some(KEY.to_string()),
+            key_file: None,
+        };
+        assert!(!format!("{config:?}").contains(KEY));
+    }
+}
@@ -1,9 +1,12 @@
 use std::path::PathBuf;
 
 use actix_files::NamedFile;
+use actix_web::http::header::{ContentDisposition, DispositionParam, DispositionType};
 use actix_web::{HttpRequest, HttpResponse, Responder};
 use futures::Stream;
 
+use super::archive_stream::ArchiveStream;
+
 pub struct SnapShotStreamLocalFS {
     pub snapshot_path: PathBuf,
     pub req: HttpRequest,
@@ -13,9 +16,16 @@ pub struct SnapShotStreamCloudStrage {
         std::pin::Pin<Box<dyn Stream<Item = Result<bytes::Bytes, object_store::Error>> + Send>>,
 }
 
+/// Snapshot archive, which is built while it is being sent
+pub struct SnapShotStreamArchive {
+    pub snapshot_name: String,
+    pub archive: ArchiveStream,
+}
+
 pub enum SnapshotStream {
     LocalFS(SnapShotStreamLocalFS),
     CloudStorage(SnapShotStreamCloudStrage),
+    Archive(SnapShotStreamArchive),
 }
 
 impl Responder for SnapshotStream {
@@ -37,6 +47,14 @@ impl Responder for SnapshotStream {
             SnapshotStream::CloudStorage(stream) => HttpResponse::Ok()
                 .content_type("application/octet-stream")
                 .streaming(stream.streamer),
+
+            SnapshotStream::Archive(stream) => HttpResponse::Ok()
+                .content_type("application/octet-stream")
+                .insert_header(ContentDisposition {
+                    disposition: DispositionType::Attachment,
+                    parameters: vec![DispositionParam::Filename(stream.snapshot_name)],
+                })
+                .streaming(stream.archive),
         }
     }
 }
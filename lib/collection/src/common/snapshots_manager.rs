@@ -2,13 +2,20 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use actix_web::HttpRequest;
+use futures::StreamExt as _;
 use object_store::aws::AmazonS3Builder;
+use object_store::RetryConfig;
 use serde::Deserialize;
+use sha2::{Digest as _, Sha256};
 use tempfile::TempPath;
 use tokio::io::AsyncWriteExt;
+use url::Url;
 
+use super::archive_stream::ArchiveStream;
+use super::snapshot_encryption::SnapshotEncryptionConfig;
 use super::snapshot_stream::{SnapShotStreamCloudStrage, SnapShotStreamLocalFS, SnapshotStream};
 use crate::common::file_utils::move_file;
+use crate::common::segment_storage::GcsConfig;
 use crate::common::sha_256::hash_file;
 use crate::operations::snapshot_ops::{
     get_checksum_path, get_snapshot_description, SnapshotDescription,
@@ -22,6 +29,41 @@ use crate::shards::shard_holder::LockedShardHolder;
 pub struct SnapShotsConfig {
     pub snapshots_storage: SnapshotsStorageConfig,
     pub s3_config: Option<S3Config>,
+    pub gcs_config: Option<GcsConfig>,
+    /// Keys to encrypt collection snapshots
+    #[serde(default)]
+    pub encryption: SnapshotEncryptionConfig,
+}
+
+impl SnapShotsConfig {
+    /// Create client for the bucket of the snapshot URL, e.g. `s3://bucket/path/to.snapshot`
+    ///
+    /// Credentials of the configured object storage are used to access the bucket.
+    pub fn bucket_client(&self, url: &Url) -> CollectionResult<Box<dyn object_store::ObjectStore>> {
+        let bucket = url
+            .host_str()
+            .filter(|bucket| !bucket.is_empty())
+            .ok_or_else(|| {
+                CollectionError::bad_input(format!("Invalid snapshot URL {url}: bucket is missing"))
+            })?
+            .to_string();
+
+        match url.scheme() {
+            "s3" => S3Config {
+                bucket,
+                ..self.s3_config.clone().unwrap_or_default()
+            }
+            .build_client(),
+            "gs" => GcsConfig {
+                bucket,
+                ..self.gcs_config.clone().unwrap_or_default()
+            }
+            .build_client(),
+            scheme => Err(CollectionError::bad_input(format!(
+                "URL {url} with schema {scheme} is not an object storage URL"
+            ))),
+        }
+    }
 }
 
 #[derive(Clone, Debug, Default, Deserialize)]
@@ -29,6 +71,16 @@ pub enum SnapshotsStorageConfig {
     #[default]
     Local,
     S3,
+    Gcs,
+}
+
+/// Retry policy of object storage requests, `max_retries` overrides the default number of retries
+pub(crate) fn retry_config(max_retries: Option<usize>) -> RetryConfig {
+    let mut retry_config = RetryConfig::default();
+    if let Some(max_retries) = max_retries {
+        retry_config.max_retries = max_retries;
+    }
+    retry_config
 }
 
 #[derive(Clone, Deserialize, Debug, Default)]
@@ -38,6 +90,38 @@ pub struct S3Config {
     pub access_key: Option<String>,
     pub secret_key: Option<String>,
     pub endpoint_url: Option<String>,
+    /// Number of retries of failed requests, including parts of multipart uploads
+    pub max_retries: Option<usize>,
+}
+
+impl S3Config {
+    /// Create object storage client for the configured bucket
+    pub fn build_client(&self) -> CollectionResult<Box<dyn object_store::ObjectStore>> {
+        let mut builder = AmazonS3Builder::new()
+            .with_bucket_name(&self.bucket)
+            .with_retry(retry_config(self.max_retries));
+
+        if let Some(access_key) = &self.access_key {
+            builder = builder.with_access_key_id(access_key);
+        }
+        if let Some(secret_key) = &self.secret_key {
+            builder = builder.with_secret_access_key(secret_key);
+        }
+        if let Some(region) = &self.region {
+            builder = builder.with_region(region);
+        }
+        if let Some(endpoint_url) = &self.endpoint_url {
+            builder = builder.with_endpoint(endpoint_url);
+            if endpoint_url.starts_with("http://") {
+                builder = builder.with_allow_http(true);
+            }
+        }
+
+        let client = builder.build().map_err(|e| {
+            CollectionError::service_error(format!("Failed to create S3 client: {}", e))
+        })?;
+        Ok(Box::new(client))
+    }
 }
 
 #[allow(dead_code)]
@@ -51,8 +135,8 @@ pub enum SnapshotStorageManager {
     LocalFS(SnapshotStorageLocalFS),
     // Assuming that we can have common operations for all cloud storages
     S3(SnapshotStorageCloud),
+    Gcs(SnapshotStorageCloud),
     // <TODO> : Implement other cloud storage
-    // GCS(SnapshotStorageCloud),
     // AZURE(SnapshotStorageCloud),
 }
 
@@ -63,33 +147,31 @@ impl SnapshotStorageManager {
                 Ok(SnapshotStorageManager::LocalFS(SnapshotStorageLocalFS))
             }
             SnapshotsStorageConfig::S3 => {
-                let mut builder = AmazonS3Builder::new();
-                if let Some(s3_config) = &snapshots_config.s3_config {
-                    builder = builder.with_bucket_name(&s3_config.bucket);
-
-                    if let Some(access_key) = &s3_config.access_key {
-                        builder = builder.with_access_key_id(access_key);
-                    }
-                    if let Some(secret_key) = &s3_config.secret_key {
-                        builder = builder.with_secret_access_key(secret_key);
-                    }
-                    if let Some(region) = &s3_config.region {
-                        builder = builder.with_region(region);
-                    }
-                    if let Some(endpoint_url) = &s3_config.endpoint_url {
-                        builder = builder.with_endpoint(endpoint_url);
-                        if endpoint_url.starts_with("http://") {
-                            builder = builder.with_allow_http(true);
-                        }
-                    }
-                }
-                let client: Box<dyn object_store::ObjectStore> =
-                    Box::new(builder.build().map_err(|e| {
-                        CollectionError::service_error(format!("Failed to create S3 client: {}", e))
-                    })?);
+                let client = snapshots_config
+                    .s3_config
+                    .as_ref()
+                    .ok_or_else(|| {
+                        CollectionError::service_error(
+                            "Failed to create S3 client: `s3_config` is missing",
+                        )
+                    })?
+                    .build_client()?;
 
                 Ok(SnapshotStorageManager::S3(SnapshotStorageCloud { client }))
             }
+            SnapshotsStorageConfig::Gcs => {
+                let client = snapshots_config
+                    .gcs_config
+                    .as_ref()
+                    .ok_or_else(|| {
+                        CollectionError::service_error(
+                            "Failed to create GCS client: `gcs_config` is missing",
+                        )
+                    })?
+                    .build_client()?;
+
+                Ok(SnapshotStorageManager::Gcs(SnapshotStorageCloud { client }))
+            }
         }
     }
 
@@ -98,7 +180,8 @@ impl SnapshotStorageManager {
             SnapshotStorageManager::LocalFS(storage_impl) => {
                 storage_impl.delete_snapshot(snapshot_name).await
             }
-            SnapshotStorageManager::S3(storage_impl) => {
+            SnapshotStorageManager::S3(storage_impl)
+            | SnapshotStorageManager::Gcs(storage_impl) => {
                 storage_impl.delete_snapshot(snapshot_name).await
             }
         }
@@ -111,7 +194,8 @@ impl SnapshotStorageManager {
             SnapshotStorageManager::LocalFS(storage_impl) => {
                 storage_impl.list_snapshots(directory).await
             }
-            SnapshotStorageManager::S3(storage_impl) => {
+            SnapshotStorageManager::S3(storage_impl)
+            | SnapshotStorageManager::Gcs(storage_impl) => {
                 storage_impl.list_snapshots(directory).await
             }
         }
@@ -125,12 +209,30 @@ impl SnapshotStorageManager {
             SnapshotStorageManager::LocalFS(storage_impl) => {
                 storage_impl.store_file(source_path, target_path).await
             }
-            SnapshotStorageManager::S3(storage_impl) => {
+            SnapshotStorageManager::S3(storage_impl)
+            | SnapshotStorageManager::Gcs(storage_impl) => {
                 storage_impl.store_file(source_path, target_path).await
             }
         }
     }
 
+    /// Store snapshot archive, which is written as it is being built
+    pub async fn store_stream(
+        &self,
+        stream: ArchiveStream,
+        target_path: &Path,
+    ) -> CollectionResult<SnapshotDescription> {
+        match self {
+            SnapshotStorageManager::LocalFS(storage_impl) => {
+                storage_impl.store_stream(stream, target_path).await
+            }
+            SnapshotStorageManager::S3(storage_impl)
+            | SnapshotStorageManager::Gcs(storage_impl) => {
+                storage_impl.store_stream(stream, target_path).await
+            }
+        }
+    }
+
     pub async fn get_stored_file(
         &self,
         storage_path: &Path,
@@ -140,7 +242,8 @@ impl SnapshotStorageManager {
             SnapshotStorageManager::LocalFS(storage_impl) => {
                 storage_impl.get_stored_file(storage_path, local_path).await
             }
-            SnapshotStorageManager::S3(storage_impl) => {
+            SnapshotStorageManager::S3(storage_impl)
+            | SnapshotStorageManager::Gcs(storage_impl) => {
                 storage_impl.get_stored_file(storage_path, local_path).await
             }
         }
@@ -157,7 +260,8 @@ impl SnapshotStorageManager {
                     .get_snapshot_path(snapshots_path, snapshot_name)
                     .await
             }
-            SnapshotStorageManager::S3(storage_impl) => {
+            SnapshotStorageManager::S3(storage_impl)
+            | SnapshotStorageManager::Gcs(storage_impl) => {
                 storage_impl
                     .get_snapshot_path(snapshots_path, snapshot_name)
                     .await
@@ -176,7 +280,8 @@ impl SnapshotStorageManager {
                     .get_full_snapshot_path(snapshots_path, snapshot_name)
                     .await
             }
-            SnapshotStorageManager::S3(storage_impl) => {
+            SnapshotStorageManager::S3(storage_impl)
+            | SnapshotStorageManager::Gcs(storage_impl) => {
                 storage_impl
                     .get_full_snapshot_path(snapshots_path, snapshot_name)
                     .await
@@ -202,7 +307,8 @@ impl SnapshotStorageManager {
                     )
                     .await
             }
-            SnapshotStorageManager::S3(storage_impl) => {
+            SnapshotStorageManager::S3(storage_impl)
+            | SnapshotStorageManager::Gcs(storage_impl) => {
                 storage_impl
                     .get_shard_snapshot_path(
                         shards_holder,
@@ -224,7 +330,8 @@ impl SnapshotStorageManager {
             SnapshotStorageManager::LocalFS(storage_impl) => {
                 storage_impl.get_snapshot_stream(req, snapshot_path).await
             }
-            SnapshotStorageManager::S3(storage_impl) => {
+            SnapshotStorageManager::S3(storage_impl)
+            | SnapshotStorageManager::Gcs(storage_impl) => {
                 storage_impl.get_snapshot_stream(snapshot_path).await
             }
         }
@@ -308,6 +415,44 @@ impl SnapshotStorageLocalFS {
         get_snapshot_description(target_path).await
     }
 
+    async fn store_stream(
+        &self,
+        mut stream: ArchiveStream,
+        target_path: &Path,
+    ) -> CollectionResult<SnapshotDescription> {
+        if let Some(target_dir) = target_path.parent() {
+            if !target_dir.exists() {
+                std::fs::create_dir_all(target_dir)?;
+            }
+        }
+
+        // Write the archive under a temporary name next to the target and rename it atomically,
+        // checksum is computed on the fly, so the archive is never read back
+        let target_path_tmp = target_path.with_extension("tmp");
+        // Ensure that the temporary file is deleted on error
+        let target_temp_path = TempPath::from_path(&target_path_tmp);
+
+        let mut file = tokio::fs::File::create(&target_path_tmp).await?;
+        let mut sha = Sha256::new();
+        while let Some(data) = stream.next().await {
+            let data = data?;
+            sha.update(&data);
+            file.write_all(&data).await?;
+        }
+        file.sync_all().await?;
+        drop(file);
+
+        let checksum_path = get_checksum_path(target_path);
+        let checksum_file = TempPath::from_path(&checksum_path);
+        tokio::fs::write(&checksum_path, format!("{:x}", sha.finalize())).await?;
+
+        tokio::fs::rename(&target_path_tmp, target_path).await?;
+        target_temp_path.keep()?;
+
+        checksum_file.keep()?;
+        get_snapshot_description(target_path).await
+    }
+
     async fn get_stored_file(
         &self,
         storage_path: &Path,
@@ -433,6 +578,15 @@ impl SnapshotStorageCloud {
         snapshot_storage_ops::get_snapshot_description(&self.client, target_path).await
     }
 
+    async fn store_stream(
+        &self,
+        stream: ArchiveStream,
+        target_path: &Path,
+    ) -> CollectionResult<SnapshotDescription> {
+        snapshot_storage_ops::multipart_upload_stream(&self.client, stream, target_path).await?;
+        snapshot_storage_ops::get_snapshot_description(&self.client, target_path).await
+    }
+
     async fn get_stored_file(
         &self,
         storage_path: &Path,
@@ -498,3 +652,22 @@ impl SnapshotStorageCloud {
         }))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_client() {
+        let config = SnapShotsConfig::default();
+
+        let url = Url::parse("s3://bucket/snapshots/test.snapshot").unwrap();
+        assert!(config.bucket_client(&url).is_ok());
+
+        let url = Url::parse("s3:///snapshots/test.snapshot").unwrap();
+        assert!(config.bucket_client(&url).is_err());
+
+        let url = Url::parse("ftp://bucket/snapshots/test.snapshot").unwrap();
+        assert!(config.bucket_client(&url).is_err());
+    }
+}
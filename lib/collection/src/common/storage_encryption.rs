@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use segment::common::encryption::StorageCipher;
+use segment::types::{PayloadStorageType, QuantizationConfig};
+use serde::Deserialize;
+
+use super::wal_encryption::decode_hex_key;
+use crate::config::CollectionParams;
+use crate::operations::types::{CollectionError, CollectionResult};
+use crate::optimizers_builder::OptimizersConfig;
+
+/// Keys used to encrypt vector and payload storage of segments at rest.
+///
+/// Keys are 256-bit, hex-encoded. A collection-level key takes precedence over the node-level one.
+/// The key of a collection wraps random data keys of its segments, so the same key must be
+/// configured on all peers and wherever snapshots of the collection are recovered.
+/// Segments created while no key applies to a collection are written in plain text.
+///
+/// Only values of in-memory vector storages and RocksDB payload storages are encrypted.
+/// Collections, which need other storages, are refused while a key applies to them.
+#[derive(Clone, Deserialize, Default)]
+pub struct StorageEncryptionConfig {
+    /// Key for all collections, which don't have their own key
+    #[serde(default)]
+    pub key: Option<String>,
+    /// Keys of individual collections
+    #[serde(default)]
+    pub collection_keys: HashMap<String, String>,
+}
+
+impl fmt::Debug for StorageEncryptionConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Never expose key material in logs
+        f.debug_struct("StorageEncryptionConfig")
+            .field("key", &self.key.as_ref().map(|_| "<redacted>"))
+            .field(
+                "collection_keys",
+                &self.collection_keys.keys().collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl StorageEncryptionConfig {
+    /// Whether any key applies to the given collection
+    pub fn is_encrypted(&self, collection_name: &str) -> bool {
+        self.collection_keys.contains_key(collection_name) || self.key.is_some()
+    }
+
+    /// Key of the given collection, if any key applies to it
+    pub fn cipher_for(
+        &self,
+        collection_name: &str,
+    ) -> CollectionResult<Option<Arc<StorageCipher>>> {
+        let Some(key) = self
+            .collection_keys
+            .get(collection_name)
+            .or(self.key.as_ref())
+        else {
+            return Ok(None);
+        };
+
+        let key = decode_hex_key(key, "Storage encryption key")?;
+        Ok(Some(Arc::new(StorageCipher::new(&key)?)))
+    }
+}
+
+/// Check that the collection configuration keeps all storages encrypted
+pub fn check_supported(
+    collection_params: &CollectionParams,
+    optimizer_config: &OptimizersConfig,
+    quantization_config: Option<&QuantizationConfig>,
+) -> CollectionResult<()> {
+    let unsupported = |feature: &str| {
+        Err(CollectionError::bad_input(format!(
+            "Storage encryption is not supported with {feature}",
+        )))
+    };
+
+    for (vector_name, params) in collection_params.vectors.params_iter() {
+        if params.on_disk == Some(true) {
+            return unsupported(&format!("on-disk storage of vector `{vector_name}`"));
+        }
+        if params.quantization_config.is_some() {
+            return unsupported(&format!("quantization of vector `{vector_name}`"));
+        }
+    }
+    if quantization_config.is_some() {
+        return unsupported("quantization");
+    }
+    if collection_params
+        .sparse_vectors
+        .as_ref()
+        .is_some_and(|sparse_vectors| !sparse_vectors.is_empty())
+    {
+        return unsupported("sparse vectors");
+    }
+    match collection_params.payload_storage_type() {
+        PayloadStorageType::InMemory | PayloadStorageType::OnDisk => {}
+        PayloadStorageType::AppendOnly | PayloadStorageType::Mmap => {
+            return unsupported("append-only or mmap storage engine");
+        }
+    }
+    if collection_params
+        .payload_columns
+        .as_ref()
+        .is_some_and(|payload_columns| !payload_columns.is_empty())
+    {
+        return unsupported("payload columns");
+    }
+    if optimizer_config
+        .memmap_threshold
+        .is_some_and(|memmap_threshold| memmap_threshold > 0)
+    {
+        return unsupported("memmap threshold of optimizers");
+    }
+    Ok(())
+}
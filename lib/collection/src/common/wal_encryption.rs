@@ -0,0 +1,193 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::Deserialize;
+
+use crate::operations::types::{CollectionError, CollectionResult};
+
+/// Size of the encryption key in bytes
+const KEY_LEN: usize = 32;
+
+/// Decode hex-encoded 256-bit key, `name` of the key is used in error messages
+pub(crate) fn decode_hex_key(key: &str, name: &str) -> CollectionResult<Vec<u8>> {
+    let key = key.trim();
+    let invalid_key = || {
+        CollectionError::bad_input(format!(
+            "{name} must be {} hex characters long",
+            KEY_LEN * 2
+        ))
+    };
+
+    if key.len() != KEY_LEN * 2 || !key.is_ascii() {
+        return Err(invalid_key());
+    }
+
+    (0..key.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&key[i..i + 2], 16).map_err(|_| invalid_key()))
+        .collect()
+}
+
+/// Keys used to encrypt WAL entries at rest.
+///
+/// Keys are 256-bit, hex-encoded. A collection-level key takes precedence over the node-level one.
+/// If no key applies to a collection, its WAL is written in plain text.
+#[derive(Clone, Deserialize, Default)]
+pub struct WalEncryptionConfig {
+    /// Key for all collections, which don't have their own key
+    #[serde(default)]
+    pub key: Option<String>,
+    /// Keys of individual collections
+    #[serde(default)]
+    pub collection_keys: HashMap<String, String>,
+}
+
+impl fmt::Debug for WalEncryptionConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Never expose key material in logs
+        f.debug_struct("WalEncryptionConfig")
+            .field("key", &self.key.as_ref().map(|_| "<redacted>"))
+            .field(
+                "collection_keys",
+                &self.collection_keys.keys().collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl WalEncryptionConfig {
+    /// Cipher for the WAL of the given collection, if any key applies to it
+    pub fn cipher_for(&self, collection_name: &str) -> CollectionResult<Option<Arc<WalCipher>>> {
+        let key = self
+            .collection_keys
+            .get(collection_name)
+            .or(self.key.as_ref());
+
+        key.map(|key| WalCipher::from_hex(key).map(Arc::new))
+            .transpose()
+    }
+}
+
+/// Authenticated encryption of WAL entries with AES-256-GCM.
+///
+/// Every entry is sealed with a random nonce, which is stored in front of the ciphertext.
+pub struct WalCipher {
+    key: LessSafeKey,
+    rng: SystemRandom,
+}
+
+impl fmt::Debug for WalCipher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WalCipher").finish_non_exhaustive()
+    }
+}
+
+impl WalCipher {
+    pub fn new(key: &[u8]) -> CollectionResult<Self> {
+        let key = UnboundKey::new(&AES_256_GCM, key).map_err(|_| {
+            CollectionError::bad_input(format!("WAL encryption key must be {KEY_LEN} bytes long"))
+        })?;
+        Ok(Self {
+            key: LessSafeKey::new(key),
+            rng: SystemRandom::new(),
+        })
+    }
+
+    pub fn from_hex(key: &str) -> CollectionResult<Self> {
+        Self::new(&decode_hex_key(key, "WAL encryption key")?)
+    }
+
+    /// Encrypt data, returns nonce followed by ciphertext and authentication tag
+    pub fn encrypt(&self, data: &[u8]) -> CollectionResult<Vec<u8>> {
+        let mut nonce = [0u8; NONCE_LEN];
+        self.rng
+            .fill(&mut nonce)
+            .map_err(|_| CollectionError::service_error("Failed to generate WAL nonce"))?;
+
+        let mut in_out = Vec::with_capacity(NONCE_LEN + data.len() + AES_256_GCM.tag_len());
+        in_out.extend_from_slice(&nonce);
+        in_out.extend_from_slice(data);
+
+        let tag = self
+            .key
+            .seal_in_place_separate_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::empty(),
+                &mut in_out[NONCE_LEN..],
+            )
+            .map_err(|_| CollectionError::service_error("Failed to encrypt WAL entry"))?;
+        in_out.extend_from_slice(tag.as_ref());
+
+        Ok(in_out)
+    }
+
+    /// Decrypt data produced by [`WalCipher::encrypt`]
+    pub fn decrypt(&self, data: &[u8]) -> CollectionResult<Vec<u8>> {
+        if data.len() < NONCE_LEN + AES_256_GCM.tag_len() {
+            return Err(CollectionError::service_error(
+                "Encrypted WAL entry is truncated",
+            ));
+        }
+
+        let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(nonce)
+            .map_err(|_| CollectionError::service_error("Invalid WAL entry nonce"))?;
+
+        let mut in_out = ciphertext.to_vec();
+        let plaintext_len = self
+            .key
+            .open_in_place(nonce, Aad::empty(), &mut in_out)
+            .map_err(|_| {
+                CollectionError::service_error(
+                    "Failed to decrypt WAL entry, probably wrong encryption key",
+                )
+            })?
+            .len();
+        in_out.truncate(plaintext_len);
+
+        Ok(in_out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+
+    #[test]
+    fn test_wal_cipher_roundtrip() {
+        let cipher = WalCipher::from_hex(KEY).unwrap();
+        let data = b"upsert point 42 with payload";
+
+        let encrypted = cipher.encrypt(data).unwrap();
+        assert!(!encrypted
+            .windows(data.len())
+            .any(|window| window == data.as_slice()));
+        assert_eq!(cipher.decrypt(&encrypted).unwrap(), data);
+
+        // Same data is encrypted differently every time
+        assert_ne!(cipher.encrypt(data).unwrap(), encrypted);
+
+        let other_key = KEY.replace("1f", "ff");
+        let other_cipher = WalCipher::from_hex(&other_key).unwrap();
+        assert!(other_cipher.decrypt(&encrypted).is_err());
+    }
+
+    #[test]
+    fn test_wal_encryption_config() {
+        assert!(WalCipher::from_hex("abc").is_err());
+        assert!(WalCipher::from_hex(&KEY.replace('0', "x")).is_err());
+
+        let config = WalEncryptionConfig {
+            key: None,
+            collection_keys: HashMap::from([("secret".to_string(), KEY.to_string())]),
+        };
+        assert!(config.cipher_for("secret").unwrap().is_some());
+        assert!(config.cipher_for("public").unwrap().is_none());
+        assert!(!format!("{config:?}").contains(KEY));
+    }
+}
@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::collections::{BTreeMap, HashMap};
 use std::fs::File;
 use std::io::{Read, Write};
@@ -10,14 +11,15 @@ use schemars::JsonSchema;
 use segment::common::anonymize::Anonymize;
 use segment::data_types::vectors::DEFAULT_VECTOR_NAME;
 use segment::index::sparse_index::sparse_index_config::{SparseIndexConfig, SparseIndexType};
+use segment::json_path::JsonPath;
 use segment::types::{
     default_replication_factor_const, default_shard_number_const,
     default_write_consistency_factor_const, Distance, HnswConfig, Indexes, PayloadStorageType,
-    QuantizationConfig, SparseVectorDataConfig, VectorDataConfig, VectorStorageDatatype,
-    VectorStorageType,
+    QuantizationConfig, SearchParams, ShardKey, SparseVectorDataConfig, VectorDataConfig,
+    VectorStorageDatatype, VectorStorageType,
 };
 use serde::{Deserialize, Serialize};
-use validator::Validate;
+use validator::{Validate, ValidationError};
 use wal::WalOptions;
 
 use crate::operations::config_diff::{DiffConfig, QuantizationConfigDiff};
@@ -27,6 +29,7 @@ use crate::operations::types::{
 };
 use crate::operations::validation;
 use crate::optimizers_builder::OptimizersConfig;
+use crate::shards::shard::{PeerId, ShardId};
 
 pub const COLLECTION_CONFIG_FILE: &str = "config.json";
 
@@ -37,6 +40,40 @@ pub struct WalConfig {
     pub wal_capacity_mb: usize,
     /// Number of WAL segments to create ahead of actually used ones
     pub wal_segments_ahead: usize,
+    /// Compression of WAL entries. Applies to new entries only, existing entries remain readable.
+    #[serde(default)]
+    pub wal_compression: WalCompression,
+    /// When write operations are persisted to disk
+    #[serde(default)]
+    pub wal_durability: DurabilityPolicy,
+}
+
+/// Trade-off between durability of write operations and ingest throughput
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DurabilityPolicy {
+    /// Fsync WAL after every write operation and flush segments synchronously.
+    /// Acknowledged operations survive power loss.
+    Fsync,
+    /// Fsync WAL for operations with `wait=true` and every `flush_interval_sec` otherwise.
+    #[default]
+    Interval,
+    /// Never fsync WAL explicitly, rely on the OS to write buffered data.
+    /// Recent operations might be lost on power loss, but not on process crash.
+    OsBuffered,
+}
+
+/// Compression algorithm for WAL entries
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum WalCompression {
+    /// Store entries as is
+    #[default]
+    None,
+    /// Fast compression with moderate ratio
+    Lz4,
+    /// Better compression ratio at the cost of more CPU
+    Zstd,
 }
 
 impl From<&WalConfig> for WalOptions {
@@ -53,10 +90,190 @@ impl Default for WalConfig {
         WalConfig {
             wal_capacity_mb: 32,
             wal_segments_ahead: 0,
+            wal_compression: WalCompression::default(),
+            wal_durability: DurabilityPolicy::default(),
         }
     }
 }
 
+/// Configuration of the hot/cold storage tiering.
+///
+/// Segments, which were not accessed for a while, are moved to the cold tier, configured by the
+/// `segment_storage` section of the service configuration.
+/// They are transparently brought back on the next access, which needs them: requests for
+/// specific points restore only the segments holding these points, while searches, scrolls and
+/// updates by filter restore all cold segments of the shard.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub struct TieringConfig {
+    /// If false - segments are never moved to the cold tier, already demoted segments are
+    /// restored on the next access.
+    #[serde(default = "default_tiering_enabled")]
+    pub enabled: bool,
+    /// Number of seconds without any read or write touching a segment, after which the segment
+    /// is moved to the cold tier.
+    #[validate(range(min = 1))]
+    pub cold_after_sec: u64,
+    /// Segments with fewer points are kept in the hot tier, as moving them is not worth the
+    /// hydration latency. Default: 0
+    #[serde(default)]
+    pub min_segment_points: usize,
+}
+
+pub const fn default_tiering_enabled() -> bool {
+    true
+}
+
+/// Configuration of the automatic expiry of points.
+///
+/// Points, which datetime payload field is older than `ttl_sec`, are periodically deleted in
+/// background. The field should be indexed with the `datetime` schema, so that expired points
+/// can be found efficiently. Points without the field never expire.
+///
+/// With `point_expiry`, points may also have their own expiration moment, set with `expires_at`
+/// on insertion.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+#[validate(schema(function = "validate_ttl_config"))]
+pub struct TtlConfig {
+    /// If false - points are never deleted by TTL
+    #[serde(default = "default_ttl_enabled")]
+    pub enabled: bool,
+    /// Payload field with the datetime, from which the lifetime of the point is counted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub field: Option<JsonPath>,
+    /// Number of seconds since the datetime in `field`, after which the point is deleted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub ttl_sec: Option<u64>,
+    /// If true - points may have own expiration moment, set with `expires_at`. Default: false
+    #[serde(default)]
+    pub point_expiry: bool,
+    /// Interval between checks for expired points, in seconds. Default: 60
+    #[serde(default = "default_ttl_check_interval_sec")]
+    #[validate(range(min = 1))]
+    pub check_interval_sec: u64,
+}
+
+fn validate_ttl_config(config: &TtlConfig) -> Result<(), ValidationError> {
+    if config.field.is_some() != config.ttl_sec.is_some() {
+        let mut error = ValidationError::new("invalid_ttl_config");
+        error.add_param(
+            Cow::from("message"),
+            &"`field` and `ttl_sec` must be set together",
+        );
+        return Err(error);
+    }
+    Ok(())
+}
+
+impl TtlConfig {
+    /// Payload field and lifetime of points, if expiry by the collection TTL is configured
+    pub fn collection_ttl(&self) -> Option<(&JsonPath, u64)> {
+        self.field.as_ref().zip(self.ttl_sec)
+    }
+}
+
+pub const fn default_ttl_enabled() -> bool {
+    true
+}
+
+pub const fn default_ttl_check_interval_sec() -> u64 {
+    60
+}
+
+/// Configuration of the asynchronous replication to a remote Qdrant cluster.
+///
+/// Operations are read from the WAL of local shards and replayed, in order, to the remote
+/// collection through its gRPC API. The remote collection is eventually consistent with this one.
+/// Operations are kept in the WAL of the source replica until they are replayed. If the source
+/// replica changes, or replication is paused for too long, all points of the shard are copied to
+/// the remote collection before the replay continues.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub struct AsyncReplicationConfig {
+    /// If false - replication is paused, replayed offsets are kept
+    #[serde(default = "default_async_replication_enabled")]
+    pub enabled: bool,
+    /// gRPC URI of the remote cluster, e.g. `http://dr-cluster:6334`
+    pub uri: String,
+    /// Name of the collection in the remote cluster. Default: name of this collection
+    #[serde(default)]
+    pub collection_name: Option<String>,
+    /// Maximum number of operations of a shard replayed at once. Default: 100
+    #[serde(default = "default_async_replication_batch_size")]
+    #[validate(range(min = 1))]
+    pub batch_size: usize,
+}
+
+const fn default_async_replication_enabled() -> bool {
+    true
+}
+
+const fn default_async_replication_batch_size() -> usize {
+    100
+}
+
+/// Configuration of the embedding service, used to convert raw inputs into vectors.
+///
+/// Documents and images, provided in upserts and searches instead of vectors, are sent to the
+/// endpoint and replaced with the returned embeddings before the request is processed.
+/// The endpoint receives `{"model": ..., "inputs": [{"text": ...} | {"image": ...}]}` and must
+/// respond with `{"embeddings": [[...], ...]}`, one embedding per input.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub struct InferenceConfig {
+    /// HTTP(S) URL of the embedding endpoint, e.g. `http://embedder:8080/embed`
+    pub endpoint: String,
+    /// Name of the model, used if the input does not specify one
+    #[validate(length(min = 1))]
+    pub model: String,
+    /// Timeout of a single request to the endpoint, in seconds. Default: 30
+    #[serde(default = "default_inference_timeout_sec")]
+    #[validate(range(min = 1))]
+    pub timeout_sec: u64,
+}
+
+const fn default_inference_timeout_sec() -> u64 {
+    30
+}
+
+/// Limits of a single tenant of a shared collection.
+///
+/// Tenant is identified by the shard key of its points, or is the whole collection if it is not
+/// sharded by keys. Upserts, which would exceed any of the limits of the tenant, are rejected.
+/// Usage is measured periodically and accounts writes accepted since the last measurement.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub struct QuotaConfig {
+    /// Maximal number of points of a tenant
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_points: Option<usize>,
+    /// Maximal total size of payloads of a tenant, in bytes of serialized JSON
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_payload_bytes: Option<usize>,
+    /// Maximal number of points upserted by a tenant per second
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub max_writes_per_second: Option<usize>,
+}
+
+/// Multitenancy mode, where each point belongs to a tenant, identified by a payload field.
+///
+/// Every upserted point must have the tenant field, and access, which is limited to a tenant,
+/// only sees and modifies points of that tenant. The tenant field is indexed, so that points of
+/// each tenant are linked together in the vector index of every segment.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub struct MultitenancyConfig {
+    /// Payload field, which holds the tenant of a point
+    pub tenant_key: JsonPath,
+    /// If true - optimizers rewrite segments so that points of each tenant are stored next to
+    /// each other, and searches within a tenant touch fewer pages. Default: false
+    #[serde(default)]
+    pub defragment: bool,
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Eq, Hash, Clone, Copy, Default)]
 #[serde(rename_all = "snake_case")]
 pub enum ShardingMethod {
@@ -65,6 +282,21 @@ pub enum ShardingMethod {
     Custom,
 }
 
+/// Engine used by segments to persist payload and point id mappings
+#[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Eq, Hash, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageEngine {
+    #[default]
+    Rocksdb,
+    /// Append-only logs with in-memory index, payload is read through mmap.
+    /// Avoids RocksDB compaction stalls and memory overhead for simple point lookups.
+    AppendOnly,
+    /// On-disk payload in mmapped blocks with a compact on-disk offset index,
+    /// point ids in append-only logs.
+    /// Reduces resident memory for collections with large payloads.
+    Mmap,
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub struct CollectionParams {
@@ -106,14 +338,86 @@ pub struct CollectionParams {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     #[validate]
     pub sparse_vectors: Option<BTreeMap<String, SparseVectorParams>>,
+    /// Engine to store payload and point ids with.
+    /// Default is RocksDB. `append_only` and `mmap` always keep payload on disk,
+    /// `on_disk_payload` is ignored for them.
+    /// Can't be changed after the collection is created.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage_engine: Option<StorageEngine>,
+    /// Payload fields, values of which are additionally stored column-wise.
+    /// Filtering by those fields and scanning their values doesn't require reading whole payloads.
+    /// Can't be changed after the collection is created.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload_columns: Option<Vec<JsonPath>>,
+    /// Only peers with all of these labels may host shards of the collection.
+    /// Can't be changed after the collection is created.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub placement_labels: Option<BTreeMap<String, String>>,
+    /// Shards, replicas of which may only be placed on specific peers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub shard_pinning: Option<Vec<ShardPin>>,
+}
+
+/// Pins replicas of a shard, or of all shards of a shard key, to specific peers.
+/// Exactly one of `shard_id` and `shard_key` must be set.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq, Hash)]
+#[validate(schema(function = "validate_shard_pin"))]
+pub struct ShardPin {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard_id: Option<ShardId>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKey>,
+    /// Peers allowed to host replicas of the shard
+    #[validate(length(min = 1))]
+    pub peers: Vec<PeerId>,
+}
+
+fn validate_shard_pin(pin: &ShardPin) -> Result<(), ValidationError> {
+    if pin.shard_id.is_some() == pin.shard_key.is_some() {
+        let mut error = ValidationError::new("invalid_shard_pin");
+        error.add_param(
+            Cow::from("message"),
+            &"exactly one of `shard_id` and `shard_key` must be set",
+        );
+        return Err(error);
+    }
+    Ok(())
+}
+
+/// Peers allowed to host replicas of the shard, `None` if the shard is not pinned
+///
+/// A pin of the shard id takes precedence over a pin of its shard key.
+pub fn pinned_peers<'a>(
+    shard_pinning: Option<&'a [ShardPin]>,
+    shard_id: ShardId,
+    shard_key: Option<&ShardKey>,
+) -> Option<&'a [PeerId]> {
+    let pins = shard_pinning?;
+    pins.iter()
+        .find(|pin| pin.shard_id == Some(shard_id))
+        .or_else(|| {
+            shard_key.and_then(|key| pins.iter().find(|pin| pin.shard_key.as_ref() == Some(key)))
+        })
+        .map(|pin| pin.peers.as_slice())
 }
 
 impl CollectionParams {
+    /// Peers allowed to host replicas of the shard, `None` if the shard is not pinned
+    pub fn pinned_peers(
+        &self,
+        shard_id: ShardId,
+        shard_key: Option<&ShardKey>,
+    ) -> Option<&[PeerId]> {
+        pinned_peers(self.shard_pinning.as_deref(), shard_id, shard_key)
+    }
+
     pub fn payload_storage_type(&self) -> PayloadStorageType {
-        if self.on_disk_payload {
-            PayloadStorageType::OnDisk
-        } else {
-            PayloadStorageType::InMemory
+        match self.storage_engine.unwrap_or_default() {
+            StorageEngine::AppendOnly => PayloadStorageType::AppendOnly,
+            StorageEngine::Mmap => PayloadStorageType::Mmap,
+            StorageEngine::Rocksdb if self.on_disk_payload => PayloadStorageType::OnDisk,
+            StorageEngine::Rocksdb => PayloadStorageType::InMemory,
         }
     }
 }
@@ -129,6 +433,10 @@ impl Anonymize for CollectionParams {
             read_fan_out_factor: self.read_fan_out_factor,
             on_disk_payload: self.on_disk_payload,
             sparse_vectors: self.sparse_vectors.anonymize(),
+            storage_engine: self.storage_engine,
+            payload_columns: self.payload_columns.anonymize(),
+            placement_labels: self.placement_labels.anonymize(),
+            shard_pinning: self.shard_pinning.clone(),
         }
     }
 }
@@ -161,9 +469,35 @@ pub struct CollectionConfig {
     pub wal_config: WalConfig,
     #[serde(default)]
     pub quantization_config: Option<QuantizationConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub tiering_config: Option<TieringConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub ttl_config: Option<TtlConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub async_replication_config: Option<AsyncReplicationConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub inference_config: Option<InferenceConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub quota_config: Option<QuotaConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub multitenancy_config: Option<MultitenancyConfig>,
 }
 
 impl CollectionConfig {
+    /// Payload field, by which optimizers group points in segments, if enabled
+    pub fn defragment_key(&self) -> Option<&JsonPath> {
+        self.multitenancy_config
+            .as_ref()
+            .filter(|config| config.defragment)
+            .map(|config| &config.tenant_key)
+    }
+
     pub fn save(&self, path: &Path) -> CollectionResult<()> {
         let config_path = path.join(COLLECTION_CONFIG_FILE);
         let af = AtomicFile::new(&config_path, AllowOverwrite);
@@ -206,6 +540,10 @@ impl CollectionParams {
             read_fan_out_factor: None,
             on_disk_payload: default_on_disk_payload(),
             sparse_vectors: None,
+            storage_engine: None,
+            payload_columns: None,
+            placement_labels: None,
+            shard_pinning: None,
         }
     }
 
@@ -235,6 +573,31 @@ impl CollectionParams {
         }
     }
 
+    /// Get distance to score the search with, considering the override from search params
+    pub fn get_search_distance(
+        &self,
+        vector_name: &str,
+        params: Option<&SearchParams>,
+    ) -> CollectionResult<Distance> {
+        let distance = self.get_distance(vector_name)?;
+        let Some(search_distance) = params.and_then(|params| params.distance) else {
+            return Ok(distance);
+        };
+        if self.vectors.get_params(vector_name).is_none() {
+            return Err(CollectionError::BadInput {
+                description: format!("Distance of sparse vector {vector_name} can't be overridden"),
+            });
+        }
+        if !search_distance.is_applicable_to(distance) {
+            return Err(CollectionError::BadInput {
+                description: format!(
+                    "Vector {vector_name} with {distance:?} distance can't be scored with {search_distance:?} distance"
+                ),
+            });
+        }
+        Ok(search_distance)
+    }
+
     fn get_vector_params_mut(&mut self, vector_name: &str) -> CollectionResult<&mut VectorParams> {
         self.vectors
             .get_params_mut(vector_name)
@@ -318,7 +681,20 @@ impl CollectionParams {
     ) -> CollectionResult<()> {
         for (vector_name, update_params) in update_vectors.0.iter() {
             let sparse_vector_params = self.get_sparse_vector_params_mut(vector_name)?;
-            let SparseVectorParams { index, modifier } = update_params.clone();
+            let SparseVectorParams {
+                index,
+                modifier,
+                text,
+            } = update_params.clone();
+
+            // Stored vectors were produced with the current conversion
+            if text.is_some() && text != sparse_vector_params.text {
+                return Err(CollectionError::BadInput {
+                    description: format!(
+                        "Text conversion of sparse vector {vector_name} can't be changed"
+                    ),
+                });
+            }
 
             if let Some(modifier) = modifier {
                 sparse_vector_params.modifier = Some(modifier);
@@ -339,7 +715,13 @@ impl CollectionParams {
     ///
     /// It is the job of the segment optimizer to change this configuration with optimized settings
     /// based on threshold configurations.
-    pub fn to_base_vector_data(&self) -> CollectionResult<HashMap<String, VectorDataConfig>> {
+    ///
+    /// `quantization_config` of the collection is used for vectors without their own one, to
+    /// maintain quantized vectors in appendable segments.
+    pub fn to_base_vector_data(
+        &self,
+        quantization_config: Option<&QuantizationConfig>,
+    ) -> CollectionResult<HashMap<String, VectorDataConfig>> {
         Ok(self
             .vectors
             .params_iter()
@@ -351,8 +733,12 @@ impl CollectionParams {
                         distance: params.distance,
                         // Plain (disabled) index
                         index: Indexes::Plain {},
-                        // Disabled quantization
-                        quantization_config: None,
+                        // Quantization of vector params overrides the one of the collection
+                        quantization_config: params
+                            .quantization_config
+                            .as_ref()
+                            .or(quantization_config)
+                            .cloned(),
                         // Default to in memory storage
                         storage_type: if params.on_disk.unwrap_or_default() {
                             VectorStorageType::ChunkedMmap
@@ -1,8 +1,10 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use segment::json_path::JsonPathV2;
 use segment::types::{Filter, PayloadFieldSchema};
+use serde::Serialize;
 
+use crate::shards::shard::ShardId;
 use crate::shards::CollectionId;
 
 pub struct CollectionDeletedEvent {
@@ -15,6 +17,33 @@ pub struct SlowQueryEvent {
     pub schema: HashMap<JsonPathV2, PayloadFieldSchema>,
 }
 
+/// Search, scroll or update, which took longer than the slow log threshold
+#[derive(Debug, Serialize)]
+pub struct SlowOperationEvent {
+    #[serde(rename = "collection")]
+    pub collection_id: CollectionId,
+    /// `search`, `scroll` or `update`
+    pub operation: &'static str,
+    /// Duration of the whole operation in seconds
+    pub took: f64,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub filters: Vec<Filter>,
+    /// Parameters of the operation, without vectors and payloads
+    pub params: serde_json::Value,
+    pub shards: Vec<ShardTiming>,
+}
+
+/// Part of a slow operation on a single shard
+#[derive(Debug, Serialize)]
+pub struct ShardTiming {
+    pub shard_id: ShardId,
+    /// Duration in seconds, including the network round trip to remote replicas
+    pub took: f64,
+    /// Number of segment searches by chosen strategy, only known for local replicas
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub plan: BTreeMap<&'static str, usize>,
+}
+
 pub struct IndexCreatedEvent {
     pub collection_id: CollectionId,
     pub field_name: JsonPathV2,
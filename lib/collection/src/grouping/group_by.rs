@@ -82,7 +82,7 @@ impl GroupRequest {
         Fut: Future<Output = Option<RwLockReadGuard<'a, Collection>>>,
     {
         let core_search = match self.source {
-            SourceRequest::Search(search_req) => search_req.into(),
+            SourceRequest::Search(search_req) => search_req.try_into()?,
             SourceRequest::Recommend(recommend_req) => {
                 let referenced_vectors = fetch_vectors::resolve_referenced_vectors_batch(
                     &[(recommend_req.clone(), shard_selection)],
@@ -235,7 +235,9 @@ pub async fn group_by(
     let score_ordering = if request.source.query.is_distance_scored() {
         let vector_name = request.source.query.get_vector_name();
         let collection_params = collection.collection_config.read().await;
-        let distance = collection_params.params.get_distance(vector_name)?;
+        let distance = collection_params
+            .params
+            .get_search_distance(vector_name, request.source.params.as_ref())?;
         distance.distance_order()
     } else {
         Order::LargeBetter
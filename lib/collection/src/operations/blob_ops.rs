@@ -0,0 +1,84 @@
+use std::borrow::Cow;
+
+use segment::blob_storage::MAX_BLOB_SIZE;
+use segment::types::PointIdType;
+use serde::{Deserialize, Serialize};
+use strum::{EnumDiscriminants, EnumIter};
+use validator::{Validate, ValidationError};
+
+use super::{point_to_shards, OperationToShard, SplitByShard};
+use crate::hash_ring::HashRing;
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, Validate)]
+pub struct SetBlobOp {
+    /// Point to attach the blob to
+    pub point_id: PointIdType,
+    /// Opaque binary data
+    #[validate(custom = "validate_blob_size")]
+    pub blob: Vec<u8>,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct DeleteBlobOp {
+    /// Point to remove the blob from
+    pub point_id: PointIdType,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, EnumDiscriminants)]
+#[strum_discriminants(derive(EnumIter))]
+#[serde(rename_all = "snake_case")]
+pub enum BlobOperations {
+    /// Attach blob to the point, replacing the existing one
+    SetBlob(SetBlobOp),
+    /// Remove blob of the point, if any
+    DeleteBlob(DeleteBlobOp),
+}
+
+impl BlobOperations {
+    pub fn is_write_operation(&self) -> bool {
+        match self {
+            BlobOperations::SetBlob(_) => true,
+            BlobOperations::DeleteBlob(_) => false,
+        }
+    }
+
+    pub fn point_id(&self) -> PointIdType {
+        match self {
+            BlobOperations::SetBlob(op) => op.point_id,
+            BlobOperations::DeleteBlob(op) => op.point_id,
+        }
+    }
+}
+
+impl Validate for BlobOperations {
+    fn validate(&self) -> Result<(), validator::ValidationErrors> {
+        match self {
+            BlobOperations::SetBlob(set_blob) => set_blob.validate(),
+            BlobOperations::DeleteBlob(_) => Ok(()),
+        }
+    }
+}
+
+impl SplitByShard for BlobOperations {
+    fn split_by_shard(self, ring: &HashRing) -> OperationToShard<Self> {
+        let shard_ids = point_to_shards(&self.point_id(), ring);
+        OperationToShard::by_shard(
+            shard_ids
+                .into_iter()
+                .map(|shard_id| (shard_id, self.clone())),
+        )
+    }
+}
+
+pub fn validate_blob_size(blob: &[u8]) -> Result<(), ValidationError> {
+    if !blob.is_empty() && blob.len() <= MAX_BLOB_SIZE {
+        return Ok(());
+    }
+    let mut err = ValidationError::new("length");
+    err.message = Some(Cow::from(format!(
+        "must be from 1 to {MAX_BLOB_SIZE} bytes"
+    )));
+    err.add_param(Cow::from("min"), &1);
+    err.add_param(Cow::from("max"), &MAX_BLOB_SIZE);
+    Err(err)
+}
@@ -8,9 +8,9 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use validator::{Validate, ValidationErrors};
 
-use crate::config::{CollectionParams, WalConfig};
+use crate::config::{CollectionParams, DurabilityPolicy, WalCompression, WalConfig};
 use crate::operations::types::CollectionResult;
-use crate::optimizers_builder::OptimizersConfig;
+use crate::optimizers_builder::{MergePolicy, OptimizersConfig};
 
 // Structures for partial update of collection params
 // TODO: make auto-generated somehow...
@@ -94,6 +94,12 @@ pub struct WalConfigDiff {
     pub wal_capacity_mb: Option<usize>,
     /// Number of WAL segments to create ahead of actually used ones
     pub wal_segments_ahead: Option<usize>,
+    /// Compression of WAL entries
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wal_compression: Option<WalCompression>,
+    /// When write operations are persisted to disk
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wal_durability: Option<DurabilityPolicy>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Merge, PartialEq, Eq, Hash)]
@@ -162,6 +168,15 @@ pub struct OptimizersConfigDiff {
     /// If null - have no limit and choose dynamically to saturate CPU.
     /// If 0 - no optimization threads, optimizations will be disabled.
     pub max_optimization_threads: Option<usize>,
+    /// Strategy of selecting segments to merge, when there are more segments than `default_segment_number`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub merge_policy: Option<MergePolicy>,
+    /// Max number of CPUs, which optimizations of all shards of the collection may use at once on each peer.
+    /// Optimizations are also limited by the CPU budget of the peer.
+    /// Can be changed at runtime, to keep indexing of one collection from starving searches of others.
+    /// If null or 0 - limited only by the CPU budget of the peer.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu_budget: Option<usize>,
 }
 
 impl std::hash::Hash for OptimizersConfigDiff {
@@ -174,6 +189,8 @@ impl std::hash::Hash for OptimizersConfigDiff {
         self.indexing_threshold.hash(state);
         self.flush_interval_sec.hash(state);
         self.max_optimization_threads.hash(state);
+        self.merge_policy.hash(state);
+        self.cpu_budget.hash(state);
     }
 }
 
@@ -188,6 +205,8 @@ impl PartialEq for OptimizersConfigDiff {
             && self.indexing_threshold == other.indexing_threshold
             && self.flush_interval_sec == other.flush_interval_sec
             && self.max_optimization_threads == other.max_optimization_threads
+            && self.merge_policy == other.merge_policy
+            && self.cpu_budget == other.cpu_budget
     }
 }
 
@@ -340,6 +359,7 @@ mod tests {
             vectors: VectorParamsBuilder::new(128, Distance::Cosine)
                 .build()
                 .into(),
+            storage_engine: None,
             ..CollectionParams::empty()
         };
 
@@ -376,6 +396,8 @@ mod tests {
             indexing_threshold: Some(50_000),
             flush_interval_sec: 30,
             max_optimization_threads: Some(1),
+            merge_policy: MergePolicy::SizeTiered,
+            cpu_budget: None,
         };
         let update: OptimizersConfigDiff =
             serde_json::from_str(r#"{ "indexing_threshold": 10000 }"#).unwrap();
@@ -388,6 +410,13 @@ mod tests {
         let base_config = WalConfig::default();
         let update: WalConfigDiff = serde_json::from_str(r#"{ "wal_segments_ahead": 2 }"#).unwrap();
         let new_config = update.update(&base_config).unwrap();
-        assert_eq!(new_config.wal_segments_ahead, 2)
+        assert_eq!(new_config.wal_segments_ahead, 2);
+        assert_eq!(new_config.wal_durability, DurabilityPolicy::Interval);
+
+        let update: WalConfigDiff =
+            serde_json::from_str(r#"{ "wal_durability": "os_buffered" }"#).unwrap();
+        let new_config = update.update(&new_config).unwrap();
+        assert_eq!(new_config.wal_segments_ahead, 2);
+        assert_eq!(new_config.wal_durability, DurabilityPolicy::OsBuffered);
     }
 }
@@ -4,7 +4,8 @@ use std::time::Duration;
 
 use api::grpc::conversions::{
     convert_shard_key_from_grpc, convert_shard_key_from_grpc_opt, convert_shard_key_to_grpc,
-    from_grpc_dist, json_path_from_proto, payload_to_proto, proto_to_payloads,
+    date_time_to_proto, from_grpc_dist, json_path_from_proto, payload_to_proto, proto_to_payloads,
+    try_date_time_from_proto,
 };
 use api::grpc::qdrant::quantization_config_diff::Quantization;
 use api::grpc::qdrant::update_collection_cluster_setup_request::{
@@ -31,8 +32,9 @@ use super::types::{
     VectorsConfigDiff,
 };
 use crate::config::{
-    default_replication_factor, default_write_consistency_factor, CollectionConfig,
-    CollectionParams, ShardingMethod, WalConfig,
+    default_replication_factor, default_tiering_enabled, default_ttl_check_interval_sec,
+    default_ttl_enabled, default_write_consistency_factor, CollectionConfig, CollectionParams,
+    DurabilityPolicy, ShardingMethod, TieringConfig, TtlConfig, WalCompression, WalConfig,
 };
 use crate::lookup::types::WithLookupInterface;
 use crate::lookup::WithLookup;
@@ -48,7 +50,7 @@ use crate::operations::config_diff::{
 };
 use crate::operations::point_ops::PointsSelector::PointIdsSelector;
 use crate::operations::point_ops::{
-    Batch, FilterSelector, PointIdsList, PointStruct, PointsSelector, WriteOrdering,
+    Batch, FilterSelector, PointIdsList, PointStruct, PointsSelector, UpdateMode, WriteOrdering,
 };
 use crate::operations::query_enum::QueryEnum;
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
@@ -58,7 +60,7 @@ use crate::operations::types::{
     RemoteShardInfo, SearchRequestInternal, ShardTransferInfo, UpdateResult, UpdateStatus,
     VectorParams, VectorsConfig,
 };
-use crate::optimizers_builder::OptimizersConfig;
+use crate::optimizers_builder::{MergePolicy, OptimizersConfig};
 use crate::shards::remote_shard::{CollectionCoreSearchRequest, CollectionSearchRequest};
 use crate::shards::replica_set::ReplicaState;
 use crate::shards::transfer::ShardTransferMethod;
@@ -116,6 +118,28 @@ pub fn write_ordering_from_proto(
     })
 }
 
+pub fn update_mode_to_proto(update_mode: UpdateMode) -> api::grpc::qdrant::UpdateMode {
+    match update_mode {
+        UpdateMode::Upsert => api::grpc::qdrant::UpdateMode::Upsert,
+        UpdateMode::InsertOnly => api::grpc::qdrant::UpdateMode::InsertOnly,
+        UpdateMode::UpdateOnly => api::grpc::qdrant::UpdateMode::UpdateOnly,
+    }
+}
+
+pub fn update_mode_from_proto(update_mode: Option<i32>) -> Result<Option<UpdateMode>, Status> {
+    let Some(update_mode) = update_mode else {
+        return Ok(None);
+    };
+    let update_mode = api::grpc::qdrant::UpdateMode::from_i32(update_mode).ok_or_else(|| {
+        Status::invalid_argument(format!("cannot convert update mode: {update_mode}"))
+    })?;
+    Ok(Some(match update_mode {
+        api::grpc::qdrant::UpdateMode::Upsert => UpdateMode::Upsert,
+        api::grpc::qdrant::UpdateMode::InsertOnly => UpdateMode::InsertOnly,
+        api::grpc::qdrant::UpdateMode::UpdateOnly => UpdateMode::UpdateOnly,
+    }))
+}
+
 pub fn try_record_from_grpc(
     point: api::grpc::qdrant::RetrievedPoint,
     with_payload: bool,
@@ -142,6 +166,7 @@ pub fn try_record_from_grpc(
         payload,
         vector,
         shard_key: convert_shard_key_from_grpc_opt(point.shard_key),
+        version: point.version,
     })
 }
 
@@ -256,6 +281,129 @@ impl From<api::grpc::qdrant::WalConfigDiff> for WalConfigDiff {
         Self {
             wal_capacity_mb: value.wal_capacity_mb.map(|v| v as usize),
             wal_segments_ahead: value.wal_segments_ahead.map(|v| v as usize),
+            wal_compression: value
+                .wal_compression
+                .and_then(api::grpc::qdrant::WalCompression::from_i32)
+                .map(WalCompression::from),
+            wal_durability: value
+                .wal_durability
+                .and_then(api::grpc::qdrant::DurabilityPolicy::from_i32)
+                .map(DurabilityPolicy::from),
+        }
+    }
+}
+
+impl From<api::grpc::qdrant::WalCompression> for WalCompression {
+    fn from(value: api::grpc::qdrant::WalCompression) -> Self {
+        match value {
+            api::grpc::qdrant::WalCompression::NoCompression => WalCompression::None,
+            api::grpc::qdrant::WalCompression::Lz4 => WalCompression::Lz4,
+            api::grpc::qdrant::WalCompression::Zstd => WalCompression::Zstd,
+        }
+    }
+}
+
+impl From<WalCompression> for api::grpc::qdrant::WalCompression {
+    fn from(value: WalCompression) -> Self {
+        match value {
+            WalCompression::None => api::grpc::qdrant::WalCompression::NoCompression,
+            WalCompression::Lz4 => api::grpc::qdrant::WalCompression::Lz4,
+            WalCompression::Zstd => api::grpc::qdrant::WalCompression::Zstd,
+        }
+    }
+}
+
+impl From<api::grpc::qdrant::DurabilityPolicy> for DurabilityPolicy {
+    fn from(value: api::grpc::qdrant::DurabilityPolicy) -> Self {
+        match value {
+            api::grpc::qdrant::DurabilityPolicy::Fsync => DurabilityPolicy::Fsync,
+            api::grpc::qdrant::DurabilityPolicy::Interval => DurabilityPolicy::Interval,
+            api::grpc::qdrant::DurabilityPolicy::OsBuffered => DurabilityPolicy::OsBuffered,
+        }
+    }
+}
+
+impl From<DurabilityPolicy> for api::grpc::qdrant::DurabilityPolicy {
+    fn from(value: DurabilityPolicy) -> Self {
+        match value {
+            DurabilityPolicy::Fsync => api::grpc::qdrant::DurabilityPolicy::Fsync,
+            DurabilityPolicy::Interval => api::grpc::qdrant::DurabilityPolicy::Interval,
+            DurabilityPolicy::OsBuffered => api::grpc::qdrant::DurabilityPolicy::OsBuffered,
+        }
+    }
+}
+
+impl From<api::grpc::qdrant::MergePolicy> for MergePolicy {
+    fn from(value: api::grpc::qdrant::MergePolicy) -> Self {
+        match value {
+            api::grpc::qdrant::MergePolicy::SizeTiered => MergePolicy::SizeTiered,
+            api::grpc::qdrant::MergePolicy::Leveled => MergePolicy::Leveled,
+        }
+    }
+}
+
+impl From<MergePolicy> for api::grpc::qdrant::MergePolicy {
+    fn from(value: MergePolicy) -> Self {
+        match value {
+            MergePolicy::SizeTiered => api::grpc::qdrant::MergePolicy::SizeTiered,
+            MergePolicy::Leveled => api::grpc::qdrant::MergePolicy::Leveled,
+        }
+    }
+}
+
+impl From<api::grpc::qdrant::TieringConfig> for TieringConfig {
+    fn from(value: api::grpc::qdrant::TieringConfig) -> Self {
+        Self {
+            enabled: value.enabled.unwrap_or_else(default_tiering_enabled),
+            cold_after_sec: value.cold_after_sec,
+            min_segment_points: value.min_segment_points.unwrap_or_default() as usize,
+        }
+    }
+}
+
+impl From<TieringConfig> for api::grpc::qdrant::TieringConfig {
+    fn from(value: TieringConfig) -> Self {
+        Self {
+            enabled: Some(value.enabled),
+            cold_after_sec: value.cold_after_sec,
+            min_segment_points: Some(value.min_segment_points as u64),
+        }
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::TtlConfig> for TtlConfig {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::TtlConfig) -> Result<Self, Self::Error> {
+        if value.field.is_some() != value.ttl_sec.is_some() {
+            return Err(Status::invalid_argument(
+                "`field` and `ttl_sec` must be set together",
+            ));
+        }
+        Ok(Self {
+            enabled: value.enabled.unwrap_or_else(default_ttl_enabled),
+            field: value
+                .field
+                .as_deref()
+                .map(json_path_from_proto)
+                .transpose()?,
+            ttl_sec: value.ttl_sec,
+            point_expiry: value.point_expiry.unwrap_or_default(),
+            check_interval_sec: value
+                .check_interval_sec
+                .unwrap_or_else(default_ttl_check_interval_sec),
+        })
+    }
+}
+
+impl From<TtlConfig> for api::grpc::qdrant::TtlConfig {
+    fn from(value: TtlConfig) -> Self {
+        Self {
+            enabled: Some(value.enabled),
+            field: value.field.map(|field| field.to_string()),
+            ttl_sec: value.ttl_sec,
+            check_interval_sec: Some(value.check_interval_sec),
+            point_expiry: Some(value.point_expiry),
         }
     }
 }
@@ -297,6 +445,11 @@ impl From<api::grpc::qdrant::OptimizersConfigDiff> for OptimizersConfigDiff {
             indexing_threshold: value.indexing_threshold.map(|v| v as usize),
             flush_interval_sec: value.flush_interval_sec,
             max_optimization_threads: value.max_optimization_threads.map(|v| v as usize),
+            merge_policy: value
+                .merge_policy
+                .and_then(api::grpc::qdrant::MergePolicy::from_i32)
+                .map(MergePolicy::from),
+            cpu_budget: None,
         }
     }
 }
@@ -330,6 +483,7 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
             segments_count,
             config,
             payload_schema,
+            sparse_vectors_stats: _,
         } = value;
 
         api::grpc::qdrant::CollectionInfo {
@@ -421,12 +575,23 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
                         .optimizer_config
                         .max_optimization_threads
                         .map(|n| n as u64),
+                    merge_policy: Some(api::grpc::qdrant::MergePolicy::from(
+                        config.optimizer_config.merge_policy,
+                    ) as i32),
                 }),
                 wal_config: Some(api::grpc::qdrant::WalConfigDiff {
                     wal_capacity_mb: Some(config.wal_config.wal_capacity_mb as u64),
                     wal_segments_ahead: Some(config.wal_config.wal_segments_ahead as u64),
+                    wal_compression: Some(api::grpc::qdrant::WalCompression::from(
+                        config.wal_config.wal_compression,
+                    ) as i32),
+                    wal_durability: Some(api::grpc::qdrant::DurabilityPolicy::from(
+                        config.wal_config.wal_durability,
+                    ) as i32),
                 }),
                 quantization_config: config.quantization_config.map(|x| x.into()),
+                tiering_config: config.tiering_config.map(Into::into),
+                ttl_config: config.ttl_config.map(Into::into),
             }),
             payload_schema: payload_schema
                 .into_iter()
@@ -445,6 +610,7 @@ impl From<Record> for api::grpc::qdrant::RetrievedPoint {
             payload: record.payload.map(payload_to_proto).unwrap_or_default(),
             vectors: vectors.map(api::grpc::qdrant::Vectors::from),
             shard_key: record.shard_key.map(convert_shard_key_to_grpc),
+            version: record.version,
         }
     }
 }
@@ -478,6 +644,12 @@ impl From<api::grpc::qdrant::OptimizersConfigDiff> for OptimizersConfig {
             max_optimization_threads: optimizer_config
                 .max_optimization_threads
                 .map(|n| n as usize),
+            merge_policy: optimizer_config
+                .merge_policy
+                .and_then(api::grpc::qdrant::MergePolicy::from_i32)
+                .map(MergePolicy::from)
+                .unwrap_or_default(),
+            cpu_budget: None,
         }
     }
 }
@@ -487,6 +659,16 @@ impl From<api::grpc::qdrant::WalConfigDiff> for WalConfig {
         Self {
             wal_capacity_mb: wal_config.wal_capacity_mb.unwrap_or_default() as usize,
             wal_segments_ahead: wal_config.wal_segments_ahead.unwrap_or_default() as usize,
+            wal_compression: wal_config
+                .wal_compression
+                .and_then(api::grpc::qdrant::WalCompression::from_i32)
+                .map(WalCompression::from)
+                .unwrap_or_default(),
+            wal_durability: wal_config
+                .wal_durability
+                .and_then(api::grpc::qdrant::DurabilityPolicy::from_i32)
+                .map(DurabilityPolicy::from)
+                .unwrap_or_default(),
         }
     }
 }
@@ -552,6 +734,8 @@ impl TryFrom<api::grpc::qdrant::VectorParams> for VectorParams {
                 .multivector_config
                 .map(MultiVectorConfig::try_from)
                 .transpose()?,
+            // Not part of the gRPC API
+            projection: None,
         })
     }
 }
@@ -564,6 +748,7 @@ fn convert_datatype_from_proto(datatype: Option<i32>) -> Result<Option<Datatype>
                 api::grpc::qdrant::Datatype::Uint8 => Ok(Some(Datatype::Uint8)),
                 api::grpc::qdrant::Datatype::Float32 => Ok(Some(Datatype::Float32)),
                 api::grpc::qdrant::Datatype::Float16 => Ok(Some(Datatype::Float16)),
+                api::grpc::qdrant::Datatype::Float64 => Ok(Some(Datatype::Float64)),
                 api::grpc::qdrant::Datatype::Default => Ok(None),
             }
         } else {
@@ -614,6 +799,7 @@ impl From<api::grpc::qdrant::SparseVectorParams> for SparseVectorParams {
                 .modifier
                 .and_then(api::grpc::qdrant::Modifier::from_i32)
                 .map(Modifier::from),
+            text: None, // Not part of the gRPC API
         }
     }
 }
@@ -629,6 +815,7 @@ impl From<Modifier> for api::grpc::qdrant::Modifier {
 
 impl From<SparseVectorParams> for api::grpc::qdrant::SparseVectorParams {
     fn from(sparse_vector_params: SparseVectorParams) -> Self {
+        // Text conversion is not part of the gRPC API
         Self {
             index: sparse_vector_params.index.map(|index_config| {
                 api::grpc::qdrant::SparseIndexConfig {
@@ -728,6 +915,10 @@ impl TryFrom<api::grpc::qdrant::CollectionConfig> for CollectionConfig {
                         .sharding_method
                         .map(sharding_method_from_proto)
                         .transpose()?,
+                    storage_engine: None,
+                    payload_columns: None,
+                    placement_labels: None,
+                    shard_pinning: None,
                 },
             },
             hnsw_config: match config.hnsw_config {
@@ -749,6 +940,12 @@ impl TryFrom<api::grpc::qdrant::CollectionConfig> for CollectionConfig {
                     None
                 }
             },
+            tiering_config: config.tiering_config.map(Into::into),
+            ttl_config: config.ttl_config.map(TryInto::try_into).transpose()?,
+            async_replication_config: None,
+            inference_config: None,
+            quota_config: None,
+            multitenancy_config: None,
         })
     }
 }
@@ -794,6 +991,8 @@ impl TryFrom<api::grpc::qdrant::GetCollectionInfoResponse> for CollectionInfo {
                     .into_iter()
                     .map(|(k, v)| Ok::<_, Status>((json_path_from_proto(&k)?, v.try_into()?)))
                     .try_collect()?,
+                // Not part of the gRPC API, only available for local shards
+                sparse_vectors_stats: HashMap::new(),
             }),
         }
     }
@@ -807,6 +1006,7 @@ impl TryFrom<api::grpc::qdrant::PointStruct> for PointStruct {
             id,
             vectors,
             payload,
+            expires_at,
         } = value;
 
         // empty payload means None in PointStruct
@@ -827,6 +1027,7 @@ impl TryFrom<api::grpc::qdrant::PointStruct> for PointStruct {
                 .try_into()?,
             vector: api::rest::VectorStruct::from(vector_struct),
             payload: converted_payload,
+            expires_at: expires_at.map(try_date_time_from_proto).transpose()?,
         })
     }
 }
@@ -835,11 +1036,15 @@ impl TryFrom<PointStruct> for api::grpc::qdrant::PointStruct {
     type Error = Status;
 
     fn try_from(value: PointStruct) -> Result<Self, Self::Error> {
-        let vectors = VectorStruct::from(value.vector);
-        let vectors = api::grpc::qdrant::Vectors::from(vectors);
+        let PointStruct {
+            id,
+            vector,
+            payload,
+            expires_at,
+        } = value;
 
-        let id = value.id;
-        let payload = value.payload;
+        let vectors = VectorStruct::try_from(vector)?;
+        let vectors = api::grpc::qdrant::Vectors::from(vectors);
 
         let converted_payload = match payload {
             None => HashMap::new(),
@@ -850,6 +1055,7 @@ impl TryFrom<PointStruct> for api::grpc::qdrant::PointStruct {
             id: Some(id.into()),
             vectors: Some(vectors),
             payload: converted_payload,
+            expires_at: expires_at.map(date_time_to_proto),
         })
     }
 }
@@ -859,7 +1065,7 @@ impl TryFrom<Batch> for Vec<api::grpc::qdrant::PointStruct> {
 
     fn try_from(batch: Batch) -> Result<Self, Self::Error> {
         let mut points = Vec::new();
-        let batch_vectors = BatchVectorStruct::from(batch.vectors);
+        let batch_vectors = BatchVectorStruct::try_from(batch.vectors)?;
         let all_vectors = batch_vectors.into_all_vectors(batch.ids.len());
         for (i, p_id) in batch.ids.into_iter().enumerate() {
             let id = Some(p_id.into());
@@ -876,6 +1082,7 @@ impl TryFrom<Batch> for Vec<api::grpc::qdrant::PointStruct> {
                 id,
                 vectors: vectors.map(|v| v.into()),
                 payload: payload.unwrap_or_default(),
+                expires_at: None,
             };
             points.push(point);
         }
@@ -1039,16 +1246,22 @@ impl TryFrom<api::grpc::qdrant::SearchPoints> for CoreSearchRequest {
 }
 
 // Use wrapper type to bundle CollectionId & SearchRequest
-impl<'a> From<CollectionSearchRequest<'a>> for api::grpc::qdrant::SearchPoints {
-    fn from(value: CollectionSearchRequest<'a>) -> Self {
+impl<'a> TryFrom<CollectionSearchRequest<'a>> for api::grpc::qdrant::SearchPoints {
+    type Error = Status;
+
+    fn try_from(value: CollectionSearchRequest<'a>) -> Result<Self, Self::Error> {
         let (collection_id, request) = value.0;
-        let named_vector = NamedVectorStruct::from(request.clone().vector);
+        let named_vector = NamedVectorStruct::try_from(request.clone().vector)?;
         let vector_name = match named_vector.get_name() {
             DEFAULT_VECTOR_NAME => None,
             vector_name => Some(vector_name.to_string()),
         };
         let (vector, sparse_indices) = match named_vector.to_vector() {
             Vector::Dense(vector) => (vector, None),
+            // Search requests take single precision vectors
+            Vector::DenseDouble(vector) => {
+                (vector.into_iter().map(|value| value as f32).collect(), None)
+            }
             Vector::Sparse(vector) => (
                 vector.values,
                 Some(api::grpc::qdrant::SparseIndices {
@@ -1060,7 +1273,7 @@ impl<'a> From<CollectionSearchRequest<'a>> for api::grpc::qdrant::SearchPoints {
                 unimplemented!("MultiDenseVector is not supported")
             }
         };
-        Self {
+        Ok(Self {
             collection_name: collection_id,
             vector,
             filter: request.filter.clone().map(|f| f.into()),
@@ -1075,7 +1288,7 @@ impl<'a> From<CollectionSearchRequest<'a>> for api::grpc::qdrant::SearchPoints {
             timeout: None,
             shard_key_selector: None,
             sparse_indices,
-        }
+        })
     }
 }
 
@@ -1378,20 +1591,23 @@ impl TryFrom<api::grpc::qdrant::SearchPointGroups> for SearchGroupsRequestIntern
     }
 }
 
-impl From<PointGroup> for api::grpc::qdrant::PointGroup {
-    fn from(group: PointGroup) -> Self {
-        Self {
+impl TryFrom<PointGroup> for api::grpc::qdrant::PointGroup {
+    type Error = Status;
+
+    fn try_from(group: PointGroup) -> Result<Self, Self::Error> {
+        Ok(Self {
             hits: group
                 .hits
                 .into_iter()
-                .map_into::<ScoredPoint>()
-                .map_into()
-                .collect(),
+                .map(|hit| ScoredPoint::try_from(hit).map(Into::into))
+                .collect::<Result<_, _>>()?,
             id: Some(group.id.into()),
             lookup: group
                 .lookup
-                .map(|record| api::grpc::qdrant::RetrievedPoint::from(Record::from(record))),
-        }
+                .map(Record::try_from)
+                .transpose()?
+                .map(api::grpc::qdrant::RetrievedPoint::from),
+        })
     }
 }
 
@@ -1460,6 +1676,10 @@ impl TryFrom<api::grpc::qdrant::Vector> for RecommendExample {
         let vector: Vector = value.try_into()?;
         Ok(match vector {
             Vector::Dense(vector) => Self::Dense(vector),
+            // Recommend requests take single precision vectors
+            Vector::DenseDouble(vector) => {
+                Self::Dense(vector.into_iter().map(|value| value as f32).collect())
+            }
             Vector::Sparse(vector) => Self::Sparse(vector),
             Vector::MultiDense(_vector) => {
                 // TODO(colbert)
@@ -1617,11 +1837,17 @@ impl TryFrom<api::grpc::qdrant::RecommendPointGroups> for RecommendGroupsRequest
     }
 }
 
-impl From<GroupsResult> for api::grpc::qdrant::GroupsResult {
-    fn from(value: GroupsResult) -> Self {
-        Self {
-            groups: value.groups.into_iter().map(Into::into).collect(),
-        }
+impl TryFrom<GroupsResult> for api::grpc::qdrant::GroupsResult {
+    type Error = Status;
+
+    fn try_from(value: GroupsResult) -> Result<Self, Self::Error> {
+        Ok(Self {
+            groups: value
+                .groups
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+        })
     }
 }
 
@@ -1655,6 +1881,7 @@ impl From<Datatype> for api::grpc::qdrant::Datatype {
             Datatype::Float32 => api::grpc::qdrant::Datatype::Float32,
             Datatype::Uint8 => api::grpc::qdrant::Datatype::Uint8,
             Datatype::Float16 => api::grpc::qdrant::Datatype::Float16,
+            Datatype::Float64 => api::grpc::qdrant::Datatype::Float64,
         }
     }
 }
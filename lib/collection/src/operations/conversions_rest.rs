@@ -1,3 +1,4 @@
+use api::rest::conversions::InferenceInputError;
 use segment::data_types::vectors::VectorStruct;
 
 use super::types::Record;
@@ -9,17 +10,21 @@ impl From<Record> for api::rest::Record {
             payload: value.payload,
             vector: value.vector.map(api::rest::VectorStruct::from),
             shard_key: value.shard_key,
+            version: value.version,
         }
     }
 }
 
-impl From<api::rest::Record> for Record {
-    fn from(value: api::rest::Record) -> Self {
-        Self {
+impl TryFrom<api::rest::Record> for Record {
+    type Error = InferenceInputError;
+
+    fn try_from(value: api::rest::Record) -> Result<Self, Self::Error> {
+        Ok(Self {
             id: value.id,
             payload: value.payload,
-            vector: value.vector.map(VectorStruct::from),
+            vector: value.vector.map(VectorStruct::try_from).transpose()?,
             shard_key: value.shard_key,
-        }
+            version: value.version,
+        })
     }
 }
@@ -1,3 +1,4 @@
+pub mod blob_ops;
 pub mod cluster_ops;
 pub mod config_diff;
 pub mod consistency_params;
@@ -11,6 +12,7 @@ pub mod shard_selector_internal;
 pub mod shared_storage_config;
 pub mod snapshot_ops;
 pub mod snapshot_storage_ops;
+pub mod sparse_text;
 pub mod types;
 pub mod universal_query;
 pub mod validation;
@@ -20,7 +22,7 @@ pub mod vector_params_builder;
 use std::collections::HashMap;
 
 use segment::json_path::JsonPath;
-use segment::types::{ExtendedPointId, PayloadFieldSchema};
+use segment::types::{ExtendedPointId, PayloadFieldSchema, SeqNumberType};
 use serde::{Deserialize, Serialize};
 use strum::{EnumDiscriminants, EnumIter};
 use validator::Validate;
@@ -143,6 +145,7 @@ pub enum CollectionUpdateOperations {
     VectorOperation(vector_ops::VectorOperations),
     PayloadOperation(payload_ops::PayloadOps),
     FieldIndexOperation(FieldIndexOperations),
+    BlobOperation(blob_ops::BlobOperations),
 }
 
 /// A mapping of operation to shard.
@@ -203,6 +206,7 @@ impl Validate for CollectionUpdateOperations {
             CollectionUpdateOperations::VectorOperation(operation) => operation.validate(),
             CollectionUpdateOperations::PayloadOperation(operation) => operation.validate(),
             CollectionUpdateOperations::FieldIndexOperation(operation) => operation.validate(),
+            CollectionUpdateOperations::BlobOperation(operation) => operation.validate(),
         }
     }
 }
@@ -268,6 +272,9 @@ impl SplitByShard for CollectionUpdateOperations {
             operation @ CollectionUpdateOperations::FieldIndexOperation(_) => {
                 OperationToShard::to_all(operation)
             }
+            CollectionUpdateOperations::BlobOperation(operation) => operation
+                .split_by_shard(ring)
+                .map(CollectionUpdateOperations::BlobOperation),
         }
     }
 }
@@ -285,6 +292,64 @@ impl CollectionUpdateOperations {
             CollectionUpdateOperations::FieldIndexOperation(operation) => {
                 operation.is_write_operation()
             }
+            CollectionUpdateOperations::BlobOperation(operation) => operation.is_write_operation(),
+        }
+    }
+
+    /// Whether operation writes the reserved expiry key in payload of any point directly,
+    /// instead of setting `expires_at` of the point
+    pub fn writes_expiry_payload(&self) -> bool {
+        match self {
+            CollectionUpdateOperations::PointOperation(operation) => {
+                operation.writes_expiry_payload()
+            }
+            CollectionUpdateOperations::PayloadOperation(operation) => {
+                operation.writes_payload_key(point_ops::EXPIRES_AT_KEY)
+            }
+            CollectionUpdateOperations::VectorOperation(_)
+            | CollectionUpdateOperations::FieldIndexOperation(_)
+            | CollectionUpdateOperations::BlobOperation(_) => false,
+        }
+    }
+
+    /// Whether operation is applied only if its points have the expected version
+    pub fn has_version_guard(&self) -> bool {
+        match self {
+            CollectionUpdateOperations::PointOperation(
+                point_ops::PointOperations::UpsertPointsConditional(operation),
+            ) => operation.if_version.is_some(),
+            CollectionUpdateOperations::PointOperation(
+                point_ops::PointOperations::DeletePoints { if_version, .. },
+            ) => if_version.is_some(),
+            _ => false,
+        }
+    }
+
+    /// Remove the version guard from the operation, see [`PointOperations::take_version_guard`]
+    ///
+    /// [`PointOperations::take_version_guard`]: point_ops::PointOperations::take_version_guard
+    pub fn take_version_guard(&mut self) -> Option<(Vec<ExtendedPointId>, SeqNumberType)> {
+        match self {
+            CollectionUpdateOperations::PointOperation(operation) => {
+                operation.take_version_guard()
+            }
+            CollectionUpdateOperations::VectorOperation(_)
+            | CollectionUpdateOperations::PayloadOperation(_)
+            | CollectionUpdateOperations::FieldIndexOperation(_)
+            | CollectionUpdateOperations::BlobOperation(_) => None,
+        }
+    }
+
+    /// Whether operation inserts any point with an expiration moment
+    pub fn has_expiring_points(&self) -> bool {
+        match self {
+            CollectionUpdateOperations::PointOperation(operation) => {
+                operation.has_expiring_points()
+            }
+            CollectionUpdateOperations::VectorOperation(_)
+            | CollectionUpdateOperations::PayloadOperation(_)
+            | CollectionUpdateOperations::FieldIndexOperation(_)
+            | CollectionUpdateOperations::BlobOperation(_) => false,
         }
     }
 }
@@ -294,6 +359,7 @@ mod tests {
     use proptest::prelude::*;
     use segment::types::*;
 
+    use super::blob_ops::*;
     use super::payload_ops::*;
     use super::point_ops::*;
     use super::vector_ops::*;
@@ -368,6 +434,7 @@ mod tests {
                 any::<vector_ops::VectorOperations>().prop_map(Self::VectorOperation),
                 any::<payload_ops::PayloadOps>().prop_map(Self::PayloadOperation),
                 any::<FieldIndexOperations>().prop_map(Self::FieldIndexOperation),
+                any::<blob_ops::BlobOperations>().prop_map(Self::BlobOperation),
             ]
             .boxed()
         }
@@ -379,7 +446,20 @@ mod tests {
 
         fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
             let upsert = Self::UpsertPoints(PointInsertOperationsInternal::PointsList(Vec::new()));
-            let delete = Self::DeletePoints { ids: Vec::new() };
+
+            let upsert_conditional =
+                Self::UpsertPointsConditional(ConditionalInsertOperationInternal {
+                    points_op: PointInsertOperationsInternal::PointsList(Vec::new()),
+                    update_mode: UpdateMode::InsertOnly,
+                    update_filter: None,
+                    if_version: None,
+                    dedup_key: None,
+                });
+
+            let delete = Self::DeletePoints {
+                ids: Vec::new(),
+                if_version: None,
+            };
 
             let delete_by_filter = Self::DeletePointsByFilter(Filter {
                 should: None,
@@ -396,6 +476,7 @@ mod tests {
 
             prop_oneof![
                 Just(upsert),
+                Just(upsert_conditional),
                 Just(delete),
                 Just(delete_by_filter),
                 Just(sync),
@@ -493,4 +574,26 @@ mod tests {
             prop_oneof![Just(create), Just(delete),].boxed()
         }
     }
+
+    impl Arbitrary for blob_ops::BlobOperations {
+        type Parameters = ();
+        type Strategy = BoxedStrategy<Self>;
+
+        fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+            let set = any::<(u64, Vec<u8>)>().prop_map(|(id, blob)| {
+                Self::SetBlob(SetBlobOp {
+                    point_id: id.into(),
+                    blob,
+                })
+            });
+
+            let delete = any::<u64>().prop_map(|id| {
+                Self::DeleteBlob(DeleteBlobOp {
+                    point_id: id.into(),
+                })
+            });
+
+            prop_oneof![set, delete].boxed()
+        }
+    }
 }
@@ -38,6 +38,9 @@ impl EstimateOperationEffectArea for CollectionUpdateOperations {
                 payload_operation.estimate_effect_area()
             }
             CollectionUpdateOperations::FieldIndexOperation(_) => OperationEffectArea::Empty,
+            CollectionUpdateOperations::BlobOperation(blob_operation) => {
+                OperationEffectArea::Points(vec![blob_operation.point_id()])
+            }
         }
     }
 }
@@ -48,7 +51,10 @@ impl EstimateOperationEffectArea for point_ops::PointOperations {
             point_ops::PointOperations::UpsertPoints(insert_operations) => {
                 insert_operations.estimate_effect_area()
             }
-            point_ops::PointOperations::DeletePoints { ids } => {
+            point_ops::PointOperations::UpsertPointsConditional(operation) => {
+                operation.points_op.estimate_effect_area()
+            }
+            point_ops::PointOperations::DeletePoints { ids, .. } => {
                 OperationEffectArea::Points(ids.clone())
             }
             point_ops::PointOperations::DeletePointsByFilter(filter) => {
@@ -127,6 +133,15 @@ impl EstimateOperationEffectArea for PayloadOps {
                     OperationEffectArea::Empty
                 }
             }
+            PayloadOps::PatchPayload(patch_payload) => {
+                if let Some(points) = &patch_payload.points {
+                    OperationEffectArea::Points(points.clone())
+                } else if let Some(filter) = &patch_payload.filter {
+                    OperationEffectArea::Filter(filter.clone())
+                } else {
+                    OperationEffectArea::Empty
+                }
+            }
         }
     }
 }
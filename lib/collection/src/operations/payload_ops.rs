@@ -1,5 +1,6 @@
 use api::rest::ShardKeySelector;
 use schemars::JsonSchema;
+use segment::json_patch::PatchOperation;
 use segment::json_path::JsonPath;
 use segment::types::{Filter, Payload, PayloadKeyType, PointIdType};
 use serde;
@@ -133,6 +134,61 @@ impl TryFrom<DeletePayloadShadow> for DeletePayload {
     }
 }
 
+/// This data structure is used in API interface and applied across multiple shards
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[serde(try_from = "PatchPayloadShadow")]
+pub struct PatchPayload {
+    /// JSON Patch (RFC 6902) operations, applied to the payload of each point in order
+    #[validate(length(min = 1))]
+    pub patch: Vec<PatchOperation>,
+    /// Patches payload of each point in this list
+    pub points: Option<Vec<PointIdType>>,
+    /// Patches payload of each point that satisfy this filter condition
+    pub filter: Option<Filter>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKeySelector>,
+}
+
+/// This data structure is used inside shard operations queue
+/// and supposed to be written into WAL of individual shard.
+///
+/// Unlike `PatchPayload` it does not contain `shard_key` field
+/// as individual shard does not need to know about shard key
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, Validate)]
+pub struct PatchPayloadOp {
+    /// JSON Patch (RFC 6902) operations, applied to the payload of each point in order
+    pub patch: Vec<PatchOperation>,
+    /// Patches payload of each point in this list
+    pub points: Option<Vec<PointIdType>>,
+    /// Patches payload of each point that satisfy this filter condition
+    pub filter: Option<Filter>,
+}
+
+#[derive(Deserialize)]
+struct PatchPayloadShadow {
+    pub patch: Vec<PatchOperation>,
+    pub points: Option<Vec<PointIdType>>,
+    pub filter: Option<Filter>,
+    pub shard_key: Option<ShardKeySelector>,
+}
+
+impl TryFrom<PatchPayloadShadow> for PatchPayload {
+    type Error = PointsSelectorValidationError;
+
+    fn try_from(value: PatchPayloadShadow) -> Result<Self, Self::Error> {
+        if value.points.is_some() || value.filter.is_some() {
+            Ok(PatchPayload {
+                patch: value.patch,
+                points: value.points,
+                filter: value.filter,
+                shard_key: value.shard_key,
+            })
+        } else {
+            Err(PointsSelectorValidationError)
+        }
+    }
+}
+
 /// Define operations description for point payloads manipulation
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize, EnumDiscriminants)]
 #[strum_discriminants(derive(EnumIter))]
@@ -148,6 +204,8 @@ pub enum PayloadOps {
     ClearPayloadByFilter(Filter),
     /// Overwrite full payload with given keys
     OverwritePayload(SetPayloadOp),
+    /// Modify payload with JSON Patch operations
+    PatchPayload(PatchPayloadOp),
 }
 
 impl PayloadOps {
@@ -158,6 +216,33 @@ impl PayloadOps {
             PayloadOps::ClearPayload { .. } => false,
             PayloadOps::ClearPayloadByFilter(_) => false,
             PayloadOps::OverwritePayload(_) => true,
+            PayloadOps::PatchPayload(_) => true,
+        }
+    }
+
+    /// Whether operation writes the given top-level payload key
+    pub fn writes_payload_key(&self, key: &str) -> bool {
+        let sets_key = |operation: &SetPayloadOp| match &operation.key {
+            Some(path) => path.first_key == key,
+            None => operation.payload.0.contains_key(key),
+        };
+        match self {
+            PayloadOps::SetPayload(operation) => sets_key(operation),
+            PayloadOps::DeletePayload(_) => false,
+            PayloadOps::ClearPayload { .. } => false,
+            PayloadOps::ClearPayloadByFilter(_) => false,
+            PayloadOps::OverwritePayload(operation) => sets_key(operation),
+            PayloadOps::PatchPayload(operation) => {
+                operation.patch.iter().any(|patch| match patch {
+                    PatchOperation::Add { path, .. }
+                    | PatchOperation::Replace { path, .. }
+                    | PatchOperation::Move { path, .. }
+                    | PatchOperation::Copy { path, .. } => {
+                        path.trim_start_matches('/').split('/').next() == Some(key)
+                    }
+                    PatchOperation::Remove { .. } | PatchOperation::Test { .. } => false,
+                })
+            }
         }
     }
 }
@@ -170,6 +255,7 @@ impl Validate for PayloadOps {
             PayloadOps::ClearPayload { .. } => Ok(()),
             PayloadOps::ClearPayloadByFilter(_) => Ok(()),
             PayloadOps::OverwritePayload(operation) => operation.validate(),
+            PayloadOps::PatchPayload(operation) => operation.validate(),
         }
     }
 }
@@ -189,6 +275,9 @@ impl SplitByShard for PayloadOps {
             PayloadOps::OverwritePayload(operation) => operation
                 .split_by_shard(ring)
                 .map(PayloadOps::OverwritePayload),
+            PayloadOps::PatchPayload(operation) => {
+                operation.split_by_shard(ring).map(PayloadOps::PatchPayload)
+            }
         }
     }
 }
@@ -230,6 +319,24 @@ impl SplitByShard for SetPayloadOp {
     }
 }
 
+impl SplitByShard for PatchPayloadOp {
+    fn split_by_shard(self, ring: &HashRing) -> OperationToShard<Self> {
+        match (&self.points, &self.filter) {
+            (Some(_), _) => {
+                split_iter_by_shard(self.points.unwrap(), |id| *id, ring).map(|points| {
+                    PatchPayloadOp {
+                        points: Some(points),
+                        patch: self.patch.clone(),
+                        filter: self.filter.clone(),
+                    }
+                })
+            }
+            (None, Some(_)) => OperationToShard::to_all(self),
+            (None, None) => OperationToShard::to_none(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use segment::types::{Payload, PayloadContainer};
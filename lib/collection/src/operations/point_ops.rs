@@ -1,13 +1,17 @@
 use std::borrow::Cow;
 use std::collections::HashMap;
 
+use api::rest::conversions::InferenceInputError;
 use api::rest::{BatchVectorStruct, ShardKeySelector, VectorStruct};
 use itertools::izip;
 use schemars::JsonSchema;
 use segment::common::utils::transpose_map_into_named_vector;
 use segment::data_types::named_vectors::NamedVectors;
 use segment::data_types::vectors::{Vector, DEFAULT_VECTOR_NAME};
-use segment::types::{Filter, Payload, PointIdType};
+use segment::json_path::JsonPath;
+use segment::types::{
+    DateTimePayloadType, Filter, Payload, PayloadKeyType, PointIdType, SeqNumberType,
+};
 use serde::{Deserialize, Serialize};
 use strum::{EnumDiscriminants, EnumIter};
 use validator::Validate;
@@ -17,6 +21,16 @@ use crate::hash_ring::HashRing;
 use crate::operations::types::Record;
 use crate::shards::shard::ShardId;
 
+/// Reserved payload key, under which the expiration moment of the point is stored.
+///
+/// Users can't write this key in payloads directly, the expiration moment is set with
+/// `expires_at` of the point, if `point_expiry` is enabled in the collection TTL config.
+pub const EXPIRES_AT_KEY: &str = "__qdrant_expires_at";
+
+pub fn expires_at_key() -> JsonPath {
+    JsonPath::try_from(EXPIRES_AT_KEY).expect("`__qdrant_expires_at` is a valid JSON path")
+}
+
 /// Defines write ordering guarantees for collection operations
 ///
 /// * `weak` - write operations may be reordered, works faster, default
@@ -34,6 +48,23 @@ pub enum WriteOrdering {
     Strong,
 }
 
+/// Defines whether an upsert inserts new points and updates existing ones
+///
+/// * `upsert` - insert new points and update existing ones, default
+///
+/// * `insert_only` - only insert new points, existing points are left untouched
+///
+/// * `update_only` - only update existing points, new points are ignored
+///
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdateMode {
+    #[default]
+    Upsert,
+    InsertOnly,
+    UpdateOnly,
+}
+
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize, JsonSchema, Validate)]
 #[serde(rename_all = "snake_case")]
 pub struct PointStruct {
@@ -45,6 +76,11 @@ pub struct PointStruct {
     pub vector: VectorStruct,
     /// Payload values (optional)
     pub payload: Option<Payload>,
+    /// Moment after which the point expires (optional).
+    /// Expired points are excluded from all requests and eventually deleted.
+    /// Requires `point_expiry` to be enabled in the collection TTL config.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTimePayloadType>,
 }
 
 /// Warn: panics if the vector is empty
@@ -57,16 +93,27 @@ impl TryFrom<Record> for PointStruct {
             payload,
             vector,
             shard_key: _,
+            version: _,
         } = record;
 
         if vector.is_none() {
             return Err("Vector is empty".to_string());
         }
 
+        // Stored expiration moment is moved back from the reserved payload key to the point
+        let mut payload = payload;
+        let expires_at = payload
+            .as_mut()
+            .and_then(|payload| payload.0.remove(EXPIRES_AT_KEY))
+            .map(serde_json::from_value)
+            .transpose()
+            .map_err(|err| format!("Invalid expiration moment of point {id}: {err}"))?;
+
         Ok(Self {
             id,
             payload,
             vector: api::rest::VectorStruct::from(vector.unwrap()),
+            expires_at,
         })
     }
 }
@@ -155,6 +202,18 @@ pub struct PointsBatch {
     pub batch: Batch,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub shard_key: Option<ShardKeySelector>,
+    /// Defines whether new points are inserted and existing points are updated. Default: upsert
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub update_mode: Option<UpdateMode>,
+    /// If specified, existing points are updated only if they satisfy this filter
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub update_filter: Option<Filter>,
+    /// If specified, this payload field is used as a logical key of the points.
+    /// A point with the same key value, which already exists in the shard, is replaced
+    /// and keeps its ID, instead of a duplicate being created under a new ID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dedup_key: Option<PayloadKeyType>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, JsonSchema, Validate)]
@@ -163,6 +222,18 @@ pub struct PointsList {
     pub points: Vec<PointStruct>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub shard_key: Option<ShardKeySelector>,
+    /// Defines whether new points are inserted and existing points are updated. Default: upsert
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub update_mode: Option<UpdateMode>,
+    /// If specified, existing points are updated only if they satisfy this filter
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub update_filter: Option<Filter>,
+    /// If specified, this payload field is used as a logical key of the points.
+    /// A point with the same key value, which already exists in the shard, is replaced
+    /// and keeps its ID, instead of a duplicate being created under a new ID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dedup_key: Option<PayloadKeyType>,
 }
 
 impl<'de> serde::Deserialize<'de> for PointInsertOperations {
@@ -213,12 +284,56 @@ impl Validate for PointInsertOperations {
 }
 
 impl PointInsertOperations {
-    pub fn decompose(self) -> (Option<ShardKeySelector>, PointInsertOperationsInternal) {
+    /// Number of inserted points
+    pub fn points_count(&self) -> usize {
         match self {
-            PointInsertOperations::PointsBatch(batch) => (batch.shard_key, batch.batch.into()),
-            PointInsertOperations::PointsList(list) => (list.shard_key, list.points.into()),
+            PointInsertOperations::PointsBatch(batch) => batch.batch.ids.len(),
+            PointInsertOperations::PointsList(list) => list.points.len(),
         }
     }
+
+    /// Split into the shard key selector and the point operation to apply.
+    ///
+    /// Upserts with a non-default update mode, an update filter, a version guard
+    /// or a deduplication key become conditional upserts.
+    pub fn decompose(
+        self,
+        if_version: Option<SeqNumberType>,
+    ) -> (Option<ShardKeySelector>, PointOperations) {
+        let (shard_key, points_op, update_mode, update_filter, dedup_key) = match self {
+            PointInsertOperations::PointsBatch(batch) => (
+                batch.shard_key,
+                batch.batch.into(),
+                batch.update_mode,
+                batch.update_filter,
+                batch.dedup_key,
+            ),
+            PointInsertOperations::PointsList(list) => (
+                list.shard_key,
+                list.points.into(),
+                list.update_mode,
+                list.update_filter,
+                list.dedup_key,
+            ),
+        };
+        let update_mode = update_mode.unwrap_or_default();
+        let is_conditional = update_mode != UpdateMode::Upsert
+            || update_filter.is_some()
+            || if_version.is_some()
+            || dedup_key.is_some();
+        let operation = if is_conditional {
+            PointOperations::UpsertPointsConditional(ConditionalInsertOperationInternal {
+                points_op,
+                update_mode,
+                update_filter,
+                if_version,
+                dedup_key,
+            })
+        } else {
+            PointOperations::UpsertPoints(points_op)
+        };
+        (shard_key, operation)
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize, EnumDiscriminants)]
@@ -311,6 +426,9 @@ impl From<Batch> for PointInsertOperations {
         PointInsertOperations::PointsBatch(PointsBatch {
             batch,
             shard_key: None,
+            update_mode: None,
+            update_filter: None,
+            dedup_key: None,
         })
     }
 }
@@ -320,6 +438,9 @@ impl From<Vec<PointStruct>> for PointInsertOperations {
         PointInsertOperations::PointsList(PointsList {
             points,
             shard_key: None,
+            update_mode: None,
+            update_filter: None,
+            dedup_key: None,
         })
     }
 }
@@ -336,14 +457,55 @@ impl From<Vec<PointStruct>> for PointInsertOperationsInternal {
     }
 }
 
+/// Upsert, which inserts or updates each point only if the update mode and filter permit it
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct ConditionalInsertOperationInternal {
+    pub points_op: PointInsertOperationsInternal,
+    pub update_mode: UpdateMode,
+    /// Existing points are updated only if they satisfy this filter
+    pub update_filter: Option<Filter>,
+    /// Whole operation is rejected, unless all points exist and have this version
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub if_version: Option<SeqNumberType>,
+    /// Payload field, which is used as a logical key to replace existing points
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dedup_key: Option<PayloadKeyType>,
+}
+
+impl SplitByShard for ConditionalInsertOperationInternal {
+    fn split_by_shard(self, ring: &HashRing) -> OperationToShard<Self> {
+        let Self {
+            points_op,
+            update_mode,
+            update_filter,
+            if_version,
+            dedup_key,
+        } = self;
+        points_op.split_by_shard(ring).map(|points_op| Self {
+            points_op,
+            update_mode,
+            update_filter: update_filter.clone(),
+            if_version,
+            dedup_key: dedup_key.clone(),
+        })
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize, EnumDiscriminants)]
 #[strum_discriminants(derive(EnumIter))]
 #[serde(rename_all = "snake_case")]
 pub enum PointOperations {
     /// Insert or update points
     UpsertPoints(PointInsertOperationsInternal),
+    /// Insert or update points, depending on their existence and a filter
+    UpsertPointsConditional(ConditionalInsertOperationInternal),
     /// Delete point if exists
-    DeletePoints { ids: Vec<PointIdType> },
+    DeletePoints {
+        ids: Vec<PointIdType>,
+        /// Whole operation is rejected, unless all points exist and have this version
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        if_version: Option<SeqNumberType>,
+    },
     /// Delete points by given filter criteria
     DeletePointsByFilter(Filter),
     /// Points Sync
@@ -354,18 +516,75 @@ impl PointOperations {
     pub fn is_write_operation(&self) -> bool {
         match self {
             PointOperations::UpsertPoints(_) => true,
+            PointOperations::UpsertPointsConditional(_) => true,
             PointOperations::DeletePoints { .. } => false,
             PointOperations::DeletePointsByFilter(_) => false,
             PointOperations::SyncPoints(_) => true,
         }
     }
+
+    /// Whether operation inserts any point with an expiration moment
+    pub fn has_expiring_points(&self) -> bool {
+        match self {
+            PointOperations::UpsertPoints(upsert_points) => upsert_points.has_expiring_points(),
+            PointOperations::UpsertPointsConditional(operation) => {
+                operation.points_op.has_expiring_points()
+            }
+            PointOperations::DeletePoints { .. } => false,
+            PointOperations::DeletePointsByFilter(_) => false,
+            PointOperations::SyncPoints(sync) => sync.points.iter().any(PointStruct::is_expiring),
+        }
+    }
+
+    /// Remove the version guard from the operation.
+    ///
+    /// Returns ids of the guarded points and their expected version, if the operation was guarded.
+    pub fn take_version_guard(&mut self) -> Option<(Vec<PointIdType>, SeqNumberType)> {
+        match self {
+            PointOperations::UpsertPointsConditional(operation) => {
+                let if_version = operation.if_version.take()?;
+                Some((operation.points_op.point_ids(), if_version))
+            }
+            PointOperations::DeletePoints { ids, if_version } => {
+                let if_version = if_version.take()?;
+                Some((ids.clone(), if_version))
+            }
+            PointOperations::UpsertPoints(_)
+            | PointOperations::DeletePointsByFilter(_)
+            | PointOperations::SyncPoints(_) => None,
+        }
+    }
+
+    /// Whether operation writes the reserved expiry key in payload of any point directly
+    pub fn writes_expiry_payload(&self) -> bool {
+        let has_expiry_key = |payload: Option<&Payload>| {
+            payload.map_or(false, |payload| payload.0.contains_key(EXPIRES_AT_KEY))
+        };
+        match self {
+            PointOperations::UpsertPoints(upsert_points) => {
+                upsert_points.payloads().into_iter().any(has_expiry_key)
+            }
+            PointOperations::UpsertPointsConditional(operation) => operation
+                .points_op
+                .payloads()
+                .into_iter()
+                .any(has_expiry_key),
+            PointOperations::DeletePoints { .. } => false,
+            PointOperations::DeletePointsByFilter(_) => false,
+            PointOperations::SyncPoints(sync) => sync
+                .points
+                .iter()
+                .any(|point| has_expiry_key(point.payload.as_ref())),
+        }
+    }
 }
 
 impl Validate for PointOperations {
     fn validate(&self) -> Result<(), validator::ValidationErrors> {
         match self {
             PointOperations::UpsertPoints(upsert_points) => upsert_points.validate(),
-            PointOperations::DeletePoints { ids: _ } => Ok(()),
+            PointOperations::UpsertPointsConditional(operation) => operation.points_op.validate(),
+            PointOperations::DeletePoints { .. } => Ok(()),
             PointOperations::DeletePointsByFilter(_) => Ok(()),
             PointOperations::SyncPoints(_) => Ok(()),
         }
@@ -496,8 +715,13 @@ impl SplitByShard for PointOperations {
             PointOperations::UpsertPoints(upsert_points) => upsert_points
                 .split_by_shard(ring)
                 .map(PointOperations::UpsertPoints),
-            PointOperations::DeletePoints { ids } => split_iter_by_shard(ids, |id| *id, ring)
-                .map(|ids| PointOperations::DeletePoints { ids }),
+            PointOperations::UpsertPointsConditional(operation) => operation
+                .split_by_shard(ring)
+                .map(PointOperations::UpsertPointsConditional),
+            PointOperations::DeletePoints { ids, if_version } => {
+                split_iter_by_shard(ids, |id| *id, ring)
+                    .map(|ids| PointOperations::DeletePoints { ids, if_version })
+            }
             by_filter @ PointOperations::DeletePointsByFilter(_) => {
                 OperationToShard::to_all(by_filter)
             }
@@ -524,7 +748,7 @@ impl From<Vec<PointStruct>> for PointOperations {
 }
 
 impl PointStruct {
-    pub fn get_vectors(&self) -> NamedVectors {
+    pub fn get_vectors(&self) -> Result<NamedVectors, InferenceInputError> {
         let mut named_vectors = NamedVectors::default();
         match &self.vector {
             VectorStruct::Single(vector) => named_vectors.insert(
@@ -533,14 +757,107 @@ impl PointStruct {
             ),
             VectorStruct::Multi(vectors) => {
                 for (name, vector) in vectors {
-                    named_vectors.insert(name.clone(), Vector::from(vector.clone()));
+                    named_vectors.insert(name.clone(), Vector::try_from(vector.clone())?);
                 }
             }
         }
-        named_vectors
+        Ok(named_vectors)
+    }
+
+    /// Payload to store for the point, including its expiration moment
+    pub fn full_payload(&self) -> Option<Cow<'_, Payload>> {
+        let Some(expires_at) = &self.expires_at else {
+            return self.payload.as_ref().map(Cow::Borrowed);
+        };
+        let mut payload = self.payload.clone().unwrap_or_default();
+        payload.0.insert(
+            EXPIRES_AT_KEY.to_string(),
+            serde_json::Value::String(expires_at.0.to_rfc3339()),
+        );
+        Some(Cow::Owned(payload))
+    }
+
+    /// Whether the point has an expiration moment, set either explicitly or in the payload
+    pub fn is_expiring(&self) -> bool {
+        self.expires_at.is_some()
+            || self
+                .payload
+                .as_ref()
+                .map_or(false, |payload| payload.0.contains_key(EXPIRES_AT_KEY))
     }
 }
 
+impl PointInsertOperationsInternal {
+    /// Whether any of the inserted points has an expiration moment
+    pub fn has_expiring_points(&self) -> bool {
+        match self {
+            PointInsertOperationsInternal::PointsBatch(batch) => batch
+                .payloads
+                .iter()
+                .flatten()
+                .flatten()
+                .any(|payload| payload.0.contains_key(EXPIRES_AT_KEY)),
+            PointInsertOperationsInternal::PointsList(points) => {
+                points.iter().any(PointStruct::is_expiring)
+            }
+        }
+    }
+
+    /// Ids of inserted points
+    pub fn point_ids(&self) -> Vec<PointIdType> {
+        match self {
+            PointInsertOperationsInternal::PointsBatch(batch) => batch.ids.clone(),
+            PointInsertOperationsInternal::PointsList(points) => {
+                points.iter().map(|point| point.id).collect()
+            }
+        }
+    }
+
+    /// Number of inserted points
+    pub fn points_count(&self) -> usize {
+        match self {
+            PointInsertOperationsInternal::PointsBatch(batch) => batch.ids.len(),
+            PointInsertOperationsInternal::PointsList(points) => points.len(),
+        }
+    }
+
+    /// Payload of each inserted point, `None` for points without payload
+    pub fn payloads(&self) -> Vec<Option<&Payload>> {
+        match self {
+            PointInsertOperationsInternal::PointsBatch(batch) => match &batch.payloads {
+                Some(payloads) => payloads.iter().map(Option::as_ref).collect(),
+                None => vec![None; batch.ids.len()],
+            },
+            PointInsertOperationsInternal::PointsList(points) => {
+                points.iter().map(|point| point.payload.as_ref()).collect()
+            }
+        }
+    }
+
+    /// Total size of inserted payloads, in bytes of serialized JSON
+    pub fn payload_bytes(&self) -> usize {
+        match self {
+            PointInsertOperationsInternal::PointsBatch(batch) => batch
+                .payloads
+                .iter()
+                .flatten()
+                .flatten()
+                .map(payload_size_bytes)
+                .sum(),
+            PointInsertOperationsInternal::PointsList(points) => points
+                .iter()
+                .filter_map(|point| point.payload.as_ref())
+                .map(payload_size_bytes)
+                .sum(),
+        }
+    }
+}
+
+/// Size of the payload, in bytes of serialized JSON
+pub fn payload_size_bytes(payload: &Payload) -> usize {
+    serde_json::to_vec(payload).map_or(0, |bytes| bytes.len())
+}
+
 #[cfg(test)]
 mod tests {
     use segment::data_types::vectors::BatchVectorStruct;
@@ -1,5 +1,8 @@
-use segment::data_types::vectors::{DenseVector, Named, NamedQuery, NamedVectorStruct, Vector};
-use segment::vector_storage::query::{ContextQuery, DiscoveryQuery, RecoQuery};
+use segment::common::operation_error::OperationResult;
+use segment::data_types::vectors::{
+    DenseVector, Named, NamedDenseDoubleVector, NamedQuery, NamedVector, NamedVectorStruct, Vector,
+};
+use segment::vector_storage::query::{ContextQuery, DiscoveryQuery, RecoQuery, TransformInto};
 use sparse::common::sparse_vector::SparseVector;
 
 impl QueryEnum {
@@ -22,6 +25,51 @@ impl QueryEnum {
         }
     }
 
+    /// Replace dense vectors of the query, e.g. to project them to a lower dimension
+    pub fn transform_dense(
+        self,
+        mut f: impl FnMut(DenseVector) -> OperationResult<DenseVector>,
+    ) -> OperationResult<Self> {
+        // Double precision vectors are transformed in single precision
+        let mut transform_vector = |vector| match vector {
+            Vector::Dense(_) | Vector::DenseDouble(_) => f(vector.try_into()?).map(Vector::Dense),
+            Vector::Sparse(_) | Vector::MultiDense(_) => Ok(vector),
+        };
+        let query = match self {
+            QueryEnum::Nearest(vector) => QueryEnum::Nearest(match vector {
+                NamedVectorStruct::Default(vector) => NamedVectorStruct::Default(f(vector)?),
+                NamedVectorStruct::Dense(NamedVector { name, vector }) => {
+                    NamedVectorStruct::Dense(NamedVector {
+                        name,
+                        vector: f(vector)?,
+                    })
+                }
+                NamedVectorStruct::DenseDouble(NamedDenseDoubleVector { name, vector }) => {
+                    NamedVectorStruct::Dense(NamedVector {
+                        name,
+                        vector: f(Vector::DenseDouble(vector).try_into()?)?,
+                    })
+                }
+                NamedVectorStruct::Sparse(_) | NamedVectorStruct::MultiDense(_) => vector,
+            }),
+            QueryEnum::RecommendBestScore(NamedQuery { query, using }) => {
+                QueryEnum::RecommendBestScore(NamedQuery {
+                    query: query.transform(&mut transform_vector)?,
+                    using,
+                })
+            }
+            QueryEnum::Discover(NamedQuery { query, using }) => QueryEnum::Discover(NamedQuery {
+                query: query.transform(&mut transform_vector)?,
+                using,
+            }),
+            QueryEnum::Context(NamedQuery { query, using }) => QueryEnum::Context(NamedQuery {
+                query: query.transform(&mut transform_vector)?,
+                using,
+            }),
+        };
+        Ok(query)
+    }
+
     pub fn iterate_sparse(&self, mut f: impl FnMut(&str, &SparseVector)) {
         match self {
             QueryEnum::Nearest(vector) => match vector {
@@ -30,6 +78,7 @@ impl QueryEnum {
                 }
                 NamedVectorStruct::Default(_)
                 | NamedVectorStruct::Dense(_)
+                | NamedVectorStruct::DenseDouble(_)
                 | NamedVectorStruct::MultiDense(_) => {}
             },
             QueryEnum::RecommendBestScore(reco_query) => {
@@ -37,7 +86,7 @@ impl QueryEnum {
                 for vector in reco_query.query.flat_iter() {
                     match vector {
                         Vector::Sparse(sparse_vector) => f(name, sparse_vector),
-                        Vector::Dense(_) | Vector::MultiDense(_) => {}
+                        Vector::Dense(_) | Vector::DenseDouble(_) | Vector::MultiDense(_) => {}
                     }
                 }
             }
@@ -46,7 +95,7 @@ impl QueryEnum {
                 for pair in discovery_query.query.flat_iter() {
                     match pair {
                         Vector::Sparse(sparse_vector) => f(name, sparse_vector),
-                        Vector::Dense(_) | Vector::MultiDense(_) => {}
+                        Vector::Dense(_) | Vector::DenseDouble(_) | Vector::MultiDense(_) => {}
                     }
                 }
             }
@@ -55,7 +104,7 @@ impl QueryEnum {
                 for pair in context_query.query.flat_iter() {
                     match pair {
                         Vector::Sparse(sparse_vector) => f(name, sparse_vector),
-                        Vector::Dense(_) | Vector::MultiDense(_) => {}
+                        Vector::Dense(_) | Vector::DenseDouble(_) | Vector::MultiDense(_) => {}
                     }
                 }
             }
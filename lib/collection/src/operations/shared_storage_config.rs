@@ -2,8 +2,12 @@ use std::default;
 use std::num::NonZeroUsize;
 use std::time::Duration;
 
+use crate::common::segment_storage::SegmentStorageConfig;
 use crate::common::snapshots_manager::SnapShotsConfig;
+use crate::common::storage_encryption::StorageEncryptionConfig;
+use crate::common::wal_encryption::WalEncryptionConfig;
 use crate::operations::types::NodeType;
+use crate::shards::local_shard::wal_archive::WalArchiveConfig;
 use crate::shards::transfer::ShardTransferMethod;
 
 /// Default timeout for search requests.
@@ -31,6 +35,10 @@ pub struct SharedStorageConfig {
     pub outgoing_shard_transfers_limit: Option<usize>,
     pub snapshots_path: String,
     pub snapshots_config: SnapShotsConfig,
+    pub segment_storage_config: SegmentStorageConfig,
+    pub wal_encryption_config: WalEncryptionConfig,
+    pub storage_encryption_config: StorageEncryptionConfig,
+    pub wal_archive_config: WalArchiveConfig,
 }
 
 impl Default for SharedStorageConfig {
@@ -48,6 +56,10 @@ impl Default for SharedStorageConfig {
             outgoing_shard_transfers_limit: DEFAULT_IO_SHARD_TRANSFER_LIMIT,
             snapshots_path: DEFAULT_SNAPSHOTS_PATH.to_string(),
             snapshots_config: default::Default::default(),
+            segment_storage_config: default::Default::default(),
+            wal_encryption_config: default::Default::default(),
+            storage_encryption_config: default::Default::default(),
+            wal_archive_config: default::Default::default(),
         }
     }
 }
@@ -67,6 +79,10 @@ impl SharedStorageConfig {
         outgoing_shard_transfers_limit: Option<usize>,
         snapshots_path: String,
         snapshots_config: SnapShotsConfig,
+        segment_storage_config: SegmentStorageConfig,
+        wal_encryption_config: WalEncryptionConfig,
+        storage_encryption_config: StorageEncryptionConfig,
+        wal_archive_config: WalArchiveConfig,
     ) -> Self {
         let update_queue_size = update_queue_size.unwrap_or(match node_type {
             NodeType::Normal => DEFAULT_UPDATE_QUEUE_SIZE,
@@ -85,6 +101,10 @@ impl SharedStorageConfig {
             outgoing_shard_transfers_limit,
             snapshots_path,
             snapshots_config,
+            segment_storage_config,
+            wal_encryption_config,
+            storage_encryption_config,
+            wal_archive_config,
         }
     }
 }
@@ -1,14 +1,18 @@
+use std::borrow::Cow;
 use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 
 use api::grpc::conversions::naive_date_time_to_proto;
-use chrono::{DateTime, NaiveDateTime};
+use chrono::{DateTime, NaiveDateTime, Utc};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use url::Url;
-use validator::Validate;
+use validator::{Validate, ValidationError};
 
-use crate::operations::types::CollectionResult;
+use crate::common::cron::CronSchedule;
+use crate::operations::types::{CollectionError, CollectionResult};
+use crate::shards::local_shard::point_in_time::PointInTimeTarget;
+use crate::shards::shard::PeerId;
 
 /// Defines source of truth for snapshot recovery:
 /// `NoSync` means - restore snapshot without *any* additional synchronization.
@@ -63,6 +67,7 @@ pub struct SnapshotRecover {
     /// Examples:
     /// - URL `http://localhost:8080/collections/my_collection/snapshots/my_snapshot`
     /// - Local path `file:///qdrant/snapshots/test_collection-2022-08-04-10-49-10.snapshot`
+    /// - Bucket `s3://my-bucket/snapshots/test_collection-2022-08-04-10-49-10.snapshot`
     pub location: Url,
 
     /// Defines which data should be used as a source of truth if there are other replicas in the cluster.
@@ -79,6 +84,208 @@ pub struct SnapshotRecover {
     /// Optional API key used when fetching the snapshot from a remote URL.
     #[serde(default)]
     pub api_key: Option<String>,
+
+    /// Optional hex-encoded 256-bit key to decrypt the snapshot, if it is encrypted.
+    /// If not set, the configured snapshot encryption key is used.
+    #[serde(default)]
+    pub encryption_key: Option<String>,
+
+    /// Optional point in time to restore the collection to.
+    /// Operations made after the snapshot are replayed from the WAL archive.
+    #[serde(default)]
+    #[validate]
+    pub point_in_time: Option<PointInTime>,
+}
+
+/// Point in time to restore the collection to, exactly one of `timestamp` and `operation_id` must be set
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[validate(schema(function = "validate_point_in_time"))]
+pub struct PointInTime {
+    /// Restore operations made before this moment.
+    /// Granularity is a whole archived WAL segment: the collection is restored to the end of
+    /// the last segment written entirely before this moment.
+    #[serde(default)]
+    pub timestamp: Option<DateTime<Utc>>,
+    /// Restore operations up to this number in the WAL of each shard, inclusive
+    #[serde(default)]
+    pub operation_id: Option<u64>,
+    /// Peer, which archived the WAL of the shards. Default: this peer
+    #[serde(default)]
+    pub peer_id: Option<PeerId>,
+}
+
+fn validate_point_in_time(point_in_time: &PointInTime) -> Result<(), ValidationError> {
+    if point_in_time.timestamp.is_some() == point_in_time.operation_id.is_some() {
+        let mut error = ValidationError::new("invalid_point_in_time");
+        error.add_param(
+            Cow::from("message"),
+            &"exactly one of `timestamp` and `operation_id` must be set",
+        );
+        return Err(error);
+    }
+    Ok(())
+}
+
+impl PointInTime {
+    pub fn target(&self) -> PointInTimeTarget {
+        match self.operation_id {
+            Some(operation_id) => PointInTimeTarget::Operation(operation_id),
+            None => PointInTimeTarget::Timestamp(self.timestamp.unwrap_or_else(Utc::now)),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SnapshotRecoveryStage {
+    Downloading,
+    Unpacking,
+    ReplayingWal,
+    RecoveringShards,
+    Finished,
+    Failed,
+}
+
+/// Progress of the latest recovery of the collection from a snapshot on this node
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+pub struct SnapshotRecoveryStatus {
+    pub stage: SnapshotRecoveryStage,
+    pub started_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<DateTime<Utc>>,
+    /// Number of shards to recover
+    pub shards_total: usize,
+    pub shards_recovered: usize,
+    /// Number of archived WAL segments to replay, for point-in-time recovery
+    pub wal_segments_total: usize,
+    pub wal_segments_downloaded: usize,
+    /// Number of operations replayed from the WAL archive
+    pub wal_operations_replayed: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl SnapshotRecoveryStatus {
+    pub fn started() -> Self {
+        Self {
+            stage: SnapshotRecoveryStage::Downloading,
+            started_at: Utc::now(),
+            finished_at: None,
+            shards_total: 0,
+            shards_recovered: 0,
+            wal_segments_total: 0,
+            wal_segments_downloaded: 0,
+            wal_operations_replayed: 0,
+            error: None,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+pub struct CreateSnapshot {
+    /// Optional hex-encoded 256-bit key to encrypt the snapshot with AES-256-GCM.
+    /// If not set, the configured snapshot encryption key is used, if any.
+    #[serde(default)]
+    pub encryption_key: Option<String>,
+}
+
+/// Schedule of automatic snapshots of the collection, created on this node
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
+pub struct SnapshotSchedule {
+    /// Cron expression `minute hour day-of-month month day-of-week`, evaluated in UTC.
+    /// Example: `0 3 * * *` - every day at 03:00.
+    #[validate(custom = "validate_cron_expression")]
+    pub cron: String,
+
+    /// Which snapshots of the collection to keep after a scheduled snapshot is created.
+    /// All snapshots are kept, if not set.
+    #[serde(default)]
+    #[validate]
+    pub retention: SnapshotRetention,
+}
+
+impl SnapshotSchedule {
+    pub fn cron_schedule(&self) -> CollectionResult<CronSchedule> {
+        self.cron
+            .parse::<CronSchedule>()
+            .map_err(|err| CollectionError::bad_input(err.to_string()))
+    }
+
+    /// Time of the next scheduled snapshot after the given time, in UTC
+    pub fn next_run_after(&self, time: NaiveDateTime) -> Option<NaiveDateTime> {
+        self.cron_schedule().ok()?.next_after(time)
+    }
+}
+
+fn validate_cron_expression(cron: &str) -> Result<(), ValidationError> {
+    cron.parse::<CronSchedule>().map(|_| ()).map_err(|err| {
+        let mut error = ValidationError::new("invalid_cron_expression");
+        error.add_param(Cow::from("message"), &err.to_string());
+        error
+    })
+}
+
+/// Retention policy of collection snapshots.
+/// Applies to all snapshots of the collection on this node, including manually created ones.
+/// The most recent snapshot is never removed.
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
+pub struct SnapshotRetention {
+    /// Keep only this number of the most recent snapshots
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub keep_last: Option<usize>,
+
+    /// Remove snapshots older than this number of days
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub keep_days: Option<u32>,
+}
+
+impl SnapshotRetention {
+    /// Select snapshots, which should be removed according to the policy
+    pub fn expired_snapshots(
+        &self,
+        mut snapshots: Vec<SnapshotDescription>,
+        now: NaiveDateTime,
+    ) -> Vec<SnapshotDescription> {
+        // Most recent first
+        snapshots.sort_by(|a, b| {
+            b.creation_time
+                .cmp(&a.creation_time)
+                .then_with(|| b.name.cmp(&a.name))
+        });
+
+        let oldest_allowed = self
+            .keep_days
+            .map(|days| now - chrono::Duration::days(i64::from(days)));
+
+        snapshots
+            .into_iter()
+            .enumerate()
+            .skip(1)
+            .filter(|(index, snapshot)| {
+                let over_limit = self
+                    .keep_last
+                    .map_or(false, |keep_last| *index >= keep_last);
+                let too_old = oldest_allowed.map_or(false, |oldest_allowed| {
+                    snapshot
+                        .creation_time
+                        .map_or(false, |creation_time| creation_time < oldest_allowed)
+                });
+                over_limit || too_old
+            })
+            .map(|(_, snapshot)| snapshot)
+            .collect()
+    }
+}
+
+/// Snapshot schedule of the collection along with the time of the next scheduled snapshot
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
+pub struct SnapshotScheduleInfo {
+    #[serde(flatten)]
+    pub schedule: SnapshotSchedule,
+    /// Time of the next scheduled snapshot, in UTC
+    pub next_run: Option<NaiveDateTime>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone)]
@@ -225,3 +432,67 @@ impl TryFrom<api::grpc::qdrant::ShardSnapshotLocation> for ShardSnapshotLocation
         Ok(location)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(name: &str, creation_time: &str) -> SnapshotDescription {
+        SnapshotDescription {
+            name: name.to_string(),
+            creation_time: Some(
+                NaiveDateTime::parse_from_str(creation_time, "%Y-%m-%d %H:%M:%S").unwrap(),
+            ),
+            size: 0,
+            checksum: None,
+        }
+    }
+
+    fn expired(retention: SnapshotRetention) -> Vec<String> {
+        let snapshots = vec![
+            snapshot("b", "2024-05-02 00:00:00"),
+            snapshot("d", "2024-05-04 00:00:00"),
+            snapshot("a", "2024-05-01 00:00:00"),
+            snapshot("c", "2024-05-03 00:00:00"),
+        ];
+        let now =
+            NaiveDateTime::parse_from_str("2024-05-09 12:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let mut names: Vec<_> = retention
+            .expired_snapshots(snapshots, now)
+            .into_iter()
+            .map(|snapshot| snapshot.name)
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_snapshot_retention() {
+        assert!(expired(SnapshotRetention::default()).is_empty());
+
+        let keep_last = SnapshotRetention {
+            keep_last: Some(2),
+            keep_days: None,
+        };
+        assert_eq!(expired(keep_last), ["a", "b"]);
+
+        let keep_days = SnapshotRetention {
+            keep_last: None,
+            keep_days: Some(7),
+        };
+        assert_eq!(expired(keep_days), ["a", "b"]);
+
+        // The most recent snapshot is always kept
+        let keep_days = SnapshotRetention {
+            keep_last: None,
+            keep_days: Some(1),
+        };
+        assert_eq!(expired(keep_days), ["a", "b", "c"]);
+
+        let both = SnapshotRetention {
+            keep_last: Some(3),
+            keep_days: Some(7),
+        };
+        assert_eq!(expired(both), ["a", "b"]);
+    }
+}
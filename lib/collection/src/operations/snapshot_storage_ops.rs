@@ -8,6 +8,12 @@ use tokio::io::AsyncWriteExt;
 
 use super::snapshot_ops::SnapshotDescription;
 use super::types::{CollectionError, CollectionResult};
+use crate::common::archive_stream::ArchiveStream;
+
+const DEFAULT_CHUNK_SIZE: usize = 50 * 1024 * 1024;
+
+/// Number of parts, which may be uploaded concurrently while streaming
+const MAX_CONCURRENT_PARTS: usize = 4;
 
 pub(crate) fn trim_dot_slash(path: &Path) -> CollectionResult<object_store::path::Path> {
     // Get file name by trimming the path.
@@ -71,7 +77,6 @@ pub async fn get_snapshot_description(
 /// * Azure Storage: https://learn.microsoft.com/en-us/rest/api/storageservices/put-blob?tabs=microsoft-entra-id#remarks
 ///     <TODO> It looks like Azure Storage has different limits for different service versions.
 pub async fn get_appropriate_chunk_size(local_source_path: &Path) -> CollectionResult<usize> {
-    const DEFAULT_CHUNK_SIZE: usize = 50 * 1024 * 1024;
     const MAX_PART_NUMBER: usize = 10000;
     const MAX_UPLOAD_SIZE: usize = 5 * 1024 * 1024 * 1024;
 
@@ -130,6 +135,48 @@ pub async fn multipart_upload(
     Ok(())
 }
 
+/// Upload archive as it is being built, without storing it on the local disk.
+///
+/// The size of the archive is not known in advance, so the default chunk size is used.
+pub async fn multipart_upload_stream(
+    client: &dyn object_store::ObjectStore,
+    mut stream: ArchiveStream,
+    target_path: &Path,
+) -> CollectionResult<()> {
+    let s3_path = trim_dot_slash(target_path)?;
+    let upload = client
+        .put_multipart(&s3_path)
+        .await
+        .map_err(|e| CollectionError::service_error(format!("Failed to put multipart: {}", e)))?;
+
+    let mut write = WriteMultipart::new_with_chunk_size(upload, DEFAULT_CHUNK_SIZE);
+    while let Some(data) = stream.next().await {
+        let data = match data {
+            Ok(data) => data,
+            Err(err) => {
+                if let Err(abort_err) = write.abort().await {
+                    log::warn!("Failed to abort upload of {s3_path}: {abort_err}");
+                }
+                return Err(CollectionError::service_error(format!(
+                    "Failed to create snapshot archive: {err}"
+                )));
+            }
+        };
+        // Don't buffer more parts in memory, than can be uploaded at once
+        write
+            .wait_for_capacity(MAX_CONCURRENT_PARTS)
+            .await
+            .map_err(|e| CollectionError::service_error(format!("Failed to upload part: {}", e)))?;
+        write.write(&data);
+    }
+    write
+        .finish()
+        .await
+        .map_err(|e| CollectionError::service_error(format!("Failed to finish upload: {}", e)))?;
+
+    Ok(())
+}
+
 pub async fn list_snapshot_descriptions(
     client: &dyn object_store::ObjectStore,
     directory: &Path,
@@ -0,0 +1,107 @@
+//! Conversion of text into sparse vectors, configured by [`SparseTextParams`].
+//!
+//! Documents and queries are tokenized with the same tokenizers as the full-text payload index.
+//! Each token is hashed into a dimension of the sparse vector, so the same text is converted
+//! into the same vector on ingest and on query, on any peer and any version.
+
+use std::collections::BTreeMap;
+
+use segment::data_types::text_index::{TextIndexParams, TextIndexType};
+use segment::index::field_index::full_text_index::tokenizers::Tokenizer;
+use sparse::common::sparse_vector::SparseVector;
+use sparse::common::types::{DimId, DimWeight};
+
+use super::types::SparseTextParams;
+
+/// FNV-1a parameters, the hash must never change to keep stored vectors valid
+const FNV_OFFSET_BASIS: u32 = 0x811c9dc5;
+const FNV_PRIME: u32 = 0x01000193;
+
+impl SparseTextParams {
+    /// Convert text of a point into a sparse vector
+    pub fn document_to_sparse(&self, text: &str) -> SparseVector {
+        let mut weights = BTreeMap::<DimId, DimWeight>::new();
+        Tokenizer::tokenize_doc(text, &self.text_index_params(), |token| {
+            *weights.entry(token_dimension(token)).or_default() += 1.0;
+        });
+        sparse_from_weights(weights)
+    }
+
+    /// Convert text of a query into a sparse vector
+    pub fn query_to_sparse(&self, text: &str) -> SparseVector {
+        let mut weights = BTreeMap::<DimId, DimWeight>::new();
+        Tokenizer::tokenize_query(text, &self.text_index_params(), |token| {
+            *weights.entry(token_dimension(token)).or_default() += 1.0;
+        });
+        sparse_from_weights(weights)
+    }
+
+    fn text_index_params(&self) -> TextIndexParams {
+        TextIndexParams {
+            r#type: TextIndexType::Text,
+            tokenizer: self.tokenizer,
+            min_token_len: self.min_token_len,
+            max_token_len: self.max_token_len,
+            lowercase: self.lowercase,
+        }
+    }
+}
+
+/// Dimension of the sparse vector, which represents the token
+fn token_dimension(token: &str) -> DimId {
+    token.bytes().fold(FNV_OFFSET_BASIS, |hash, byte| {
+        (hash ^ DimId::from(byte)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+fn sparse_from_weights(weights: BTreeMap<DimId, DimWeight>) -> SparseVector {
+    let (indices, values) = weights.into_iter().unzip();
+    SparseVector { indices, values }
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::data_types::text_index::TokenizerType;
+
+    use super::*;
+
+    #[test]
+    fn test_document_to_sparse() {
+        let params = SparseTextParams::default();
+
+        let vector = params.document_to_sparse("The cat sat on the mat");
+        assert_eq!(vector.indices.len(), 5);
+        assert!(vector.indices.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(vector.values.iter().sum::<f32>(), 6.0);
+
+        let the = token_dimension("the");
+        let position = vector.indices.iter().position(|&idx| idx == the).unwrap();
+        assert_eq!(vector.values[position], 2.0);
+
+        // Same tokens produce same dimensions in queries
+        let query = params.query_to_sparse("CAT");
+        assert_eq!(query.indices, vec![token_dimension("cat")]);
+        assert_eq!(query.values, vec![1.0]);
+        assert!(vector.indices.contains(&query.indices[0]));
+    }
+
+    #[test]
+    fn test_prefix_tokenizer_to_sparse() {
+        let params = SparseTextParams {
+            tokenizer: TokenizerType::Prefix,
+            min_token_len: Some(2),
+            ..Default::default()
+        };
+
+        let vector = params.document_to_sparse("search");
+        let query = params.query_to_sparse("sea");
+        assert_eq!(query.indices.len(), 1);
+        assert!(vector.indices.contains(&query.indices[0]));
+    }
+
+    #[test]
+    fn test_token_dimension_is_stable() {
+        assert_eq!(token_dimension(""), 0x811c9dc5);
+        assert_eq!(token_dimension("a"), 0xe40c292c);
+    }
+}
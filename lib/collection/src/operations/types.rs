@@ -1,4 +1,5 @@
 use std::backtrace::Backtrace;
+use std::borrow::Cow;
 use std::collections::{BTreeMap, HashMap};
 use std::error::Error as _;
 use std::fmt::Write as _;
@@ -7,6 +8,7 @@ use std::num::NonZeroU64;
 use std::time::SystemTimeError;
 
 use api::grpc::transport_channel_pool::RequestError;
+use api::rest::conversions::InferenceInputError;
 use api::rest::{OrderByInterface, RecommendStrategy, ShardKeySelector};
 use common::defaults;
 use common::types::ScoreType;
@@ -18,14 +20,16 @@ use schemars::JsonSchema;
 use segment::common::anonymize::Anonymize;
 use segment::common::operation_error::OperationError;
 use segment::data_types::groups::GroupId;
+use segment::data_types::text_index::TokenizerType;
 use segment::data_types::vectors::{
     DenseVector, QueryVector, VectorRef, VectorStruct, DEFAULT_VECTOR_NAME,
 };
+use segment::index::vector_index_info::VectorIndexInfo;
 use segment::json_path::{JsonPath, JsonPathInterface};
 use segment::types::{
     Distance, Filter, MultiVectorConfig, Payload, PayloadIndexInfo, PayloadKeyType, PointIdType,
-    QuantizationConfig, SearchParams, SeqNumberType, ShardKey, VectorStorageDatatype,
-    WithPayloadInterface, WithVector,
+    QuantizationConfig, SearchParams, SegmentType, SeqNumberType, ShardKey, SparseVectorStats,
+    VectorStorageDatatype, WithPayloadInterface, WithVector,
 };
 use semver::Version;
 use serde;
@@ -41,7 +45,8 @@ use validator::{Validate, ValidationError, ValidationErrors};
 
 use super::config_diff::{self};
 use super::ClockTag;
-use crate::config::{CollectionConfig, CollectionParams};
+use crate::collection_manager::holders::segment_holder::SegmentId;
+use crate::config::{CollectionConfig, CollectionParams, QuotaConfig};
 use crate::lookup::types::WithLookupInterface;
 use crate::operations::config_diff::{HnswConfigDiff, QuantizationConfigDiff};
 use crate::operations::query_enum::QueryEnum;
@@ -96,6 +101,9 @@ pub struct Record {
     pub vector: Option<VectorStruct>,
     /// Shard Key
     pub shard_key: Option<ShardKey>,
+    /// Point version, the last update operation applied to this point.
+    /// Versions are local to the replica, which served the request
+    pub version: Option<SeqNumberType>,
 }
 
 /// Current statistics and configuration of the collection
@@ -126,6 +134,10 @@ pub struct CollectionInfo {
     pub config: CollectionConfig,
     /// Types of stored payload
     pub payload_schema: HashMap<PayloadKeyType, PayloadIndexInfo>,
+    /// Statistics of sparse vectors by name, used to weight dimensions with the IDF modifier.
+    /// Only available for collections with sparse vectors.
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub sparse_vectors_stats: HashMap<String, SparseVectorStats>,
 }
 
 impl CollectionInfo {
@@ -139,6 +151,7 @@ impl CollectionInfo {
             segments_count: 0,
             config: collection_config,
             payload_schema: HashMap::new(),
+            sparse_vectors_stats: HashMap::new(),
         }
     }
 }
@@ -154,6 +167,7 @@ impl From<CollectionInfoInternal> for CollectionInfo {
             segments_count: info.segments_count,
             config: info.config,
             payload_schema: info.payload_schema,
+            sparse_vectors_stats: info.sparse_vectors_stats,
         }
     }
 }
@@ -184,6 +198,8 @@ pub struct CollectionInfoInternal {
     pub config: CollectionConfig,
     /// Types of stored payload
     pub payload_schema: HashMap<PayloadKeyType, PayloadIndexInfo>,
+    /// Statistics of sparse vectors by name
+    pub sparse_vectors_stats: HashMap<String, SparseVectorStats>,
 }
 
 /// Current clustering distribution for the collection
@@ -251,6 +267,374 @@ pub struct RemoteShardInfo {
     pub state: ReplicaState,
 }
 
+/// Number of points in a single segment
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct SegmentPointsBreakdown {
+    pub segment_type: SegmentType,
+    pub is_appendable: bool,
+    /// Number of live points in the segment
+    pub points_count: usize,
+    /// Number of deleted points, which are not yet cleaned up by the optimizer
+    pub deleted_points_count: usize,
+}
+
+/// Number of points in the segments of a local shard
+#[derive(Debug, Default)]
+pub struct LocalShardPointsBreakdown {
+    pub segments: Vec<SegmentPointsBreakdown>,
+    pub cold_points_count: usize,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ShardPointsBreakdown {
+    /// Local shard id
+    pub shard_id: ShardId,
+    /// User-defined sharding key
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKey>,
+    /// Is replica active
+    pub state: ReplicaState,
+    /// Number of live points in all segments of the shard
+    pub points_count: usize,
+    /// Number of live points in segments with vector index built
+    pub indexed_points_count: usize,
+    /// Number of live points in plain segments, which are not indexed yet
+    pub plain_points_count: usize,
+    /// Number of deleted points, which are not yet cleaned up by the optimizer
+    pub deleted_points_count: usize,
+    /// Number of points in cold segments, offloaded by tiering
+    pub cold_points_count: usize,
+    pub segments: Vec<SegmentPointsBreakdown>,
+}
+
+impl ShardPointsBreakdown {
+    pub fn new(
+        shard_id: ShardId,
+        shard_key: Option<ShardKey>,
+        state: ReplicaState,
+        breakdown: LocalShardPointsBreakdown,
+    ) -> Self {
+        let LocalShardPointsBreakdown {
+            segments,
+            cold_points_count,
+        } = breakdown;
+
+        let mut indexed_points_count = 0;
+        let mut plain_points_count = 0;
+        let mut deleted_points_count = 0;
+        for segment in &segments {
+            match segment.segment_type {
+                SegmentType::Indexed => indexed_points_count += segment.points_count,
+                SegmentType::Plain | SegmentType::Special => {
+                    plain_points_count += segment.points_count
+                }
+            }
+            deleted_points_count += segment.deleted_points_count;
+        }
+
+        Self {
+            shard_id,
+            shard_key,
+            state,
+            points_count: indexed_points_count + plain_points_count,
+            indexed_points_count,
+            plain_points_count,
+            deleted_points_count,
+            cold_points_count,
+            segments,
+        }
+    }
+}
+
+/// Point counts of the local shards of a collection
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct CollectionPointsBreakdown {
+    /// ID of this peer
+    pub peer_id: PeerId,
+    /// Local shards, sorted by shard id
+    pub shards: Vec<ShardPointsBreakdown>,
+}
+
+/// Bytes on disk of segments, by kind of stored data
+#[derive(Debug, Serialize, JsonSchema, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct SegmentDataDiskUsage {
+    /// Original vectors
+    pub vector_storage_bytes: u64,
+    /// Quantized vectors
+    pub quantized_vectors_bytes: u64,
+    /// HNSW graphs and sparse vector indexes
+    pub vector_index_bytes: u64,
+    /// Payloads, if stored in separate files
+    pub payload_storage_bytes: u64,
+    /// Indexes of payload fields
+    pub payload_index_bytes: u64,
+    /// ID tracker, metadata and RocksDB of the segment, which may also hold vectors and payloads
+    pub other_bytes: u64,
+}
+
+impl SegmentDataDiskUsage {
+    pub fn total_bytes(&self) -> u64 {
+        let Self {
+            vector_storage_bytes,
+            quantized_vectors_bytes,
+            vector_index_bytes,
+            payload_storage_bytes,
+            payload_index_bytes,
+            other_bytes,
+        } = self;
+        vector_storage_bytes
+            + quantized_vectors_bytes
+            + vector_index_bytes
+            + payload_storage_bytes
+            + payload_index_bytes
+            + other_bytes
+    }
+
+    pub fn add(&mut self, other: &Self) {
+        self.vector_storage_bytes += other.vector_storage_bytes;
+        self.quantized_vectors_bytes += other.quantized_vectors_bytes;
+        self.vector_index_bytes += other.vector_index_bytes;
+        self.payload_storage_bytes += other.payload_storage_bytes;
+        self.payload_index_bytes += other.payload_index_bytes;
+        self.other_bytes += other.other_bytes;
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct SegmentDiskUsage {
+    /// Name of the segment directory
+    pub segment_id: String,
+    pub total_bytes: u64,
+    #[serde(flatten)]
+    pub data: SegmentDataDiskUsage,
+}
+
+/// Disk usage of a local shard, as found in its directory
+#[derive(Debug, Clone, Default)]
+pub struct LocalShardDiskUsage {
+    /// All files of the shard
+    pub total_bytes: u64,
+    pub wal_bytes: u64,
+    pub segments: Vec<SegmentDiskUsage>,
+}
+
+#[derive(Debug, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct ShardDiskUsage {
+    /// Local shard id
+    pub shard_id: ShardId,
+    /// User-defined sharding key
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKey>,
+    /// Is replica active
+    pub state: ReplicaState,
+    /// All files of the shard, including WAL, segments and shard metadata
+    pub total_bytes: u64,
+    /// Write-ahead log
+    pub wal_bytes: u64,
+    /// Data of all segments of the shard
+    pub segments_data: SegmentDataDiskUsage,
+    /// Segments, sorted by size, largest first
+    pub segments: Vec<SegmentDiskUsage>,
+}
+
+impl ShardDiskUsage {
+    pub fn new(
+        shard_id: ShardId,
+        shard_key: Option<ShardKey>,
+        state: ReplicaState,
+        usage: LocalShardDiskUsage,
+    ) -> Self {
+        let LocalShardDiskUsage {
+            total_bytes,
+            wal_bytes,
+            mut segments,
+        } = usage;
+
+        segments.sort_by(|a, b| b.total_bytes.cmp(&a.total_bytes));
+
+        let mut segments_data = SegmentDataDiskUsage::default();
+        for segment in &segments {
+            segments_data.add(&segment.data);
+        }
+
+        Self {
+            shard_id,
+            shard_key,
+            state,
+            total_bytes,
+            wal_bytes,
+            segments_data,
+            segments,
+        }
+    }
+}
+
+/// Disk usage of a collection on this peer
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct CollectionDiskUsage {
+    /// ID of this peer
+    pub peer_id: PeerId,
+    /// All files of the collection, including snapshots
+    pub total_bytes: u64,
+    /// Write-ahead logs of all local shards
+    pub wal_bytes: u64,
+    /// Snapshots of the collection and its shards, stored on the local disk
+    pub snapshots_bytes: u64,
+    /// Data of all segments of local shards
+    pub segments_data: SegmentDataDiskUsage,
+    /// Local shards, sorted by shard id
+    pub shards: Vec<ShardDiskUsage>,
+}
+
+#[derive(Debug, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct SegmentIndexInfo {
+    /// Name of the segment directory
+    pub segment_id: String,
+    pub segment_type: SegmentType,
+    /// Number of live points in the segment
+    pub points_count: usize,
+    pub deleted_points_count: usize,
+    /// Indexes and quantization of each vector, sorted by vector name
+    pub vectors: Vec<VectorIndexInfo>,
+}
+
+#[derive(Debug, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct ShardIndexInfo {
+    /// Local shard id
+    pub shard_id: ShardId,
+    /// User-defined sharding key
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKey>,
+    /// Is replica active
+    pub state: ReplicaState,
+    pub segments: Vec<SegmentIndexInfo>,
+}
+
+/// Vector indexes and quantized vectors of the local shards of a collection
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct CollectionIndexInfo {
+    /// ID of this peer
+    pub peer_id: PeerId,
+    /// Local shards, sorted by shard id
+    pub shards: Vec<ShardIndexInfo>,
+}
+
+#[derive(Debug, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SegmentOptimizationStatus {
+    /// Waiting for previous segments and CPU budget
+    Pending,
+    /// Segment is being optimized
+    Optimizing,
+    /// Segment is optimized
+    Done,
+    /// Segment was optimized by the optimizers of the collection in the meantime
+    Skipped,
+    /// Optimization was stopped, e.g. because of collection update or shutdown
+    Cancelled,
+    /// Optimization failed, see the error
+    Failed,
+}
+
+#[derive(Debug, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct SegmentOptimizationProgress {
+    /// Local shard id
+    pub shard_id: ShardId,
+    /// ID of the segment in the shard, same as in optimizer telemetry
+    pub segment_id: SegmentId,
+    /// Number of live points in the segment, when the optimization was requested
+    pub points_count: usize,
+    pub status: SegmentOptimizationStatus,
+    /// Error of the failed optimization
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Progress of the requested optimization of local shards of a collection
+#[derive(Debug, Serialize, JsonSchema, Clone, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct ForceOptimizationProgress {
+    /// Segments, which are not optimized yet, in order of optimization
+    pub segments: Vec<SegmentOptimizationProgress>,
+}
+
+impl ForceOptimizationProgress {
+    pub fn update(
+        &mut self,
+        shard_id: ShardId,
+        segment_id: SegmentId,
+        status: SegmentOptimizationStatus,
+        error: Option<String>,
+    ) {
+        if let Some(segment) = self
+            .segments
+            .iter_mut()
+            .find(|segment| segment.shard_id == shard_id && segment.segment_id == segment_id)
+        {
+            segment.status = status;
+            segment.error = error;
+        }
+    }
+}
+
+/// Compaction of segments with many deleted points
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct VacuumRequest {
+    /// Minimal fraction of deleted points in a segment, required to compact it.
+    /// If not set - `deleted_threshold` of the optimizers config of the collection is used.
+    #[validate(range(min = 0.0, max = 1.0))]
+    #[serde(default)]
+    pub deleted_threshold: Option<f64>,
+}
+
+/// Outcome of the compaction of segments of local shards
+#[derive(Debug, Serialize, JsonSchema, Clone, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct VacuumResult {
+    /// Number of compacted segments
+    pub segments: usize,
+    /// Number of deleted points, removed from the compacted segments
+    pub deleted_points: usize,
+    /// Decrease of disk usage of segments of local shards.
+    /// May be lower than actually reclaimed, if points are inserted during the compaction.
+    pub reclaimed_bytes: u64,
+}
+
+/// Usage of a tenant, which is limited by the quotas of the collection
+#[derive(Debug, Serialize, JsonSchema, Clone, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct TenantUsage {
+    /// Shard key of the tenant, if the collection is sharded by keys
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKey>,
+    /// Number of points of the tenant
+    pub points_count: usize,
+    /// Total size of payloads of the tenant, in bytes of serialized JSON
+    pub payload_bytes: usize,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct CollectionQuotaUsage {
+    /// Limits of each tenant, if configured
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quota: Option<QuotaConfig>,
+    /// Usage of each tenant
+    pub tenants: Vec<TenantUsage>,
+}
+
 /// `Acknowledged` - Request is saved to WAL and will be process in a queue.
 /// `Completed` - Request is completed, changes are actual.
 #[derive(Debug, Serialize, JsonSchema, PartialEq, Eq)]
@@ -829,6 +1213,17 @@ pub struct CountResult {
     pub count: usize,
 }
 
+/// Points, which would be affected by an update operation
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DryRunResult {
+    /// Number of points, which would be affected by the operation
+    pub count: usize,
+    /// IDs of some of the affected points, if requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sample_ids: Option<Vec<PointIdType>>,
+}
+
 #[derive(Error, Debug, Clone, PartialEq)]
 #[error("{0}")]
 pub enum CollectionError {
@@ -867,6 +1262,10 @@ pub enum CollectionError {
     PreConditionFailed { description: String },
     #[error("Object Store error: {what}")]
     ObjectStoreError { what: String },
+    #[error("Conflict: {description}")]
+    Conflict { description: String },
+    #[error("Quota exceeded: {description}")]
+    QuotaExceeded { description: String },
 }
 
 impl CollectionError {
@@ -939,6 +1338,18 @@ impl CollectionError {
         }
     }
 
+    pub fn conflict(description: impl Into<String>) -> CollectionError {
+        CollectionError::Conflict {
+            description: description.into(),
+        }
+    }
+
+    pub fn quota_exceeded(description: impl Into<String>) -> CollectionError {
+        CollectionError::QuotaExceeded {
+            description: description.into(),
+        }
+    }
+
     /// Returns true if the error is transient and the operation can be retried.
     /// Returns false if the error is not transient and the operation should fail on all replicas.
     pub fn is_transient(&self) -> bool {
@@ -958,6 +1369,8 @@ impl CollectionError {
             Self::InconsistentShardFailure { .. } => false,
             Self::ForwardProxyError { .. } => false,
             Self::ObjectStoreError { .. } => false,
+            Self::Conflict { .. } => false,
+            Self::QuotaExceeded { .. } => false,
         }
     }
 }
@@ -1025,6 +1438,10 @@ impl From<OperationError> for CollectionError {
             OperationError::WrongMulti => Self::BadInput {
                 description: "Conversion between multi and regular vectors failed".to_string(),
             },
+            OperationError::WrongDouble => Self::BadInput {
+                description: "Conversion between double precision and regular vectors failed"
+                    .to_string(),
+            },
             OperationError::WrongPayloadKey { description } => Self::BadInput { description },
         }
     }
@@ -1102,6 +1519,12 @@ impl From<InvalidUri> for CollectionError {
     }
 }
 
+impl From<InferenceInputError> for CollectionError {
+    fn from(err: InferenceInputError) -> Self {
+        CollectionError::bad_input(err.to_string())
+    }
+}
+
 impl From<tonic::Status> for CollectionError {
     fn from(err: tonic::Status) -> Self {
         match err.code() {
@@ -1127,6 +1550,9 @@ impl From<tonic::Status> for CollectionError {
             tonic::Code::FailedPrecondition => CollectionError::PreConditionFailed {
                 description: format!("{err}"),
             },
+            tonic::Code::Aborted => CollectionError::Conflict {
+                description: format!("{err}"),
+            },
             _other => CollectionError::ServiceError {
                 error: format!("Tonic status error: {err}"),
                 backtrace: Some(Backtrace::force_capture().to_string()),
@@ -1233,11 +1659,13 @@ impl Record {
 /// Choosing different datatypes allows to optimize memory usage and performance vs accuracy.
 /// - For `float32` datatype - vectors are stored as single-precision floating point numbers, 4bytes.
 /// - For `uint8` datatype - vectors are stored as unsigned 8-bit integers, 1byte. It expects vector elements to be in range `[0, 255]`.
+/// - For `float64` datatype - vectors are stored as double-precision floating point numbers, 8bytes. Distances are accumulated in double precision.
 pub enum Datatype {
     #[default]
     Float32,
     Uint8,
     Float16,
+    Float64,
 }
 
 impl From<Datatype> for VectorStorageDatatype {
@@ -1246,6 +1674,7 @@ impl From<Datatype> for VectorStorageDatatype {
             Datatype::Float32 => VectorStorageDatatype::Float32,
             Datatype::Uint8 => VectorStorageDatatype::Uint8,
             Datatype::Float16 => VectorStorageDatatype::Float16,
+            Datatype::Float64 => VectorStorageDatatype::Float64,
         }
     }
 }
@@ -1253,6 +1682,7 @@ impl From<Datatype> for VectorStorageDatatype {
 /// Params of single vector data storage
 #[derive(Debug, Hash, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
+#[validate(schema(function = "validate_vector_params_datatype"))]
 pub struct VectorParams {
     /// Size of a vectors used
     #[validate(custom = "validate_nonzerou64_range_min_1_max_65536")]
@@ -1281,8 +1711,64 @@ pub struct VectorParams {
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub multivec_config: Option<MultiVectorConfig>,
+
+    /// Reduce dimensionality of vectors on upsert and in queries.
+    /// Vectors are provided with `input_size` dimensions and stored with `size` dimensions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate]
+    pub projection: Option<ProjectionConfig>,
+}
+
+/// Projection of vectors to a lower number of dimensions, applied by the server
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct ProjectionConfig {
+    /// Size of vectors before the projection, as they are provided in upserts and queries
+    #[validate(custom = "validate_nonzerou64_range_min_1_max_65536")]
+    pub input_size: NonZeroU64,
+    /// How vectors are projected
+    pub method: ProjectionMethod,
+    /// Name of another vector of the collection, to store original vectors in.
+    /// If set, search results are rescored with the original vectors.
+    /// The vector has to be of `input_size` and of the same distance, preferably on disk.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original_vector: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectionMethod {
+    /// Keep first `size` dimensions of vectors, e.g. of Matryoshka embeddings.
+    /// The index is built over the prefixes, full vectors can be kept for rescoring in the
+    /// original vector.
+    Prefix,
+    /// Sparse random projection, with the matrix generated from the seed
+    Random { seed: u64 },
+    /// Projection with a given matrix, e.g. principal components of a sample of vectors.
+    /// One row of `input_size` values per dimension of the projected vectors.
+    Matrix {
+        matrix: Vec<DenseVector>,
+        /// Subtracted from vectors before the projection, e.g. mean of the sample of vectors
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        offset: Option<DenseVector>,
+    },
 }
 
+impl std::hash::Hash for ProjectionConfig {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.input_size.hash(state);
+        self.original_vector.hash(state);
+        std::mem::discriminant(&self.method).hash(state);
+        match &self.method {
+            ProjectionMethod::Prefix => {}
+            ProjectionMethod::Random { seed } => seed.hash(state),
+            ProjectionMethod::Matrix { matrix, .. } => matrix.len().hash(state),
+        }
+    }
+}
+
+impl Eq for ProjectionConfig {}
+
 /// Validate the value is in `[1, 65536]` or `None`.
 pub fn validate_nonzerou64_range_min_1_max_65536(
     value: &NonZeroU64,
@@ -1290,6 +1776,19 @@ pub fn validate_nonzerou64_range_min_1_max_65536(
     validate_range_generic(value.get(), Some(1), Some(65536))
 }
 
+/// Multivectors can't be stored with `float64` datatype.
+fn validate_vector_params_datatype(params: &VectorParams) -> Result<(), ValidationError> {
+    if params.multivec_config.is_some() && params.datatype == Some(Datatype::Float64) {
+        let mut error = ValidationError::new("unsupported_datatype");
+        error.add_param(
+            Cow::from("message"),
+            &"`float64` datatype is not supported for multivectors",
+        );
+        return Err(error);
+    }
+    Ok(())
+}
+
 /// Is considered empty if `None` or if diff has no field specified
 fn is_hnsw_diff_empty(hnsw_config: &Option<HnswConfigDiff>) -> bool {
     hnsw_config
@@ -1327,6 +1826,12 @@ pub struct SparseVectorParams {
     /// Default: none
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub modifier: Option<Modifier>,
+
+    /// If set, the server converts documents, provided instead of vectors, into sparse vectors.
+    /// Can't be changed after the collection is created.
+    /// Default: none
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<SparseTextParams>,
 }
 
 impl Anonymize for SparseVectorParams {
@@ -1334,10 +1839,33 @@ impl Anonymize for SparseVectorParams {
         Self {
             index: self.index.anonymize(),
             modifier: self.modifier.clone(),
+            text: self.text.clone(),
         }
     }
 }
 
+/// Conversion of text into sparse vectors by the server.
+///
+/// Text is split into tokens, each token is hashed into an index of the sparse vector,
+/// and the number of occurrences of the token is used as its value.
+/// Use `idf` modifier to weight tokens by inverse document frequency.
+#[derive(Debug, Hash, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct SparseTextParams {
+    /// Tokenizer to split text into tokens. Default: word
+    #[serde(default)]
+    pub tokenizer: TokenizerType,
+    /// Tokens shorter than this are ignored
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_token_len: Option<usize>,
+    /// Tokens longer than this are ignored
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_token_len: Option<usize>,
+    /// If true, lowercase all tokens. Default: true
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lowercase: Option<bool>,
+}
+
 /// Configuration for sparse inverted index.
 #[derive(Debug, Hash, Deserialize, Serialize, JsonSchema, Copy, Clone, PartialEq, Eq, Default)]
 #[serde(rename_all = "snake_case")]
@@ -1487,6 +2015,66 @@ impl VectorsConfig {
 
         Ok(())
     }
+
+    /// Check that projections of vectors match sizes of the vectors they are stored in
+    pub fn check_projections(&self) -> CollectionResult<()> {
+        for (vector_name, params) in self.params_iter() {
+            let Some(projection) = &params.projection else {
+                continue;
+            };
+            let bad_input = |description: String| {
+                Err(CollectionError::bad_input(format!(
+                    "Invalid projection of vector {vector_name}: {description}"
+                )))
+            };
+
+            if params.multivec_config.is_some() {
+                return bad_input("multivectors can't be projected".to_string());
+            }
+            let input_size = projection.input_size.get() as usize;
+            let size = params.size.get() as usize;
+            if input_size < size {
+                return bad_input(format!(
+                    "input size {input_size} is smaller than vector size {size}"
+                ));
+            }
+
+            if let ProjectionMethod::Matrix { matrix, offset } = &projection.method {
+                if matrix.len() != size {
+                    return bad_input(format!(
+                        "matrix has {} rows, while vector size is {size}",
+                        matrix.len()
+                    ));
+                }
+                let mut rows = matrix.iter().chain(offset);
+                if let Some(row) = rows.find(|row| row.len() != input_size) {
+                    return bad_input(format!(
+                        "matrix row has {} values, while input size is {input_size}",
+                        row.len()
+                    ));
+                }
+            }
+
+            if let Some(original_vector) = &projection.original_vector {
+                let Some(original) = self.get_params(original_vector) else {
+                    return bad_input(format!("original vector {original_vector} doesn't exist"));
+                };
+                if original.projection.is_some() || original.multivec_config.is_some() {
+                    return bad_input(format!(
+                        "original vector {original_vector} can't be projected or a multivector"
+                    ));
+                }
+                if original.size != projection.input_size || original.distance != params.distance {
+                    return bad_input(format!(
+                        "original vector {original_vector} has to be of size {input_size} \
+                         and {:?} distance",
+                        params.distance,
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
 }
 
 // TODO(sparse): Further unify `check_compatible` and `check_compatible_with_segment_config`?
@@ -1761,10 +2349,12 @@ pub struct BaseGroupRequest {
     pub with_lookup: Option<WithLookupInterface>,
 }
 
-impl From<SearchRequestInternal> for CoreSearchRequest {
-    fn from(request: SearchRequestInternal) -> Self {
-        Self {
-            query: QueryEnum::Nearest(request.vector.into()),
+impl TryFrom<SearchRequestInternal> for CoreSearchRequest {
+    type Error = CollectionError;
+
+    fn try_from(request: SearchRequestInternal) -> Result<Self, Self::Error> {
+        Ok(Self {
+            query: QueryEnum::Nearest(request.vector.try_into()?),
             filter: request.filter,
             params: request.params,
             limit: request.limit,
@@ -1772,7 +2362,7 @@ impl From<SearchRequestInternal> for CoreSearchRequest {
             with_payload: request.with_payload,
             with_vector: request.with_vector,
             score_threshold: request.score_threshold,
-        }
+        })
     }
 }
 
@@ -1798,15 +2388,50 @@ pub struct IssuesReport {
 pub struct PeerMetadata {
     /// Peer Qdrant version
     pub(crate) version: Version,
+    /// Availability zone or rack of the peer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) zone: Option<String>,
+    /// Arbitrary labels of the peer, used in placement constraints of collections
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub(crate) labels: BTreeMap<String, String>,
+    /// Peer is in read-only mode, it rejects writes and incoming shard transfers
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub(crate) read_only: bool,
 }
 
 impl PeerMetadata {
-    pub fn current() -> Self {
+    pub fn current(
+        zone: Option<String>,
+        labels: BTreeMap<String, String>,
+        read_only: bool,
+    ) -> Self {
         Self {
             version: defaults::QDRANT_VERSION.clone(),
+            zone,
+            labels,
+            read_only,
         }
     }
 
+    pub fn zone(&self) -> Option<&str> {
+        self.zone.as_deref()
+    }
+
+    pub fn labels(&self) -> &BTreeMap<String, String> {
+        &self.labels
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Whether the peer has all of the given labels, with the same values
+    pub fn has_labels(&self, labels: &BTreeMap<String, String>) -> bool {
+        labels
+            .iter()
+            .all(|(key, value)| self.labels.get(key) == Some(value))
+    }
+
     /// Whether this metadata has a different version than our current Qdrant instance.
     pub fn is_different_version(&self) -> bool {
         self.version != *defaults::QDRANT_VERSION
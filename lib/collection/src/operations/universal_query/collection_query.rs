@@ -211,6 +211,9 @@ mod from_rest {
                     // TODO(universal-query): Validate at API level
                     Vector::MultiDense(MultiDenseVector::new_unchecked(multi_dense)),
                 ),
+                rest::VectorInput::DenseDoubleVector(dense_double) => {
+                    VectorInput::Vector(Vector::DenseDouble(dense_double.float64))
+                }
             }
         }
     }
@@ -458,6 +461,9 @@ mod from_grpc {
                     // TODO(universal-query): Validate at API level
                     Vector::MultiDense(From::from(multi_dense)),
                 ),
+                Variant::DenseDouble(dense_double) => {
+                    VectorInput::Vector(Vector::DenseDouble(From::from(dense_double)))
+                }
             };
 
             Ok(vector_input)
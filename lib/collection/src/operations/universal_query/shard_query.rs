@@ -161,10 +161,14 @@ impl QueryEnum {
                     (None, Vector::Sparse(_)) => {
                         return Err(Status::invalid_argument("Sparse vector must have a name"))
                     }
-                    (Some(name), Vector::MultiDense(_) | Vector::Sparse(_) | Vector::Dense(_)) => {
-                        name
-                    }
-                    (None, Vector::MultiDense(_) | Vector::Dense(_)) => {
+                    (
+                        Some(name),
+                        Vector::MultiDense(_)
+                        | Vector::Sparse(_)
+                        | Vector::Dense(_)
+                        | Vector::DenseDouble(_),
+                    ) => name,
+                    (None, Vector::MultiDense(_) | Vector::Dense(_) | Vector::DenseDouble(_)) => {
                         DEFAULT_VECTOR_NAME.to_string()
                     }
                 };
@@ -3,7 +3,7 @@ use std::num::NonZeroU64;
 use segment::types::{Distance, MultiVectorConfig, QuantizationConfig};
 
 use crate::operations::config_diff::HnswConfigDiff;
-use crate::operations::types::{Datatype, VectorParams};
+use crate::operations::types::{Datatype, ProjectionConfig, VectorParams};
 
 pub struct VectorParamsBuilder {
     vector_params: VectorParams,
@@ -20,6 +20,7 @@ impl VectorParamsBuilder {
                 on_disk: None,
                 datatype: None,
                 multivec_config: None,
+                projection: None,
             },
         }
     }
@@ -49,6 +50,11 @@ impl VectorParamsBuilder {
         self
     }
 
+    pub fn with_projection(mut self, projection: ProjectionConfig) -> Self {
+        self.vector_params.projection = Some(projection);
+        self
+    }
+
     pub fn build(self) -> VectorParams {
         self.vector_params
     }
@@ -2,6 +2,8 @@ use std::path::Path;
 use std::sync::Arc;
 
 use schemars::JsonSchema;
+use segment::common::encryption::StorageCipher;
+use segment::json_path::JsonPath;
 use segment::types::{HnswConfig, QuantizationConfig};
 use serde::{Deserialize, Serialize};
 use validator::Validate;
@@ -76,6 +78,29 @@ pub struct OptimizersConfig {
     /// If 0 - no optimization threads, optimizations will be disabled.
     #[serde(default)]
     pub max_optimization_threads: Option<usize>,
+    /// Strategy of selecting segments to merge, when there are more segments than `default_segment_number`
+    #[serde(default)]
+    pub merge_policy: MergePolicy,
+    /// Max number of CPUs, which optimizations of all shards of the collection may use at once on each peer.
+    /// Optimizations are also limited by the CPU budget of the peer.
+    /// Can be changed at runtime, to keep indexing of one collection from starving searches of others.
+    /// If null or 0 - limited only by the CPU budget of the peer.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu_budget: Option<usize>,
+}
+
+/// Strategy of selecting segments for merging
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum MergePolicy {
+    /// Merge smallest segments together.
+    /// Keeps the number of segments low with the least amount of merges.
+    #[default]
+    SizeTiered,
+    /// Merge segments of a similar size only.
+    /// Segments are grouped into levels, each next level holds segments several times larger.
+    /// Reduces write amplification of repeatedly merging small segments into a large one.
+    Leveled,
 }
 
 impl OptimizersConfig {
@@ -90,6 +115,8 @@ impl OptimizersConfig {
             indexing_threshold: Some(100_000),
             flush_interval_sec: 60,
             max_optimization_threads: Some(0),
+            merge_policy: MergePolicy::default(),
+            cpu_budget: None,
         }
     }
 
@@ -128,16 +155,7 @@ pub fn clear_temp_segments(shard_path: &Path) {
     }
 }
 
-pub fn build_optimizers(
-    shard_path: &Path,
-    collection_params: &CollectionParams,
-    optimizers_config: &OptimizersConfig,
-    hnsw_config: &HnswConfig,
-    quantization_config: &Option<QuantizationConfig>,
-) -> Arc<Vec<Arc<Optimizer>>> {
-    let segments_path = shard_path.join(SEGMENTS_PATH);
-    let temp_segments_path = shard_path.join(TEMP_SEGMENTS_PATH);
-
+fn optimizer_thresholds(optimizers_config: &OptimizersConfig) -> OptimizerThresholds {
     let indexing_threshold = match optimizers_config.indexing_threshold {
         None => DEFAULT_INDEXING_THRESHOLD_KB, // default value
         Some(0) => usize::MAX,                 // disable vector index
@@ -149,48 +167,171 @@ pub fn build_optimizers(
         Some(custom) => custom,
     };
 
-    let threshold_config = OptimizerThresholds {
+    OptimizerThresholds {
         memmap_threshold,
         indexing_threshold,
         max_segment_size: optimizers_config.get_max_segment_size(),
-    };
+    }
+}
+
+pub fn build_optimizers(
+    shard_path: &Path,
+    collection_params: &CollectionParams,
+    optimizers_config: &OptimizersConfig,
+    hnsw_config: &HnswConfig,
+    quantization_config: &Option<QuantizationConfig>,
+    defragment_key: Option<&JsonPath>,
+    storage_cipher: Option<&Arc<StorageCipher>>,
+) -> Arc<Vec<Arc<Optimizer>>> {
+    let segments_path = shard_path.join(SEGMENTS_PATH);
+    let temp_segments_path = shard_path.join(TEMP_SEGMENTS_PATH);
+    let threshold_config = optimizer_thresholds(optimizers_config);
 
     Arc::new(vec![
-        Arc::new(MergeOptimizer::new(
-            optimizers_config.get_number_segments(),
-            threshold_config.clone(),
-            segments_path.clone(),
-            temp_segments_path.clone(),
-            collection_params.clone(),
-            hnsw_config.clone(),
-            quantization_config.clone(),
-        )),
-        Arc::new(IndexingOptimizer::new(
+        Arc::new(
+            MergeOptimizer::new(
+                optimizers_config.get_number_segments(),
+                optimizers_config.merge_policy,
+                threshold_config.clone(),
+                segments_path.clone(),
+                temp_segments_path.clone(),
+                collection_params.clone(),
+                hnsw_config.clone(),
+                quantization_config.clone(),
+            )
+            .with_defragment_key(defragment_key.cloned())
+            .with_storage_cipher(storage_cipher.cloned()),
+        ),
+        Arc::new(
+            IndexingOptimizer::new(
+                optimizers_config.get_number_segments(),
+                threshold_config.clone(),
+                segments_path.clone(),
+                temp_segments_path.clone(),
+                collection_params.clone(),
+                hnsw_config.clone(),
+                quantization_config.clone(),
+            )
+            .with_defragment_key(defragment_key.cloned())
+            .with_storage_cipher(storage_cipher.cloned()),
+        ),
+        Arc::new(
+            VacuumOptimizer::new(
+                optimizers_config.deleted_threshold,
+                optimizers_config.vacuum_min_vector_number,
+                threshold_config.clone(),
+                segments_path.clone(),
+                temp_segments_path.clone(),
+                collection_params.clone(),
+                hnsw_config.clone(),
+                quantization_config.clone(),
+            )
+            .with_defragment_key(defragment_key.cloned())
+            .with_storage_cipher(storage_cipher.cloned()),
+        ),
+        Arc::new(
+            ConfigMismatchOptimizer::new(
+                threshold_config,
+                segments_path,
+                temp_segments_path,
+                collection_params.clone(),
+                hnsw_config.clone(),
+                quantization_config.clone(),
+            )
+            .with_defragment_key(defragment_key.cloned())
+            .with_storage_cipher(storage_cipher.cloned()),
+        ),
+    ])
+}
+
+/// Optimizer, which indexes segments regardless of their size, to optimize a shard on request.
+///
+/// Returns `None` if vector index is disabled.
+pub fn build_forced_optimizer(
+    shard_path: &Path,
+    collection_params: &CollectionParams,
+    optimizers_config: &OptimizersConfig,
+    hnsw_config: &HnswConfig,
+    quantization_config: &Option<QuantizationConfig>,
+    defragment_key: Option<&JsonPath>,
+    storage_cipher: Option<&Arc<StorageCipher>>,
+) -> Option<Arc<Optimizer>> {
+    if optimizers_config.indexing_threshold == Some(0) {
+        return None;
+    }
+    let threshold_config = OptimizerThresholds {
+        indexing_threshold: 0,
+        ..optimizer_thresholds(optimizers_config)
+    };
+
+    Some(Arc::new(
+        IndexingOptimizer::new(
             optimizers_config.get_number_segments(),
-            threshold_config.clone(),
-            segments_path.clone(),
-            temp_segments_path.clone(),
+            threshold_config,
+            shard_path.join(SEGMENTS_PATH),
+            shard_path.join(TEMP_SEGMENTS_PATH),
             collection_params.clone(),
             hnsw_config.clone(),
             quantization_config.clone(),
-        )),
-        Arc::new(VacuumOptimizer::new(
+        )
+        .with_defragment_key(defragment_key.cloned())
+        .with_storage_cipher(storage_cipher.cloned()),
+    ))
+}
+
+/// Optimizer, which compacts segments with deleted points regardless of their size, to vacuum
+/// a shard on request.
+pub fn build_vacuum_optimizer(
+    shard_path: &Path,
+    collection_params: &CollectionParams,
+    optimizers_config: &OptimizersConfig,
+    hnsw_config: &HnswConfig,
+    quantization_config: &Option<QuantizationConfig>,
+    defragment_key: Option<&JsonPath>,
+    storage_cipher: Option<&Arc<StorageCipher>>,
+) -> Arc<Optimizer> {
+    Arc::new(
+        VacuumOptimizer::new(
             optimizers_config.deleted_threshold,
-            optimizers_config.vacuum_min_vector_number,
-            threshold_config.clone(),
-            segments_path.clone(),
-            temp_segments_path.clone(),
+            0,
+            optimizer_thresholds(optimizers_config),
+            shard_path.join(SEGMENTS_PATH),
+            shard_path.join(TEMP_SEGMENTS_PATH),
             collection_params.clone(),
             hnsw_config.clone(),
             quantization_config.clone(),
-        )),
-        Arc::new(ConfigMismatchOptimizer::new(
+        )
+        .with_defragment_key(defragment_key.cloned())
+        .with_storage_cipher(storage_cipher.cloned()),
+    )
+}
+
+/// Optimizer, which moves vectors of segments to disk regardless of their size, to keep RAM usage
+/// of the node within its memory budget.
+pub fn build_spill_optimizer(
+    shard_path: &Path,
+    collection_params: &CollectionParams,
+    optimizers_config: &OptimizersConfig,
+    hnsw_config: &HnswConfig,
+    quantization_config: &Option<QuantizationConfig>,
+    defragment_key: Option<&JsonPath>,
+    storage_cipher: Option<&Arc<StorageCipher>>,
+) -> Arc<Optimizer> {
+    let threshold_config = OptimizerThresholds {
+        memmap_threshold: 0,
+        ..optimizer_thresholds(optimizers_config)
+    };
+
+    Arc::new(
+        ConfigMismatchOptimizer::new(
             threshold_config,
-            segments_path,
-            temp_segments_path,
+            shard_path.join(SEGMENTS_PATH),
+            shard_path.join(TEMP_SEGMENTS_PATH),
             collection_params.clone(),
             hnsw_config.clone(),
             quantization_config.clone(),
-        )),
-    ])
+        )
+        .with_defragment_key(defragment_key.cloned())
+        .with_storage_cipher(storage_cipher.cloned()),
+    )
 }
@@ -46,6 +46,16 @@ fn avg_vectors<'a>(vectors: impl Iterator<Item = VectorRef<'a>>) -> CollectionRe
                     }
                 }
             }
+            VectorRef::DenseDouble(vector) => {
+                dense_count += 1;
+                for (i, &value) in vector.iter().enumerate() {
+                    if i >= avg_dense.len() {
+                        avg_dense.push(value as f32)
+                    } else {
+                        avg_dense[i] += value as f32;
+                    }
+                }
+            }
             VectorRef::Sparse(vector) => {
                 sparse_count += 1;
                 avg_sparse = vector.combine_aggregate(&avg_sparse, |v1, v2| v1 + v2);
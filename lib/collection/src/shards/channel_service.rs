@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -176,6 +176,35 @@ impl ChannelService {
             .all(|metadata| metadata.version >= version)
     }
 
+    /// Availability zones of the peers, which have one configured
+    pub fn peer_zones(&self) -> HashMap<PeerId, String> {
+        self.id_to_metadata
+            .read()
+            .iter()
+            .filter_map(|(&peer_id, metadata)| Some((peer_id, metadata.zone()?.to_string())))
+            .collect()
+    }
+
+    /// Peers, which have all of the given labels
+    pub fn peers_with_labels(&self, labels: &BTreeMap<String, String>) -> HashSet<PeerId> {
+        self.id_to_metadata
+            .read()
+            .iter()
+            .filter(|(_, metadata)| metadata.has_labels(labels))
+            .map(|(&peer_id, _)| peer_id)
+            .collect()
+    }
+
+    /// Peers in read-only mode
+    pub fn read_only_peers(&self) -> HashSet<PeerId> {
+        self.id_to_metadata
+            .read()
+            .iter()
+            .filter(|(_, metadata)| metadata.is_read_only())
+            .map(|(&peer_id, _)| peer_id)
+            .collect()
+    }
+
     /// Get the REST address for the current peer.
     pub fn current_rest_address(&self, this_peer_id: PeerId) -> CollectionResult<Url> {
         // Get local peer URI
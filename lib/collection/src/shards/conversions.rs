@@ -2,22 +2,27 @@ use api::grpc::conversions::{convert_shard_key_from_grpc_opt, payload_to_proto};
 use api::grpc::qdrant::points_selector::PointsSelectorOneOf;
 use api::grpc::qdrant::{
     ClearPayloadPoints, ClearPayloadPointsInternal, CreateFieldIndexCollection,
-    CreateFieldIndexCollectionInternal, DeleteFieldIndexCollection,
-    DeleteFieldIndexCollectionInternal, DeletePayloadPoints, DeletePayloadPointsInternal,
-    DeletePointVectors, DeletePoints, DeletePointsInternal, DeleteVectorsInternal, PointVectors,
-    PointsIdsList, PointsSelector, SetPayloadPoints, SetPayloadPointsInternal, SyncPoints,
-    SyncPointsInternal, UpdatePointVectors, UpdateVectorsInternal, UpsertPoints,
-    UpsertPointsInternal, VectorsSelector,
+    CreateFieldIndexCollectionInternal, DeleteBlobPoint, DeleteBlobPointInternal,
+    DeleteFieldIndexCollection, DeleteFieldIndexCollectionInternal, DeletePayloadPoints,
+    DeletePayloadPointsInternal, DeletePointVectors, DeletePoints, DeletePointsInternal,
+    DeleteVectorsInternal, PatchPayloadPoints, PatchPayloadPointsInternal, PointVectors,
+    PointsIdsList, PointsSelector, SetBlobPoint, SetBlobPointInternal, SetPayloadPoints,
+    SetPayloadPointsInternal, SyncPoints, SyncPointsInternal, UpdatePointVectors,
+    UpdateVectorsInternal, UpsertPoints, UpsertPointsInternal, VectorsSelector,
 };
 use segment::data_types::vectors::VectorStruct;
 use segment::json_path::JsonPath;
-use segment::types::{Filter, PayloadFieldSchema, PayloadSchemaParams, PointIdType, ScoredPoint};
+use segment::types::{
+    Filter, PayloadFieldSchema, PayloadSchemaParams, PointIdType, ScoredPoint, SeqNumberType,
+};
 use tonic::Status;
 
-use crate::operations::conversions::write_ordering_to_proto;
-use crate::operations::payload_ops::{DeletePayloadOp, SetPayloadOp};
+use crate::operations::blob_ops::{DeleteBlobOp, SetBlobOp};
+use crate::operations::conversions::{update_mode_to_proto, write_ordering_to_proto};
+use crate::operations::payload_ops::{DeletePayloadOp, PatchPayloadOp, SetPayloadOp};
 use crate::operations::point_ops::{
-    PointInsertOperationsInternal, PointSyncOperation, WriteOrdering,
+    ConditionalInsertOperationInternal, PointInsertOperationsInternal, PointSyncOperation,
+    WriteOrdering,
 };
 use crate::operations::types::CollectionResult;
 use crate::operations::vector_ops::UpdateVectorsOp;
@@ -73,15 +78,52 @@ pub fn internal_upsert_points(
             },
             ordering: ordering.map(write_ordering_to_proto),
             shard_key_selector: None,
+            update_mode: None,
+            update_filter: None,
+            if_version: None,
+            dedup_key: None,
         }),
     })
 }
 
+pub fn internal_conditional_upsert_points(
+    shard_id: Option<ShardId>,
+    clock_tag: Option<ClockTag>,
+    collection_name: String,
+    operation: ConditionalInsertOperationInternal,
+    wait: bool,
+    ordering: Option<WriteOrdering>,
+) -> CollectionResult<UpsertPointsInternal> {
+    let ConditionalInsertOperationInternal {
+        points_op,
+        update_mode,
+        update_filter,
+        if_version,
+        dedup_key,
+    } = operation;
+    let mut request = internal_upsert_points(
+        shard_id,
+        clock_tag,
+        collection_name,
+        points_op,
+        wait,
+        ordering,
+    )?;
+    if let Some(upsert_points) = &mut request.upsert_points {
+        upsert_points.update_mode = Some(update_mode_to_proto(update_mode) as i32);
+        upsert_points.update_filter = update_filter.map(Into::into);
+        upsert_points.if_version = if_version;
+        upsert_points.dedup_key = dedup_key.map(|key| key.to_string());
+    }
+    Ok(request)
+}
+
 pub fn internal_delete_points(
     shard_id: Option<ShardId>,
     clock_tag: Option<ClockTag>,
     collection_name: String,
     ids: Vec<PointIdType>,
+    if_version: Option<SeqNumberType>,
     wait: bool,
     ordering: Option<WriteOrdering>,
 ) -> DeletePointsInternal {
@@ -98,6 +140,7 @@ pub fn internal_delete_points(
             }),
             ordering: ordering.map(write_ordering_to_proto),
             shard_key_selector: None,
+            if_version,
         }),
     }
 }
@@ -121,6 +164,7 @@ pub fn internal_delete_points_by_filter(
             }),
             ordering: ordering.map(write_ordering_to_proto),
             shard_key_selector: None,
+            if_version: None,
         }),
     }
 }
@@ -132,25 +176,29 @@ pub fn internal_update_vectors(
     update_vectors: UpdateVectorsOp,
     wait: bool,
     ordering: Option<WriteOrdering>,
-) -> UpdateVectorsInternal {
-    UpdateVectorsInternal {
+) -> CollectionResult<UpdateVectorsInternal> {
+    let points = update_vectors
+        .points
+        .into_iter()
+        .map(|point| {
+            Ok(PointVectors {
+                id: Some(point.id.into()),
+                vectors: Some(VectorStruct::try_from(point.vector)?.into()),
+            })
+        })
+        .collect::<CollectionResult<_>>()?;
+
+    Ok(UpdateVectorsInternal {
         shard_id,
         clock_tag: clock_tag.map(Into::into),
         update_vectors: Some(UpdatePointVectors {
             collection_name,
             wait: Some(wait),
-            points: update_vectors
-                .points
-                .into_iter()
-                .map(|point| PointVectors {
-                    id: Some(point.id.into()),
-                    vectors: Some(VectorStruct::from(point.vector).into()),
-                })
-                .collect(),
+            points,
             ordering: ordering.map(write_ordering_to_proto),
             shard_key_selector: None,
         }),
-    }
+    })
 }
 
 pub fn internal_delete_vectors(
@@ -244,6 +292,40 @@ pub fn internal_set_payload(
     }
 }
 
+pub fn internal_patch_payload(
+    shard_id: Option<ShardId>,
+    clock_tag: Option<ClockTag>,
+    collection_name: String,
+    patch_payload: PatchPayloadOp,
+    wait: bool,
+    ordering: Option<WriteOrdering>,
+) -> PatchPayloadPointsInternal {
+    let points_selector = if let Some(points) = patch_payload.points {
+        Some(PointsSelector {
+            points_selector_one_of: Some(PointsSelectorOneOf::Points(PointsIdsList {
+                ids: points.into_iter().map(|id| id.into()).collect(),
+            })),
+        })
+    } else {
+        patch_payload.filter.map(|filter| PointsSelector {
+            points_selector_one_of: Some(PointsSelectorOneOf::Filter(filter.into())),
+        })
+    };
+
+    PatchPayloadPointsInternal {
+        shard_id,
+        clock_tag: clock_tag.map(Into::into),
+        patch_payload_points: Some(PatchPayloadPoints {
+            collection_name,
+            wait: Some(wait),
+            patch: patch_payload.patch.into_iter().map(Into::into).collect(),
+            points_selector,
+            ordering: ordering.map(write_ordering_to_proto),
+            shard_key_selector: None,
+        }),
+    }
+}
+
 pub fn internal_delete_payload(
     shard_id: Option<ShardId>,
     clock_tag: Option<ClockTag>,
@@ -415,6 +497,49 @@ pub fn internal_delete_index(
     }
 }
 
+pub fn internal_set_blob(
+    shard_id: Option<ShardId>,
+    clock_tag: Option<ClockTag>,
+    collection_name: String,
+    set_blob: SetBlobOp,
+    wait: bool,
+    ordering: Option<WriteOrdering>,
+) -> SetBlobPointInternal {
+    SetBlobPointInternal {
+        shard_id,
+        clock_tag: clock_tag.map(Into::into),
+        set_blob_point: Some(SetBlobPoint {
+            collection_name,
+            wait: Some(wait),
+            id: Some(set_blob.point_id.into()),
+            blob: set_blob.blob,
+            ordering: ordering.map(write_ordering_to_proto),
+            shard_key_selector: None,
+        }),
+    }
+}
+
+pub fn internal_delete_blob(
+    shard_id: Option<ShardId>,
+    clock_tag: Option<ClockTag>,
+    collection_name: String,
+    delete_blob: DeleteBlobOp,
+    wait: bool,
+    ordering: Option<WriteOrdering>,
+) -> DeleteBlobPointInternal {
+    DeleteBlobPointInternal {
+        shard_id,
+        clock_tag: clock_tag.map(Into::into),
+        delete_blob_point: Some(DeleteBlobPoint {
+            collection_name,
+            wait: Some(wait),
+            id: Some(delete_blob.point_id.into()),
+            ordering: ordering.map(write_ordering_to_proto),
+            shard_key_selector: None,
+        }),
+    }
+}
+
 pub fn try_scored_point_from_grpc(
     point: api::grpc::qdrant::ScoredPoint,
     with_payload: bool,
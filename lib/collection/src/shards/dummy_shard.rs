@@ -3,6 +3,7 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
+use segment::blob_storage::BlobChunk;
 use segment::data_types::order_by::OrderBy;
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
@@ -48,6 +49,8 @@ impl DummyShard {
             variant_name: Some("dummy shard".into()),
             segments: vec![],
             optimizations: Default::default(),
+            tiering: None,
+            wal_archive: None,
         }
     }
 
@@ -102,6 +105,15 @@ impl ShardOperation for DummyShard {
         self.dummy()
     }
 
+    async fn read_blob(
+        &self,
+        _: ExtendedPointId,
+        _: usize,
+        _: usize,
+    ) -> CollectionResult<Option<BlobChunk>> {
+        self.dummy()
+    }
+
     async fn query(
         &self,
         _: Arc<ShardQueryRequest>,
@@ -4,6 +4,7 @@ use std::time::Duration;
 
 use async_trait::async_trait;
 use common::types::TelemetryDetail;
+use segment::blob_storage::{BlobChunk, MAX_BLOB_SIZE};
 use segment::data_types::order_by::OrderBy;
 use segment::types::{
     ExtendedPointId, Filter, PointIdType, ScoredPoint, WithPayload, WithPayloadInterface,
@@ -13,16 +14,22 @@ use tokio::runtime::Handle;
 use tokio::sync::Mutex;
 
 use super::update_tracker::UpdateTracker;
+use crate::collection_manager::optimizers::TrackerTelemetry;
 use crate::hash_ring::HashRing;
+use crate::operations::blob_ops::{BlobOperations, SetBlobOp};
 use crate::operations::point_ops::{PointOperations, PointStruct, PointSyncOperation};
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch,
-    CountRequestInternal, CountResult, PointRequestInternal, Record, UpdateResult, UpdateStatus,
+    CountRequestInternal, CountResult, LocalShardPointsBreakdown, PointRequestInternal, Record,
+    SegmentIndexInfo, UpdateResult, UpdateStatus,
 };
 use crate::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
 use crate::operations::{
     CollectionUpdateOperations, CreateIndex, FieldIndexOperations, OperationWithClockTag,
 };
+use crate::shards::local_shard::force_optimize::ForcedOptimization;
+use crate::shards::local_shard::memory_spill::MemorySpill;
+use crate::shards::local_shard::vacuum::Vacuum;
 use crate::shards::local_shard::LocalShard;
 use crate::shards::remote_shard::RemoteShard;
 use crate::shards::shard_trait::ShardOperation;
@@ -124,6 +131,7 @@ impl ForwardProxyShard {
             .collect();
 
         let points = points?;
+        let point_ids: Vec<_> = points.iter().map(|point| point.id).collect();
 
         // Use sync API to leverage potentially existing points
         let insert_points_operation = {
@@ -144,6 +152,25 @@ impl ForwardProxyShard {
             .update(OperationWithClockTag::from(insert_points_operation), wait) // TODO: Assign clock tag!? 🤔
             .await?;
 
+        // Blobs are not part of the point structure, transfer them one by one
+        for point_id in point_ids {
+            let Some(blob) = self
+                .wrapped_shard
+                .read_blob(point_id, 0, MAX_BLOB_SIZE)
+                .await?
+            else {
+                continue;
+            };
+            let set_blob_operation =
+                CollectionUpdateOperations::BlobOperation(BlobOperations::SetBlob(SetBlobOp {
+                    point_id,
+                    blob: blob.data,
+                }));
+            self.remote_shard
+                .update(OperationWithClockTag::from(set_blob_operation), wait)
+                .await?;
+        }
+
         Ok(next_page_offset)
     }
 
@@ -171,6 +198,30 @@ impl ForwardProxyShard {
         self.wrapped_shard.get_telemetry_data(detail)
     }
 
+    pub fn points_breakdown(&self) -> LocalShardPointsBreakdown {
+        self.wrapped_shard.points_breakdown()
+    }
+
+    pub async fn index_info(&self) -> CollectionResult<Vec<SegmentIndexInfo>> {
+        self.wrapped_shard.index_info().await
+    }
+
+    pub fn optimizations_log(&self) -> Vec<TrackerTelemetry> {
+        self.wrapped_shard.optimizations_log()
+    }
+
+    pub async fn forced_optimization(&self) -> ForcedOptimization {
+        self.wrapped_shard.forced_optimization().await
+    }
+
+    pub async fn vacuum(&self, deleted_threshold: Option<f64>) -> Vacuum {
+        self.wrapped_shard.vacuum(deleted_threshold).await
+    }
+
+    pub async fn memory_spill(&self) -> MemorySpill {
+        self.wrapped_shard.memory_spill().await
+    }
+
     pub fn update_tracker(&self) -> &UpdateTracker {
         self.wrapped_shard.update_tracker()
     }
@@ -283,6 +334,16 @@ impl ShardOperation for ForwardProxyShard {
             .await
     }
 
+    async fn read_blob(
+        &self,
+        point_id: ExtendedPointId,
+        offset: usize,
+        limit: usize,
+    ) -> CollectionResult<Option<BlobChunk>> {
+        let local_shard = &self.wrapped_shard;
+        local_shard.read_blob(point_id, offset, limit).await
+    }
+
     async fn query(
         &self,
         request: Arc<ShardQueryRequest>,
@@ -0,0 +1,226 @@
+use std::sync::Arc;
+
+use parking_lot::Mutex as ParkingMutex;
+use segment::entry::entry_point::SegmentEntry as _;
+use segment::index::hnsw_index::num_rayon_threads;
+use segment::types::SegmentType;
+use tokio::sync::{oneshot, Mutex};
+
+use crate::collection_manager::holders::segment_holder::{
+    LockedSegment, LockedSegmentHolder, SegmentId,
+};
+use crate::operations::types::{
+    CollectionError, CollectionResult, ForceOptimizationProgress, SegmentOptimizationProgress,
+    SegmentOptimizationStatus,
+};
+use crate::optimizers_builder::build_forced_optimizer;
+use crate::shards::local_shard::LocalShard;
+use crate::shards::shard::ShardId;
+use crate::update_handler::{Optimizer, UpdateHandler};
+
+/// Optimization of the segments of a local shard, requested by the user.
+///
+/// Does not borrow the shard, so that the shard is not locked while segments are optimized.
+pub struct ForcedOptimization {
+    /// `None` if vector index is disabled
+    optimizer: Option<Arc<Optimizer>>,
+    update_handler: Arc<Mutex<UpdateHandler>>,
+    /// Segments to optimize, with their number of points
+    segments: Vec<(SegmentId, usize)>,
+}
+
+impl LocalShard {
+    /// Plan optimization of all segments, which are not indexed yet, regardless of optimizer
+    /// thresholds.
+    pub async fn forced_optimization(&self) -> ForcedOptimization {
+        let config = self.collection_config.read().await;
+        let optimizer = build_forced_optimizer(
+            &self.path,
+            &config.params,
+            &config.optimizer_config,
+            &config.hnsw_config,
+            &config.quantization_config,
+            config.defragment_key(),
+            self.storage_cipher.as_ref(),
+        );
+        drop(config);
+
+        let segments = match &optimizer {
+            Some(_) => unindexed_segments(&self.segments),
+            // Nothing to index if vector index is disabled
+            None => vec![],
+        };
+
+        ForcedOptimization {
+            optimizer,
+            update_handler: self.update_handler.clone(),
+            segments,
+        }
+    }
+}
+
+/// Segments with points, which are appendable or have vectors without index, largest first
+fn unindexed_segments(segments: &LockedSegmentHolder) -> Vec<(SegmentId, usize)> {
+    let mut unindexed: Vec<_> = segments
+        .read()
+        .iter()
+        .filter_map(|(segment_id, segment)| {
+            // Segments under optimization are already being rebuilt
+            let LockedSegment::Original(segment) = segment else {
+                return None;
+            };
+            let segment = segment.read();
+            let points_count = segment.available_point_count();
+
+            let is_unindexed = segment.segment_type() != SegmentType::Special
+                && (segment.is_appendable() || !segment.config().are_all_vectors_indexed());
+            (is_unindexed && points_count > 0).then_some((*segment_id, points_count))
+        })
+        .collect();
+    unindexed.sort_by(|a, b| b.1.cmp(&a.1));
+    unindexed
+}
+
+impl ForcedOptimization {
+    /// Segments, which are going to be optimized
+    pub fn progress(
+        &self,
+        shard_id: ShardId,
+    ) -> impl Iterator<Item = SegmentOptimizationProgress> + '_ {
+        self.segments.iter().map(
+            move |&(segment_id, points_count)| SegmentOptimizationProgress {
+                shard_id,
+                segment_id,
+                points_count,
+                status: SegmentOptimizationStatus::Pending,
+                error: None,
+            },
+        )
+    }
+
+    /// Optimize segments one by one, once CPU budget is available, and report their status to
+    /// `progress`.
+    pub async fn run(self, shard_id: ShardId, progress: &ParkingMutex<ForceOptimizationProgress>) {
+        let Some(optimizer) = self.optimizer else {
+            return;
+        };
+        let mut segments = self.segments.into_iter();
+        for (segment_id, _) in segments.by_ref() {
+            let result = optimize_segment(&self.update_handler, &optimizer, segment_id, || {
+                progress.lock().update(
+                    shard_id,
+                    segment_id,
+                    SegmentOptimizationStatus::Optimizing,
+                    None,
+                );
+            })
+            .await;
+
+            let (status, error) = match result {
+                Ok(true) => (SegmentOptimizationStatus::Done, None),
+                Ok(false) => (SegmentOptimizationStatus::Skipped, None),
+                Err(CollectionError::Cancelled { .. }) => {
+                    (SegmentOptimizationStatus::Cancelled, None)
+                }
+                Err(error) => (SegmentOptimizationStatus::Failed, Some(error.to_string())),
+            };
+            progress.lock().update(shard_id, segment_id, status, error);
+
+            if status == SegmentOptimizationStatus::Cancelled {
+                break;
+            }
+        }
+
+        // Remaining segments are not optimized, if the optimization was cancelled
+        for (segment_id, _) in segments {
+            progress.lock().update(
+                shard_id,
+                segment_id,
+                SegmentOptimizationStatus::Cancelled,
+                None,
+            );
+        }
+    }
+}
+
+/// Optimize a single segment with `optimizer`, once CPU budget is available.
+///
+/// `on_start` is called when the optimization is launched. Returns whether the segment was
+/// optimized, or `false` if it was optimized by other optimizers in the meantime.
+pub(super) async fn optimize_segment(
+    update_handler: &Mutex<UpdateHandler>,
+    optimizer: &Arc<Optimizer>,
+    segment_id: SegmentId,
+    on_start: impl FnOnce(),
+) -> CollectionResult<bool> {
+    let desired_cpus = num_rayon_threads(optimizer.hnsw_config().max_indexing_threads);
+    let cpu_budget = update_handler.lock().await.optimizer_cpu_budget().clone();
+    let permit = loop {
+        cpu_budget.notify_on_budget_available(desired_cpus).await;
+        if let Some(permit) = cpu_budget.try_acquire(desired_cpus) {
+            break permit;
+        }
+    };
+
+    on_start();
+
+    let (sender, receiver) = oneshot::channel();
+    update_handler
+        .lock()
+        .await
+        .launch_forced_optimization(optimizer.clone(), segment_id, permit, sender)
+        .await;
+
+    // Sender is dropped, if the task is stopped before it started
+    receiver.await.unwrap_or_else(|_| {
+        Err(CollectionError::Cancelled {
+            description: "optimization task is stopped".to_string(),
+        })
+    })
+}
+
+/// Optimize segments of the planned shards one by one.
+///
+/// Status of each segment is reported to `progress` as soon as it changes.
+pub async fn run_forced_optimizations(
+    optimizations: Vec<(ShardId, ForcedOptimization)>,
+    progress: &ParkingMutex<ForceOptimizationProgress>,
+) -> ForceOptimizationProgress {
+    progress.lock().segments = optimizations
+        .iter()
+        .flat_map(|(shard_id, optimization)| optimization.progress(*shard_id))
+        .collect();
+
+    for (shard_id, optimization) in optimizations {
+        optimization.run(shard_id, progress).await;
+    }
+
+    progress.lock().clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use parking_lot::RwLock;
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::collection_manager::fixtures::{empty_segment, random_segment};
+    use crate::collection_manager::holders::segment_holder::SegmentHolder;
+
+    #[test]
+    fn test_unindexed_segments_largest_first() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+        let mut holder = SegmentHolder::default();
+        let _empty = holder.add(empty_segment(dir.path()));
+        let small = holder.add(random_segment(dir.path(), 100, 10, 4));
+        let large = holder.add(random_segment(dir.path(), 100, 50, 4));
+        let segments = Arc::new(RwLock::new(holder));
+
+        // Empty segment has nothing to index
+        assert_eq!(
+            unindexed_segments(&segments),
+            vec![(large, 50), (small, 10)],
+        );
+    }
+}
@@ -0,0 +1,191 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use common::types::{DetailsLevel, TelemetryDetail};
+use segment::entry::entry_point::SegmentEntry as _;
+use segment::telemetry::SegmentTelemetry;
+use segment::types::SegmentType;
+use tokio::sync::Mutex;
+
+use crate::collection_manager::holders::segment_holder::{
+    LockedSegment, LockedSegmentHolder, SegmentId,
+};
+use crate::config::CollectionParams;
+use crate::operations::types::CollectionResult;
+use crate::optimizers_builder::build_spill_optimizer;
+use crate::shards::local_shard::force_optimize::optimize_segment;
+use crate::shards::local_shard::LocalShard;
+use crate::update_handler::{Optimizer, UpdateHandler};
+
+/// Segment with vectors in RAM, which can be moved to disk
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpillCandidate {
+    pub segment_id: SegmentId,
+    /// Estimated size of vectors and indexes of the segment in RAM
+    pub ram_usage_bytes: usize,
+    /// Last time the segment was searched, `None` if it was not searched since it was loaded
+    pub last_searched: Option<DateTime<Utc>>,
+}
+
+/// Moving vectors of the segments of a local shard to disk, to keep RAM usage of the node within
+/// its memory budget.
+///
+/// Does not borrow the shard, so that the shard is not locked while segments are rebuilt.
+pub struct MemorySpill {
+    optimizer: Arc<Optimizer>,
+    update_handler: Arc<Mutex<UpdateHandler>>,
+    /// Estimated size of vectors and indexes of all segments of the shard in RAM
+    pub ram_usage_bytes: usize,
+    /// Segments, which vectors can be moved to disk
+    pub candidates: Vec<SpillCandidate>,
+}
+
+impl LocalShard {
+    /// Plan moving vectors of segments to disk, with the RAM usage of the shard
+    pub async fn memory_spill(&self) -> MemorySpill {
+        let config = self.collection_config.read().await;
+        let optimizer = build_spill_optimizer(
+            &self.path,
+            &config.params,
+            &config.optimizer_config,
+            &config.hnsw_config,
+            &config.quantization_config,
+            config.defragment_key(),
+            self.storage_cipher.as_ref(),
+        );
+        let (ram_usage_bytes, mut candidates) =
+            segments_memory_usage(&self.segments, &config.params);
+        drop(config);
+
+        // Storages on disk are not encrypted, vectors of encrypted shards are kept in RAM
+        if self.storage_cipher.is_some() {
+            candidates.clear();
+        }
+
+        MemorySpill {
+            optimizer,
+            update_handler: self.update_handler.clone(),
+            ram_usage_bytes,
+            candidates,
+        }
+    }
+}
+
+/// RAM usage of all segments, and segments which vectors can be moved to disk.
+///
+/// Appendable segments are never moved to disk, as well as vectors explicitly configured to be
+/// kept in RAM.
+fn segments_memory_usage(
+    segments: &LockedSegmentHolder,
+    collection_params: &CollectionParams,
+) -> (usize, Vec<SpillCandidate>) {
+    let detail = TelemetryDetail {
+        level: DetailsLevel::Level0,
+        histograms: false,
+    };
+
+    let mut ram_usage_bytes = 0;
+    let mut candidates = Vec::new();
+    for (segment_id, segment) in segments.read().iter() {
+        let telemetry = segment.get().read().get_telemetry_data(detail);
+        ram_usage_bytes += telemetry.info.ram_usage_bytes;
+
+        // Segments under optimization are already being rebuilt
+        let LockedSegment::Original(_) = segment else {
+            continue;
+        };
+        if telemetry.info.segment_type == SegmentType::Special || telemetry.info.is_appendable {
+            continue;
+        }
+
+        let has_vectors_to_spill =
+            telemetry
+                .config
+                .vector_data
+                .iter()
+                .any(|(vector_name, vector_config)| {
+                    let on_disk = collection_params
+                        .vectors
+                        .get_params(vector_name)
+                        .and_then(|params| params.on_disk);
+                    !vector_config.storage_type.is_on_disk() && on_disk != Some(false)
+                });
+        if has_vectors_to_spill {
+            candidates.push(SpillCandidate {
+                segment_id: *segment_id,
+                ram_usage_bytes: telemetry.info.ram_usage_bytes,
+                last_searched: last_searched(&telemetry),
+            });
+        }
+    }
+    (ram_usage_bytes, candidates)
+}
+
+fn last_searched(telemetry: &SegmentTelemetry) -> Option<DateTime<Utc>> {
+    telemetry
+        .vector_index_searches
+        .iter()
+        .flat_map(|searches| {
+            [
+                &searches.unfiltered_plain,
+                &searches.unfiltered_hnsw,
+                &searches.unfiltered_sparse,
+                &searches.filtered_plain,
+                &searches.filtered_small_cardinality,
+                &searches.filtered_large_cardinality,
+                &searches.filtered_exact,
+                &searches.filtered_sparse,
+                &searches.unfiltered_exact,
+            ]
+        })
+        .filter_map(|statistics| statistics.last_responded)
+        .max()
+}
+
+impl MemorySpill {
+    /// Move vectors of the given segments to disk one by one, once CPU budget is available.
+    ///
+    /// Returns number of segments moved to disk.
+    pub async fn run(self, segment_ids: &[SegmentId]) -> CollectionResult<usize> {
+        let mut spilled = 0;
+        for &segment_id in segment_ids {
+            let optimized =
+                optimize_segment(&self.update_handler, &self.optimizer, segment_id, || {}).await?;
+            // Segment may be optimized by the optimizers of the collection in the meantime
+            if optimized {
+                spilled += 1;
+            }
+        }
+        Ok(spilled)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parking_lot::RwLock;
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::collection_manager::fixtures::{build_segment_1, empty_segment};
+    use crate::collection_manager::holders::segment_holder::SegmentHolder;
+
+    #[test]
+    fn test_segments_memory_usage() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+        let mut holder = SegmentHolder::default();
+        let _appendable = holder.add(empty_segment(dir.path()));
+        let mut segment = build_segment_1(dir.path());
+        segment.appendable_flag = false;
+        let non_appendable = holder.add(segment);
+        let segments = Arc::new(RwLock::new(holder));
+
+        let (ram_usage_bytes, candidates) =
+            segments_memory_usage(&segments, &CollectionParams::empty());
+        assert!(ram_usage_bytes > 0);
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].segment_id, non_appendable);
+        assert!(candidates[0].ram_usage_bytes > 0);
+        assert_eq!(candidates[0].last_searched, None);
+    }
+}
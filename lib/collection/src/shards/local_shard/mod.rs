@@ -1,15 +1,24 @@
 pub mod clock_map;
 pub mod disk_usage_watcher;
+pub mod force_optimize;
+pub mod memory_spill;
+pub mod point_in_time;
+mod point_versions;
 pub(super) mod query;
 pub(super) mod scroll;
 pub(super) mod search;
 pub(super) mod shard_ops;
+pub mod tiering;
+mod ttl;
+pub mod vacuum;
+pub mod wal_archive;
+pub mod wal_tail;
 
 use std::collections::{BTreeSet, HashMap};
 use std::mem::size_of;
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
-use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use std::thread;
 use std::time::{Duration, Instant};
@@ -21,36 +30,42 @@ use common::types::TelemetryDetail;
 use indicatif::{ProgressBar, ProgressStyle};
 use itertools::Itertools;
 use parking_lot::{Mutex as ParkingMutex, RwLock};
+use segment::common::encryption::StorageCipher;
 use segment::data_types::vectors::VectorElementType;
 use segment::entry::entry_point::SegmentEntry as _;
 use segment::index::field_index::CardinalityEstimation;
 use segment::segment::Segment;
 use segment::segment_constructor::{build_segment, load_segment};
 use segment::types::{
-    CompressionRatio, Filter, PayloadIndexInfo, PayloadKeyType, PayloadStorageType, PointIdType,
-    QuantizationConfig, SegmentConfig, SegmentType,
+    CompressionRatio, Filter, PayloadIndexInfo, PayloadKeyType, PointIdType, QuantizationConfig,
+    SegmentConfig, SegmentType, SparseVectorStats,
 };
 use segment::utils::mem::Mem;
 use tokio::fs::{copy, create_dir_all, remove_dir_all, remove_file};
 use tokio::runtime::Handle;
 use tokio::sync::mpsc::Sender;
 use tokio::sync::{mpsc, oneshot, Mutex, RwLock as TokioRwLock};
+use tokio_util::sync::CancellationToken;
 use wal::{Wal, WalOptions};
 
 use self::clock_map::{ClockMap, RecoveryPoint};
 use self::disk_usage_watcher::DiskUsageWatcher;
+use self::tiering::{HotGuard, HydrationScope, SegmentTiering};
+use self::wal_archive::WalArchiver;
 use super::update_tracker::UpdateTracker;
 use crate::collection_manager::collection_updater::CollectionUpdater;
 use crate::collection_manager::holders::segment_holder::{
     LockedSegment, LockedSegmentHolder, SegmentHolder,
 };
-use crate::collection_manager::optimizers::TrackerLog;
+use crate::collection_manager::optimizers::{TrackerLog, TrackerTelemetry};
 use crate::common::file_utils::{move_dir, move_file};
+use crate::common::storage_encryption;
 use crate::config::CollectionConfig;
 use crate::operations::shared_storage_config::SharedStorageConfig;
 use crate::operations::types::{
     check_sparse_compatible_with_segment_config, CollectionError, CollectionInfoInternal,
-    CollectionResult, CollectionStatus, OptimizersStatus,
+    CollectionResult, CollectionStatus, LocalShardPointsBreakdown, OptimizersStatus,
+    SegmentIndexInfo, SegmentPointsBreakdown,
 };
 use crate::operations::OperationWithClockTag;
 use crate::optimizers_builder::{build_optimizers, clear_temp_segments};
@@ -76,13 +91,26 @@ pub struct LocalShard {
     pub(super) shared_storage_config: Arc<SharedStorageConfig>,
     pub(super) wal: RecoverableWal,
     pub(super) update_handler: Arc<Mutex<UpdateHandler>>,
-    pub(super) update_sender: ArcSwap<Sender<UpdateSignal>>,
+    pub(super) update_sender: Arc<ArcSwap<Sender<UpdateSignal>>>,
     pub(super) update_tracker: UpdateTracker,
     pub(super) path: PathBuf,
     pub(super) optimizers: Arc<Vec<Arc<Optimizer>>>,
     pub(super) optimizers_log: Arc<ParkingMutex<TrackerLog>>,
     update_runtime: Handle,
     disk_usage_watcher: DiskUsageWatcher,
+    pub(super) tiering: Arc<SegmentTiering>,
+    /// Whether any point of the shard has an expiration moment.
+    /// If so, expired points are excluded from all requests.
+    pub(super) has_expiring_points: Arc<AtomicBool>,
+    /// Ships closed WAL segments to the archive, if enabled
+    wal_archiver: Option<Arc<WalArchiver>>,
+    /// WAL operations at or after this index are kept until they are replicated asynchronously,
+    /// `u64::MAX` if not pinned
+    replication_pin: Arc<AtomicU64>,
+    /// Stops background workers of the shard: tiering and WAL archiving
+    workers_cancel: CancellationToken,
+    /// Key of the collection, new segments of the shard are encrypted with
+    pub(super) storage_cipher: Option<Arc<StorageCipher>>,
 }
 
 /// Shard holds information about segments and WAL.
@@ -126,6 +154,9 @@ impl LocalShard {
 
         LocalShardClocks::delete_data(shard_path).await?;
 
+        // New WAL starts from scratch, so should its archive
+        WalArchiver::delete_manifest(shard_path).await?;
+
         Ok(())
     }
 
@@ -140,9 +171,19 @@ impl LocalShard {
         shard_path: &Path,
         clocks: LocalShardClocks,
         update_runtime: Handle,
+        tiering: SegmentTiering,
+        wal_archiver: Option<WalArchiver>,
     ) -> Self {
+        let storage_cipher = segment_holder.storage_cipher.clone();
         let segment_holder = Arc::new(RwLock::new(segment_holder));
         let config = collection_config.read().await;
+        // Closed WAL segments must not be removed until they are archived or replicated
+        let mut wal = wal;
+        if let Some(wal_archiver) = &wal_archiver {
+            wal = wal.with_truncation_limit(wal_archiver.truncation_limit());
+        }
+        let replication_pin = Arc::new(AtomicU64::new(u64::MAX));
+        let wal = wal.with_truncation_limit(replication_pin.clone());
         let locked_wal = Arc::new(ParkingMutex::new(wal));
         let optimizers_log = Arc::new(ParkingMutex::new(Default::default()));
 
@@ -178,19 +219,47 @@ impl LocalShard {
 
         drop(config); // release `shared_config` from borrow checker
 
+        let update_sender = Arc::new(ArcSwap::from_pointee(update_sender));
+
+        let has_expiring_points = Arc::new(AtomicBool::new(ttl::has_expiry_index(&segment_holder)));
+
+        let tiering = Arc::new(tiering);
+        let workers_cancel = CancellationToken::new();
+        update_runtime.spawn(tiering::tiering_worker(
+            tiering.clone(),
+            segment_holder.clone(),
+            update_sender.clone(),
+            collection_config.clone(),
+            workers_cancel.clone(),
+        ));
+
+        let wal_archiver = wal_archiver.map(Arc::new);
+        if let Some(wal_archiver) = &wal_archiver {
+            update_runtime.spawn(wal_archive::wal_archive_worker(
+                wal_archiver.clone(),
+                workers_cancel.clone(),
+            ));
+        }
+
         Self {
             segments: segment_holder,
             collection_config,
             shared_storage_config,
             wal: RecoverableWal::new(locked_wal, clocks.newest_clocks, clocks.oldest_clocks),
             update_handler: Arc::new(Mutex::new(update_handler)),
-            update_sender: ArcSwap::from_pointee(update_sender),
+            update_sender,
             update_tracker,
             path: shard_path.to_owned(),
             update_runtime,
             optimizers,
             optimizers_log,
             disk_usage_watcher,
+            tiering,
+            has_expiring_points,
+            wal_archiver,
+            replication_pin,
+            workers_cancel,
+            storage_cipher,
         }
     }
 
@@ -198,6 +267,32 @@ impl LocalShard {
         self.segments.deref()
     }
 
+    /// Bring back segments, required to serve a request with the given scope, from the cold tier,
+    /// and register access to them
+    ///
+    /// Returned guard must be held while the request is using segments of the shard.
+    pub(super) async fn ensure_hot(
+        &self,
+        scope: HydrationScope<'_>,
+    ) -> CollectionResult<HotGuard<'_>> {
+        loop {
+            let hot = self.tiering.hot().await;
+            if !self.tiering.needs_hydration(scope).await {
+                self.tiering.touch(&self.segments, scope);
+                return Ok(hot);
+            }
+            drop(hot);
+
+            let hydrated = self.tiering.hydrate(&self.segments, scope).await?;
+            if hydrated > 0 {
+                log::debug!(
+                    "Restored {hydrated} segments of shard {} from the cold tier",
+                    self.path.display(),
+                );
+            }
+        }
+    }
+
     /// Recovers shard from disk.
     pub async fn load(
         id: ShardId,
@@ -213,11 +308,29 @@ impl LocalShard {
         let wal_path = Self::wal_path(shard_path);
         let segments_path = Self::segments_path(shard_path);
 
+        let storage_cipher = shared_storage_config
+            .storage_encryption_config
+            .cipher_for(&collection_id)?;
+        if storage_cipher.is_some() {
+            storage_encryption::check_supported(
+                &collection_config_read.params,
+                &collection_config_read.optimizer_config,
+                collection_config_read.quantization_config.as_ref(),
+            )?;
+        }
+
         let wal: SerdeWal<OperationWithClockTag> = SerdeWal::new(
             wal_path.to_str().unwrap(),
             (&collection_config_read.wal_config).into(),
         )
-        .map_err(|e| CollectionError::service_error(format!("Wal error: {e}")))?;
+        .map_err(|e| CollectionError::service_error(format!("Wal error: {e}")))?
+        .with_compression(collection_config_read.wal_config.wal_compression)
+        .with_durability(collection_config_read.wal_config.wal_durability)
+        .with_cipher(
+            shared_storage_config
+                .wal_encryption_config
+                .cipher_for(&collection_id)?,
+        );
 
         let segment_dirs = std::fs::read_dir(&segments_path).map_err(|err| {
             CollectionError::service_error(format!(
@@ -235,13 +348,15 @@ impl LocalShard {
 
         for entry in segment_dirs {
             let segments_path = entry.unwrap().path();
+            let storage_cipher = storage_cipher.clone();
             // let semaphore_clone = semaphore.clone();
             load_handlers.push(
                 thread::Builder::new()
                     .name(format!("shard-load-{collection_id}-{id}"))
                     .spawn(move || {
                         // let _guard = semaphore_clone.lock();
-                        let mut res = load_segment(&segments_path, &AtomicBool::new(false))?;
+                        let mut res =
+                            load_segment(&segments_path, storage_cipher, &AtomicBool::new(false))?;
                         if let Some(segment) = &mut res {
                             segment.check_consistency_and_repair()?;
                         } else {
@@ -259,6 +374,7 @@ impl LocalShard {
         }
 
         let mut segment_holder = SegmentHolder::default();
+        segment_holder.storage_cipher = storage_cipher.clone();
 
         for handler in load_handlers {
             let segment = handler.join().map_err(|err| {
@@ -304,12 +420,22 @@ impl LocalShard {
             &collection_config_read.optimizer_config,
             &collection_config_read.hnsw_config,
             &collection_config_read.quantization_config,
+            collection_config_read.defragment_key(),
+            storage_cipher.as_ref(),
         );
 
         drop(collection_config_read); // release `shared_config` from borrow checker
 
         let clocks = LocalShardClocks::load(shard_path)?;
 
+        let tiering = SegmentTiering::load(
+            shard_path,
+            shared_storage_config.segment_storage_config.clone(),
+        )?;
+
+        let wal_archiver =
+            WalArchiver::load(shard_path, shared_storage_config.wal_archive_config.clone())?;
+
         // Always make sure we have any appendable segments, needed for update operations
         if !segment_holder.has_appendable_segment() {
             debug_assert!(
@@ -332,6 +458,8 @@ impl LocalShard {
             shard_path,
             clocks,
             update_runtime,
+            tiering,
+            wal_archiver,
         )
         .await;
 
@@ -426,10 +554,24 @@ impl LocalShard {
             ))
         })?;
 
+        let storage_cipher = shared_storage_config
+            .storage_encryption_config
+            .cipher_for(&collection_id)?;
+        if storage_cipher.is_some() {
+            storage_encryption::check_supported(
+                &config.params,
+                &config.optimizer_config,
+                config.quantization_config.as_ref(),
+            )?;
+        }
+
         let mut segment_holder = SegmentHolder::default();
+        segment_holder.storage_cipher = storage_cipher.clone();
         let mut build_handlers = vec![];
 
-        let vector_params = config.params.to_base_vector_data()?;
+        let vector_params = config
+            .params
+            .to_base_vector_data(config.quantization_config.as_ref())?;
         let sparse_vector_params = config.params.to_sparse_vector_data()?;
         let segment_number = config.optimizer_config.get_number_segments();
 
@@ -438,11 +580,9 @@ impl LocalShard {
             let segment_config = SegmentConfig {
                 vector_data: vector_params.clone(),
                 sparse_vector_data: sparse_vector_params.clone(),
-                payload_storage_type: if config.params.on_disk_payload {
-                    PayloadStorageType::OnDisk
-                } else {
-                    PayloadStorageType::InMemory
-                },
+                payload_storage_type: config.params.payload_storage_type(),
+                payload_columns: config.params.payload_columns.clone().unwrap_or_default(),
+                storage_cipher: storage_cipher.clone(),
             };
             let segment = thread::Builder::new()
                 .name(format!("shard-build-{collection_id}-{id}"))
@@ -470,7 +610,14 @@ impl LocalShard {
         }
 
         let wal: SerdeWal<OperationWithClockTag> =
-            SerdeWal::new(wal_path.to_str().unwrap(), (&config.wal_config).into())?;
+            SerdeWal::new(wal_path.to_str().unwrap(), (&config.wal_config).into())?
+                .with_compression(config.wal_config.wal_compression)
+                .with_durability(config.wal_config.wal_durability)
+                .with_cipher(
+                    shared_storage_config
+                        .wal_encryption_config
+                        .cipher_for(&collection_id)?,
+                );
 
         let optimizers = build_optimizers(
             shard_path,
@@ -478,10 +625,20 @@ impl LocalShard {
             &config.optimizer_config,
             &config.hnsw_config,
             &config.quantization_config,
+            config.defragment_key(),
+            storage_cipher.as_ref(),
         );
 
         drop(config); // release `shared_config` from borrow checker
 
+        let tiering = SegmentTiering::load(
+            shard_path,
+            shared_storage_config.segment_storage_config.clone(),
+        )?;
+
+        let wal_archiver =
+            WalArchiver::load(shard_path, shared_storage_config.wal_archive_config.clone())?;
+
         let collection = LocalShard::new(
             segment_holder,
             collection_config,
@@ -492,6 +649,8 @@ impl LocalShard {
             shard_path,
             LocalShardClocks::default(),
             update_runtime,
+            tiering,
+            wal_archiver,
         )
         .await;
 
@@ -545,7 +704,16 @@ impl LocalShard {
         // (`SerdeWal::read_all` may even start reading WAL from some already truncated
         // index *occasionally*), but the storage can handle it.
 
-        for (op_num, update) in wal.read_all(false) {
+        for record in wal.read_all(false) {
+            // An unreadable entry, e.g. because of a wrong encryption key, fails the shard load
+            let (op_num, update) = record.map_err(|err| {
+                CollectionError::service_error(format!(
+                    "Can't load WAL of collection {collection_id}, shard {}: {err}. \
+                     Check that the WAL encryption key matches the one the WAL was written with",
+                    self.path.display(),
+                ))
+            })?;
+
             if let Some(clock_tag) = update.clock_tag {
                 newest_clocks.advance_clock(clock_tag);
             }
@@ -609,6 +777,11 @@ impl LocalShard {
             segments.flush_all(true)?;
         }
 
+        // Operations from WAL might have introduced expiring points
+        if ttl::has_expiry_index(&self.segments) {
+            self.has_expiring_points.store(true, Ordering::Relaxed);
+        }
+
         bar.finish();
         if !show_progress_bar {
             log::info!(
@@ -638,6 +811,8 @@ impl LocalShard {
             &config.optimizer_config,
             &config.hnsw_config,
             &config.quantization_config,
+            config.defragment_key(),
+            self.storage_cipher.as_ref(),
         );
         update_handler.optimizers = new_optimizers;
         update_handler.flush_interval_sec = config.optimizer_config.flush_interval_sec;
@@ -650,6 +825,8 @@ impl LocalShard {
 
     /// Finishes ongoing update tasks
     pub async fn stop_gracefully(&self) {
+        self.workers_cancel.cancel();
+
         if let Err(err) = self.update_sender.load().send(UpdateSignal::Stop).await {
             log::warn!("Error sending stop signal to update handler: {}", err);
         }
@@ -693,6 +870,9 @@ impl LocalShard {
     ) -> CollectionResult<()> {
         let snapshot_shard_path = target_path;
 
+        // Cold segments must be part of the snapshot
+        let _hot = self.ensure_hot(HydrationScope::All).await?;
+
         // snapshot all shard's segment
         let snapshot_segments_shard_path = snapshot_shard_path.join("segments");
         create_dir_all(&snapshot_segments_shard_path).await?;
@@ -859,9 +1039,76 @@ impl LocalShard {
                 optimizations,
                 log: self.optimizers_log.lock().to_telemetry(),
             },
+            tiering: Some(self.tiering.get_telemetry_data(detail)),
+            wal_archive: self
+                .wal_archiver
+                .as_ref()
+                .map(|archiver| archiver.get_telemetry_data()),
+        }
+    }
+
+    pub fn points_breakdown(&self) -> LocalShardPointsBreakdown {
+        let segments = self
+            .segments
+            .read()
+            .iter()
+            .map(|(_id, segment)| {
+                let segment = segment.get();
+                let segment = segment.read();
+                SegmentPointsBreakdown {
+                    segment_type: segment.segment_type(),
+                    is_appendable: segment.is_appendable(),
+                    points_count: segment.available_point_count(),
+                    deleted_points_count: segment.deleted_point_count(),
+                }
+            })
+            .collect();
+
+        LocalShardPointsBreakdown {
+            segments,
+            cold_points_count: self.tiering.cold_points_count(),
         }
     }
 
+    /// Statistics of vector indexes of the segments, walking HNSW graphs in a blocking task
+    pub async fn index_info(&self) -> CollectionResult<Vec<SegmentIndexInfo>> {
+        let segments: Vec<_> = self
+            .segments
+            .read()
+            .iter()
+            .map(|(_id, segment)| segment.clone())
+            .collect();
+
+        let info = tokio::task::spawn_blocking(move || {
+            segments
+                .iter()
+                .map(|segment| {
+                    let segment = segment.get();
+                    let segment = segment.read();
+                    SegmentIndexInfo {
+                        segment_id: segment
+                            .data_path()
+                            .file_name()
+                            .map(|name| name.to_string_lossy().into_owned())
+                            .unwrap_or_default(),
+                        segment_type: segment.segment_type(),
+                        points_count: segment.available_point_count(),
+                        deleted_points_count: segment.deleted_point_count(),
+                        vectors: segment.vector_index_info(),
+                    }
+                })
+                .collect()
+        })
+        .await?;
+
+        Ok(info)
+    }
+
+    /// Last optimizations of the shard since the start of this peer
+    pub fn optimizations_log(&self) -> Vec<TrackerTelemetry> {
+        self.optimizers_log.lock().to_telemetry()
+    }
+
     /// Returns estimated size of vector data in bytes
     async fn estimate_vector_data_size(&self) -> usize {
         let info = self.local_shard_info().await;
@@ -907,6 +1154,7 @@ impl LocalShard {
         let mut segments_count = 0;
         let mut status = CollectionStatus::Green;
         let mut schema: HashMap<PayloadKeyType, PayloadIndexInfo> = Default::default();
+        let mut sparse_vectors_stats: HashMap<String, SparseVectorStats> = Default::default();
         let mut optimizer_status = OptimizersStatus::Ok;
 
         {
@@ -928,7 +1176,18 @@ impl LocalShard {
                         .and_modify(|entry| entry.points += val.points)
                         .or_insert(val);
                 }
+                for (vector_name, vector_data) in segment_info.vector_data {
+                    if let Some(stats) = vector_data.sparse_stats {
+                        sparse_vectors_stats
+                            .entry(vector_name)
+                            .or_default()
+                            .merge(&stats);
+                    }
+                }
             }
+            // Points of the cold segments are still part of the shard
+            points_count += self.tiering.cold_points_count();
+
             if !segments.failed_operation.is_empty() || segments.optimizer_errors.is_some() {
                 status = CollectionStatus::Red;
             }
@@ -960,6 +1219,7 @@ impl LocalShard {
             segments_count,
             config: collection_config,
             payload_schema: schema,
+            sparse_vectors_stats,
         }
     }
 
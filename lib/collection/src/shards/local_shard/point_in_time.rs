@@ -0,0 +1,280 @@
+//! Point-in-time recovery of a shard from the WAL archive.
+//!
+//! Shard is restored from a snapshot first. Then operations from the archived WAL segments are
+//! appended to the WAL of the restored shard, right after its last entry. Appended operations are
+//! applied on top of the snapshot segments, once the shard is loaded.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use object_store::ObjectStore;
+use wal::WalOptions;
+
+use super::wal_archive::{
+    ArchivedWalSegment, WalArchiveConfig, WalArchiveManifest, WAL_ARCHIVE_MANIFEST_OBJECT,
+};
+use crate::common::sha_256::{hash_file, hashes_equal};
+use crate::common::wal_encryption::WalCipher;
+use crate::operations::snapshot_storage_ops;
+use crate::operations::types::{CollectionError, CollectionResult};
+use crate::operations::OperationWithClockTag;
+use crate::wal::SerdeWal;
+
+/// Report progress after this number of replayed operations
+const PROGRESS_REPORT_INTERVAL: u64 = 1000;
+
+/// State of the shard to recover
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointInTimeTarget {
+    /// Operations written before the given moment.
+    ///
+    /// Granularity is a whole WAL segment: a segment is replayed only if its last write happened
+    /// before the moment, so the shard is restored to the end of the last such segment.
+    /// Operations of the following segment, made before the moment, are not restored.
+    Timestamp(DateTime<Utc>),
+    /// Operations up to the given WAL index, inclusive
+    Operation(u64),
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WalReplayProgress {
+    pub segments_total: usize,
+    pub segments_downloaded: usize,
+    pub operations_replayed: u64,
+}
+
+async fn load_archive_manifest(
+    client: &dyn ObjectStore,
+    key_prefix: &str,
+) -> CollectionResult<WalArchiveManifest> {
+    let manifest_path =
+        object_store::path::Path::from(format!("{key_prefix}/{WAL_ARCHIVE_MANIFEST_OBJECT}"));
+    let data = match client.get(&manifest_path).await {
+        Ok(result) => result.bytes().await,
+        Err(object_store::Error::NotFound { .. }) => {
+            return Err(CollectionError::not_found(format!(
+                "WAL archive {key_prefix}"
+            )))
+        }
+        Err(err) => Err(err),
+    }
+    .map_err(|err| {
+        CollectionError::object_storage_error(format!(
+            "Failed to download WAL archive manifest {manifest_path}: {err}"
+        ))
+    })?;
+    Ok(serde_json::from_slice(&data)?)
+}
+
+/// Archived segments, required to replay operations from `from_index` up to the target
+fn segments_to_replay(
+    manifest: &WalArchiveManifest,
+    from_index: u64,
+    target: PointInTimeTarget,
+) -> CollectionResult<Vec<&ArchivedWalSegment>> {
+    if let PointInTimeTarget::Operation(operation) = target {
+        if operation < from_index {
+            return Err(CollectionError::bad_input(format!(
+                "Snapshot already contains operations after {operation}, \
+                 first operation to replay is {from_index}"
+            )));
+        }
+    }
+
+    let mut segments = Vec::new();
+    for (i, segment) in manifest.segments.iter().enumerate() {
+        let reached = match target {
+            PointInTimeTarget::Timestamp(timestamp) => segment
+                .last_write_time
+                .map_or(true, |last_write_time| last_write_time > timestamp),
+            PointInTimeTarget::Operation(operation) => segment.first_index > operation,
+        };
+        if reached {
+            // Target is resolved to the start of this segment, which the snapshot already passed
+            if segment.first_index < from_index {
+                return Err(CollectionError::bad_input(format!(
+                    "Snapshot already contains operations after the target moment: \
+                     it ends at operation {}, but the moment is resolved to operation {}, \
+                     with a granularity of a WAL segment",
+                    from_index - 1,
+                    segment.first_index.saturating_sub(1),
+                )));
+            }
+            break;
+        }
+
+        // Segment is entirely covered by the snapshot
+        let next_first_index = manifest.segments.get(i + 1).map(|next| next.first_index);
+        if next_first_index.is_some_and(|next| next <= from_index) {
+            continue;
+        }
+
+        segments.push(segment);
+    }
+
+    let covers_snapshot = segments
+        .first()
+        .is_some_and(|segment| segment.first_index <= from_index);
+    let requires_replay = !segments.is_empty() || matches!(target, PointInTimeTarget::Operation(_));
+    if requires_replay && !covers_snapshot {
+        return Err(CollectionError::bad_input(format!(
+            "WAL archive does not contain operation {from_index}, which follows the snapshot"
+        )));
+    }
+
+    Ok(segments)
+}
+
+/// Append archived operations to the WAL at `wal_path`, up to the target.
+///
+/// Segments of the archive are looked up by `key_prefix`, see [`super::wal_archive::shard_archive_prefix`].
+#[allow(clippy::too_many_arguments)]
+pub async fn replay_archived_wal(
+    config: &WalArchiveConfig,
+    key_prefix: &str,
+    wal_path: &Path,
+    wal_options: WalOptions,
+    cipher: Option<Arc<WalCipher>>,
+    target: PointInTimeTarget,
+    temp_path: &Path,
+    mut on_progress: impl FnMut(WalReplayProgress),
+) -> CollectionResult<WalReplayProgress> {
+    let client = config.build_client()?;
+
+    let mut wal: SerdeWal<OperationWithClockTag> =
+        SerdeWal::new(&wal_path.to_string_lossy(), wal_options.clone())?
+            .with_cipher(cipher.clone());
+    let from_index = wal.first_closed_index() + wal.len(true);
+
+    let manifest = load_archive_manifest(client.as_ref(), key_prefix).await?;
+    let segments = segments_to_replay(&manifest, from_index, target)?;
+
+    let mut progress = WalReplayProgress {
+        segments_total: segments.len(),
+        ..Default::default()
+    };
+    on_progress(progress);
+    if segments.is_empty() {
+        return Ok(progress);
+    }
+
+    // Archived segments are downloaded as a separate WAL, to read entries from it
+    let archive_dir = tempfile::Builder::new()
+        .prefix("wal-replay-")
+        .tempdir_in(temp_path)?;
+    for segment in &segments {
+        let segment_path = archive_dir.path().join(&segment.name);
+        snapshot_storage_ops::download_snapshot(
+            client.as_ref(),
+            &Path::new(key_prefix).join(&segment.name),
+            &segment_path,
+        )
+        .await?;
+
+        let checksum = hash_file(&segment_path).await?;
+        if !hashes_equal(&checksum, &segment.checksum) {
+            return Err(CollectionError::service_error(format!(
+                "Checksum mismatch of archived WAL segment {key_prefix}/{}: expected {}, got {checksum}",
+                segment.name, segment.checksum,
+            )));
+        }
+
+        progress.segments_downloaded += 1;
+        on_progress(progress);
+    }
+
+    let archive_wal: SerdeWal<OperationWithClockTag> =
+        SerdeWal::new(&archive_dir.path().to_string_lossy(), wal_options)?.with_cipher(cipher);
+
+    let last_index = match target {
+        PointInTimeTarget::Timestamp(_) => u64::MAX,
+        PointInTimeTarget::Operation(operation) => operation,
+    };
+    for record in archive_wal.read(from_index) {
+        let (index, operation) = record?;
+        if index > last_index {
+            break;
+        }
+        let written = wal.write(&operation)?;
+        if written != index {
+            return Err(CollectionError::service_error(format!(
+                "WAL index mismatch during replay: archived operation {index} written as {written}"
+            )));
+        }
+
+        progress.operations_replayed += 1;
+        if progress.operations_replayed % PROGRESS_REPORT_INTERVAL == 0 {
+            on_progress(progress);
+        }
+    }
+    wal.flush()?;
+
+    if let PointInTimeTarget::Operation(operation) = target {
+        let replayed_until = from_index + progress.operations_replayed;
+        if replayed_until <= operation {
+            return Err(CollectionError::bad_input(format!(
+                "Operation {operation} is not archived yet, last archived operation is {}",
+                replayed_until.saturating_sub(1),
+            )));
+        }
+    }
+
+    on_progress(progress);
+    Ok(progress)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn manifest(segments: &[(u64, i64)]) -> WalArchiveManifest {
+        WalArchiveManifest {
+            segments: segments
+                .iter()
+                .map(|&(first_index, last_write)| ArchivedWalSegment {
+                    name: format!("closed-{first_index}"),
+                    first_index,
+                    size: 0,
+                    checksum: String::new(),
+                    last_write_time: Utc.timestamp_opt(last_write, 0).single(),
+                    archived_at: Utc::now(),
+                })
+                .collect(),
+        }
+    }
+
+    fn first_indices(segments: Vec<&ArchivedWalSegment>) -> Vec<u64> {
+        segments.iter().map(|segment| segment.first_index).collect()
+    }
+
+    #[test]
+    fn test_segments_to_replay() {
+        let manifest = manifest(&[(1, 100), (10, 200), (20, 300), (30, 400)]);
+        let at = |secs| PointInTimeTarget::Timestamp(Utc.timestamp_opt(secs, 0).unwrap());
+
+        let segments = segments_to_replay(&manifest, 15, PointInTimeTarget::Operation(25));
+        assert_eq!(first_indices(segments.unwrap()), [10, 20]);
+
+        let segments = segments_to_replay(&manifest, 15, at(350));
+        assert_eq!(first_indices(segments.unwrap()), [10, 20]);
+
+        // Snapshot is newer than the target moment, resolved to the end of segment 20
+        assert!(segments_to_replay(&manifest, 35, at(350)).is_err());
+        assert!(segments_to_replay(&manifest, 15, at(50)).is_err());
+
+        // Snapshot ends exactly at the resolved moment, nothing to replay
+        let segments = segments_to_replay(&manifest, 30, at(350));
+        assert_eq!(first_indices(segments.unwrap()), Vec::<u64>::new());
+
+        // Snapshot is newer than the target operation
+        assert!(segments_to_replay(&manifest, 15, PointInTimeTarget::Operation(5)).is_err());
+
+        // Archive has a gap after the snapshot
+        let manifest = self::manifest(&[(20, 300), (30, 400)]);
+        assert!(segments_to_replay(&manifest, 15, PointInTimeTarget::Operation(25)).is_err());
+        assert!(segments_to_replay(&manifest, 15, at(350)).is_err());
+    }
+}
@@ -0,0 +1,30 @@
+use segment::types::{PointIdType, SeqNumberType};
+use tokio::sync::oneshot;
+
+use crate::collection_manager::segments_updater::check_point_versions;
+use crate::operations::types::CollectionResult;
+use crate::shards::local_shard::tiering::HydrationScope;
+use crate::shards::local_shard::LocalShard;
+use crate::update_handler::UpdateSignal;
+
+impl LocalShard {
+    /// Check that all given points exist and have the expected version.
+    ///
+    /// All updates submitted so far are applied before the check, so the versions are not stale.
+    pub async fn check_point_versions(
+        &self,
+        ids: &[PointIdType],
+        expected_version: SeqNumberType,
+    ) -> CollectionResult<()> {
+        let _hot = self.ensure_hot(HydrationScope::Points(ids)).await?;
+
+        let (tx, rx) = oneshot::channel();
+        self.update_sender
+            .load()
+            .send(UpdateSignal::Plunger(tx))
+            .await?;
+        rx.await?;
+
+        check_point_versions(&self.segments.read(), ids, expected_version)
+    }
+}
@@ -2,6 +2,7 @@ use std::sync::Arc;
 
 use futures::future::try_join_all;
 use itertools::Itertools as _;
+use segment::common::request_usage::measure as measure_usage;
 use segment::data_types::order_by::{Direction, OrderBy, OrderValue};
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
@@ -11,6 +12,7 @@ use tokio::runtime::Handle;
 use super::LocalShard;
 use crate::collection_manager::holders::segment_holder::LockedSegment;
 use crate::collection_manager::segments_searcher::SegmentsSearcher;
+use crate::common::request_usage;
 use crate::operations::types::{CollectionResult, Record, ScrollRequestInternal};
 
 impl LocalShard {
@@ -120,14 +122,18 @@ impl LocalShard {
 
         let (non_appendable, appendable) = segments.read().split_segments();
 
+        let usage = request_usage::current();
         let read_filtered = |segment: LockedSegment| {
             let filter = filter.cloned();
+            let usage = usage.clone();
 
             search_runtime_handle.spawn_blocking(move || {
-                segment
-                    .get()
-                    .read()
-                    .read_filtered(offset, Some(limit), filter.as_ref())
+                measure_usage(usage.as_deref(), || {
+                    segment
+                        .get()
+                        .read()
+                        .read_filtered(offset, Some(limit), filter.as_ref())
+                })
             })
         };
 
@@ -165,15 +171,20 @@ impl LocalShard {
 
         let (non_appendable, appendable) = segments.read().split_segments();
 
+        let usage = request_usage::current();
         let read_ordered_filtered = |segment: LockedSegment| {
             let filter = filter.cloned();
             let order_by = order_by.clone();
+            let usage = usage.clone();
 
             search_runtime_handle.spawn_blocking(move || {
-                segment
-                    .get()
-                    .read()
-                    .read_ordered_filtered(Some(limit), filter.as_ref(), &order_by)
+                measure_usage(usage.as_deref(), || {
+                    segment.get().read().read_ordered_filtered(
+                        Some(limit),
+                        filter.as_ref(),
+                        &order_by,
+                    )
+                })
             })
         };
 
@@ -6,6 +6,8 @@ use tokio::runtime::Handle;
 
 use super::LocalShard;
 use crate::collection_manager::segments_searcher::SegmentsSearcher;
+use crate::common::request_usage;
+use crate::common::slow_log::current_search_plan;
 use crate::common::stopping_guard::StoppingGuard;
 use crate::operations::query_enum::QueryEnum;
 use crate::operations::types::{CollectionError, CollectionResult, CoreSearchRequestBatch};
@@ -19,7 +21,7 @@ impl LocalShard {
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
         let is_stopped_guard = StoppingGuard::new();
 
-        let (query_context, collection_params) = {
+        let (query_context, distances) = {
             let collection_config = self.collection_config.read().await;
 
             let query_context_opt = SegmentsSearcher::prepare_query_context(
@@ -34,8 +36,21 @@ impl LocalShard {
                 // No segments to search
                 return Ok(vec![]);
             };
+            let query_context = query_context
+                .with_search_plan(current_search_plan())
+                .with_usage(request_usage::current());
 
-            (query_context, collection_config.params.clone())
+            let distances = core_request
+                .searches
+                .iter()
+                .map(|req| {
+                    collection_config
+                        .params
+                        .get_search_distance(req.query.get_vector_name(), req.params.as_ref())
+                })
+                .collect::<CollectionResult<Vec<_>>>()?;
+
+            (query_context, distances)
         };
 
         let search_request = SegmentsSearcher::search(
@@ -59,9 +74,8 @@ impl LocalShard {
         let top_results = res
             .into_iter()
             .zip(core_request.searches.iter())
-            .map(|(vector_res, req)| {
-                let vector_name = req.query.get_vector_name();
-                let distance = collection_params.get_distance(vector_name).unwrap();
+            .zip(distances)
+            .map(|((vector_res, req), distance)| {
                 let processed_res = vector_res.into_iter().map(|mut scored_point| {
                     match req.query {
                         QueryEnum::Nearest(_) => {
@@ -1,7 +1,9 @@
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
+use segment::blob_storage::BlobChunk;
 use segment::data_types::order_by::OrderBy;
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
@@ -10,6 +12,7 @@ use tokio::runtime::Handle;
 use tokio::sync::oneshot;
 
 use crate::collection_manager::segments_searcher::SegmentsSearcher;
+use crate::operations::operation_effect::{EstimateOperationEffectArea, OperationEffectArea};
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch,
     CountRequestInternal, CountResult, PointRequestInternal, Record, UpdateResult, UpdateStatus,
@@ -17,6 +20,7 @@ use crate::operations::types::{
 use crate::operations::universal_query::planned_query::PlannedQuery;
 use crate::operations::universal_query::shard_query::ShardQueryRequest;
 use crate::operations::OperationWithClockTag;
+use crate::shards::local_shard::tiering::HydrationScope;
 use crate::shards::local_shard::LocalShard;
 use crate::shards::shard_trait::ShardOperation;
 use crate::update_handler::{OperationData, UpdateSignal};
@@ -37,6 +41,15 @@ impl ShardOperation for LocalShard {
     ) -> CollectionResult<UpdateResult> {
         // `LocalShard::update` only has a single cancel safe `await`, WAL operations are blocking,
         // and update is applied by a separate task, so, surprisingly, this method is cancel safe. :D
+        //
+        // Hydration of cold segments is done before anything is written, and is resumed by the
+        // next request if cancelled.
+        let effect_area = operation.operation.estimate_effect_area();
+        let scope = match &effect_area {
+            OperationEffectArea::Points(ids) => HydrationScope::Points(ids),
+            OperationEffectArea::Empty | OperationEffectArea::Filter(_) => HydrationScope::All,
+        };
+        let _hot = self.ensure_hot(scope).await?;
 
         let (callback_sender, callback_receiver) = if wait {
             let (tx, rx) = oneshot::channel();
@@ -56,6 +69,10 @@ impl ShardOperation for LocalShard {
             ));
         }
 
+        if operation.operation.has_expiring_points() {
+            self.has_expiring_points.store(true, Ordering::Relaxed);
+        }
+
         let operation_id = {
             let update_sender = self.update_sender.load();
             let channel_permit = update_sender.reserve().await?;
@@ -114,6 +131,11 @@ impl ShardOperation for LocalShard {
         search_runtime_handle: &Handle,
         order_by: Option<&OrderBy>,
     ) -> CollectionResult<Vec<Record>> {
+        let _hot = self.ensure_hot(HydrationScope::All).await?;
+
+        let filter = self.exclude_expired(filter);
+        let filter = filter.as_deref();
+
         match order_by {
             None => {
                 self.scroll_by_id(
@@ -162,16 +184,21 @@ impl ShardOperation for LocalShard {
         search_runtime_handle: &Handle,
         timeout: Option<Duration>,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        let _hot = self.ensure_hot(HydrationScope::All).await?;
+        let request = self.exclude_expired_from_searches(request);
         self.do_search(request, search_runtime_handle, timeout)
             .await
     }
 
     async fn count(&self, request: Arc<CountRequestInternal>) -> CollectionResult<CountResult> {
+        let _hot = self.ensure_hot(HydrationScope::All).await?;
+
+        let filter = self.exclude_expired(request.filter.as_ref());
         let total_count = if request.exact {
-            let all_points = self.read_filtered(request.filter.as_ref())?;
+            let all_points = self.read_filtered(filter.as_deref())?;
             all_points.len()
         } else {
-            self.estimate_cardinality(request.filter.as_ref())?.exp
+            self.estimate_cardinality(filter.as_deref())?.exp
         };
         Ok(CountResult { count: total_count })
     }
@@ -182,7 +209,29 @@ impl ShardOperation for LocalShard {
         with_payload: &WithPayload,
         with_vector: &WithVector,
     ) -> CollectionResult<Vec<Record>> {
-        SegmentsSearcher::retrieve(self.segments(), &request.ids, with_payload, with_vector)
+        let _hot = self
+            .ensure_hot(HydrationScope::Points(&request.ids))
+            .await?;
+        let mut records =
+            SegmentsSearcher::retrieve(self.segments(), &request.ids, with_payload, with_vector)?;
+        self.retain_unexpired(&mut records)?;
+        Ok(records)
+    }
+
+    async fn read_blob(
+        &self,
+        point_id: ExtendedPointId,
+        offset: usize,
+        limit: usize,
+    ) -> CollectionResult<Option<BlobChunk>> {
+        let _hot = self
+            .ensure_hot(HydrationScope::Points(std::slice::from_ref(&point_id)))
+            .await?;
+        let blob = SegmentsSearcher::read_blob(self.segments(), point_id, offset, limit)?;
+        if blob.is_some() && self.is_expired(point_id)? {
+            return Ok(None);
+        }
+        Ok(blob)
     }
 
     async fn query(
@@ -190,11 +239,20 @@ impl ShardOperation for LocalShard {
         request: Arc<ShardQueryRequest>,
         search_runtime_handle: &Handle,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
-        self.do_planned_query(
-            PlannedQuery::try_from(request.as_ref().to_owned())?,
-            search_runtime_handle,
-            None,
-        )
-        .await
+        let _hot = self.ensure_hot(HydrationScope::All).await?;
+
+        let mut planned_query = PlannedQuery::try_from(request.as_ref().to_owned())?;
+        planned_query.searches = self.exclude_expired_from_searches(planned_query.searches);
+        if let Some(exclusion) = self.exclude_expired(None) {
+            for scroll in Arc::make_mut(&mut planned_query.scrolls) {
+                scroll.filter = Some(match scroll.filter.take() {
+                    Some(filter) => filter.merge(&exclusion),
+                    None => exclusion.clone().into_owned(),
+                });
+            }
+        }
+
+        self.do_planned_query(planned_query, search_runtime_handle, None)
+            .await
     }
 }
@@ -0,0 +1,806 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use arc_swap::ArcSwap;
+use common::types::TelemetryDetail;
+use parking_lot::{Mutex as ParkingMutex, RwLock};
+use schemars::JsonSchema;
+use segment::common::anonymize::Anonymize;
+use segment::common::operation_time_statistics::{
+    OperationDurationStatistics, OperationDurationsAggregator, ScopeDurationMeasurer,
+};
+use segment::entry::entry_point::SegmentEntry as _;
+use segment::segment::Segment;
+use segment::segment_constructor::load_segment;
+use segment::types::{PointIdType, SeqNumberType};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{mpsc, oneshot, Mutex, RwLock as TokioRwLock, RwLockReadGuard};
+use tokio_util::sync::CancellationToken;
+
+use crate::collection_manager::holders::segment_holder::{
+    LockedSegment, LockedSegmentHolder, SegmentId,
+};
+use crate::common::file_utils::move_dir;
+use crate::common::segment_storage::{SegmentStorageConfig, SegmentStorageSS};
+use crate::config::CollectionConfig;
+use crate::operations::types::{CollectionError, CollectionResult};
+use crate::shards::local_shard::LocalShard;
+use crate::update_handler::UpdateSignal;
+
+/// File with the list of segments, which are moved to the cold tier
+pub const COLD_SEGMENTS_FILE: &str = "cold_segments.json";
+
+/// Directory with the point ids of each cold segment, one file per segment
+pub const COLD_SEGMENTS_POINTS_DIR: &str = "cold_segments";
+
+/// How often the tiering worker checks for segments, which became cold
+const TIERING_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Segment, which files are kept in the cold tier
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColdSegment {
+    /// Key of the segment in the segment storage
+    pub key: String,
+    /// Name of the segment directory inside of the shard segments directory
+    pub dir_name: String,
+    /// Number of available points at the moment of demotion
+    pub points_count: usize,
+    /// Ids of the points in the segment, stored in [`COLD_SEGMENTS_POINTS_DIR`].
+    /// If unknown, segment is hydrated on any access.
+    #[serde(skip)]
+    pub point_ids: Option<Arc<HashSet<PointIdType>>>,
+}
+
+/// Part of the shard, which has to be hot to serve a request
+#[derive(Debug, Clone, Copy)]
+pub enum HydrationScope<'a> {
+    /// Request may read or change any point, e.g. search, scroll or update by filter
+    All,
+    /// Request reads or changes only the listed points
+    Points(&'a [PointIdType]),
+}
+
+impl HydrationScope<'_> {
+    fn needs(&self, cold_segment: &ColdSegment) -> bool {
+        match self {
+            HydrationScope::All => true,
+            HydrationScope::Points(ids) => match &cold_segment.point_ids {
+                Some(point_ids) => ids.iter().any(|id| point_ids.contains(id)),
+                None => true,
+            },
+        }
+    }
+}
+
+/// Guard, which keeps hot segments of the shard in place while a request is using them
+pub type HotGuard<'a> = RwLockReadGuard<'a, ()>;
+
+#[derive(Serialize, Clone, Debug, JsonSchema, Default)]
+pub struct TieringTelemetry {
+    /// Number of segments, which are currently in the cold tier
+    pub cold_segments: usize,
+    /// Number of points in the cold segments
+    pub cold_points: usize,
+    /// Number of segments moved to the cold tier since start
+    pub demoted_segments: usize,
+    /// Number of segments restored from the cold tier since start
+    pub hydrated_segments: usize,
+    /// Duration of restoring cold segments on access
+    pub hydrations: OperationDurationStatistics,
+}
+
+impl Anonymize for TieringTelemetry {
+    fn anonymize(&self) -> Self {
+        Self {
+            cold_segments: self.cold_segments,
+            cold_points: self.cold_points,
+            demoted_segments: self.demoted_segments,
+            hydrated_segments: self.hydrated_segments,
+            hydrations: self.hydrations.anonymize(),
+        }
+    }
+}
+
+/// Moves rarely accessed segments of a shard to the cold tier and brings them back on access.
+///
+/// Access is tracked per segment: requests for specific points touch only the segments holding
+/// them, while searches, scrolls and updates by filter touch every segment of the shard.
+/// In the same way, point requests hydrate only the cold segments holding their points.
+pub struct SegmentTiering {
+    shard_path: PathBuf,
+    storage_config: SegmentStorageConfig,
+    storage: ParkingMutex<Option<Arc<SegmentStorageSS>>>,
+    /// Last access to the whole shard
+    last_access: ParkingMutex<Instant>,
+    /// Last access to individual hot segments, by segment directory name
+    segment_access: ParkingMutex<HashMap<String, Instant>>,
+    /// Held for reading by requests while they use segments of the shard,
+    /// and for writing by demotion while it swaps segments out of the shard.
+    hot: TokioRwLock<()>,
+    /// Only one demotion may run at a time
+    demotion: Mutex<()>,
+    /// Segments, which are currently in the cold tier.
+    /// Lock is held for the whole duration of hydration.
+    cold_segments: Mutex<Vec<ColdSegment>>,
+    /// Number of cold segments, used as fast path check to avoid locking on every request
+    cold_segments_count: AtomicUsize,
+    cold_points_count: AtomicUsize,
+    demoted_segments: AtomicUsize,
+    hydrated_segments: AtomicUsize,
+    hydrations: Arc<ParkingMutex<OperationDurationsAggregator>>,
+}
+
+impl SegmentTiering {
+    pub fn load(shard_path: &Path, storage_config: SegmentStorageConfig) -> CollectionResult<Self> {
+        let cold_segments_path = shard_path.join(COLD_SEGMENTS_FILE);
+        let mut cold_segments: Vec<ColdSegment> = if cold_segments_path.exists() {
+            io::file_operations::read_json(&cold_segments_path)?
+        } else {
+            Vec::new()
+        };
+
+        for cold_segment in &mut cold_segments {
+            let point_ids_path = Self::point_ids_path(shard_path, &cold_segment.dir_name);
+            if !point_ids_path.exists() {
+                continue;
+            }
+            match io::file_operations::read_json::<Vec<PointIdType>>(&point_ids_path) {
+                Ok(point_ids) => {
+                    cold_segment.point_ids = Some(Arc::new(point_ids.into_iter().collect()));
+                }
+                Err(err) => log::warn!(
+                    "Failed to read point ids of cold segment {}: {err}",
+                    cold_segment.key,
+                ),
+            }
+        }
+
+        Ok(Self {
+            shard_path: shard_path.to_owned(),
+            storage_config,
+            storage: ParkingMutex::new(None),
+            last_access: ParkingMutex::new(Instant::now()),
+            segment_access: ParkingMutex::new(HashMap::new()),
+            hot: TokioRwLock::new(()),
+            demotion: Mutex::new(()),
+            cold_segments_count: AtomicUsize::new(cold_segments.len()),
+            cold_points_count: AtomicUsize::new(
+                cold_segments
+                    .iter()
+                    .map(|segment| segment.points_count)
+                    .sum(),
+            ),
+            cold_segments: Mutex::new(cold_segments),
+            demoted_segments: AtomicUsize::new(0),
+            hydrated_segments: AtomicUsize::new(0),
+            hydrations: OperationDurationsAggregator::new(),
+        })
+    }
+
+    /// Remove the list of cold segments of the shard, and the segments themselves
+    pub async fn clear(shard_path: &Path, storage_config: &SegmentStorageConfig) {
+        let point_ids_dir = shard_path.join(COLD_SEGMENTS_POINTS_DIR);
+        if point_ids_dir.exists() {
+            if let Err(err) = tokio::fs::remove_dir_all(&point_ids_dir).await {
+                log::warn!("Failed to remove point ids of cold segments: {err}");
+            }
+        }
+
+        let cold_segments_path = shard_path.join(COLD_SEGMENTS_FILE);
+        if !cold_segments_path.exists() {
+            return;
+        }
+
+        let cold_segments: Vec<ColdSegment> =
+            match io::file_operations::read_json(&cold_segments_path) {
+                Ok(cold_segments) => cold_segments,
+                Err(err) => {
+                    log::warn!("Failed to read cold segments list: {err}");
+                    Vec::new()
+                }
+            };
+
+        if !cold_segments.is_empty() {
+            match storage_config.build() {
+                Ok(storage) => {
+                    for cold_segment in cold_segments {
+                        if let Err(err) = storage.delete_segment(&cold_segment.key).await {
+                            log::warn!("Failed to delete cold segment {}: {err}", cold_segment.key);
+                        }
+                    }
+                }
+                Err(err) => log::warn!("Failed to access segment storage: {err}"),
+            }
+        }
+
+        if let Err(err) = tokio::fs::remove_file(&cold_segments_path).await {
+            log::warn!("Failed to remove cold segments list: {err}");
+        }
+    }
+
+    fn storage(&self) -> CollectionResult<Arc<SegmentStorageSS>> {
+        let mut storage = self.storage.lock();
+        if let Some(storage) = storage.as_ref() {
+            return Ok(storage.clone());
+        }
+        let built = self.storage_config.build()?;
+        *storage = Some(built.clone());
+        Ok(built)
+    }
+
+    /// Key prefix of the shard segments: `<collection>/<shard id>`
+    fn key_prefix(&self) -> String {
+        let mut components = self
+            .shard_path
+            .components()
+            .rev()
+            .take(2)
+            .map(|component| component.as_os_str().to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+        components.reverse();
+        components.join("/")
+    }
+
+    fn point_ids_path(shard_path: &Path, dir_name: &str) -> PathBuf {
+        shard_path
+            .join(COLD_SEGMENTS_POINTS_DIR)
+            .join(format!("{dir_name}.json"))
+    }
+
+    fn save(&self, cold_segments: &[ColdSegment]) -> CollectionResult<()> {
+        io::file_operations::atomic_save_json(
+            &self.shard_path.join(COLD_SEGMENTS_FILE),
+            &cold_segments,
+        )?;
+        self.cold_segments_count
+            .store(cold_segments.len(), Ordering::Release);
+        self.cold_points_count.store(
+            cold_segments
+                .iter()
+                .map(|segment| segment.points_count)
+                .sum(),
+            Ordering::Relaxed,
+        );
+        Ok(())
+    }
+
+    /// Acquire guard, which prevents segments from being demoted while a request is using them
+    pub async fn hot(&self) -> HotGuard<'_> {
+        self.hot.read().await
+    }
+
+    /// Register access to the segments in `scope`, postponing their demotion
+    pub fn touch(&self, segments: &LockedSegmentHolder, scope: HydrationScope<'_>) {
+        let now = Instant::now();
+        match scope {
+            HydrationScope::All => *self.last_access.lock() = now,
+            HydrationScope::Points(ids) => {
+                let touched = segments
+                    .read()
+                    .iter()
+                    .filter_map(|(_, segment)| {
+                        let segment_arc = segment.get();
+                        let segment_read = segment_arc.read();
+                        if ids.iter().any(|id| segment_read.has_point(*id)) {
+                            segment_dir_name(&segment_read.data_path())
+                        } else {
+                            None
+                        }
+                    })
+                    .collect::<Vec<_>>();
+
+                let mut segment_access = self.segment_access.lock();
+                for dir_name in touched {
+                    segment_access.insert(dir_name, now);
+                }
+            }
+        }
+    }
+
+    /// Time since the last access to the segment with the given directory name
+    fn idle_time(&self, dir_name: &str) -> Duration {
+        let shard_access = *self.last_access.lock();
+        let last_access = match self.segment_access.lock().get(dir_name) {
+            Some(segment_access) => shard_access.max(*segment_access),
+            None => shard_access,
+        };
+        last_access.elapsed()
+    }
+
+    /// Whether any of the cold segments is required to serve a request with the given scope
+    pub async fn needs_hydration(&self, scope: HydrationScope<'_>) -> bool {
+        if !self.has_cold_segments() {
+            return false;
+        }
+        self.cold_segments
+            .lock()
+            .await
+            .iter()
+            .any(|cold_segment| scope.needs(cold_segment))
+    }
+
+    /// Non-appendable segments, which were not accessed for `cold_after`
+    fn demotion_candidates(
+        &self,
+        segments: &LockedSegmentHolder,
+        cold_after: Duration,
+        min_segment_points: usize,
+    ) -> Vec<(SegmentId, Arc<RwLock<Segment>>)> {
+        let now = Instant::now();
+        let shard_access = *self.last_access.lock();
+        let segments_read = segments.read();
+        let mut segment_access = self.segment_access.lock();
+        let mut present = HashSet::new();
+
+        let candidates = segments_read
+            .iter()
+            .filter_map(|(id, segment)| {
+                let LockedSegment::Original(original) = segment else {
+                    return None;
+                };
+                let segment_read = original.read();
+                let dir_name = segment_dir_name(&segment_read.data_path())?;
+                // Segments, which were never seen before, are given the full grace period
+                let last_access =
+                    shard_access.max(*segment_access.entry(dir_name.clone()).or_insert(now));
+                present.insert(dir_name);
+
+                let is_candidate = !segment_read.is_appendable()
+                    && segment_read.available_point_count() >= min_segment_points
+                    && now.duration_since(last_access) >= cold_after;
+                is_candidate.then(|| (*id, original.clone()))
+            })
+            .collect();
+
+        segment_access.retain(|dir_name, _| present.contains(dir_name));
+        candidates
+    }
+
+    /// Move sufficiently large non-appendable segments, which were not accessed for `cold_after`,
+    /// to the cold tier
+    ///
+    /// Segments keep serving requests until their upload is complete, and are swapped out of the
+    /// shard only if they were neither changed nor accessed in the meantime.
+    ///
+    /// Returns number of demoted segments.
+    pub async fn demote(
+        &self,
+        segments: &LockedSegmentHolder,
+        update_sender: &ArcSwap<mpsc::Sender<UpdateSignal>>,
+        cold_after: Duration,
+        min_segment_points: usize,
+    ) -> CollectionResult<usize> {
+        let _demotion = self.demotion.lock().await;
+
+        let candidates = self.demotion_candidates(segments, cold_after, min_segment_points);
+        if candidates.is_empty() {
+            return Ok(0);
+        }
+
+        let storage = self.storage()?;
+        let key_prefix = self.key_prefix();
+
+        let mut demoted = 0;
+        for (segment_id, segment) in candidates {
+            let result = self
+                .demote_segment(
+                    segments,
+                    update_sender,
+                    &storage,
+                    &key_prefix,
+                    cold_after,
+                    segment_id,
+                    segment,
+                )
+                .await;
+            match result {
+                Ok(true) => demoted += 1,
+                Ok(false) => {}
+                Err(err) => log::error!("Failed to move segment to the cold tier: {err}"),
+            }
+        }
+
+        self.demoted_segments.fetch_add(demoted, Ordering::Relaxed);
+        Ok(demoted)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn demote_segment(
+        &self,
+        segments: &LockedSegmentHolder,
+        update_sender: &ArcSwap<mpsc::Sender<UpdateSignal>>,
+        storage: &Arc<SegmentStorageSS>,
+        key_prefix: &str,
+        cold_after: Duration,
+        segment_id: SegmentId,
+        segment: Arc<RwLock<Segment>>,
+    ) -> CollectionResult<bool> {
+        let (segment_path, version, point_ids) = {
+            let segment_read = segment.read();
+            segment_read.flush(true)?;
+            (
+                segment_read.data_path(),
+                segment_read.version(),
+                segment_read.iter_points().collect::<Vec<_>>(),
+            )
+        };
+
+        let Some(dir_name) = segment_dir_name(&segment_path) else {
+            return Ok(false);
+        };
+        let key = format!("{key_prefix}/{dir_name}");
+        let point_ids_path = Self::point_ids_path(&self.shard_path, &dir_name);
+
+        // Segment stays in the shard while it is uploaded
+        let uploaded = async {
+            storage.upload_segment(&segment_path, &key).await?;
+            tokio::fs::create_dir_all(self.shard_path.join(COLD_SEGMENTS_POINTS_DIR)).await?;
+            io::file_operations::atomic_save_json(&point_ids_path, &point_ids)?;
+            CollectionResult::Ok(())
+        };
+
+        let swapped = match uploaded.await {
+            Ok(()) => {
+                let cold_segment = ColdSegment {
+                    key: key.clone(),
+                    dir_name: dir_name.clone(),
+                    points_count: point_ids.len(),
+                    point_ids: Some(Arc::new(point_ids.into_iter().collect())),
+                };
+                self.swap_out(
+                    segments,
+                    update_sender,
+                    cold_after,
+                    segment_id,
+                    &segment,
+                    version,
+                    cold_segment,
+                )
+                .await
+            }
+            Err(err) => Err(err),
+        };
+
+        let removed = match swapped {
+            Ok(Some(removed)) => removed,
+            Ok(None) => {
+                log::debug!("Segment {key} was changed or accessed during upload, keeping it hot");
+                self.discard_upload(storage, &key, &point_ids_path).await;
+                return Ok(false);
+            }
+            Err(err) => {
+                self.discard_upload(storage, &key, &point_ids_path).await;
+                return Err(err);
+            }
+        };
+
+        self.segment_access.lock().remove(&dir_name);
+
+        // Segment is committed to the cold tier, local files are no longer needed
+        drop(segment);
+        if let Err(err) = tokio::task::spawn_blocking(move || removed.drop_data()).await? {
+            log::warn!("Failed to remove local files of cold segment {key}: {err}");
+        }
+        Ok(true)
+    }
+
+    /// Atomically replace the segment in the shard with its cold copy
+    ///
+    /// Returns `None` if the segment was changed or accessed since its upload started.
+    #[allow(clippy::too_many_arguments)]
+    async fn swap_out(
+        &self,
+        segments: &LockedSegmentHolder,
+        update_sender: &ArcSwap<mpsc::Sender<UpdateSignal>>,
+        cold_after: Duration,
+        segment_id: SegmentId,
+        segment: &Arc<RwLock<Segment>>,
+        version: SeqNumberType,
+        cold_segment: ColdSegment,
+    ) -> CollectionResult<Option<LockedSegment>> {
+        // Wait for in-flight requests to finish, and for the updates they submitted to be applied
+        let _hot = self.hot.write().await;
+        let (tx, rx) = oneshot::channel();
+        update_sender.load().send(UpdateSignal::Plunger(tx)).await?;
+        rx.await?;
+
+        if self.idle_time(&cold_segment.dir_name) < cold_after {
+            return Ok(None);
+        }
+
+        let removed = {
+            let mut segments_write = segments.write();
+            let is_unchanged = match segments_write.get(segment_id) {
+                Some(LockedSegment::Original(current)) => {
+                    Arc::ptr_eq(current, segment) && current.read().version() == version
+                }
+                _ => false,
+            };
+            if !is_unchanged {
+                return Ok(None);
+            }
+            segments_write.remove(&[segment_id]).pop()
+        };
+        let Some(removed) = removed else {
+            return Ok(None);
+        };
+
+        let mut cold_segments = self.cold_segments.lock().await;
+        cold_segments.push(cold_segment);
+        if let Err(err) = self.save(&cold_segments) {
+            cold_segments.pop();
+            segments.write().add_locked(removed);
+            return Err(err);
+        }
+
+        Ok(Some(removed))
+    }
+
+    /// Remove the copy of a segment, which was not moved to the cold tier
+    async fn discard_upload(&self, storage: &SegmentStorageSS, key: &str, point_ids_path: &Path) {
+        if let Err(err) = storage.delete_segment(key).await {
+            log::warn!("Failed to remove discarded cold segment {key}: {err}");
+        }
+        if point_ids_path.exists() {
+            if let Err(err) = tokio::fs::remove_file(point_ids_path).await {
+                log::warn!("Failed to remove point ids of discarded cold segment {key}: {err}");
+            }
+        }
+    }
+
+    /// Bring cold segments, required to serve a request with the given scope, back to the shard
+    ///
+    /// Returns number of hydrated segments.
+    pub async fn hydrate(
+        &self,
+        segments: &LockedSegmentHolder,
+        scope: HydrationScope<'_>,
+    ) -> CollectionResult<usize> {
+        if !self.has_cold_segments() {
+            return Ok(0);
+        }
+
+        let mut cold_segments = self.cold_segments.lock().await;
+        if !cold_segments
+            .iter()
+            .any(|cold_segment| scope.needs(cold_segment))
+        {
+            return Ok(0);
+        }
+
+        let mut timer = ScopeDurationMeasurer::new(&self.hydrations);
+        timer.set_success(false);
+
+        let storage = self.storage()?;
+        let segments_path = LocalShard::segments_path(&self.shard_path);
+        let cache_path = self.storage_config.cache_path();
+        let storage_cipher = segments.read().storage_cipher.clone();
+
+        let mut hydrated = 0;
+        while let Some(index) = cold_segments
+            .iter()
+            .rposition(|cold_segment| scope.needs(cold_segment))
+        {
+            let cold_segment = cold_segments[index].clone();
+
+            // Download into the cache directory first, so that an interrupted download never
+            // leaves a broken segment in the shard directory
+            let staging_path = cache_path.join(&cold_segment.key);
+            if staging_path.exists() {
+                tokio::fs::remove_dir_all(&staging_path).await?;
+            }
+            storage
+                .download_segment(&cold_segment.key, &staging_path)
+                .await?;
+
+            let segment_path = segments_path.join(&cold_segment.dir_name);
+            move_dir(&staging_path, &segment_path).await?;
+
+            let storage_cipher = storage_cipher.clone();
+            let segment = tokio::task::spawn_blocking(move || {
+                load_segment(&segment_path, storage_cipher, &AtomicBool::new(false))
+            })
+            .await??
+            .ok_or_else(|| {
+                CollectionError::service_error(format!(
+                    "Cold segment {} can't be loaded",
+                    cold_segment.key,
+                ))
+            })?;
+
+            segments.write().add(segment);
+            self.segment_access
+                .lock()
+                .insert(cold_segment.dir_name.clone(), Instant::now());
+            cold_segments.remove(index);
+            self.save(&cold_segments)?;
+
+            let point_ids_path = Self::point_ids_path(&self.shard_path, &cold_segment.dir_name);
+            if point_ids_path.exists() {
+                if let Err(err) = tokio::fs::remove_file(&point_ids_path).await {
+                    log::warn!(
+                        "Failed to remove point ids of hydrated segment {}: {err}",
+                        cold_segment.key,
+                    );
+                }
+            }
+
+            if let Err(err) = storage.delete_segment(&cold_segment.key).await {
+                log::warn!(
+                    "Failed to remove hydrated segment {} from the cold tier: {err}",
+                    cold_segment.key,
+                );
+            }
+            hydrated += 1;
+        }
+
+        // Points might have been re-inserted into hot segments while their older versions were cold
+        let deduplicated = segments.read().deduplicate_points()?;
+        if deduplicated > 0 {
+            log::debug!("Deduplicated {deduplicated} points after hydration");
+        }
+
+        timer.set_success(true);
+        self.hydrated_segments
+            .fetch_add(hydrated, Ordering::Relaxed);
+        Ok(hydrated)
+    }
+
+    /// Whether any segment of the shard is currently in the cold tier
+    pub fn has_cold_segments(&self) -> bool {
+        self.cold_segments_count.load(Ordering::Acquire) > 0
+    }
+
+    /// Number of points in the cold segments
+    pub fn cold_points_count(&self) -> usize {
+        self.cold_points_count.load(Ordering::Relaxed)
+    }
+
+    pub fn get_telemetry_data(&self, detail: TelemetryDetail) -> TieringTelemetry {
+        TieringTelemetry {
+            cold_segments: self.cold_segments_count.load(Ordering::Relaxed),
+            cold_points: self.cold_points_count(),
+            demoted_segments: self.demoted_segments.load(Ordering::Relaxed),
+            hydrated_segments: self.hydrated_segments.load(Ordering::Relaxed),
+            hydrations: self.hydrations.lock().get_statistics(detail),
+        }
+    }
+}
+
+fn segment_dir_name(segment_path: &Path) -> Option<String> {
+    segment_path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+}
+
+/// Periodically moves segments, which were not accessed long enough, to the cold tier
+pub(super) async fn tiering_worker(
+    tiering: Arc<SegmentTiering>,
+    segments: LockedSegmentHolder,
+    update_sender: Arc<ArcSwap<mpsc::Sender<UpdateSignal>>>,
+    collection_config: Arc<TokioRwLock<CollectionConfig>>,
+    cancel: CancellationToken,
+) {
+    loop {
+        tokio::select! {
+            _ = cancel.cancelled() => return,
+            _ = tokio::time::sleep(TIERING_CHECK_INTERVAL) => {}
+        }
+
+        let Some(tiering_config) = collection_config.read().await.tiering_config else {
+            continue;
+        };
+
+        if !tiering_config.enabled {
+            continue;
+        }
+
+        match tiering
+            .demote(
+                &segments,
+                &update_sender,
+                Duration::from_secs(tiering_config.cold_after_sec),
+                tiering_config.min_segment_points,
+            )
+            .await
+        {
+            Ok(0) => {}
+            Ok(demoted) => log::debug!(
+                "Moved {demoted} segments of shard {} to the cold tier",
+                tiering.shard_path.display(),
+            ),
+            Err(err) => log::error!("Failed to move segments to the cold tier: {err}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::types::DetailsLevel;
+    use tempfile::Builder;
+
+    use super::*;
+
+    #[test]
+    fn test_key_prefix() {
+        let dir = Builder::new().prefix("tiering").tempdir().unwrap();
+        let shard_path = dir
+            .path()
+            .join("collections")
+            .join("my_collection")
+            .join("3");
+        let tiering = SegmentTiering::load(&shard_path, SegmentStorageConfig::default()).unwrap();
+        assert_eq!(tiering.key_prefix(), "my_collection/3");
+    }
+
+    #[test]
+    fn test_cold_segments_persisted() {
+        let dir = Builder::new().prefix("tiering").tempdir().unwrap();
+        let tiering = SegmentTiering::load(dir.path(), SegmentStorageConfig::default()).unwrap();
+        assert_eq!(tiering.cold_segments_count.load(Ordering::Acquire), 0);
+
+        let point_ids_path = SegmentTiering::point_ids_path(dir.path(), "segment");
+        std::fs::create_dir_all(point_ids_path.parent().unwrap()).unwrap();
+        io::file_operations::atomic_save_json(
+            &point_ids_path,
+            &(0..10).map(PointIdType::from).collect::<Vec<_>>(),
+        )
+        .unwrap();
+
+        tiering
+            .save(&[ColdSegment {
+                key: "collection/0/segment".to_string(),
+                dir_name: "segment".to_string(),
+                points_count: 10,
+                point_ids: None,
+            }])
+            .unwrap();
+
+        let reloaded = SegmentTiering::load(dir.path(), SegmentStorageConfig::default()).unwrap();
+        assert_eq!(reloaded.cold_points_count(), 10);
+        let telemetry = reloaded.get_telemetry_data(TelemetryDetail {
+            level: DetailsLevel::Level0,
+            histograms: false,
+        });
+        assert_eq!(telemetry.cold_segments, 1);
+        assert_eq!(telemetry.cold_points, 10);
+
+        let cold_segments = reloaded.cold_segments.try_lock().unwrap();
+        let point_ids = cold_segments[0].point_ids.as_ref().unwrap();
+        assert_eq!(point_ids.len(), 10);
+        assert!(point_ids.contains(&PointIdType::from(3)));
+    }
+
+    #[test]
+    fn test_hydration_scope() {
+        let known = ColdSegment {
+            key: "collection/0/known".to_string(),
+            dir_name: "known".to_string(),
+            points_count: 2,
+            point_ids: Some(Arc::new(
+                [PointIdType::from(1), PointIdType::from(2)]
+                    .into_iter()
+                    .collect(),
+            )),
+        };
+        let unknown = ColdSegment {
+            key: "collection/0/unknown".to_string(),
+            dir_name: "unknown".to_string(),
+            points_count: 2,
+            point_ids: None,
+        };
+
+        assert!(HydrationScope::All.needs(&known));
+        assert!(HydrationScope::All.needs(&unknown));
+
+        let other_points = [PointIdType::from(3), PointIdType::from(4)];
+        assert!(!HydrationScope::Points(&other_points).needs(&known));
+        assert!(HydrationScope::Points(&other_points).needs(&unknown));
+
+        let some_points = [PointIdType::from(2), PointIdType::from(5)];
+        assert!(HydrationScope::Points(&some_points).needs(&known));
+    }
+}
@@ -0,0 +1,333 @@
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use segment::entry::entry_point::SegmentEntry as _;
+use segment::types::{Condition, FieldCondition, Filter, HasIdCondition, PointIdType, Range};
+
+use crate::collection_manager::holders::segment_holder::LockedSegmentHolder;
+use crate::config::TtlConfig;
+use crate::operations::point_ops::expires_at_key;
+use crate::operations::types::{CollectionResult, CoreSearchRequestBatch, Record};
+use crate::shards::local_shard::LocalShard;
+
+/// Condition, which selects points expired by the collection TTL at the given moment,
+/// `None` if the collection TTL is not configured
+fn ttl_condition(ttl_config: &TtlConfig, now: DateTime<Utc>) -> Option<Condition> {
+    let (field, ttl_sec) = ttl_config.collection_ttl()?;
+    let ttl = chrono::Duration::seconds(i64::try_from(ttl_sec).unwrap_or(i64::MAX));
+    let cutoff = now
+        .checked_sub_signed(ttl)
+        .unwrap_or(DateTime::<Utc>::MIN_UTC);
+
+    Some(Condition::Field(FieldCondition::new_datetime_range(
+        field.clone(),
+        Range {
+            lt: Some(cutoff.into()),
+            ..Default::default()
+        },
+    )))
+}
+
+/// Condition, which selects points with own expiration moment, passed at the given moment
+fn expired_points_condition(now: DateTime<Utc>) -> Condition {
+    Condition::Field(FieldCondition::new_datetime_range(
+        expires_at_key(),
+        Range {
+            lte: Some(now.into()),
+            ..Default::default()
+        },
+    ))
+}
+
+/// Filter, which selects points to be deleted at the given moment
+fn expiry_filter(
+    ttl_config: Option<&TtlConfig>,
+    has_expiring_points: bool,
+    now: DateTime<Utc>,
+) -> Option<Filter> {
+    let conditions = ttl_config
+        .and_then(|ttl_config| ttl_condition(ttl_config, now))
+        .into_iter()
+        .chain(has_expiring_points.then(|| expired_points_condition(now)))
+        .collect::<Vec<_>>();
+
+    if conditions.is_empty() {
+        return None;
+    }
+
+    Some(Filter {
+        should: Some(conditions),
+        min_should: None,
+        must: None,
+        must_not: None,
+    })
+}
+
+/// Check whether any segment of the shard indexes expiration moments of points
+pub(super) fn has_expiry_index(segments: &LockedSegmentHolder) -> bool {
+    let key = expires_at_key();
+    segments
+        .read()
+        .iter()
+        .any(|(_, segment)| segment.get().read().get_indexed_fields().contains_key(&key))
+}
+
+/// Check whether any segment of the shard contains points matching the filter
+fn has_matching_points(segments: &LockedSegmentHolder, filter: &Filter) -> bool {
+    segments.read().iter().any(|(_, segment)| {
+        !segment
+            .get()
+            .read()
+            .read_filtered(None, Some(1), Some(filter))
+            .is_empty()
+    })
+}
+
+impl LocalShard {
+    /// Filter, which selects points of the shard to be deleted by expiry.
+    ///
+    /// Returns `None` if there are no expired points. Expiry is also postponed while
+    /// optimizations are running, so that deletions don't have to be propagated through proxy
+    /// segments, and while the shard has cold segments, so that they are not loaded back.
+    pub async fn expired_points_filter(&self) -> CollectionResult<Option<Filter>> {
+        let ttl_config = self
+            .collection_config
+            .read()
+            .await
+            .ttl_config
+            .clone()
+            .filter(|ttl_config| ttl_config.enabled);
+
+        let Some(filter) = expiry_filter(
+            ttl_config.as_ref(),
+            self.has_expiring_points.load(Ordering::Relaxed),
+            Utc::now(),
+        ) else {
+            return Ok(None);
+        };
+
+        if self.tiering.has_cold_segments() || self.optimizers_log.lock().is_optimizing() {
+            return Ok(None);
+        }
+
+        let segments = self.segments.clone();
+        let has_expired_points = {
+            let filter = filter.clone();
+            tokio::task::spawn_blocking(move || has_matching_points(&segments, &filter)).await?
+        };
+        Ok(has_expired_points.then_some(filter))
+    }
+
+    /// Filter, which excludes expired points, if the shard has any points with expiration moment
+    fn expired_points_exclusion(&self) -> Option<Filter> {
+        self.has_expiring_points
+            .load(Ordering::Relaxed)
+            .then(|| Filter::new_must_not(expired_points_condition(Utc::now())))
+    }
+
+    /// Add exclusion of expired points to the filter of a request
+    pub(super) fn exclude_expired<'a>(
+        &self,
+        filter: Option<&'a Filter>,
+    ) -> Option<Cow<'a, Filter>> {
+        match (filter, self.expired_points_exclusion()) {
+            (filter, None) => filter.map(Cow::Borrowed),
+            (None, Some(exclusion)) => Some(Cow::Owned(exclusion)),
+            (Some(filter), Some(exclusion)) => Some(Cow::Owned(filter.merge(&exclusion))),
+        }
+    }
+
+    /// Add exclusion of expired points to every search of the batch
+    pub(super) fn exclude_expired_from_searches(
+        &self,
+        request: Arc<CoreSearchRequestBatch>,
+    ) -> Arc<CoreSearchRequestBatch> {
+        let Some(exclusion) = self.expired_points_exclusion() else {
+            return request;
+        };
+
+        let mut request = Arc::unwrap_or_clone(request);
+        for search in &mut request.searches {
+            search.filter = Some(match search.filter.take() {
+                Some(filter) => filter.merge_owned(exclusion.clone()),
+                None => exclusion.clone(),
+            });
+        }
+        Arc::new(request)
+    }
+
+    /// Remove expired points from the retrieved records
+    pub(super) fn retain_unexpired(&self, records: &mut Vec<Record>) -> CollectionResult<()> {
+        if records.is_empty() {
+            return Ok(());
+        }
+        let Some(exclusion) = self.expired_points_exclusion() else {
+            return Ok(());
+        };
+
+        let filter =
+            exclusion.merge_owned(Filter::new_must(Condition::HasId(HasIdCondition::from(
+                records
+                    .iter()
+                    .map(|record| record.id)
+                    .collect::<HashSet<_>>(),
+            ))));
+        let unexpired = self.read_filtered(Some(&filter))?;
+
+        records.retain(|record| unexpired.contains(&record.id));
+        Ok(())
+    }
+
+    /// Check whether the point has expired, but is not deleted yet
+    pub(super) fn is_expired(&self, point_id: PointIdType) -> CollectionResult<bool> {
+        let Some(exclusion) = self.expired_points_exclusion() else {
+            return Ok(false);
+        };
+
+        let filter = exclusion.merge_owned(Filter::new_must(Condition::HasId(
+            HasIdCondition::from(HashSet::from([point_id])),
+        )));
+        Ok(self.read_filtered(Some(&filter))?.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::entry::entry_point::SegmentEntry;
+    use segment::types::{ExtendedPointId, Payload};
+    use serde_json::json;
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::collection_manager::fixtures::{build_segment_1, empty_segment};
+    use crate::collection_manager::holders::segment_holder::SegmentHolder;
+    use crate::collection_manager::segments_updater::upsert_points;
+    use crate::operations::point_ops::PointStruct;
+
+    fn read_filtered(segments: &LockedSegmentHolder, filter: &Filter) -> Vec<ExtendedPointId> {
+        let mut points = segments
+            .read()
+            .iter()
+            .flat_map(|(_, segment)| segment.get().read().read_filtered(None, None, Some(filter)))
+            .collect::<Vec<_>>();
+        points.sort();
+        points
+    }
+
+    #[test]
+    fn test_expiry_filter() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let mut segment = build_segment_1(dir.path());
+
+        let now: DateTime<Utc> = "2024-06-01T12:00:00Z".parse().unwrap();
+        let created_at = [
+            (1u64, "2024-06-01T11:59:00Z"),
+            (2, "2024-06-01T10:00:00Z"),
+            (3, "2024-05-01T00:00:00Z"),
+        ];
+        for (id, datetime) in created_at {
+            let payload: Payload = json!({ "created_at": datetime }).into();
+            segment.set_payload(10, id.into(), &payload, &None).unwrap();
+        }
+
+        let mut holder = SegmentHolder::default();
+        holder.add(segment);
+        let segments: LockedSegmentHolder = Arc::new(parking_lot::RwLock::new(holder));
+
+        let ttl_config = TtlConfig {
+            enabled: true,
+            field: Some("created_at".parse().unwrap()),
+            ttl_sec: Some(3600),
+            point_expiry: false,
+            check_interval_sec: 60,
+        };
+
+        assert!(expiry_filter(None, false, now).is_none());
+
+        // Only own expiration moments of points are enabled
+        let point_expiry_config = TtlConfig {
+            field: None,
+            ttl_sec: None,
+            point_expiry: true,
+            ..ttl_config.clone()
+        };
+        assert!(expiry_filter(Some(&point_expiry_config), false, now).is_none());
+
+        let filter = expiry_filter(Some(&ttl_config), false, now).unwrap();
+
+        // Points without the field never expire
+        assert_eq!(
+            read_filtered(&segments, &filter),
+            vec![ExtendedPointId::NumId(2), ExtendedPointId::NumId(3)],
+        );
+        assert!(has_matching_points(&segments, &filter));
+
+        let later_filter = expiry_filter(
+            Some(&ttl_config),
+            false,
+            "2024-05-01T00:30:00Z".parse().unwrap(),
+        )
+        .unwrap();
+        assert!(!has_matching_points(&segments, &later_filter));
+    }
+
+    #[test]
+    fn test_expiring_points() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+        let mut holder = SegmentHolder::default();
+        holder.add(empty_segment(dir.path()));
+        let segments: LockedSegmentHolder = Arc::new(parking_lot::RwLock::new(holder));
+        assert!(!has_expiry_index(&segments));
+
+        // User payload keys are never treated as expiration moments, nor indexed implicitly
+        let user_point = PointStruct {
+            id: 4.into(),
+            vector: api::rest::VectorStruct::Single(vec![1.0, 0.0, 1.0, 1.0]),
+            payload: Some(json!({ "expires_at": "2024-06-01T11:00:00Z" }).into()),
+            expires_at: None,
+        };
+        assert!(!user_point.is_expiring());
+        upsert_points(&segments.read(), 1, &[user_point]).unwrap();
+        assert!(!has_expiry_index(&segments));
+
+        let expires_at = [
+            (1u64, None),
+            (2, Some("2024-06-01T11:00:00Z")),
+            (3, Some("2024-06-01T13:00:00Z")),
+        ];
+        let points = expires_at
+            .into_iter()
+            .map(|(id, expires_at)| PointStruct {
+                id: id.into(),
+                vector: api::rest::VectorStruct::Single(vec![1.0, 0.0, 1.0, 1.0]),
+                payload: None,
+                expires_at: expires_at.map(|datetime| datetime.parse().unwrap()),
+            })
+            .collect::<Vec<_>>();
+        upsert_points(&segments.read(), 2, &points).unwrap();
+
+        // Reserved expiry key is indexed on the first insertion of an expiring point
+        assert!(has_expiry_index(&segments));
+
+        let now: DateTime<Utc> = "2024-06-01T12:00:00Z".parse().unwrap();
+        let filter = expiry_filter(None, true, now).unwrap();
+        assert_eq!(
+            read_filtered(&segments, &filter),
+            vec![ExtendedPointId::NumId(2)],
+        );
+
+        let exclusion = Filter::new_must_not(expired_points_condition(now));
+        assert_eq!(
+            read_filtered(&segments, &exclusion),
+            vec![
+                ExtendedPointId::NumId(1),
+                ExtendedPointId::NumId(3),
+                ExtendedPointId::NumId(4),
+            ],
+        );
+    }
+}
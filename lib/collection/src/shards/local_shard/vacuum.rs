@@ -0,0 +1,166 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use segment::entry::entry_point::SegmentEntry as _;
+use segment::types::SegmentType;
+use tokio::sync::Mutex;
+
+use crate::collection_manager::holders::segment_holder::{
+    LockedSegment, LockedSegmentHolder, SegmentId,
+};
+use crate::common::disk_usage::dir_disk_usage;
+use crate::operations::types::{CollectionResult, VacuumResult};
+use crate::optimizers_builder::build_vacuum_optimizer;
+use crate::shards::local_shard::force_optimize::optimize_segment;
+use crate::shards::local_shard::LocalShard;
+use crate::update_handler::{Optimizer, UpdateHandler};
+
+/// Compaction of the segments of a local shard with many deleted points, requested by the user.
+///
+/// Does not borrow the shard, so that the shard is not locked while segments are compacted.
+pub struct Vacuum {
+    optimizer: Arc<Optimizer>,
+    update_handler: Arc<Mutex<UpdateHandler>>,
+    segments_path: PathBuf,
+    /// Segments to compact, with their number of deleted points
+    segments: Vec<(SegmentId, usize)>,
+}
+
+impl LocalShard {
+    /// Plan compaction of all segments, in which the fraction of deleted points is above
+    /// `deleted_threshold`, or above the threshold of the optimizers config if not set.
+    pub async fn vacuum(&self, deleted_threshold: Option<f64>) -> Vacuum {
+        let config = self.collection_config.read().await;
+        let optimizer = build_vacuum_optimizer(
+            &self.path,
+            &config.params,
+            &config.optimizer_config,
+            &config.hnsw_config,
+            &config.quantization_config,
+            config.defragment_key(),
+            self.storage_cipher.as_ref(),
+        );
+        let deleted_threshold =
+            deleted_threshold.unwrap_or(config.optimizer_config.deleted_threshold);
+        drop(config);
+
+        Vacuum {
+            optimizer,
+            update_handler: self.update_handler.clone(),
+            segments_path: Self::segments_path(&self.path),
+            segments: littered_segments(&self.segments, deleted_threshold),
+        }
+    }
+}
+
+/// Segments with a fraction of deleted points above `deleted_threshold`, most deleted points first
+fn littered_segments(
+    segments: &LockedSegmentHolder,
+    deleted_threshold: f64,
+) -> Vec<(SegmentId, usize)> {
+    let mut littered: Vec<_> = segments
+        .read()
+        .iter()
+        .filter_map(|(segment_id, segment)| {
+            // Segments under optimization are already being rebuilt
+            let LockedSegment::Original(segment) = segment else {
+                return None;
+            };
+            let segment = segment.read();
+            if segment.segment_type() == SegmentType::Special {
+                return None;
+            }
+
+            let deleted_count = segment.deleted_point_count();
+            let littered_ratio = deleted_count as f64 / segment.total_point_count() as f64;
+            (deleted_count > 0 && littered_ratio > deleted_threshold)
+                .then_some((*segment_id, deleted_count))
+        })
+        .collect();
+    littered.sort_by(|a, b| b.1.cmp(&a.1));
+    littered
+}
+
+impl Vacuum {
+    /// Compact segments one by one, once CPU budget is available
+    pub async fn run(self) -> CollectionResult<VacuumResult> {
+        let mut result = VacuumResult::default();
+
+        for (segment_id, deleted_count) in self.segments {
+            let bytes_before = segments_disk_usage(&self.segments_path).await?;
+            let compacted =
+                optimize_segment(&self.update_handler, &self.optimizer, segment_id, || {}).await?;
+            // Segment may be compacted by the optimizers of the collection in the meantime
+            if !compacted {
+                continue;
+            }
+
+            result.segments += 1;
+            result.deleted_points += deleted_count;
+            let bytes_after = segments_disk_usage(&self.segments_path).await?;
+            result.reclaimed_bytes += bytes_before.saturating_sub(bytes_after);
+        }
+
+        Ok(result)
+    }
+}
+
+async fn segments_disk_usage(segments_path: &Path) -> CollectionResult<u64> {
+    let segments_path = segments_path.to_path_buf();
+    Ok(tokio::task::spawn_blocking(move || dir_disk_usage(&segments_path)).await?)
+}
+
+/// Compact segments of the planned shards one by one, and sum up their outcome
+pub async fn run_vacuums(vacuums: Vec<Vacuum>) -> CollectionResult<VacuumResult> {
+    let mut result = VacuumResult::default();
+    for vacuum in vacuums {
+        let shard_result = vacuum.run().await?;
+        result.segments += shard_result.segments;
+        result.deleted_points += shard_result.deleted_points;
+        result.reclaimed_bytes += shard_result.reclaimed_bytes;
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use parking_lot::RwLock;
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::collection_manager::fixtures::{empty_segment, random_segment};
+    use crate::collection_manager::holders::segment_holder::SegmentHolder;
+
+    #[test]
+    fn test_littered_segments() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+        let mut segments = [
+            random_segment(dir.path(), 100, 100, 4),
+            random_segment(dir.path(), 100, 100, 4),
+            random_segment(dir.path(), 100, 100, 4),
+        ];
+        // Delete 10%, 50% and 30% of points
+        for (segment, deleted_count) in segments.iter_mut().zip([10, 50, 30]) {
+            let point_ids: Vec<_> = segment.iter_points().take(deleted_count).collect();
+            for point_id in point_ids {
+                segment.delete_point(101, point_id).unwrap();
+            }
+        }
+
+        let mut holder = SegmentHolder::default();
+        let _empty = holder.add(empty_segment(dir.path()));
+        let [few, most, some] = segments.map(|segment| holder.add(segment));
+        let segments = Arc::new(RwLock::new(holder));
+
+        assert_eq!(
+            littered_segments(&segments, 0.2),
+            vec![(most, 50), (some, 30)],
+        );
+        assert_eq!(
+            littered_segments(&segments, 0.0),
+            vec![(most, 50), (some, 30), (few, 10)],
+        );
+        assert!(littered_segments(&segments, 0.5).is_empty());
+    }
+}
@@ -0,0 +1,491 @@
+//! Continuous archiving of the shard WAL.
+//!
+//! Segments of the WAL are immutable once they are closed, so they can be shipped to an object
+//! storage as is. Along with the segments, the archive contains a manifest, which lists archived
+//! segments in order and is the entry point for point-in-time recovery and async replicas.
+//!
+//! WAL segments are not removed from the disk until they are archived, so the archive has no gaps
+//! even if the archive storage is temporarily unavailable.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use object_store::local::LocalFileSystem;
+use object_store::ObjectStore;
+use parking_lot::Mutex as ParkingMutex;
+use schemars::JsonSchema;
+use segment::common::anonymize::Anonymize;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use tokio_util::sync::CancellationToken;
+
+use crate::common::segment_storage::{AzureConfig, GcsConfig, SegmentStorageBackendType};
+use crate::common::sha_256::hash_file;
+use crate::common::snapshots_manager::S3Config;
+use crate::operations::snapshot_storage_ops;
+use crate::operations::types::{CollectionError, CollectionResult};
+use crate::shards::local_shard::LocalShard;
+use crate::shards::shard::PeerId;
+
+/// Local copy of the archive manifest, kept in the shard directory
+pub const WAL_ARCHIVE_MANIFEST_FILE: &str = "wal_archive.json";
+
+/// Name of the manifest object in the archive of the shard
+pub const WAL_ARCHIVE_MANIFEST_OBJECT: &str = "manifest.json";
+
+/// Default sub-directory of the storage path, used by the local backend
+pub const DEFAULT_WAL_ARCHIVE_DIR: &str = "wal_archive";
+
+const DEFAULT_ARCHIVE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Closed WAL segments are named `closed-<index of the first entry>`
+const CLOSED_SEGMENT_PREFIX: &str = "closed-";
+
+#[derive(Clone, Deserialize, Debug, Default)]
+pub struct WalArchiveConfig {
+    /// Ship closed WAL segments of all local shards to the archive
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub backend: SegmentStorageBackendType,
+    /// Directory for the local backend.
+    /// If not set, `<storage_path>/wal_archive` is used.
+    #[serde(default)]
+    pub local_path: Option<String>,
+    #[serde(default)]
+    pub s3_config: Option<S3Config>,
+    #[serde(default)]
+    pub gcs_config: Option<GcsConfig>,
+    #[serde(default)]
+    pub azure_config: Option<AzureConfig>,
+    /// How often to check for new closed segments, in seconds. Default: 60
+    #[serde(default)]
+    pub archive_interval_sec: Option<u64>,
+    /// Archives of different replicas of the same shard are kept apart by peer id
+    #[serde(skip)]
+    pub peer_id: Option<PeerId>,
+}
+
+impl WalArchiveConfig {
+    /// Resolve directories, which are not set explicitly, relative to the storage path
+    pub fn with_storage_path(mut self, storage_path: &Path) -> Self {
+        self.local_path.get_or_insert_with(|| {
+            storage_path
+                .join(DEFAULT_WAL_ARCHIVE_DIR)
+                .to_string_lossy()
+                .into_owned()
+        });
+        self
+    }
+
+    pub fn with_peer_id(mut self, peer_id: PeerId) -> Self {
+        self.peer_id = Some(peer_id);
+        self
+    }
+
+    pub fn archive_interval(&self) -> Duration {
+        self.archive_interval_sec
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_ARCHIVE_INTERVAL)
+    }
+
+    /// Create object storage client for the configured backend
+    pub fn build_client(&self) -> CollectionResult<Box<dyn ObjectStore>> {
+        match self.backend {
+            SegmentStorageBackendType::Local => {
+                let path = self
+                    .local_path
+                    .as_ref()
+                    .map(PathBuf::from)
+                    .unwrap_or_else(|| PathBuf::from(DEFAULT_WAL_ARCHIVE_DIR));
+                std::fs::create_dir_all(&path)?;
+                let client = LocalFileSystem::new_with_prefix(&path).map_err(|err| {
+                    CollectionError::service_error(format!(
+                        "Failed to open WAL archive directory {}: {err}",
+                        path.display(),
+                    ))
+                })?;
+                Ok(Box::new(client))
+            }
+            SegmentStorageBackendType::S3 => self
+                .s3_config
+                .as_ref()
+                .ok_or_else(|| {
+                    CollectionError::bad_input("`s3_config` is required for S3 WAL archive")
+                })?
+                .build_client(),
+            SegmentStorageBackendType::Gcs => self
+                .gcs_config
+                .as_ref()
+                .ok_or_else(|| {
+                    CollectionError::bad_input("`gcs_config` is required for GCS WAL archive")
+                })?
+                .build_client(),
+            SegmentStorageBackendType::Azure => self
+                .azure_config
+                .as_ref()
+                .ok_or_else(|| {
+                    CollectionError::bad_input("`azure_config` is required for Azure WAL archive")
+                })?
+                .build_client(),
+        }
+    }
+}
+
+/// List of archived segments of the shard WAL
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct WalArchiveManifest {
+    /// Archived segments, ordered by the index of their first entry
+    pub segments: Vec<ArchivedWalSegment>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ArchivedWalSegment {
+    /// Name of the segment object, relative to the archive of the shard
+    pub name: String,
+    /// Index of the first entry of the segment.
+    /// Segment spans until the first index of the next segment.
+    pub first_index: u64,
+    pub size: u64,
+    /// SHA256 checksum of the segment file
+    pub checksum: String,
+    /// Time of the last write into the segment, all its entries were written before it
+    pub last_write_time: Option<DateTime<Utc>>,
+    pub archived_at: DateTime<Utc>,
+}
+
+impl WalArchiveManifest {
+    pub fn load(shard_path: &Path) -> CollectionResult<Self> {
+        let manifest_path = shard_path.join(WAL_ARCHIVE_MANIFEST_FILE);
+        if manifest_path.exists() {
+            Ok(io::file_operations::read_json(&manifest_path)?)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    /// Index of the first entry of the most recently archived segment
+    pub fn last_archived_index(&self) -> Option<u64> {
+        self.segments.last().map(|segment| segment.first_index)
+    }
+}
+
+#[derive(Serialize, Clone, Debug, JsonSchema, Default)]
+pub struct WalArchiveTelemetry {
+    /// Number of segments in the archive
+    pub archived_segments: usize,
+    /// Index of the first entry of the most recently archived segment
+    pub last_archived_index: Option<u64>,
+    /// Number of failed archiving attempts since start
+    pub failures: usize,
+}
+
+impl Anonymize for WalArchiveTelemetry {
+    fn anonymize(&self) -> Self {
+        self.clone()
+    }
+}
+
+/// Prefix of the shard archive in the storage: `<collection>/<shard id>[/<peer id>]`
+pub fn shard_archive_prefix(
+    collection_name: &str,
+    shard_id: &str,
+    peer_id: Option<PeerId>,
+) -> String {
+    match peer_id {
+        Some(peer_id) => format!("{collection_name}/{shard_id}/{peer_id}"),
+        None => format!("{collection_name}/{shard_id}"),
+    }
+}
+
+/// Ships closed segments of the shard WAL to the archive storage
+pub struct WalArchiver {
+    shard_path: PathBuf,
+    config: WalArchiveConfig,
+    client: ParkingMutex<Option<Arc<dyn ObjectStore>>>,
+    manifest: Mutex<WalArchiveManifest>,
+    /// WAL may not remove segments, which contain entries at or after this index.
+    /// Set to the first index of the most recently archived segment.
+    truncation_limit: Arc<AtomicU64>,
+    archived_segments: AtomicUsize,
+    failures: AtomicUsize,
+}
+
+impl WalArchiver {
+    /// Returns `None` if WAL archiving is disabled
+    pub fn load(shard_path: &Path, config: WalArchiveConfig) -> CollectionResult<Option<Self>> {
+        if !config.enabled {
+            return Ok(None);
+        }
+
+        let manifest = WalArchiveManifest::load(shard_path)?;
+        Ok(Some(Self {
+            shard_path: shard_path.to_owned(),
+            config,
+            client: ParkingMutex::new(None),
+            truncation_limit: Arc::new(AtomicU64::new(manifest.last_archived_index().unwrap_or(0))),
+            archived_segments: AtomicUsize::new(manifest.segments.len()),
+            failures: AtomicUsize::new(0),
+            manifest: Mutex::new(manifest),
+        }))
+    }
+
+    /// Remove the local copy of the manifest, so archiving starts over with a new WAL
+    pub async fn delete_manifest(shard_path: &Path) -> CollectionResult<()> {
+        let manifest_path = shard_path.join(WAL_ARCHIVE_MANIFEST_FILE);
+        if manifest_path.exists() {
+            tokio::fs::remove_file(manifest_path).await?;
+        }
+        Ok(())
+    }
+
+    /// Limit of WAL truncation, which keeps segments until they are archived
+    pub fn truncation_limit(&self) -> Arc<AtomicU64> {
+        self.truncation_limit.clone()
+    }
+
+    fn client(&self) -> CollectionResult<Arc<dyn ObjectStore>> {
+        let mut client = self.client.lock();
+        if let Some(client) = client.as_ref() {
+            return Ok(client.clone());
+        }
+        let built: Arc<dyn ObjectStore> = Arc::from(self.config.build_client()?);
+        *client = Some(built.clone());
+        Ok(built)
+    }
+
+    /// Prefix of the shard archive, see [`shard_archive_prefix`]
+    pub fn key_prefix(&self) -> String {
+        let mut components = self
+            .shard_path
+            .components()
+            .rev()
+            .map(|component| component.as_os_str().to_string_lossy());
+        let shard_dir = components.next().unwrap_or_default();
+        let collection_dir = components.next().unwrap_or_default();
+        // Directory of a shard may have a version suffix: `<shard id>-<version>`
+        let shard_id = shard_dir.split('-').next().unwrap_or_default();
+        shard_archive_prefix(&collection_dir, shard_id, self.config.peer_id)
+    }
+
+    /// Closed segments of the WAL, which are newer than the last archived one, ordered by first index
+    fn pending_segments(
+        &self,
+        last_archived_index: Option<u64>,
+    ) -> CollectionResult<Vec<(u64, PathBuf)>> {
+        let wal_path = LocalShard::wal_path(&self.shard_path);
+        let mut segments = Vec::new();
+        for entry in std::fs::read_dir(&wal_path)? {
+            let path = entry?.path();
+            let Some(first_index) = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_prefix(CLOSED_SEGMENT_PREFIX))
+                .and_then(|index| index.parse::<u64>().ok())
+            else {
+                continue;
+            };
+            if last_archived_index.map_or(true, |last| first_index > last) {
+                segments.push((first_index, path));
+            }
+        }
+        segments.sort_unstable_by_key(|(first_index, _)| *first_index);
+        Ok(segments)
+    }
+
+    /// Archive all closed WAL segments, which are not archived yet
+    ///
+    /// Returns number of archived segments.
+    pub async fn archive_closed_segments(&self) -> CollectionResult<usize> {
+        let result = self.archive_closed_segments_impl().await;
+        if result.is_err() {
+            self.failures.fetch_add(1, Ordering::Relaxed);
+        }
+        result
+    }
+
+    async fn archive_closed_segments_impl(&self) -> CollectionResult<usize> {
+        let mut manifest = self.manifest.lock().await;
+        let pending = self.pending_segments(manifest.last_archived_index())?;
+        if pending.is_empty() {
+            return Ok(0);
+        }
+
+        let client = self.client()?;
+        let key_prefix = self.key_prefix();
+
+        let mut archived = 0;
+        for (first_index, segment_path) in pending {
+            let name = segment_path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let metadata = tokio::fs::metadata(&segment_path).await?;
+            let checksum = hash_file(&segment_path).await?;
+
+            snapshot_storage_ops::multipart_upload(
+                client.as_ref(),
+                &segment_path,
+                &Path::new(&key_prefix).join(&name),
+            )
+            .await?;
+
+            manifest.segments.push(ArchivedWalSegment {
+                name,
+                first_index,
+                size: metadata.len(),
+                checksum,
+                last_write_time: metadata.modified().ok().map(DateTime::<Utc>::from),
+                archived_at: Utc::now(),
+            });
+            self.save_manifest(client.as_ref(), &key_prefix, &manifest)
+                .await?;
+
+            // Segment is safely archived, WAL may remove it from now on
+            self.truncation_limit.store(first_index, Ordering::Release);
+            self.archived_segments
+                .store(manifest.segments.len(), Ordering::Relaxed);
+            archived += 1;
+        }
+
+        Ok(archived)
+    }
+
+    async fn save_manifest(
+        &self,
+        client: &dyn ObjectStore,
+        key_prefix: &str,
+        manifest: &WalArchiveManifest,
+    ) -> CollectionResult<()> {
+        let manifest_path =
+            object_store::path::Path::from(format!("{key_prefix}/{WAL_ARCHIVE_MANIFEST_OBJECT}"));
+        let data = serde_json::to_vec(manifest)?;
+        client
+            .put(&manifest_path, data.into())
+            .await
+            .map_err(|err| {
+                CollectionError::object_storage_error(format!(
+                    "Failed to upload WAL archive manifest {manifest_path}: {err}"
+                ))
+            })?;
+
+        io::file_operations::atomic_save_json(
+            &self.shard_path.join(WAL_ARCHIVE_MANIFEST_FILE),
+            manifest,
+        )?;
+        Ok(())
+    }
+
+    pub fn get_telemetry_data(&self) -> WalArchiveTelemetry {
+        let last_archived_index = self.truncation_limit.load(Ordering::Relaxed);
+        let archived_segments = self.archived_segments.load(Ordering::Relaxed);
+        WalArchiveTelemetry {
+            archived_segments,
+            last_archived_index: (archived_segments > 0).then_some(last_archived_index),
+            failures: self.failures.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Periodically ships closed WAL segments of the shard to the archive
+pub(super) async fn wal_archive_worker(archiver: Arc<WalArchiver>, cancel: CancellationToken) {
+    let interval = archiver.config.archive_interval();
+    loop {
+        tokio::select! {
+            _ = cancel.cancelled() => return,
+            _ = tokio::time::sleep(interval) => {}
+        }
+
+        match archiver.archive_closed_segments().await {
+            Ok(0) => {}
+            Ok(archived) => log::debug!(
+                "Archived {archived} WAL segments of shard {}",
+                archiver.shard_path.display(),
+            ),
+            Err(err) => log::error!(
+                "Failed to archive WAL segments of shard {}: {err}",
+                archiver.shard_path.display(),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::Builder;
+
+    use super::*;
+
+    fn local_config(archive_path: &Path) -> WalArchiveConfig {
+        WalArchiveConfig {
+            enabled: true,
+            local_path: Some(archive_path.to_string_lossy().into_owned()),
+            ..Default::default()
+        }
+        .with_peer_id(42)
+    }
+
+    #[test]
+    fn test_disabled() {
+        let dir = Builder::new().prefix("wal_archive").tempdir().unwrap();
+        let archiver = WalArchiver::load(dir.path(), WalArchiveConfig::default()).unwrap();
+        assert!(archiver.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_archive_closed_segments() {
+        let storage_dir = Builder::new().prefix("storage").tempdir().unwrap();
+        let archive_dir = Builder::new().prefix("wal_archive").tempdir().unwrap();
+
+        let shard_path = storage_dir.path().join("my_collection").join("0");
+        let wal_path = LocalShard::wal_path(&shard_path);
+        std::fs::create_dir_all(&wal_path).unwrap();
+        std::fs::write(wal_path.join("closed-1"), b"first segment").unwrap();
+        std::fs::write(wal_path.join("closed-10"), b"second segment").unwrap();
+        std::fs::write(wal_path.join("open-1"), b"open segment").unwrap();
+
+        let archiver = WalArchiver::load(&shard_path, local_config(archive_dir.path()))
+            .unwrap()
+            .unwrap();
+        assert_eq!(archiver.key_prefix(), "my_collection/0/42");
+        assert_eq!(archiver.truncation_limit().load(Ordering::Relaxed), 0);
+
+        assert_eq!(archiver.archive_closed_segments().await.unwrap(), 2);
+        assert_eq!(archiver.truncation_limit().load(Ordering::Relaxed), 10);
+        // Nothing new to archive
+        assert_eq!(archiver.archive_closed_segments().await.unwrap(), 0);
+
+        let shard_archive = archive_dir.path().join("my_collection/0/42");
+        assert_eq!(
+            std::fs::read(shard_archive.join("closed-10")).unwrap(),
+            b"second segment",
+        );
+        assert!(!shard_archive.join("open-1").exists());
+
+        let manifest: WalArchiveManifest =
+            serde_json::from_slice(&std::fs::read(shard_archive.join("manifest.json")).unwrap())
+                .unwrap();
+        let first_indices: Vec<_> = manifest
+            .segments
+            .iter()
+            .map(|segment| segment.first_index)
+            .collect();
+        assert_eq!(first_indices, [1, 10]);
+
+        // Archived segments are removed by WAL, new segment is closed
+        std::fs::remove_file(wal_path.join("closed-1")).unwrap();
+        std::fs::write(wal_path.join("closed-20"), b"third segment").unwrap();
+
+        // State is restored from the local manifest
+        let archiver = WalArchiver::load(&shard_path, local_config(archive_dir.path()))
+            .unwrap()
+            .unwrap();
+        assert_eq!(archiver.truncation_limit().load(Ordering::Relaxed), 10);
+        assert_eq!(archiver.archive_closed_segments().await.unwrap(), 1);
+        assert_eq!(archiver.get_telemetry_data().archived_segments, 3);
+        assert_eq!(archiver.get_telemetry_data().last_archived_index, Some(20));
+    }
+}
@@ -0,0 +1,89 @@
+use std::ops::Range;
+use std::sync::atomic::Ordering;
+
+use crate::operations::types::CollectionResult;
+use crate::operations::OperationWithClockTag;
+use crate::shards::local_shard::LocalShard;
+
+/// Operations read from the WAL of a local shard, to be replayed elsewhere
+#[derive(Debug)]
+pub struct WalBatch {
+    /// Index of the first operation in the batch
+    pub from: u64,
+    /// Index right after the last operation in the WAL
+    pub end: u64,
+    pub operations: Vec<(u64, OperationWithClockTag)>,
+}
+
+/// Result of reading the WAL from a given offset
+#[derive(Debug)]
+pub enum WalTail {
+    Batch(WalBatch),
+    /// Operations at the offset are no longer stored in the WAL: they were truncated, or the WAL
+    /// was replaced, e.g. by a shard transfer. Reading can't continue without a gap.
+    Gap,
+}
+
+/// Whether reading can continue from `offset` without skipping any operation
+fn is_stored(offset: u64, stored: Range<u64>) -> bool {
+    stored.start <= offset && offset <= stored.end
+}
+
+impl LocalShard {
+    /// Read up to `limit` operations from the WAL, starting at `offset`
+    ///
+    /// Acknowledged operations are read as well, as long as they are still stored in closed
+    /// segments, see [`LocalShard::pin_wal`].
+    pub fn read_wal_batch(&self, offset: u64, limit: usize) -> CollectionResult<WalTail> {
+        let wal = self.wal.wal.lock();
+        let stored = wal.first_closed_index()..wal.first_index() + wal.len(false);
+        if !is_stored(offset, stored.clone()) {
+            return Ok(WalTail::Gap);
+        }
+        let operations = wal.read(offset).take(limit).collect::<Result<_, _>>()?;
+        Ok(WalTail::Batch(WalBatch {
+            from: offset,
+            end: stored.end,
+            operations,
+        }))
+    }
+
+    /// Keep WAL operations at or after `offset`, until they are replayed elsewhere.
+    /// `None` releases the pin.
+    pub fn pin_wal(&self, offset: Option<u64>) {
+        self.replication_pin
+            .store(offset.unwrap_or(u64::MAX), Ordering::Release);
+    }
+
+    /// Pin and return the offset to replay the WAL from, after all points of the shard are
+    /// copied elsewhere.
+    ///
+    /// All operations before the offset are persisted in segments, so they are included in the
+    /// copy. Operations after the offset might be included as well, so they are replayed at least
+    /// once.
+    pub fn pin_wal_for_copy(&self) -> u64 {
+        // Hold the WAL lock, so that it is not truncated before it is pinned
+        let wal = self.wal.wal.lock();
+        let offset = wal.first_index();
+        self.pin_wal(Some(offset));
+        offset
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_stored() {
+        // Continue where the previous batch ended
+        assert!(is_stored(15, 10..20));
+        assert!(is_stored(10, 10..20));
+        // Nothing new to read
+        assert!(is_stored(20, 10..20));
+        // Operations were truncated before they were read
+        assert!(!is_stored(4, 10..20));
+        // WAL was replaced
+        assert!(!is_stored(25, 0..3));
+    }
+}
@@ -6,6 +6,7 @@ use std::time::Duration;
 
 use async_trait::async_trait;
 use common::types::TelemetryDetail;
+use segment::blob_storage::BlobChunk;
 use segment::data_types::order_by::OrderBy;
 use segment::types::{
     ExtendedPointId, Filter, PointIdType, ScoredPoint, WithPayload, WithPayloadInterface,
@@ -16,15 +17,20 @@ use tokio::sync::{oneshot, RwLock};
 use tokio::time::timeout;
 
 use super::update_tracker::UpdateTracker;
+use crate::collection_manager::optimizers::TrackerTelemetry;
 use crate::operations::operation_effect::{
     EstimateOperationEffectArea, OperationEffectArea, PointsOperationEffect,
 };
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch,
-    CountRequestInternal, CountResult, PointRequestInternal, Record, UpdateResult,
+    CountRequestInternal, CountResult, LocalShardPointsBreakdown, PointRequestInternal, Record,
+    SegmentIndexInfo, UpdateResult,
 };
 use crate::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
 use crate::operations::OperationWithClockTag;
+use crate::shards::local_shard::force_optimize::ForcedOptimization;
+use crate::shards::local_shard::memory_spill::MemorySpill;
+use crate::shards::local_shard::vacuum::Vacuum;
 use crate::shards::local_shard::LocalShard;
 use crate::shards::shard_trait::ShardOperation;
 use crate::shards::telemetry::LocalShardTelemetry;
@@ -124,6 +130,30 @@ impl ProxyShard {
         self.wrapped_shard.get_telemetry_data(detail)
     }
 
+    pub fn points_breakdown(&self) -> LocalShardPointsBreakdown {
+        self.wrapped_shard.points_breakdown()
+    }
+
+    pub async fn index_info(&self) -> CollectionResult<Vec<SegmentIndexInfo>> {
+        self.wrapped_shard.index_info().await
+    }
+
+    pub fn optimizations_log(&self) -> Vec<TrackerTelemetry> {
+        self.wrapped_shard.optimizations_log()
+    }
+
+    pub async fn forced_optimization(&self) -> ForcedOptimization {
+        self.wrapped_shard.forced_optimization().await
+    }
+
+    pub async fn vacuum(&self, deleted_threshold: Option<f64>) -> Vacuum {
+        self.wrapped_shard.vacuum(deleted_threshold).await
+    }
+
+    pub async fn memory_spill(&self) -> MemorySpill {
+        self.wrapped_shard.memory_spill().await
+    }
+
     pub fn update_tracker(&self) -> &UpdateTracker {
         self.wrapped_shard.update_tracker()
     }
@@ -247,6 +277,17 @@ impl ShardOperation for ProxyShard {
             .await
     }
 
+    /// Forward read-only `read_blob` to `wrapped_shard`
+    async fn read_blob(
+        &self,
+        point_id: ExtendedPointId,
+        offset: usize,
+        limit: usize,
+    ) -> CollectionResult<Option<BlobChunk>> {
+        let local_shard = &self.wrapped_shard;
+        local_shard.read_blob(point_id, offset, limit).await
+    }
+
     /// Forward read-only `query` to `wrapped_shard`
     async fn query(
         &self,
@@ -6,6 +6,7 @@ use std::time::Duration;
 use async_trait::async_trait;
 use common::types::TelemetryDetail;
 use parking_lot::Mutex as ParkingMutex;
+use segment::blob_storage::BlobChunk;
 use segment::data_types::order_by::OrderBy;
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
@@ -17,13 +18,18 @@ use super::remote_shard::RemoteShard;
 use super::transfer::driver::MAX_RETRY_COUNT;
 use super::transfer::transfer_tasks_pool::TransferTaskProgress;
 use super::update_tracker::UpdateTracker;
+use crate::collection_manager::optimizers::TrackerTelemetry;
 use crate::operations::point_ops::WriteOrdering;
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch,
-    CountRequestInternal, CountResult, PointRequestInternal, Record, UpdateResult,
+    CountRequestInternal, CountResult, LocalShardPointsBreakdown, PointRequestInternal, Record,
+    SegmentIndexInfo, UpdateResult,
 };
 use crate::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
 use crate::operations::OperationWithClockTag;
+use crate::shards::local_shard::force_optimize::ForcedOptimization;
+use crate::shards::local_shard::memory_spill::MemorySpill;
+use crate::shards::local_shard::vacuum::Vacuum;
 use crate::shards::local_shard::LocalShard;
 use crate::shards::shard_trait::ShardOperation;
 use crate::shards::telemetry::LocalShardTelemetry;
@@ -164,6 +170,58 @@ impl QueueProxyShard {
             .get_telemetry_data(detail)
     }
 
+    pub fn points_breakdown(&self) -> LocalShardPointsBreakdown {
+        self.inner
+            .as_ref()
+            .expect("Queue proxy has been finalized")
+            .wrapped_shard
+            .points_breakdown()
+    }
+
+    pub async fn index_info(&self) -> CollectionResult<Vec<SegmentIndexInfo>> {
+        self.inner
+            .as_ref()
+            .expect("Queue proxy has been finalized")
+            .wrapped_shard
+            .index_info()
+            .await
+    }
+
+    pub fn optimizations_log(&self) -> Vec<TrackerTelemetry> {
+        self.inner
+            .as_ref()
+            .expect("Queue proxy has been finalized")
+            .wrapped_shard
+            .optimizations_log()
+    }
+
+    pub async fn forced_optimization(&self) -> ForcedOptimization {
+        self.inner
+            .as_ref()
+            .expect("Queue proxy has been finalized")
+            .wrapped_shard
+            .forced_optimization()
+            .await
+    }
+
+    pub async fn vacuum(&self, deleted_threshold: Option<f64>) -> Vacuum {
+        self.inner
+            .as_ref()
+            .expect("Queue proxy has been finalized")
+            .wrapped_shard
+            .vacuum(deleted_threshold)
+            .await
+    }
+
+    pub async fn memory_spill(&self) -> MemorySpill {
+        self.inner
+            .as_ref()
+            .expect("Queue proxy has been finalized")
+            .wrapped_shard
+            .memory_spill()
+            .await
+    }
+
     pub fn update_tracker(&self) -> &UpdateTracker {
         self.inner
             .as_ref()
@@ -292,6 +350,20 @@ impl ShardOperation for QueueProxyShard {
             .await
     }
 
+    /// Forward read-only `read_blob` to `wrapped_shard`
+    async fn read_blob(
+        &self,
+        point_id: ExtendedPointId,
+        offset: usize,
+        limit: usize,
+    ) -> CollectionResult<Option<BlobChunk>> {
+        self.inner
+            .as_ref()
+            .expect("Queue proxy has been finalized")
+            .read_blob(point_id, offset, limit)
+            .await
+    }
+
     /// Forward read-only `query` to `wrapped_shard`
     async fn query(
         &self,
@@ -423,7 +495,10 @@ impl Inner {
             let wal = self.wrapped_shard.wal.wal.lock();
             let items_left = (wal.last_index() + 1).saturating_sub(transfer_from);
             let items_total = (transfer_from - self.started_at) + items_left;
-            let batch = wal.read(transfer_from).take(BATCH_SIZE).collect::<Vec<_>>();
+            let batch = wal
+                .read(transfer_from)
+                .take(BATCH_SIZE)
+                .collect::<Result<Vec<_>, _>>()?;
             (items_left, items_total, batch)
         };
 
@@ -578,6 +653,17 @@ impl ShardOperation for Inner {
             .await
     }
 
+    /// Forward read-only `read_blob` to `wrapped_shard`
+    async fn read_blob(
+        &self,
+        point_id: ExtendedPointId,
+        offset: usize,
+        limit: usize,
+    ) -> CollectionResult<Option<BlobChunk>> {
+        let local_shard = &self.wrapped_shard;
+        local_shard.read_blob(point_id, offset, limit).await
+    }
+
     /// Forward read-only `query` to `wrapped_shard`    
     async fn query(
         &self,
@@ -10,16 +10,17 @@ use api::grpc::qdrant::shard_snapshot_location::Location;
 use api::grpc::qdrant::shard_snapshots_client::ShardSnapshotsClient;
 use api::grpc::qdrant::{
     CollectionOperationResponse, CoreSearchBatchPointsInternal, CountPoints, CountPointsInternal,
-    GetCollectionInfoRequest, GetCollectionInfoRequestInternal, GetPoints, GetPointsInternal,
-    GetShardRecoveryPointRequest, HealthCheckRequest, InitiateShardTransferRequest,
-    QueryPointsInternal, QueryShardPoints, RecoverShardSnapshotRequest, RecoverSnapshotResponse,
-    ScrollPoints, ScrollPointsInternal, ShardSnapshotLocation, UpdateShardCutoffPointRequest,
-    WaitForShardStateRequest,
+    GetBlobPoint, GetBlobPointInternal, GetCollectionInfoRequest, GetCollectionInfoRequestInternal,
+    GetPoints, GetPointsInternal, GetShardRecoveryPointRequest, HealthCheckRequest,
+    InitiateShardTransferRequest, QueryPointsInternal, QueryShardPoints,
+    RecoverShardSnapshotRequest, RecoverSnapshotResponse, ScrollPoints, ScrollPointsInternal,
+    ShardSnapshotLocation, UpdateShardCutoffPointRequest, WaitForShardStateRequest,
 };
 use api::grpc::transport_channel_pool::{AddTimeout, MAX_GRPC_CHANNEL_TIMEOUT};
 use async_trait::async_trait;
 use common::types::TelemetryDetail;
 use parking_lot::Mutex;
+use segment::blob_storage::BlobChunk;
 use segment::common::operation_time_statistics::{
     OperationDurationsAggregator, ScopeDurationMeasurer,
 };
@@ -38,6 +39,7 @@ use super::conversions::{
 };
 use super::local_shard::clock_map::RecoveryPoint;
 use super::replica_set::ReplicaState;
+use crate::operations::blob_ops::BlobOperations;
 use crate::operations::conversions::try_record_from_grpc;
 use crate::operations::payload_ops::PayloadOps;
 use crate::operations::point_ops::{PointOperations, WriteOrdering};
@@ -52,10 +54,11 @@ use crate::operations::vector_ops::VectorOperations;
 use crate::operations::{CollectionUpdateOperations, FieldIndexOperations, OperationWithClockTag};
 use crate::shards::channel_service::ChannelService;
 use crate::shards::conversions::{
-    internal_clear_payload, internal_clear_payload_by_filter, internal_create_index,
-    internal_delete_index, internal_delete_payload, internal_delete_points,
-    internal_delete_points_by_filter, internal_set_payload, internal_sync_points,
-    internal_upsert_points, try_scored_point_from_grpc,
+    internal_clear_payload, internal_clear_payload_by_filter, internal_conditional_upsert_points,
+    internal_create_index, internal_delete_blob, internal_delete_index, internal_delete_payload,
+    internal_delete_points, internal_delete_points_by_filter, internal_patch_payload,
+    internal_set_blob, internal_set_payload, internal_sync_points, internal_upsert_points,
+    try_scored_point_from_grpc,
 };
 use crate::shards::shard::{PeerId, ShardId};
 use crate::shards::shard_trait::ShardOperation;
@@ -267,12 +270,28 @@ impl RemoteShard {
                     .await?
                     .into_inner()
                 }
-                PointOperations::DeletePoints { ids } => {
+                PointOperations::UpsertPointsConditional(conditional_upsert) => {
+                    let request = &internal_conditional_upsert_points(
+                        shard_id,
+                        operation.clock_tag,
+                        collection_name,
+                        conditional_upsert,
+                        wait,
+                        ordering,
+                    )?;
+                    self.with_points_client(|mut client| async move {
+                        client.upsert(tonic::Request::new(request.clone())).await
+                    })
+                    .await?
+                    .into_inner()
+                }
+                PointOperations::DeletePoints { ids, if_version } => {
                     let request = &internal_delete_points(
                         shard_id,
                         operation.clock_tag,
                         collection_name,
                         ids,
+                        if_version,
                         wait,
                         ordering,
                     );
@@ -322,7 +341,7 @@ impl RemoteShard {
                         update_operation,
                         wait,
                         ordering,
-                    );
+                    )?;
                     self.with_points_client(|mut client| async move {
                         client
                             .update_vectors(tonic::Request::new(request.clone()))
@@ -454,6 +473,23 @@ impl RemoteShard {
                     .await?
                     .into_inner()
                 }
+                PayloadOps::PatchPayload(patch_payload) => {
+                    let request = &internal_patch_payload(
+                        shard_id,
+                        operation.clock_tag,
+                        collection_name,
+                        patch_payload,
+                        wait,
+                        ordering,
+                    );
+                    self.with_points_client(|mut client| async move {
+                        client
+                            .patch_payload(tonic::Request::new(request.clone()))
+                            .await
+                    })
+                    .await?
+                    .into_inner()
+                }
             },
             CollectionUpdateOperations::FieldIndexOperation(field_index_op) => match field_index_op
             {
@@ -492,6 +528,40 @@ impl RemoteShard {
                     .into_inner()
                 }
             },
+            CollectionUpdateOperations::BlobOperation(blob_op) => match blob_op {
+                BlobOperations::SetBlob(set_blob) => {
+                    let request = &internal_set_blob(
+                        shard_id,
+                        operation.clock_tag,
+                        collection_name,
+                        set_blob,
+                        wait,
+                        ordering,
+                    );
+                    self.with_points_client(|mut client| async move {
+                        client.set_blob(tonic::Request::new(request.clone())).await
+                    })
+                    .await?
+                    .into_inner()
+                }
+                BlobOperations::DeleteBlob(delete_blob) => {
+                    let request = &internal_delete_blob(
+                        shard_id,
+                        operation.clock_tag,
+                        collection_name,
+                        delete_blob,
+                        wait,
+                        ordering,
+                    );
+                    self.with_points_client(|mut client| async move {
+                        client
+                            .delete_blob(tonic::Request::new(request.clone()))
+                            .await
+                    })
+                    .await?
+                    .into_inner()
+                }
+            },
         };
         match point_operation_response.result {
             None => Err(CollectionError::service_error(
@@ -837,6 +907,37 @@ impl ShardOperation for RemoteShard {
         result.map_err(|e| e.into())
     }
 
+    async fn read_blob(
+        &self,
+        point_id: ExtendedPointId,
+        offset: usize,
+        limit: usize,
+    ) -> CollectionResult<Option<BlobChunk>> {
+        let get_blob_point = GetBlobPoint {
+            collection_name: self.collection_id.clone(),
+            id: Some(point_id.into()),
+            offset: Some(offset as u64),
+            limit: Some(limit as u64),
+            shard_key_selector: None,
+        };
+        let request = &GetBlobPointInternal {
+            get_blob_point: Some(get_blob_point),
+            shard_id: Some(self.id),
+        };
+
+        let get_blob_response = self
+            .with_points_client(|mut client| async move {
+                client.get_blob(tonic::Request::new(request.clone())).await
+            })
+            .await?
+            .into_inner();
+
+        Ok(get_blob_response.result.map(|chunk| BlobChunk {
+            data: chunk.data,
+            total_len: chunk.total_size as usize,
+        }))
+    }
+
     async fn query(
         &self,
         request: Arc<ShardQueryRequest>,
@@ -20,6 +20,8 @@ use tokio::runtime::Handle;
 use tokio::sync::{Mutex, RwLock};
 
 use super::local_shard::clock_map::RecoveryPoint;
+use super::local_shard::tiering::SegmentTiering;
+use super::local_shard::wal_tail::WalTail;
 use super::local_shard::LocalShard;
 use super::remote_shard::RemoteShard;
 use super::transfer::ShardTransfer;
@@ -353,6 +355,47 @@ impl ShardReplicaSet {
             .collect()
     }
 
+    /// Whether the local replica replays operations of this shard to a remote cluster
+    ///
+    /// Every active replica has its own WAL, so only one of them does that,
+    /// the one on the active peer with the lowest ID.
+    pub fn is_async_replication_source(&self) -> bool {
+        let replica_state = self.replica_state.read();
+        replica_state.is_local
+            && replica_state.active_peers().into_iter().min() == Some(replica_state.this_peer_id)
+    }
+
+    /// Read a batch of operations from the WAL of the local shard, see [`LocalShard::read_wal_batch`]
+    ///
+    /// Returns `None` if there is no plain local shard, e.g. while it is being transferred.
+    pub async fn read_local_wal(
+        &self,
+        offset: u64,
+        limit: usize,
+    ) -> CollectionResult<Option<WalTail>> {
+        match &*self.local.read().await {
+            Some(Shard::Local(local)) => local.read_wal_batch(offset, limit).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// Keep operations of the local WAL until they are replayed, see [`LocalShard::pin_wal`]
+    pub async fn pin_local_wal(&self, offset: Option<u64>) {
+        if let Some(Shard::Local(local)) = &*self.local.read().await {
+            local.pin_wal(offset);
+        }
+    }
+
+    /// Pin the local WAL before copying all points of the shard, see [`LocalShard::pin_wal_for_copy`]
+    ///
+    /// Returns `None` if there is no plain local shard, e.g. while it is being transferred.
+    pub async fn pin_local_wal_for_copy(&self) -> Option<u64> {
+        match &*self.local.read().await {
+            Some(Shard::Local(local)) => Some(local.pin_wal_for_copy()),
+            _ => None,
+        }
+    }
+
     /// Wait for a local shard to be initialized.
     ///
     /// Uses a blocking thread internally.
@@ -509,6 +552,11 @@ impl ShardReplicaSet {
         if let Some(removing_local) = removing_local {
             // stop ongoing tasks and delete data
             drop(removing_local);
+            SegmentTiering::clear(
+                &self.shard_path,
+                &self.shared_storage_config.segment_storage_config,
+            )
+            .await;
             LocalShard::clear(&self.shard_path).await?;
         }
         Ok(())
@@ -742,6 +790,24 @@ impl ShardReplicaSet {
         Ok(())
     }
 
+    /// Disable an unreachable peer locally and report its failure, the same way as on a failed
+    /// update. The last active replica of the shard is never disabled.
+    pub fn report_unreachable_peer(&self, peer_id: PeerId) {
+        if !self.peer_is_active(&peer_id) {
+            return;
+        }
+
+        let has_other_active_replica = self
+            .replica_state
+            .read()
+            .active_peers()
+            .into_iter()
+            .any(|id| id != peer_id && !self.is_locally_disabled(&id));
+        if has_other_active_replica {
+            self.add_locally_disabled(peer_id);
+        }
+    }
+
     fn init_remote_shards(
         shard_id: ShardId,
         collection_id: CollectionId,
@@ -2,13 +2,18 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use futures::FutureExt as _;
+use segment::blob_storage::BlobChunk;
 use segment::data_types::order_by::OrderBy;
 use segment::types::*;
 
 use super::ShardReplicaSet;
+use crate::collection_manager::optimizers::TrackerTelemetry;
 use crate::operations::consistency_params::ReadConsistency;
 use crate::operations::types::*;
 use crate::operations::universal_query::shard_query::ShardQueryRequest;
+use crate::shards::local_shard::force_optimize::ForcedOptimization;
+use crate::shards::local_shard::memory_spill::MemorySpill;
+use crate::shards::local_shard::vacuum::Vacuum;
 
 impl ShardReplicaSet {
     #[allow(clippy::too_many_arguments)]
@@ -119,6 +124,20 @@ impl ShardReplicaSet {
         .await
     }
 
+    pub async fn read_blob(
+        &self,
+        point_id: ExtendedPointId,
+        offset: usize,
+        limit: usize,
+        local_only: bool,
+    ) -> CollectionResult<Option<BlobChunk>> {
+        self.execute_read_operation(
+            |shard| async move { shard.read_blob(point_id, offset, limit).await }.boxed(),
+            local_only,
+        )
+        .await
+    }
+
     pub async fn info(&self, local_only: bool) -> CollectionResult<CollectionInfo> {
         self.execute_read_operation(
             |shard| async move { shard.info().await }.boxed(),
@@ -138,6 +157,48 @@ impl ShardReplicaSet {
         }
     }
 
+    pub async fn local_points_breakdown(&self) -> Option<LocalShardPointsBreakdown> {
+        let local = self.local.read().await;
+        local.as_ref().and_then(|shard| shard.points_breakdown())
+    }
+
+    pub async fn local_index_info(&self) -> CollectionResult<Option<Vec<SegmentIndexInfo>>> {
+        let local = self.local.read().await;
+        match &*local {
+            None => Ok(None),
+            Some(shard) => shard.index_info().await,
+        }
+    }
+
+    pub async fn local_optimizations_log(&self) -> Option<Vec<TrackerTelemetry>> {
+        let local = self.local.read().await;
+        local.as_ref().and_then(|shard| shard.optimizations_log())
+    }
+
+    pub async fn local_forced_optimization(&self) -> Option<ForcedOptimization> {
+        let local = self.local.read().await;
+        match &*local {
+            None => None,
+            Some(shard) => shard.forced_optimization().await,
+        }
+    }
+
+    pub async fn local_vacuum(&self, deleted_threshold: Option<f64>) -> Option<Vacuum> {
+        let local = self.local.read().await;
+        match &*local {
+            None => None,
+            Some(shard) => shard.vacuum(deleted_threshold).await,
+        }
+    }
+
+    pub async fn local_memory_spill(&self) -> Option<MemorySpill> {
+        let local = self.local.read().await;
+        match &*local {
+            None => None,
+            Some(shard) => shard.memory_spill().await,
+        }
+    }
+
     pub async fn query(
         &self,
         request: Arc<ShardQueryRequest>,
@@ -5,6 +5,7 @@ use super::{ReplicaSetState, ReplicaState, ShardReplicaSet, REPLICA_STATE_FILE};
 use crate::operations::types::{CollectionError, CollectionResult};
 use crate::save_on_disk::SaveOnDisk;
 use crate::shards::dummy_shard::DummyShard;
+use crate::shards::local_shard::tiering::SegmentTiering;
 use crate::shards::local_shard::LocalShard;
 use crate::shards::shard::{PeerId, Shard};
 use crate::shards::shard_config::ShardConfig;
@@ -101,6 +102,11 @@ impl ShardReplicaSet {
         // Try to restore local replica from specified shard snapshot directory
         let restore = async {
             if clear {
+                SegmentTiering::clear(
+                    &self.shard_path,
+                    &self.shared_storage_config.segment_storage_config,
+                )
+                .await;
                 LocalShard::clear(&self.shard_path).await?;
             }
 
@@ -4,12 +4,13 @@ use std::time::Duration;
 use futures::stream::FuturesUnordered;
 use futures::{FutureExt as _, StreamExt as _};
 use itertools::Itertools as _;
+use segment::types::{PointIdType, SeqNumberType};
 
 use super::{clock_set, ReplicaSetState, ReplicaState, ShardReplicaSet};
-use crate::operations::point_ops::WriteOrdering;
+use crate::operations::point_ops::{PointOperations, WriteOrdering};
 use crate::operations::types::{CollectionError, CollectionResult, UpdateResult, UpdateStatus};
 use crate::operations::{ClockTag, CollectionUpdateOperations, OperationWithClockTag};
-use crate::shards::shard::PeerId;
+use crate::shards::shard::{PeerId, Shard};
 use crate::shards::shard_trait::ShardOperation as _;
 
 /// Maximum number of attempts for applying an update with a new clock.
@@ -63,17 +64,27 @@ impl ShardReplicaSet {
         }
     }
 
+    /// Operations with a version guard are always ordered strongly: the guard is resolved once by
+    /// the leader, and the operation is replicated without it. Point versions differ between
+    /// replicas, so replicas must not compare them independently.
+    ///
     /// # Cancel safety
     ///
     /// This method is *not* cancel safe.
     pub async fn update_with_consistency(
         &self,
-        operation: CollectionUpdateOperations,
+        mut operation: CollectionUpdateOperations,
         wait: bool,
         ordering: WriteOrdering,
     ) -> CollectionResult<UpdateResult> {
         // `ShardReplicaSet::update` is not cancel safe, so this method is not cancel safe.
 
+        let ordering = if operation.has_version_guard() {
+            WriteOrdering::Strong
+        } else {
+            ordering
+        };
+
         let Some(leader_peer) = self.leader_peer_for_update(ordering) else {
             return Err(CollectionError::service_error(format!(
                 "Cannot update shard {}:{} with {ordering:?} ordering because no leader could be selected",
@@ -91,6 +102,10 @@ impl ShardReplicaSet {
                 WriteOrdering::Weak => None,
             };
 
+            if let Some((ids, if_version)) = operation.take_version_guard() {
+                self.resolve_version_guard(&ids, if_version).await?;
+            }
+
             self.update(operation, wait).await
         } else {
             // Forward the update to the designated leader
@@ -112,6 +127,75 @@ impl ShardReplicaSet {
         }
     }
 
+    /// Check the version guard of an operation against the local replica of the leader.
+    ///
+    /// Must be called under the write ordering lock, so that no other strongly ordered update is
+    /// applied between the check and the update.
+    async fn resolve_version_guard(
+        &self,
+        ids: &[PointIdType],
+        if_version: SeqNumberType,
+    ) -> CollectionResult<()> {
+        let this_peer_id = self.this_peer_id();
+        if !self.peer_is_active(&this_peer_id) {
+            return Err(CollectionError::service_error(format!(
+                "Can't check point versions of shard {}:{}, leader replica is not active",
+                self.collection_id, self.shard_id,
+            )));
+        }
+
+        match self.local.read().await.deref() {
+            Some(Shard::Local(local)) => local.check_point_versions(ids, if_version).await,
+            // Updates are forwarded to the transfer target after they are applied locally
+            Some(Shard::ForwardProxy(proxy)) => {
+                proxy
+                    .wrapped_shard
+                    .check_point_versions(ids, if_version)
+                    .await
+            }
+            Some(shard) => Err(CollectionError::service_error(format!(
+                "Can't check point versions of shard {}:{} on {}",
+                self.collection_id,
+                self.shard_id,
+                shard.variant_name(),
+            ))),
+            None => Err(CollectionError::service_error(format!(
+                "Can't check point versions of shard {}:{}, leader has no local replica",
+                self.collection_id, self.shard_id,
+            ))),
+        }
+    }
+
+    /// Delete expired points of the shard, see [`LocalShard::expired_points_filter`].
+    ///
+    /// Deletion is issued only by the leader of strongly ordered updates, and is applied to all
+    /// replicas through the regular update path.
+    /// Returns whether deletion was issued.
+    ///
+    /// [`LocalShard::expired_points_filter`]: crate::shards::local_shard::LocalShard::expired_points_filter
+    pub async fn delete_expired_points(&self) -> CollectionResult<bool> {
+        let this_peer_id = self.this_peer_id();
+        let is_leader = self.leader_peer_for_update(WriteOrdering::Strong) == Some(this_peer_id);
+        if !is_leader || !self.peer_is_active(&this_peer_id) {
+            return Ok(false);
+        }
+
+        let filter = match self.local.read().await.deref() {
+            Some(Shard::Local(local)) => local.expired_points_filter().await?,
+            _ => None,
+        };
+        let Some(filter) = filter else {
+            return Ok(false);
+        };
+
+        let operation = CollectionUpdateOperations::PointOperation(
+            PointOperations::DeletePointsByFilter(filter),
+        );
+        self.update_with_consistency(operation, false, WriteOrdering::Strong)
+            .await?;
+        Ok(true)
+    }
+
     /// Designated a leader replica for the update based on the WriteOrdering
     fn leader_peer_for_update(&self, ordering: WriteOrdering) -> Option<PeerId> {
         match ordering {
@@ -509,7 +593,7 @@ mod tests {
     use crate::config::*;
     use crate::operations::types::VectorsConfig;
     use crate::operations::vector_params_builder::VectorParamsBuilder;
-    use crate::optimizers_builder::OptimizersConfig;
+    use crate::optimizers_builder::{MergePolicy, OptimizersConfig};
     use crate::shards::replica_set::{AbortShardTransfer, ChangePeerState};
 
     #[tokio::test]
@@ -539,6 +623,8 @@ mod tests {
         indexing_threshold: Some(50_000),
         flush_interval_sec: 30,
         max_optimization_threads: Some(2),
+        merge_policy: MergePolicy::SizeTiered,
+        cpu_budget: None,
     };
 
     async fn new_shard_replica_set(collection_dir: &TempDir) -> ShardReplicaSet {
@@ -548,6 +634,8 @@ mod tests {
         let wal_config = WalConfig {
             wal_capacity_mb: 1,
             wal_segments_ahead: 0,
+            wal_compression: Default::default(),
+            wal_durability: Default::default(),
         };
 
         let collection_params = CollectionParams {
@@ -555,6 +643,7 @@ mod tests {
             shard_number: NonZeroU32::new(4).unwrap(),
             replication_factor: NonZeroU32::new(3).unwrap(),
             write_consistency_factor: NonZeroU32::new(2).unwrap(),
+            storage_engine: None,
             ..CollectionParams::empty()
         };
 
@@ -564,6 +653,12 @@ mod tests {
             wal_config,
             hnsw_config: Default::default(),
             quantization_config: None,
+            tiering_config: None,
+            ttl_config: None,
+            async_replication_config: None,
+            inference_config: None,
+            quota_config: None,
+            multitenancy_config: None,
         };
 
         let shared_config = Arc::new(RwLock::new(config.clone()));
@@ -6,9 +6,15 @@ use common::types::TelemetryDetail;
 
 use super::local_shard::clock_map::RecoveryPoint;
 use super::update_tracker::UpdateTracker;
-use crate::operations::types::{CollectionError, CollectionResult};
+use crate::collection_manager::optimizers::TrackerTelemetry;
+use crate::operations::types::{
+    CollectionError, CollectionResult, LocalShardPointsBreakdown, SegmentIndexInfo,
+};
 use crate::shards::dummy_shard::DummyShard;
 use crate::shards::forward_proxy_shard::ForwardProxyShard;
+use crate::shards::local_shard::force_optimize::ForcedOptimization;
+use crate::shards::local_shard::memory_spill::MemorySpill;
+use crate::shards::local_shard::vacuum::Vacuum;
 use crate::shards::local_shard::LocalShard;
 use crate::shards::proxy_shard::ProxyShard;
 use crate::shards::queue_proxy_shard::QueueProxyShard;
@@ -75,6 +81,76 @@ impl Shard {
         telemetry
     }
 
+    /// Point counts of the segments, `None` for a dummy shard
+    pub fn points_breakdown(&self) -> Option<LocalShardPointsBreakdown> {
+        match self {
+            Shard::Local(local_shard) => Some(local_shard.points_breakdown()),
+            Shard::Proxy(proxy_shard) => Some(proxy_shard.points_breakdown()),
+            Shard::ForwardProxy(proxy_shard) => Some(proxy_shard.points_breakdown()),
+            Shard::QueueProxy(proxy_shard) => Some(proxy_shard.points_breakdown()),
+            Shard::Dummy(_) => None,
+        }
+    }
+
+    /// Statistics of vector indexes of the segments, `None` for a dummy shard
+    pub async fn index_info(&self) -> CollectionResult<Option<Vec<SegmentIndexInfo>>> {
+        let info = match self {
+            Shard::Local(local_shard) => local_shard.index_info().await?,
+            Shard::Proxy(proxy_shard) => proxy_shard.index_info().await?,
+            Shard::ForwardProxy(proxy_shard) => proxy_shard.index_info().await?,
+            Shard::QueueProxy(proxy_shard) => proxy_shard.index_info().await?,
+            Shard::Dummy(_) => return Ok(None),
+        };
+        Ok(Some(info))
+    }
+
+    /// Last optimizations of the shard, `None` for a dummy shard
+    pub fn optimizations_log(&self) -> Option<Vec<TrackerTelemetry>> {
+        match self {
+            Shard::Local(local_shard) => Some(local_shard.optimizations_log()),
+            Shard::Proxy(proxy_shard) => Some(proxy_shard.optimizations_log()),
+            Shard::ForwardProxy(proxy_shard) => Some(proxy_shard.optimizations_log()),
+            Shard::QueueProxy(proxy_shard) => Some(proxy_shard.optimizations_log()),
+            Shard::Dummy(_) => None,
+        }
+    }
+
+    /// Optimization of segments, which are not indexed yet, `None` for a dummy shard
+    pub async fn forced_optimization(&self) -> Option<ForcedOptimization> {
+        let optimization = match self {
+            Shard::Local(local_shard) => local_shard.forced_optimization().await,
+            Shard::Proxy(proxy_shard) => proxy_shard.forced_optimization().await,
+            Shard::ForwardProxy(proxy_shard) => proxy_shard.forced_optimization().await,
+            Shard::QueueProxy(proxy_shard) => proxy_shard.forced_optimization().await,
+            Shard::Dummy(_) => return None,
+        };
+        Some(optimization)
+    }
+
+    /// Compaction of segments with many deleted points, `None` for a dummy shard
+    pub async fn vacuum(&self, deleted_threshold: Option<f64>) -> Option<Vacuum> {
+        let vacuum = match self {
+            Shard::Local(local_shard) => local_shard.vacuum(deleted_threshold).await,
+            Shard::Proxy(proxy_shard) => proxy_shard.vacuum(deleted_threshold).await,
+            Shard::ForwardProxy(proxy_shard) => proxy_shard.vacuum(deleted_threshold).await,
+            Shard::QueueProxy(proxy_shard) => proxy_shard.vacuum(deleted_threshold).await,
+            Shard::Dummy(_) => return None,
+        };
+        Some(vacuum)
+    }
+
+    /// Moving vectors of segments to disk, `None` for a dummy shard
+    pub async fn memory_spill(&self) -> Option<MemorySpill> {
+        let spill = match self {
+            Shard::Local(local_shard) => local_shard.memory_spill().await,
+            Shard::Proxy(proxy_shard) => proxy_shard.memory_spill().await,
+            Shard::ForwardProxy(proxy_shard) => proxy_shard.memory_spill().await,
+            Shard::QueueProxy(proxy_shard) => proxy_shard.memory_spill().await,
+            Shard::Dummy(_) => return None,
+        };
+        Some(spill)
+    }
+
     pub async fn create_snapshot(
         &self,
         temp_path: &Path,
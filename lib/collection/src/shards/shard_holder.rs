@@ -1,6 +1,6 @@
 use std::collections::{HashMap, HashSet};
 use std::fmt;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 use std::sync::Arc;
 
 use common::cpu::CpuBudget;
@@ -14,7 +14,7 @@ use tokio::sync::RwLock;
 use super::replica_set::AbortShardTransfer;
 use super::transfer::transfer_tasks_pool::TransferTasksPool;
 use crate::common::validate_snapshot_archive::validate_open_snapshot_archive;
-use crate::config::{CollectionConfig, ShardingMethod};
+use crate::config::{CollectionConfig, ShardingMethod, COLLECTION_CONFIG_FILE};
 use crate::hash_ring::HashRing;
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::shared_storage_config::SharedStorageConfig;
@@ -27,7 +27,7 @@ use crate::shards::local_shard::LocalShard;
 use crate::shards::replica_set::{ChangePeerState, ReplicaState, ShardReplicaSet}; // TODO rename ReplicaShard to ReplicaSetShard
 use crate::shards::shard::{PeerId, ShardId};
 use crate::shards::shard_config::{ShardConfig, ShardType};
-use crate::shards::shard_versioning::latest_shard_paths;
+use crate::shards::shard_versioning::{latest_shard_paths, versioned_shard_path};
 use crate::shards::transfer::{ShardTransfer, ShardTransferKey};
 use crate::shards::CollectionId;
 
@@ -940,6 +940,7 @@ impl ShardHolder {
             std::fs::create_dir_all(temp_dir)?;
         }
 
+        // Snapshot might be either a snapshot of the shard, or a snapshot of the whole collection
         let snapshot_file_name = snapshot_path.file_name().unwrap().to_string_lossy();
 
         let snapshot_path = snapshot_path.to_path_buf();
@@ -955,14 +956,19 @@ impl ShardHolder {
             cancel::blocking::spawn_cancel_on_token(
                 cancel.child_token(),
                 move |cancel| -> CollectionResult<_> {
-                    let mut tar = validate_open_snapshot_archive(snapshot_path)?;
+                    let tar = validate_open_snapshot_archive(&snapshot_path)?;
 
                     if cancel.is_cancelled() {
                         return Err(cancel::Error::Cancelled.into());
                     }
 
-                    tar.unpack(&snapshot_temp_dir)?;
-                    drop(tar);
+                    if is_collection_snapshot(&snapshot_path)? {
+                        // Collection snapshot contains all shards, restore only the requested one
+                        unpack_collection_snapshot_shard(tar, shard_id, &snapshot_temp_dir)?;
+                    } else {
+                        let mut tar = tar;
+                        tar.unpack(&snapshot_temp_dir)?;
+                    }
 
                     if cancel.is_cancelled() {
                         return Err(cancel::Error::Cancelled.into());
@@ -1075,3 +1081,72 @@ pub(crate) fn shard_not_found_error(shard_id: ShardId) -> CollectionError {
         what: format!("shard {shard_id}"),
     }
 }
+
+/// Path of the archive entry without leading `./`
+fn normalized_entry_path(entry: &tar::Entry<impl std::io::Read>) -> CollectionResult<PathBuf> {
+    Ok(entry
+        .path()?
+        .components()
+        .filter(|component| !matches!(component, Component::CurDir))
+        .collect())
+}
+
+/// Check whether the archive is a snapshot of the whole collection, rather than of a single shard.
+///
+/// Only collection snapshots have collection config in the root.
+fn is_collection_snapshot(snapshot_path: &Path) -> CollectionResult<bool> {
+    let mut tar = validate_open_snapshot_archive(snapshot_path)?;
+    for entry in tar.entries_with_seek()? {
+        if normalized_entry_path(&entry?)? == Path::new(COLLECTION_CONFIG_FILE) {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Unpack directory of a single shard from the collection snapshot into `target_dir`
+fn unpack_collection_snapshot_shard(
+    mut tar: tar::Archive<std::fs::File>,
+    shard_id: ShardId,
+    target_dir: &Path,
+) -> CollectionResult<()> {
+    // Collection snapshots always contain the initial version of shard directories
+    let shard_dir = versioned_shard_path(Path::new(""), shard_id, 0);
+
+    let mut unpacked = false;
+    for entry in tar.entries()? {
+        let mut entry = entry?;
+        let path = normalized_entry_path(&entry)?;
+        let Ok(relative_path) = path.strip_prefix(&shard_dir) else {
+            continue;
+        };
+        if relative_path.as_os_str().is_empty() {
+            continue;
+        }
+
+        if relative_path
+            .components()
+            .any(|component| !matches!(component, Component::Normal(_)))
+        {
+            return Err(CollectionError::bad_input(format!(
+                "Malformed snapshot, invalid entry path {}",
+                path.display(),
+            )));
+        }
+
+        let target_path = target_dir.join(relative_path);
+        if let Some(parent) = target_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        entry.unpack(&target_path)?;
+        unpacked = true;
+    }
+
+    if !unpacked {
+        return Err(CollectionError::bad_input(format!(
+            "Collection snapshot does not contain shard {shard_id}"
+        )));
+    }
+
+    Ok(())
+}
@@ -8,6 +8,8 @@ use serde::Serialize;
 
 use crate::collection_manager::optimizers::TrackerTelemetry;
 use crate::operations::types::OptimizersStatus;
+use crate::shards::local_shard::tiering::TieringTelemetry;
+use crate::shards::local_shard::wal_archive::WalArchiveTelemetry;
 use crate::shards::replica_set::ReplicaState;
 use crate::shards::shard::{PeerId, ShardId};
 
@@ -32,6 +34,10 @@ pub struct LocalShardTelemetry {
     pub variant_name: Option<String>,
     pub segments: Vec<SegmentTelemetry>,
     pub optimizations: OptimizerTelemetry,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tiering: Option<TieringTelemetry>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wal_archive: Option<WalArchiveTelemetry>,
 }
 
 #[derive(Serialize, Clone, Debug, JsonSchema, Default)]
@@ -57,6 +63,8 @@ impl Anonymize for LocalShardTelemetry {
             variant_name: self.variant_name.clone(),
             segments: self.segments.anonymize(),
             optimizations: self.optimizations.anonymize(),
+            tiering: self.tiering.anonymize(),
+            wal_archive: self.wal_archive.anonymize(),
         }
     }
 }
@@ -1,7 +1,15 @@
+use std::sync::Arc;
+
+use common::types::TelemetryDetail;
+use parking_lot::Mutex;
 use schemars::JsonSchema;
 use segment::common::anonymize::Anonymize;
+use segment::common::operation_time_statistics::{
+    OperationDurationStatistics, OperationDurationsAggregator,
+};
 use serde::Serialize;
 
+use crate::collection::async_replication::AsyncReplicationStatus;
 use crate::config::CollectionConfig;
 use crate::operations::types::ShardTransferInfo;
 use crate::shards::telemetry::ReplicaSetTelemetry;
@@ -13,6 +21,47 @@ pub struct CollectionTelemetry {
     pub config: CollectionConfig,
     pub shards: Vec<ReplicaSetTelemetry>,
     pub transfers: Vec<ShardTransferInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub async_replication: Option<AsyncReplicationStatus>,
+    pub requests: CollectionRequestsTelemetry,
+}
+
+/// Durations of requests to the collection, including all of its shards
+#[derive(Serialize, Clone, Debug, JsonSchema)]
+pub struct CollectionRequestsTelemetry {
+    pub searches: OperationDurationStatistics,
+    pub scrolls: OperationDurationStatistics,
+    pub updates: OperationDurationStatistics,
+}
+
+pub struct CollectionRequestsAggregators {
+    pub searches: Arc<Mutex<OperationDurationsAggregator>>,
+    pub scrolls: Arc<Mutex<OperationDurationsAggregator>>,
+    pub updates: Arc<Mutex<OperationDurationsAggregator>>,
+}
+
+impl CollectionRequestsAggregators {
+    pub fn new() -> Self {
+        Self {
+            searches: OperationDurationsAggregator::new(),
+            scrolls: OperationDurationsAggregator::new(),
+            updates: OperationDurationsAggregator::new(),
+        }
+    }
+
+    pub fn get_telemetry_data(&self, detail: TelemetryDetail) -> CollectionRequestsTelemetry {
+        CollectionRequestsTelemetry {
+            searches: self.searches.lock().get_statistics(detail),
+            scrolls: self.scrolls.lock().get_statistics(detail),
+            updates: self.updates.lock().get_statistics(detail),
+        }
+    }
+}
+
+impl Default for CollectionRequestsAggregators {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl CollectionTelemetry {
@@ -24,6 +73,20 @@ impl CollectionTelemetry {
             .map(|s| s.info.num_vectors)
             .sum()
     }
+
+    /// Number of segments and points of local shards, which are in the cold tier
+    pub fn count_cold(&self) -> (usize, usize) {
+        self.shards
+            .iter()
+            .flat_map(|shard| shard.local.as_ref())
+            .flat_map(|x| x.tiering.as_ref())
+            .fold((0, 0), |(segments, points), tiering| {
+                (
+                    segments + tiering.cold_segments,
+                    points + tiering.cold_points,
+                )
+            })
+    }
 }
 
 impl Anonymize for CollectionTelemetry {
@@ -34,6 +97,18 @@ impl Anonymize for CollectionTelemetry {
             init_time_ms: self.init_time_ms,
             shards: self.shards.anonymize(),
             transfers: vec![],
+            async_replication: None,
+            requests: self.requests.anonymize(),
+        }
+    }
+}
+
+impl Anonymize for CollectionRequestsTelemetry {
+    fn anonymize(&self) -> Self {
+        Self {
+            searches: self.searches.anonymize(),
+            scrolls: self.scrolls.anonymize(),
+            updates: self.updates.anonymize(),
         }
     }
 }
@@ -46,6 +121,12 @@ impl Anonymize for CollectionConfig {
             optimizer_config: self.optimizer_config.clone(),
             wal_config: self.wal_config.clone(),
             quantization_config: self.quantization_config.clone(),
+            tiering_config: self.tiering_config,
+            ttl_config: self.ttl_config.clone(),
+            async_replication_config: self.async_replication_config.clone(),
+            inference_config: self.inference_config.clone(),
+            quota_config: self.quota_config.clone(),
+            multitenancy_config: self.multitenancy_config.clone(),
         }
     }
 }
@@ -6,7 +6,7 @@ use crate::operations::point_ops::{PointOperations, PointStruct};
 use crate::operations::types::VectorsConfig;
 use crate::operations::vector_params_builder::VectorParamsBuilder;
 use crate::operations::{CollectionUpdateOperations, CreateIndex, FieldIndexOperations};
-use crate::optimizers_builder::OptimizersConfig;
+use crate::optimizers_builder::{MergePolicy, OptimizersConfig};
 
 pub const TEST_OPTIMIZERS_CONFIG: OptimizersConfig = OptimizersConfig {
     deleted_threshold: 0.9,
@@ -17,16 +17,21 @@ pub const TEST_OPTIMIZERS_CONFIG: OptimizersConfig = OptimizersConfig {
     indexing_threshold: Some(50_000),
     flush_interval_sec: 30,
     max_optimization_threads: Some(2),
+    merge_policy: MergePolicy::SizeTiered,
+    cpu_budget: None,
 };
 
 pub fn create_collection_config() -> CollectionConfig {
     let wal_config = WalConfig {
         wal_capacity_mb: 1,
         wal_segments_ahead: 0,
+        wal_compression: Default::default(),
+        wal_durability: Default::default(),
     };
 
     let collection_params = CollectionParams {
         vectors: VectorsConfig::Single(VectorParamsBuilder::new(4, Distance::Dot).build()),
+        storage_engine: None,
         ..CollectionParams::empty()
     };
 
@@ -41,6 +46,12 @@ pub fn create_collection_config() -> CollectionConfig {
         wal_config,
         hnsw_config: Default::default(),
         quantization_config: Default::default(),
+        tiering_config: None,
+        ttl_config: None,
+        async_replication_config: None,
+        inference_config: None,
+        quota_config: None,
+        multitenancy_config: None,
     }
 }
 
@@ -53,6 +64,7 @@ pub fn upsert_operation() -> CollectionUpdateOperations {
                 payload: Some(
                     serde_json::from_str(r#"{ "location": { "lat": 10.12, "lon": 32.12  } }"#).unwrap(),
                 ),
+                expires_at: None,
             },
             PointStruct {
                 id: 2.into(),
@@ -60,6 +72,7 @@ pub fn upsert_operation() -> CollectionUpdateOperations {
                 payload: Some(
                     serde_json::from_str(r#"{ "location": { "lat": 11.12, "lon": 34.82  } }"#).unwrap(),
                 ),
+                expires_at: None,
             },
             PointStruct {
                 id: 3.into(),
@@ -67,6 +80,7 @@ pub fn upsert_operation() -> CollectionUpdateOperations {
                 payload: Some(
                     serde_json::from_str(r#"{ "location": [ { "lat": 12.12, "lon": 34.82  }, { "lat": 12.2, "lon": 12.82  }] }"#).unwrap(),
                 ),
+                expires_at: None,
             },
             PointStruct {
                 id: 4.into(),
@@ -74,6 +88,7 @@ pub fn upsert_operation() -> CollectionUpdateOperations {
                 payload: Some(
                     serde_json::from_str(r#"{ "location": { "lat": 13.12, "lon": 34.82  } }"#).unwrap(),
                 ),
+                expires_at: None,
             },
             PointStruct {
                 id: 5.into(),
@@ -81,6 +96,7 @@ pub fn upsert_operation() -> CollectionUpdateOperations {
                 payload: Some(
                     serde_json::from_str(r#"{ "location": { "lat": 14.12, "lon": 32.12  } }"#).unwrap(),
                 ),
+                expires_at: None,
             },
 
         ]
@@ -100,5 +116,6 @@ pub fn create_payload_index_operation() -> CollectionUpdateOperations {
 pub fn delete_point_operation(idx: u64) -> CollectionUpdateOperations {
     CollectionUpdateOperations::PointOperation(PointOperations::DeletePoints {
         ids: vec![idx.into()],
+        if_version: None,
     })
 }
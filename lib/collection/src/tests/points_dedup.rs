@@ -39,6 +39,8 @@ async fn fixture() -> Collection {
     let wal_config = WalConfig {
         wal_capacity_mb: 1,
         wal_segments_ahead: 0,
+        wal_compression: Default::default(),
+        wal_durability: Default::default(),
     };
 
     let collection_params = CollectionParams {
@@ -46,6 +48,7 @@ async fn fixture() -> Collection {
         shard_number: NonZeroU32::new(SHARD_COUNT).unwrap(),
         replication_factor: NonZeroU32::new(1).unwrap(),
         write_consistency_factor: NonZeroU32::new(1).unwrap(),
+        storage_engine: None,
         ..CollectionParams::empty()
     };
 
@@ -55,6 +58,12 @@ async fn fixture() -> Collection {
         wal_config,
         hnsw_config: Default::default(),
         quantization_config: Default::default(),
+        tiering_config: None,
+        ttl_config: None,
+        async_replication_config: None,
+        inference_config: None,
+        quota_config: None,
+        multitenancy_config: None,
     };
 
     let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
@@ -112,6 +121,7 @@ async fn fixture() -> Collection {
                         "num".to_string(),
                         Value::from(-(*shard_id as i32)),
                     )]))),
+                    expires_at: None,
                 },
                 PointStruct {
                     id: DUPLICATE_POINT_ID,
@@ -122,6 +132,7 @@ async fn fixture() -> Collection {
                         "num".to_string(),
                         Value::from(100 - *shard_id as i32),
                     )]))),
+                    expires_at: None,
                 },
             ])),
         ));
@@ -36,6 +36,8 @@ async fn _test_snapshot_collection(node_type: NodeType) {
     let wal_config = WalConfig {
         wal_capacity_mb: 1,
         wal_segments_ahead: 0,
+        wal_compression: Default::default(),
+        wal_durability: Default::default(),
     };
 
     let collection_params = CollectionParams {
@@ -43,6 +45,7 @@ async fn _test_snapshot_collection(node_type: NodeType) {
         shard_number: NonZeroU32::new(4).unwrap(),
         replication_factor: NonZeroU32::new(3).unwrap(),
         write_consistency_factor: NonZeroU32::new(2).unwrap(),
+        storage_engine: None,
         ..CollectionParams::empty()
     };
 
@@ -52,6 +55,12 @@ async fn _test_snapshot_collection(node_type: NodeType) {
         wal_config,
         hnsw_config: Default::default(),
         quantization_config: Default::default(),
+        tiering_config: None,
+        ttl_config: None,
+        async_replication_config: None,
+        inference_config: None,
+        quota_config: None,
+        multitenancy_config: None,
     };
 
     let snapshots_path = Builder::new().prefix("test_snapshots").tempdir().unwrap();
@@ -91,7 +100,7 @@ async fn _test_snapshot_collection(node_type: NodeType) {
 
     let snapshots_temp_dir = Builder::new().prefix("temp_dir").tempdir().unwrap();
     let snapshot_description = collection
-        .create_snapshot(snapshots_temp_dir.path(), 0)
+        .create_snapshot(snapshots_temp_dir.path(), 0, None)
         .await
         .unwrap();
 
@@ -32,6 +32,7 @@ fn wrong_point_struct() -> PointStruct {
         id: 0.into(),
         vector: VectorStruct::Multi(vector_data).into(),
         payload: None,
+        expires_at: None,
     }
 }
 
@@ -63,6 +64,9 @@ fn validate_error_sparse_vector_points_batch() {
             payloads: None,
         },
         shard_key: None,
+        update_mode: None,
+        update_filter: None,
+        dedup_key: None,
     });
 }
 
@@ -71,6 +75,9 @@ fn validate_error_sparse_vector_points_list() {
     check_validation_error(PointsList {
         points: vec![wrong_point_struct()],
         shard_key: None,
+        update_mode: None,
+        update_filter: None,
+        dedup_key: None,
     });
 }
 
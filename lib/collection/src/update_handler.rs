@@ -4,8 +4,8 @@ use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 
-use common::cpu::CpuBudget;
-use common::panic;
+use common::cpu::{CpuBudget, CpuPermit};
+use common::{io_throttle, panic};
 use itertools::Itertools;
 use log::{debug, error, info, trace, warn};
 use parking_lot::Mutex;
@@ -20,10 +20,11 @@ use tokio::time::error::Elapsed;
 use tokio::time::{timeout, Duration};
 
 use crate::collection_manager::collection_updater::CollectionUpdater;
-use crate::collection_manager::holders::segment_holder::LockedSegmentHolder;
+use crate::collection_manager::holders::segment_holder::{LockedSegmentHolder, SegmentId};
 use crate::collection_manager::optimizers::segment_optimizer::SegmentOptimizer;
 use crate::collection_manager::optimizers::{Tracker, TrackerLog, TrackerStatus};
 use crate::common::stoppable_task::{spawn_stoppable, StoppableTaskHandle};
+use crate::config::DurabilityPolicy;
 use crate::operations::shared_storage_config::SharedStorageConfig;
 use crate::operations::types::{CollectionError, CollectionResult};
 use crate::operations::CollectionUpdateOperations;
@@ -308,6 +309,9 @@ impl UpdateHandler {
                     {
                         let segments = segments.clone();
                         move |stopped| {
+                            // Reads and writes of optimization interfere less with searches
+                            let _io_priority = io_throttle::lower_io_priority();
+
                             // Track optimizer status
                             let tracker = Tracker::start(optimizer.as_ref().name(), nsi.clone());
                             let tracker_handle = tracker.handle();
@@ -376,6 +380,56 @@ impl UpdateHandler {
         handles
     }
 
+    /// Budget of CPUs for optimizations of this shard
+    pub(crate) fn optimizer_cpu_budget(&self) -> &CpuBudget {
+        &self.optimizer_cpu_budget
+    }
+
+    /// Optimize the segment with `optimizer` right away, regardless of conditions of the optimizer.
+    ///
+    /// Outcome of the optimization is sent to `sender`. If the task is stopped before it starts,
+    /// `sender` is dropped.
+    pub(crate) async fn launch_forced_optimization(
+        &self,
+        optimizer: Arc<Optimizer>,
+        segment_id: SegmentId,
+        permit: CpuPermit,
+        sender: oneshot::Sender<CollectionResult<bool>>,
+    ) {
+        let optimizers_log = self.optimizers_log.clone();
+        let segments = self.segments.clone();
+
+        let handle = spawn_stoppable(
+            move |stopped| {
+                let _io_priority = io_throttle::lower_io_priority();
+
+                let tracker = Tracker::start(optimizer.name(), vec![segment_id]);
+                let tracker_handle = tracker.handle();
+                optimizers_log.lock().register(tracker);
+
+                let result =
+                    optimizer.optimize(segments.clone(), vec![segment_id], permit, stopped);
+                match &result {
+                    Ok(_) => tracker_handle.update(TrackerStatus::Done),
+                    Err(CollectionError::Cancelled { description }) => {
+                        tracker_handle.update(TrackerStatus::Cancelled(description.clone()));
+                    }
+                    Err(error) => {
+                        log::error!("Forced optimization error: {error}");
+                        segments.write().report_optimizer_error(error.clone());
+                        tracker_handle.update(TrackerStatus::Error(error.to_string()));
+                    }
+                }
+
+                let optimized = matches!(result, Ok(true));
+                let _ = sender.send(result);
+                optimized
+            },
+            None,
+        );
+        self.optimization_handles.lock().await.push(handle);
+    }
+
     /// Checks conditions for all optimizers and returns whether any is satisfied
     ///
     /// In other words, if this returns true we have pending optimizations.
@@ -559,15 +613,20 @@ impl UpdateHandler {
                     sender,
                     wait,
                 }) => {
-                    let flush_res = if wait {
-                        wal.lock().flush().map_err(|err| {
-                            CollectionError::service_error(format!(
-                                "Can't flush WAL before operation {} - {}",
-                                op_num, err
-                            ))
-                        })
-                    } else {
-                        Ok(())
+                    let flush_res = {
+                        let mut wal = wal.lock();
+                        // With other policies WAL is either already flushed on write,
+                        // or should never be flushed explicitly
+                        if wait && wal.durability() == DurabilityPolicy::Interval {
+                            wal.flush().map_err(|err| {
+                                CollectionError::service_error(format!(
+                                    "Can't flush WAL before operation {} - {}",
+                                    op_num, err
+                                ))
+                            })
+                        } else {
+                            Ok(())
+                        }
                     };
 
                     let operation_result = flush_res
@@ -642,19 +701,24 @@ impl UpdateHandler {
             }
 
             trace!("Attempting flushing");
-            let wal_flash_job = wal.lock().flush_async();
-
-            if let Err(err) = wal_flash_job.join() {
-                error!("Failed to flush wal: {:?}", err);
-                segments
-                    .write()
-                    .report_optimizer_error(WalError::WriteWalError(format!(
-                        "WAL flush error: {err:?}"
-                    )));
-                continue;
+            let durability = wal.lock().durability();
+
+            if durability != DurabilityPolicy::OsBuffered {
+                let wal_flash_job = wal.lock().flush_async();
+
+                if let Err(err) = wal_flash_job.join() {
+                    error!("Failed to flush wal: {:?}", err);
+                    segments
+                        .write()
+                        .report_optimizer_error(WalError::WriteWalError(format!(
+                            "WAL flush error: {err:?}"
+                        )));
+                    continue;
+                }
             }
 
-            let confirmed_version = Self::flush_segments(segments.clone());
+            let confirmed_version =
+                Self::flush_segments(segments.clone(), durability == DurabilityPolicy::Fsync);
             let confirmed_version = match confirmed_version {
                 Ok(version) => version,
                 Err(err) => {
@@ -692,11 +756,13 @@ impl UpdateHandler {
 
     /// Returns confirmed version after flush of all segments
     ///
+    /// If `sync` is set, waits for flushes already running in background instead of skipping them.
+    ///
     /// # Errors
     /// Returns an error on flush failure
-    fn flush_segments(segments: LockedSegmentHolder) -> OperationResult<SeqNumberType> {
+    fn flush_segments(segments: LockedSegmentHolder, sync: bool) -> OperationResult<SeqNumberType> {
         let read_segments = segments.read();
-        let flushed_version = read_segments.flush_all(false)?;
+        let flushed_version = read_segments.flush_all(sync)?;
         Ok(match read_segments.failed_operation.iter().cloned().min() {
             None => flushed_version,
             Some(failed_operation) => min(failed_operation, flushed_version),
@@ -1,7 +1,10 @@
+use std::borrow::Cow;
 use std::fmt::Debug;
 use std::marker::PhantomData;
 use std::path::Path;
 use std::result;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 use std::thread::JoinHandle;
 
 use io::file_operations::{atomic_save_json, read_json};
@@ -10,6 +13,9 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use wal::{Wal, WalOptions};
 
+use crate::common::wal_encryption::WalCipher;
+use crate::config::{DurabilityPolicy, WalCompression};
+
 #[derive(Error, Debug)]
 #[error("{0}")]
 pub enum WalError {
@@ -19,6 +25,8 @@ pub enum WalError {
     WriteWalError(String),
     #[error("Can't truncate WAL: {0}")]
     TruncateWalError(String),
+    #[error("Can't read WAL: {0}")]
+    ReadWalError(String),
     #[error("Operation rejected by WAL for old clock")]
     ClockRejected,
 }
@@ -46,6 +54,68 @@ enum TestRecord {
 
 pub(super) type Result<T> = result::Result<T, WalError>;
 
+/// First byte of compressed WAL entries.
+///
+/// Plain entries are CBOR or MessagePack encoded structures, neither of which can start with it.
+const COMPRESSED_ENTRY_MARKER: u8 = 0xFF;
+
+const LZ4_CODEC: u8 = 1;
+const ZSTD_CODEC: u8 = 2;
+
+/// First byte of encrypted WAL entries, followed by the output of [`WalCipher::encrypt`].
+/// Encryption is applied on top of compression.
+const ENCRYPTED_ENTRY_MARKER: u8 = 0xFE;
+
+/// Entries smaller than this are not worth compressing
+const MIN_COMPRESSED_ENTRY_SIZE: usize = 128;
+
+fn encode_entry(data: Vec<u8>, compression: WalCompression) -> Vec<u8> {
+    if data.len() < MIN_COMPRESSED_ENTRY_SIZE {
+        return data;
+    }
+
+    let (codec, compressed) = match compression {
+        WalCompression::None => return data,
+        WalCompression::Lz4 => (LZ4_CODEC, lz4_flex::compress_prepend_size(&data)),
+        WalCompression::Zstd => {
+            match zstd::bulk::compress(&data, zstd::DEFAULT_COMPRESSION_LEVEL) {
+                Ok(compressed) => (ZSTD_CODEC, compressed),
+                Err(err) => {
+                    log::warn!("Failed to compress WAL entry, storing it uncompressed: {err}");
+                    return data;
+                }
+            }
+        }
+    };
+
+    // Incompressible data, keep it as is
+    if compressed.len() + 2 >= data.len() {
+        return data;
+    }
+
+    let mut entry = Vec::with_capacity(compressed.len() + 2);
+    entry.push(COMPRESSED_ENTRY_MARKER);
+    entry.push(codec);
+    entry.extend_from_slice(&compressed);
+    entry
+}
+
+fn decode_entry(entry: &[u8]) -> std::io::Result<Cow<[u8]>> {
+    match entry {
+        [COMPRESSED_ENTRY_MARKER, LZ4_CODEC, data @ ..] => {
+            lz4_flex::decompress_size_prepended(data)
+                .map(Cow::Owned)
+                .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))
+        }
+        [COMPRESSED_ENTRY_MARKER, ZSTD_CODEC, data @ ..] => zstd::decode_all(data).map(Cow::Owned),
+        [COMPRESSED_ENTRY_MARKER, codec, ..] => Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("unknown WAL entry codec {codec}"),
+        )),
+        _ => Ok(Cow::Borrowed(entry)),
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 struct WalState {
     pub ack_index: u64,
@@ -70,6 +140,15 @@ pub struct SerdeWal<R> {
     options: WalOptions,
     /// First index of our logical WAL.
     first_index: Option<u64>,
+    /// Compression applied to new entries
+    compression: WalCompression,
+    /// Encryption of entries at rest
+    cipher: Option<Arc<WalCipher>>,
+    /// When written entries are fsynced to disk
+    durability: DurabilityPolicy,
+    /// Segments with entries at or after any of these indices are not removed, even if
+    /// acknowledged. Used to keep segments until they are archived or replicated.
+    truncation_limits: Vec<Arc<AtomicU64>>,
 }
 
 const FIRST_INDEX_FILE: &str = "first-index";
@@ -100,22 +179,94 @@ impl<R: DeserializeOwned + Serialize + Debug> SerdeWal<R> {
             wal,
             options: wal_options,
             first_index,
+            compression: WalCompression::None,
+            cipher: None,
+            durability: DurabilityPolicy::default(),
+            truncation_limits: Vec::new(),
         })
     }
 
-    /// Write a record to the WAL but does guarantee durability.
+    /// Compress entries written from now on.
+    /// Entries are decompressed on read regardless of this setting.
+    pub fn with_compression(mut self, compression: WalCompression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Encrypt entries written from now on.
+    /// The cipher is also required to read previously encrypted entries.
+    pub fn with_cipher(mut self, cipher: Option<Arc<WalCipher>>) -> Self {
+        self.cipher = cipher;
+        self
+    }
+
+    /// Set when written entries are fsynced to disk
+    pub fn with_durability(mut self, durability: DurabilityPolicy) -> Self {
+        self.durability = durability;
+        self
+    }
+
+    pub fn durability(&self) -> DurabilityPolicy {
+        self.durability
+    }
+
+    /// Keep segments with entries at or after the given index on disk, even if acknowledged.
+    /// Limit is read on every acknowledgement, so it can be moved by the owner.
+    /// Multiple limits may be set, the lowest one applies.
+    pub fn with_truncation_limit(mut self, truncation_limit: Arc<AtomicU64>) -> Self {
+        self.truncation_limits.push(truncation_limit);
+        self
+    }
+
+    fn encrypt_entry(&self, entry: Vec<u8>) -> Result<Vec<u8>> {
+        let Some(cipher) = &self.cipher else {
+            return Ok(entry);
+        };
+        let encrypted = cipher
+            .encrypt(&entry)
+            .map_err(|err| WalError::WriteWalError(err.to_string()))?;
+
+        let mut result = Vec::with_capacity(encrypted.len() + 1);
+        result.push(ENCRYPTED_ENTRY_MARKER);
+        result.extend_from_slice(&encrypted);
+        Ok(result)
+    }
+
+    fn decrypt_entry<'a>(&self, entry: &'a [u8]) -> Result<Cow<'a, [u8]>> {
+        let [ENCRYPTED_ENTRY_MARKER, encrypted @ ..] = entry else {
+            return Ok(Cow::Borrowed(entry));
+        };
+        let cipher = self.cipher.as_ref().ok_or_else(|| {
+            WalError::ReadWalError("entry is encrypted, but no encryption key is set".to_string())
+        })?;
+        cipher
+            .decrypt(encrypted)
+            .map(Cow::Owned)
+            .map_err(|err| WalError::ReadWalError(format!("can't decrypt entry: {err}")))
+    }
+
+    /// Write a record to the WAL.
+    /// Does not guarantee durability, unless [`DurabilityPolicy::Fsync`] is used.
     pub fn write(&mut self, entity: &R) -> Result<u64> {
         // ToDo: Replace back to faster rmp, once this https://github.com/serde-rs/serde/issues/2055 solved
-        let binary_entity = serde_cbor::to_vec(&entity).unwrap();
-        self.wal
+        let binary_entity = encode_entry(serde_cbor::to_vec(&entity).unwrap(), self.compression);
+        let binary_entity = self.encrypt_entry(binary_entity)?;
+        let index = self
+            .wal
             .append(&binary_entity)
-            .map_err(|err| WalError::WriteWalError(format!("{err:?}")))
+            .map_err(|err| WalError::WriteWalError(format!("{err:?}")))?;
+
+        if self.durability == DurabilityPolicy::Fsync {
+            self.flush()?;
+        }
+
+        Ok(index)
     }
 
     pub fn read_all(
         &self,
         with_acknowledged: bool,
-    ) -> impl DoubleEndedIterator<Item = (u64, R)> + '_ {
+    ) -> impl DoubleEndedIterator<Item = Result<(u64, R)>> + '_ {
         if with_acknowledged {
             self.read(self.first_closed_index())
         } else {
@@ -123,7 +274,11 @@ impl<R: DeserializeOwned + Serialize + Debug> SerdeWal<R> {
         }
     }
 
-    pub fn read(&self, from: u64) -> impl DoubleEndedIterator<Item = (u64, R)> + '_ {
+    /// Read records starting from `from`.
+    ///
+    /// Entries which can't be decrypted or decompressed, for example because the WAL was written
+    /// with a different encryption key, are returned as [`WalError::ReadWalError`].
+    pub fn read(&self, from: u64) -> impl DoubleEndedIterator<Item = Result<(u64, R)>> + '_ {
         // We have to explicitly do `from..self.first_index() + self.len(false)`, instead of more
         // concise `from..=self.last_index()`, because if the WAL is empty, `Wal::last_index`
         // returns `Wal::first_index`, so we end up with `1..=1` instead of an empty range. 😕
@@ -131,13 +286,17 @@ impl<R: DeserializeOwned + Serialize + Debug> SerdeWal<R> {
         let to = self.first_index() + self.len(false);
 
         (from..to).map(move |idx| {
-            let record_bin = self.wal.entry(idx).expect("Can't read entry from WAL");
+            let entry = self.wal.entry(idx).expect("Can't read entry from WAL");
+            let decrypted = self.decrypt_entry(&entry)?;
+            let record_bin = decode_entry(&decrypted).map_err(|err| {
+                WalError::ReadWalError(format!("can't decompress entry {idx}: {err}"))
+            })?;
 
             let record: R = serde_cbor::from_slice(&record_bin)
                 .or_else(|_err| rmp_serde::from_slice(&record_bin))
                 .expect("Can't deserialize entry, probably corrupted WAL or version mismatch");
 
-            (idx, record)
+            Ok((idx, record))
         })
     }
 
@@ -182,9 +341,14 @@ impl<R: DeserializeOwned + Serialize + Debug> SerdeWal<R> {
     ///
     /// * `until_index` - the newest no longer required record sequence number
     pub(super) fn ack(&mut self, until_index: u64) -> Result<()> {
-        // Truncate WAL
+        // Truncate WAL, but keep segments which are still needed by the owners of the limits
+        let truncate_until = self
+            .truncation_limits
+            .iter()
+            .map(|limit| limit.load(Ordering::Acquire))
+            .fold(until_index, u64::min);
         self.wal
-            .prefix_truncate(until_index)
+            .prefix_truncate(truncate_until)
             .map_err(|err| WalError::TruncateWalError(format!("{err:?}")))?;
 
         // Acknowledge index should not decrease
@@ -294,7 +458,7 @@ mod tests {
             assert_eq!(metadata.size() as usize, capacity);
         };
 
-        for (_idx, rec) in serde_wal.read(0) {
+        for (_idx, rec) in serde_wal.read(0).map(Result::unwrap) {
             println!("{rec:?}");
         }
 
@@ -302,7 +466,7 @@ mod tests {
 
         serde_wal.write(&record).expect("Can't write");
 
-        let mut read_iterator = serde_wal.read(0);
+        let mut read_iterator = serde_wal.read(0).map(Result::unwrap);
 
         let (idx1, record1) = read_iterator.next().unwrap();
         let (idx2, record2) = read_iterator.next().unwrap();
@@ -323,4 +487,153 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_wal_truncation_limit() {
+        let dir = Builder::new().prefix("wal_test").tempdir().unwrap();
+        let wal_options = WalOptions {
+            segment_capacity: 4096,
+            segment_queue_len: 0,
+        };
+        let limit = Arc::new(AtomicU64::new(0));
+
+        let mut serde_wal: SerdeWal<TestRecord> =
+            SerdeWal::new(dir.path().to_str().unwrap(), wal_options)
+                .unwrap()
+                .with_truncation_limit(limit.clone());
+
+        for data in 0..1000 {
+            let record = TestRecord::Struct1(TestInternalStruct1 { data });
+            serde_wal.write(&record).expect("Can't write");
+        }
+
+        // Acknowledged segments are kept on disk, while the limit is not moved
+        serde_wal.ack(900).unwrap();
+        assert_eq!(serde_wal.first_closed_index(), 0);
+        assert_eq!(serde_wal.first_index(), 900);
+        assert_eq!(serde_wal.len(false), 100);
+
+        limit.store(900, Ordering::Release);
+        serde_wal.ack(900).unwrap();
+        assert!(serde_wal.first_closed_index() > 0);
+        assert!(serde_wal.first_closed_index() <= 900);
+        assert_eq!(serde_wal.first_index(), 900);
+        assert_eq!(serde_wal.len(false), 100);
+    }
+
+    #[test]
+    fn test_wal_compression() {
+        let dir = Builder::new().prefix("wal_test").tempdir().unwrap();
+        let wal_options = || WalOptions {
+            segment_capacity: 1024 * 1024,
+            segment_queue_len: 0,
+        };
+
+        let record = |data| -> Vec<TestInternalStruct1> {
+            (0..100).map(|_| TestInternalStruct1 { data }).collect()
+        };
+
+        for (data, compression) in [
+            WalCompression::None,
+            WalCompression::Lz4,
+            WalCompression::Zstd,
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let mut serde_wal: SerdeWal<Vec<TestInternalStruct1>> =
+                SerdeWal::new(dir.path().to_str().unwrap(), wal_options())
+                    .unwrap()
+                    .with_compression(compression);
+            serde_wal.write(&record(data)).expect("Can't write");
+        }
+
+        // Entries written with any compression are readable
+        let serde_wal: SerdeWal<Vec<TestInternalStruct1>> =
+            SerdeWal::new(dir.path().to_str().unwrap(), wal_options()).unwrap();
+        let read: Vec<_> = serde_wal
+            .read(0)
+            .map(Result::unwrap)
+            .map(|(_, record)| {
+                assert_eq!(record.len(), 100);
+                record[0].data
+            })
+            .collect();
+        assert_eq!(read, vec![0, 1, 2]);
+
+        let entry = serde_cbor::to_vec(&record(42)).unwrap();
+        for compression in [WalCompression::Lz4, WalCompression::Zstd] {
+            let compressed = encode_entry(entry.clone(), compression);
+            assert!(compressed.len() < entry.len());
+            assert_eq!(
+                decode_entry(&compressed).unwrap().as_ref(),
+                entry.as_slice()
+            );
+        }
+
+        // Small entries are stored as is
+        let small = serde_cbor::to_vec(&TestInternalStruct1 { data: 42 }).unwrap();
+        assert_eq!(encode_entry(small.clone(), WalCompression::Lz4), small);
+    }
+
+    #[test]
+    fn test_wal_encryption() {
+        let dir = Builder::new().prefix("wal_test").tempdir().unwrap();
+        let wal_options = || WalOptions {
+            segment_capacity: 1024 * 1024,
+            segment_queue_len: 0,
+        };
+        let cipher =
+            WalCipher::from_hex("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f")
+                .map(Arc::new)
+                .unwrap();
+
+        let mut serde_wal: SerdeWal<TestRecord> =
+            SerdeWal::new(dir.path().to_str().unwrap(), wal_options())
+                .unwrap()
+                .with_compression(WalCompression::Zstd)
+                .with_cipher(Some(cipher.clone()));
+        serde_wal
+            .write(&TestRecord::Struct2(TestInternalStruct2 { a: 12, b: 13 }))
+            .expect("Can't write");
+        drop(serde_wal);
+
+        let serde_wal: SerdeWal<TestRecord> =
+            SerdeWal::new(dir.path().to_str().unwrap(), wal_options())
+                .unwrap()
+                .with_cipher(Some(cipher));
+        let (_, record) = serde_wal.read(0).next().unwrap().unwrap();
+        match record {
+            TestRecord::Struct1(_) => panic!("Wrong structure"),
+            TestRecord::Struct2(x) => {
+                assert_eq!(x.a, 12);
+                assert_eq!(x.b, 13);
+            }
+        }
+
+        // Encrypted entries can't be read without the key
+        let serde_wal: SerdeWal<TestRecord> =
+            SerdeWal::new(dir.path().to_str().unwrap(), wal_options()).unwrap();
+        let entry = serde_wal.wal.entry(0).unwrap();
+        assert_eq!(entry[0], ENCRYPTED_ENTRY_MARKER);
+        assert!(serde_wal.decrypt_entry(&entry).is_err());
+        assert!(matches!(
+            serde_wal.read(0).next(),
+            Some(Err(WalError::ReadWalError(_))),
+        ));
+
+        // Nor with a different key
+        let other_cipher =
+            WalCipher::from_hex("1f1e1d1c1b1a191817161514131211100f0e0d0c0b0a09080706050403020100")
+                .map(Arc::new)
+                .unwrap();
+        let serde_wal: SerdeWal<TestRecord> =
+            SerdeWal::new(dir.path().to_str().unwrap(), wal_options())
+                .unwrap()
+                .with_cipher(Some(other_cipher));
+        assert!(matches!(
+            serde_wal.read_all(false).next(),
+            Some(Err(WalError::ReadWalError(_))),
+        ));
+    }
 }
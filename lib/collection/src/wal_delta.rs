@@ -121,7 +121,7 @@ impl RecoverableWal {
             self.wal
                 .lock()
                 .read_all(true)
-                .map(|(op_num, op)| (op_num, op.clock_tag)),
+                .map(|record| record.map(|(op_num, op)| (op_num, op.clock_tag))),
             recovery_point,
             newest_clocks,
             oldest_clocks,
@@ -135,8 +135,8 @@ impl RecoverableWal {
             .wal
             .lock()
             .read(append_from)
-            .map(|(_, op)| op)
-            .collect::<Vec<_>>();
+            .map(|record| record.map(|(_, op)| op))
+            .collect::<crate::wal::Result<Vec<_>>>()?;
         for update in operations.iter_mut() {
             let (_, _) = self.lock_and_write(update).await?;
         }
@@ -160,7 +160,7 @@ impl RecoverableWal {
 /// If `None` - the remote WAL is already equal, and we don't have to send any records.
 /// If `Err` - no delta can be resolved.
 fn resolve_wal_delta(
-    operations: impl DoubleEndedIterator<Item = (u64, Option<ClockTag>)>,
+    operations: impl DoubleEndedIterator<Item = crate::wal::Result<(u64, Option<ClockTag>)>>,
     mut recovery_point: RecoveryPoint,
     mut newest_clocks: RecoveryPoint,
     mut oldest_clocks: RecoveryPoint,
@@ -212,7 +212,9 @@ fn resolve_wal_delta(
 
     let mut last_op_num = None;
 
-    for (op_num, clock_tag) in operations.rev() {
+    for record in operations.rev() {
+        let (op_num, clock_tag) = record.map_err(|err| WalDeltaError::ReadWal(err.to_string()))?;
+
         // We cannot resolve a delta if we have untagged records
         let Some(clock_tag) = clock_tag else {
             return Err(WalDeltaError::UntaggedRecords);
@@ -254,6 +256,8 @@ pub enum WalDeltaError {
     UntaggedRecords,
     #[error("cannot find slice of WAL records that satisfies the recovery point")]
     NotFound,
+    #[error("cannot read WAL records: {0}")]
+    ReadWal(String),
 }
 
 #[cfg(test)]
@@ -303,6 +307,7 @@ mod tests {
                 id: id.into(),
                 vector: VectorStruct::from(vec![1.0, 2.0, 3.0]).into(),
                 payload: None,
+                expires_at: None,
             }]),
         ))
     }
@@ -385,8 +390,9 @@ mod tests {
             .wal
             .lock()
             .read(0)
-            .zip(b_wal.wal.lock().read(0))
-            .zip(c_wal.wal.lock().read(0))
+            .map(Result::unwrap)
+            .zip(b_wal.wal.lock().read(0).map(Result::unwrap))
+            .zip(c_wal.wal.lock().read(0).map(Result::unwrap))
             .for_each(|((a, b), c)| {
                 assert_eq!(a, b);
                 assert_eq!(b, c);
@@ -493,8 +499,9 @@ mod tests {
             .wal
             .lock()
             .read(0)
-            .zip(b_wal.wal.lock().read(0))
-            .zip(c_wal.wal.lock().read(0))
+            .map(Result::unwrap)
+            .zip(b_wal.wal.lock().read(0).map(Result::unwrap))
+            .zip(c_wal.wal.lock().read(0).map(Result::unwrap))
             .for_each(|((a, b), c)| {
                 assert_eq!(a, b);
                 assert_eq!(b, c);
@@ -588,8 +595,9 @@ mod tests {
             .wal
             .lock()
             .read(0)
-            .zip(b_wal.wal.lock().read(0))
-            .zip(c_wal.wal.lock().read(0))
+            .map(Result::unwrap)
+            .zip(b_wal.wal.lock().read(0).map(Result::unwrap))
+            .zip(c_wal.wal.lock().read(0).map(Result::unwrap))
             .for_each(|((a, b), c)| {
                 assert_eq!(a, b);
                 assert_eq!(b, c);
@@ -693,8 +701,9 @@ mod tests {
             .wal
             .lock()
             .read(0)
-            .zip(b_wal.wal.lock().read(0))
-            .zip(c_wal.wal.lock().read(0))
+            .map(Result::unwrap)
+            .zip(b_wal.wal.lock().read(0).map(Result::unwrap))
+            .zip(c_wal.wal.lock().read(0).map(Result::unwrap))
             .for_each(|((a, b), c)| {
                 assert_eq!(a, b);
                 assert_eq!(b, c);
@@ -798,13 +807,15 @@ mod tests {
             .wal
             .lock()
             .read(0)
-            .zip(c_wal.wal.lock().read(0))
+            .map(Result::unwrap)
+            .zip(c_wal.wal.lock().read(0).map(Result::unwrap))
             .all(|(a, c)| a == c));
         assert!(b_wal
             .wal
             .lock()
             .read(0)
-            .zip(c_wal.wal.lock().read(0))
+            .map(Result::unwrap)
+            .zip(c_wal.wal.lock().read(0).map(Result::unwrap))
             .all(|(b, c)| b == c));
 
         // All WALs should have 3 operations
@@ -827,18 +838,21 @@ mod tests {
             .wal
             .lock()
             .read(0)
+            .map(Result::unwrap)
             .map(|(_, op)| get_point(op).id)
             .collect::<HashSet<_>>();
         let b_wal_point_ids = b_wal
             .wal
             .lock()
             .read(0)
+            .map(Result::unwrap)
             .map(|(_, op)| get_point(op).id)
             .collect::<HashSet<_>>();
         let c_wal_point_ids = c_wal
             .wal
             .lock()
             .read(0)
+            .map(Result::unwrap)
             .map(|(_, op)| get_point(op).id)
             .collect::<HashSet<_>>();
         (1..=3).for_each(|i| {
@@ -1278,6 +1292,7 @@ mod tests {
                             )
                             .into(),
                             payload: None,
+                            expires_at: None,
                         }]),
                     ));
                 let operation = OperationWithClockTag::new(bare_operation, Some(clock_tag));
@@ -1324,6 +1339,7 @@ mod tests {
                             )
                             .into(),
                             payload: None,
+                            expires_at: None,
                         }]),
                     ));
                 let operation = OperationWithClockTag::new(bare_operation, Some(clock_tag));
@@ -1385,7 +1401,10 @@ mod tests {
                 .windows(2)
                 .for_each(|wals| {
                     assert!(
-                        wals[0].read(0).eq(wals[1].read(0)),
+                        wals[0]
+                            .read(0)
+                            .map(Result::unwrap)
+                            .eq(wals[1].read(0).map(Result::unwrap)),
                         "all WALs must have the same entries",
                     );
                 });
@@ -1475,7 +1494,7 @@ mod tests {
             wal.wal
                 .lock()
                 .read_all(true)
-                .map(|(op_num, op)| (op_num, op.clock_tag)),
+                .map(|record| record.map(|(op_num, op)| (op_num, op.clock_tag))),
             recovery_point,
             newest_clocks,
             RecoveryPoint::default(),
@@ -1502,7 +1521,7 @@ mod tests {
             wal.wal
                 .lock()
                 .read_all(true)
-                .map(|(op_num, op)| (op_num, op.clock_tag)),
+                .map(|record| record.map(|(op_num, op)| (op_num, op.clock_tag))),
             recovery_point,
             newest_clocks,
             RecoveryPoint::default(),
@@ -1528,7 +1547,7 @@ mod tests {
             wal.wal
                 .lock()
                 .read_all(true)
-                .map(|(op_num, op)| (op_num, op.clock_tag)),
+                .map(|record| record.map(|(op_num, op)| (op_num, op.clock_tag))),
             recovery_point,
             newest_clocks,
             RecoveryPoint::default(),
@@ -1559,7 +1578,7 @@ mod tests {
             wal.wal
                 .lock()
                 .read_all(true)
-                .map(|(op_num, op)| (op_num, op.clock_tag)),
+                .map(|record| record.map(|(op_num, op)| (op_num, op.clock_tag))),
             recovery_point,
             newest_clocks,
             oldest_clocks,
@@ -1585,7 +1604,7 @@ mod tests {
             wal.wal
                 .lock()
                 .read_all(true)
-                .map(|(op_num, op)| (op_num, op.clock_tag)),
+                .map(|record| record.map(|(op_num, op)| (op_num, op.clock_tag))),
             recovery_point,
             newest_clocks,
             RecoveryPoint::default(),
@@ -1601,6 +1620,7 @@ mod tests {
             wal.wal
                 .lock()
                 .read(0)
+                .map(Result::unwrap)
                 // Only take records with clock tags
                 .filter_map(|(_, operation)| operation.clock_tag)
                 // Clock tags must be equal or higher to cutoff point
@@ -78,7 +78,7 @@ async fn test_collection_updater_with_shards(shard_number: u32) {
 
     let search_res = collection
         .search(
-            search_request.into(),
+            search_request.try_into().unwrap(),
             None,
             &ShardSelectorInternal::All,
             None,
@@ -146,7 +146,7 @@ async fn test_collection_search_with_payload_and_vector_with_shards(shard_number
 
     let search_res = collection
         .search(
-            search_request.into(),
+            search_request.try_into().unwrap(),
             None,
             &ShardSelectorInternal::All,
             None,
@@ -296,11 +296,13 @@ fn test_deserialization2() {
                 id: 0.into(),
                 vector: VectorStruct::from(vec![1.0, 0.0, 1.0, 1.0]).into(),
                 payload: None,
+                expires_at: None,
             },
             PointStruct {
                 id: 1.into(),
                 vector: VectorStruct::from(vec![1.0, 0.0, 1.0, 0.0]).into(),
                 payload: None,
+                expires_at: None,
             },
         ]
         .into(),
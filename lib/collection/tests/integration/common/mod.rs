@@ -8,7 +8,7 @@ use collection::collection::{Collection, RequestShardTransfer};
 use collection::config::{CollectionConfig, CollectionParams, WalConfig};
 use collection::operations::types::CollectionError;
 use collection::operations::vector_params_builder::VectorParamsBuilder;
-use collection::optimizers_builder::OptimizersConfig;
+use collection::optimizers_builder::{MergePolicy, OptimizersConfig};
 use collection::shards::channel_service::ChannelService;
 use collection::shards::collection_shard_distribution::CollectionShardDistribution;
 use collection::shards::replica_set::{AbortShardTransfer, ChangePeerState, ReplicaState};
@@ -32,6 +32,8 @@ pub const TEST_OPTIMIZERS_CONFIG: OptimizersConfig = OptimizersConfig {
     indexing_threshold: Some(50_000),
     flush_interval_sec: 30,
     max_optimization_threads: Some(2),
+    merge_policy: MergePolicy::SizeTiered,
+    cpu_budget: None,
 };
 
 #[cfg(test)]
@@ -40,11 +42,14 @@ pub async fn simple_collection_fixture(collection_path: &Path, shard_number: u32
     let wal_config = WalConfig {
         wal_capacity_mb: 1,
         wal_segments_ahead: 0,
+        wal_compression: Default::default(),
+        wal_durability: Default::default(),
     };
 
     let collection_params = CollectionParams {
         vectors: VectorParamsBuilder::new(4, Distance::Dot).build().into(),
         shard_number: NonZeroU32::new(shard_number).expect("Shard number can not be zero"),
+        storage_engine: None,
         ..CollectionParams::empty()
     };
 
@@ -54,6 +59,12 @@ pub async fn simple_collection_fixture(collection_path: &Path, shard_number: u32
         wal_config,
         hnsw_config: Default::default(),
         quantization_config: Default::default(),
+        tiering_config: None,
+        ttl_config: None,
+        async_replication_config: None,
+        inference_config: None,
+        quota_config: None,
+        multitenancy_config: None,
     };
 
     let snapshot_path = collection_path.join("snapshots");
@@ -36,6 +36,8 @@ pub async fn multi_vec_collection_fixture(collection_path: &Path, shard_number:
     let wal_config = WalConfig {
         wal_capacity_mb: 1,
         wal_segments_ahead: 0,
+        wal_compression: Default::default(),
+        wal_durability: Default::default(),
     };
 
     let vector_params1 = VectorParamsBuilder::new(4, Distance::Dot).build();
@@ -49,6 +51,7 @@ pub async fn multi_vec_collection_fixture(collection_path: &Path, shard_number:
     let collection_params = CollectionParams {
         vectors: VectorsConfig::Multi(vectors_config),
         shard_number: NonZeroU32::new(shard_number).expect("Shard number can not be zero"),
+        storage_engine: None,
         ..CollectionParams::empty()
     };
 
@@ -58,6 +61,12 @@ pub async fn multi_vec_collection_fixture(collection_path: &Path, shard_number:
         wal_config,
         hnsw_config: Default::default(),
         quantization_config: Default::default(),
+        tiering_config: None,
+        ttl_config: None,
+        async_replication_config: None,
+        inference_config: None,
+        quota_config: None,
+        multitenancy_config: None,
     };
 
     let snapshot_path = collection_path.join("snapshots");
@@ -92,6 +101,7 @@ async fn test_multi_vec_with_shards(shard_number: u32) {
             id: i.into(),
             vector: VectorStruct::from(vectors).into(),
             payload: Some(serde_json::from_str(r#"{"number": "John Doe"}"#).unwrap()),
+            expires_at: None,
         });
     }
     let insert_points = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
@@ -121,7 +131,7 @@ async fn test_multi_vec_with_shards(shard_number: u32) {
 
     let result = collection
         .search(
-            full_search_request.into(),
+            full_search_request.try_into().unwrap(),
             None,
             &ShardSelectorInternal::All,
             None,
@@ -154,7 +164,7 @@ async fn test_multi_vec_with_shards(shard_number: u32) {
 
     let result = collection
         .search(
-            failed_search_request.into(),
+            failed_search_request.try_into().unwrap(),
             None,
             &ShardSelectorInternal::All,
             None,
@@ -183,7 +193,7 @@ async fn test_multi_vec_with_shards(shard_number: u32) {
 
     let result = collection
         .search(
-            full_search_request.into(),
+            full_search_request.try_into().unwrap(),
             None,
             &ShardSelectorInternal::All,
             None,
@@ -31,6 +31,7 @@ async fn test_collection_paginated_search_with_shards(shard_number: u32) {
             id: i.into(),
             vector: VectorStruct::from(vec![i as f32, 0.0, 0.0, 0.0]).into(),
             payload: Some(serde_json::from_str(r#"{"number": "John Doe"}"#).unwrap()),
+            expires_at: None,
         });
     }
     let insert_points = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
@@ -56,7 +57,7 @@ async fn test_collection_paginated_search_with_shards(shard_number: u32) {
 
     let reference_result = collection
         .search(
-            full_search_request.into(),
+            full_search_request.try_into().unwrap(),
             None,
             &ShardSelectorInternal::All,
             None,
@@ -27,10 +27,13 @@ async fn _test_snapshot_and_recover_collection(node_type: NodeType) {
     let wal_config = WalConfig {
         wal_capacity_mb: 1,
         wal_segments_ahead: 0,
+        wal_compression: Default::default(),
+        wal_durability: Default::default(),
     };
 
     let collection_params = CollectionParams {
         vectors: VectorsConfig::Single(VectorParamsBuilder::new(4, Distance::Dot).build()),
+        storage_engine: None,
         ..CollectionParams::empty()
     };
 
@@ -40,6 +43,12 @@ async fn _test_snapshot_and_recover_collection(node_type: NodeType) {
         wal_config,
         hnsw_config: Default::default(),
         quantization_config: Default::default(),
+        tiering_config: None,
+        ttl_config: None,
+        async_replication_config: None,
+        inference_config: None,
+        quota_config: None,
+        multitenancy_config: None,
     };
 
     let snapshots_path = Builder::new().prefix("test_snapshots").tempdir().unwrap();
@@ -96,6 +105,7 @@ async fn _test_snapshot_and_recover_collection(node_type: NodeType) {
             id: i.into(),
             vector: VectorStruct::from(vec![i as f32, 0.0, 0.0, 0.0]).into(),
             payload: Some(serde_json::from_str(r#"{"number": "John Doe"}"#).unwrap()),
+            expires_at: None,
         });
     }
     let insert_points = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
@@ -109,7 +119,7 @@ async fn _test_snapshot_and_recover_collection(node_type: NodeType) {
     // Take a snapshot
     let snapshots_temp_dir = Builder::new().prefix("temp_dir").tempdir().unwrap();
     let snapshot_description = collection
-        .create_snapshot(snapshots_temp_dir.path(), 0)
+        .create_snapshot(snapshots_temp_dir.path(), 0, None)
         .await
         .unwrap();
 
@@ -153,7 +163,7 @@ async fn _test_snapshot_and_recover_collection(node_type: NodeType) {
 
     let reference_result = collection
         .search(
-            full_search_request.clone().into(),
+            full_search_request.clone().try_into().unwrap(),
             None,
             &ShardSelectorInternal::All,
             None,
@@ -163,7 +173,7 @@ async fn _test_snapshot_and_recover_collection(node_type: NodeType) {
 
     let recovered_result = recovered_collection
         .search(
-            full_search_request.into(),
+            full_search_request.try_into().unwrap(),
             None,
             &ShardSelectorInternal::All,
             None,
@@ -1,5 +1,5 @@
 use std::cmp::Ordering;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 #[cfg(target_os = "linux")]
@@ -62,6 +62,15 @@ pub struct CpuBudget {
     semaphore: Arc<Semaphore>,
     /// Total CPU budget, available and leased out.
     cpu_budget: usize,
+    /// Limit of CPUs within this budget, shared by all clones of a limited budget.
+    limit: Option<Arc<CpuLimit>>,
+}
+
+/// Resizable limit of CPUs for a part of the workload, e.g. for a single collection.
+#[derive(Debug, Default)]
+struct CpuLimit {
+    /// Semaphore of the limit and the max number of CPUs, `None` if there is no limit.
+    semaphore: Mutex<Option<(Arc<Semaphore>, usize)>>,
 }
 
 impl CpuBudget {
@@ -69,12 +78,65 @@ impl CpuBudget {
         Self {
             semaphore: Arc::new(Semaphore::new(cpu_budget)),
             cpu_budget,
+            limit: None,
+        }
+    }
+
+    /// Budget, which leases out at most `max_cpus` CPUs of this budget at once.
+    ///
+    /// `None` or `0` means no limit. All clones of the returned budget share the limit, which can
+    /// be changed with [`Self::set_limit`].
+    pub fn with_limit(&self, max_cpus: Option<usize>) -> Self {
+        let budget = Self {
+            semaphore: self.semaphore.clone(),
+            cpu_budget: self.cpu_budget,
+            limit: Some(Arc::default()),
+        };
+        budget.set_limit(max_cpus);
+        budget
+    }
+
+    /// Change the limit of a budget created with [`Self::with_limit`].
+    ///
+    /// CPUs leased out before the change don't count towards the new limit.
+    /// Does nothing for a budget without a limit.
+    pub fn set_limit(&self, max_cpus: Option<usize>) {
+        let Some(limit) = &self.limit else {
+            return;
+        };
+        let max_cpus = max_cpus.filter(|&max_cpus| max_cpus > 0);
+
+        let mut semaphore = limit.semaphore.lock().unwrap();
+        if semaphore.as_ref().map(|(_, max_cpus)| *max_cpus) != max_cpus {
+            *semaphore = max_cpus.map(|max_cpus| (Arc::new(Semaphore::new(max_cpus)), max_cpus));
+        }
+    }
+
+    /// Max number of CPUs of the limit of this budget, if any.
+    pub fn limit(&self) -> Option<usize> {
+        self.limit_semaphore().map(|(_, max_cpus)| max_cpus)
+    }
+
+    fn limit_semaphore(&self) -> Option<(Arc<Semaphore>, usize)> {
+        self.limit.as_ref()?.semaphore.lock().unwrap().clone()
+    }
+
+    /// Number of CPUs, which can be leased out right now, respecting the limit.
+    fn available_permits(&self) -> usize {
+        let available = self.semaphore.available_permits();
+        match self.limit_semaphore() {
+            Some((limit, _)) => available.min(limit.available_permits()),
+            None => available,
         }
     }
 
     /// For the given desired number of CPUs, return the minimum number of required CPUs.
     fn min_permits(&self, desired_cpus: usize) -> usize {
-        desired_cpus.min(self.cpu_budget).div_ceil(2)
+        let total_cpus = match self.limit() {
+            Some(max_cpus) => self.cpu_budget.min(max_cpus),
+            None => self.cpu_budget,
+        };
+        desired_cpus.min(total_cpus).div_ceil(2)
     }
 
     /// Try to acquire CPU permit for optimization task from global CPU budget.
@@ -86,20 +148,31 @@ impl CpuBudget {
     pub fn try_acquire(&self, desired_cpus: usize) -> Option<CpuPermit> {
         // Determine what number of CPUs to acquire based on available budget
         let min_required = self.min_permits(desired_cpus) as u32;
-        let num_cpus = self.semaphore.available_permits().min(desired_cpus) as u32;
+        let num_cpus = self.available_permits().min(desired_cpus) as u32;
         if desired_cpus == 0 || num_cpus < min_required {
             return None;
         }
 
-        // Try to acquire selected number of CPUs
-        let result = Semaphore::try_acquire_many_owned(self.semaphore.clone(), num_cpus);
-        let permit = match result {
-            Ok(permit) => permit,
-            Err(TryAcquireError::NoPermits) => return None,
-            Err(TryAcquireError::Closed) => unreachable!("Cannot acquire CPU permit because CPU budget semaphore is closed, this should never happen"),
+        // Try to acquire selected number of CPUs, from the limit first
+        let limit_permit = match self.limit_semaphore() {
+            Some((limit, _)) => Some(Self::try_acquire_many(limit, num_cpus)?),
+            None => None,
         };
+        let permit = Self::try_acquire_many(self.semaphore.clone(), num_cpus)?;
 
-        Some(CpuPermit::new(num_cpus, permit))
+        Some(CpuPermit {
+            num_cpus,
+            permit: Some(permit),
+            limit_permit,
+        })
+    }
+
+    fn try_acquire_many(semaphore: Arc<Semaphore>, num_cpus: u32) -> Option<OwnedSemaphorePermit> {
+        match Semaphore::try_acquire_many_owned(semaphore, num_cpus) {
+            Ok(permit) => Some(permit),
+            Err(TryAcquireError::NoPermits) => None,
+            Err(TryAcquireError::Closed) => unreachable!("Cannot acquire CPU permit because CPU budget semaphore is closed, this should never happen"),
+        }
     }
 
     /// Check if there is enough CPU budget available for the given `desired_cpus`.
@@ -116,7 +189,7 @@ impl CpuBudget {
     ///
     /// A budget of `0` will always return `true`.
     fn has_budget_exact(&self, budget: usize) -> bool {
-        self.semaphore.available_permits() >= budget
+        self.available_permits() >= budget
     }
 
     /// Notify when we have CPU budget available for the given number of desired CPUs.
@@ -165,6 +238,8 @@ pub struct CpuPermit {
     pub num_cpus: u32,
     /// Semaphore permit.
     permit: Option<OwnedSemaphorePermit>,
+    /// Semaphore permit of the limit of the budget, if any.
+    limit_permit: Option<OwnedSemaphorePermit>,
 }
 
 impl CpuPermit {
@@ -173,6 +248,7 @@ impl CpuPermit {
         Self {
             num_cpus: count,
             permit: Some(permit),
+            limit_permit: None,
         }
     }
 
@@ -182,12 +258,14 @@ impl CpuPermit {
         Self {
             num_cpus: count,
             permit: None,
+            limit_permit: None,
         }
     }
 
     /// Release CPU permit, giving them back to the semaphore.
     pub fn release(&mut self) {
         self.permit.take();
+        self.limit_permit.take();
     }
 }
 
@@ -239,3 +317,26 @@ fn set_linux_thread_priority(priority: u8) -> Result<(), ThreadPriorityError> {
     );
     set_current_thread_priority(new_priority).map_err(ThreadPriorityError::SetThreadPriority)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cpu_budget_limit() {
+        let budget = CpuBudget::new(8);
+        let limited = budget.with_limit(Some(2));
+
+        let permit = limited.try_acquire(4).unwrap();
+        assert_eq!(permit.num_cpus, 2);
+        assert!(limited.try_acquire(1).is_none());
+        assert!(budget.has_budget_exact(6));
+
+        limited.set_limit(Some(3));
+        assert_eq!(limited.clone().try_acquire(4).unwrap().num_cpus, 3);
+
+        drop(permit);
+        limited.set_limit(None);
+        assert_eq!(limited.try_acquire(8).unwrap().num_cpus, 8);
+    }
+}
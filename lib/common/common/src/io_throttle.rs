@@ -0,0 +1,319 @@
+//! Scheduling of disk IO of optimizations, so that building and merging segments interferes less
+//! with searches.
+//!
+//! Optimization threads can be moved to a lower IO scheduling class, like with `ionice`.
+//! While the average latency of searches is above the threshold, reads and writes of optimizations
+//! are additionally limited to the configured rate.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+static IO_THROTTLE: OnceLock<IoThrottle> = OnceLock::new();
+
+/// Weight of a new search in the moving average of search latency
+const LATENCY_AVERAGE_WEIGHT: f64 = 0.1;
+
+/// If there were no searches for this long, searches are not considered slow anymore
+const LATENCY_EXPIRATION: Duration = Duration::from_secs(10);
+
+const BYTES_IN_MB: u64 = 1024 * 1024;
+
+/// IO scheduling class of optimization threads
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum IoClass {
+    /// Lowest priority of the best-effort class, used by other threads
+    BestEffort,
+    /// Disk is used only if no other thread needs it
+    Idle,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+pub struct OptimizerIoConfig {
+    /// IO scheduling class of optimization threads, Linux only.
+    /// If not set - optimizations use the same class as searches.
+    #[serde(default)]
+    pub io_class: Option<IoClass>,
+    /// Average latency of searches in milliseconds, above which disk IO of optimizations is throttled.
+    /// If not set - disk IO of optimizations is not throttled.
+    #[serde(default)]
+    pub search_latency_threshold_ms: Option<u64>,
+    /// Max rate of disk reads and writes of optimizations while throttled, in megabytes per second
+    #[serde(default = "default_throttled_mb_per_sec")]
+    pub throttled_mb_per_sec: u64,
+}
+
+const fn default_throttled_mb_per_sec() -> u64 {
+    32
+}
+
+impl Default for OptimizerIoConfig {
+    fn default() -> Self {
+        Self {
+            io_class: None,
+            search_latency_threshold_ms: None,
+            throttled_mb_per_sec: default_throttled_mb_per_sec(),
+        }
+    }
+}
+
+/// Throttling applied to optimizations since the start
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct IoThrottleStats {
+    /// Whether disk IO of optimizations is throttled right now
+    pub throttling: bool,
+    /// Moving average of latency of searches
+    pub search_latency_secs: f64,
+    /// Bytes read or written by optimizations while throttled
+    pub throttled_bytes: u64,
+    /// Time optimizations waited for the rate limit
+    pub wait_secs: f64,
+}
+
+struct IoThrottle {
+    config: OptimizerIoConfig,
+    latency: Mutex<SearchLatency>,
+    bucket: Mutex<TokenBucket>,
+    throttled_bytes: AtomicU64,
+    wait_nanos: AtomicU64,
+}
+
+struct SearchLatency {
+    average_secs: f64,
+    updated_at: Option<Instant>,
+}
+
+/// Bytes optimizations may use without waiting, refilled at the throttled rate
+struct TokenBucket {
+    available_bytes: f64,
+    refilled_at: Instant,
+}
+
+/// Use the configuration for all optimizations, must be called before any optimization starts.
+///
+/// Returns false if IO scheduling is already set up.
+pub fn setup(config: OptimizerIoConfig) -> bool {
+    IO_THROTTLE.set(IoThrottle::new(config)).is_ok()
+}
+
+/// Add the latency of a search to the moving average
+pub fn record_search_latency(duration: Duration) {
+    if let Some(throttle) = IO_THROTTLE.get() {
+        throttle.record_search_latency(duration);
+    }
+}
+
+/// Account `bytes` read or written by an optimization, and block the current thread, if disk IO
+/// of optimizations is throttled and the rate is exceeded.
+pub fn consume(bytes: u64) {
+    let Some(throttle) = IO_THROTTLE.get() else {
+        return;
+    };
+    let wait = throttle.consume(bytes);
+    if !wait.is_zero() {
+        std::thread::sleep(wait);
+    }
+}
+
+/// Throttling applied so far, `None` if IO scheduling is not set up
+pub fn stats() -> Option<IoThrottleStats> {
+    IO_THROTTLE.get().map(IoThrottle::stats)
+}
+
+impl IoThrottle {
+    fn new(config: OptimizerIoConfig) -> Self {
+        Self {
+            config,
+            latency: Mutex::new(SearchLatency {
+                average_secs: 0.0,
+                updated_at: None,
+            }),
+            bucket: Mutex::new(TokenBucket {
+                available_bytes: 0.0,
+                refilled_at: Instant::now(),
+            }),
+            throttled_bytes: AtomicU64::new(0),
+            wait_nanos: AtomicU64::new(0),
+        }
+    }
+
+    fn record_search_latency(&self, duration: Duration) {
+        if self.config.search_latency_threshold_ms.is_none() {
+            return;
+        }
+
+        let mut latency = self.latency.lock().unwrap();
+        let sample = duration.as_secs_f64();
+        latency.average_secs = match latency.updated_at {
+            Some(updated_at) if updated_at.elapsed() < LATENCY_EXPIRATION => {
+                latency.average_secs * (1.0 - LATENCY_AVERAGE_WEIGHT)
+                    + sample * LATENCY_AVERAGE_WEIGHT
+            }
+            _ => sample,
+        };
+        latency.updated_at = Some(Instant::now());
+    }
+
+    fn is_throttling(&self) -> bool {
+        let Some(threshold_ms) = self.config.search_latency_threshold_ms else {
+            return false;
+        };
+        let latency = self.latency.lock().unwrap();
+        let is_recent = latency
+            .updated_at
+            .is_some_and(|updated_at| updated_at.elapsed() < LATENCY_EXPIRATION);
+        is_recent && latency.average_secs * 1000.0 > threshold_ms as f64
+    }
+
+    /// Account `bytes`, returns how long to wait to stay within the rate
+    fn consume(&self, bytes: u64) -> Duration {
+        if bytes == 0 || !self.is_throttling() {
+            return Duration::ZERO;
+        }
+
+        let rate = (self.config.throttled_mb_per_sec.max(1) * BYTES_IN_MB) as f64;
+        let wait = {
+            let mut bucket = self.bucket.lock().unwrap();
+            let now = Instant::now();
+            let refilled = (now - bucket.refilled_at).as_secs_f64() * rate;
+            // Allow bursts of up to a second of the rate
+            bucket.available_bytes = (bucket.available_bytes + refilled).min(rate) - bytes as f64;
+            bucket.refilled_at = now;
+            if bucket.available_bytes < 0.0 {
+                Duration::from_secs_f64(-bucket.available_bytes / rate)
+            } else {
+                Duration::ZERO
+            }
+        };
+
+        self.throttled_bytes.fetch_add(bytes, Ordering::Relaxed);
+        self.wait_nanos
+            .fetch_add(wait.as_nanos() as u64, Ordering::Relaxed);
+        wait
+    }
+
+    fn stats(&self) -> IoThrottleStats {
+        IoThrottleStats {
+            throttling: self.is_throttling(),
+            search_latency_secs: self.latency.lock().unwrap().average_secs,
+            throttled_bytes: self.throttled_bytes.load(Ordering::Relaxed),
+            wait_secs: Duration::from_nanos(self.wait_nanos.load(Ordering::Relaxed)).as_secs_f64(),
+        }
+    }
+}
+
+/// Restores the IO priority of the current thread on drop
+pub struct IoPriorityGuard {
+    #[cfg(target_os = "linux")]
+    previous: Option<libc::c_int>,
+}
+
+/// Move the current thread to the IO scheduling class of optimizations, if configured, until the
+/// returned guard is dropped.
+pub fn lower_io_priority() -> IoPriorityGuard {
+    #[cfg(target_os = "linux")]
+    {
+        let io_class = IO_THROTTLE
+            .get()
+            .and_then(|throttle| throttle.config.io_class);
+        let previous = io_class.and_then(|io_class| {
+            let previous = ioprio::get()?;
+            ioprio::set(ioprio::value(io_class)).then_some(previous)
+        });
+        IoPriorityGuard { previous }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    IoPriorityGuard {}
+}
+
+impl Drop for IoPriorityGuard {
+    fn drop(&mut self) {
+        #[cfg(target_os = "linux")]
+        if let Some(previous) = self.previous {
+            ioprio::set(previous);
+        }
+    }
+}
+
+/// IO priority of threads, see `man 2 ioprio_set`
+#[cfg(target_os = "linux")]
+mod ioprio {
+    use super::IoClass;
+
+    const IOPRIO_WHO_PROCESS: libc::c_int = 1;
+    const IOPRIO_CLASS_SHIFT: libc::c_int = 13;
+    const IOPRIO_CLASS_BE: libc::c_int = 2;
+    const IOPRIO_CLASS_IDLE: libc::c_int = 3;
+    /// Lowest level of the best-effort class
+    const IOPRIO_BE_LOWEST_LEVEL: libc::c_int = 7;
+
+    pub fn value(io_class: IoClass) -> libc::c_int {
+        match io_class {
+            IoClass::BestEffort => (IOPRIO_CLASS_BE << IOPRIO_CLASS_SHIFT) | IOPRIO_BE_LOWEST_LEVEL,
+            IoClass::Idle => IOPRIO_CLASS_IDLE << IOPRIO_CLASS_SHIFT,
+        }
+    }
+
+    /// IO priority of the current thread
+    pub fn get() -> Option<libc::c_int> {
+        // SAFETY: the syscall only reads its integer arguments
+        let priority = unsafe { libc::syscall(libc::SYS_ioprio_get, IOPRIO_WHO_PROCESS, 0) };
+        (priority >= 0).then_some(priority as libc::c_int)
+    }
+
+    /// Set IO priority of the current thread, returns false if it's not permitted
+    pub fn set(priority: libc::c_int) -> bool {
+        // SAFETY: the syscall only reads its integer arguments
+        unsafe { libc::syscall(libc::SYS_ioprio_set, IOPRIO_WHO_PROCESS, 0, priority) == 0 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_throttle_while_searches_are_slow() {
+        let throttle = IoThrottle::new(OptimizerIoConfig {
+            io_class: None,
+            search_latency_threshold_ms: Some(200),
+            throttled_mb_per_sec: 1,
+        });
+
+        // No searches yet, optimizations are not throttled
+        assert_eq!(throttle.consume(10 * BYTES_IN_MB), Duration::ZERO);
+
+        throttle.record_search_latency(Duration::from_millis(50));
+        assert!(!throttle.is_throttling());
+
+        // Single slow search is a sample of the moving average
+        throttle.record_search_latency(Duration::from_millis(1000));
+        assert!(!throttle.is_throttling());
+
+        for _ in 0..10 {
+            throttle.record_search_latency(Duration::from_millis(1000));
+        }
+        assert!(throttle.is_throttling());
+
+        // 2 MB at 1 MB/s, of which none is available yet
+        let wait = throttle.consume(2 * BYTES_IN_MB);
+        assert!(wait > Duration::from_millis(1900), "{wait:?}");
+        assert!(wait <= Duration::from_secs(2), "{wait:?}");
+
+        let stats = throttle.stats();
+        assert!(stats.throttling);
+        assert_eq!(stats.throttled_bytes, 2 * BYTES_IN_MB);
+        assert!(stats.wait_secs > 1.9);
+
+        // Searches are fast again
+        for _ in 0..50 {
+            throttle.record_search_latency(Duration::from_millis(10));
+        }
+        assert!(!throttle.is_throttling());
+        assert_eq!(throttle.consume(10 * BYTES_IN_MB), Duration::ZERO);
+    }
+}
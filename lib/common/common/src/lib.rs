@@ -1,8 +1,10 @@
 pub mod cpu;
 pub mod defaults;
 pub mod fixed_length_priority_queue;
+pub mod io_throttle;
 pub mod math;
 pub mod panic;
+pub mod request_id;
 pub mod top_k;
 pub mod types;
 pub mod validation;
@@ -0,0 +1,69 @@
+//! ID of the request, which is being served by the current task.
+//!
+//! It is accepted from clients, or generated, by REST and gRPC APIs, and passed to other peers
+//! with internal gRPC calls, so logs of the request can be correlated across the cluster.
+
+use std::future::Future;
+use std::sync::Arc;
+
+/// Header of REST and gRPC requests and responses, which carries the request ID
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Longer request IDs provided by clients are replaced with generated ones
+pub const MAX_REQUEST_ID_LEN: usize = 128;
+
+tokio::task_local! {
+    static REQUEST_ID: Arc<str>;
+}
+
+/// ID of the request served by the current task, if any
+pub fn current() -> Option<Arc<str>> {
+    REQUEST_ID.try_with(Arc::clone).ok()
+}
+
+/// Run the future with the request ID, so it's passed on with internal gRPC calls
+pub async fn scope<F: Future>(request_id: Option<Arc<str>>, future: F) -> F::Output {
+    match request_id {
+        Some(request_id) => REQUEST_ID.scope(request_id, future).await,
+        None => future.await,
+    }
+}
+
+/// Spawn the task with the request ID of the current task, if any
+pub fn spawn<F>(future: F) -> tokio::task::JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    tokio::task::spawn(scope(current(), future))
+}
+
+/// Whether the request ID provided by a client can be used, it's echoed in headers and logs
+pub fn is_valid(request_id: &str) -> bool {
+    !request_id.is_empty()
+        && request_id.len() <= MAX_REQUEST_ID_LEN
+        && request_id
+            .bytes()
+            .all(|byte| byte.is_ascii_graphic() && byte != b'"' && byte != b'\\')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_valid() {
+        assert!(is_valid("3f2b8c1e-request"));
+        assert!(!is_valid(""));
+        assert!(!is_valid("with space"));
+        assert!(!is_valid(&"a".repeat(MAX_REQUEST_ID_LEN + 1)));
+    }
+
+    #[tokio::test]
+    async fn test_scope() {
+        assert!(current().is_none());
+        let request_id = scope(Some("abc".into()), async { current() }).await;
+        assert_eq!(request_id.as_deref(), Some("abc"));
+        assert!(scope(None, async { current() }).await.is_none());
+    }
+}
@@ -36,9 +36,15 @@ fn init_vector_storage(
 ) {
     let db = open_db(path, &[DB_VECTOR_CF]).unwrap();
     let id_tracker = Arc::new(AtomicRefCell::new(FixtureIdTracker::new(num)));
-    let storage =
-        open_simple_dense_vector_storage(db, DB_VECTOR_CF, dim, dist, &AtomicBool::new(false))
-            .unwrap();
+    let storage = open_simple_dense_vector_storage(
+        db,
+        DB_VECTOR_CF,
+        None,
+        dim,
+        dist,
+        &AtomicBool::new(false),
+    )
+    .unwrap();
     {
         let mut borrowed_storage = storage.borrow_mut();
         for i in 0..num {
@@ -0,0 +1,229 @@
+use std::fs::create_dir_all;
+use std::path::{Path, PathBuf};
+
+use common::types::PointOffsetType;
+
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::common::Flusher;
+use crate::vector_storage::chunked_mmap_vectors::ChunkedMmapVectors;
+
+const BLOB_STORAGE_PATH: &str = "blob_storage";
+const BLOCKS_DIR_PATH: &str = "blocks";
+const OFFSETS_DIR_PATH: &str = "offsets";
+
+/// Size of the allocation unit for blob data
+const BLOCK_SIZE: usize = 4096;
+
+/// Maximal size of a single blob in bytes
+///
+/// Blocks of a blob never cross a chunk boundary, so a blob must fit into the smallest chunk.
+pub const MAX_BLOB_SIZE: usize = 256 * 1024;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct BlobOffset {
+    /// First block of the blob
+    block: PointOffsetType,
+    /// Size of the blob in bytes, 0 if point has no blob
+    len: u32,
+    /// Number of blocks allocated for the blob
+    capacity: u32,
+}
+
+fn blocks_for(len: usize) -> usize {
+    len.div_ceil(BLOCK_SIZE)
+}
+
+/// Part of a blob, read from the storage
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlobChunk {
+    /// Bytes of the blob, starting from the requested offset
+    pub data: Vec<u8>,
+    /// Size of the whole blob in bytes
+    pub total_len: usize,
+}
+
+impl BlobChunk {
+    /// Take `limit` bytes of the `blob` starting from `offset`
+    pub fn from_blob(blob: &[u8], offset: usize, limit: usize) -> Self {
+        let start = offset.min(blob.len());
+        let end = start.saturating_add(limit).min(blob.len());
+        Self {
+            data: blob[start..end].to_vec(),
+            total_len: blob.len(),
+        }
+    }
+}
+
+/// Storage of opaque binary blobs, attached to points of a segment.
+///
+/// Same layout as `MmapPayloadStorage`: blobs are written into blocks of a chunked mmap file,
+/// a compact offset index maps each point to its blocks. Blobs are never loaded into memory
+/// as a whole, so they can be read in parts.
+///
+/// Updated blob is written in place if it fits into the allocated blocks, otherwise it is
+/// appended to the end. Space of the abandoned blocks is reclaimed by segment optimization.
+pub struct BlobStorage {
+    blocks: ChunkedMmapVectors<u8>,
+    offsets: ChunkedMmapVectors<BlobOffset>,
+}
+
+impl BlobStorage {
+    pub fn open(segment_path: &Path) -> OperationResult<Self> {
+        let path = segment_path.join(BLOB_STORAGE_PATH);
+        create_dir_all(&path)?;
+
+        let blocks = ChunkedMmapVectors::open(&path.join(BLOCKS_DIR_PATH), BLOCK_SIZE)?;
+        let offsets = ChunkedMmapVectors::open(&path.join(OFFSETS_DIR_PATH), 1)?;
+
+        Ok(BlobStorage { blocks, offsets })
+    }
+
+    fn get_offset(&self, point_id: PointOffsetType) -> BlobOffset {
+        self.offsets
+            .get(point_id as usize)
+            .and_then(|offset| offset.first().copied())
+            .unwrap_or_default()
+    }
+
+    /// Blob of the point, `None` if the point has no blob
+    pub fn get(&self, point_id: PointOffsetType) -> OperationResult<Option<&[u8]>> {
+        let offset = self.get_offset(point_id);
+        if offset.len == 0 {
+            return Ok(None);
+        }
+
+        let raw = self
+            .blocks
+            .get_many(offset.block, blocks_for(offset.len as usize))
+            .ok_or_else(|| {
+                OperationError::service_error(format!("Blob of point {point_id} not found"))
+            })?;
+        Ok(Some(&raw[..offset.len as usize]))
+    }
+
+    /// Attach blob to the point, replacing the existing one. Empty blob removes it.
+    pub fn set(&mut self, point_id: PointOffsetType, blob: &[u8]) -> OperationResult<()> {
+        if blob.is_empty() {
+            self.delete(point_id)?;
+            return Ok(());
+        }
+        if blob.len() > MAX_BLOB_SIZE {
+            return Err(OperationError::ValidationError {
+                description: format!(
+                    "Blob of {} bytes exceeds the maximal size of {MAX_BLOB_SIZE} bytes",
+                    blob.len(),
+                ),
+            });
+        }
+
+        let len = blob.len();
+        let required_blocks = blocks_for(len);
+        let mut data = Vec::with_capacity(required_blocks * BLOCK_SIZE);
+        data.extend_from_slice(blob);
+        data.resize(required_blocks * BLOCK_SIZE, 0);
+
+        let mut offset = self.get_offset(point_id);
+        if required_blocks > offset.capacity as usize {
+            // append blob to the end, blocks of a single blob never cross chunk boundary
+            let mut new_block = self.blocks.len();
+            let chunk_left_blocks = self.blocks.get_remaining_chunk_keys(new_block);
+            if required_blocks > chunk_left_blocks {
+                new_block += chunk_left_blocks;
+            }
+
+            offset.block = new_block as PointOffsetType;
+            offset.capacity = required_blocks as u32;
+        }
+        offset.len = len as u32;
+
+        self.blocks
+            .insert_many(offset.block, &data, required_blocks)?;
+        self.offsets.insert(point_id as usize, &[offset])?;
+        Ok(())
+    }
+
+    /// Remove blob of the point, returns `false` if the point had no blob
+    pub fn delete(&mut self, point_id: PointOffsetType) -> OperationResult<bool> {
+        let mut offset = self.get_offset(point_id);
+        if offset.len == 0 {
+            return Ok(false);
+        }
+        // keep allocated blocks, so that the next blob of the point could reuse them
+        offset.len = 0;
+        self.offsets.insert(point_id as usize, &[offset])?;
+        Ok(true)
+    }
+
+    pub fn flusher(&self) -> Flusher {
+        Box::new({
+            let blocks_flusher = self.blocks.flusher();
+            let offsets_flusher = self.offsets.flusher();
+            move || {
+                blocks_flusher()?;
+                offsets_flusher()?;
+                Ok(())
+            }
+        })
+    }
+
+    pub fn files(&self) -> Vec<PathBuf> {
+        let mut files = self.blocks.files();
+        files.extend(self.offsets.files());
+        files
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::Builder;
+
+    use super::*;
+
+    #[test]
+    fn test_blob_storage() {
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+
+        let mut storage = BlobStorage::open(dir.path()).unwrap();
+        let small = b"thumbnail".to_vec();
+        let large: Vec<u8> = (0..10 * BLOCK_SIZE).map(|i| i as u8).collect();
+
+        storage.set(0, &small).unwrap();
+        storage.set(1, &large).unwrap();
+        storage.set(2, &small).unwrap();
+        let blocks_len = storage.blocks.len();
+
+        // Smaller blob reuses allocated blocks
+        storage.set(1, &small).unwrap();
+        assert_eq!(storage.blocks.len(), blocks_len);
+
+        // Larger blob is moved to the end
+        storage.set(0, &large).unwrap();
+        assert!(storage.blocks.len() > blocks_len);
+
+        assert!(storage.delete(2).unwrap());
+        assert!(!storage.delete(2).unwrap());
+        assert!(!storage.delete(3).unwrap());
+
+        let too_large = vec![0; MAX_BLOB_SIZE + 1];
+        assert!(storage.set(3, &too_large).is_err());
+
+        storage.flusher()().unwrap();
+        drop(storage);
+
+        let storage = BlobStorage::open(dir.path()).unwrap();
+        assert_eq!(storage.get(0).unwrap(), Some(large.as_slice()));
+        assert_eq!(storage.get(1).unwrap(), Some(small.as_slice()));
+        assert_eq!(storage.get(2).unwrap(), None);
+        assert_eq!(storage.get(3).unwrap(), None);
+
+        let chunk = BlobChunk::from_blob(storage.get(0).unwrap().unwrap(), BLOCK_SIZE, 10);
+        assert_eq!(chunk.data, large[BLOCK_SIZE..BLOCK_SIZE + 10]);
+        assert_eq!(chunk.total_len, large.len());
+
+        let tail = BlobChunk::from_blob(&small, 4, usize::MAX);
+        assert_eq!(tail.data, b"nail");
+        let past_end = BlobChunk::from_blob(&small, 100, 10);
+        assert!(past_end.data.is_empty());
+        assert_eq!(past_end.total_len, small.len());
+    }
+}
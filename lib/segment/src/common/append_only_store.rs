@@ -0,0 +1,431 @@
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use common::types::PointOffsetType;
+use memmap2::Mmap;
+use parking_lot::RwLock;
+
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::common::Flusher;
+
+/// Size of the record header: key, value length and checksum
+const HEADER_SIZE: usize = 12;
+
+/// Value length, which marks removal of the key
+const TOMBSTONE: u32 = u32::MAX;
+
+/// Don't bother compacting files smaller than this
+const MIN_COMPACTION_SIZE: u64 = 4 * 1024 * 1024;
+
+/// Location of the persisted value inside of the file
+#[derive(Debug, Clone, Copy)]
+struct Location {
+    offset: usize,
+    len: usize,
+}
+
+impl Location {
+    fn record_size(&self) -> u64 {
+        (HEADER_SIZE + self.len) as u64
+    }
+}
+
+/// Append-only key-value storage, keyed by point offset.
+///
+/// Every change is appended to a single log file as a record of
+/// `key: u32 | len: u32 | crc32: u32 | value`, the latest record of each key wins.
+/// Locations of the latest values are kept in memory, values themselves are read through mmap.
+///
+/// Changes are buffered in memory until flushed. The log is rewritten without obsolete records
+/// once they take more space than the live ones.
+#[derive(Debug, Clone)]
+pub struct AppendOnlyStore {
+    inner: Arc<RwLock<StoreInner>>,
+}
+
+#[derive(Debug)]
+struct StoreInner {
+    path: PathBuf,
+    file: File,
+    /// Persisted part of the log, `None` if the log is empty
+    mmap: Option<Mmap>,
+    file_len: u64,
+    /// Size of the records, which are still in use
+    live_bytes: u64,
+    /// Location of the latest persisted value for each key
+    index: Vec<Option<Location>>,
+    /// Changes, which are not persisted yet. `None` marks removed key
+    pending: HashMap<PointOffsetType, Option<Vec<u8>>>,
+}
+
+fn checksum(key: PointOffsetType, len: u32, value: &[u8]) -> u32 {
+    let mut hasher = crc32fast::Hasher::new();
+    hasher.update(&key.to_le_bytes());
+    hasher.update(&len.to_le_bytes());
+    hasher.update(value);
+    hasher.finalize()
+}
+
+fn encode_record(buffer: &mut Vec<u8>, key: PointOffsetType, value: Option<&[u8]>) {
+    let len = value.map_or(TOMBSTONE, |value| value.len() as u32);
+    let value = value.unwrap_or_default();
+    buffer.extend_from_slice(&key.to_le_bytes());
+    buffer.extend_from_slice(&len.to_le_bytes());
+    buffer.extend_from_slice(&checksum(key, len, value).to_le_bytes());
+    buffer.extend_from_slice(value);
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+fn open_append(path: &Path) -> OperationResult<File> {
+    Ok(OpenOptions::new()
+        .read(true)
+        .append(true)
+        .create(true)
+        .open(path)?)
+}
+
+fn map_file(file: &File, len: u64) -> OperationResult<Option<Mmap>> {
+    if len == 0 {
+        // Empty files can't be mapped
+        return Ok(None);
+    }
+    Ok(Some(unsafe { Mmap::map(file)? }))
+}
+
+impl AppendOnlyStore {
+    pub fn open(path: &Path) -> OperationResult<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let file = open_append(path)?;
+        let file_len = file.metadata()?.len();
+        let mmap = map_file(&file, file_len)?;
+
+        let mut inner = StoreInner {
+            path: path.to_path_buf(),
+            file,
+            mmap,
+            file_len,
+            live_bytes: 0,
+            index: Vec::new(),
+            pending: HashMap::new(),
+        };
+        inner.load()?;
+
+        Ok(Self {
+            inner: Arc::new(RwLock::new(inner)),
+        })
+    }
+
+    /// Read the latest value of the key, if any
+    pub fn get<T>(&self, key: PointOffsetType, f: impl FnOnce(&[u8]) -> T) -> Option<T> {
+        self.inner.read().get(key).map(f)
+    }
+
+    pub fn put(&self, key: PointOffsetType, value: Vec<u8>) {
+        self.inner.write().pending.insert(key, Some(value));
+    }
+
+    pub fn remove(&self, key: PointOffsetType) {
+        self.inner.write().pending.insert(key, None);
+    }
+
+    /// Iterate over the latest values of all keys in ascending key order
+    pub fn iter<F>(&self, mut callback: F) -> OperationResult<()>
+    where
+        F: FnMut(PointOffsetType, &[u8]) -> OperationResult<bool>,
+    {
+        let inner = self.inner.read();
+        let max_key = inner
+            .pending
+            .keys()
+            .map(|key| *key as usize + 1)
+            .max()
+            .unwrap_or(0)
+            .max(inner.index.len());
+
+        for key in 0..max_key as PointOffsetType {
+            if let Some(value) = inner.get(key) {
+                if !callback(key, value)? {
+                    return Ok(());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Remove all keys and truncate the log
+    pub fn clear(&self) -> OperationResult<()> {
+        let mut inner = self.inner.write();
+        inner.mmap = None;
+        inner.file.set_len(0)?;
+        inner.file.sync_all()?;
+        inner.file_len = 0;
+        inner.live_bytes = 0;
+        inner.index.clear();
+        inner.pending.clear();
+        Ok(())
+    }
+
+    pub fn flusher(&self) -> Flusher {
+        let inner = self.inner.clone();
+        Box::new(move || inner.write().flush())
+    }
+
+    pub fn files(&self) -> Vec<PathBuf> {
+        vec![self.inner.read().path.clone()]
+    }
+}
+
+impl StoreInner {
+    fn get(&self, key: PointOffsetType) -> Option<&[u8]> {
+        if let Some(value) = self.pending.get(&key) {
+            return value.as_deref();
+        }
+        let location = self.index.get(key as usize).copied().flatten()?;
+        let mmap = self.mmap.as_ref()?;
+        Some(&mmap[location.offset..location.offset + location.len])
+    }
+
+    fn set_location(&mut self, key: PointOffsetType, location: Option<Location>) {
+        let key = key as usize;
+        if key >= self.index.len() {
+            self.index.resize(key + 1, None);
+        }
+        if let Some(previous) = self.index[key].take() {
+            self.live_bytes -= previous.record_size();
+        }
+        if let Some(location) = location {
+            self.live_bytes += location.record_size();
+        }
+        self.index[key] = location;
+    }
+
+    /// Build index from the persisted records, dropping incomplete tail of the log
+    fn load(&mut self) -> OperationResult<()> {
+        let data_len = self.mmap.as_ref().map_or(0, |mmap| mmap.len());
+        let mut offset = 0;
+        let mut locations = Vec::new();
+
+        if let Some(mmap) = self.mmap.as_ref() {
+            while offset + HEADER_SIZE <= data_len {
+                let key = read_u32(mmap, offset);
+                let len = read_u32(mmap, offset + 4);
+                let crc = read_u32(mmap, offset + 8);
+
+                let value_len = if len == TOMBSTONE { 0 } else { len as usize };
+                let value_offset = offset + HEADER_SIZE;
+                if value_offset + value_len > data_len {
+                    break;
+                }
+                let value = &mmap[value_offset..value_offset + value_len];
+                if checksum(key, len, value) != crc {
+                    break;
+                }
+
+                let location = (len != TOMBSTONE).then_some(Location {
+                    offset: value_offset,
+                    len: value_len,
+                });
+                locations.push((key, location));
+                offset = value_offset + value_len;
+            }
+        }
+
+        for (key, location) in locations {
+            self.set_location(key, location);
+        }
+
+        if offset < data_len {
+            log::warn!(
+                "Dropping {} bytes of incomplete records at the end of {}",
+                data_len - offset,
+                self.path.display(),
+            );
+            self.mmap = None;
+            self.file.set_len(offset as u64)?;
+            self.file.sync_all()?;
+            self.file_len = offset as u64;
+            self.mmap = map_file(&self.file, self.file_len)?;
+        }
+
+        Ok(())
+    }
+
+    fn flush(&mut self) -> OperationResult<()> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+
+        let mut pending: Vec<_> = self.pending.drain().collect();
+        pending.sort_unstable_by_key(|(key, _)| *key);
+
+        let mut buffer = Vec::new();
+        let mut locations = Vec::with_capacity(pending.len());
+        for (key, value) in &pending {
+            let record_offset = self.file_len as usize + buffer.len();
+            encode_record(&mut buffer, *key, value.as_deref());
+            let location = value.as_ref().map(|value| Location {
+                offset: record_offset + HEADER_SIZE,
+                len: value.len(),
+            });
+            locations.push((*key, location));
+        }
+
+        if let Err(err) = self
+            .file
+            .write_all(&buffer)
+            .and_then(|_| self.file.sync_data())
+        {
+            // Drop partially written records and keep changes in memory,
+            // so that the next flush could retry them
+            let _ = self.file.set_len(self.file_len);
+            self.pending.extend(pending);
+            return Err(OperationError::service_error(format!(
+                "Failed to write {}: {err}",
+                self.path.display(),
+            )));
+        }
+
+        self.file_len += buffer.len() as u64;
+        self.mmap = map_file(&self.file, self.file_len)?;
+        for (key, location) in locations {
+            self.set_location(key, location);
+        }
+
+        if self.file_len >= MIN_COMPACTION_SIZE && self.file_len > 2 * self.live_bytes {
+            self.compact()?;
+        }
+
+        Ok(())
+    }
+
+    /// Rewrite the log with the latest values only
+    fn compact(&mut self) -> OperationResult<()> {
+        let tmp_path = self.path.with_extension("compact");
+
+        let mut buffer = Vec::with_capacity(self.live_bytes as usize);
+        let mut locations = Vec::new();
+        if let Some(mmap) = self.mmap.as_ref() {
+            for (key, location) in self.index.iter().enumerate() {
+                if let Some(location) = location {
+                    let value = &mmap[location.offset..location.offset + location.len];
+                    let offset = buffer.len() + HEADER_SIZE;
+                    encode_record(&mut buffer, key as PointOffsetType, Some(value));
+                    locations.push((
+                        key as PointOffsetType,
+                        Location {
+                            offset,
+                            len: location.len,
+                        },
+                    ));
+                }
+            }
+        }
+
+        {
+            let mut tmp_file = File::create(&tmp_path)?;
+            tmp_file.write_all(&buffer)?;
+            tmp_file.sync_all()?;
+        }
+
+        self.mmap = None;
+        std::fs::rename(&tmp_path, &self.path)?;
+        self.file = open_append(&self.path)?;
+        self.file_len = buffer.len() as u64;
+        self.mmap = map_file(&self.file, self.file_len)?;
+
+        self.index.clear();
+        self.live_bytes = 0;
+        for (key, location) in locations {
+            self.set_location(key, Some(location));
+        }
+
+        log::debug!(
+            "Compacted {} to {} bytes",
+            self.path.display(),
+            self.file_len,
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::Builder;
+
+    use super::*;
+
+    #[test]
+    fn test_append_only_store() {
+        let dir = Builder::new().prefix("store_dir").tempdir().unwrap();
+        let path = dir.path().join("store.log");
+
+        let store = AppendOnlyStore::open(&path).unwrap();
+        store.put(1, b"one".to_vec());
+        store.put(3, b"three".to_vec());
+        store.put(5, b"five".to_vec());
+
+        // Not flushed changes are readable
+        assert_eq!(store.get(3, |v| v.to_vec()), Some(b"three".to_vec()));
+        store.flusher()().unwrap();
+
+        store.put(3, b"THREE".to_vec());
+        store.remove(5);
+        store.flusher()().unwrap();
+
+        // Write incomplete record, as if the process crashed in the middle of flush
+        OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(&[7, 0, 0, 0, 100])
+            .unwrap();
+
+        let store = AppendOnlyStore::open(&path).unwrap();
+        assert_eq!(store.get(1, |v| v.to_vec()), Some(b"one".to_vec()));
+        assert_eq!(store.get(3, |v| v.to_vec()), Some(b"THREE".to_vec()));
+        assert_eq!(store.get(5, |v| v.to_vec()), None);
+        assert_eq!(store.get(7, |v| v.to_vec()), None);
+
+        let mut keys = Vec::new();
+        store
+            .iter(|key, _| {
+                keys.push(key);
+                Ok(true)
+            })
+            .unwrap();
+        assert_eq!(keys, vec![1, 3]);
+
+        store.clear().unwrap();
+        assert_eq!(store.get(1, |v| v.to_vec()), None);
+    }
+
+    #[test]
+    fn test_append_only_store_compaction() {
+        let dir = Builder::new().prefix("store_dir").tempdir().unwrap();
+        let path = dir.path().join("store.log");
+
+        let store = AppendOnlyStore::open(&path).unwrap();
+        let value = vec![42u8; 64 * 1024];
+        for round in 0..128 {
+            store.put(0, value.clone());
+            store.put(1, vec![round as u8]);
+            store.flusher()().unwrap();
+        }
+
+        assert!(std::fs::metadata(&path).unwrap().len() < MIN_COMPACTION_SIZE);
+
+        let store = AppendOnlyStore::open(&path).unwrap();
+        assert_eq!(store.get(0, |v| v.len()), Some(value.len()));
+        assert_eq!(store.get(1, |v| v.to_vec()), Some(vec![127]));
+    }
+}
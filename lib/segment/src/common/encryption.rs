@@ -0,0 +1,215 @@
+//! Encryption of values stored in RocksDB at rest, with AES-256-GCM.
+//!
+//! Every segment has its own random data key, which is stored in the segment directory,
+//! wrapped with the key of its collection. The key of the collection is passed to segments
+//! with [`SegmentConfig::storage_cipher`], segments created with it are encrypted.
+//! Segments created before the key was configured stay in plain text.
+
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
+
+use io::file_operations::{atomic_save_bin, read_bin};
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::types::{PayloadStorageType, SegmentConfig, VectorStorageType};
+
+/// Size of the encryption key in bytes
+pub const KEY_LEN: usize = 32;
+
+/// Data key of the segment, wrapped with the key of the collection
+pub const DATA_KEY_FILE: &str = "data_key.bin";
+
+/// Authenticated encryption of stored values.
+///
+/// Every value is sealed with a random nonce, which is stored in front of the ciphertext.
+pub struct StorageCipher {
+    key: LessSafeKey,
+    rng: SystemRandom,
+}
+
+impl fmt::Debug for StorageCipher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StorageCipher").finish_non_exhaustive()
+    }
+}
+
+impl StorageCipher {
+    pub fn new(key: &[u8]) -> OperationResult<Self> {
+        let key = UnboundKey::new(&AES_256_GCM, key).map_err(|_| {
+            OperationError::service_error(format!(
+                "Storage encryption key must be {KEY_LEN} bytes long"
+            ))
+        })?;
+        Ok(Self {
+            key: LessSafeKey::new(key),
+            rng: SystemRandom::new(),
+        })
+    }
+
+    /// Encrypt data, returns nonce followed by ciphertext and authentication tag
+    pub fn encrypt(&self, data: &[u8]) -> OperationResult<Vec<u8>> {
+        let mut nonce = [0u8; NONCE_LEN];
+        self.rng
+            .fill(&mut nonce)
+            .map_err(|_| OperationError::service_error("Failed to generate storage nonce"))?;
+
+        let mut in_out = Vec::with_capacity(NONCE_LEN + data.len() + AES_256_GCM.tag_len());
+        in_out.extend_from_slice(&nonce);
+        in_out.extend_from_slice(data);
+
+        let tag = self
+            .key
+            .seal_in_place_separate_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::empty(),
+                &mut in_out[NONCE_LEN..],
+            )
+            .map_err(|_| OperationError::service_error("Failed to encrypt stored value"))?;
+        in_out.extend_from_slice(tag.as_ref());
+
+        Ok(in_out)
+    }
+
+    /// Decrypt data produced by [`StorageCipher::encrypt`]
+    pub fn decrypt(&self, data: &[u8]) -> OperationResult<Vec<u8>> {
+        if data.len() < NONCE_LEN + AES_256_GCM.tag_len() {
+            return Err(OperationError::service_error(
+                "Encrypted stored value is truncated",
+            ));
+        }
+
+        let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(nonce)
+            .map_err(|_| OperationError::service_error("Invalid stored value nonce"))?;
+
+        let mut in_out = ciphertext.to_vec();
+        let plaintext_len = self
+            .key
+            .open_in_place(nonce, Aad::empty(), &mut in_out)
+            .map_err(|_| {
+                OperationError::service_error(
+                    "Failed to decrypt stored value, probably wrong encryption key",
+                )
+            })?
+            .len();
+        in_out.truncate(plaintext_len);
+
+        Ok(in_out)
+    }
+}
+
+/// Load the data key of the segment, which its vectors and payload are encrypted with.
+///
+/// A new data key is generated for a new segment, if `collection_key` is given.
+/// Existing segments without a data key stay in plain text.
+pub fn load_segment_key(
+    segment_path: &Path,
+    collection_key: Option<&StorageCipher>,
+    is_new: bool,
+) -> OperationResult<Option<Arc<StorageCipher>>> {
+    let data_key_path = segment_path.join(DATA_KEY_FILE);
+
+    if data_key_path.exists() {
+        let Some(collection_key) = collection_key else {
+            return Err(OperationError::service_error(format!(
+                "Segment {} is encrypted, but no storage encryption key is configured for its collection",
+                segment_path.display(),
+            )));
+        };
+        let wrapped_key: Vec<u8> = read_bin(&data_key_path)?;
+        let data_key = collection_key.decrypt(&wrapped_key)?;
+        return Ok(Some(Arc::new(StorageCipher::new(&data_key)?)));
+    }
+
+    match collection_key {
+        Some(collection_key) if is_new => {
+            let mut data_key = [0u8; KEY_LEN];
+            SystemRandom::new()
+                .fill(&mut data_key)
+                .map_err(|_| OperationError::service_error("Failed to generate data key"))?;
+            atomic_save_bin(&data_key_path, &collection_key.encrypt(&data_key)?)?;
+            Ok(Some(Arc::new(StorageCipher::new(&data_key)?)))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Check that all storages of the segment keep their values encrypted.
+///
+/// Only values of in-memory vector storages and RocksDB payload storages are encrypted, so
+/// segments with other storages are refused rather than written partially in plain text.
+pub fn check_supported(config: &SegmentConfig) -> OperationResult<()> {
+    let unsupported = |storage: &str| {
+        Err(OperationError::ValidationError {
+            description: format!("Storage encryption is not supported with {storage}"),
+        })
+    };
+
+    for (vector_name, vector_config) in &config.vector_data {
+        if vector_config.storage_type != VectorStorageType::Memory {
+            return unsupported(&format!("on-disk storage of vector `{vector_name}`"));
+        }
+        if vector_config.quantization_config.is_some() {
+            return unsupported(&format!("quantization of vector `{vector_name}`"));
+        }
+    }
+    if !config.sparse_vector_data.is_empty() {
+        return unsupported("sparse vectors");
+    }
+    match config.payload_storage_type {
+        PayloadStorageType::InMemory | PayloadStorageType::OnDisk => {}
+        PayloadStorageType::AppendOnly | PayloadStorageType::Mmap => {
+            return unsupported("append-only or mmap storage engine");
+        }
+    }
+    if !config.payload_columns.is_empty() {
+        return unsupported("payload columns");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::Builder;
+
+    use super::*;
+
+    #[test]
+    fn test_segment_key() {
+        let dir = Builder::new().prefix("segment").tempdir().unwrap();
+        let segment_path = dir.path();
+
+        // Not encrypted without a collection key
+        assert!(load_segment_key(segment_path, None, true)
+            .unwrap()
+            .is_none());
+
+        let collection_key = StorageCipher::new(&[7; KEY_LEN]).unwrap();
+
+        // Existing segments stay in plain text
+        assert!(load_segment_key(segment_path, Some(&collection_key), false)
+            .unwrap()
+            .is_none());
+
+        let data_key = load_segment_key(segment_path, Some(&collection_key), true)
+            .unwrap()
+            .unwrap();
+        let encrypted = data_key.encrypt(b"vector").unwrap();
+
+        // Data key is loaded on restart
+        let data_key = load_segment_key(segment_path, Some(&collection_key), false)
+            .unwrap()
+            .unwrap();
+        assert_eq!(data_key.decrypt(&encrypted).unwrap(), b"vector");
+
+        // Wrong key of the collection
+        let other_key = StorageCipher::new(&[8; KEY_LEN]).unwrap();
+        assert!(load_segment_key(segment_path, Some(&other_key), false).is_err());
+
+        // Encrypted segment without a key of the collection
+        assert!(load_segment_key(segment_path, None, false).is_err());
+    }
+}
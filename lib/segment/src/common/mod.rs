@@ -1,10 +1,13 @@
 pub mod anonymize;
+pub mod append_only_store;
+pub mod encryption;
 pub mod error_logging;
 pub mod macros;
 pub mod mmap_type;
 pub mod operation_error;
 pub mod operation_time_statistics;
 pub mod reciprocal_rank_fusion;
+pub mod request_usage;
 pub mod rocksdb_buffered_delete_wrapper;
 pub mod rocksdb_buffered_update_wrapper;
 pub mod rocksdb_wrapper;
@@ -186,6 +189,17 @@ fn check_vector_against_config(
             }
             Ok(())
         }
+        VectorRef::DenseDouble(vector) => {
+            // Check dimensionality
+            let dim = vector_config.size;
+            if vector.len() != dim {
+                return Err(OperationError::WrongVectorDimension {
+                    expected_dim: dim,
+                    received_dim: vector.len(),
+                });
+            }
+            Ok(())
+        }
         VectorRef::Sparse(_) => Err(OperationError::WrongSparse),
         VectorRef::MultiDense(multi_vector) => {
             // Check dimensionality
@@ -208,7 +222,7 @@ fn check_sparse_vector_against_config(
     _vector_config: &SparseVectorDataConfig,
 ) -> OperationResult<()> {
     match vector {
-        VectorRef::Dense(_) => Err(OperationError::WrongSparse),
+        VectorRef::Dense(_) | VectorRef::DenseDouble(_) => Err(OperationError::WrongSparse),
         VectorRef::Sparse(_vector) => Ok(()), // TODO(sparse) check vector by config
         VectorRef::MultiDense(_) => Err(OperationError::WrongMulti),
     }
@@ -54,6 +54,8 @@ pub enum OperationError {
     WrongSparse,
     #[error("Wrong usage of multi vectors")]
     WrongMulti,
+    #[error("Wrong usage of double precision vectors")]
+    WrongDouble,
     #[error("Wrong key of payload")]
     WrongPayloadKey { description: String },
 }
@@ -0,0 +1,135 @@
+//! Resources used by a single search or scroll request, to attribute load to query patterns.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Resources used by a request so far, shared between all threads serving it
+#[derive(Debug, Default)]
+pub struct RequestUsage {
+    cpu_nanos: AtomicU64,
+    disk_read_bytes: AtomicU64,
+    vectors_scored: AtomicU64,
+    payloads_read: AtomicU64,
+}
+
+/// Resources used by a request on the peer, which served it.
+///
+/// Shards on other peers are not included.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct RequestUsageReport {
+    /// CPU time spent by threads searching and reading segments, in seconds
+    pub cpu_seconds: f64,
+    /// Number of vectors compared with the query, including quantized ones and rescoring
+    pub vectors_scored: u64,
+    /// Number of payloads read from storage
+    pub payloads_read: u64,
+    /// Bytes read from disk, while pages were not in the page cache
+    pub disk_read_bytes: u64,
+}
+
+impl RequestUsage {
+    pub fn add_vectors_scored(&self, count: usize) {
+        self.vectors_scored
+            .fetch_add(count as u64, Ordering::Relaxed);
+    }
+
+    pub fn add_payloads_read(&self, count: usize) {
+        self.payloads_read
+            .fetch_add(count as u64, Ordering::Relaxed);
+    }
+
+    /// Add CPU time and disk reads of the current thread while running `f`
+    pub fn measure<T>(&self, f: impl FnOnce() -> T) -> T {
+        let started = ThreadUsage::current();
+        let output = f();
+        let finished = ThreadUsage::current();
+        if let (Some(started), Some(finished)) = (started.cpu_nanos, finished.cpu_nanos) {
+            self.cpu_nanos
+                .fetch_add(finished.saturating_sub(started), Ordering::Relaxed);
+        }
+        if let (Some(started), Some(finished)) = (started.read_bytes, finished.read_bytes) {
+            self.disk_read_bytes
+                .fetch_add(finished.saturating_sub(started), Ordering::Relaxed);
+        }
+        output
+    }
+
+    pub fn report(&self) -> RequestUsageReport {
+        RequestUsageReport {
+            cpu_seconds: self.cpu_nanos.load(Ordering::Relaxed) as f64 / 1e9,
+            vectors_scored: self.vectors_scored.load(Ordering::Relaxed),
+            payloads_read: self.payloads_read.load(Ordering::Relaxed),
+            disk_read_bytes: self.disk_read_bytes.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Run `f`, measuring it into the usage of the request, if the usage is requested
+pub fn measure<T>(usage: Option<&RequestUsage>, f: impl FnOnce() -> T) -> T {
+    match usage {
+        Some(usage) => usage.measure(f),
+        None => f(),
+    }
+}
+
+/// Counters of the current thread, maintained by the kernel
+struct ThreadUsage {
+    cpu_nanos: Option<u64>,
+    read_bytes: Option<u64>,
+}
+
+impl ThreadUsage {
+    /// Counters are read from procfs, they are not available on other platforms
+    #[cfg(target_os = "linux")]
+    fn current() -> Self {
+        // First field is the time spent on the CPU, in nanoseconds
+        let cpu_nanos = std::fs::read_to_string("/proc/thread-self/schedstat")
+            .ok()
+            .and_then(|schedstat| schedstat.split_whitespace().next()?.parse().ok());
+
+        let read_bytes = std::fs::read_to_string("/proc/thread-self/io")
+            .ok()
+            .and_then(|io| {
+                io.lines()
+                    .find_map(|line| line.strip_prefix("read_bytes:"))?
+                    .trim()
+                    .parse()
+                    .ok()
+            });
+
+        Self {
+            cpu_nanos,
+            read_bytes,
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn current() -> Self {
+        Self {
+            cpu_nanos: None,
+            read_bytes: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_usage() {
+        let usage = RequestUsage::default();
+        usage.add_vectors_scored(10);
+        usage.add_vectors_scored(5);
+        usage.add_payloads_read(3);
+
+        let sum = usage.measure(|| (0..1_000_000u64).map(std::hint::black_box).sum::<u64>());
+        assert_eq!(sum, 499_999_500_000);
+
+        let report = usage.report();
+        assert_eq!(report.vectors_scored, 15);
+        assert_eq!(report.payloads_read, 3);
+    }
+}
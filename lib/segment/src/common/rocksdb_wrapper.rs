@@ -6,6 +6,7 @@ use parking_lot::RwLock;
 use rocksdb::{ColumnFamily, DBRecoveryMode, LogLevel, Options, WriteOptions, DB};
 
 //use crate::common::arc_rwlock_iterator::ArcRwLockIterator;
+use crate::common::encryption::StorageCipher;
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::Flusher;
 
@@ -25,16 +26,20 @@ pub const DB_DEFAULT_CF: &str = "default";
 pub struct DatabaseColumnWrapper {
     pub database: Arc<RwLock<DB>>,
     pub column_name: String,
+    /// Data key of the segment, values are stored encrypted with it
+    cipher: Option<Arc<StorageCipher>>,
 }
 
 pub struct DatabaseColumnIterator<'a> {
     pub handle: &'a ColumnFamily,
     pub iter: rocksdb::DBRawIterator<'a>,
+    cipher: Option<&'a StorageCipher>,
 }
 
 pub struct LockedDatabaseColumnWrapper<'a> {
     guard: parking_lot::RwLockReadGuard<'a, DB>,
     column_name: &'a str,
+    cipher: Option<&'a StorageCipher>,
 }
 
 /// RocksDB options (both global and for column families)
@@ -119,6 +124,20 @@ impl DatabaseColumnWrapper {
         Self {
             database,
             column_name: column_name.to_string(),
+            cipher: None,
+        }
+    }
+
+    /// Column, values of which are stored encrypted with the data key of the segment, if any
+    pub fn new_encrypted(
+        database: Arc<RwLock<DB>>,
+        column_name: &str,
+        cipher: Option<Arc<StorageCipher>>,
+    ) -> Self {
+        Self {
+            database,
+            column_name: column_name.to_string(),
+            cipher,
         }
     }
 
@@ -127,6 +146,15 @@ impl DatabaseColumnWrapper {
         K: AsRef<[u8]>,
         V: AsRef<[u8]>,
     {
+        let encrypted;
+        let value = match &self.cipher {
+            Some(cipher) => {
+                encrypted = cipher.encrypt(value.as_ref())?;
+                encrypted.as_slice()
+            }
+            None => value.as_ref(),
+        };
+
         let db = self.database.read();
         let cf_handle = self.get_column_family(&db)?;
         db.put_cf_opt(cf_handle, key, value, &Self::get_write_options())
@@ -140,9 +168,14 @@ impl DatabaseColumnWrapper {
     {
         let db = self.database.read();
         let cf_handle = self.get_column_family(&db)?;
-        db.get_cf(cf_handle, key)
+        let value = db
+            .get_cf(cf_handle, key)
             .map_err(|err| OperationError::service_error(format!("RocksDB get_cf error: {err}")))?
-            .ok_or_else(|| OperationError::service_error("RocksDB get_cf error: key not found"))
+            .ok_or_else(|| OperationError::service_error("RocksDB get_cf error: key not found"))?;
+        match &self.cipher {
+            Some(cipher) => cipher.decrypt(&value),
+            None => Ok(value),
+        }
     }
 
     pub fn get_pinned<T, F>(&self, key: &[u8], f: F) -> OperationResult<Option<T>>
@@ -151,13 +184,16 @@ impl DatabaseColumnWrapper {
     {
         let db = self.database.read();
         let cf_handle = self.get_column_family(&db)?;
-        let result = db
-            .get_pinned_cf(cf_handle, key)
-            .map_err(|err| {
-                OperationError::service_error(format!("RocksDB get_pinned_cf error: {err}"))
-            })?
-            .map(|value| f(&value));
-        Ok(result)
+        let Some(value) = db.get_pinned_cf(cf_handle, key).map_err(|err| {
+            OperationError::service_error(format!("RocksDB get_pinned_cf error: {err}"))
+        })?
+        else {
+            return Ok(None);
+        };
+        match &self.cipher {
+            Some(cipher) => Ok(Some(f(&cipher.decrypt(&value)?))),
+            None => Ok(Some(f(&value))),
+        }
     }
 
     pub fn remove<K>(&self, key: K) -> OperationResult<()>
@@ -176,6 +212,7 @@ impl DatabaseColumnWrapper {
         LockedDatabaseColumnWrapper {
             guard: self.database.read(),
             column_name: &self.column_name,
+            cipher: self.cipher.as_deref(),
         }
     }
 
@@ -256,7 +293,9 @@ impl DatabaseColumnWrapper {
 
 impl<'a> LockedDatabaseColumnWrapper<'a> {
     pub fn iter(&self) -> OperationResult<DatabaseColumnIterator> {
-        DatabaseColumnIterator::new(&self.guard, self.column_name)
+        let mut iter = DatabaseColumnIterator::new(&self.guard, self.column_name)?;
+        iter.cipher = self.cipher;
+        Ok(iter)
     }
 }
 
@@ -269,12 +308,16 @@ impl<'a> DatabaseColumnIterator<'a> {
         })?;
         let mut iter = db.raw_iterator_cf(&handle);
         iter.seek_to_first();
-        Ok(DatabaseColumnIterator { handle, iter })
+        Ok(DatabaseColumnIterator {
+            handle,
+            iter,
+            cipher: None,
+        })
     }
 }
 
 impl<'a> Iterator for DatabaseColumnIterator<'a> {
-    type Item = (Box<[u8]>, Box<[u8]>);
+    type Item = OperationResult<(Box<[u8]>, Box<[u8]>)>;
 
     fn next(&mut self) -> Option<Self::Item> {
         // Stop if iterator has ended or errored
@@ -282,14 +325,16 @@ impl<'a> Iterator for DatabaseColumnIterator<'a> {
             return None;
         }
 
-        let item = (
-            Box::from(self.iter.key().unwrap()),
-            Box::from(self.iter.value().unwrap()),
-        );
+        let key = Box::from(self.iter.key().unwrap());
+        let value = self.iter.value().unwrap();
+        let value = match self.cipher {
+            Some(cipher) => cipher.decrypt(value).map(Box::from),
+            None => Ok(Box::from(value)),
+        };
 
         // Search to next item for next iteration
         self.iter.next();
 
-        Some(item)
+        Some(value.map(|value| (key, value)))
     }
 }
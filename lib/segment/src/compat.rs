@@ -64,6 +64,8 @@ impl From<SegmentConfigV5> for SegmentConfig {
             vector_data,
             sparse_vector_data: Default::default(),
             payload_storage_type: old_segment.payload_storage_type,
+            payload_columns: vec![],
+            storage_cipher: None,
         }
     }
 }
@@ -2,6 +2,7 @@ pub mod groups;
 pub mod integer_index;
 pub mod named_vectors;
 pub mod order_by;
+pub mod payload_projection;
 pub mod primitive;
 pub mod query_context;
 pub mod text_index;
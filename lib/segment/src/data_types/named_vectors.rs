@@ -7,10 +7,12 @@ use super::primitive::PrimitiveVectorElement;
 use super::tiny_map;
 use super::vectors::{
     DenseVector, MultiDenseVector, TypedMultiDenseVector, TypedMultiDenseVectorRef, Vector,
-    VectorElementType, VectorElementTypeByte, VectorElementTypeHalf, VectorRef,
+    VectorElementType, VectorElementTypeByte, VectorElementTypeDouble, VectorElementTypeHalf,
+    VectorRef,
 };
 use crate::common::operation_error::OperationError;
 use crate::spaces::metric::Metric;
+use crate::spaces::metric_f64::preprocess_double;
 use crate::spaces::simple::{CosineMetric, DotProductMetric, EuclidMetric, ManhattanMetric};
 use crate::types::{Distance, VectorDataConfig, VectorStorageDatatype};
 
@@ -25,6 +27,7 @@ pub enum CowMultiVector<'a, TElement: PrimitiveVectorElement> {
 #[derive(Clone, PartialEq, Debug)]
 pub enum CowVector<'a> {
     Dense(Cow<'a, [VectorElementType]>),
+    DenseDouble(Cow<'a, [VectorElementTypeDouble]>),
     Sparse(Cow<'a, SparseVector>),
     MultiDense(CowMultiVector<'a, VectorElementType>),
 }
@@ -69,6 +72,7 @@ impl<'a> CowVector<'a> {
     pub fn to_owned(self) -> Vector {
         match self {
             CowVector::Dense(v) => Vector::Dense(v.into_owned()),
+            CowVector::DenseDouble(v) => Vector::DenseDouble(v.into_owned()),
             CowVector::Sparse(v) => Vector::Sparse(v.into_owned()),
             CowVector::MultiDense(v) => Vector::MultiDense(v.to_owned()),
         }
@@ -77,6 +81,7 @@ impl<'a> CowVector<'a> {
     pub fn as_vec_ref(&self) -> VectorRef {
         match self {
             CowVector::Dense(v) => VectorRef::Dense(v.as_ref()),
+            CowVector::DenseDouble(v) => VectorRef::DenseDouble(v.as_ref()),
             CowVector::Sparse(v) => VectorRef::Sparse(v.as_ref()),
             CowVector::MultiDense(v) => VectorRef::MultiDense(v.as_vec_ref()),
         }
@@ -96,6 +101,7 @@ impl<'a> From<Vector> for CowVector<'a> {
     fn from(v: Vector) -> Self {
         match v {
             Vector::Dense(v) => CowVector::Dense(Cow::Owned(v)),
+            Vector::DenseDouble(v) => CowVector::DenseDouble(Cow::Owned(v)),
             Vector::Sparse(v) => CowVector::Sparse(Cow::Owned(v)),
             Vector::MultiDense(v) => CowVector::MultiDense(CowMultiVector::Owned(v)),
         }
@@ -154,7 +160,7 @@ impl<'a> TryFrom<CowVector<'a>> for SparseVector {
 
     fn try_from(value: CowVector<'a>) -> Result<Self, Self::Error> {
         match value {
-            CowVector::Dense(_) => Err(OperationError::WrongSparse),
+            CowVector::Dense(_) | CowVector::DenseDouble(_) => Err(OperationError::WrongSparse),
             CowVector::Sparse(v) => Ok(v.into_owned()),
             CowVector::MultiDense(_) => Err(OperationError::WrongSparse),
         }
@@ -167,6 +173,7 @@ impl<'a> TryFrom<CowVector<'a>> for DenseVector {
     fn try_from(value: CowVector<'a>) -> Result<Self, Self::Error> {
         match value {
             CowVector::Dense(v) => Ok(v.into_owned()),
+            CowVector::DenseDouble(_) => Err(OperationError::WrongDouble),
             CowVector::Sparse(_) => Err(OperationError::WrongSparse),
             CowVector::MultiDense(_) => Err(OperationError::WrongMulti),
         }
@@ -179,6 +186,7 @@ impl<'a> TryFrom<CowVector<'a>> for Cow<'a, [VectorElementType]> {
     fn try_from(value: CowVector<'a>) -> Result<Self, Self::Error> {
         match value {
             CowVector::Dense(v) => Ok(v),
+            CowVector::DenseDouble(_) => Err(OperationError::WrongDouble),
             CowVector::Sparse(_) => Err(OperationError::WrongSparse),
             CowVector::MultiDense(_) => Err(OperationError::WrongMulti),
         }
@@ -189,6 +197,7 @@ impl<'a> From<VectorRef<'a>> for CowVector<'a> {
     fn from(v: VectorRef<'a>) -> Self {
         match v {
             VectorRef::Dense(v) => CowVector::Dense(Cow::Borrowed(v)),
+            VectorRef::DenseDouble(v) => CowVector::DenseDouble(Cow::Borrowed(v)),
             VectorRef::Sparse(v) => CowVector::Sparse(Cow::Borrowed(v)),
             VectorRef::MultiDense(v) => CowVector::MultiDense(CowMultiVector::Borrowed(v)),
         }
@@ -273,10 +282,9 @@ impl<'a> NamedVectors<'a> {
     pub fn preprocess<'b>(&mut self, get_vector_data: impl Fn(&str) -> &'b VectorDataConfig) {
         for (name, vector) in self.map.iter_mut() {
             match vector {
-                CowVector::Dense(v) => {
+                CowVector::Dense(_) | CowVector::DenseDouble(_) => {
                     let config = get_vector_data(name.as_ref());
-                    let preprocessed_vector = Self::preprocess_dense_vector(v.to_vec(), config);
-                    *vector = CowVector::Dense(Cow::Owned(preprocessed_vector))
+                    *vector = Self::preprocess_dense(std::mem::take(vector), config);
                 }
                 CowVector::Sparse(v) => {
                     // sort by indices to enable faster dot product and overlap checks
@@ -306,6 +314,34 @@ impl<'a> NamedVectors<'a> {
         }
     }
 
+    /// Convert dense vector into the precision of the storage and preprocess it
+    ///
+    /// Vectors of `float64` storages are preprocessed in double precision.
+    fn preprocess_dense(vector: CowVector<'a>, config: &VectorDataConfig) -> CowVector<'a> {
+        let is_double = config.datatype == Some(VectorStorageDatatype::Float64);
+        match vector {
+            CowVector::Dense(v) if is_double => {
+                let v = v
+                    .iter()
+                    .map(|&x| VectorElementTypeDouble::from(x))
+                    .collect();
+                CowVector::DenseDouble(Cow::Owned(preprocess_double(config.distance, v)))
+            }
+            CowVector::DenseDouble(v) if is_double => {
+                CowVector::DenseDouble(Cow::Owned(preprocess_double(config.distance, v.to_vec())))
+            }
+            CowVector::Dense(v) => CowVector::Dense(Cow::Owned(Self::preprocess_dense_vector(
+                v.to_vec(),
+                config,
+            ))),
+            CowVector::DenseDouble(v) => {
+                let v = v.iter().map(|&x| x as VectorElementType).collect();
+                CowVector::Dense(Cow::Owned(Self::preprocess_dense_vector(v, config)))
+            }
+            CowVector::Sparse(_) | CowVector::MultiDense(_) => vector,
+        }
+    }
+
     fn preprocess_dense_vector(
         dense_vector: DenseVector,
         config: &VectorDataConfig,
@@ -353,6 +389,20 @@ impl<'a> NamedVectors<'a> {
                     <ManhattanMetric as Metric<VectorElementTypeHalf>>::preprocess(dense_vector)
                 }
             },
+            Some(VectorStorageDatatype::Float64) => match config.distance {
+                Distance::Cosine => {
+                    <CosineMetric as Metric<VectorElementTypeDouble>>::preprocess(dense_vector)
+                }
+                Distance::Euclid => {
+                    <EuclidMetric as Metric<VectorElementTypeDouble>>::preprocess(dense_vector)
+                }
+                Distance::Dot => {
+                    <DotProductMetric as Metric<VectorElementTypeDouble>>::preprocess(dense_vector)
+                }
+                Distance::Manhattan => {
+                    <ManhattanMetric as Metric<VectorElementTypeDouble>>::preprocess(dense_vector)
+                }
+            },
         }
     }
 }
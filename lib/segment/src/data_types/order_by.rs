@@ -6,7 +6,8 @@ use validator::Validate;
 
 use crate::json_path::JsonPath;
 use crate::types::{
-    DateTimePayloadType, FloatPayloadType, IntPayloadType, Payload, Range, RangeInterface,
+    DateTimePayloadType, DateTimeTimestamp, FloatPayloadType, IntPayloadType, Payload, Range,
+    RangeInterface,
 };
 
 const INTERNAL_KEY_OF_ORDER_BY_VALUE: &str = "____ordered_with____";
@@ -90,7 +91,7 @@ impl OrderBy {
             .map(|start_from| match start_from {
                 StartFrom::Integer(i) => OrderValue::Int(*i),
                 StartFrom::Float(f) => OrderValue::Float(*f),
-                StartFrom::Datetime(dt) => OrderValue::Int(dt.timestamp()),
+                StartFrom::Datetime(dt) => OrderValue::from(dt.timestamp()),
             })
             .unwrap_or_else(|| match self.direction() {
                 Direction::Asc => OrderValue::MIN,
@@ -178,6 +179,16 @@ impl From<IntPayloadType> for OrderValue {
     }
 }
 
+/// Timestamps out of the i64 range are ordered as floats
+impl From<DateTimeTimestamp> for OrderValue {
+    fn from(value: DateTimeTimestamp) -> Self {
+        match IntPayloadType::try_from(value) {
+            Ok(value) => OrderValue::Int(value),
+            Err(_) => OrderValue::Float(value as FloatPayloadType),
+        }
+    }
+}
+
 impl Eq for OrderValue {}
 
 impl PartialEq for OrderValue {
@@ -0,0 +1,442 @@
+//! Projection of payload with glob patterns over nested keys.
+//!
+//! Pattern is a dot-separated path of keys, each key may contain `*` wildcards,
+//! which match any part of a single key. E.g. `metadata.*` matches all keys of `metadata` object,
+//! `meta*.raw_*` matches `raw_text` in `metadata` and `meta_info` objects.
+//! Arrays are transparent for patterns: pattern `items.name` selects `name` in every object of `items` array.
+//!
+//! Projection may be applied to an already deserialized payload, or while deserializing it,
+//! in which case values which are not selected are skipped without being allocated.
+
+use std::fmt;
+use std::str::FromStr;
+
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
+use schemars::JsonSchema;
+use serde::de::{DeserializeSeed, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Map, Value};
+
+use crate::types::Payload;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PayloadKeyPattern {
+    keys: Vec<String>,
+}
+
+impl PayloadKeyPattern {
+    /// Check if the pattern matches first keys of the path
+    fn matches_prefix(&self, path: &[String]) -> bool {
+        self.keys
+            .iter()
+            .zip(path)
+            .all(|(pattern, key)| wildcard_match(pattern, key))
+    }
+
+    /// Pattern selects exactly this path
+    fn matches(&self, path: &[String]) -> bool {
+        self.keys.len() == path.len() && self.matches_prefix(path)
+    }
+
+    /// Pattern selects something nested into this path
+    fn matches_nested(&self, path: &[String]) -> bool {
+        self.keys.len() > path.len() && self.matches_prefix(path)
+    }
+}
+
+/// Match `key` against `pattern`, where `*` matches any sequence of characters
+fn wildcard_match(pattern: &str, key: &str) -> bool {
+    let mut parts = pattern.split('*');
+    // There is always at least one part
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = key.strip_prefix(first) else {
+        return false;
+    };
+
+    let mut parts = parts.peekable();
+    while let Some(part) = parts.next() {
+        if parts.peek().is_none() {
+            // Last part must be at the end of the key
+            return rest.len() >= part.len() && rest.ends_with(part);
+        }
+        match rest.find(part) {
+            Some(position) => rest = &rest[position + part.len()..],
+            None => return false,
+        }
+    }
+
+    // No wildcards in pattern
+    rest.is_empty()
+}
+
+impl FromStr for PayloadKeyPattern {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let keys: Vec<String> = s.split('.').map(str::to_string).collect();
+        if keys.iter().any(String::is_empty) {
+            return Err(format!(
+                "Invalid payload key pattern `{s}`, keys must not be empty"
+            ));
+        }
+        Ok(Self { keys })
+    }
+}
+
+impl fmt::Display for PayloadKeyPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.keys.join("."))
+    }
+}
+
+impl Serialize for PayloadKeyPattern {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for PayloadKeyPattern {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+impl JsonSchema for PayloadKeyPattern {
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    fn schema_name() -> String {
+        "PayloadKeyPattern".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        String::json_schema(gen)
+    }
+}
+
+/// What to do with a value at some path
+enum Selection {
+    /// Value is selected completely
+    Keep,
+    /// Value is not selected
+    Skip,
+    /// Some of the nested values are selected, `true` if the value itself is included
+    Descend(bool),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct PayloadProjection<'a> {
+    /// Only keys matching any of these patterns are selected. All keys, if empty
+    pub include: &'a [PayloadKeyPattern],
+    /// Keys matching any of these patterns are not selected, even if included
+    pub exclude: &'a [PayloadKeyPattern],
+}
+
+impl<'a> PayloadProjection<'a> {
+    fn select(&self, path: &[String], included: bool) -> Selection {
+        if self.exclude.iter().any(|pattern| pattern.matches(path)) {
+            return Selection::Skip;
+        }
+
+        let included = included || self.include.iter().any(|pattern| pattern.matches(path));
+        let nested_exclude = || {
+            self.exclude
+                .iter()
+                .any(|pattern| pattern.matches_nested(path))
+        };
+
+        if included {
+            if nested_exclude() {
+                Selection::Descend(true)
+            } else {
+                Selection::Keep
+            }
+        } else if self
+            .include
+            .iter()
+            .any(|pattern| pattern.matches_nested(path))
+        {
+            Selection::Descend(false)
+        } else {
+            Selection::Skip
+        }
+    }
+
+    fn root_included(&self) -> bool {
+        self.include.is_empty()
+    }
+
+    /// Select values of an already deserialized payload
+    /// Deserialize CBOR-encoded payload, skipping values which are not selected
+    pub fn from_cbor(self, raw: &[u8]) -> serde_cbor::Result<Payload> {
+        self.deserialize(&mut serde_cbor::Deserializer::from_slice(raw))
+    }
+
+    pub fn project(&self, payload: Payload) -> Payload {
+        let mut path = Vec::new();
+        Payload(self.project_map(payload.0, &mut path, self.root_included()))
+    }
+
+    fn project_map(
+        &self,
+        map: Map<String, Value>,
+        path: &mut Vec<String>,
+        included: bool,
+    ) -> Map<String, Value> {
+        let mut result = Map::new();
+        for (key, value) in map {
+            path.push(key);
+            let selected = match self.select(path, included) {
+                Selection::Keep => Some(value),
+                Selection::Skip => None,
+                Selection::Descend(included) => self.project_value(value, path, included),
+            };
+            let key = path.pop().unwrap_or_default();
+            if let Some(value) = selected {
+                result.insert(key, value);
+            }
+        }
+        result
+    }
+
+    fn project_value(&self, value: Value, path: &mut Vec<String>, included: bool) -> Option<Value> {
+        match value {
+            Value::Object(map) => Some(Value::Object(self.project_map(map, path, included))),
+            Value::Array(array) => Some(Value::Array(
+                array
+                    .into_iter()
+                    .filter_map(|value| self.project_value(value, path, included))
+                    .collect(),
+            )),
+            value => included.then_some(value),
+        }
+    }
+}
+
+/// Deserialize payload, skipping values which are not selected by the projection
+impl<'de, 'a> DeserializeSeed<'de> for PayloadProjection<'a> {
+    type Value = Payload;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        let seed = ProjectedValue {
+            projection: self,
+            path: Vec::new(),
+            included: self.root_included(),
+        };
+        match deserializer.deserialize_map(seed)? {
+            Some(Value::Object(map)) => Ok(Payload(map)),
+            _ => Ok(Payload::default()),
+        }
+    }
+}
+
+/// Value at a given path, which is partially selected
+struct ProjectedValue<'a> {
+    projection: PayloadProjection<'a>,
+    path: Vec<String>,
+    included: bool,
+}
+
+impl<'de, 'a> DeserializeSeed<'de> for ProjectedValue<'a> {
+    type Value = Option<Value>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de, 'a> Visitor<'de> for ProjectedValue<'a> {
+    type Value = Option<Value>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("any valid JSON value")
+    }
+
+    fn visit_bool<E>(self, value: bool) -> Result<Self::Value, E> {
+        Ok(self.included.then_some(Value::Bool(value)))
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E> {
+        Ok(self.included.then(|| Value::from(value)))
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E> {
+        Ok(self.included.then(|| Value::from(value)))
+    }
+
+    fn visit_f64<E>(self, value: f64) -> Result<Self::Value, E> {
+        Ok(self.included.then(|| Value::from(value)))
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E> {
+        Ok(self.included.then(|| Value::String(value.to_string())))
+    }
+
+    fn visit_string<E>(self, value: String) -> Result<Self::Value, E> {
+        Ok(self.included.then_some(Value::String(value)))
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E> {
+        Ok(self.included.then_some(Value::Null))
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E> {
+        Ok(self.included.then_some(Value::Null))
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut values = Vec::new();
+        // Arrays are transparent for patterns, all elements share the path
+        while let Some(value) = seq.next_element_seed(ProjectedValue {
+            projection: self.projection,
+            path: self.path.clone(),
+            included: self.included,
+        })? {
+            values.extend(value);
+        }
+        Ok(Some(Value::Array(values)))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut result = Map::new();
+        let mut path = self.path;
+        while let Some(key) = map.next_key::<String>()? {
+            path.push(key);
+            let selected = match self.projection.select(&path, self.included) {
+                Selection::Keep => Some(map.next_value::<Value>()?),
+                Selection::Skip => {
+                    map.next_value::<IgnoredAny>()?;
+                    None
+                }
+                Selection::Descend(included) => map.next_value_seed(ProjectedValue {
+                    projection: self.projection,
+                    path: path.clone(),
+                    included,
+                })?,
+            };
+            let key = path.pop().unwrap_or_default();
+            if let Some(value) = selected {
+                result.insert(key, value);
+            }
+        }
+        Ok(Some(Value::Object(result)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn patterns(patterns: &[&str]) -> Vec<PayloadKeyPattern> {
+        patterns.iter().map(|p| p.parse().unwrap()).collect()
+    }
+
+    fn payload() -> Value {
+        json!({
+            "title": "Document",
+            "metadata": {
+                "author": "John",
+                "raw_text": "Very long text",
+                "raw_html": "<p>Very long text</p>",
+                "pages": 10,
+            },
+            "chunks": [
+                {"text": "First", "embedding_id": 1},
+                {"text": "Second", "embedding_id": 2},
+            ],
+        })
+    }
+
+    fn check(include: &[&str], exclude: &[&str], expected: Value) {
+        let include = patterns(include);
+        let exclude = patterns(exclude);
+        let projection = PayloadProjection {
+            include: &include,
+            exclude: &exclude,
+        };
+
+        let Value::Object(map) = payload() else {
+            unreachable!()
+        };
+        let projected = projection.project(Payload(map));
+        assert_eq!(Value::Object(projected.0), expected);
+
+        // Same result while deserializing
+        let bytes = serde_cbor::to_vec(&payload()).unwrap();
+        let mut deserializer = serde_cbor::Deserializer::from_slice(&bytes);
+        let projected = projection.deserialize(&mut deserializer).unwrap();
+        assert_eq!(Value::Object(projected.0), expected);
+    }
+
+    #[test]
+    fn test_wildcard_match() {
+        assert!(wildcard_match("raw_*", "raw_text"));
+        assert!(wildcard_match("*", "anything"));
+        assert!(wildcard_match("*_text", "raw_text"));
+        assert!(wildcard_match("r*_t*t", "raw_text"));
+        assert!(wildcard_match("raw_text", "raw_text"));
+        assert!(!wildcard_match("raw_text", "raw_texts"));
+        assert!(!wildcard_match("raw_*", "text"));
+        assert!(!wildcard_match("*_html", "raw_text"));
+        assert!(!wildcard_match("a*a", "a"));
+    }
+
+    #[test]
+    fn test_include_with_exclude() {
+        check(
+            &["metadata.*"],
+            &["metadata.raw_text"],
+            json!({
+                "metadata": {
+                    "author": "John",
+                    "raw_html": "<p>Very long text</p>",
+                    "pages": 10,
+                },
+            }),
+        );
+    }
+
+    #[test]
+    fn test_exclude_wildcard() {
+        check(
+            &[],
+            &["metadata.raw_*", "chunks.embedding_id"],
+            json!({
+                "title": "Document",
+                "metadata": {
+                    "author": "John",
+                    "pages": 10,
+                },
+                "chunks": [
+                    {"text": "First"},
+                    {"text": "Second"},
+                ],
+            }),
+        );
+    }
+
+    #[test]
+    fn test_include_through_array() {
+        check(
+            &["title", "chunks.text"],
+            &[],
+            json!({
+                "title": "Document",
+                "chunks": [
+                    {"text": "First"},
+                    {"text": "Second"},
+                ],
+            }),
+        );
+    }
+}
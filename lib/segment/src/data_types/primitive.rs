@@ -4,9 +4,12 @@ use half::f16;
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 
-use super::named_vectors::CowMultiVector;
-use super::vectors::TypedMultiDenseVector;
-use crate::data_types::vectors::{VectorElementType, VectorElementTypeByte, VectorElementTypeHalf};
+use super::named_vectors::{CowMultiVector, CowVector};
+use super::vectors::{TypedMultiDenseVector, VectorRef};
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::data_types::vectors::{
+    VectorElementType, VectorElementTypeByte, VectorElementTypeDouble, VectorElementTypeHalf,
+};
 use crate::spaces::metric::Metric;
 use crate::spaces::simple::{CosineMetric, DotProductMetric, EuclidMetric, ManhattanMetric};
 use crate::types::{Distance, QuantizationConfig, VectorStorageDatatype};
@@ -16,8 +19,25 @@ pub trait PrimitiveVectorElement:
 {
     fn slice_from_float_cow(vector: Cow<[VectorElementType]>) -> Cow<[Self]>;
 
+    fn slice_from_double_cow(vector: Cow<[VectorElementTypeDouble]>) -> Cow<[Self]>;
+
     fn slice_to_float_cow(vector: Cow<[Self]>) -> Cow<[VectorElementType]>;
 
+    /// Convert dense vector of either precision into this type
+    fn slice_from_vector_ref(vector: VectorRef) -> OperationResult<Cow<[Self]>> {
+        match vector {
+            VectorRef::Dense(v) => Ok(Self::slice_from_float_cow(Cow::Borrowed(v))),
+            VectorRef::DenseDouble(v) => Ok(Self::slice_from_double_cow(Cow::Borrowed(v))),
+            VectorRef::Sparse(_) => Err(OperationError::WrongSparse),
+            VectorRef::MultiDense(_) => Err(OperationError::WrongMulti),
+        }
+    }
+
+    /// Stored vector, as returned by the storage
+    fn slice_to_cow_vector(vector: Cow<[Self]>) -> CowVector {
+        CowVector::from(Self::slice_to_float_cow(vector))
+    }
+
     fn quantization_preprocess<'a>(
         quantization_config: &QuantizationConfig,
         distance: Distance,
@@ -40,6 +60,10 @@ impl PrimitiveVectorElement for VectorElementType {
         vector
     }
 
+    fn slice_from_double_cow(vector: Cow<[VectorElementTypeDouble]>) -> Cow<[Self]> {
+        Cow::Owned(vector.iter().map(|&x| x as Self).collect())
+    }
+
     fn slice_to_float_cow(vector: Cow<[Self]>) -> Cow<[VectorElementType]> {
         vector
     }
@@ -74,6 +98,10 @@ impl PrimitiveVectorElement for VectorElementTypeHalf {
         Cow::Owned(vector.iter().map(|&x| f16::from_f32(x)).collect())
     }
 
+    fn slice_from_double_cow(vector: Cow<[VectorElementTypeDouble]>) -> Cow<[Self]> {
+        Cow::Owned(vector.iter().map(|&x| f16::from_f64(x)).collect())
+    }
+
     fn slice_to_float_cow(vector: Cow<[Self]>) -> Cow<[VectorElementType]> {
         Cow::Owned(vector.iter().map(|&x| f16::to_f32(x)).collect_vec())
     }
@@ -119,11 +147,74 @@ impl PrimitiveVectorElement for VectorElementTypeHalf {
     }
 }
 
+impl PrimitiveVectorElement for VectorElementTypeDouble {
+    fn slice_from_float_cow(vector: Cow<[VectorElementType]>) -> Cow<[Self]> {
+        Cow::Owned(vector.iter().map(|&x| Self::from(x)).collect())
+    }
+
+    fn slice_from_double_cow(vector: Cow<[VectorElementTypeDouble]>) -> Cow<[Self]> {
+        vector
+    }
+
+    fn slice_to_float_cow(vector: Cow<[Self]>) -> Cow<[VectorElementType]> {
+        Cow::Owned(vector.iter().map(|&x| x as VectorElementType).collect_vec())
+    }
+
+    /// Vectors are returned in double precision, not converted into single precision
+    fn slice_to_cow_vector(vector: Cow<[Self]>) -> CowVector {
+        CowVector::DenseDouble(vector)
+    }
+
+    fn quantization_preprocess<'a>(
+        _quantization_config: &QuantizationConfig,
+        _distance: Distance,
+        vector: &'a [Self],
+    ) -> Cow<'a, [f32]> {
+        Cow::Owned(vector.iter().map(|&x| x as f32).collect_vec())
+    }
+
+    fn from_float_multivector(
+        multivector: CowMultiVector<VectorElementType>,
+    ) -> CowMultiVector<Self> {
+        CowMultiVector::Owned(TypedMultiDenseVector::new(
+            multivector
+                .as_vec_ref()
+                .flattened_vectors
+                .iter()
+                .map(|&x| Self::from(x))
+                .collect_vec(),
+            multivector.as_vec_ref().dim,
+        ))
+    }
+
+    fn into_float_multivector(
+        multivector: CowMultiVector<Self>,
+    ) -> CowMultiVector<VectorElementType> {
+        CowMultiVector::Owned(TypedMultiDenseVector::new(
+            multivector
+                .as_vec_ref()
+                .flattened_vectors
+                .iter()
+                .map(|&x| x as VectorElementType)
+                .collect_vec(),
+            multivector.as_vec_ref().dim,
+        ))
+    }
+
+    fn datatype() -> VectorStorageDatatype {
+        VectorStorageDatatype::Float64
+    }
+}
+
 impl PrimitiveVectorElement for VectorElementTypeByte {
     fn slice_from_float_cow(vector: Cow<[VectorElementType]>) -> Cow<[Self]> {
         Cow::Owned(vector.iter().map(|&x| x as u8).collect())
     }
 
+    fn slice_from_double_cow(vector: Cow<[VectorElementTypeDouble]>) -> Cow<[Self]> {
+        Cow::Owned(vector.iter().map(|&x| x as u8).collect())
+    }
+
     fn slice_to_float_cow(vector: Cow<[Self]>) -> Cow<[VectorElementType]> {
         Cow::Owned(vector.iter().map(|&x| x as VectorElementType).collect_vec())
     }
@@ -1,12 +1,17 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::ops::Deref;
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
 use bitvec::prelude::BitSlice;
+use common::types::PointOffsetType;
+use parking_lot::Mutex;
 use sparse::common::types::{DimId, DimWeight};
 
+use crate::common::request_usage::RequestUsage;
 use crate::data_types::tiny_map;
+use crate::index::field_index::CardinalityEstimation;
+use crate::types::Filter;
 
 #[derive(Debug)]
 pub struct QueryContext {
@@ -26,6 +31,12 @@ pub struct QueryContext {
     /// Required for processing sparse vector search with `idf-dot` similarity.
     #[allow(dead_code)]
     idf: tiny_map::TinyMap<String, HashMap<DimId, usize>>,
+
+    /// Records search strategies chosen by segments, if set
+    search_plan: Option<Arc<SearchPlan>>,
+
+    /// Resources used by the request, if requested
+    usage: Option<Arc<RequestUsage>>,
 }
 
 impl QueryContext {
@@ -35,6 +46,8 @@ impl QueryContext {
             search_optimized_threshold_kb,
             is_stopped: Arc::new(AtomicBool::new(false)),
             idf: tiny_map::TinyMap::new(),
+            search_plan: None,
+            usage: None,
         }
     }
 
@@ -47,6 +60,22 @@ impl QueryContext {
         self
     }
 
+    /// Record search strategies chosen by segments into the plan
+    pub fn with_search_plan(mut self, search_plan: Option<Arc<SearchPlan>>) -> Self {
+        self.search_plan = search_plan;
+        self
+    }
+
+    /// Measure resources used by segments into the usage of the request
+    pub fn with_usage(mut self, usage: Option<Arc<RequestUsage>>) -> Self {
+        self.usage = usage;
+        self
+    }
+
+    pub fn usage(&self) -> Option<&RequestUsage> {
+        self.usage.as_deref()
+    }
+
     pub fn available_point_count(&self) -> usize {
         self.available_point_count
     }
@@ -83,6 +112,7 @@ impl QueryContext {
         SegmentQueryContext {
             query_context: Some(self),
             deleted_points: None,
+            filter_plans: None,
         }
     }
 }
@@ -98,6 +128,8 @@ impl Default for QueryContext {
 pub struct SegmentQueryContext<'a> {
     query_context: Option<&'a QueryContext>,
     deleted_points: Option<&'a BitSlice>,
+    /// Filter planning shared between searches in the same segment
+    filter_plans: Option<&'a FilterPlans>,
 }
 
 impl<'a> SegmentQueryContext<'a> {
@@ -109,10 +141,14 @@ impl<'a> SegmentQueryContext<'a> {
                 is_stopped: Some(&query_context.is_stopped),
                 idf: query_context.idf.get(vector_name),
                 deleted_points: self.deleted_points,
+                filter_plans: self.filter_plans,
+                search_plan: query_context.search_plan.as_deref(),
+                usage: query_context.usage.as_deref(),
             }
         } else {
             VectorQueryContext {
                 deleted_points: self.deleted_points,
+                filter_plans: self.filter_plans,
                 ..Default::default()
             }
         }
@@ -122,6 +158,62 @@ impl<'a> SegmentQueryContext<'a> {
         self.deleted_points = Some(deleted_points);
         self
     }
+
+    /// Share filter planning between all searches made with this context.
+    ///
+    /// Plans are only valid for a single segment, which must not change while they are in use.
+    pub fn with_filter_plans(mut self, filter_plans: &'a FilterPlans) -> Self {
+        self.filter_plans = Some(filter_plans);
+        self
+    }
+
+    /// Context for searching in another segment, which can't use the plans of this one
+    pub fn without_filter_plans(mut self) -> Self {
+        self.filter_plans = None;
+        self
+    }
+}
+
+/// Cardinality estimations and filtered points of a segment, computed once per distinct filter
+#[derive(Debug, Default)]
+pub struct FilterPlans {
+    plans: Mutex<Vec<(Filter, Arc<FilterPlan>)>>,
+}
+
+#[derive(Debug, Default)]
+struct FilterPlan {
+    cardinality: Mutex<Option<CardinalityEstimation>>,
+    filtered_points: Mutex<Option<Arc<Vec<PointOffsetType>>>>,
+}
+
+impl FilterPlans {
+    fn plan(&self, filter: &Filter) -> Arc<FilterPlan> {
+        let mut plans = self.plans.lock();
+        // Batches contain only a few distinct filters, linear lookup is fine
+        if let Some((_, plan)) = plans.iter().find(|(plan_filter, _)| plan_filter == filter) {
+            return plan.clone();
+        }
+        let plan = Arc::new(FilterPlan::default());
+        plans.push((filter.clone(), plan.clone()));
+        plan
+    }
+}
+
+/// Search strategies chosen by segments, e.g. to explain a slow search
+#[derive(Debug, Default)]
+pub struct SearchPlan {
+    strategies: Mutex<BTreeMap<&'static str, usize>>,
+}
+
+impl SearchPlan {
+    pub fn record(&self, strategy: &'static str) {
+        *self.strategies.lock().entry(strategy).or_default() += 1;
+    }
+
+    /// Number of segment searches by strategy
+    pub fn strategies(&self) -> BTreeMap<&'static str, usize> {
+        self.strategies.lock().clone()
+    }
 }
 
 /// Query context related to a specific vector
@@ -139,6 +231,12 @@ pub struct VectorQueryContext<'a> {
     idf: Option<&'a HashMap<DimId, usize>>,
 
     deleted_points: Option<&'a BitSlice>,
+
+    filter_plans: Option<&'a FilterPlans>,
+
+    search_plan: Option<&'a SearchPlan>,
+
+    usage: Option<&'a RequestUsage>,
 }
 
 pub enum SimpleCow<'a, T> {
@@ -170,6 +268,52 @@ impl VectorQueryContext<'_> {
         self.deleted_points
     }
 
+    /// Cardinality of the filter, estimated once per segment if filter plans are shared
+    pub fn estimate_cardinality(
+        &self,
+        filter: &Filter,
+        estimate: impl FnOnce() -> CardinalityEstimation,
+    ) -> CardinalityEstimation {
+        let Some(filter_plans) = self.filter_plans else {
+            return estimate();
+        };
+        filter_plans
+            .plan(filter)
+            .cardinality
+            .lock()
+            .get_or_insert_with(estimate)
+            .clone()
+    }
+
+    /// Points matching the filter, queried once per segment if filter plans are shared
+    pub fn filtered_points(
+        &self,
+        filter: &Filter,
+        query: impl FnOnce() -> Vec<PointOffsetType>,
+    ) -> Arc<Vec<PointOffsetType>> {
+        let Some(filter_plans) = self.filter_plans else {
+            return Arc::new(query());
+        };
+        filter_plans
+            .plan(filter)
+            .filtered_points
+            .lock()
+            .get_or_insert_with(|| Arc::new(query()))
+            .clone()
+    }
+
+    /// Record the search strategy chosen by the segment, named as in its telemetry
+    pub fn record_search_strategy(&self, strategy: &'static str) {
+        if let Some(search_plan) = self.search_plan {
+            search_plan.record(strategy);
+        }
+    }
+
+    /// Usage of the request to count scored vectors into, if requested
+    pub fn usage(&self) -> Option<&RequestUsage> {
+        self.usage
+    }
+
     pub fn is_stopped(&self) -> SimpleCow<'_, AtomicBool> {
         self.is_stopped
             .map(SimpleCow::Borrowed)
@@ -211,6 +355,98 @@ impl Default for VectorQueryContext<'_> {
             is_stopped: None,
             idf: None,
             deleted_points: None,
+            filter_plans: None,
+            search_plan: None,
+            usage: None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Condition, FieldCondition};
+
+    #[test]
+    fn test_filter_plans_are_shared() {
+        let filter = Filter::new_must(Condition::Field(FieldCondition::new_match(
+            "color".parse().unwrap(),
+            "red".to_owned().into(),
+        )));
+        let other_filter = Filter::new_must_not(Condition::Field(FieldCondition::new_match(
+            "color".parse().unwrap(),
+            "red".to_owned().into(),
+        )));
+
+        let filter_plans = FilterPlans::default();
+        let query_context = QueryContext::default();
+        let segment_context = query_context
+            .get_segment_query_context()
+            .with_filter_plans(&filter_plans);
+        let vector_context = segment_context.get_vector_context("");
+
+        let mut queries = 0;
+        for _ in 0..3 {
+            let points = vector_context.filtered_points(&filter, || {
+                queries += 1;
+                vec![1, 2, 3]
+            });
+            assert_eq!(points.as_slice(), &[1, 2, 3]);
+        }
+        assert_eq!(queries, 1);
+
+        vector_context.filtered_points(&other_filter, || {
+            queries += 1;
+            vec![]
+        });
+        assert_eq!(queries, 2);
+
+        let mut estimations = 0;
+        for _ in 0..3 {
+            let cardinality = vector_context.estimate_cardinality(&filter, || {
+                estimations += 1;
+                CardinalityEstimation::exact(3)
+            });
+            assert_eq!(cardinality.exp, 3);
+        }
+        assert_eq!(estimations, 1);
+
+        // Without shared plans every search does its own planning
+        let vector_context = segment_context
+            .without_filter_plans()
+            .get_vector_context("");
+        vector_context.filtered_points(&filter, || {
+            queries += 1;
+            vec![]
+        });
+        assert_eq!(queries, 3);
+    }
+
+    #[test]
+    fn test_search_plan() {
+        let search_plan = Arc::new(SearchPlan::default());
+        let query_context = QueryContext::default().with_search_plan(Some(search_plan.clone()));
+        let segment_context = query_context.get_segment_query_context();
+
+        segment_context
+            .get_vector_context("")
+            .record_search_strategy("unfiltered_hnsw");
+        segment_context
+            .get_vector_context("")
+            .record_search_strategy("unfiltered_hnsw");
+        segment_context
+            .get_vector_context("")
+            .record_search_strategy("small_cardinality");
+
+        assert_eq!(
+            search_plan.strategies(),
+            BTreeMap::from([("small_cardinality", 1), ("unfiltered_hnsw", 2)]),
+        );
+
+        // Nothing is recorded without a plan
+        QueryContext::default()
+            .get_segment_query_context()
+            .get_vector_context("")
+            .record_search_strategy("unfiltered_hnsw");
+    }
+}
@@ -17,6 +17,7 @@ use crate::vector_storage::query::{ContextQuery, DiscoveryQuery, RecoQuery, Tran
 #[derive(Clone, Debug, PartialEq)]
 pub enum Vector {
     Dense(DenseVector),
+    DenseDouble(DenseDoubleVector),
     Sparse(SparseVector),
     MultiDense(MultiDenseVector),
 }
@@ -25,7 +26,7 @@ impl Vector {
     pub fn is_sparse(&self) -> bool {
         match self {
             Vector::Sparse(_) => true,
-            Vector::Dense(_) | Vector::MultiDense(_) => false,
+            Vector::Dense(_) | Vector::DenseDouble(_) | Vector::MultiDense(_) => false,
         }
     }
 }
@@ -33,6 +34,7 @@ impl Vector {
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum VectorRef<'a> {
     Dense(&'a [VectorElementType]),
+    DenseDouble(&'a [VectorElementTypeDouble]),
     Sparse(&'a SparseVector),
     MultiDense(TypedMultiDenseVectorRef<'a, VectorElementType>),
 }
@@ -43,6 +45,7 @@ impl<'a> TryFrom<VectorRef<'a>> for &'a [VectorElementType] {
     fn try_from(value: VectorRef<'a>) -> Result<Self, Self::Error> {
         match value {
             VectorRef::Dense(v) => Ok(v),
+            VectorRef::DenseDouble(_) => Err(OperationError::WrongDouble),
             VectorRef::Sparse(_) => Err(OperationError::WrongSparse),
             VectorRef::MultiDense(_) => Err(OperationError::WrongMulti),
         }
@@ -54,7 +57,7 @@ impl<'a> TryFrom<VectorRef<'a>> for &'a SparseVector {
 
     fn try_from(value: VectorRef<'a>) -> Result<Self, Self::Error> {
         match value {
-            VectorRef::Dense(_) => Err(OperationError::WrongSparse),
+            VectorRef::Dense(_) | VectorRef::DenseDouble(_) => Err(OperationError::WrongSparse),
             VectorRef::Sparse(v) => Ok(v),
             VectorRef::MultiDense(_) => Err(OperationError::WrongMulti),
         }
@@ -70,6 +73,7 @@ impl<'a> TryFrom<VectorRef<'a>> for TypedMultiDenseVectorRef<'a, f32> {
                 flattened_vectors: d,
                 dim: d.len(),
             }),
+            VectorRef::DenseDouble(_) => Err(OperationError::WrongDouble),
             VectorRef::Sparse(_v) => Err(OperationError::WrongSparse),
             VectorRef::MultiDense(v) => Ok(v),
         }
@@ -81,18 +85,38 @@ impl From<NamedVectorStruct> for Vector {
         match value {
             NamedVectorStruct::Default(v) => Vector::Dense(v),
             NamedVectorStruct::Dense(v) => Vector::Dense(v.vector),
+            NamedVectorStruct::DenseDouble(v) => Vector::DenseDouble(v.vector),
             NamedVectorStruct::Sparse(v) => Vector::Sparse(v.vector),
             NamedVectorStruct::MultiDense(v) => Vector::MultiDense(v.vector),
         }
     }
 }
 
+/// Double precision vectors are converted into single precision
 impl TryFrom<Vector> for DenseVector {
     type Error = OperationError;
 
     fn try_from(value: Vector) -> Result<Self, Self::Error> {
         match value {
             Vector::Dense(v) => Ok(v),
+            Vector::DenseDouble(v) => Ok(v.iter().map(|&x| x as VectorElementType).collect()),
+            Vector::Sparse(_) => Err(OperationError::WrongSparse),
+            Vector::MultiDense(_) => Err(OperationError::WrongMulti),
+        }
+    }
+}
+
+/// Single precision vectors are converted into double precision
+impl TryFrom<Vector> for DenseDoubleVector {
+    type Error = OperationError;
+
+    fn try_from(value: Vector) -> Result<Self, Self::Error> {
+        match value {
+            Vector::Dense(v) => Ok(v
+                .iter()
+                .map(|&x| VectorElementTypeDouble::from(x))
+                .collect()),
+            Vector::DenseDouble(v) => Ok(v),
             Vector::Sparse(_) => Err(OperationError::WrongSparse),
             Vector::MultiDense(_) => Err(OperationError::WrongMulti),
         }
@@ -104,7 +128,7 @@ impl TryFrom<Vector> for SparseVector {
 
     fn try_from(value: Vector) -> Result<Self, Self::Error> {
         match value {
-            Vector::Dense(_) => Err(OperationError::WrongSparse),
+            Vector::Dense(_) | Vector::DenseDouble(_) => Err(OperationError::WrongSparse),
             Vector::Sparse(v) => Ok(v),
             Vector::MultiDense(_) => Err(OperationError::WrongMulti),
         }
@@ -121,6 +145,7 @@ impl TryFrom<Vector> for MultiDenseVector {
                 let len = v.len();
                 Ok(MultiDenseVector::new(v, len))
             }
+            Vector::DenseDouble(_) => Err(OperationError::WrongDouble),
             Vector::Sparse(_) => Err(OperationError::WrongSparse),
             Vector::MultiDense(v) => Ok(v),
         }
@@ -139,6 +164,12 @@ impl<'a> From<&'a DenseVector> for VectorRef<'a> {
     }
 }
 
+impl<'a> From<&'a [VectorElementTypeDouble]> for VectorRef<'a> {
+    fn from(val: &'a [VectorElementTypeDouble]) -> Self {
+        VectorRef::DenseDouble(val)
+    }
+}
+
 impl<'a> From<&'a MultiDenseVector> for VectorRef<'a> {
     fn from(val: &'a MultiDenseVector) -> Self {
         VectorRef::MultiDense(TypedMultiDenseVectorRef::from(val))
@@ -163,6 +194,12 @@ impl From<DenseVector> for Vector {
     }
 }
 
+impl From<DenseDoubleVector> for Vector {
+    fn from(val: DenseDoubleVector) -> Self {
+        Vector::DenseDouble(val)
+    }
+}
+
 impl From<SparseVector> for Vector {
     fn from(val: SparseVector) -> Self {
         Vector::Sparse(val)
@@ -179,6 +216,7 @@ impl<'a> From<&'a Vector> for VectorRef<'a> {
     fn from(val: &'a Vector) -> Self {
         match val {
             Vector::Dense(v) => VectorRef::Dense(v.as_slice()),
+            Vector::DenseDouble(v) => VectorRef::DenseDouble(v.as_slice()),
             Vector::Sparse(v) => VectorRef::Sparse(v),
             Vector::MultiDense(v) => VectorRef::MultiDense(TypedMultiDenseVectorRef::from(v)),
         }
@@ -190,6 +228,8 @@ pub type VectorElementType = f32;
 
 pub type VectorElementTypeHalf = f16;
 
+pub type VectorElementTypeDouble = f64;
+
 pub type VectorElementTypeByte = u8;
 
 pub const DEFAULT_VECTOR_NAME: &str = "";
@@ -199,6 +239,9 @@ pub type TypedDenseVector<T> = Vec<T>;
 /// Type for dense vector
 pub type DenseVector = TypedDenseVector<VectorElementType>;
 
+/// Type for dense vector in double precision
+pub type DenseDoubleVector = TypedDenseVector<VectorElementTypeDouble>;
+
 /// Type for multi dense vector
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct TypedMultiDenseVector<T> {
@@ -348,6 +391,7 @@ impl<'a> VectorRef<'a> {
     pub fn to_owned(self) -> Vector {
         match self {
             VectorRef::Dense(v) => Vector::Dense(v.to_vec()),
+            VectorRef::DenseDouble(v) => Vector::DenseDouble(v.to_vec()),
             VectorRef::Sparse(v) => Vector::Sparse(v.clone()),
             VectorRef::MultiDense(v) => Vector::MultiDense(v.to_owned()),
         }
@@ -360,6 +404,7 @@ impl<'a> TryInto<&'a [VectorElementType]> for &'a Vector {
     fn try_into(self) -> Result<&'a [VectorElementType], Self::Error> {
         match self {
             Vector::Dense(v) => Ok(v),
+            Vector::DenseDouble(_) => Err(OperationError::WrongDouble),
             Vector::Sparse(_) => Err(OperationError::WrongSparse),
             Vector::MultiDense(_) => Err(OperationError::WrongMulti),
         }
@@ -371,7 +416,7 @@ impl<'a> TryInto<&'a SparseVector> for &'a Vector {
 
     fn try_into(self) -> Result<&'a SparseVector, Self::Error> {
         match self {
-            Vector::Dense(_) => Err(OperationError::WrongSparse),
+            Vector::Dense(_) | Vector::DenseDouble(_) => Err(OperationError::WrongSparse),
             Vector::Sparse(v) => Ok(v),
             Vector::MultiDense(_) => Err(OperationError::WrongMulti),
         }
@@ -384,6 +429,7 @@ impl<'a> TryInto<&'a MultiDenseVector> for &'a Vector {
     fn try_into(self) -> Result<&'a MultiDenseVector, Self::Error> {
         match self {
             Vector::Dense(_) => Err(OperationError::WrongMulti), // &Dense vector cannot be converted to &MultiDense
+            Vector::DenseDouble(_) => Err(OperationError::WrongDouble),
             Vector::Sparse(_) => Err(OperationError::WrongSparse),
             Vector::MultiDense(v) => Ok(v),
         }
@@ -457,11 +503,12 @@ impl From<&[VectorElementType]> for VectorStruct {
 
 impl<'a> From<NamedVectors<'a>> for VectorStruct {
     fn from(v: NamedVectors) -> Self {
-        if v.len() == 1 && v.contains_key(DEFAULT_VECTOR_NAME) {
-            let vector: &[_] = v.get(DEFAULT_VECTOR_NAME).unwrap().try_into().unwrap();
-            VectorStruct::Single(vector.to_owned())
-        } else {
-            VectorStruct::Multi(v.into_owned_map())
+        // Double precision default vector is kept in the map, `Single` is a single precision one
+        match v.get(DEFAULT_VECTOR_NAME) {
+            Some(VectorRef::Dense(vector)) if v.len() == 1 => {
+                VectorStruct::Single(vector.to_owned())
+            }
+            _ => VectorStruct::Multi(v.into_owned_map()),
         }
     }
 }
@@ -492,6 +539,15 @@ pub struct NamedVector {
     pub vector: DenseVector,
 }
 
+/// Dense vector data in double precision with name
+#[derive(Debug, Clone, PartialEq)]
+pub struct NamedDenseDoubleVector {
+    /// Name of vector data
+    pub name: String,
+    /// Vector data
+    pub vector: DenseDoubleVector,
+}
+
 /// MultiDense vector data with name
 #[derive(Debug, Clone, PartialEq)]
 pub struct NamedMultiDenseVector {
@@ -516,6 +572,7 @@ pub struct NamedSparseVector {
 pub enum NamedVectorStruct {
     Default(DenseVector),
     Dense(NamedVector),
+    DenseDouble(NamedDenseDoubleVector),
     Sparse(NamedSparseVector),
     MultiDense(NamedMultiDenseVector),
 }
@@ -547,6 +604,7 @@ impl Named for NamedVectorStruct {
         match self {
             NamedVectorStruct::Default(_) => DEFAULT_VECTOR_NAME,
             NamedVectorStruct::Dense(v) => &v.name,
+            NamedVectorStruct::DenseDouble(v) => &v.name,
             NamedVectorStruct::Sparse(v) => &v.name,
             NamedVectorStruct::MultiDense(v) => &v.name,
         }
@@ -558,6 +616,9 @@ impl NamedVectorStruct {
         let name = name.into();
         match vector {
             Vector::Dense(vector) => NamedVectorStruct::Dense(NamedVector { name, vector }),
+            Vector::DenseDouble(vector) => {
+                NamedVectorStruct::DenseDouble(NamedDenseDoubleVector { name, vector })
+            }
             Vector::Sparse(vector) => NamedVectorStruct::Sparse(NamedSparseVector { name, vector }),
             Vector::MultiDense(vector) => {
                 NamedVectorStruct::MultiDense(NamedMultiDenseVector { name, vector })
@@ -569,6 +630,7 @@ impl NamedVectorStruct {
         match self {
             NamedVectorStruct::Default(v) => v.as_slice().into(),
             NamedVectorStruct::Dense(v) => v.vector.as_slice().into(),
+            NamedVectorStruct::DenseDouble(v) => v.vector.as_slice().into(),
             NamedVectorStruct::Sparse(v) => (&v.vector).into(),
             NamedVectorStruct::MultiDense(v) => (&v.vector).into(),
         }
@@ -578,6 +640,7 @@ impl NamedVectorStruct {
         match self {
             NamedVectorStruct::Default(v) => v.into(),
             NamedVectorStruct::Dense(v) => v.vector.into(),
+            NamedVectorStruct::DenseDouble(v) => v.vector.into(),
             NamedVectorStruct::Sparse(v) => v.vector.into(),
             NamedVectorStruct::MultiDense(v) => v.vector.into(),
         }
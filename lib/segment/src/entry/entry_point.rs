@@ -3,12 +3,15 @@ use std::path::{Path, PathBuf};
 
 use common::types::TelemetryDetail;
 
+use crate::blob_storage::BlobChunk;
 use crate::common::operation_error::{OperationResult, SegmentFailedState};
 use crate::data_types::named_vectors::NamedVectors;
 use crate::data_types::order_by::{OrderBy, OrderValue};
+use crate::data_types::payload_projection::PayloadProjection;
 use crate::data_types::query_context::{QueryContext, SegmentQueryContext};
 use crate::data_types::vectors::{QueryVector, Vector};
 use crate::index::field_index::CardinalityEstimation;
+use crate::index::vector_index_info::VectorIndexInfo;
 use crate::json_path::JsonPath;
 use crate::telemetry::SegmentTelemetry;
 use crate::types::{
@@ -96,6 +99,21 @@ pub trait SegmentEntry {
         point_id: PointIdType,
     ) -> OperationResult<bool>;
 
+    /// Attach binary blob to the point, replacing the existing one
+    fn set_blob(
+        &mut self,
+        op_num: SeqNumberType,
+        point_id: PointIdType,
+        blob: &[u8],
+    ) -> OperationResult<bool>;
+
+    /// Remove binary blob of the point, returns `false` if the point had no blob
+    fn delete_blob(
+        &mut self,
+        op_num: SeqNumberType,
+        point_id: PointIdType,
+    ) -> OperationResult<bool>;
+
     fn vector(&self, vector_name: &str, point_id: PointIdType) -> OperationResult<Option<Vector>>;
 
     fn all_vectors(&self, point_id: PointIdType) -> OperationResult<NamedVectors>;
@@ -104,6 +122,34 @@ pub trait SegmentEntry {
     /// If not found, return empty payload
     fn payload(&self, point_id: PointIdType) -> OperationResult<Payload>;
 
+    /// Retrieve payloads for multiple points, in the same order
+    /// If not found, return empty payload
+    fn payloads(&self, point_ids: &[PointIdType]) -> OperationResult<Vec<Payload>> {
+        point_ids
+            .iter()
+            .map(|&point_id| self.payload(point_id))
+            .collect()
+    }
+
+    /// Retrieve payload for the point, keeping only the fields selected by projection
+    /// If not found, return empty payload
+    fn payload_projected(
+        &self,
+        point_id: PointIdType,
+        projection: PayloadProjection,
+    ) -> OperationResult<Payload> {
+        Ok(projection.project(self.payload(point_id)?))
+    }
+
+    /// Retrieve up to `limit` bytes of the point blob, starting from `offset`
+    /// If the point has no blob, return None
+    fn blob(
+        &self,
+        point_id: PointIdType,
+        offset: usize,
+        limit: usize,
+    ) -> OperationResult<Option<BlobChunk>>;
+
     /// Iterator over all points in segment in ascending order.
     fn iter_points(&self) -> Box<dyn Iterator<Item = PointIdType> + '_>;
 
@@ -209,5 +255,9 @@ pub trait SegmentEntry {
     // Get collected telemetry data of segment
     fn get_telemetry_data(&self, detail: TelemetryDetail) -> SegmentTelemetry;
 
+    /// Statistics of vector indexes and quantized vectors, sorted by vector name.
+    /// Walks HNSW graphs, so it's expensive.
+    fn vector_index_info(&self) -> Vec<VectorIndexInfo>;
+
     fn fill_query_context(&self, query_context: &mut QueryContext);
 }
@@ -140,6 +140,7 @@ where
             self,
             self.deleted_vector_bitslice(),
             &DEFAULT_STOPPED,
+            self.distance(),
         )
     }
 }
@@ -0,0 +1,351 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use bitvec::prelude::{BitSlice, BitVec};
+use common::types::PointOffsetType;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::common::append_only_store::AppendOnlyStore;
+use crate::common::operation_error::OperationResult;
+use crate::common::Flusher;
+use crate::id_tracker::simple_id_tracker::{
+    external_to_stored_id, stored_to_external_id, StoredPointId,
+};
+use crate::id_tracker::IdTracker;
+use crate::types::{PointIdType, SeqNumberType};
+
+const ID_TRACKER_PATH: &str = "id_tracker";
+const MAPPINGS_LOG_FILE: &str = "mappings.log";
+
+/// State of the internal id, persisted in the log
+#[derive(Debug, Deserialize, Serialize)]
+struct StoredMapping {
+    external_id: StoredPointId,
+    version: Option<SeqNumberType>,
+}
+
+/// Id tracker, which persists mappings in an append-only log instead of RocksDB.
+///
+/// Mapping and version of each internal id are stored in a single record,
+/// so both of them are always persisted together.
+pub struct AppendOnlyIdTracker {
+    deleted: BitVec,
+    internal_to_external: Vec<PointIdType>,
+    internal_to_version: Vec<SeqNumberType>,
+    external_to_internal_num: BTreeMap<u64, PointOffsetType>,
+    external_to_internal_uuid: BTreeMap<Uuid, PointOffsetType>,
+    store: AppendOnlyStore,
+}
+
+impl AppendOnlyIdTracker {
+    pub fn open(segment_path: &Path) -> OperationResult<Self> {
+        let path = segment_path.join(ID_TRACKER_PATH).join(MAPPINGS_LOG_FILE);
+        let store = AppendOnlyStore::open(&path)?;
+
+        let mut id_tracker = AppendOnlyIdTracker {
+            deleted: BitVec::new(),
+            internal_to_external: Vec::new(),
+            internal_to_version: Vec::new(),
+            external_to_internal_num: BTreeMap::new(),
+            external_to_internal_uuid: BTreeMap::new(),
+            store: store.clone(),
+        };
+
+        let mut duplicates = Vec::new();
+        store.iter(|internal_id, raw| {
+            let mapping: StoredMapping = bincode::deserialize(raw).unwrap();
+            let external_id = stored_to_external_id(mapping.external_id);
+
+            if let Some(replaced_id) = id_tracker.internal_id(external_id) {
+                // Fixing corrupted mapping - the latest internal id wins
+                log::warn!(
+                    "removing duplicated external id {} in internal id {}",
+                    external_id,
+                    replaced_id
+                );
+                id_tracker.deleted.set(replaced_id as usize, true);
+                duplicates.push(replaced_id);
+            }
+
+            id_tracker.insert_link(external_id, internal_id);
+            if let Some(version) = mapping.version {
+                id_tracker.insert_version(internal_id, version);
+            }
+            Ok(true)
+        })?;
+
+        for internal_id in duplicates {
+            id_tracker.internal_to_external[internal_id as usize] = PointIdType::NumId(u64::MAX);
+            id_tracker.store.remove(internal_id);
+        }
+
+        Ok(id_tracker)
+    }
+
+    fn insert_link(&mut self, external_id: PointIdType, internal_id: PointOffsetType) {
+        match external_id {
+            PointIdType::NumId(idx) => {
+                self.external_to_internal_num.insert(idx, internal_id);
+            }
+            PointIdType::Uuid(uuid) => {
+                self.external_to_internal_uuid.insert(uuid, internal_id);
+            }
+        }
+
+        let internal_id = internal_id as usize;
+        if internal_id >= self.internal_to_external.len() {
+            self.internal_to_external
+                .resize(internal_id + 1, PointIdType::NumId(u64::MAX));
+        }
+        if internal_id >= self.deleted.len() {
+            self.deleted.resize(internal_id + 1, true);
+        }
+        self.internal_to_external[internal_id] = external_id;
+        self.deleted.set(internal_id, false);
+    }
+
+    fn insert_version(&mut self, internal_id: PointOffsetType, version: SeqNumberType) {
+        if internal_id as usize >= self.internal_to_version.len() {
+            self.internal_to_version.resize(internal_id as usize + 1, 0);
+        }
+        self.internal_to_version[internal_id as usize] = version;
+    }
+
+    fn persist(
+        &self,
+        internal_id: PointOffsetType,
+        external_id: &PointIdType,
+        version: Option<SeqNumberType>,
+    ) {
+        let mapping = StoredMapping {
+            external_id: external_to_stored_id(external_id),
+            version,
+        };
+        self.store
+            .put(internal_id, bincode::serialize(&mapping).unwrap());
+    }
+}
+
+impl IdTracker for AppendOnlyIdTracker {
+    fn internal_version(&self, internal_id: PointOffsetType) -> Option<SeqNumberType> {
+        self.internal_to_version.get(internal_id as usize).copied()
+    }
+
+    fn set_internal_version(
+        &mut self,
+        internal_id: PointOffsetType,
+        version: SeqNumberType,
+    ) -> OperationResult<()> {
+        if let Some(external_id) = self.external_id(internal_id) {
+            self.insert_version(internal_id, version);
+            self.persist(internal_id, &external_id, Some(version));
+        }
+        Ok(())
+    }
+
+    fn internal_id(&self, external_id: PointIdType) -> Option<PointOffsetType> {
+        match external_id {
+            PointIdType::NumId(idx) => self.external_to_internal_num.get(&idx).copied(),
+            PointIdType::Uuid(uuid) => self.external_to_internal_uuid.get(&uuid).copied(),
+        }
+    }
+
+    fn external_id(&self, internal_id: PointOffsetType) -> Option<PointIdType> {
+        if let Some(deleted) = self.deleted.get(internal_id as usize) {
+            if !deleted {
+                return self.internal_to_external.get(internal_id as usize).copied();
+            }
+        }
+        None
+    }
+
+    fn set_link(
+        &mut self,
+        external_id: PointIdType,
+        internal_id: PointOffsetType,
+    ) -> OperationResult<()> {
+        self.insert_link(external_id, internal_id);
+        self.persist(
+            internal_id,
+            &external_id,
+            self.internal_version(internal_id),
+        );
+        Ok(())
+    }
+
+    fn drop(&mut self, external_id: PointIdType) -> OperationResult<()> {
+        let internal_id = match &external_id {
+            PointIdType::NumId(idx) => self.external_to_internal_num.remove(idx),
+            PointIdType::Uuid(uuid) => self.external_to_internal_uuid.remove(uuid),
+        };
+        if let Some(internal_id) = internal_id {
+            self.deleted.set(internal_id as usize, true);
+            self.internal_to_external[internal_id as usize] = PointIdType::NumId(u64::MAX);
+            self.store.remove(internal_id);
+        }
+        Ok(())
+    }
+
+    fn iter_external(&self) -> Box<dyn Iterator<Item = PointIdType> + '_> {
+        let iter_num = self
+            .external_to_internal_num
+            .keys()
+            .copied()
+            .map(PointIdType::NumId);
+        let iter_uuid = self
+            .external_to_internal_uuid
+            .keys()
+            .copied()
+            .map(PointIdType::Uuid);
+        // order is important here, we want to iterate over the u64 ids first
+        Box::new(iter_num.chain(iter_uuid))
+    }
+
+    fn iter_internal(&self) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        Box::new(
+            (0..self.internal_to_external.len() as PointOffsetType)
+                .filter(move |i| !self.deleted[*i as usize]),
+        )
+    }
+
+    fn iter_from(
+        &self,
+        external_id: Option<PointIdType>,
+    ) -> Box<dyn Iterator<Item = (PointIdType, PointOffsetType)> + '_> {
+        let iter_num = |offset: u64| {
+            self.external_to_internal_num
+                .range(offset..)
+                .map(|(k, v)| (PointIdType::NumId(*k), *v))
+        };
+        let iter_uuid = |offset: Uuid| {
+            self.external_to_internal_uuid
+                .range(offset..)
+                .map(|(k, v)| (PointIdType::Uuid(*k), *v))
+        };
+
+        match external_id {
+            // order is important here, we want to iterate over the u64 ids first
+            None => Box::new(iter_num(0).chain(iter_uuid(Uuid::nil()))),
+            Some(PointIdType::NumId(idx)) => Box::new(iter_num(idx).chain(iter_uuid(Uuid::nil()))),
+            // if offset is a uuid, we can only iterate over uuids
+            Some(PointIdType::Uuid(uuid)) => Box::new(iter_uuid(uuid)),
+        }
+    }
+
+    fn total_point_count(&self) -> usize {
+        self.internal_to_external.len()
+    }
+
+    fn available_point_count(&self) -> usize {
+        self.external_to_internal_num.len() + self.external_to_internal_uuid.len()
+    }
+
+    fn deleted_point_count(&self) -> usize {
+        self.total_point_count() - self.available_point_count()
+    }
+
+    fn iter_ids(&self) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        self.iter_internal()
+    }
+
+    fn mapping_flusher(&self) -> Flusher {
+        self.store.flusher()
+    }
+
+    /// Versions are persisted together with mappings,
+    /// this flusher only catches up with changes made after the mapping flush.
+    fn versions_flusher(&self) -> Flusher {
+        self.store.flusher()
+    }
+
+    fn is_deleted_point(&self, key: PointOffsetType) -> bool {
+        let key = key as usize;
+        if key >= self.deleted.len() {
+            return true;
+        }
+        self.deleted[key]
+    }
+
+    fn deleted_point_bitslice(&self) -> &BitSlice {
+        &self.deleted
+    }
+
+    fn cleanup_versions(&mut self) -> OperationResult<()> {
+        let mut to_remove = Vec::new();
+        for internal_id in self.iter_internal() {
+            if self.internal_version(internal_id).is_none() {
+                if let Some(external_id) = self.external_id(internal_id) {
+                    to_remove.push(external_id);
+                } else {
+                    debug_assert!(false, "internal id {} has no external id", internal_id);
+                }
+            }
+        }
+        for external_id in to_remove {
+            self.drop(external_id)?;
+            #[cfg(debug_assertions)] // Only for dev builds
+            {
+                log::debug!("dropped version for point {} without version", external_id);
+            }
+        }
+        Ok(())
+    }
+
+    fn files(&self) -> Vec<PathBuf> {
+        self.store.files()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use tempfile::Builder;
+
+    use super::*;
+
+    #[test]
+    fn test_append_only_id_tracker_persistence() {
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+
+        let mut id_tracker = AppendOnlyIdTracker::open(dir.path()).unwrap();
+        let values: Vec<PointIdType> = vec![
+            200.into(),
+            100.into(),
+            PointIdType::Uuid(Uuid::from_u128(123_u128)),
+            150.into(),
+            PointIdType::Uuid(Uuid::from_u128(12_u128)),
+        ];
+        for (internal_id, external_id) in values.iter().enumerate() {
+            let internal_id = internal_id as PointOffsetType;
+            id_tracker.set_link(*external_id, internal_id).unwrap();
+            id_tracker
+                .set_internal_version(internal_id, internal_id as SeqNumberType + 10)
+                .unwrap();
+        }
+        id_tracker.drop(150.into()).unwrap();
+        id_tracker.mapping_flusher()().unwrap();
+        id_tracker.versions_flusher()().unwrap();
+
+        let id_tracker = AppendOnlyIdTracker::open(dir.path()).unwrap();
+        assert_eq!(id_tracker.available_point_count(), 4);
+        assert_eq!(id_tracker.internal_id(200.into()), Some(0));
+        assert_eq!(id_tracker.internal_version(2), Some(12));
+        assert!(id_tracker.is_deleted_point(3));
+        assert_eq!(id_tracker.internal_id(150.into()), None);
+
+        let from_tracker = id_tracker.iter_from(None).map(|(k, _)| k).collect_vec();
+        assert_eq!(
+            from_tracker,
+            vec![
+                100.into(),
+                200.into(),
+                PointIdType::Uuid(Uuid::from_u128(12_u128)),
+                PointIdType::Uuid(Uuid::from_u128(123_u128)),
+            ],
+        );
+
+        let from_offset = id_tracker.iter_from(Some(150.into())).count();
+        assert_eq!(from_offset, 3);
+    }
+}
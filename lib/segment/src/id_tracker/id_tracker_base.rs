@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use bitvec::prelude::BitSlice;
 use common::types::PointOffsetType;
 use rand::rngs::StdRng;
@@ -5,7 +7,7 @@ use rand::{Rng, SeedableRng};
 
 use crate::common::operation_error::OperationResult;
 use crate::common::Flusher;
-use crate::types::{PointIdType, SeqNumberType};
+use crate::types::{ExtendedPointId, PointIdRange, PointIdType, SeqNumberType};
 
 /// Sampling randomness seed
 ///
@@ -59,6 +61,18 @@ pub trait IdTracker {
         external_id: Option<PointIdType>,
     ) -> Box<dyn Iterator<Item = (PointIdType, PointOffsetType)> + '_>;
 
+    /// Iterate over points with numeric IDs in a given range, in order of IDs
+    fn iter_range(
+        &self,
+        range: PointIdRange,
+    ) -> Box<dyn Iterator<Item = (PointIdType, PointOffsetType)> + '_> {
+        let start = ExtendedPointId::NumId(range.gte.unwrap_or(0));
+        Box::new(
+            self.iter_from(Some(start))
+                .take_while(move |(external_id, _)| range.contains(external_id)),
+        )
+    }
+
     /// Iterate over internal IDs (offsets)
     ///
     /// - excludes removed points
@@ -141,6 +155,11 @@ pub trait IdTracker {
     /// It might happen that point doesn't have version due to un-flushed WAL.
     /// This method makes those points usable again.
     fn cleanup_versions(&mut self) -> OperationResult<()>;
+
+    /// Files of the tracker, which are not kept in the segment's RocksDB
+    fn files(&self) -> Vec<PathBuf> {
+        vec![]
+    }
 }
 
 pub type IdTrackerSS = dyn IdTracker + Sync + Send;
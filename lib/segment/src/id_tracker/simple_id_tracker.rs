@@ -20,7 +20,7 @@ use crate::types::{ExtendedPointId, PointIdType, SeqNumberType};
 /// Point Id type used for storing ids internally
 /// Should be serializable by `bincode`, therefore is not untagged.
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
-enum StoredPointId {
+pub(super) enum StoredPointId {
     NumId(u64),
     Uuid(Uuid),
     String(String),
@@ -48,12 +48,12 @@ impl From<StoredPointId> for ExtendedPointId {
 }
 
 #[inline]
-fn stored_to_external_id(point_id: StoredPointId) -> PointIdType {
+pub(super) fn stored_to_external_id(point_id: StoredPointId) -> PointIdType {
     point_id.into()
 }
 
 #[inline]
-fn external_to_stored_id(point_id: &PointIdType) -> StoredPointId {
+pub(super) fn external_to_stored_id(point_id: &PointIdType) -> StoredPointId {
     point_id.into()
 }
 
@@ -77,7 +77,8 @@ impl SimpleIdTracker {
         let mapping_db_wrapper = DatabaseColumnScheduledDeleteWrapper::new(
             DatabaseColumnWrapper::new(store.clone(), DB_MAPPING_CF),
         );
-        for (key, val) in mapping_db_wrapper.lock_db().iter()? {
+        for item in mapping_db_wrapper.lock_db().iter()? {
+            let (key, val) = item?;
             let external_id = Self::restore_key(&key);
             let internal_id: PointOffsetType =
                 bincode::deserialize::<PointOffsetType>(&val).unwrap();
@@ -124,7 +125,8 @@ impl SimpleIdTracker {
         let versions_db_wrapper = DatabaseColumnScheduledUpdateWrapper::new(
             DatabaseColumnWrapper::new(store, DB_VERSIONS_CF),
         );
-        for (key, val) in versions_db_wrapper.lock_db().iter()? {
+        for item in versions_db_wrapper.lock_db().iter()? {
+            let (key, val) = item?;
             let external_id = Self::restore_key(&key);
             let version: SeqNumberType = bincode::deserialize(&val).unwrap();
             let internal_id = match external_id {
@@ -217,7 +217,8 @@ impl PayloadFieldIndex for BinaryIndex {
             return Ok(false);
         }
 
-        for (key, value) in self.db_wrapper.lock_db().iter()? {
+        for item in self.db_wrapper.lock_db().iter()? {
+            let (key, value) = item?;
             let idx = PointOffsetType::from_be_bytes(key.as_ref().try_into().unwrap());
 
             debug_assert_eq!(value.len(), 1);
@@ -13,11 +13,13 @@ use crate::index::field_index::binary_index::BinaryIndex;
 use crate::index::field_index::full_text_index::text_index::FullTextIndex;
 use crate::index::field_index::geo_index::GeoMapIndex;
 use crate::index::field_index::numeric_index::NumericIndex;
-use crate::index::field_index::{CardinalityEstimation, PayloadBlockCondition};
+use crate::index::field_index::{
+    CardinalityEstimation, FieldIndexStatistics, PayloadBlockCondition,
+};
 use crate::telemetry::PayloadIndexTelemetry;
 use crate::types::{
-    DateTimePayloadType, FieldCondition, FloatPayloadType, IntPayloadType, Match, MatchText,
-    PayloadKeyType, RangeInterface,
+    DateTimePayloadType, DateTimeTimestamp, FieldCondition, FloatPayloadType, IntPayloadType,
+    Match, MatchText, PayloadKeyType, RangeInterface,
 };
 
 pub trait PayloadFieldIndex {
@@ -98,7 +100,7 @@ pub trait ValueIndexer<T> {
 /// Enables polymorphism on field indexes
 pub enum FieldIndex {
     IntIndex(NumericIndex<IntPayloadType>),
-    DatetimeIndex(NumericIndex<IntPayloadType>),
+    DatetimeIndex(NumericIndex<DateTimeTimestamp>),
     IntMapIndex(MapIndex<IntPayloadType>),
     KeywordIndex(MapIndex<SmolStr>),
     FloatIndex(NumericIndex<FloatPayloadType>),
@@ -312,6 +314,25 @@ impl FieldIndex {
         }
     }
 
+    pub fn statistics(&self) -> FieldIndexStatistics {
+        let telemetry = self.get_telemetry_data();
+        let distinct_values_count = match self {
+            FieldIndex::IntMapIndex(index) => Some(index.get_unique_values_count()),
+            FieldIndex::KeywordIndex(index) => Some(index.get_unique_values_count()),
+            FieldIndex::IntIndex(_)
+            | FieldIndex::DatetimeIndex(_)
+            | FieldIndex::FloatIndex(_)
+            | FieldIndex::GeoIndex(_)
+            | FieldIndex::FullTextIndex(_)
+            | FieldIndex::BinaryIndex(_) => None,
+        };
+        FieldIndexStatistics {
+            points_count: telemetry.points_count,
+            values_count: telemetry.points_values_count,
+            distinct_values_count,
+        }
+    }
+
     pub fn values_count(&self, point_id: PointOffsetType) -> usize {
         match self {
             FieldIndex::IntIndex(index) => index.values_count(point_id),
@@ -341,7 +362,7 @@ impl FieldIndex {
     pub fn as_numeric(&self) -> Option<NumericFieldIndex> {
         match self {
             FieldIndex::IntIndex(index) => Some(NumericFieldIndex::IntIndex(index)),
-            FieldIndex::DatetimeIndex(index) => Some(NumericFieldIndex::IntIndex(index)),
+            FieldIndex::DatetimeIndex(index) => Some(NumericFieldIndex::DatetimeIndex(index)),
             FieldIndex::FloatIndex(index) => Some(NumericFieldIndex::FloatIndex(index)),
             FieldIndex::IntMapIndex(_)
             | FieldIndex::KeywordIndex(_)
@@ -355,6 +376,7 @@ impl FieldIndex {
 pub enum NumericFieldIndex<'a> {
     IntIndex(&'a NumericIndex<IntPayloadType>),
     FloatIndex(&'a NumericIndex<FloatPayloadType>),
+    DatetimeIndex(&'a NumericIndex<DateTimeTimestamp>),
 }
 
 impl<'a> StreamRange<OrderValue> for NumericFieldIndex<'a> {
@@ -373,6 +395,11 @@ impl<'a> StreamRange<OrderValue> for NumericFieldIndex<'a> {
                     .stream_range(range)
                     .map(|(v, p)| (OrderValue::from(v), p)),
             ),
+            NumericFieldIndex::DatetimeIndex(index) => Box::new(
+                index
+                    .stream_range(range)
+                    .map(|(v, p)| (OrderValue::from(v), p)),
+            ),
         }
     }
 }
@@ -399,6 +426,14 @@ impl<'a> NumericFieldIndex<'a> {
                     .copied()
                     .map(OrderValue::Float),
             ),
+            NumericFieldIndex::DatetimeIndex(index) => Box::new(
+                index
+                    .get_values(idx)
+                    .into_iter()
+                    .flatten()
+                    .copied()
+                    .map(OrderValue::from),
+            ),
         }
     }
 }
@@ -182,7 +182,8 @@ impl PayloadFieldIndex for FullTextIndex {
         };
 
         let db = self.db_wrapper.lock_db();
-        let i = db.iter()?.map(|(key, value)| {
+        let i = db.iter()?.map(|item| {
+            let (key, value) = item?;
             let idx = Self::restore_key(&key);
             let tokens = Self::deserialize_document(&value)?;
             Ok((idx, tokens))
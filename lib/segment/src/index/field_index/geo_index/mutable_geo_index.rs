@@ -85,7 +85,8 @@ impl MutableGeoMapIndex {
 
         let mut points_to_hashes: BTreeMap<PointOffsetType, Vec<GeoHash>> = Default::default();
 
-        for (key, value) in self.db_wrapper.lock_db().iter()? {
+        for item in self.db_wrapper.lock_db().iter()? {
+            let (key, value) = item?;
             let key_str = std::str::from_utf8(&key).map_err(|_| {
                 OperationError::service_error("Index load error: UTF8 error while DB parsing")
             })?;
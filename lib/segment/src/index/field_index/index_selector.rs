@@ -59,9 +59,11 @@ pub fn index_selector(
             ))],
             PayloadSchemaType::Bool => vec![FieldIndex::BinaryIndex(BinaryIndex::new(db, field))],
             PayloadSchemaType::Datetime => {
-                vec![FieldIndex::DatetimeIndex(
-                    NumericIndex::<IntPayloadType>::new(db, field, is_appendable),
-                )]
+                vec![FieldIndex::DatetimeIndex(NumericIndex::new_datetime(
+                    db,
+                    field,
+                    is_appendable,
+                ))]
             }
         },
         PayloadFieldSchema::FieldParams(payload_params) => match payload_params {
@@ -96,7 +96,8 @@ impl<N: Hash + Eq + Clone + Display + FromStr + Default> MutableMapIndex<N> {
             return Ok(false);
         }
         self.indexed_points = 0;
-        for (record, _) in self.db_wrapper.lock_db().iter()? {
+        for item in self.db_wrapper.lock_db().iter()? {
+            let (record, _) = item?;
             let record = std::str::from_utf8(&record).map_err(|_| {
                 OperationError::service_error("Index load error: UTF8 error while DB parsing")
             })?;
@@ -49,6 +49,17 @@ pub struct CardinalityEstimation {
     pub max: usize,
 }
 
+/// Statistics of the values of an indexed field
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FieldIndexStatistics {
+    /// Number of points with at least one indexed value
+    pub points_count: usize,
+    /// Number of indexed values of all points
+    pub values_count: usize,
+    /// Number of distinct values, if the index keeps track of them
+    pub distinct_values_count: Option<usize>,
+}
+
 impl CardinalityEstimation {
     pub const fn exact(count: usize) -> Self {
         CardinalityEstimation {
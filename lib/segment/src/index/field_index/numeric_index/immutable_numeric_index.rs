@@ -7,7 +7,7 @@ use parking_lot::RwLock;
 use rocksdb::DB;
 
 use super::mutable_numeric_index::MutableNumericIndex;
-use super::{Encodable, NumericIndex, HISTOGRAM_MAX_BUCKET_SIZE, HISTOGRAM_PRECISION};
+use super::{Encodable, HISTOGRAM_MAX_BUCKET_SIZE, HISTOGRAM_PRECISION};
 use crate::common::operation_error::OperationResult;
 use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
 use crate::index::field_index::histogram::{Histogram, Numericable, Point};
@@ -200,9 +200,8 @@ impl<'a, T: Encodable + Numericable> DoubleEndedIterator for NumericKeySortedVec
 }
 
 impl<T: Encodable + Numericable + Default> ImmutableNumericIndex<T> {
-    pub(super) fn new(db: Arc<RwLock<DB>>, field: &str) -> Self {
-        let store_cf_name = NumericIndex::<T>::storage_cf_name(field);
-        let db_wrapper = DatabaseColumnWrapper::new(db, &store_cf_name);
+    pub(super) fn new(db: Arc<RwLock<DB>>, store_cf_name: &str) -> Self {
+        let db_wrapper = DatabaseColumnWrapper::new(db, store_cf_name);
         Self {
             map: NumericKeySortedVec {
                 data: Default::default(),
@@ -10,7 +10,6 @@ use std::ops::Bound::{Excluded, Included, Unbounded};
 use std::str::FromStr;
 use std::sync::Arc;
 
-use chrono::DateTime;
 use common::types::PointOffsetType;
 use mutable_numeric_index::MutableNumericIndex;
 use parking_lot::RwLock;
@@ -29,13 +28,13 @@ use crate::index::field_index::{
     CardinalityEstimation, PayloadBlockCondition, PayloadFieldIndex, PrimaryCondition, ValueIndexer,
 };
 use crate::index::key_encoding::{
-    decode_f64_key_ascending, decode_i64_key_ascending, encode_f64_key_ascending,
-    encode_i64_key_ascending,
+    decode_f64_key_ascending, decode_i128_key_ascending, decode_i64_key_ascending,
+    encode_f64_key_ascending, encode_i128_key_ascending, encode_i64_key_ascending,
 };
 use crate::telemetry::PayloadIndexTelemetry;
 use crate::types::{
-    DateTimePayloadType, FieldCondition, FloatPayloadType, IntPayloadType, PayloadKeyType, Range,
-    RangeInterface,
+    DateTimePayloadType, DateTimeTimestamp, FieldCondition, FloatPayloadType, IntPayloadType,
+    PayloadKeyType, Range, RangeInterface,
 };
 
 const HISTOGRAM_MAX_BUCKET_SIZE: usize = 10_000;
@@ -70,6 +69,20 @@ impl Encodable for IntPayloadType {
     }
 }
 
+impl Encodable for DateTimeTimestamp {
+    fn encode_key(&self, id: PointOffsetType) -> Vec<u8> {
+        encode_i128_key_ascending(*self, id)
+    }
+
+    fn decode_key(key: &[u8]) -> (PointOffsetType, Self) {
+        decode_i128_key_ascending(key)
+    }
+
+    fn cmp_encoded(&self, other: &Self) -> std::cmp::Ordering {
+        self.cmp(other)
+    }
+}
+
 impl Encodable for FloatPayloadType {
     fn encode_key(&self, id: PointOffsetType) -> Vec<u8> {
         encode_f64_key_ascending(*self, id)
@@ -93,21 +106,15 @@ impl Encodable for FloatPayloadType {
     }
 }
 
-/// Encodes timestamps as i64 in microseconds
+/// Encodes timestamps as i128 in nanoseconds
 impl Encodable for DateTimePayloadType {
     fn encode_key(&self, id: PointOffsetType) -> Vec<u8> {
-        encode_i64_key_ascending(self.timestamp(), id)
+        encode_i128_key_ascending(self.timestamp(), id)
     }
 
     fn decode_key(key: &[u8]) -> (PointOffsetType, Self) {
-        let (id, timestamp) = decode_i64_key_ascending(key);
-        let datetime =
-            DateTime::from_timestamp(timestamp / 1000, (timestamp % 1000) as u32 * 1_000_000)
-                .unwrap_or_else(|| {
-                    log::warn!("Failed to decode timestamp {timestamp}, fallback to UNIX_EPOCH");
-                    DateTime::UNIX_EPOCH
-                });
-        (id, datetime.into())
+        let (id, timestamp) = decode_i128_key_ascending(key);
+        (id, DateTimePayloadType::from_timestamp(timestamp))
     }
 
     fn cmp_encoded(&self, other: &Self) -> std::cmp::Ordering {
@@ -144,12 +151,35 @@ pub enum NumericIndex<T: Encodable + Numericable + Default> {
     Immutable(ImmutableNumericIndex<T>),
 }
 
+impl NumericIndex<DateTimeTimestamp> {
+    /// Index of datetime values, stored as i128 timestamps in nanoseconds
+    pub fn new_datetime(db: Arc<RwLock<DB>>, field: &str, is_appendable: bool) -> Self {
+        Self::with_cf_name(db, &Self::datetime_storage_cf_name(field), is_appendable)
+    }
+
+    /// Datetime indexes used to store timestamps in microseconds, in the column family of the
+    /// numeric index. Those are not loaded under the new name, and are rebuilt from payload.
+    fn datetime_storage_cf_name(field: &str) -> String {
+        format!("{field}_datetime")
+    }
+
+    /// Remove the column family, in which older versions stored the datetime index of `field`
+    pub fn remove_legacy_datetime_storage(db: Arc<RwLock<DB>>, field: &str) -> OperationResult<()> {
+        DatabaseColumnWrapper::new(db, &NumericIndex::<IntPayloadType>::storage_cf_name(field))
+            .remove_column_family()
+    }
+}
+
 impl<T: Encodable + Numericable + Default> NumericIndex<T> {
     pub fn new(db: Arc<RwLock<DB>>, field: &str, is_appendable: bool) -> Self {
+        Self::with_cf_name(db, &Self::storage_cf_name(field), is_appendable)
+    }
+
+    fn with_cf_name(db: Arc<RwLock<DB>>, store_cf_name: &str, is_appendable: bool) -> Self {
         if is_appendable {
-            NumericIndex::Mutable(MutableNumericIndex::new(db, field))
+            NumericIndex::Mutable(MutableNumericIndex::new(db, store_cf_name))
         } else {
-            NumericIndex::Immutable(ImmutableNumericIndex::new(db, field))
+            NumericIndex::Immutable(ImmutableNumericIndex::new(db, store_cf_name))
         }
     }
 
@@ -235,7 +265,7 @@ impl<T: Encodable + Numericable + Default> NumericIndex<T> {
         let range = match range {
             RangeInterface::Float(float_range) => float_range.map(T::from_f64),
             RangeInterface::DateTime(datetime_range) => {
-                datetime_range.map(|dt| T::from_i64(dt.timestamp()))
+                datetime_range.map(|dt| T::from_i128(dt.timestamp()))
             }
         };
 
@@ -343,7 +373,7 @@ impl<T: Encodable + Numericable + Default> PayloadFieldIndex for NumericIndex<T>
         let (start_bound, end_bound) = match range_cond {
             RangeInterface::Float(float_range) => float_range.map(T::from_f64),
             RangeInterface::DateTime(datetime_range) => {
-                datetime_range.map(|dt| T::from_i64(dt.timestamp()))
+                datetime_range.map(|dt| T::from_i128(dt.timestamp()))
             }
         }
         .as_index_key_bounds();
@@ -475,7 +505,7 @@ impl ValueIndexer<IntPayloadType> for NumericIndex<IntPayloadType> {
     }
 }
 
-impl ValueIndexer<DateTimePayloadType> for NumericIndex<IntPayloadType> {
+impl ValueIndexer<DateTimePayloadType> for NumericIndex<DateTimeTimestamp> {
     fn add_many(
         &mut self,
         id: PointOffsetType,
@@ -534,7 +564,7 @@ where
         let range = match range {
             RangeInterface::Float(float_range) => float_range.map(T::from_f64),
             RangeInterface::DateTime(datetime_range) => {
-                datetime_range.map(|dt| T::from_i64(dt.timestamp()))
+                datetime_range.map(|dt| T::from_i128(dt.timestamp()))
             }
         };
         let (start_bound, end_bound) = range.as_index_key_bounds();
@@ -7,7 +7,7 @@ use common::types::PointOffsetType;
 use parking_lot::RwLock;
 use rocksdb::DB;
 
-use super::{Encodable, NumericIndex, HISTOGRAM_MAX_BUCKET_SIZE, HISTOGRAM_PRECISION};
+use super::{Encodable, HISTOGRAM_MAX_BUCKET_SIZE, HISTOGRAM_PRECISION};
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
 use crate::index::field_index::histogram::{Histogram, Numericable, Point};
@@ -22,9 +22,8 @@ pub struct MutableNumericIndex<T: Encodable + Numericable> {
 }
 
 impl<T: Encodable + Numericable + Default> MutableNumericIndex<T> {
-    pub fn new(db: Arc<RwLock<DB>>, field: &str) -> Self {
-        let store_cf_name = NumericIndex::<T>::storage_cf_name(field);
-        let db_wrapper = DatabaseColumnWrapper::new(db, &store_cf_name);
+    pub fn new(db: Arc<RwLock<DB>>, store_cf_name: &str) -> Self {
+        let db_wrapper = DatabaseColumnWrapper::new(db, store_cf_name);
         Self {
             map: BTreeMap::new(),
             db_wrapper,
@@ -100,7 +99,8 @@ impl<T: Encodable + Numericable + Default> MutableNumericIndex<T> {
             return Ok(false);
         };
 
-        for (key, value) in self.db_wrapper.lock_db().iter()? {
+        for item in self.db_wrapper.lock_db().iter()? {
+            let (key, value) = item?;
             let value_idx = u32::from_be_bytes(value.as_ref().try_into().unwrap());
             let (idx, value) = T::decode_key(&key);
 
@@ -377,6 +377,95 @@ fn test_cond<T: Encodable + Numericable + PartialOrd + Clone + Default>(
     assert_eq!(offsets, result);
 }
 
+#[rstest]
+#[case(true)]
+#[case(false)]
+fn test_datetime_index_nanoseconds(#[case] immutable: bool) {
+    let temp_dir = Builder::new()
+        .prefix("test_datetime_index")
+        .tempdir()
+        .unwrap();
+    let db = open_db_with_existing_cf(temp_dir.path()).unwrap();
+    let mut index = NumericIndex::new_datetime(db, COLUMN_NAME, true);
+    index.recreate().unwrap();
+
+    // Same microsecond, only nanoseconds differ
+    let datetimes = [
+        "2024-05-01T12:00:00.000000300Z",
+        "2024-05-01T12:00:00.000000100Z",
+        "2024-05-01T12:00:00.000000200Z",
+        "2024-05-01T12:00:00.000001Z",
+        // Out of the i64 nanosecond range
+        "1500-01-01T00:00:00Z",
+        "3000-01-01T00:00:00Z",
+    ];
+    let datetime = |s: &str| DateTimePayloadType::from_str(s).unwrap();
+
+    for (idx, value) in datetimes.into_iter().enumerate() {
+        ValueIndexer::add_many(&mut index, idx as PointOffsetType, vec![datetime(value)]).unwrap();
+    }
+    index.flusher()().unwrap();
+
+    let index = if immutable {
+        let db_ref = index.get_db_wrapper().database.clone();
+        let mut new_index = NumericIndex::new_datetime(db_ref, COLUMN_NAME, false);
+        assert!(new_index.load().unwrap());
+        new_index
+    } else {
+        index
+    };
+
+    let range = Range {
+        gt: Some(datetime("2024-05-01T12:00:00.000000100Z")),
+        gte: None,
+        lt: None,
+        lte: Some(datetime("2024-05-01T12:00:00.000000300Z")),
+    };
+    let condition = FieldCondition::new_datetime_range(path("unused"), range);
+    let offsets = index.filter(&condition).unwrap().sorted().collect_vec();
+    assert_eq!(offsets, vec![0, 2]);
+
+    let range = Range {
+        gt: Some(datetime("2500-01-01T00:00:00Z")),
+        gte: None,
+        lt: None,
+        lte: None,
+    };
+    let condition = FieldCondition::new_datetime_range(path("unused"), range);
+    let offsets = index.filter(&condition).unwrap().collect_vec();
+    assert_eq!(offsets, vec![5]);
+
+    let ordered = index
+        .stream_range(&RangeInterface::DateTime(Range::default()))
+        .map(|(_, idx)| idx)
+        .collect_vec();
+    assert_eq!(ordered, vec![4, 1, 2, 0, 3, 5]);
+}
+
+#[test]
+fn test_datetime_index_ignores_microsecond_storage() {
+    let temp_dir = Builder::new()
+        .prefix("test_datetime_index")
+        .tempdir()
+        .unwrap();
+    let db = open_db_with_existing_cf(temp_dir.path()).unwrap();
+
+    // Datetime index of the older versions, timestamps in microseconds
+    let mut legacy_index = NumericIndex::<IntPayloadType>::new(db.clone(), COLUMN_NAME, true);
+    legacy_index.recreate().unwrap();
+    ValueIndexer::<IntPayloadType>::add_many(&mut legacy_index, 0, vec![1_714_564_800_000_000])
+        .unwrap();
+    legacy_index.flusher()().unwrap();
+
+    // Is not loaded, so that it is rebuilt from payload
+    let mut index = NumericIndex::new_datetime(db.clone(), COLUMN_NAME, true);
+    assert!(!index.load().unwrap());
+
+    // Is dropped after the index is rebuilt
+    NumericIndex::<DateTimeTimestamp>::remove_legacy_datetime_storage(db, COLUMN_NAME).unwrap();
+    assert!(!legacy_index.get_db_wrapper().has_column_family().unwrap());
+}
+
 // Check we don't panic on an empty index. See <https://github.com/qdrant/qdrant/pull/2933>.
 #[rstest]
 #[case(true)]
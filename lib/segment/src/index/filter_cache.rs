@@ -0,0 +1,363 @@
+//! Cache of points matching frequently used filters, so that the same selective filters are not
+//! re-evaluated on every search.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::VecDeque;
+use std::hash::Hasher;
+use std::io;
+use std::sync::Arc;
+
+use bitvec::prelude::BitVec;
+use common::types::PointOffsetType;
+use parking_lot::Mutex;
+
+use crate::payload_storage::FilterContext;
+use crate::types::{Condition, Filter, RangeInterface};
+
+/// Max number of filters cached per segment, least recently used filters are evicted first
+const FILTER_CACHE_CAPACITY: usize = 16;
+
+/// Max number of filters, which missed the cache once and are admitted on the next miss
+const FILTER_CANDIDATES_CAPACITY: usize = 4 * FILTER_CACHE_CAPACITY;
+
+/// Points matching recently used filters.
+///
+/// Filters are only cached on their second miss, so that one-off filters do not evict the
+/// frequently used ones. Filters with datetime conditions are never cached, those are usually
+/// relative to the current time and never repeat.
+///
+/// Must be cleared on every change of payload, new points are detected by the number of points
+/// at the time the filter was evaluated.
+#[derive(Default)]
+pub struct FilterCache {
+    entries: Mutex<CacheEntries>,
+}
+
+#[derive(Default)]
+struct CacheEntries {
+    filters: Vec<CachedFilter>,
+    /// Hashes of filters, which missed the cache once
+    candidates: VecDeque<u64>,
+    /// Incremented on each access, to find the least recently used filter
+    clock: u64,
+}
+
+struct CachedFilter {
+    filter: Filter,
+    points: Arc<CachedPoints>,
+    /// Total number of points in the segment, when the filter was evaluated
+    total_point_count: usize,
+    used_at: u64,
+}
+
+/// Points matching a filter, in the most compact representation
+pub enum CachedPoints {
+    /// Sorted ids of matching points, if there are only a few of them
+    Sorted(Vec<PointOffsetType>),
+    /// Bit of each point of the segment
+    Bitset(BitVec),
+}
+
+impl CachedPoints {
+    fn new(points: &[PointOffsetType], total_point_count: usize) -> Self {
+        // Take whichever is smaller, ids or one bit per point of the segment
+        if points.len() * PointOffsetType::BITS as usize <= total_point_count {
+            let mut points = points.to_vec();
+            points.sort_unstable();
+            return CachedPoints::Sorted(points);
+        }
+
+        let mut bitset = BitVec::repeat(false, total_point_count);
+        for &point_id in points {
+            if let Some(mut bit) = bitset.get_mut(point_id as usize) {
+                *bit = true;
+            }
+        }
+        CachedPoints::Bitset(bitset)
+    }
+
+    pub fn contains(&self, point_id: PointOffsetType) -> bool {
+        match self {
+            CachedPoints::Sorted(points) => points.binary_search(&point_id).is_ok(),
+            CachedPoints::Bitset(bitset) => bitset
+                .get(point_id as usize)
+                .as_deref()
+                .copied()
+                .unwrap_or(false),
+        }
+    }
+
+    pub fn to_vec(&self) -> Vec<PointOffsetType> {
+        match self {
+            CachedPoints::Sorted(points) => points.clone(),
+            CachedPoints::Bitset(bitset) => {
+                bitset.iter_ones().map(|id| id as PointOffsetType).collect()
+            }
+        }
+    }
+}
+
+impl FilterCache {
+    /// Points matching the filter, if it was evaluated since the last change of the segment
+    pub fn get(&self, filter: &Filter, total_point_count: usize) -> Option<Arc<CachedPoints>> {
+        let mut entries = self.entries.lock();
+        entries.clock += 1;
+        let clock = entries.clock;
+
+        // Only a few filters are cached, linear lookup is fine
+        let position = entries
+            .filters
+            .iter()
+            .position(|cached| &cached.filter == filter)?;
+        if entries.filters[position].total_point_count != total_point_count {
+            entries.filters.swap_remove(position);
+            return None;
+        }
+        let cached = &mut entries.filters[position];
+        cached.used_at = clock;
+        Some(cached.points.clone())
+    }
+
+    /// Cache points matching the filter, if the filter missed the cache before
+    pub fn insert(&self, filter: &Filter, points: &[PointOffsetType], total_point_count: usize) {
+        if is_time_dependent(filter) {
+            return;
+        }
+
+        let filter_hash = filter_hash(filter);
+        {
+            let mut entries = self.entries.lock();
+            match entries
+                .candidates
+                .iter()
+                .position(|&candidate| candidate == filter_hash)
+            {
+                Some(position) => {
+                    entries.candidates.remove(position);
+                }
+                None => {
+                    if entries.candidates.len() >= FILTER_CANDIDATES_CAPACITY {
+                        entries.candidates.pop_front();
+                    }
+                    entries.candidates.push_back(filter_hash);
+                    return;
+                }
+            }
+        }
+
+        let points = Arc::new(CachedPoints::new(points, total_point_count));
+
+        let mut entries = self.entries.lock();
+        entries.clock += 1;
+        let used_at = entries.clock;
+
+        entries.filters.retain(|cached| &cached.filter != filter);
+        if entries.filters.len() >= FILTER_CACHE_CAPACITY {
+            let least_recently_used = entries
+                .filters
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, cached)| cached.used_at)
+                .map(|(position, _)| position);
+            if let Some(position) = least_recently_used {
+                entries.filters.swap_remove(position);
+            }
+        }
+        entries.filters.push(CachedFilter {
+            filter: filter.clone(),
+            points,
+            total_point_count,
+            used_at,
+        });
+    }
+
+    /// Forget all cached filters, on change of payload
+    pub fn clear(&mut self) {
+        let entries = self.entries.get_mut();
+        entries.filters.clear();
+        entries.candidates.clear();
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.entries.lock().filters.len()
+    }
+}
+
+/// Hash of the filter, used to recognize filters which missed the cache before
+///
+/// Collisions only admit a filter to the cache earlier, cached filters are compared as a whole.
+fn filter_hash(filter: &Filter) -> u64 {
+    struct HashWriter(DefaultHasher);
+
+    impl io::Write for HashWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.write(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut writer = HashWriter(DefaultHasher::new());
+    // Serialization into a hasher can't fail
+    let _ = serde_json::to_writer(&mut writer, filter);
+    writer.0.finish()
+}
+
+/// Whether the filter compares datetimes, e.g. expiration moments with the current time
+fn is_time_dependent(filter: &Filter) -> bool {
+    let Filter {
+        should,
+        min_should,
+        must,
+        must_not,
+    } = filter;
+
+    should
+        .iter()
+        .chain(must)
+        .chain(must_not)
+        .flatten()
+        .chain(
+            min_should
+                .iter()
+                .flat_map(|min_should| &min_should.conditions),
+        )
+        .any(|condition| match condition {
+            Condition::Field(field_condition) => {
+                matches!(field_condition.range, Some(RangeInterface::DateTime(_)))
+            }
+            Condition::Nested(nested) => is_time_dependent(nested.filter()),
+            Condition::Filter(filter) => is_time_dependent(filter),
+            Condition::IsEmpty(_)
+            | Condition::IsNull(_)
+            | Condition::HasId(_)
+            | Condition::HasIdRange(_) => false,
+        })
+}
+
+/// Filter context, which checks points against the cached result of the filter
+pub struct CachedFilterContext {
+    points: Arc<CachedPoints>,
+}
+
+impl CachedFilterContext {
+    pub fn new(points: Arc<CachedPoints>) -> Self {
+        Self { points }
+    }
+}
+
+impl FilterContext for CachedFilterContext {
+    fn check(&self, point_id: PointOffsetType) -> bool {
+        self.points.contains(point_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::json_path::path;
+    use crate::types::{DateTimePayloadType, FieldCondition, Range};
+
+    fn filter(value: i64) -> Filter {
+        Filter::new_must(Condition::Field(FieldCondition::new_match(
+            path("tenant"),
+            value.into(),
+        )))
+    }
+
+    #[test]
+    fn test_filter_cache() {
+        let mut cache = FilterCache::default();
+        assert!(cache.get(&filter(0), 10).is_none());
+
+        // Cached on the second miss only
+        cache.insert(&filter(0), &[1, 3, 5], 10);
+        assert!(cache.get(&filter(0), 10).is_none());
+        cache.insert(&filter(0), &[1, 3, 5], 10);
+        let context = CachedFilterContext::new(cache.get(&filter(0), 10).unwrap());
+        assert!(context.check(3));
+        assert!(!context.check(4));
+        assert!(!context.check(100));
+
+        // New points might match the filter
+        assert!(cache.get(&filter(0), 11).is_none());
+        assert_eq!(cache.len(), 0);
+
+        // Least recently used filter is evicted
+        for value in 0..FILTER_CACHE_CAPACITY as i64 {
+            cache.insert(&filter(value), &[], 10);
+            cache.insert(&filter(value), &[], 10);
+        }
+        assert!(cache.get(&filter(0), 10).is_some());
+        cache.insert(&filter(-1), &[], 10);
+        cache.insert(&filter(-1), &[], 10);
+        assert_eq!(cache.len(), FILTER_CACHE_CAPACITY);
+        assert!(cache.get(&filter(0), 10).is_some());
+        assert!(cache.get(&filter(1), 10).is_none());
+
+        cache.clear();
+        assert_eq!(cache.len(), 0);
+    }
+
+    #[test]
+    fn test_one_off_filters_do_not_evict_hot_filters() {
+        let cache = FilterCache::default();
+        for value in 0..FILTER_CACHE_CAPACITY as i64 {
+            cache.insert(&filter(value), &[1], 10);
+            cache.insert(&filter(value), &[1], 10);
+        }
+
+        for value in FILTER_CACHE_CAPACITY as i64..10 * FILTER_CACHE_CAPACITY as i64 {
+            cache.insert(&filter(value), &[1], 10);
+        }
+
+        assert_eq!(cache.len(), FILTER_CACHE_CAPACITY);
+        for value in 0..FILTER_CACHE_CAPACITY as i64 {
+            assert!(cache.get(&filter(value), 10).is_some());
+        }
+    }
+
+    #[test]
+    fn test_time_dependent_filters_are_not_cached() {
+        let cache = FilterCache::default();
+        let now = DateTimePayloadType::from_str("2024-05-01T12:00:00Z").unwrap();
+        let expired = Filter::new_must_not(Condition::Field(FieldCondition::new_datetime_range(
+            path("expires_at"),
+            Range {
+                lt: None,
+                gt: None,
+                gte: None,
+                lte: Some(now),
+            },
+        )));
+        let filter = filter(0).merge(&expired);
+
+        cache.insert(&filter, &[1], 10);
+        cache.insert(&filter, &[1], 10);
+        assert!(cache.get(&filter, 10).is_none());
+    }
+
+    #[test]
+    fn test_cached_points_representation() {
+        let points = [7, 1, 3];
+
+        let sorted = CachedPoints::new(&points, 1000);
+        assert!(matches!(&sorted, CachedPoints::Sorted(points) if points == &[1, 3, 7]));
+
+        let bitset = CachedPoints::new(&points, 10);
+        assert!(matches!(bitset, CachedPoints::Bitset(_)));
+
+        for cached in [sorted, bitset] {
+            assert_eq!(cached.to_vec(), vec![1, 3, 7]);
+            assert!(cached.contains(3));
+            assert!(!cached.contains(4));
+            assert!(!cached.contains(100));
+        }
+    }
+}
@@ -0,0 +1,243 @@
+//! Cache of cardinality estimations of frequently used filters, so that the same filter templates
+//! are not planned again on every search.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use serde_json::Value;
+
+use crate::index::field_index::CardinalityEstimation;
+use crate::types::{Condition, Filter};
+
+/// Max number of filter plans cached per segment, least recently used plans are evicted first
+const FILTER_PLAN_CACHE_CAPACITY: usize = 64;
+
+/// Keys of the filter, which contain lists of conditions with no meaningful order
+const CONDITION_LIST_KEYS: [&str; 4] = ["must", "should", "must_not", "conditions"];
+
+/// Estimations of a filter and of the conditions it consists of
+#[derive(Debug)]
+pub struct FilterPlan {
+    pub estimation: CardinalityEstimation,
+    condition_estimations: Vec<(Condition, CardinalityEstimation)>,
+}
+
+impl FilterPlan {
+    pub fn new(
+        estimation: CardinalityEstimation,
+        condition_estimations: Vec<(Condition, CardinalityEstimation)>,
+    ) -> Self {
+        Self {
+            estimation,
+            condition_estimations,
+        }
+    }
+
+    /// Estimation of one of the conditions of the filter
+    pub fn condition_estimation(&self, condition: &Condition) -> Option<CardinalityEstimation> {
+        // Filters consist of only a few conditions, linear lookup is fine
+        self.condition_estimations
+            .iter()
+            .find(|(planned, _)| planned == condition)
+            .map(|(_, estimation)| estimation.clone())
+    }
+}
+
+/// Plans of recently used filters, keyed by the normalized structure of the filter.
+///
+/// Must be cleared on every change of payload indexes, changes of the number of points are
+/// detected by the point counts at the time the filter was planned.
+#[derive(Default)]
+pub struct FilterPlanCache {
+    entries: Mutex<CacheEntries>,
+}
+
+#[derive(Default)]
+struct CacheEntries {
+    plans: HashMap<String, CachedPlan>,
+    /// Incremented on each access, to find the least recently used plan
+    clock: u64,
+}
+
+struct CachedPlan {
+    plan: Arc<FilterPlan>,
+    /// Total and available number of points in the segment, when the filter was planned
+    point_counts: (usize, usize),
+    used_at: u64,
+}
+
+impl FilterPlanCache {
+    /// Plan of the filter, if it was planned since the last change of the segment
+    pub fn get(&self, filter: &Filter, point_counts: (usize, usize)) -> Option<Arc<FilterPlan>> {
+        let key = normalized_key(filter)?;
+        let mut entries = self.entries.lock();
+        entries.clock += 1;
+        let clock = entries.clock;
+
+        let cached = entries.plans.get_mut(&key)?;
+        if cached.point_counts != point_counts {
+            entries.plans.remove(&key);
+            return None;
+        }
+        cached.used_at = clock;
+        Some(cached.plan.clone())
+    }
+
+    /// Cache the plan of the filter, returns the plan back for convenience
+    pub fn insert(
+        &self,
+        filter: &Filter,
+        plan: FilterPlan,
+        point_counts: (usize, usize),
+    ) -> Arc<FilterPlan> {
+        let plan = Arc::new(plan);
+        let Some(key) = normalized_key(filter) else {
+            return plan;
+        };
+
+        let mut entries = self.entries.lock();
+        entries.clock += 1;
+        let used_at = entries.clock;
+
+        if !entries.plans.contains_key(&key) && entries.plans.len() >= FILTER_PLAN_CACHE_CAPACITY {
+            let least_recently_used = entries
+                .plans
+                .iter()
+                .min_by_key(|(_, cached)| cached.used_at)
+                .map(|(evicted, _)| evicted.clone());
+            if let Some(evicted) = least_recently_used {
+                entries.plans.remove(&evicted);
+            }
+        }
+        entries.plans.insert(
+            key,
+            CachedPlan {
+                plan: plan.clone(),
+                point_counts,
+                used_at,
+            },
+        );
+        plan
+    }
+
+    /// Forget all plans, on change of payload indexes
+    pub fn clear(&mut self) {
+        self.entries.get_mut().plans.clear();
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.entries.lock().plans.len()
+    }
+}
+
+/// Key of the filter, which doesn't depend on the order of conditions in the clauses
+fn normalized_key(filter: &Filter) -> Option<String> {
+    let mut value = serde_json::to_value(filter).ok()?;
+    normalize(&mut value);
+    Some(value.to_string())
+}
+
+fn normalize(value: &mut Value) {
+    match value {
+        Value::Object(object) => {
+            for (key, value) in object.iter_mut() {
+                normalize(value);
+                if let Value::Array(conditions) = value {
+                    if CONDITION_LIST_KEYS.contains(&key.as_str()) {
+                        conditions.sort_by_cached_key(Value::to_string);
+                    }
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(normalize),
+        Value::Null | Value::Bool(_) | Value::Number(_) | Value::String(_) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::json_path::path;
+    use crate::types::FieldCondition;
+
+    fn condition(key: &str, value: i64) -> Condition {
+        Condition::Field(FieldCondition::new_match(path(key), value.into()))
+    }
+
+    fn plan(exp: usize) -> FilterPlan {
+        let estimation = CardinalityEstimation {
+            primary_clauses: vec![],
+            min: 0,
+            exp,
+            max: 100,
+        };
+        let condition_estimations = vec![(condition("tenant", 0), estimation.clone())];
+        FilterPlan::new(estimation, condition_estimations)
+    }
+
+    #[test]
+    fn test_filter_plan_cache() {
+        let mut cache = FilterPlanCache::default();
+        let filter = Filter {
+            must: Some(vec![condition("tenant", 0), condition("group", 1)]),
+            ..Default::default()
+        };
+        assert!(cache.get(&filter, (10, 10)).is_none());
+
+        cache.insert(&filter, plan(42), (10, 10));
+        let cached = cache.get(&filter, (10, 10)).unwrap();
+        assert_eq!(cached.estimation.exp, 42);
+        assert_eq!(
+            cached
+                .condition_estimation(&condition("tenant", 0))
+                .map(|estimation| estimation.exp),
+            Some(42),
+        );
+        assert!(cached
+            .condition_estimation(&condition("tenant", 1))
+            .is_none());
+
+        // Order of conditions doesn't matter
+        let reordered = Filter {
+            must: Some(vec![condition("group", 1), condition("tenant", 0)]),
+            ..Default::default()
+        };
+        assert!(cache.get(&reordered, (10, 10)).is_some());
+
+        // Clauses do matter
+        let should = Filter {
+            should: Some(vec![condition("tenant", 0), condition("group", 1)]),
+            ..Default::default()
+        };
+        assert!(cache.get(&should, (10, 10)).is_none());
+
+        // Estimations are outdated with the new points
+        assert!(cache.get(&filter, (11, 11)).is_none());
+        assert_eq!(cache.len(), 0);
+
+        // Least recently used plan is evicted
+        for value in 0..FILTER_PLAN_CACHE_CAPACITY as i64 {
+            cache.insert(
+                &Filter::new_must(condition("tenant", value)),
+                plan(0),
+                (10, 10),
+            );
+        }
+        assert!(cache
+            .get(&Filter::new_must(condition("tenant", 0)), (10, 10))
+            .is_some());
+        cache.insert(&filter, plan(0), (10, 10));
+        assert_eq!(cache.len(), FILTER_PLAN_CACHE_CAPACITY);
+        assert!(cache
+            .get(&Filter::new_must(condition("tenant", 0)), (10, 10))
+            .is_some());
+        assert!(cache
+            .get(&Filter::new_must(condition("tenant", 1)), (10, 10))
+            .is_none());
+
+        cache.clear();
+        assert_eq!(cache.len(), 0);
+    }
+}
@@ -7,6 +7,9 @@ use crate::common::operation_error::OperationResult;
 
 pub const HNSW_INDEX_CONFIG_FILE: &str = "hnsw_config.json";
 
+/// Cost of checking the filter for a point, relative to scoring its vector
+const FILTER_CHECK_COST: f64 = 0.1;
+
 #[derive(Debug, Deserialize, Serialize, Copy, Clone, PartialEq, Eq)]
 pub struct HnswGraphConfig {
     pub m: usize,
@@ -53,6 +56,28 @@ impl HnswGraphConfig {
         }
     }
 
+    /// Number of vectors matching a filtered query, below which scoring all of them is expected
+    /// to be cheaper than searching the graph with the given `ef`.
+    ///
+    /// Graph search expands about `ef` points, checking the query filter for `m0` neighbours of
+    /// each and scoring the matching ones. So it costs about
+    /// `ef * m0 * (FILTER_CHECK_COST + cardinality / available_vectors)` scorings, compared to
+    /// `cardinality` scorings of the plain search. Never less than the full scan threshold.
+    ///
+    /// Unfiltered queries match all available vectors, so those are compared to the full scan
+    /// threshold only.
+    pub fn plain_search_threshold(&self, ef: usize, available_vectors: usize) -> usize {
+        let expanded_neighbours = ef.saturating_mul(self.m0) as f64;
+        let available_vectors = available_vectors as f64;
+        if expanded_neighbours >= available_vectors {
+            // Graph search would visit all vectors anyway
+            return usize::MAX;
+        }
+        let break_even = expanded_neighbours * FILTER_CHECK_COST
+            / (1.0 - expanded_neighbours / available_vectors);
+        (break_even as usize).max(self.full_scan_threshold)
+    }
+
     pub fn get_config_path(path: &Path) -> PathBuf {
         path.join(HNSW_INDEX_CONFIG_FILE)
     }
@@ -65,3 +90,23 @@ impl HnswGraphConfig {
         Ok(atomic_save_json(path, self)?)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_search_threshold() {
+        let config = HnswGraphConfig::new(16, 100, 10, 0, None, 1_000_000);
+
+        // 100 * 32 * 0.1 / (1 - 100 * 32 / 1_000_000)
+        assert_eq!(config.plain_search_threshold(100, 1_000_000), 321);
+        // Larger `ef` makes graph search more expensive
+        assert_eq!(config.plain_search_threshold(400, 1_000_000), 1_296);
+        // As well as larger fraction of the segment visited
+        assert_eq!(config.plain_search_threshold(100, 10_000), 470);
+        assert_eq!(config.plain_search_threshold(100, 3_000), usize::MAX);
+        // Never less than the full scan threshold
+        assert_eq!(config.plain_search_threshold(1, 1_000_000), 10);
+    }
+}
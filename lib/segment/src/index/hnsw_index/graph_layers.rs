@@ -1,6 +1,7 @@
 use std::cmp::max;
 use std::path::{Path, PathBuf};
 
+use bitvec::prelude::BitVec;
 use common::fixed_length_priority_queue::FixedLengthPriorityQueue;
 use common::types::{PointOffsetType, ScoredPointOffset};
 use io::file_operations::{atomic_save_bin, read_bin, FileStorageError};
@@ -16,6 +17,7 @@ use crate::index::hnsw_index::entry_points::EntryPoints;
 use crate::index::hnsw_index::graph_links::GraphLinksConverter;
 use crate::index::hnsw_index::point_scorer::FilteredScorer;
 use crate::index::hnsw_index::search_context::SearchContext;
+use crate::index::vector_index_info::{HnswGraphInfo, HnswLayerInfo};
 use crate::index::visited_pool::{VisitedListHandle, VisitedPool};
 
 pub type LinkContainer = Vec<PointOffsetType>;
@@ -233,6 +235,59 @@ impl<TGraphLinks: GraphLinks> GraphLayers<TGraphLinks> {
     pub fn num_points(&self) -> usize {
         self.links.num_points()
     }
+
+    /// Statistics of layers, and points not reachable from the entry point.
+    ///
+    /// Like searches, walking the graph doesn't pass through points, which are not `is_live`.
+    pub fn info(&self, is_live: impl Fn(PointOffsetType) -> bool) -> HnswGraphInfo {
+        let layers = (0..self.links.levels_count())
+            .filter_map(|level| self.links.get_level_offsets_range(level))
+            .map(|offsets| {
+                let points_count = offsets.len();
+                let links_count: usize = offsets
+                    .map(|idx| self.links.get_links_range(idx).len())
+                    .sum();
+                HnswLayerInfo {
+                    points_count,
+                    avg_out_degree: if points_count > 0 {
+                        links_count as f64 / points_count as f64
+                    } else {
+                        0.0
+                    },
+                }
+            })
+            .collect();
+
+        let num_points = self.num_points();
+        let mut reached: BitVec = BitVec::repeat(false, num_points);
+        let mut queue = Vec::new();
+        if let Some(entry_point) = self.entry_points.get_entry_point(&is_live) {
+            reached.set(entry_point.point_id as usize, true);
+            queue.push(entry_point.point_id);
+        }
+        while let Some(point_id) = queue.pop() {
+            for level in 0..=self.point_level(point_id) {
+                for &link in self.links.links(point_id, level) {
+                    if is_live(link) && !reached.replace(link as usize, true) {
+                        queue.push(link);
+                    }
+                }
+            }
+        }
+
+        let disconnected_points = (0..num_points as PointOffsetType)
+            .filter(|&point_id| is_live(point_id) && !reached[point_id as usize])
+            .count();
+
+        HnswGraphInfo {
+            m: self.m,
+            m0: self.m0,
+            ef_construct: self.ef_construct,
+            layers,
+            disconnected_points,
+            built_at: None,
+        }
+    }
 }
 
 impl<TGraphLinks> GraphLayers<TGraphLinks>
@@ -382,6 +437,39 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_graph_info() {
+        let links = vec![
+            vec![vec![1, 2]],
+            vec![vec![0, 3]],
+            vec![vec![0]],
+            vec![vec![1]],
+            vec![vec![5]],
+            vec![vec![4]],
+        ];
+        let mut entry_points = EntryPoints::new(10);
+        entry_points.new_point(0, 0, |_| true);
+
+        let graph_layers = GraphLayers {
+            m: M,
+            m0: 2 * M,
+            ef_construct: 16,
+            links: GraphLinksRam::from_converter(GraphLinksConverter::new(links)).unwrap(),
+            entry_points,
+            visited_pool: VisitedPool::new(),
+        };
+
+        let info = graph_layers.info(|_| true);
+        assert_eq!(info.layers.len(), 1);
+        assert_eq!(info.layers[0].points_count, 6);
+        assert!((info.layers[0].avg_out_degree - 8.0 / 6.0).abs() < 1e-9);
+        assert_eq!(info.disconnected_points, 2);
+
+        // Point 3 is only reachable through deleted point 1
+        let info = graph_layers.info(|point_id| point_id != 1);
+        assert_eq!(info.disconnected_points, 3);
+    }
+
     #[test]
     fn test_save_and_load() {
         let num_vectors = 100;
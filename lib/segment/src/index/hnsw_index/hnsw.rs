@@ -1,4 +1,4 @@
-use std::fs::create_dir_all;
+use std::fs::{self, create_dir_all};
 use std::path::{Path, PathBuf};
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
@@ -6,9 +6,11 @@ use std::thread;
 
 use atomic_refcell::AtomicRefCell;
 use bitvec::prelude::BitSlice;
+use chrono::{DateTime, Utc};
 #[cfg(target_os = "linux")]
 use common::cpu::linux_low_thread_priority;
 use common::cpu::CpuPermit;
+use common::io_throttle;
 use common::types::{PointOffsetType, ScoredPointOffset, TelemetryDetail};
 use log::debug;
 use memory::mmap_ops;
@@ -22,6 +24,7 @@ use crate::common::operation_error::{check_process_stopped, OperationError, Oper
 use crate::common::operation_time_statistics::{
     OperationDurationsAggregator, ScopeDurationMeasurer,
 };
+use crate::common::request_usage::RequestUsage;
 use crate::common::BYTES_IN_KB;
 use crate::data_types::query_context::VectorQueryContext;
 use crate::data_types::vectors::{QueryVector, Vector, VectorRef};
@@ -34,6 +37,7 @@ use crate::index::hnsw_index::point_scorer::FilteredScorer;
 use crate::index::query_estimator::adjust_to_available_vectors;
 use crate::index::sample_estimation::sample_check_cardinality;
 use crate::index::struct_payload_index::StructPayloadIndex;
+use crate::index::vector_index_info::HnswGraphInfo;
 use crate::index::visited_pool::{VisitedListHandle, VisitedPool};
 use crate::index::{PayloadIndex, VectorIndex};
 use crate::telemetry::VectorIndexSearchesTelemetry;
@@ -45,7 +49,8 @@ use crate::types::{
 use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
 use crate::vector_storage::query::DiscoveryQuery;
 use crate::vector_storage::{
-    new_raw_scorer, new_stoppable_raw_scorer, RawScorer, VectorStorage, VectorStorageEnum,
+    check_deleted_condition, new_metered_raw_scorer, new_raw_scorer, new_stoppable_raw_scorer,
+    RawScorer, VectorStorage, VectorStorageEnum,
 };
 
 const HNSW_USE_HEURISTIC: bool = true;
@@ -142,6 +147,23 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         self.quantized_vectors.clone()
     }
 
+    /// Statistics of the graph, `None` if it's not built
+    pub fn graph_info(&self) -> Option<HnswGraphInfo> {
+        let graph = self.graph.as_ref()?;
+        let id_tracker = self.id_tracker.borrow();
+        let vector_storage = self.vector_storage.borrow();
+        let deleted_points = id_tracker.deleted_point_bitslice();
+        let deleted_vectors = vector_storage.deleted_vector_bitslice();
+
+        let mut info = graph
+            .info(|point_id| check_deleted_condition(point_id, deleted_vectors, deleted_points));
+        info.built_at = fs::metadata(GraphLayers::<TGraphLinks>::get_path(&self.path))
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .map(DateTime::<Utc>::from);
+        Some(info)
+    }
+
     fn save_config(&self) -> OperationResult<()> {
         let config_path = HnswGraphConfig::get_config_path(&self.path);
         self.config.save(&config_path)
@@ -285,6 +307,7 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
             deleted_points,
             params,
             &is_stopped,
+            vector_query_context.usage(),
         )?;
         let oversampled_top = Self::get_oversampled_top(quantized_vectors.as_ref(), params, top);
 
@@ -295,7 +318,14 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
             Some(graph) => {
                 let search_result =
                     graph.search(oversampled_top, ef, points_scorer, custom_entry_points);
-                self.postprocess_search_result(search_result, vector, params, top, &is_stopped)
+                self.postprocess_search_result(
+                    search_result,
+                    vector,
+                    params,
+                    top,
+                    &is_stopped,
+                    vector_query_context.usage(),
+                )
             }
             None => Ok(Default::default()),
         }
@@ -351,13 +381,21 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
             deleted_points,
             params,
             &is_stopped,
+            vector_query_context.usage(),
         )?;
         let oversampled_top = Self::get_oversampled_top(quantized_vectors.as_ref(), params, top);
 
         let search_result =
             raw_scorer.peek_top_iter(&mut filtered_points.iter().copied(), oversampled_top);
 
-        self.postprocess_search_result(search_result, vector, params, top, &is_stopped)
+        self.postprocess_search_result(
+            search_result,
+            vector,
+            params,
+            top,
+            &is_stopped,
+            vector_query_context.usage(),
+        )
     }
 
     fn search_vectors_plain(
@@ -370,7 +408,8 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
     ) -> OperationResult<Vec<Vec<ScoredPointOffset>>> {
         let payload_index = self.payload_index.borrow();
         // share filtered points for all query vectors
-        let filtered_points = payload_index.query_points(filter);
+        let filtered_points =
+            vector_query_context.filtered_points(filter, || payload_index.query_points(filter));
         vectors
             .iter()
             .map(|vector| {
@@ -434,9 +473,10 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         deleted_points: &'a BitSlice,
         params: Option<&SearchParams>,
         is_stopped: &'a AtomicBool,
+        usage: Option<&'a RequestUsage>,
     ) -> OperationResult<Box<dyn RawScorer + 'a>> {
         let quantization_enabled = Self::is_quantized_search(quantized_storage, params);
-        match quantized_storage {
+        let raw_scorer = match quantized_storage {
             Some(quantized_storage) if quantization_enabled => quantized_storage.raw_scorer(
                 vector.to_owned(),
                 deleted_points,
@@ -449,7 +489,12 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
                 deleted_points,
                 is_stopped,
             ),
-        }
+        }?;
+        Ok(new_metered_raw_scorer(
+            raw_scorer,
+            usage,
+            vector_storage.available_vector_count(),
+        ))
     }
 
     fn get_oversampled_top(
@@ -479,6 +524,7 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         params: Option<&SearchParams>,
         top: usize,
         is_stopped: &AtomicBool,
+        usage: Option<&RequestUsage>,
     ) -> OperationResult<Vec<ScoredPointOffset>> {
         let id_tracker = self.id_tracker.borrow();
         let vector_storage = self.vector_storage.borrow();
@@ -503,6 +549,8 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
                 id_tracker.deleted_point_bitslice(),
                 is_stopped,
             )?;
+            let raw_scorer =
+                new_metered_raw_scorer(raw_scorer, usage, vector_storage.available_vector_count());
 
             let mut ids_iterator = search_result.iter().map(|x| x.idx);
             let mut re_scored = raw_scorer.score_points_unfiltered(&mut ids_iterator);
@@ -549,8 +597,10 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
                 // Do plain or graph search
                 if plain_search {
                     let _timer = ScopeDurationMeasurer::new(if exact {
+                        query_context.record_search_strategy("exact_unfiltered");
                         &self.searches_telemetry.exact_unfiltered
                     } else {
+                        query_context.record_search_strategy("unfiltered_plain");
                         &self.searches_telemetry.unfiltered_plain
                     });
                     let deleted_points = query_context
@@ -568,12 +618,20 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
                                 deleted_points,
                                 &is_stopped,
                             )
+                            .map(|scorer| {
+                                new_metered_raw_scorer(
+                                    scorer,
+                                    query_context.usage(),
+                                    vector_storage.available_vector_count(),
+                                )
+                            })
                             .map(|scorer| scorer.peek_top_all(top))
                         })
                         .collect()
                 } else {
                     let _timer =
                         ScopeDurationMeasurer::new(&self.searches_telemetry.unfiltered_hnsw);
+                    query_context.record_search_strategy("unfiltered_hnsw");
                     self.search_vectors_with_graph(vectors, None, top, params, query_context)
                 }
             }
@@ -595,6 +653,7 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
                     });
                     let _timer =
                         ScopeDurationMeasurer::new(&self.searches_telemetry.exact_filtered);
+                    query_context.record_search_strategy("exact_filtered");
                     return self.search_vectors_plain(
                         vectors,
                         query_filter,
@@ -608,17 +667,28 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
                 let vector_storage = self.vector_storage.borrow();
                 let id_tracker = self.id_tracker.borrow();
                 let available_vector_count = vector_storage.available_vector_count();
-                let query_point_cardinality = payload_index.estimate_cardinality(query_filter);
+                let query_point_cardinality = query_context
+                    .estimate_cardinality(query_filter, || {
+                        payload_index.estimate_cardinality(query_filter)
+                    });
                 let query_cardinality = adjust_to_available_vectors(
                     query_point_cardinality,
                     available_vector_count,
                     id_tracker.available_point_count(),
                 );
-
-                if query_cardinality.max < self.config.full_scan_threshold {
+                let ef = params
+                    .and_then(|params| params.hnsw_ef)
+                    .unwrap_or(self.config.ef)
+                    .max(top);
+                let plain_search_threshold = self
+                    .config
+                    .plain_search_threshold(ef, available_vector_count);
+
+                if query_cardinality.max < plain_search_threshold {
                     // if cardinality is small - use plain index
                     let _timer =
                         ScopeDurationMeasurer::new(&self.searches_telemetry.small_cardinality);
+                    query_context.record_search_strategy("small_cardinality");
                     return self.search_vectors_plain(
                         vectors,
                         query_filter,
@@ -628,10 +698,11 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
                     );
                 }
 
-                if query_cardinality.min > self.config.full_scan_threshold {
+                if query_cardinality.min > plain_search_threshold {
                     // if cardinality is high enough - use HNSW index
                     let _timer =
                         ScopeDurationMeasurer::new(&self.searches_telemetry.large_cardinality);
+                    query_context.record_search_strategy("large_cardinality");
                     return self.search_vectors_with_graph(
                         vectors,
                         filter,
@@ -648,17 +719,19 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
                 if sample_check_cardinality(
                     id_tracker.sample_ids(Some(vector_storage.deleted_vector_bitslice())),
                     |idx| filter_context.check(idx),
-                    self.config.full_scan_threshold,
+                    plain_search_threshold,
                     available_vector_count, // Check cardinality among available vectors
                 ) {
                     // if cardinality is high enough - use HNSW index
                     let _timer =
                         ScopeDurationMeasurer::new(&self.searches_telemetry.large_cardinality);
+                    query_context.record_search_strategy("large_cardinality");
                     self.search_vectors_with_graph(vectors, filter, top, params, query_context)
                 } else {
                     // if cardinality is small - use plain index
                     let _timer =
                         ScopeDurationMeasurer::new(&self.searches_telemetry.small_cardinality);
+                    query_context.record_search_strategy("small_cardinality");
                     self.search_vectors_plain(vectors, query_filter, top, params, query_context)
                 }
             }
@@ -717,6 +790,7 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
                             "Failed to set low thread priority for HNSW building, ignoring: {err}"
                         );
                     }
+                    let _io_priority = io_throttle::lower_io_priority();
 
                     thread.run()
                 })?;
@@ -51,6 +51,8 @@ fn test_graph_connectivity() {
         )]),
         payload_storage_type: Default::default(),
         sparse_vector_data: Default::default(),
+        payload_columns: vec![],
+        storage_cipher: None,
     };
 
     let mut segment = build_segment(dir.path(), &config, true).unwrap();
@@ -1,4 +1,6 @@
 pub mod field_index;
+mod filter_cache;
+mod filter_plan_cache;
 pub mod hnsw_index;
 mod key_encoding;
 mod payload_config;
@@ -11,6 +13,7 @@ pub mod sparse_index;
 mod struct_filter_context;
 pub mod struct_payload_index;
 pub mod vector_index_base;
+pub mod vector_index_info;
 mod visited_pool;
 
 pub use payload_index_base::*;
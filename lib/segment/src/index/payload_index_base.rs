@@ -6,6 +6,7 @@ use serde_json::Value;
 
 use crate::common::operation_error::OperationResult;
 use crate::common::Flusher;
+use crate::data_types::payload_projection::PayloadProjection;
 use crate::index::field_index::{CardinalityEstimation, PayloadBlockCondition};
 use crate::json_path::JsonPath;
 use crate::payload_storage::FilterContext;
@@ -75,6 +76,23 @@ pub trait PayloadIndex {
     /// Get payload for point
     fn payload(&self, point_id: PointOffsetType) -> OperationResult<Payload>;
 
+    /// Get payloads for multiple points, in the same order
+    fn payloads(&self, point_ids: &[PointOffsetType]) -> OperationResult<Vec<Payload>> {
+        point_ids
+            .iter()
+            .map(|&point_id| self.payload(point_id))
+            .collect()
+    }
+
+    /// Get payload for point, keeping only the fields selected by projection
+    fn payload_projected(
+        &self,
+        point_id: PointOffsetType,
+        projection: PayloadProjection,
+    ) -> OperationResult<Payload> {
+        Ok(projection.project(self.payload(point_id)?))
+    }
+
     /// Delete payload by key
     fn delete(
         &mut self,
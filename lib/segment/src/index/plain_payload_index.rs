@@ -1,11 +1,13 @@
 use std::collections::HashMap;
 use std::fs::create_dir_all;
 use std::path::{Path, PathBuf};
-use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 use atomic_refcell::AtomicRefCell;
+use bitvec::slice::BitSlice;
 use common::cpu::CpuPermit;
+use common::top_k::TopK;
 use common::types::{PointOffsetType, ScoredPointOffset, TelemetryDetail};
 use parking_lot::Mutex;
 use schemars::_serde_json::Value;
@@ -16,7 +18,7 @@ use crate::common::operation_time_statistics::{
 };
 use crate::common::{Flusher, BYTES_IN_KB};
 use crate::data_types::query_context::VectorQueryContext;
-use crate::data_types::vectors::{QueryVector, VectorRef};
+use crate::data_types::vectors::{QueryVector, Vector, VectorRef};
 use crate::id_tracker::IdTrackerSS;
 use crate::index::field_index::{CardinalityEstimation, PayloadBlockCondition};
 use crate::index::payload_config::PayloadConfig;
@@ -27,9 +29,13 @@ use crate::payload_storage::{ConditionCheckerSS, FilterContext};
 use crate::telemetry::VectorIndexSearchesTelemetry;
 use crate::types::{
     Filter, Payload, PayloadFieldSchema, PayloadKeyType, PayloadKeyTypeRef, PayloadSchemaType,
-    SearchParams, VECTOR_ELEMENT_SIZE,
+    QuantizationConfig, SearchParams, VECTOR_ELEMENT_SIZE,
+};
+use crate::vector_storage::quantized::appendable_quantized_vectors::AppendableQuantizedVectors;
+use crate::vector_storage::{
+    check_deleted_condition, new_metered_raw_scorer, new_stoppable_raw_scorer, VectorStorage,
+    VectorStorageEnum,
 };
-use crate::vector_storage::{new_stoppable_raw_scorer, VectorStorage, VectorStorageEnum};
 
 /// Implementation of `PayloadIndex` which does not really indexes anything.
 ///
@@ -207,6 +213,8 @@ pub struct PlainIndex {
     id_tracker: Arc<AtomicRefCell<IdTrackerSS>>,
     vector_storage: Arc<AtomicRefCell<VectorStorageEnum>>,
     payload_index: Arc<AtomicRefCell<StructPayloadIndex>>,
+    /// Quantized vectors of an appendable segment, maintained on every update
+    quantized_vectors: Option<AppendableQuantizedVectors>,
     filtered_searches_telemetry: Arc<Mutex<OperationDurationsAggregator>>,
     unfiltered_searches_telemetry: Arc<Mutex<OperationDurationsAggregator>>,
 }
@@ -221,11 +229,22 @@ impl PlainIndex {
             id_tracker,
             vector_storage,
             payload_index,
+            quantized_vectors: None,
             filtered_searches_telemetry: OperationDurationsAggregator::new(),
             unfiltered_searches_telemetry: OperationDurationsAggregator::new(),
         }
     }
 
+    /// Maintain quantized vectors on every update, to score not yet indexed vectors faster.
+    ///
+    /// Quantization is not applied, if it can't be maintained incrementally.
+    pub fn with_quantization(mut self, quantization_config: Option<&QuantizationConfig>) -> Self {
+        self.quantized_vectors = quantization_config.and_then(|config| {
+            AppendableQuantizedVectors::new(config, &self.vector_storage.borrow())
+        });
+        self
+    }
+
     pub fn is_small_enough_for_unindexed_search(
         &self,
         search_optimized_threshold_kb: usize,
@@ -246,6 +265,81 @@ impl PlainIndex {
             vector_storage_size <= indexing_threshold_bytes
         }
     }
+
+    /// Pre-select candidates with quantized vectors, and rescore them with original vectors.
+    ///
+    /// Returns `None` if the query can't be scored with quantized vectors.
+    #[allow(clippy::too_many_arguments)]
+    fn search_quantized(
+        &self,
+        vector: &QueryVector,
+        points: &mut dyn Iterator<Item = PointOffsetType>,
+        top: usize,
+        params: Option<&SearchParams>,
+        vector_storage: &VectorStorageEnum,
+        deleted_points: &BitSlice,
+        is_stopped: &AtomicBool,
+        query_context: &VectorQueryContext,
+    ) -> Option<OperationResult<Vec<ScoredPointOffset>>> {
+        let quantization_params = params.and_then(|params| params.quantization);
+        let is_exact = params.is_some_and(|params| params.exact);
+        if is_exact || quantization_params.is_some_and(|params| params.ignore) {
+            return None;
+        }
+        let QueryVector::Nearest(Vector::Dense(query)) = vector else {
+            return None;
+        };
+        let scorer = self.quantized_vectors.as_ref()?.scorer(query.clone())?;
+        query_context.record_search_strategy("quantized_plain");
+        if top == 0 {
+            return Some(Ok(vec![]));
+        }
+
+        let oversampling = quantization_params
+            .and_then(|params| params.oversampling)
+            .unwrap_or(1.0);
+        let oversampled_top = ((top as f64 * oversampling).ceil() as usize).max(top);
+        let vec_deleted = vector_storage.deleted_vector_bitslice();
+        let mut candidates = TopK::new(oversampled_top);
+        for point in points {
+            if is_stopped.load(Ordering::Relaxed) {
+                break;
+            }
+            if !check_deleted_condition(point, vec_deleted, deleted_points) {
+                continue;
+            }
+            if let Some(score) = scorer.score(point) {
+                candidates.push(ScoredPointOffset { idx: point, score });
+            }
+        }
+        let mut candidates = candidates.into_vec();
+
+        let rescore = quantization_params
+            .and_then(|params| params.rescore)
+            .unwrap_or(true);
+        if !rescore {
+            candidates.truncate(top);
+            return Some(Ok(candidates));
+        }
+
+        let result = new_stoppable_raw_scorer(
+            vector.to_owned(),
+            vector_storage,
+            deleted_points,
+            is_stopped,
+        )
+        .map(|scorer| {
+            new_metered_raw_scorer(
+                scorer,
+                query_context.usage(),
+                vector_storage.available_vector_count(),
+            )
+        })
+        .map(|scorer| {
+            scorer.peek_top_iter(&mut candidates.iter().map(|candidate| candidate.idx), top)
+        });
+        Some(result)
+    }
 }
 
 impl VectorIndex for PlainIndex {
@@ -272,22 +366,43 @@ impl VectorIndex for PlainIndex {
         match filter {
             Some(filter) => {
                 let _timer = ScopeDurationMeasurer::new(&self.filtered_searches_telemetry);
+                query_context.record_search_strategy("filtered_plain");
                 let id_tracker = self.id_tracker.borrow();
                 let payload_index = self.payload_index.borrow();
                 let vector_storage = self.vector_storage.borrow();
-                let filtered_ids_vec = payload_index.query_points(filter);
+                let filtered_ids_vec =
+                    query_context.filtered_points(filter, || payload_index.query_points(filter));
                 let deleted_points = query_context
                     .deleted_points()
                     .unwrap_or(id_tracker.deleted_point_bitslice());
                 vectors
                     .iter()
                     .map(|&vector| {
+                        if let Some(result) = self.search_quantized(
+                            vector,
+                            &mut filtered_ids_vec.iter().copied(),
+                            top,
+                            params,
+                            &vector_storage,
+                            deleted_points,
+                            &is_stopped,
+                            query_context,
+                        ) {
+                            return result;
+                        }
                         new_stoppable_raw_scorer(
                             vector.to_owned(),
                             &vector_storage,
                             deleted_points,
                             &is_stopped,
                         )
+                        .map(|scorer| {
+                            new_metered_raw_scorer(
+                                scorer,
+                                query_context.usage(),
+                                vector_storage.available_vector_count(),
+                            )
+                        })
                         .map(|scorer| {
                             scorer.peek_top_iter(&mut filtered_ids_vec.iter().copied(), top)
                         })
@@ -296,6 +411,7 @@ impl VectorIndex for PlainIndex {
             }
             None => {
                 let _timer = ScopeDurationMeasurer::new(&self.unfiltered_searches_telemetry);
+                query_context.record_search_strategy("unfiltered_plain");
                 let vector_storage = self.vector_storage.borrow();
                 let id_tracker = self.id_tracker.borrow();
                 let deleted_points = query_context
@@ -304,12 +420,31 @@ impl VectorIndex for PlainIndex {
                 vectors
                     .iter()
                     .map(|&vector| {
+                        if let Some(result) = self.search_quantized(
+                            vector,
+                            &mut id_tracker.iter_ids(),
+                            top,
+                            params,
+                            &vector_storage,
+                            deleted_points,
+                            &is_stopped,
+                            query_context,
+                        ) {
+                            return result;
+                        }
                         new_stoppable_raw_scorer(
                             vector.to_owned(),
                             &vector_storage,
                             deleted_points,
                             &is_stopped,
                         )
+                        .map(|scorer| {
+                            new_metered_raw_scorer(
+                                scorer,
+                                query_context.usage(),
+                                vector_storage.available_vector_count(),
+                            )
+                        })
                         .map(|scorer| scorer.peek_top_all(top))
                     })
                     .collect()
@@ -355,7 +490,10 @@ impl VectorIndex for PlainIndex {
         0
     }
 
-    fn update_vector(&mut self, _id: PointOffsetType, _vector: VectorRef) -> OperationResult<()> {
+    fn update_vector(&mut self, id: PointOffsetType, _vector: VectorRef) -> OperationResult<()> {
+        if let Some(quantized_vectors) = &mut self.quantized_vectors {
+            quantized_vectors.update(id, &self.vector_storage.borrow());
+        }
         Ok(())
     }
 }
@@ -7,8 +7,10 @@ use std::cmp::{max, min};
 
 use itertools::Itertools;
 
-use crate::index::field_index::{CardinalityEstimation, PrimaryCondition};
-use crate::types::{Condition, Filter, MinShould};
+use crate::index::field_index::{CardinalityEstimation, FieldIndexStatistics, PrimaryCondition};
+use crate::types::{
+    AnyVariants, Condition, FieldCondition, Filter, Match, MatchAny, MatchExcept, MinShould,
+};
 
 /// Re-estimate cardinality based on number of available vectors
 /// Assuming that deleted vectors are not correlated with the filter
@@ -158,6 +160,61 @@ pub fn combine_must_estimations(
     }
 }
 
+/// Estimate cardinality of a field condition, which the index of the field can't estimate by
+/// itself, from the statistics of the indexed values.
+///
+/// Values are assumed to be evenly distributed among distinct values, and to be of the type of the
+/// index, so that points without indexed values don't match the condition.
+/// The estimation has no primary clauses, as the index can't select the matching points.
+pub fn estimate_from_statistics(
+    condition: &FieldCondition,
+    statistics: &FieldIndexStatistics,
+    available_points: usize,
+) -> CardinalityEstimation {
+    if condition.values_count.is_some() {
+        // Points without values match a condition on a small number of values
+        return CardinalityEstimation::unknown(available_points);
+    }
+
+    let points_count = statistics.points_count.min(available_points);
+    // Expected number of points with one of the given number of distinct values
+    let points_with_values = |values: usize| {
+        statistics
+            .distinct_values_count
+            .filter(|&distinct| distinct > 0)
+            .map(|distinct| {
+                (values.saturating_mul(statistics.values_count) / distinct).min(points_count)
+            })
+    };
+    let any_len = |variants: &AnyVariants| match variants {
+        AnyVariants::Keywords(keywords) => keywords.len(),
+        AnyVariants::Integers(integers) => integers.len(),
+    };
+
+    let (exp, max) = match &condition.r#match {
+        Some(Match::Value(_)) => (points_with_values(1), points_count),
+        Some(Match::Any(MatchAny { any })) => {
+            if any_len(any) == 0 {
+                return CardinalityEstimation::exact(0);
+            }
+            (points_with_values(any_len(any)), points_count)
+        }
+        Some(Match::Except(MatchExcept { except })) => {
+            // Values of other types are not indexed, but match the condition
+            let exp = points_with_values(any_len(except)).map(|excluded| points_count - excluded);
+            (exp, available_points)
+        }
+        Some(Match::Text(_)) | None => (None, points_count),
+    };
+
+    CardinalityEstimation {
+        primary_clauses: vec![],
+        min: 0,
+        exp: exp.unwrap_or(points_count / 2),
+        max,
+    }
+}
+
 fn estimate_condition<F>(
     estimator: &F,
     condition: &Condition,
@@ -284,7 +341,7 @@ mod tests {
 
     use super::*;
     use crate::json_path::path;
-    use crate::types::{FieldCondition, HasIdCondition};
+    use crate::types::{HasIdCondition, IntPayloadType};
 
     const TOTAL: usize = 1000;
 
@@ -337,6 +394,7 @@ mod tests {
                 exp: has_id.has_id.len(),
                 max: has_id.has_id.len(),
             },
+            Condition::HasIdRange(_) => panic!("unexpected HasIdRange"),
             Condition::IsEmpty(condition) => CardinalityEstimation {
                 primary_clauses: vec![PrimaryCondition::IsEmpty(condition.to_owned())],
                 min: 0,
@@ -580,4 +638,54 @@ mod tests {
         assert_eq!(new_estimation.exp, 16);
         assert_eq!(new_estimation.max, 50);
     }
+
+    #[test]
+    fn test_estimate_from_statistics() {
+        // 100 points with 200 values, 10 distinct values
+        let statistics = FieldIndexStatistics {
+            points_count: 100,
+            values_count: 200,
+            distinct_values_count: Some(10),
+        };
+        let estimate = |r#match: Match| {
+            let condition = FieldCondition::new_match(path("tag"), r#match);
+            estimate_from_statistics(&condition, &statistics, TOTAL)
+        };
+
+        let estimation = estimate(Match::from(1));
+        assert!(estimation.primary_clauses.is_empty());
+        assert_eq!(
+            (estimation.min, estimation.exp, estimation.max),
+            (0, 20, 100)
+        );
+
+        let estimation = estimate(Match::from(vec![1, 2, 3]));
+        assert_eq!(
+            (estimation.min, estimation.exp, estimation.max),
+            (0, 60, 100)
+        );
+
+        let estimation = estimate(Match::from(Vec::<IntPayloadType>::new()));
+        assert_eq!((estimation.min, estimation.exp, estimation.max), (0, 0, 0));
+
+        let estimation = estimate(Match::new_except(AnyVariants::Integers(
+            [1, 2].into_iter().collect(),
+        )));
+        assert_eq!(
+            (estimation.min, estimation.exp, estimation.max),
+            (0, 60, TOTAL)
+        );
+
+        // Without distinct values, only points without values are excluded
+        let statistics = FieldIndexStatistics {
+            distinct_values_count: None,
+            ..statistics
+        };
+        let condition = FieldCondition::new_match(path("tag"), Match::from(1));
+        let estimation = estimate_from_statistics(&condition, &statistics, TOTAL);
+        assert_eq!(
+            (estimation.min, estimation.exp, estimation.max),
+            (0, 50, 100)
+        );
+    }
 }
@@ -35,8 +35,8 @@ pub fn condition_converter<'a>(
             })
             .unwrap_or_else(|| {
                 Box::new(move |point_id| {
-                    payload_provider.with_payload(point_id, |payload| {
-                        check_field_condition(field_condition, &payload, field_indexes)
+                    payload_provider.with_field_payload(point_id, &field_condition.key, |payload| {
+                        check_field_condition(field_condition, payload, field_indexes)
                     })
                 })
             }),
@@ -48,8 +48,8 @@ pub fn condition_converter<'a>(
                 .and_then(|indexes| indexes.first());
 
             let fallback = Box::new(move |point_id| {
-                payload_provider.with_payload(point_id, |payload| {
-                    check_is_empty_condition(is_empty, &payload)
+                payload_provider.with_field_payload(point_id, &is_empty.is_empty.key, |payload| {
+                    check_is_empty_condition(is_empty, payload)
                 })
             });
 
@@ -60,8 +60,8 @@ pub fn condition_converter<'a>(
         }
 
         Condition::IsNull(is_null) => Box::new(move |point_id| {
-            payload_provider.with_payload(point_id, |payload| {
-                check_is_null_condition(is_null, &payload)
+            payload_provider.with_field_payload(point_id, &is_null.is_null.key, |payload| {
+                check_is_null_condition(is_null, payload)
             })
         }),
         // ToDo: It might be possible to make this condition faster by using `VisitedPool` instead of HashSet
@@ -73,6 +73,13 @@ pub fn condition_converter<'a>(
                 .collect();
             Box::new(move |point_id| segment_ids.contains(&point_id))
         }
+        Condition::HasIdRange(has_id_range) => {
+            let segment_ids: HashSet<_> = id_tracker
+                .iter_range(has_id_range.has_id_range)
+                .map(|(_, internal_id)| internal_id)
+                .collect();
+            Box::new(move |point_id| segment_ids.contains(&point_id))
+        }
         Condition::Nested(nested) => {
             // Select indexes for nested fields. Trim nested part from key, so
             // that nested condition can address fields without nested part.
@@ -4,12 +4,15 @@ use std::sync::Arc;
 use atomic_refcell::AtomicRefCell;
 use common::types::PointOffsetType;
 
+use crate::json_path::JsonPath;
+use crate::payload_storage::payload_column::{ColumnPayload, PayloadColumns};
 use crate::payload_storage::payload_storage_enum::PayloadStorageEnum;
-use crate::types::{OwnedPayloadRef, Payload};
+use crate::types::{OwnedPayloadRef, Payload, PayloadContainer};
 
 #[derive(Clone)]
 pub struct PayloadProvider {
     payload_storage: Arc<AtomicRefCell<PayloadStorageEnum>>,
+    columns: PayloadColumns,
     empty_payload: Payload,
 }
 
@@ -17,10 +20,41 @@ impl PayloadProvider {
     pub fn new(payload_storage: Arc<AtomicRefCell<PayloadStorageEnum>>) -> Self {
         Self {
             payload_storage,
+            columns: Default::default(),
             empty_payload: Default::default(),
         }
     }
 
+    /// Read values of the fields, which are stored column-wise, from their columns
+    pub fn with_columns(mut self, columns: PayloadColumns) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    /// Provides payload, which is only guaranteed to contain the given field.
+    ///
+    /// If the field is stored column-wise, only its values are read instead of the whole payload.
+    pub fn with_field_payload<F, G>(
+        &self,
+        point_id: PointOffsetType,
+        field: &JsonPath,
+        callback: F,
+    ) -> G
+    where
+        F: FnOnce(&dyn PayloadContainer) -> G,
+    {
+        match self.columns.get(field) {
+            Some(column) => {
+                // Same as for the payload storage, failed read means that the storage is corrupted
+                let values = column
+                    .get(point_id)
+                    .unwrap_or_else(|err| panic!("Payload column is corrupted: {err}"));
+                callback(&ColumnPayload::new(field, values))
+            }
+            None => self.with_payload(point_id, |payload| callback(&payload)),
+        }
+    }
+
     pub fn with_payload<F, G>(&self, point_id: PointOffsetType, callback: F) -> G
     where
         F: FnOnce(OwnedPayloadRef) -> G,
@@ -51,6 +85,14 @@ impl PayloadProvider {
                 .read_payload(point_id)
                 .unwrap_or_else(|err| panic!("Payload storage is corrupted: {err}"))
                 .map(|x| x.into()),
+            PayloadStorageEnum::AppendOnlyPayloadStorage(s) => s
+                .read_payload(point_id)
+                .unwrap_or_else(|err| panic!("Payload storage is corrupted: {err}"))
+                .map(|x| x.into()),
+            PayloadStorageEnum::MmapPayloadStorage(s) => s
+                .read_payload(point_id)
+                .unwrap_or_else(|err| panic!("Payload storage is corrupted: {err}"))
+                .map(|x| x.into()),
         };
 
         let payload = if let Some(payload_ptr) = payload_ptr_opt {
@@ -10,7 +10,7 @@ use common::types::{PointOffsetType, ScoredPointOffset, TelemetryDetail};
 use itertools::Itertools;
 use sparse::common::scores_memory_pool::ScoresMemoryPool;
 use sparse::common::sparse_vector::SparseVector;
-use sparse::common::types::DimId;
+use sparse::common::types::{DimId, DimOffset};
 use sparse::index::inverted_index::inverted_index_ram::InvertedIndexRam;
 use sparse::index::inverted_index::inverted_index_ram_builder::InvertedIndexBuilder;
 use sparse::index::inverted_index::InvertedIndex;
@@ -31,10 +31,11 @@ use crate::index::sparse_index::sparse_search_telemetry::SparseSearchesTelemetry
 use crate::index::struct_payload_index::StructPayloadIndex;
 use crate::index::{PayloadIndex, VectorIndex};
 use crate::telemetry::VectorIndexSearchesTelemetry;
-use crate::types::{Filter, SearchParams, DEFAULT_SPARSE_FULL_SCAN_THRESHOLD};
+use crate::types::{Filter, SearchParams, SparseVectorStats, DEFAULT_SPARSE_FULL_SCAN_THRESHOLD};
 use crate::vector_storage::query::TransformInto;
 use crate::vector_storage::{
-    check_deleted_condition, new_stoppable_raw_scorer, VectorStorage, VectorStorageEnum,
+    check_deleted_condition, new_metered_raw_scorer, new_stoppable_raw_scorer, VectorStorage,
+    VectorStorageEnum,
 };
 
 pub struct SparseVectorIndex<TInvertedIndex: InvertedIndex> {
@@ -237,6 +238,11 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
             deleted_point_bitslice,
             &is_stopped,
         )?;
+        let raw_scorer = new_metered_raw_scorer(
+            raw_scorer,
+            vector_query_context.usage(),
+            vector_storage.available_vector_count(),
+        );
         match filter {
             Some(filter) => {
                 let payload_index = self.payload_index.borrow();
@@ -284,6 +290,9 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
         .copied()
         .filter(|&idx| check_deleted_condition(idx, deleted_vectors, deleted_point_bitslice))
         .collect_vec();
+        if let Some(usage) = vector_query_context.usage() {
+            usage.add_vectors_scored(ids.len());
+        }
 
         let sparse_vector = self.indices_tracker.remap_vector(sparse_vector.to_owned());
         let memory_handle = self.scores_memory_pool.get();
@@ -298,6 +307,7 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
     }
 
     // search using sparse vector inverted index
+    // scores are accumulated from posting lists, so no vectors are counted as scored in usage
     fn search_sparse(
         &self,
         sparse_vector: &SparseVector,
@@ -366,6 +376,7 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
                 if query_cardinality.max < threshold {
                     let _timer =
                         ScopeDurationMeasurer::new(&self.searches_telemetry.small_cardinality);
+                    vector_query_context.record_search_strategy("small_cardinality");
                     self.search_plain(
                         &vector,
                         filter,
@@ -376,11 +387,13 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
                 } else {
                     let _timer =
                         ScopeDurationMeasurer::new(&self.searches_telemetry.filtered_sparse);
+                    vector_query_context.record_search_strategy("filtered_sparse");
                     self.search_sparse(&vector, Some(filter), top, vector_query_context)
                 }
             }
             None => {
                 let _timer = ScopeDurationMeasurer::new(&self.searches_telemetry.unfiltered_sparse);
+                vector_query_context.record_search_strategy("unfiltered_sparse");
                 self.search_sparse(&vector, filter, top, vector_query_context)
             }
         }
@@ -408,8 +421,10 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
             ),
             QueryVector::Recommend(_) | QueryVector::Discovery(_) | QueryVector::Context(_) => {
                 let _timer = if filter.is_some() {
+                    vector_query_context.record_search_strategy("filtered_plain");
                     ScopeDurationMeasurer::new(&self.searches_telemetry.filtered_plain)
                 } else {
+                    vector_query_context.record_search_strategy("unfiltered_plain");
                     ScopeDurationMeasurer::new(&self.searches_telemetry.unfiltered_plain)
                 };
                 self.search_scored(
@@ -423,6 +438,20 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
         }
     }
 
+    /// Statistics of the inverted index, which are used for idf-dot similarity
+    ///
+    /// Posting lists keep deleted vectors until the index is rebuilt, so deleted vectors are
+    /// counted in both of the statistics. Iterates over all dimensions, callers should cache it.
+    pub fn statistics(&self) -> SparseVectorStats {
+        let values_count = (0..self.inverted_index.len() as DimOffset)
+            .filter_map(|dim_offset| self.inverted_index.posting_list_len(&dim_offset))
+            .sum();
+        SparseVectorStats {
+            vectors_count: self.vector_storage.borrow().total_vector_count(),
+            values_count,
+        }
+    }
+
     // Update statistics for idf-dot similarity
     pub fn fill_idf_statistics(&self, idf: &mut HashMap<DimId, usize>) {
         for (dim_id, count) in idf.iter_mut() {
@@ -454,7 +483,7 @@ impl<TInvertedIndex: InvertedIndex> VectorIndex for SparseVectorIndex<TInvertedI
             let search_results = if query_context.is_require_idf() {
                 let vector = (*vector).clone().transform(|mut vector| {
                     match &mut vector {
-                        Vector::Dense(_) | Vector::MultiDense(_) => {
+                        Vector::Dense(_) | Vector::DenseDouble(_) | Vector::MultiDense(_) => {
                             return Err(OperationError::WrongSparse);
                         }
                         Vector::Sparse(sparse) => {
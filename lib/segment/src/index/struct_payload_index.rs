@@ -1,3 +1,4 @@
+use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use std::fs::create_dir_all;
 use std::ops::Deref;
@@ -15,28 +16,34 @@ use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::rocksdb_wrapper::open_db_with_existing_cf;
 use crate::common::utils::IndexesMap;
 use crate::common::Flusher;
+use crate::data_types::payload_projection::PayloadProjection;
 use crate::id_tracker::IdTrackerSS;
 use crate::index::field_index::index_selector::index_selector;
+use crate::index::field_index::numeric_index::NumericIndex;
 use crate::index::field_index::{
     CardinalityEstimation, FieldIndex, PayloadBlockCondition, PrimaryCondition,
 };
+use crate::index::filter_cache::{CachedFilterContext, FilterCache};
+use crate::index::filter_plan_cache::{FilterPlan, FilterPlanCache};
 use crate::index::payload_config::PayloadConfig;
-use crate::index::query_estimator::estimate_filter;
+use crate::index::query_estimator::{estimate_filter, estimate_from_statistics};
 use crate::index::query_optimization::payload_provider::PayloadProvider;
 use crate::index::struct_filter_context::StructFilterContext;
 use crate::index::visited_pool::VisitedPool;
 use crate::index::PayloadIndex;
 use crate::json_path::{JsonPath, JsonPathInterface as _};
+use crate::payload_storage::payload_column::PayloadColumns;
 use crate::payload_storage::payload_storage_enum::PayloadStorageEnum;
 use crate::payload_storage::{FilterContext, PayloadStorage};
 use crate::telemetry::PayloadIndexTelemetry;
 use crate::types::{
-    infer_collection_value_type, infer_value_type, Condition, FieldCondition, Filter,
-    IsEmptyCondition, IsNullCondition, Payload, PayloadContainer, PayloadField, PayloadFieldSchema,
-    PayloadKeyType, PayloadKeyTypeRef, PayloadSchemaType,
+    infer_collection_value_type, infer_value_type, Condition, DateTimeTimestamp, FieldCondition,
+    Filter, IsEmptyCondition, IsNullCondition, Payload, PayloadContainer, PayloadField,
+    PayloadFieldSchema, PayloadKeyType, PayloadKeyTypeRef, PayloadSchemaType,
 };
 
 pub const PAYLOAD_FIELD_INDEX_PATH: &str = "fields";
+pub const PAYLOAD_COLUMNS_PATH: &str = "columns";
 
 /// `PayloadIndex` implementation, which actually uses index structures for providing faster search
 pub struct StructPayloadIndex {
@@ -46,11 +53,17 @@ pub struct StructPayloadIndex {
     id_tracker: Arc<AtomicRefCell<IdTrackerSS>>,
     /// Indexes, associated with fields
     pub field_indexes: IndexesMap,
+    /// Values of the fields, which are additionally stored column-wise
+    columns: PayloadColumns,
     config: PayloadConfig,
     /// Root of index persistence dir
     path: PathBuf,
     /// Used to select unique point ids
     visited_pool: VisitedPool,
+    /// Points matching recently queried filters, cleared on every change of payload
+    filter_cache: FilterCache,
+    /// Estimations of recently planned filters, cleared on every change of payload indexes
+    filter_plan_cache: FilterPlanCache,
     db: Arc<RwLock<DB>>,
 }
 
@@ -71,6 +84,18 @@ impl StructPayloadIndex {
             indexes
                 .iter()
                 .find_map(|index| index.estimate_cardinality(&full_path_condition).ok())
+                .or_else(|| {
+                    // None of the indexes supports the condition, use statistics of the values
+                    let statistics = indexes
+                        .iter()
+                        .map(FieldIndex::statistics)
+                        .max_by_key(|statistics| statistics.points_count)?;
+                    Some(estimate_from_statistics(
+                        &full_path_condition,
+                        &statistics,
+                        self.available_point_count(),
+                    ))
+                })
         })
     }
 
@@ -154,9 +179,12 @@ impl StructPayloadIndex {
             payload,
             id_tracker,
             field_indexes: Default::default(),
+            columns: Default::default(),
             config,
             path: path.to_owned(),
             visited_pool: Default::default(),
+            filter_cache: Default::default(),
+            filter_plan_cache: Default::default(),
             db,
         };
 
@@ -170,24 +198,87 @@ impl StructPayloadIndex {
         Ok(index)
     }
 
+    /// Open columns of the given payload fields, missing columns are filled from the payload storage.
+    ///
+    /// Columns of the fields, which are not listed, are removed.
+    pub fn open_columns(&mut self, fields: &[PayloadKeyType]) -> OperationResult<()> {
+        let (columns, new_fields) =
+            PayloadColumns::open(&self.path.join(PAYLOAD_COLUMNS_PATH), fields)?;
+
+        if !new_fields.is_empty() {
+            debug!("Payload columns {new_fields:?} were not found. Building...");
+            let new_columns: Vec<_> = new_fields
+                .iter()
+                .filter_map(|field| Some((field, columns.get(field)?)))
+                .collect();
+            self.payload.borrow().iter(|point_id, point_payload| {
+                for (field, column) in &new_columns {
+                    column.set(point_id, &point_payload.get_value(field));
+                }
+                Ok(true)
+            })?;
+            // Persist built columns right away, so they are not considered built if not flushed
+            for (_, column) in new_columns {
+                column.flusher()()?;
+            }
+        }
+
+        self.columns = columns;
+        Ok(())
+    }
+
+    /// Iterate over values of the field in all points, which have it.
+    ///
+    /// Values are read from the column if the field is stored column-wise,
+    /// otherwise each payload is read from the payload storage.
+    pub fn iter_field_values<F>(
+        &self,
+        field: PayloadKeyTypeRef,
+        mut callback: F,
+    ) -> OperationResult<()>
+    where
+        F: FnMut(PointOffsetType, &[&Value]) -> OperationResult<bool>,
+    {
+        match self.columns.get(field) {
+            Some(column) => column.iter(|point_id, values| {
+                let values: Vec<_> = values.iter().collect();
+                callback(point_id, &values)
+            }),
+            None => self.payload.borrow().iter(|point_id, point_payload| {
+                let values = point_payload.get_value(field);
+                if values.is_empty() {
+                    return Ok(true);
+                }
+                callback(point_id, &values)
+            }),
+        }
+    }
+
     pub fn build_field_indexes(
         &self,
         field: PayloadKeyTypeRef,
         payload_schema: PayloadFieldSchema,
     ) -> OperationResult<Vec<FieldIndex>> {
-        let payload_storage = self.payload.borrow();
         let mut field_indexes = index_selector(field, &payload_schema, self.db.clone(), true);
         for index in &field_indexes {
             index.recreate()?;
         }
 
-        payload_storage.iter(|point_id, point_payload| {
-            let field_value = &point_payload.get_value(field);
+        self.iter_field_values(field, |point_id, field_value| {
             for field_index in field_indexes.iter_mut() {
                 field_index.add_point(point_id, field_value)?;
             }
             Ok(true)
         })?;
+
+        // Older versions stored the datetime index in the column family of the numeric index,
+        // it is orphaned once the index is rebuilt
+        if payload_schema == PayloadFieldSchema::FieldType(PayloadSchemaType::Datetime) {
+            NumericIndex::<DateTimeTimestamp>::remove_legacy_datetime_storage(
+                self.db.clone(),
+                &field.to_string(),
+            )?;
+        }
         Ok(field_indexes)
     }
 
@@ -208,10 +299,88 @@ impl StructPayloadIndex {
         self.id_tracker.borrow().available_point_count()
     }
 
+    fn query_points_uncached(&self, query: &Filter) -> Vec<PointOffsetType> {
+        // Assume query is already estimated to be small enough so we can iterate over all matched ids
+
+        let query_cardinality = self.estimate_cardinality(query);
+
+        if query_cardinality.primary_clauses.is_empty() {
+            let id_tracker = self.id_tracker.borrow();
+            let full_scan_iterator = id_tracker.iter_ids();
+
+            let struct_filtered_context = self.struct_filtered_context(query);
+            // Worst case: query expected to return few matches, but index can't be used
+            let matched_points =
+                full_scan_iterator.filter(move |i| struct_filtered_context.check(*i));
+
+            matched_points.collect()
+        } else {
+            let points_iterator_ref = self.id_tracker.borrow();
+            let struct_filtered_context = self.struct_filtered_context(query);
+
+            // CPU-optimized strategy here: points are made unique before applying other filters.
+            // TODO: Implement iterator which holds the `visited_pool` and borrowed `vector_storage_ref` to prevent `preselected` array creation
+            let mut visited_list = self
+                .visited_pool
+                .get(points_iterator_ref.total_point_count());
+
+            let preselected: Vec<PointOffsetType> = query_cardinality
+                .primary_clauses
+                .iter()
+                .flat_map(|clause| {
+                    match clause {
+                        PrimaryCondition::Condition(field_condition) => {
+                            self.query_field(field_condition).unwrap_or_else(
+                                || points_iterator_ref.iter_ids(), /* index is not built */
+                            )
+                        }
+                        PrimaryCondition::Ids(ids) => Box::new(ids.iter().copied()),
+                        PrimaryCondition::IsEmpty(_) => points_iterator_ref.iter_ids(), /* there are no fast index for IsEmpty */
+                        PrimaryCondition::IsNull(_) => points_iterator_ref.iter_ids(),  /* no fast index for IsNull too */
+                    }
+                })
+                .filter(|&id| !visited_list.check_and_update_visited(id))
+                .filter(move |&i| struct_filtered_context.check(i))
+                .collect();
+            preselected
+        }
+    }
+
+    /// Estimations of the filter and its conditions, planned once until the segment is changed
+    fn filter_plan(&self, filter: &Filter) -> Arc<FilterPlan> {
+        let point_counts = {
+            let id_tracker = self.id_tracker.borrow();
+            (
+                id_tracker.total_point_count(),
+                id_tracker.available_point_count(),
+            )
+        };
+        if let Some(plan) = self.filter_plan_cache.get(filter, point_counts) {
+            return plan;
+        }
+
+        let condition_estimations = RefCell::new(Vec::new());
+        let estimator = |condition: &Condition| {
+            let estimation = self.condition_cardinality(condition, None);
+            condition_estimations
+                .borrow_mut()
+                .push((condition.clone(), estimation.clone()));
+            estimation
+        };
+        let estimation = estimate_filter(&estimator, filter, point_counts.1);
+        let plan = FilterPlan::new(estimation, condition_estimations.into_inner());
+        self.filter_plan_cache.insert(filter, plan, point_counts)
+    }
+
     fn struct_filtered_context<'a>(&'a self, filter: &'a Filter) -> StructFilterContext<'a> {
-        let estimator = |condition: &Condition| self.condition_cardinality(condition, None);
+        let plan = self.filter_plan(filter);
+        let estimator = |condition: &Condition| {
+            plan.condition_estimation(condition)
+                .unwrap_or_else(|| self.condition_cardinality(condition, None))
+        };
         let id_tracker = self.id_tracker.borrow();
-        let payload_provider = PayloadProvider::new(self.payload.clone());
+        let payload_provider =
+            PayloadProvider::new(self.payload.clone()).with_columns(self.columns.clone());
         StructFilterContext::new(
             filter,
             id_tracker.deref(),
@@ -305,6 +474,21 @@ impl StructPayloadIndex {
                     max: num_ids,
                 }
             }
+            Condition::HasIdRange(has_id_range) => {
+                // Resolved with ordered iteration over the ID tracker, without enumerating all points
+                let id_tracker_ref = self.id_tracker.borrow();
+                let mapped_ids: HashSet<PointOffsetType> = id_tracker_ref
+                    .iter_range(has_id_range.has_id_range)
+                    .map(|(_, internal_id)| internal_id)
+                    .collect();
+                let num_ids = mapped_ids.len();
+                CardinalityEstimation {
+                    primary_clauses: vec![PrimaryCondition::Ids(mapped_ids)],
+                    min: num_ids,
+                    exp: num_ids,
+                    max: num_ids,
+                }
+            }
             Condition::Field(field_condition) => self
                 .estimate_field_condition(field_condition, nested_path)
                 .unwrap_or_else(|| CardinalityEstimation::unknown(self.available_point_count())),
@@ -341,6 +525,9 @@ impl PayloadIndex for StructPayloadIndex {
         field: PayloadKeyTypeRef,
         payload_schema: PayloadFieldSchema,
     ) -> OperationResult<()> {
+        // Values of the field may be matched differently with the new index
+        self.filter_cache.clear();
+        self.filter_plan_cache.clear();
         if let Some(prev_schema) = self
             .config
             .indexed_fields
@@ -359,6 +546,8 @@ impl PayloadIndex for StructPayloadIndex {
     }
 
     fn drop_index(&mut self, field: PayloadKeyTypeRef) -> OperationResult<()> {
+        self.filter_cache.clear();
+        self.filter_plan_cache.clear();
         self.config.indexed_fields.remove(field);
         let removed_indexes = self.field_indexes.remove(field);
 
@@ -373,9 +562,7 @@ impl PayloadIndex for StructPayloadIndex {
     }
 
     fn estimate_cardinality(&self, query: &Filter) -> CardinalityEstimation {
-        let available_points = self.available_point_count();
-        let estimator = |condition: &Condition| self.condition_cardinality(condition, None);
-        estimate_filter(&estimator, query, available_points)
+        self.filter_plan(query).estimation.clone()
     }
 
     fn estimate_nested_cardinality(
@@ -390,50 +577,14 @@ impl PayloadIndex for StructPayloadIndex {
     }
 
     fn query_points(&self, query: &Filter) -> Vec<PointOffsetType> {
-        // Assume query is already estimated to be small enough so we can iterate over all matched ids
-
-        let query_cardinality = self.estimate_cardinality(query);
-
-        if query_cardinality.primary_clauses.is_empty() {
-            let id_tracker = self.id_tracker.borrow();
-            let full_scan_iterator = id_tracker.iter_ids();
-
-            let struct_filtered_context = self.struct_filtered_context(query);
-            // Worst case: query expected to return few matches, but index can't be used
-            let matched_points =
-                full_scan_iterator.filter(move |i| struct_filtered_context.check(*i));
-
-            matched_points.collect()
-        } else {
-            let points_iterator_ref = self.id_tracker.borrow();
-            let struct_filtered_context = self.struct_filtered_context(query);
-
-            // CPU-optimized strategy here: points are made unique before applying other filters.
-            // TODO: Implement iterator which holds the `visited_pool` and borrowed `vector_storage_ref` to prevent `preselected` array creation
-            let mut visited_list = self
-                .visited_pool
-                .get(points_iterator_ref.total_point_count());
-
-            let preselected: Vec<PointOffsetType> = query_cardinality
-                .primary_clauses
-                .iter()
-                .flat_map(|clause| {
-                    match clause {
-                        PrimaryCondition::Condition(field_condition) => {
-                            self.query_field(field_condition).unwrap_or_else(
-                                || points_iterator_ref.iter_ids(), /* index is not built */
-                            )
-                        }
-                        PrimaryCondition::Ids(ids) => Box::new(ids.iter().copied()),
-                        PrimaryCondition::IsEmpty(_) => points_iterator_ref.iter_ids(), /* there are no fast index for IsEmpty */
-                        PrimaryCondition::IsNull(_) => points_iterator_ref.iter_ids(),  /* no fast index for IsNull too */
-                    }
-                })
-                .filter(|&id| !visited_list.check_and_update_visited(id))
-                .filter(move |&i| struct_filtered_context.check(i))
-                .collect();
-            preselected
+        let total_point_count = self.id_tracker.borrow().total_point_count();
+        if let Some(points) = self.filter_cache.get(query, total_point_count) {
+            return points.to_vec();
         }
+
+        let points = self.query_points_uncached(query);
+        self.filter_cache.insert(query, &points, total_point_count);
+        points
     }
 
     fn indexed_points(&self, field: PayloadKeyTypeRef) -> usize {
@@ -450,7 +601,11 @@ impl PayloadIndex for StructPayloadIndex {
     }
 
     fn filter_context<'a>(&'a self, filter: &'a Filter) -> Box<dyn FilterContext + 'a> {
-        Box::new(self.struct_filtered_context(filter))
+        let total_point_count = self.id_tracker.borrow().total_point_count();
+        match self.filter_cache.get(filter, total_point_count) {
+            Some(points) => Box::new(CachedFilterContext::new(points)),
+            None => Box::new(self.struct_filtered_context(filter)),
+        }
     }
 
     fn payload_blocks(
@@ -475,6 +630,8 @@ impl PayloadIndex for StructPayloadIndex {
         payload: &Payload,
         key: &Option<JsonPath>,
     ) -> OperationResult<()> {
+        self.filter_cache.clear();
+        self.filter_plan_cache.clear();
         if let Some(key) = key {
             self.payload
                 .borrow_mut()
@@ -484,6 +641,11 @@ impl PayloadIndex for StructPayloadIndex {
         };
 
         let updated_payload = self.payload(point_id)?;
+        for (field, column) in self.columns.iter() {
+            if field.is_affected_by_value_set(&payload.0, key.as_ref()) {
+                column.set(point_id, &updated_payload.get_value(field));
+            }
+        }
         for (field, field_index) in &mut self.field_indexes {
             if !field.is_affected_by_value_set(&payload.0, key.as_ref()) {
                 continue;
@@ -506,25 +668,57 @@ impl PayloadIndex for StructPayloadIndex {
         self.payload.borrow().payload(point_id)
     }
 
+    fn payloads(&self, point_ids: &[PointOffsetType]) -> OperationResult<Vec<Payload>> {
+        self.payload.borrow().payloads(point_ids)
+    }
+
+    fn payload_projected(
+        &self,
+        point_id: PointOffsetType,
+        projection: PayloadProjection,
+    ) -> OperationResult<Payload> {
+        self.payload
+            .borrow()
+            .payload_projected(point_id, projection)
+    }
+
     fn delete(
         &mut self,
         point_id: PointOffsetType,
         key: PayloadKeyTypeRef,
     ) -> OperationResult<Vec<Value>> {
+        self.filter_cache.clear();
+        self.filter_plan_cache.clear();
         if let Some(indexes) = self.field_indexes.get_mut(key) {
             for index in indexes {
                 index.remove_point(point_id)?;
             }
         }
-        self.payload.borrow_mut().delete(point_id, key)
+        let removed = self.payload.borrow_mut().delete(point_id, key)?;
+
+        let mut affected_columns = self
+            .columns
+            .iter()
+            .filter(|(field, _)| field.is_affected_by_value_remove(key))
+            .peekable();
+        if !removed.is_empty() && affected_columns.peek().is_some() {
+            let updated_payload = self.payload(point_id)?;
+            for (field, column) in affected_columns {
+                column.set(point_id, &updated_payload.get_value(field));
+            }
+        }
+        Ok(removed)
     }
 
     fn drop(&mut self, point_id: PointOffsetType) -> OperationResult<Option<Payload>> {
+        self.filter_cache.clear();
+        self.filter_plan_cache.clear();
         for (_, field_indexes) in self.field_indexes.iter_mut() {
             for index in field_indexes {
                 index.remove_point(point_id)?;
             }
         }
+        self.columns.remove(point_id);
         self.payload.borrow_mut().drop(point_id)
     }
 
@@ -535,6 +729,7 @@ impl PayloadIndex for StructPayloadIndex {
                 flushers.push(index.flusher());
             }
         }
+        flushers.extend(self.columns.flushers());
         flushers.push(self.payload.borrow().flusher());
         Box::new(move || {
             for flusher in flushers {
@@ -566,6 +761,9 @@ impl PayloadIndex for StructPayloadIndex {
     }
 
     fn files(&self) -> Vec<PathBuf> {
-        vec![self.config_path()]
+        let mut files = vec![self.config_path()];
+        files.extend(self.columns.files());
+        files.extend(self.payload.borrow().files());
+        files
     }
 }
@@ -74,6 +74,18 @@ impl VectorIndexEnum {
             Self::SparseMmap(_) => true,
         }
     }
+
+    /// Whether the index is read from disk, rather than loaded into RAM
+    pub fn is_on_disk(&self) -> bool {
+        match self {
+            Self::Plain(_) => false,
+            Self::HnswRam(_) => false,
+            Self::HnswMmap(_) => true,
+            Self::SparseRam(_) => false,
+            Self::SparseImmutableRam(_) => false,
+            Self::SparseMmap(_) => true,
+        }
+    }
 }
 
 impl VectorIndex for VectorIndexEnum {
@@ -0,0 +1,66 @@
+//! Statistics of vector indexes and quantized vectors of a segment, to diagnose search quality.
+
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use crate::types::QuantizationConfig;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum VectorIndexType {
+    /// Vectors are searched by full scan
+    Plain,
+    Hnsw,
+    /// Inverted index of sparse vectors
+    Sparse,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct VectorIndexInfo {
+    /// Name of the vector, empty for the default one
+    pub vector_name: String,
+    pub index_type: VectorIndexType,
+    /// Number of vectors in the index
+    pub indexed_vectors_count: usize,
+    /// Statistics of the HNSW graph, if it's built
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hnsw: Option<HnswGraphInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quantization: Option<QuantizationInfo>,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct HnswGraphInfo {
+    /// Max number of links of a point on upper layers
+    pub m: usize,
+    /// Max number of links of a point on the bottom layer
+    pub m0: usize,
+    pub ef_construct: usize,
+    /// Layers of the graph, starting from the bottom one, which contains all points
+    pub layers: Vec<HnswLayerInfo>,
+    /// Points, which are neither deleted nor reachable by following links from the entry point.
+    ///
+    /// Searches without filters can't find them, and don't pass through deleted points.
+    pub disconnected_points: usize,
+    /// Time the graph was last saved, after it was built
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub built_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct HnswLayerInfo {
+    /// Number of points on the layer, including deleted ones
+    pub points_count: usize,
+    /// Average number of links of a point on the layer
+    pub avg_out_degree: f64,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct QuantizationInfo {
+    pub config: QuantizationConfig,
+    /// Quantized vectors are read from disk with mmap
+    pub on_disk: bool,
+    /// Size of files of quantized vectors
+    pub size_bytes: u64,
+}
@@ -0,0 +1,224 @@
+//! Payload modification with JSON Patch documents, as described in RFC 6902
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::types::Payload;
+
+/// Single operation of a JSON Patch document (RFC 6902).
+///
+/// Paths are JSON Pointers (RFC 6901), e.g. `/a/b/0`. In `add` operations the `-` index
+/// refers to the end of an array.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum PatchOperation {
+    /// Add a value to an object or insert it into an array
+    Add { path: String, value: Value },
+    /// Remove a value from an object or an array
+    Remove { path: String },
+    /// Replace an existing value
+    Replace { path: String, value: Value },
+    /// Remove a value and add it at another location
+    Move { from: String, path: String },
+    /// Copy a value to another location
+    Copy { from: String, path: String },
+    /// Check that the value at the location is equal to the given one
+    Test { path: String, value: Value },
+}
+
+/// Apply all operations of the patch to the payload, in order.
+///
+/// Patch is applied atomically: if any operation fails, the payload is left untouched.
+pub fn apply_patch(payload: &mut Payload, patch: &[PatchOperation]) -> OperationResult<()> {
+    let mut document = Value::Object(payload.0.clone());
+    for operation in patch {
+        apply_operation(&mut document, operation)?;
+    }
+    match document {
+        Value::Object(map) => {
+            payload.0 = map;
+            Ok(())
+        }
+        _ => Err(patch_error("payload must remain a JSON object")),
+    }
+}
+
+fn apply_operation(document: &mut Value, operation: &PatchOperation) -> OperationResult<()> {
+    match operation {
+        PatchOperation::Add { path, value } => add(document, path, value.clone()),
+        PatchOperation::Remove { path } => remove(document, path).map(|_| ()),
+        PatchOperation::Replace { path, value } => {
+            let target = document
+                .pointer_mut(path)
+                .ok_or_else(|| patch_error(format!("path `{path}` does not exist")))?;
+            *target = value.clone();
+            Ok(())
+        }
+        PatchOperation::Move { from, path } => {
+            if path.starts_with(&format!("{from}/")) {
+                return Err(patch_error(format!(
+                    "can't move `{from}` into its own child `{path}`"
+                )));
+            }
+            let value = remove(document, from)?;
+            add(document, path, value)
+        }
+        PatchOperation::Copy { from, path } => {
+            let value = document
+                .pointer(from)
+                .cloned()
+                .ok_or_else(|| patch_error(format!("path `{from}` does not exist")))?;
+            add(document, path, value)
+        }
+        PatchOperation::Test { path, value } => match document.pointer(path) {
+            Some(current) if current == value => Ok(()),
+            Some(_) => Err(patch_error(format!(
+                "test failed, value at `{path}` is different"
+            ))),
+            None => Err(patch_error(format!("path `{path}` does not exist"))),
+        },
+    }
+}
+
+fn add(document: &mut Value, path: &str, value: Value) -> OperationResult<()> {
+    if path.is_empty() {
+        *document = value;
+        return Ok(());
+    }
+    let (parent, token) = split_pointer(path)?;
+    match document.pointer_mut(parent) {
+        Some(Value::Object(map)) => {
+            map.insert(token, value);
+            Ok(())
+        }
+        Some(Value::Array(array)) => {
+            let index = if token == "-" {
+                array.len()
+            } else {
+                parse_index(&token, array.len() + 1)?
+            };
+            array.insert(index, value);
+            Ok(())
+        }
+        Some(_) => Err(patch_error(format!(
+            "path `{parent}` is neither an object nor an array"
+        ))),
+        None => Err(patch_error(format!("path `{parent}` does not exist"))),
+    }
+}
+
+fn remove(document: &mut Value, path: &str) -> OperationResult<Value> {
+    if path.is_empty() {
+        return Err(patch_error("can't remove the whole payload"));
+    }
+    let (parent, token) = split_pointer(path)?;
+    let removed = match document.pointer_mut(parent) {
+        Some(Value::Object(map)) => map.remove(&token),
+        Some(Value::Array(array)) => {
+            let index = parse_index(&token, array.len())?;
+            Some(array.remove(index))
+        }
+        _ => None,
+    };
+    removed.ok_or_else(|| patch_error(format!("path `{path}` does not exist")))
+}
+
+/// Split non-empty JSON Pointer into the pointer to the parent and the unescaped last token
+fn split_pointer(path: &str) -> OperationResult<(&str, String)> {
+    let Some(separator) = path.rfind('/').filter(|_| path.starts_with('/')) else {
+        return Err(patch_error(format!(
+            "path `{path}` is not a valid JSON Pointer"
+        )));
+    };
+    let token = path[separator + 1..].replace("~1", "/").replace("~0", "~");
+    Ok((&path[..separator], token))
+}
+
+/// Parse array index, which must be below `bound` and have no leading zeros
+fn parse_index(token: &str, bound: usize) -> OperationResult<usize> {
+    let is_canonical = !token.starts_with('+') && (token == "0" || !token.starts_with('0'));
+    token
+        .parse::<usize>()
+        .ok()
+        .filter(|index| is_canonical && *index < bound)
+        .ok_or_else(|| patch_error(format!("invalid array index `{token}`")))
+}
+
+fn patch_error(description: impl Into<String>) -> OperationError {
+    OperationError::ValidationError {
+        description: format!("Failed to apply JSON Patch: {}", description.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn patched(payload: Value, patch: Value) -> OperationResult<Value> {
+        let mut payload: Payload = serde_json::from_value(payload).unwrap();
+        let patch: Vec<PatchOperation> = serde_json::from_value(patch).unwrap();
+        apply_patch(&mut payload, &patch)?;
+        Ok(serde_json::to_value(payload).unwrap())
+    }
+
+    #[test]
+    fn test_nested_operations() {
+        let payload = json!({"a": {"b": [1, 2, 3]}, "c": "x"});
+        let patch = json!([
+            {"op": "add", "path": "/a/b/1", "value": 10},
+            {"op": "add", "path": "/a/b/-", "value": 20},
+            {"op": "remove", "path": "/a/b/0"},
+            {"op": "replace", "path": "/c", "value": {"d": true}},
+            {"op": "copy", "from": "/c/d", "path": "/a/e"},
+            {"op": "move", "from": "/a/b", "path": "/f~1g"},
+            {"op": "test", "path": "/a/e", "value": true},
+        ]);
+        assert_eq!(
+            patched(payload, patch).unwrap(),
+            json!({"a": {"e": true}, "c": {"d": true}, "f/g": [10, 2, 3, 20]}),
+        );
+    }
+
+    #[test]
+    fn test_failed_patch() {
+        let payload = json!({"a": [1]});
+
+        let missing = json!([{"op": "replace", "path": "/b", "value": 1}]);
+        assert!(patched(payload.clone(), missing).is_err());
+
+        let out_of_bounds = json!([{"op": "add", "path": "/a/2", "value": 1}]);
+        assert!(patched(payload.clone(), out_of_bounds).is_err());
+
+        let leading_zero = json!([{"op": "remove", "path": "/a/00"}]);
+        assert!(patched(payload.clone(), leading_zero).is_err());
+
+        let failed_test = json!([{"op": "test", "path": "/a/0", "value": 2}]);
+        assert!(patched(payload.clone(), failed_test).is_err());
+
+        let into_child = json!([{"op": "move", "from": "/a", "path": "/a/0"}]);
+        assert!(patched(payload.clone(), into_child).is_err());
+
+        let not_object = json!([{"op": "replace", "path": "", "value": [1]}]);
+        assert!(patched(payload, not_object).is_err());
+    }
+
+    #[test]
+    fn test_patch_is_atomic() {
+        let mut payload: Payload = serde_json::from_value(json!({"a": 1})).unwrap();
+        let patch = vec![
+            PatchOperation::Replace {
+                path: "/a".to_string(),
+                value: json!(2),
+            },
+            PatchOperation::Remove {
+                path: "/b".to_string(),
+            },
+        ];
+        assert!(apply_patch(&mut payload, &patch).is_err());
+        assert_eq!(serde_json::to_value(payload).unwrap(), json!({"a": 1}));
+    }
+}
@@ -1,3 +1,4 @@
+pub mod blob_storage;
 pub mod common;
 pub mod entry;
 #[cfg(feature = "testing")]
@@ -14,6 +15,7 @@ pub mod telemetry;
 
 mod compat;
 pub mod data_types;
+pub mod json_patch;
 pub mod json_path;
 pub mod types;
 pub mod utils;
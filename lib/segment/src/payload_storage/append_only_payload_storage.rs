@@ -0,0 +1,168 @@
+use std::path::{Path, PathBuf};
+
+use common::types::PointOffsetType;
+use serde_json::Value;
+
+use crate::common::append_only_store::AppendOnlyStore;
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::common::Flusher;
+use crate::data_types::payload_projection::PayloadProjection;
+use crate::json_path::JsonPath;
+use crate::payload_storage::PayloadStorage;
+use crate::types::Payload;
+
+const PAYLOAD_STORAGE_PATH: &str = "payload_storage";
+const PAYLOAD_LOG_FILE: &str = "payload.log";
+
+/// Implementation of `PayloadStorage` on top of an append-only log.
+/// Only offsets of payloads are kept in memory, payloads are read from mmap every time
+/// they are requested. Does not depend on RocksDB.
+pub struct AppendOnlyPayloadStorage {
+    store: AppendOnlyStore,
+}
+
+impl AppendOnlyPayloadStorage {
+    pub fn open(segment_path: &Path) -> OperationResult<Self> {
+        let path = segment_path
+            .join(PAYLOAD_STORAGE_PATH)
+            .join(PAYLOAD_LOG_FILE);
+        Ok(AppendOnlyPayloadStorage {
+            store: AppendOnlyStore::open(&path)?,
+        })
+    }
+
+    pub fn update_storage(&self, point_id: PointOffsetType, payload: &Payload) {
+        self.store
+            .put(point_id, serde_cbor::to_vec(payload).unwrap());
+    }
+
+    pub fn read_payload(&self, point_id: PointOffsetType) -> OperationResult<Option<Payload>> {
+        self.store
+            .get(point_id, |raw| serde_cbor::from_slice(raw))
+            .transpose()
+            .map_err(OperationError::from)
+    }
+
+    pub fn iter<F>(&self, mut callback: F) -> OperationResult<()>
+    where
+        F: FnMut(PointOffsetType, &Payload) -> OperationResult<bool>,
+    {
+        self.store
+            .iter(|point_id, raw| callback(point_id, &serde_cbor::from_slice(raw)?))
+    }
+
+    pub fn files(&self) -> Vec<PathBuf> {
+        self.store.files()
+    }
+}
+
+impl PayloadStorage for AppendOnlyPayloadStorage {
+    fn assign_all(&mut self, point_id: PointOffsetType, payload: &Payload) -> OperationResult<()> {
+        self.update_storage(point_id, payload);
+        Ok(())
+    }
+
+    fn assign(&mut self, point_id: PointOffsetType, payload: &Payload) -> OperationResult<()> {
+        match self.read_payload(point_id)? {
+            Some(mut point_payload) => {
+                point_payload.merge(payload);
+                self.update_storage(point_id, &point_payload);
+            }
+            None => self.update_storage(point_id, payload),
+        }
+        Ok(())
+    }
+
+    fn assign_by_key(
+        &mut self,
+        point_id: PointOffsetType,
+        payload: &Payload,
+        key: &JsonPath,
+    ) -> OperationResult<()> {
+        let mut point_payload = self.read_payload(point_id)?.unwrap_or_default();
+        point_payload.merge_by_key(payload, key)?;
+        self.update_storage(point_id, &point_payload);
+        Ok(())
+    }
+
+    fn payload(&self, point_id: PointOffsetType) -> OperationResult<Payload> {
+        Ok(self.read_payload(point_id)?.unwrap_or_default())
+    }
+
+    fn payload_projected(
+        &self,
+        point_id: PointOffsetType,
+        projection: PayloadProjection,
+    ) -> OperationResult<Payload> {
+        let payload = self
+            .store
+            .get(point_id, |raw| projection.from_cbor(raw))
+            .transpose()?;
+        Ok(payload.unwrap_or_default())
+    }
+
+    fn delete(&mut self, point_id: PointOffsetType, key: &JsonPath) -> OperationResult<Vec<Value>> {
+        match self.read_payload(point_id)? {
+            Some(mut payload) => {
+                let res = payload.remove(key);
+                if !res.is_empty() {
+                    self.update_storage(point_id, &payload);
+                }
+                Ok(res)
+            }
+            None => Ok(vec![]),
+        }
+    }
+
+    fn drop(&mut self, point_id: PointOffsetType) -> OperationResult<Option<Payload>> {
+        let payload = self.read_payload(point_id)?;
+        if payload.is_some() {
+            self.store.remove(point_id);
+        }
+        Ok(payload)
+    }
+
+    fn wipe(&mut self) -> OperationResult<()> {
+        self.store.clear()
+    }
+
+    fn flusher(&self) -> Flusher {
+        self.store.flusher()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::json_path::path;
+
+    #[test]
+    fn test_append_only_payload_storage() {
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+
+        let mut storage = AppendOnlyPayloadStorage::open(dir.path()).unwrap();
+        let payload: Payload = serde_json::from_str(r#"{"name": "John Doe", "age": 52}"#).unwrap();
+        storage.assign(100, &payload).unwrap();
+        storage.assign(200, &payload).unwrap();
+        storage.delete(100, &path("age")).unwrap();
+        storage.drop(200).unwrap();
+        storage.flusher()().unwrap();
+
+        let storage = AppendOnlyPayloadStorage::open(dir.path()).unwrap();
+        let stored = storage.payload(100).unwrap();
+        assert_eq!(stored.get_value(&path("name")).len(), 1);
+        assert!(stored.get_value(&path("age")).is_empty());
+        assert_eq!(storage.payload(200).unwrap(), Payload::default());
+
+        let mut count = 0;
+        storage
+            .iter(|_, _| {
+                count += 1;
+                Ok(true)
+            })
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+}
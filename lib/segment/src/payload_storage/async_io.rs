@@ -0,0 +1,156 @@
+use std::fs::File;
+use std::os::fd::AsRawFd;
+use std::path::{Path, PathBuf};
+
+use io_uring::{opcode, types, IoUring};
+
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::vector_storage::chunked_mmap_vectors::ChunkedMmapVectors;
+use crate::vector_storage::common::get_async_scorer_parallelism;
+
+/// Single read of serialized payload
+pub struct PayloadRead {
+    /// Index of the chunk file to read from
+    pub chunk: usize,
+    /// Offset of the payload in the chunk file, in bytes
+    pub offset: usize,
+    /// Size of the payload, in bytes
+    pub len: usize,
+}
+
+/// io_uring based reader of payloads from chunk files of [`MmapPayloadStorage`].
+///
+/// Reads of a batch are issued concurrently, which is faster than sequential page faults of
+/// the memory map, if the payloads are not in page cache.
+///
+/// [`MmapPayloadStorage`]: super::mmap_payload_storage::MmapPayloadStorage
+pub struct UringPayloadReader {
+    /// Directory of the chunk files
+    directory: PathBuf,
+    /// Opened chunk files, new chunks are opened on first read
+    chunk_files: Vec<File>,
+    io_uring: Option<IoUring>,
+    /// Read buffers, one per in-flight read
+    buffers: Vec<Vec<u8>>,
+}
+
+impl UringPayloadReader {
+    pub fn new(directory: &Path) -> OperationResult<Self> {
+        let parallelism = get_async_scorer_parallelism();
+        let io_uring = IoUring::new(parallelism as _)?;
+
+        Ok(Self {
+            directory: directory.to_owned(),
+            chunk_files: Vec::new(),
+            io_uring: Some(io_uring),
+            buffers: vec![Vec::new(); parallelism],
+        })
+    }
+
+    fn chunk_file(&mut self, chunk: usize) -> OperationResult<&File> {
+        while self.chunk_files.len() <= chunk {
+            let path =
+                ChunkedMmapVectors::<u8>::chunk_path(&self.directory, self.chunk_files.len());
+            self.chunk_files.push(File::open(path)?);
+        }
+        Ok(&self.chunk_files[chunk])
+    }
+
+    /// Read all given payloads and call `callback` with the index of the read and its data.
+    ///
+    /// Callback is called in order of completion, not in order of `reads`.
+    pub fn read_batch(
+        &mut self,
+        reads: &[PayloadRead],
+        mut callback: impl FnMut(usize, &[u8]) -> OperationResult<()>,
+    ) -> OperationResult<()> {
+        // Take `io_uring`, so that it is dropped with all in-flight reads if we return an error
+        let mut io_uring = match self.io_uring.take() {
+            Some(io_uring) => io_uring,
+            None => IoUring::new(self.buffers.len() as _)?,
+        };
+
+        // Index of the read, which is in flight in the buffer
+        let mut in_flight: Vec<Option<usize>> = vec![None; self.buffers.len()];
+        let mut unused_buffer_ids = (0..self.buffers.len()).collect::<Vec<_>>();
+
+        for (idx, read) in reads.iter().enumerate() {
+            if unused_buffer_ids.is_empty() {
+                submit_and_read(
+                    &mut io_uring,
+                    &self.buffers,
+                    &mut in_flight,
+                    &mut unused_buffer_ids,
+                    reads,
+                    &mut callback,
+                )?;
+            }
+            // Assume there is at least one buffer available at this point
+            let buffer_id = unused_buffer_ids.pop().unwrap();
+            let fd = self.chunk_file(read.chunk)?.as_raw_fd();
+
+            let buffer = &mut self.buffers[buffer_id];
+            buffer.resize(read.len, 0);
+            in_flight[buffer_id] = Some(idx);
+
+            let read_e = opcode::Read::new(types::Fd(fd), buffer.as_mut_ptr(), read.len as _)
+                .offset(read.offset as _)
+                .build()
+                .user_data(buffer_id as _);
+
+            unsafe {
+                io_uring.submission().push(&read_e).map_err(|err| {
+                    OperationError::service_error(format!("Failed using io-uring: {err}"))
+                })?;
+            }
+        }
+
+        while unused_buffer_ids.len() < self.buffers.len() {
+            submit_and_read(
+                &mut io_uring,
+                &self.buffers,
+                &mut in_flight,
+                &mut unused_buffer_ids,
+                reads,
+                &mut callback,
+            )?;
+        }
+
+        self.io_uring = Some(io_uring);
+
+        Ok(())
+    }
+}
+
+fn submit_and_read(
+    io_uring: &mut IoUring,
+    buffers: &[Vec<u8>],
+    in_flight: &mut [Option<usize>],
+    unused_buffer_ids: &mut Vec<usize>,
+    reads: &[PayloadRead],
+    mut callback: impl FnMut(usize, &[u8]) -> OperationResult<()>,
+) -> OperationResult<()> {
+    // Wait for at least one buffer to become available
+    io_uring.submit_and_wait(1)?;
+
+    for entry in io_uring.completion() {
+        let buffer_id = entry.user_data() as usize;
+        let idx = in_flight[buffer_id].take().unwrap();
+        let result = entry.result();
+        if result < 0 {
+            return Err(OperationError::service_error(format!(
+                "io_uring operation failed with {result} error",
+            )));
+        } else if result as usize != reads[idx].len {
+            return Err(OperationError::service_error(format!(
+                "io_uring operation returned {result} bytes instead of {}",
+                reads[idx].len,
+            )));
+        }
+
+        callback(idx, &buffers[buffer_id])?;
+        unused_buffer_ids.push(buffer_id);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,418 @@
+use std::fs::create_dir_all;
+use std::path::{Path, PathBuf};
+
+use common::types::PointOffsetType;
+#[cfg(target_os = "linux")]
+use parking_lot::Mutex;
+use serde_json::Value;
+
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::common::Flusher;
+use crate::data_types::payload_projection::PayloadProjection;
+use crate::json_path::JsonPath;
+#[cfg(target_os = "linux")]
+use crate::payload_storage::async_io::{PayloadRead, UringPayloadReader};
+use crate::payload_storage::PayloadStorage;
+use crate::types::Payload;
+use crate::vector_storage::chunked_mmap_vectors::ChunkedMmapVectors;
+use crate::vector_storage::common::get_async_payload_reads;
+
+const PAYLOAD_STORAGE_PATH: &str = "payload_storage";
+const BLOCKS_DIR_PATH: &str = "blocks";
+const OFFSETS_DIR_PATH: &str = "offsets";
+
+/// Size of the allocation unit for payload data
+const BLOCK_SIZE: usize = 128;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct PayloadMmapOffset {
+    /// First block of the payload
+    block: PointOffsetType,
+    /// Size of the serialized payload in bytes, 0 if point has no payload
+    len: u32,
+    /// Number of blocks allocated for the payload
+    capacity: u32,
+}
+
+fn blocks_for(len: usize) -> usize {
+    len.div_ceil(BLOCK_SIZE)
+}
+
+/// On-disk implementation of `PayloadStorage` on top of memory maps.
+///
+/// Payloads are serialized into blocks of a chunked mmap file, a compact offset index maps each
+/// point to its blocks. Neither payloads nor offsets are kept in memory, so resident memory
+/// is only used by pages of the recently read payloads.
+///
+/// Updated payload is written in place if it fits into the allocated blocks, otherwise it is
+/// appended to the end. Space of the abandoned blocks is reclaimed by segment optimization.
+///
+/// With async IO enabled, payloads of multiple points are read with io_uring on Linux.
+pub struct MmapPayloadStorage {
+    path: PathBuf,
+    blocks: ChunkedMmapVectors<u8>,
+    offsets: ChunkedMmapVectors<PayloadMmapOffset>,
+    /// Context for io_uring-base async IO
+    #[cfg(target_os = "linux")]
+    uring_reader: Option<Mutex<UringPayloadReader>>,
+}
+
+impl MmapPayloadStorage {
+    pub fn open(segment_path: &Path) -> OperationResult<Self> {
+        Self::open_with_async_io(segment_path, get_async_payload_reads())
+    }
+
+    #[cfg_attr(not(target_os = "linux"), allow(unused_variables))]
+    pub fn open_with_async_io(segment_path: &Path, with_async_io: bool) -> OperationResult<Self> {
+        let path = segment_path.join(PAYLOAD_STORAGE_PATH);
+        create_dir_all(&path)?;
+
+        let blocks_path = path.join(BLOCKS_DIR_PATH);
+        let blocks = ChunkedMmapVectors::open(&blocks_path, BLOCK_SIZE)?;
+        let offsets = ChunkedMmapVectors::open(&path.join(OFFSETS_DIR_PATH), 1)?;
+
+        #[cfg(target_os = "linux")]
+        let uring_reader = if with_async_io {
+            Some(Mutex::new(UringPayloadReader::new(&blocks_path)?))
+        } else {
+            None
+        };
+
+        Ok(MmapPayloadStorage {
+            path,
+            blocks,
+            offsets,
+            #[cfg(target_os = "linux")]
+            uring_reader,
+        })
+    }
+
+    pub fn has_async_reader(&self) -> bool {
+        #[cfg(target_os = "linux")]
+        {
+            self.uring_reader.is_some()
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            false
+        }
+    }
+
+    fn get_offset(&self, point_id: PointOffsetType) -> PayloadMmapOffset {
+        self.offsets
+            .get(point_id as usize)
+            .and_then(|offset| offset.first().copied())
+            .unwrap_or_default()
+    }
+
+    fn read_raw(&self, point_id: PointOffsetType) -> OperationResult<Option<&[u8]>> {
+        let offset = self.get_offset(point_id);
+        if offset.len == 0 {
+            return Ok(None);
+        }
+
+        let raw = self
+            .blocks
+            .get_many(offset.block, blocks_for(offset.len as usize))
+            .ok_or_else(|| {
+                OperationError::service_error(format!("Payload of point {point_id} not found"))
+            })?;
+        Ok(Some(&raw[..offset.len as usize]))
+    }
+
+    pub fn read_payload(&self, point_id: PointOffsetType) -> OperationResult<Option<Payload>> {
+        match self.read_raw(point_id)? {
+            Some(raw) => Ok(Some(serde_cbor::from_slice(raw)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Reads payloads of the given points, in the same order.
+    /// Uses io_uring if async IO is enabled, memory map otherwise.
+    pub fn read_payloads(
+        &self,
+        point_ids: &[PointOffsetType],
+    ) -> OperationResult<Vec<Option<Payload>>> {
+        #[cfg(target_os = "linux")]
+        if let Some(uring_reader) = &self.uring_reader {
+            return self.read_payloads_uring(&mut uring_reader.lock(), point_ids);
+        }
+
+        point_ids
+            .iter()
+            .map(|&point_id| self.read_payload(point_id))
+            .collect()
+    }
+
+    #[cfg(target_os = "linux")]
+    fn read_payloads_uring(
+        &self,
+        uring_reader: &mut UringPayloadReader,
+        point_ids: &[PointOffsetType],
+    ) -> OperationResult<Vec<Option<Payload>>> {
+        let mut payloads = vec![None; point_ids.len()];
+
+        // Position in `point_ids` of each read
+        let mut read_positions = Vec::with_capacity(point_ids.len());
+        let mut reads = Vec::with_capacity(point_ids.len());
+        for (position, &point_id) in point_ids.iter().enumerate() {
+            let offset = self.get_offset(point_id);
+            if offset.len == 0 {
+                continue;
+            }
+
+            let (chunk, chunk_offset) = self
+                .blocks
+                .chunk_location(offset.block, blocks_for(offset.len as usize))
+                .ok_or_else(|| {
+                    OperationError::service_error(format!("Payload of point {point_id} not found"))
+                })?;
+            read_positions.push(position);
+            reads.push(PayloadRead {
+                chunk,
+                offset: chunk_offset,
+                len: offset.len as usize,
+            });
+        }
+
+        uring_reader.read_batch(&reads, |read_idx, raw| {
+            payloads[read_positions[read_idx]] = Some(serde_cbor::from_slice(raw)?);
+            Ok(())
+        })?;
+
+        Ok(payloads)
+    }
+
+    pub fn update_storage(
+        &mut self,
+        point_id: PointOffsetType,
+        payload: &Payload,
+    ) -> OperationResult<()> {
+        let mut data = serde_cbor::to_vec(payload).unwrap();
+        let len = data.len();
+        let required_blocks = blocks_for(len);
+        data.resize(required_blocks * BLOCK_SIZE, 0);
+
+        let mut offset = self.get_offset(point_id);
+        if required_blocks > offset.capacity as usize {
+            // append payload to the end, blocks of a single payload never cross chunk boundary
+            let mut new_block = self.blocks.len();
+            let chunk_left_blocks = self.blocks.get_remaining_chunk_keys(new_block);
+            if required_blocks > chunk_left_blocks {
+                new_block += chunk_left_blocks;
+            }
+
+            offset.block = new_block as PointOffsetType;
+            offset.capacity = required_blocks as u32;
+        }
+        offset.len = len as u32;
+
+        self.blocks
+            .insert_many(offset.block, &data, required_blocks)?;
+        self.offsets.insert(point_id as usize, &[offset])?;
+        Ok(())
+    }
+
+    fn remove_from_storage(&mut self, point_id: PointOffsetType) -> OperationResult<()> {
+        let mut offset = self.get_offset(point_id);
+        if offset.len != 0 {
+            // keep allocated blocks, so that the next payload of the point could reuse them
+            offset.len = 0;
+            self.offsets.insert(point_id as usize, &[offset])?;
+        }
+        Ok(())
+    }
+
+    pub fn iter<F>(&self, mut callback: F) -> OperationResult<()>
+    where
+        F: FnMut(PointOffsetType, &Payload) -> OperationResult<bool>,
+    {
+        for point_id in 0..self.offsets.len() as PointOffsetType {
+            if let Some(payload) = self.read_payload(point_id)? {
+                if !callback(point_id, &payload)? {
+                    return Ok(());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn files(&self) -> Vec<PathBuf> {
+        let mut files = self.blocks.files();
+        files.extend(self.offsets.files());
+        files
+    }
+}
+
+impl PayloadStorage for MmapPayloadStorage {
+    fn assign_all(&mut self, point_id: PointOffsetType, payload: &Payload) -> OperationResult<()> {
+        self.update_storage(point_id, payload)
+    }
+
+    fn assign(&mut self, point_id: PointOffsetType, payload: &Payload) -> OperationResult<()> {
+        match self.read_payload(point_id)? {
+            Some(mut point_payload) => {
+                point_payload.merge(payload);
+                self.update_storage(point_id, &point_payload)
+            }
+            None => self.update_storage(point_id, payload),
+        }
+    }
+
+    fn assign_by_key(
+        &mut self,
+        point_id: PointOffsetType,
+        payload: &Payload,
+        key: &JsonPath,
+    ) -> OperationResult<()> {
+        let mut point_payload = self.read_payload(point_id)?.unwrap_or_default();
+        point_payload.merge_by_key(payload, key)?;
+        self.update_storage(point_id, &point_payload)
+    }
+
+    fn payload(&self, point_id: PointOffsetType) -> OperationResult<Payload> {
+        Ok(self.read_payload(point_id)?.unwrap_or_default())
+    }
+
+    fn payloads(&self, point_ids: &[PointOffsetType]) -> OperationResult<Vec<Payload>> {
+        Ok(self
+            .read_payloads(point_ids)?
+            .into_iter()
+            .map(Option::unwrap_or_default)
+            .collect())
+    }
+
+    fn payload_projected(
+        &self,
+        point_id: PointOffsetType,
+        projection: PayloadProjection,
+    ) -> OperationResult<Payload> {
+        match self.read_raw(point_id)? {
+            Some(raw) => Ok(projection.from_cbor(raw)?),
+            None => Ok(Payload::default()),
+        }
+    }
+
+    fn delete(&mut self, point_id: PointOffsetType, key: &JsonPath) -> OperationResult<Vec<Value>> {
+        match self.read_payload(point_id)? {
+            Some(mut payload) => {
+                let res = payload.remove(key);
+                if !res.is_empty() {
+                    self.update_storage(point_id, &payload)?;
+                }
+                Ok(res)
+            }
+            None => Ok(vec![]),
+        }
+    }
+
+    fn drop(&mut self, point_id: PointOffsetType) -> OperationResult<Option<Payload>> {
+        let payload = self.read_payload(point_id)?;
+        self.remove_from_storage(point_id)?;
+        Ok(payload)
+    }
+
+    fn wipe(&mut self) -> OperationResult<()> {
+        let segment_path = self.path.parent().unwrap().to_path_buf();
+        let with_async_io = self.has_async_reader();
+        std::fs::remove_dir_all(&self.path)?;
+        *self = Self::open_with_async_io(&segment_path, with_async_io)?;
+        Ok(())
+    }
+
+    fn flusher(&self) -> Flusher {
+        Box::new({
+            let blocks_flusher = self.blocks.flusher();
+            let offsets_flusher = self.offsets.flusher();
+            move || {
+                blocks_flusher()?;
+                offsets_flusher()?;
+                Ok(())
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::json_path::path;
+
+    #[test]
+    fn test_mmap_payload_storage() {
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+
+        let mut storage = MmapPayloadStorage::open(dir.path()).unwrap();
+        let small: Payload = serde_json::from_str(r#"{"name": "John Doe"}"#).unwrap();
+        let large: Payload = json!({ "blob": "x".repeat(10 * BLOCK_SIZE) }).into();
+
+        storage.assign(0, &small).unwrap();
+        storage.assign(1, &large).unwrap();
+        storage.assign(2, &small).unwrap();
+        let blocks_len = storage.blocks.len();
+
+        // Smaller payload reuses allocated blocks
+        storage.assign_all(1, &small).unwrap();
+        assert_eq!(storage.blocks.len(), blocks_len);
+
+        // Larger payload is moved to the end
+        storage.assign(0, &large).unwrap();
+        assert!(storage.blocks.len() > blocks_len);
+
+        storage.drop(2).unwrap();
+        storage.flusher()().unwrap();
+        drop(storage);
+
+        let storage = MmapPayloadStorage::open(dir.path()).unwrap();
+        let stored = storage.payload(0).unwrap();
+        assert_eq!(stored.get_value(&path("name")).len(), 1);
+        assert_eq!(stored.get_value(&path("blob")).len(), 1);
+        assert_eq!(storage.payload(1).unwrap(), small);
+        assert_eq!(storage.read_payload(2).unwrap(), None);
+
+        let mut count = 0;
+        storage
+            .iter(|_, _| {
+                count += 1;
+                Ok(true)
+            })
+            .unwrap();
+        assert_eq!(count, 2);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_mmap_payload_storage_async_reads() {
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+
+        let mut storage = MmapPayloadStorage::open_with_async_io(dir.path(), true).unwrap();
+        assert!(storage.has_async_reader());
+
+        // More points than in-flight reads, with payloads of different sizes
+        for point_id in 0..100 {
+            if point_id % 7 == 0 {
+                continue;
+            }
+            let payload: Payload =
+                json!({ "id": point_id, "blob": "x".repeat(point_id as usize * 10) }).into();
+            storage.assign(point_id, &payload).unwrap();
+        }
+
+        let point_ids = (0..110).rev().collect::<Vec<_>>();
+        let async_payloads = storage.read_payloads(&point_ids).unwrap();
+        let mmap_payloads = point_ids
+            .iter()
+            .map(|&point_id| storage.read_payload(point_id).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(async_payloads, mmap_payloads);
+        assert_eq!(async_payloads.iter().flatten().count(), 85);
+        assert_eq!(
+            async_payloads[10],
+            Some(json!({ "id": 99, "blob": "x".repeat(990) }).into()),
+        );
+    }
+}
@@ -1,9 +1,14 @@
+pub mod append_only_payload_storage;
+#[cfg(target_os = "linux")]
+mod async_io;
 pub mod condition_checker;
 #[cfg(feature = "testing")]
 pub mod in_memory_payload_storage;
 #[cfg(feature = "testing")]
 pub mod in_memory_payload_storage_impl;
+pub mod mmap_payload_storage;
 pub mod on_disk_payload_storage;
+pub mod payload_column;
 mod payload_storage_base;
 pub mod payload_storage_enum;
 pub mod query_checker;
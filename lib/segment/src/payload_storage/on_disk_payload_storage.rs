@@ -5,9 +5,11 @@ use parking_lot::RwLock;
 use rocksdb::DB;
 use serde_json::Value;
 
+use crate::common::encryption::StorageCipher;
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::rocksdb_wrapper::{DatabaseColumnWrapper, DB_PAYLOAD_CF};
 use crate::common::Flusher;
+use crate::data_types::payload_projection::PayloadProjection;
 use crate::json_path::JsonPath;
 use crate::payload_storage::PayloadStorage;
 use crate::types::Payload;
@@ -19,8 +21,11 @@ pub struct OnDiskPayloadStorage {
 }
 
 impl OnDiskPayloadStorage {
-    pub fn open(database: Arc<RwLock<DB>>) -> OperationResult<Self> {
-        let db_wrapper = DatabaseColumnWrapper::new(database, DB_PAYLOAD_CF);
+    pub fn open(
+        database: Arc<RwLock<DB>>,
+        cipher: Option<Arc<StorageCipher>>,
+    ) -> OperationResult<Self> {
+        let db_wrapper = DatabaseColumnWrapper::new_encrypted(database, DB_PAYLOAD_CF, cipher);
         Ok(OnDiskPayloadStorage { db_wrapper })
     }
 
@@ -52,7 +57,8 @@ impl OnDiskPayloadStorage {
     where
         F: FnMut(PointOffsetType, &Payload) -> OperationResult<bool>,
     {
-        for (key, val) in self.db_wrapper.lock_db().iter()? {
+        for item in self.db_wrapper.lock_db().iter()? {
+            let (key, val) = item?;
             let do_continue = callback(
                 serde_cbor::from_slice(&key)?,
                 &serde_cbor::from_slice(&val)?,
@@ -110,6 +116,19 @@ impl PayloadStorage for OnDiskPayloadStorage {
         }
     }
 
+    fn payload_projected(
+        &self,
+        point_id: PointOffsetType,
+        projection: PayloadProjection,
+    ) -> OperationResult<Payload> {
+        let key = serde_cbor::to_vec(&point_id).unwrap();
+        let payload = self
+            .db_wrapper
+            .get_pinned(&key, |raw| projection.from_cbor(raw))?
+            .transpose()?;
+        Ok(payload.unwrap_or_default())
+    }
+
     fn delete(&mut self, point_id: PointOffsetType, key: &JsonPath) -> OperationResult<Vec<Value>> {
         let stored_payload = self.read_payload(point_id)?;
 
@@ -0,0 +1,243 @@
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+
+use common::types::PointOffsetType;
+use serde_json::Value;
+
+use crate::common::append_only_store::AppendOnlyStore;
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::common::utils::MultiValue;
+use crate::common::Flusher;
+use crate::json_path::JsonPath;
+use crate::types::{PayloadContainer, PayloadKeyType};
+
+const COLUMN_FILE_EXTENSION: &str = "column";
+
+/// File name of the column, which is stable for the field and safe for the file system
+fn column_file_name(field: &JsonPath) -> String {
+    let field = field.to_string();
+    let readable: String = field
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    let checksum = crc32fast::hash(field.as_bytes());
+    format!("{readable}-{checksum:08x}.{COLUMN_FILE_EXTENSION}")
+}
+
+/// Values of a single payload field, stored separately from the rest of the payload.
+///
+/// Values of all points are kept in a single append-only log, so a field can be checked
+/// or scanned without deserializing whole payloads.
+#[derive(Debug, Clone)]
+pub struct PayloadColumn {
+    store: AppendOnlyStore,
+}
+
+impl PayloadColumn {
+    pub fn open(path: &Path) -> OperationResult<Self> {
+        Ok(Self {
+            store: AppendOnlyStore::open(path)?,
+        })
+    }
+
+    /// Values of the field in the point payload, empty if the field is not present
+    pub fn get(&self, point_id: PointOffsetType) -> OperationResult<Vec<Value>> {
+        self.store
+            .get(point_id, |raw| serde_cbor::from_slice::<Vec<Value>>(raw))
+            .transpose()
+            .map(Option::unwrap_or_default)
+            .map_err(OperationError::from)
+    }
+
+    pub fn set(&self, point_id: PointOffsetType, values: &[&Value]) {
+        if values.is_empty() {
+            self.store.remove(point_id);
+        } else {
+            self.store
+                .put(point_id, serde_cbor::to_vec(values).unwrap());
+        }
+    }
+
+    pub fn remove(&self, point_id: PointOffsetType) {
+        self.store.remove(point_id);
+    }
+
+    /// Iterate over values of all points, which have the field, in ascending order of point ids
+    pub fn iter<F>(&self, mut callback: F) -> OperationResult<()>
+    where
+        F: FnMut(PointOffsetType, &[Value]) -> OperationResult<bool>,
+    {
+        self.store.iter(|point_id, raw| {
+            let values: Vec<Value> = serde_cbor::from_slice(raw)?;
+            callback(point_id, &values)
+        })
+    }
+
+    pub fn clear(&self) -> OperationResult<()> {
+        self.store.clear()
+    }
+
+    pub fn flusher(&self) -> Flusher {
+        self.store.flusher()
+    }
+
+    pub fn files(&self) -> Vec<PathBuf> {
+        self.store.files()
+    }
+}
+
+/// Columns of the payload fields, which are configured to be stored column-wise
+#[derive(Debug, Clone, Default)]
+pub struct PayloadColumns {
+    columns: HashMap<PayloadKeyType, PayloadColumn>,
+}
+
+impl PayloadColumns {
+    /// Open columns of the given fields in the directory.
+    ///
+    /// Returns columns along with the fields, which have no persisted values yet
+    /// and therefore have to be filled from the payload storage.
+    pub fn open(
+        path: &Path,
+        fields: &[PayloadKeyType],
+    ) -> OperationResult<(Self, Vec<PayloadKeyType>)> {
+        let mut columns = HashMap::new();
+        let mut new_fields = Vec::new();
+        for field in fields {
+            let column_path = path.join(column_file_name(field));
+            if !column_path.exists() {
+                new_fields.push(field.clone());
+            }
+            columns.insert(field.clone(), PayloadColumn::open(&column_path)?);
+        }
+
+        // Remove columns of the fields, which are no longer configured
+        if path.exists() {
+            let known_files: Vec<_> = fields.iter().map(column_file_name).collect();
+            for entry in std::fs::read_dir(path)? {
+                let entry = entry?;
+                let entry_path = entry.path();
+                let is_column = entry_path.extension() == Some(OsStr::new(COLUMN_FILE_EXTENSION));
+                let file_name = entry.file_name().to_string_lossy().into_owned();
+                if is_column && !known_files.contains(&file_name) {
+                    std::fs::remove_file(entry_path)?;
+                }
+            }
+        }
+
+        Ok((Self { columns }, new_fields))
+    }
+
+    pub fn get(&self, field: &JsonPath) -> Option<&PayloadColumn> {
+        self.columns.get(field)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.columns.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&PayloadKeyType, &PayloadColumn)> {
+        self.columns.iter()
+    }
+
+    /// Update all columns with the values of the point payload
+    pub fn update(&self, point_id: PointOffsetType, payload: &impl PayloadContainer) {
+        for (field, column) in &self.columns {
+            column.set(point_id, &payload.get_value(field));
+        }
+    }
+
+    pub fn remove(&self, point_id: PointOffsetType) {
+        for column in self.columns.values() {
+            column.remove(point_id);
+        }
+    }
+
+    pub fn flushers(&self) -> Vec<Flusher> {
+        self.columns.values().map(PayloadColumn::flusher).collect()
+    }
+
+    pub fn files(&self) -> Vec<PathBuf> {
+        self.columns
+            .values()
+            .flat_map(PayloadColumn::files)
+            .collect()
+    }
+}
+
+/// Values of a single field, read from its column.
+/// Acts as a payload, which only contains this field.
+pub struct ColumnPayload<'a> {
+    field: &'a JsonPath,
+    values: Vec<Value>,
+}
+
+impl<'a> ColumnPayload<'a> {
+    pub fn new(field: &'a JsonPath, values: Vec<Value>) -> Self {
+        Self { field, values }
+    }
+}
+
+impl<'a> PayloadContainer for ColumnPayload<'a> {
+    fn get_value(&self, path: &JsonPath) -> MultiValue<&Value> {
+        debug_assert_eq!(path, self.field, "column only contains values of its field");
+        if path == self.field {
+            self.values.iter().collect()
+        } else {
+            MultiValue::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::json_path::path;
+    use crate::types::Payload;
+
+    #[test]
+    fn test_payload_columns() {
+        let dir = Builder::new().prefix("columns_dir").tempdir().unwrap();
+        let fields = vec![path("price"), path("meta.color")];
+
+        let (columns, new_fields) = PayloadColumns::open(dir.path(), &fields).unwrap();
+        assert_eq!(new_fields, fields);
+
+        let payload: Payload = json!({"price": 10, "meta": {"color": ["red", "blue"]}}).into();
+        columns.update(0, &payload);
+        let payload: Payload = json!({"price": 20}).into();
+        columns.update(1, &payload);
+        columns.update(2, &payload);
+        columns.remove(2);
+        for flusher in columns.flushers() {
+            flusher().unwrap();
+        }
+        drop(columns);
+
+        // Fields, which are no longer configured, are removed
+        let (columns, new_fields) = PayloadColumns::open(dir.path(), &fields[..1]).unwrap();
+        assert!(new_fields.is_empty());
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+
+        let price = columns.get(&path("price")).unwrap();
+        let mut scanned = vec![];
+        price
+            .iter(|point_id, values| {
+                scanned.push((point_id, values.to_vec()));
+                Ok(true)
+            })
+            .unwrap();
+        assert_eq!(scanned, vec![(0, vec![json!(10)]), (1, vec![json!(20)])]);
+
+        let column_payload = ColumnPayload::new(&fields[0], price.get(1).unwrap());
+        assert_eq!(
+            column_payload.get_value(&fields[0]).as_slice(),
+            &[&json!(20)]
+        );
+        assert!(price.get(2).unwrap().is_empty());
+    }
+}
@@ -3,6 +3,7 @@ use serde_json::Value;
 
 use crate::common::operation_error::OperationResult;
 use crate::common::Flusher;
+use crate::data_types::payload_projection::PayloadProjection;
 use crate::json_path::JsonPath;
 use crate::types::{Filter, Payload};
 
@@ -30,6 +31,25 @@ pub trait PayloadStorage {
     /// If no payload found, return empty payload
     fn payload(&self, point_id: PointOffsetType) -> OperationResult<Payload>;
 
+    /// Get payloads for multiple points, in the same order.
+    /// Storages, which support async IO, read them concurrently
+    fn payloads(&self, point_ids: &[PointOffsetType]) -> OperationResult<Vec<Payload>> {
+        point_ids
+            .iter()
+            .map(|&point_id| self.payload(point_id))
+            .collect()
+    }
+
+    /// Get payload for point, keeping only the fields selected by projection.
+    /// Storages, which keep payload serialized, skip unselected fields while reading
+    fn payload_projected(
+        &self,
+        point_id: PointOffsetType,
+        projection: PayloadProjection,
+    ) -> OperationResult<Payload> {
+        Ok(projection.project(self.payload(point_id)?))
+    }
+
     /// Delete payload by key
     fn delete(&mut self, point_id: PointOffsetType, key: &JsonPath) -> OperationResult<Vec<Value>>;
 
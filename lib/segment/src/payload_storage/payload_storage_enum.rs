@@ -1,11 +1,16 @@
+use std::path::PathBuf;
+
 use common::types::PointOffsetType;
 use serde_json::Value;
 
 use crate::common::operation_error::OperationResult;
 use crate::common::Flusher;
+use crate::data_types::payload_projection::PayloadProjection;
 use crate::json_path::JsonPath;
+use crate::payload_storage::append_only_payload_storage::AppendOnlyPayloadStorage;
 #[cfg(feature = "testing")]
 use crate::payload_storage::in_memory_payload_storage::InMemoryPayloadStorage;
+use crate::payload_storage::mmap_payload_storage::MmapPayloadStorage;
 use crate::payload_storage::on_disk_payload_storage::OnDiskPayloadStorage;
 use crate::payload_storage::simple_payload_storage::SimplePayloadStorage;
 use crate::payload_storage::PayloadStorage;
@@ -16,6 +21,8 @@ pub enum PayloadStorageEnum {
     InMemoryPayloadStorage(InMemoryPayloadStorage),
     SimplePayloadStorage(SimplePayloadStorage),
     OnDiskPayloadStorage(OnDiskPayloadStorage),
+    AppendOnlyPayloadStorage(AppendOnlyPayloadStorage),
+    MmapPayloadStorage(MmapPayloadStorage),
 }
 
 #[cfg(feature = "testing")]
@@ -37,6 +44,18 @@ impl From<OnDiskPayloadStorage> for PayloadStorageEnum {
     }
 }
 
+impl From<AppendOnlyPayloadStorage> for PayloadStorageEnum {
+    fn from(a: AppendOnlyPayloadStorage) -> Self {
+        PayloadStorageEnum::AppendOnlyPayloadStorage(a)
+    }
+}
+
+impl From<MmapPayloadStorage> for PayloadStorageEnum {
+    fn from(a: MmapPayloadStorage) -> Self {
+        PayloadStorageEnum::MmapPayloadStorage(a)
+    }
+}
+
 impl PayloadStorageEnum {
     pub fn iter<F>(&self, callback: F) -> OperationResult<()>
     where
@@ -47,6 +66,20 @@ impl PayloadStorageEnum {
             PayloadStorageEnum::InMemoryPayloadStorage(s) => s.iter(callback),
             PayloadStorageEnum::SimplePayloadStorage(s) => s.iter(callback),
             PayloadStorageEnum::OnDiskPayloadStorage(s) => s.iter(callback),
+            PayloadStorageEnum::AppendOnlyPayloadStorage(s) => s.iter(callback),
+            PayloadStorageEnum::MmapPayloadStorage(s) => s.iter(callback),
+        }
+    }
+
+    /// Files of the storage, which are not kept in the segment's RocksDB
+    pub fn files(&self) -> Vec<PathBuf> {
+        match self {
+            #[cfg(feature = "testing")]
+            PayloadStorageEnum::InMemoryPayloadStorage(_) => vec![],
+            PayloadStorageEnum::SimplePayloadStorage(_) => vec![],
+            PayloadStorageEnum::OnDiskPayloadStorage(_) => vec![],
+            PayloadStorageEnum::AppendOnlyPayloadStorage(s) => s.files(),
+            PayloadStorageEnum::MmapPayloadStorage(s) => s.files(),
         }
     }
 }
@@ -58,6 +91,8 @@ impl PayloadStorage for PayloadStorageEnum {
             PayloadStorageEnum::InMemoryPayloadStorage(s) => s.assign(point_id, payload),
             PayloadStorageEnum::SimplePayloadStorage(s) => s.assign(point_id, payload),
             PayloadStorageEnum::OnDiskPayloadStorage(s) => s.assign(point_id, payload),
+            PayloadStorageEnum::AppendOnlyPayloadStorage(s) => s.assign(point_id, payload),
+            PayloadStorageEnum::MmapPayloadStorage(s) => s.assign(point_id, payload),
         }
     }
 
@@ -74,6 +109,10 @@ impl PayloadStorage for PayloadStorageEnum {
             }
             PayloadStorageEnum::SimplePayloadStorage(s) => s.assign_by_key(point_id, payload, key),
             PayloadStorageEnum::OnDiskPayloadStorage(s) => s.assign_by_key(point_id, payload, key),
+            PayloadStorageEnum::AppendOnlyPayloadStorage(s) => {
+                s.assign_by_key(point_id, payload, key)
+            }
+            PayloadStorageEnum::MmapPayloadStorage(s) => s.assign_by_key(point_id, payload, key),
         }
     }
 
@@ -83,6 +122,42 @@ impl PayloadStorage for PayloadStorageEnum {
             PayloadStorageEnum::InMemoryPayloadStorage(s) => s.payload(point_id),
             PayloadStorageEnum::SimplePayloadStorage(s) => s.payload(point_id),
             PayloadStorageEnum::OnDiskPayloadStorage(s) => s.payload(point_id),
+            PayloadStorageEnum::AppendOnlyPayloadStorage(s) => s.payload(point_id),
+            PayloadStorageEnum::MmapPayloadStorage(s) => s.payload(point_id),
+        }
+    }
+
+    fn payloads(&self, point_ids: &[PointOffsetType]) -> OperationResult<Vec<Payload>> {
+        match self {
+            #[cfg(feature = "testing")]
+            PayloadStorageEnum::InMemoryPayloadStorage(s) => s.payloads(point_ids),
+            PayloadStorageEnum::SimplePayloadStorage(s) => s.payloads(point_ids),
+            PayloadStorageEnum::OnDiskPayloadStorage(s) => s.payloads(point_ids),
+            PayloadStorageEnum::AppendOnlyPayloadStorage(s) => s.payloads(point_ids),
+            PayloadStorageEnum::MmapPayloadStorage(s) => s.payloads(point_ids),
+        }
+    }
+
+    fn payload_projected(
+        &self,
+        point_id: PointOffsetType,
+        projection: PayloadProjection,
+    ) -> OperationResult<Payload> {
+        match self {
+            #[cfg(feature = "testing")]
+            PayloadStorageEnum::InMemoryPayloadStorage(s) => {
+                s.payload_projected(point_id, projection)
+            }
+            PayloadStorageEnum::SimplePayloadStorage(s) => {
+                s.payload_projected(point_id, projection)
+            }
+            PayloadStorageEnum::OnDiskPayloadStorage(s) => {
+                s.payload_projected(point_id, projection)
+            }
+            PayloadStorageEnum::AppendOnlyPayloadStorage(s) => {
+                s.payload_projected(point_id, projection)
+            }
+            PayloadStorageEnum::MmapPayloadStorage(s) => s.payload_projected(point_id, projection),
         }
     }
 
@@ -92,6 +167,8 @@ impl PayloadStorage for PayloadStorageEnum {
             PayloadStorageEnum::InMemoryPayloadStorage(s) => s.delete(point_id, key),
             PayloadStorageEnum::SimplePayloadStorage(s) => s.delete(point_id, key),
             PayloadStorageEnum::OnDiskPayloadStorage(s) => s.delete(point_id, key),
+            PayloadStorageEnum::AppendOnlyPayloadStorage(s) => s.delete(point_id, key),
+            PayloadStorageEnum::MmapPayloadStorage(s) => s.delete(point_id, key),
         }
     }
 
@@ -101,6 +178,8 @@ impl PayloadStorage for PayloadStorageEnum {
             PayloadStorageEnum::InMemoryPayloadStorage(s) => s.drop(point_id),
             PayloadStorageEnum::SimplePayloadStorage(s) => s.drop(point_id),
             PayloadStorageEnum::OnDiskPayloadStorage(s) => s.drop(point_id),
+            PayloadStorageEnum::AppendOnlyPayloadStorage(s) => s.drop(point_id),
+            PayloadStorageEnum::MmapPayloadStorage(s) => s.drop(point_id),
         }
     }
 
@@ -110,6 +189,8 @@ impl PayloadStorage for PayloadStorageEnum {
             PayloadStorageEnum::InMemoryPayloadStorage(s) => s.wipe(),
             PayloadStorageEnum::SimplePayloadStorage(s) => s.wipe(),
             PayloadStorageEnum::OnDiskPayloadStorage(s) => s.wipe(),
+            PayloadStorageEnum::AppendOnlyPayloadStorage(s) => s.wipe(),
+            PayloadStorageEnum::MmapPayloadStorage(s) => s.wipe(),
         }
     }
 
@@ -119,6 +200,8 @@ impl PayloadStorage for PayloadStorageEnum {
             PayloadStorageEnum::InMemoryPayloadStorage(s) => s.flusher(),
             PayloadStorageEnum::SimplePayloadStorage(s) => s.flusher(),
             PayloadStorageEnum::OnDiskPayloadStorage(s) => s.flusher(),
+            PayloadStorageEnum::AppendOnlyPayloadStorage(s) => s.flusher(),
+            PayloadStorageEnum::MmapPayloadStorage(s) => s.flusher(),
         }
     }
 }
@@ -137,7 +220,7 @@ mod tests {
         let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
         let db = open_db(dir.path(), &[DB_VECTOR_CF]).unwrap();
 
-        let mut storage: PayloadStorageEnum = SimplePayloadStorage::open(db).unwrap().into();
+        let mut storage: PayloadStorageEnum = SimplePayloadStorage::open(db, None).unwrap().into();
         let payload: Payload = serde_json::from_str(r#"{"name": "John Doe"}"#).unwrap();
         storage.assign(100, &payload).unwrap();
         storage.wipe().unwrap();
@@ -156,7 +239,7 @@ mod tests {
 
         {
             let mut storage: PayloadStorageEnum =
-                SimplePayloadStorage::open(db.clone()).unwrap().into();
+                SimplePayloadStorage::open(db.clone(), None).unwrap().into();
             let payload: Payload = serde_json::from_str(
                 r#"{
                 "name": "John Doe",
@@ -187,7 +270,8 @@ mod tests {
         }
 
         {
-            let mut storage: PayloadStorageEnum = OnDiskPayloadStorage::open(db).unwrap().into();
+            let mut storage: PayloadStorageEnum =
+                OnDiskPayloadStorage::open(db, None).unwrap().into();
 
             let res = storage.payload(100).unwrap();
 
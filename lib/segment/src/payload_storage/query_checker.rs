@@ -130,6 +130,9 @@ where
         Condition::HasId(has_id) => id_tracker
             .and_then(|id_tracker| id_tracker.external_id(point_id))
             .map_or(false, |id| has_id.has_id.contains(&id)),
+        Condition::HasIdRange(has_id_range) => id_tracker
+            .and_then(|id_tracker| id_tracker.external_id(point_id))
+            .map_or(false, |id| has_id_range.has_id_range.contains(&id)),
         Condition::Nested(nested) => {
             let nested_path = nested.array_key();
             let nested_indexes = select_nested_indexes(&nested_path, field_indexes);
@@ -155,18 +158,21 @@ where
 
 pub fn check_is_empty_condition(
     is_empty: &IsEmptyCondition,
-    payload: &impl PayloadContainer,
+    payload: &(impl PayloadContainer + ?Sized),
 ) -> bool {
     check_is_empty(payload.get_value(&is_empty.is_empty.key).iter().copied())
 }
 
-pub fn check_is_null_condition(is_null: &IsNullCondition, payload: &impl PayloadContainer) -> bool {
+pub fn check_is_null_condition(
+    is_null: &IsNullCondition,
+    payload: &(impl PayloadContainer + ?Sized),
+) -> bool {
     check_is_null(payload.get_value(&is_null.is_null.key).iter().copied())
 }
 
 pub fn check_field_condition<R>(
     field_condition: &FieldCondition,
-    payload: &impl PayloadContainer,
+    payload: &(impl PayloadContainer + ?Sized),
     field_indexes: &HashMap<PayloadKeyType, R>,
 ) -> bool
 where
@@ -264,6 +270,14 @@ impl ConditionChecker for SimpleConditionChecker {
                                 .unwrap_or_else(|err| panic!("Payload storage is corrupted: {err}"))
                                 .map(|x| x.into())
                         }
+                        PayloadStorageEnum::AppendOnlyPayloadStorage(s) => s
+                            .read_payload(point_id)
+                            .unwrap_or_else(|err| panic!("Payload storage is corrupted: {err}"))
+                            .map(|x| x.into()),
+                        PayloadStorageEnum::MmapPayloadStorage(s) => s
+                            .read_payload(point_id)
+                            .unwrap_or_else(|err| panic!("Payload storage is corrupted: {err}"))
+                            .map(|x| x.into()),
                     };
 
                     payload_ref_cell
@@ -295,7 +309,8 @@ mod tests {
     use crate::payload_storage::simple_payload_storage::SimplePayloadStorage;
     use crate::payload_storage::PayloadStorage;
     use crate::types::{
-        DateTimeWrapper, FieldCondition, GeoBoundingBox, GeoPoint, PayloadField, Range, ValuesCount,
+        DateTimeWrapper, FieldCondition, GeoBoundingBox, GeoPoint, PayloadField, PointIdRange,
+        Range, ValuesCount,
     };
 
     #[test]
@@ -322,7 +337,7 @@ mod tests {
         .into();
 
         let mut payload_storage: PayloadStorageEnum =
-            SimplePayloadStorage::open(db.clone()).unwrap().into();
+            SimplePayloadStorage::open(db.clone(), None).unwrap().into();
         let mut id_tracker = SimpleIdTracker::open(db).unwrap();
 
         id_tracker.set_link(0.into(), 0).unwrap();
@@ -611,5 +626,16 @@ mod tests {
 
         let query = Filter::new_must(Condition::HasId(ids.into()));
         assert!(payload_checker.check(2, &query));
+
+        // id range Filter
+        let range = PointIdRange {
+            gte: Some(1),
+            lt: Some(10),
+        };
+
+        let query = Filter::new_must(Condition::HasIdRange(range.into()));
+        assert!(payload_checker.check(2, &query));
+        assert!(!payload_checker.check(0, &query));
+        assert!(!payload_checker.check(10, &query));
     }
 }
@@ -5,6 +5,7 @@ use common::types::PointOffsetType;
 use parking_lot::RwLock;
 use rocksdb::DB;
 
+use crate::common::encryption::StorageCipher;
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::rocksdb_wrapper::{DatabaseColumnWrapper, DB_PAYLOAD_CF};
 use crate::types::Payload;
@@ -17,12 +18,16 @@ pub struct SimplePayloadStorage {
 }
 
 impl SimplePayloadStorage {
-    pub fn open(database: Arc<RwLock<DB>>) -> OperationResult<Self> {
+    pub fn open(
+        database: Arc<RwLock<DB>>,
+        cipher: Option<Arc<StorageCipher>>,
+    ) -> OperationResult<Self> {
         let mut payload_map: HashMap<PointOffsetType, Payload> = Default::default();
 
-        let db_wrapper = DatabaseColumnWrapper::new(database, DB_PAYLOAD_CF);
+        let db_wrapper = DatabaseColumnWrapper::new_encrypted(database, DB_PAYLOAD_CF, cipher);
 
-        for (key, val) in db_wrapper.lock_db().iter()? {
+        for item in db_wrapper.lock_db().iter()? {
+            let (key, val) = item?;
             let point_id: PointOffsetType = serde_cbor::from_slice(&key)
                 .map_err(|_| OperationError::service_error("cannot deserialize point id"))?;
             let payload: Payload = serde_cbor::from_slice(&val)
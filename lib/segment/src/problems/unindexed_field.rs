@@ -322,7 +322,7 @@ impl<'a> Extractor<'a> {
                 inferred = all_indexes().collect();
             }
             // No index needed
-            Condition::HasId(_) => return,
+            Condition::HasId(_) | Condition::HasIdRange(_) => return,
         };
 
         let full_key = JsonPathV2::extend_or_new(nested_prefix, key);
@@ -19,6 +19,8 @@ use sparse::common::sparse_vector::SparseVector;
 use tar::Builder;
 use uuid::Uuid;
 
+use crate::blob_storage::{BlobChunk, BlobStorage};
+use crate::common::encryption::{self, StorageCipher};
 use crate::common::operation_error::OperationError::TypeInferenceError;
 use crate::common::operation_error::{
     get_service_error, OperationError, OperationResult, SegmentFailedState,
@@ -27,26 +29,32 @@ use crate::common::validate_snapshot_archive::open_snapshot_archive_with_validat
 use crate::common::{check_named_vectors, check_query_vectors, check_stopped, check_vector_name};
 use crate::data_types::named_vectors::NamedVectors;
 use crate::data_types::order_by::{Direction, OrderBy, OrderValue};
-use crate::data_types::query_context::{QueryContext, SegmentQueryContext};
+use crate::data_types::payload_projection::PayloadProjection;
+use crate::data_types::query_context::{QueryContext, SegmentQueryContext, VectorQueryContext};
 use crate::data_types::vectors::{MultiDenseVector, QueryVector, Vector, VectorRef};
 use crate::entry::entry_point::SegmentEntry;
 use crate::id_tracker::IdTrackerSS;
 use crate::index::field_index::numeric_index::StreamRange;
 use crate::index::field_index::CardinalityEstimation;
 use crate::index::struct_payload_index::StructPayloadIndex;
+use crate::index::vector_index_info::{VectorIndexInfo, VectorIndexType};
 use crate::index::{PayloadIndex, VectorIndex, VectorIndexEnum};
 use crate::json_path::JsonPath;
 use crate::spaces::tools::{peek_top_largest_iterable, peek_top_smallest_iterable};
 use crate::telemetry::SegmentTelemetry;
 use crate::types::{
-    Filter, Payload, PayloadFieldSchema, PayloadIndexInfo, PayloadKeyType, PayloadKeyTypeRef,
-    PayloadSchemaType, PointIdType, ScoredPoint, SearchParams, SegmentConfig, SegmentInfo,
-    SegmentState, SegmentType, SeqNumberType, VectorDataInfo, WithPayload, WithVector,
+    Distance, Filter, Payload, PayloadFieldSchema, PayloadIndexInfo, PayloadKeyType,
+    PayloadKeyTypeRef, PayloadSchemaType, PayloadSelector, PointIdType, ScoredPoint, SearchParams,
+    SegmentConfig, SegmentInfo, SegmentState, SegmentType, SeqNumberType, SparseVectorStats,
+    VectorDataInfo, WithPayload, WithVector, VECTOR_ELEMENT_SIZE,
 };
 use crate::utils;
 use crate::utils::fs::find_symlink;
 use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
-use crate::vector_storage::{VectorStorage, VectorStorageEnum};
+use crate::vector_storage::{
+    new_metered_raw_scorer, new_stoppable_raw_scorer_with_distance, VectorStorage,
+    VectorStorageEnum,
+};
 
 pub const SEGMENT_STATE_FILE: &str = "segment.json";
 
@@ -83,16 +91,22 @@ pub struct Segment {
     pub id_tracker: Arc<AtomicRefCell<IdTrackerSS>>,
     pub vector_data: HashMap<String, VectorData>,
     pub payload_index: Arc<AtomicRefCell<StructPayloadIndex>>,
+    /// Binary blobs attached to points
+    pub blob_storage: Arc<AtomicRefCell<BlobStorage>>,
     /// Shows if it is possible to insert more points into this segment
     pub appendable_flag: bool,
     /// Shows what kind of indexes and storages are used in this segment
     pub segment_type: SegmentType,
     pub segment_config: SegmentConfig,
+    /// Key, which vectors and payload of the segment are encrypted with, if any
+    pub data_key: Option<Arc<StorageCipher>>,
     /// Last unhandled error
     /// If not None, all update operations will be aborted until original operation is performed properly
     pub error_status: Option<SegmentFailedState>,
     pub database: Arc<RwLock<DB>>,
     pub flush_thread: Mutex<Option<JoinHandle<OperationResult<SeqNumberType>>>>,
+    /// Statistics of sparse vectors by name, with the version of the segment they are computed at
+    pub sparse_stats_cache: Mutex<HashMap<String, (Option<SeqNumberType>, SparseVectorStats)>>,
 }
 
 pub struct VectorData {
@@ -115,6 +129,38 @@ impl VectorData {
 
         index_task.into_iter().chain(storage_task)
     }
+
+    /// Estimated size of vectors, quantized vectors and index, which are loaded into RAM
+    pub fn ram_usage_bytes(&self) -> usize {
+        let vector_storage = self.vector_storage.borrow();
+        let storage_bytes = if vector_storage.is_on_disk() {
+            0
+        } else {
+            vector_storage.total_vector_count() * vector_storage.vector_dim() * VECTOR_ELEMENT_SIZE
+        };
+
+        let vector_index = self.vector_index.borrow();
+        let index_bytes = if vector_index.is_on_disk() {
+            0
+        } else {
+            files_size(&vector_index.files())
+        };
+
+        let quantized_bytes = match &*self.quantized_vectors.borrow() {
+            Some(quantized) if !quantized.is_on_disk() => files_size(&quantized.files()),
+            _ => 0,
+        };
+
+        storage_bytes + index_bytes + quantized_bytes
+    }
+}
+
+fn files_size(files: &[PathBuf]) -> usize {
+    files
+        .iter()
+        .filter_map(|file| fs::metadata(file).ok())
+        .map(|metadata| metadata.len() as usize)
+        .sum()
 }
 
 impl Segment {
@@ -217,12 +263,15 @@ impl Segment {
                         VectorStorageEnum::DenseSimple(_)
                         | VectorStorageEnum::DenseSimpleByte(_)
                         | VectorStorageEnum::DenseSimpleHalf(_)
+                        | VectorStorageEnum::DenseSimpleDouble(_)
                         | VectorStorageEnum::DenseMemmap(_)
                         | VectorStorageEnum::DenseMemmapByte(_)
                         | VectorStorageEnum::DenseMemmapHalf(_)
+                        | VectorStorageEnum::DenseMemmapDouble(_)
                         | VectorStorageEnum::DenseAppendableMemmap(_)
                         | VectorStorageEnum::DenseAppendableMemmapByte(_)
-                        | VectorStorageEnum::DenseAppendableMemmapHalf(_) => {
+                        | VectorStorageEnum::DenseAppendableMemmapHalf(_)
+                        | VectorStorageEnum::DenseAppendableMemmapDouble(_) => {
                             Vector::from(vec![1.0; dim])
                         }
                         VectorStorageEnum::SparseSimple(_) => Vector::from(SparseVector::default()),
@@ -249,6 +298,16 @@ impl Segment {
         Ok(new_index)
     }
 
+    /// Refuse storing data in plain text storages of an encrypted segment
+    fn check_unencrypted(&self, storage: &str) -> OperationResult<()> {
+        if self.data_key.is_some() {
+            return Err(OperationError::ValidationError {
+                description: format!("Storage encryption is not supported with {storage}"),
+            });
+        }
+        Ok(())
+    }
+
     /// Operation wrapped, which handles previous and new errors in the segment, automatically
     /// updates versions and skips operations if the segment version is too old
     ///
@@ -548,6 +607,16 @@ impl Segment {
         self.payload_index.borrow().payload(point_offset)
     }
 
+    fn payload_projected_by_offset(
+        &self,
+        point_offset: PointOffsetType,
+        projection: PayloadProjection,
+    ) -> OperationResult<Payload> {
+        self.payload_index
+            .borrow()
+            .payload_projected(point_offset, projection)
+    }
+
     pub fn save_current_state(&self) -> OperationResult<()> {
         Self::save_state(&self.get_state(), &self.current_path)
     }
@@ -637,6 +706,62 @@ impl Segment {
         }
     }
 
+    /// Exact search, which scores vectors with the given `distance`
+    /// instead of the one configured for the vector.
+    fn search_with_distance(
+        &self,
+        vector_name: &str,
+        distance: Distance,
+        query_vectors: &[&QueryVector],
+        filter: Option<&Filter>,
+        top: usize,
+        query_context: &VectorQueryContext,
+    ) -> OperationResult<Vec<Vec<ScoredPointOffset>>> {
+        let Some(vector_config) = self.segment_config.vector_data.get(vector_name) else {
+            // Sparse vectors have no distance to override
+            return Err(OperationError::WrongSparse);
+        };
+        if !distance.is_applicable_to(vector_config.distance) {
+            return Err(OperationError::ValidationError {
+                description: format!(
+                    "Vector `{vector_name}` with {:?} distance can't be scored with {distance:?} distance",
+                    vector_config.distance,
+                ),
+            });
+        }
+        query_context.record_search_strategy("exact_distance_override");
+
+        let vector_storage = self.vector_data[vector_name].vector_storage.borrow();
+        let id_tracker = self.id_tracker.borrow();
+        let deleted_points = query_context
+            .deleted_points()
+            .unwrap_or(id_tracker.deleted_point_bitslice());
+        let filtered_ids = filter.map(|filter| self.payload_index.borrow().query_points(filter));
+        let is_stopped = query_context.is_stopped();
+
+        query_vectors
+            .iter()
+            .map(|&query_vector| {
+                let scorer = new_stoppable_raw_scorer_with_distance(
+                    query_vector.to_owned(),
+                    &vector_storage,
+                    deleted_points,
+                    &is_stopped,
+                    distance,
+                )?;
+                let scorer = new_metered_raw_scorer(
+                    scorer,
+                    query_context.usage(),
+                    vector_storage.available_vector_count(),
+                );
+                Ok(match &filtered_ids {
+                    Some(ids) => scorer.peek_top_iter(&mut ids.iter().copied(), top),
+                    None => scorer.peek_top_iter(&mut id_tracker.iter_ids(), top),
+                })
+            })
+            .collect()
+    }
+
     /// Converts raw ScoredPointOffset search result into ScoredPoint result
     fn process_search_result(
         &self,
@@ -669,11 +794,12 @@ impl Segment {
                     ))
                 })?;
                 let payload = if with_payload.enable {
-                    let initial_payload = self.payload_by_offset(point_offset)?;
-                    let processed_payload = if let Some(i) = &with_payload.payload_selector {
-                        i.process(initial_payload)
-                    } else {
-                        initial_payload
+                    let processed_payload = match &with_payload.payload_selector {
+                        Some(PayloadSelector::Patterns(selector)) => {
+                            self.payload_projected_by_offset(point_offset, selector.projection())?
+                        }
+                        Some(i) => i.process(self.payload_by_offset(point_offset)?),
+                        None => self.payload_by_offset(point_offset)?,
                     };
                     Some(processed_payload)
                 } else {
@@ -695,6 +821,20 @@ impl Segment {
                         }
                         Some(result.into())
                     }
+                    WithVector::Exclude(_) => {
+                        let mut result = NamedVectors::default();
+                        for vector_name in self.vector_data.keys() {
+                            if !with_vector.is_selected(vector_name) {
+                                continue;
+                            }
+                            if let Some(vector) =
+                                self.vector_by_offset(vector_name, point_offset)?
+                            {
+                                result.insert(vector_name.clone(), vector);
+                            }
+                        }
+                        Some(result.into())
+                    }
                 };
 
                 Ok(ScoredPoint {
@@ -974,6 +1114,33 @@ impl Segment {
         self.id_tracker.borrow().total_point_count()
     }
 
+    /// Statistics of the sparse vector index, computed once per version of the segment
+    fn sparse_vector_stats(
+        &self,
+        vector_name: &str,
+        vector_index: &VectorIndexEnum,
+    ) -> Option<SparseVectorStats> {
+        let mut cache = self.sparse_stats_cache.lock();
+        if let Some(&(version, stats)) = cache.get(vector_name) {
+            if version == self.version {
+                return Some(stats);
+            }
+        }
+
+        let stats = match vector_index {
+            VectorIndexEnum::SparseRam(sparse_index) => sparse_index.statistics(),
+            VectorIndexEnum::SparseImmutableRam(sparse_index) => sparse_index.statistics(),
+            VectorIndexEnum::SparseMmap(sparse_index) => sparse_index.statistics(),
+            VectorIndexEnum::Plain(_)
+            | VectorIndexEnum::HnswRam(_)
+            | VectorIndexEnum::HnswMmap(_) => {
+                return None;
+            }
+        };
+        cache.insert(vector_name.to_string(), (self.version, stats));
+        Some(stats)
+    }
+
     pub fn prefault_mmap_pages(&self) {
         let tasks: Vec<_> = self
             .vector_data
@@ -1049,13 +1216,23 @@ impl SegmentEntry for Segment {
         check_query_vectors(vector_name, query_vectors, &self.segment_config)?;
         let vector_data = &self.vector_data[vector_name];
         let vector_query_context = query_context.get_vector_context(vector_name);
-        let internal_results = vector_data.vector_index.borrow().search(
-            query_vectors,
-            filter,
-            top,
-            params,
-            &vector_query_context,
-        )?;
+        let internal_results = match params.and_then(|params| params.distance) {
+            Some(distance) => self.search_with_distance(
+                vector_name,
+                distance,
+                query_vectors,
+                filter,
+                top,
+                &vector_query_context,
+            )?,
+            None => vector_data.vector_index.borrow().search(
+                query_vectors,
+                filter,
+                top,
+                params,
+                &vector_query_context,
+            )?,
+        };
 
         check_stopped(&vector_query_context.is_stopped())?;
 
@@ -1103,6 +1280,7 @@ impl SegmentEntry for Segment {
                 self.handle_point_version_and_failure(op_num, Some(internal_id), |segment| {
                     // Mark point as deleted, drop mapping
                     segment.payload_index.borrow_mut().drop(internal_id)?;
+                    segment.blob_storage.borrow_mut().delete(internal_id)?;
                     segment.id_tracker.borrow_mut().drop(point_id)?;
 
                     // Before, we propagated point deletions to also delete its vectors. This turns
@@ -1253,6 +1431,42 @@ impl SegmentEntry for Segment {
         })
     }
 
+    fn set_blob(
+        &mut self,
+        op_num: SeqNumberType,
+        point_id: PointIdType,
+        blob: &[u8],
+    ) -> OperationResult<bool> {
+        self.check_unencrypted("blobs")?;
+        let internal_id = self.id_tracker.borrow().internal_id(point_id);
+        self.handle_point_version_and_failure(op_num, internal_id, |segment| match internal_id {
+            Some(internal_id) => {
+                segment.blob_storage.borrow_mut().set(internal_id, blob)?;
+                Ok((true, Some(internal_id)))
+            }
+            None => Err(OperationError::PointIdError {
+                missed_point_id: point_id,
+            }),
+        })
+    }
+
+    fn delete_blob(
+        &mut self,
+        op_num: SeqNumberType,
+        point_id: PointIdType,
+    ) -> OperationResult<bool> {
+        let internal_id = self.id_tracker.borrow().internal_id(point_id);
+        self.handle_point_version_and_failure(op_num, internal_id, |segment| match internal_id {
+            Some(internal_id) => {
+                let is_deleted = segment.blob_storage.borrow_mut().delete(internal_id)?;
+                Ok((is_deleted, Some(internal_id)))
+            }
+            None => Err(OperationError::PointIdError {
+                missed_point_id: point_id,
+            }),
+        })
+    }
+
     fn vector(&self, vector_name: &str, point_id: PointIdType) -> OperationResult<Option<Vector>> {
         check_vector_name(vector_name, &self.segment_config)?;
         let internal_id = self.lookup_internal_id(point_id)?;
@@ -1275,6 +1489,35 @@ impl SegmentEntry for Segment {
         self.payload_by_offset(internal_id)
     }
 
+    fn payloads(&self, point_ids: &[PointIdType]) -> OperationResult<Vec<Payload>> {
+        let internal_ids = point_ids
+            .iter()
+            .map(|&point_id| self.lookup_internal_id(point_id))
+            .collect::<OperationResult<Vec<_>>>()?;
+        self.payload_index.borrow().payloads(&internal_ids)
+    }
+
+    fn payload_projected(
+        &self,
+        point_id: PointIdType,
+        projection: PayloadProjection,
+    ) -> OperationResult<Payload> {
+        let internal_id = self.lookup_internal_id(point_id)?;
+        self.payload_projected_by_offset(internal_id, projection)
+    }
+
+    fn blob(
+        &self,
+        point_id: PointIdType,
+        offset: usize,
+        limit: usize,
+    ) -> OperationResult<Option<BlobChunk>> {
+        let internal_id = self.lookup_internal_id(point_id)?;
+        let blob_storage = self.blob_storage.borrow();
+        let blob = blob_storage.get(internal_id)?;
+        Ok(blob.map(|blob| BlobChunk::from_blob(blob, offset, limit)))
+    }
+
     fn iter_points(&self) -> Box<dyn Iterator<Item = PointIdType> + '_> {
         // Sorry for that, but I didn't find any way easier.
         // If you try simply return iterator - it won't work because AtomicRef should exist
@@ -1387,6 +1630,7 @@ impl SegmentEntry for Segment {
                 let num_vectors = vector_storage.available_vector_count();
                 let vector_index = vector_data.vector_index.borrow();
                 let is_indexed = vector_index.is_index();
+                let sparse_stats = self.sparse_vector_stats(key, &vector_index);
                 let vector_data_info = VectorDataInfo {
                     num_vectors,
                     num_indexed_vectors: if is_indexed {
@@ -1395,6 +1639,7 @@ impl SegmentEntry for Segment {
                         0
                     },
                     num_deleted_vectors: vector_storage.deleted_vector_count(),
+                    sparse_stats,
                 };
                 (key.to_string(), vector_data_info)
             })
@@ -1415,7 +1660,11 @@ impl SegmentEntry for Segment {
             num_indexed_vectors,
             num_points: self.available_point_count(),
             num_deleted_vectors: self.deleted_point_count(),
-            ram_usage_bytes: 0,  // ToDo: Implement
+            ram_usage_bytes: self
+                .vector_data
+                .values()
+                .map(VectorData::ram_usage_bytes)
+                .sum(),
             disk_usage_bytes: 0, // ToDo: Implement
             is_appendable: self.appendable_flag,
             index_schema: schema,
@@ -1461,13 +1710,14 @@ impl SegmentEntry for Segment {
         let current_path = self.current_path.clone();
         let id_tracker_mapping_flusher = self.id_tracker.borrow().mapping_flusher();
         let payload_index_flusher = self.payload_index.borrow().flusher();
+        let blob_storage_flusher = self.blob_storage.borrow().flusher();
         let id_tracker_versions_flusher = self.id_tracker.borrow().versions_flusher();
         let persisted_version = self.persisted_version.clone();
 
         // Flush order is important:
         //
         // 1. Flush id mapping. So during recovery the point will be recovered er in proper segment.
-        // 2. Flush vectors and payloads.
+        // 2. Flush vectors, payloads and blobs.
         // 3. Flush id versions last. So presence of version indicates that all other data is up-to-date.
         //
         // Example of recovery from WAL in case of partial flush:
@@ -1524,6 +1774,9 @@ impl SegmentEntry for Segment {
             payload_index_flusher().map_err(|err| {
                 OperationError::service_error(format!("Failed to flush payload_index: {err}"))
             })?;
+            blob_storage_flusher().map_err(|err| {
+                OperationError::service_error(format!("Failed to flush blob_storage: {err}"))
+            })?;
             // Id Tracker contains versions of points. We need to flush it after vector_storage and payload_index flush.
             // This is because vector_storage and payload_index flush are not atomic.
             // If payload or vector flush fails, we will be able to recover data from WAL.
@@ -1587,6 +1840,7 @@ impl SegmentEntry for Segment {
         key: PayloadKeyTypeRef,
         field_type: Option<&PayloadFieldSchema>,
     ) -> OperationResult<bool> {
+        self.check_unencrypted("payload indexes")?;
         self.handle_segment_version_and_failure(op_num, |segment| match field_type {
             Some(schema) => {
                 segment
@@ -1753,6 +2007,24 @@ impl SegmentEntry for Segment {
             )?;
         }
 
+        for file in self.blob_storage.borrow().files() {
+            utils::tar::append_file_relative_to_base(
+                &mut builder,
+                &self.current_path,
+                &file,
+                &files,
+            )?;
+        }
+
+        for file in self.id_tracker.borrow().files() {
+            utils::tar::append_file_relative_to_base(
+                &mut builder,
+                &self.current_path,
+                &file,
+                &files,
+            )?;
+        }
+
         utils::tar::append_file(
             &mut builder,
             &self.current_path.join(SEGMENT_STATE_FILE),
@@ -1765,6 +2037,15 @@ impl SegmentEntry for Segment {
             &files.join(VERSION_FILE),
         )?;
 
+        let data_key_path = self.current_path.join(encryption::DATA_KEY_FILE);
+        if data_key_path.exists() {
+            utils::tar::append_file(
+                &mut builder,
+                &data_key_path,
+                &files.join(encryption::DATA_KEY_FILE),
+            )?;
+        }
+
         builder.finish()?;
 
         // remove tmp directory in background
@@ -1800,6 +2081,37 @@ impl SegmentEntry for Segment {
         }
     }
 
+    fn vector_index_info(&self) -> Vec<VectorIndexInfo> {
+        let mut info: Vec<_> = self
+            .vector_data
+            .iter()
+            .map(|(vector_name, vector_data)| {
+                let vector_index = vector_data.vector_index.borrow();
+                let (index_type, hnsw) = match vector_index.deref() {
+                    VectorIndexEnum::Plain(_) => (VectorIndexType::Plain, None),
+                    VectorIndexEnum::HnswRam(index) => (VectorIndexType::Hnsw, index.graph_info()),
+                    VectorIndexEnum::HnswMmap(index) => (VectorIndexType::Hnsw, index.graph_info()),
+                    VectorIndexEnum::SparseRam(_)
+                    | VectorIndexEnum::SparseImmutableRam(_)
+                    | VectorIndexEnum::SparseMmap(_) => (VectorIndexType::Sparse, None),
+                };
+                VectorIndexInfo {
+                    vector_name: vector_name.clone(),
+                    index_type,
+                    indexed_vectors_count: vector_index.indexed_vector_count(),
+                    hnsw,
+                    quantization: vector_data
+                        .quantized_vectors
+                        .borrow()
+                        .as_ref()
+                        .map(QuantizedVectors::info),
+                }
+            })
+            .collect();
+        info.sort_by(|a, b| a.vector_name.cmp(&b.vector_name));
+        info
+    }
+
     fn fill_query_context(&self, query_context: &mut QueryContext) {
         query_context.add_available_point_count(self.available_point_count());
 
@@ -1897,6 +2209,8 @@ mod tests {
             )]),
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_columns: vec![],
+            storage_cipher: None,
         };
         let mut segment = build_segment(dir.path(), &config, true).unwrap();
 
@@ -1971,6 +2285,8 @@ mod tests {
             )]),
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_columns: vec![],
+            storage_cipher: None,
         };
 
         let mut segment = build_segment(dir.path(), &config, true).unwrap();
@@ -2063,6 +2379,8 @@ mod tests {
             )]),
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_columns: vec![],
+            storage_cipher: None,
         };
 
         let mut segment = build_segment(segment_base_dir.path(), &config, true).unwrap();
@@ -2101,6 +2419,7 @@ mod tests {
 
         let restored_segment = load_segment(
             &snapshot_dir.path().join(segment_id),
+            None,
             &AtomicBool::new(false),
         )
         .unwrap()
@@ -2160,6 +2479,8 @@ mod tests {
             )]),
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_columns: vec![],
+            storage_cipher: None,
         };
 
         let mut segment = build_segment(segment_base_dir.path(), &config, true).unwrap();
@@ -2194,6 +2515,8 @@ mod tests {
             )]),
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_columns: vec![],
+            storage_cipher: None,
         };
         let mut segment = build_segment(dir.path(), &config, true).unwrap();
 
@@ -2289,6 +2612,8 @@ mod tests {
             )]),
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_columns: vec![],
+            storage_cipher: None,
         };
         let mut segment = build_segment(dir.path(), &config, true).unwrap();
 
@@ -2357,6 +2682,8 @@ mod tests {
             ]),
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_columns: vec![],
+            storage_cipher: None,
         };
         let mut segment = build_segment(dir.path(), &config, true).unwrap();
 
@@ -2467,6 +2794,8 @@ mod tests {
             ]),
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_columns: vec![],
+            storage_cipher: None,
         };
         let mut segment = build_segment(dir.path(), &config, true).unwrap();
 
@@ -2629,6 +2958,8 @@ mod tests {
             )]),
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_columns: vec![],
+            storage_cipher: None,
         };
         let mut segment = build_segment(dir.path(), &config, true).unwrap();
         segment
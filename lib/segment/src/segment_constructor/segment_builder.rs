@@ -1,10 +1,14 @@
 use std::cmp;
 use std::collections::HashMap;
+use std::mem::size_of;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
 use common::cpu::CpuPermit;
+use common::io_throttle;
+use common::types::PointOffsetType;
+use half::f16;
 
 use super::get_vector_storage_path;
 use crate::common::error_logging::LogError;
@@ -14,7 +18,10 @@ use crate::index::hnsw_index::num_rayon_threads;
 use crate::index::{PayloadIndex, VectorIndex};
 use crate::segment::Segment;
 use crate::segment_constructor::{build_segment, load_segment};
-use crate::types::{Indexes, PayloadFieldSchema, PayloadKeyType, SegmentConfig};
+use crate::types::{
+    Indexes, PayloadContainer, PayloadFieldSchema, PayloadKeyType, SegmentConfig,
+    VectorStorageDatatype,
+};
 use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
 use crate::vector_storage::VectorStorage;
 
@@ -24,6 +31,10 @@ pub struct SegmentBuilder {
     pub destination_path: PathBuf,
     pub temp_path: PathBuf,
     pub indexed_fields: HashMap<PayloadKeyType, PayloadFieldSchema>,
+    /// Payload field, by which points are grouped in the constructed segment.
+    /// Points with the same value are stored next to each other, so that filtered searches by
+    /// this field touch fewer pages.
+    pub defragment_key: Option<PayloadKeyType>,
 }
 
 impl SegmentBuilder {
@@ -42,9 +53,62 @@ impl SegmentBuilder {
             destination_path,
             temp_path,
             indexed_fields: Default::default(),
+            defragment_key: None,
         })
     }
 
+    /// Update current segment builder with all (not deleted) vectors and payload from `segments`.
+    ///
+    /// If `defragment_key` is set, points of all segments are grouped by its value, otherwise
+    /// segments are added one after another.
+    ///
+    /// # Result
+    ///
+    /// * `bool` - if `true` - data successfully added, if `false` - process was interrupted
+    ///
+    pub fn update(&mut self, segments: &[&Segment], stopped: &AtomicBool) -> OperationResult<bool> {
+        let Some(defragment_key) = self.defragment_key.clone() else {
+            for segment in segments {
+                if !self.update_from(segment, stopped)? {
+                    return Ok(false);
+                }
+            }
+            return Ok(true);
+        };
+
+        // Value of the key, index of the segment and internal id of each point
+        let mut points = Vec::new();
+        for (segment_index, segment) in segments.iter().enumerate() {
+            check_process_stopped(stopped)?;
+            let payload_index = segment.payload_index.borrow();
+            for internal_id in segment.id_tracker.borrow().iter_ids() {
+                let payload = payload_index.payload(internal_id)?;
+                let value = payload
+                    .get_value(&defragment_key)
+                    .first()
+                    .and_then(|value| value.as_str())
+                    .map(str::to_owned);
+                points.push((value, segment_index, internal_id));
+            }
+        }
+        // Sort is stable, so points of a segment with the same value keep their order
+        points.sort_by(|(value_a, index_a, _), (value_b, index_b, _)| {
+            (value_a, index_a).cmp(&(value_b, index_b))
+        });
+
+        for run in points.chunk_by(|(_, index_a, _), (_, index_b, _)| index_a == index_b) {
+            let segment = segments[run[0].1];
+            let internal_ids: Vec<_> = run.iter().map(|(_, _, internal_id)| *internal_id).collect();
+            self.copy_points(segment, &internal_ids, stopped)?;
+        }
+
+        for segment in segments {
+            self.update_metadata(segment)?;
+        }
+
+        Ok(true)
+    }
+
     /// Update current segment builder with all (not deleted) vectors and payload form `other` segment
     /// Perform index building at the end of update
     ///
@@ -57,15 +121,20 @@ impl SegmentBuilder {
     /// * `bool` - if `true` - data successfully added, if `false` - process was interrupted
     ///
     pub fn update_from(&mut self, other: &Segment, stopped: &AtomicBool) -> OperationResult<bool> {
-        let self_segment = match &mut self.segment {
-            Some(segment) => segment,
-            None => {
-                return Err(OperationError::service_error(
-                    "Segment building error: created segment not found",
-                ));
-            }
-        };
-        self_segment.version = Some(cmp::max(self_segment.version(), other.version()));
+        let other_ids: Vec<_> = other.id_tracker.borrow().iter_ids().collect();
+        self.copy_points(other, &other_ids, stopped)?;
+        self.update_metadata(other)?;
+        Ok(true)
+    }
+
+    /// Copy vectors, payload and blobs of points with `other_ids` from `other` segment, in this order
+    fn copy_points(
+        &mut self,
+        other: &Segment,
+        other_ids: &[PointOffsetType],
+        stopped: &AtomicBool,
+    ) -> OperationResult<()> {
+        let self_segment = self.segment_mut()?;
 
         let other_id_tracker = other.id_tracker.borrow();
         let other_vector_storages: HashMap<_, _> = other
@@ -76,6 +145,7 @@ impl SegmentBuilder {
             })
             .collect();
         let other_payload_index = other.payload_index.borrow();
+        let other_blob_storage = other.blob_storage.borrow();
 
         let mut id_tracker = self_segment.id_tracker.borrow_mut();
         let mut vector_storages: HashMap<_, _> = self_segment
@@ -89,6 +159,7 @@ impl SegmentBuilder {
             })
             .collect();
         let mut payload_index = self_segment.payload_index.borrow_mut();
+        let mut blob_storage = self_segment.blob_storage.borrow_mut();
 
         if vector_storages.len() != other_vector_storages.len() {
             return Err(OperationError::service_error(
@@ -104,9 +175,20 @@ impl SegmentBuilder {
                     "Cannot update from other segment because if missing vector name {vector_name}"
                 ))
             })?;
+            // Copied vectors are read and written once, throttle by their approximate size
+            let vector_size = other_vector_storage.vector_dim()
+                * match other_vector_storage.datatype() {
+                    VectorStorageDatatype::Float32 => size_of::<f32>(),
+                    VectorStorageDatatype::Float16 => size_of::<f16>(),
+                    VectorStorageDatatype::Float64 => size_of::<f64>(),
+                    VectorStorageDatatype::Uint8 => size_of::<u8>(),
+                };
             let internal_range = vector_storage.update_from(
                 other_vector_storage,
-                &mut other_id_tracker.iter_ids(),
+                &mut other_ids
+                    .iter()
+                    .copied()
+                    .inspect(|_| io_throttle::consume(vector_size as u64)),
                 stopped,
             )?;
             match new_internal_range.clone() {
@@ -122,7 +204,7 @@ impl SegmentBuilder {
         }
 
         if let Some(new_internal_range) = new_internal_range {
-            let internal_id_iter = new_internal_range.zip(other_id_tracker.iter_ids());
+            let internal_id_iter = new_internal_range.zip(other_ids.iter().copied());
 
             for (new_internal_id, old_internal_id) in internal_id_iter {
                 check_process_stopped(stopped)?;
@@ -156,6 +238,9 @@ impl SegmentBuilder {
                         if !other_payload.is_empty() {
                             payload_index.assign(new_internal_id, &other_payload, &None)?;
                         }
+                        if let Some(other_blob) = other_blob_storage.get(old_internal_id)? {
+                            blob_storage.set(new_internal_id, other_blob)?;
+                        }
                     }
                     Some(existing_internal_id) => {
                         // Point exists in both: newly constructed and old segments, so we need to merge them
@@ -168,11 +253,15 @@ impl SegmentBuilder {
                             id_tracker.set_link(external_id, new_internal_id)?;
                             id_tracker.set_internal_version(new_internal_id, other_version)?;
                             payload_index.drop(existing_internal_id)?;
+                            blob_storage.delete(existing_internal_id)?;
                             let other_payload = other_payload_index.payload(old_internal_id)?;
                             // Propagate payload to new segment
                             if !other_payload.is_empty() {
                                 payload_index.assign(new_internal_id, &other_payload, &None)?;
                             }
+                            if let Some(other_blob) = other_blob_storage.get(old_internal_id)? {
+                                blob_storage.set(new_internal_id, other_blob)?;
+                            }
                             existing_internal_id
                         } else {
                             // Old version is still good, do not move anything else
@@ -187,11 +276,24 @@ impl SegmentBuilder {
             }
         }
 
+        Ok(())
+    }
+
+    /// Take version and indexed fields of `other` segment into account
+    fn update_metadata(&mut self, other: &Segment) -> OperationResult<()> {
+        let self_segment = self.segment_mut()?;
+        self_segment.version = Some(cmp::max(self_segment.version(), other.version()));
+
         for (field, payload_schema) in other.payload_index.borrow().indexed_fields() {
             self.indexed_fields.insert(field, payload_schema);
         }
+        Ok(())
+    }
 
-        Ok(true)
+    fn segment_mut(&mut self) -> OperationResult<&mut Segment> {
+        self.segment.as_mut().ok_or_else(|| {
+            OperationError::service_error("Segment building error: created segment not found")
+        })
     }
 
     pub fn build(
@@ -199,6 +301,12 @@ impl SegmentBuilder {
         permit: CpuPermit,
         stopped: &AtomicBool,
     ) -> Result<Segment, OperationError> {
+        // Key of the collection is not stored with the segment, pass it to the loaded one
+        let storage_cipher = self
+            .segment
+            .as_ref()
+            .and_then(|segment| segment.segment_config.storage_cipher.clone());
+
         {
             // Arc permit to share it with each vector store
             let permit = Arc::new(permit);
@@ -238,12 +346,13 @@ impl SegmentBuilder {
         std::fs::rename(&self.temp_path, &self.destination_path)
             .describe("Moving segment data after optimization")?;
 
-        let loaded_segment = load_segment(&self.destination_path, stopped)?.ok_or_else(|| {
-            OperationError::service_error(format!(
-                "Segment loading error: {}",
-                self.destination_path.display()
-            ))
-        })?;
+        let loaded_segment = load_segment(&self.destination_path, storage_cipher, stopped)?
+            .ok_or_else(|| {
+                OperationError::service_error(format!(
+                    "Segment loading error: {}",
+                    self.destination_path.display()
+                ))
+            })?;
         Ok(loaded_segment)
     }
 
@@ -252,6 +361,10 @@ impl SegmentBuilder {
 
         for (vector_name, vector_data) in &mut segment.vector_data {
             let max_threads = if let Some(config) = config.vector_data.get(vector_name) {
+                // appendable segments maintain quantized vectors on their own
+                if config.is_appendable() {
+                    continue;
+                }
                 match &config.index {
                     Indexes::Hnsw(hnsw) => num_rayon_threads(hnsw.max_indexing_threads),
                     _ => 1,
@@ -12,11 +12,14 @@ use parking_lot::Mutex;
 use serde::Deserialize;
 use uuid::Uuid;
 
+use crate::blob_storage::BlobStorage;
+use crate::common::encryption::{self, StorageCipher};
 use crate::common::operation_error::{check_process_stopped, OperationError, OperationResult};
-use crate::common::rocksdb_wrapper::{open_db, DB_VECTOR_CF};
+use crate::common::rocksdb_wrapper::{check_db_exists, open_db, DB_VECTOR_CF};
 use crate::data_types::vectors::DEFAULT_VECTOR_NAME;
+use crate::id_tracker::append_only_id_tracker::AppendOnlyIdTracker;
 use crate::id_tracker::simple_id_tracker::SimpleIdTracker;
-use crate::id_tracker::IdTracker;
+use crate::id_tracker::{IdTracker, IdTrackerSS};
 use crate::index::hnsw_index::graph_links::{GraphLinksMmap, GraphLinksRam};
 use crate::index::hnsw_index::hnsw::HNSWIndex;
 use crate::index::plain_payload_index::PlainIndex;
@@ -24,6 +27,8 @@ use crate::index::sparse_index::sparse_index_config::SparseIndexType;
 use crate::index::sparse_index::sparse_vector_index::SparseVectorIndex;
 use crate::index::struct_payload_index::StructPayloadIndex;
 use crate::index::VectorIndexEnum;
+use crate::payload_storage::append_only_payload_storage::AppendOnlyPayloadStorage;
+use crate::payload_storage::mmap_payload_storage::MmapPayloadStorage;
 use crate::payload_storage::on_disk_payload_storage::OnDiskPayloadStorage;
 use crate::payload_storage::simple_payload_storage::SimplePayloadStorage;
 use crate::segment::{Segment, SegmentVersion, VectorData, SEGMENT_STATE_FILE};
@@ -33,14 +38,15 @@ use crate::types::{
 };
 use crate::vector_storage::dense::appendable_mmap_dense_vector_storage::{
     open_appendable_memmap_vector_storage, open_appendable_memmap_vector_storage_byte,
-    open_appendable_memmap_vector_storage_half,
+    open_appendable_memmap_vector_storage_double, open_appendable_memmap_vector_storage_half,
 };
 use crate::vector_storage::dense::memmap_dense_vector_storage::{
-    open_memmap_vector_storage, open_memmap_vector_storage_byte, open_memmap_vector_storage_half,
+    open_memmap_vector_storage, open_memmap_vector_storage_byte, open_memmap_vector_storage_double,
+    open_memmap_vector_storage_half,
 };
 use crate::vector_storage::dense::simple_dense_vector_storage::{
-    open_simple_dense_byte_vector_storage, open_simple_dense_half_vector_storage,
-    open_simple_dense_vector_storage,
+    open_simple_dense_byte_vector_storage, open_simple_dense_double_vector_storage,
+    open_simple_dense_half_vector_storage, open_simple_dense_vector_storage,
 };
 use crate::vector_storage::multi_dense::appendable_mmap_multi_dense_vector_storage::{
     open_appendable_memmap_multi_vector_storage, open_appendable_memmap_multi_vector_storage_byte,
@@ -81,6 +87,12 @@ pub fn get_vector_index_path(segment_path: &Path, vector_name: &str) -> PathBuf
     segment_path.join(get_vector_name_with_prefix(VECTOR_INDEX_PATH, vector_name))
 }
 
+fn multi_vector_datatype_unsupported(datatype: VectorStorageDatatype) -> OperationError {
+    OperationError::ValidationError {
+        description: format!("Datatype {datatype:?} is not supported for multivectors"),
+    }
+}
+
 fn create_segment(
     version: Option<SeqNumberType>,
     segment_path: &Path,
@@ -98,15 +110,36 @@ fn create_segment(
                 .map(|vector_name| get_vector_name_with_prefix(DB_VECTOR_CF, vector_name)),
         )
         .collect();
+    let data_key = encryption::load_segment_key(
+        segment_path,
+        config.storage_cipher.as_deref(),
+        !check_db_exists(segment_path),
+    )?;
+    if data_key.is_some() {
+        encryption::check_supported(config)?;
+    }
     let database = open_db(segment_path, &vector_db_names)
         .map_err(|err| OperationError::service_error(format!("RocksDB open error: {err}")))?;
 
     let payload_storage = match config.payload_storage_type {
-        PayloadStorageType::InMemory => sp(SimplePayloadStorage::open(database.clone())?.into()),
-        PayloadStorageType::OnDisk => sp(OnDiskPayloadStorage::open(database.clone())?.into()),
+        PayloadStorageType::InMemory => {
+            sp(SimplePayloadStorage::open(database.clone(), data_key.clone())?.into())
+        }
+        PayloadStorageType::OnDisk => {
+            sp(OnDiskPayloadStorage::open(database.clone(), data_key.clone())?.into())
+        }
+        PayloadStorageType::AppendOnly => sp(AppendOnlyPayloadStorage::open(segment_path)?.into()),
+        PayloadStorageType::Mmap => sp(MmapPayloadStorage::open(segment_path)?.into()),
     };
 
-    let id_tracker = sp(SimpleIdTracker::open(database.clone())?);
+    let id_tracker: Arc<AtomicRefCell<IdTrackerSS>> = match config.payload_storage_type {
+        PayloadStorageType::InMemory | PayloadStorageType::OnDisk => {
+            sp(SimpleIdTracker::open(database.clone())?)
+        }
+        PayloadStorageType::AppendOnly | PayloadStorageType::Mmap => {
+            sp(AppendOnlyIdTracker::open(segment_path)?)
+        }
+    };
 
     let appendable_flag = config
         .vector_data
@@ -121,12 +154,16 @@ fn create_segment(
         .all(|v| v);
 
     let payload_index_path = segment_path.join(PAYLOAD_INDEX_PATH);
-    let payload_index: Arc<AtomicRefCell<StructPayloadIndex>> = sp(StructPayloadIndex::open(
+    let mut payload_index = StructPayloadIndex::open(
         payload_storage,
         id_tracker.clone(),
         &payload_index_path,
         appendable_flag,
-    )?);
+    )?;
+    payload_index.open_columns(&config.payload_columns)?;
+    let payload_index: Arc<AtomicRefCell<StructPayloadIndex>> = sp(payload_index);
+
+    let blob_storage = sp(BlobStorage::open(segment_path)?);
 
     let mut vector_data = HashMap::new();
     for (vector_name, vector_config) in &config.vector_data {
@@ -144,6 +181,7 @@ fn create_segment(
                         VectorStorageDatatype::Float32 => open_simple_multi_dense_vector_storage(
                             database.clone(),
                             &db_column_name,
+                            data_key.clone(),
                             vector_config.size,
                             vector_config.distance,
                             *multi_vec_config,
@@ -153,6 +191,7 @@ fn create_segment(
                             open_simple_multi_dense_vector_storage_byte(
                                 database.clone(),
                                 &db_column_name,
+                                data_key.clone(),
                                 vector_config.size,
                                 vector_config.distance,
                                 *multi_vec_config,
@@ -163,18 +202,23 @@ fn create_segment(
                             open_simple_multi_dense_vector_storage_half(
                                 database.clone(),
                                 &db_column_name,
+                                data_key.clone(),
                                 vector_config.size,
                                 vector_config.distance,
                                 *multi_vec_config,
                                 stopped,
                             )?
                         }
+                        VectorStorageDatatype::Float64 => {
+                            return Err(multi_vector_datatype_unsupported(storage_element_type));
+                        }
                     }
                 } else {
                     match storage_element_type {
                         VectorStorageDatatype::Float32 => open_simple_dense_vector_storage(
                             database.clone(),
                             &db_column_name,
+                            data_key.clone(),
                             vector_config.size,
                             vector_config.distance,
                             stopped,
@@ -182,6 +226,7 @@ fn create_segment(
                         VectorStorageDatatype::Uint8 => open_simple_dense_byte_vector_storage(
                             database.clone(),
                             &db_column_name,
+                            data_key.clone(),
                             vector_config.size,
                             vector_config.distance,
                             stopped,
@@ -189,6 +234,15 @@ fn create_segment(
                         VectorStorageDatatype::Float16 => open_simple_dense_half_vector_storage(
                             database.clone(),
                             &db_column_name,
+                            data_key.clone(),
+                            vector_config.size,
+                            vector_config.distance,
+                            stopped,
+                        )?,
+                        VectorStorageDatatype::Float64 => open_simple_dense_double_vector_storage(
+                            database.clone(),
+                            &db_column_name,
+                            data_key.clone(),
                             vector_config.size,
                             vector_config.distance,
                             stopped,
@@ -225,6 +279,9 @@ fn create_segment(
                                 *multi_vec_config,
                             )?
                         }
+                        VectorStorageDatatype::Float64 => {
+                            return Err(multi_vector_datatype_unsupported(storage_element_type));
+                        }
                     }
                 } else {
                     match storage_element_type {
@@ -243,6 +300,11 @@ fn create_segment(
                             vector_config.size,
                             vector_config.distance,
                         )?,
+                        VectorStorageDatatype::Float64 => open_memmap_vector_storage_double(
+                            &vector_storage_path,
+                            vector_config.size,
+                            vector_config.distance,
+                        )?,
                     }
                 }
             }
@@ -274,6 +336,9 @@ fn create_segment(
                                 *multi_vec_config,
                             )?
                         }
+                        VectorStorageDatatype::Float64 => {
+                            return Err(multi_vector_datatype_unsupported(storage_element_type));
+                        }
                     }
                 } else {
                     match storage_element_type {
@@ -294,6 +359,13 @@ fn create_segment(
                                 vector_config.distance,
                             )?
                         }
+                        VectorStorageDatatype::Float64 => {
+                            open_appendable_memmap_vector_storage_double(
+                                &vector_storage_path,
+                                vector_config.size,
+                                vector_config.distance,
+                            )?
+                        }
                     }
                 }
             }
@@ -323,11 +395,20 @@ fn create_segment(
         });
 
         let vector_index: Arc<AtomicRefCell<VectorIndexEnum>> = match &vector_config.index {
-            Indexes::Plain {} => sp(VectorIndexEnum::Plain(PlainIndex::new(
-                id_tracker.clone(),
-                vector_storage.clone(),
-                payload_index.clone(),
-            ))),
+            Indexes::Plain {} => {
+                let plain_index = PlainIndex::new(
+                    id_tracker.clone(),
+                    vector_storage.clone(),
+                    payload_index.clone(),
+                );
+                // Quantization of appendable segments is maintained on every update
+                let plain_index = if vector_config.is_appendable() {
+                    plain_index.with_quantization(config.quantization_config(vector_name))
+                } else {
+                    plain_index
+                };
+                sp(VectorIndexEnum::Plain(plain_index))
+            }
             Indexes::Hnsw(vector_hnsw_config) => sp(if vector_hnsw_config.on_disk == Some(true) {
                 VectorIndexEnum::HnswMmap(HNSWIndex::<GraphLinksMmap>::open(
                     &vector_index_path,
@@ -435,14 +516,25 @@ fn create_segment(
         segment_type,
         appendable_flag,
         payload_index,
+        blob_storage,
         segment_config: config.clone(),
+        data_key,
         error_status: None,
         database,
         flush_thread: Mutex::new(None),
+        sparse_stats_cache: Default::default(),
     })
 }
 
-pub fn load_segment(path: &Path, stopped: &AtomicBool) -> OperationResult<Option<Segment>> {
+/// Load segment from `path`.
+///
+/// `storage_cipher` is the key of the collection, which the data key of an encrypted segment is
+/// wrapped with.
+pub fn load_segment(
+    path: &Path,
+    storage_cipher: Option<Arc<StorageCipher>>,
+    stopped: &AtomicBool,
+) -> OperationResult<Option<Segment>> {
     if path
         .extension()
         .and_then(|ext| ext.to_str())
@@ -493,7 +585,9 @@ pub fn load_segment(path: &Path, stopped: &AtomicBool) -> OperationResult<Option
         SegmentVersion::save(path)?
     }
 
-    let segment_state = Segment::load_state(path)?;
+    let mut segment_state = Segment::load_state(path)?;
+    // Key of the collection is never stored with the segment
+    segment_state.config.storage_cipher = storage_cipher;
 
     let segment = create_segment(segment_state.version, path, &segment_state.config, stopped)?;
 
@@ -0,0 +1,25 @@
+//! Metrics for double-precision vectors.
+//!
+//! Elements are accumulated in `f64`, only the final score is converted into [`ScoreType`].
+//!
+//! [`ScoreType`]: common::types::ScoreType
+
+pub mod simple_cosine;
+pub mod simple_dot;
+pub mod simple_euclid;
+pub mod simple_manhattan;
+
+use self::simple_cosine::cosine_preprocess_double;
+use crate::data_types::vectors::DenseDoubleVector;
+use crate::types::Distance;
+
+/// Preprocess double precision vector for the distance, as [`Metric::preprocess`] does for single
+/// precision ones.
+///
+/// [`Metric::preprocess`]: crate::spaces::metric::Metric::preprocess
+pub fn preprocess_double(distance: Distance, vector: DenseDoubleVector) -> DenseDoubleVector {
+    match distance {
+        Distance::Cosine => cosine_preprocess_double(vector),
+        Distance::Euclid | Distance::Dot | Distance::Manhattan => vector,
+    }
+}
@@ -0,0 +1,43 @@
+use common::types::ScoreType;
+
+use super::simple_dot::dot_similarity_double;
+use crate::data_types::vectors::{
+    DenseDoubleVector, DenseVector, VectorElementType, VectorElementTypeDouble,
+};
+use crate::spaces::metric::Metric;
+use crate::spaces::simple::CosineMetric;
+use crate::types::Distance;
+
+impl Metric<VectorElementTypeDouble> for CosineMetric {
+    fn distance() -> Distance {
+        Distance::Cosine
+    }
+
+    fn similarity(v1: &[VectorElementTypeDouble], v2: &[VectorElementTypeDouble]) -> ScoreType {
+        dot_similarity_double(v1, v2)
+    }
+
+    fn preprocess(vector: DenseVector) -> DenseVector {
+        <CosineMetric as Metric<VectorElementType>>::preprocess(vector)
+    }
+}
+
+pub fn cosine_preprocess_double(vector: DenseDoubleVector) -> DenseDoubleVector {
+    let length = vector.iter().map(|x| x * x).sum::<f64>().sqrt();
+    if length < f64::EPSILON || length == 1.0 {
+        return vector;
+    }
+    vector.iter().map(|x| x / length).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cosine_preprocess_double() {
+        // Normalized in double precision, `0.6` and `0.8` are not representable in `f32`
+        let vector = cosine_preprocess_double(vec![3.0, 4.0, 0.0]);
+        assert_eq!(vector, vec![0.6, 0.8, 0.0]);
+    }
+}
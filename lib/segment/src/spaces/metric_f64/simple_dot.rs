@@ -0,0 +1,45 @@
+use common::types::ScoreType;
+
+use crate::data_types::vectors::{DenseVector, VectorElementTypeDouble};
+use crate::spaces::metric::Metric;
+use crate::spaces::simple::DotProductMetric;
+use crate::types::Distance;
+
+impl Metric<VectorElementTypeDouble> for DotProductMetric {
+    fn distance() -> Distance {
+        Distance::Dot
+    }
+
+    fn similarity(v1: &[VectorElementTypeDouble], v2: &[VectorElementTypeDouble]) -> ScoreType {
+        dot_similarity_double(v1, v2)
+    }
+
+    fn preprocess(vector: DenseVector) -> DenseVector {
+        vector
+    }
+}
+
+pub fn dot_similarity_double(
+    v1: &[VectorElementTypeDouble],
+    v2: &[VectorElementTypeDouble],
+) -> ScoreType {
+    v1.iter().zip(v2).map(|(a, b)| a * b).sum::<f64>() as ScoreType
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spaces::simple::dot_similarity;
+
+    #[test]
+    fn test_dot_similarity_double_precision() {
+        // Large terms cancel each other, f32 accumulation loses the small ones
+        let v1: Vec<f64> = vec![1e8, 1.0, -1e8, 1.0];
+        let v2: Vec<f64> = vec![1.0, 1.0, 1.0, 1.0];
+        assert_eq!(dot_similarity_double(&v1, &v2), 2.0);
+
+        let v1_f32: Vec<f32> = v1.iter().map(|&x| x as f32).collect();
+        let v2_f32: Vec<f32> = v2.iter().map(|&x| x as f32).collect();
+        assert_ne!(dot_similarity(&v1_f32, &v2_f32), 2.0);
+    }
+}
@@ -0,0 +1,27 @@
+use common::types::ScoreType;
+
+use crate::data_types::vectors::{DenseVector, VectorElementTypeDouble};
+use crate::spaces::metric::Metric;
+use crate::spaces::simple::ManhattanMetric;
+use crate::types::Distance;
+
+impl Metric<VectorElementTypeDouble> for ManhattanMetric {
+    fn distance() -> Distance {
+        Distance::Manhattan
+    }
+
+    fn similarity(v1: &[VectorElementTypeDouble], v2: &[VectorElementTypeDouble]) -> ScoreType {
+        manhattan_similarity_double(v1, v2)
+    }
+
+    fn preprocess(vector: DenseVector) -> DenseVector {
+        vector
+    }
+}
+
+pub fn manhattan_similarity_double(
+    v1: &[VectorElementTypeDouble],
+    v2: &[VectorElementTypeDouble],
+) -> ScoreType {
+    -v1.iter().zip(v2).map(|(a, b)| (a - b).abs()).sum::<f64>() as ScoreType
+}
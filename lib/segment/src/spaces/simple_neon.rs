@@ -1,3 +1,7 @@
+//! NEON kernels of distances, for aarch64 CPUs such as Graviton.
+//!
+//! SVE intrinsics are not available in stable Rust, so wider vectors of SVE CPUs are not used yet.
+
 #[cfg(target_feature = "neon")]
 use std::arch::aarch64::*;
 
@@ -41,9 +45,17 @@ pub(crate) unsafe fn euclid_similarity_neon(
         ptr2 = ptr2.add(16);
         i += 16;
     }
-    let mut result = vaddvq_f32(sum1) + vaddvq_f32(sum2) + vaddvq_f32(sum3) + vaddvq_f32(sum4);
-    for i in 0..n - m {
-        result += (*ptr1.add(i) - *ptr2.add(i)).powi(2);
+    // Remaining chunks of 4
+    while i + 4 <= n {
+        let sub1 = vsubq_f32(vld1q_f32(ptr1), vld1q_f32(ptr2));
+        sum1 = vfmaq_f32(sum1, sub1, sub1);
+        ptr1 = ptr1.add(4);
+        ptr2 = ptr2.add(4);
+        i += 4;
+    }
+    let mut result = hsum4_neon(sum1, sum2, sum3, sum4);
+    for j in 0..n - i {
+        result += (*ptr1.add(j) - *ptr2.add(j)).powi(2);
     }
     -result
 }
@@ -80,15 +92,23 @@ pub(crate) unsafe fn manhattan_similarity_neon(
         ptr2 = ptr2.add(16);
         i += 16;
     }
-    let mut result = vaddvq_f32(sum1) + vaddvq_f32(sum2) + vaddvq_f32(sum3) + vaddvq_f32(sum4);
-    for i in 0..n - m {
-        result += (*ptr1.add(i) - *ptr2.add(i)).abs();
+    // Remaining chunks of 4
+    while i + 4 <= n {
+        let sub1 = vsubq_f32(vld1q_f32(ptr1), vld1q_f32(ptr2));
+        sum1 = vaddq_f32(sum1, vabsq_f32(sub1));
+        ptr1 = ptr1.add(4);
+        ptr2 = ptr2.add(4);
+        i += 4;
+    }
+    let mut result = hsum4_neon(sum1, sum2, sum3, sum4);
+    for j in 0..n - i {
+        result += (*ptr1.add(j) - *ptr2.add(j)).abs();
     }
     -result
 }
 
 #[cfg(target_feature = "neon")]
-pub(crate) unsafe fn cosine_preprocess_neon(vector: DenseVector) -> DenseVector {
+pub(crate) unsafe fn cosine_preprocess_neon(mut vector: DenseVector) -> DenseVector {
     let n = vector.len();
     let m = n - (n % 16);
     let mut ptr: *const f32 = vector.as_ptr();
@@ -114,15 +134,34 @@ pub(crate) unsafe fn cosine_preprocess_neon(vector: DenseVector) -> DenseVector
         ptr = ptr.add(16);
         i += 16;
     }
-    let mut length = vaddvq_f32(sum1) + vaddvq_f32(sum2) + vaddvq_f32(sum3) + vaddvq_f32(sum4);
-    for v in vector.iter().take(n).skip(m) {
+    // Remaining chunks of 4
+    while i + 4 <= n {
+        let d1 = vld1q_f32(ptr);
+        sum1 = vfmaq_f32(sum1, d1, d1);
+        ptr = ptr.add(4);
+        i += 4;
+    }
+    let mut length = hsum4_neon(sum1, sum2, sum3, sum4);
+    for v in vector.iter().skip(i) {
         length += v.powi(2);
     }
     if is_length_zero_or_normalized(length) {
         return vector;
     }
     let length = length.sqrt();
-    vector.into_iter().map(|x| x / length).collect()
+
+    let m = n - (n % 4);
+    let length4 = vdupq_n_f32(length);
+    let ptr: *mut f32 = vector.as_mut_ptr();
+    let mut i: usize = 0;
+    while i < m {
+        vst1q_f32(ptr.add(i), vdivq_f32(vld1q_f32(ptr.add(i)), length4));
+        i += 4;
+    }
+    for x in vector.iter_mut().skip(m) {
+        *x /= length;
+    }
+    vector
 }
 
 #[cfg(target_feature = "neon")]
@@ -149,13 +188,32 @@ pub(crate) unsafe fn dot_similarity_neon(
         ptr2 = ptr2.add(16);
         i += 16;
     }
-    let mut result = vaddvq_f32(sum1) + vaddvq_f32(sum2) + vaddvq_f32(sum3) + vaddvq_f32(sum4);
-    for i in 0..n - m {
-        result += (*ptr1.add(i)) * (*ptr2.add(i));
+    // Remaining chunks of 4
+    while i + 4 <= n {
+        sum1 = vfmaq_f32(sum1, vld1q_f32(ptr1), vld1q_f32(ptr2));
+        ptr1 = ptr1.add(4);
+        ptr2 = ptr2.add(4);
+        i += 4;
+    }
+    let mut result = hsum4_neon(sum1, sum2, sum3, sum4);
+    for j in 0..n - i {
+        result += (*ptr1.add(j)) * (*ptr2.add(j));
     }
     result
 }
 
+/// Sum of all lanes of the accumulators, with a single horizontal add
+#[cfg(target_feature = "neon")]
+#[inline]
+unsafe fn hsum4_neon(
+    sum1: float32x4_t,
+    sum2: float32x4_t,
+    sum3: float32x4_t,
+    sum4: float32x4_t,
+) -> ScoreType {
+    vaddvq_f32(vaddq_f32(vaddq_f32(sum1, sum2), vaddq_f32(sum3, sum4)))
+}
+
 #[cfg(test)]
 mod tests {
     #[cfg(target_feature = "neon")]
@@ -193,4 +251,54 @@ mod tests {
             println!("neon test skipped");
         }
     }
+
+    #[cfg(target_feature = "neon")]
+    #[test]
+    fn test_spaces_neon_remainders() {
+        use rand::rngs::StdRng;
+        use rand::{Rng, SeedableRng};
+
+        use super::*;
+        use crate::spaces::simple::*;
+
+        if !std::arch::is_aarch64_feature_detected!("neon") {
+            println!("neon test skipped");
+            return;
+        }
+
+        // Summation order differs from the scalar implementations
+        fn assert_close(simd: f32, scalar: f32) {
+            assert!(
+                (simd - scalar).abs() <= 1e-5 * scalar.abs().max(1.0),
+                "simd: {simd}, scalar: {scalar}",
+            );
+        }
+
+        let mut rng = StdRng::seed_from_u64(42);
+        // Lengths not multiple of 16 and 4 use remainder loops
+        for len in 1..=33 {
+            let v1: Vec<f32> = (0..len).map(|_| rng.gen_range(-1.0..1.0)).collect();
+            let v2: Vec<f32> = (0..len).map(|_| rng.gen_range(-1.0..1.0)).collect();
+
+            assert_close(
+                unsafe { euclid_similarity_neon(&v1, &v2) },
+                euclid_similarity(&v1, &v2),
+            );
+            assert_close(
+                unsafe { manhattan_similarity_neon(&v1, &v2) },
+                manhattan_similarity(&v1, &v2),
+            );
+            assert_close(
+                unsafe { dot_similarity_neon(&v1, &v2) },
+                dot_similarity(&v1, &v2),
+            );
+
+            let cosine_simd = unsafe { cosine_preprocess_neon(v1.clone()) };
+            let cosine = cosine_preprocess(v1);
+            assert_eq!(cosine_simd.len(), len);
+            for (simd, scalar) in cosine_simd.into_iter().zip(cosine) {
+                assert_close(simd, scalar);
+            }
+        }
+    }
 }
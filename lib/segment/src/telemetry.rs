@@ -4,8 +4,8 @@ use serde::Serialize;
 use crate::common::anonymize::Anonymize;
 use crate::common::operation_time_statistics::OperationDurationStatistics;
 use crate::types::{
-    PayloadIndexInfo, SegmentConfig, SegmentInfo, SparseVectorDataConfig, VectorDataConfig,
-    VectorDataInfo,
+    PayloadIndexInfo, SegmentConfig, SegmentInfo, SparseVectorDataConfig, SparseVectorStats,
+    VectorDataConfig, VectorDataInfo,
 };
 
 #[derive(Serialize, Clone, Debug, JsonSchema)]
@@ -105,6 +105,10 @@ impl Anonymize for VectorDataInfo {
             num_vectors: self.num_vectors.anonymize(),
             num_indexed_vectors: self.num_indexed_vectors.anonymize(),
             num_deleted_vectors: self.num_deleted_vectors.anonymize(),
+            sparse_stats: self.sparse_stats.map(|stats| SparseVectorStats {
+                vectors_count: stats.vectors_count.anonymize(),
+                values_count: stats.values_count.anonymize(),
+            }),
         }
     }
 }
@@ -125,6 +129,8 @@ impl Anonymize for SegmentConfig {
             vector_data: self.vector_data.anonymize(),
             sparse_vector_data: self.sparse_vector_data.anonymize(),
             payload_storage_type: self.payload_storage_type,
+            payload_columns: self.payload_columns.anonymize(),
+            storage_cipher: None,
         }
     }
 }
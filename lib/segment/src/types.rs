@@ -6,6 +6,7 @@ use std::mem::size_of;
 use std::ops::Deref;
 use std::rc::Rc;
 use std::str::FromStr;
+use std::sync::Arc;
 
 use common::types::ScoreType;
 use fnv::FnvBuildHasher;
@@ -22,10 +23,12 @@ use strum::EnumIter;
 use uuid::Uuid;
 use validator::{Validate, ValidationError, ValidationErrors};
 
+use crate::common::encryption::StorageCipher;
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::utils::{self, MaybeOneOrMany, MultiValue};
 use crate::data_types::integer_index::IntegerIndexParams;
 use crate::data_types::order_by::OrderValue;
+use crate::data_types::payload_projection::{PayloadKeyPattern, PayloadProjection};
 use crate::data_types::text_index::TextIndexParams;
 use crate::data_types::vectors::{VectorElementType, VectorStruct};
 use crate::index::sparse_index::sparse_index_config::SparseIndexConfig;
@@ -46,15 +49,37 @@ pub type IntPayloadType = i64;
 /// Type of datetime point payload
 pub type DateTimePayloadType = DateTimeWrapper;
 
+/// Timestamp of [`DateTimePayloadType`] in nanoseconds
+pub type DateTimeTimestamp = i128;
+
+const NANOS_PER_SECOND: DateTimeTimestamp = 1_000_000_000;
+
 /// Wraps `DateTime<Utc>` to allow more flexible deserialization
 #[derive(Clone, Copy, Serialize, JsonSchema, Debug, PartialEq, PartialOrd)]
 #[serde(transparent)]
 pub struct DateTimeWrapper(pub chrono::DateTime<chrono::Utc>);
 
 impl DateTimeWrapper {
-    /// Qdrant's representation of datetime as timestamp is an i64 of microseconds
-    pub fn timestamp(&self) -> i64 {
-        self.0.timestamp_micros()
+    /// Qdrant's representation of datetime as timestamp is an i128 of nanoseconds
+    ///
+    /// Unlike i64, it covers the whole range of datetimes, not only years 1677 to 2262.
+    pub fn timestamp(&self) -> DateTimeTimestamp {
+        DateTimeTimestamp::from(self.0.timestamp()) * NANOS_PER_SECOND
+            + DateTimeTimestamp::from(self.0.timestamp_subsec_nanos())
+    }
+
+    /// Inverse of [`DateTimeWrapper::timestamp`]
+    pub fn from_timestamp(timestamp: DateTimeTimestamp) -> Self {
+        let seconds = timestamp.div_euclid(NANOS_PER_SECOND);
+        let nanos = timestamp.rem_euclid(NANOS_PER_SECOND) as u32;
+        let datetime = i64::try_from(seconds)
+            .ok()
+            .and_then(|seconds| chrono::DateTime::from_timestamp(seconds, nanos))
+            .unwrap_or_else(|| {
+                log::warn!("Failed to decode timestamp {timestamp}, fallback to UNIX_EPOCH");
+                chrono::DateTime::UNIX_EPOCH
+            });
+        DateTimeWrapper(datetime)
     }
 }
 
@@ -183,6 +208,14 @@ impl Distance {
         }
     }
 
+    /// Check if vectors, stored for the `stored` distance, can be compared with this distance.
+    ///
+    /// Vectors of the cosine distance are stored normalized, so any distance applies to them.
+    /// Other vectors are stored as is and can't be compared with the cosine distance.
+    pub fn is_applicable_to(&self, stored: Distance) -> bool {
+        *self != Distance::Cosine || stored == Distance::Cosine
+    }
+
     /// Checks if score satisfies threshold condition
     pub fn check_threshold(&self, score: ScoreType, threshold: ScoreType) -> bool {
         match self.distance_order() {
@@ -295,6 +328,28 @@ pub struct VectorDataInfo {
     pub num_vectors: usize,
     pub num_indexed_vectors: usize,
     pub num_deleted_vectors: usize,
+    /// Statistics of the inverted index, only for sparse vectors
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sparse_stats: Option<SparseVectorStats>,
+}
+
+/// Statistics of sparse vectors, which are used to weight dimensions with the IDF modifier
+///
+/// Both include deleted vectors, until segments are optimized.
+#[derive(Debug, Default, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct SparseVectorStats {
+    /// Number of stored vectors, including deleted ones
+    pub vectors_count: usize,
+    /// Total number of non-zero values of all vectors in the inverted index, including deleted ones
+    pub values_count: usize,
+}
+
+impl SparseVectorStats {
+    pub fn merge(&mut self, other: &Self) {
+        self.vectors_count += other.vectors_count;
+        self.values_count += other.values_count;
+    }
 }
 
 /// Aggregated information about segment
@@ -369,6 +424,13 @@ pub struct SearchParams {
     /// guarantee that all uploaded vectors will be included in search results
     #[serde(default)]
     pub indexed_only: bool,
+
+    /// Score vectors with this distance instead of the one configured for the vector.
+    /// Vectors configured with `Cosine` distance can be scored with any distance,
+    /// other vectors can't be scored with `Cosine`.
+    /// Search is always exact if the distance is overridden.
+    #[serde(default)]
+    pub distance: Option<Distance>,
 }
 
 /// Collection default values
@@ -679,11 +741,20 @@ pub enum PayloadStorageType {
     InMemory,
     // Store payload on disk only, read each time it is requested
     OnDisk,
+    // Store payload and point id mappings in append-only logs instead of RocksDB,
+    // read payload through mmap each time it is requested
+    AppendOnly,
+    // Store payload in mmapped blocks with an on-disk offset index, point id mappings in
+    // append-only logs. Only recently read payload pages are kept in memory
+    Mmap,
 }
 
 impl PayloadStorageType {
     pub fn is_on_disk(&self) -> bool {
-        matches!(self, PayloadStorageType::OnDisk)
+        matches!(
+            self,
+            PayloadStorageType::OnDisk | PayloadStorageType::AppendOnly | PayloadStorageType::Mmap
+        )
     }
 }
 
@@ -697,6 +768,14 @@ pub struct SegmentConfig {
     pub sparse_vector_data: HashMap<String, SparseVectorDataConfig>,
     /// Defines payload storage type
     pub payload_storage_type: PayloadStorageType,
+    /// Payload fields, values of which are additionally stored column-wise
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub payload_columns: Vec<PayloadKeyType>,
+    /// Key of the collection, which wraps data keys of encrypted segments.
+    /// Never stored, provided by the collection on every segment creation and load.
+    #[serde(skip)]
+    pub storage_cipher: Option<Arc<StorageCipher>>,
 }
 
 impl SegmentConfig {
@@ -786,6 +865,8 @@ pub enum VectorStorageDatatype {
     Float32,
     // Half-precision floating point
     Float16,
+    // Double-precision floating point
+    Float64,
     // Unsigned 8-bit integer
     Uint8,
 }
@@ -800,8 +881,13 @@ pub struct MultiVectorConfig {
 #[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Eq, PartialEq, Copy, Clone, Hash)]
 #[serde(rename_all = "snake_case")]
 pub enum MultiVectorComparator {
+    // Sum of max similarities of each vector of the query, as in ColBERT
     #[default]
     MaxSim,
+    // Mean of similarities between all pairs of vectors
+    MeanSim,
+    // Sum of similarities between all pairs of vectors
+    Sum,
 }
 
 impl VectorStorageType {
@@ -1876,6 +1962,40 @@ impl From<HashSet<PointIdType>> for HasIdCondition {
     }
 }
 
+/// Range of numeric point IDs, UUIDs never match
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct PointIdRange {
+    /// Point ID greater than or equal
+    pub gte: Option<u64>,
+    /// Point ID less than
+    pub lt: Option<u64>,
+}
+
+impl PointIdRange {
+    pub fn contains(&self, id: &PointIdType) -> bool {
+        match id {
+            ExtendedPointId::NumId(id) => {
+                self.gte.map_or(true, |gte| *id >= gte) && self.lt.map_or(true, |lt| *id < lt)
+            }
+            ExtendedPointId::Uuid(_) => false,
+        }
+    }
+}
+
+/// ID range filtering condition
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+pub struct HasIdRangeCondition {
+    pub has_id_range: PointIdRange,
+}
+
+impl From<PointIdRange> for HasIdRangeCondition {
+    fn from(range: PointIdRange) -> Self {
+        HasIdRangeCondition {
+            has_id_range: range,
+        }
+    }
+}
+
 /// Select points with payload for a specified nested field
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Validate)]
 pub struct Nested {
@@ -1923,6 +2043,8 @@ pub enum Condition {
     IsNull(IsNullCondition),
     /// Check if points id is in a given set
     HasId(HasIdCondition),
+    /// Check if points id is in a given numeric range
+    HasIdRange(HasIdRangeCondition),
     /// Nested filters
     Nested(NestedCondition),
     /// Nested filter
@@ -1941,7 +2063,10 @@ impl Condition {
 impl Validate for Condition {
     fn validate(&self) -> Result<(), ValidationErrors> {
         match self {
-            Condition::HasId(_) | Condition::IsEmpty(_) | Condition::IsNull(_) => Ok(()),
+            Condition::HasId(_)
+            | Condition::HasIdRange(_)
+            | Condition::IsEmpty(_)
+            | Condition::IsNull(_) => Ok(()),
             Condition::Field(field_condition) => field_condition.validate(),
             Condition::Nested(nested_condition) => nested_condition.validate(),
             Condition::Filter(filter) => filter.validate(),
@@ -1968,6 +2093,13 @@ impl From<bool> for WithPayloadInterface {
     }
 }
 
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct WithVectorExclude {
+    /// Names of the vectors to omit from the result
+    pub exclude: Vec<String>,
+}
+
 /// Options for specifying which vector to include
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
 #[serde(untagged, rename_all = "snake_case")]
@@ -1977,6 +2109,8 @@ pub enum WithVector {
     Bool(bool),
     /// Specify which vector to return
     Selector(Vec<String>),
+    /// Return all vectors, except the specified ones
+    Exclude(WithVectorExclude),
 }
 
 impl WithVector {
@@ -1984,6 +2118,18 @@ impl WithVector {
         match self {
             WithVector::Bool(b) => *b,
             WithVector::Selector(_) => true,
+            WithVector::Exclude(_) => true,
+        }
+    }
+
+    /// Check if vector with the given name should be returned
+    pub fn is_selected(&self, vector_name: &str) -> bool {
+        match self {
+            WithVector::Bool(b) => *b,
+            WithVector::Selector(names) => names.iter().any(|name| name == vector_name),
+            WithVector::Exclude(selector) => {
+                !selector.exclude.iter().any(|name| name == vector_name)
+            }
         }
     }
 }
@@ -2063,6 +2209,27 @@ impl PayloadSelectorExclude {
     }
 }
 
+/// Select payload keys by glob patterns over nested keys, e.g. `metadata.*`
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub struct PayloadSelectorPatterns {
+    /// Only include keys matching any of these patterns. All keys are included, if empty
+    #[serde(default)]
+    pub include: Vec<PayloadKeyPattern>,
+    /// Exclude keys matching any of these patterns, even if they are included
+    #[serde(default)]
+    pub exclude: Vec<PayloadKeyPattern>,
+}
+
+impl PayloadSelectorPatterns {
+    pub fn projection(&self) -> PayloadProjection {
+        PayloadProjection {
+            include: &self.include,
+            exclude: &self.exclude,
+        }
+    }
+}
+
 /// Specifies how to treat payload selector
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
 #[serde(untagged, rename_all = "snake_case")]
@@ -2071,6 +2238,8 @@ pub enum PayloadSelector {
     Include(PayloadSelectorInclude),
     /// Exclude this fields from result payload. Keep all other fields.
     Exclude(PayloadSelectorExclude),
+    /// Include and exclude fields by wildcard patterns, e.g. `metadata.*`
+    Patterns(PayloadSelectorPatterns),
 }
 
 impl From<PayloadSelectorExclude> for WithPayloadInterface {
@@ -2115,6 +2284,7 @@ impl PayloadSelector {
                     .all(|pattern| !pattern.check_exclude_pattern(key))
             })
             .into(),
+            PayloadSelector::Patterns(selector) => selector.projection().project(x),
         }
     }
 }
@@ -2330,10 +2500,12 @@ mod tests {
     #[case::rfc_3339_custom_tz_no_colon_and_t("2020-03-01T00:00:00-0900")]
     #[case::rfc_3339_custom_tz_no_minutes("2020-03-01 00:00:00-09")]
     #[case::rfc_3339_and_decimals("2020-03-01T00:00:00.123456Z")]
+    #[case::rfc_3339_and_nanos("2020-03-01T00:00:00.123456789Z")]
     #[case::without_z("2020-03-01T00:00:00")]
     #[case::without_z_and_decimals("2020-03-01T00:00:00.12")]
     #[case::space_sep_without_z("2020-03-01 00:00:00")]
     #[case::space_sep_without_z_and_decimals("2020-03-01 00:00:00.123456")]
+    #[case::space_sep_without_z_and_nanos("2020-03-01 00:00:00.123456789")]
     fn test_datetime_deserialization(#[case] datetime: &str) {
         let datetime = DateTimePayloadType::from_str(datetime).unwrap();
         let serialized = serde_json::to_string(&datetime).unwrap();
@@ -2352,6 +2524,38 @@ mod tests {
         assert_eq!(datetime.timestamp(), datetime_no_z.timestamp());
     }
 
+    #[test]
+    fn test_datetime_nanosecond_precision() {
+        let datetime = DateTimePayloadType::from_str("2020-03-01T01:02:03.123456789Z").unwrap();
+        assert_eq!(
+            serde_json::to_string(&datetime).unwrap(),
+            r#""2020-03-01T01:02:03.123456789Z""#,
+        );
+        assert_eq!(datetime.timestamp() % 1_000_000_000, 123_456_789);
+        assert_eq!(
+            DateTimePayloadType::from_timestamp(datetime.timestamp()),
+            datetime
+        );
+
+        // Differ within the same microsecond
+        let next = DateTimePayloadType::from_str("2020-03-01T01:02:03.12345679Z").unwrap();
+        assert!(datetime < next);
+        assert!(datetime.timestamp() < next.timestamp());
+
+        // Out of the i64 nanosecond range, not clamped
+        let past = DateTimePayloadType::from_str("1500-01-01T00:00:00.000000001Z").unwrap();
+        let future = DateTimePayloadType::from_str("3000-01-01T00:00:00.999999999Z").unwrap();
+        assert!(past.timestamp() < i128::from(i64::MIN));
+        assert!(future.timestamp() > i128::from(i64::MAX));
+        assert_eq!(DateTimePayloadType::from_timestamp(past.timestamp()), past);
+        assert_eq!(
+            DateTimePayloadType::from_timestamp(future.timestamp()),
+            future
+        );
+        assert!(past.timestamp() < datetime.timestamp());
+        assert!(datetime.timestamp() < future.timestamp());
+    }
+
     #[test]
     fn test_timezone_ordering() {
         let datetimes = [
@@ -2862,6 +3066,34 @@ mod tests {
         assert!(c.range.is_some());
     }
 
+    #[test]
+    fn test_has_id_range_parse() {
+        let query = r#"
+        {
+            "must": [
+                {
+                    "has_id_range": {
+                        "gte": 10,
+                        "lt": 20
+                    }
+                }
+            ]
+        }
+        "#;
+
+        let filter: Filter = serde_json::from_str(query).unwrap();
+        let Some(Condition::HasIdRange(condition)) = filter.must.unwrap().pop() else {
+            panic!("Condition::HasIdRange expected");
+        };
+
+        let range = condition.has_id_range;
+        assert!(range.contains(&ExtendedPointId::NumId(10)));
+        assert!(range.contains(&ExtendedPointId::NumId(19)));
+        assert!(!range.contains(&ExtendedPointId::NumId(20)));
+        assert!(!range.contains(&ExtendedPointId::NumId(9)));
+        assert!(!range.contains(&ExtendedPointId::Uuid(Uuid::nil())));
+    }
+
     #[test]
     fn test_payload_query_parse() {
         let query1 = r#"
@@ -3356,6 +3588,30 @@ mod tests {
         assert_eq!(payload, expected.into());
     }
 
+    #[test]
+    fn test_payload_selector_patterns_parse() {
+        let selector: PayloadSelector = serde_json::from_value(json!({
+            "include": ["metadata.*"],
+            "exclude": ["metadata.raw_text"],
+        }))
+        .unwrap();
+        assert!(matches!(selector, PayloadSelector::Patterns(_)));
+
+        // Plain paths are still parsed as include selector
+        let selector: PayloadSelector =
+            serde_json::from_value(json!({"include": ["metadata.author"]})).unwrap();
+        assert!(matches!(selector, PayloadSelector::Include(_)));
+
+        let payload = json!({
+            "metadata": {"author": "John", "raw_text": "Very long text"},
+            "title": "Document",
+        });
+        let selector: PayloadSelector =
+            serde_json::from_value(json!({"include": ["meta*"], "exclude": ["*.raw_*"]})).unwrap();
+        let payload = selector.process(payload.into());
+        assert_eq!(payload, json!({"metadata": {"author": "John"}}).into());
+    }
+
     #[test]
     fn test_payload_selector_array_include() {
         let payload = json!({
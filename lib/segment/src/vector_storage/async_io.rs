@@ -7,8 +7,7 @@ use memory::mmap_ops::transmute_from_u8_to_slice;
 
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::data_types::primitive::PrimitiveVectorElement;
-
-const DISK_PARALLELISM: usize = 16; // TODO: benchmark it better, or make it configurable
+use crate::vector_storage::common::get_async_scorer_parallelism;
 
 struct BufferMeta {
     /// Sequential index of the processing point
@@ -51,6 +50,8 @@ pub struct UringReader<T: PrimitiveVectorElement> {
     file: File,
     buffers: BufferStore,
     io_uring: Option<IoUring>,
+    /// Max number of in-flight reads
+    parallelism: usize,
     raw_size: usize,
     header_size: usize,
     _phantom: std::marker::PhantomData<T>,
@@ -58,13 +59,15 @@ pub struct UringReader<T: PrimitiveVectorElement> {
 
 impl<T: PrimitiveVectorElement> UringReader<T> {
     pub fn new(file: File, raw_size: usize, header_size: usize) -> OperationResult<Self> {
-        let buffers = BufferStore::new(DISK_PARALLELISM, raw_size);
-        let io_uring = IoUring::new(DISK_PARALLELISM as _)?;
+        let parallelism = get_async_scorer_parallelism();
+        let buffers = BufferStore::new(parallelism, raw_size);
+        let io_uring = IoUring::new(parallelism as _)?;
 
         Ok(Self {
             file,
             buffers,
             io_uring: Some(io_uring),
+            parallelism,
             raw_size,
             header_size,
             _phantom: std::marker::PhantomData,
@@ -83,7 +86,7 @@ impl<T: PrimitiveVectorElement> UringReader<T> {
             // Use existing `IoUring` if there's one...
             Some(io_uring) => io_uring,
             // ...or create a new one if not
-            None => IoUring::new(DISK_PARALLELISM as _)?,
+            None => IoUring::new(self.parallelism as _)?,
         };
 
         let buffers_count = self.buffers.buffers.len();
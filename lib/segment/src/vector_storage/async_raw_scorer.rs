@@ -7,7 +7,8 @@ use common::types::{PointOffsetType, ScoreType, ScoredPointOffset};
 use super::query::{ContextQuery, DiscoveryQuery, RecoQuery, TransformInto};
 use super::query_scorer::custom_query_scorer::CustomQueryScorer;
 use crate::common::operation_error::{OperationError, OperationResult};
-use crate::data_types::vectors::{DenseVector, QueryVector, Vector, VectorElementType};
+use crate::data_types::primitive::PrimitiveVectorElement;
+use crate::data_types::vectors::{DenseVector, QueryVector, Vector};
 use crate::spaces::metric::Metric;
 use crate::spaces::simple::{CosineMetric, DotProductMetric, EuclidMetric, ManhattanMetric};
 use crate::types::Distance;
@@ -17,21 +18,27 @@ use crate::vector_storage::query_scorer::metric_query_scorer::MetricQueryScorer;
 use crate::vector_storage::query_scorer::QueryScorer;
 use crate::vector_storage::{RawScorer, VectorStorage as _, DEFAULT_STOPPED};
 
-pub fn new<'a>(
+pub fn new<'a, T: PrimitiveVectorElement>(
     query: QueryVector,
-    storage: &'a MemmapDenseVectorStorage<VectorElementType>,
+    storage: &'a MemmapDenseVectorStorage<T>,
     point_deleted: &'a BitSlice,
     is_stopped: &'a AtomicBool,
-) -> OperationResult<Box<dyn RawScorer + 'a>> {
+) -> OperationResult<Box<dyn RawScorer + 'a>>
+where
+    CosineMetric: Metric<T>,
+    EuclidMetric: Metric<T>,
+    DotProductMetric: Metric<T>,
+    ManhattanMetric: Metric<T>,
+{
     AsyncRawScorerBuilder::new(query, storage, point_deleted)?
         .with_is_stopped(is_stopped)
         .build()
 }
 
-pub struct AsyncRawScorerImpl<'a, TQueryScorer: QueryScorer<[VectorElementType]>> {
+pub struct AsyncRawScorerImpl<'a, T: PrimitiveVectorElement, TQueryScorer: QueryScorer<[T]>> {
     points_count: PointOffsetType,
     query_scorer: TQueryScorer,
-    storage: &'a MmapDenseVectors<VectorElementType>,
+    storage: &'a MmapDenseVectors<T>,
     point_deleted: &'a BitSlice,
     vec_deleted: &'a BitSlice,
     /// This flag indicates that the search process is stopped externally,
@@ -39,14 +46,15 @@ pub struct AsyncRawScorerImpl<'a, TQueryScorer: QueryScorer<[VectorElementType]>
     pub is_stopped: &'a AtomicBool,
 }
 
-impl<'a, TQueryScorer> AsyncRawScorerImpl<'a, TQueryScorer>
+impl<'a, T, TQueryScorer> AsyncRawScorerImpl<'a, T, TQueryScorer>
 where
-    TQueryScorer: QueryScorer<[VectorElementType]>,
+    T: PrimitiveVectorElement,
+    TQueryScorer: QueryScorer<[T]>,
 {
     fn new(
         points_count: PointOffsetType,
         query_scorer: TQueryScorer,
-        storage: &'a MmapDenseVectors<VectorElementType>,
+        storage: &'a MmapDenseVectors<T>,
         point_deleted: &'a BitSlice,
         vec_deleted: &'a BitSlice,
         is_stopped: &'a AtomicBool,
@@ -62,9 +70,10 @@ where
     }
 }
 
-impl<'a, TQueryScorer> RawScorer for AsyncRawScorerImpl<'a, TQueryScorer>
+impl<'a, T, TQueryScorer> RawScorer for AsyncRawScorerImpl<'a, T, TQueryScorer>
 where
-    TQueryScorer: QueryScorer<[VectorElementType]>,
+    T: PrimitiveVectorElement,
+    TQueryScorer: QueryScorer<[T]>,
 {
     fn score_points(&self, points: &[PointOffsetType], scores: &mut [ScoredPointOffset]) -> usize {
         if self.is_stopped.load(Ordering::Relaxed) {
@@ -203,20 +212,26 @@ where
     }
 }
 
-struct AsyncRawScorerBuilder<'a> {
+struct AsyncRawScorerBuilder<'a, T: PrimitiveVectorElement> {
     points_count: PointOffsetType,
     query: QueryVector,
-    storage: &'a MemmapDenseVectorStorage<VectorElementType>,
+    storage: &'a MemmapDenseVectorStorage<T>,
     point_deleted: &'a BitSlice,
     vec_deleted: &'a BitSlice,
     distance: Distance,
     is_stopped: Option<&'a AtomicBool>,
 }
 
-impl<'a> AsyncRawScorerBuilder<'a> {
+impl<'a, T: PrimitiveVectorElement> AsyncRawScorerBuilder<'a, T>
+where
+    CosineMetric: Metric<T>,
+    EuclidMetric: Metric<T>,
+    DotProductMetric: Metric<T>,
+    ManhattanMetric: Metric<T>,
+{
     pub fn new(
         query: QueryVector,
-        storage: &'a MemmapDenseVectorStorage<VectorElementType>,
+        storage: &'a MemmapDenseVectorStorage<T>,
         point_deleted: &'a BitSlice,
     ) -> OperationResult<Self> {
         let points_count = storage.total_vector_count() as _;
@@ -251,7 +266,7 @@ impl<'a> AsyncRawScorerBuilder<'a> {
         self
     }
 
-    fn _build_with_metric<TMetric: Metric<VectorElementType> + 'a>(
+    fn _build_with_metric<TMetric: Metric<T> + 'a>(
         self,
     ) -> OperationResult<Box<dyn RawScorer + 'a>> {
         let Self {
@@ -267,11 +282,9 @@ impl<'a> AsyncRawScorerBuilder<'a> {
         match query {
             QueryVector::Nearest(vector) => {
                 match vector {
-                    Vector::Dense(dense_vector) => {
-                        let query_scorer = MetricQueryScorer::<VectorElementType, TMetric, _>::new(
-                            dense_vector,
-                            storage,
-                        );
+                    vector @ (Vector::Dense(_) | Vector::DenseDouble(_)) => {
+                        let query_scorer =
+                            MetricQueryScorer::<T, TMetric, _>::new(vector.try_into()?, storage);
                         Ok(Box::new(AsyncRawScorerImpl::new(
                             points_count,
                             query_scorer,
@@ -291,9 +304,8 @@ impl<'a> AsyncRawScorerBuilder<'a> {
             }
             QueryVector::Recommend(reco_query) => {
                 let reco_query: RecoQuery<DenseVector> = reco_query.transform_into()?;
-                let query_scorer = CustomQueryScorer::<VectorElementType, TMetric, _, _, _>::new(
-                    reco_query, storage,
-                );
+                let query_scorer =
+                    CustomQueryScorer::<T, TMetric, _, _, _>::new(reco_query, storage);
                 Ok(Box::new(AsyncRawScorerImpl::new(
                     points_count,
                     query_scorer,
@@ -306,10 +318,8 @@ impl<'a> AsyncRawScorerBuilder<'a> {
             QueryVector::Discovery(discovery_query) => {
                 let discovery_query: DiscoveryQuery<DenseVector> =
                     discovery_query.transform_into()?;
-                let query_scorer = CustomQueryScorer::<VectorElementType, TMetric, _, _, _>::new(
-                    discovery_query,
-                    storage,
-                );
+                let query_scorer =
+                    CustomQueryScorer::<T, TMetric, _, _, _>::new(discovery_query, storage);
                 Ok(Box::new(AsyncRawScorerImpl::new(
                     points_count,
                     query_scorer,
@@ -321,10 +331,8 @@ impl<'a> AsyncRawScorerBuilder<'a> {
             }
             QueryVector::Context(context_query) => {
                 let context_query: ContextQuery<DenseVector> = context_query.transform_into()?;
-                let query_scorer = CustomQueryScorer::<VectorElementType, TMetric, _, _, _>::new(
-                    context_query,
-                    storage,
-                );
+                let query_scorer =
+                    CustomQueryScorer::<T, TMetric, _, _, _>::new(context_query, storage);
                 Ok(Box::new(AsyncRawScorerImpl::new(
                     points_count,
                     query_scorer,
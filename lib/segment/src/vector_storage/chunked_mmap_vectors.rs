@@ -245,6 +245,28 @@ impl<T: Sized + Copy + 'static> ChunkedMmapVectors<T> {
         }
     }
 
+    /// Returns chunk index and byte offset in the chunk file of `count` vectors starting from key.
+    /// If chunk boundary is crossed, returns None
+    pub fn chunk_location<TKey>(&self, start_key: TKey, count: usize) -> Option<(usize, usize)>
+    where
+        TKey: num_traits::cast::AsPrimitive<usize>,
+    {
+        let start_key: usize = start_key.as_();
+        let chunk_idx = self.get_chunk_index(start_key);
+        let chunk_offset = self.get_chunk_offset(start_key);
+        if chunk_idx >= self.chunks.len()
+            || chunk_offset + count * self.config.dim > self.chunks[chunk_idx].len()
+        {
+            return None;
+        }
+        Some((chunk_idx, chunk_offset * std::mem::size_of::<T>()))
+    }
+
+    /// Path of the chunk file
+    pub fn chunk_path(directory: &Path, chunk_idx: usize) -> PathBuf {
+        chunk_name(directory, chunk_idx)
+    }
+
     pub fn flusher(&self) -> Flusher {
         Box::new({
             let status_flusher = self.status.flusher();
@@ -1,9 +1,16 @@
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 
 use serde::{Deserialize, Serialize};
 
 static ASYNC_SCORER: AtomicBool = AtomicBool::new(false);
 
+static ASYNC_PAYLOAD_READS: AtomicBool = AtomicBool::new(false);
+
+/// Default number of concurrent disk reads, issued by a single async scorer
+pub const DEFAULT_ASYNC_SCORER_PARALLELISM: usize = 16;
+
+static ASYNC_SCORER_PARALLELISM: AtomicUsize = AtomicUsize::new(DEFAULT_ASYNC_SCORER_PARALLELISM);
+
 pub fn set_async_scorer(async_scorer: bool) {
     ASYNC_SCORER.store(async_scorer, Ordering::Relaxed);
 }
@@ -12,6 +19,23 @@ pub fn get_async_scorer() -> bool {
     ASYNC_SCORER.load(Ordering::Relaxed)
 }
 
+/// Read payloads of on-disk mmap payload storages in batches with io_uring, where supported
+pub fn set_async_payload_reads(async_payload_reads: bool) {
+    ASYNC_PAYLOAD_READS.store(async_payload_reads, Ordering::Relaxed);
+}
+
+pub fn get_async_payload_reads() -> bool {
+    ASYNC_PAYLOAD_READS.load(Ordering::Relaxed)
+}
+
+pub fn set_async_scorer_parallelism(parallelism: usize) {
+    ASYNC_SCORER_PARALLELISM.store(parallelism.max(1), Ordering::Relaxed);
+}
+
+pub fn get_async_scorer_parallelism() -> usize {
+    ASYNC_SCORER_PARALLELISM.load(Ordering::Relaxed)
+}
+
 /// Storage type for RocksDB based storage
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct StoredRecord<T> {
@@ -1,4 +1,3 @@
-use std::borrow::Cow;
 use std::fs::{create_dir_all, File, OpenOptions};
 use std::io::{self, Write};
 use std::ops::Range;
@@ -77,6 +76,18 @@ pub fn open_memmap_vector_storage_half(
     )))
 }
 
+pub fn open_memmap_vector_storage_double(
+    path: &Path,
+    dim: usize,
+    distance: Distance,
+) -> OperationResult<Arc<AtomicRefCell<VectorStorageEnum>>> {
+    let storage =
+        open_memmap_vector_storage_with_async_io_impl(path, dim, distance, get_async_scorer())?;
+    Ok(Arc::new(AtomicRefCell::new(
+        VectorStorageEnum::DenseMemmapDouble(storage),
+    )))
+}
+
 pub fn open_memmap_vector_storage_with_async_io(
     path: &Path,
     dim: usize,
@@ -139,6 +150,10 @@ impl<T: PrimitiveVectorElement> DenseVectorStorage<T> for MemmapDenseVectorStora
     fn get_dense(&self, key: PointOffsetType) -> &[T] {
         self.mmap_store.as_ref().unwrap().get_vector(key)
     }
+
+    fn prefetch_dense(&self, keys: &[PointOffsetType]) {
+        self.mmap_store.as_ref().unwrap().prefetch(keys);
+    }
 }
 
 impl<T: PrimitiveVectorElement> VectorStorage for MemmapDenseVectorStorage<T> {
@@ -163,9 +178,7 @@ impl<T: PrimitiveVectorElement> VectorStorage for MemmapDenseVectorStorage<T> {
     }
 
     fn get_vector(&self, key: PointOffsetType) -> CowVector {
-        CowVector::from(T::slice_to_float_cow(
-            self.mmap_store.as_ref().unwrap().get_vector(key).into(),
-        ))
+        T::slice_to_cow_vector(self.mmap_store.as_ref().unwrap().get_vector(key).into())
     }
 
     fn insert_vector(&mut self, _key: PointOffsetType, _vector: VectorRef) -> OperationResult<()> {
@@ -194,7 +207,7 @@ impl<T: PrimitiveVectorElement> VectorStorage for MemmapDenseVectorStorage<T> {
         for id in other_ids {
             check_process_stopped(stopped)?;
             let other_vector = other.get_vector(id);
-            let vector = T::slice_from_float_cow(Cow::try_from(other_vector)?);
+            let vector = T::slice_from_vector_ref(other_vector.as_vec_ref())?;
             let raw_bites = mmap_ops::transmute_to_u8_slice(vector.as_ref());
             vectors_file.write_all(raw_bites)?;
             end_index += 1;
@@ -268,7 +281,7 @@ fn open_append<P: AsRef<Path>>(path: P) -> io::Result<File> {
 
 #[cfg(test)]
 mod tests {
-    use std::mem::transmute;
+    use std::mem::{align_of, transmute};
 
     use common::types::ScoredPointOffset;
     use memory::mmap_ops::transmute_to_u8_slice;
@@ -280,7 +293,9 @@ mod tests {
     use crate::fixtures::payload_context_fixture::FixtureIdTracker;
     use crate::id_tracker::IdTracker;
     use crate::types::{PointIdType, QuantizationConfig, ScalarQuantizationConfig};
-    use crate::vector_storage::dense::simple_dense_vector_storage::open_simple_dense_vector_storage;
+    use crate::vector_storage::dense::simple_dense_vector_storage::{
+        open_simple_dense_double_vector_storage, open_simple_dense_vector_storage,
+    };
     use crate::vector_storage::new_raw_scorer;
     use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
 
@@ -315,6 +330,7 @@ mod tests {
             let storage2 = open_simple_dense_vector_storage(
                 db,
                 DB_VECTOR_CF,
+                None,
                 4,
                 Distance::Dot,
                 &AtomicBool::new(false),
@@ -352,6 +368,7 @@ mod tests {
             let storage2 = open_simple_dense_vector_storage(
                 db,
                 DB_VECTOR_CF,
+                None,
                 4,
                 Distance::Dot,
                 &AtomicBool::new(false),
@@ -418,6 +435,7 @@ mod tests {
             let storage2 = open_simple_dense_vector_storage(
                 db,
                 DB_VECTOR_CF,
+                None,
                 4,
                 Distance::Dot,
                 &AtomicBool::new(false),
@@ -549,6 +567,7 @@ mod tests {
             let storage2 = open_simple_dense_vector_storage(
                 db,
                 DB_VECTOR_CF,
+                None,
                 4,
                 Distance::Dot,
                 &AtomicBool::new(false),
@@ -635,6 +654,7 @@ mod tests {
             let storage2 = open_simple_dense_vector_storage(
                 db,
                 DB_VECTOR_CF,
+                None,
                 4,
                 Distance::Dot,
                 &AtomicBool::new(false),
@@ -680,6 +700,83 @@ mod tests {
         assert_eq!(res[2].score, -1.0);
     }
 
+    #[test]
+    fn test_mmap_double_storage() {
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+
+        // Values, which are not representable in single precision, must be kept as is
+        let points: [Vec<f64>; 3] = [
+            vec![1.0, 0.0, 1.0, 0.1234567890123],
+            vec![1.0, 0.0, 1.0, 1e-50],
+            vec![1.0, 1.0, 1.0, 1.0],
+        ];
+        let id_tracker = Arc::new(AtomicRefCell::new(FixtureIdTracker::new(points.len())));
+
+        {
+            let storage = open_memmap_vector_storage_double(dir.path(), 4, Distance::Dot).unwrap();
+            let mut borrowed_storage = storage.borrow_mut();
+
+            let dir2 = Builder::new().prefix("db_dir").tempdir().unwrap();
+            let db = open_db(dir2.path(), &[DB_VECTOR_CF]).unwrap();
+            let storage2 = open_simple_dense_double_vector_storage(
+                db,
+                DB_VECTOR_CF,
+                None,
+                4,
+                Distance::Dot,
+                &AtomicBool::new(false),
+            )
+            .unwrap();
+            {
+                let mut borrowed_storage2 = storage2.borrow_mut();
+                for (i, vec) in points.iter().enumerate() {
+                    borrowed_storage2
+                        .insert_vector(i as PointOffsetType, vec.as_slice().into())
+                        .unwrap();
+                }
+            }
+            borrowed_storage
+                .update_from(
+                    &storage2.borrow(),
+                    &mut Box::new(0..points.len() as PointOffsetType),
+                    &Default::default(),
+                )
+                .unwrap();
+        }
+
+        // Reopen to read vectors back from the file
+        let storage = open_memmap_vector_storage_double(dir.path(), 4, Distance::Dot).unwrap();
+        let borrowed_storage = storage.borrow();
+        assert_eq!(borrowed_storage.total_vector_count(), points.len());
+        assert_eq!(borrowed_storage.datatype(), VectorStorageDatatype::Float64);
+
+        let VectorStorageEnum::DenseMemmapDouble(double_storage) = &*borrowed_storage else {
+            panic!("storage must be double precision");
+        };
+        for (i, point) in points.iter().enumerate() {
+            let stored = double_storage.get_dense(i as PointOffsetType);
+            assert_eq!(stored.as_ptr().align_offset(align_of::<f64>()), 0);
+            assert_eq!(stored, point.as_slice());
+            assert_eq!(
+                borrowed_storage
+                    .get_vector(i as PointOffsetType)
+                    .as_vec_ref(),
+                VectorRef::from(point.as_slice()),
+            );
+        }
+
+        let borrowed_id_tracker = id_tracker.borrow();
+        let scorer = new_raw_scorer(
+            vec![1.0, 1.0, 1.0, 1.0].as_slice().into(),
+            &borrowed_storage,
+            borrowed_id_tracker.deleted_point_bitslice(),
+        )
+        .unwrap();
+        let res = scorer.peek_top_all(3);
+        assert_eq!(res[0].idx, 2);
+        assert_eq!(res[0].score, 4.0);
+    }
+
     #[test]
     fn test_casts() {
         let data: DenseVector = vec![0.42, 0.069, 333.1, 100500.];
@@ -721,6 +818,7 @@ mod tests {
             let storage2 = open_simple_dense_vector_storage(
                 db,
                 DB_VECTOR_CF,
+                None,
                 4,
                 Distance::Dot,
                 &AtomicBool::new(false),
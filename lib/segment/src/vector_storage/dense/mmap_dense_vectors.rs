@@ -1,6 +1,6 @@
 use std::fs::{File, OpenOptions};
 use std::io::Write;
-use std::mem::{self, size_of, transmute};
+use std::mem::{self, align_of, size_of, transmute};
 use std::path::Path;
 use std::sync::Arc;
 
@@ -24,6 +24,10 @@ const HEADER_SIZE: usize = 4;
 const VECTORS_HEADER: &[u8; HEADER_SIZE] = b"data";
 const DELETED_HEADER: &[u8; HEADER_SIZE] = b"drop";
 
+/// Vectors closer than this are prefetched as a single range, reading a few extra pages is cheaper
+/// than a separate syscall
+const PREFETCH_GAP_BYTES: usize = 16 * 1024;
+
 /// Mem-mapped file for dense vectors
 pub struct MmapDenseVectors<T: PrimitiveVectorElement> {
     pub dim: usize,
@@ -49,10 +53,10 @@ impl<T: PrimitiveVectorElement> MmapDenseVectors<T> {
         with_async_io: bool,
     ) -> OperationResult<Self> {
         // Allocate/open vectors mmap
-        ensure_mmap_file_size(vectors_path, VECTORS_HEADER, None)
+        ensure_mmap_file_size(vectors_path, &vectors_header::<T>(), None)
             .describe("Create mmap data file")?;
         let mmap = mmap_ops::open_read_mmap(vectors_path).describe("Open mmap for reading")?;
-        let num_vectors = (mmap.len() - HEADER_SIZE) / dim / size_of::<T>();
+        let num_vectors = (mmap.len() - vectors_data_start::<T>()) / dim / size_of::<T>();
 
         // Allocate/open deleted mmap
         let deleted_mmap_size = deleted_mmap_size(num_vectors);
@@ -75,7 +79,11 @@ impl<T: PrimitiveVectorElement> MmapDenseVectors<T> {
             // Keep file handle open for async IO
             let vectors_file = File::open(vectors_path)?;
             let raw_size = dim * size_of::<T>();
-            Some(UringReader::new(vectors_file, raw_size, HEADER_SIZE)?)
+            Some(UringReader::new(
+                vectors_file,
+                raw_size,
+                vectors_data_start::<T>(),
+            )?)
         } else {
             None
         };
@@ -100,7 +108,7 @@ impl<T: PrimitiveVectorElement> MmapDenseVectors<T> {
 
     pub fn data_offset(&self, key: PointOffsetType) -> Option<usize> {
         let vector_data_length = self.dim * size_of::<T>();
-        let offset = (key as usize) * vector_data_length + HEADER_SIZE;
+        let offset = (key as usize) * vector_data_length + vectors_data_start::<T>();
         if key >= (self.num_vectors as PointOffsetType) {
             return None;
         }
@@ -123,6 +131,33 @@ impl<T: PrimitiveVectorElement> MmapDenseVectors<T> {
         self.raw_vector_offset(offset)
     }
 
+    /// Advise the kernel to read vectors of the keys from disk in advance.
+    ///
+    /// Vectors close to each other are advised as a single range, to issue fewer syscalls.
+    pub fn prefetch(&self, keys: &[PointOffsetType]) {
+        #[cfg(unix)]
+        {
+            let raw_size = self.raw_size();
+            let mut offsets: Vec<_> = keys
+                .iter()
+                .filter_map(|&key| self.data_offset(key))
+                .collect();
+            offsets.sort_unstable();
+
+            for (start, end) in merge_ranges(&offsets, raw_size, PREFETCH_GAP_BYTES) {
+                if let Err(err) =
+                    self.mmap
+                        .advise_range(memmap2::Advice::WillNeed, start, end - start)
+                {
+                    log::debug!("Failed to advise MADV_WILLNEED for vectors: {err}");
+                    return;
+                }
+            }
+        }
+        #[cfg(not(unix))]
+        let _ = keys;
+    }
+
     pub fn delete(&mut self, key: PointOffsetType) -> bool {
         if self.num_vectors <= key as usize {
             return false;
@@ -224,6 +259,36 @@ fn ensure_mmap_file_size(path: &Path, header: &[u8], size: Option<u64>) -> Opera
     Ok(())
 }
 
+/// Merge ranges of `len` bytes starting at sorted `offsets`, which are at most `max_gap` apart
+#[cfg_attr(not(unix), allow(dead_code))]
+fn merge_ranges(offsets: &[usize], len: usize, max_gap: usize) -> Vec<(usize, usize)> {
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for &offset in offsets {
+        match ranges.last_mut() {
+            Some((_, end)) if offset <= *end + max_gap => *end = (*end).max(offset + len),
+            _ => ranges.push((offset, offset + len)),
+        }
+    }
+    ranges
+}
+
+/// Get start position of vector data in vectors mmap.
+///
+/// The header is padded to keep vectors aligned for their element type. This is a no-op for
+/// element types with an alignment up to the header size, which keeps their existing file layout.
+#[inline]
+const fn vectors_data_start<T>() -> usize {
+    let align = align_of::<T>();
+    HEADER_SIZE.div_ceil(align) * align
+}
+
+/// Header of a newly created vectors mmap, padded to the start of vector data.
+fn vectors_header<T>() -> Vec<u8> {
+    let mut header = VECTORS_HEADER.to_vec();
+    header.resize(vectors_data_start::<T>(), 0);
+    header
+}
+
 /// Get start position of flags `BitSlice` in deleted mmap.
 #[inline]
 const fn deleted_mmap_data_start() -> usize {
@@ -241,3 +306,27 @@ fn deleted_mmap_size(num: usize) -> usize {
     let data_size = num_usizes * unit_size;
     deleted_mmap_data_start() + data_size
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_ranges() {
+        assert!(merge_ranges(&[], 10, 5).is_empty());
+        assert_eq!(
+            merge_ranges(&[0, 10, 20, 100, 112, 200], 10, 5),
+            vec![(0, 30), (100, 122), (200, 210)],
+        );
+    }
+
+    #[test]
+    fn test_vectors_data_start() {
+        // Layout of existing element types must not change
+        assert_eq!(vectors_data_start::<u8>(), HEADER_SIZE);
+        assert_eq!(vectors_data_start::<half::f16>(), HEADER_SIZE);
+        assert_eq!(vectors_data_start::<f32>(), HEADER_SIZE);
+        assert_eq!(vectors_data_start::<f64>(), 8);
+        assert_eq!(&vectors_header::<f64>(), b"data\0\0\0\0");
+    }
+}
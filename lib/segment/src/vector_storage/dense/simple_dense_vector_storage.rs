@@ -1,4 +1,3 @@
-use std::borrow::Cow;
 use std::mem::size_of;
 use std::ops::Range;
 use std::sync::atomic::AtomicBool;
@@ -11,6 +10,7 @@ use log::debug;
 use parking_lot::RwLock;
 use rocksdb::DB;
 
+use crate::common::encryption::StorageCipher;
 use crate::common::operation_error::{check_process_stopped, OperationError, OperationResult};
 use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
 use crate::common::Flusher;
@@ -41,6 +41,7 @@ pub struct SimpleDenseVectorStorage<T: PrimitiveVectorElement> {
 fn open_simple_dense_vector_storage_impl<T: PrimitiveVectorElement>(
     database: Arc<RwLock<DB>>,
     database_column_name: &str,
+    cipher: Option<Arc<StorageCipher>>,
     dim: usize,
     distance: Distance,
     stopped: &AtomicBool,
@@ -48,9 +49,10 @@ fn open_simple_dense_vector_storage_impl<T: PrimitiveVectorElement>(
     let mut vectors = ChunkedVectors::new(dim);
     let (mut deleted, mut deleted_count) = (BitVec::new(), 0);
 
-    let db_wrapper = DatabaseColumnWrapper::new(database, database_column_name);
+    let db_wrapper = DatabaseColumnWrapper::new_encrypted(database, database_column_name, cipher);
 
-    for (key, value) in db_wrapper.lock_db().iter()? {
+    for item in db_wrapper.lock_db().iter()? {
+        let (key, value) = item?;
         let point_id: PointOffsetType = bincode::deserialize(&key)
             .map_err(|_| OperationError::service_error("cannot deserialize point id from db"))?;
         let stored_record: StoredDenseVector<T> = bincode::deserialize(&value)
@@ -89,6 +91,7 @@ fn open_simple_dense_vector_storage_impl<T: PrimitiveVectorElement>(
 pub fn open_simple_dense_vector_storage(
     database: Arc<RwLock<DB>>,
     database_column_name: &str,
+    cipher: Option<Arc<StorageCipher>>,
     dim: usize,
     distance: Distance,
     stopped: &AtomicBool,
@@ -96,6 +99,7 @@ pub fn open_simple_dense_vector_storage(
     let storage = open_simple_dense_vector_storage_impl::<VectorElementType>(
         database,
         database_column_name,
+        cipher,
         dim,
         distance,
         stopped,
@@ -109,6 +113,7 @@ pub fn open_simple_dense_vector_storage(
 pub fn open_simple_dense_byte_vector_storage(
     database: Arc<RwLock<DB>>,
     database_column_name: &str,
+    cipher: Option<Arc<StorageCipher>>,
     dim: usize,
     distance: Distance,
     stopped: &AtomicBool,
@@ -116,6 +121,7 @@ pub fn open_simple_dense_byte_vector_storage(
     let storage = open_simple_dense_vector_storage_impl(
         database,
         database_column_name,
+        cipher,
         dim,
         distance,
         stopped,
@@ -129,6 +135,7 @@ pub fn open_simple_dense_byte_vector_storage(
 pub fn open_simple_dense_half_vector_storage(
     database: Arc<RwLock<DB>>,
     database_column_name: &str,
+    cipher: Option<Arc<StorageCipher>>,
     dim: usize,
     distance: Distance,
     stopped: &AtomicBool,
@@ -136,6 +143,7 @@ pub fn open_simple_dense_half_vector_storage(
     let storage = open_simple_dense_vector_storage_impl(
         database,
         database_column_name,
+        cipher,
         dim,
         distance,
         stopped,
@@ -146,6 +154,28 @@ pub fn open_simple_dense_half_vector_storage(
     )))
 }
 
+pub fn open_simple_dense_double_vector_storage(
+    database: Arc<RwLock<DB>>,
+    database_column_name: &str,
+    cipher: Option<Arc<StorageCipher>>,
+    dim: usize,
+    distance: Distance,
+    stopped: &AtomicBool,
+) -> OperationResult<Arc<AtomicRefCell<VectorStorageEnum>>> {
+    let storage = open_simple_dense_vector_storage_impl(
+        database,
+        database_column_name,
+        cipher,
+        dim,
+        distance,
+        stopped,
+    )?;
+
+    Ok(Arc::new(AtomicRefCell::new(
+        VectorStorageEnum::DenseSimpleDouble(storage),
+    )))
+}
+
 impl<T: PrimitiveVectorElement> SimpleDenseVectorStorage<T> {
     /// Set deleted flag for given key. Returns previous deleted state.
     #[inline]
@@ -215,12 +245,11 @@ impl<T: PrimitiveVectorElement> VectorStorage for SimpleDenseVectorStorage<T> {
     }
 
     fn get_vector(&self, key: PointOffsetType) -> CowVector {
-        CowVector::from(T::slice_to_float_cow(self.vectors.get(key).into()))
+        T::slice_to_cow_vector(self.vectors.get(key).into())
     }
 
     fn insert_vector(&mut self, key: PointOffsetType, vector: VectorRef) -> OperationResult<()> {
-        let vector: &[VectorElementType] = vector.try_into()?;
-        let vector = T::slice_from_float_cow(Cow::from(vector));
+        let vector = T::slice_from_vector_ref(vector)?;
         self.vectors.insert(key, vector.as_ref())?;
         self.set_deleted(key, false);
         self.update_stored(key, false, Some(vector.as_ref()))?;
@@ -238,7 +267,7 @@ impl<T: PrimitiveVectorElement> VectorStorage for SimpleDenseVectorStorage<T> {
             check_process_stopped(stopped)?;
             // Do not perform preprocessing - vectors should be already processed
             let other_vector = other.get_vector(point_id);
-            let other_vector = T::slice_from_float_cow(Cow::try_from(other_vector)?);
+            let other_vector = T::slice_from_vector_ref(other_vector.as_vec_ref())?;
             let other_deleted = other.is_deleted_vector(point_id);
             let new_id = self.vectors.push(other_vector.as_ref())?;
             self.set_deleted(new_id, other_deleted);
@@ -1,6 +1,6 @@
 #[cfg(target_os = "linux")]
 pub mod async_raw_scorer;
-mod chunked_mmap_vectors;
+pub(crate) mod chunked_mmap_vectors;
 mod chunked_utils;
 pub mod chunked_vectors;
 pub mod quantized;
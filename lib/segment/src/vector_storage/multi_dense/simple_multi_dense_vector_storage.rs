@@ -8,6 +8,7 @@ use common::types::PointOffsetType;
 use parking_lot::RwLock;
 use rocksdb::DB;
 
+use crate::common::encryption::StorageCipher;
 use crate::common::operation_error::{check_process_stopped, OperationError, OperationResult};
 use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
 use crate::common::Flusher;
@@ -51,6 +52,7 @@ pub struct SimpleMultiDenseVectorStorage<T: PrimitiveVectorElement> {
 pub fn open_simple_multi_dense_vector_storage(
     database: Arc<RwLock<DB>>,
     database_column_name: &str,
+    cipher: Option<Arc<StorageCipher>>,
     dim: usize,
     distance: Distance,
     multi_vector_config: MultiVectorConfig,
@@ -59,6 +61,7 @@ pub fn open_simple_multi_dense_vector_storage(
     let storage = open_simple_multi_dense_vector_storage_impl(
         database,
         database_column_name,
+        cipher,
         dim,
         distance,
         multi_vector_config,
@@ -72,6 +75,7 @@ pub fn open_simple_multi_dense_vector_storage(
 pub fn open_simple_multi_dense_vector_storage_byte(
     database: Arc<RwLock<DB>>,
     database_column_name: &str,
+    cipher: Option<Arc<StorageCipher>>,
     dim: usize,
     distance: Distance,
     multi_vector_config: MultiVectorConfig,
@@ -80,6 +84,7 @@ pub fn open_simple_multi_dense_vector_storage_byte(
     let storage = open_simple_multi_dense_vector_storage_impl(
         database,
         database_column_name,
+        cipher,
         dim,
         distance,
         multi_vector_config,
@@ -93,6 +98,7 @@ pub fn open_simple_multi_dense_vector_storage_byte(
 pub fn open_simple_multi_dense_vector_storage_half(
     database: Arc<RwLock<DB>>,
     database_column_name: &str,
+    cipher: Option<Arc<StorageCipher>>,
     dim: usize,
     distance: Distance,
     multi_vector_config: MultiVectorConfig,
@@ -101,6 +107,7 @@ pub fn open_simple_multi_dense_vector_storage_half(
     let storage = open_simple_multi_dense_vector_storage_impl(
         database,
         database_column_name,
+        cipher,
         dim,
         distance,
         multi_vector_config,
@@ -114,6 +121,7 @@ pub fn open_simple_multi_dense_vector_storage_half(
 fn open_simple_multi_dense_vector_storage_impl<T: PrimitiveVectorElement>(
     database: Arc<RwLock<DB>>,
     database_column_name: &str,
+    cipher: Option<Arc<StorageCipher>>,
     dim: usize,
     distance: Distance,
     multi_vector_config: MultiVectorConfig,
@@ -122,9 +130,10 @@ fn open_simple_multi_dense_vector_storage_impl<T: PrimitiveVectorElement>(
     let mut vectors = ChunkedVectors::new(dim);
     let mut vectors_metadata = Vec::<MultiVectorMetadata>::new();
     let (mut deleted, mut deleted_count) = (BitVec::new(), 0);
-    let db_wrapper = DatabaseColumnWrapper::new(database, database_column_name);
+    let db_wrapper = DatabaseColumnWrapper::new_encrypted(database, database_column_name, cipher);
     db_wrapper.lock_db().iter()?;
-    for (key, value) in db_wrapper.lock_db().iter()? {
+    for item in db_wrapper.lock_db().iter()? {
+        let (key, value) = item?;
         let point_id: PointOffsetType = bincode::deserialize(&key)
             .map_err(|_| OperationError::service_error("cannot deserialize point id from db"))?;
         let stored_record: StoredMultiDenseVector<T> = bincode::deserialize(&value)
@@ -0,0 +1,239 @@
+use common::types::{PointOffsetType, ScoreType};
+
+use crate::data_types::vectors::{DenseVector, VectorElementType};
+use crate::spaces::metric::Metric;
+use crate::spaces::simple::CosineMetric;
+use crate::types::{Distance, QuantizationConfig, ScalarQuantization};
+use crate::vector_storage::{DenseVectorStorage, VectorStorage, VectorStorageEnum};
+
+/// Number of first vectors, from which the bounds of quantization are derived
+const TRAINING_VECTORS: usize = 1_000;
+
+/// Max value of 8-bit codes
+const MAX_CODE: f32 = u8::MAX as f32;
+
+/// Scalar quantized codes of vectors of an appendable segment, updated on every change of a vector.
+///
+/// Bounds of quantization are derived once from the first vectors of the segment, values outside
+/// of the bounds are clamped. Codes are kept in RAM only and rebuilt when the segment is loaded.
+pub struct AppendableQuantizedVectors {
+    dim: usize,
+    distance: Distance,
+    /// Fraction of values within the bounds, the whole range if not set
+    quantile: Option<f32>,
+    /// `None` until there are enough vectors to derive bounds from
+    encoder: Option<ScalarEncoder>,
+    /// Codes of all vectors, `dim` bytes per vector
+    codes: Vec<u8>,
+}
+
+/// Maps values from `[offset, offset + alpha * MAX_CODE]` to 8-bit codes
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ScalarEncoder {
+    offset: f32,
+    alpha: f32,
+}
+
+impl ScalarEncoder {
+    /// Derive bounds from values, so that `quantile` of them are within bounds
+    fn train(mut values: Vec<f32>, quantile: Option<f32>) -> Self {
+        values.retain(|value| value.is_finite());
+        values.sort_unstable_by(f32::total_cmp);
+        let (min, max) = match (values.first(), values.last()) {
+            (Some(&min), Some(&max)) => match quantile {
+                Some(quantile) if quantile < 1.0 => {
+                    let cut = ((1.0 - quantile) / 2.0 * values.len() as f32) as usize;
+                    (values[cut], values[values.len() - 1 - cut])
+                }
+                _ => (min, max),
+            },
+            _ => (0.0, 0.0),
+        };
+        let alpha = if max > min {
+            (max - min) / MAX_CODE
+        } else {
+            1.0
+        };
+        Self { offset: min, alpha }
+    }
+
+    fn encode(&self, value: f32) -> u8 {
+        ((value - self.offset) / self.alpha)
+            .round()
+            .clamp(0.0, MAX_CODE) as u8
+    }
+
+    fn decode(&self, code: u8) -> f32 {
+        self.offset + self.alpha * f32::from(code)
+    }
+}
+
+impl AppendableQuantizedVectors {
+    /// Quantization of vectors of the storage, `None` if it can't be maintained incrementally.
+    ///
+    /// Only scalar quantization of dense vectors of full precision is supported.
+    pub fn new(
+        quantization_config: &QuantizationConfig,
+        vector_storage: &VectorStorageEnum,
+    ) -> Option<Self> {
+        let QuantizationConfig::Scalar(ScalarQuantization { scalar }) = quantization_config else {
+            return None;
+        };
+        if !matches!(
+            vector_storage,
+            VectorStorageEnum::DenseSimple(_) | VectorStorageEnum::DenseAppendableMemmap(_)
+        ) {
+            return None;
+        }
+
+        let mut quantized = Self {
+            dim: vector_storage.vector_dim(),
+            distance: vector_storage.distance(),
+            quantile: scalar.quantile,
+            encoder: None,
+            codes: Vec::new(),
+        };
+        quantized.train(vector_storage);
+        Some(quantized)
+    }
+
+    /// Whether there are enough vectors to derive bounds of quantization from
+    pub fn is_ready(&self) -> bool {
+        self.encoder.is_some()
+    }
+
+    /// Derive bounds from the first vectors and encode all vectors, once there are enough of them
+    fn train(&mut self, vector_storage: &VectorStorageEnum) {
+        let count = vector_storage.total_vector_count();
+        if count < TRAINING_VECTORS {
+            return;
+        }
+
+        let values = (0..TRAINING_VECTORS as PointOffsetType)
+            .filter_map(|id| dense_vector(vector_storage, id))
+            .flatten()
+            .copied()
+            .collect();
+        self.encoder = Some(ScalarEncoder::train(values, self.quantile));
+        for id in 0..count as PointOffsetType {
+            self.update(id, vector_storage);
+        }
+    }
+
+    /// Encode the vector of the storage with the given id, after it was inserted or updated
+    pub fn update(&mut self, id: PointOffsetType, vector_storage: &VectorStorageEnum) {
+        let Some(encoder) = self.encoder else {
+            self.train(vector_storage);
+            return;
+        };
+        let Some(vector) = dense_vector(vector_storage, id) else {
+            return;
+        };
+
+        let start = id as usize * self.dim;
+        if self.codes.len() < start + self.dim {
+            self.codes.resize(start + self.dim, 0);
+        }
+        for (code, &value) in self.codes[start..start + self.dim].iter_mut().zip(vector) {
+            *code = encoder.encode(value);
+        }
+    }
+
+    /// Scorer of quantized vectors against the query, `None` until bounds are derived
+    pub fn scorer(&self, query: DenseVector) -> Option<AppendableQuantizedScorer<'_>> {
+        let encoder = self.encoder?;
+        let query = match self.distance {
+            Distance::Cosine => <CosineMetric as Metric<VectorElementType>>::preprocess(query),
+            Distance::Euclid | Distance::Dot | Distance::Manhattan => query,
+        };
+        let query_sum = query.iter().sum();
+        Some(AppendableQuantizedScorer {
+            quantized: self,
+            encoder,
+            query,
+            query_sum,
+        })
+    }
+}
+
+/// Approximate similarity of a query to quantized vectors
+pub struct AppendableQuantizedScorer<'a> {
+    quantized: &'a AppendableQuantizedVectors,
+    encoder: ScalarEncoder,
+    query: DenseVector,
+    query_sum: ScoreType,
+}
+
+impl AppendableQuantizedScorer<'_> {
+    /// `None` if the vector was not encoded
+    pub fn score(&self, id: PointOffsetType) -> Option<ScoreType> {
+        let dim = self.quantized.dim;
+        let start = id as usize * dim;
+        let codes = self.quantized.codes.get(start..start + dim)?;
+        let encoder = &self.encoder;
+
+        let score = match self.quantized.distance {
+            // sum(q * (offset + alpha * c)) = offset * sum(q) + alpha * sum(q * c)
+            Distance::Cosine | Distance::Dot => {
+                let dot: ScoreType = self
+                    .query
+                    .iter()
+                    .zip(codes)
+                    .map(|(&q, &c)| q * f32::from(c))
+                    .sum();
+                encoder.offset * self.query_sum + encoder.alpha * dot
+            }
+            Distance::Euclid => -self
+                .query
+                .iter()
+                .zip(codes)
+                .map(|(&q, &c)| (q - encoder.decode(c)).powi(2))
+                .sum::<ScoreType>(),
+            Distance::Manhattan => -self
+                .query
+                .iter()
+                .zip(codes)
+                .map(|(&q, &c)| (q - encoder.decode(c)).abs())
+                .sum::<ScoreType>(),
+        };
+        Some(score)
+    }
+}
+
+/// Vector with the given id, `None` if it's out of range or the storage is not of full precision
+fn dense_vector(
+    vector_storage: &VectorStorageEnum,
+    id: PointOffsetType,
+) -> Option<&[VectorElementType]> {
+    match vector_storage {
+        VectorStorageEnum::DenseSimple(storage) => {
+            ((id as usize) < storage.total_vector_count()).then(|| storage.get_dense(id))
+        }
+        VectorStorageEnum::DenseAppendableMemmap(storage) => {
+            ((id as usize) < storage.total_vector_count()).then(|| storage.get_dense(id))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scalar_encoder() {
+        let values = (0..=100).map(|value| value as f32).collect();
+        let encoder = ScalarEncoder::train(values, None);
+        assert_eq!(encoder.encode(0.0), 0);
+        assert_eq!(encoder.encode(100.0), u8::MAX);
+        assert_eq!(encoder.encode(-10.0), 0);
+        assert_eq!(encoder.encode(1000.0), u8::MAX);
+        assert!((encoder.decode(encoder.encode(42.0)) - 42.0).abs() <= encoder.alpha);
+
+        // Outliers are excluded from the bounds
+        let mut values: Vec<_> = (0..1000).map(|value| (value % 10) as f32).collect();
+        values.push(1_000_000.0);
+        let encoder = ScalarEncoder::train(values, Some(0.99));
+        assert_eq!(encoder.encode(9.0), u8::MAX);
+    }
+}
@@ -1,3 +1,4 @@
+pub mod appendable_quantized_vectors;
 mod quantized_custom_query_scorer;
 mod quantized_mmap_storage;
 pub mod quantized_multivector_storage;
@@ -148,6 +148,38 @@ where
         }
         sum
     }
+
+    /// Custom `score_sum_similarity` implementation for quantized vectors
+    fn score_point_sum_similarity(&self, query: &Vec<TEncodedQuery>, vector_index: u32) -> f32 {
+        let vectors_count = self.offsets[vector_index as usize].count;
+        let vectors_offset = self.offsets[vector_index as usize].offset;
+        let mut sum = 0.0;
+        for inner_query in query {
+            for i in 0..vectors_count {
+                sum += self
+                    .quantized_storage
+                    .score_point(inner_query, vectors_offset + i);
+            }
+        }
+        sum
+    }
+
+    /// Custom `score_sum_similarity` implementation for quantized vectors
+    fn score_internal_sum_similarity(&self, vector_a_index: u32, vector_b_index: u32) -> f32 {
+        let vector_a_count = self.offsets[vector_a_index as usize].count;
+        let vector_b_count = self.offsets[vector_b_index as usize].count;
+        let vector_a_offset = self.offsets[vector_a_index as usize].offset;
+        let vector_b_offset = self.offsets[vector_b_index as usize].offset;
+        let mut sum = 0.0;
+        for a in 0..vector_a_count {
+            for b in 0..vector_b_count {
+                sum += self
+                    .quantized_storage
+                    .score_internal(vector_a_offset + a, vector_b_offset + b);
+            }
+        }
+        sum
+    }
 }
 
 impl<TEncodedQuery, QuantizedStorage> EncodedVectors<Vec<TEncodedQuery>>
@@ -186,12 +218,22 @@ where
     fn score_point(&self, query: &Vec<TEncodedQuery>, i: u32) -> f32 {
         match self.multi_vector_config.comparator {
             MultiVectorComparator::MaxSim => self.score_point_max_similarity(query, i),
+            MultiVectorComparator::MeanSim => {
+                let pairs_count = query.len() * self.offsets[i as usize].count as usize;
+                self.score_point_sum_similarity(query, i) / pairs_count as f32
+            }
+            MultiVectorComparator::Sum => self.score_point_sum_similarity(query, i),
         }
     }
 
     fn score_internal(&self, i: u32, j: u32) -> f32 {
         match self.multi_vector_config.comparator {
             MultiVectorComparator::MaxSim => self.score_internal_max_similarity(i, j),
+            MultiVectorComparator::MeanSim => {
+                let pairs_count = self.offsets[i as usize].count * self.offsets[j as usize].count;
+                self.score_internal_sum_similarity(i, j) / pairs_count as f32
+            }
+            MultiVectorComparator::Sum => self.score_internal_sum_similarity(i, j),
         }
     }
 }
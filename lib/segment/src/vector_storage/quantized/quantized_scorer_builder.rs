@@ -10,7 +10,7 @@ use crate::common::operation_error::OperationResult;
 use crate::data_types::primitive::PrimitiveVectorElement;
 use crate::data_types::vectors::{
     DenseVector, MultiDenseVector, QueryVector, VectorElementType, VectorElementTypeByte,
-    VectorElementTypeHalf,
+    VectorElementTypeDouble, VectorElementTypeHalf,
 };
 use crate::spaces::metric::Metric;
 use crate::spaces::simple::{CosineMetric, DotProductMetric, EuclidMetric, ManhattanMetric};
@@ -83,6 +83,20 @@ impl<'a> QuantizedScorerBuilder<'a> {
                     self.build_with_metric::<VectorElementTypeHalf, ManhattanMetric>()
                 }
             },
+            VectorStorageDatatype::Float64 => match self.distance {
+                Distance::Cosine => {
+                    self.build_with_metric::<VectorElementTypeDouble, CosineMetric>()
+                }
+                Distance::Euclid => {
+                    self.build_with_metric::<VectorElementTypeDouble, EuclidMetric>()
+                }
+                Distance::Dot => {
+                    self.build_with_metric::<VectorElementTypeDouble, DotProductMetric>()
+                }
+                Distance::Manhattan => {
+                    self.build_with_metric::<VectorElementTypeDouble, ManhattanMetric>()
+                }
+            },
         }
     }
 
@@ -17,6 +17,7 @@ use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::vector_utils::TrySetCapacityExact;
 use crate::data_types::primitive::PrimitiveVectorElement;
 use crate::data_types::vectors::{QueryVector, VectorElementType};
+use crate::index::vector_index_info::QuantizationInfo;
 use crate::types::{
     BinaryQuantization, BinaryQuantizationConfig, CompressionRatio, Distance, ProductQuantization,
     ProductQuantizationConfig, QuantizationConfig, ScalarQuantization, ScalarQuantizationConfig,
@@ -100,6 +101,36 @@ impl QuantizedVectors {
         }
     }
 
+    pub fn is_on_disk(&self) -> bool {
+        match self.storage_impl {
+            QuantizedVectorStorage::ScalarRam(_) => false,
+            QuantizedVectorStorage::ScalarMmap(_) => true,
+            QuantizedVectorStorage::PQRam(_) => false,
+            QuantizedVectorStorage::PQMmap(_) => true,
+            QuantizedVectorStorage::BinaryRam(_) => false,
+            QuantizedVectorStorage::BinaryMmap(_) => true,
+            QuantizedVectorStorage::ScalarRamMulti(_) => false,
+            QuantizedVectorStorage::ScalarMmapMulti(_) => true,
+            QuantizedVectorStorage::PQRamMulti(_) => false,
+            QuantizedVectorStorage::PQMmapMulti(_) => true,
+            QuantizedVectorStorage::BinaryRamMulti(_) => false,
+            QuantizedVectorStorage::BinaryMmapMulti(_) => true,
+        }
+    }
+
+    pub fn info(&self) -> QuantizationInfo {
+        QuantizationInfo {
+            config: self.config.quantization_config.clone(),
+            on_disk: self.is_on_disk(),
+            size_bytes: self
+                .files()
+                .iter()
+                .filter_map(|path| path.metadata().ok())
+                .map(|metadata| metadata.len())
+                .sum(),
+        }
+    }
+
     pub fn raw_scorer<'a>(
         &'a self,
         query: QueryVector,
@@ -185,6 +216,9 @@ impl QuantizedVectors {
             VectorStorageEnum::DenseSimpleHalf(v) => {
                 Self::create_impl(v, quantization_config, path, max_threads, stopped)
             }
+            VectorStorageEnum::DenseSimpleDouble(v) => {
+                Self::create_impl(v, quantization_config, path, max_threads, stopped)
+            }
             VectorStorageEnum::DenseMemmap(v) => {
                 Self::create_impl(v.as_ref(), quantization_config, path, max_threads, stopped)
             }
@@ -194,6 +228,9 @@ impl QuantizedVectors {
             VectorStorageEnum::DenseMemmapHalf(v) => {
                 Self::create_impl(v.as_ref(), quantization_config, path, max_threads, stopped)
             }
+            VectorStorageEnum::DenseMemmapDouble(v) => {
+                Self::create_impl(v.as_ref(), quantization_config, path, max_threads, stopped)
+            }
             VectorStorageEnum::DenseAppendableMemmap(v) => {
                 Self::create_impl(v.as_ref(), quantization_config, path, max_threads, stopped)
             }
@@ -203,6 +240,9 @@ impl QuantizedVectors {
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => {
                 Self::create_impl(v.as_ref(), quantization_config, path, max_threads, stopped)
             }
+            VectorStorageEnum::DenseAppendableMemmapDouble(v) => {
+                Self::create_impl(v.as_ref(), quantization_config, path, max_threads, stopped)
+            }
             VectorStorageEnum::SparseSimple(_) => Err(OperationError::WrongSparse),
             VectorStorageEnum::MultiDenseSimple(v) => {
                 Self::create_multi_impl(v, quantization_config, path, max_threads, stopped)
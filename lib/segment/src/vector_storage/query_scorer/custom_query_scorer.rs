@@ -54,6 +54,28 @@ impl<
     }
 }
 
+impl<
+        'a,
+        TElement: PrimitiveVectorElement,
+        TMetric: Metric<TElement>,
+        TVectorStorage: DenseVectorStorage<TElement>,
+        TInputQuery: Query<DenseVector>,
+        TStoredQuery: Query<TypedDenseVector<TElement>>,
+    > CustomQueryScorer<'a, TElement, TMetric, TVectorStorage, TInputQuery, TStoredQuery>
+{
+    /// Scorer for a query, which vectors are already preprocessed and converted into storage
+    /// element type
+    pub fn from_preprocessed(query: TStoredQuery, vector_storage: &'a TVectorStorage) -> Self {
+        Self {
+            query,
+            vector_storage,
+            metric: PhantomData,
+            _input_query: PhantomData,
+            _element: PhantomData,
+        }
+    }
+}
+
 impl<
         'a,
         TElement: PrimitiveVectorElement,
@@ -79,4 +101,8 @@ impl<
     fn score_internal(&self, _point_a: PointOffsetType, _point_b: PointOffsetType) -> ScoreType {
         unimplemented!("Custom scorer can compare against multiple vectors, not just one")
     }
+
+    fn prefetch(&self, points: &[PointOffsetType]) {
+        self.vector_storage.prefetch_dense(points);
+    }
 }
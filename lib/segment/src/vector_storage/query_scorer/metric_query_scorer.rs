@@ -40,6 +40,18 @@ impl<
             metric: PhantomData,
         }
     }
+
+    /// Scorer for a query, which is already preprocessed and converted into storage element type
+    pub fn from_preprocessed(
+        query: TypedDenseVector<TElement>,
+        vector_storage: &'a TVectorStorage,
+    ) -> Self {
+        Self {
+            query,
+            vector_storage,
+            metric: PhantomData,
+        }
+    }
 }
 
 impl<
@@ -64,4 +76,8 @@ impl<
         let v2 = self.vector_storage.get_dense(point_b);
         TMetric::similarity(v1, v2)
     }
+
+    fn prefetch(&self, points: &[PointOffsetType]) {
+        self.vector_storage.prefetch_dense(points);
+    }
 }
@@ -17,6 +17,9 @@ pub trait QueryScorer<TVector: ?Sized> {
     fn score(&self, v2: &TVector) -> ScoreType;
 
     fn score_internal(&self, point_a: PointOffsetType, point_b: PointOffsetType) -> ScoreType;
+
+    /// Hint that the stored vectors of `points` are going to be scored soon
+    fn prefetch(&self, _points: &[PointOffsetType]) {}
 }
 
 /// Colbert MaxSim metric, metric for multi-dense vectors
@@ -44,6 +47,32 @@ pub fn score_max_similarity<T: PrimitiveVectorElement, TMetric: Metric<T>>(
     sum
 }
 
+/// Sum of similarities between all pairs of vectors of multi-dense vectors
+/// This metric is also implemented in `QuantizedMultivectorStorage` structure for quantized data.
+pub fn score_sum_similarity<T: PrimitiveVectorElement, TMetric: Metric<T>>(
+    multi_dense_a: TypedMultiDenseVectorRef<T>,
+    multi_dense_b: TypedMultiDenseVectorRef<T>,
+) -> ScoreType {
+    debug_assert!(!multi_dense_a.is_empty());
+    debug_assert!(!multi_dense_b.is_empty());
+    let mut sum = 0.0;
+    for dense_a in multi_dense_a.multi_vectors() {
+        for dense_b in multi_dense_b.multi_vectors() {
+            sum += TMetric::similarity(dense_a, dense_b);
+        }
+    }
+    sum
+}
+
+/// Mean of similarities between all pairs of vectors of multi-dense vectors
+pub fn score_mean_similarity<T: PrimitiveVectorElement, TMetric: Metric<T>>(
+    multi_dense_a: TypedMultiDenseVectorRef<T>,
+    multi_dense_b: TypedMultiDenseVectorRef<T>,
+) -> ScoreType {
+    let pairs_count = multi_dense_a.len() * multi_dense_b.len();
+    score_sum_similarity::<T, TMetric>(multi_dense_a, multi_dense_b) / pairs_count as ScoreType
+}
+
 fn score_multi<T: PrimitiveVectorElement, TMetric: Metric<T>>(
     multi_vector_config: &MultiVectorConfig,
     multi_dense_a: TypedMultiDenseVectorRef<T>,
@@ -53,5 +82,43 @@ fn score_multi<T: PrimitiveVectorElement, TMetric: Metric<T>>(
         MultiVectorComparator::MaxSim => {
             score_max_similarity::<T, TMetric>(multi_dense_a, multi_dense_b)
         }
+        MultiVectorComparator::MeanSim => {
+            score_mean_similarity::<T, TMetric>(multi_dense_a, multi_dense_b)
+        }
+        MultiVectorComparator::Sum => {
+            score_sum_similarity::<T, TMetric>(multi_dense_a, multi_dense_b)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_types::vectors::VectorElementType;
+    use crate::spaces::simple::DotProductMetric;
+
+    #[test]
+    fn test_multi_vector_comparators() {
+        let multi_dense_a = TypedMultiDenseVectorRef::<VectorElementType> {
+            flattened_vectors: &[1.0, 0.0, 0.0, 1.0],
+            dim: 2,
+        };
+        let multi_dense_b = TypedMultiDenseVectorRef::<VectorElementType> {
+            flattened_vectors: &[1.0, 0.0, 1.0, 1.0],
+            dim: 2,
+        };
+
+        let score = |comparator| {
+            score_multi::<VectorElementType, DotProductMetric>(
+                &MultiVectorConfig { comparator },
+                multi_dense_a,
+                multi_dense_b,
+            )
+        };
+
+        // Pairwise similarities are: 1.0, 1.0, 0.0, 1.0
+        assert_eq!(score(MultiVectorComparator::MaxSim), 2.0);
+        assert_eq!(score(MultiVectorComparator::MeanSim), 0.75);
+        assert_eq!(score(MultiVectorComparator::Sum), 3.0);
     }
 }
@@ -1,3 +1,4 @@
+use std::cell::Cell;
 use std::sync::atomic::{AtomicBool, Ordering};
 
 use bitvec::prelude::BitSlice;
@@ -10,18 +11,25 @@ use super::query_scorer::multi_custom_query_scorer::MultiCustomQueryScorer;
 use super::query_scorer::sparse_custom_query_scorer::SparseCustomQueryScorer;
 use super::{DenseVectorStorage, MultiVectorStorage, SparseVectorStorage, VectorStorageEnum};
 use crate::common::operation_error::{OperationError, OperationResult};
+use crate::common::request_usage::RequestUsage;
+use crate::data_types::primitive::PrimitiveVectorElement;
 use crate::data_types::vectors::{
-    DenseVector, MultiDenseVector, QueryVector, VectorElementType, VectorElementTypeByte,
-    VectorElementTypeHalf,
+    DenseDoubleVector, DenseVector, MultiDenseVector, QueryVector, VectorElementType,
+    VectorElementTypeByte, VectorElementTypeDouble, VectorElementTypeHalf,
 };
 use crate::spaces::metric::Metric;
+use crate::spaces::metric_f64::preprocess_double;
 use crate::spaces::simple::{CosineMetric, DotProductMetric, EuclidMetric, ManhattanMetric};
 use crate::spaces::tools::peek_top_largest_iterable;
 use crate::types::Distance;
+use crate::vector_storage::dense::memmap_dense_vector_storage::MemmapDenseVectorStorage;
 use crate::vector_storage::query_scorer::metric_query_scorer::MetricQueryScorer;
 use crate::vector_storage::query_scorer::multi_metric_query_scorer::MultiMetricQueryScorer;
 use crate::vector_storage::query_scorer::QueryScorer;
 
+/// Number of points, which vectors are prefetched while the previous batch is scored
+const PREFETCH_BATCH_SIZE: usize = 64;
+
 /// RawScorer composition:
 ///                                              Metric
 ///                                             ┌───────────────────┐
@@ -103,77 +111,169 @@ where
     vector: std::marker::PhantomData<*const TVector>,
 }
 
+/// Iterate points in batches, prefetching vectors of the next batch while the current one is
+/// scored, so that reading vectors from disk overlaps with scoring.
+fn with_prefetch<'b>(
+    mut points: impl Iterator<Item = PointOffsetType> + 'b,
+    prefetch: impl Fn(&[PointOffsetType]) + 'b,
+) -> impl Iterator<Item = PointOffsetType> + 'b {
+    let mut current: Vec<_> = points.by_ref().take(PREFETCH_BATCH_SIZE).collect();
+    prefetch(&current);
+    std::iter::from_fn(move || {
+        if current.is_empty() {
+            return None;
+        }
+        let next: Vec<_> = points.by_ref().take(PREFETCH_BATCH_SIZE).collect();
+        prefetch(&next);
+        Some(std::mem::replace(&mut current, next))
+    })
+    .flatten()
+}
+
+/// Try to create io_uring based scorer for the memmap storage, if async IO is enabled for it.
+///
+/// Returns `None` if the storage has no async reader, or the async scorer can't be initialized,
+/// in which case the regular mmap based scorer should be used.
+#[cfg_attr(not(target_os = "linux"), allow(unused_variables))]
+fn try_async_raw_scorer<'a, T: PrimitiveVectorElement>(
+    query: &QueryVector,
+    vector_storage: &'a MemmapDenseVectorStorage<T>,
+    point_deleted: &'a BitSlice,
+    is_stopped: &'a AtomicBool,
+) -> Option<Box<dyn RawScorer + 'a>>
+where
+    CosineMetric: Metric<T>,
+    EuclidMetric: Metric<T>,
+    DotProductMetric: Metric<T>,
+    ManhattanMetric: Metric<T>,
+{
+    if !vector_storage.has_async_reader() {
+        return None;
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        let scorer_result =
+            super::async_raw_scorer::new(query.clone(), vector_storage, point_deleted, is_stopped);
+        match scorer_result {
+            Ok(raw_scorer) => return Some(raw_scorer),
+            Err(err) => log::error!("failed to initialize async raw scorer: {err}"),
+        };
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    log::warn!("async raw scorer is only supported on Linux");
+
+    None
+}
+
 pub fn new_stoppable_raw_scorer<'a>(
     query: QueryVector,
     vector_storage: &'a VectorStorageEnum,
     point_deleted: &'a BitSlice,
     is_stopped: &'a AtomicBool,
 ) -> OperationResult<Box<dyn RawScorer + 'a>> {
+    new_stoppable_raw_scorer_with_distance(
+        query,
+        vector_storage,
+        point_deleted,
+        is_stopped,
+        vector_storage.distance(),
+    )
+}
+
+/// Create raw scorer, which compares vectors using the given `distance`
+/// instead of the one configured for the storage.
+///
+/// Stored vectors are used as is, so the caller is responsible for checking that the
+/// distance is applicable to them, see [`Distance::is_applicable_to`].
+pub fn new_stoppable_raw_scorer_with_distance<'a>(
+    query: QueryVector,
+    vector_storage: &'a VectorStorageEnum,
+    point_deleted: &'a BitSlice,
+    is_stopped: &'a AtomicBool,
+    distance: Distance,
+) -> OperationResult<Box<dyn RawScorer + 'a>> {
+    // Async scorer always uses the distance of the storage
+    let use_async = distance == vector_storage.distance();
     match vector_storage {
-        VectorStorageEnum::DenseSimple(vs) => raw_scorer_impl(query, vs, point_deleted, is_stopped),
+        VectorStorageEnum::DenseSimple(vs) => {
+            raw_scorer_impl(query, vs, point_deleted, is_stopped, distance)
+        }
         VectorStorageEnum::DenseSimpleByte(vs) => {
-            raw_scorer_byte_impl(query, vs, point_deleted, is_stopped)
+            raw_scorer_byte_impl(query, vs, point_deleted, is_stopped, distance)
         }
         VectorStorageEnum::DenseSimpleHalf(vs) => {
-            raw_scorer_half_impl(query, vs, point_deleted, is_stopped)
+            raw_scorer_half_impl(query, vs, point_deleted, is_stopped, distance)
+        }
+        VectorStorageEnum::DenseSimpleDouble(vs) => {
+            raw_scorer_double_impl(query, vs, point_deleted, is_stopped, distance)
         }
 
         VectorStorageEnum::DenseMemmap(vs) => {
-            if vs.has_async_reader() {
-                #[cfg(target_os = "linux")]
-                {
-                    let scorer_result =
-                        super::async_raw_scorer::new(query.clone(), vs, point_deleted, is_stopped);
-                    match scorer_result {
-                        Ok(raw_scorer) => return Ok(raw_scorer),
-                        Err(err) => log::error!("failed to initialize async raw scorer: {err}"),
-                    };
-                }
-
-                #[cfg(not(target_os = "linux"))]
-                log::warn!("async raw scorer is only supported on Linux");
+            if let Some(raw_scorer) = use_async
+                .then(|| try_async_raw_scorer(&query, vs, point_deleted, is_stopped))
+                .flatten()
+            {
+                return Ok(raw_scorer);
             }
-
-            raw_scorer_impl(query, vs.as_ref(), point_deleted, is_stopped)
+            raw_scorer_impl(query, vs.as_ref(), point_deleted, is_stopped, distance)
         }
-
-        // TODO(byte_storage): Implement async raw scorer for DenseMemmapByte and DenseMemmapHalf
         VectorStorageEnum::DenseMemmapByte(vs) => {
-            raw_scorer_byte_impl(query, vs.as_ref(), point_deleted, is_stopped)
+            if let Some(raw_scorer) = use_async
+                .then(|| try_async_raw_scorer(&query, vs, point_deleted, is_stopped))
+                .flatten()
+            {
+                return Ok(raw_scorer);
+            }
+            raw_scorer_byte_impl(query, vs.as_ref(), point_deleted, is_stopped, distance)
         }
         VectorStorageEnum::DenseMemmapHalf(vs) => {
-            raw_scorer_half_impl(query, vs.as_ref(), point_deleted, is_stopped)
+            if let Some(raw_scorer) = use_async
+                .then(|| try_async_raw_scorer(&query, vs, point_deleted, is_stopped))
+                .flatten()
+            {
+                return Ok(raw_scorer);
+            }
+            raw_scorer_half_impl(query, vs.as_ref(), point_deleted, is_stopped, distance)
+        }
+        VectorStorageEnum::DenseMemmapDouble(vs) => {
+            // Async scorer takes single precision queries, so it is not used for double storages
+            raw_scorer_double_impl(query, vs.as_ref(), point_deleted, is_stopped, distance)
         }
 
         VectorStorageEnum::DenseAppendableMemmap(vs) => {
-            raw_scorer_impl(query, vs.as_ref(), point_deleted, is_stopped)
+            raw_scorer_impl(query, vs.as_ref(), point_deleted, is_stopped, distance)
         }
         VectorStorageEnum::DenseAppendableMemmapByte(vs) => {
-            raw_scorer_byte_impl(query, vs.as_ref(), point_deleted, is_stopped)
+            raw_scorer_byte_impl(query, vs.as_ref(), point_deleted, is_stopped, distance)
         }
         VectorStorageEnum::DenseAppendableMemmapHalf(vs) => {
-            raw_scorer_half_impl(query, vs.as_ref(), point_deleted, is_stopped)
+            raw_scorer_half_impl(query, vs.as_ref(), point_deleted, is_stopped, distance)
+        }
+        VectorStorageEnum::DenseAppendableMemmapDouble(vs) => {
+            raw_scorer_double_impl(query, vs.as_ref(), point_deleted, is_stopped, distance)
         }
         VectorStorageEnum::SparseSimple(vs) => {
             raw_sparse_scorer_impl(query, vs, point_deleted, is_stopped)
         }
         VectorStorageEnum::MultiDenseSimple(vs) => {
-            raw_multi_scorer_impl(query, vs, point_deleted, is_stopped)
+            raw_multi_scorer_impl(query, vs, point_deleted, is_stopped, distance)
         }
         VectorStorageEnum::MultiDenseSimpleByte(vs) => {
-            raw_multi_scorer_byte_impl(query, vs, point_deleted, is_stopped)
+            raw_multi_scorer_byte_impl(query, vs, point_deleted, is_stopped, distance)
         }
         VectorStorageEnum::MultiDenseSimpleHalf(vs) => {
-            raw_multi_scorer_half_impl(query, vs, point_deleted, is_stopped)
+            raw_multi_scorer_half_impl(query, vs, point_deleted, is_stopped, distance)
         }
         VectorStorageEnum::MultiDenseAppendableMemmap(vs) => {
-            raw_multi_scorer_impl(query, vs.as_ref(), point_deleted, is_stopped)
+            raw_multi_scorer_impl(query, vs.as_ref(), point_deleted, is_stopped, distance)
         }
         VectorStorageEnum::MultiDenseAppendableMemmapByte(vs) => {
-            raw_multi_scorer_byte_impl(query, vs.as_ref(), point_deleted, is_stopped)
+            raw_multi_scorer_byte_impl(query, vs.as_ref(), point_deleted, is_stopped, distance)
         }
         VectorStorageEnum::MultiDenseAppendableMemmapHalf(vs) => {
-            raw_multi_scorer_half_impl(query, vs.as_ref(), point_deleted, is_stopped)
+            raw_multi_scorer_half_impl(query, vs.as_ref(), point_deleted, is_stopped, distance)
         }
     }
 }
@@ -234,8 +334,9 @@ pub fn raw_scorer_impl<'a, TVectorStorage: DenseVectorStorage<VectorElementType>
     vector_storage: &'a TVectorStorage,
     point_deleted: &'a BitSlice,
     is_stopped: &'a AtomicBool,
+    distance: Distance,
 ) -> OperationResult<Box<dyn RawScorer + 'a>> {
-    match vector_storage.distance() {
+    match distance {
         Distance::Cosine => new_scorer_with_metric::<CosineMetric, _>(
             query,
             vector_storage,
@@ -328,8 +429,9 @@ pub fn raw_scorer_byte_impl<'a, TVectorStorage: DenseVectorStorage<VectorElement
     vector_storage: &'a TVectorStorage,
     point_deleted: &'a BitSlice,
     is_stopped: &'a AtomicBool,
+    distance: Distance,
 ) -> OperationResult<Box<dyn RawScorer + 'a>> {
-    match vector_storage.distance() {
+    match distance {
         Distance::Cosine => new_scorer_byte_with_metric::<CosineMetric, _>(
             query,
             vector_storage,
@@ -422,8 +524,9 @@ pub fn raw_scorer_half_impl<'a, TVectorStorage: DenseVectorStorage<VectorElement
     vector_storage: &'a TVectorStorage,
     point_deleted: &'a BitSlice,
     is_stopped: &'a AtomicBool,
+    distance: Distance,
 ) -> OperationResult<Box<dyn RawScorer + 'a>> {
-    match vector_storage.distance() {
+    match distance {
         Distance::Cosine => new_scorer_half_with_metric::<CosineMetric, _>(
             query,
             vector_storage,
@@ -511,6 +614,116 @@ fn new_scorer_half_with_metric<
     }
 }
 
+pub fn raw_scorer_double_impl<'a, TVectorStorage: DenseVectorStorage<VectorElementTypeDouble>>(
+    query: QueryVector,
+    vector_storage: &'a TVectorStorage,
+    point_deleted: &'a BitSlice,
+    is_stopped: &'a AtomicBool,
+    distance: Distance,
+) -> OperationResult<Box<dyn RawScorer + 'a>> {
+    match distance {
+        Distance::Cosine => new_scorer_double_with_metric::<CosineMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
+        Distance::Euclid => new_scorer_double_with_metric::<EuclidMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
+        Distance::Dot => new_scorer_double_with_metric::<DotProductMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
+        Distance::Manhattan => new_scorer_double_with_metric::<ManhattanMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
+    }
+}
+
+fn new_scorer_double_with_metric<
+    'a,
+    TMetric: Metric<VectorElementTypeDouble> + 'a,
+    TVectorStorage: DenseVectorStorage<VectorElementTypeDouble>,
+>(
+    query: QueryVector,
+    vector_storage: &'a TVectorStorage,
+    point_deleted: &'a BitSlice,
+    is_stopped: &'a AtomicBool,
+) -> OperationResult<Box<dyn RawScorer + 'a>> {
+    let vec_deleted = vector_storage.deleted_vector_bitslice();
+    // Queries are preprocessed in double precision, not to lose it before scoring
+    let preprocess = |vector: DenseDoubleVector| Ok(preprocess_double(TMetric::distance(), vector));
+    match query {
+        QueryVector::Nearest(vector) => raw_scorer_from_query_scorer(
+            MetricQueryScorer::<VectorElementTypeDouble, TMetric, _>::from_preprocessed(
+                preprocess_double(TMetric::distance(), vector.try_into()?),
+                vector_storage,
+            ),
+            point_deleted,
+            vec_deleted,
+            is_stopped,
+        ),
+        QueryVector::Recommend(reco_query) => {
+            let reco_query: RecoQuery<DenseDoubleVector> = reco_query.transform_into()?;
+            let reco_query = reco_query.transform(preprocess)?;
+            raw_scorer_from_query_scorer(
+                CustomQueryScorer::<
+                    VectorElementTypeDouble,
+                    TMetric,
+                    _,
+                    RecoQuery<DenseVector>,
+                    _,
+                >::from_preprocessed(reco_query, vector_storage),
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
+        QueryVector::Discovery(discovery_query) => {
+            let discovery_query: DiscoveryQuery<DenseDoubleVector> =
+                discovery_query.transform_into()?;
+            let discovery_query = discovery_query.transform(preprocess)?;
+            raw_scorer_from_query_scorer(
+                CustomQueryScorer::<
+                    VectorElementTypeDouble,
+                    TMetric,
+                    _,
+                    DiscoveryQuery<DenseVector>,
+                    _,
+                >::from_preprocessed(discovery_query, vector_storage),
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
+        QueryVector::Context(context_query) => {
+            let context_query: ContextQuery<DenseDoubleVector> = context_query.transform_into()?;
+            let context_query = context_query.transform(preprocess)?;
+            raw_scorer_from_query_scorer(
+                CustomQueryScorer::<
+                    VectorElementTypeDouble,
+                    TMetric,
+                    _,
+                    ContextQuery<DenseVector>,
+                    _,
+                >::from_preprocessed(context_query, vector_storage),
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
+    }
+}
+
 pub fn raw_scorer_from_query_scorer<'a, TVector, TQueryScorer>(
     query_scorer: TQueryScorer,
     point_deleted: &'a BitSlice,
@@ -535,8 +748,9 @@ pub fn raw_multi_scorer_impl<'a, TVectorStorage: MultiVectorStorage<VectorElemen
     vector_storage: &'a TVectorStorage,
     point_deleted: &'a BitSlice,
     is_stopped: &'a AtomicBool,
+    distance: Distance,
 ) -> OperationResult<Box<dyn RawScorer + 'a>> {
-    match vector_storage.distance() {
+    match distance {
         Distance::Cosine => new_multi_scorer_with_metric::<CosineMetric, _>(
             query,
             vector_storage,
@@ -630,8 +844,9 @@ pub fn raw_multi_scorer_byte_impl<'a, TVectorStorage: MultiVectorStorage<VectorE
     vector_storage: &'a TVectorStorage,
     point_deleted: &'a BitSlice,
     is_stopped: &'a AtomicBool,
+    distance: Distance,
 ) -> OperationResult<Box<dyn RawScorer + 'a>> {
-    match vector_storage.distance() {
+    match distance {
         Distance::Cosine => new_multi_scorer_byte_with_metric::<CosineMetric, _>(
             query,
             vector_storage,
@@ -725,8 +940,9 @@ pub fn raw_multi_scorer_half_impl<'a, TVectorStorage: MultiVectorStorage<VectorE
     vector_storage: &'a TVectorStorage,
     point_deleted: &'a BitSlice,
     is_stopped: &'a AtomicBool,
+    distance: Distance,
 ) -> OperationResult<Box<dyn RawScorer + 'a>> {
-    match vector_storage.distance() {
+    match distance {
         Distance::Cosine => new_multi_scorer_half_with_metric::<CosineMetric, _>(
             query,
             vector_storage,
@@ -876,22 +1092,25 @@ where
         points: &mut dyn Iterator<Item = PointOffsetType>,
         top: usize,
     ) -> Vec<ScoredPointOffset> {
-        let scores = points
+        let points = points
             .take_while(|_| !self.is_stopped.load(Ordering::Relaxed))
-            .filter(|point_id| self.check_vector(*point_id))
-            .map(|point_id| ScoredPointOffset {
-                idx: point_id,
-                score: self.query_scorer.score_stored(point_id),
+            .filter(|point_id| self.check_vector(*point_id));
+        let scores =
+            with_prefetch(points, |batch| self.query_scorer.prefetch(batch)).map(|point_id| {
+                ScoredPointOffset {
+                    idx: point_id,
+                    score: self.query_scorer.score_stored(point_id),
+                }
             });
         peek_top_largest_iterable(scores, top)
     }
 
     fn peek_top_all(&self, top: usize) -> Vec<ScoredPointOffset> {
-        let scores = (0..self.point_deleted.len() as PointOffsetType)
+        let points = (0..self.point_deleted.len() as PointOffsetType)
             .take_while(|_| !self.is_stopped.load(Ordering::Relaxed))
-            .filter(|point_id| self.check_vector(*point_id))
-            .map(|point_id| {
-                let point_id = point_id as PointOffsetType;
+            .filter(|point_id| self.check_vector(*point_id));
+        let scores =
+            with_prefetch(points, |batch| self.query_scorer.prefetch(batch)).map(|point_id| {
                 ScoredPointOffset {
                     idx: point_id,
                     score: self.query_scorer.score_stored(point_id),
@@ -901,6 +1120,90 @@ where
     }
 }
 
+/// Count vectors scored by the scorer into the usage of the request, if the usage is requested.
+///
+/// `vector_count` is the number of available vectors, which are scored by `peek_top_all`.
+pub fn new_metered_raw_scorer<'a>(
+    raw_scorer: Box<dyn RawScorer + 'a>,
+    usage: Option<&'a RequestUsage>,
+    vector_count: usize,
+) -> Box<dyn RawScorer + 'a> {
+    match usage {
+        Some(usage) => Box::new(MeteredRawScorer {
+            raw_scorer,
+            usage,
+            vector_count,
+            scored: Cell::new(0),
+        }),
+        None => raw_scorer,
+    }
+}
+
+/// Counts scored vectors locally, and adds them to the shared usage once dropped
+struct MeteredRawScorer<'a> {
+    raw_scorer: Box<dyn RawScorer + 'a>,
+    usage: &'a RequestUsage,
+    vector_count: usize,
+    scored: Cell<usize>,
+}
+
+impl MeteredRawScorer<'_> {
+    fn count(&self, scored: usize) {
+        self.scored.set(self.scored.get() + scored);
+    }
+}
+
+impl Drop for MeteredRawScorer<'_> {
+    fn drop(&mut self) {
+        self.usage.add_vectors_scored(self.scored.get());
+    }
+}
+
+impl RawScorer for MeteredRawScorer<'_> {
+    fn score_points(&self, points: &[PointOffsetType], scores: &mut [ScoredPointOffset]) -> usize {
+        let scored = self.raw_scorer.score_points(points, scores);
+        self.count(scored);
+        scored
+    }
+
+    fn score_points_unfiltered(
+        &self,
+        points: &mut dyn Iterator<Item = PointOffsetType>,
+    ) -> Vec<ScoredPointOffset> {
+        let scores = self.raw_scorer.score_points_unfiltered(points);
+        self.count(scores.len());
+        scores
+    }
+
+    fn check_vector(&self, point: PointOffsetType) -> bool {
+        self.raw_scorer.check_vector(point)
+    }
+
+    fn score_point(&self, point: PointOffsetType) -> ScoreType {
+        self.count(1);
+        self.raw_scorer.score_point(point)
+    }
+
+    fn score_internal(&self, point_a: PointOffsetType, point_b: PointOffsetType) -> ScoreType {
+        self.count(1);
+        self.raw_scorer.score_internal(point_a, point_b)
+    }
+
+    fn peek_top_iter(
+        &self,
+        points: &mut dyn Iterator<Item = PointOffsetType>,
+        top: usize,
+    ) -> Vec<ScoredPointOffset> {
+        let mut points = points.inspect(|_| self.count(1));
+        self.raw_scorer.peek_top_iter(&mut points, top)
+    }
+
+    fn peek_top_all(&self, top: usize) -> Vec<ScoredPointOffset> {
+        self.count(self.vector_count);
+        self.raw_scorer.peek_top_all(top)
+    }
+}
+
 #[inline]
 pub fn check_deleted_condition(
     point: PointOffsetType,
@@ -48,7 +48,8 @@ pub fn open_simple_sparse_vector_storage(
     let mut total_vector_count = 0;
     let mut total_sparse_size = 0;
     db_wrapper.lock_db().iter()?;
-    for (key, value) in db_wrapper.lock_db().iter()? {
+    for item in db_wrapper.lock_db().iter()? {
+        let (key, value) = item?;
         let point_id: PointOffsetType = bincode::deserialize(&key)
             .map_err(|_| OperationError::service_error("cannot deserialize point id from db"))?;
         let stored_record: StoredSparseVector = bincode::deserialize(&value)
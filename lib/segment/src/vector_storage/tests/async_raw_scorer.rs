@@ -69,6 +69,7 @@ fn test_async_raw_scorer(
         let mutable_storage = open_simple_dense_vector_storage(
             db,
             rocksdb_wrapper::DB_VECTOR_CF,
+            None,
             4,
             distance,
             &AtomicBool::new(false),
@@ -80,10 +81,20 @@ fn test_async_raw_scorer(
         delete_random_vectors(&mut rng, &mut mutable_storage, &mut id_tracker, delete)?;
 
         storage.update_from(&mutable_storage, &mut (0..points as _), &Default::default())?;
+        storage.flusher()()?;
     }
 
+    // Same storage files, scored through the memory map
+    let mmap_storage = open_memmap_vector_storage_with_async_io(dir.path(), dim, distance, false)?;
+    let mmap_storage = mmap_storage.borrow();
+
     for _ in 0..score {
-        test_random_score(&mut rng, &storage, id_tracker.deleted_point_bitslice())?;
+        test_random_score(
+            &mut rng,
+            &storage,
+            &mmap_storage,
+            id_tracker.deleted_point_bitslice(),
+        )?;
     }
 
     Ok(())
@@ -100,6 +111,7 @@ fn insert_random_vectors(
 fn test_random_score(
     mut rng: impl rand::Rng,
     storage: &VectorStorageEnum,
+    mmap_storage: &VectorStorageEnum,
     deleted_points: &BitSlice,
 ) -> Result<()> {
     let query: QueryVector = sampler(&mut rng)
@@ -107,13 +119,20 @@ fn test_random_score(
         .collect_vec()
         .into();
 
-    let raw_scorer = new_raw_scorer(query.clone(), storage, deleted_points).unwrap();
+    // Without async reader, the regular memory map scorer is used
+    let raw_scorer = match mmap_storage {
+        VectorStorageEnum::DenseMemmap(vs) if !vs.has_async_reader() => {
+            new_raw_scorer(query.clone(), mmap_storage, deleted_points)?
+        }
+        _ => unreachable!(),
+    };
 
     let is_stopped = AtomicBool::new(false);
-    let async_raw_scorer = if let VectorStorageEnum::DenseMemmap(storage) = storage {
-        async_raw_scorer::new(query, storage, deleted_points, &is_stopped)?
-    } else {
-        unreachable!();
+    let async_raw_scorer = match storage {
+        VectorStorageEnum::DenseMemmap(vs) if vs.has_async_reader() => {
+            async_raw_scorer::new(query, vs, deleted_points, &is_stopped)?
+        }
+        _ => unreachable!(),
     };
 
     let points = rng.gen_range(1..storage.total_vector_count());
@@ -113,6 +113,7 @@ fn ram_storage(dir: &Path) -> AtomicRefCell<VectorStorageEnum> {
     let storage = open_simple_dense_vector_storage(
         rocksdb_wrapper::open_db(dir, &[rocksdb_wrapper::DB_VECTOR_CF]).unwrap(),
         rocksdb_wrapper::DB_VECTOR_CF,
+        None,
         DIMS,
         DISTANCE,
         &AtomicBool::new(false),
@@ -198,6 +199,7 @@ fn scoring_equivalency(
     let raw_storage = open_simple_dense_vector_storage(
         db,
         rocksdb_wrapper::DB_VECTOR_CF,
+        None,
         DIMS,
         DISTANCE,
         &AtomicBool::default(),
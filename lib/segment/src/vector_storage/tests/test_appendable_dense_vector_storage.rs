@@ -137,6 +137,7 @@ fn do_test_update_from_delete_points(storage: Arc<AtomicRefCell<VectorStorageEnu
         let storage2 = open_simple_dense_vector_storage(
             db,
             DB_VECTOR_CF,
+            None,
             4,
             Distance::Dot,
             &AtomicBool::new(false),
@@ -381,6 +382,7 @@ fn test_delete_points_in_simple_vector_storages() {
         let storage = open_simple_dense_vector_storage(
             db,
             DB_VECTOR_CF,
+            None,
             4,
             Distance::Dot,
             &AtomicBool::new(false),
@@ -393,6 +395,7 @@ fn test_delete_points_in_simple_vector_storages() {
     let _storage = open_simple_dense_vector_storage(
         db,
         DB_VECTOR_CF,
+        None,
         4,
         Distance::Dot,
         &AtomicBool::new(false),
@@ -408,6 +411,7 @@ fn test_update_from_delete_points_simple_vector_storages() {
         let storage = open_simple_dense_vector_storage(
             db,
             DB_VECTOR_CF,
+            None,
             4,
             Distance::Dot,
             &AtomicBool::new(false),
@@ -421,6 +425,7 @@ fn test_update_from_delete_points_simple_vector_storages() {
     let _storage = open_simple_dense_vector_storage(
         db,
         DB_VECTOR_CF,
+        None,
         4,
         Distance::Dot,
         &AtomicBool::new(false),
@@ -436,6 +441,7 @@ fn test_score_points_in_simple_vector_storages() {
         let storage = open_simple_dense_vector_storage(
             db,
             DB_VECTOR_CF,
+            None,
             4,
             Distance::Dot,
             &AtomicBool::new(false),
@@ -449,6 +455,7 @@ fn test_score_points_in_simple_vector_storages() {
     let _storage = open_simple_dense_vector_storage(
         db,
         DB_VECTOR_CF,
+        None,
         4,
         Distance::Dot,
         &AtomicBool::new(false),
@@ -464,6 +471,7 @@ fn test_score_quantized_points_simple_vector_storages() {
         let storage = open_simple_dense_vector_storage(
             db,
             DB_VECTOR_CF,
+            None,
             4,
             Distance::Dot,
             &AtomicBool::new(false),
@@ -477,6 +485,7 @@ fn test_score_quantized_points_simple_vector_storages() {
     let _storage = open_simple_dense_vector_storage(
         db,
         DB_VECTOR_CF,
+        None,
         4,
         Distance::Dot,
         &AtomicBool::new(false),
@@ -69,12 +69,15 @@ fn do_test_delete_points(storage: Arc<AtomicRefCell<VectorStorageEnum>>) {
             VectorStorageEnum::DenseSimple(_) => unreachable!(),
             VectorStorageEnum::DenseSimpleByte(_) => unreachable!(),
             VectorStorageEnum::DenseSimpleHalf(_) => unreachable!(),
+            VectorStorageEnum::DenseSimpleDouble(_) => unreachable!(),
             VectorStorageEnum::DenseMemmap(_) => unreachable!(),
             VectorStorageEnum::DenseMemmapByte(_) => unreachable!(),
             VectorStorageEnum::DenseMemmapHalf(_) => unreachable!(),
+            VectorStorageEnum::DenseMemmapDouble(_) => unreachable!(),
             VectorStorageEnum::DenseAppendableMemmap(_) => unreachable!(),
             VectorStorageEnum::DenseAppendableMemmapByte(_) => unreachable!(),
             VectorStorageEnum::DenseAppendableMemmapHalf(_) => unreachable!(),
+            VectorStorageEnum::DenseAppendableMemmapDouble(_) => unreachable!(),
             VectorStorageEnum::SparseSimple(_) => unreachable!(),
             VectorStorageEnum::MultiDenseSimple(v) => {
                 for (orig, vec) in orig_iter.zip(v.iterate_inner_vectors()) {
@@ -189,6 +192,7 @@ fn do_test_update_from_delete_points(storage: Arc<AtomicRefCell<VectorStorageEnu
         let storage2 = open_simple_multi_dense_vector_storage(
             db,
             DB_VECTOR_CF,
+            None,
             4,
             Distance::Dot,
             MultiVectorConfig::default(),
@@ -266,6 +270,7 @@ fn create_vector_storage(
             open_simple_multi_dense_vector_storage(
                 db,
                 DB_VECTOR_CF,
+                None,
                 4,
                 Distance::Dot,
                 MultiVectorConfig::default(),
@@ -84,6 +84,7 @@ fn compare_search_result(result_a: &[Vec<ScoredPointOffset>], result_b: &[Vec<Sc
 #[rstest]
 #[case::nearest(QueryVariant::Nearest, VectorStorageDatatype::Uint8, 32, 10)]
 #[case::nearest(QueryVariant::Nearest, VectorStorageDatatype::Float16, 32, 10)]
+#[case::nearest(QueryVariant::Nearest, VectorStorageDatatype::Float64, 32, 10)]
 #[case::discovery(QueryVariant::Discovery, VectorStorageDatatype::Uint8, 128, 20)]
 #[case::recommend(
     QueryVariant::RecommendBestScore,
@@ -134,6 +135,8 @@ fn test_byte_storage_hnsw(
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_columns: vec![],
+        storage_cipher: None,
     };
     let config_byte = SegmentConfig {
         vector_data: HashMap::from([(
@@ -150,13 +153,15 @@ fn test_byte_storage_hnsw(
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_columns: vec![],
+        storage_cipher: None,
     };
 
     let int_key = "int";
 
     let mut segment_float = build_segment(dir_float.path(), &config_float, true).unwrap();
     let mut segment_byte = build_segment(dir_byte.path(), &config_byte, true).unwrap();
-    // check that `segment_byte` uses byte or half storage
+    // check that `segment_byte` uses byte, half or double storage
     {
         let borrowed_storage = segment_byte.vector_data[DEFAULT_VECTOR_NAME]
             .vector_storage
@@ -165,6 +170,7 @@ fn test_byte_storage_hnsw(
         assert!(
             matches!(raw_storage, &VectorStorageEnum::DenseSimpleByte(_))
                 | matches!(raw_storage, &VectorStorageEnum::DenseSimpleHalf(_))
+                | matches!(raw_storage, &VectorStorageEnum::DenseSimpleDouble(_))
         );
     }
 
@@ -52,7 +52,7 @@ where
 {
     match data_type {
         VectorStorageDatatype::Float32 => unreachable!(),
-        VectorStorageDatatype::Float16 => {
+        VectorStorageDatatype::Float16 | VectorStorageDatatype::Float64 => {
             let mut vector = segment::fixtures::payload_fixtures::random_vector(rnd_gen, dim);
             vector.iter_mut().for_each(|x| *x -= 0.5);
             vector
@@ -257,12 +257,14 @@ fn test_byte_storage_binary_quantization_hnsw(
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_columns: vec![],
+        storage_cipher: None,
     };
 
     let int_key = "int";
 
     let mut segment_byte = build_segment(dir_byte.path(), &config_byte, true).unwrap();
-    // check that `segment_byte` uses byte or half storage
+    // check that `segment_byte` uses byte, half or double storage
     {
         let borrowed_storage = segment_byte.vector_data[DEFAULT_VECTOR_NAME]
             .vector_storage
@@ -271,6 +273,7 @@ fn test_byte_storage_binary_quantization_hnsw(
         assert!(
             matches!(raw_storage, &VectorStorageEnum::DenseSimpleByte(_))
                 | matches!(raw_storage, &VectorStorageEnum::DenseSimpleHalf(_))
+                | matches!(raw_storage, &VectorStorageEnum::DenseSimpleDouble(_))
         );
     }
 
@@ -56,6 +56,8 @@ fn exact_search_test() {
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_columns: vec![],
+        storage_cipher: None,
     };
 
     let int_key = "int";
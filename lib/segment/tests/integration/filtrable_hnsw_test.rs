@@ -118,6 +118,8 @@ fn _test_filterable_hnsw(
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_columns: vec![],
+        storage_cipher: None,
     };
 
     let int_key = "int";
@@ -266,3 +268,129 @@ fn _test_filterable_hnsw(
     ); // Not more than X% failures
     eprintln!("hits = {hits:#?} out of {attempts}");
 }
+
+/// Filters matching fewer points than the break-even of the cost model are searched without the
+/// graph, even if they match more points than the full scan threshold.
+#[test]
+fn test_filtered_plain_search_by_cost_model() {
+    let stopped = AtomicBool::new(false);
+
+    let dim = 8;
+    let m = 8;
+    let ef = 64;
+    let num_vectors: u64 = 5_000;
+    let num_groups = 64;
+    let full_scan_threshold = 1; // KB, 32 vectors
+
+    let mut rnd = StdRng::seed_from_u64(42);
+
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let hnsw_dir = Builder::new().prefix("hnsw_dir").tempdir().unwrap();
+
+    let config = SegmentConfig {
+        vector_data: HashMap::from([(
+            DEFAULT_VECTOR_NAME.to_owned(),
+            VectorDataConfig {
+                size: dim,
+                distance: Distance::Dot,
+                storage_type: VectorStorageType::Memory,
+                index: Indexes::Plain {},
+                quantization_config: None,
+                multivec_config: None,
+                datatype: None,
+            },
+        )]),
+        sparse_vector_data: Default::default(),
+        payload_storage_type: Default::default(),
+        payload_columns: vec![],
+        storage_cipher: None,
+    };
+
+    let group_key = "group";
+
+    let mut segment = build_segment(dir.path(), &config, true).unwrap();
+    for n in 0..num_vectors {
+        let idx = n.into();
+        let vector = random_vector(&mut rnd, dim);
+        let payload: Payload = json!({ group_key: n % num_groups }).into();
+
+        segment
+            .upsert_point(n as SeqNumberType, idx, only_default_vector(&vector))
+            .unwrap();
+        segment
+            .set_full_payload(n as SeqNumberType, idx, &payload)
+            .unwrap();
+    }
+
+    let payload_index_ptr = segment.payload_index.clone();
+    payload_index_ptr
+        .borrow_mut()
+        .set_indexed(&path(group_key), PayloadSchemaType::Integer.into())
+        .unwrap();
+
+    let hnsw_config = HnswConfig {
+        m,
+        ef_construct: 16,
+        full_scan_threshold,
+        max_indexing_threads: 2,
+        on_disk: Some(false),
+        payload_m: None,
+    };
+
+    let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
+    let permit = Arc::new(CpuPermit::dummy(permit_cpu_count as u32));
+
+    let vector_storage = &segment.vector_data[DEFAULT_VECTOR_NAME].vector_storage;
+    let quantized_vectors = &segment.vector_data[DEFAULT_VECTOR_NAME].quantized_vectors;
+    let mut hnsw_index = HNSWIndex::<GraphLinksRam>::open(
+        hnsw_dir.path(),
+        segment.id_tracker.clone(),
+        vector_storage.clone(),
+        quantized_vectors.clone(),
+        payload_index_ptr.clone(),
+        hnsw_config,
+    )
+    .unwrap();
+    hnsw_index.build_index(permit, &stopped).unwrap();
+
+    // Each group has about 78 points, more than the full scan threshold of 32 vectors, but less
+    // than the break-even of `64 * 16 * 0.1 / (1 - 64 * 16 / 5000) = 128` vectors
+    let top = 10;
+    let attempts = 10;
+    for i in 0..attempts {
+        let query: QueryVector = random_vector(&mut rnd, dim).into();
+        let filter = Filter::new_must(Condition::Field(FieldCondition::new_match(
+            path(group_key),
+            (i as i64).into(),
+        )));
+
+        let index_result = hnsw_index
+            .search(
+                &[&query],
+                Some(&filter),
+                top,
+                Some(&SearchParams {
+                    hnsw_ef: Some(ef),
+                    ..Default::default()
+                }),
+                &Default::default(),
+            )
+            .unwrap();
+
+        // check that search was performed without HNSW index
+        assert_eq!(
+            hnsw_index
+                .get_telemetry_data(TelemetryDetail::default())
+                .filtered_small_cardinality
+                .count,
+            i + 1
+        );
+
+        let plain_result = segment.vector_data[DEFAULT_VECTOR_NAME]
+            .vector_index
+            .borrow()
+            .search(&[&query], Some(&filter), top, None, &Default::default())
+            .unwrap();
+        assert_eq!(index_result, plain_result);
+    }
+}
@@ -161,6 +161,8 @@ pub fn build_segment_3(path: &Path) -> Segment {
             ]),
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_columns: vec![],
+            storage_cipher: None,
         },
         true,
     )
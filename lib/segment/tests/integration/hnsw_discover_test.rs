@@ -81,6 +81,8 @@ fn hnsw_discover_precision() {
         )]),
         payload_storage_type: Default::default(),
         sparse_vector_data: Default::default(),
+        payload_columns: vec![],
+        storage_cipher: None,
     };
 
     let mut segment = build_segment(dir.path(), &config, true).unwrap();
@@ -193,6 +195,8 @@ fn filtered_hnsw_discover_precision() {
         )]),
         payload_storage_type: Default::default(),
         sparse_vector_data: Default::default(),
+        payload_columns: vec![],
+        storage_cipher: None,
     };
 
     let keyword_key = "keyword";
@@ -77,6 +77,8 @@ fn hnsw_quantized_search_test(
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_columns: vec![],
+        storage_cipher: None,
     };
 
     let mut segment = build_segment(dir.path(), &config, true).unwrap();
@@ -55,6 +55,8 @@ fn test_single_multi_and_dense_hnsw_equivalency() {
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_columns: vec![],
+        storage_cipher: None,
     };
 
     let int_key = "int";
@@ -70,6 +72,7 @@ fn test_single_multi_and_dense_hnsw_equivalency() {
     let multi_storage = open_simple_multi_dense_vector_storage(
         db,
         DB_VECTOR_CF,
+        None,
         dim,
         distance,
         MultiVectorConfig::default(),
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 use std::path::Path;
+use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
 use atomic_refcell::AtomicRefCell;
@@ -25,7 +26,7 @@ use segment::json_path::path;
 use segment::payload_storage::in_memory_payload_storage::InMemoryPayloadStorage;
 use segment::payload_storage::PayloadStorage;
 use segment::segment::Segment;
-use segment::segment_constructor::build_segment;
+use segment::segment_constructor::{build_segment, load_segment};
 use segment::types::PayloadFieldSchema::{FieldParams, FieldType};
 use segment::types::PayloadSchemaType::{Integer, Keyword};
 use segment::types::{
@@ -60,6 +61,8 @@ fn build_test_segments(path_struct: &Path, path_plain: &Path) -> (Segment, Segme
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_columns: vec![],
+        storage_cipher: None,
     };
 
     let mut plain_segment = build_segment(path_plain, &config, true).unwrap();
@@ -197,6 +200,8 @@ fn build_test_segments_nested_payload(path_struct: &Path, path_plain: &Path) ->
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_columns: vec![],
+        storage_cipher: None,
     };
 
     let mut plain_segment = build_segment(path_plain, &config, true).unwrap();
@@ -920,3 +925,101 @@ fn test_any_matcher_cardinality_estimation() {
     assert!(exact <= estimation.max);
     assert!(exact >= estimation.min);
 }
+
+#[test]
+fn test_payload_columns_filtering() {
+    // Compare filtering of plain segment with the one, which stores some fields column-wise
+    let dir_plain = Builder::new()
+        .prefix("segment_plain_dir")
+        .tempdir()
+        .unwrap();
+    let dir_columns = Builder::new()
+        .prefix("segment_columns_dir")
+        .tempdir()
+        .unwrap();
+
+    let mut rnd = StdRng::seed_from_u64(42);
+
+    let mut config = SegmentConfig {
+        vector_data: HashMap::from([(
+            DEFAULT_VECTOR_NAME.to_owned(),
+            VectorDataConfig {
+                size: DIM,
+                distance: Distance::Dot,
+                storage_type: VectorStorageType::Memory,
+                index: Indexes::Plain {},
+                quantization_config: None,
+                multivec_config: None,
+                datatype: None,
+            },
+        )]),
+        sparse_vector_data: Default::default(),
+        payload_storage_type: Default::default(),
+        payload_columns: vec![],
+        storage_cipher: None,
+    };
+    let mut plain_segment = build_segment(dir_plain.path(), &config, true).unwrap();
+
+    config.payload_columns = vec![
+        path(STR_KEY),
+        path(INT_KEY),
+        path(INT_KEY_2),
+        path(GEO_KEY),
+        path(FLICKING_KEY),
+    ];
+    let mut columns_segment = build_segment(dir_columns.path(), &config, true).unwrap();
+
+    let num_points = 1000;
+    for n in 0..num_points {
+        let idx = n.into();
+        let vector = random_vector(&mut rnd, DIM);
+        let payload: Payload = generate_diverse_payload(&mut rnd);
+
+        for segment in [&mut plain_segment, &mut columns_segment] {
+            segment
+                .upsert_point(n, idx, only_default_vector(&vector))
+                .unwrap();
+            segment.set_full_payload(n, idx, &payload).unwrap();
+            if n % 7 == 0 {
+                segment.delete_payload(n, idx, &path(INT_KEY)).unwrap();
+            }
+            if n % 11 == 0 {
+                segment.clear_payload(n, idx).unwrap();
+            }
+        }
+    }
+
+    // Columns are persisted along with the segment
+    columns_segment.flush(true).unwrap();
+    drop(columns_segment);
+    let columns_segment = load_segment(dir_columns.path(), None, &AtomicBool::new(false))
+        .unwrap()
+        .unwrap();
+
+    for _ in 0..ATTEMPTS {
+        let query_filter = random_filter(&mut rnd, 3);
+        let plain_result = plain_segment.read_filtered(None, None, Some(&query_filter));
+        let columns_result = columns_segment.read_filtered(None, None, Some(&query_filter));
+        assert_eq!(plain_result, columns_result, "filter: {query_filter:#?}");
+    }
+
+    let mut column_count = 0;
+    columns_segment
+        .payload_index
+        .borrow()
+        .iter_field_values(&path(INT_KEY), |_, values| {
+            assert!(!values.is_empty());
+            column_count += 1;
+            Ok(true)
+        })
+        .unwrap();
+    let has_int_key = Filter::new_must_not(Condition::IsEmpty(IsEmptyCondition {
+        is_empty: PayloadField { key: path(INT_KEY) },
+    }));
+    assert_eq!(
+        column_count,
+        plain_segment
+            .read_filtered(None, None, Some(&has_int_key))
+            .len(),
+    );
+}
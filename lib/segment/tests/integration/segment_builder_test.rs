@@ -9,9 +9,14 @@ use segment::common::operation_error::OperationError;
 use segment::data_types::vectors::{only_default_vector, DEFAULT_VECTOR_NAME};
 use segment::entry::entry_point::SegmentEntry;
 use segment::index::hnsw_index::num_rayon_threads;
+use segment::json_path::path;
 use segment::segment::Segment;
 use segment::segment_constructor::segment_builder::SegmentBuilder;
-use segment::types::{Indexes, SegmentConfig, VectorDataConfig, VectorStorageType};
+use segment::types::{
+    Indexes, Payload, PayloadContainer, PayloadKeyType, SegmentConfig, VectorDataConfig,
+    VectorStorageType,
+};
+use serde_json::json;
 use tempfile::Builder;
 
 use crate::fixtures::segment::{build_segment_1, build_segment_2, empty_segment};
@@ -75,6 +80,106 @@ fn test_building_new_segment() {
     assert_eq!(merged_segment.point_version(3.into()), Some(100));
 }
 
+#[test]
+fn test_building_segment_with_blobs() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let temp_dir = Builder::new().prefix("segment_temp_dir").tempdir().unwrap();
+
+    let stopped = AtomicBool::new(false);
+
+    let mut segment1 = build_segment_1(dir.path());
+    let mut segment2 = build_segment_2(dir.path());
+
+    segment1.set_blob(20, 1.into(), b"first").unwrap();
+    segment1.set_blob(21, 2.into(), b"outdated").unwrap();
+    segment1.set_blob(22, 4.into(), b"deleted").unwrap();
+    segment1.delete_point(23, 4.into()).unwrap();
+
+    // Newer version of point 2 without blob, newer version of point 3 with blob
+    segment2
+        .upsert_point(100, 2.into(), only_default_vector(&[0., 0., 0., 0.]))
+        .unwrap();
+    segment2
+        .upsert_point(101, 3.into(), only_default_vector(&[0., 0., 0., 0.]))
+        .unwrap();
+    segment2.set_blob(102, 3.into(), b"second").unwrap();
+
+    let mut builder =
+        SegmentBuilder::new(dir.path(), temp_dir.path(), &segment1.segment_config).unwrap();
+    builder.update_from(&segment1, &stopped).unwrap();
+    builder.update_from(&segment2, &stopped).unwrap();
+
+    let permit_cpu_count = num_rayon_threads(0);
+    let permit = CpuPermit::dummy(permit_cpu_count as u32);
+    let merged_segment: Segment = builder.build(permit, &stopped).unwrap();
+
+    let blob = |point_id: u64| {
+        merged_segment
+            .blob(point_id.into(), 0, usize::MAX)
+            .unwrap()
+            .map(|chunk| chunk.data)
+    };
+    assert_eq!(blob(1), Some(b"first".to_vec()));
+    assert_eq!(blob(2), None);
+    assert_eq!(blob(3), Some(b"second".to_vec()));
+    assert_eq!(blob(5), None);
+    assert!(merged_segment.blob(4.into(), 0, usize::MAX).is_err());
+}
+
+#[test]
+fn test_building_defragmented_segment() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let temp_dir = Builder::new().prefix("segment_temp_dir").tempdir().unwrap();
+
+    let stopped = AtomicBool::new(false);
+    let tenant_key: PayloadKeyType = path("tenant");
+
+    // Points of each tenant are spread over both segments
+    let mut segments = [empty_segment(dir.path()), empty_segment(dir.path())];
+    for point_id in 0..30u64 {
+        let segment = &mut segments[point_id as usize % 2];
+        segment
+            .upsert_point(1, point_id.into(), only_default_vector(&[1., 0., 0., 0.]))
+            .unwrap();
+        let tenant = ["b", "a", "c"][point_id as usize % 3];
+        let payload: Payload = json!({ "tenant": tenant }).into();
+        segment
+            .set_payload(1, point_id.into(), &payload, &None)
+            .unwrap();
+    }
+
+    let mut builder =
+        SegmentBuilder::new(dir.path(), temp_dir.path(), &segments[0].segment_config).unwrap();
+    builder.defragment_key = Some(tenant_key.clone());
+    assert!(builder
+        .update(&[&segments[0], &segments[1]], &stopped)
+        .unwrap());
+
+    let permit = CpuPermit::dummy(num_rayon_threads(0) as u32);
+    let merged_segment = builder.build(permit, &stopped).unwrap();
+    assert_eq!(merged_segment.available_point_count(), 30);
+
+    // Tenants of points in the order of their internal ids
+    let id_tracker = merged_segment.id_tracker.borrow();
+    let tenants: Vec<_> = id_tracker
+        .iter_ids()
+        .map(|internal_id| {
+            let point_id = id_tracker.external_id(internal_id).unwrap();
+            let payload = merged_segment.payload(point_id).unwrap();
+            payload.get_value(&tenant_key)[0]
+                .as_str()
+                .unwrap()
+                .to_owned()
+        })
+        .collect();
+
+    let expected: Vec<_> = ["a", "b", "c"]
+        .into_iter()
+        .flat_map(|tenant| std::iter::repeat(tenant.to_owned()).take(10))
+        .collect();
+    assert_eq!(tenants, expected);
+}
+
 fn estimate_build_time(segment: &Segment, stop_delay_millis: u64) -> (u64, bool) {
     let stopped = Arc::new(AtomicBool::new(false));
 
@@ -96,6 +201,8 @@ fn estimate_build_time(segment: &Segment, stop_delay_millis: u64) -> (u64, bool)
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_columns: vec![],
+        storage_cipher: None,
     };
 
     let mut builder = SegmentBuilder::new(dir.path(), temp_dir.path(), &segment_config).unwrap();
@@ -193,7 +193,7 @@ fn ordered_deletion_test() {
         segment.current_path.clone()
     };
 
-    let segment = load_segment(&path, &AtomicBool::new(false))
+    let segment = load_segment(&path, None, &AtomicBool::new(false))
         .unwrap()
         .unwrap();
     let query_vector = [1.0, 1.0, 1.0, 1.0].into();
@@ -228,7 +228,7 @@ fn skip_deleted_segment() {
     let new_path = path.with_extension("deleted");
     std::fs::rename(&path, new_path).unwrap();
 
-    let segment = load_segment(&path, &AtomicBool::new(false)).unwrap();
+    let segment = load_segment(&path, None, &AtomicBool::new(false)).unwrap();
 
     assert!(segment.is_none());
 }
@@ -328,3 +328,69 @@ fn test_update_named_vector() {
     // check that nearests are the same
     assert_eq!(nearest_upsert.id, nearest_update.id);
 }
+
+#[test]
+fn test_search_with_distance_override() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+    let segment = build_segment_1(dir.path());
+
+    let query_vector = [1.0, 0.0, 0.0, 0.0].into();
+
+    let search_params = SearchParams {
+        distance: Some(Distance::Euclid),
+        ..Default::default()
+    };
+
+    let res = segment
+        .search(
+            DEFAULT_VECTOR_NAME,
+            &query_vector,
+            &WithPayload::default(),
+            &false.into(),
+            None,
+            2,
+            Some(&search_params),
+        )
+        .unwrap();
+
+    // With dot product all points have the same score, euclid prefers the closest ones
+    let ids = res.iter().map(|point| point.id).collect_vec();
+    assert_eq!(ids, vec![5.into(), 2.into()]);
+    assert_eq!(res[0].score, 0.0);
+    assert_eq!(res[1].score, -1.0);
+
+    // Filters are respected
+    let filter = Filter::new_must_not(Condition::HasId(HashSet::from([5.into()]).into()));
+    let res = segment
+        .search(
+            DEFAULT_VECTOR_NAME,
+            &query_vector,
+            &WithPayload::default(),
+            &false.into(),
+            Some(&filter),
+            1,
+            Some(&search_params),
+        )
+        .unwrap();
+    assert_eq!(res[0].id, 2.into());
+
+    // Vectors are not normalized, so they can't be scored with cosine
+    let search_params = SearchParams {
+        distance: Some(Distance::Cosine),
+        ..Default::default()
+    };
+    let result = segment.search(
+        DEFAULT_VECTOR_NAME,
+        &query_vector,
+        &WithPayload::default(),
+        &false.into(),
+        None,
+        1,
+        Some(&search_params),
+    );
+    assert!(matches!(
+        result,
+        Err(OperationError::ValidationError { .. })
+    ));
+}
@@ -127,6 +127,8 @@ fn sparse_index_discover_test() {
             },
         )]),
         payload_storage_type: Default::default(),
+        payload_columns: vec![],
+        storage_cipher: None,
     };
     let dense_config = SegmentConfig {
         vector_data: HashMap::from([(
@@ -143,6 +145,8 @@ fn sparse_index_discover_test() {
         )]),
         payload_storage_type: Default::default(),
         sparse_vector_data: Default::default(),
+        payload_columns: vec![],
+        storage_cipher: None,
     };
 
     let mut sparse_segment = build_segment(dir.path(), &sparse_config, true).unwrap();
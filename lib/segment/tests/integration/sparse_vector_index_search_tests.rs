@@ -622,6 +622,8 @@ fn sparse_vector_index_persistence_test() {
             },
         )]),
         payload_storage_type: Default::default(),
+        payload_columns: vec![],
+        storage_cipher: None,
     };
     let mut segment = build_segment(dir.path(), &config, true).unwrap();
 
@@ -661,7 +663,7 @@ fn sparse_vector_index_persistence_test() {
 
     // persistence using rebuild of inverted index
     // for appendable segment vector index has to be rebuilt
-    let segment = load_segment(&path, &stopped).unwrap().unwrap();
+    let segment = load_segment(&path, None, &stopped).unwrap().unwrap();
     let search_after_reload_result = segment
         .search(
             SPARSE_VECTOR_NAME,
@@ -865,6 +867,8 @@ fn sparse_vector_test_large_index() {
             },
         )]),
         payload_storage_type: Default::default(),
+        payload_columns: vec![],
+        storage_cipher: None,
     };
     let mut segment = build_segment(dir.path(), &config, true).unwrap();
 
@@ -894,3 +898,62 @@ fn sparse_vector_test_large_index() {
         _ => panic!("unexpected vector index type"),
     }
 }
+
+#[test]
+fn sparse_vector_info_statistics() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let config = SegmentConfig {
+        vector_data: Default::default(),
+        sparse_vector_data: HashMap::from([(
+            SPARSE_VECTOR_NAME.to_owned(),
+            SparseVectorDataConfig {
+                index: SparseIndexConfig {
+                    full_scan_threshold: Some(DEFAULT_SPARSE_FULL_SCAN_THRESHOLD),
+                    index_type: SparseIndexType::MutableRam,
+                },
+            },
+        )]),
+        payload_storage_type: Default::default(),
+        payload_columns: vec![],
+        storage_cipher: None,
+    };
+    let mut segment = build_segment(dir.path(), &config, true).unwrap();
+
+    let vectors = [
+        SparseVector::new(vec![1, 2, 3], vec![0.1, 0.2, 0.3]).unwrap(),
+        SparseVector::new(vec![2, 100], vec![0.5, 0.5]).unwrap(),
+    ];
+    for (n, vector) in vectors.into_iter().enumerate() {
+        let mut named_vector = NamedVectors::default();
+        named_vector.insert(SPARSE_VECTOR_NAME.to_owned(), vector.into());
+        segment
+            .upsert_point(n as SeqNumberType, (n as u64).into(), named_vector)
+            .unwrap();
+    }
+
+    let info = segment.info();
+    let stats = info.vector_data[SPARSE_VECTOR_NAME].sparse_stats.unwrap();
+    assert_eq!(stats.vectors_count, 2);
+    assert_eq!(stats.values_count, 5);
+
+    // Deleted vectors are counted in both statistics, until the index is rebuilt
+    segment.delete_point(2, 0.into()).unwrap();
+    let info = segment.info();
+    assert_eq!(info.vector_data[SPARSE_VECTOR_NAME].num_vectors, 1);
+    let stats = info.vector_data[SPARSE_VECTOR_NAME].sparse_stats.unwrap();
+    assert_eq!(stats.vectors_count, 2);
+    assert_eq!(stats.values_count, 5);
+
+    // Cached statistics are recomputed on the next version of the segment
+    let mut named_vector = NamedVectors::default();
+    named_vector.insert(
+        SPARSE_VECTOR_NAME.to_owned(),
+        SparseVector::new(vec![7], vec![1.0]).unwrap().into(),
+    );
+    segment.upsert_point(3, 2.into(), named_vector).unwrap();
+    let stats = segment.info().vector_data[SPARSE_VECTOR_NAME]
+        .sparse_stats
+        .unwrap();
+    assert_eq!(stats.vectors_count, 3);
+    assert_eq!(stats.values_count, 6);
+}
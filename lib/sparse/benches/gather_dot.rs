@@ -0,0 +1,81 @@
+#[cfg(not(target_os = "windows"))]
+mod prof;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+#[cfg(target_arch = "x86_64")]
+use sparse::common::gather_dot::gather_dot_avx2;
+use sparse::common::gather_dot::{gather_dot, gather_dot_scalar};
+use sparse::common::types::{DimOffset, DimWeight};
+
+/// Number of dimensions in the query, which offsets point to
+const QUERY_DIM: usize = 100;
+const COUNT: usize = 10_000;
+
+fn gather_dot_bench(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(42);
+
+    let query: Vec<DimWeight> = (0..QUERY_DIM).map(|_| rng.gen_range(0.0..1.0)).collect();
+
+    // Number of matched query dimensions of a point, as collected by plain search
+    for len in [8, 16, 32, 64] {
+        let mut group = c.benchmark_group(format!("gather-dot-{len}"));
+
+        let offsets: Vec<Vec<DimOffset>> = (0..COUNT)
+            .map(|_| {
+                (0..len)
+                    .map(|_| rng.gen_range(0..QUERY_DIM as DimOffset))
+                    .collect()
+            })
+            .collect();
+        let values: Vec<Vec<DimWeight>> = (0..COUNT)
+            .map(|_| (0..len).map(|_| rng.gen_range(0.0..1.0)).collect())
+            .collect();
+
+        group.bench_function("gather-dot", |b| {
+            let mut i = 0;
+            b.iter(|| {
+                i = (i + 1) % COUNT;
+                gather_dot(&query, &offsets[i], &values[i])
+            });
+        });
+
+        group.bench_function("gather-dot-no-simd", |b| {
+            let mut i = 0;
+            b.iter(|| {
+                i = (i + 1) % COUNT;
+                gather_dot_scalar(&query, &offsets[i], &values[i])
+            });
+        });
+
+        #[cfg(target_arch = "x86_64")]
+        if is_x86_feature_detected!("avx2") && is_x86_feature_detected!("fma") {
+            group.bench_function("gather-dot-avx2", |b| {
+                let mut i = 0;
+                b.iter(|| unsafe {
+                    i = (i + 1) % COUNT;
+                    gather_dot_avx2(&query, &offsets[i], &values[i])
+                });
+            });
+        }
+
+        group.finish();
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+criterion_group! {
+    name = benches;
+    config = Criterion::default().with_profiler(prof::FlamegraphProfiler::new(100));
+    targets = gather_dot_bench,
+}
+
+#[cfg(target_os = "windows")]
+criterion_group! {
+    name = benches;
+    config = Criterion::default();
+    targets = gather_dot_bench,
+}
+
+criterion_main!(benches);
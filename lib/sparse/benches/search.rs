@@ -9,7 +9,7 @@ use dataset::Dataset;
 use indicatif::{ProgressBar, ProgressDrawTarget};
 use itertools::Itertools;
 use rand::rngs::StdRng;
-use rand::SeedableRng as _;
+use rand::{Rng as _, SeedableRng as _};
 use sparse::common::scores_memory_pool::ScoresMemoryPool;
 use sparse::common::sparse_vector::{RemappedSparseVector, SparseVector};
 use sparse::common::sparse_vector_fixture::{random_positive_sparse_vector, random_sparse_vector};
@@ -26,6 +26,7 @@ mod prof;
 const NUM_QUERIES: usize = 2048;
 const MAX_SPARSE_DIM: usize = 30_000;
 const TOP: usize = 10;
+const PLAIN_SEARCH_IDS: usize = 1000;
 
 pub fn bench_search(c: &mut Criterion) {
     bench_uniform_random(c, "random-50k", 50_000);
@@ -190,6 +191,22 @@ fn run_bench2(
             criterion::BatchSize::SmallInput,
         )
     });
+
+    // Plain search scores the points with `gather_dot`, as done for selective filters
+    let mut rnd = StdRng::seed_from_u64(42);
+    let plain_ids = (0..PLAIN_SEARCH_IDS)
+        .map(|_| rnd.gen_range(0..index.vector_count() as PointOffsetType))
+        .collect_vec();
+    let mut it = query_vectors.iter().cycle();
+    group.bench_function("plain", |b| {
+        b.iter_batched(
+            || it.next().unwrap().clone().into_remapped(),
+            |vec| {
+                SearchContext::new(vec, TOP, index, pool.get(), &stopped).plain_search(&plain_ids)
+            },
+            criterion::BatchSize::SmallInput,
+        )
+    });
 }
 
 fn load_csr_index(path: impl AsRef<Path>, ratio: f32) -> io::Result<InvertedIndexRam> {
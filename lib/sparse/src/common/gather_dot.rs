@@ -0,0 +1,173 @@
+//! Dot product of a sparse vector with a dense vector, with SIMD gather kernels selected at runtime.
+//!
+//! AVX-512 gather intrinsics are not stable in the minimum supported Rust version, so x86_64 uses
+//! AVX2 gathers. There are no gather instructions in NEON, aarch64 uses the scalar kernel.
+//!
+//! The kernel is used for plain search only: the batched search of the inverted index scatters
+//! scores of posting list elements into per-point accumulators, there are no gathers to vectorize.
+//! See `benches/gather_dot.rs` for the comparison with the scalar kernel.
+
+use common::types::ScoreType;
+
+use crate::common::types::{DimOffset, DimWeight};
+
+#[cfg(target_arch = "x86_64")]
+const MIN_LEN_AVX2: usize = 16;
+
+/// Sum of `values[i] * dense[offsets[i]]`.
+///
+/// Offsets out of range of `dense` are skipped, as if `dense` was zero there.
+pub fn gather_dot(dense: &[DimWeight], offsets: &[DimOffset], values: &[DimWeight]) -> ScoreType {
+    debug_assert_eq!(offsets.len(), values.len());
+
+    #[cfg(target_arch = "x86_64")]
+    {
+        // Gather offsets are signed 32-bit integers
+        if is_x86_feature_detected!("avx2")
+            && is_x86_feature_detected!("fma")
+            && offsets.len() >= MIN_LEN_AVX2
+            && !dense.is_empty()
+            && dense.len() <= i32::MAX as usize
+        {
+            return unsafe { gather_dot_avx2(dense, offsets, values) };
+        }
+    }
+
+    gather_dot_scalar(dense, offsets, values)
+}
+
+pub fn gather_dot_scalar(
+    dense: &[DimWeight],
+    offsets: &[DimOffset],
+    values: &[DimWeight],
+) -> ScoreType {
+    offsets
+        .iter()
+        .zip(values)
+        .filter_map(|(&offset, &value)| Some(dense.get(offset as usize)? * value))
+        .sum()
+}
+
+/// # Safety
+///
+/// Requires `avx2` and `fma` CPU features, and `dense` to be non-empty and not longer than
+/// `i32::MAX`.
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+#[target_feature(enable = "fma")]
+pub unsafe fn gather_dot_avx2(
+    dense: &[DimWeight],
+    offsets: &[DimOffset],
+    values: &[DimWeight],
+) -> ScoreType {
+    use std::arch::x86_64::*;
+
+    let n = offsets.len().min(values.len());
+    let m = n - (n % 8);
+    let max_offset = _mm256_set1_epi32((dense.len() - 1) as i32);
+    let mut sum256: __m256 = _mm256_setzero_ps();
+    let mut i: usize = 0;
+    while i < m {
+        let offsets256 = _mm256_loadu_si256(offsets.as_ptr().add(i) as *const __m256i);
+        // Unsigned comparison `offset <= max_offset`, out of range lanes are not gathered
+        let in_range = _mm256_cmpeq_epi32(_mm256_min_epu32(offsets256, max_offset), offsets256);
+        let gathered = _mm256_mask_i32gather_ps::<4>(
+            _mm256_setzero_ps(),
+            dense.as_ptr(),
+            offsets256,
+            _mm256_castsi256_ps(in_range),
+        );
+        sum256 = _mm256_fmadd_ps(gathered, _mm256_loadu_ps(values.as_ptr().add(i)), sum256);
+        i += 8;
+    }
+
+    let x128: __m128 = _mm_add_ps(
+        _mm256_extractf128_ps(sum256, 1),
+        _mm256_castps256_ps128(sum256),
+    );
+    let x64: __m128 = _mm_add_ps(x128, _mm_movehl_ps(x128, x128));
+    let x32: __m128 = _mm_add_ss(x64, _mm_shuffle_ps(x64, x64, 0x55));
+    _mm_cvtss_f32(x32) + gather_dot_scalar(dense, &offsets[m..n], &values[m..n])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gather_dot() {
+        let dense = [1.0, 2.0, 3.0, 4.0];
+        let score = gather_dot(
+            &dense,
+            &[0, 3, 10, DimOffset::MAX],
+            &[1.0, 0.5, 100.0, 100.0],
+        );
+        assert_eq!(score, 3.0);
+        assert_eq!(gather_dot(&[], &[0, 1], &[1.0, 1.0]), 0.0);
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    #[test]
+    fn test_gather_dot_avx2() {
+        use rand::rngs::StdRng;
+        use rand::{Rng, SeedableRng};
+
+        if is_x86_feature_detected!("avx2") && is_x86_feature_detected!("fma") {
+            let mut rng = StdRng::seed_from_u64(42);
+            // Integer weights, so that the order of summation doesn't change the result
+            let dense: Vec<DimWeight> = (0..1000)
+                .map(|_| rng.gen_range(-10..10) as DimWeight)
+                .collect();
+            for len in [16, 17, 100, 1001] {
+                // Some offsets are out of range, including the ones negative as signed integers
+                let offsets: Vec<DimOffset> = (0..len)
+                    .map(|i| match i % 10 {
+                        0 => rng.gen_range(1000..2000),
+                        1 => rng.gen_range(i32::MAX as DimOffset..DimOffset::MAX),
+                        _ => rng.gen_range(0..1000),
+                    })
+                    .collect();
+                let values: Vec<DimWeight> = (0..len)
+                    .map(|_| rng.gen_range(-5..5) as DimWeight)
+                    .collect();
+
+                let simd = unsafe { gather_dot_avx2(&dense, &offsets, &values) };
+                assert_eq!(simd, gather_dot_scalar(&dense, &offsets, &values));
+            }
+        } else {
+            println!("avx2 test skipped");
+        }
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    #[test]
+    fn test_gather_dot_avx2_remainders() {
+        use rand::rngs::StdRng;
+        use rand::{Rng, SeedableRng};
+
+        if !is_x86_feature_detected!("avx2") || !is_x86_feature_detected!("fma") {
+            println!("avx2 test skipped");
+            return;
+        }
+
+        let mut rng = StdRng::seed_from_u64(42);
+        // Integer weights, so that the order of summation doesn't change the result
+        let dense: Vec<DimWeight> = (0..100)
+            .map(|_| rng.gen_range(-10..10) as DimWeight)
+            .collect();
+        // Lengths not multiple of 8 score the remainder with the scalar kernel
+        for len in (1..=33).filter(|len| len % 8 != 0) {
+            let offsets: Vec<DimOffset> = (0..len).map(|_| rng.gen_range(0..110)).collect();
+            let values: Vec<DimWeight> = (0..len)
+                .map(|_| rng.gen_range(-5..5) as DimWeight)
+                .collect();
+
+            let scalar = gather_dot_scalar(&dense, &offsets, &values);
+            assert_eq!(
+                unsafe { gather_dot_avx2(&dense, &offsets, &values) },
+                scalar
+            );
+            assert_eq!(gather_dot(&dense, &offsets, &values), scalar);
+        }
+    }
+}
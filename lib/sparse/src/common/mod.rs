@@ -1,3 +1,4 @@
+pub mod gather_dot;
 pub mod scores_memory_pool;
 pub mod sparse_vector;
 pub mod sparse_vector_fixture;
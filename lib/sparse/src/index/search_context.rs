@@ -7,16 +7,18 @@ use common::top_k::TopK;
 use common::types::{PointOffsetType, ScoredPointOffset};
 
 use super::posting_list_common::PostingListIter;
+use crate::common::gather_dot::gather_dot;
 use crate::common::scores_memory_pool::PooledScoresHandle;
 use crate::common::sparse_vector::RemappedSparseVector;
-use crate::common::types::{DimId, DimWeight};
+use crate::common::types::{DimOffset, DimWeight};
 use crate::index::inverted_index::InvertedIndex;
 use crate::index::posting_list::PostingListIterator;
 
 /// Iterator over posting lists with a reference to the corresponding query index and weight
 pub struct IndexedPostingListIterator<T: PostingListIter> {
     posting_list_iterator: T,
-    query_index: DimId,
+    /// Offset of the weight in the query
+    query_weight_offset: DimOffset,
     query_weight: DimWeight,
 }
 
@@ -60,12 +62,11 @@ impl<'a, 'b, T: PostingListIter> SearchContext<'a, 'b, T> {
                     max_record_id = max(max_record_id, max_record_id_posting);
 
                     // capture query info
-                    let query_index = *id;
                     let query_weight = query.values[query_weight_offset];
 
                     postings_iterators.push(IndexedPostingListIterator {
                         posting_list_iterator: it,
-                        query_index,
+                        query_weight_offset: query_weight_offset as DimOffset,
                         query_weight,
                     });
                 }
@@ -96,30 +97,31 @@ impl<'a, 'b, T: PostingListIter> SearchContext<'a, 'b, T> {
         let mut sorted_ids = ids.to_vec();
         sorted_ids.sort_unstable();
 
+        let mut offsets = Vec::with_capacity(self.postings_iterators.len());
+        let mut values = Vec::with_capacity(self.postings_iterators.len());
         for id in sorted_ids {
             // check for cancellation
             if self.is_stopped.load(Relaxed) {
                 break;
             }
 
-            let mut indices = Vec::with_capacity(self.query.indices.len());
-            let mut values = Vec::with_capacity(self.query.values.len());
-            // collect indices and values for the current record id from the query's posting lists *only*
+            offsets.clear();
+            values.clear();
+            // collect query offsets and values for the current record id from the query's posting lists *only*
             for posting_iterator in self.postings_iterators.iter_mut() {
                 // rely on underlying binary search as the posting lists are sorted by record id
                 match posting_iterator.posting_list_iterator.skip_to(id) {
                     None => {} // no match for posting list
                     Some(element) => {
                         // match for posting list
-                        indices.push(posting_iterator.query_index);
+                        offsets.push(posting_iterator.query_weight_offset);
                         values.push(element.weight);
                     }
                 }
             }
-            // reconstruct sparse vector and score against query
-            let sparse_vector = RemappedSparseVector { indices, values };
+            // score against query weights at the collected offsets
             self.top_results.push(ScoredPointOffset {
-                score: sparse_vector.score(&self.query).unwrap_or(0.0),
+                score: gather_dot(&self.query.values, &offsets, &values),
                 idx: id,
             });
         }
@@ -24,6 +24,30 @@ impl AliasMapping {
     pub fn save(&self, path: &Path) -> Result<(), StorageError> {
         Ok(atomic_save_json(path, self)?)
     }
+
+    pub fn insert(&mut self, alias: Alias, collection_name: CollectionId) {
+        self.0.insert(alias, collection_name);
+    }
+
+    pub fn remove(&mut self, alias: &str) -> Option<CollectionId> {
+        self.0.remove(alias)
+    }
+
+    pub fn rename_alias(
+        &mut self,
+        old_alias_name: &str,
+        new_alias_name: Alias,
+    ) -> Result<(), StorageError> {
+        match self.0.remove(old_alias_name) {
+            None => Err(StorageError::NotFound {
+                description: format!("Alias {old_alias_name} does not exists!"),
+            }),
+            Some(collection_name) => {
+                self.0.insert(new_alias_name, collection_name);
+                Ok(())
+            }
+        }
+    }
 }
 
 /// Persists mapping between alias and collection name. The data is assumed to be relatively small.
@@ -95,23 +119,38 @@ impl AliasPersistence {
         Ok(())
     }
 
+    /// Points all aliases of a given collection to its new name.
+    pub fn rename_collection(
+        &mut self,
+        collection_name: &str,
+        new_collection_name: &str,
+    ) -> Result<(), StorageError> {
+        let mut changed = false;
+
+        for target_collection in self.alias_mapping.0.values_mut() {
+            if *target_collection == collection_name {
+                *target_collection = new_collection_name.to_string();
+                changed = true;
+            }
+        }
+
+        if changed {
+            self.alias_mapping.save(&self.data_path)?;
+        }
+
+        Ok(())
+    }
+
     pub fn rename_alias(
         &mut self,
         old_alias_name: &str,
         new_alias_name: String,
     ) -> Result<(), StorageError> {
-        match self.get(old_alias_name) {
-            None => Err(StorageError::NotFound {
-                description: format!("Alias {old_alias_name} does not exists!"),
-            }),
-            Some(collection_name) => {
-                self.alias_mapping.0.remove(old_alias_name);
-                self.alias_mapping.0.insert(new_alias_name, collection_name);
-                // 'remove' & 'insert' saved atomically
-                self.alias_mapping.save(&self.data_path)?;
-                Ok(())
-            }
-        }
+        self.alias_mapping
+            .rename_alias(old_alias_name, new_alias_name)?;
+        // 'remove' & 'insert' saved atomically
+        self.alias_mapping.save(&self.data_path)?;
+        Ok(())
     }
 
     pub fn collection_aliases(&self, collection_name: &str) -> Vec<String> {
@@ -0,0 +1,100 @@
+use std::sync::Arc;
+
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use segment::types::PointIdType;
+use tokio::sync::mpsc;
+
+use crate::content_manager::errors::StorageError;
+use crate::content_manager::toc::TableOfContent;
+use crate::rbac::Access;
+
+/// Number of bytes of a blob read at once
+const BLOB_READ_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Number of read chunks waiting to be sent
+const READ_AHEAD_CHUNKS: usize = 2;
+
+/// Blob of a point, streamed in chunks
+pub struct BlobStream {
+    /// Size of the whole blob in bytes
+    pub total_len: usize,
+    /// Chunks of the blob, in order. An error is sent in place of a chunk if reading fails midway.
+    pub chunks: mpsc::Receiver<Result<Vec<u8>, StorageError>>,
+}
+
+/// Stream the blob of a point, chunk by chunk
+///
+/// The first chunk is read right away, so a missing blob is reported before streaming starts.
+/// Remaining chunks are read in the background.
+pub async fn stream_blob(
+    toc: Arc<TableOfContent>,
+    collection_name: String,
+    point_id: PointIdType,
+    shard_selection: ShardSelectorInternal,
+    access: Access,
+) -> Result<BlobStream, StorageError> {
+    let read_chunk = {
+        let toc = toc.clone();
+        let collection_name = collection_name.clone();
+        move |offset: usize| {
+            let toc = toc.clone();
+            let collection_name = collection_name.clone();
+            let shard_selection = shard_selection.clone();
+            let access = access.clone();
+            async move {
+                toc.read_blob(
+                    &collection_name,
+                    point_id,
+                    offset,
+                    BLOB_READ_CHUNK_SIZE,
+                    shard_selection,
+                    access,
+                )
+                .await
+            }
+        }
+    };
+
+    let first_chunk = read_chunk(0)
+        .await?
+        .ok_or_else(|| StorageError::not_found(format!("Point {point_id} has no blob")))?;
+    let total_len = first_chunk.total_len;
+
+    let (sender, receiver) = mpsc::channel(READ_AHEAD_CHUNKS);
+    tokio::spawn(async move {
+        let mut offset = first_chunk.data.len();
+        if sender.send(Ok(first_chunk.data)).await.is_err() {
+            return;
+        }
+
+        while offset < total_len {
+            let chunk = match read_chunk(offset).await {
+                Ok(Some(chunk)) if chunk.total_len == total_len && !chunk.data.is_empty() => {
+                    offset += chunk.data.len();
+                    Ok(chunk.data)
+                }
+                // Blob was replaced or removed while streaming, the parts would not match
+                Ok(_) => Err(StorageError::service_error(format!(
+                    "Blob of point {point_id} was modified while reading",
+                ))),
+                Err(err) => Err(err),
+            };
+
+            if let Err(err) = &chunk {
+                log::error!(
+                    "Failed to read blob of point {point_id} in collection {collection_name}: {err}",
+                );
+            }
+            let is_err = chunk.is_err();
+            if sender.send(chunk).await.is_err() || is_err {
+                // Nobody is waiting for the blob anymore, or it can't be read further
+                return;
+            }
+        }
+    });
+
+    Ok(BlobStream {
+        total_len,
+        chunks: receiver,
+    })
+}
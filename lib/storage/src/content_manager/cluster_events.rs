@@ -0,0 +1,207 @@
+use collection::shards::replica_set::ReplicaState;
+use collection::shards::shard::{PeerId, ShardId};
+use schemars::JsonSchema;
+use serde::Serialize;
+use tokio::sync::broadcast;
+
+use crate::content_manager::collection_meta_ops::{
+    CollectionMetaOperations, ShardTransferOperations,
+};
+
+/// How many events are buffered for each subscriber.
+/// Subscribers lagging behind by more than that miss the oldest events.
+const CLUSTER_EVENTS_CAPACITY: usize = 1024;
+
+/// Change of the cluster state, applied through consensus
+///
+/// All peers apply the same operations, so subscribers of any peer observe the same events.
+#[derive(Debug, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClusterEvent {
+    PeerJoined {
+        peer_id: PeerId,
+        uri: String,
+    },
+    PeerLeft {
+        peer_id: PeerId,
+    },
+    CollectionCreated {
+        collection_name: String,
+    },
+    CollectionDeleted {
+        collection_name: String,
+    },
+    CollectionRenamed {
+        collection_name: String,
+        new_collection_name: String,
+    },
+    ReplicaStateChanged {
+        collection_name: String,
+        shard_id: ShardId,
+        peer_id: PeerId,
+        state: ReplicaState,
+    },
+    TransferStarted {
+        collection_name: String,
+        shard_id: ShardId,
+        from_peer_id: PeerId,
+        to_peer_id: PeerId,
+    },
+    TransferFinished {
+        collection_name: String,
+        shard_id: ShardId,
+        from_peer_id: PeerId,
+        to_peer_id: PeerId,
+    },
+    TransferAborted {
+        collection_name: String,
+        shard_id: ShardId,
+        from_peer_id: PeerId,
+        to_peer_id: PeerId,
+        reason: String,
+    },
+}
+
+impl ClusterEvent {
+    /// Event caused by the collection operation, if any
+    pub fn from_collection_meta_op(operation: &CollectionMetaOperations) -> Option<Self> {
+        let event = match operation {
+            CollectionMetaOperations::CreateCollection(operation) => Self::CollectionCreated {
+                collection_name: operation.collection_name.clone(),
+            },
+            CollectionMetaOperations::DeleteCollection(operation) => Self::CollectionDeleted {
+                collection_name: operation.0.clone(),
+            },
+            CollectionMetaOperations::RenameCollection(operation) => Self::CollectionRenamed {
+                collection_name: operation.collection_name.clone(),
+                new_collection_name: operation.rename_collection.new_collection_name.clone(),
+            },
+            CollectionMetaOperations::SetShardReplicaState(operation) => {
+                Self::ReplicaStateChanged {
+                    collection_name: operation.collection_name.clone(),
+                    shard_id: operation.shard_id,
+                    peer_id: operation.peer_id,
+                    state: operation.state,
+                }
+            }
+            CollectionMetaOperations::TransferShard(collection_name, operation) => {
+                let collection_name = collection_name.clone();
+                match operation {
+                    ShardTransferOperations::Start(transfer) => Self::TransferStarted {
+                        collection_name,
+                        shard_id: transfer.shard_id,
+                        from_peer_id: transfer.from,
+                        to_peer_id: transfer.to,
+                    },
+                    ShardTransferOperations::Finish(transfer) => Self::TransferFinished {
+                        collection_name,
+                        shard_id: transfer.shard_id,
+                        from_peer_id: transfer.from,
+                        to_peer_id: transfer.to,
+                    },
+                    ShardTransferOperations::Abort { transfer, reason } => Self::TransferAborted {
+                        collection_name,
+                        shard_id: transfer.shard_id,
+                        from_peer_id: transfer.from,
+                        to_peer_id: transfer.to,
+                        reason: reason.clone(),
+                    },
+                    ShardTransferOperations::Restart(_)
+                    | ShardTransferOperations::SnapshotRecovered(_)
+                    | ShardTransferOperations::RecoveryToPartial(_) => return None,
+                }
+            }
+            CollectionMetaOperations::UpdateCollection(_)
+            | CollectionMetaOperations::ChangeAliases(_)
+            | CollectionMetaOperations::Resharding(_, _)
+            | CollectionMetaOperations::CreateShardKey(_)
+            | CollectionMetaOperations::DropShardKey(_)
+            | CollectionMetaOperations::CreatePayloadIndex(_)
+            | CollectionMetaOperations::DropPayloadIndex(_)
+            | CollectionMetaOperations::Nop { .. } => return None,
+        };
+        Some(event)
+    }
+}
+
+/// Broadcasts cluster events to all subscribers
+pub struct ClusterEvents {
+    sender: broadcast::Sender<ClusterEvent>,
+}
+
+impl Default for ClusterEvents {
+    fn default() -> Self {
+        let (sender, _) = broadcast::channel(CLUSTER_EVENTS_CAPACITY);
+        Self { sender }
+    }
+}
+
+impl ClusterEvents {
+    pub fn send(&self, event: ClusterEvent) {
+        log::trace!("Cluster event: {event:?}");
+        // Error only means there are no subscribers at the moment
+        let _ = self.sender.send(event);
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<ClusterEvent> {
+        self.sender.subscribe()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use collection::shards::transfer::ShardTransferKey;
+
+    use super::*;
+    use crate::content_manager::collection_meta_ops::DeleteCollectionOperation;
+
+    #[test]
+    fn test_events_from_collection_meta_ops() {
+        let operation = CollectionMetaOperations::TransferShard(
+            "test".to_string(),
+            ShardTransferOperations::Abort {
+                transfer: ShardTransferKey {
+                    shard_id: 2,
+                    from: 1,
+                    to: 3,
+                },
+                reason: "failed".to_string(),
+            },
+        );
+        assert_eq!(
+            ClusterEvent::from_collection_meta_op(&operation),
+            Some(ClusterEvent::TransferAborted {
+                collection_name: "test".to_string(),
+                shard_id: 2,
+                from_peer_id: 1,
+                to_peer_id: 3,
+                reason: "failed".to_string(),
+            }),
+        );
+
+        let operation =
+            CollectionMetaOperations::DeleteCollection(DeleteCollectionOperation("test".into()));
+        let event = ClusterEvent::from_collection_meta_op(&operation).unwrap();
+        assert_eq!(
+            serde_json::to_value(&event).unwrap(),
+            serde_json::json!({"type": "collection_deleted", "collection_name": "test"}),
+        );
+
+        let operation = CollectionMetaOperations::Nop { token: 0 };
+        assert_eq!(ClusterEvent::from_collection_meta_op(&operation), None);
+    }
+
+    #[tokio::test]
+    async fn test_subscribers_receive_events() {
+        let events = ClusterEvents::default();
+        // Not received by anyone
+        events.send(ClusterEvent::PeerLeft { peer_id: 1 });
+
+        let mut receiver = events.subscribe();
+        events.send(ClusterEvent::PeerLeft { peer_id: 2 });
+        assert_eq!(
+            receiver.recv().await.unwrap(),
+            ClusterEvent::PeerLeft { peer_id: 2 },
+        );
+    }
+}
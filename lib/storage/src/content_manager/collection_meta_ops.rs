@@ -1,6 +1,9 @@
 use std::collections::BTreeMap;
 
-use collection::config::{CollectionConfig, ShardingMethod};
+use collection::config::{
+    AsyncReplicationConfig, CollectionConfig, InferenceConfig, MultitenancyConfig, QuotaConfig,
+    ShardPin, ShardingMethod, StorageEngine, TieringConfig, TtlConfig,
+};
 use collection::operations::config_diff::{
     CollectionParamsDiff, HnswConfigDiff, OptimizersConfigDiff, QuantizationConfigDiff,
     WalConfigDiff,
@@ -13,7 +16,7 @@ use collection::shards::shard::{PeerId, ShardId, ShardsPlacement};
 use collection::shards::transfer::{ShardTransfer, ShardTransferKey, ShardTransferRestart};
 use collection::shards::{replica_set, CollectionId};
 use schemars::JsonSchema;
-use segment::types::{PayloadFieldSchema, PayloadKeyType, QuantizationConfig, ShardKey};
+use segment::types::{Filter, PayloadFieldSchema, PayloadKeyType, QuantizationConfig, ShardKey};
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
@@ -94,10 +97,26 @@ impl From<RenameAlias> for AliasOperations {
 }
 
 /// Operation for creating new collection and (optionally) specify index params
-#[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Eq, Hash, Clone)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, PartialEq, Clone)]
 #[serde(rename_all = "snake_case")]
 pub struct InitFrom {
     pub collection: CollectionId,
+    /// Copy only points, which satisfy this filter. If none - all points are copied.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<Filter>,
+}
+
+impl Eq for InitFrom {}
+
+impl std::hash::Hash for InitFrom {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.collection.hash(state);
+        // Filter contains floats and can't derive `Hash`, hash its serialized form instead
+        self.filter
+            .as_ref()
+            .map(|filter| serde_json::to_string(filter).unwrap_or_default())
+            .hash(state);
+    }
 }
 
 /// Operation for creating new collection and (optionally) specify index params
@@ -144,6 +163,23 @@ pub struct CreateCollection {
     /// Note: those payload values that are involved in filtering and are indexed - remain in RAM.
     #[serde(default)]
     pub on_disk_payload: Option<bool>,
+    /// Engine to store payload and point ids with. If none - RocksDB is used.
+    /// `append_only` and `mmap` always keep payload on disk, `on_disk_payload` is ignored for them.
+    #[serde(default)]
+    pub storage_engine: Option<StorageEngine>,
+    /// Payload fields, values of which are additionally stored column-wise.
+    /// Speeds up filtering by those fields and scanning their values on large payloads.
+    #[serde(default)]
+    pub payload_columns: Option<Vec<PayloadKeyType>>,
+    /// Only peers with all of these labels may host shards of the collection, e.g. `{"disk": "nvme"}`.
+    /// Enforced when shards are placed and transferred.
+    #[serde(default)]
+    pub placement_labels: Option<BTreeMap<String, String>>,
+    /// Pin replicas of shards, or of all shards of a shard key, to specific peers.
+    /// Enforced when shards are placed and transferred.
+    #[serde(default)]
+    #[validate]
+    pub shard_pinning: Option<Vec<ShardPin>>,
     /// Custom params for HNSW index. If none - values from service configuration file are used.
     #[validate]
     pub hnsw_config: Option<HnswConfigDiff>,
@@ -164,6 +200,31 @@ pub struct CreateCollection {
     /// Sparse vector data config.
     #[validate]
     pub sparse_vectors: Option<BTreeMap<String, SparseVectorParams>>,
+    /// Hot/cold storage tiering. If none - all segments are kept in the hot tier.
+    #[serde(default)]
+    #[validate]
+    pub tiering_config: Option<TieringConfig>,
+    /// Automatic expiry of points by a datetime payload field. If none - points never expire.
+    #[serde(default)]
+    #[validate]
+    pub ttl_config: Option<TtlConfig>,
+    /// Asynchronous replication to a remote cluster. If none - collection is not replicated.
+    #[serde(default)]
+    #[validate]
+    pub async_replication_config: Option<AsyncReplicationConfig>,
+    /// Embedding service for documents and images. If none - only vectors are accepted.
+    #[serde(default)]
+    #[validate]
+    pub inference_config: Option<InferenceConfig>,
+    /// Limits of each tenant of the collection. If none - tenants are not limited.
+    #[serde(default)]
+    #[validate]
+    pub quota_config: Option<QuotaConfig>,
+    /// Tenant payload field of the collection. If none - points don't belong to tenants.
+    /// Can't be changed after the collection is created.
+    #[serde(default)]
+    #[validate]
+    pub multitenancy_config: Option<MultitenancyConfig>,
 }
 
 /// Operation for creating new collection and (optionally) specify index params
@@ -197,6 +258,27 @@ impl CreateCollectionOperation {
     }
 }
 
+/// Operation for copying an existing collection into a new one.
+/// Configuration of the new collection is taken from the source collection.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct CloneCollection {
+    /// Name of the new collection
+    #[validate(
+        length(min = 1, max = 255),
+        custom = "common::validation::validate_collection_name"
+    )]
+    pub name: String,
+    /// Vector data config of the new collection. If none - config of the source collection is used.
+    #[serde(default)]
+    #[validate]
+    pub vectors: Option<VectorsConfig>,
+    /// Copy only points, which satisfy this filter. If none - all points are copied.
+    #[serde(default)]
+    #[validate]
+    pub filter: Option<Filter>,
+}
+
 /// Operation for updating parameters of the existing collection
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, PartialEq, Eq, Hash, Clone)]
 #[serde(rename_all = "snake_case")]
@@ -221,6 +303,31 @@ pub struct UpdateCollection {
     /// Map of sparse vector data parameters to update for each sparse vector.
     #[validate]
     pub sparse_vectors: Option<SparseVectorsConfig>,
+    /// Hot/cold storage tiering parameters. If none - it is left unchanged.
+    #[serde(default)]
+    #[validate]
+    pub tiering_config: Option<TieringConfig>,
+    /// Points expiry parameters. If none - it is left unchanged.
+    #[serde(default)]
+    #[validate]
+    pub ttl_config: Option<TtlConfig>,
+    /// Asynchronous replication parameters. If none - it is left unchanged.
+    #[serde(default)]
+    #[validate]
+    pub async_replication_config: Option<AsyncReplicationConfig>,
+    /// Embedding service parameters. If none - it is left unchanged.
+    #[serde(default)]
+    #[validate]
+    pub inference_config: Option<InferenceConfig>,
+    /// Tenant quotas. If none - it is left unchanged.
+    #[serde(default)]
+    #[validate]
+    pub quota_config: Option<QuotaConfig>,
+    /// Shard pinning policy to replace the current one. Empty list removes all pins.
+    /// If none - it is left unchanged.
+    #[serde(default)]
+    #[validate]
+    pub shard_pinning: Option<Vec<ShardPin>>,
 }
 
 /// Operation for updating parameters of the existing collection
@@ -243,6 +350,12 @@ impl UpdateCollectionOperation {
                 optimizers_config: None,
                 quantization_config: None,
                 sparse_vectors: None,
+                tiering_config: None,
+                ttl_config: None,
+                async_replication_config: None,
+                inference_config: None,
+                quota_config: None,
+                shard_pinning: None,
             },
             shard_replica_changes: None,
         }
@@ -279,7 +392,7 @@ impl UpdateCollectionOperation {
 
 /// Operation for performing changes of collection aliases.
 /// Alias changes are atomic, meaning that no collection modifications can happen between
-/// alias operations.
+/// alias operations. If any of the changes fails, none of them are applied.
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, PartialEq, Eq, Hash, Clone)]
 #[serde(rename_all = "snake_case")]
 pub struct ChangeAliasesOperation {
@@ -291,6 +404,36 @@ pub struct ChangeAliasesOperation {
 #[serde(rename_all = "snake_case")]
 pub struct DeleteCollectionOperation(pub String);
 
+/// Operation for changing the name of an existing collection.
+/// Aliases of the collection are moved to the new name.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, PartialEq, Eq, Hash, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct RenameCollection {
+    /// New name of the collection
+    #[validate(
+        length(min = 1, max = 255),
+        custom = "common::validation::validate_collection_name"
+    )]
+    pub new_collection_name: String,
+}
+
+/// Operation for renaming collection with given name
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Hash, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct RenameCollectionOperation {
+    pub collection_name: String,
+    pub rename_collection: RenameCollection,
+}
+
+impl RenameCollectionOperation {
+    pub fn new(collection_name: String, rename_collection: RenameCollection) -> Self {
+        Self {
+            collection_name,
+            rename_collection,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize, Serialize)]
 pub enum ReshardingOperation {
     Start {
@@ -382,6 +525,7 @@ pub enum CollectionMetaOperations {
     CreateCollection(CreateCollectionOperation),
     UpdateCollection(UpdateCollectionOperation),
     DeleteCollection(DeleteCollectionOperation),
+    RenameCollection(RenameCollectionOperation),
     ChangeAliases(ChangeAliasesOperation),
     Resharding(CollectionId, ReshardingOperation),
     TransferShard(CollectionId, ShardTransferOperations),
@@ -404,12 +548,22 @@ impl From<CollectionConfig> for CreateCollection {
             replication_factor: Some(value.params.replication_factor.get()),
             write_consistency_factor: Some(value.params.write_consistency_factor.get()),
             on_disk_payload: Some(value.params.on_disk_payload),
+            storage_engine: value.params.storage_engine,
+            payload_columns: value.params.payload_columns,
+            placement_labels: value.params.placement_labels,
+            shard_pinning: value.params.shard_pinning,
             hnsw_config: Some(value.hnsw_config.into()),
             wal_config: Some(value.wal_config.into()),
             optimizers_config: Some(value.optimizer_config.into()),
             init_from: None,
             quantization_config: value.quantization_config,
             sparse_vectors: value.params.sparse_vectors,
+            tiering_config: value.tiering_config,
+            ttl_config: value.ttl_config,
+            async_replication_config: value.async_replication_config,
+            inference_config: value.inference_config,
+            quota_config: value.quota_config,
+            multitenancy_config: value.multitenancy_config,
         }
     }
 }
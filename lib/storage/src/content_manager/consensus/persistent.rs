@@ -1,4 +1,5 @@
 use std::cmp;
+use std::collections::HashSet;
 use std::fs::{create_dir_all, File};
 use std::io::BufWriter;
 use std::path::{Path, PathBuf};
@@ -15,7 +16,7 @@ use raft::RaftState;
 use serde::{Deserialize, Serialize};
 
 use crate::content_manager::consensus::entry_queue::{EntryApplyProgressQueue, EntryId};
-use crate::types::{PeerAddressById, PeerMetadataById};
+use crate::types::{ClusterMetadata, ClusterMetadataEntry, PeerAddressById, PeerMetadataById};
 use crate::StorageError;
 
 // Deprecated, use `STATE_FILE_NAME` instead
@@ -42,6 +43,12 @@ pub struct Persistent {
     pub peer_address_by_id: Arc<RwLock<PeerAddressById>>,
     #[serde(default)]
     pub peer_metadata_by_id: Arc<RwLock<PeerMetadataById>>,
+    /// Peers, which joined as learners and are never promoted to voters
+    #[serde(default)]
+    pub learner_peers: HashSet<PeerId>,
+    /// Key-value store of the cluster-wide metadata
+    #[serde(default)]
+    pub cluster_metadata: ClusterMetadata,
     pub this_peer_id: PeerId,
     #[serde(skip)]
     pub path: PathBuf,
@@ -64,9 +71,13 @@ impl Persistent {
         meta: &SnapshotMetadata,
         address_by_id: PeerAddressById,
         metadata_by_id: PeerMetadataById,
+        learner_peers: HashSet<PeerId>,
+        cluster_metadata: ClusterMetadata,
     ) -> Result<(), StorageError> {
         *self.peer_address_by_id.write() = address_by_id;
         *self.peer_metadata_by_id.write() = metadata_by_id;
+        self.learner_peers = learner_peers;
+        self.cluster_metadata = cluster_metadata;
         self.state.conf_state = meta.get_conf_state().clone();
         self.state.hard_state.term = cmp::max(self.state.hard_state.term, meta.term);
         self.state.hard_state.commit = meta.index;
@@ -174,6 +185,62 @@ impl Persistent {
         self.save()
     }
 
+    pub fn set_peer_learner(&mut self, peer_id: PeerId, learner: bool) -> Result<(), StorageError> {
+        let changed = if learner {
+            self.learner_peers.insert(peer_id)
+        } else {
+            self.learner_peers.remove(&peer_id)
+        };
+        if !changed {
+            return Ok(());
+        }
+        log::debug!("Peer {peer_id} is a learner: {learner}");
+        self.save()
+    }
+
+    pub fn is_learner_peer(&self, peer_id: PeerId) -> bool {
+        self.learner_peers.contains(&peer_id)
+    }
+
+    /// Set the key of the cluster metadata, or remove it if `value` is `None`
+    ///
+    /// `version` is the index of the consensus operation, it becomes the new version of the key.
+    pub fn set_cluster_metadata(
+        &mut self,
+        key: String,
+        value: Option<String>,
+        expected_version: Option<u64>,
+        version: u64,
+    ) -> Result<(), StorageError> {
+        let current_version = self
+            .cluster_metadata
+            .get(&key)
+            .map_or(0, |entry| entry.version);
+        if let Some(expected_version) = expected_version {
+            if expected_version != current_version {
+                return Err(StorageError::AlreadyExists {
+                    description: format!(
+                        "Version of cluster metadata key {key} is {current_version}, \
+                         expected {expected_version}",
+                    ),
+                });
+            }
+        }
+
+        match value {
+            Some(value) => {
+                self.cluster_metadata
+                    .insert(key, ClusterMetadataEntry { value, version });
+            }
+            None => {
+                if self.cluster_metadata.remove(&key).is_none() {
+                    return Ok(());
+                }
+            }
+        }
+        self.save()
+    }
+
     pub fn last_applied_entry(&self) -> Option<u64> {
         self.apply_progress_queue.get_last_applied()
     }
@@ -186,11 +253,11 @@ impl Persistent {
         self.peer_metadata_by_id.read().clone()
     }
 
-    pub fn is_our_metadata_outdated(&self) -> bool {
+    pub fn is_our_metadata_outdated(&self, current: &PeerMetadata) -> bool {
         self.peer_metadata_by_id
             .read()
             .get(&self.this_peer_id())
-            .map_or(true, |metadata| metadata.is_different_version())
+            .map_or(true, |metadata| metadata != current)
     }
 
     pub fn this_peer_id(&self) -> PeerId {
@@ -224,6 +291,8 @@ impl Persistent {
             apply_progress_queue: Default::default(),
             peer_address_by_id: Default::default(),
             peer_metadata_by_id: Default::default(),
+            learner_peers: Default::default(),
+            cluster_metadata: Default::default(),
             this_peer_id,
             path,
             latest_snapshot_meta: Default::default(),
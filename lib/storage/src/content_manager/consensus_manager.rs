@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::error::Error;
 use std::fmt::Display;
 use std::future::Future;
@@ -17,7 +17,7 @@ use common::defaults;
 use futures::future::join_all;
 use parking_lot::{Mutex, RwLock};
 use raft::eraftpb::{ConfChangeType, ConfChangeV2, Entry as RaftEntry};
-use raft::{GetEntriesContext, RaftState, RawNode, SoftState, Storage};
+use raft::{GetEntriesContext, RaftState, RawNode, SoftState, StateRole, Storage};
 use serde::{Deserialize, Serialize};
 use tokio::sync::broadcast;
 use tokio::sync::broadcast::Receiver;
@@ -25,6 +25,7 @@ use tokio::time::error::Elapsed;
 use tonic::transport::Uri;
 
 use super::alias_mapping::AliasMapping;
+use super::cluster_events::{ClusterEvent, ClusterEvents};
 use super::consensus_ops::{ConsensusOperations, SnapshotStatus};
 use super::errors::StorageError;
 use super::CollectionContainer;
@@ -32,9 +33,10 @@ use crate::content_manager::consensus::consensus_wal::ConsensusOpWal;
 use crate::content_manager::consensus::entry_queue::EntryId;
 use crate::content_manager::consensus::operation_sender::OperationSender;
 use crate::content_manager::consensus::persistent::Persistent;
+use crate::rbac::revocations::RevokedCredentials;
 use crate::types::{
-    ClusterInfo, ClusterStatus, ConsensusThreadStatus, MessageSendErrors, PeerAddressById,
-    PeerInfo, PeerMetadataById, RaftInfo,
+    ClusterInfo, ClusterMetadata, ClusterStatus, ConsensusThreadStatus, MessageSendErrors,
+    PeerAddressById, PeerInfo, PeerMetadataById, RaftInfo,
 };
 
 pub mod prelude {
@@ -53,6 +55,12 @@ pub struct SnapshotData {
     pub address_by_id: PeerAddressById,
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub metadata_by_id: PeerMetadataById,
+    #[serde(default, skip_serializing_if = "HashSet::is_empty")]
+    pub learner_peers: HashSet<PeerId>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub cluster_metadata: ClusterMetadata,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub revoked_credentials: RevokedCredentials,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
@@ -100,6 +108,12 @@ pub struct ConsensusManager<C: CollectionContainer> {
     message_send_failures: RwLock<HashMap<String, MessageSendErrors>>,
     /// Last time we attempted to update the peer metadata
     next_peer_metadata_update_attempt: Mutex<Instant>,
+    /// Availability zone of this peer, shared with other peers as part of the metadata
+    zone: Option<String>,
+    /// Labels of this peer, shared with other peers as part of the metadata
+    labels: BTreeMap<String, String>,
+    /// Subscribers of changes applied through consensus
+    cluster_events: ClusterEvents,
 }
 
 impl<C: CollectionContainer> ConsensusManager<C> {
@@ -123,9 +137,27 @@ impl<C: CollectionContainer> ConsensusManager<C> {
             }),
             message_send_failures: Default::default(),
             next_peer_metadata_update_attempt: Mutex::new(Instant::now()),
+            zone: None,
+            labels: BTreeMap::new(),
+            cluster_events: ClusterEvents::default(),
         }
     }
 
+    pub fn with_zone(mut self, zone: Option<String>) -> Self {
+        self.zone = zone;
+        self
+    }
+
+    pub fn with_labels(mut self, labels: BTreeMap<String, String>) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    /// Subscribe to events about changes of the cluster, which are applied from now on
+    pub fn subscribe_cluster_events(&self) -> Receiver<ClusterEvent> {
+        self.cluster_events.subscribe()
+    }
+
     pub fn report_snapshot(
         &self,
         peer_id: u64,
@@ -200,6 +232,8 @@ impl<C: CollectionContainer> ConsensusManager<C> {
     pub fn cluster_status(&self) -> ClusterStatus {
         let persistent = self.persistent.read();
         let hard_state = &persistent.state.hard_state;
+        let voters = persistent.state.conf_state.get_voters();
+        let metadata_by_id = persistent.peer_metadata_by_id.read();
         let peers = persistent
             .peer_address_by_id()
             .into_iter()
@@ -208,6 +242,17 @@ impl<C: CollectionContainer> ConsensusManager<C> {
                     peer_id,
                     PeerInfo {
                         uri: uri.to_string(),
+                        is_voter: voters.contains(&peer_id),
+                        zone: metadata_by_id
+                            .get(&peer_id)
+                            .and_then(|metadata| metadata.zone().map(str::to_string)),
+                        labels: metadata_by_id
+                            .get(&peer_id)
+                            .map(|metadata| metadata.labels().clone())
+                            .unwrap_or_default(),
+                        read_only: metadata_by_id
+                            .get(&peer_id)
+                            .is_some_and(|metadata| metadata.is_read_only()),
                     },
                 )
             })
@@ -249,6 +294,7 @@ impl<C: CollectionContainer> ConsensusManager<C> {
                 if self.this_peer_id() == peer_id {
                     stop_consensus = true;
                 }
+                self.cluster_events.send(ClusterEvent::PeerLeft { peer_id });
                 Ok(true)
             }
             Err(err) => match err {
@@ -397,6 +443,10 @@ impl<C: CollectionContainer> ConsensusManager<C> {
                         let peer_uri: Uri = peer_uri;
                         // Add peer to state
                         self.add_peer(single_change.node_id, peer_uri.clone())?;
+                        self.cluster_events.send(ClusterEvent::PeerJoined {
+                            peer_id: single_change.node_id,
+                            uri: peer_uri.to_string(),
+                        });
 
                         // Notify the submitter, that operation was performed
                         {
@@ -442,7 +492,12 @@ impl<C: CollectionContainer> ConsensusManager<C> {
         let on_apply = self.on_consensus_op_apply.lock().remove(&operation);
         let result = match operation {
             ConsensusOperations::CollectionMeta(operation) => {
-                self.toc.perform_collection_meta_op(*operation)
+                let event = ClusterEvent::from_collection_meta_op(&operation);
+                let result = self.toc.perform_collection_meta_op(*operation);
+                if let (Ok(true), Some(event)) = (&result, event) {
+                    self.cluster_events.send(event);
+                }
+                result
             }
 
             ConsensusOperations::AddPeer { .. } | ConsensusOperations::RemovePeer(_) => {
@@ -464,6 +519,32 @@ impl<C: CollectionContainer> ConsensusManager<C> {
                 Ok(true)
             }
 
+            ConsensusOperations::SetPeerLearner { peer_id, learner } => {
+                self.persistent.write().set_peer_learner(peer_id, learner)?;
+                Ok(true)
+            }
+
+            ConsensusOperations::SetClusterMetadata {
+                key,
+                value,
+                expected_version,
+            } => {
+                self.persistent.write().set_cluster_metadata(
+                    key,
+                    value,
+                    expected_version,
+                    entry.index,
+                )?;
+                Ok(true)
+            }
+
+            ConsensusOperations::SetCredentialRevocation {
+                credential_id,
+                revocation,
+            } => self
+                .toc
+                .set_credential_revocation(credential_id, revocation),
+
             ConsensusOperations::RequestSnapshot | ConsensusOperations::ReportSnapshot { .. } => {
                 unreachable!()
             }
@@ -486,11 +567,15 @@ impl<C: CollectionContainer> ConsensusManager<C> {
 
         let data: SnapshotData = snapshot.get_data().try_into()?;
         self.toc.apply_collections_snapshot(data.collections_data)?;
+        self.toc
+            .apply_revocations_snapshot(data.revoked_credentials)?;
         self.wal.lock().clear()?;
         self.persistent.write().update_from_snapshot(
             meta,
             data.address_by_id,
             data.metadata_by_id,
+            data.learner_peers,
+            data.cluster_metadata,
         )?;
 
         Ok(Ok(()))
@@ -537,7 +622,9 @@ impl<C: CollectionContainer> ConsensusManager<C> {
         // plus we need to make additional removing in the `channel_pool`.
         // So we handle `remove_peer` inside the `toc` and persist changes in the `persistent` after that.
         self.toc.remove_peer(peer_id)?;
-        self.persistent.read().save()
+        let mut persistent = self.persistent.write();
+        persistent.learner_peers.remove(&peer_id);
+        persistent.save()
     }
 
     async fn await_receiver(
@@ -712,6 +799,35 @@ impl<C: CollectionContainer> ConsensusManager<C> {
         self.persistent.read().peer_metadata_by_id()
     }
 
+    pub fn is_leader(&self) -> bool {
+        self.soft_state
+            .read()
+            .as_ref()
+            .is_some_and(|state| state.raft_state == StateRole::Leader)
+    }
+
+    /// Peers, to which sending consensus messages failed since the last successful attempt
+    ///
+    /// Only the leader sends messages to all peers, followers only know about the leader.
+    pub fn unreachable_peers(&self) -> HashSet<PeerId> {
+        let message_send_failures = self.message_send_failures.read();
+        self.peer_address_by_id()
+            .into_iter()
+            .filter(|(_, address)| message_send_failures.contains_key(&address.to_string()))
+            .map(|(peer_id, _)| peer_id)
+            .collect()
+    }
+
+    /// Key-value store of the cluster-wide metadata
+    pub fn cluster_metadata(&self) -> ClusterMetadata {
+        self.persistent.read().cluster_metadata.clone()
+    }
+
+    /// Whether the peer joined as a learner, which should never be promoted to a voter
+    pub fn is_learner_peer(&self, peer_id: PeerId) -> bool {
+        self.persistent.read().is_learner_peer(peer_id)
+    }
+
     pub fn peer_count(&self) -> usize {
         self.persistent.read().peer_address_by_id.read().len()
     }
@@ -729,6 +845,13 @@ impl<C: CollectionContainer> ConsensusManager<C> {
         self.toc.sync_local_state()
     }
 
+    /// Propose our peer metadata on the next sync, without waiting for the update interval
+    ///
+    /// Used when the metadata changes at runtime, e.g. read-only mode is toggled.
+    pub fn request_peer_metadata_update(&self) {
+        *self.next_peer_metadata_update_attempt.lock() = Instant::now();
+    }
+
     /// Try to update our peer metadata if it's outdated
     ///
     /// It rate limits updating to `CONSENSUS_PEER_METADATA_UPDATE_INTERVAL`.
@@ -738,7 +861,12 @@ impl<C: CollectionContainer> ConsensusManager<C> {
             return Ok(());
         }
 
-        if !self.persistent.read().is_our_metadata_outdated() {
+        let metadata = PeerMetadata::current(
+            self.zone.clone(),
+            self.labels.clone(),
+            self.toc.is_read_only(),
+        );
+        if !self.persistent.read().is_our_metadata_outdated(&metadata) {
             return Ok(());
         }
 
@@ -747,7 +875,7 @@ impl<C: CollectionContainer> ConsensusManager<C> {
             .propose_sender
             .send(ConsensusOperations::UpdatePeerMetadata {
                 peer_id: self.this_peer_id(),
-                metadata: PeerMetadata::current(),
+                metadata,
             });
         if let Err(err) = result {
             log::error!("Failed to propose consensus peer metadata update for this peer: {err}");
@@ -824,6 +952,9 @@ impl<C: CollectionContainer> Storage for ConsensusManager<C> {
                 collections_data,
                 address_by_id: persistent.peer_address_by_id(),
                 metadata_by_id: persistent.peer_metadata_by_id(),
+                learner_peers: persistent.learner_peers.clone(),
+                cluster_metadata: persistent.cluster_metadata.clone(),
+                revoked_credentials: self.toc.revoked_credentials(),
             };
             Ok(raft::eraftpb::Snapshot {
                 data: serde_cbor::to_vec(&snapshot).map_err(raft_error_other)?,
@@ -1038,6 +1169,29 @@ mod tests {
         fn sync_local_state(&self) -> Result<(), crate::content_manager::errors::StorageError> {
             Ok(())
         }
+
+        fn is_read_only(&self) -> bool {
+            false
+        }
+
+        fn set_credential_revocation(
+            &self,
+            _credential_id: String,
+            _revocation: Option<crate::rbac::revocations::CredentialRevocation>,
+        ) -> Result<bool, crate::content_manager::errors::StorageError> {
+            Ok(true)
+        }
+
+        fn revoked_credentials(&self) -> crate::rbac::revocations::RevokedCredentials {
+            Default::default()
+        }
+
+        fn apply_revocations_snapshot(
+            &self,
+            _revoked: crate::rbac::revocations::RevokedCredentials,
+        ) -> Result<(), crate::content_manager::errors::StorageError> {
+            Ok(())
+        }
     }
 
     fn setup_storages(
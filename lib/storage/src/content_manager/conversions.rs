@@ -22,6 +22,8 @@ pub fn error_to_status(error: StorageError) -> tonic::Status {
         StorageError::ChecksumMismatch { .. } => tonic::Code::DataLoss,
         StorageError::Forbidden { .. } => tonic::Code::PermissionDenied,
         StorageError::PreconditionFailed { .. } => tonic::Code::FailedPrecondition,
+        StorageError::Conflict { .. } => tonic::Code::Aborted,
+        StorageError::QuotaExceeded { .. } => tonic::Code::ResourceExhausted,
     };
     tonic::Status::new(error_code, format!("{error}"))
 }
@@ -46,11 +48,13 @@ impl TryFrom<api::grpc::qdrant::CreateCollection> for CollectionMetaOperations {
                 optimizers_config: value.optimizers_config.map(|v| v.into()),
                 shard_number: value.shard_number,
                 on_disk_payload: value.on_disk_payload,
+                storage_engine: None,
                 replication_factor: value.replication_factor,
                 write_consistency_factor: value.write_consistency_factor,
-                init_from: value
-                    .init_from_collection
-                    .map(|v| InitFrom { collection: v }),
+                init_from: value.init_from_collection.map(|v| InitFrom {
+                    collection: v,
+                    filter: None,
+                }),
                 quantization_config: value
                     .quantization_config
                     .map(TryInto::try_into)
@@ -59,6 +63,15 @@ impl TryFrom<api::grpc::qdrant::CreateCollection> for CollectionMetaOperations {
                     .sharding_method
                     .map(sharding_method_from_proto)
                     .transpose()?,
+                tiering_config: value.tiering_config.map(Into::into),
+                ttl_config: value.ttl_config.map(TryInto::try_into).transpose()?,
+                async_replication_config: None,
+                inference_config: None,
+                quota_config: None,
+                multitenancy_config: None,
+                payload_columns: None,
+                placement_labels: None,
+                shard_pinning: None,
             },
         )))
     }
@@ -88,6 +101,12 @@ impl TryFrom<api::grpc::qdrant::UpdateCollection> for CollectionMetaOperations {
                         config.map.into_iter().map(|(k, v)| (k, v.into())).collect(),
                     )
                 }),
+                tiering_config: value.tiering_config.map(Into::into),
+                ttl_config: value.ttl_config.map(TryInto::try_into).transpose()?,
+                async_replication_config: None,
+                inference_config: None,
+                quota_config: None,
+                shard_pinning: None,
             },
         )))
     }
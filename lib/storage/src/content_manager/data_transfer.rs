@@ -11,7 +11,7 @@ use collection::operations::{CollectionUpdateOperations, CreateIndex, FieldIndex
 use collection::shards::replica_set::ReplicaState;
 use collection::shards::shard::{PeerId, ShardId};
 use collection::shards::CollectionId;
-use segment::types::{WithPayloadInterface, WithVector};
+use segment::types::{Filter, WithPayloadInterface, WithVector};
 use tokio::sync::RwLock;
 
 use crate::content_manager::collections_ops::Collections;
@@ -73,6 +73,7 @@ async fn replicate_shard_data(
     source_collection_name: &CollectionId,
     target_collection_name: &CollectionId,
     shard_id: ShardId,
+    filter: Option<&Filter>,
 ) -> CollectionResult<()> {
     let mut offset = None;
     let limit = MIGRATION_BATCH_SIZE;
@@ -81,7 +82,7 @@ async fn replicate_shard_data(
         let request = ScrollRequestInternal {
             offset,
             limit: Some(limit),
-            filter: None,
+            filter: filter.cloned(),
             with_payload: Some(WithPayloadInterface::Bool(true)),
             with_vector: WithVector::Bool(true),
             order_by: None,
@@ -109,6 +110,7 @@ async fn replicate_shard_data(
                 id: point.id,
                 vector: point.vector.unwrap(),
                 payload: point.payload,
+                expires_at: None,
             })
             .collect();
 
@@ -149,10 +151,13 @@ async fn wait_all_shards_active(
 
 /// Spawns a task which will retrieve data from appropriate local shards of the `source` collection
 /// into target collection.
+///
+/// If `filter` is specified, only matching points are transferred.
 pub async fn populate_collection(
     collections: Arc<RwLock<Collections>>,
     source_collection: &CollectionId,
     target_collection: &CollectionId,
+    filter: Option<&Filter>,
     this_peer_id: PeerId,
 ) -> CollectionResult<()> {
     let collections_read = collections.read().await;
@@ -175,6 +180,7 @@ pub async fn populate_collection(
             source_collection,
             target_collection,
             shard_id,
+            filter,
         )
         .await?;
     }
@@ -32,6 +32,10 @@ pub enum StorageError {
     Forbidden { description: String },
     #[error("Pre-condition failure: {description}")]
     PreconditionFailed { description: String }, // system is not in the state to perform the operation
+    #[error("Conflict: {description}")]
+    Conflict { description: String },
+    #[error("Quota exceeded: {description}")]
+    QuotaExceeded { description: String },
 }
 
 impl StorageError {
@@ -131,6 +135,12 @@ impl StorageError {
                 description: overriding_description,
                 backtrace: None,
             },
+            CollectionError::Conflict { .. } => StorageError::Conflict {
+                description: overriding_description,
+            },
+            CollectionError::QuotaExceeded { .. } => StorageError::QuotaExceeded {
+                description: overriding_description,
+            },
         }
     }
 }
@@ -179,6 +189,10 @@ impl From<CollectionError> for StorageError {
                 description: format!("{err}"),
                 backtrace: None,
             },
+            CollectionError::Conflict { description } => StorageError::Conflict { description },
+            CollectionError::QuotaExceeded { description } => {
+                StorageError::QuotaExceeded { description }
+            }
         }
     }
 }
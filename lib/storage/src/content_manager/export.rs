@@ -0,0 +1,440 @@
+use std::sync::Arc;
+
+use api::rest::{Record, Vector, VectorStruct};
+use arrow::array::{ArrayRef, Float32Builder, ListBuilder, StringBuilder};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use arrow::record_batch::RecordBatch;
+use collection::operations::consistency_params::ReadConsistency;
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::types::{Datatype, ScrollRequestInternal};
+use parquet::arrow::ArrowWriter;
+use schemars::JsonSchema;
+use segment::data_types::vectors::DEFAULT_VECTOR_NAME;
+use segment::types::{Filter, WithPayloadInterface, WithVector};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use validator::Validate;
+
+use crate::content_manager::errors::StorageError;
+use crate::content_manager::toc::TableOfContent;
+use crate::rbac::{Access, AccessRequirements};
+
+const DEFAULT_BATCH_SIZE: usize = 1000;
+
+/// Number of encoded batches waiting to be sent
+const READ_AHEAD_BATCHES: usize = 2;
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    /// One JSON object per line, in the same format as points returned by scroll
+    #[default]
+    Jsonl,
+    /// Columns `id`, one column per vector and a `payload` column with JSON strings.
+    /// Dense `float32`, `float16` and `uint8` vectors are lists of floats, other vectors are JSON
+    /// strings.
+    Parquet,
+}
+
+impl ExportFormat {
+    pub fn content_type(self) -> &'static str {
+        match self {
+            ExportFormat::Jsonl => "application/x-ndjson",
+            ExportFormat::Parquet => "application/vnd.apache.parquet",
+        }
+    }
+}
+
+/// Export points of a collection
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, Default)]
+pub struct ExportPoints {
+    /// Format of the export. Default: JSON Lines
+    #[serde(default)]
+    pub format: ExportFormat,
+    /// Export only points which satisfy this conditions. If not provided - all points.
+    #[validate]
+    pub filter: Option<Filter>,
+    /// Select which payload to export. Default: all
+    #[serde(default)]
+    pub with_payload: Option<WithPayloadInterface>,
+    /// Select which vectors to export. Default: all
+    #[serde(default)]
+    pub with_vector: Option<WithVector>,
+    /// Number of points read at once, also the size of Parquet row groups
+    #[serde(default)]
+    #[validate(range(min = 1, max = 65536))]
+    pub batch_size: Option<usize>,
+}
+
+/// Vector exported to a Parquet column
+#[derive(Debug)]
+struct VectorColumn {
+    name: String,
+    /// Dense vectors are lists of floats, others are serialized to JSON
+    dense: bool,
+}
+
+/// Columns of the exported Parquet file
+#[derive(Debug)]
+struct ParquetColumns {
+    schema: SchemaRef,
+    vectors: Vec<VectorColumn>,
+    with_payload: bool,
+}
+
+impl ParquetColumns {
+    fn new(vectors: Vec<VectorColumn>, with_payload: bool) -> Result<Self, StorageError> {
+        let mut fields = vec![Field::new("id", DataType::Utf8, false)];
+        for vector in &vectors {
+            let column = vector_column_name(&vector.name);
+            if column == "id" || (with_payload && column == "payload") {
+                return Err(StorageError::bad_input(format!(
+                    "Vector name {column} conflicts with the {column} column of the export",
+                )));
+            }
+            let data_type = if vector.dense {
+                DataType::List(Arc::new(Field::new("item", DataType::Float32, true)))
+            } else {
+                DataType::Utf8
+            };
+            fields.push(Field::new(column, data_type, true));
+        }
+        if with_payload {
+            fields.push(Field::new("payload", DataType::Utf8, true));
+        }
+
+        Ok(Self {
+            schema: Arc::new(Schema::new(fields)),
+            vectors,
+            with_payload,
+        })
+    }
+
+    fn record_batch(&self, records: &[Record]) -> Result<RecordBatch, StorageError> {
+        let mut columns: Vec<ArrayRef> = Vec::with_capacity(self.schema.fields().len());
+
+        let mut ids = StringBuilder::new();
+        for record in records {
+            ids.append_value(record.id.to_string());
+        }
+        columns.push(Arc::new(ids.finish()));
+
+        for vector in &self.vectors {
+            let vectors = records
+                .iter()
+                .map(|record| record_vector(record, &vector.name));
+            if vector.dense {
+                let mut builder = ListBuilder::new(Float32Builder::new());
+                for vector in vectors {
+                    match vector {
+                        Some(Vector::Dense(values)) => {
+                            builder.values().append_slice(&values);
+                            builder.append(true);
+                        }
+                        _ => builder.append(false),
+                    }
+                }
+                columns.push(Arc::new(builder.finish()));
+            } else {
+                let mut builder = StringBuilder::new();
+                for vector in vectors {
+                    builder.append_option(vector.map(|vector| to_json(&vector)).transpose()?);
+                }
+                columns.push(Arc::new(builder.finish()));
+            }
+        }
+
+        if self.with_payload {
+            let mut builder = StringBuilder::new();
+            for record in records {
+                builder.append_option(record.payload.as_ref().map(to_json).transpose()?);
+            }
+            columns.push(Arc::new(builder.finish()));
+        }
+
+        RecordBatch::try_new(self.schema.clone(), columns).map_err(export_error)
+    }
+}
+
+fn vector_column_name(vector_name: &str) -> &str {
+    if vector_name == DEFAULT_VECTOR_NAME {
+        "vector"
+    } else {
+        vector_name
+    }
+}
+
+fn record_vector(record: &Record, name: &str) -> Option<Vector> {
+    match record.vector.as_ref()? {
+        VectorStruct::Single(vector) => {
+            (name == DEFAULT_VECTOR_NAME).then(|| Vector::Dense(vector.clone()))
+        }
+        VectorStruct::Multi(vectors) => vectors.get(name).cloned(),
+    }
+}
+
+fn to_json(value: &impl Serialize) -> Result<String, StorageError> {
+    serde_json::to_string(value).map_err(export_error)
+}
+
+fn export_error(err: impl std::fmt::Display) -> StorageError {
+    StorageError::service_error(format!("Failed to export points: {err}"))
+}
+
+/// Encodes pages of points into the export format
+enum Encoder {
+    Jsonl,
+    Parquet {
+        columns: ParquetColumns,
+        writer: ArrowWriter<Vec<u8>>,
+    },
+}
+
+impl Encoder {
+    fn new(
+        format: ExportFormat,
+        columns: impl FnOnce() -> Result<ParquetColumns, StorageError>,
+    ) -> Result<Self, StorageError> {
+        match format {
+            ExportFormat::Jsonl => Ok(Encoder::Jsonl),
+            ExportFormat::Parquet => {
+                let columns = columns()?;
+                let writer = ArrowWriter::try_new(Vec::new(), columns.schema.clone(), None)
+                    .map_err(export_error)?;
+                Ok(Encoder::Parquet { columns, writer })
+            }
+        }
+    }
+
+    fn encode(&mut self, records: &[Record]) -> Result<Vec<u8>, StorageError> {
+        match self {
+            Encoder::Jsonl => {
+                let mut bytes = Vec::new();
+                for record in records {
+                    serde_json::to_writer(&mut bytes, record).map_err(export_error)?;
+                    bytes.push(b'\n');
+                }
+                Ok(bytes)
+            }
+            Encoder::Parquet { columns, writer } => {
+                writer
+                    .write(&columns.record_batch(records)?)
+                    .map_err(export_error)?;
+                // Every page is a row group, so it can be sent right away
+                writer.flush().map_err(export_error)?;
+                // The writer tracks its position itself, taking written bytes is fine
+                Ok(std::mem::take(writer.inner_mut()))
+            }
+        }
+    }
+
+    fn finish(self) -> Result<Vec<u8>, StorageError> {
+        match self {
+            Encoder::Jsonl => Ok(Vec::new()),
+            Encoder::Parquet { writer, .. } => writer.into_inner().map_err(export_error),
+        }
+    }
+}
+
+/// Export points of the collection, page by page
+///
+/// Returns a receiver of encoded chunks of the export, which are produced in the background.
+/// An error is sent in place of a chunk if the export fails midway.
+pub async fn export_points(
+    toc: Arc<TableOfContent>,
+    collection_name: String,
+    request: ExportPoints,
+    read_consistency: Option<ReadConsistency>,
+    access: Access,
+) -> Result<mpsc::Receiver<Result<Vec<u8>, StorageError>>, StorageError> {
+    let ExportPoints {
+        format,
+        filter,
+        with_payload,
+        with_vector,
+        batch_size,
+    } = request;
+
+    let with_payload = with_payload.unwrap_or(WithPayloadInterface::Bool(true));
+    let with_vector = with_vector.unwrap_or(WithVector::Bool(true));
+
+    let collection_pass =
+        access.check_collection_access(&collection_name, AccessRequirements::new())?;
+    let params = toc
+        .get_collection(&collection_pass)
+        .await?
+        .state()
+        .await
+        .config
+        .params;
+
+    let mut encoder = Encoder::new(format, || {
+        let dense = params
+            .vectors
+            .params_iter()
+            .map(|(name, params)| VectorColumn {
+                name: name.to_string(),
+                // Double precision vectors are kept in JSON, not to narrow them into the list
+                dense: params.multivec_config.is_none()
+                    && params.datatype != Some(Datatype::Float64),
+            });
+        let sparse = params
+            .sparse_vectors
+            .iter()
+            .flatten()
+            .map(|(name, _)| VectorColumn {
+                name: name.clone(),
+                dense: false,
+            });
+        let mut vectors: Vec<_> = dense
+            .chain(sparse)
+            .filter(|v| with_vector.is_selected(&v.name))
+            .collect();
+        vectors.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+        ParquetColumns::new(
+            vectors,
+            !matches!(with_payload, WithPayloadInterface::Bool(false)),
+        )
+    })?;
+
+    let mut scroll_request = ScrollRequestInternal {
+        offset: None,
+        limit: Some(batch_size.unwrap_or(DEFAULT_BATCH_SIZE)),
+        filter,
+        with_payload: Some(with_payload),
+        with_vector,
+        order_by: None,
+    };
+
+    let (sender, receiver) = mpsc::channel(READ_AHEAD_BATCHES);
+    tokio::spawn(async move {
+        let export = async {
+            loop {
+                let page = toc
+                    .scroll(
+                        &collection_name,
+                        scroll_request.clone(),
+                        read_consistency,
+                        ShardSelectorInternal::All,
+                        access.clone(),
+                    )
+                    .await?;
+
+                if !page.points.is_empty() {
+                    let chunk = encoder.encode(&page.points)?;
+                    if sender.send(Ok(chunk)).await.is_err() {
+                        // Nobody is waiting for the export anymore
+                        return Ok(None);
+                    }
+                }
+
+                match page.next_page_offset {
+                    Some(offset) => scroll_request.offset = Some(offset),
+                    None => break,
+                }
+            }
+            encoder.finish().map(Some)
+        };
+
+        let chunk = match export.await {
+            Ok(Some(chunk)) if chunk.is_empty() => return,
+            Ok(Some(chunk)) => Ok(chunk),
+            Ok(None) => return,
+            Err(err) => {
+                log::error!("Failed to export points of collection {collection_name}: {err}");
+                Err(err)
+            }
+        };
+        let _ = sender.send(chunk).await;
+    });
+
+    Ok(receiver)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use arrow::array::{Array, AsArray, Float32Array};
+    use arrow::datatypes::Float32Type;
+    use segment::types::{ExtendedPointId, Payload};
+
+    use super::*;
+
+    #[test]
+    fn test_records_to_parquet_batch() {
+        let records = vec![
+            Record {
+                id: ExtendedPointId::NumId(1),
+                payload: Some(Payload(
+                    serde_json::json!({"city": "Berlin"})
+                        .as_object()
+                        .unwrap()
+                        .clone(),
+                )),
+                vector: Some(VectorStruct::Multi(HashMap::from([
+                    ("image".to_string(), Vector::Dense(vec![1.0, 2.0])),
+                    (
+                        "text".to_string(),
+                        Vector::MultiDense(vec![vec![3.0], vec![4.0]]),
+                    ),
+                ]))),
+                shard_key: None,
+                version: None,
+            },
+            Record {
+                id: ExtendedPointId::NumId(2),
+                payload: None,
+                vector: Some(VectorStruct::Multi(HashMap::new())),
+                shard_key: None,
+                version: None,
+            },
+        ];
+
+        let columns = ParquetColumns::new(
+            vec![
+                VectorColumn {
+                    name: "image".to_string(),
+                    dense: true,
+                },
+                VectorColumn {
+                    name: "text".to_string(),
+                    dense: false,
+                },
+            ],
+            true,
+        )
+        .unwrap();
+        let batch = columns.record_batch(&records).unwrap();
+
+        assert_eq!(batch.num_rows(), 2);
+        let ids = batch.column_by_name("id").unwrap().as_string::<i32>();
+        assert_eq!(ids.value(0), "1");
+        assert_eq!(ids.value(1), "2");
+
+        let images = batch.column_by_name("image").unwrap().as_list::<i32>();
+        assert_eq!(
+            images.value(0).as_primitive::<Float32Type>(),
+            &Float32Array::from(vec![1.0, 2.0]),
+        );
+        assert!(images.is_null(1));
+
+        let texts = batch.column_by_name("text").unwrap().as_string::<i32>();
+        assert_eq!(texts.value(0), "[[3.0],[4.0]]");
+        assert!(texts.is_null(1));
+
+        let payloads = batch.column_by_name("payload").unwrap().as_string::<i32>();
+        assert_eq!(payloads.value(0), r#"{"city":"Berlin"}"#);
+        assert!(payloads.is_null(1));
+
+        // Vector names must not shadow other columns
+        let conflict = ParquetColumns::new(
+            vec![VectorColumn {
+                name: "id".to_string(),
+                dense: true,
+            }],
+            true,
+        );
+        assert!(conflict.is_err());
+    }
+}
@@ -0,0 +1,443 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use api::rest::{DenseVector, Vector, VectorStruct};
+use arrow::array::{Array, ArrayRef, AsArray};
+use arrow::compute::{cast, cast_with_options, CastOptions};
+use arrow::datatypes::{DataType, Field, Float32Type, Schema, UInt64Type};
+use arrow::record_batch::{RecordBatch, RecordBatchReader};
+use collection::operations::point_ops::{
+    PointInsertOperationsInternal, PointOperations, PointStruct, WriteOrdering,
+};
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::{CollectionUpdateOperations, OperationWithClockTag};
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use schemars::JsonSchema;
+use segment::data_types::vectors::DEFAULT_VECTOR_NAME;
+use segment::types::{ExtendedPointId, Payload, PointIdType};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use url::Url;
+use validator::Validate;
+
+use crate::content_manager::errors::StorageError;
+use crate::content_manager::snapshots::download::download_snapshot;
+use crate::content_manager::toc::TableOfContent;
+use crate::rbac::{Access, AccessRequirements};
+
+const DEFAULT_BATCH_SIZE: usize = 1000;
+
+/// Number of converted batches waiting to be upserted
+const READ_AHEAD_BATCHES: usize = 2;
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportFormat {
+    Parquet,
+    /// Arrow IPC file format, also known as Feather V2
+    ArrowIpc,
+}
+
+impl ImportFormat {
+    fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "parquet" => Some(ImportFormat::Parquet),
+            "arrow" | "feather" | "ipc" => Some(ImportFormat::ArrowIpc),
+            _ => None,
+        }
+    }
+}
+
+/// Import points from a columnar file
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+pub struct ImportPoints {
+    /// Location of the file: `file:///path/to/file`, HTTP(S) URL, `s3://bucket/key` or `gs://bucket/key`
+    pub location: Url,
+    /// Format of the file. If not set - inferred from the file extension
+    #[serde(default)]
+    pub format: Option<ImportFormat>,
+    /// Column with point ids, unsigned integers or strings with UUIDs or integers
+    #[serde(default = "default_id_column")]
+    pub id_column: String,
+    /// Columns with dense vectors, lists of numbers, by vector name.
+    /// Default is the `vector` column for the single unnamed vector.
+    #[serde(default)]
+    pub vector_columns: Option<HashMap<String, String>>,
+    /// Columns to store in the payload. If not set - all columns except id and vectors.
+    #[serde(default)]
+    pub payload_columns: Option<Vec<String>>,
+    /// Number of points upserted in one operation
+    #[serde(default)]
+    #[validate(range(min = 1, max = 65536))]
+    pub batch_size: Option<usize>,
+}
+
+fn default_id_column() -> String {
+    "id".to_string()
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ImportResult {
+    /// Number of imported points
+    pub points_count: usize,
+}
+
+/// Indices of the file columns, mapped to parts of the point
+#[derive(Debug)]
+struct ColumnMapping {
+    id: usize,
+    vectors: Vec<(String, usize)>,
+    payload: Vec<usize>,
+}
+
+impl ColumnMapping {
+    fn new(schema: &Schema, request: &ImportPoints) -> Result<Self, StorageError> {
+        let column_index = |name: &str| {
+            schema.index_of(name).map_err(|_| {
+                StorageError::bad_input(format!("Column {name} not found in the import file"))
+            })
+        };
+
+        let id = column_index(&request.id_column)?;
+
+        let default_vectors = HashMap::from([(DEFAULT_VECTOR_NAME.to_string(), "vector".into())]);
+        let mut vectors = request
+            .vector_columns
+            .as_ref()
+            .unwrap_or(&default_vectors)
+            .iter()
+            .map(|(name, column)| Ok((name.clone(), column_index(column)?)))
+            .collect::<Result<Vec<_>, StorageError>>()?;
+        vectors.sort_unstable();
+
+        let payload = match &request.payload_columns {
+            Some(columns) => columns
+                .iter()
+                .map(|column| column_index(column))
+                .collect::<Result<_, _>>()?,
+            None => (0..schema.fields().len())
+                .filter(|&index| index != id && vectors.iter().all(|(_, i)| *i != index))
+                .collect(),
+        };
+
+        Ok(Self {
+            id,
+            vectors,
+            payload,
+        })
+    }
+
+    fn points(&self, batch: &RecordBatch) -> Result<Vec<PointStruct>, StorageError> {
+        let ids = point_ids(batch.column(self.id))?;
+
+        let mut vectors = Vec::with_capacity(self.vectors.len());
+        for (name, index) in &self.vectors {
+            vectors.push((name, dense_vectors(batch.column(*index))?));
+        }
+
+        let payloads = if self.payload.is_empty() {
+            vec![None; batch.num_rows()]
+        } else {
+            payloads(&batch.project(&self.payload).map_err(import_error)?)?
+                .into_iter()
+                .map(Some)
+                .collect()
+        };
+
+        let mut points = Vec::with_capacity(batch.num_rows());
+        for (row, (id, payload)) in ids.into_iter().zip(payloads).enumerate() {
+            let vector = match vectors.as_slice() {
+                [(name, values)] if name.as_str() == DEFAULT_VECTOR_NAME => {
+                    VectorStruct::Single(values[row].clone().ok_or_else(|| {
+                        StorageError::bad_input(format!("Point {id} has no vector"))
+                    })?)
+                }
+                vectors => VectorStruct::Multi(
+                    vectors
+                        .iter()
+                        .filter_map(|(name, values)| {
+                            let vector = values[row].clone()?;
+                            Some((name.to_string(), Vector::Dense(vector)))
+                        })
+                        .collect(),
+                ),
+            };
+            points.push(PointStruct {
+                id,
+                vector,
+                payload,
+                expires_at: None,
+            });
+        }
+        Ok(points)
+    }
+}
+
+fn import_error(err: impl Display) -> StorageError {
+    StorageError::bad_input(format!("Failed to read import file: {err}"))
+}
+
+fn point_ids(column: &ArrayRef) -> Result<Vec<PointIdType>, StorageError> {
+    if column.null_count() > 0 {
+        return Err(StorageError::bad_input("Point ids must not be null"));
+    }
+
+    match column.data_type() {
+        DataType::Utf8 | DataType::LargeUtf8 => {
+            let column = cast(column, &DataType::Utf8).map_err(import_error)?;
+            column
+                .as_string::<i32>()
+                .iter()
+                .map(|id| {
+                    let id = id.unwrap_or_default();
+                    // Numbers in strings are accepted too, as in exported Parquet files
+                    ExtendedPointId::from_str(id).map_err(|_| {
+                        StorageError::bad_input(format!(
+                            "Point id {id} is not a valid UUID or unsigned integer"
+                        ))
+                    })
+                })
+                .collect()
+        }
+        data_type if data_type.is_integer() => {
+            // Negative ids fail the cast instead of turning into nulls
+            let options = CastOptions {
+                safe: false,
+                ..Default::default()
+            };
+            let column = cast_with_options(column, &DataType::UInt64, &options)
+                .map_err(|err| StorageError::bad_input(format!("Invalid point id: {err}")))?;
+            Ok(column
+                .as_primitive::<UInt64Type>()
+                .values()
+                .iter()
+                .map(|&id| ExtendedPointId::NumId(id))
+                .collect())
+        }
+        data_type => Err(StorageError::bad_input(format!(
+            "Point ids must be unsigned integers or strings, got {data_type}"
+        ))),
+    }
+}
+
+/// Dense vectors from a list column, `None` for null rows
+fn dense_vectors(column: &ArrayRef) -> Result<Vec<Option<DenseVector>>, StorageError> {
+    match column.data_type() {
+        DataType::List(_) | DataType::LargeList(_) | DataType::FixedSizeList(_, _) => {}
+        data_type => {
+            return Err(StorageError::bad_input(format!(
+                "Vectors must be lists of numbers, got {data_type}"
+            )))
+        }
+    }
+
+    let list_type = DataType::List(Arc::new(Field::new("item", DataType::Float32, true)));
+    let column = cast(column, &list_type).map_err(import_error)?;
+    let lists = column.as_list::<i32>();
+    Ok((0..lists.len())
+        .map(|row| {
+            lists.is_valid(row).then(|| {
+                lists
+                    .value(row)
+                    .as_primitive::<Float32Type>()
+                    .values()
+                    .to_vec()
+            })
+        })
+        .collect())
+}
+
+/// Convert rows to JSON objects, null values are omitted
+fn payloads(batch: &RecordBatch) -> Result<Vec<Payload>, StorageError> {
+    let mut writer = arrow::json::ArrayWriter::new(Vec::new());
+    writer.write(batch).map_err(import_error)?;
+    writer.finish().map_err(import_error)?;
+    let rows: Vec<serde_json::Map<String, serde_json::Value>> =
+        serde_json::from_slice(&writer.into_inner()).map_err(import_error)?;
+    Ok(rows.into_iter().map(Payload).collect())
+}
+
+/// Read the file in batches of points, sending them to `sender`
+///
+/// Blocking, stops early if the receiver is dropped.
+fn read_points(
+    path: &Path,
+    format: ImportFormat,
+    request: &ImportPoints,
+    sender: &mpsc::Sender<Result<Vec<PointStruct>, StorageError>>,
+) -> Result<(), StorageError> {
+    let batch_size = request.batch_size.unwrap_or(DEFAULT_BATCH_SIZE);
+    let file = File::open(path)?;
+
+    let batches: Box<dyn RecordBatchReader> = match format {
+        ImportFormat::Parquet => Box::new(
+            ParquetRecordBatchReaderBuilder::try_new(file)
+                .map_err(import_error)?
+                .with_batch_size(batch_size)
+                .build()
+                .map_err(import_error)?,
+        ),
+        ImportFormat::ArrowIpc => {
+            Box::new(arrow::ipc::reader::FileReader::try_new(file, None).map_err(import_error)?)
+        }
+    };
+
+    let mapping = ColumnMapping::new(&batches.schema(), request)?;
+    for batch in batches {
+        let batch = batch.map_err(import_error)?;
+
+        // Batches of Arrow IPC files are not limited by the batch size
+        for offset in (0..batch.num_rows()).step_by(batch_size) {
+            let length = batch_size.min(batch.num_rows() - offset);
+            let points = mapping.points(&batch.slice(offset, length))?;
+            if sender.blocking_send(Ok(points)).is_err() {
+                return Ok(());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Upsert points from a Parquet or Arrow IPC file into the collection
+///
+/// The file is converted to points directly, in batches, without JSON serialization of vectors.
+/// Reading files from the local file system requires manage access.
+pub async fn import_points(
+    toc: Arc<TableOfContent>,
+    collection_name: String,
+    request: ImportPoints,
+    client: &reqwest::Client,
+    ordering: WriteOrdering,
+    access: Access,
+) -> Result<ImportResult, StorageError> {
+    access.check_global_access(AccessRequirements::new().manage())?;
+
+    let format = match request.format {
+        Some(format) => format,
+        None => ImportFormat::from_path(Path::new(request.location.path())).ok_or_else(|| {
+            StorageError::bad_input(
+                "Can't infer the format of the import file from its extension, set `format`",
+            )
+        })?,
+    };
+
+    let download_dir = toc.snapshots_download_tempdir()?;
+    let (path, _temp_path): (PathBuf, _) = match request.location.scheme() {
+        "file" => {
+            let path = request.location.to_file_path().map_err(|_| {
+                StorageError::bad_request(
+                    "Invalid import file URI, file path must be absolute or on localhost",
+                )
+            })?;
+            if !path.exists() {
+                return Err(StorageError::bad_request(format!(
+                    "Import file {path:?} does not exist"
+                )));
+            }
+            (path, None)
+        }
+        _ => {
+            download_snapshot(
+                client,
+                toc.snapshots_config(),
+                request.location.clone(),
+                download_dir.path(),
+            )
+            .await?
+        }
+    };
+
+    log::debug!("Importing points into collection {collection_name} from {path:?}");
+
+    let (sender, mut receiver) = mpsc::channel(READ_AHEAD_BATCHES);
+    let reader = tokio::task::spawn_blocking(move || {
+        if let Err(err) = read_points(&path, format, &request, &sender) {
+            let _ = sender.blocking_send(Err(err));
+        }
+    });
+
+    let mut points_count = 0;
+    while let Some(points) = receiver.recv().await {
+        let points = points?;
+        points_count += points.len();
+
+        let operation = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+            PointInsertOperationsInternal::PointsList(points),
+        ));
+        // Wait for every batch, so the import doesn't overflow the update queues
+        toc.update(
+            &collection_name,
+            OperationWithClockTag::new(operation, None),
+            true,
+            ordering,
+            ShardSelectorInternal::Empty,
+            access.clone(),
+        )
+        .await?;
+    }
+
+    reader.await?;
+
+    log::debug!("Imported {points_count} points into collection {collection_name}");
+
+    Ok(ImportResult { points_count })
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::{FixedSizeListArray, Float32Array, StringArray, UInt64Array};
+
+    use super::*;
+
+    #[test]
+    fn test_record_batch_to_points() {
+        let vectors = FixedSizeListArray::try_new(
+            Arc::new(Field::new("item", DataType::Float32, true)),
+            2,
+            Arc::new(Float32Array::from(vec![1.0, 2.0, 3.0, 4.0])),
+            None,
+        )
+        .unwrap();
+        let batch = RecordBatch::try_from_iter([
+            ("id", Arc::new(UInt64Array::from(vec![1, 2])) as ArrayRef),
+            ("vector", Arc::new(vectors) as ArrayRef),
+            (
+                "city",
+                Arc::new(StringArray::from(vec![Some("Berlin"), None])) as ArrayRef,
+            ),
+        ])
+        .unwrap();
+
+        let request: ImportPoints =
+            serde_json::from_value(serde_json::json!({"location": "file:///points.parquet"}))
+                .unwrap();
+        let mapping = ColumnMapping::new(&batch.schema(), &request).unwrap();
+        let points = mapping.points(&batch).unwrap();
+
+        assert_eq!(points.len(), 2);
+        assert_eq!(points[0].id, ExtendedPointId::NumId(1));
+        assert_eq!(points[0].vector, VectorStruct::Single(vec![1.0, 2.0]));
+        assert_eq!(
+            points[0].payload,
+            Some(Payload(
+                serde_json::json!({"city": "Berlin"})
+                    .as_object()
+                    .unwrap()
+                    .clone()
+            )),
+        );
+        assert_eq!(points[1].vector, VectorStruct::Single(vec![3.0, 4.0]));
+        assert_eq!(points[1].payload, Some(Payload(Default::default())));
+
+        let request = ImportPoints {
+            id_column: "missing".to_string(),
+            ..request
+        };
+        assert!(ColumnMapping::new(&batch.schema(), &request).is_err());
+    }
+}
@@ -3,8 +3,11 @@ use collection::shards::shard::PeerId;
 use self::collection_meta_ops::CollectionMetaOperations;
 use self::consensus_manager::CollectionsSnapshot;
 use self::errors::StorageError;
+use crate::rbac::revocations::{CredentialRevocation, RevokedCredentials};
 
 pub mod alias_mapping;
+pub mod blob;
+pub mod cluster_events;
 pub mod collection_meta_ops;
 mod collections_ops;
 pub mod consensus;
@@ -12,6 +15,8 @@ pub mod consensus_manager;
 pub mod conversions;
 mod data_transfer;
 pub mod errors;
+pub mod export;
+pub mod import;
 pub mod shard_distribution;
 pub mod snapshots;
 pub mod toc;
@@ -30,6 +35,7 @@ pub mod consensus_ops {
         CollectionMetaOperations, SetShardReplicaState, ShardTransferOperations, UpdateCollection,
         UpdateCollectionOperation,
     };
+    use crate::rbac::revocations::CredentialRevocation;
 
     /// Operation that should pass consensus
     #[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Hash, Clone)]
@@ -44,6 +50,25 @@ pub mod consensus_ops {
             peer_id: PeerId,
             metadata: PeerMetadata,
         },
+        /// Learners are never promoted to voters
+        SetPeerLearner {
+            peer_id: PeerId,
+            learner: bool,
+        },
+        /// Set the key of the cluster metadata, or remove it if `value` is `None`
+        ///
+        /// If `expected_version` is set, the operation is applied only if it matches the current
+        /// version of the key. Version `0` expects the key to not exist.
+        SetClusterMetadata {
+            key: String,
+            value: Option<String>,
+            expected_version: Option<u64>,
+        },
+        /// Revoke an API key or JWT by its identifier, or restore it if `revocation` is `None`
+        SetCredentialRevocation {
+            credential_id: String,
+            revocation: Option<CredentialRevocation>,
+        },
         RequestSnapshot,
         ReportSnapshot {
             peer_id: PeerId,
@@ -114,6 +139,12 @@ pub mod consensus_ops {
                     hnsw_config: None,
                     quantization_config: None,
                     sparse_vectors: None,
+                    tiering_config: None,
+                    ttl_config: None,
+                    async_replication_config: None,
+                    inference_config: None,
+                    quota_config: None,
+                    shard_pinning: None,
                 },
             );
             operation
@@ -198,4 +229,17 @@ pub trait CollectionContainer {
     fn remove_peer(&self, peer_id: PeerId) -> Result<(), StorageError>;
 
     fn sync_local_state(&self) -> Result<(), StorageError>;
+
+    /// Whether this peer is in read-only mode
+    fn is_read_only(&self) -> bool;
+
+    fn set_credential_revocation(
+        &self,
+        credential_id: String,
+        revocation: Option<CredentialRevocation>,
+    ) -> Result<bool, StorageError>;
+
+    fn revoked_credentials(&self) -> RevokedCredentials;
+
+    fn apply_revocations_snapshot(&self, revoked: RevokedCredentials) -> Result<(), StorageError>;
 }
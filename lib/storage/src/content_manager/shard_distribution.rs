@@ -1,8 +1,8 @@
-use std::cmp::{self, Reverse};
-use std::collections::BinaryHeap;
-use std::iter::repeat_with;
+use std::cmp;
+use std::collections::{HashMap, HashSet};
 use std::num::NonZeroU32;
 
+use collection::config::{pinned_peers, ShardPin};
 use collection::shards::collection_shard_distribution::CollectionShardDistribution;
 use collection::shards::shard::{PeerId, ShardId};
 use schemars::JsonSchema;
@@ -73,27 +73,63 @@ impl ShardDistributionProposal {
 
     /// Builds a proposal for the distribution of shards.
     /// It will propose to allocate shards so that all peers have the same number of shards of this collection  at the end.
+    ///
+    /// Replicas of a shard are placed in different zones of `peer_zones`, as long as there are
+    /// enough zones. Peers without a zone are considered to be in a zone of their own.
+    ///
+    /// Replicas of pinned shards are only placed on known peers they are pinned to.
     pub fn new(
         shard_number: NonZeroU32,
         replication_factor: NonZeroU32,
         known_peers: &[PeerId],
+        peer_zones: &HashMap<PeerId, String>,
+        shard_pinning: Option<&[ShardPin]>,
     ) -> Self {
-        // Min-heap: peer with lowest number of shards is on top
-        let mut min_heap: BinaryHeap<_> = known_peers
+        let mut peers: Vec<_> = known_peers
             .iter()
-            .map(|peer| Reverse(PeerShardCount::new(*peer)))
+            .map(|peer| PeerShardCount::new(*peer))
             .collect();
 
-        // There should not be more than 1 replica per peer
-        let replica_number = cmp::min(replication_factor.get() as usize, known_peers.len());
-
         // Get fair distribution of shards on peers
         let distribution = (0..shard_number.get())
             .map(|shard_id| {
-                let replicas =
-                    repeat_with(|| min_heap.peek_mut().unwrap().0.get_and_inc_shard_count())
-                        .take(replica_number)
-                        .collect();
+                let pinned = pinned_peers(shard_pinning, shard_id, None);
+                let is_allowed =
+                    |peer_id: PeerId| pinned.map_or(true, |pinned| pinned.contains(&peer_id));
+                let allowed_peers = known_peers
+                    .iter()
+                    .filter(|peer_id| is_allowed(**peer_id))
+                    .count();
+
+                // There should not be more than 1 replica per peer
+                let replica_number = cmp::min(replication_factor.get() as usize, allowed_peers);
+
+                let mut replicas = Vec::with_capacity(replica_number);
+                let mut used_zones = HashSet::new();
+                for _ in 0..replica_number {
+                    let in_used_zone = |peer_id: PeerId| {
+                        peer_zones
+                            .get(&peer_id)
+                            .is_some_and(|zone| used_zones.contains(zone))
+                    };
+                    // Peer with the lowest number of shards, preferably in a new zone
+                    let peer = peers
+                        .iter_mut()
+                        .filter(|peer| {
+                            is_allowed(peer.peer_id) && !replicas.contains(&peer.peer_id)
+                        })
+                        .min_by(|a, b| {
+                            in_used_zone(a.peer_id)
+                                .cmp(&in_used_zone(b.peer_id))
+                                .then_with(|| a.cmp(b))
+                        })
+                        .expect("replica number does not exceed number of peers");
+                    let peer_id = peer.get_and_inc_shard_count();
+                    replicas.push(peer_id);
+                    if let Some(zone) = peer_zones.get(&peer_id) {
+                        used_zones.insert(zone);
+                    }
+                }
                 (shard_id, replicas)
             })
             .collect();
@@ -149,6 +185,8 @@ mod tests {
             NonZeroU32::new(6).unwrap(),
             NonZeroU32::new(1).unwrap(),
             &known_peers,
+            &HashMap::new(),
+            None,
         );
 
         // Check it distribution is as even as possible
@@ -188,6 +226,8 @@ mod tests {
                             NonZeroU32::new(shard_number).unwrap(),
                             NonZeroU32::new(replication_factor).unwrap(),
                             &known_peers,
+                            &HashMap::new(),
+                            None,
                         )
                     })
                     // Take just the inhabited peer IDs
@@ -209,4 +249,81 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_distribution_across_zones() {
+        let known_peers = vec![1, 2, 3, 4, 5, 6];
+        let peer_zones = HashMap::from([
+            (1, "a".to_string()),
+            (2, "a".to_string()),
+            (3, "b".to_string()),
+            (4, "b".to_string()),
+            (5, "c".to_string()),
+            (6, "c".to_string()),
+        ]);
+
+        for _ in 0..100 {
+            let distribution = ShardDistributionProposal::new(
+                NonZeroU32::new(4).unwrap(),
+                NonZeroU32::new(3).unwrap(),
+                &known_peers,
+                &peer_zones,
+                None,
+            );
+
+            for (shard_id, peers) in &distribution.distribution {
+                let zones: HashSet<_> = peers.iter().map(|peer| &peer_zones[peer]).collect();
+                assert_eq!(zones.len(), 3, "replicas of shard {shard_id} share a zone");
+            }
+        }
+
+        // Not enough zones, but replicas are still placed on different peers
+        let distribution = ShardDistributionProposal::new(
+            NonZeroU32::new(2).unwrap(),
+            NonZeroU32::new(3).unwrap(),
+            &[1, 2, 3],
+            &HashMap::from([(1, "a".to_string()), (2, "a".to_string())]),
+            None,
+        );
+        for (_shard_id, peers) in &distribution.distribution {
+            assert_eq!(peers.iter().collect::<HashSet<_>>().len(), 3);
+        }
+    }
+
+    #[test]
+    fn test_distribution_with_pinning() {
+        let known_peers = vec![1, 2, 3, 4];
+        let shard_pinning = vec![
+            ShardPin {
+                shard_id: Some(0),
+                shard_key: None,
+                peers: vec![1, 2],
+            },
+            // Pinned peers, which are not known, are ignored
+            ShardPin {
+                shard_id: Some(1),
+                shard_key: None,
+                peers: vec![3, 5],
+            },
+        ];
+
+        for _ in 0..100 {
+            let distribution = ShardDistributionProposal::new(
+                NonZeroU32::new(3).unwrap(),
+                NonZeroU32::new(3).unwrap(),
+                &known_peers,
+                &HashMap::new(),
+                Some(&shard_pinning),
+            );
+
+            let replicas: HashMap<_, HashSet<_>> = distribution
+                .distribution
+                .into_iter()
+                .map(|(shard_id, peers)| (shard_id, peers.into_iter().collect()))
+                .collect();
+            assert_eq!(replicas[&0], HashSet::from([1, 2]));
+            assert_eq!(replicas[&1], HashSet::from([3]));
+            assert_eq!(replicas[&2].len(), 3);
+        }
+    }
 }
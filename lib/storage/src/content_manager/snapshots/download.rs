@@ -1,5 +1,7 @@
 use std::path::{Path, PathBuf};
 
+use collection::common::snapshots_manager::SnapShotsConfig;
+use collection::operations::snapshot_storage_ops;
 use futures::StreamExt;
 use reqwest;
 use tempfile::TempPath;
@@ -58,13 +60,32 @@ async fn download_file(
     Ok(temp_path)
 }
 
+/// Download an object from the bucket of `url` (`s3://bucket/key` or `gs://bucket/key`) to `path`
+///
+/// Returns a `TempPath` that will delete the downloaded file once it is dropped.
+#[must_use = "returns a TempPath, if dropped the downloaded file is deleted"]
+async fn download_object(
+    snapshots_config: &SnapShotsConfig,
+    url: &Url,
+    path: &Path,
+) -> Result<TempPath, StorageError> {
+    let bucket_client = snapshots_config.bucket_client(url)?;
+    let temp_path = TempPath::from_path(path);
+    let key = Path::new(url.path().trim_start_matches('/'));
+    snapshot_storage_ops::download_snapshot(bucket_client.as_ref(), key, path).await?;
+    Ok(temp_path)
+}
+
 /// Download a snapshot from the given URI.
 ///
+/// Object storage URLs are accessed with credentials of the configured snapshots storage.
+///
 /// May returen a `TempPath` if a file was downloaded from a remote source. If it is dropped the
 /// downloaded file is deleted automatically. To keep the file `keep()` may be used.
 #[must_use = "may return a TempPath, if dropped the downloaded file is deleted"]
 pub async fn download_snapshot(
     client: &reqwest::Client,
+    snapshots_config: &SnapShotsConfig,
     url: Url,
     snapshots_dir: &Path,
 ) -> Result<(PathBuf, Option<TempPath>), StorageError> {
@@ -88,6 +109,12 @@ pub async fn download_snapshot(
             let temp_path = download_file(client, &url, &download_to).await?;
             Ok((download_to, Some(temp_path)))
         }
+        "s3" | "gs" => {
+            let download_to = snapshots_dir.join(snapshot_name(&url));
+
+            let temp_path = download_object(snapshots_config, &url, &download_to).await?;
+            Ok((download_to, Some(temp_path)))
+        }
         _ => Err(StorageError::bad_request(format!(
             "URL {} with schema {} is not supported",
             url,
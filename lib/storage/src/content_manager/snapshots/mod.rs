@@ -13,7 +13,7 @@ use tokio::task::JoinHandle;
 
 use crate::content_manager::toc::FULL_SNAPSHOT_FILE_NAME;
 use crate::dispatcher::Dispatcher;
-use crate::rbac::{Access, AccessRequirements};
+use crate::rbac::{Access, AccessRequirements, SnapshotAction};
 use crate::{StorageError, TableOfContent};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -30,7 +30,11 @@ pub async fn do_delete_full_snapshot(
     access: Access,
     snapshot_name: &str,
 ) -> Result<JoinHandle<Result<bool, StorageError>>, StorageError> {
-    access.check_global_access(AccessRequirements::new().manage())?;
+    access.check_global_access(
+        AccessRequirements::new()
+            .manage()
+            .snapshot(SnapshotAction::Create),
+    )?;
     let toc = dispatcher.toc(&access);
     let snapshot_manager = toc.get_snapshots_storage_manager()?;
     let snapshot_dir = snapshot_manager
@@ -48,8 +52,12 @@ pub async fn do_delete_collection_snapshot(
     collection_name: &str,
     snapshot_name: &str,
 ) -> Result<JoinHandle<Result<bool, StorageError>>, StorageError> {
-    let collection_pass = access
-        .check_collection_access(collection_name, AccessRequirements::new().write().whole())?;
+    let collection_pass = access.check_collection_access(
+        collection_name,
+        AccessRequirements::new()
+            .whole()
+            .snapshot(SnapshotAction::Create),
+    )?;
     let toc = dispatcher.toc(&access);
     let snapshot_name = snapshot_name.to_string();
     let collection = toc.get_collection(&collection_pass).await?;
@@ -68,7 +76,7 @@ pub async fn do_list_full_snapshots(
     toc: &TableOfContent,
     access: Access,
 ) -> Result<Vec<SnapshotDescription>, StorageError> {
-    access.check_global_access(AccessRequirements::new())?;
+    access.check_global_access(AccessRequirements::new().snapshot(SnapshotAction::List))?;
     let snapshots_manager = toc.get_snapshots_storage_manager()?;
     let snapshots_path = Path::new(toc.snapshots_path());
     Ok(snapshots_manager.list_snapshots(snapshots_path).await?)
@@ -78,7 +86,11 @@ pub fn do_create_full_snapshot(
     dispatcher: &Dispatcher,
     access: Access,
 ) -> Result<JoinHandle<Result<SnapshotDescription, StorageError>>, StorageError> {
-    access.check_global_access(AccessRequirements::new().manage())?;
+    access.check_global_access(
+        AccessRequirements::new()
+            .manage()
+            .snapshot(SnapshotAction::Create),
+    )?;
     let toc = dispatcher.toc(&access).clone();
     Ok(tokio::spawn(async move {
         _do_create_full_snapshot(&toc, access).await
@@ -94,7 +106,7 @@ async fn _do_create_full_snapshot(
     let all_collections = toc.all_collections(&access).await;
     let mut created_snapshots: Vec<(&str, SnapshotDescription)> = vec![];
     for collection_pass in &all_collections {
-        let snapshot_details = toc.create_snapshot(collection_pass).await?;
+        let snapshot_details = toc.create_snapshot(collection_pass, None).await?;
         created_snapshots.push((collection_pass.name(), snapshot_details));
     }
     let current_time = chrono::Utc::now().format("%Y-%m-%d-%H-%M-%S").to_string();
@@ -1,7 +1,15 @@
+use std::path::{Path, PathBuf};
+
 use collection::collection::Collection;
 use collection::common::sha_256::{hash_file, hashes_equal};
+use collection::common::snapshot_encryption::decrypt_snapshot;
 use collection::config::CollectionConfig;
-use collection::operations::snapshot_ops::{SnapshotPriority, SnapshotRecover};
+use collection::operations::snapshot_ops::{
+    SnapshotPriority, SnapshotRecover, SnapshotRecoveryStage, SnapshotRecoveryStatus,
+};
+use collection::shards::local_shard::point_in_time::{replay_archived_wal, WalReplayProgress};
+use collection::shards::local_shard::wal_archive::shard_archive_prefix;
+use collection::shards::local_shard::LocalShard;
 use collection::shards::replica_set::ReplicaState;
 use collection::shards::shard::{PeerId, ShardId};
 use collection::shards::shard_config::ShardType;
@@ -13,7 +21,7 @@ use crate::content_manager::collection_meta_ops::{
 };
 use crate::content_manager::snapshots::download::download_snapshot;
 use crate::dispatcher::Dispatcher;
-use crate::rbac::{Access, AccessRequirements, CollectionPass};
+use crate::rbac::{Access, AccessRequirements, CollectionPass, SnapshotAction};
 use crate::{StorageError, TableOfContent};
 
 pub async fn activate_shard(
@@ -55,7 +63,11 @@ pub fn do_recover_from_snapshot(
     access: Access,
     client: reqwest::Client,
 ) -> Result<JoinHandle<Result<bool, StorageError>>, StorageError> {
-    let multipass = access.check_global_access(AccessRequirements::new().manage())?;
+    let multipass = access.check_global_access(
+        AccessRequirements::new()
+            .manage()
+            .snapshot(SnapshotAction::Restore),
+    )?;
 
     let dispatcher = dispatcher.clone();
     let collection_pass = multipass.issue_pass(collection_name).into_static();
@@ -64,20 +76,74 @@ pub fn do_recover_from_snapshot(
     }))
 }
 
+/// Path of the local shard data in the unpacked collection snapshot, if the shard is there
+async fn snapshot_local_shard_path(
+    snapshot_collection_path: &Path,
+    shard_id: ShardId,
+) -> Result<Option<PathBuf>, StorageError> {
+    let shards = latest_shard_paths(snapshot_collection_path, shard_id).await?;
+
+    Ok(shards
+        .into_iter()
+        .filter_map(
+            |(snapshot_shard_path, _version, shard_type)| match shard_type {
+                ShardType::Local => Some(snapshot_shard_path),
+                ShardType::ReplicaSet => Some(snapshot_shard_path),
+                ShardType::Remote { .. } => None,
+                ShardType::Temporary => None,
+            },
+        )
+        .next())
+}
+
 async fn _do_recover_from_snapshot(
     dispatcher: Dispatcher,
     access: Access,
     collection_pass: CollectionPass<'static>,
     source: SnapshotRecover,
     client: &reqwest::Client,
+) -> Result<bool, StorageError> {
+    let toc = dispatcher.toc(&access).clone();
+    let collection_name = collection_pass.to_string();
+
+    toc.update_snapshot_recovery_status(&collection_name, |status| {
+        *status = SnapshotRecoveryStatus::started();
+    });
+
+    let result =
+        recover_collection_from_snapshot(dispatcher, access, collection_pass, source, client).await;
+
+    toc.update_snapshot_recovery_status(&collection_name, |status| {
+        status.finished_at = Some(chrono::Utc::now());
+        match &result {
+            Ok(_) => status.stage = SnapshotRecoveryStage::Finished,
+            Err(err) => {
+                status.stage = SnapshotRecoveryStage::Failed;
+                status.error = Some(err.to_string());
+            }
+        }
+    });
+
+    result
+}
+
+async fn recover_collection_from_snapshot(
+    dispatcher: Dispatcher,
+    access: Access,
+    collection_pass: CollectionPass<'static>,
+    source: SnapshotRecover,
+    client: &reqwest::Client,
 ) -> Result<bool, StorageError> {
     let SnapshotRecover {
         location,
         priority,
         checksum,
         api_key: _,
+        encryption_key,
+        point_in_time,
     } = source;
     let toc = dispatcher.toc(&access);
+    let collection_name = collection_pass.to_string();
 
     let this_peer_id = toc.this_peer_id;
 
@@ -90,8 +156,13 @@ async fn _do_recover_from_snapshot(
         download_dir.path().display(),
     );
 
-    let (snapshot_path, snapshot_temp_path) =
-        download_snapshot(client, location, download_dir.path()).await?;
+    let (snapshot_path, snapshot_temp_path) = download_snapshot(
+        client,
+        toc.snapshots_config(),
+        location,
+        download_dir.path(),
+    )
+    .await?;
 
     if let Some(checksum) = checksum {
         let snapshot_checksum = hash_file(&snapshot_path).await?;
@@ -104,6 +175,10 @@ async fn _do_recover_from_snapshot(
 
     log::debug!("Snapshot downloaded to {}", snapshot_path.display());
 
+    toc.update_snapshot_recovery_status(&collection_name, |status| {
+        status.stage = SnapshotRecoveryStage::Unpacking;
+    });
+
     let temp_storage_path = toc.optional_temp_or_storage_temp_path()?;
 
     let tmp_collection_dir = tempfile::Builder::new()
@@ -120,8 +195,18 @@ async fn _do_recover_from_snapshot(
         tmp_collection_dir.path().display(),
     );
 
+    let cipher = toc
+        .snapshots_config()
+        .encryption
+        .cipher(encryption_key.as_deref())?;
+
     let tmp_collection_dir_clone = tmp_collection_dir.path().to_path_buf();
+    let download_dir_path = download_dir.path().to_path_buf();
     let restoring = tokio::task::spawn_blocking(move || {
+        // Decrypt snapshot next to the downloaded one, decrypted file is deleted on drop
+        let (snapshot_path, _decrypted_snapshot) =
+            decrypt_snapshot(&snapshot_path, cipher.as_deref(), &download_dir_path)?;
+
         // Unpack snapshot collection to the target folder
         Collection::restore_snapshot(
             &snapshot_path,
@@ -169,6 +254,67 @@ async fn _do_recover_from_snapshot(
         )));
     }
 
+    // Replay operations, made after the snapshot, before touching the local shards
+    if let Some(point_in_time) = point_in_time {
+        toc.update_snapshot_recovery_status(&collection_name, |status| {
+            status.stage = SnapshotRecoveryStage::ReplayingWal;
+        });
+
+        let archive_config = toc.wal_archive_config();
+        let cipher = toc.wal_cipher_for(&collection_name)?;
+        let archive_peer_id = point_in_time.peer_id.unwrap_or(this_peer_id);
+        let target = point_in_time.target();
+
+        let mut replayed = WalReplayProgress::default();
+        for shard_id in state.shards.keys() {
+            let Some(snapshot_shard_path) =
+                snapshot_local_shard_path(tmp_collection_dir.path(), *shard_id).await?
+            else {
+                continue;
+            };
+
+            let key_prefix = shard_archive_prefix(
+                &collection_name,
+                &shard_id.to_string(),
+                Some(archive_peer_id),
+            );
+            let shard_progress = replay_archived_wal(
+                &archive_config,
+                &key_prefix,
+                &LocalShard::wal_path(&snapshot_shard_path),
+                (&snapshot_config.wal_config).into(),
+                cipher.clone(),
+                target,
+                &temp_storage_path,
+                |progress| {
+                    toc.update_snapshot_recovery_status(&collection_name, |status| {
+                        status.wal_segments_total =
+                            replayed.segments_total + progress.segments_total;
+                        status.wal_segments_downloaded =
+                            replayed.segments_downloaded + progress.segments_downloaded;
+                        status.wal_operations_replayed =
+                            replayed.operations_replayed + progress.operations_replayed;
+                    });
+                },
+            )
+            .await?;
+
+            log::debug!(
+                "Replayed {} operations of shard {shard_id} from WAL archive {key_prefix}",
+                shard_progress.operations_replayed,
+            );
+
+            replayed.segments_total += shard_progress.segments_total;
+            replayed.segments_downloaded += shard_progress.segments_downloaded;
+            replayed.operations_replayed += shard_progress.operations_replayed;
+        }
+    }
+
+    toc.update_snapshot_recovery_status(&collection_name, |status| {
+        status.stage = SnapshotRecoveryStage::RecoveringShards;
+        status.shards_total = state.shards.len();
+    });
+
     // Deactivate collection local shards during recovery
     for (shard_id, shard_info) in &state.shards {
         let local_shard_state = shard_info.replicas.get(&this_peer_id);
@@ -192,19 +338,8 @@ async fn _do_recover_from_snapshot(
 
     // Recover shards from the snapshot
     for (shard_id, shard_info) in &state.shards {
-        let shards = latest_shard_paths(tmp_collection_dir.path(), *shard_id).await?;
-
-        let snapshot_shard_path = shards
-            .into_iter()
-            .filter_map(
-                |(snapshot_shard_path, _version, shard_type)| match shard_type {
-                    ShardType::Local => Some(snapshot_shard_path),
-                    ShardType::ReplicaSet => Some(snapshot_shard_path),
-                    ShardType::Remote { .. } => None,
-                    ShardType::Temporary => None,
-                },
-            )
-            .next();
+        let snapshot_shard_path =
+            snapshot_local_shard_path(tmp_collection_dir.path(), *shard_id).await?;
 
         if let Some(snapshot_shard_path) = snapshot_shard_path {
             log::debug!(
@@ -231,6 +366,10 @@ async fn _do_recover_from_snapshot(
                 continue;
             }
 
+            toc.update_snapshot_recovery_status(&collection_name, |status| {
+                status.shards_recovered += 1;
+            });
+
             // If this is the only replica, we can activate it
             // If not - de-sync is possible, so we need to run synchronization
             let other_active_replicas: Vec<_> = shard_info
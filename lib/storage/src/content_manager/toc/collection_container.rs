@@ -15,6 +15,7 @@ use crate::content_manager::consensus::operation_sender::OperationSender;
 use crate::content_manager::consensus_ops::ConsensusOperations;
 use crate::content_manager::errors::StorageError;
 use crate::content_manager::{consensus_manager, CollectionContainer};
+use crate::rbac::revocations::{CredentialRevocation, RevokedCredentials};
 
 impl CollectionContainer for TableOfContent {
     fn perform_collection_meta_op(
@@ -66,6 +67,26 @@ impl CollectionContainer for TableOfContent {
         })
     }
 
+    fn is_read_only(&self) -> bool {
+        TableOfContent::is_read_only(self)
+    }
+
+    fn set_credential_revocation(
+        &self,
+        credential_id: String,
+        revocation: Option<CredentialRevocation>,
+    ) -> Result<bool, StorageError> {
+        TableOfContent::set_credential_revocation(self, credential_id, revocation)
+    }
+
+    fn revoked_credentials(&self) -> RevokedCredentials {
+        TableOfContent::revoked_credentials(self)
+    }
+
+    fn apply_revocations_snapshot(&self, revoked: RevokedCredentials) -> Result<(), StorageError> {
+        TableOfContent::apply_revocations_snapshot(self, revoked)
+    }
+
     fn sync_local_state(&self) -> Result<(), StorageError> {
         self.general_runtime.block_on(async {
             let collections = self.collections.read().await;
@@ -148,7 +169,7 @@ impl TableOfContent {
                         &snapshots_path,
                         &state.config,
                         self.storage_config
-                            .to_shared_storage_config(self.is_distributed())
+                            .to_shared_storage_config(self.is_distributed(), self.this_peer_id)
                             .into(),
                         shard_distribution,
                         self.channel_service.clone(),
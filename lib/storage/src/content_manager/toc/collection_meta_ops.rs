@@ -1,6 +1,8 @@
 use std::collections::HashSet;
 use std::path::Path;
 
+use collection::collection::event_log::CollectionEventKind;
+use collection::collection::Collection;
 use collection::collection_state;
 use collection::config::ShardingMethod;
 use collection::events::{CollectionDeletedEvent, IndexCreatedEvent};
@@ -62,6 +64,14 @@ impl TableOfContent {
                 log::info!("Deleting collection {}", operation.0);
                 self.delete_collection(&operation.0).await
             }
+            CollectionMetaOperations::RenameCollection(operation) => {
+                log::info!(
+                    "Renaming collection {} to {}",
+                    operation.collection_name,
+                    operation.rename_collection.new_collection_name,
+                );
+                self.rename_collection(operation).await
+            }
             CollectionMetaOperations::ChangeAliases(operation) => {
                 log::debug!("Changing aliases");
                 self.update_aliases(operation).await
@@ -113,6 +123,7 @@ impl TableOfContent {
         mut operation: UpdateCollectionOperation,
     ) -> Result<bool, StorageError> {
         let replica_changes = operation.take_shard_replica_changes();
+        let update = serde_json::to_value(&operation.update_collection).unwrap_or_default();
         let UpdateCollection {
             vectors,
             hnsw_config,
@@ -120,6 +131,12 @@ impl TableOfContent {
             optimizers_config,
             quantization_config,
             sparse_vectors,
+            tiering_config,
+            ttl_config,
+            async_replication_config,
+            inference_config,
+            quota_config,
+            shard_pinning,
         } = operation.update_collection;
         let collection = self
             .get_collection_unchecked(&operation.collection_name)
@@ -152,6 +169,26 @@ impl TableOfContent {
             collection.update_sparse_vectors_from_other(&diff).await?;
             recreate_optimizers = true;
         }
+        if let Some(tiering_config) = tiering_config {
+            collection.update_tiering_config(tiering_config).await?;
+        }
+        if let Some(ttl_config) = ttl_config {
+            collection.update_ttl_config(ttl_config).await?;
+        }
+        if let Some(async_replication_config) = async_replication_config {
+            collection
+                .update_async_replication_config(async_replication_config)
+                .await?;
+        }
+        if let Some(inference_config) = inference_config {
+            collection.update_inference_config(inference_config).await?;
+        }
+        if let Some(quota_config) = quota_config {
+            collection.update_quota_config(quota_config).await?;
+        }
+        if let Some(shard_pinning) = shard_pinning {
+            collection.update_shard_pinning(shard_pinning).await?;
+        }
         if let Some(changes) = replica_changes {
             collection.handle_replica_changes(changes).await?;
         }
@@ -160,6 +197,9 @@ impl TableOfContent {
         if recreate_optimizers {
             collection.recreate_optimizers_blocking().await?;
         }
+
+        collection.record_event(CollectionEventKind::ConfigChanged { update });
+
         Ok(true)
     }
 
@@ -220,7 +260,123 @@ impl TableOfContent {
         }
     }
 
+    /// Change name of the collection and move its aliases to the new name
+    ///
+    /// The collection is unloaded, its files are moved to the directory of the new name and the
+    /// collection is loaded again. This way all internal references use the new name.
+    async fn rename_collection(
+        &self,
+        operation: RenameCollectionOperation,
+    ) -> Result<bool, StorageError> {
+        let RenameCollectionOperation {
+            collection_name,
+            rename_collection: RenameCollection {
+                new_collection_name,
+            },
+        } = operation;
+
+        let _collection_create_guard = self.collection_create_lock.lock().await;
+        let mut collections = self.collections.write().await;
+        let mut alias_lock = self.alias_persistence.write().await;
+
+        collections
+            .validate_collection_not_exists(&new_collection_name)
+            .await?;
+        if alias_lock.check_alias_exists(&new_collection_name) {
+            return Err(StorageError::bad_input(format!(
+                "Can't rename collection to {new_collection_name}. Alias with the same name already exists",
+            )));
+        }
+
+        let Some(collection) = collections.get(&collection_name) else {
+            return Err(StorageError::NotFound {
+                description: format!("Collection `{collection_name}` doesn't exist!"),
+            });
+        };
+
+        let state = collection.state().await;
+        if !state.transfers.is_empty() || state.resharding.is_some() {
+            return Err(StorageError::bad_request(format!(
+                "Can't rename collection {collection_name} while shard transfers or resharding are in progress",
+            )));
+        }
+
+        // Stop all shards before moving their files
+        drop(collections.remove(&collection_name));
+
+        let path = self.get_collection_path(&collection_name);
+        let new_path = self.get_collection_path(&new_collection_name);
+        if let Err(err) = tokio::fs::rename(&path, &new_path).await {
+            // Bring the collection back under the old name
+            let collection = self.load_collection(&collection_name, &path).await?;
+            collections.insert(collection_name, collection);
+            return Err(err.into());
+        }
+
+        let snapshots_path = self.snapshots_path_for_collection(&collection_name);
+        if snapshots_path.exists() {
+            let new_snapshots_path = self.snapshots_path_for_collection(&new_collection_name);
+            if let Err(err) = tokio::fs::rename(&snapshots_path, &new_snapshots_path).await {
+                log::warn!("Can't move snapshots of collection {collection_name}: {err}");
+            }
+        }
+
+        let collection = self
+            .load_collection(&new_collection_name, &new_path)
+            .await?;
+        collections.insert(new_collection_name.clone(), collection);
+
+        alias_lock.rename_collection(&collection_name, &new_collection_name)?;
+
+        // Issues of the old name can't be solved anymore
+        issues::publish(CollectionDeletedEvent {
+            collection_id: collection_name,
+        });
+
+        Ok(true)
+    }
+
+    /// Load collection, which is stored on disk in `collection_path`
+    async fn load_collection(
+        &self,
+        collection_name: &str,
+        collection_path: &Path,
+    ) -> Result<Collection, StorageError> {
+        let snapshots_path = self.create_snapshots_path(collection_name).await?;
+        let collection = Collection::load(
+            collection_name.to_string(),
+            self.this_peer_id,
+            collection_path,
+            &snapshots_path,
+            self.storage_config
+                .to_shared_storage_config(self.is_distributed(), self.this_peer_id)
+                .into(),
+            self.channel_service.clone(),
+            Self::change_peer_state_callback(
+                self.consensus_proposal_sender.clone(),
+                collection_name.to_string(),
+                ReplicaState::Dead,
+                None,
+            ),
+            Self::request_shard_transfer_callback(
+                self.consensus_proposal_sender.clone(),
+                collection_name.to_string(),
+            ),
+            Self::abort_shard_transfer_callback(
+                self.consensus_proposal_sender.clone(),
+                collection_name.to_string(),
+            ),
+            Some(self.search_runtime.handle().clone()),
+            Some(self.update_runtime.handle().clone()),
+            self.optimizer_cpu_budget.clone(),
+        )
+        .await;
+        Ok(collection)
+    }
+
     /// performs several alias changes in an atomic fashion
+    ///
+    /// Either all changes are applied, or none of them if any change fails.
     async fn update_aliases(
         &self,
         operation: ChangeAliasesOperation,
@@ -229,6 +385,9 @@ impl TableOfContent {
         // Prevent search on partially switched collections
         let collection_lock = self.collections.write().await;
         let mut alias_lock = self.alias_persistence.write().await;
+
+        // Changes are applied to a copy of the mapping, which is persisted only if all succeed
+        let mut alias_mapping = alias_lock.state().clone();
         for action in operation.actions {
             match action {
                 AliasOperations::CreateAlias(CreateAliasOperation {
@@ -245,12 +404,12 @@ impl TableOfContent {
                         .validate_collection_not_exists(&alias_name)
                         .await?;
 
-                    alias_lock.insert(alias_name, collection_name)?;
+                    alias_mapping.insert(alias_name, collection_name);
                 }
                 AliasOperations::DeleteAlias(DeleteAliasOperation {
                     delete_alias: DeleteAlias { alias_name },
                 }) => {
-                    alias_lock.remove(&alias_name)?;
+                    alias_mapping.remove(&alias_name);
                 }
                 AliasOperations::RenameAlias(RenameAliasOperation {
                     rename_alias:
@@ -259,10 +418,11 @@ impl TableOfContent {
                             new_alias_name,
                         },
                 }) => {
-                    alias_lock.rename_alias(&old_alias_name, new_alias_name)?;
+                    alias_mapping.rename_alias(&old_alias_name, new_alias_name)?;
                 }
             };
         }
+        alias_lock.apply_state(alias_mapping)?;
         Ok(true)
     }
 
@@ -316,6 +476,7 @@ impl TableOfContent {
         match transfer_operation {
             ShardTransferOperations::Start(transfer) => {
                 let collection_state::State {
+                    config,
                     shards,
                     transfers,
                     shards_key_mapping,
@@ -348,6 +509,44 @@ impl TableOfContent {
                     &shards_key_mapping,
                 )?;
 
+                if self
+                    .channel_service
+                    .read_only_peers()
+                    .contains(&transfer.to)
+                {
+                    return Err(StorageError::bad_request(format!(
+                        "Peer {} is in read-only mode and can't receive shards",
+                        transfer.to,
+                    )));
+                }
+
+                if let Some(labels) = &config.params.placement_labels {
+                    if !self
+                        .channel_service
+                        .peers_with_labels(labels)
+                        .contains(&transfer.to)
+                    {
+                        return Err(StorageError::bad_request(format!(
+                            "Peer {} does not match placement labels {labels:?} of collection {collection_id}",
+                            transfer.to,
+                        )));
+                    }
+                }
+
+                let shard_key = shards_key_mapping
+                    .iter()
+                    .find_map(|(shard_key, shard_ids)| {
+                        shard_ids.contains(&transfer.shard_id).then_some(shard_key)
+                    });
+                if let Some(peers) = config.params.pinned_peers(transfer.shard_id, shard_key) {
+                    if !peers.contains(&transfer.to) {
+                        return Err(StorageError::bad_request(format!(
+                            "Shard {} of collection {collection_id} is pinned to peers {peers:?}, peer {} can't receive it",
+                            transfer.shard_id, transfer.to,
+                        )));
+                    }
+                }
+
                 let on_finish = {
                     let collection_id = collection_id.clone();
                     let transfer = transfer.clone();
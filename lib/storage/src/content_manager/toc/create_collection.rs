@@ -11,6 +11,7 @@ use collection::shards::collection_shard_distribution::CollectionShardDistributi
 use collection::shards::replica_set::ReplicaState;
 use collection::shards::shard::{PeerId, ShardId};
 use collection::shards::CollectionId;
+use segment::types::{Filter, PayloadFieldSchema, PayloadSchemaType};
 
 use super::TableOfContent;
 use crate::content_manager::collection_meta_ops::*;
@@ -36,6 +37,10 @@ impl TableOfContent {
             shard_number,
             sharding_method,
             on_disk_payload,
+            storage_engine,
+            payload_columns,
+            placement_labels,
+            shard_pinning,
             hnsw_config: hnsw_config_diff,
             wal_config: wal_config_diff,
             optimizers_config: optimizers_config_diff,
@@ -44,6 +49,12 @@ impl TableOfContent {
             init_from,
             quantization_config,
             sparse_vectors,
+            tiering_config,
+            ttl_config,
+            async_replication_config,
+            inference_config,
+            quota_config,
+            multitenancy_config,
         } = operation;
 
         self.collections
@@ -63,6 +74,8 @@ impl TableOfContent {
             )));
         }
 
+        vectors.check_projections()?;
+
         if let Some(init_from) = &init_from {
             self.check_collections_compatibility(&vectors, &sparse_vectors, &init_from.collection)
                 .await?;
@@ -150,6 +163,10 @@ impl TableOfContent {
                 },
             )?,
             read_fan_out_factor: None,
+            storage_engine,
+            payload_columns,
+            placement_labels,
+            shard_pinning,
         };
         let wal_config = match wal_config_diff {
             None => self.storage_config.wal.clone(),
@@ -161,10 +178,14 @@ impl TableOfContent {
             Some(diff) => diff.update(&self.storage_config.optimizers)?,
         };
 
-        let hnsw_config = match hnsw_config_diff {
+        let mut hnsw_config = match hnsw_config_diff {
             None => self.storage_config.hnsw_index.clone(),
             Some(diff) => diff.update(&self.storage_config.hnsw_index)?,
         };
+        if multitenancy_config.is_some() {
+            // Link points of each tenant together, so that searches within a tenant stay fast
+            hnsw_config.payload_m.get_or_insert(hnsw_config.m);
+        }
 
         let quantization_config = match quantization_config {
             None => self
@@ -177,7 +198,7 @@ impl TableOfContent {
 
         let storage_config = self
             .storage_config
-            .to_shared_storage_config(self.is_distributed())
+            .to_shared_storage_config(self.is_distributed(), self.this_peer_id)
             .into();
 
         let collection_config = CollectionConfig {
@@ -186,6 +207,12 @@ impl TableOfContent {
             optimizer_config: optimizers_config,
             hnsw_config,
             quantization_config,
+            tiering_config,
+            ttl_config,
+            async_replication_config,
+            inference_config,
+            quota_config,
+            multitenancy_config: multitenancy_config.clone(),
         };
         let collection = Collection::new(
             collection_name.to_string(),
@@ -216,6 +243,16 @@ impl TableOfContent {
         )
         .await?;
 
+        if let Some(multitenancy_config) = multitenancy_config {
+            // Payload-based links of the vector index are built for indexed fields only
+            collection
+                .create_payload_index(
+                    multitenancy_config.tenant_key,
+                    PayloadFieldSchema::FieldType(PayloadSchemaType::Keyword),
+                )
+                .await?;
+        }
+
         let local_shards = collection.get_local_shards().await;
 
         {
@@ -235,8 +272,12 @@ impl TableOfContent {
         }
 
         if let Some(init_from) = init_from {
-            self.run_data_initialization(init_from.collection, collection_name.to_string())
-                .await;
+            self.run_data_initialization(
+                init_from.collection,
+                collection_name.to_string(),
+                init_from.filter,
+            )
+            .await;
         }
 
         Ok(true)
@@ -300,6 +341,7 @@ impl TableOfContent {
         &self,
         from_collection: CollectionId,
         to_collection: CollectionId,
+        filter: Option<Filter>,
     ) {
         let collections = self.collections.clone();
         let this_peer_id = self.this_peer_id;
@@ -324,6 +366,7 @@ impl TableOfContent {
                 collections,
                 &from_collection,
                 &to_collection,
+                filter.as_ref(),
                 this_peer_id,
             )
             .await
@@ -2,9 +2,15 @@ use std::sync::atomic;
 
 use super::TableOfContent;
 use crate::content_manager::errors::StorageError;
+use crate::types::QuorumLossFencing;
 
 pub const DEFAULT_WRITE_LOCK_ERROR_MESSAGE: &str = "Write operations are forbidden";
 
+pub const READ_ONLY_ERROR_MESSAGE: &str = "Node is in read-only mode";
+
+pub const QUORUM_LOSS_ERROR_MESSAGE: &str =
+    "Node lost connection to the consensus quorum, operations are rejected to prevent split-brain";
+
 impl TableOfContent {
     pub fn is_write_locked(&self) -> bool {
         self.is_write_locked.load(atomic::Ordering::Relaxed)
@@ -14,8 +20,23 @@ impl TableOfContent {
         self.lock_error_message.lock().clone()
     }
 
-    /// Returns an error if the write lock is set
+    pub fn is_read_only(&self) -> bool {
+        self.is_read_only.load(atomic::Ordering::Relaxed)
+    }
+
+    /// Returns an error if the write lock is set, the node is in read-only mode
+    /// or writes are fenced due to the loss of the consensus quorum
     pub fn check_write_lock(&self) -> Result<(), StorageError> {
+        if self.quorum_loss_fencing().fences_writes() {
+            return Err(StorageError::Locked {
+                description: QUORUM_LOSS_ERROR_MESSAGE.to_string(),
+            });
+        }
+        if self.is_read_only() {
+            return Err(StorageError::Locked {
+                description: READ_ONLY_ERROR_MESSAGE.to_string(),
+            });
+        }
         if self.is_write_locked.load(atomic::Ordering::Relaxed) {
             return Err(StorageError::Locked {
                 description: self
@@ -28,9 +49,39 @@ impl TableOfContent {
         Ok(())
     }
 
+    /// Returns an error if reads are fenced due to the loss of the consensus quorum
+    pub fn check_read_fence(&self) -> Result<(), StorageError> {
+        if self.quorum_loss_fencing().fences_reads() {
+            return Err(StorageError::Locked {
+                description: QUORUM_LOSS_ERROR_MESSAGE.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    pub fn quorum_loss_fencing(&self) -> QuorumLossFencing {
+        *self.quorum_loss_fencing.lock()
+    }
+
+    /// Reject operations, while the node is cut off from the consensus quorum,
+    /// or accept them again with [`QuorumLossFencing::None`]
+    ///
+    /// Returns the previous fencing.
+    pub fn set_quorum_loss_fencing(&self, fencing: QuorumLossFencing) -> QuorumLossFencing {
+        std::mem::replace(&mut *self.quorum_loss_fencing.lock(), fencing)
+    }
+
     pub fn set_locks(&self, is_write_locked: bool, error_message: Option<String>) {
         self.is_write_locked
             .store(is_write_locked, atomic::Ordering::Relaxed);
         *self.lock_error_message.lock() = error_message;
     }
+
+    /// Put the node into read-only mode, or back into normal mode
+    ///
+    /// Returns the previous mode.
+    pub fn set_read_only(&self, is_read_only: bool) -> bool {
+        self.is_read_only
+            .swap(is_read_only, atomic::Ordering::Relaxed)
+    }
 }
@@ -5,6 +5,8 @@ mod create_collection;
 mod locks;
 mod point_ops;
 mod point_ops_internal;
+mod restart;
+mod revocations;
 mod snapshots;
 mod temp_directories;
 pub mod transfer;
@@ -14,7 +16,7 @@ use std::collections::{HashMap, HashSet};
 use std::fs::{create_dir_all, read_dir};
 use std::num::NonZeroU32;
 use std::path::{Path, PathBuf};
-use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicBool, AtomicUsize};
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -22,7 +24,8 @@ use api::grpc::qdrant::qdrant_internal_client::QdrantInternalClient;
 use api::grpc::qdrant::WaitOnConsensusCommitRequest;
 use api::grpc::transport_channel_pool::AddTimeout;
 use collection::collection::{Collection, RequestShardTransfer};
-use collection::config::{default_replication_factor, CollectionConfig};
+use collection::config::{default_replication_factor, pinned_peers, CollectionConfig};
+use collection::operations::snapshot_ops::SnapshotRecoveryStatus;
 use collection::operations::types::*;
 use collection::shards::channel_service::ChannelService;
 use collection::shards::replica_set;
@@ -32,6 +35,7 @@ use collection::telemetry::CollectionTelemetry;
 use common::cpu::{get_num_cpus, CpuBudget};
 use futures::future::try_join_all;
 use futures::Future;
+use segment::json_path::JsonPath;
 use tokio::runtime::Runtime;
 use tokio::sync::{Mutex, RwLock, RwLockReadGuard, Semaphore};
 use tonic::codegen::InterceptedService;
@@ -45,8 +49,9 @@ use crate::content_manager::collections_ops::{Checker, Collections};
 use crate::content_manager::consensus::operation_sender::OperationSender;
 use crate::content_manager::errors::StorageError;
 use crate::content_manager::shard_distribution::ShardDistributionProposal;
+use crate::rbac::revocations::RevocationList;
 use crate::rbac::{Access, AccessRequirements, CollectionPass};
-use crate::types::{PeerAddressById, StorageConfig};
+use crate::types::{PeerAddressById, QuorumLossFencing, StorageConfig};
 use crate::ConsensusOperations;
 
 pub const ALIASES_PATH: &str = "aliases";
@@ -72,6 +77,14 @@ pub struct TableOfContent {
     consensus_proposal_sender: Option<OperationSender>,
     is_write_locked: AtomicBool,
     lock_error_message: parking_lot::Mutex<Option<String>>,
+    /// Node is in read-only mode, it rejects writes and incoming shard transfers
+    is_read_only: AtomicBool,
+    /// Operations rejected while this peer is cut off from the consensus quorum
+    quorum_loss_fencing: parking_lot::Mutex<QuorumLossFencing>,
+    /// Node is cordoned before a restart. Holds the read-only mode the node was in before
+    cordoned: parking_lot::Mutex<Option<bool>>,
+    /// Number of update operations currently processed by this node
+    in_flight_updates: AtomicUsize,
     /// Prevent DDoS of too many concurrent updates in distributed mode.
     /// One external update usually triggers multiple internal updates, which breaks internal
     /// timings. For example, the health check timing and consensus timing.
@@ -83,6 +96,13 @@ pub struct TableOfContent {
     collection_create_lock: Mutex<()>,
     /// Dispatcher for shard transfer to access consensus.
     shard_transfer_dispatcher: parking_lot::Mutex<Option<ShardTransferDispatcher>>,
+    /// Progress of the latest snapshot recovery of each collection on this node
+    snapshot_recoveries: parking_lot::Mutex<HashMap<String, SnapshotRecoveryStatus>>,
+    /// Peers, which are being drained by this node before removal from the cluster.
+    /// New shards are not placed on them.
+    decommissioning_peers: parking_lot::Mutex<HashSet<PeerId>>,
+    /// API keys and JWTs, which are revoked in the cluster
+    revocations: parking_lot::RwLock<RevocationList>,
 }
 
 impl TableOfContent {
@@ -141,7 +161,7 @@ impl TableOfContent {
                 &collection_path,
                 &collection_snapshots_path,
                 storage_config
-                    .to_shared_storage_config(is_distributed)
+                    .to_shared_storage_config(is_distributed, this_peer_id)
                     .into(),
                 channel_service.clone(),
                 Self::change_peer_state_callback(
@@ -168,6 +188,8 @@ impl TableOfContent {
         let alias_path = Path::new(&storage_config.storage_path).join(ALIASES_PATH);
         let alias_persistence =
             AliasPersistence::open(alias_path).expect("Can't open database by the provided config");
+        let revocations = RevocationList::open(Path::new(&storage_config.storage_path))
+            .expect("Can't open revoked credentials");
 
         let rate_limiter = match storage_config.performance.update_rate_limit {
             Some(limit) => Some(Semaphore::new(limit)),
@@ -199,9 +221,16 @@ impl TableOfContent {
             consensus_proposal_sender,
             is_write_locked: AtomicBool::new(false),
             lock_error_message: parking_lot::Mutex::new(None),
+            is_read_only: AtomicBool::new(false),
+            quorum_loss_fencing: parking_lot::Mutex::new(QuorumLossFencing::None),
+            cordoned: parking_lot::Mutex::new(None),
+            in_flight_updates: AtomicUsize::new(0),
             update_rate_limiter: rate_limiter,
             collection_create_lock: Default::default(),
             shard_transfer_dispatcher: Default::default(),
+            snapshot_recoveries: Default::default(),
+            decommissioning_peers: Default::default(),
+            revocations: parking_lot::RwLock::new(revocations),
         }
     }
 
@@ -272,6 +301,14 @@ impl TableOfContent {
         self.get_collection_unchecked(&collection_name).await.ok()
     }
 
+    /// Tenant field of the collection, if it is in multitenancy mode.
+    ///
+    /// Unknown collections have no tenant field, so that access is checked before existence.
+    async fn tenant_key(&self, collection_name: &str) -> Option<JsonPath> {
+        let collection = self.get_collection_unchecked(collection_name).await.ok()?;
+        collection.tenant_key().await
+    }
+
     /// Finds the original name of the collection
     ///
     /// # Arguments
@@ -344,7 +381,7 @@ impl TableOfContent {
         &self,
         op: &CreateCollectionOperation,
         suggested_shard_number: NonZeroU32,
-    ) -> ShardDistributionProposal {
+    ) -> Result<ShardDistributionProposal, StorageError> {
         let shard_number = op
             .create_collection
             .shard_number
@@ -358,6 +395,39 @@ impl TableOfContent {
             .copied()
             .collect();
         known_peers_set.insert(self.this_peer_id());
+        known_peers_set.retain(|peer_id| !self.is_peer_decommissioning(*peer_id));
+        let read_only_peers = self.channel_service.read_only_peers();
+        known_peers_set.retain(|peer_id| !read_only_peers.contains(peer_id));
+        if known_peers_set.is_empty() {
+            return Err(StorageError::bad_input(format!(
+                "No peers can host shards of collection {}, all of them are read-only",
+                op.collection_name,
+            )));
+        }
+        if let Some(labels) = &op.create_collection.placement_labels {
+            let matching_peers = self.channel_service.peers_with_labels(labels);
+            known_peers_set.retain(|peer_id| matching_peers.contains(peer_id));
+            if known_peers_set.is_empty() {
+                return Err(StorageError::bad_input(format!(
+                    "No peers match placement labels {labels:?} of collection {}",
+                    op.collection_name,
+                )));
+            }
+        }
+        let shard_pinning = op.create_collection.shard_pinning.as_deref();
+        for shard_id in 0..shard_number.get() {
+            if let Some(peers) = pinned_peers(shard_pinning, shard_id, None) {
+                if !peers
+                    .iter()
+                    .any(|peer_id| known_peers_set.contains(peer_id))
+                {
+                    return Err(StorageError::bad_input(format!(
+                        "Shard {shard_id} of collection {} is pinned to peers {peers:?}, none of which can host it",
+                        op.collection_name,
+                    )));
+                }
+            }
+        }
         let known_peers: Vec<_> = known_peers_set.into_iter().collect();
         let replication_factor = op
             .create_collection
@@ -365,8 +435,13 @@ impl TableOfContent {
             .and_then(NonZeroU32::new)
             .unwrap_or_else(default_replication_factor);
 
-        let shard_distribution =
-            ShardDistributionProposal::new(shard_number, replication_factor, &known_peers);
+        let shard_distribution = ShardDistributionProposal::new(
+            shard_number,
+            replication_factor,
+            &known_peers,
+            &self.channel_service.peer_zones(),
+            shard_pinning,
+        );
 
         log::debug!(
             "Suggesting distribution for {} shards for collection '{}' among {} peers {:?}",
@@ -375,7 +450,7 @@ impl TableOfContent {
             known_peers.len(),
             shard_distribution.distribution
         );
-        shard_distribution
+        Ok(shard_distribution)
     }
 
     /// Initiate receiving shard.
@@ -429,6 +504,28 @@ impl TableOfContent {
         false
     }
 
+    /// Disable replicas of a peer, which doesn't respond to health checks
+    pub async fn report_unreachable_peer(&self, peer_id: PeerId) {
+        for collection in self.collections.read().await.values() {
+            collection.report_unreachable_peer(peer_id).await;
+        }
+    }
+
+    /// Mark peer as being decommissioned.
+    ///
+    /// Returns `false` if the peer is already being decommissioned.
+    pub fn start_peer_decommission(&self, peer_id: PeerId) -> bool {
+        self.decommissioning_peers.lock().insert(peer_id)
+    }
+
+    pub fn finish_peer_decommission(&self, peer_id: PeerId) {
+        self.decommissioning_peers.lock().remove(&peer_id);
+    }
+
+    pub fn is_peer_decommissioning(&self, peer_id: PeerId) -> bool {
+        self.decommissioning_peers.lock().contains(&peer_id)
+    }
+
     pub async fn get_telemetry_data(
         &self,
         detail: TelemetryDetail,
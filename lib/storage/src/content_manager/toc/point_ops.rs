@@ -12,11 +12,12 @@ use collection::operations::{CollectionUpdateOperations, OperationWithClockTag};
 use collection::{discovery, recommendations};
 use futures::stream::FuturesUnordered;
 use futures::TryStreamExt as _;
-use segment::types::{ScoredPoint, ShardKey};
+use segment::blob_storage::BlobChunk;
+use segment::types::{PointIdType, ScoredPoint, ShardKey};
 
 use super::TableOfContent;
 use crate::content_manager::errors::StorageError;
-use crate::rbac::Access;
+use crate::rbac::{Access, AccessRequirements};
 
 impl TableOfContent {
     /// Recommend points using positive and negative example from the request
@@ -38,7 +39,11 @@ impl TableOfContent {
         access: Access,
         timeout: Option<Duration>,
     ) -> Result<Vec<ScoredPoint>, StorageError> {
-        let collection_pass = access.check_point_op(collection_name, &mut request)?;
+        self.check_read_fence()?;
+
+        let tenant_key = self.tenant_key(collection_name).await;
+        let collection_pass =
+            access.check_point_op(collection_name, tenant_key.as_ref(), &mut request)?;
 
         let collection = self.get_collection(&collection_pass).await?;
         recommendations::recommend_by(
@@ -71,9 +76,13 @@ impl TableOfContent {
         access: Access,
         timeout: Option<Duration>,
     ) -> Result<Vec<Vec<ScoredPoint>>, StorageError> {
+        self.check_read_fence()?;
+
+        let tenant_key = self.tenant_key(collection_name).await;
         let mut collection_pass = None;
         for (request, _shard_selector) in &mut requests {
-            collection_pass = Some(access.check_point_op(collection_name, request)?);
+            collection_pass =
+                Some(access.check_point_op(collection_name, tenant_key.as_ref(), request)?);
         }
         let Some(collection_pass) = collection_pass else {
             return Ok(vec![]);
@@ -114,9 +123,13 @@ impl TableOfContent {
         access: Access,
         timeout: Option<Duration>,
     ) -> Result<Vec<Vec<ScoredPoint>>, StorageError> {
+        self.check_read_fence()?;
+
+        let tenant_key = self.tenant_key(collection_name).await;
         let mut collection_pass = None;
         for request in &mut request.searches {
-            collection_pass = Some(access.check_point_op(collection_name, request)?);
+            collection_pass =
+                Some(access.check_point_op(collection_name, tenant_key.as_ref(), request)?);
         }
         let Some(collection_pass) = collection_pass else {
             return Ok(vec![]);
@@ -149,7 +162,11 @@ impl TableOfContent {
         shard_selection: ShardSelectorInternal,
         access: Access,
     ) -> Result<CountResult, StorageError> {
-        let collection_pass = access.check_point_op(collection_name, &mut request)?;
+        self.check_read_fence()?;
+
+        let tenant_key = self.tenant_key(collection_name).await;
+        let collection_pass =
+            access.check_point_op(collection_name, tenant_key.as_ref(), &mut request)?;
 
         let collection = self.get_collection(&collection_pass).await?;
         collection
@@ -177,7 +194,11 @@ impl TableOfContent {
         shard_selection: ShardSelectorInternal,
         access: Access,
     ) -> Result<Vec<Record>, StorageError> {
-        let collection_pass = access.check_point_op(collection_name, &mut request)?;
+        self.check_read_fence()?;
+
+        let tenant_key = self.tenant_key(collection_name).await;
+        let collection_pass =
+            access.check_point_op(collection_name, tenant_key.as_ref(), &mut request)?;
 
         let collection = self.get_collection(&collection_pass).await?;
         collection
@@ -186,6 +207,29 @@ impl TableOfContent {
             .map_err(|err| err.into())
     }
 
+    /// Read up to `limit` bytes of the point blob, starting from `offset`
+    pub async fn read_blob(
+        &self,
+        collection_name: &str,
+        point_id: PointIdType,
+        offset: usize,
+        limit: usize,
+        shard_selection: ShardSelectorInternal,
+        access: Access,
+    ) -> Result<Option<BlobChunk>, StorageError> {
+        self.check_read_fence()?;
+
+        // Blobs are opaque, payload constraint can't be applied to them
+        let collection_pass =
+            access.check_collection_access(collection_name, AccessRequirements::new().whole())?;
+
+        let collection = self.get_collection(&collection_pass).await?;
+        collection
+            .read_blob(point_id, offset, limit, &shard_selection)
+            .await
+            .map_err(|err| err.into())
+    }
+
     pub async fn group(
         &self,
         collection_name: &str,
@@ -195,7 +239,11 @@ impl TableOfContent {
         access: Access,
         timeout: Option<Duration>,
     ) -> Result<GroupsResult, StorageError> {
-        let collection_pass = access.check_point_op(collection_name, &mut request)?;
+        self.check_read_fence()?;
+
+        let tenant_key = self.tenant_key(collection_name).await;
+        let collection_pass =
+            access.check_point_op(collection_name, tenant_key.as_ref(), &mut request)?;
 
         let collection = self.get_collection(&collection_pass).await?;
 
@@ -222,7 +270,11 @@ impl TableOfContent {
         access: Access,
         timeout: Option<Duration>,
     ) -> Result<Vec<ScoredPoint>, StorageError> {
-        let collection_pass = access.check_point_op(collection_name, &mut request)?;
+        self.check_read_fence()?;
+
+        let tenant_key = self.tenant_key(collection_name).await;
+        let collection_pass =
+            access.check_point_op(collection_name, tenant_key.as_ref(), &mut request)?;
 
         let collection = self.get_collection(&collection_pass).await?;
         discovery::discover(
@@ -245,9 +297,13 @@ impl TableOfContent {
         access: Access,
         timeout: Option<Duration>,
     ) -> Result<Vec<Vec<ScoredPoint>>, StorageError> {
+        self.check_read_fence()?;
+
+        let tenant_key = self.tenant_key(collection_name).await;
         let mut collection_pass = None;
         for (request, _shard_selector) in &mut requests {
-            collection_pass = Some(access.check_point_op(collection_name, request)?);
+            collection_pass =
+                Some(access.check_point_op(collection_name, tenant_key.as_ref(), request)?);
         }
         let Some(collection_pass) = collection_pass else {
             return Ok(vec![]);
@@ -285,7 +341,11 @@ impl TableOfContent {
         shard_selection: ShardSelectorInternal,
         access: Access,
     ) -> Result<ScrollResult, StorageError> {
-        let collection_pass = access.check_point_op(collection_name, &mut request)?;
+        self.check_read_fence()?;
+
+        let tenant_key = self.tenant_key(collection_name).await;
+        let collection_pass =
+            access.check_point_op(collection_name, tenant_key.as_ref(), &mut request)?;
 
         let collection = self.get_collection(&collection_pass).await?;
         collection
@@ -302,7 +362,11 @@ impl TableOfContent {
         _shard_selection: ShardSelectorInternal, // TODO(universal-query): pass this to collection
         access: Access,
     ) -> Result<Vec<ScoredPoint>, StorageError> {
-        let collection_pass = access.check_point_op(collection_name, &mut request)?;
+        self.check_read_fence()?;
+
+        let tenant_key = self.tenant_key(collection_name).await;
+        let collection_pass =
+            access.check_point_op(collection_name, tenant_key.as_ref(), &mut request)?;
 
         let _collection = self.get_collection(&collection_pass).await?;
 
@@ -358,7 +422,12 @@ impl TableOfContent {
         shard_selector: ShardSelectorInternal,
         access: Access,
     ) -> Result<UpdateResult, StorageError> {
-        let collection_pass = access.check_point_op(collection_name, &mut operation.operation)?;
+        let tenant_key = self.tenant_key(collection_name).await;
+        let collection_pass = access.check_point_op(
+            collection_name,
+            tenant_key.as_ref(),
+            &mut operation.operation,
+        )?;
 
         // `TableOfContent::_update_shard_keys` and `Collection::update_from_*` are cancel safe,
         // so this method is cancel safe.
@@ -409,6 +478,8 @@ impl TableOfContent {
             self.check_write_lock()?;
         }
 
+        let _in_flight_update = self.track_in_flight_update();
+
         // TODO: `debug_assert(operation.clock_tag.is_none())` for `_update_shard_keys`/`update_from_client`!?
 
         let res = match shard_selector {
@@ -0,0 +1,59 @@
+use std::sync::atomic::{self, AtomicUsize};
+
+use super::TableOfContent;
+
+/// Counts an update operation as in-flight until dropped
+pub(super) struct InFlightUpdateGuard<'a> {
+    counter: &'a AtomicUsize,
+}
+
+impl Drop for InFlightUpdateGuard<'_> {
+    fn drop(&mut self) {
+        self.counter.fetch_sub(1, atomic::Ordering::Relaxed);
+    }
+}
+
+impl TableOfContent {
+    pub(super) fn track_in_flight_update(&self) -> InFlightUpdateGuard<'_> {
+        self.in_flight_updates
+            .fetch_add(1, atomic::Ordering::Relaxed);
+        InFlightUpdateGuard {
+            counter: &self.in_flight_updates,
+        }
+    }
+
+    /// Number of update operations currently processed by this node
+    pub fn in_flight_updates(&self) -> usize {
+        self.in_flight_updates.load(atomic::Ordering::Relaxed)
+    }
+
+    pub fn is_cordoned(&self) -> bool {
+        self.cordoned.lock().is_some()
+    }
+
+    /// Cordon the node before a restart: put it into read-only mode, so it stops accepting
+    /// writes and incoming shard transfers
+    ///
+    /// Returns `false` if the node is already cordoned.
+    pub fn cordon(&self) -> bool {
+        let mut cordoned = self.cordoned.lock();
+        if cordoned.is_some() {
+            return false;
+        }
+        *cordoned = Some(self.set_read_only(true));
+        true
+    }
+
+    /// Uncordon the node, restoring the read-only mode it was in before cordoning
+    ///
+    /// Returns `false` if the node is not cordoned.
+    pub fn uncordon(&self) -> bool {
+        match self.cordoned.lock().take() {
+            Some(was_read_only) => {
+                self.set_read_only(was_read_only);
+                true
+            }
+            None => false,
+        }
+    }
+}
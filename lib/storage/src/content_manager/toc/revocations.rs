@@ -0,0 +1,30 @@
+use super::TableOfContent;
+use crate::content_manager::errors::StorageError;
+use crate::rbac::revocations::{CredentialRevocation, RevokedCredentials};
+
+impl TableOfContent {
+    pub fn is_credential_revoked(&self, credential_id: &str) -> bool {
+        self.revocations.read().is_revoked(credential_id)
+    }
+
+    pub fn revoked_credentials(&self) -> RevokedCredentials {
+        self.revocations.read().state().clone()
+    }
+
+    /// Revoke the credential, or restore it if `revocation` is `None`
+    pub fn set_credential_revocation(
+        &self,
+        credential_id: String,
+        revocation: Option<CredentialRevocation>,
+    ) -> Result<bool, StorageError> {
+        self.revocations.write().set(credential_id, revocation)?;
+        Ok(true)
+    }
+
+    pub fn apply_revocations_snapshot(
+        &self,
+        revoked: RevokedCredentials,
+    ) -> Result<(), StorageError> {
+        self.revocations.write().replace(revoked)
+    }
+}
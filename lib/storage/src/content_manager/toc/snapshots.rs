@@ -1,7 +1,11 @@
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
-use collection::common::snapshots_manager::SnapshotStorageManager;
-use collection::operations::snapshot_ops::SnapshotDescription;
+use collection::common::archive_stream::ArchiveStream;
+use collection::common::snapshots_manager::{SnapShotsConfig, SnapshotStorageManager};
+use collection::common::wal_encryption::WalCipher;
+use collection::operations::snapshot_ops::{SnapshotDescription, SnapshotRecoveryStatus};
+use collection::shards::local_shard::wal_archive::WalArchiveConfig;
 use collection::shards::replica_set::ReplicaState;
 use collection::shards::shard::{PeerId, ShardId};
 use collection::shards::transfer::{ShardTransfer, ShardTransferMethod};
@@ -21,6 +25,52 @@ impl TableOfContent {
         })
     }
 
+    pub fn snapshots_config(&self) -> &SnapShotsConfig {
+        &self.storage_config.snapshots_config
+    }
+
+    /// WAL archive of this node, also used as a source of point-in-time recovery
+    pub fn wal_archive_config(&self) -> WalArchiveConfig {
+        self.storage_config
+            .wal_archive
+            .clone()
+            .with_storage_path(Path::new(&self.storage_config.storage_path))
+    }
+
+    /// Cipher of the collection WAL, required to read and write encrypted entries
+    pub fn wal_cipher_for(
+        &self,
+        collection_name: &str,
+    ) -> Result<Option<Arc<WalCipher>>, StorageError> {
+        Ok(self
+            .storage_config
+            .wal_encryption
+            .cipher_for(collection_name)?)
+    }
+
+    pub fn snapshot_recovery_status(
+        &self,
+        collection_name: &str,
+    ) -> Option<SnapshotRecoveryStatus> {
+        self.snapshot_recoveries
+            .lock()
+            .get(collection_name)
+            .cloned()
+    }
+
+    /// Update progress of the ongoing snapshot recovery of the collection
+    pub fn update_snapshot_recovery_status(
+        &self,
+        collection_name: &str,
+        update: impl FnOnce(&mut SnapshotRecoveryStatus),
+    ) {
+        let mut recoveries = self.snapshot_recoveries.lock();
+        let status = recoveries
+            .entry(collection_name.to_string())
+            .or_insert_with(SnapshotRecoveryStatus::started);
+        update(status);
+    }
+
     pub fn snapshots_path(&self) -> &str {
         &self.storage_config.snapshots_path
     }
@@ -55,13 +105,29 @@ impl TableOfContent {
     pub async fn create_snapshot<'a>(
         &self,
         collection: &CollectionPass<'a>,
+        encryption_key: Option<&str>,
     ) -> Result<SnapshotDescription, StorageError> {
         let collection = self.get_collection(collection).await?;
         // We want to use temp dir inside the temp_path (storage if not specified), because it is possible, that
         // snapshot directory is mounted as network share and multiple writes to it could be slow
         let temp_dir = self.optional_temp_or_storage_temp_path()?;
         Ok(collection
-            .create_snapshot(&temp_dir, self.this_peer_id)
+            .create_snapshot(&temp_dir, self.this_peer_id, encryption_key)
+            .await?)
+    }
+
+    /// Create a snapshot of the collection and stream it, without storing the archive.
+    ///
+    /// Returns the name of the snapshot along with the archive stream.
+    pub async fn stream_snapshot<'a>(
+        &self,
+        collection: &CollectionPass<'a>,
+        encryption_key: Option<&str>,
+    ) -> Result<(String, ArchiveStream), StorageError> {
+        let collection = self.get_collection(collection).await?;
+        let temp_dir = self.optional_temp_or_storage_temp_path()?;
+        Ok(collection
+            .stream_snapshot(&temp_dir, self.this_peer_id, encryption_key)
             .await?)
     }
 
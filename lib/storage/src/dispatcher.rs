@@ -77,7 +77,7 @@ impl Dispatcher {
                                         NonZeroU32::new(number_of_peers as u32)
                                             .expect("Peer count should be always >= 1"),
                                     )
-                                    .await;
+                                    .await?;
 
                                 // Expect all replicas to become active eventually
                                 for (shard_id, peer_ids) in &shard_distribution.distribution {
@@ -126,6 +126,9 @@ impl Dispatcher {
                 CollectionMetaOperations::CreateCollection(_)
                 | CollectionMetaOperations::CreateShardKey(_) => true,
 
+                // Sync nodes after collection renaming, so requests with the new name succeed
+                CollectionMetaOperations::RenameCollection(_) => true,
+
                 // Sync nodes when creating or renaming collection aliases
                 CollectionMetaOperations::ChangeAliases(changes) => {
                     changes.actions.iter().any(|change| match change {
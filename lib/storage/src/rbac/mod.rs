@@ -2,13 +2,14 @@ use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
 
 use segment::json_path::JsonPath;
-use segment::types::ValueVariants;
+use segment::types::{Filter, ValueVariants};
 use serde::{Deserialize, Serialize};
 use validator::{Validate, ValidateArgs, ValidationError, ValidationErrors};
 
 use crate::content_manager::errors::StorageError;
 
 mod ops_checks;
+pub mod revocations;
 
 /// A structure that defines access rights.
 #[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
@@ -25,7 +26,8 @@ pub struct CollectionAccessList(pub Vec<CollectionAccess>);
 
 #[derive(Serialize, Deserialize, Validate, PartialEq, Clone, Debug)]
 pub struct CollectionAccess {
-    /// Collection names that are allowed to be accessed
+    /// Collection names that are allowed to be accessed.
+    /// May be a pattern with `*` wildcards, e.g. `logs_*`, to match multiple collections.
     #[validate(custom(
         function = "validate_unique_collections",
         arg = "&'v_a mut HashSet<String>"
@@ -38,6 +40,23 @@ pub struct CollectionAccess {
     /// An object where each key is a JSON path, and each value is JSON value.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub payload: Option<PayloadConstraint>,
+
+    /// Mandatory filter, e.g. by tenant id.
+    /// It is combined with filters of all read and delete operations, so only matching points
+    /// are visible and affected.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<Filter>,
+
+    /// Tenant of the user in a collection in multitenancy mode.
+    /// Only points of this tenant are visible and affected, and upserted points must belong to
+    /// it. Collections, which are not multitenant, can't be accessed with a tenant.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tenant: Option<String>,
+
+    /// Permitted actions on snapshots of the collection.
+    /// If not set, `m` access permits all actions, and other access modes permit none.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snapshots: Option<Vec<SnapshotAction>>,
 }
 
 #[derive(Serialize, Deserialize, Eq, PartialEq, Copy, Clone, Debug)]
@@ -60,6 +79,36 @@ pub enum CollectionAccessMode {
     /// Read and write access to a collection, with some restrictions.
     #[serde(rename = "rw")]
     ReadWrite,
+
+    /// Read and write access to a collection, and managing it: create, update or delete it.
+    #[serde(rename = "m")]
+    Manage,
+}
+
+/// Action on snapshots, permitted separately from read and write access, since snapshots expose
+/// or replace all data of a collection.
+#[derive(Serialize, Deserialize, Eq, PartialEq, Copy, Clone, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum SnapshotAction {
+    /// Create and delete snapshots
+    Create,
+    /// List snapshots
+    List,
+    /// Download snapshots
+    Download,
+    /// Recover from snapshots
+    Restore,
+}
+
+impl SnapshotAction {
+    fn as_str(self) -> &'static str {
+        match self {
+            SnapshotAction::Create => "create",
+            SnapshotAction::List => "list",
+            SnapshotAction::Download => "download",
+            SnapshotAction::Restore => "restore",
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
@@ -111,10 +160,16 @@ impl CollectionAccessList {
         &'a self,
         collection_name: &'a str,
     ) -> Result<CollectionAccessView<'a>, StorageError> {
+        // Exact name takes precedence over patterns
         let access = self
             .0
             .iter()
             .find(|collections| collections.collection == collection_name)
+            .or_else(|| {
+                self.0.iter().find(|collections| {
+                    matches_collection_pattern(&collections.collection, collection_name)
+                })
+            })
             .ok_or_else(|| {
                 StorageError::forbidden(format!(
                     "Access to collection {collection_name} is required"
@@ -124,6 +179,10 @@ impl CollectionAccessList {
             collection: collection_name,
             access: access.access,
             payload: &access.payload,
+            filter: &access.filter,
+            tenant: access.tenant.as_deref(),
+            tenant_key: None,
+            snapshots: access.snapshots.as_deref(),
         })
     }
 }
@@ -133,11 +192,36 @@ struct CollectionAccessView<'a> {
     pub collection: &'a str,
     pub access: CollectionAccessMode,
     pub payload: &'a Option<PayloadConstraint>,
+    pub filter: &'a Option<Filter>,
+    pub tenant: Option<&'a str>,
+    /// Tenant field of the collection, if it is multitenant
+    pub tenant_key: Option<&'a JsonPath>,
+    pub snapshots: Option<&'a [SnapshotAction]>,
 }
 
 impl<'a> CollectionAccessView<'a> {
+    /// Access is limited to a subset of points by `payload`, `filter` or `tenant` restriction
+    pub(self) fn is_restricted(&self) -> bool {
+        self.payload.is_some() || self.filter.is_some() || self.tenant.is_some()
+    }
+
+    /// Bind the `tenant` restriction to the tenant field of the collection
+    pub(self) fn with_tenant_key(
+        self,
+        tenant_key: Option<&'a JsonPath>,
+    ) -> Result<Self, StorageError> {
+        if self.tenant.is_some() && tenant_key.is_none() {
+            return Err(StorageError::forbidden(format!(
+                "Access to collection {} is limited to a tenant, but the collection is not \
+                 multitenant",
+                self.collection,
+            )));
+        }
+        Ok(Self { tenant_key, ..self })
+    }
+
     pub(self) fn check_whole_access(&self) -> Result<(), StorageError> {
-        if self.payload.is_some() {
+        if self.is_restricted() {
             return incompatible_with_payload_constraint(self.collection);
         }
         Ok(())
@@ -148,6 +232,7 @@ impl<'a> CollectionAccessView<'a> {
             write,
             manage,
             whole,
+            snapshot,
         } = requirements;
         if write {
             match self.access {
@@ -157,19 +242,37 @@ impl<'a> CollectionAccessView<'a> {
                         self.collection,
                     )))
                 }
-                CollectionAccessMode::ReadWrite => (),
+                CollectionAccessMode::ReadWrite | CollectionAccessMode::Manage => (),
             }
         }
         if manage {
-            // Don't specify collection name since the manage access could be enabled globally, and
-            // not per collection.
-            return Err(StorageError::forbidden(
-                "Manage access for this operation is required",
-            ));
+            match self.access {
+                CollectionAccessMode::Read | CollectionAccessMode::ReadWrite => {
+                    // Don't specify collection name since the manage access could be enabled
+                    // globally, and not per collection.
+                    return Err(StorageError::forbidden(
+                        "Manage access for this operation is required",
+                    ));
+                }
+                CollectionAccessMode::Manage => (),
+            }
         }
-        if whole && self.payload.is_some() {
+        if whole && self.is_restricted() {
             return incompatible_with_payload_constraint(self.collection);
         }
+        if let Some(action) = snapshot {
+            let is_permitted = match self.snapshots {
+                Some(actions) => actions.contains(&action),
+                None => self.access == CollectionAccessMode::Manage,
+            };
+            if !is_permitted {
+                return Err(StorageError::forbidden(format!(
+                    "Permission to {} snapshots of collection {} is required",
+                    action.as_str(),
+                    self.collection,
+                )));
+            }
+        }
         Ok(())
     }
 }
@@ -211,6 +314,8 @@ pub struct AccessRequirements {
     pub manage: bool,
     /// If true, the access should be not limited by a payload restrictions.
     pub whole: bool,
+    /// Action on snapshots, which must be permitted explicitly.
+    pub snapshot: Option<SnapshotAction>,
 }
 
 impl AccessRequirements {
@@ -238,6 +343,13 @@ impl AccessRequirements {
             ..*self
         }
     }
+
+    pub fn snapshot(&self, action: SnapshotAction) -> Self {
+        Self {
+            snapshot: Some(action),
+            ..*self
+        }
+    }
 }
 
 impl GlobalAccessMode {
@@ -246,6 +358,7 @@ impl GlobalAccessMode {
             write,
             manage,
             whole: _,
+            snapshot,
         } = requirements;
         if write || manage {
             match self {
@@ -255,16 +368,51 @@ impl GlobalAccessMode {
                 GlobalAccessMode::Manage => (),
             }
         }
+        // Global read-only access can only list snapshots
+        match (self, snapshot) {
+            (GlobalAccessMode::Read, Some(action)) if action != SnapshotAction::List => {
+                return Err(StorageError::forbidden(format!(
+                    "Global manage access is required to {} snapshots",
+                    action.as_str(),
+                )))
+            }
+            _ => (),
+        }
         Ok(())
     }
 }
 
-/// Helper function to indicate that the operation is not allowed when `payload` constraint is
-/// present.
+/// Check if the collection name matches a pattern, where `*` matches any sequence of characters.
+/// Collection names can't contain `*`, so a pattern without it only matches the exact name.
+fn matches_collection_pattern(pattern: &str, collection_name: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let Some(prefix) = parts.next() else {
+        return false;
+    };
+    let Some(mut rest) = collection_name.strip_prefix(prefix) else {
+        return false;
+    };
+    let mut parts = parts.peekable();
+    while let Some(part) = parts.next() {
+        if parts.peek().is_none() {
+            // Last part must be a suffix, empty part matches anything
+            return rest.ends_with(part);
+        }
+        match rest.find(part) {
+            Some(pos) => rest = &rest[pos + part.len()..],
+            None => return false,
+        }
+    }
+    // No wildcards in the pattern
+    rest.is_empty()
+}
+
+/// Helper function to indicate that the operation is not allowed when `payload` constraint,
+/// `filter` or `tenant` is present.
 fn incompatible_with_payload_constraint<T>(collection_name: &str) -> Result<T, StorageError> {
     Err(StorageError::forbidden(format!(
-        "This operation is not allowed when \"payload\" restriction is present for collection \
-         {collection_name}"
+        "This operation is not allowed when \"payload\", \"filter\" or \"tenant\" restriction \
+         is present for collection {collection_name}"
     )))
 }
 
@@ -324,6 +472,9 @@ impl AccessCollectionBuilder {
                 CollectionAccessMode::Read
             },
             payload: (!whole).then(|| PayloadConstraint::new_test(name)),
+            filter: None,
+            tenant: None,
+            snapshots: None,
         });
         self
     }
@@ -356,3 +507,124 @@ impl PayloadConstraint {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content_manager::collection_meta_ops::{
+        CollectionMetaOperations, DeleteCollectionOperation,
+    };
+
+    #[test]
+    fn test_matches_collection_pattern() {
+        assert!(matches_collection_pattern("logs", "logs"));
+        assert!(!matches_collection_pattern("logs", "logs_1"));
+        assert!(matches_collection_pattern("logs_*", "logs_1"));
+        assert!(matches_collection_pattern("logs_*", "logs_"));
+        assert!(!matches_collection_pattern("logs_*", "metrics_1"));
+        assert!(matches_collection_pattern("*_logs", "app_logs"));
+        assert!(!matches_collection_pattern("*_logs", "app_logs_1"));
+        assert!(matches_collection_pattern("a*b*c", "a_b_c"));
+        assert!(matches_collection_pattern("a*b*c", "abc"));
+        assert!(!matches_collection_pattern("a*b*c", "a_c_b"));
+        assert!(!matches_collection_pattern("ab*ba", "aba"));
+        assert!(matches_collection_pattern("*", "anything"));
+    }
+
+    #[test]
+    fn test_snapshot_permissions() {
+        let download = AccessRequirements::new()
+            .whole()
+            .snapshot(SnapshotAction::Download);
+        let list = AccessRequirements::new().snapshot(SnapshotAction::List);
+
+        assert!(Access::full("Test")
+            .check_collection_access("col", download)
+            .is_ok());
+        assert!(Access::full_ro("Test")
+            .check_collection_access("col", list)
+            .is_ok());
+        assert!(Access::full_ro("Test")
+            .check_collection_access("col", download)
+            .is_err());
+
+        let collection_access = |access, snapshots| {
+            Access::Collection(CollectionAccessList(vec![CollectionAccess {
+                collection: "col".to_string(),
+                access,
+                payload: None,
+                filter: None,
+                tenant: None,
+                snapshots,
+            }]))
+        };
+
+        // Snapshots are not permitted by read and write access
+        let access = collection_access(CollectionAccessMode::ReadWrite, None);
+        assert!(access.check_collection_access("col", list).is_err());
+        let access = collection_access(CollectionAccessMode::Manage, None);
+        assert!(access.check_collection_access("col", download).is_ok());
+
+        let access = collection_access(
+            CollectionAccessMode::Read,
+            Some(vec![SnapshotAction::List, SnapshotAction::Download]),
+        );
+        assert!(access.check_collection_access("col", download).is_ok());
+        assert!(access
+            .check_collection_access(
+                "col",
+                AccessRequirements::new().snapshot(SnapshotAction::Create)
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_collection_access_patterns() {
+        let access = Access::Collection(CollectionAccessList(vec![
+            CollectionAccess {
+                collection: "logs_*".to_string(),
+                access: CollectionAccessMode::Manage,
+                payload: None,
+                filter: None,
+                tenant: None,
+                snapshots: None,
+            },
+            CollectionAccess {
+                collection: "logs_audit".to_string(),
+                access: CollectionAccessMode::Read,
+                payload: None,
+                filter: None,
+                tenant: None,
+                snapshots: None,
+            },
+        ]));
+
+        let manage = AccessRequirements::new().manage().whole();
+        assert!(access.check_collection_access("logs_app", manage).is_ok());
+        assert!(access
+            .check_collection_access("logs_app", AccessRequirements::new().write())
+            .is_ok());
+        assert!(access.check_collection_access("metrics", manage).is_err());
+
+        // Exact name takes precedence over the pattern
+        assert!(access
+            .check_collection_access("logs_audit", AccessRequirements::new())
+            .is_ok());
+        assert!(access
+            .check_collection_access("logs_audit", AccessRequirements::new().write())
+            .is_err());
+
+        let delete = |name: &str| {
+            CollectionMetaOperations::DeleteCollection(DeleteCollectionOperation(name.to_string()))
+        };
+        assert!(access
+            .check_collection_meta_operation(&delete("logs_app"))
+            .is_ok());
+        assert!(access
+            .check_collection_meta_operation(&delete("logs_audit"))
+            .is_err());
+        assert!(access
+            .check_global_access(AccessRequirements::new())
+            .is_err());
+    }
+}
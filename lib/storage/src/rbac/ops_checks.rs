@@ -4,8 +4,13 @@ use std::mem::take;
 
 use collection::grouping::group_by::{GroupRequest, SourceRequest};
 use collection::lookup::WithLookup;
-use collection::operations::payload_ops::{DeletePayloadOp, PayloadOps, SetPayloadOp};
-use collection::operations::point_ops::{PointIdsList, PointOperations};
+use collection::operations::payload_ops::{
+    DeletePayloadOp, PatchPayloadOp, PayloadOps, SetPayloadOp,
+};
+use collection::operations::point_ops::{
+    ConditionalInsertOperationInternal, PointIdsList, PointInsertOperationsInternal,
+    PointOperations, UpdateMode,
+};
 use collection::operations::types::{
     ContextExamplePair, CoreSearchRequest, CountRequestInternal, DiscoverRequestInternal,
     LookupLocation, PointRequestInternal, RecommendExample, RecommendRequestInternal,
@@ -16,7 +21,10 @@ use collection::operations::universal_query::collection_query::{
 };
 use collection::operations::vector_ops::VectorOperations;
 use collection::operations::CollectionUpdateOperations;
-use segment::types::{Condition, ExtendedPointId, FieldCondition, Filter, Match, Payload};
+use segment::json_path::{JsonPath, JsonPathInterface as _};
+use segment::types::{
+    Condition, ExtendedPointId, FieldCondition, Filter, Match, Payload, ValueVariants,
+};
 
 use super::{
     incompatible_with_payload_constraint, Access, AccessRequirements, CollectionAccessList,
@@ -30,13 +38,16 @@ impl Access {
     pub(crate) fn check_point_op<'a>(
         &self,
         collection_name: &'a str,
+        tenant_key: Option<&JsonPath>,
         op: &mut impl CheckableCollectionOperation,
     ) -> Result<CollectionPass<'a>, StorageError> {
         let requirements = op.access_requirements();
         match self {
             Access::Global(mode) => mode.meets_requirements(requirements)?,
             Access::Collection(list) => {
-                let view = list.find_view(collection_name)?;
+                let view = list
+                    .find_view(collection_name)?
+                    .with_tenant_key(tenant_key)?;
                 view.meets_requirements(requirements)?;
                 op.check_access(view, list)?;
             }
@@ -49,15 +60,49 @@ impl Access {
         operation: &CollectionMetaOperations,
     ) -> Result<(), StorageError> {
         match operation {
-            CollectionMetaOperations::CreateCollection(_)
-            | CollectionMetaOperations::UpdateCollection(_)
-            | CollectionMetaOperations::DeleteCollection(_)
-            | CollectionMetaOperations::ChangeAliases(_)
+            CollectionMetaOperations::CreateCollection(op) => {
+                self.check_collection_access(
+                    &op.collection_name,
+                    AccessRequirements::new().manage().whole(),
+                )?;
+            }
+            CollectionMetaOperations::UpdateCollection(op) => {
+                self.check_collection_access(
+                    &op.collection_name,
+                    AccessRequirements::new().manage().whole(),
+                )?;
+            }
+            CollectionMetaOperations::DeleteCollection(op) => {
+                self.check_collection_access(&op.0, AccessRequirements::new().manage().whole())?;
+            }
+            CollectionMetaOperations::RenameCollection(op) => {
+                // Both names must be manageable, so that the collection can't be moved
+                // out of the scope of the access
+                self.check_collection_access(
+                    &op.collection_name,
+                    AccessRequirements::new().manage().whole(),
+                )?;
+                self.check_collection_access(
+                    &op.rename_collection.new_collection_name,
+                    AccessRequirements::new().manage().whole(),
+                )?;
+            }
+            CollectionMetaOperations::CreateShardKey(op) => {
+                self.check_collection_access(
+                    &op.collection_name,
+                    AccessRequirements::new().manage().whole(),
+                )?;
+            }
+            CollectionMetaOperations::DropShardKey(op) => {
+                self.check_collection_access(
+                    &op.collection_name,
+                    AccessRequirements::new().manage().whole(),
+                )?;
+            }
+            CollectionMetaOperations::ChangeAliases(_)
             | CollectionMetaOperations::Resharding(_, _)
             | CollectionMetaOperations::TransferShard(_, _)
-            | CollectionMetaOperations::SetShardReplicaState(_)
-            | CollectionMetaOperations::CreateShardKey(_)
-            | CollectionMetaOperations::DropShardKey(_) => {
+            | CollectionMetaOperations::SetShardReplicaState(_) => {
                 self.check_global_access(AccessRequirements::new().manage())?;
             }
             CollectionMetaOperations::CreatePayloadIndex(op) => {
@@ -111,13 +156,107 @@ impl CollectionAccessList {
 }
 
 impl<'a> CollectionAccessView<'a> {
+    /// Filter, which limits the access to a subset of points, if any
+    fn restriction_filter(&self) -> Option<Filter> {
+        let payload_filter = self.payload.as_ref().map(PayloadConstraint::to_filter);
+        // Nest the mandatory filter, so that its `should` clauses are not mixed with the ones of
+        // the filter it is merged into
+        let filter = self
+            .filter
+            .as_ref()
+            .map(|filter| Filter::new_must(Condition::Filter(filter.clone())));
+        [payload_filter, filter, self.tenant_filter()]
+            .into_iter()
+            .flatten()
+            .reduce(Filter::merge_owned)
+    }
+
+    /// Filter by the tenant field of the collection, if the access is limited to a tenant
+    fn tenant_filter(&self) -> Option<Filter> {
+        let (Some(tenant), Some(tenant_key)) = (self.tenant, self.tenant_key) else {
+            return None;
+        };
+        let condition = FieldCondition::new_match(
+            tenant_key.clone(),
+            Match::new_value(ValueVariants::Keyword(tenant.to_string())),
+        );
+        Some(Filter::new_must(Condition::Field(condition)))
+    }
+
+    /// Upserts are allowed, if the access is limited by the tenant only, and all points belong
+    /// to the tenant. Existing points of other tenants with the same IDs are left untouched.
+    fn check_upsert(&self, op: &mut PointOperations) -> Result<(), StorageError> {
+        if !self.is_restricted() {
+            return Ok(());
+        }
+        let (Some(tenant), Some(tenant_key), None, None) =
+            (self.tenant, self.tenant_key, self.payload, self.filter)
+        else {
+            return incompatible_with_payload_constraint(self.collection);
+        };
+
+        let points_op = match op {
+            PointOperations::UpsertPoints(points_op) => &*points_op,
+            PointOperations::UpsertPointsConditional(operation) => {
+                if operation.if_version.is_some() {
+                    // Versions of points of other tenants must not be revealed
+                    return incompatible_with_payload_constraint(self.collection);
+                }
+                &operation.points_op
+            }
+            _ => return Ok(()),
+        };
+        for payload in points_op.payloads() {
+            let belongs_to_tenant = payload.map_or(false, |payload| {
+                match tenant_key.value_get(&payload.0).as_slice() {
+                    [serde_json::Value::String(value)] => value == tenant,
+                    _ => false,
+                }
+            });
+            if !belongs_to_tenant {
+                return Err(StorageError::forbidden(format!(
+                    "All points must belong to tenant {tenant} in field {tenant_key}",
+                )));
+            }
+        }
+
+        let restriction = self.tenant_filter();
+        match op {
+            PointOperations::UpsertPoints(points_op) => {
+                let points_op =
+                    std::mem::replace(points_op, PointInsertOperationsInternal::PointsList(vec![]));
+                let operation = ConditionalInsertOperationInternal {
+                    points_op,
+                    update_mode: UpdateMode::default(),
+                    update_filter: restriction,
+                    if_version: None,
+                    dedup_key: None,
+                };
+                *op = PointOperations::UpsertPointsConditional(operation);
+            }
+            PointOperations::UpsertPointsConditional(operation) => {
+                operation.update_filter = match (take(&mut operation.update_filter), restriction) {
+                    (Some(filter), Some(restriction)) => Some(filter.merge_owned(restriction)),
+                    (filter, restriction) => filter.or(restriction),
+                };
+            }
+            _ => (),
+        }
+        Ok(())
+    }
+
     fn apply_filter(&self, filter: &mut Option<Filter>) {
-        if let Some(payload) = &self.payload {
+        if let Some(restriction) = self.restriction_filter() {
             let f = filter.get_or_insert_with(Default::default);
-            *f = take(f).merge_owned(payload.to_filter());
+            *f = take(f).merge_owned(restriction);
         }
     }
 
+    fn make_payload(&self) -> Result<Payload, StorageError> {
+        // TODO: We need to construct a payload, then validate it against the claim
+        incompatible_with_payload_constraint(self.collection) // Reject as not implemented
+    }
+
     fn check_recommend_example(&self, example: &RecommendExample) -> Result<(), StorageError> {
         match example {
             RecommendExample::PointId(_) => self.check_whole_access(),
@@ -162,6 +301,7 @@ impl CheckableCollectionOperation for RecommendRequestInternal {
             write: false,
             manage: false,
             whole: false,
+            snapshot: None,
         }
     }
 
@@ -188,6 +328,7 @@ impl CheckableCollectionOperation for PointRequestInternal {
             write: false,
             manage: false,
             whole: true,
+            snapshot: None,
         }
     }
 
@@ -206,6 +347,7 @@ impl CheckableCollectionOperation for CoreSearchRequest {
             write: false,
             manage: false,
             whole: false,
+            snapshot: None,
         }
     }
 
@@ -225,6 +367,7 @@ impl CheckableCollectionOperation for CountRequestInternal {
             write: false,
             manage: false,
             whole: false,
+            snapshot: None,
         }
     }
 
@@ -244,6 +387,7 @@ impl CheckableCollectionOperation for GroupRequest {
             write: false,
             manage: false,
             whole: false,
+            snapshot: None,
         }
     }
 
@@ -269,6 +413,7 @@ impl CheckableCollectionOperation for DiscoverRequestInternal {
             write: false,
             manage: false,
             whole: false,
+            snapshot: None,
         }
     }
 
@@ -297,6 +442,7 @@ impl CheckableCollectionOperation for ScrollRequestInternal {
             write: false,
             manage: false,
             whole: false,
+            snapshot: None,
         }
     }
 
@@ -316,6 +462,7 @@ impl CheckableCollectionOperation for CollectionQueryRequest {
             write: false,
             manage: false,
             whole: false,
+            snapshot: None,
         }
     }
 
@@ -366,15 +513,18 @@ impl CheckableCollectionOperation for CollectionUpdateOperations {
         match self {
             CollectionUpdateOperations::PointOperation(_)
             | CollectionUpdateOperations::VectorOperation(_)
-            | CollectionUpdateOperations::PayloadOperation(_) => AccessRequirements {
+            | CollectionUpdateOperations::PayloadOperation(_)
+            | CollectionUpdateOperations::BlobOperation(_) => AccessRequirements {
                 write: true,
                 manage: false,
                 whole: false, // Checked in `check_access()`
+                snapshot: None,
             },
             CollectionUpdateOperations::FieldIndexOperation(_) => AccessRequirements {
                 write: true,
                 manage: true,
                 whole: true,
+                snapshot: None,
             },
         }
     }
@@ -386,19 +536,29 @@ impl CheckableCollectionOperation for CollectionUpdateOperations {
     ) -> Result<(), StorageError> {
         match self {
             CollectionUpdateOperations::PointOperation(op) => match op {
-                PointOperations::UpsertPoints(_) => {
+                PointOperations::UpsertPoints(_) | PointOperations::UpsertPointsConditional(_) => {
+                    view.check_upsert(op)?;
+                }
+                PointOperations::DeletePoints {
+                    ids: _,
+                    if_version: Some(_),
+                } => {
+                    // Version guard can't be preserved by rewriting into a filter
                     view.check_whole_access()?;
                 }
-                PointOperations::DeletePoints { ids } => {
-                    if let Some(payload) = &view.payload {
+                PointOperations::DeletePoints {
+                    ids,
+                    if_version: None,
+                } => {
+                    if let Some(restriction) = view.restriction_filter() {
                         *op = PointOperations::DeletePointsByFilter(
-                            make_filter_from_ids(take(ids)).merge_owned(payload.to_filter()),
+                            make_filter_from_ids(take(ids)).merge_owned(restriction),
                         );
                     }
                 }
                 PointOperations::DeletePointsByFilter(filter) => {
-                    if let Some(payload) = &view.payload {
-                        *filter = take(filter).merge_owned(payload.to_filter());
+                    if let Some(restriction) = view.restriction_filter() {
+                        *filter = take(filter).merge_owned(restriction);
                     }
                 }
                 PointOperations::SyncPoints(_) => {
@@ -411,27 +571,27 @@ impl CheckableCollectionOperation for CollectionUpdateOperations {
                     view.check_whole_access()?;
                 }
                 VectorOperations::DeleteVectors(PointIdsList { points, shard_key }, vectors) => {
-                    if let Some(payload) = &view.payload {
+                    if let Some(restriction) = view.restriction_filter() {
                         if shard_key.is_some() {
                             // It is unclear where to put the shard_key
                             return incompatible_with_payload_constraint(view.collection);
                         }
                         *op = VectorOperations::DeleteVectorsByFilter(
-                            make_filter_from_ids(take(points)).merge_owned(payload.to_filter()),
+                            make_filter_from_ids(take(points)).merge_owned(restriction),
                             take(vectors),
                         );
                     }
                 }
                 VectorOperations::DeleteVectorsByFilter(filter, _) => {
-                    if let Some(payload) = &view.payload {
-                        *filter = take(filter).merge_owned(payload.to_filter());
+                    if let Some(restriction) = view.restriction_filter() {
+                        *filter = take(filter).merge_owned(restriction);
                     }
                 }
             },
 
             CollectionUpdateOperations::PayloadOperation(op) => 'a: {
-                let Some(payload) = &view.payload else {
-                    // Allow all operations when there is no payload constraint
+                let Some(restriction) = view.restriction_filter() else {
+                    // Allow all operations when there is no payload constraint or filter
                     break 'a;
                 };
 
@@ -465,19 +625,19 @@ impl CheckableCollectionOperation for CollectionUpdateOperations {
                     }
                     PayloadOps::ClearPayload { points } => {
                         *op = PayloadOps::OverwritePayload(SetPayloadOp {
-                            payload: payload.make_payload(view.collection)?,
+                            payload: view.make_payload()?,
                             points: None,
                             filter: Some(
-                                make_filter_from_ids(take(points)).merge_owned(payload.to_filter()),
+                                make_filter_from_ids(take(points)).merge_owned(restriction),
                             ),
                             key: None,
                         });
                     }
                     PayloadOps::ClearPayloadByFilter(filter) => {
                         *op = PayloadOps::OverwritePayload(SetPayloadOp {
-                            payload: payload.make_payload(view.collection)?,
+                            payload: view.make_payload()?,
                             points: None,
-                            filter: Some(take(filter).merge_owned(payload.to_filter())),
+                            filter: Some(take(filter).merge_owned(restriction)),
                             key: None,
                         });
                     }
@@ -492,6 +652,19 @@ impl CheckableCollectionOperation for CollectionUpdateOperations {
                             *filter = take(filter).merge_owned(make_filter_from_ids(points));
                         }
 
+                        // Reject as not implemented
+                        return incompatible_with_payload_constraint(view.collection);
+                    }
+                    PayloadOps::PatchPayload(PatchPayloadOp {
+                        patch: _, // TODO: validate
+                        points,
+                        filter,
+                    }) => {
+                        let filter = filter.get_or_insert_with(Default::default);
+                        if let Some(points) = take(points) {
+                            *filter = take(filter).merge_owned(make_filter_from_ids(points));
+                        }
+
                         // Reject as not implemented
                         return incompatible_with_payload_constraint(view.collection);
                     }
@@ -499,6 +672,11 @@ impl CheckableCollectionOperation for CollectionUpdateOperations {
             }
 
             CollectionUpdateOperations::FieldIndexOperation(_) => (),
+
+            CollectionUpdateOperations::BlobOperation(_) => {
+                // Blobs are opaque, payload constraint can't be applied to them
+                view.check_whole_access()?;
+            }
         }
         Ok(())
     }
@@ -531,19 +709,15 @@ impl PayloadConstraint {
             ..Default::default()
         }
     }
-
-    fn make_payload(&self, collection_name: &str) -> Result<Payload, StorageError> {
-        // TODO: We need to construct a payload, then validate it against the claim
-        incompatible_with_payload_constraint(collection_name) // Reject as not implemented
-    }
 }
 
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
 
-    use segment::json_path::JsonPath;
-    use segment::types::{MinShould, ValueVariants};
+    use api::rest::VectorStruct;
+    use collection::operations::point_ops::PointStruct;
+    use segment::types::MinShould;
 
     use super::*;
     use crate::rbac::{CollectionAccess, CollectionAccessMode};
@@ -557,6 +731,9 @@ mod tests {
                 "field".parse().unwrap(),
                 ValueVariants::Integer(42),
             )]))),
+            filter: None,
+            tenant: None,
+            snapshots: None,
         }]);
 
         let mut filter = None;
@@ -603,6 +780,126 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn test_apply_mandatory_filter() {
+        let tenant = |tenant: i64| {
+            Condition::Field(FieldCondition::new_match(
+                "tenant".parse().unwrap(),
+                Match::new_value(ValueVariants::Integer(tenant)),
+            ))
+        };
+        let list = CollectionAccessList(vec![CollectionAccess {
+            collection: "col".to_string(),
+            access: CollectionAccessMode::ReadWrite,
+            payload: Some(PayloadConstraint(HashMap::from([(
+                "field".parse().unwrap(),
+                ValueVariants::Integer(42),
+            )]))),
+            filter: Some(Filter::new_must(tenant(1))),
+            tenant: None,
+            snapshots: None,
+        }]);
+        let view = list.find_view("col").unwrap();
+        assert!(view.check_whole_access().is_err());
+
+        let mut filter = Some(Filter::new_must_not(tenant(2)));
+        view.apply_filter(&mut filter);
+        assert_eq!(
+            filter,
+            Some(Filter {
+                must: Some(vec![
+                    Condition::Field(FieldCondition::new_match(
+                        "field".parse().unwrap(),
+                        Match::new_value(ValueVariants::Integer(42))
+                    )),
+                    Condition::Filter(Filter::new_must(tenant(1))),
+                ]),
+                must_not: Some(vec![tenant(2)]),
+                ..Default::default()
+            })
+        );
+
+        let mut op = CollectionUpdateOperations::PointOperation(
+            PointOperations::DeletePointsByFilter(Filter::new_must_not(tenant(2))),
+        );
+        Access::Collection(list.clone())
+            .check_point_op("col", None, &mut op)
+            .unwrap();
+        let CollectionUpdateOperations::PointOperation(PointOperations::DeletePointsByFilter(
+            filter,
+        )) = op
+        else {
+            panic!("Expected delete by filter");
+        };
+        assert!(filter
+            .must
+            .unwrap()
+            .contains(&Condition::Filter(Filter::new_must(tenant(1)))));
+    }
+
+    #[test]
+    fn test_tenant_restriction() {
+        let access = Access::Collection(CollectionAccessList(vec![CollectionAccess {
+            collection: "col".to_string(),
+            access: CollectionAccessMode::ReadWrite,
+            payload: None,
+            filter: None,
+            tenant: Some("acme".to_string()),
+            snapshots: None,
+        }]));
+        let tenant_key: JsonPath = "tenant".parse().unwrap();
+        let tenant_filter = Filter::new_must(Condition::Field(FieldCondition::new_match(
+            tenant_key.clone(),
+            Match::new_value(ValueVariants::Keyword("acme".to_string())),
+        )));
+
+        let upsert = |tenant: &str| {
+            let payload: Payload =
+                serde_json::from_value(serde_json::json!({ "tenant": tenant })).unwrap();
+            CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+                PointInsertOperationsInternal::PointsList(vec![PointStruct {
+                    id: ExtendedPointId::NumId(1),
+                    vector: VectorStruct::Single(vec![0.0, 1.0]),
+                    payload: Some(payload),
+                    expires_at: None,
+                }]),
+            ))
+        };
+
+        // Collection must be multitenant
+        assert!(access
+            .check_point_op("col", None, &mut upsert("acme"))
+            .is_err());
+
+        // Upserts of other tenants are rejected
+        assert!(access
+            .check_point_op("col", Some(&tenant_key), &mut upsert("other"))
+            .is_err());
+
+        // Upserts don't update points of other tenants
+        let mut op = upsert("acme");
+        access
+            .check_point_op("col", Some(&tenant_key), &mut op)
+            .unwrap();
+        let CollectionUpdateOperations::PointOperation(PointOperations::UpsertPointsConditional(
+            operation,
+        )) = op
+        else {
+            panic!("Expected conditional upsert");
+        };
+        assert_eq!(operation.update_filter, Some(tenant_filter.clone()));
+
+        // Reads are restricted to the tenant
+        let mut request = CountRequestInternal {
+            filter: None,
+            exact: true,
+        };
+        access
+            .check_point_op("col", Some(&tenant_key), &mut request)
+            .unwrap();
+        assert_eq!(request.filter, Some(tenant_filter));
+    }
 }
 
 #[cfg(test)]
@@ -610,10 +907,14 @@ mod tests_ops {
     use std::fmt::Debug;
 
     use api::rest::{BatchVectorStruct, OrderByInterface, RecommendStrategy, VectorStruct};
+    use collection::operations::blob_ops::{
+        BlobOperations, BlobOperationsDiscriminants, DeleteBlobOp, SetBlobOp,
+    };
     use collection::operations::payload_ops::PayloadOpsDiscriminants;
     use collection::operations::point_ops::{
-        Batch, PointInsertOperationsInternal, PointInsertOperationsInternalDiscriminants,
-        PointOperationsDiscriminants, PointStruct, PointSyncOperation,
+        Batch, ConditionalInsertOperationInternal, PointInsertOperationsInternal,
+        PointInsertOperationsInternalDiscriminants, PointOperationsDiscriminants, PointStruct,
+        PointSyncOperation, UpdateMode,
     };
     use collection::operations::query_enum::QueryEnum;
     use collection::operations::types::{SearchRequestInternal, UsingVector};
@@ -625,6 +926,7 @@ mod tests_ops {
         FieldIndexOperationsDiscriminants,
     };
     use segment::data_types::vectors::NamedVectorStruct;
+    use segment::json_patch::PatchOperation;
     use segment::types::{PointIdType, SearchParams, WithPayloadInterface, WithVector};
     use strum::IntoEnumIterator as _;
 
@@ -638,7 +940,7 @@ mod tests_ops {
     ) {
         let mut op_actual = op.clone();
         access
-            .check_point_op("col", &mut op_actual)
+            .check_point_op("col", None, &mut op_actual)
             .expect("Should be allowed");
         assert_eq!(op, &op_actual, "Expected not to change");
     }
@@ -652,7 +954,7 @@ mod tests_ops {
     ) {
         let mut op_actual = op.clone();
         access
-            .check_point_op("col", &mut op_actual)
+            .check_point_op("col", None, &mut op_actual)
             .expect("Should be allowed");
         let mut op_reference = op.clone();
         rewrite(&mut op_reference);
@@ -665,7 +967,7 @@ mod tests_ops {
         access: &Access,
     ) {
         access
-            .check_point_op("col", &mut op.clone())
+            .check_point_op("col", None, &mut op.clone())
             .expect_err("Should be allowed");
     }
 
@@ -1079,6 +1381,9 @@ mod tests_ops {
             CollectionUpdateOperationsDiscriminants::FieldIndexOperation => {
                 check_collection_update_operations_field_index()
             }
+            CollectionUpdateOperationsDiscriminants::BlobOperation => {
+                check_collection_update_operations_blob()
+            }
         });
     }
 
@@ -1100,6 +1405,7 @@ mod tests_ops {
                                 id: ExtendedPointId::NumId(12345),
                                 vector: VectorStruct::Single(vec![0.0, 1.0, 2.0]),
                                 payload: None,
+                                expires_at: None,
                             }])
                         }
                     };
@@ -1111,12 +1417,40 @@ mod tests_ops {
                 }
             }
 
+            PointOperationsDiscriminants::UpsertPointsConditional => {
+                let op = CollectionUpdateOperations::PointOperation(
+                    PointOperations::UpsertPointsConditional(ConditionalInsertOperationInternal {
+                        points_op: PointInsertOperationsInternal::PointsList(vec![PointStruct {
+                            id: ExtendedPointId::NumId(12345),
+                            vector: VectorStruct::Single(vec![0.0, 1.0, 2.0]),
+                            payload: None,
+                            expires_at: None,
+                        }]),
+                        update_mode: UpdateMode::UpdateOnly,
+                        update_filter: Some(make_filter_from_ids(vec![ExtendedPointId::NumId(
+                            12345,
+                        )])),
+                        if_version: None,
+                        dedup_key: None,
+                    }),
+                );
+                assert_requires_whole_write_access(&op);
+            }
+
             PointOperationsDiscriminants::DeletePoints => {
                 let op =
                     CollectionUpdateOperations::PointOperation(PointOperations::DeletePoints {
                         ids: vec![ExtendedPointId::NumId(12345)],
+                        if_version: None,
                     });
                 check_collection_update_operations_delete_points(&op);
+
+                let op =
+                    CollectionUpdateOperations::PointOperation(PointOperations::DeletePoints {
+                        ids: vec![ExtendedPointId::NumId(12345)],
+                        if_version: Some(1),
+                    });
+                assert_requires_whole_write_access(&op);
             }
 
             PointOperationsDiscriminants::DeletePointsByFilter => {
@@ -1278,6 +1612,13 @@ mod tests_ops {
                         key: None,
                     })
                 }
+                PayloadOpsDiscriminants::PatchPayload => PayloadOps::PatchPayload(PatchPayloadOp {
+                    patch: vec![PatchOperation::Remove {
+                        path: "/path".to_string(),
+                    }],
+                    points: Some(vec![ExtendedPointId::NumId(12345)]),
+                    filter: None,
+                }),
             };
 
             let op = CollectionUpdateOperations::PayloadOperation(inner);
@@ -1315,4 +1656,24 @@ mod tests_ops {
             );
         }
     }
+
+    /// Tests for [`CollectionUpdateOperations::BlobOperation`].
+    fn check_collection_update_operations_blob() {
+        for discr in BlobOperationsDiscriminants::iter() {
+            let inner = match discr {
+                BlobOperationsDiscriminants::SetBlob => BlobOperations::SetBlob(SetBlobOp {
+                    point_id: ExtendedPointId::NumId(12345),
+                    blob: vec![1, 2, 3],
+                }),
+                BlobOperationsDiscriminants::DeleteBlob => {
+                    BlobOperations::DeleteBlob(DeleteBlobOp {
+                        point_id: ExtendedPointId::NumId(12345),
+                    })
+                }
+            };
+
+            let op = CollectionUpdateOperations::BlobOperation(inner);
+            assert_requires_whole_write_access(&op);
+        }
+    }
 }
@@ -0,0 +1,128 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use io::file_operations::{atomic_save_json, read_json};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::content_manager::errors::StorageError;
+
+pub const REVOCATIONS_FILE: &str = "revoked_credentials.json";
+
+/// Revocation of an API key or JWT
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq, Hash)]
+pub struct CredentialRevocation {
+    /// Time of the revocation, seconds since UNIX epoch
+    pub revoked_at: u64,
+    /// Revocation is dropped after this time, seconds since UNIX epoch.
+    /// Useful for JWTs, which can't be used after their expiration anyway.
+    #[serde(default)]
+    pub expires_at: Option<u64>,
+}
+
+impl CredentialRevocation {
+    pub fn is_expired(&self, now: u64) -> bool {
+        self.expires_at
+            .map_or(false, |expires_at| expires_at <= now)
+    }
+}
+
+/// Revocations by identifiers of credentials, see `credential_id` of the auth layer
+pub type RevokedCredentials = BTreeMap<String, CredentialRevocation>;
+
+/// Persists revoked credentials of the cluster. The list is assumed to be relatively small.
+/// - Reads are served from memory.
+/// - Writes are durably saved.
+#[derive(Debug)]
+pub struct RevocationList {
+    data_path: PathBuf,
+    revoked: RevokedCredentials,
+}
+
+impl RevocationList {
+    pub fn open(storage_path: &Path) -> Result<Self, StorageError> {
+        let data_path = storage_path.join(REVOCATIONS_FILE);
+        let revoked = if data_path.exists() {
+            read_json(&data_path)?
+        } else {
+            RevokedCredentials::new()
+        };
+        Ok(Self { data_path, revoked })
+    }
+
+    pub fn state(&self) -> &RevokedCredentials {
+        &self.revoked
+    }
+
+    pub fn is_revoked(&self, credential_id: &str) -> bool {
+        self.revoked
+            .get(credential_id)
+            .map_or(false, |revocation| !revocation.is_expired(now()))
+    }
+
+    /// Revoke the credential, or restore it if `revocation` is `None`.
+    ///
+    /// Expired revocations are dropped on every change.
+    pub fn set(
+        &mut self,
+        credential_id: String,
+        revocation: Option<CredentialRevocation>,
+    ) -> Result<(), StorageError> {
+        let mut revoked = self.revoked.clone();
+        match revocation {
+            Some(revocation) => revoked.insert(credential_id, revocation),
+            None => revoked.remove(&credential_id),
+        };
+        let now = now();
+        revoked.retain(|_, revocation| !revocation.is_expired(now));
+        self.replace(revoked)
+    }
+
+    pub fn replace(&mut self, revoked: RevokedCredentials) -> Result<(), StorageError> {
+        atomic_save_json(&self.data_path, &revoked)?;
+        self.revoked = revoked;
+        Ok(())
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::Builder;
+
+    use super::*;
+
+    #[test]
+    fn test_revocation_list() {
+        let dir = Builder::new().prefix("storage").tempdir().unwrap();
+        let mut list = RevocationList::open(dir.path()).unwrap();
+        assert!(!list.is_revoked("a"));
+
+        let revocation = CredentialRevocation {
+            revoked_at: 1,
+            expires_at: None,
+        };
+        list.set("a".to_string(), Some(revocation.clone())).unwrap();
+        let expired = CredentialRevocation {
+            expires_at: Some(2),
+            ..revocation
+        };
+        list.set("b".to_string(), Some(expired)).unwrap();
+        assert!(list.is_revoked("a"));
+        assert!(!list.is_revoked("b"));
+
+        // Persisted, without expired revocations
+        let mut list = RevocationList::open(dir.path()).unwrap();
+        assert!(list.is_revoked("a"));
+        assert_eq!(list.state().len(), 1);
+
+        list.set("a".to_string(), None).unwrap();
+        assert!(!RevocationList::open(dir.path()).unwrap().is_revoked("a"));
+    }
+}
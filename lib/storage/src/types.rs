@@ -1,17 +1,24 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
 use std::num::NonZeroUsize;
+use std::path::Path;
 use std::time::Duration;
 
 use chrono::{DateTime, Utc};
+use collection::common::segment_storage::SegmentStorageConfig;
 use collection::common::snapshots_manager::SnapShotsConfig;
+use collection::common::storage_encryption::StorageEncryptionConfig;
+use collection::common::wal_encryption::WalEncryptionConfig;
 use collection::config::WalConfig;
 use collection::operations::shared_storage_config::{
     SharedStorageConfig, DEFAULT_IO_SHARD_TRANSFER_LIMIT, DEFAULT_SNAPSHOTS_PATH,
 };
 use collection::operations::types::{NodeType, PeerMetadata};
 use collection::optimizers_builder::OptimizersConfig;
+use collection::shards::local_shard::wal_archive::WalArchiveConfig;
 use collection::shards::shard::PeerId;
 use collection::shards::transfer::ShardTransferMethod;
+use common::io_throttle::OptimizerIoConfig;
 use memory::madvise;
 use schemars::JsonSchema;
 use segment::common::anonymize::Anonymize;
@@ -22,6 +29,7 @@ use validator::Validate;
 
 pub type PeerAddressById = HashMap<PeerId, Uri>;
 pub type PeerMetadataById = HashMap<PeerId, PeerMetadata>;
+pub type ClusterMetadata = BTreeMap<String, ClusterMetadataEntry>;
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct PerformanceConfig {
@@ -42,6 +50,14 @@ pub struct PerformanceConfig {
     pub incoming_shard_transfers_limit: Option<usize>,
     #[serde(default = "default_io_shard_transfers_limit")]
     pub outgoing_shard_transfers_limit: Option<usize>,
+    /// Scheduling of disk IO of optimizations, to keep latency of searches low
+    #[serde(default)]
+    pub optimizer_io: OptimizerIoConfig,
+    /// Max size of vectors and indexes in RAM on this node, in megabytes.
+    /// If exceeded, vectors of the least recently searched segments are moved to disk.
+    /// If null - not limited.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_budget_mb: Option<usize>,
 }
 
 const fn default_io_shard_transfers_limit() -> Option<usize> {
@@ -58,6 +74,21 @@ pub struct StorageConfig {
     pub snapshots_path: String,
     #[serde(default)]
     pub snapshots_config: SnapShotsConfig,
+    /// Where to keep files of segments, which are not loaded on the node
+    #[serde(default)]
+    pub segment_storage: SegmentStorageConfig,
+    /// Keys to encrypt WAL of collections at rest
+    #[serde(default)]
+    pub wal_encryption: WalEncryptionConfig,
+    /// Keys to encrypt vector and payload storage of collections at rest
+    #[serde(default)]
+    pub storage_encryption: StorageEncryptionConfig,
+    /// Where to ship closed WAL segments of local shards
+    #[serde(default)]
+    pub wal_archive: WalArchiveConfig,
+    /// Node-level parameters of asynchronous replication of collections to remote clusters
+    #[serde(default)]
+    pub async_replication: AsyncReplicationSettings,
     #[validate(length(min = 1))]
     #[serde(default)]
     pub temp_path: Option<String>,
@@ -80,6 +111,13 @@ pub struct StorageConfig {
     pub handle_collection_load_errors: bool,
     #[serde(default)]
     pub async_scorer: bool,
+    /// Read payloads of the mmap payload storage with io_uring, Linux only
+    #[serde(default)]
+    pub async_payload_reads: bool,
+    /// Number of concurrent disk reads of a single async scorer.
+    /// Higher values help fast NVMe disks under concurrent search load.
+    #[serde(default)]
+    pub async_scorer_parallelism: Option<usize>,
     /// If provided - qdrant will start in recovery mode, which means that it will not accept any new data.
     /// Only collection metadata will be available, and it will only process collection delete requests.
     /// Provided value will be used error message for unavailable requests.
@@ -95,8 +133,93 @@ pub struct StorageConfig {
     pub collection: Option<CollectionConfigDefaults>,
 }
 
+/// Operations rejected by a node, while it is cut off from the consensus quorum
+///
+/// Such a node can't tell whether the rest of the cluster is still operating,
+/// so operations accepted by it may be lost or overwritten once the cluster recovers.
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum QuorumLossFencing {
+    /// Accept all operations
+    #[default]
+    None,
+    /// Reject write operations
+    Writes,
+    /// Reject both write and read operations
+    All,
+}
+
+impl QuorumLossFencing {
+    pub fn fences_writes(self) -> bool {
+        matches!(self, Self::Writes | Self::All)
+    }
+
+    pub fn fences_reads(self) -> bool {
+        matches!(self, Self::All)
+    }
+}
+
+/// Node-level parameters of asynchronous replication, see [`collection::config::AsyncReplicationConfig`]
+///
+/// API keys of remote clusters are kept in the node configuration,
+/// so they are not replicated through consensus or exposed in collection info.
+#[derive(Clone, Deserialize)]
+pub struct AsyncReplicationSettings {
+    /// How often new operations are replayed to remote clusters
+    #[serde(default = "default_async_replication_interval_sec")]
+    pub interval_sec: u64,
+    /// API key for all remote clusters
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// API keys of remote clusters of individual collections, take precedence over `api_key`
+    #[serde(default)]
+    pub collection_api_keys: HashMap<String, String>,
+}
+
+const fn default_async_replication_interval_sec() -> u64 {
+    1
+}
+
+impl Default for AsyncReplicationSettings {
+    fn default() -> Self {
+        Self {
+            interval_sec: default_async_replication_interval_sec(),
+            api_key: None,
+            collection_api_keys: HashMap::new(),
+        }
+    }
+}
+
+impl fmt::Debug for AsyncReplicationSettings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Never expose API keys in logs
+        f.debug_struct("AsyncReplicationSettings")
+            .field("interval_sec", &self.interval_sec)
+            .field("api_key", &self.api_key.as_ref().map(|_| "<redacted>"))
+            .field(
+                "collection_api_keys",
+                &self.collection_api_keys.keys().collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl AsyncReplicationSettings {
+    /// API key of the remote cluster of the given collection, if any
+    pub fn api_key_for(&self, collection_name: &str) -> Option<&str> {
+        self.collection_api_keys
+            .get(collection_name)
+            .or(self.api_key.as_ref())
+            .map(String::as_str)
+    }
+}
+
 impl StorageConfig {
-    pub fn to_shared_storage_config(&self, is_distributed: bool) -> SharedStorageConfig {
+    pub fn to_shared_storage_config(
+        &self,
+        is_distributed: bool,
+        this_peer_id: PeerId,
+    ) -> SharedStorageConfig {
         SharedStorageConfig::new(
             self.update_queue_size,
             self.node_type,
@@ -112,6 +235,15 @@ impl StorageConfig {
             self.performance.outgoing_shard_transfers_limit,
             self.snapshots_path.clone(),
             self.snapshots_config.clone(),
+            self.segment_storage
+                .clone()
+                .with_storage_path(Path::new(&self.storage_path)),
+            self.wal_encryption.clone(),
+            self.storage_encryption.clone(),
+            self.wal_archive
+                .clone()
+                .with_storage_path(Path::new(&self.storage_path))
+                .with_peer_id(this_peer_id),
         )
     }
 }
@@ -132,10 +264,29 @@ const fn default_mmap_advice() -> madvise::Advice {
 #[derive(Debug, Serialize, JsonSchema, Clone)]
 pub struct PeerInfo {
     pub uri: String,
+    /// Whether the peer votes in the consensus, learners don't
+    pub is_voter: bool,
+    /// Availability zone or rack of the peer, if configured
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub zone: Option<String>,
+    /// Labels of the peer
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub labels: BTreeMap<String, String>,
+    /// Whether the peer is in read-only mode
+    pub read_only: bool,
     // ToDo: How long ago was the last communication? In milliseconds
     // pub last_responded_millis: usize
 }
 
+/// Value of a key in the cluster metadata
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, PartialEq, Eq)]
+pub struct ClusterMetadataEntry {
+    pub value: String,
+    /// Index of the consensus operation, which set the value.
+    /// Used as the expected version in compare-and-set.
+    pub version: u64,
+}
+
 /// Summary information about the current raft state
 #[derive(Debug, Serialize, JsonSchema, Clone)]
 pub struct RaftInfo {
@@ -228,6 +379,10 @@ impl Anonymize for PeerInfo {
     fn anonymize(&self) -> Self {
         PeerInfo {
             uri: self.uri.anonymize(),
+            is_voter: self.is_voter,
+            zone: self.zone.anonymize(),
+            labels: self.labels.anonymize(),
+            read_only: self.read_only,
         }
     }
 }
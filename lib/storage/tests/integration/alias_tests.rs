@@ -2,7 +2,7 @@ use std::num::NonZeroUsize;
 use std::sync::Arc;
 
 use collection::operations::vector_params_builder::VectorParamsBuilder;
-use collection::optimizers_builder::OptimizersConfig;
+use collection::optimizers_builder::{MergePolicy, OptimizersConfig};
 use collection::shards::channel_service::ChannelService;
 use common::cpu::CpuBudget;
 use memory::madvise;
@@ -34,6 +34,9 @@ fn test_alias_operation() {
             .unwrap()
             .to_string(),
         snapshots_config: Default::default(),
+        segment_storage: Default::default(),
+        wal_encryption: Default::default(),
+        storage_encryption: Default::default(),
         temp_path: None,
         on_disk_payload: false,
         optimizers: OptimizersConfig {
@@ -45,6 +48,8 @@ fn test_alias_operation() {
             indexing_threshold: Some(100),
             flush_interval_sec: 2,
             max_optimization_threads: Some(2),
+            merge_policy: MergePolicy::SizeTiered,
+            cpu_budget: None,
         },
         wal: Default::default(),
         performance: PerformanceConfig {
@@ -55,6 +60,8 @@ fn test_alias_operation() {
             search_timeout_sec: None,
             incoming_shard_transfers_limit: Some(1),
             outgoing_shard_transfers_limit: Some(1),
+            optimizer_io: Default::default(),
+            memory_budget_mb: None,
         },
         hnsw_index: Default::default(),
         mmap_advice: madvise::Advice::Random,
@@ -63,6 +70,8 @@ fn test_alias_operation() {
         handle_collection_load_errors: false,
         recovery_mode: None,
         async_scorer: false,
+        async_payload_reads: false,
+        async_scorer_parallelism: None,
         update_concurrency: Some(NonZeroUsize::new(2).unwrap()),
         // update_concurrency: None,
         shard_transfer_method: None,
@@ -106,11 +115,21 @@ fn test_alias_operation() {
                         optimizers_config: None,
                         shard_number: Some(1),
                         on_disk_payload: None,
+                        storage_engine: None,
                         replication_factor: None,
                         write_consistency_factor: None,
                         init_from: None,
                         quantization_config: None,
                         sharding_method: None,
+                        tiering_config: None,
+                        ttl_config: None,
+                        async_replication_config: None,
+                        inference_config: None,
+                        quota_config: None,
+                        multitenancy_config: None,
+                        payload_columns: None,
+                        placement_labels: None,
+                        shard_pinning: None,
                     },
                 )),
                 FULL_ACCESS.clone(),
@@ -167,4 +186,36 @@ fn test_alias_operation() {
             ),
         )
         .unwrap();
+
+    // Failed change rolls back all other changes of the same operation
+    handle
+        .block_on(dispatcher.submit_collection_meta_op(
+            CollectionMetaOperations::ChangeAliases(ChangeAliasesOperation {
+                actions: vec![
+                    DeleteAlias {
+                        alias_name: "test_alias3".to_string(),
+                    }
+                    .into(),
+                    CreateAlias {
+                        collection_name: "test".to_string(),
+                        alias_name: "test_alias4".to_string(),
+                    }
+                    .into(),
+                    RenameAlias {
+                        old_alias_name: "missing_alias".to_string(),
+                        new_alias_name: "test_alias5".to_string(),
+                    }
+                    .into(),
+                ],
+            }),
+            FULL_ACCESS.clone(),
+            None,
+        ))
+        .unwrap_err();
+
+    let aliases = handle
+        .block_on(dispatcher.toc(&FULL_ACCESS).list_aliases(&FULL_ACCESS))
+        .unwrap();
+    assert_eq!(aliases.len(), 1);
+    assert_eq!(aliases[0].alias_name, "test_alias3");
 }
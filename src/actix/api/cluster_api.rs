@@ -1,16 +1,25 @@
 use std::future::Future;
 
-use actix_web::{delete, get, post, web, HttpResponse};
-use actix_web_validator::Query;
-use serde::Deserialize;
+use actix_web::http::header;
+use actix_web::rt::time::Instant;
+use actix_web::{delete, get, post, put, web, HttpResponse};
+use actix_web_validator::{Json, Query};
+use serde::{Deserialize, Serialize};
 use storage::content_manager::consensus_ops::ConsensusOperations;
 use storage::content_manager::errors::StorageError;
 use storage::dispatcher::Dispatcher;
 use storage::rbac::AccessRequirements;
+use tokio::sync::broadcast::error::RecvError;
 use validator::Validate;
 
 use crate::actix::auth::ActixAccess;
-use crate::actix::helpers;
+use crate::actix::helpers::{self, process_response_error};
+use crate::common::cluster_metadata::{
+    do_get_cluster_metadata, do_get_cluster_metadata_key, do_set_cluster_metadata_key,
+    SetClusterMetadata,
+};
+use crate::common::decommission::do_decommission_peer;
+use crate::common::rebalance::{do_rebalance_cluster, RebalanceRequest};
 
 #[derive(Debug, Deserialize, Validate)]
 struct QueryParams {
@@ -32,6 +41,53 @@ fn cluster_status(
     })
 }
 
+/// Stream of cluster events, in the server-sent events format
+///
+/// Each event is sent as JSON in the `data` field. If the subscriber falls behind, some events are
+/// skipped and a `lagged` event with the number of skipped events is sent instead.
+#[get("/cluster/events")]
+async fn cluster_events(
+    dispatcher: web::Data<Dispatcher>,
+    ActixAccess(access): ActixAccess,
+) -> HttpResponse {
+    if let Err(err) = access.check_global_access(AccessRequirements::new()) {
+        return process_response_error(err, Instant::now());
+    }
+
+    let Some(consensus_state) = dispatcher.consensus_state() else {
+        let err = StorageError::BadRequest {
+            description: "Distributed mode disabled.".to_string(),
+        };
+        return process_response_error(err, Instant::now());
+    };
+
+    let receiver = consensus_state.subscribe_cluster_events();
+    let events = futures::stream::unfold(receiver, |mut receiver| async move {
+        let message = match receiver.recv().await {
+            Ok(event) => server_sent_event(None, &event),
+            Err(RecvError::Lagged(skipped)) => {
+                server_sent_event(Some("lagged"), &serde_json::json!({ "skipped": skipped }))
+            }
+            Err(RecvError::Closed) => return None,
+        };
+        Some((Ok::<_, actix_web::Error>(message), receiver))
+    });
+
+    HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header((header::CACHE_CONTROL, "no-cache"))
+        .streaming(events)
+}
+
+fn server_sent_event(name: Option<&str>, data: &impl Serialize) -> web::Bytes {
+    let data = serde_json::to_string(data).unwrap_or_default();
+    let message = match name {
+        Some(name) => format!("event: {name}\ndata: {data}\n\n"),
+        None => format!("data: {data}\n\n"),
+    };
+    web::Bytes::from(message)
+}
+
 #[post("/cluster/recover")]
 fn recover_current_peer(
     dispatcher: web::Data<Dispatcher>,
@@ -81,9 +137,130 @@ fn remove_peer(
     })
 }
 
+#[derive(Debug, Deserialize, Validate)]
+struct DecommissionParams {
+    #[serde(default)]
+    #[validate(range(min = 1))]
+    timeout: Option<u64>,
+}
+
+#[post("/cluster/peer/{peer_id}/decommission")]
+fn decommission_peer(
+    dispatcher: web::Data<Dispatcher>,
+    peer_id: web::Path<u64>,
+    Query(params): Query<DecommissionParams>,
+    ActixAccess(access): ActixAccess,
+) -> impl Future<Output = HttpResponse> {
+    helpers::time(async move {
+        do_decommission_peer(
+            dispatcher.get_ref(),
+            peer_id.into_inner(),
+            access,
+            params.timeout.map(std::time::Duration::from_secs),
+        )
+        .await
+    })
+}
+
+#[post("/cluster/rebalance")]
+fn rebalance_cluster(
+    dispatcher: web::Data<Dispatcher>,
+    request: Json<RebalanceRequest>,
+    ActixAccess(access): ActixAccess,
+) -> impl Future<Output = HttpResponse> {
+    helpers::time(async move {
+        do_rebalance_cluster(dispatcher.get_ref(), request.into_inner(), access).await
+    })
+}
+
+#[get("/cluster/metadata")]
+fn get_cluster_metadata(
+    dispatcher: web::Data<Dispatcher>,
+    ActixAccess(access): ActixAccess,
+) -> impl Future<Output = HttpResponse> {
+    helpers::time(async move { do_get_cluster_metadata(dispatcher.get_ref(), access) })
+}
+
+#[get("/cluster/metadata/{key}")]
+fn get_cluster_metadata_key(
+    dispatcher: web::Data<Dispatcher>,
+    key: web::Path<String>,
+    ActixAccess(access): ActixAccess,
+) -> impl Future<Output = HttpResponse> {
+    helpers::time(async move { do_get_cluster_metadata_key(dispatcher.get_ref(), &key, access) })
+}
+
+#[derive(Debug, Deserialize, Validate)]
+struct MetadataParams {
+    #[serde(default)]
+    #[validate(range(min = 1))]
+    timeout: Option<u64>,
+}
+
+#[put("/cluster/metadata/{key}")]
+fn put_cluster_metadata_key(
+    dispatcher: web::Data<Dispatcher>,
+    key: web::Path<String>,
+    request: Json<SetClusterMetadata>,
+    Query(params): Query<MetadataParams>,
+    ActixAccess(access): ActixAccess,
+) -> impl Future<Output = HttpResponse> {
+    helpers::time(async move {
+        let SetClusterMetadata {
+            value,
+            expected_version,
+        } = request.into_inner();
+        do_set_cluster_metadata_key(
+            dispatcher.get_ref(),
+            key.into_inner(),
+            Some(value),
+            expected_version,
+            access,
+            params.timeout.map(std::time::Duration::from_secs),
+        )
+        .await
+    })
+}
+
+#[derive(Debug, Deserialize, Validate)]
+struct DeleteMetadataParams {
+    /// Delete the key only if its current version matches
+    expected_version: Option<u64>,
+    #[serde(default)]
+    #[validate(range(min = 1))]
+    timeout: Option<u64>,
+}
+
+#[delete("/cluster/metadata/{key}")]
+fn delete_cluster_metadata_key(
+    dispatcher: web::Data<Dispatcher>,
+    key: web::Path<String>,
+    Query(params): Query<DeleteMetadataParams>,
+    ActixAccess(access): ActixAccess,
+) -> impl Future<Output = HttpResponse> {
+    helpers::time(async move {
+        do_set_cluster_metadata_key(
+            dispatcher.get_ref(),
+            key.into_inner(),
+            None,
+            params.expected_version,
+            access,
+            params.timeout.map(std::time::Duration::from_secs),
+        )
+        .await
+    })
+}
+
 // Configure services
 pub fn config_cluster_api(cfg: &mut web::ServiceConfig) {
     cfg.service(cluster_status)
+        .service(cluster_events)
         .service(remove_peer)
+        .service(decommission_peer)
+        .service(rebalance_cluster)
+        .service(get_cluster_metadata)
+        .service(get_cluster_metadata_key)
+        .service(put_cluster_metadata_key)
+        .service(delete_cluster_metadata_key)
         .service(recover_current_peer);
 }
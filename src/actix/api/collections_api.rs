@@ -5,18 +5,23 @@ use actix_web::rt::time::Instant;
 use actix_web::{delete, get, patch, post, put, web, HttpResponse, Responder};
 use actix_web_validator::{Json, Path, Query};
 use collection::operations::cluster_ops::ClusterOperations;
+use collection::operations::types::VacuumRequest;
+use collection::shards::shard::ShardId;
 use serde::Deserialize;
 use storage::content_manager::collection_meta_ops::{
-    ChangeAliasesOperation, CollectionMetaOperations, CreateCollection, CreateCollectionOperation,
-    DeleteCollectionOperation, UpdateCollection, UpdateCollectionOperation,
+    ChangeAliasesOperation, CloneCollection, CollectionMetaOperations, CreateCollection,
+    CreateCollectionOperation, DeleteCollectionOperation, RenameCollection,
+    RenameCollectionOperation, UpdateCollection, UpdateCollectionOperation,
 };
 use storage::dispatcher::Dispatcher;
 use validator::Validate;
 
+use super::operations_api::AsyncParam;
 use super::CollectionPath;
 use crate::actix::api::StrictCollectionPath;
 use crate::actix::auth::ActixAccess;
 use crate::actix::helpers::{self, process_response};
+use crate::common::async_operations::AsyncOperations;
 use crate::common::collections::*;
 
 #[derive(Debug, Deserialize, Validate)]
@@ -31,6 +36,18 @@ impl WaitTimeout {
     }
 }
 
+#[derive(Debug, Deserialize, Validate)]
+pub struct EventsParams {
+    #[validate(range(min = 1))]
+    limit: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct OptimizeParams {
+    /// Optimize only this local shard
+    shard_id: Option<ShardId>,
+}
+
 #[get("/collections")]
 fn get_collections(
     dispatcher: web::Data<Dispatcher>,
@@ -148,6 +165,48 @@ async fn delete_collection(
     process_response(response, timing)
 }
 
+#[post("/collections/{name}/rename")]
+async fn rename_collection(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    operation: Json<RenameCollection>,
+    Query(query): Query<WaitTimeout>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = dispatcher
+        .submit_collection_meta_op(
+            CollectionMetaOperations::RenameCollection(RenameCollectionOperation::new(
+                collection.name.clone(),
+                operation.into_inner(),
+            )),
+            access,
+            query.timeout(),
+        )
+        .await;
+    process_response(response, timing)
+}
+
+#[post("/collections/{name}/clone")]
+async fn clone_collection(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    operation: Json<CloneCollection>,
+    Query(query): Query<WaitTimeout>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = do_clone_collection(
+        &dispatcher.into_inner(),
+        collection.name.clone(),
+        operation.into_inner(),
+        access,
+        query.timeout(),
+    )
+    .await;
+    process_response(response, timing)
+}
+
 #[post("/collections/aliases")]
 async fn update_aliases(
     dispatcher: web::Data<Dispatcher>,
@@ -199,6 +258,138 @@ async fn update_collection_cluster(
     process_response(response, timing)
 }
 
+#[get("/collections/{name}/points_breakdown")]
+async fn get_points_breakdown(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response =
+        do_get_collection_points_breakdown(dispatcher.toc(&access), access, &collection.name).await;
+    process_response(response, timing)
+}
+
+#[get("/collections/{name}/disk_usage")]
+async fn get_disk_usage(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response =
+        do_get_collection_disk_usage(dispatcher.toc(&access), access, &collection.name).await;
+    process_response(response, timing)
+}
+
+#[get("/collections/{name}/index_info")]
+async fn get_index_info(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response =
+        do_get_collection_index_info(dispatcher.toc(&access), access, &collection.name).await;
+    process_response(response, timing)
+}
+
+#[get("/collections/{name}/events")]
+async fn get_events(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    params: Query<EventsParams>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = do_get_collection_events(
+        dispatcher.toc(&access),
+        access,
+        &collection.name,
+        params.limit,
+    )
+    .await;
+    process_response(response, timing)
+}
+
+#[post("/collections/{name}/optimize")]
+async fn force_optimize(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    params: Query<OptimizeParams>,
+    operations: web::Data<AsyncOperations>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = do_force_optimize(
+        dispatcher.toc(&access),
+        access,
+        &collection.name,
+        params.shard_id,
+        &operations,
+    )
+    .await;
+    process_response(response, timing)
+}
+
+#[post("/collections/{name}/vacuum")]
+async fn vacuum_collection(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<VacuumRequest>,
+    async_param: Query<AsyncParam>,
+    operations: web::Data<AsyncOperations>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let deleted_threshold = request.into_inner().deleted_threshold;
+
+    if async_param.is_async() {
+        let response = do_vacuum_collection_async(
+            dispatcher.toc(&access),
+            access,
+            &collection.name,
+            deleted_threshold,
+            &operations,
+        )
+        .await;
+        return process_response(response, timing);
+    }
+
+    let response = do_vacuum_collection(
+        dispatcher.toc(&access),
+        access,
+        &collection.name,
+        deleted_threshold,
+    )
+    .await;
+    process_response(response, timing)
+}
+
+#[get("/collections/{name}/quota")]
+async fn get_quota_usage(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response =
+        do_get_collection_quota_usage(dispatcher.toc(&access), access, &collection.name).await;
+    process_response(response, timing)
+}
+
+#[get("/collections/{name}/async_replication")]
+async fn get_async_replication_status(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response =
+        do_get_async_replication_status(dispatcher.toc(&access), access, &collection.name).await;
+    process_response(response, timing)
+}
+
 // Configure services
 pub fn config_collections_api(cfg: &mut web::ServiceConfig) {
     // Ordering of services is important for correct path pattern matching
@@ -210,10 +401,20 @@ pub fn config_collections_api(cfg: &mut web::ServiceConfig) {
         .service(create_collection)
         .service(update_collection)
         .service(delete_collection)
+        .service(rename_collection)
+        .service(clone_collection)
         .service(get_aliases)
         .service(get_collection_aliases)
         .service(get_cluster_info)
-        .service(update_collection_cluster);
+        .service(update_collection_cluster)
+        .service(get_points_breakdown)
+        .service(get_disk_usage)
+        .service(get_index_info)
+        .service(get_events)
+        .service(force_optimize)
+        .service(vacuum_collection)
+        .service(get_quota_usage)
+        .service(get_async_replication_status);
 }
 
 #[cfg(test)]
@@ -3,6 +3,7 @@ pub mod collections_api;
 pub mod count_api;
 pub mod discovery_api;
 pub mod issues_api;
+pub mod operations_api;
 pub mod read_params;
 pub mod recommend_api;
 pub mod retrieve_api;
@@ -13,7 +14,9 @@ pub mod snapshot_api;
 pub mod update_api;
 
 use common::validation::validate_collection_name;
+use segment::types::PointIdType;
 use serde::Deserialize;
+use storage::content_manager::errors::StorageError;
 use validator::Validate;
 
 /// A collection path with stricter validation
@@ -37,3 +40,18 @@ struct CollectionPath {
     #[validate(length(min = 1, max = 255))]
     name: String,
 }
+
+#[derive(Deserialize, Validate)]
+struct PointPath {
+    #[validate(length(min = 1))]
+    // TODO: validate this is a valid ID type (usize or UUID)? Does currently error on deserialize.
+    id: String,
+}
+
+impl PointPath {
+    fn point_id(&self) -> Result<PointIdType, StorageError> {
+        self.id.parse().map_err(|_| StorageError::BadInput {
+            description: format!("Can not recognize \"{}\" as point id", self.id),
+        })
+    }
+}
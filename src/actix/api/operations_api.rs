@@ -0,0 +1,55 @@
+use actix_web::rt::time::Instant;
+use actix_web::{get, web, Responder};
+use actix_web_validator::Path;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::actix::auth::ActixAccess;
+use crate::actix::helpers::process_response;
+use crate::common::async_operations::AsyncOperations;
+
+#[derive(Deserialize, Serialize, JsonSchema, Validate)]
+pub struct AsyncParam {
+    /// If true, the operation is executed in background and its ID is returned immediately.
+    /// Status and outcome of the operation are available at `/operations/{operation_id}`
+    #[serde(rename = "async")]
+    pub run_async: Option<bool>,
+}
+
+impl AsyncParam {
+    pub fn is_async(&self) -> bool {
+        self.run_async.unwrap_or(false)
+    }
+}
+
+#[derive(Deserialize, Validate)]
+struct OperationPath {
+    operation_id: Uuid,
+}
+
+#[get("/operations")]
+async fn list_operations(
+    operations: web::Data<AsyncOperations>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    process_response(Ok(operations.list(&access)), timing)
+}
+
+#[get("/operations/{operation_id}")]
+async fn get_operation(
+    operations: web::Data<AsyncOperations>,
+    path: Path<OperationPath>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let response = operations.get(path.operation_id, &access);
+    process_response(response, timing)
+}
+
+// Configure services
+pub fn config_operations_api(cfg: &mut web::ServiceConfig) {
+    cfg.service(list_operations).service(get_operation);
+}
@@ -13,6 +13,10 @@ pub struct ReadParams {
     pub consistency: Option<ReadConsistency>,
     /// If set, overrides global timeout for this request. Unit is seconds.
     pub timeout: Option<NonZeroU64>,
+    /// If set, resources used by the request are reported in the response.
+    /// Supported by search and scroll.
+    #[serde(default)]
+    pub usage: bool,
 }
 
 impl ReadParams {
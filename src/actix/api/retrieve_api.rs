@@ -1,31 +1,29 @@
 use actix_web::rt::time::Instant;
-use actix_web::{get, post, web, Responder};
+use actix_web::{get, post, web, HttpResponse, Responder};
 use actix_web_validator::{Json, Path, Query};
+use collection::common::request_usage;
 use collection::operations::consistency_params::ReadConsistency;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{PointRequest, PointRequestInternal, Record, ScrollRequest};
 use itertools::Itertools;
 use segment::types::{PointIdType, WithPayloadInterface};
 use serde::Deserialize;
+use storage::content_manager::blob::stream_blob;
 use storage::content_manager::errors::StorageError;
+use storage::content_manager::export::{export_points as do_export_points, ExportPoints};
 use storage::content_manager::toc::TableOfContent;
 use storage::dispatcher::Dispatcher;
 use storage::rbac::Access;
 use validator::Validate;
 
 use super::read_params::ReadParams;
-use super::CollectionPath;
+use super::{CollectionPath, PointPath};
 use crate::actix::auth::ActixAccess;
-use crate::actix::helpers::{self, process_response};
+use crate::actix::helpers::{
+    self, process_response, process_response_error, process_response_with_usage,
+};
 use crate::common::points::do_get_points;
 
-#[derive(Deserialize, Validate)]
-struct PointPath {
-    #[validate(length(min = 1))]
-    // TODO: validate this is a valid ID type (usize or UUID)? Does currently error on deserialize.
-    id: String,
-}
-
 async fn do_get_point(
     toc: &TableOfContent,
     collection_name: &str,
@@ -61,9 +59,7 @@ async fn get_point(
     ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     helpers::time(async move {
-        let point_id: PointIdType = point.id.parse().map_err(|_| StorageError::BadInput {
-            description: format!("Can not recognize \"{}\" as point id", point.id),
-        })?;
+        let point_id = point.point_id()?;
 
         let Some(record) = do_get_point(
             dispatcher.toc(&access),
@@ -84,6 +80,51 @@ async fn get_point(
     .await
 }
 
+/// Stream the blob attached to the point as raw bytes
+///
+/// If reading fails midway, the response is cut short.
+#[get("/collections/{name}/points/{id}/blob")]
+async fn get_point_blob(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    point: Path<PointPath>,
+    ActixAccess(access): ActixAccess,
+) -> HttpResponse {
+    let timing = Instant::now();
+
+    let point_id = match point.point_id() {
+        Ok(point_id) => point_id,
+        Err(err) => return process_response_error(err, timing),
+    };
+
+    let blob = match stream_blob(
+        dispatcher.toc(&access).clone(),
+        collection.into_inner().name,
+        point_id,
+        ShardSelectorInternal::All,
+        access,
+    )
+    .await
+    {
+        Ok(blob) => blob,
+        Err(err) => return process_response_error(err, timing),
+    };
+
+    let chunks = futures::stream::unfold(blob.chunks, |mut receiver| async move {
+        let chunk = receiver
+            .recv()
+            .await?
+            .map(web::Bytes::from)
+            .map_err(|err| actix_web::Error::from(helpers::HttpError::from(err)));
+        Some((chunk, receiver))
+    });
+
+    HttpResponse::Ok()
+        .content_type("application/octet-stream")
+        .no_chunking(blob.total_len as u64)
+        .streaming(chunks)
+}
+
 #[post("/collections/{name}/points")]
 async fn get_points(
     dispatcher: web::Data<Dispatcher>,
@@ -137,17 +178,56 @@ async fn scroll_points(
         Some(shard_keys) => ShardSelectorInternal::from(shard_keys),
     };
 
-    let response = dispatcher
-        .toc(&access)
-        .scroll(
-            &collection.name,
-            scroll_request,
-            params.consistency,
-            // TODO: handle params.timeout
-            shard_selection,
-            access,
-        )
-        .await;
+    let scroll = dispatcher.toc(&access).scroll(
+        &collection.name,
+        scroll_request,
+        params.consistency,
+        // TODO: handle params.timeout
+        shard_selection,
+        access,
+    );
+    let (response, usage) = request_usage::measure(params.usage, scroll).await;
 
-    process_response(response, timing)
+    process_response_with_usage(response, timing, usage)
+}
+
+/// Stream all points of the collection, or the ones matching a filter, as a file
+///
+/// If the export fails midway, the response is cut short.
+#[post("/collections/{name}/points/export")]
+async fn export_points(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<ExportPoints>,
+    params: Query<ReadParams>,
+    ActixAccess(access): ActixAccess,
+) -> HttpResponse {
+    let request = request.into_inner();
+    let format = request.format;
+
+    let receiver = match do_export_points(
+        dispatcher.toc(&access).clone(),
+        collection.name.clone(),
+        request,
+        params.consistency,
+        access,
+    )
+    .await
+    {
+        Ok(receiver) => receiver,
+        Err(err) => return process_response_error(err, Instant::now()),
+    };
+
+    let chunks = futures::stream::unfold(receiver, |mut receiver| async move {
+        let chunk = receiver
+            .recv()
+            .await?
+            .map(web::Bytes::from)
+            .map_err(|err| actix_web::Error::from(helpers::HttpError::from(err)));
+        Some((chunk, receiver))
+    });
+
+    HttpResponse::Ok()
+        .content_type(format.content_type())
+        .streaming(chunks)
 }
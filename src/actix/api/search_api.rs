@@ -1,24 +1,30 @@
 use actix_web::rt::time::Instant;
 use actix_web::{post, web, Responder};
 use actix_web_validator::{Json, Path, Query};
+use collection::common::request_usage;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{
     CoreSearchRequest, SearchGroupsRequest, SearchRequest, SearchRequestBatch,
 };
 use itertools::Itertools;
+use storage::content_manager::errors::StorageError;
 use storage::dispatcher::Dispatcher;
 
 use super::read_params::ReadParams;
 use super::CollectionPath;
 use crate::actix::auth::ActixAccess;
-use crate::actix::helpers::process_response;
+use crate::actix::helpers::{process_response, process_response_with_usage};
+use crate::common::http_client::HttpClient;
+use crate::common::inference::InferenceBatch;
 use crate::common::points::{
-    do_core_search_points, do_search_batch_points, do_search_point_groups,
+    do_core_search_points, do_read_batch_points, do_search_batch_points, do_search_point_groups,
+    ReadOperations,
 };
 
 #[post("/collections/{name}/points/search")]
 async fn search_points(
     dispatcher: web::Data<Dispatcher>,
+    http_client: web::Data<HttpClient>,
     collection: Path<CollectionPath>,
     request: Json<SearchRequest>,
     params: Query<ReadParams>,
@@ -27,7 +33,7 @@ async fn search_points(
     let timing = Instant::now();
 
     let SearchRequest {
-        search_request,
+        mut search_request,
         shard_key,
     } = request.into_inner();
 
@@ -36,29 +42,42 @@ async fn search_points(
         Some(shard_keys) => shard_keys.into(),
     };
 
-    let response = do_core_search_points(
-        dispatcher.toc(&access),
-        &collection.name,
-        search_request.into(),
-        params.consistency,
-        shard_selection,
-        access,
-        params.timeout(),
-    )
-    .await
-    .map(|scored_points| {
+    let toc = dispatcher.toc(&access);
+
+    let response = async {
+        let mut inference = InferenceBatch::default();
+        inference.add_query(&mut search_request.vector);
+        inference
+            .infer(toc, &collection.name, &access, &http_client)
+            .await?;
+
+        do_core_search_points(
+            toc,
+            &collection.name,
+            CoreSearchRequest::try_from(search_request)?,
+            params.consistency,
+            shard_selection,
+            access,
+            params.timeout(),
+        )
+        .await
+    };
+
+    let (response, usage) = request_usage::measure(params.usage, response).await;
+    let response = response.map(|scored_points| {
         scored_points
             .into_iter()
             .map(api::rest::ScoredPoint::from)
             .collect_vec()
     });
 
-    process_response(response, timing)
+    process_response_with_usage(response, timing, usage)
 }
 
 #[post("/collections/{name}/points/search/batch")]
 async fn batch_search_points(
     dispatcher: web::Data<Dispatcher>,
+    http_client: web::Data<HttpClient>,
     collection: Path<CollectionPath>,
     request: Json<SearchRequestBatch>,
     params: Query<ReadParams>,
@@ -66,33 +85,46 @@ async fn batch_search_points(
 ) -> impl Responder {
     let timing = Instant::now();
 
-    let request = request.into_inner();
-    let requests = request
-        .searches
-        .into_iter()
-        .map(|req| {
-            let SearchRequest {
-                search_request,
-                shard_key,
-            } = req;
-            let shard_selection = match shard_key {
-                None => ShardSelectorInternal::All,
-                Some(shard_keys) => shard_keys.into(),
-            };
-            let core_request: CoreSearchRequest = search_request.into();
-
-            (core_request, shard_selection)
-        })
-        .collect();
-
-    let response = do_search_batch_points(
-        dispatcher.toc(&access),
-        &collection.name,
-        requests,
-        params.consistency,
-        access,
-        params.timeout(),
-    )
+    let mut request = request.into_inner();
+    let toc = dispatcher.toc(&access);
+
+    let response = async {
+        let mut inference = InferenceBatch::default();
+        for search in &mut request.searches {
+            inference.add_query(&mut search.search_request.vector);
+        }
+        inference
+            .infer(toc, &collection.name, &access, &http_client)
+            .await?;
+
+        let requests = request
+            .searches
+            .into_iter()
+            .map(|req| {
+                let SearchRequest {
+                    search_request,
+                    shard_key,
+                } = req;
+                let shard_selection = match shard_key {
+                    None => ShardSelectorInternal::All,
+                    Some(shard_keys) => shard_keys.into(),
+                };
+                let core_request = CoreSearchRequest::try_from(search_request)?;
+
+                Ok((core_request, shard_selection))
+            })
+            .collect::<Result<_, StorageError>>()?;
+
+        do_search_batch_points(
+            toc,
+            &collection.name,
+            requests,
+            params.consistency,
+            access,
+            params.timeout(),
+        )
+        .await
+    }
     .await
     .map(|batch_scored_points| {
         batch_scored_points
@@ -112,6 +144,7 @@ async fn batch_search_points(
 #[post("/collections/{name}/points/search/groups")]
 async fn search_point_groups(
     dispatcher: web::Data<Dispatcher>,
+    http_client: web::Data<HttpClient>,
     collection: Path<CollectionPath>,
     request: Json<SearchGroupsRequest>,
     params: Query<ReadParams>,
@@ -120,7 +153,7 @@ async fn search_point_groups(
     let timing = Instant::now();
 
     let SearchGroupsRequest {
-        search_group_request,
+        mut search_group_request,
         shard_key,
     } = request.into_inner();
 
@@ -129,23 +162,73 @@ async fn search_point_groups(
         Some(shard_keys) => shard_keys.into(),
     };
 
-    let response = do_search_point_groups(
-        dispatcher.toc(&access),
-        &collection.name,
-        search_group_request,
-        params.consistency,
-        shard_selection,
-        access,
-        params.timeout(),
-    )
+    let toc = dispatcher.toc(&access);
+
+    let response = async {
+        let mut inference = InferenceBatch::default();
+        inference.add_query(&mut search_group_request.vector);
+        inference
+            .infer(toc, &collection.name, &access, &http_client)
+            .await?;
+
+        do_search_point_groups(
+            toc,
+            &collection.name,
+            search_group_request,
+            params.consistency,
+            shard_selection,
+            access,
+            params.timeout(),
+        )
+        .await
+    }
     .await;
 
     process_response(response, timing)
 }
 
 // Configure services
+#[post("/collections/{name}/points/read/batch")]
+async fn read_batch_points(
+    dispatcher: web::Data<Dispatcher>,
+    http_client: web::Data<HttpClient>,
+    collection: Path<CollectionPath>,
+    operations: Json<ReadOperations>,
+    params: Query<ReadParams>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let mut operations = operations.into_inner();
+    let toc = dispatcher.toc(&access);
+
+    let response = async {
+        let mut inference = InferenceBatch::default();
+        for operation in &mut operations.operations {
+            operation.collect_inference_inputs(&mut inference);
+        }
+        inference
+            .infer(toc, &collection.name, &access, &http_client)
+            .await?;
+
+        do_read_batch_points(
+            toc,
+            &collection.name,
+            operations,
+            params.consistency,
+            access,
+            params.timeout(),
+        )
+        .await
+    }
+    .await;
+
+    process_response(response, timing)
+}
+
 pub fn config_search_api(cfg: &mut web::ServiceConfig) {
     cfg.service(search_points)
         .service(batch_search_points)
-        .service(search_point_groups);
+        .service(search_point_groups)
+        .service(read_batch_points);
 }
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::future::Future;
 use std::sync::Arc;
 
@@ -5,7 +6,7 @@ use actix_web::http::header::ContentType;
 use actix_web::http::StatusCode;
 use actix_web::rt::time::Instant;
 use actix_web::web::Query;
-use actix_web::{get, post, web, HttpResponse, Responder};
+use actix_web::{delete, get, post, put, web, HttpResponse, Responder};
 use actix_web_validator::Json;
 use common::types::{DetailsLevel, TelemetryDetail};
 use schemars::JsonSchema;
@@ -18,11 +19,18 @@ use tokio::sync::Mutex;
 
 use crate::actix::auth::ActixAccess;
 use crate::actix::helpers::{self, process_response_error};
+use crate::common::auth::revocations::{
+    do_get_revoked_credentials, do_restore_credential, do_revoke_credential, RevokeCredential,
+};
+use crate::common::effective_config::do_get_effective_config;
 use crate::common::health;
-use crate::common::helpers::LocksOption;
+use crate::common::helpers::{LocksOption, ReadOnlyOption};
 use crate::common::metrics::MetricsData;
+use crate::common::profiling::{capture_cpu_profile, ProfileParams};
+use crate::common::rolling_restart::{do_get_restart_status, do_set_cordon};
 use crate::common::stacktrace::get_stack_trace;
 use crate::common::telemetry::TelemetryCollector;
+use crate::settings::Settings;
 use crate::tracing;
 
 #[derive(Deserialize, Serialize, JsonSchema)]
@@ -61,6 +69,8 @@ fn telemetry(
 #[derive(Deserialize, Serialize, JsonSchema)]
 pub struct MetricsParam {
     pub anonymize: Option<bool>,
+    /// Add sizes and index status of each local shard of collections
+    pub per_shard: Option<bool>,
 }
 
 #[get("/metrics")]
@@ -74,12 +84,13 @@ async fn metrics(
     }
 
     let anonymize = params.anonymize.unwrap_or(false);
+    let per_shard = params.per_shard.unwrap_or(false);
     let telemetry_collector = telemetry_collector.lock().await;
     let telemetry_data = telemetry_collector
         .prepare_data(
             &access,
             TelemetryDetail {
-                level: DetailsLevel::Level1,
+                level: DetailsLevel::Level2,
                 histograms: true,
             },
         )
@@ -92,7 +103,7 @@ async fn metrics(
 
     HttpResponse::Ok()
         .content_type(ContentType::plaintext())
-        .body(MetricsData::from(telemetry_data).format_metrics())
+        .body(MetricsData::new(&telemetry_data, per_shard).format_metrics())
 }
 
 #[post("/locks")]
@@ -129,6 +140,116 @@ fn get_locks(
     })
 }
 
+#[post("/read_only")]
+fn put_read_only(
+    dispatcher: web::Data<Dispatcher>,
+    read_only_option: Json<ReadOnlyOption>,
+    ActixAccess(access): ActixAccess,
+) -> impl Future<Output = HttpResponse> {
+    helpers::time(async move {
+        access.check_global_access(AccessRequirements::new().manage())?;
+        let toc = dispatcher.toc(&access);
+        let result = ReadOnlyOption {
+            read_only: toc.set_read_only(read_only_option.read_only),
+        };
+        // Let other peers know about the mode as soon as possible
+        if let Some(consensus_state) = dispatcher.consensus_state() {
+            consensus_state.request_peer_metadata_update();
+        }
+        Ok(result)
+    })
+}
+
+#[get("/read_only")]
+fn get_read_only(
+    dispatcher: web::Data<Dispatcher>,
+    ActixAccess(access): ActixAccess,
+) -> impl Future<Output = HttpResponse> {
+    helpers::time(async move {
+        access.check_global_access(AccessRequirements::new())?;
+        let toc = dispatcher.toc(&access);
+        Ok(ReadOnlyOption {
+            read_only: toc.is_read_only(),
+        })
+    })
+}
+
+#[get("/restart")]
+fn get_restart_status(
+    dispatcher: web::Data<Dispatcher>,
+    ActixAccess(access): ActixAccess,
+) -> impl Future<Output = HttpResponse> {
+    helpers::time(async move { do_get_restart_status(dispatcher.get_ref(), access).await })
+}
+
+#[post("/restart/cordon")]
+fn cordon(
+    dispatcher: web::Data<Dispatcher>,
+    ActixAccess(access): ActixAccess,
+) -> impl Future<Output = HttpResponse> {
+    helpers::time(async move { do_set_cordon(dispatcher.get_ref(), true, access).await })
+}
+
+#[post("/restart/uncordon")]
+fn uncordon(
+    dispatcher: web::Data<Dispatcher>,
+    ActixAccess(access): ActixAccess,
+) -> impl Future<Output = HttpResponse> {
+    helpers::time(async move { do_set_cordon(dispatcher.get_ref(), false, access).await })
+}
+
+#[get("/auth/revocations")]
+fn get_revoked_credentials(
+    dispatcher: web::Data<Dispatcher>,
+    ActixAccess(access): ActixAccess,
+) -> impl Future<Output = HttpResponse> {
+    helpers::time(async move { do_get_revoked_credentials(dispatcher.get_ref(), access) })
+}
+
+#[derive(Debug, Deserialize)]
+struct RevocationParams {
+    #[serde(default)]
+    timeout: Option<u64>,
+}
+
+#[post("/auth/revocations")]
+fn revoke_credential(
+    dispatcher: web::Data<Dispatcher>,
+    settings: web::Data<Settings>,
+    request: Json<RevokeCredential>,
+    Query(params): Query<RevocationParams>,
+    ActixAccess(access): ActixAccess,
+) -> impl Future<Output = HttpResponse> {
+    helpers::time(async move {
+        do_revoke_credential(
+            dispatcher.get_ref(),
+            request.into_inner(),
+            settings.service.api_key.as_deref(),
+            access,
+            params.timeout.map(std::time::Duration::from_secs),
+        )
+        .await
+    })
+}
+
+#[delete("/auth/revocations/{credential_id}")]
+fn restore_credential(
+    dispatcher: web::Data<Dispatcher>,
+    credential_id: web::Path<String>,
+    Query(params): Query<RevocationParams>,
+    ActixAccess(access): ActixAccess,
+) -> impl Future<Output = HttpResponse> {
+    helpers::time(async move {
+        do_restore_credential(
+            dispatcher.get_ref(),
+            credential_id.into_inner(),
+            access,
+            params.timeout.map(std::time::Duration::from_secs),
+        )
+        .await
+    })
+}
+
 #[get("/stacktrace")]
 fn get_stacktrace(ActixAccess(access): ActixAccess) -> impl Future<Output = HttpResponse> {
     helpers::time(async move {
@@ -137,6 +258,25 @@ fn get_stacktrace(ActixAccess(access): ActixAccess) -> impl Future<Output = Http
     })
 }
 
+#[get("/debug/pprof/profile")]
+async fn get_cpu_profile(
+    Query(params): Query<ProfileParams>,
+    ActixAccess(access): ActixAccess,
+) -> HttpResponse {
+    let timing = Instant::now();
+    if let Err(err) = access.check_global_access(AccessRequirements::new().manage()) {
+        return process_response_error(err, timing);
+    }
+
+    let format = params.format;
+    match capture_cpu_profile(params).await {
+        Ok(profile) => HttpResponse::Ok()
+            .content_type(format.content_type())
+            .body(profile),
+        Err(err) => process_response_error(err, timing),
+    }
+}
+
 #[get("/healthz")]
 async fn healthz() -> impl Responder {
     kubernetes_healthz().await
@@ -172,6 +312,18 @@ async fn kubernetes_healthz() -> impl Responder {
         .body("healthz check passed")
 }
 
+/// Effective configuration of this node and its collections, with secrets masked
+#[get("/config")]
+async fn get_effective_config(
+    dispatcher: web::Data<Dispatcher>,
+    settings: web::Data<Settings>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let result = do_get_effective_config(&settings, dispatcher.toc(&access), access).await;
+    helpers::process_response(result, timing)
+}
+
 #[get("/logger")]
 async fn get_logger_config(handle: web::Data<tracing::LoggerHandle>) -> impl Responder {
     let timing = Instant::now();
@@ -195,16 +347,49 @@ async fn update_logger_config(
     helpers::process_response(result, timing)
 }
 
+/// Set log levels of modules, e.g. `{"collection::shards": "debug"}`.
+/// `null` resets the module to log levels of loggers.
+#[put("/logger/levels")]
+async fn update_logger_module_levels(
+    handle: web::Data<tracing::LoggerHandle>,
+    module_levels: web::Json<BTreeMap<String, Option<String>>>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let result = match access.check_global_access(AccessRequirements::new().manage()) {
+        Ok(()) => handle
+            .update_module_levels(module_levels.into_inner())
+            .await
+            .map(|_| true)
+            .map_err(|err| StorageError::bad_input(err.to_string())),
+        Err(err) => Err(err),
+    };
+
+    helpers::process_response(result, timing)
+}
+
 // Configure services
 pub fn config_service_api(cfg: &mut web::ServiceConfig) {
     cfg.service(telemetry)
         .service(metrics)
         .service(put_locks)
         .service(get_locks)
+        .service(put_read_only)
+        .service(get_read_only)
+        .service(get_restart_status)
+        .service(cordon)
+        .service(uncordon)
+        .service(get_revoked_credentials)
+        .service(revoke_credential)
+        .service(restore_credential)
         .service(get_stacktrace)
+        .service(get_cpu_profile)
         .service(healthz)
         .service(livez)
         .service(readyz)
+        .service(get_effective_config)
         .service(get_logger_config)
-        .service(update_logger_config);
+        .service(update_logger_config)
+        .service(update_logger_module_levels);
 }
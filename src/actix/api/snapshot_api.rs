@@ -7,9 +7,9 @@ use actix_web::{delete, get, post, put, web, HttpRequest, Responder, Result};
 use actix_web_validator as valid;
 use collection::common::file_utils::move_file;
 use collection::common::sha_256::{hash_file, hashes_equal};
-use collection::common::snapshot_stream::SnapshotStream;
+use collection::common::snapshot_stream::{SnapShotStreamArchive, SnapshotStream};
 use collection::operations::snapshot_ops::{
-    ShardSnapshotRecover, SnapshotPriority, SnapshotRecover,
+    CreateSnapshot, ShardSnapshotRecover, SnapshotPriority, SnapshotRecover, SnapshotSchedule,
 };
 use collection::shards::shard::ShardId;
 use futures::{FutureExt as _, TryFutureExt as _};
@@ -24,14 +24,16 @@ use storage::content_manager::snapshots::{
 };
 use storage::content_manager::toc::TableOfContent;
 use storage::dispatcher::Dispatcher;
-use storage::rbac::{Access, AccessRequirements};
+use storage::rbac::{Access, AccessRequirements, SnapshotAction};
 use uuid::Uuid;
 use validator::Validate;
 
+use super::operations_api::AsyncParam;
 use super::{CollectionPath, StrictCollectionPath};
 use crate::actix::auth::ActixAccess;
 use crate::actix::helpers::{self, process_response, HttpError};
 use crate::common;
+use crate::common::async_operations::AsyncOperations;
 use crate::common::collections::*;
 use crate::common::http_client::HttpClient;
 
@@ -70,7 +72,7 @@ pub async fn do_get_full_snapshot(
     access: Access,
     snapshot_name: &str,
 ) -> Result<SnapshotStream, HttpError> {
-    access.check_global_access(AccessRequirements::new())?;
+    access.check_global_access(AccessRequirements::new().snapshot(SnapshotAction::Download))?;
     let snapshots_storage_manager = toc.get_snapshots_storage_manager()?;
     let snapshot_path = snapshots_storage_manager
         .get_full_snapshot_path(toc.snapshots_path(), snapshot_name)
@@ -131,8 +133,12 @@ pub async fn do_get_snapshot(
     collection_name: &str,
     snapshot_name: &str,
 ) -> Result<SnapshotStream, HttpError> {
-    let collection_pass =
-        access.check_collection_access(collection_name, AccessRequirements::new().whole())?;
+    let collection_pass = access.check_collection_access(
+        collection_name,
+        AccessRequirements::new()
+            .whole()
+            .snapshot(SnapshotAction::Download),
+    )?;
     let collection: tokio::sync::RwLockReadGuard<collection::collection::Collection> =
         toc.get_collection(&collection_pass).await?;
     let snapshot_storage_manager = collection.get_snapshots_storage_manager()?;
@@ -145,6 +151,25 @@ pub async fn do_get_snapshot(
     Ok(snapshot_stream)
 }
 
+// Actix specific code
+pub async fn do_stream_snapshot(
+    toc: &TableOfContent,
+    access: Access,
+    collection_name: &str,
+) -> Result<SnapshotStream, HttpError> {
+    let collection_pass = access.check_collection_access(
+        collection_name,
+        AccessRequirements::new()
+            .whole()
+            .snapshot(SnapshotAction::Download),
+    )?;
+    let (snapshot_name, archive) = toc.stream_snapshot(&collection_pass, None).await?;
+    Ok(SnapshotStream::Archive(SnapShotStreamArchive {
+        snapshot_name,
+        archive,
+    }))
+}
+
 #[get("/collections/{name}/snapshots")]
 async fn list_snapshots(
     dispatcher: web::Data<Dispatcher>,
@@ -162,16 +187,33 @@ async fn list_snapshots(
 async fn create_snapshot(
     dispatcher: web::Data<Dispatcher>,
     path: web::Path<String>,
+    request: Option<valid::Json<CreateSnapshot>>,
     params: valid::Query<SnapshottingParam>,
     ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let collection_name = path.into_inner();
+    let CreateSnapshot { encryption_key } = request.map(|r| r.into_inner()).unwrap_or_default();
     helpers::time_or_accept_with_handle(params.wait.unwrap_or(true), async move {
-        do_create_snapshot(dispatcher.toc(&access).clone(), access, &collection_name)
+        do_create_snapshot(
+            dispatcher.toc(&access).clone(),
+            access,
+            &collection_name,
+            encryption_key,
+        )
     })
     .await
 }
 
+#[get("/collections/{name}/snapshot")]
+async fn stream_snapshot(
+    dispatcher: web::Data<Dispatcher>,
+    path: web::Path<String>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let collection_name = path.into_inner();
+    do_stream_snapshot(dispatcher.toc(&access), access, &collection_name).await
+}
+
 #[post("/collections/{name}/snapshots/upload")]
 async fn upload_snapshot(
     dispatcher: web::Data<Dispatcher>,
@@ -184,7 +226,11 @@ async fn upload_snapshot(
     helpers::time_or_accept_with_handle(params.wait.unwrap_or(true), async move {
         let snapshot = form.snapshot;
 
-        access.check_global_access(AccessRequirements::new().manage())?;
+        access.check_global_access(
+            AccessRequirements::new()
+                .manage()
+                .snapshot(SnapshotAction::Restore),
+        )?;
 
         if let Some(checksum) = &params.checksum {
             let snapshot_checksum = hash_file(snapshot.file.path()).await?;
@@ -204,6 +250,8 @@ async fn upload_snapshot(
             priority: params.priority,
             checksum: None,
             api_key: None,
+            encryption_key: None,
+            point_in_time: None,
         };
 
         do_recover_from_snapshot(
@@ -224,8 +272,32 @@ async fn recover_from_snapshot(
     collection: valid::Path<CollectionPath>,
     request: valid::Json<SnapshotRecover>,
     params: valid::Query<SnapshottingParam>,
+    async_param: valid::Query<AsyncParam>,
+    operations: web::Data<AsyncOperations>,
     ActixAccess(access): ActixAccess,
 ) -> impl Responder {
+    if async_param.is_async() {
+        let timing = Instant::now();
+        let response = (|| -> Result<_, StorageError> {
+            let snapshot_recover = request.into_inner();
+            let http_client = http_client.client(snapshot_recover.api_key.as_deref())?;
+            let handle = do_recover_from_snapshot(
+                dispatcher.get_ref(),
+                &collection.name,
+                snapshot_recover,
+                access.clone(),
+                http_client,
+            )?;
+            operations.spawn(
+                "recover_from_snapshot",
+                &collection.name,
+                &access,
+                async move { handle.await.map_err(StorageError::from).and_then(|x| x) },
+            )
+        })();
+        return process_response(response, timing);
+    }
+
     helpers::time_or_accept_with_handle(params.wait.unwrap_or(true), async move {
         let snapshot_recover = request.into_inner();
         let http_client = http_client.client(snapshot_recover.api_key.as_deref())?;
@@ -240,6 +312,68 @@ async fn recover_from_snapshot(
     .await
 }
 
+#[get("/collections/{name}/snapshots/recover")]
+async fn get_snapshot_recovery_status(
+    dispatcher: web::Data<Dispatcher>,
+    path: web::Path<String>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let collection_name = path.into_inner();
+    let timing = Instant::now();
+
+    let response =
+        do_get_snapshot_recovery_status(dispatcher.toc(&access), access, &collection_name);
+    process_response(response, timing)
+}
+
+#[get("/collections/{name}/snapshots/schedule")]
+async fn get_snapshot_schedule(
+    dispatcher: web::Data<Dispatcher>,
+    path: web::Path<String>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let collection_name = path.into_inner();
+    let timing = Instant::now();
+
+    let response =
+        do_get_snapshot_schedule(dispatcher.toc(&access), access, &collection_name).await;
+    process_response(response, timing)
+}
+
+#[put("/collections/{name}/snapshots/schedule")]
+async fn set_snapshot_schedule(
+    dispatcher: web::Data<Dispatcher>,
+    path: web::Path<String>,
+    request: valid::Json<SnapshotSchedule>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let collection_name = path.into_inner();
+    let timing = Instant::now();
+
+    let response = do_set_snapshot_schedule(
+        dispatcher.toc(&access),
+        access,
+        &collection_name,
+        Some(request.into_inner()),
+    )
+    .await;
+    process_response(response, timing)
+}
+
+#[delete("/collections/{name}/snapshots/schedule")]
+async fn delete_snapshot_schedule(
+    dispatcher: web::Data<Dispatcher>,
+    path: web::Path<String>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let collection_name = path.into_inner();
+    let timing = Instant::now();
+
+    let response =
+        do_set_snapshot_schedule(dispatcher.toc(&access), access, &collection_name, None).await;
+    process_response(response, timing)
+}
+
 #[get("/collections/{name}/snapshots/{snapshot_name}")]
 async fn get_snapshot(
     req: HttpRequest,
@@ -415,7 +549,11 @@ async fn upload_shard_snapshot(
     let future = cancel::future::spawn_cancel_on_drop(move |cancel| async move {
         // TODO: Run this check before the multipart blob is uploaded
         let collection_pass = access
-            .check_global_access(AccessRequirements::new().manage())?
+            .check_global_access(
+                AccessRequirements::new()
+                    .manage()
+                    .snapshot(SnapshotAction::Restore),
+            )?
             .issue_pass(&collection);
 
         if let Some(checksum) = checksum {
@@ -463,8 +601,12 @@ async fn download_shard_snapshot(
     ActixAccess(access): ActixAccess,
 ) -> Result<impl Responder, HttpError> {
     let (collection, shard, snapshot) = path.into_inner();
-    let collection_pass =
-        access.check_collection_access(&collection, AccessRequirements::new().whole())?;
+    let collection_pass = access.check_collection_access(
+        &collection,
+        AccessRequirements::new()
+            .whole()
+            .snapshot(SnapshotAction::Download),
+    )?;
     let collection = dispatcher
         .toc(&access)
         .get_collection(&collection_pass)
@@ -509,8 +651,14 @@ async fn delete_shard_snapshot(
 pub fn config_snapshots_api(cfg: &mut web::ServiceConfig) {
     cfg.service(list_snapshots)
         .service(create_snapshot)
+        .service(stream_snapshot)
         .service(upload_snapshot)
         .service(recover_from_snapshot)
+        // Recovery status and schedule routes must be registered before the routes with snapshot name
+        .service(get_snapshot_recovery_status)
+        .service(get_snapshot_schedule)
+        .service(set_snapshot_schedule)
+        .service(delete_snapshot_schedule)
         .service(get_snapshot)
         .service(list_full_snapshots)
         .service(create_full_snapshot)
@@ -1,21 +1,31 @@
 use actix_web::rt::time::Instant;
 use actix_web::{delete, post, put, web, Responder};
 use actix_web_validator::{Json, Path, Query};
-use collection::operations::payload_ops::{DeletePayload, SetPayload};
-use collection::operations::point_ops::{PointInsertOperations, PointsSelector, WriteOrdering};
+use collection::operations::blob_ops::{DeleteBlobOp, SetBlobOp};
+use collection::operations::payload_ops::{DeletePayload, PatchPayload, SetPayload};
+use collection::operations::point_ops::{
+    FilterSelector, PointIdsList, PointInsertOperations, PointsSelector, WriteOrdering,
+};
 use collection::operations::vector_ops::{DeleteVectors, UpdateVectors};
 use schemars::JsonSchema;
 use segment::json_path::{JsonPath, JsonPathInterface};
+use segment::types::SeqNumberType;
 use serde::{Deserialize, Serialize};
+use storage::content_manager::import::{self, ImportPoints};
 use storage::dispatcher::Dispatcher;
 use validator::Validate;
 
-use super::CollectionPath;
-use crate::actix::auth::ActixAccess;
-use crate::actix::helpers::process_response;
+use super::operations_api::AsyncParam;
+use super::{CollectionPath, PointPath};
+use crate::actix::auth::{ActixAccess, ActixRateLimiter};
+use crate::actix::helpers::{self, process_response};
+use crate::common::async_operations::AsyncOperations;
+use crate::common::http_client::HttpClient;
+use crate::common::inference::InferenceBatch;
 use crate::common::points::{
-    do_batch_update_points, do_clear_payload, do_create_index, do_delete_index, do_delete_payload,
-    do_delete_points, do_delete_vectors, do_overwrite_payload, do_set_payload, do_update_vectors,
+    do_batch_update_points, do_clear_payload, do_create_index, do_delete_blob, do_delete_index,
+    do_delete_payload, do_delete_points, do_delete_vectors, do_dry_run_update,
+    do_overwrite_payload, do_patch_payload, do_set_blob, do_set_payload, do_update_vectors,
     do_upsert_points, CreateFieldIndex, UpdateOperations,
 };
 
@@ -32,29 +42,68 @@ pub struct UpdateParam {
     pub ordering: Option<WriteOrdering>,
 }
 
+#[derive(Deserialize, Serialize, JsonSchema, Validate)]
+pub struct VersionedUpdateParam {
+    pub wait: Option<bool>,
+    pub ordering: Option<WriteOrdering>,
+    /// Reject the operation, unless all affected points exist and have this version on the shard
+    /// leader. Guarded operations are always ordered strongly
+    pub if_version: Option<SeqNumberType>,
+}
+
+#[derive(Deserialize, Serialize, JsonSchema, Validate)]
+pub struct DryRunParam {
+    /// If true, the operation is not applied, only points it would affect are returned
+    pub dry_run: Option<bool>,
+    /// Number of IDs of affected points to return in dry run. Default: 0
+    #[validate(range(max = 1000))]
+    pub dry_run_samples: Option<usize>,
+}
+
 #[put("/collections/{name}/points")]
 async fn upsert_points(
     dispatcher: web::Data<Dispatcher>,
+    http_client: web::Data<HttpClient>,
     collection: Path<CollectionPath>,
     operation: Json<PointInsertOperations>,
-    params: Query<UpdateParam>,
+    params: Query<VersionedUpdateParam>,
     ActixAccess(access): ActixAccess,
+    ActixRateLimiter(rate_limiter): ActixRateLimiter,
 ) -> impl Responder {
     let timing = Instant::now();
-    let operation = operation.into_inner();
+    let mut operation = operation.into_inner();
+
+    if let Some(rate_limiter) = rate_limiter {
+        if let Err(err) = rate_limiter.check_vectors(operation.points_count()) {
+            return helpers::rate_limited_response(err, timing);
+        }
+    }
+
     let wait = params.wait.unwrap_or(false);
     let ordering = params.ordering.unwrap_or_default();
+    let toc = dispatcher.toc(&access).clone();
+    let collection_name = collection.into_inner().name;
 
-    let response = do_upsert_points(
-        dispatcher.toc(&access).clone(),
-        collection.into_inner().name,
-        operation,
-        None,
-        None,
-        wait,
-        ordering,
-        access,
-    )
+    let response = async {
+        let mut inference = InferenceBatch::default();
+        inference.add_point_insert_operations(&mut operation);
+        inference
+            .infer(&toc, &collection_name, &access, &http_client)
+            .await?;
+
+        do_upsert_points(
+            toc,
+            collection_name,
+            operation,
+            params.if_version,
+            None,
+            None,
+            wait,
+            ordering,
+            access,
+        )
+        .await
+    }
     .await;
     process_response(response, timing)
 }
@@ -64,7 +113,10 @@ async fn delete_points(
     dispatcher: web::Data<Dispatcher>,
     collection: Path<CollectionPath>,
     operation: Json<PointsSelector>,
-    params: Query<UpdateParam>,
+    params: Query<VersionedUpdateParam>,
+    dry_run: Query<DryRunParam>,
+    async_param: Query<AsyncParam>,
+    operations: web::Data<AsyncOperations>,
     ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
@@ -72,10 +124,54 @@ async fn delete_points(
     let wait = params.wait.unwrap_or(false);
     let ordering = params.ordering.unwrap_or_default();
 
+    if dry_run.dry_run.unwrap_or(false) {
+        let (points, filter, shard_key) = match operation {
+            PointsSelector::PointIdsSelector(PointIdsList { points, shard_key }) => {
+                (Some(points), None, shard_key)
+            }
+            PointsSelector::FilterSelector(FilterSelector { filter, shard_key }) => {
+                (None, Some(filter), shard_key)
+            }
+        };
+        let response = do_dry_run_update(
+            dispatcher.toc(&access),
+            &collection.name,
+            points,
+            filter,
+            shard_key,
+            dry_run.dry_run_samples,
+            access,
+        )
+        .await;
+        return process_response(response, timing);
+    }
+
+    if async_param.is_async() {
+        let collection_name = collection.into_inner().name;
+        let response = operations.spawn(
+            "delete_points",
+            &collection_name,
+            &access,
+            do_delete_points(
+                dispatcher.toc(&access).clone(),
+                collection_name.clone(),
+                operation,
+                params.if_version,
+                None,
+                None,
+                true,
+                ordering,
+                access.clone(),
+            ),
+        );
+        return process_response(response, timing);
+    }
+
     let response = do_delete_points(
         dispatcher.toc(&access).clone(),
         collection.into_inner().name,
         operation,
+        params.if_version,
         None,
         None,
         wait,
@@ -89,26 +185,38 @@ async fn delete_points(
 #[put("/collections/{name}/points/vectors")]
 async fn update_vectors(
     dispatcher: web::Data<Dispatcher>,
+    http_client: web::Data<HttpClient>,
     collection: Path<CollectionPath>,
     operation: Json<UpdateVectors>,
     params: Query<UpdateParam>,
     ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
-    let operation = operation.into_inner();
+    let mut operation = operation.into_inner();
     let wait = params.wait.unwrap_or(false);
     let ordering = params.ordering.unwrap_or_default();
+    let toc = dispatcher.toc(&access).clone();
+    let collection_name = collection.into_inner().name;
 
-    let response = do_update_vectors(
-        dispatcher.toc(&access).clone(),
-        collection.into_inner().name,
-        operation,
-        None,
-        None,
-        wait,
-        ordering,
-        access,
-    )
+    let response = async {
+        let mut inference = InferenceBatch::default();
+        inference.add_update_vectors(&mut operation);
+        inference
+            .infer(&toc, &collection_name, &access, &http_client)
+            .await?;
+
+        do_update_vectors(
+            toc,
+            collection_name,
+            operation,
+            None,
+            None,
+            wait,
+            ordering,
+            access,
+        )
+        .await
+    }
     .await;
     process_response(response, timing)
 }
@@ -146,6 +254,7 @@ async fn set_payload(
     collection: Path<CollectionPath>,
     operation: Json<SetPayload>,
     params: Query<UpdateParam>,
+    dry_run: Query<DryRunParam>,
     ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
@@ -153,6 +262,26 @@ async fn set_payload(
     let wait = params.wait.unwrap_or(false);
     let ordering = params.ordering.unwrap_or_default();
 
+    if dry_run.dry_run.unwrap_or(false) {
+        let SetPayload {
+            points,
+            filter,
+            shard_key,
+            ..
+        } = operation;
+        let response = do_dry_run_update(
+            dispatcher.toc(&access),
+            &collection.name,
+            points,
+            filter,
+            shard_key,
+            dry_run.dry_run_samples,
+            access,
+        )
+        .await;
+        return process_response(response, timing);
+    }
+
     let response = do_set_payload(
         dispatcher.toc(&access).clone(),
         collection.into_inner().name,
@@ -194,6 +323,103 @@ async fn overwrite_payload(
     process_response(response, timing)
 }
 
+#[post("/collections/{name}/points/payload/patch")]
+async fn patch_payload(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    operation: Json<PatchPayload>,
+    params: Query<UpdateParam>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let operation = operation.into_inner();
+    let wait = params.wait.unwrap_or(false);
+    let ordering = params.ordering.unwrap_or_default();
+
+    let response = do_patch_payload(
+        dispatcher.toc(&access).clone(),
+        collection.into_inner().name,
+        operation,
+        None,
+        None,
+        wait,
+        ordering,
+        access,
+    )
+    .await;
+    process_response(response, timing)
+}
+
+/// Attach the raw request body to the point as a blob, replacing the existing one
+#[put("/collections/{name}/points/{id}/blob")]
+async fn set_point_blob(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    point: Path<PointPath>,
+    blob: web::Bytes,
+    params: Query<UpdateParam>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let wait = params.wait.unwrap_or(false);
+    let ordering = params.ordering.unwrap_or_default();
+
+    let response = match point.point_id() {
+        Ok(point_id) => {
+            let operation = SetBlobOp {
+                point_id,
+                blob: blob.to_vec(),
+            };
+            do_set_blob(
+                dispatcher.toc(&access).clone(),
+                collection.into_inner().name,
+                operation,
+                None,
+                None,
+                None,
+                wait,
+                ordering,
+                access,
+            )
+            .await
+        }
+        Err(err) => Err(err),
+    };
+    process_response(response, timing)
+}
+
+#[delete("/collections/{name}/points/{id}/blob")]
+async fn delete_point_blob(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    point: Path<PointPath>,
+    params: Query<UpdateParam>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+    let wait = params.wait.unwrap_or(false);
+    let ordering = params.ordering.unwrap_or_default();
+
+    let response = match point.point_id() {
+        Ok(point_id) => {
+            do_delete_blob(
+                dispatcher.toc(&access).clone(),
+                collection.into_inner().name,
+                DeleteBlobOp { point_id },
+                None,
+                None,
+                None,
+                wait,
+                ordering,
+                access,
+            )
+            .await
+        }
+        Err(err) => Err(err),
+    };
+    process_response(response, timing)
+}
+
 #[post("/collections/{name}/points/payload/delete")]
 async fn delete_payload(
     dispatcher: web::Data<Dispatcher>,
@@ -251,26 +477,40 @@ async fn clear_payload(
 #[post("/collections/{name}/points/batch")]
 async fn update_batch(
     dispatcher: web::Data<Dispatcher>,
+    http_client: web::Data<HttpClient>,
     collection: Path<CollectionPath>,
     operations: Json<UpdateOperations>,
     params: Query<UpdateParam>,
     ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
-    let operations = operations.into_inner();
+    let mut operations = operations.into_inner();
     let wait = params.wait.unwrap_or(false);
     let ordering = params.ordering.unwrap_or_default();
+    let toc = dispatcher.toc(&access).clone();
+    let collection_name = collection.into_inner().name;
 
-    let response = do_batch_update_points(
-        dispatcher.toc(&access).clone(),
-        collection.into_inner().name,
-        operations.operations,
-        None,
-        None,
-        wait,
-        ordering,
-        access,
-    )
+    let response = async {
+        let mut inference = InferenceBatch::default();
+        for operation in &mut operations.operations {
+            operation.collect_inference_inputs(&mut inference);
+        }
+        inference
+            .infer(&toc, &collection_name, &access, &http_client)
+            .await?;
+
+        do_batch_update_points(
+            toc,
+            collection_name,
+            operations.operations,
+            None,
+            None,
+            wait,
+            ordering,
+            access,
+        )
+        .await
+    }
     .await;
     process_response(response, timing)
 }
@@ -280,6 +520,8 @@ async fn create_field_index(
     collection: Path<CollectionPath>,
     operation: Json<CreateFieldIndex>,
     params: Query<UpdateParam>,
+    async_param: Query<AsyncParam>,
+    operations: web::Data<AsyncOperations>,
     ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
@@ -287,6 +529,26 @@ async fn create_field_index(
     let wait = params.wait.unwrap_or(false);
     let ordering = params.ordering.unwrap_or_default();
 
+    if async_param.is_async() {
+        let collection_name = collection.into_inner().name;
+        let response = operations.spawn(
+            "create_field_index",
+            &collection_name,
+            &access,
+            do_create_index(
+                dispatcher.into_inner(),
+                collection_name.clone(),
+                operation,
+                None,
+                None,
+                true,
+                ordering,
+                access.clone(),
+            ),
+        );
+        return process_response(response, timing);
+    }
+
     let response = do_create_index(
         dispatcher.into_inner(),
         collection.into_inner().name,
@@ -327,6 +589,37 @@ async fn delete_field_index(
     process_response(response, timing)
 }
 
+#[post("/collections/{name}/points/import")]
+async fn import_points(
+    dispatcher: web::Data<Dispatcher>,
+    http_client: web::Data<HttpClient>,
+    collection: Path<CollectionPath>,
+    request: Json<ImportPoints>,
+    params: Query<UpdateParam>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let toc = dispatcher.toc(&access).clone();
+    let wait = params.wait.unwrap_or(true);
+    let ordering = params.ordering.unwrap_or_default();
+
+    helpers::time_or_accept(
+        async move {
+            let http_client = http_client.client(None)?;
+            import::import_points(
+                toc,
+                collection.into_inner().name,
+                request.into_inner(),
+                &http_client,
+                ordering,
+                access,
+            )
+            .await
+        },
+        wait,
+    )
+    .await
+}
+
 // Configure services
 pub fn config_update_api(cfg: &mut web::ServiceConfig) {
     cfg.service(upsert_points)
@@ -335,9 +628,13 @@ pub fn config_update_api(cfg: &mut web::ServiceConfig) {
         .service(delete_vectors)
         .service(set_payload)
         .service(overwrite_payload)
+        .service(patch_payload)
         .service(delete_payload)
         .service(clear_payload)
+        .service(set_point_blob)
+        .service(delete_point_blob)
         .service(create_field_index)
         .service(delete_field_index)
-        .service(update_batch);
+        .service(update_batch)
+        .service(import_points);
 }
@@ -0,0 +1,97 @@
+use std::future::{ready, Ready};
+use std::sync::Arc;
+
+use actix_web::dev::{Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::{Error, HttpMessage as _};
+use chrono::Utc;
+use futures_util::future::LocalBoxFuture;
+
+use crate::common::audit::{is_audited_rest_request, AuditLog, AuditRecord, ANONYMOUS_SUBJECT};
+use crate::common::auth::AuthSubject;
+
+pub struct AuditService<S> {
+    service: S,
+    audit_log: Arc<AuditLog>,
+}
+
+/// Records write and administrative requests to the audit log.
+///
+/// Must be registered before `Auth`, so that the authenticated subject is known.
+pub struct Audit {
+    audit_log: Arc<AuditLog>,
+}
+
+impl Audit {
+    pub fn new(audit_log: Arc<AuditLog>) -> Self {
+        Self { audit_log }
+    }
+}
+
+impl<S, B> Service<ServiceRequest> for AuditService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    actix_web::dev::forward_ready!(service);
+
+    fn call(&self, request: ServiceRequest) -> Self::Future {
+        if matches!(request.method().as_str(), "GET" | "HEAD" | "OPTIONS") {
+            return Box::pin(self.service.call(request));
+        }
+
+        let subject = request.extensions().get::<AuthSubject>().map_or_else(
+            || ANONYMOUS_SUBJECT.to_string(),
+            |subject| subject.0.clone(),
+        );
+        let remote_addr = request.peer_addr().map(|addr| addr.to_string());
+        let method = request.method().to_string();
+        let path = request.path().to_string();
+
+        let future = self.service.call(request);
+        let audit_log = self.audit_log.clone();
+        Box::pin(async move {
+            let response = future.await?;
+
+            // Route pattern is only known after the request is matched
+            let request = response.request();
+            if is_audited_rest_request(&method, request.match_pattern().as_deref()) {
+                audit_log.record(&AuditRecord {
+                    timestamp: Utc::now(),
+                    subject,
+                    remote_addr,
+                    api: "rest",
+                    operation: format!("{method} {path}"),
+                    collection: request.match_info().get("name").map(ToString::to_string),
+                    status: response.status().as_u16().to_string(),
+                });
+            }
+
+            Ok(response)
+        })
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for Audit
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = AuditService<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(AuditService {
+            service,
+            audit_log: self.audit_log.clone(),
+        }))
+    }
+}
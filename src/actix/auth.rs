@@ -4,12 +4,14 @@ use std::sync::Arc;
 
 use actix_web::body::{BoxBody, EitherBody};
 use actix_web::dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::rt::time::Instant;
 use actix_web::{Error, FromRequest, HttpMessage, HttpResponse, ResponseError};
 use futures_util::future::LocalBoxFuture;
 use storage::rbac::Access;
 
-use super::helpers::HttpError;
-use crate::common::auth::{AuthError, AuthKeys};
+use super::helpers::{self, HttpError};
+use crate::common::auth::{AuthError, AuthKeys, AuthSubject};
+use crate::common::rate_limiting::RateLimiter;
 
 pub struct Auth {
     auth_keys: AuthKeys,
@@ -117,16 +119,25 @@ where
         let auth_keys = self.auth_keys.clone();
         let service = self.service.clone();
         Box::pin(async move {
+            let remote_ip = req.peer_addr().map(|addr| addr.ip());
             match auth_keys
-                .validate_request(|key| req.headers().get(key).and_then(|val| val.to_str().ok()))
+                .validate_request(
+                    |key| req.headers().get(key).and_then(|val| val.to_str().ok()),
+                    remote_ip,
+                )
                 .await
             {
-                Ok(access) => {
+                Ok((access, subject, rate_limiter)) => {
                     let _previous = req.extensions_mut().insert::<Access>(access);
                     debug_assert!(
                         _previous.is_none(),
                         "Previous access object should not exist in the request"
                     );
+                    req.extensions_mut().insert::<AuthSubject>(subject);
+                    if let Some(rate_limiter) = rate_limiter {
+                        req.extensions_mut()
+                            .insert::<Arc<RateLimiter>>(rate_limiter);
+                    }
                     service.call(req).await
                 }
                 Err(e) => {
@@ -134,6 +145,9 @@ where
                         AuthError::Unauthorized(e) => HttpResponse::Unauthorized().body(e),
                         AuthError::Forbidden(e) => HttpResponse::Forbidden().body(e),
                         AuthError::StorageError(e) => HttpError::from(e).error_response(),
+                        AuthError::RateLimited(e) => {
+                            helpers::rate_limited_response(e, Instant::now())
+                        }
                     };
                     Ok(req.into_response(resp).map_into_right_body())
                 }
@@ -158,3 +172,19 @@ impl FromRequest for ActixAccess {
         ready(Ok(ActixAccess(access)))
     }
 }
+
+/// Rate limiter of the API key, which the request is authenticated with
+pub struct ActixRateLimiter(pub Option<Arc<RateLimiter>>);
+
+impl FromRequest for ActixRateLimiter {
+    type Error = Infallible;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(
+        req: &actix_web::HttpRequest,
+        _payload: &mut actix_web::dev::Payload,
+    ) -> Self::Future {
+        let rate_limiter = req.extensions().get::<Arc<RateLimiter>>().cloned();
+        ready(Ok(ActixRateLimiter(rate_limiter)))
+    }
+}
@@ -0,0 +1,81 @@
+use std::future::{ready, Ready};
+
+use actix_web::body::{BodySize, MessageBody};
+use actix_web::dev::{Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::http::header::{HeaderValue, CONTENT_ENCODING};
+use actix_web::Error;
+use futures_util::future::LocalBoxFuture;
+
+pub struct CompressionThresholdService<S> {
+    service: S,
+    min_size: u64,
+}
+
+/// Excludes small responses from compression.
+///
+/// Compressing a few hundred bytes costs more CPU than it saves on the wire,
+/// so responses of known size below the threshold are marked with `Content-Encoding: identity`,
+/// which makes the outer `Compress` middleware pass them through as is.
+/// Must be registered before `Compress`, so that it wraps the response first.
+pub struct CompressionThreshold {
+    min_size: u64,
+}
+
+impl CompressionThreshold {
+    pub fn new(min_size: usize) -> Self {
+        Self {
+            min_size: min_size as u64,
+        }
+    }
+}
+
+impl<S, B> Service<ServiceRequest> for CompressionThresholdService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    actix_web::dev::forward_ready!(service);
+
+    fn call(&self, request: ServiceRequest) -> Self::Future {
+        let future = self.service.call(request);
+        let min_size = self.min_size;
+        Box::pin(async move {
+            let mut response = future.await?;
+            let is_small = match response.response().body().size() {
+                BodySize::Sized(size) => size < min_size,
+                BodySize::None | BodySize::Stream => false,
+            };
+            if is_small && !response.headers().contains_key(CONTENT_ENCODING) {
+                response
+                    .headers_mut()
+                    .insert(CONTENT_ENCODING, HeaderValue::from_static("identity"));
+            }
+            Ok(response)
+        })
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for CompressionThreshold
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = CompressionThresholdService<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(CompressionThresholdService {
+            service,
+            min_size: self.min_size,
+        }))
+    }
+}
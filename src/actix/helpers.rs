@@ -5,19 +5,46 @@ use actix_web::rt::time::Instant;
 use actix_web::{http, HttpResponse, ResponseError};
 use api::grpc::models::{ApiResponse, ApiStatus};
 use collection::operations::types::CollectionError;
+use segment::common::request_usage::RequestUsageReport;
 use serde::Serialize;
 use storage::content_manager::errors::StorageError;
 use tokio::task::JoinHandle;
 
+use crate::common::rate_limiting::RateLimited;
+
 pub fn accepted_response(timing: Instant) -> HttpResponse {
     HttpResponse::Accepted().json(ApiResponse::<()> {
         result: None,
         status: ApiStatus::Accepted,
         time: timing.elapsed().as_secs_f64(),
+        usage: None,
     })
 }
 
+pub fn rate_limited_response(err: RateLimited, timing: Instant) -> HttpResponse {
+    HttpResponse::TooManyRequests()
+        .insert_header((http::header::RETRY_AFTER, err.retry_after_secs()))
+        .json(ApiResponse::<()> {
+            result: None,
+            status: ApiStatus::Error(err.to_string()),
+            time: timing.elapsed().as_secs_f64(),
+            usage: None,
+        })
+}
+
 pub fn process_response<D>(response: Result<D, StorageError>, timing: Instant) -> HttpResponse
+where
+    D: Serialize,
+{
+    process_response_with_usage(response, timing, None)
+}
+
+/// Response with resources used by the request, if they were measured
+pub fn process_response_with_usage<D>(
+    response: Result<D, StorageError>,
+    timing: Instant,
+    usage: Option<RequestUsageReport>,
+) -> HttpResponse
 where
     D: Serialize,
 {
@@ -26,6 +53,7 @@ where
             result: Some(res),
             status: ApiStatus::Ok,
             time: timing.elapsed().as_secs_f64(),
+            usage,
         }),
         Err(err) => process_response_error(err, timing),
     }
@@ -49,6 +77,7 @@ pub fn process_response_error(err: StorageError, timing: Instant) -> HttpRespons
         result: None,
         status: ApiStatus::Error(error.to_string()),
         time: timing.elapsed().as_secs_f64(),
+        usage: None,
     })
 }
 
@@ -158,6 +187,8 @@ impl ResponseError for HttpError {
             StorageError::ChecksumMismatch { .. } => http::StatusCode::BAD_REQUEST,
             StorageError::Forbidden { .. } => http::StatusCode::FORBIDDEN,
             StorageError::PreconditionFailed { .. } => http::StatusCode::INTERNAL_SERVER_ERROR,
+            StorageError::Conflict { .. } => http::StatusCode::CONFLICT,
+            StorageError::QuotaExceeded { .. } => http::StatusCode::TOO_MANY_REQUESTS,
         }
     }
 }
@@ -0,0 +1,79 @@
+use std::future::{ready, Ready};
+use std::sync::Arc;
+
+use actix_web::body::{BoxBody, EitherBody};
+use actix_web::dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::{Error, HttpResponse};
+use futures_util::future::LocalBoxFuture;
+
+use crate::settings::IpFilterConfig;
+
+pub struct IpFilterService<S> {
+    service: S,
+    ip_filter: Arc<IpFilterConfig>,
+}
+
+/// Rejects requests of clients, which are not allowed by the IP filter of the REST API,
+/// and closes their connections.
+///
+/// Must be registered last, so that rejected requests are not processed any further.
+pub struct IpFilter {
+    ip_filter: Arc<IpFilterConfig>,
+}
+
+impl IpFilter {
+    pub fn new(ip_filter: IpFilterConfig) -> Self {
+        Self {
+            ip_filter: Arc::new(ip_filter),
+        }
+    }
+}
+
+impl<S, B> Service<ServiceRequest> for IpFilterService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B, BoxBody>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, request: ServiceRequest) -> Self::Future {
+        let remote_ip = request.peer_addr().map(|addr| addr.ip());
+        if remote_ip.map_or(false, |ip| self.ip_filter.is_allowed(ip)) {
+            let future = self.service.call(request);
+            return Box::pin(async move { Ok(future.await?.map_into_left_body()) });
+        }
+
+        log::debug!("Rejected REST request from {remote_ip:?}");
+        let response = HttpResponse::Forbidden()
+            .force_close()
+            .body("Client address is not allowed");
+        Box::pin(ready(Ok(request
+            .into_response(response)
+            .map_into_right_body())))
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for IpFilter
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B, BoxBody>>;
+    type Error = Error;
+    type Transform = IpFilterService<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(IpFilterService {
+            service,
+            ip_filter: self.ip_filter.clone(),
+        }))
+    }
+}
@@ -1,10 +1,13 @@
 #[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
 pub mod actix_telemetry;
 pub mod api;
+mod audit;
 mod auth;
-mod certificate_helpers;
+mod compression;
 #[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
 pub mod helpers;
+mod ip_filter;
+mod request_id;
 
 use std::io;
 use std::path::Path;
@@ -26,15 +29,25 @@ use crate::actix::api::collections_api::config_collections_api;
 use crate::actix::api::count_api::count_points;
 use crate::actix::api::discovery_api::config_discovery_api;
 use crate::actix::api::issues_api::config_issues_api;
+use crate::actix::api::operations_api::config_operations_api;
 use crate::actix::api::recommend_api::config_recommend_api;
-use crate::actix::api::retrieve_api::{get_point, get_points, scroll_points};
+use crate::actix::api::retrieve_api::{
+    export_points, get_point, get_point_blob, get_points, scroll_points,
+};
 use crate::actix::api::search_api::config_search_api;
 use crate::actix::api::service_api::config_service_api;
 use crate::actix::api::shards_api::config_shards_api;
 use crate::actix::api::snapshot_api::config_snapshots_api;
 use crate::actix::api::update_api::config_update_api;
+use crate::actix::audit::Audit;
 use crate::actix::auth::{Auth, WhitelistItem};
+use crate::actix::compression::CompressionThreshold;
+use crate::actix::ip_filter::IpFilter;
+use crate::actix::request_id::RequestId;
+use crate::common::async_operations::AsyncOperations;
+use crate::common::audit::AuditLog;
 use crate::common::auth::AuthKeys;
+use crate::common::certificate_helpers;
 use crate::common::health;
 use crate::common::http_client::HttpClient;
 use crate::common::telemetry::TelemetryCollector;
@@ -54,14 +67,12 @@ pub fn init(
     dispatcher: Arc<Dispatcher>,
     telemetry_collector: Arc<tokio::sync::Mutex<TelemetryCollector>>,
     health_checker: Option<Arc<health::HealthChecker>>,
+    auth_keys: Option<AuthKeys>,
+    audit_log: Option<Arc<AuditLog>>,
     settings: Settings,
     logger_handle: LoggerHandle,
 ) -> io::Result<()> {
     actix_web::rt::System::new().block_on(async {
-        let auth_keys = AuthKeys::try_create(
-            &settings.service,
-            dispatcher.toc(&Access::full("For JWT validation")).clone(),
-        );
         let upload_dir = dispatcher
             .toc(&Access::full("For upload dir"))
             .upload_dir()
@@ -75,7 +86,9 @@ pub fn init(
         let telemetry_collector_data = web::Data::from(telemetry_collector);
         let logger_handle_data = web::Data::new(logger_handle);
         let http_client = web::Data::new(HttpClient::from_settings(&settings)?);
+        let async_operations = web::Data::new(AsyncOperations::default());
         let health_checker = web::Data::new(health_checker);
+        let settings_data = web::Data::new(settings.clone());
         let static_folder = settings
             .service
             .static_content_dir
@@ -126,7 +139,20 @@ pub fn init(
                 .error_handler(|err, rec| validation_error_handler("JSON body", err, rec));
 
             let mut app = App::new()
-                .wrap(Compress::default()) // Reads the `Accept-Encoding` header to negotiate which compression codec to use.
+                // Must be inside of `Compress`, to exclude small responses from compression
+                .wrap(CompressionThreshold::new(
+                    settings.service.compression_min_size_bytes,
+                ))
+                .wrap(Condition::new(
+                    settings.service.enable_compression,
+                    Compress::default(), // Reads the `Accept-Encoding` header to negotiate which compression codec to use.
+                ))
+                // Must be inside of `Auth`, to know the authenticated subject
+                .wrap(ConditionEx::from_option(
+                    audit_log
+                        .as_ref()
+                        .map(|audit_log| Audit::new(audit_log.clone())),
+                ))
                 // api_key middleware
                 // note: the last call to `wrap()` or `wrap_fn()` is executed first
                 .wrap(ConditionEx::from_option(auth_keys.as_ref().map(
@@ -146,11 +172,18 @@ pub fn init(
                 .wrap(actix_telemetry::ActixTelemetryTransform::new(
                     actix_telemetry_collector.clone(),
                 ))
+                .wrap(RequestId)
+                // Must be the outermost, to reject clients before anything else
+                .wrap(ConditionEx::from_option(
+                    settings.service.ip_filter.rest.clone().map(IpFilter::new),
+                ))
                 .app_data(dispatcher_data.clone())
                 .app_data(telemetry_collector_data.clone())
                 .app_data(logger_handle_data.clone())
                 .app_data(http_client.clone())
+                .app_data(async_operations.clone())
                 .app_data(health_checker.clone())
+                .app_data(settings_data.clone())
                 .app_data(validate_path_config)
                 .app_data(validate_query_config)
                 .app_data(validate_json_config)
@@ -167,11 +200,14 @@ pub fn init(
                 .configure(config_discovery_api)
                 .configure(config_shards_api)
                 .configure(config_issues_api)
+                .configure(config_operations_api)
                 // Ordering of services is important for correct path pattern matching
                 // See: <https://github.com/qdrant/qdrant/issues/3543>
                 .service(scroll_points)
+                .service(export_points)
                 .service(count_points)
                 .service(get_point)
+                .service(get_point_blob)
                 .service(get_points);
 
             if web_ui_available {
@@ -251,6 +287,7 @@ fn validation_error_handler(
         result: None,
         status: ApiStatus::Error(msg),
         time: 0.0,
+        usage: None,
     });
     error::InternalError::from_response(err, response).into()
 }
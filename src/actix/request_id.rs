@@ -0,0 +1,73 @@
+use std::future::{ready, Ready};
+use std::sync::Arc;
+
+use actix_web::dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::http::header::{HeaderName, HeaderValue};
+use actix_web::Error;
+use common::request_id::{self, REQUEST_ID_HEADER};
+use futures_util::future::LocalBoxFuture;
+use tracing::Instrument as _;
+
+pub struct RequestIdService<S> {
+    service: S,
+}
+
+/// Accepts the request ID from the `x-request-id` header or generates one, attaches it to logs
+/// and internal gRPC calls of the request, and returns it in the response.
+///
+/// Must be registered after the logger, so that access log lines have the request ID.
+pub struct RequestId;
+
+impl<S, B> Service<ServiceRequest> for RequestIdService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, request: ServiceRequest) -> Self::Future {
+        let request_id: Arc<str> = request
+            .headers()
+            .get(REQUEST_ID_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .filter(|value| request_id::is_valid(value))
+            .map_or_else(|| uuid::Uuid::new_v4().to_string().into(), Into::into);
+
+        let span = tracing::info_span!("request", request_id = %request_id);
+        let future = span.in_scope(|| self.service.call(request));
+
+        Box::pin(async move {
+            let mut response = request_id::scope(Some(request_id.clone()), future)
+                .instrument(span)
+                .await?;
+            if let Ok(value) = HeaderValue::from_str(&request_id) {
+                response
+                    .headers_mut()
+                    .insert(HeaderName::from_static(REQUEST_ID_HEADER), value);
+            }
+            Ok(response)
+        })
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for RequestId
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = RequestIdService<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RequestIdService { service }))
+    }
+}
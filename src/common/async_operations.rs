@@ -0,0 +1,328 @@
+//! Long-running operations, which are executed in background on request of a client.
+//!
+//! Client receives an ID of the operation immediately and polls its status and outcome,
+//! instead of holding the connection open until the operation is finished.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::Serialize;
+use storage::content_manager::errors::StorageError;
+use storage::rbac::{Access, AccessRequirements};
+use uuid::Uuid;
+
+/// How many finished operations are kept for polling.
+/// Oldest finished operations are forgotten first.
+const MAX_FINISHED_OPERATIONS: usize = 1000;
+
+#[derive(Debug, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AsyncOperationStatus {
+    Running,
+    Completed,
+    Failed,
+}
+
+/// State of an operation, which is executed in background
+#[derive(Debug, Serialize, JsonSchema, Clone)]
+pub struct AsyncOperationInfo {
+    pub id: Uuid,
+    /// Kind of the operation, e.g. `delete_points`
+    pub operation: String,
+    pub collection_name: String,
+    pub status: AsyncOperationStatus,
+    pub started_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<DateTime<Utc>>,
+    /// Result of the completed operation, same as the synchronous request would return
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<serde_json::Value>,
+    /// Error of the failed operation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Progress of the operation, if the operation reports it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub progress: Option<serde_json::Value>,
+}
+
+/// Operation is accepted and is executed in background
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct AsyncOperationAccepted {
+    /// ID to poll status of the operation with
+    pub operation_id: Uuid,
+}
+
+/// Reports current progress of a running operation
+type ProgressFn = Box<dyn Fn() -> serde_json::Value + Send + Sync>;
+
+#[derive(Default)]
+pub struct AsyncOperations {
+    operations: Mutex<HashMap<Uuid, AsyncOperationInfo>>,
+    /// Progress of running operations, which report it
+    progress: Mutex<HashMap<Uuid, ProgressFn>>,
+}
+
+impl AsyncOperations {
+    /// Run `future` in background and track its outcome.
+    ///
+    /// Requires access to the collection, so that the caller is able to poll the operation.
+    pub fn spawn<T, Fut>(
+        self: &Arc<Self>,
+        operation: &str,
+        collection_name: &str,
+        access: &Access,
+        future: Fut,
+    ) -> Result<AsyncOperationAccepted, StorageError>
+    where
+        Fut: Future<Output = Result<T, StorageError>> + Send + 'static,
+        T: Serialize + Send + 'static,
+    {
+        self.spawn_inner(operation, collection_name, access, None, future)
+    }
+
+    /// Same as [`Self::spawn`], and reports `progress` of the operation until it is finished
+    pub fn spawn_with_progress<T, Fut, P>(
+        self: &Arc<Self>,
+        operation: &str,
+        collection_name: &str,
+        access: &Access,
+        progress: P,
+        future: Fut,
+    ) -> Result<AsyncOperationAccepted, StorageError>
+    where
+        Fut: Future<Output = Result<T, StorageError>> + Send + 'static,
+        T: Serialize + Send + 'static,
+        P: Fn() -> serde_json::Value + Send + Sync + 'static,
+    {
+        self.spawn_inner(
+            operation,
+            collection_name,
+            access,
+            Some(Box::new(progress)),
+            future,
+        )
+    }
+
+    fn spawn_inner<T, Fut>(
+        self: &Arc<Self>,
+        operation: &str,
+        collection_name: &str,
+        access: &Access,
+        progress: Option<ProgressFn>,
+        future: Fut,
+    ) -> Result<AsyncOperationAccepted, StorageError>
+    where
+        Fut: Future<Output = Result<T, StorageError>> + Send + 'static,
+        T: Serialize + Send + 'static,
+    {
+        access.check_collection_access(collection_name, AccessRequirements::new())?;
+
+        let id = Uuid::new_v4();
+        self.operations.lock().insert(
+            id,
+            AsyncOperationInfo {
+                id,
+                operation: operation.to_string(),
+                collection_name: collection_name.to_string(),
+                status: AsyncOperationStatus::Running,
+                started_at: Utc::now(),
+                finished_at: None,
+                result: None,
+                error: None,
+                progress: None,
+            },
+        );
+        if let Some(progress) = progress {
+            self.progress.lock().insert(id, progress);
+        }
+
+        let operations = self.clone();
+        tokio::spawn(async move {
+            let outcome = future.await.and_then(|result| {
+                serde_json::to_value(result).map_err(|err| {
+                    StorageError::service_error(format!("Failed to serialize result: {err}"))
+                })
+            });
+            operations.finish(id, outcome);
+        });
+
+        Ok(AsyncOperationAccepted { operation_id: id })
+    }
+
+    fn finish(&self, id: Uuid, outcome: Result<serde_json::Value, StorageError>) {
+        let progress = self.progress.lock().remove(&id).map(|progress| progress());
+        let mut operations = self.operations.lock();
+
+        if let Some(info) = operations.get_mut(&id) {
+            info.finished_at = Some(Utc::now());
+            info.progress = progress;
+            match outcome {
+                Ok(result) => {
+                    info.status = AsyncOperationStatus::Completed;
+                    info.result = Some(result);
+                }
+                Err(err) => {
+                    log::warn!("Operation {} {id} failed: {err}", info.operation);
+                    info.status = AsyncOperationStatus::Failed;
+                    info.error = Some(err.to_string());
+                }
+            }
+        }
+
+        let mut finished: Vec<_> = operations
+            .values()
+            .filter_map(|info| info.finished_at.map(|finished_at| (finished_at, info.id)))
+            .collect();
+        if finished.len() > MAX_FINISHED_OPERATIONS {
+            finished.sort_unstable();
+            let excess = finished.len() - MAX_FINISHED_OPERATIONS;
+            for (_, id) in finished.into_iter().take(excess) {
+                operations.remove(&id);
+            }
+        }
+    }
+
+    pub fn get(&self, id: Uuid, access: &Access) -> Result<AsyncOperationInfo, StorageError> {
+        let info = self
+            .operations
+            .lock()
+            .get(&id)
+            .cloned()
+            .ok_or_else(|| StorageError::not_found(format!("Operation {id} is not found")))?;
+        access.check_collection_access(&info.collection_name, AccessRequirements::new())?;
+        Ok(self.with_progress(info))
+    }
+
+    /// All known operations, to which collections the user has access, latest first
+    pub fn list(&self, access: &Access) -> Vec<AsyncOperationInfo> {
+        let mut operations: Vec<_> = self
+            .operations
+            .lock()
+            .values()
+            .filter(|info| {
+                access
+                    .check_collection_access(&info.collection_name, AccessRequirements::new())
+                    .is_ok()
+            })
+            .cloned()
+            .collect();
+        operations.sort_unstable_by(|a, b| b.started_at.cmp(&a.started_at));
+        operations
+            .into_iter()
+            .map(|info| self.with_progress(info))
+            .collect()
+    }
+
+    /// Add current progress to the info of a running operation
+    fn with_progress(&self, mut info: AsyncOperationInfo) -> AsyncOperationInfo {
+        if let Some(progress) = self.progress.lock().get(&info.id) {
+            info.progress = Some(progress());
+        }
+        info
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_operation_outcome() {
+        let access = Access::full("For test");
+        let operations = Arc::new(AsyncOperations::default());
+
+        let (sender, receiver) = tokio::sync::oneshot::channel::<()>();
+        let accepted = operations
+            .spawn("test", "collection", &access, async move {
+                receiver.await.unwrap();
+                Ok(42)
+            })
+            .unwrap();
+
+        let info = operations.get(accepted.operation_id, &access).unwrap();
+        assert_eq!(info.status, AsyncOperationStatus::Running);
+
+        sender.send(()).unwrap();
+        while operations
+            .get(accepted.operation_id, &access)
+            .unwrap()
+            .status
+            == AsyncOperationStatus::Running
+        {
+            tokio::task::yield_now().await;
+        }
+
+        let info = operations.get(accepted.operation_id, &access).unwrap();
+        assert_eq!(info.status, AsyncOperationStatus::Completed);
+        assert_eq!(info.result, Some(serde_json::json!(42)));
+
+        let failed = operations
+            .spawn::<(), _>("test", "collection", &access, async {
+                Err(StorageError::bad_input("failed"))
+            })
+            .unwrap();
+        while operations.get(failed.operation_id, &access).unwrap().status
+            == AsyncOperationStatus::Running
+        {
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(operations.list(&access).len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_operation_progress() {
+        let access = Access::full("For test");
+        let operations = Arc::new(AsyncOperations::default());
+
+        let progress = Arc::new(Mutex::new(0));
+        let (sender, receiver) = tokio::sync::oneshot::channel::<()>();
+        let accepted = operations
+            .spawn_with_progress(
+                "test",
+                "collection",
+                &access,
+                {
+                    let progress = progress.clone();
+                    move || serde_json::json!(*progress.lock())
+                },
+                {
+                    let progress = progress.clone();
+                    async move {
+                        *progress.lock() = 1;
+                        receiver.await.unwrap();
+                        *progress.lock() = 2;
+                        Ok(())
+                    }
+                },
+            )
+            .unwrap();
+
+        while operations
+            .get(accepted.operation_id, &access)
+            .unwrap()
+            .progress
+            != Some(serde_json::json!(1))
+        {
+            tokio::task::yield_now().await;
+        }
+
+        sender.send(()).unwrap();
+        while operations
+            .get(accepted.operation_id, &access)
+            .unwrap()
+            .status
+            == AsyncOperationStatus::Running
+        {
+            tokio::task::yield_now().await;
+        }
+
+        // Last progress is kept after the operation is finished
+        let info = operations.get(accepted.operation_id, &access).unwrap();
+        assert_eq!(info.status, AsyncOperationStatus::Completed);
+        assert_eq!(info.progress, Some(serde_json::json!(2)));
+    }
+}
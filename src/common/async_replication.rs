@@ -0,0 +1,47 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use storage::content_manager::toc::TableOfContent;
+use storage::rbac::Access;
+use storage::types::AsyncReplicationSettings;
+
+const FULL_ACCESS: Access = Access::full("Asynchronous replication");
+
+/// Background task, which replays operations of collections to their remote clusters
+pub struct AsyncReplicator {
+    toc: Arc<TableOfContent>,
+    settings: AsyncReplicationSettings,
+}
+
+impl AsyncReplicator {
+    async fn replicate(&self) {
+        for collection_pass in self.toc.all_collections(&FULL_ACCESS).await {
+            let collection_name = collection_pass.name();
+            let collection = match self.toc.get_collection(&collection_pass).await {
+                Ok(collection) => collection,
+                // Collection was removed in the meantime
+                Err(_) => continue,
+            };
+
+            let api_key = self.settings.api_key_for(collection_name);
+            match collection.replicate_async(api_key).await {
+                Ok(0) => {}
+                Ok(replayed) => log::debug!(
+                    "Replayed {replayed} operations of collection {collection_name} to remote cluster",
+                ),
+                Err(err) => log::error!(
+                    "Failed to replicate collection {collection_name} to remote cluster: {err}",
+                ),
+            }
+        }
+    }
+
+    pub async fn run_forever(toc: Arc<TableOfContent>, settings: AsyncReplicationSettings) {
+        let interval = Duration::from_secs(settings.interval_sec.max(1));
+        let replicator = Self { toc, settings };
+        loop {
+            replicator.replicate().await;
+            tokio::time::sleep(interval).await;
+        }
+    }
+}
@@ -0,0 +1,185 @@
+//! Audit log of write and administrative requests to external APIs.
+//!
+//! Records who made the request, what was requested, when, from where, and the outcome.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write as _};
+use std::path::Path;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::Serialize;
+
+use crate::settings::{AuditConfig, AuditSinkConfig};
+
+/// Subject of requests, which are not authenticated, because no API key is configured
+pub const ANONYMOUS_SUBJECT: &str = "anonymous";
+
+/// REST routes, which are requested with `POST` but only read data
+const READ_ONLY_POST_ROUTES: &[&str] = &[
+    "/collections/{name}/points",
+    "/collections/{name}/points/count",
+    "/collections/{name}/points/discover",
+    "/collections/{name}/points/discover/batch",
+    "/collections/{name}/points/export",
+    "/collections/{name}/points/read/batch",
+    "/collections/{name}/points/recommend",
+    "/collections/{name}/points/recommend/batch",
+    "/collections/{name}/points/recommend/groups",
+    "/collections/{name}/points/scroll",
+    "/collections/{name}/points/search",
+    "/collections/{name}/points/search/batch",
+    "/collections/{name}/points/search/groups",
+];
+
+/// gRPC methods, which only read data
+const READ_ONLY_GRPC_METHODS: &[&str] = &[
+    "/qdrant.Qdrant/HealthCheck",
+    "/qdrant.Collections/Get",
+    "/qdrant.Collections/List",
+    "/qdrant.Collections/ListCollectionAliases",
+    "/qdrant.Collections/ListAliases",
+    "/qdrant.Collections/CollectionClusterInfo",
+    "/qdrant.Collections/CollectionExists",
+    "/qdrant.Points/Get",
+    "/qdrant.Points/Search",
+    "/qdrant.Points/SearchBatch",
+    "/qdrant.Points/SearchGroups",
+    "/qdrant.Points/Scroll",
+    "/qdrant.Points/Recommend",
+    "/qdrant.Points/RecommendBatch",
+    "/qdrant.Points/RecommendGroups",
+    "/qdrant.Points/Discover",
+    "/qdrant.Points/DiscoverBatch",
+    "/qdrant.Points/Count",
+    "/qdrant.Snapshots/List",
+    "/qdrant.Snapshots/ListFull",
+];
+
+/// gRPC services, which only read data
+const READ_ONLY_GRPC_PREFIXES: &[&str] = &["/grpc.health.v1.Health/", "/grpc.reflection."];
+
+/// Check if the REST request should be recorded, by its method and matched route pattern
+pub fn is_audited_rest_request(method: &str, route: Option<&str>) -> bool {
+    match method {
+        "GET" | "HEAD" | "OPTIONS" => false,
+        "POST" => route.map_or(true, |route| !READ_ONLY_POST_ROUTES.contains(&route)),
+        _ => true,
+    }
+}
+
+/// Check if the gRPC request should be recorded, by its full method name
+pub fn is_audited_grpc_request(method: &str) -> bool {
+    !READ_ONLY_GRPC_METHODS.contains(&method)
+        && !READ_ONLY_GRPC_PREFIXES
+            .iter()
+            .any(|prefix| method.starts_with(prefix))
+}
+
+#[derive(Debug, Serialize)]
+pub struct AuditRecord {
+    pub timestamp: DateTime<Utc>,
+    /// Credential the request is authenticated with, e.g. `role:admin`
+    pub subject: String,
+    /// Remote address of the client
+    pub remote_addr: Option<String>,
+    /// `rest` or `grpc`
+    pub api: &'static str,
+    /// HTTP method and path, or full gRPC method name
+    pub operation: String,
+    /// Collection the operation is applied to, if known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collection: Option<String>,
+    /// HTTP status code, or gRPC status code
+    pub status: String,
+}
+
+enum AuditSink {
+    File(Mutex<File>),
+    Log,
+}
+
+pub struct AuditLog {
+    sinks: Vec<AuditSink>,
+}
+
+impl AuditLog {
+    /// Open configured sinks, returns `None` if the audit log is disabled
+    pub fn new(config: &AuditConfig) -> io::Result<Option<Arc<Self>>> {
+        if !config.enabled {
+            return Ok(None);
+        }
+
+        let sinks = config
+            .sinks
+            .iter()
+            .map(|sink| match sink {
+                AuditSinkConfig::File { path } => {
+                    if let Some(dir) = Path::new(path).parent() {
+                        fs::create_dir_all(dir)?;
+                    }
+                    let file = OpenOptions::new().create(true).append(true).open(path)?;
+                    Ok(AuditSink::File(Mutex::new(file)))
+                }
+                AuditSinkConfig::Log => Ok(AuditSink::Log),
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+
+        if sinks.is_empty() {
+            log::warn!("Audit log is enabled, but no sinks are configured");
+        }
+
+        Ok(Some(Arc::new(Self { sinks })))
+    }
+
+    pub fn record(&self, record: &AuditRecord) {
+        let line = match serde_json::to_string(record) {
+            Ok(line) => line,
+            Err(err) => {
+                log::error!("Failed to serialize audit record {record:?}: {err}");
+                return;
+            }
+        };
+
+        for sink in &self.sinks {
+            match sink {
+                AuditSink::File(file) => {
+                    if let Err(err) = writeln!(file.lock(), "{line}") {
+                        log::error!("Failed to write audit record {line}: {err}");
+                    }
+                }
+                AuditSink::Log => log::info!(target: "audit", "{line}"),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_audited_requests() {
+        assert!(!is_audited_rest_request("GET", Some("/collections/{name}")));
+        assert!(is_audited_rest_request("PUT", Some("/collections/{name}")));
+        assert!(is_audited_rest_request(
+            "DELETE",
+            Some("/collections/{name}")
+        ));
+        assert!(!is_audited_rest_request(
+            "POST",
+            Some("/collections/{name}/points/search")
+        ));
+        assert!(is_audited_rest_request(
+            "POST",
+            Some("/collections/{name}/points/delete")
+        ));
+        assert!(is_audited_rest_request("POST", None));
+
+        assert!(is_audited_grpc_request("/qdrant.Points/Upsert"));
+        assert!(is_audited_grpc_request("/qdrant.Collections/Delete"));
+        assert!(!is_audited_grpc_request("/qdrant.Points/Search"));
+        assert!(!is_audited_grpc_request("/grpc.health.v1.Health/Check"));
+    }
+}
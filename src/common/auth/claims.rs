@@ -9,6 +9,11 @@ pub struct Claims {
     /// Expiration time (seconds since UNIX epoch)
     pub exp: Option<u64>,
 
+    /// Subject, e.g. user or service the token is issued to.
+    /// Only used to identify the client, e.g. in the audit log.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sub: Option<String>,
+
     #[serde(default = "default_access")]
     pub access: Access,
 
@@ -77,6 +77,7 @@ mod tests {
             .as_secs();
         let claims = Claims {
             exp: Some(exp),
+            sub: Some("user".to_string()),
             access: Access::Collection(CollectionAccessList(vec![CollectionAccess {
                 collection: "collection".to_string(),
                 access: CollectionAccessMode::ReadWrite,
@@ -92,6 +93,9 @@ mod tests {
                     .into_iter()
                     .collect(),
                 )),
+                filter: None,
+                tenant: None,
+                snapshots: None,
             }])),
             value_exists: None,
         };
@@ -114,6 +118,7 @@ mod tests {
 
         let mut claims = Claims {
             exp: Some(exp),
+            sub: None,
             access: Access::Global(GlobalAccessMode::Read),
             value_exists: None,
         };
@@ -140,6 +145,7 @@ mod tests {
     fn test_invalid_token() {
         let claims = Claims {
             exp: None,
+            sub: None,
             access: Access::Global(GlobalAccessMode::Read),
             value_exists: None,
         };
@@ -1,7 +1,10 @@
+use std::net::IpAddr;
 use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::ScrollRequestInternal;
+use parking_lot::RwLock;
 use segment::types::{WithPayloadInterface, WithVector};
 use storage::content_manager::errors::StorageError;
 use storage::content_manager::toc::TableOfContent;
@@ -9,28 +12,56 @@ use storage::rbac::Access;
 
 use self::claims::{Claims, ValueExists};
 use self::jwt_parser::JwtParser;
+use self::revocations::credential_id;
+use super::rate_limiting::{RateLimited, RateLimiter};
 use super::strings::ct_eq;
-use crate::settings::ServiceConfig;
+use crate::settings::{IpFilterConfig, ServiceConfig};
 
 pub mod claims;
 pub mod jwt_parser;
+pub mod revocations;
 
 pub const HTTP_HEADER_API_KEY: &str = "api-key";
 
+/// Identity of the authenticated client, e.g. for the audit log
+#[derive(Clone, Debug)]
+pub struct AuthSubject(pub String);
+
+/// Key with access defined by its role
+#[derive(Clone)]
+struct RoleKey {
+    key: String,
+    role: String,
+    access: Access,
+    /// Shared by all clones of the keys, so that the limit applies to all APIs
+    rate_limiter: Option<Arc<RateLimiter>>,
+    ip_filter: Option<IpFilterConfig>,
+    /// Expiration time, seconds since UNIX epoch
+    expires_at: Option<u64>,
+}
+
 /// The API keys used for auth
 #[derive(Clone)]
 pub struct AuthKeys {
+    /// Keys are shared by all clones, so that rotated keys apply to all APIs at once
+    keys: Arc<RwLock<Keys>>,
+
+    /// Table of content, needed to do stateful validation of JWT and to check revocations
+    toc: Arc<TableOfContent>,
+}
+
+struct Keys {
     /// A key allowing Read or Write operations
     read_write: Option<String>,
 
     /// A key allowing Read operations
     read_only: Option<String>,
 
+    /// Keys with access defined by their roles
+    role_keys: Vec<RoleKey>,
+
     /// A JWT parser, based on the read_write key
     jwt_parser: Option<JwtParser>,
-
-    /// Table of content, needed to do stateful validation of JWT
-    toc: Arc<TableOfContent>,
 }
 
 #[derive(Debug)]
@@ -38,9 +69,23 @@ pub enum AuthError {
     Unauthorized(String),
     Forbidden(String),
     StorageError(StorageError),
+    RateLimited(RateLimited),
 }
 
-impl AuthKeys {
+impl Keys {
+    fn new(service_config: &ServiceConfig) -> Self {
+        Self {
+            read_write: service_config.api_key.clone(),
+            read_only: service_config.read_only_api_key.clone(),
+            role_keys: Self::get_role_keys(service_config),
+            jwt_parser: Self::get_jwt_parser(service_config),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.read_write.is_none() && self.read_only.is_none() && self.role_keys.is_empty()
+    }
+
     fn get_jwt_parser(service_config: &ServiceConfig) -> Option<JwtParser> {
         if service_config.jwt_rbac.unwrap_or_default() {
             service_config
@@ -52,29 +97,92 @@ impl AuthKeys {
         }
     }
 
+    /// Resolve access and rate limits of the role-based keys, keys with unknown roles are ignored
+    fn get_role_keys(service_config: &ServiceConfig) -> Vec<RoleKey> {
+        service_config
+            .api_keys
+            .iter()
+            .filter_map(|api_key| match service_config.roles.get(&api_key.role) {
+                Some(access) => Some(RoleKey {
+                    key: api_key.key.clone(),
+                    role: api_key.role.clone(),
+                    access: access.clone(),
+                    rate_limiter: api_key
+                        .rate_limit
+                        .as_ref()
+                        .or_else(|| service_config.rate_limits.get(&api_key.role))
+                        .map(|rate_limit| Arc::new(RateLimiter::new(rate_limit))),
+                    ip_filter: api_key.ip_filter.clone(),
+                    expires_at: api_key.expires_at,
+                }),
+                None => {
+                    log::error!(
+                        "API key is ignored, because its role {} is not defined",
+                        api_key.role,
+                    );
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Check if a key is allowed to read
+    #[inline]
+    fn can_read(&self, key: &str) -> bool {
+        self.read_only
+            .as_ref()
+            .map(|ro_key| ct_eq(ro_key, key))
+            .unwrap_or_default()
+    }
+
+    /// Role-based key, which matches the key
+    #[inline]
+    fn role_key(&self, key: &str) -> Option<&RoleKey> {
+        self.role_keys
+            .iter()
+            .find(|role_key| ct_eq(&role_key.key, key))
+    }
+
+    /// Check if a key is allowed to write
+    #[inline]
+    fn can_write(&self, key: &str) -> bool {
+        self.read_write
+            .as_ref()
+            .map(|rw_key| ct_eq(rw_key, key))
+            .unwrap_or_default()
+    }
+}
+
+impl AuthKeys {
     /// Defines the auth scheme given the service config
     ///
     /// Returns None if no scheme is specified.
     pub fn try_create(service_config: &ServiceConfig, toc: Arc<TableOfContent>) -> Option<Self> {
-        match (
-            service_config.api_key.clone(),
-            service_config.read_only_api_key.clone(),
-        ) {
-            (None, None) => None,
-            (read_write, read_only) => Some(Self {
-                read_write,
-                read_only,
-                jwt_parser: Self::get_jwt_parser(service_config),
-                toc,
-            }),
+        let keys = Keys::new(service_config);
+        if keys.is_empty() {
+            return None;
         }
+        Some(Self {
+            keys: Arc::new(RwLock::new(keys)),
+            toc,
+        })
+    }
+
+    /// Replace the keys with rotated ones from the service config.
+    ///
+    /// Rate limits of role-based keys start over.
+    pub fn update(&self, service_config: &ServiceConfig) {
+        *self.keys.write() = Keys::new(service_config);
     }
 
     /// Validate that the specified request is allowed for given keys.
+    ///
+    /// `remote_ip` is checked against the IP filter of the key, if the key has one.
     pub async fn validate_request<'a>(
         &self,
         get_header: impl Fn(&'a str) -> Option<&'a str>,
-    ) -> Result<Access, AuthError> {
+        remote_ip: Option<IpAddr>,
+    ) -> Result<(Access, AuthSubject, Option<Arc<RateLimiter>>), AuthError> {
         let Some(key) = get_header(HTTP_HEADER_API_KEY)
             .or_else(|| get_header("authorization").and_then(|v| v.strip_prefix("Bearer ")))
         else {
@@ -83,17 +191,65 @@ impl AuthKeys {
             ));
         };
 
-        if self.can_write(key) {
-            return Ok(Access::full("Read-write access by key"));
+        // The master key is never revoked, so that the cluster can't be locked out of management
+        if self.keys.read().can_write(key) {
+            return Ok((
+                Access::full("Read-write access by key"),
+                AuthSubject("api_key".to_string()),
+                None,
+            ));
         }
 
-        if self.can_read(key) {
-            return Ok(Access::full_ro("Read-only access by key"));
+        if self.toc.is_credential_revoked(&credential_id(key)) {
+            return Err(AuthError::Unauthorized(
+                "API key or token has been revoked".to_string(),
+            ));
         }
 
-        if let Some(claims) = self.jwt_parser.as_ref().and_then(|p| p.decode(key)) {
+        let claims = {
+            let keys = self.keys.read();
+
+            if keys.can_read(key) {
+                return Ok((
+                    Access::full_ro("Read-only access by key"),
+                    AuthSubject("read_only_api_key".to_string()),
+                    None,
+                ));
+            }
+
+            if let Some(role_key) = keys.role_key(key) {
+                if role_key
+                    .expires_at
+                    .map_or(false, |expires_at| expires_at <= now_secs())
+                {
+                    return Err(AuthError::Unauthorized("API key has expired".to_string()));
+                }
+                if let Some(ip_filter) = &role_key.ip_filter {
+                    if !remote_ip.map_or(false, |ip| ip_filter.is_allowed(ip)) {
+                        return Err(AuthError::Forbidden(
+                            "API key is not allowed from this address".to_string(),
+                        ));
+                    }
+                }
+                if let Some(rate_limiter) = &role_key.rate_limiter {
+                    rate_limiter
+                        .check_request()
+                        .map_err(AuthError::RateLimited)?;
+                }
+                return Ok((
+                    role_key.access.clone(),
+                    AuthSubject(format!("role:{}", role_key.role)),
+                    role_key.rate_limiter.clone(),
+                ));
+            }
+
+            keys.jwt_parser.as_ref().and_then(|p| p.decode(key))
+        };
+
+        if let Some(claims) = claims {
             let Claims {
                 exp: _, // already validated on decoding
+                sub,
                 access,
                 value_exists,
             } = claims?;
@@ -102,7 +258,11 @@ impl AuthKeys {
                 self.validate_value_exists(&value_exists).await?;
             }
 
-            return Ok(access);
+            let subject = match sub {
+                Some(sub) => format!("jwt:{sub}"),
+                None => "jwt".to_string(),
+            };
+            return Ok((access, AuthSubject(subject), None));
         }
 
         Err(AuthError::Unauthorized(
@@ -145,22 +305,11 @@ impl AuthKeys {
 
         Ok(())
     }
+}
 
-    /// Check if a key is allowed to read
-    #[inline]
-    fn can_read(&self, key: &str) -> bool {
-        self.read_only
-            .as_ref()
-            .map(|ro_key| ct_eq(ro_key, key))
-            .unwrap_or_default()
-    }
-
-    /// Check if a key is allowed to write
-    #[inline]
-    fn can_write(&self, key: &str) -> bool {
-        self.read_write
-            .as_ref()
-            .map(|rw_key| ct_eq(rw_key, key))
-            .unwrap_or_default()
-    }
+/// Current time, seconds since UNIX epoch
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
 }
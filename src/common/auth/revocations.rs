@@ -0,0 +1,136 @@
+use std::fmt::Write as _;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use storage::content_manager::consensus_ops::ConsensusOperations;
+use storage::content_manager::errors::StorageError;
+use storage::dispatcher::Dispatcher;
+use storage::rbac::revocations::{CredentialRevocation, RevokedCredentials};
+use storage::rbac::{Access, AccessRequirements};
+use validator::Validate;
+
+use crate::common::strings::ct_eq;
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate)]
+pub struct RevokeCredential {
+    /// API key or JWT to revoke. Either the credential or its identifier must be specified.
+    pub credential: Option<String>,
+    /// Identifier of the credential to revoke: hex-encoded SHA-256 hash of the API key or JWT
+    #[validate(length(equal = 64))]
+    pub credential_id: Option<String>,
+    /// Revocation is dropped after this time, seconds since UNIX epoch.
+    /// Set it to the expiration time of a JWT to keep the revocation list short.
+    pub expires_at: Option<u64>,
+}
+
+/// Identifier of an API key or JWT in the revocation list, so the list never holds secrets
+pub fn credential_id(credential: &str) -> String {
+    Sha256::digest(credential.as_bytes())
+        .iter()
+        .fold(String::with_capacity(64), |mut id, byte| {
+            let _ = write!(id, "{byte:02x}");
+            id
+        })
+}
+
+pub fn do_get_revoked_credentials(
+    dispatcher: &Dispatcher,
+    access: Access,
+) -> Result<RevokedCredentials, StorageError> {
+    access.check_global_access(AccessRequirements::new().manage())?;
+    Ok(dispatcher.toc(&access).revoked_credentials())
+}
+
+/// Revoke the credential on all peers, it is rejected from then on
+///
+/// The master API key can't be revoked, so that the cluster can't be locked out of management.
+pub async fn do_revoke_credential(
+    dispatcher: &Dispatcher,
+    request: RevokeCredential,
+    master_api_key: Option<&str>,
+    access: Access,
+    timeout: Option<Duration>,
+) -> Result<bool, StorageError> {
+    access.check_global_access(AccessRequirements::new().manage())?;
+
+    let credential_id = match (request.credential, request.credential_id) {
+        (Some(credential), None) => credential_id(&credential),
+        (None, Some(credential_id)) => credential_id.to_ascii_lowercase(),
+        _ => {
+            return Err(StorageError::bad_input(
+                "Either credential or credential_id must be specified",
+            ))
+        }
+    };
+    if master_api_key.map_or(false, |key| ct_eq(&credential_id(key), &credential_id)) {
+        return Err(StorageError::bad_input("Master API key can't be revoked"));
+    }
+
+    let revoked_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs());
+    let revocation = CredentialRevocation {
+        revoked_at,
+        expires_at: request.expires_at,
+    };
+
+    set_credential_revocation(
+        dispatcher,
+        credential_id,
+        Some(revocation),
+        &access,
+        timeout,
+    )
+    .await
+}
+
+/// Remove the credential from the revocation list on all peers
+pub async fn do_restore_credential(
+    dispatcher: &Dispatcher,
+    credential_id: String,
+    access: Access,
+    timeout: Option<Duration>,
+) -> Result<bool, StorageError> {
+    access.check_global_access(AccessRequirements::new().manage())?;
+    set_credential_revocation(dispatcher, credential_id, None, &access, timeout).await
+}
+
+async fn set_credential_revocation(
+    dispatcher: &Dispatcher,
+    credential_id: String,
+    revocation: Option<CredentialRevocation>,
+    access: &Access,
+    timeout: Option<Duration>,
+) -> Result<bool, StorageError> {
+    match dispatcher.consensus_state() {
+        Some(consensus_state) => {
+            consensus_state
+                .propose_consensus_op_with_await(
+                    ConsensusOperations::SetCredentialRevocation {
+                        credential_id,
+                        revocation,
+                    },
+                    timeout,
+                )
+                .await
+        }
+        None => dispatcher
+            .toc(access)
+            .set_credential_revocation(credential_id, revocation),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_credential_id() {
+        assert_eq!(
+            credential_id("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+        );
+    }
+}
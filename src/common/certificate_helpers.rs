@@ -7,6 +7,7 @@ use std::time::{Duration, Instant};
 use parking_lot::RwLock;
 use rustls::client::VerifierBuilderError;
 use rustls::pki_types::CertificateDer;
+use rustls::server::danger::ClientCertVerifier;
 use rustls::server::{ClientHello, ResolvesServerCert, WebPkiClientVerifier};
 use rustls::sign::CertifiedKey;
 use rustls::{crypto, RootCertStore, ServerConfig};
@@ -150,15 +151,7 @@ pub fn actix_tls_server_config(settings: &Settings) -> Result<ServerConfig> {
 
     // Verify client CA or not
     let config = if settings.service.verify_https_client_certificate {
-        let mut root_cert_store = RootCertStore::empty();
-        let ca_certs: Vec<CertificateDer> = with_buf_read(&tls_config.ca_cert, |rd| {
-            rustls_pemfile::certs(rd).collect()
-        })?;
-        root_cert_store.add_parsable_certificates(ca_certs);
-        let client_cert_verifier = WebPkiClientVerifier::builder(root_cert_store.into())
-            .build()
-            .map_err(Error::ClientCertVerifier)?;
-        config.with_client_cert_verifier(client_cert_verifier)
+        config.with_client_cert_verifier(load_client_cert_verifier(&tls_config)?)
     } else {
         config.with_no_client_auth()
     };
@@ -174,6 +167,34 @@ pub fn actix_tls_server_config(settings: &Settings) -> Result<ServerConfig> {
     Ok(config)
 }
 
+/// Generate a server configuration with TLS for internal gRPC
+///
+/// Clients must present a certificate signed by the configured CA. Certificates are not rotated by
+/// this configuration, a new one is generated instead when they are changed on disk.
+pub fn internal_tls_server_config(tls_config: &TlsConfig) -> Result<ServerConfig> {
+    let cert_resolver = RotatingCertificateResolver::new(tls_config.clone(), None)?;
+    let mut config = ServerConfig::builder()
+        .with_client_cert_verifier(load_client_cert_verifier(tls_config)?)
+        .with_cert_resolver(Arc::new(cert_resolver));
+
+    // gRPC is served over HTTP/2 only
+    config.alpn_protocols = vec![b"h2".to_vec()];
+
+    Ok(config)
+}
+
+/// Construct verifier, which accepts client certificates signed by the configured CA.
+fn load_client_cert_verifier(tls_config: &TlsConfig) -> Result<Arc<dyn ClientCertVerifier>> {
+    let mut root_cert_store = RootCertStore::empty();
+    let ca_certs: Vec<CertificateDer> = with_buf_read(&tls_config.ca_cert, |rd| {
+        rustls_pemfile::certs(rd).collect()
+    })?;
+    root_cert_store.add_parsable_certificates(ca_certs);
+    WebPkiClientVerifier::builder(root_cert_store.into())
+        .build()
+        .map_err(Error::ClientCertVerifier)
+}
+
 fn with_buf_read<T>(path: &str, f: impl FnOnce(&mut dyn BufRead) -> io::Result<T>) -> Result<T> {
     let file = File::open(path).map_err(|err| Error::OpenFile(err, path.into()))?;
     let mut reader = BufReader::new(file);
@@ -181,7 +202,7 @@ fn with_buf_read<T>(path: &str, f: impl FnOnce(&mut dyn BufRead) -> io::Result<T
     f(dyn_reader).map_err(|err| Error::ReadFile(err, path.into()))
 }
 
-/// Actix TLS errors.
+/// Server TLS errors.
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
     #[error("TLS file could not be opened: {1}")]
@@ -0,0 +1,87 @@
+use std::time::Duration;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use storage::content_manager::consensus_manager::ConsensusStateRef;
+use storage::content_manager::consensus_ops::ConsensusOperations;
+use storage::content_manager::errors::StorageError;
+use storage::dispatcher::Dispatcher;
+use storage::rbac::{Access, AccessRequirements};
+use storage::types::{ClusterMetadata, ClusterMetadataEntry};
+use validator::Validate;
+
+const MAX_KEY_LENGTH: usize = 256;
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate)]
+pub struct SetClusterMetadata {
+    #[validate(length(max = 65536))]
+    pub value: String,
+    /// Set the value only if the current version of the key matches.
+    /// Version `0` sets the value only if the key does not exist.
+    pub expected_version: Option<u64>,
+}
+
+fn consensus_state(dispatcher: &Dispatcher) -> Result<&ConsensusStateRef, StorageError> {
+    dispatcher
+        .consensus_state()
+        .ok_or_else(|| StorageError::BadRequest {
+            description: "Distributed mode disabled.".to_string(),
+        })
+}
+
+fn check_key(key: &str) -> Result<(), StorageError> {
+    if key.is_empty() || key.len() > MAX_KEY_LENGTH {
+        return Err(StorageError::bad_input(format!(
+            "Cluster metadata key must be from 1 to {MAX_KEY_LENGTH} bytes long",
+        )));
+    }
+    Ok(())
+}
+
+pub fn do_get_cluster_metadata(
+    dispatcher: &Dispatcher,
+    access: Access,
+) -> Result<ClusterMetadata, StorageError> {
+    access.check_global_access(AccessRequirements::new())?;
+    Ok(consensus_state(dispatcher)?.cluster_metadata())
+}
+
+pub fn do_get_cluster_metadata_key(
+    dispatcher: &Dispatcher,
+    key: &str,
+    access: Access,
+) -> Result<ClusterMetadataEntry, StorageError> {
+    access.check_global_access(AccessRequirements::new())?;
+    consensus_state(dispatcher)?
+        .cluster_metadata()
+        .remove(key)
+        .ok_or_else(|| StorageError::NotFound {
+            description: format!("Cluster metadata key {key} not found"),
+        })
+}
+
+/// Set the key of the cluster metadata through consensus
+///
+/// Fails with a conflict if `expected_version` does not match the current version of the key.
+pub async fn do_set_cluster_metadata_key(
+    dispatcher: &Dispatcher,
+    key: String,
+    value: Option<String>,
+    expected_version: Option<u64>,
+    access: Access,
+    timeout: Option<Duration>,
+) -> Result<bool, StorageError> {
+    access.check_global_access(AccessRequirements::new().write())?;
+    check_key(&key)?;
+
+    consensus_state(dispatcher)?
+        .propose_consensus_op_with_await(
+            ConsensusOperations::SetClusterMetadata {
+                key,
+                value,
+                expected_version,
+            },
+            timeout,
+        )
+        .await
+}
@@ -1,34 +1,47 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 
 use api::grpc::models::{CollectionDescription, CollectionsResponse};
 use api::grpc::qdrant::CollectionExists;
+use collection::collection::async_replication::AsyncReplicationStatus;
+use collection::collection::event_log::CollectionEvents;
 use collection::config::ShardingMethod;
 use collection::operations::cluster_ops::{
     AbortTransferOperation, ClusterOperations, DropReplicaOperation, MoveShardOperation,
     ReplicateShardOperation, RestartTransfer, RestartTransferOperation, StartResharding,
 };
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
-use collection::operations::snapshot_ops::SnapshotDescription;
+use collection::operations::snapshot_ops::{
+    SnapshotDescription, SnapshotRecoveryStatus, SnapshotSchedule, SnapshotScheduleInfo,
+};
 use collection::operations::types::{
-    AliasDescription, CollectionClusterInfo, CollectionInfo, CollectionsAliasesResponse,
+    AliasDescription, CollectionClusterInfo, CollectionDiskUsage, CollectionIndexInfo,
+    CollectionInfo, CollectionPointsBreakdown, CollectionQuotaUsage, CollectionsAliasesResponse,
+    ForceOptimizationProgress, VacuumResult,
 };
+use collection::shards::local_shard::force_optimize::run_forced_optimizations;
+use collection::shards::local_shard::vacuum::{run_vacuums, Vacuum};
 use collection::shards::replica_set;
 use collection::shards::shard::{PeerId, ShardId, ShardsPlacement};
 use collection::shards::transfer::{ShardTransfer, ShardTransferKey, ShardTransferRestart};
 use itertools::Itertools;
+use parking_lot::Mutex;
 use rand::prelude::SliceRandom;
 use storage::content_manager::collection_meta_ops::ShardTransferOperations::{Abort, Start};
 use storage::content_manager::collection_meta_ops::{
-    CollectionMetaOperations, CreateShardKey, DropShardKey, ReshardingOperation,
-    ShardTransferOperations, UpdateCollectionOperation,
+    CloneCollection, CollectionMetaOperations, CreateCollection, CreateCollectionOperation,
+    CreateShardKey, DropShardKey, InitFrom, ReshardingOperation, ShardTransferOperations,
+    UpdateCollectionOperation,
 };
 use storage::content_manager::errors::StorageError;
 use storage::content_manager::toc::TableOfContent;
 use storage::dispatcher::Dispatcher;
-use storage::rbac::{Access, AccessRequirements};
+use storage::rbac::{Access, AccessRequirements, SnapshotAction};
 use tokio::task::JoinHandle;
 
+use crate::common::async_operations::{AsyncOperationAccepted, AsyncOperations};
+
 pub async fn do_collection_exists(
     toc: &TableOfContent,
     access: Access,
@@ -94,14 +107,19 @@ pub async fn do_list_collections(
 ///         [B, C]
 ///         [A, C]
 /// ]
+///
+/// Peers of different zones are interleaved in the pool, so replicas of a shard end up in
+/// different zones when possible.
 fn generate_even_placement(
     mut pool: Vec<PeerId>,
     shard_number: usize,
     replication_factor: usize,
+    peer_zones: &HashMap<PeerId, String>,
 ) -> ShardsPlacement {
     let mut exact_placement = Vec::new();
     let mut rng = rand::thread_rng();
     pool.shuffle(&mut rng);
+    let pool = interleave_zones(pool, peer_zones);
     let mut loop_iter = pool.iter().cycle();
 
     // pool: [1,2,3,4]
@@ -121,6 +139,32 @@ fn generate_even_placement(
     exact_placement
 }
 
+/// Reorder peers, so that consecutive peers are in different zones as much as possible.
+/// Peers without a zone are considered to be in a zone of their own.
+fn interleave_zones(pool: Vec<PeerId>, peer_zones: &HashMap<PeerId, String>) -> Vec<PeerId> {
+    let mut groups: Vec<Vec<PeerId>> = Vec::new();
+    let mut group_by_zone: HashMap<&str, usize> = HashMap::new();
+    for peer_id in pool {
+        match peer_zones.get(&peer_id) {
+            Some(zone) => {
+                let group = *group_by_zone.entry(zone.as_str()).or_insert_with(|| {
+                    groups.push(Vec::new());
+                    groups.len() - 1
+                });
+                groups[group].push(peer_id);
+            }
+            None => groups.push(vec![peer_id]),
+        }
+    }
+
+    // Largest zones first, so they are spread over the whole cycle
+    groups.sort_by_key(|group| std::cmp::Reverse(group.len()));
+    let max_group_len = groups.first().map_or(0, Vec::len);
+    (0..max_group_len)
+        .flat_map(|i| groups.iter().filter_map(move |group| group.get(i).copied()))
+        .collect()
+}
+
 pub async fn do_list_collection_aliases(
     toc: &TableOfContent,
     access: Access,
@@ -153,8 +197,12 @@ pub async fn do_list_snapshots(
     access: Access,
     collection_name: &str,
 ) -> Result<Vec<SnapshotDescription>, StorageError> {
-    let collection_pass =
-        access.check_collection_access(collection_name, AccessRequirements::new().whole())?;
+    let collection_pass = access.check_collection_access(
+        collection_name,
+        AccessRequirements::new()
+            .whole()
+            .snapshot(SnapshotAction::List),
+    )?;
     Ok(toc
         .get_collection(&collection_pass)
         .await?
@@ -166,15 +214,70 @@ pub fn do_create_snapshot(
     toc: Arc<TableOfContent>,
     access: Access,
     collection_name: &str,
+    encryption_key: Option<String>,
 ) -> Result<JoinHandle<Result<SnapshotDescription, StorageError>>, StorageError> {
     let collection_pass = access
-        .check_collection_access(collection_name, AccessRequirements::new().write().whole())?
+        .check_collection_access(
+            collection_name,
+            AccessRequirements::new()
+                .whole()
+                .snapshot(SnapshotAction::Create),
+        )?
         .into_static();
     Ok(tokio::spawn(async move {
-        toc.create_snapshot(&collection_pass).await
+        toc.create_snapshot(&collection_pass, encryption_key.as_deref())
+            .await
     }))
 }
 
+pub async fn do_get_snapshot_schedule(
+    toc: &TableOfContent,
+    access: Access,
+    collection_name: &str,
+) -> Result<SnapshotScheduleInfo, StorageError> {
+    let collection_pass =
+        access.check_collection_access(collection_name, AccessRequirements::new().whole())?;
+    let schedule = toc
+        .get_collection(&collection_pass)
+        .await?
+        .snapshot_schedule()
+        .ok_or_else(|| {
+            StorageError::not_found(format!(
+                "Collection {collection_name} has no snapshot schedule"
+            ))
+        })?;
+    let next_run = schedule.next_run_after(chrono::Utc::now().naive_utc());
+    Ok(SnapshotScheduleInfo { schedule, next_run })
+}
+
+pub fn do_get_snapshot_recovery_status(
+    toc: &TableOfContent,
+    access: Access,
+    collection_name: &str,
+) -> Result<SnapshotRecoveryStatus, StorageError> {
+    access.check_collection_access(collection_name, AccessRequirements::new().whole())?;
+    toc.snapshot_recovery_status(collection_name)
+        .ok_or_else(|| {
+            StorageError::not_found(format!(
+                "Collection {collection_name} was not recovered from a snapshot on this node"
+            ))
+        })
+}
+
+pub async fn do_set_snapshot_schedule(
+    toc: &TableOfContent,
+    access: Access,
+    collection_name: &str,
+    schedule: Option<SnapshotSchedule>,
+) -> Result<bool, StorageError> {
+    let collection_pass = access
+        .check_collection_access(collection_name, AccessRequirements::new().write().whole())?;
+    toc.get_collection(&collection_pass)
+        .await?
+        .set_snapshot_schedule(schedule)?;
+    Ok(true)
+}
+
 pub async fn do_get_collection_cluster(
     toc: &TableOfContent,
     access: Access,
@@ -186,6 +289,193 @@ pub async fn do_get_collection_cluster(
     Ok(collection.cluster_info(toc.this_peer_id).await?)
 }
 
+pub async fn do_get_collection_points_breakdown(
+    toc: &TableOfContent,
+    access: Access,
+    name: &str,
+) -> Result<CollectionPointsBreakdown, StorageError> {
+    let collection_pass =
+        access.check_collection_access(name, AccessRequirements::new().whole())?;
+    let collection = toc.get_collection(&collection_pass).await?;
+    Ok(collection.points_breakdown(toc.this_peer_id).await?)
+}
+
+pub async fn do_get_collection_disk_usage(
+    toc: &TableOfContent,
+    access: Access,
+    name: &str,
+) -> Result<CollectionDiskUsage, StorageError> {
+    let collection_pass =
+        access.check_collection_access(name, AccessRequirements::new().whole())?;
+    let collection = toc.get_collection(&collection_pass).await?;
+    Ok(collection.disk_usage(toc.this_peer_id).await?)
+}
+
+pub async fn do_get_collection_index_info(
+    toc: &TableOfContent,
+    access: Access,
+    name: &str,
+) -> Result<CollectionIndexInfo, StorageError> {
+    let collection_pass =
+        access.check_collection_access(name, AccessRequirements::new().whole())?;
+    let collection = toc.get_collection(&collection_pass).await?;
+    Ok(collection.index_info(toc.this_peer_id).await?)
+}
+
+pub async fn do_get_collection_events(
+    toc: &TableOfContent,
+    access: Access,
+    name: &str,
+    limit: Option<usize>,
+) -> Result<CollectionEvents, StorageError> {
+    let collection_pass =
+        access.check_collection_access(name, AccessRequirements::new().whole())?;
+    let collection = toc.get_collection(&collection_pass).await?;
+    Ok(collection.events(limit).await)
+}
+
+/// Start optimization of segments of local shards, which are not indexed yet, regardless of
+/// optimizer thresholds. Status of each segment is reported in the progress of the operation.
+pub async fn do_force_optimize(
+    toc: &TableOfContent,
+    access: Access,
+    name: &str,
+    shard_id: Option<ShardId>,
+    operations: &Arc<AsyncOperations>,
+) -> Result<AsyncOperationAccepted, StorageError> {
+    let collection_pass =
+        access.check_collection_access(name, AccessRequirements::new().write().whole())?;
+    let optimizations = toc
+        .get_collection(&collection_pass)
+        .await?
+        .forced_optimizations(shard_id)
+        .await?;
+
+    let progress = Arc::new(Mutex::new(ForceOptimizationProgress::default()));
+    operations.spawn_with_progress(
+        "optimize",
+        name,
+        &access,
+        {
+            let progress = progress.clone();
+            move || serde_json::to_value(&*progress.lock()).unwrap_or_default()
+        },
+        async move { Ok(run_forced_optimizations(optimizations, &progress).await) },
+    )
+}
+
+async fn plan_vacuums(
+    toc: &TableOfContent,
+    access: &Access,
+    name: &str,
+    deleted_threshold: Option<f64>,
+) -> Result<Vec<Vacuum>, StorageError> {
+    let collection_pass =
+        access.check_collection_access(name, AccessRequirements::new().write().whole())?;
+    let collection = toc.get_collection(&collection_pass).await?;
+    Ok(collection.vacuums(deleted_threshold).await)
+}
+
+pub async fn do_vacuum_collection(
+    toc: &TableOfContent,
+    access: Access,
+    name: &str,
+    deleted_threshold: Option<f64>,
+) -> Result<VacuumResult, StorageError> {
+    let vacuums = plan_vacuums(toc, &access, name, deleted_threshold).await?;
+    Ok(run_vacuums(vacuums).await?)
+}
+
+pub async fn do_vacuum_collection_async(
+    toc: &TableOfContent,
+    access: Access,
+    name: &str,
+    deleted_threshold: Option<f64>,
+    operations: &Arc<AsyncOperations>,
+) -> Result<AsyncOperationAccepted, StorageError> {
+    let vacuums = plan_vacuums(toc, &access, name, deleted_threshold).await?;
+    operations.spawn("vacuum", name, &access, async move {
+        Ok(run_vacuums(vacuums).await?)
+    })
+}
+
+pub async fn do_get_collection_quota_usage(
+    toc: &TableOfContent,
+    access: Access,
+    name: &str,
+) -> Result<CollectionQuotaUsage, StorageError> {
+    let collection_pass =
+        access.check_collection_access(name, AccessRequirements::new().whole())?;
+    let collection = toc.get_collection(&collection_pass).await?;
+    Ok(collection.quota_usage().await?)
+}
+
+pub async fn do_get_async_replication_status(
+    toc: &TableOfContent,
+    access: Access,
+    name: &str,
+) -> Result<AsyncReplicationStatus, StorageError> {
+    let collection_pass =
+        access.check_collection_access(name, AccessRequirements::new().whole())?;
+    toc.get_collection(&collection_pass)
+        .await?
+        .async_replication_status()
+        .await
+        .ok_or_else(|| {
+            StorageError::not_found(format!(
+                "Collection {name} has no asynchronous replication configured"
+            ))
+        })
+}
+
+/// Create a new collection with the configuration of `source_collection` and copy its points into it.
+///
+/// Points are transferred in the background, the new collection is available right after creation.
+pub async fn do_clone_collection(
+    dispatcher: &Dispatcher,
+    source_collection: String,
+    operation: CloneCollection,
+    access: Access,
+    wait_timeout: Option<Duration>,
+) -> Result<bool, StorageError> {
+    let collection_pass =
+        access.check_collection_access(&source_collection, AccessRequirements::new().whole())?;
+
+    let CloneCollection {
+        name,
+        vectors,
+        filter,
+    } = operation;
+
+    let config = dispatcher
+        .toc(&access)
+        .get_collection(&collection_pass)
+        .await?
+        .state()
+        .await
+        .config;
+
+    let mut create_collection = CreateCollection::from(config);
+    if let Some(vectors) = vectors {
+        create_collection.vectors = vectors;
+    }
+    create_collection.init_from = Some(InitFrom {
+        collection: source_collection,
+        filter,
+    });
+
+    dispatcher
+        .submit_collection_meta_op(
+            CollectionMetaOperations::CreateCollection(CreateCollectionOperation::new(
+                name,
+                create_collection,
+            )),
+            access,
+            wait_timeout,
+        )
+        .await
+}
+
 pub async fn do_update_collection_cluster(
     dispatcher: &Dispatcher,
     collection_name: String,
@@ -216,6 +506,14 @@ pub async fn do_update_collection_cluster(
             .collect_vec()
     };
 
+    let get_peer_zones = || -> HashMap<PeerId, String> {
+        consensus_state
+            .peer_metadata_by_id()
+            .into_iter()
+            .filter_map(|(peer_id, metadata)| Some((peer_id, metadata.zone()?.to_string())))
+            .collect()
+    };
+
     let validate_peer_exists = |peer_id| {
         let target_peer_exist = consensus_state
             .persistent
@@ -394,7 +692,36 @@ pub async fn do_update_collection_cluster(
                 .unwrap_or(state.config.params.replication_factor)
                 .get() as usize;
 
-            let shard_keys_mapping = state.shards_key_mapping;
+            let peer_metadata_by_id = consensus_state.peer_metadata_by_id();
+            let peer_matches_labels = |peer_id| {
+                state
+                    .config
+                    .params
+                    .placement_labels
+                    .as_ref()
+                    .map_or(true, |labels| {
+                        peer_metadata_by_id
+                            .get(&peer_id)
+                            .is_some_and(|metadata| metadata.has_labels(labels))
+                    })
+            };
+            let peer_is_read_only = |peer_id| {
+                peer_metadata_by_id
+                    .get(&peer_id)
+                    .is_some_and(|metadata| metadata.is_read_only())
+            };
+            let pinned_peers = state
+                .config
+                .params
+                .shard_pinning
+                .iter()
+                .flatten()
+                .find(|pin| pin.shard_key.as_ref() == Some(&create_sharding_key.shard_key))
+                .map(|pin| &pin.peers);
+            let peer_is_pinned =
+                |peer_id| pinned_peers.map_or(true, |peers| peers.contains(&peer_id));
+
+            let shard_keys_mapping = &state.shards_key_mapping;
             if shard_keys_mapping.contains_key(&create_sharding_key.shard_key) {
                 return Err(StorageError::BadRequest {
                     description: format!(
@@ -416,14 +743,56 @@ pub async fn do_update_collection_cluster(
 
                 for peer_id in placement.iter().copied() {
                     validate_peer_exists(peer_id)?;
+                    if peer_is_read_only(peer_id) {
+                        return Err(StorageError::BadRequest {
+                            description: format!(
+                                "Peer {peer_id} is in read-only mode and can't host new shards"
+                            ),
+                        });
+                    }
+                    if !peer_matches_labels(peer_id) {
+                        return Err(StorageError::BadRequest {
+                            description: format!(
+                                "Peer {peer_id} does not match placement labels of collection {collection_name}"
+                            ),
+                        });
+                    }
+                    if !peer_is_pinned(peer_id) {
+                        return Err(StorageError::BadRequest {
+                            description: format!(
+                                "Sharding key {} is pinned to peers {:?}, peer {peer_id} can't host it",
+                                create_sharding_key.shard_key,
+                                pinned_peers.unwrap_or(&Vec::new()),
+                            ),
+                        });
+                    }
                 }
                 placement
             } else {
-                get_all_peer_ids()
+                let pool = get_all_peer_ids()
+                    .into_iter()
+                    .filter(|&peer_id| {
+                        peer_matches_labels(peer_id)
+                            && !peer_is_read_only(peer_id)
+                            && peer_is_pinned(peer_id)
+                    })
+                    .collect_vec();
+                if pool.is_empty() {
+                    return Err(StorageError::BadRequest {
+                        description: format!(
+                            "No writable peers match placement labels and shard pinning of collection {collection_name}"
+                        ),
+                    });
+                }
+                pool
             };
 
-            let exact_placement =
-                generate_even_placement(peers_pool, shard_number, replication_factor);
+            let exact_placement = generate_even_placement(
+                peers_pool,
+                shard_number,
+                replication_factor,
+                &get_peer_zones(),
+            );
 
             dispatcher
                 .submit_collection_meta_op(
@@ -611,7 +980,7 @@ mod tests {
     #[test]
     fn test_generate_even_placement() {
         let pool = vec![1, 2, 3];
-        let placement = generate_even_placement(pool, 3, 2);
+        let placement = generate_even_placement(pool, 3, 2, &HashMap::new());
 
         assert_eq!(placement.len(), 3);
         for shard_placement in placement {
@@ -620,7 +989,7 @@ mod tests {
         }
 
         let pool = vec![1, 2, 3];
-        let placement = generate_even_placement(pool, 3, 3);
+        let placement = generate_even_placement(pool, 3, 3, &HashMap::new());
 
         assert_eq!(placement.len(), 3);
         for shard_placement in placement {
@@ -630,7 +999,7 @@ mod tests {
         }
 
         let pool = vec![1, 2, 3, 4, 5, 6];
-        let placement = generate_even_placement(pool, 3, 2);
+        let placement = generate_even_placement(pool, 3, 2, &HashMap::new());
 
         assert_eq!(placement.len(), 3);
         let flat_placement: Vec<_> = placement.into_iter().flatten().collect();
@@ -638,11 +1007,33 @@ mod tests {
         assert_eq!(set.len(), 6);
 
         let pool = vec![1, 2, 3, 4, 5];
-        let placement = generate_even_placement(pool, 3, 10);
+        let placement = generate_even_placement(pool, 3, 10, &HashMap::new());
 
         assert_eq!(placement.len(), 3);
         for shard_placement in placement {
             assert_eq!(shard_placement.len(), 5);
         }
     }
+
+    #[test]
+    fn test_generate_even_placement_across_zones() {
+        let peer_zones: HashMap<_, _> =
+            [(1, "a"), (2, "a"), (3, "b"), (4, "b"), (5, "c"), (6, "c")]
+                .into_iter()
+                .map(|(peer_id, zone)| (peer_id, zone.to_string()))
+                .collect();
+
+        for _ in 0..100 {
+            let placement = generate_even_placement(vec![1, 2, 3, 4, 5, 6], 4, 3, &peer_zones);
+
+            assert_eq!(placement.len(), 4);
+            for shard_placement in placement {
+                let zones: HashSet<_> = shard_placement
+                    .iter()
+                    .map(|peer| &peer_zones[peer])
+                    .collect();
+                assert_eq!(zones.len(), 3);
+            }
+        }
+    }
 }
@@ -0,0 +1,130 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use collection::shards::shard::PeerId;
+use storage::dispatcher::Dispatcher;
+use storage::rbac::Access;
+
+use crate::common::decommission::do_decommission_peer;
+use crate::settings::DeadPeerRemovalConfig;
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+const FULL_ACCESS: Access = Access::full("Dead peer removal");
+
+/// Background task, which removes peers unreachable for longer than the configured timeout
+///
+/// Only the consensus leader sends messages to all peers, so only the leader tracks them.
+/// Replicas of a dead peer are re-created on other peers before it is removed.
+pub async fn run_forever(dispatcher: Arc<Dispatcher>, config: DeadPeerRemovalConfig) {
+    let Some(consensus_state) = dispatcher.consensus_state().cloned() else {
+        return;
+    };
+
+    let timeout = Duration::from_secs(config.timeout_sec);
+    let mut tracker = UnreachablePeers::default();
+    loop {
+        tokio::time::sleep(CHECK_INTERVAL).await;
+
+        if !consensus_state.is_leader() {
+            tracker.clear();
+            continue;
+        }
+
+        let now = Instant::now();
+        tracker.update(&consensus_state.unreachable_peers(), now);
+
+        for peer_id in tracker.dead_peers(timeout, now) {
+            log::warn!(
+                "Peer {peer_id} is unreachable for more than {} seconds, removing it from the cluster",
+                timeout.as_secs(),
+            );
+
+            // Mark replicas of the peer dead, so they are re-created from other replicas
+            dispatcher
+                .toc(&FULL_ACCESS)
+                .report_unreachable_peer(peer_id)
+                .await;
+
+            match do_decommission_peer(&dispatcher, peer_id, FULL_ACCESS, None).await {
+                Ok(_) => log::info!("Dead peer {peer_id} removed from the cluster"),
+                Err(err) => log::error!(
+                    "Failed to remove dead peer {peer_id}, will retry after {} seconds: {err}",
+                    timeout.as_secs(),
+                ),
+            }
+
+            // Start over, either the peer is gone, or it gets another timeout before a retry
+            tracker.forget(peer_id);
+        }
+    }
+}
+
+/// Since when peers are unreachable
+#[derive(Default)]
+struct UnreachablePeers {
+    since: HashMap<PeerId, Instant>,
+}
+
+impl UnreachablePeers {
+    fn update(&mut self, unreachable: &HashSet<PeerId>, now: Instant) {
+        self.since
+            .retain(|peer_id, _| unreachable.contains(peer_id));
+        for &peer_id in unreachable {
+            self.since.entry(peer_id).or_insert(now);
+        }
+    }
+
+    fn dead_peers(&self, timeout: Duration, now: Instant) -> Vec<PeerId> {
+        let mut dead: Vec<_> = self
+            .since
+            .iter()
+            .filter(|(_, &since)| now.duration_since(since) >= timeout)
+            .map(|(&peer_id, _)| peer_id)
+            .collect();
+        dead.sort_unstable();
+        dead
+    }
+
+    fn forget(&mut self, peer_id: PeerId) {
+        self.since.remove(&peer_id);
+    }
+
+    fn clear(&mut self) {
+        self.since.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unreachable_peers() {
+        let timeout = Duration::from_secs(60);
+        let start = Instant::now();
+        let mut peers = UnreachablePeers::default();
+
+        peers.update(&HashSet::from([1, 2]), start);
+        peers.update(&HashSet::from([1, 2, 3]), start + Duration::from_secs(30));
+        assert!(peers
+            .dead_peers(timeout, start + Duration::from_secs(30))
+            .is_empty());
+        assert_eq!(peers.dead_peers(timeout, start + timeout), vec![1, 2]);
+
+        // Reachable peer is not tracked anymore, even if it fails again
+        peers.update(&HashSet::from([1, 3]), start + timeout);
+        peers.update(&HashSet::from([1, 2, 3]), start + timeout);
+        assert_eq!(
+            peers.dead_peers(timeout, start + Duration::from_secs(90)),
+            vec![1, 3],
+        );
+
+        peers.forget(1);
+        assert_eq!(
+            peers.dead_peers(timeout, start + Duration::from_secs(90)),
+            vec![3],
+        );
+    }
+}
@@ -0,0 +1,418 @@
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use collection::collection_state::State;
+use collection::shards::replica_set::{self, ReplicaState};
+use collection::shards::shard::{PeerId, ShardId};
+use collection::shards::transfer::{ShardTransfer, ShardTransferKey};
+use itertools::Itertools;
+use storage::content_manager::collection_meta_ops::{
+    CollectionMetaOperations, ShardTransferOperations, UpdateCollectionOperation,
+};
+use storage::content_manager::consensus_ops::ConsensusOperations;
+use storage::content_manager::errors::StorageError;
+use storage::dispatcher::Dispatcher;
+use storage::rbac::{Access, AccessRequirements, CollectionPass};
+
+/// How often to check the state of a shard transfer started by the decommission
+const TRANSFER_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Move all shard replicas away from the peer and remove it from the cluster.
+///
+/// Replicas are moved one by one, to the peers holding the least replicas of the collection.
+/// If every other peer already holds a replica of the shard, the replica is dropped instead.
+pub async fn do_decommission_peer(
+    dispatcher: &Dispatcher,
+    peer_id: PeerId,
+    access: Access,
+    timeout: Option<Duration>,
+) -> Result<bool, StorageError> {
+    access.check_global_access(AccessRequirements::new().manage())?;
+
+    let Some(consensus_state) = dispatcher.consensus_state() else {
+        return Err(StorageError::BadRequest {
+            description: "Distributed mode disabled.".to_string(),
+        });
+    };
+
+    let toc = dispatcher.toc(&access);
+
+    let peers = consensus_state
+        .persistent
+        .read()
+        .peer_address_by_id
+        .read()
+        .keys()
+        .copied()
+        .collect_vec();
+    if !peers.contains(&peer_id) {
+        return Err(StorageError::BadRequest {
+            description: format!("Peer {peer_id} does not exist"),
+        });
+    }
+
+    if !toc.start_peer_decommission(peer_id) {
+        return Err(StorageError::BadRequest {
+            description: format!("Peer {peer_id} is already being decommissioned"),
+        });
+    }
+
+    let read_only_peers: HashSet<_> = consensus_state
+        .peer_metadata_by_id()
+        .into_iter()
+        .filter(|(_, metadata)| metadata.is_read_only())
+        .map(|(id, _)| id)
+        .collect();
+    let candidates = peers
+        .into_iter()
+        .filter(|&id| {
+            id != peer_id && !toc.is_peer_decommissioning(id) && !read_only_peers.contains(&id)
+        })
+        .collect_vec();
+
+    let drain = drain_peer(dispatcher, peer_id, &candidates, &access);
+    let drained = match timeout {
+        Some(timeout) => tokio::time::timeout(timeout, drain)
+            .await
+            .unwrap_or_else(|_| {
+                Err(StorageError::Timeout {
+                    description: format!(
+                        "Peer {peer_id} was not drained in {} seconds",
+                        timeout.as_secs(),
+                    ),
+                })
+            }),
+        None => drain.await,
+    };
+    toc.finish_peer_decommission(peer_id);
+    drained?;
+
+    log::info!("Peer {peer_id} is drained, removing it from the cluster");
+
+    consensus_state
+        .propose_consensus_op_with_await(ConsensusOperations::RemovePeer(peer_id), timeout)
+        .await
+}
+
+async fn drain_peer(
+    dispatcher: &Dispatcher,
+    peer_id: PeerId,
+    candidates: &[PeerId],
+    access: &Access,
+) -> Result<(), StorageError> {
+    if candidates.is_empty() {
+        return Err(StorageError::BadRequest {
+            description: format!("Cannot decommission peer {peer_id}, there are no other peers"),
+        });
+    }
+
+    let toc = dispatcher.toc(access);
+    for collection_pass in toc.all_collections(access).await {
+        let shard_ids = collection_state(dispatcher, &collection_pass, access)
+            .await?
+            .shards
+            .into_iter()
+            .filter(|(_, shard_info)| shard_info.replicas.contains_key(&peer_id))
+            .map(|(shard_id, _)| shard_id)
+            .sorted()
+            .collect_vec();
+
+        for shard_id in shard_ids {
+            drain_shard(
+                dispatcher,
+                &collection_pass,
+                shard_id,
+                peer_id,
+                candidates,
+                access,
+            )
+            .await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Current state of the collection.
+///
+/// Collection is looked up on every call, to not hold the collections lock while waiting for
+/// transfers.
+async fn collection_state(
+    dispatcher: &Dispatcher,
+    collection_pass: &CollectionPass<'_>,
+    access: &Access,
+) -> Result<State, StorageError> {
+    Ok(dispatcher
+        .toc(access)
+        .get_collection(collection_pass)
+        .await?
+        .state()
+        .await)
+}
+
+async fn drain_shard(
+    dispatcher: &Dispatcher,
+    collection_pass: &CollectionPass<'_>,
+    shard_id: ShardId,
+    peer_id: PeerId,
+    candidates: &[PeerId],
+    access: &Access,
+) -> Result<(), StorageError> {
+    let collection_name = collection_pass.name();
+
+    // Let ongoing transfers of the shard finish first, only one transfer per shard is allowed
+    let state = loop {
+        let state = collection_state(dispatcher, collection_pass, access).await?;
+        if !state
+            .transfers
+            .iter()
+            .any(|transfer| transfer.shard_id == shard_id)
+        {
+            break state;
+        }
+        tokio::time::sleep(TRANSFER_CHECK_INTERVAL).await;
+    };
+
+    let Some(replicas) = state.shards.get(&shard_id).map(|info| &info.replicas) else {
+        // Shard was dropped in the meantime
+        return Ok(());
+    };
+    let Some(&replica_state) = replicas.get(&peer_id) else {
+        return Ok(());
+    };
+
+    let mut replica_counts: HashMap<PeerId, usize> = HashMap::new();
+    for shard_info in state.shards.values() {
+        for replica_peer in shard_info.replicas.keys() {
+            *replica_counts.entry(*replica_peer).or_default() += 1;
+        }
+    }
+    let peer_metadata_by_id = dispatcher
+        .consensus_state()
+        .map(|consensus_state| consensus_state.peer_metadata_by_id())
+        .unwrap_or_default();
+    let peer_zones = peer_metadata_by_id
+        .iter()
+        .filter_map(|(&id, metadata)| Some((id, metadata.zone()?.to_string())))
+        .collect();
+    // Only peers matching placement labels of the collection may receive the shard
+    let candidates = match &state.config.params.placement_labels {
+        Some(labels) => candidates
+            .iter()
+            .copied()
+            .filter(|id| {
+                peer_metadata_by_id
+                    .get(id)
+                    .is_some_and(|metadata| metadata.has_labels(labels))
+            })
+            .collect_vec(),
+        None => candidates.to_vec(),
+    };
+    // Pinned shards may only be moved to the peers they are pinned to
+    let candidates = match state.pinned_peers(shard_id) {
+        Some(pinned_peers) => candidates
+            .into_iter()
+            .filter(|id| pinned_peers.contains(id))
+            .collect_vec(),
+        None => candidates,
+    };
+    let target = select_target_peer(&candidates, peer_id, replicas, &replica_counts, &peer_zones);
+
+    // Transfer from the decommissioned replica if possible, it might contain the latest updates
+    let other_active = replicas
+        .iter()
+        .filter(|(&id, &state)| id != peer_id && state == ReplicaState::Active)
+        .map(|(&id, _)| id)
+        .min();
+    let source = if replica_state == ReplicaState::Active {
+        Some(peer_id)
+    } else {
+        other_active
+    };
+
+    match (target, source) {
+        (Some(to), Some(from)) => {
+            log::info!(
+                "Decommission of peer {peer_id}: transferring shard {collection_name}:{shard_id} \
+                 from peer {from} to peer {to}",
+            );
+            let sync = from != peer_id;
+            transfer_shard(
+                dispatcher,
+                collection_pass,
+                shard_id,
+                from,
+                to,
+                sync,
+                access,
+            )
+            .await?;
+            if sync {
+                drop_replica(dispatcher, collection_name, shard_id, peer_id, access).await?;
+            }
+        }
+        (None, _) if other_active.is_some() => {
+            log::warn!(
+                "Decommission of peer {peer_id}: all peers already hold shard \
+                 {collection_name}:{shard_id}, dropping its replica",
+            );
+            drop_replica(dispatcher, collection_name, shard_id, peer_id, access).await?;
+        }
+        (None, _) => {
+            return Err(StorageError::BadRequest {
+                description: format!(
+                    "Cannot move shard {collection_name}:{shard_id} from peer {peer_id}, \
+                     all other peers already hold it and none of them is active",
+                ),
+            });
+        }
+        (Some(_), None) => {
+            return Err(StorageError::BadRequest {
+                description: format!(
+                    "Cannot move shard {collection_name}:{shard_id} from peer {peer_id}, \
+                     there is no active replica to transfer from",
+                ),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Peer without a replica of the shard, which holds the least replicas of the collection.
+/// Peers in zones without other replicas of the shard are preferred.
+fn select_target_peer(
+    candidates: &[PeerId],
+    decommissioned_peer: PeerId,
+    replicas: &HashMap<PeerId, ReplicaState>,
+    replica_counts: &HashMap<PeerId, usize>,
+    peer_zones: &HashMap<PeerId, String>,
+) -> Option<PeerId> {
+    let used_zones: HashSet<_> = replicas
+        .keys()
+        .filter(|&&id| id != decommissioned_peer)
+        .filter_map(|id| peer_zones.get(id))
+        .collect();
+
+    candidates
+        .iter()
+        .copied()
+        .filter(|peer_id| !replicas.contains_key(peer_id))
+        .min_by_key(|peer_id| {
+            (
+                peer_zones
+                    .get(peer_id)
+                    .is_some_and(|zone| used_zones.contains(zone)),
+                replica_counts.get(peer_id).copied().unwrap_or_default(),
+                *peer_id,
+            )
+        })
+}
+
+async fn transfer_shard(
+    dispatcher: &Dispatcher,
+    collection_pass: &CollectionPass<'_>,
+    shard_id: ShardId,
+    from: PeerId,
+    to: PeerId,
+    sync: bool,
+    access: &Access,
+) -> Result<(), StorageError> {
+    let collection_name = collection_pass.name();
+    dispatcher
+        .submit_collection_meta_op(
+            CollectionMetaOperations::TransferShard(
+                collection_name.to_string(),
+                ShardTransferOperations::Start(ShardTransfer {
+                    shard_id,
+                    to_shard_id: None,
+                    from,
+                    to,
+                    sync,
+                    method: None,
+                }),
+            ),
+            access.clone(),
+            None,
+        )
+        .await?;
+
+    let transfer = ShardTransferKey { shard_id, from, to };
+    let state = loop {
+        let state = collection_state(dispatcher, collection_pass, access).await?;
+        if !state.transfers.iter().any(|t| t.key() == transfer) {
+            break state;
+        }
+        tokio::time::sleep(TRANSFER_CHECK_INTERVAL).await;
+    };
+
+    let replicas = state
+        .shards
+        .remove(&shard_id)
+        .map(|shard_info| shard_info.replicas)
+        .unwrap_or_default();
+    if replicas.get(&to) != Some(&ReplicaState::Active) {
+        return Err(StorageError::service_error(format!(
+            "Transfer of shard {collection_name}:{shard_id} from peer {from} to peer {to} failed",
+        )));
+    }
+
+    Ok(())
+}
+
+async fn drop_replica(
+    dispatcher: &Dispatcher,
+    collection_name: &str,
+    shard_id: ShardId,
+    peer_id: PeerId,
+    access: &Access,
+) -> Result<(), StorageError> {
+    let mut update_operation = UpdateCollectionOperation::new_empty(collection_name.to_string());
+    update_operation
+        .set_shard_replica_changes(vec![replica_set::Change::Remove(shard_id, peer_id)]);
+
+    dispatcher
+        .submit_collection_meta_op(
+            CollectionMetaOperations::UpdateCollection(update_operation),
+            access.clone(),
+            None,
+        )
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select_target_peer() {
+        let replicas = HashMap::from([(1, ReplicaState::Active), (2, ReplicaState::Active)]);
+        let replica_counts = HashMap::from([(1, 3), (2, 2), (3, 2), (4, 1)]);
+        let no_zones = HashMap::new();
+
+        assert_eq!(
+            select_target_peer(&[2, 3, 4], 1, &replicas, &replica_counts, &no_zones),
+            Some(4),
+        );
+        assert_eq!(
+            select_target_peer(&[2, 3, 5], 1, &replicas, &replica_counts, &no_zones),
+            Some(5),
+        );
+        assert_eq!(
+            select_target_peer(&[2], 1, &replicas, &replica_counts, &no_zones),
+            None,
+        );
+
+        // Peer 4 is in the same zone as the remaining replica on peer 2
+        let peer_zones: HashMap<_, _> = [(1, "a"), (2, "b"), (3, "c"), (4, "b")]
+            .into_iter()
+            .map(|(peer_id, zone)| (peer_id, zone.to_string()))
+            .collect();
+        assert_eq!(
+            select_target_peer(&[2, 3, 4], 1, &replicas, &replica_counts, &peer_zones),
+            Some(3),
+        );
+    }
+}
@@ -0,0 +1,144 @@
+//! Effective configuration of the node and its collections, to debug configuration drift.
+
+use std::collections::BTreeMap;
+
+use collection::config::CollectionConfig;
+use collection::shards::shard::PeerId;
+use schemars::JsonSchema;
+use serde::Serialize;
+use serde_json::Value;
+use storage::content_manager::errors::StorageError;
+use storage::content_manager::toc::TableOfContent;
+use storage::rbac::{Access, AccessRequirements};
+
+use crate::settings::Settings;
+
+/// Replacement of values of secret fields
+const MASKED_VALUE: &str = "<masked>";
+
+/// Maps of secrets of individual collections, keyed by collection name
+const SECRET_MAPS: &[&str] = &["collection_keys", "collection_api_keys"];
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct EffectiveConfig {
+    /// ID of this peer
+    pub peer_id: PeerId,
+    /// Configuration of the node: built-in defaults, merged with config files and `QDRANT__`
+    /// environment variables. Values of API keys, encryption keys and tokens are masked.
+    pub node: Value,
+    /// Configuration of each collection, with defaults of the node applied on its creation
+    pub collections: BTreeMap<String, CollectionConfig>,
+}
+
+pub async fn do_get_effective_config(
+    settings: &Settings,
+    toc: &TableOfContent,
+    access: Access,
+) -> Result<EffectiveConfig, StorageError> {
+    access.check_global_access(AccessRequirements::new().manage())?;
+
+    let mut node = settings.merged_config.clone();
+    mask_secrets(&mut node);
+
+    let mut collections = BTreeMap::new();
+    for collection_pass in toc.all_collections(&access).await {
+        // Collection may be deleted in the meantime
+        let Ok(collection) = toc.get_collection(&collection_pass).await else {
+            continue;
+        };
+        collections.insert(
+            collection_pass.name().to_string(),
+            collection.state().await.config,
+        );
+    }
+
+    Ok(EffectiveConfig {
+        peer_id: toc.this_peer_id,
+        node,
+        collections,
+    })
+}
+
+fn is_secret(field: &str) -> bool {
+    field == "key"
+        || field.ends_with("_key")
+        || field == "token"
+        || field.ends_with("_token")
+        || field.contains("password")
+}
+
+/// Mask values of secret fields, and of maps of secrets, in the configuration
+fn mask_secrets(value: &mut Value) {
+    match value {
+        Value::Object(fields) => {
+            for (field, value) in fields.iter_mut() {
+                if is_secret(field) {
+                    mask_value(value);
+                } else if SECRET_MAPS.contains(&field.as_str()) {
+                    if let Value::Object(secrets) = value {
+                        secrets.values_mut().for_each(mask_value);
+                    }
+                } else {
+                    mask_secrets(value);
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(mask_secrets),
+        Value::Null | Value::Bool(_) | Value::Number(_) | Value::String(_) => {}
+    }
+}
+
+/// Mask the value, unless it's not set
+fn mask_value(value: &mut Value) {
+    if !value.is_null() {
+        *value = Value::String(MASKED_VALUE.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_mask_secrets() {
+        let mut config = json!({
+            "service": {
+                "http_port": 6333,
+                "api_key": "secret",
+                "read_only_api_key": null,
+                "api_keys": [{"key": "secret", "role": "reader"}],
+            },
+            "storage": {
+                "wal_encryption": {
+                    "key": "secret",
+                    "key_file": "/run/key",
+                    "collection_keys": {"products": "secret"},
+                },
+            },
+            "secrets": {"vault": {"token": "secret", "token_file": "/run/token"}},
+        });
+        mask_secrets(&mut config);
+
+        assert_eq!(
+            config,
+            json!({
+                "service": {
+                    "http_port": 6333,
+                    "api_key": MASKED_VALUE,
+                    "read_only_api_key": null,
+                    "api_keys": [{"key": MASKED_VALUE, "role": "reader"}],
+                },
+                "storage": {
+                    "wal_encryption": {
+                        "key": MASKED_VALUE,
+                        "key_file": "/run/key",
+                        "collection_keys": {"products": MASKED_VALUE},
+                    },
+                },
+                "secrets": {"vault": {"token": MASKED_VALUE, "token_file": "/run/token"}},
+            }),
+        );
+    }
+}
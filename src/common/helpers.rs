@@ -1,7 +1,10 @@
 use std::cmp::max;
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
 use std::{fs, io};
 
+use api::grpc::transport_channel_pool::TransportChannelPool;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use tokio::runtime;
@@ -17,6 +20,12 @@ pub struct LocksOption {
     pub write: bool,
 }
 
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate)]
+pub struct ReadOnlyOption {
+    /// If true, the node rejects writes and incoming shard transfers, but keeps serving reads
+    pub read_only: bool,
+}
+
 pub fn create_search_runtime(max_search_threads: usize) -> io::Result<Runtime> {
     let mut search_threads = max_search_threads;
 
@@ -84,28 +93,42 @@ pub fn create_general_purpose_runtime() -> io::Result<Runtime> {
 pub fn load_tls_client_config(settings: &Settings) -> io::Result<Option<ClientTlsConfig>> {
     if settings.cluster.p2p.enable_tls {
         let tls_config = &settings.tls()?;
-        Ok(Some(
-            ClientTlsConfig::new()
-                .identity(load_identity(tls_config)?)
-                .ca_certificate(load_ca_certificate(tls_config)?),
-        ))
+        Ok(Some(load_tls_internal_client_config(tls_config)?))
     } else {
         Ok(None)
     }
 }
 
+fn load_tls_internal_client_config(tls_config: &TlsConfig) -> io::Result<ClientTlsConfig> {
+    Ok(ClientTlsConfig::new()
+        .identity(load_identity(tls_config)?)
+        .ca_certificate(load_ca_certificate(tls_config)?))
+}
+
+/// Reload client TLS configuration of the channel pool, when the certificates are rotated on disk
+pub async fn reload_tls_client_config_forever(
+    tls_config: TlsConfig,
+    channel_pool: Arc<TransportChannelPool>,
+) {
+    loop {
+        wait_tls_files_changed(&tls_config).await;
+        match load_tls_internal_client_config(&tls_config) {
+            Ok(client_config) => {
+                log::info!("TLS certificates are changed, reconnecting to peers");
+                channel_pool.update_tls_config(Some(client_config)).await;
+            }
+            Err(err) => {
+                log::error!("Failed to load rotated TLS certificates, keeping current: {err}");
+            }
+        }
+    }
+}
+
 /// Load server TLS configuration for external gRPC
 pub fn load_tls_external_server_config(tls_config: &TlsConfig) -> io::Result<ServerTlsConfig> {
     Ok(ServerTlsConfig::new().identity(load_identity(tls_config)?))
 }
 
-/// Load server TLS configuration for internal gRPC, check client certificate against CA
-pub fn load_tls_internal_server_config(tls_config: &TlsConfig) -> io::Result<ServerTlsConfig> {
-    Ok(ServerTlsConfig::new()
-        .identity(load_identity(tls_config)?)
-        .client_ca_root(load_ca_certificate(tls_config)?))
-}
-
 fn load_identity(tls_config: &TlsConfig) -> io::Result<Identity> {
     let cert = fs::read_to_string(&tls_config.cert)?;
     let key = fs::read_to_string(&tls_config.key)?;
@@ -117,6 +140,47 @@ fn load_ca_certificate(tls_config: &TlsConfig) -> io::Result<Certificate> {
     Ok(Certificate::from_pem(pem))
 }
 
+/// Content of the TLS files, compared to detect certificate rotation on disk
+#[derive(PartialEq, Eq)]
+struct TlsFilesContent {
+    cert: Vec<u8>,
+    key: Vec<u8>,
+    ca_cert: Vec<u8>,
+}
+
+impl TlsFilesContent {
+    fn read(tls_config: &TlsConfig) -> io::Result<Self> {
+        Ok(Self {
+            cert: fs::read(&tls_config.cert)?,
+            key: fs::read(&tls_config.key)?,
+            ca_cert: fs::read(&tls_config.ca_cert)?,
+        })
+    }
+}
+
+/// Wait until any of the TLS files is changed on disk.
+///
+/// Files are checked every `cert_ttl` seconds. Never resolves if `cert_ttl` is not configured.
+/// Files, which can't be read, e.g. in the middle of rotation, are not considered changed.
+pub async fn wait_tls_files_changed(tls_config: &TlsConfig) {
+    let Some(cert_ttl) = tls_config.cert_ttl else {
+        return futures::future::pending().await;
+    };
+    let interval = Duration::from_secs(cert_ttl);
+
+    let mut current = TlsFilesContent::read(tls_config).ok();
+    loop {
+        tokio::time::sleep(interval).await;
+
+        match TlsFilesContent::read(tls_config) {
+            Ok(content) if current.is_none() => current = Some(content),
+            Ok(content) if current.as_ref() != Some(&content) => return,
+            Ok(_) => (),
+            Err(err) => log::warn!("Failed to read TLS files to check for rotation: {err}"),
+        }
+    }
+}
+
 pub fn tonic_error_to_io_error(err: tonic::transport::Error) -> io::Error {
     io::Error::new(io::ErrorKind::Other, err)
 }
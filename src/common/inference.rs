@@ -0,0 +1,365 @@
+//! Embedding of documents and images, provided in requests instead of vectors.
+//!
+//! Raw inputs are collected from a request into an [`InferenceBatch`], sent to the embedding
+//! service configured for the collection, and replaced with the returned dense vectors. After
+//! that the request contains only vectors and is processed as usual.
+//!
+//! Documents for sparse vectors with text conversion configured are converted into sparse
+//! vectors by the server itself, without the embedding service.
+
+use std::collections::HashMap;
+use std::mem;
+use std::time::Duration;
+
+use api::rest::{BatchVectorStruct, InferenceInput, NamedVectorStruct, Vector, VectorStruct};
+use collection::config::InferenceConfig;
+use collection::operations::point_ops::PointInsertOperations;
+use collection::operations::types::SparseTextParams;
+use collection::operations::vector_ops::UpdateVectors;
+use reqwest::header::CONTENT_TYPE;
+use segment::data_types::vectors::{DenseVector, NamedSparseVector, NamedVector};
+use serde::{Deserialize, Serialize};
+use storage::content_manager::errors::StorageError;
+use storage::content_manager::toc::TableOfContent;
+use storage::rbac::{Access, AccessRequirements};
+
+use crate::common::http_client::HttpClient;
+
+#[derive(Serialize)]
+struct InferenceRequest<'a> {
+    model: &'a str,
+    inputs: Vec<&'a InferenceInput>,
+}
+
+#[derive(Deserialize)]
+struct InferenceResponse {
+    embeddings: Vec<DenseVector>,
+}
+
+/// Raw inputs of a request, which are embedded together before the request is processed
+#[derive(Default)]
+pub struct InferenceBatch<'a> {
+    /// Raw inputs of points with names of the vectors
+    vectors: Vec<(&'a str, &'a mut Vector)>,
+    queries: Vec<&'a mut NamedVectorStruct>,
+}
+
+impl<'a> InferenceBatch<'a> {
+    pub fn is_empty(&self) -> bool {
+        self.vectors.is_empty() && self.queries.is_empty()
+    }
+
+    pub fn add_vector_struct(&mut self, vector: &'a mut VectorStruct) {
+        match vector {
+            VectorStruct::Single(_) => {}
+            VectorStruct::Multi(vectors) => self.add_vectors(
+                vectors
+                    .iter_mut()
+                    .map(|(name, vector)| (name.as_str(), vector)),
+            ),
+        }
+    }
+
+    pub fn add_batch_vector_struct(&mut self, vectors: &'a mut BatchVectorStruct) {
+        match vectors {
+            BatchVectorStruct::Single(_) => {}
+            BatchVectorStruct::Multi(vectors) => {
+                self.add_vectors(vectors.iter_mut().flat_map(|(name, vectors)| {
+                    vectors
+                        .iter_mut()
+                        .map(move |vector| (name.as_str(), vector))
+                }))
+            }
+        }
+    }
+
+    pub fn add_point_insert_operations(&mut self, operation: &'a mut PointInsertOperations) {
+        match operation {
+            PointInsertOperations::PointsBatch(batch) => {
+                self.add_batch_vector_struct(&mut batch.batch.vectors)
+            }
+            PointInsertOperations::PointsList(list) => {
+                for point in &mut list.points {
+                    self.add_vector_struct(&mut point.vector);
+                }
+            }
+        }
+    }
+
+    pub fn add_update_vectors(&mut self, operation: &'a mut UpdateVectors) {
+        for point in &mut operation.points {
+            self.add_vector_struct(&mut point.vector);
+        }
+    }
+
+    pub fn add_query(&mut self, query: &'a mut NamedVectorStruct) {
+        if matches!(
+            query,
+            NamedVectorStruct::NamedInference(_) | NamedVectorStruct::Inference(_)
+        ) {
+            self.queries.push(query);
+        }
+    }
+
+    fn add_vectors(&mut self, vectors: impl Iterator<Item = (&'a str, &'a mut Vector)>) {
+        self.vectors
+            .extend(vectors.filter(|(_, vector)| matches!(vector, Vector::Inference(_))));
+    }
+
+    /// Embed all collected inputs with the inference service of the collection
+    /// and replace them with the resulting vectors.
+    ///
+    /// Does nothing if there are no raw inputs in the request.
+    pub async fn infer(
+        self,
+        toc: &TableOfContent,
+        collection_name: &str,
+        access: &Access,
+        http_client: &HttpClient,
+    ) -> Result<(), StorageError> {
+        if self.is_empty() {
+            return Ok(());
+        }
+
+        let collection_pass =
+            access.check_collection_access(collection_name, AccessRequirements::new())?;
+        let collection = toc.get_collection(&collection_pass).await?;
+
+        let sparse_text = collection.sparse_text_params().await;
+        let Self {
+            mut vectors,
+            mut queries,
+        } = self.into_sparse(&sparse_text)?;
+        if vectors.is_empty() && queries.is_empty() {
+            return Ok(());
+        }
+
+        let config = collection.inference_config().await.ok_or_else(|| {
+            StorageError::bad_input(format!(
+                "Collection {collection_name} has no inference configured, \
+                 vectors must be provided instead of documents and images"
+            ))
+        })?;
+
+        // Take inputs out of the request, placeholders are overwritten with the embeddings
+        let mut inputs = Vec::with_capacity(vectors.len() + queries.len());
+        for (_, vector) in &mut vectors {
+            if let Vector::Inference(input) = mem::replace(&mut **vector, Vector::Dense(Vec::new()))
+            {
+                inputs.push(input);
+            }
+        }
+        let mut query_names = Vec::with_capacity(queries.len());
+        for query in &mut queries {
+            match mem::replace(&mut **query, NamedVectorStruct::Default(Vec::new())) {
+                NamedVectorStruct::NamedInference(named) => {
+                    query_names.push(Some(named.name));
+                    inputs.push(named.vector);
+                }
+                NamedVectorStruct::Inference(input) => {
+                    query_names.push(None);
+                    inputs.push(input);
+                }
+                other => **query = other,
+            }
+        }
+
+        let mut embeddings = embed(&config, http_client, &inputs).await?.into_iter();
+
+        for ((_, vector), embedding) in vectors.into_iter().zip(embeddings.by_ref()) {
+            *vector = Vector::Dense(embedding);
+        }
+        for ((query, name), embedding) in queries.into_iter().zip(query_names).zip(embeddings) {
+            *query = match name {
+                Some(name) => NamedVectorStruct::Dense(NamedVector {
+                    name,
+                    vector: embedding,
+                }),
+                None => NamedVectorStruct::Default(embedding),
+            };
+        }
+
+        Ok(())
+    }
+
+    /// Convert documents of sparse vectors with text conversion into sparse vectors.
+    ///
+    /// Returns the batch of inputs left for the embedding service.
+    fn into_sparse(
+        self,
+        sparse_text: &HashMap<String, SparseTextParams>,
+    ) -> Result<Self, StorageError> {
+        if sparse_text.is_empty() {
+            return Ok(self);
+        }
+
+        let mut remaining = Self::default();
+        for (name, vector) in self.vectors {
+            let Some(params) = sparse_text.get(name) else {
+                remaining.vectors.push((name, vector));
+                continue;
+            };
+            let Vector::Inference(input) = &*vector else {
+                continue;
+            };
+            *vector = Vector::Sparse(params.document_to_sparse(document_text(name, input)?));
+        }
+        for query in self.queries {
+            let params = match &*query {
+                NamedVectorStruct::NamedInference(named) => sparse_text.get(&named.name),
+                _ => None,
+            };
+            let Some(params) = params else {
+                remaining.queries.push(query);
+                continue;
+            };
+            let NamedVectorStruct::NamedInference(named) = &*query else {
+                continue;
+            };
+            let vector = params.query_to_sparse(document_text(&named.name, &named.vector)?);
+            *query = NamedVectorStruct::Sparse(NamedSparseVector {
+                name: named.name.clone(),
+                vector,
+            });
+        }
+        Ok(remaining)
+    }
+}
+
+/// Text of the input for the sparse vector, which is produced from text
+fn document_text<'b>(name: &str, input: &'b InferenceInput) -> Result<&'b str, StorageError> {
+    match input {
+        InferenceInput::Document(document) => Ok(&document.text),
+        InferenceInput::Image(_) => Err(StorageError::bad_input(format!(
+            "Sparse vector {name} can only be produced from documents"
+        ))),
+    }
+}
+
+/// Request embeddings for all inputs, one request per model.
+///
+/// Returned embeddings are in the same order as inputs.
+async fn embed(
+    config: &InferenceConfig,
+    http_client: &HttpClient,
+    inputs: &[InferenceInput],
+) -> Result<Vec<DenseVector>, StorageError> {
+    let client = http_client.client(None)?;
+
+    let mut inputs_by_model: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (idx, input) in inputs.iter().enumerate() {
+        let model = input.model().unwrap_or(&config.model);
+        inputs_by_model.entry(model).or_default().push(idx);
+    }
+
+    let mut embeddings = vec![DenseVector::new(); inputs.len()];
+    for (model, indices) in inputs_by_model {
+        let request = InferenceRequest {
+            model,
+            inputs: indices.iter().map(|&idx| &inputs[idx]).collect(),
+        };
+        let body = serde_json::to_vec(&request).map_err(|err| {
+            StorageError::service_error(format!("Failed to serialize inference request: {err}"))
+        })?;
+
+        let response = client
+            .post(&config.endpoint)
+            .header(CONTENT_TYPE, "application/json")
+            .timeout(Duration::from_secs(config.timeout_sec))
+            .body(body)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(StorageError::service_error(format!(
+                "Inference request to {} failed: status - {}",
+                config.endpoint,
+                response.status(),
+            )));
+        }
+
+        let response: InferenceResponse = serde_json::from_slice(&response.bytes().await?)
+            .map_err(|err| {
+                StorageError::service_error(format!(
+                    "Failed to parse response of inference service {}: {err}",
+                    config.endpoint,
+                ))
+            })?;
+
+        if response.embeddings.len() != indices.len() {
+            return Err(StorageError::service_error(format!(
+                "Inference service {} returned {} embeddings for {} inputs",
+                config.endpoint,
+                response.embeddings.len(),
+                indices.len(),
+            )));
+        }
+
+        for (idx, embedding) in indices.into_iter().zip(response.embeddings) {
+            embeddings[idx] = embedding;
+        }
+    }
+
+    Ok(embeddings)
+}
+
+#[cfg(test)]
+mod tests {
+    use api::rest::conversions::InferenceInputError;
+    use api::rest::Document;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_collect_inputs() {
+        let mut operation: PointInsertOperations = serde_json::from_value(json!({
+            "points": [
+                {"id": 1, "vector": {"text": {"text": "hello"}, "image": [1.0, 2.0]}},
+                {"id": 2, "vector": [1.0, 2.0]},
+                {"id": 3, "vector": {"image": {"image": "https://example.com/cat.png"}}},
+            ]
+        }))
+        .unwrap();
+        let mut named_query: NamedVectorStruct =
+            serde_json::from_value(json!({"name": "text", "vector": {"text": "hi"}})).unwrap();
+        let mut default_query: NamedVectorStruct =
+            serde_json::from_value(json!({"text": "hi", "model": "small"})).unwrap();
+        let mut dense_query: NamedVectorStruct =
+            serde_json::from_value(json!({"name": "text", "vector": [1.0, 2.0]})).unwrap();
+
+        assert!(matches!(named_query, NamedVectorStruct::NamedInference(_)));
+        assert_eq!(
+            default_query,
+            NamedVectorStruct::Inference(InferenceInput::Document(Document {
+                text: "hi".to_string(),
+                model: Some("small".to_string()),
+            })),
+        );
+
+        let mut inference = InferenceBatch::default();
+        inference.add_point_insert_operations(&mut operation);
+        inference.add_query(&mut named_query);
+        inference.add_query(&mut default_query);
+        inference.add_query(&mut dense_query);
+
+        assert_eq!(inference.vectors.len(), 2);
+        assert_eq!(inference.queries.len(), 2);
+    }
+
+    #[test]
+    fn test_unembedded_inputs_are_rejected() {
+        let vector: VectorStruct =
+            serde_json::from_value(json!({"text": {"text": "hello"}})).unwrap();
+        let query: NamedVectorStruct = serde_json::from_value(json!({"text": "hi"})).unwrap();
+
+        assert_eq!(
+            segment::data_types::vectors::VectorStruct::try_from(vector),
+            Err(InferenceInputError),
+        );
+        assert_eq!(
+            segment::data_types::vectors::NamedVectorStruct::try_from(query),
+            Err(InferenceInputError),
+        );
+    }
+}
@@ -0,0 +1,145 @@
+//! Filtering of clients by their IP address, with CIDR allow and deny lists.
+
+use std::fmt;
+use std::net::IpAddr;
+use std::str::FromStr;
+
+use serde::Deserialize;
+
+use crate::settings::IpFilterConfig;
+
+/// Network in CIDR notation, e.g. `10.0.0.0/8` or `fd00::/8`.
+///
+/// A single address without a prefix length matches only this address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct IpNetwork {
+    addr: IpAddr,
+    prefix_len: u8,
+}
+
+impl IpNetwork {
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        match (self.addr, normalize(ip)) {
+            (IpAddr::V4(network), IpAddr::V4(ip)) => prefix_matches(
+                u32::from(network).into(),
+                u32::from(ip).into(),
+                self.prefix_len,
+                32,
+            ),
+            (IpAddr::V6(network), IpAddr::V6(ip)) => {
+                prefix_matches(u128::from(network), u128::from(ip), self.prefix_len, 128)
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Clients connected over IPv6 sockets to IPv4 listeners have IPv4-mapped addresses
+fn normalize(ip: IpAddr) -> IpAddr {
+    match ip {
+        IpAddr::V6(v6) => v6.to_ipv4_mapped().map_or(ip, IpAddr::V4),
+        IpAddr::V4(_) => ip,
+    }
+}
+
+fn prefix_matches(network: u128, ip: u128, prefix_len: u8, bits: u8) -> bool {
+    if prefix_len == 0 {
+        return true;
+    }
+    let shift = u32::from(bits - prefix_len);
+    network >> shift == ip >> shift
+}
+
+impl FromStr for IpNetwork {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (addr, prefix_len) = match s.split_once('/') {
+            Some((addr, prefix_len)) => (addr, Some(prefix_len)),
+            None => (s, None),
+        };
+        let addr = addr
+            .parse::<IpAddr>()
+            .map(normalize)
+            .map_err(|err| format!("Invalid network {s}: {err}"))?;
+        let max_prefix_len = if addr.is_ipv4() { 32 } else { 128 };
+        let prefix_len = match prefix_len {
+            Some(prefix_len) => prefix_len
+                .parse::<u8>()
+                .ok()
+                .filter(|prefix_len| *prefix_len <= max_prefix_len)
+                .ok_or_else(|| format!("Invalid prefix length of network {s}"))?,
+            None => max_prefix_len,
+        };
+        Ok(Self { addr, prefix_len })
+    }
+}
+
+impl TryFrom<String> for IpNetwork {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl fmt::Display for IpNetwork {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.addr, self.prefix_len)
+    }
+}
+
+impl IpFilterConfig {
+    /// Denied networks take precedence, empty allow list allows all other clients
+    pub fn is_allowed(&self, ip: IpAddr) -> bool {
+        if self.deny.iter().any(|network| network.contains(ip)) {
+            return false;
+        }
+        self.allow.is_empty() || self.allow.iter().any(|network| network.contains(ip))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ip_filter() {
+        let network: IpNetwork = "10.1.0.0/16".parse().unwrap();
+        assert!(network.contains("10.1.2.3".parse().unwrap()));
+        assert!(network.contains("::ffff:10.1.2.3".parse().unwrap()));
+        assert!(!network.contains("10.2.0.1".parse().unwrap()));
+        assert!(!network.contains("fd00::1".parse().unwrap()));
+
+        let network: IpNetwork = "fd00::/8".parse().unwrap();
+        assert!(network.contains("fd12::1".parse().unwrap()));
+        assert!(!network.contains("fe80::1".parse().unwrap()));
+
+        let network: IpNetwork = "192.168.0.1".parse().unwrap();
+        assert_eq!(network.to_string(), "192.168.0.1/32");
+        assert!(!network.contains("192.168.0.2".parse().unwrap()));
+
+        assert!("0.0.0.0/0"
+            .parse::<IpNetwork>()
+            .unwrap()
+            .contains("1.2.3.4".parse().unwrap()));
+        assert!("10.0.0.0/33".parse::<IpNetwork>().is_err());
+        assert!("localhost".parse::<IpNetwork>().is_err());
+
+        let filter = IpFilterConfig {
+            allow: vec!["10.0.0.0/8".parse().unwrap()],
+            deny: vec!["10.0.0.13".parse().unwrap()],
+        };
+        assert!(filter.is_allowed("10.0.0.12".parse().unwrap()));
+        assert!(!filter.is_allowed("10.0.0.13".parse().unwrap()));
+        assert!(!filter.is_allowed("127.0.0.1".parse().unwrap()));
+
+        let filter = IpFilterConfig {
+            allow: vec![],
+            deny: vec!["127.0.0.0/8".parse().unwrap()],
+        };
+        assert!(filter.is_allowed("10.0.0.12".parse().unwrap()));
+        assert!(!filter.is_allowed("127.0.0.1".parse().unwrap()));
+    }
+}
@@ -0,0 +1,150 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use collection::shards::local_shard::memory_spill::{MemorySpill, SpillCandidate};
+use storage::content_manager::toc::TableOfContent;
+use storage::rbac::Access;
+
+/// RAM usage changes with optimizations, so there is no need to check it more often
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+const FULL_ACCESS: Access = Access::full("Memory budget");
+
+/// Background task, which moves vectors of the least recently searched segments to disk,
+/// whenever vectors and indexes in RAM exceed the memory budget of the node
+pub struct MemoryBudget {
+    toc: Arc<TableOfContent>,
+    budget_bytes: usize,
+}
+
+impl MemoryBudget {
+    async fn check(&self) {
+        let mut spills: Vec<(String, MemorySpill)> = Vec::new();
+        for collection_pass in self.toc.all_collections(&FULL_ACCESS).await {
+            // Collection was removed in the meantime
+            let Ok(collection) = self.toc.get_collection(&collection_pass).await else {
+                continue;
+            };
+            for (shard_id, spill) in collection.memory_spills().await {
+                spills.push((format!("{}/{shard_id}", collection_pass.name()), spill));
+            }
+        }
+
+        let ram_usage_bytes: usize = spills.iter().map(|(_, spill)| spill.ram_usage_bytes).sum();
+        if ram_usage_bytes <= self.budget_bytes {
+            return;
+        }
+
+        let candidates = spills
+            .iter()
+            .enumerate()
+            .flat_map(|(index, (_, spill))| {
+                spill
+                    .candidates
+                    .iter()
+                    .map(move |candidate| (index, candidate))
+            })
+            .collect();
+        let selected = select_spills(ram_usage_bytes, self.budget_bytes, candidates);
+        if selected.is_empty() {
+            log::warn!(
+                "RAM usage of vectors and indexes {ram_usage_bytes} bytes exceeds memory budget {} bytes, but there are no segments to move to disk",
+                self.budget_bytes,
+            );
+            return;
+        }
+
+        log::info!(
+            "RAM usage of vectors and indexes {ram_usage_bytes} bytes exceeds memory budget {} bytes, moving {} segments to disk",
+            self.budget_bytes,
+            selected.len(),
+        );
+
+        for (index, (shard_key, spill)) in spills.into_iter().enumerate() {
+            let segment_ids: Vec<_> = selected
+                .iter()
+                .filter(|(selected_index, _)| *selected_index == index)
+                .map(|(_, candidate)| candidate.segment_id)
+                .collect();
+            if segment_ids.is_empty() {
+                continue;
+            }
+            match spill.run(&segment_ids).await {
+                Ok(spilled) => log::debug!("Moved {spilled} segments of shard {shard_key} to disk"),
+                Err(err) => {
+                    log::error!("Failed to move segments of shard {shard_key} to disk: {err}");
+                }
+            }
+        }
+    }
+
+    pub async fn run_forever(toc: Arc<TableOfContent>, budget_mb: usize) {
+        let budget = Self {
+            toc,
+            budget_bytes: budget_mb.saturating_mul(1024 * 1024),
+        };
+        loop {
+            tokio::time::sleep(CHECK_INTERVAL).await;
+            budget.check().await;
+        }
+    }
+}
+
+/// Least recently searched segments, which have to be moved to disk to fit into the budget.
+///
+/// Segments, which were never searched, are moved first.
+fn select_spills<T: Copy>(
+    ram_usage_bytes: usize,
+    budget_bytes: usize,
+    mut candidates: Vec<(T, &SpillCandidate)>,
+) -> Vec<(T, SpillCandidate)> {
+    candidates.sort_by_key(|(_, candidate)| candidate.last_searched);
+
+    let mut excess_bytes = ram_usage_bytes.saturating_sub(budget_bytes);
+    let mut selected = Vec::new();
+    for (key, candidate) in candidates {
+        if excess_bytes == 0 {
+            break;
+        }
+        excess_bytes = excess_bytes.saturating_sub(candidate.ram_usage_bytes);
+        selected.push((key, candidate.clone()));
+    }
+    selected
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+
+    fn candidate(
+        segment_id: usize,
+        ram_usage_bytes: usize,
+        searched_at: Option<i64>,
+    ) -> SpillCandidate {
+        SpillCandidate {
+            segment_id,
+            ram_usage_bytes,
+            last_searched: searched_at.map(|secs| Utc.timestamp_opt(secs, 0).unwrap()),
+        }
+    }
+
+    #[test]
+    fn test_select_least_recently_searched() {
+        let recent = candidate(1, 100, Some(2000));
+        let old = candidate(2, 100, Some(1000));
+        let never = candidate(3, 100, None);
+        let candidates = vec![(0, &recent), (1, &old), (1, &never)];
+
+        let selected = select_spills(1000, 850, candidates.clone());
+        assert_eq!(selected, vec![(1, never.clone()), (1, old.clone())]);
+
+        let selected = select_spills(1000, 1000, candidates.clone());
+        assert!(selected.is_empty());
+
+        // Everything is moved to disk, if the budget can't be met
+        let selected = select_spills(1000, 0, candidates);
+        assert_eq!(selected, vec![(1, never), (1, old), (0, recent)]);
+    }
+}
@@ -1,3 +1,9 @@
+use ::common::io_throttle::{self, IoThrottleStats};
+use collection::collection_manager::optimizers::TrackerStatus;
+use collection::common::latency_histograms::LatencyHistograms;
+use collection::operations::types::OptimizersStatus;
+use collection::shards::telemetry::LocalShardTelemetry;
+use collection::telemetry::CollectionTelemetry;
 use prometheus::proto::{Counter, Gauge, LabelPair, Metric, MetricFamily, MetricType};
 use prometheus::TextEncoder;
 use segment::common::operation_time_statistics::OperationDurationStatistics;
@@ -55,6 +61,35 @@ pub struct MetricsData {
 }
 
 impl MetricsData {
+    /// Metrics of the telemetry data.
+    /// If `per_shard` is set, sizes and index status of local shards of collections are added.
+    pub fn new(telemetry_data: &TelemetryData, per_shard: bool) -> Self {
+        let mut metrics = vec![];
+        telemetry_data.add_metrics(&mut metrics);
+
+        let collections = telemetry_data
+            .collections
+            .collections
+            .iter()
+            .flatten()
+            .filter_map(|collection| match collection {
+                CollectionTelemetryEnum::Aggregated(_) => None,
+                CollectionTelemetryEnum::Full(collection) => Some(collection),
+            })
+            .collect();
+        CollectionMetrics {
+            collections,
+            per_shard,
+        }
+        .add_metrics(&mut metrics);
+        LatencyHistograms::global().add_metrics(&mut metrics);
+        if let Some(io_throttle_stats) = io_throttle::stats() {
+            io_throttle_stats.add_metrics(&mut metrics);
+        }
+
+        Self { metrics }
+    }
+
     pub fn format_metrics(&self) -> String {
         TextEncoder::new().encode_to_string(&self.metrics).unwrap()
     }
@@ -62,9 +97,7 @@ impl MetricsData {
 
 impl From<TelemetryData> for MetricsData {
     fn from(telemetry_data: TelemetryData) -> Self {
-        let mut metrics = vec![];
-        telemetry_data.add_metrics(&mut metrics);
-        Self { metrics }
+        Self::new(&telemetry_data, false)
     }
 }
 
@@ -131,6 +164,285 @@ impl MetricsProvider for CollectionsTelemetry {
             MetricType::GAUGE,
             vec![gauge(vector_count as f64, &[])],
         ));
+
+        let (cold_segments, cold_points): (Vec<_>, Vec<_>) = self
+            .collections
+            .iter()
+            .flatten()
+            .filter_map(|p| match p {
+                CollectionTelemetryEnum::Aggregated(_) => None,
+                CollectionTelemetryEnum::Full(c) => {
+                    let (segments, points) = c.count_cold();
+                    Some((
+                        gauge(segments as f64, &[("collection", &c.id)]),
+                        gauge(points as f64, &[("collection", &c.id)]),
+                    ))
+                }
+            })
+            .unzip();
+        if !cold_segments.is_empty() {
+            metrics.push(metric_family(
+                "collection_cold_segments",
+                "number of segments in the cold storage tier",
+                MetricType::GAUGE,
+                cold_segments,
+            ));
+            metrics.push(metric_family(
+                "collection_cold_points",
+                "number of points in the cold storage tier",
+                MetricType::GAUGE,
+                cold_points,
+            ));
+        }
+
+        let replication_lag: Vec<_> = self
+            .collections
+            .iter()
+            .flatten()
+            .filter_map(|p| match p {
+                CollectionTelemetryEnum::Aggregated(_) => None,
+                CollectionTelemetryEnum::Full(c) => Some(c),
+            })
+            .flat_map(|c| {
+                let shards = c.async_replication.iter().flat_map(|status| &status.shards);
+                shards.map(move |shard| {
+                    gauge(
+                        shard.lag as f64,
+                        &[
+                            ("collection", &c.id),
+                            ("shard", &shard.shard_id.to_string()),
+                        ],
+                    )
+                })
+            })
+            .collect();
+        if !replication_lag.is_empty() {
+            metrics.push(metric_family(
+                "collection_async_replication_lag",
+                "number of operations not yet replayed to the remote cluster",
+                MetricType::GAUGE,
+                replication_lag,
+            ));
+        }
+    }
+}
+
+/// Requests, sizes and index status of individual collections
+struct CollectionMetrics<'a> {
+    collections: Vec<&'a CollectionTelemetry>,
+    per_shard: bool,
+}
+
+impl MetricsProvider for CollectionMetrics<'_> {
+    fn add_metrics(&self, metrics: &mut Vec<MetricFamily>) {
+        let mut requests = OperationDurationMetricsBuilder::default();
+        let mut collections = ShardStatsMetricsBuilder::default();
+        let mut shards = ShardStatsMetricsBuilder::default();
+
+        for collection in &self.collections {
+            let operations = [
+                ("search", &collection.requests.searches),
+                ("scroll", &collection.requests.scrolls),
+                ("update", &collection.requests.updates),
+            ];
+            for (operation, stats) in operations {
+                requests.add(
+                    stats,
+                    &[("collection", &collection.id), ("operation", operation)],
+                    true,
+                );
+            }
+
+            let mut collection_stats = ShardStats::default();
+            for shard in &collection.shards {
+                let Some(local) = &shard.local else {
+                    continue;
+                };
+                let shard_stats = ShardStats::from(local);
+                if self.per_shard {
+                    shards.add(
+                        &shard_stats,
+                        &[
+                            ("collection", &collection.id),
+                            ("shard", &shard.id.to_string()),
+                        ],
+                    );
+                }
+                collection_stats.merge(&shard_stats);
+            }
+            collections.add(&collection_stats, &[("collection", &collection.id)]);
+        }
+
+        requests.build("collection", metrics);
+        collections.build("collection", "collection", metrics);
+        shards.build("collection_shard", "local shard", metrics);
+    }
+}
+
+impl MetricsProvider for LatencyHistograms {
+    fn add_metrics(&self, metrics: &mut Vec<MetricFamily>) {
+        let histograms = self
+            .snapshot()
+            .into_iter()
+            .map(|(operation_type, stats)| {
+                histogram(
+                    stats.count,
+                    stats.sum_secs,
+                    &stats.buckets,
+                    &[("operation", operation_type.as_str())],
+                )
+            })
+            .collect();
+        metrics.push(metric_family(
+            "collection_operation_duration_seconds",
+            "duration histogram of successful collection operations by operation type",
+            MetricType::HISTOGRAM,
+            histograms,
+        ));
+    }
+}
+
+impl MetricsProvider for IoThrottleStats {
+    fn add_metrics(&self, metrics: &mut Vec<MetricFamily>) {
+        metrics.push(metric_family(
+            "optimizer_io_throttled",
+            "whether disk IO of optimizations is throttled because of slow searches",
+            MetricType::GAUGE,
+            vec![gauge(if self.throttling { 1.0 } else { 0.0 }, &[])],
+        ));
+        metrics.push(metric_family(
+            "optimizer_io_throttled_bytes_total",
+            "bytes read or written by optimizations while throttled",
+            MetricType::COUNTER,
+            vec![counter(self.throttled_bytes as f64, &[])],
+        ));
+        metrics.push(metric_family(
+            "optimizer_io_throttle_wait_seconds_total",
+            "time optimizations waited for the disk IO rate limit",
+            MetricType::COUNTER,
+            vec![counter(self.wait_secs, &[])],
+        ));
+        metrics.push(metric_family(
+            "search_latency_average_seconds",
+            "moving average of latency of searches, compared to the optimizer IO throttling threshold",
+            MetricType::GAUGE,
+            vec![gauge(self.search_latency_secs, &[])],
+        ));
+    }
+}
+
+/// Sizes and index status of local shards
+#[derive(Default)]
+struct ShardStats {
+    points: usize,
+    vectors: usize,
+    indexed_vectors: usize,
+    segments: usize,
+    running_optimizations: usize,
+    optimizer_errors: usize,
+}
+
+impl From<&LocalShardTelemetry> for ShardStats {
+    fn from(shard: &LocalShardTelemetry) -> Self {
+        let mut stats = Self {
+            segments: shard.segments.len(),
+            running_optimizations: shard
+                .optimizations
+                .log
+                .iter()
+                .filter(|tracker| tracker.status == TrackerStatus::Optimizing)
+                .count(),
+            optimizer_errors: match shard.optimizations.status {
+                OptimizersStatus::Ok => 0,
+                OptimizersStatus::Error(_) => 1,
+            },
+            ..Default::default()
+        };
+        for segment in &shard.segments {
+            stats.points += segment.info.num_points;
+            stats.vectors += segment.info.num_vectors;
+            stats.indexed_vectors += segment.info.num_indexed_vectors;
+        }
+        stats
+    }
+}
+
+impl ShardStats {
+    fn merge(&mut self, other: &Self) {
+        self.points += other.points;
+        self.vectors += other.vectors;
+        self.indexed_vectors += other.indexed_vectors;
+        self.segments += other.segments;
+        self.running_optimizations += other.running_optimizations;
+        self.optimizer_errors += other.optimizer_errors;
+    }
+}
+
+/// A helper struct to build a vector of [`MetricFamily`] out of a collection of [`ShardStats`].
+#[derive(Default)]
+struct ShardStatsMetricsBuilder {
+    points: Vec<Metric>,
+    vectors: Vec<Metric>,
+    indexed_vectors: Vec<Metric>,
+    segments: Vec<Metric>,
+    running_optimizations: Vec<Metric>,
+    optimizer_errors: Vec<Metric>,
+}
+
+impl ShardStatsMetricsBuilder {
+    fn add(&mut self, stats: &ShardStats, labels: &[(&str, &str)]) {
+        self.points.push(gauge(stats.points as f64, labels));
+        self.vectors.push(gauge(stats.vectors as f64, labels));
+        self.indexed_vectors
+            .push(gauge(stats.indexed_vectors as f64, labels));
+        self.segments.push(gauge(stats.segments as f64, labels));
+        self.running_optimizations
+            .push(gauge(stats.running_optimizations as f64, labels));
+        self.optimizer_errors
+            .push(gauge(stats.optimizer_errors as f64, labels));
+    }
+
+    /// Build metrics and add them to the provided vector.
+    fn build(self, prefix: &str, subject: &str, metrics: &mut Vec<MetricFamily>) {
+        if self.points.is_empty() {
+            return;
+        }
+        metrics.push(metric_family(
+            &format!("{prefix}_points"),
+            &format!("number of points in the {subject}"),
+            MetricType::GAUGE,
+            self.points,
+        ));
+        metrics.push(metric_family(
+            &format!("{prefix}_vectors"),
+            &format!("number of vectors in the {subject}"),
+            MetricType::GAUGE,
+            self.vectors,
+        ));
+        metrics.push(metric_family(
+            &format!("{prefix}_indexed_vectors"),
+            &format!("number of vectors in the {subject}, which are indexed"),
+            MetricType::GAUGE,
+            self.indexed_vectors,
+        ));
+        metrics.push(metric_family(
+            &format!("{prefix}_segments"),
+            &format!("number of segments in the {subject}"),
+            MetricType::GAUGE,
+            self.segments,
+        ));
+        metrics.push(metric_family(
+            &format!("{prefix}_running_optimizations"),
+            &format!("number of optimizations running in the {subject}"),
+            MetricType::GAUGE,
+            self.running_optimizations,
+        ));
+        metrics.push(metric_family(
+            &format!("{prefix}_optimizer_errors"),
+            &format!("number of local shards of the {subject} with failed optimizers"),
+            MetricType::GAUGE,
+            self.optimizer_errors,
+        ));
     }
 }
 
@@ -1,17 +1,61 @@
 #[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
+pub mod async_operations;
+#[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
+pub mod async_replication;
+#[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
+pub mod audit;
+#[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
+pub mod certificate_helpers;
+#[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
+pub mod cluster_metadata;
+#[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
 pub mod collections;
 #[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
+pub mod dead_peers;
+#[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
+pub mod decommission;
+#[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
+pub mod effective_config;
+#[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
 pub mod error_reporting;
 #[allow(dead_code)]
 pub mod health;
 #[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
 pub mod helpers;
 pub mod http_client;
+#[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
+pub mod inference;
+#[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
+pub mod ip_filter;
+#[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
+pub mod memory_budget;
 pub mod metrics;
 #[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
+pub mod peer_health;
+#[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
 pub mod points;
+#[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
+pub mod points_expiry;
+#[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
+pub mod profiling;
+#[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
+pub mod quota_usage;
+#[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
+pub mod rate_limiting;
+#[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
+pub mod rebalance;
+#[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
+pub mod rolling_restart;
+#[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
+pub mod secrets;
+#[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
+pub mod slow_log;
+#[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
+pub mod snapshot_scheduler;
 pub mod snapshots;
 #[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
+pub mod split_brain;
+#[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
 pub mod stacktrace;
 #[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
 pub mod telemetry;
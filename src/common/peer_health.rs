@@ -0,0 +1,154 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use api::grpc::qdrant::qdrant_client::QdrantClient;
+use api::grpc::qdrant::HealthCheckRequest;
+use collection::shards::channel_service::ChannelService;
+use collection::shards::shard::PeerId;
+use futures::future::join_all;
+use storage::content_manager::toc::TableOfContent;
+
+use crate::settings::PeerHealthConfig;
+
+/// Background task, which pings all other peers and disables replicas on unresponsive ones
+///
+/// Failed updates and consensus only notice a failed peer, when there is traffic to it or
+/// after consensus timeouts. Direct pings detect it within a few intervals.
+pub struct PeerHealthDetector {
+    toc: Arc<TableOfContent>,
+    channel_service: ChannelService,
+    timeout: Duration,
+    interval: Duration,
+    failures: PingFailures,
+}
+
+impl PeerHealthDetector {
+    pub fn new(
+        toc: Arc<TableOfContent>,
+        channel_service: ChannelService,
+        config: &PeerHealthConfig,
+    ) -> Self {
+        Self {
+            toc,
+            channel_service,
+            timeout: Duration::from_millis(config.timeout_ms),
+            interval: Duration::from_millis(config.interval_ms),
+            failures: PingFailures::new(config.failure_threshold),
+        }
+    }
+
+    pub async fn run_forever(mut self) {
+        loop {
+            self.check_peers().await;
+            tokio::time::sleep(self.interval).await;
+        }
+    }
+
+    async fn check_peers(&mut self) {
+        let this_peer_id = self.toc.this_peer_id;
+        let peers: Vec<_> = self
+            .channel_service
+            .id_to_address
+            .read()
+            .iter()
+            .filter(|(&peer_id, _)| peer_id != this_peer_id)
+            .map(|(&peer_id, uri)| (peer_id, uri.clone()))
+            .collect();
+
+        let timeout = self.timeout;
+        let pool = &self.channel_service.channel_pool;
+        let results = join_all(peers.iter().map(|(_, uri)| {
+            pool.with_channel_timeout(
+                uri,
+                move |channel| async move {
+                    let mut client = QdrantClient::new(channel);
+                    let mut request = tonic::Request::new(HealthCheckRequest {});
+                    request.set_timeout(timeout);
+                    client.health_check(request).await
+                },
+                Some(timeout),
+                0,
+            )
+        }))
+        .await;
+
+        let unreachable: Vec<_> = peers
+            .iter()
+            .zip(results)
+            .filter(|((peer_id, _), result)| self.failures.record(*peer_id, result.is_ok()))
+            .map(|((peer_id, _), _)| *peer_id)
+            .collect();
+
+        // Forget removed peers
+        self.failures
+            .retain(|peer_id| peers.iter().any(|(id, _)| *id == peer_id));
+
+        for peer_id in unreachable {
+            self.toc.report_unreachable_peer(peer_id).await;
+        }
+    }
+}
+
+/// Consecutive failed pings by peer
+struct PingFailures {
+    threshold: usize,
+    failures: HashMap<PeerId, usize>,
+}
+
+impl PingFailures {
+    fn new(threshold: usize) -> Self {
+        Self {
+            threshold,
+            failures: HashMap::new(),
+        }
+    }
+
+    /// Record the result of a ping, returns `true` if the peer is considered unreachable
+    fn record(&mut self, peer_id: PeerId, healthy: bool) -> bool {
+        if healthy {
+            if self.failures.remove(&peer_id) >= Some(self.threshold) {
+                log::info!("Peer {peer_id} responds to health checks again");
+            }
+            return false;
+        }
+
+        let failures = self.failures.entry(peer_id).or_default();
+        *failures += 1;
+        if *failures == self.threshold {
+            log::warn!(
+                "Peer {peer_id} failed {failures} health checks in a row, disabling its replicas",
+            );
+        }
+        *failures >= self.threshold
+    }
+
+    fn retain(&mut self, is_known: impl Fn(PeerId) -> bool) {
+        self.failures.retain(|&peer_id, _| is_known(peer_id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ping_failures() {
+        let mut failures = PingFailures::new(3);
+
+        assert!(!failures.record(1, false));
+        assert!(!failures.record(1, false));
+        // Successful ping resets the counter
+        assert!(!failures.record(1, true));
+        assert!(!failures.record(1, false));
+        assert!(!failures.record(1, false));
+        assert!(failures.record(1, false));
+        // Peer stays unreachable until it responds again
+        assert!(failures.record(1, false));
+        assert!(!failures.record(2, false));
+        assert!(!failures.record(1, true));
+
+        failures.retain(|peer_id| peer_id != 2);
+        assert!(failures.failures.is_empty());
+    }
+}
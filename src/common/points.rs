@@ -1,11 +1,14 @@
+use std::collections::HashSet;
 use std::sync::Arc;
 use std::time::Duration;
 
 use api::rest::ShardKeySelector;
 use collection::common::batching::batch_requests;
+use collection::operations::blob_ops::{BlobOperations, DeleteBlobOp, SetBlobOp};
 use collection::operations::consistency_params::ReadConsistency;
 use collection::operations::payload_ops::{
-    DeletePayload, DeletePayloadOp, PayloadOps, SetPayload, SetPayloadOp,
+    DeletePayload, DeletePayloadOp, PatchPayload, PatchPayloadOp, PayloadOps, SetPayload,
+    SetPayloadOp,
 };
 use collection::operations::point_ops::{
     FilterSelector, PointIdsList, PointInsertOperations, PointOperations, PointsSelector,
@@ -13,10 +16,10 @@ use collection::operations::point_ops::{
 };
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{
-    CoreSearchRequest, CoreSearchRequestBatch, CountRequestInternal, CountResult,
-    DiscoverRequestBatch, DiscoverRequestInternal, GroupsResult, PointRequestInternal,
-    RecommendGroupsRequestInternal, Record, ScrollRequestInternal, ScrollResult,
-    SearchGroupsRequestInternal, UpdateResult,
+    CoreSearchRequest, CoreSearchRequestBatch, CountRequest, CountRequestInternal, CountResult,
+    DiscoverRequestBatch, DiscoverRequestInternal, DryRunResult, GroupsResult,
+    PointRequestInternal, RecommendGroupsRequestInternal, RecommendRequest, Record,
+    ScrollRequestInternal, ScrollResult, SearchGroupsRequestInternal, SearchRequest, UpdateResult,
 };
 use collection::operations::vector_ops::{
     DeleteVectors, UpdateVectors, UpdateVectorsOp, VectorOperations,
@@ -27,7 +30,10 @@ use collection::operations::{
 use collection::shards::shard::ShardId;
 use schemars::JsonSchema;
 use segment::json_path::JsonPath;
-use segment::types::{PayloadFieldSchema, PayloadKeyType, ScoredPoint};
+use segment::types::{
+    Condition, Filter, HasIdCondition, PayloadFieldSchema, PayloadKeyType, PointIdType,
+    ScoredPoint, SeqNumberType, WithPayloadInterface, WithVector,
+};
 use serde::{Deserialize, Serialize};
 use storage::content_manager::collection_meta_ops::{
     CollectionMetaOperations, CreatePayloadIndex, DropPayloadIndex,
@@ -38,6 +44,8 @@ use storage::dispatcher::Dispatcher;
 use storage::rbac::Access;
 use validator::Validate;
 
+use crate::common::inference::InferenceBatch;
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate)]
 pub struct CreateFieldIndex {
     pub field_name: PayloadKeyType,
@@ -127,6 +135,85 @@ impl Validate for UpdateOperation {
     }
 }
 
+impl UpdateOperation {
+    /// Collect documents and images, which must be embedded before the operation is applied
+    pub fn collect_inference_inputs<'a>(&'a mut self, inference: &mut InferenceBatch<'a>) {
+        match self {
+            UpdateOperation::Upsert(op) => inference.add_point_insert_operations(&mut op.upsert),
+            UpdateOperation::UpdateVectors(op) => {
+                inference.add_update_vectors(&mut op.update_vectors)
+            }
+            UpdateOperation::Delete(_)
+            | UpdateOperation::SetPayload(_)
+            | UpdateOperation::OverwritePayload(_)
+            | UpdateOperation::DeletePayload(_)
+            | UpdateOperation::ClearPayload(_)
+            | UpdateOperation::DeleteVectors(_) => {}
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, JsonSchema, Validate)]
+pub struct SearchOperation {
+    #[validate]
+    search: SearchRequest,
+}
+
+#[derive(Deserialize, Serialize, JsonSchema, Validate)]
+pub struct RecommendOperation {
+    #[validate]
+    recommend: RecommendRequest,
+}
+
+#[derive(Deserialize, Serialize, JsonSchema, Validate)]
+pub struct CountOperation {
+    #[validate]
+    count: CountRequest,
+}
+
+#[derive(Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+#[serde(untagged)]
+pub enum ReadOperation {
+    Search(SearchOperation),
+    Recommend(RecommendOperation),
+    Count(CountOperation),
+}
+
+#[derive(Deserialize, Serialize, JsonSchema, Validate)]
+pub struct ReadOperations {
+    #[validate]
+    pub operations: Vec<ReadOperation>,
+}
+
+impl Validate for ReadOperation {
+    fn validate(&self) -> Result<(), validator::ValidationErrors> {
+        match self {
+            ReadOperation::Search(op) => op.validate(),
+            ReadOperation::Recommend(op) => op.validate(),
+            ReadOperation::Count(op) => op.validate(),
+        }
+    }
+}
+
+impl ReadOperation {
+    /// Collect documents and images, which must be embedded before the operation is executed
+    pub fn collect_inference_inputs<'a>(&'a mut self, inference: &mut InferenceBatch<'a>) {
+        match self {
+            ReadOperation::Search(op) => inference.add_query(&mut op.search.search_request.vector),
+            ReadOperation::Recommend(_) | ReadOperation::Count(_) => {}
+        }
+    }
+}
+
+#[derive(Serialize, JsonSchema)]
+#[serde(untagged)]
+pub enum ReadOperationResult {
+    /// Result of a search or recommend operation
+    Points(Vec<api::rest::ScoredPoint>),
+    Count(CountResult),
+}
+
 /// Converts a pair of parameters into a shard selector
 /// suitable for update operations.
 ///
@@ -163,15 +250,15 @@ pub async fn do_upsert_points(
     toc: Arc<TableOfContent>,
     collection_name: String,
     operation: PointInsertOperations,
+    if_version: Option<SeqNumberType>,
     clock_tag: Option<ClockTag>,
     shard_selection: Option<ShardId>,
     wait: bool,
     ordering: WriteOrdering,
     access: Access,
 ) -> Result<UpdateResult, StorageError> {
-    let (shard_key, operation) = operation.decompose();
-    let collection_operation =
-        CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(operation));
+    let (shard_key, operation) = operation.decompose(if_version);
+    let collection_operation = CollectionUpdateOperations::PointOperation(operation);
 
     let shard_selector = get_shard_selector_for_update(shard_selection, shard_key);
 
@@ -191,6 +278,7 @@ pub async fn do_delete_points(
     toc: Arc<TableOfContent>,
     collection_name: String,
     points: PointsSelector,
+    if_version: Option<SeqNumberType>,
     clock_tag: Option<ClockTag>,
     shard_selection: Option<ShardId>,
     wait: bool,
@@ -198,8 +286,17 @@ pub async fn do_delete_points(
     access: Access,
 ) -> Result<UpdateResult, StorageError> {
     let (point_operation, shard_key) = match points {
-        PointsSelector::PointIdsSelector(PointIdsList { points, shard_key }) => {
-            (PointOperations::DeletePoints { ids: points }, shard_key)
+        PointsSelector::PointIdsSelector(PointIdsList { points, shard_key }) => (
+            PointOperations::DeletePoints {
+                ids: points,
+                if_version,
+            },
+            shard_key,
+        ),
+        PointsSelector::FilterSelector(_) if if_version.is_some() => {
+            return Err(StorageError::bad_input(
+                "Version guard can be used only with deletion of points by ids",
+            ));
         }
         PointsSelector::FilterSelector(FilterSelector { filter, shard_key }) => {
             (PointOperations::DeletePointsByFilter(filter), shard_key)
@@ -219,6 +316,71 @@ pub async fn do_delete_points(
     .await
 }
 
+/// Find points, which would be affected by an update of the given points or filter,
+/// without modifying anything.
+///
+/// If both are specified, `points` take priority, same as in the update itself.
+pub async fn do_dry_run_update(
+    toc: &TableOfContent,
+    collection_name: &str,
+    points: Option<Vec<PointIdType>>,
+    filter: Option<Filter>,
+    shard_key: Option<ShardKeySelector>,
+    samples: Option<usize>,
+    access: Access,
+) -> Result<DryRunResult, StorageError> {
+    let filter = match (points, filter) {
+        (Some(points), _) => Filter::new_must(Condition::HasId(HasIdCondition::from(
+            points.into_iter().collect::<HashSet<_>>(),
+        ))),
+        (None, Some(filter)) => filter,
+        (None, None) => return Err(StorageError::bad_input("No points or filter specified")),
+    };
+    let shard_selector = match shard_key {
+        None => ShardSelectorInternal::All,
+        Some(shard_keys) => ShardSelectorInternal::from(shard_keys),
+    };
+
+    let CountResult { count } = toc
+        .count(
+            collection_name,
+            CountRequestInternal {
+                filter: Some(filter.clone()),
+                exact: true,
+            },
+            None,
+            shard_selector.clone(),
+            access.clone(),
+        )
+        .await?;
+
+    let sample_ids = match samples {
+        Some(limit) if limit > 0 && count > 0 => {
+            let ScrollResult { points, .. } = toc
+                .scroll(
+                    collection_name,
+                    ScrollRequestInternal {
+                        offset: None,
+                        limit: Some(limit),
+                        filter: Some(filter),
+                        with_payload: Some(WithPayloadInterface::Bool(false)),
+                        with_vector: WithVector::Bool(false),
+                        order_by: None,
+                    },
+                    None,
+                    shard_selector,
+                    access,
+                )
+                .await?;
+            Some(points.into_iter().map(|point| point.id).collect())
+        }
+        Some(_) => Some(Vec::new()),
+        None => None,
+    };
+
+    Ok(DryRunResult { count, sample_ids })
+}
+
 #[allow(clippy::too_many_arguments)]
 pub async fn do_update_vectors(
     toc: Arc<TableOfContent>,
@@ -393,6 +555,100 @@ pub async fn do_overwrite_payload(
     .await
 }
 
+#[allow(clippy::too_many_arguments)]
+pub async fn do_patch_payload(
+    toc: Arc<TableOfContent>,
+    collection_name: String,
+    operation: PatchPayload,
+    clock_tag: Option<ClockTag>,
+    shard_selection: Option<ShardId>,
+    wait: bool,
+    ordering: WriteOrdering,
+    access: Access,
+) -> Result<UpdateResult, StorageError> {
+    let PatchPayload {
+        patch,
+        points,
+        filter,
+        shard_key,
+    } = operation;
+
+    let collection_operation =
+        CollectionUpdateOperations::PayloadOperation(PayloadOps::PatchPayload(PatchPayloadOp {
+            patch,
+            points,
+            filter,
+        }));
+
+    let shard_selector = get_shard_selector_for_update(shard_selection, shard_key);
+
+    toc.update(
+        &collection_name,
+        OperationWithClockTag::new(collection_operation, clock_tag),
+        wait,
+        ordering,
+        shard_selector,
+        access,
+    )
+    .await
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn do_set_blob(
+    toc: Arc<TableOfContent>,
+    collection_name: String,
+    operation: SetBlobOp,
+    shard_key: Option<ShardKeySelector>,
+    clock_tag: Option<ClockTag>,
+    shard_selection: Option<ShardId>,
+    wait: bool,
+    ordering: WriteOrdering,
+    access: Access,
+) -> Result<UpdateResult, StorageError> {
+    let collection_operation =
+        CollectionUpdateOperations::BlobOperation(BlobOperations::SetBlob(operation));
+
+    let shard_selector = get_shard_selector_for_update(shard_selection, shard_key);
+
+    toc.update(
+        &collection_name,
+        OperationWithClockTag::new(collection_operation, clock_tag),
+        wait,
+        ordering,
+        shard_selector,
+        access,
+    )
+    .await
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn do_delete_blob(
+    toc: Arc<TableOfContent>,
+    collection_name: String,
+    operation: DeleteBlobOp,
+    shard_key: Option<ShardKeySelector>,
+    clock_tag: Option<ClockTag>,
+    shard_selection: Option<ShardId>,
+    wait: bool,
+    ordering: WriteOrdering,
+    access: Access,
+) -> Result<UpdateResult, StorageError> {
+    let collection_operation =
+        CollectionUpdateOperations::BlobOperation(BlobOperations::DeleteBlob(operation));
+
+    let shard_selector = get_shard_selector_for_update(shard_selection, shard_key);
+
+    toc.update(
+        &collection_name,
+        OperationWithClockTag::new(collection_operation, clock_tag),
+        wait,
+        ordering,
+        shard_selector,
+        access,
+    )
+    .await
+}
+
 #[allow(clippy::too_many_arguments)]
 pub async fn do_delete_payload(
     toc: Arc<TableOfContent>,
@@ -485,6 +741,7 @@ pub async fn do_batch_update_points(
                     toc.clone(),
                     collection_name.clone(),
                     operation.upsert,
+                    None,
                     clock_tag,
                     shard_selection,
                     wait,
@@ -498,6 +755,7 @@ pub async fn do_batch_update_points(
                     toc.clone(),
                     collection_name.clone(),
                     operation.delete,
+                    None,
                     clock_tag,
                     shard_selection,
                     wait,
@@ -945,6 +1203,133 @@ pub async fn do_count_points(
     .await
 }
 
+/// Execute a batch of heterogeneous read operations.
+///
+/// Operations of the same kind are executed as a single batch, so searches sharing a filter
+/// share its planning in every segment. Results are returned in the order of operations.
+pub async fn do_read_batch_points(
+    toc: &TableOfContent,
+    collection_name: &str,
+    operations: ReadOperations,
+    read_consistency: Option<ReadConsistency>,
+    access: Access,
+    timeout: Option<Duration>,
+) -> Result<Vec<ReadOperationResult>, StorageError> {
+    enum Kind {
+        Search,
+        Recommend,
+        Count,
+    }
+
+    let shard_selector = |shard_key: Option<ShardKeySelector>| match shard_key {
+        None => ShardSelectorInternal::All,
+        Some(shard_keys) => ShardSelectorInternal::from(shard_keys),
+    };
+
+    let mut kinds = Vec::with_capacity(operations.operations.len());
+    let mut searches = Vec::new();
+    let mut recommends = Vec::new();
+    let mut counts = Vec::new();
+
+    for operation in operations.operations {
+        match operation {
+            ReadOperation::Search(SearchOperation { search }) => {
+                let SearchRequest {
+                    search_request,
+                    shard_key,
+                } = search;
+                let core_request = CoreSearchRequest::try_from(search_request)?;
+                searches.push((core_request, shard_selector(shard_key)));
+                kinds.push(Kind::Search);
+            }
+            ReadOperation::Recommend(RecommendOperation { recommend }) => {
+                let RecommendRequest {
+                    recommend_request,
+                    shard_key,
+                } = recommend;
+                recommends.push((recommend_request, shard_selector(shard_key)));
+                kinds.push(Kind::Recommend);
+            }
+            ReadOperation::Count(CountOperation { count }) => {
+                let CountRequest {
+                    count_request,
+                    shard_key,
+                } = count;
+                counts.push((count_request, shard_selector(shard_key)));
+                kinds.push(Kind::Count);
+            }
+        }
+    }
+
+    let search_future = async {
+        if searches.is_empty() {
+            return Ok(vec![]);
+        }
+        do_search_batch_points(
+            toc,
+            collection_name,
+            searches,
+            read_consistency,
+            access.clone(),
+            timeout,
+        )
+        .await
+    };
+
+    let recommend_future = async {
+        if recommends.is_empty() {
+            return Ok(vec![]);
+        }
+        toc.recommend_batch(
+            collection_name,
+            recommends,
+            read_consistency,
+            access.clone(),
+            timeout,
+        )
+        .await
+    };
+
+    let count_future =
+        futures::future::try_join_all(counts.into_iter().map(|(count_request, shard_selector)| {
+            toc.count(
+                collection_name,
+                count_request,
+                read_consistency,
+                shard_selector,
+                access.clone(),
+            )
+        }));
+
+    let (search_results, recommend_results, count_results) =
+        futures::try_join!(search_future, recommend_future, count_future)?;
+
+    let mut search_results = search_results.into_iter();
+    let mut recommend_results = recommend_results.into_iter();
+    let mut count_results = count_results.into_iter();
+
+    let to_rest = |points: Vec<ScoredPoint>| {
+        ReadOperationResult::Points(
+            points
+                .into_iter()
+                .map(api::rest::ScoredPoint::from)
+                .collect(),
+        )
+    };
+
+    kinds
+        .into_iter()
+        .map(|kind| {
+            let result = match kind {
+                Kind::Search => search_results.next().map(to_rest),
+                Kind::Recommend => recommend_results.next().map(to_rest),
+                Kind::Count => count_results.next().map(ReadOperationResult::Count),
+            };
+            result.ok_or_else(|| StorageError::service_error("Missing read operation result"))
+        })
+        .collect()
+}
+
 pub async fn do_get_points(
     toc: &TableOfContent,
     collection_name: &str,
@@ -0,0 +1,79 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use storage::content_manager::toc::TableOfContent;
+use storage::rbac::Access;
+use tokio::time::Instant;
+
+/// Lowest check interval of collections, expiry intervals are configured in seconds
+const TICK_INTERVAL: Duration = Duration::from_secs(1);
+
+const FULL_ACCESS: Access = Access::full("Points expiry");
+
+/// Background task, which deletes expired points of collections.
+///
+/// Deletions are issued only for shards, in which this peer is the update leader, and go through
+/// the regular update path, so they are replicated to all replicas.
+pub struct PointsExpiry {
+    toc: Arc<TableOfContent>,
+    /// Moment of the next check of each collection
+    next_checks: HashMap<String, Instant>,
+}
+
+impl PointsExpiry {
+    async fn check(&mut self) {
+        // Expired points are hidden from reads, so deletion can wait until writes are allowed
+        if let Err(err) = self.toc.check_write_lock() {
+            log::trace!("Skipping deletion of expired points: {err}");
+            return;
+        }
+
+        let now = Instant::now();
+        let collections = self.toc.all_collections(&FULL_ACCESS).await;
+
+        let mut next_checks = HashMap::with_capacity(self.next_checks.len());
+        for collection_pass in collections {
+            let collection_name = collection_pass.name().to_string();
+            let collection = match self.toc.get_collection(&collection_pass).await {
+                Ok(collection) => collection,
+                // Collection was removed in the meantime
+                Err(_) => continue,
+            };
+
+            let check_interval = collection.expiry_check_interval().await;
+            let next_check = match self.next_checks.remove(&collection_name) {
+                Some(next_check) if next_check <= now => {
+                    match collection.delete_expired_points().await {
+                        Ok(0) => {}
+                        Ok(shards) => log::debug!(
+                            "Scheduled deletion of expired points in {shards} shards of collection {collection_name}",
+                        ),
+                        Err(err) => log::error!(
+                            "Failed to delete expired points of collection {collection_name}: {err}",
+                        ),
+                    }
+                    Instant::now() + check_interval
+                }
+                // Interval might have been shortened in the meantime
+                Some(next_check) => next_check.min(now + check_interval),
+                None => now + check_interval,
+            };
+
+            next_checks.insert(collection_name, next_check);
+        }
+
+        self.next_checks = next_checks;
+    }
+
+    pub async fn run_forever(toc: Arc<TableOfContent>) {
+        let mut expiry = Self {
+            toc,
+            next_checks: HashMap::new(),
+        };
+        loop {
+            tokio::time::sleep(TICK_INTERVAL).await;
+            expiry.check().await;
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use std::time::Duration;
+
+use serde::Deserialize;
+use storage::content_manager::errors::StorageError;
+
+/// Profiles longer than this are rejected, to not keep the sampler running for too long
+pub const MAX_PROFILE_DURATION: Duration = Duration::from_secs(300);
+
+const DEFAULT_PROFILE_SECONDS: u64 = 30;
+
+/// Samples per second, not a multiple of common timer frequencies to avoid lockstep sampling
+const DEFAULT_PROFILE_FREQUENCY: i32 = 99;
+
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProfileFormat {
+    /// Protobuf profile, compatible with `go tool pprof`
+    #[default]
+    Pprof,
+    /// SVG flamegraph
+    Flamegraph,
+}
+
+impl ProfileFormat {
+    pub fn content_type(self) -> &'static str {
+        match self {
+            ProfileFormat::Pprof => "application/octet-stream",
+            ProfileFormat::Flamegraph => "image/svg+xml",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ProfileParams {
+    /// Duration of the profile
+    pub seconds: Option<u64>,
+    /// Samples per second
+    pub frequency: Option<i32>,
+    #[serde(default)]
+    pub format: ProfileFormat,
+}
+
+/// Sample stacks of all threads for the requested duration and render the CPU profile.
+///
+/// Only one profile can be captured at a time.
+pub async fn capture_cpu_profile(params: ProfileParams) -> Result<Vec<u8>, StorageError> {
+    let duration = Duration::from_secs(params.seconds.unwrap_or(DEFAULT_PROFILE_SECONDS));
+    if duration.is_zero() || duration > MAX_PROFILE_DURATION {
+        return Err(StorageError::bad_input(format!(
+            "Profile duration must be between 1 and {} seconds",
+            MAX_PROFILE_DURATION.as_secs(),
+        )));
+    }
+
+    let frequency = params.frequency.unwrap_or(DEFAULT_PROFILE_FREQUENCY);
+    if !(1..=1000).contains(&frequency) {
+        return Err(StorageError::bad_input(
+            "Profile frequency must be between 1 and 1000",
+        ));
+    }
+
+    // Sampler is started and stopped on the same thread, which is blocked while sampling
+    tokio::task::spawn_blocking(move || profile(duration, frequency, params.format))
+        .await
+        .map_err(|err| StorageError::service_error(format!("Profiling task failed: {err}")))?
+}
+
+#[cfg(target_os = "linux")]
+fn profile(
+    duration: Duration,
+    frequency: i32,
+    format: ProfileFormat,
+) -> Result<Vec<u8>, StorageError> {
+    use pprof::protos::Message as _;
+
+    let guard = pprof::ProfilerGuardBuilder::default()
+        .frequency(frequency)
+        .blocklist(&["libc", "libgcc", "pthread", "vdso"])
+        .build()
+        .map_err(|err| match err {
+            pprof::Error::Running => StorageError::bad_request("Another profile is being captured"),
+            err => StorageError::service_error(format!("Failed to start profiler: {err}")),
+        })?;
+
+    std::thread::sleep(duration);
+
+    let report = guard
+        .report()
+        .build()
+        .map_err(|err| StorageError::service_error(format!("Failed to build profile: {err}")))?;
+    drop(guard);
+
+    let mut body = Vec::new();
+    match format {
+        ProfileFormat::Pprof => {
+            let profile = report.pprof().map_err(|err| {
+                StorageError::service_error(format!("Failed to render profile: {err}"))
+            })?;
+            profile.encode(&mut body).map_err(|err| {
+                StorageError::service_error(format!("Failed to encode profile: {err}"))
+            })?;
+        }
+        ProfileFormat::Flamegraph => {
+            report.flamegraph(&mut body).map_err(|err| {
+                StorageError::service_error(format!("Failed to render flamegraph: {err}"))
+            })?;
+        }
+    }
+    Ok(body)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn profile(
+    _duration: Duration,
+    _frequency: i32,
+    _format: ProfileFormat,
+) -> Result<Vec<u8>, StorageError> {
+    Err(StorageError::bad_request(
+        "CPU profiling is only supported on Linux",
+    ))
+}
@@ -0,0 +1,35 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use storage::content_manager::toc::TableOfContent;
+use storage::rbac::Access;
+
+/// Interval between measurements of usage of tenants
+const MEASURE_INTERVAL: Duration = Duration::from_secs(30);
+
+const FULL_ACCESS: Access = Access::full("Quota usage");
+
+/// Background task, which measures usage of tenants of collections with quotas
+///
+/// Upserts are checked against usage, which is accounted with every accepted write. Measurements
+/// correct it for overwritten and deleted points, without blocking writes.
+pub async fn run_forever(toc: Arc<TableOfContent>) {
+    loop {
+        for collection_pass in toc.all_collections(&FULL_ACCESS).await {
+            let collection = match toc.get_collection(&collection_pass).await {
+                Ok(collection) => collection,
+                // Collection was removed in the meantime
+                Err(_) => continue,
+            };
+
+            if let Err(err) = collection.measure_quota_usage().await {
+                log::warn!(
+                    "Failed to measure quota usage of collection {}: {err}",
+                    collection_pass.name(),
+                );
+            }
+        }
+
+        tokio::time::sleep(MEASURE_INTERVAL).await;
+    }
+}
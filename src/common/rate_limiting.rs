@@ -0,0 +1,135 @@
+//! Rate limiting of requests authenticated with an API key, with token buckets.
+
+use std::fmt;
+use std::num::NonZeroU32;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+
+use crate::settings::RateLimitConfig;
+
+/// Request is rejected, because the rate limit is exceeded
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimited {
+    /// How long to wait until the request would be allowed
+    pub retry_after: Duration,
+}
+
+impl RateLimited {
+    /// Value of the `Retry-After` header, in whole seconds
+    pub fn retry_after_secs(&self) -> u64 {
+        self.retry_after.as_secs_f64().ceil() as u64
+    }
+}
+
+impl fmt::Display for RateLimited {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Rate limit exceeded, retry after {:.3} seconds",
+            self.retry_after.as_secs_f64(),
+        )
+    }
+}
+
+/// Bucket is refilled with `rate` tokens per second and holds one second worth of tokens,
+/// so that short bursts are allowed.
+struct TokenBucket {
+    rate: f64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: NonZeroU32, now: Instant) -> Self {
+        let rate = f64::from(rate.get());
+        Self {
+            rate,
+            tokens: rate,
+            last_refill: now,
+        }
+    }
+
+    /// Take `cost` tokens.
+    ///
+    /// Cost above the capacity is allowed once the bucket is full, the excess is paid back over
+    /// time, so that a single large request is not rejected forever.
+    fn try_acquire(&mut self, cost: f64, now: Instant) -> Result<(), RateLimited> {
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        self.tokens = (self.tokens + elapsed.as_secs_f64() * self.rate).min(self.rate);
+        self.last_refill = now;
+
+        let required = cost.min(self.rate);
+        if self.tokens >= required {
+            self.tokens -= cost;
+            Ok(())
+        } else {
+            Err(RateLimited {
+                retry_after: Duration::from_secs_f64((required - self.tokens) / self.rate),
+            })
+        }
+    }
+}
+
+/// Rate limit of a single API key
+pub struct RateLimiter {
+    requests: Mutex<TokenBucket>,
+    vectors: Option<Mutex<TokenBucket>>,
+}
+
+impl RateLimiter {
+    pub fn new(config: &RateLimitConfig) -> Self {
+        let now = Instant::now();
+        Self {
+            requests: Mutex::new(TokenBucket::new(config.requests_per_second, now)),
+            vectors: config
+                .vectors_per_second
+                .map(|rate| Mutex::new(TokenBucket::new(rate, now))),
+        }
+    }
+
+    /// Account a single request
+    pub fn check_request(&self) -> Result<(), RateLimited> {
+        self.requests.lock().try_acquire(1.0, Instant::now())
+    }
+
+    /// Account `count` upserted vectors, if vectors are limited
+    pub fn check_vectors(&self, count: usize) -> Result<(), RateLimited> {
+        match &self.vectors {
+            Some(vectors) => vectors.lock().try_acquire(count as f64, Instant::now()),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_bucket() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(NonZeroU32::new(10).unwrap(), start);
+
+        // Burst of one second worth of tokens is allowed
+        for _ in 0..10 {
+            bucket.try_acquire(1.0, start).unwrap();
+        }
+        let limited = bucket.try_acquire(1.0, start).unwrap_err();
+        assert_eq!(limited.retry_after, Duration::from_millis(100));
+        assert_eq!(limited.retry_after_secs(), 1);
+
+        // Refilled over time
+        let later = start + Duration::from_millis(500);
+        for _ in 0..5 {
+            bucket.try_acquire(1.0, later).unwrap();
+        }
+        assert!(bucket.try_acquire(1.0, later).is_err());
+
+        // Large cost is allowed with a full bucket, and is paid back
+        let later = later + Duration::from_secs(1);
+        bucket.try_acquire(25.0, later).unwrap();
+        let limited = bucket.try_acquire(1.0, later).unwrap_err();
+        assert_eq!(limited.retry_after, Duration::from_secs_f64(1.6));
+    }
+}
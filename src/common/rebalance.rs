@@ -0,0 +1,518 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::types::{Datatype, VectorsConfig};
+use collection::shards::replica_set::ReplicaState;
+use collection::shards::shard::{PeerId, ShardId};
+use collection::shards::transfer::ShardTransfer;
+use itertools::Itertools;
+use schemars::JsonSchema;
+use segment::types::ShardKey;
+use serde::{Deserialize, Serialize};
+use storage::content_manager::collection_meta_ops::{
+    CollectionMetaOperations, ShardTransferOperations,
+};
+use storage::content_manager::errors::StorageError;
+use storage::dispatcher::Dispatcher;
+use storage::rbac::{Access, AccessRequirements, CollectionPass};
+use validator::Validate;
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+pub struct RebalanceRequest {
+    /// What to balance the cluster by. Default: `shard_count`
+    #[serde(default)]
+    pub strategy: RebalanceStrategy,
+    /// Rebalance only replicas of these collections. All collections are rebalanced if not specified.
+    pub collections: Option<Vec<String>>,
+    /// If true - start shard transfers of the plan, otherwise only return the plan.
+    /// Default: false
+    #[serde(default)]
+    pub apply: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RebalanceStrategy {
+    /// Even number of shard replicas on each peer
+    #[default]
+    ShardCount,
+    /// Even amount of data on each peer. Size of a shard is estimated from the number of points
+    /// and the size of its vectors
+    DiskSize,
+    /// Replicas of shards with the same shard key on the same peers
+    TenantLocality,
+}
+
+#[derive(Debug, Serialize, JsonSchema, Clone)]
+pub struct RebalancePlan {
+    pub strategy: RebalanceStrategy,
+    /// Shard replicas to move, at most one replica of each shard
+    pub moves: Vec<ShardMove>,
+    /// Whether shard transfers of the plan were started
+    pub applied: bool,
+}
+
+#[derive(Debug, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+pub struct ShardMove {
+    pub collection_name: String,
+    pub shard_id: ShardId,
+    pub from_peer_id: PeerId,
+    pub to_peer_id: PeerId,
+}
+
+/// Placement of a single shard, input of the planner
+#[derive(Debug, Clone)]
+struct ShardReplicas {
+    collection_name: String,
+    shard_id: ShardId,
+    shard_key: Option<ShardKey>,
+    peers: Vec<PeerId>,
+    weight: u64,
+    /// All replicas are active and the shard has no ongoing transfers
+    movable: bool,
+    /// Peers allowed to receive replicas of the shard, any peer if not set
+    allowed_peers: Option<HashSet<PeerId>>,
+}
+
+impl ShardReplicas {
+    fn allows(&self, peer_id: PeerId) -> bool {
+        self.allowed_peers
+            .as_ref()
+            .map_or(true, |allowed_peers| allowed_peers.contains(&peer_id))
+    }
+}
+
+pub async fn do_rebalance_cluster(
+    dispatcher: &Dispatcher,
+    request: RebalanceRequest,
+    access: Access,
+) -> Result<RebalancePlan, StorageError> {
+    access.check_global_access(AccessRequirements::new().manage())?;
+
+    let Some(consensus_state) = dispatcher.consensus_state() else {
+        return Err(StorageError::BadRequest {
+            description: "Distributed mode disabled.".to_string(),
+        });
+    };
+
+    let toc = dispatcher.toc(&access);
+
+    let peers = consensus_state
+        .persistent
+        .read()
+        .peer_address_by_id
+        .read()
+        .keys()
+        .copied()
+        .filter(|&peer_id| !toc.is_peer_decommissioning(peer_id))
+        .sorted()
+        .collect_vec();
+
+    let collection_passes = match &request.collections {
+        Some(collections) => collections
+            .iter()
+            .map(|name| {
+                access
+                    .check_collection_access(name, AccessRequirements::new().manage())
+                    .map(CollectionPass::into_static)
+            })
+            .collect::<Result<Vec<_>, _>>()?,
+        None => toc.all_collections(&access).await,
+    };
+
+    let peer_metadata_by_id = consensus_state.peer_metadata_by_id();
+    let read_only_peers: HashSet<PeerId> = peer_metadata_by_id
+        .iter()
+        .filter(|(_, metadata)| metadata.is_read_only())
+        .map(|(&peer_id, _)| peer_id)
+        .collect();
+
+    let mut shards = Vec::new();
+    for collection_pass in &collection_passes {
+        let collection = toc.get_collection(collection_pass).await?;
+        let state = collection.state().await;
+
+        let shard_keys: HashMap<ShardId, ShardKey> = state
+            .shards_key_mapping
+            .iter()
+            .flat_map(|(key, shard_ids)| shard_ids.iter().map(|&id| (id, key.clone())))
+            .collect();
+        let point_size = estimate_point_size(&state.config.params.vectors);
+        // Read-only peers and peers not matching placement labels can't receive replicas
+        let placement_labels = state.config.params.placement_labels.as_ref();
+        let allowed_peers: Option<HashSet<PeerId>> =
+            (placement_labels.is_some() || !read_only_peers.is_empty()).then(|| {
+                peers
+                    .iter()
+                    .copied()
+                    .filter(|peer_id| !read_only_peers.contains(peer_id))
+                    .filter(|peer_id| {
+                        placement_labels.map_or(true, |labels| {
+                            peer_metadata_by_id
+                                .get(peer_id)
+                                .is_some_and(|metadata| metadata.has_labels(labels))
+                        })
+                    })
+                    .collect()
+            });
+
+        for (&shard_id, shard_info) in state.shards.iter().sorted_by_key(|&(&id, _)| id) {
+            let weight = match request.strategy {
+                RebalanceStrategy::ShardCount | RebalanceStrategy::TenantLocality => 1,
+                RebalanceStrategy::DiskSize => {
+                    let points_count = collection
+                        .info(&ShardSelectorInternal::ShardId(shard_id))
+                        .await?
+                        .points_count
+                        .unwrap_or_default();
+                    points_count as u64 * point_size
+                }
+            };
+
+            let movable = shard_info
+                .replicas
+                .values()
+                .all(|&state| state == ReplicaState::Active)
+                && !state
+                    .transfers
+                    .iter()
+                    .any(|transfer| transfer.shard_id == shard_id);
+
+            shards.push(ShardReplicas {
+                collection_name: collection_pass.name().to_string(),
+                shard_id,
+                shard_key: shard_keys.get(&shard_id).cloned(),
+                peers: shard_info.replicas.keys().copied().sorted().collect(),
+                weight,
+                movable,
+                allowed_peers: pin_allowed_peers(
+                    allowed_peers.as_ref(),
+                    state.pinned_peers(shard_id),
+                ),
+            });
+        }
+    }
+
+    let moves = match request.strategy {
+        RebalanceStrategy::ShardCount | RebalanceStrategy::DiskSize => {
+            let peer_zones = peer_metadata_by_id
+                .iter()
+                .filter_map(|(&peer_id, metadata)| Some((peer_id, metadata.zone()?.to_string())))
+                .collect();
+            plan_balanced(&shards, &peers, &peer_zones)
+        }
+        RebalanceStrategy::TenantLocality => plan_tenant_locality(&shards, &peers),
+    };
+
+    if request.apply {
+        for shard_move in &moves {
+            log::info!(
+                "Rebalancing: moving shard {}:{} from peer {} to peer {}",
+                shard_move.collection_name,
+                shard_move.shard_id,
+                shard_move.from_peer_id,
+                shard_move.to_peer_id,
+            );
+            dispatcher
+                .submit_collection_meta_op(
+                    CollectionMetaOperations::TransferShard(
+                        shard_move.collection_name.clone(),
+                        ShardTransferOperations::Start(ShardTransfer {
+                            shard_id: shard_move.shard_id,
+                            to_shard_id: None,
+                            from: shard_move.from_peer_id,
+                            to: shard_move.to_peer_id,
+                            sync: false,
+                            method: None,
+                        }),
+                    ),
+                    access.clone(),
+                    None,
+                )
+                .await?;
+        }
+    }
+
+    Ok(RebalancePlan {
+        strategy: request.strategy,
+        moves,
+        applied: request.apply,
+    })
+}
+
+/// Narrow down peers allowed to receive replicas of a pinned shard to the peers it is pinned to
+fn pin_allowed_peers(
+    allowed_peers: Option<&HashSet<PeerId>>,
+    pinned_peers: Option<&[PeerId]>,
+) -> Option<HashSet<PeerId>> {
+    let Some(pinned_peers) = pinned_peers else {
+        return allowed_peers.cloned();
+    };
+    let pinned_peers = pinned_peers
+        .iter()
+        .copied()
+        .filter(|peer_id| allowed_peers.map_or(true, |allowed| allowed.contains(peer_id)))
+        .collect();
+    Some(pinned_peers)
+}
+
+/// Approximate size of a point in bytes, only dense vectors are taken into account
+fn estimate_point_size(vectors: &VectorsConfig) -> u64 {
+    vectors
+        .params_iter()
+        .map(|(_, params)| {
+            let element_size = match params.datatype.unwrap_or_default() {
+                Datatype::Float32 => 4,
+                Datatype::Float16 => 2,
+                Datatype::Float64 => 8,
+                Datatype::Uint8 => 1,
+            };
+            params.size.get() * element_size
+        })
+        .sum::<u64>()
+        .max(1)
+}
+
+/// Greedily move replicas from the most loaded peer to the least loaded one,
+/// while it reduces the difference between them. Each shard is moved at most once.
+///
+/// A replica is not moved into a zone, which already holds another replica of the shard.
+fn plan_balanced(
+    shards: &[ShardReplicas],
+    peers: &[PeerId],
+    peer_zones: &HashMap<PeerId, String>,
+) -> Vec<ShardMove> {
+    let mut load: BTreeMap<PeerId, u64> = peers.iter().map(|&peer_id| (peer_id, 0)).collect();
+    for shard in shards {
+        for peer_id in &shard.peers {
+            if let Some(peer_load) = load.get_mut(peer_id) {
+                *peer_load += shard.weight;
+            }
+        }
+    }
+
+    let mut placements = shards.iter().map(|shard| shard.peers.clone()).collect_vec();
+    let mut moved = vec![false; shards.len()];
+    let mut moves = Vec::new();
+
+    loop {
+        // Try pairs of peers from the most different loads, until some replica can be moved
+        let peers_by_load = load
+            .iter()
+            .map(|(&peer_id, &load)| (peer_id, load))
+            .sorted_by_key(|&(peer_id, load)| (load, peer_id))
+            .collect_vec();
+        let best_move = peers_by_load.iter().rev().find_map(|&(from, from_load)| {
+            peers_by_load
+                .iter()
+                .take_while(|&&(_, to_load)| to_load < from_load)
+                .find_map(|&(to, to_load)| {
+                    let gap = from_load - to_load;
+                    shards
+                        .iter()
+                        .enumerate()
+                        .filter(|&(i, shard)| {
+                            shard.movable
+                                && !moved[i]
+                                && shard.weight > 0
+                                && shard.weight < gap
+                                && placements[i].contains(&from)
+                                && !placements[i].contains(&to)
+                                && shard.allows(to)
+                                && !joins_used_zone(&placements[i], from, to, peer_zones)
+                        })
+                        // Best move splits the gap in half
+                        .min_by_key(|&(i, shard)| (gap.abs_diff(shard.weight * 2), i))
+                        .map(|(i, _)| (i, from, to))
+                })
+        });
+        let Some((i, from, to)) = best_move else {
+            break;
+        };
+        let shard = &shards[i];
+
+        for peer_id in placements[i].iter_mut() {
+            if *peer_id == from {
+                *peer_id = to;
+            }
+        }
+        moved[i] = true;
+        *load.get_mut(&from).unwrap() -= shard.weight;
+        *load.get_mut(&to).unwrap() += shard.weight;
+
+        moves.push(ShardMove {
+            collection_name: shard.collection_name.clone(),
+            shard_id: shard.shard_id,
+            from_peer_id: from,
+            to_peer_id: to,
+        });
+    }
+
+    moves
+}
+
+/// Whether moving a replica from `from` to `to` puts it into a zone with another replica
+fn joins_used_zone(
+    placement: &[PeerId],
+    from: PeerId,
+    to: PeerId,
+    peer_zones: &HashMap<PeerId, String>,
+) -> bool {
+    peer_zones.get(&to).is_some_and(|zone| {
+        placement
+            .iter()
+            .any(|peer_id| *peer_id != from && peer_zones.get(peer_id) == Some(zone))
+    })
+}
+
+/// Move replicas of shards with the same shard key to the peers, which already hold most of them.
+fn plan_tenant_locality(shards: &[ShardReplicas], peers: &[PeerId]) -> Vec<ShardMove> {
+    let mut groups: HashMap<(&str, &ShardKey), Vec<&ShardReplicas>> = HashMap::new();
+    for shard in shards {
+        if let Some(shard_key) = &shard.shard_key {
+            groups
+                .entry((shard.collection_name.as_str(), shard_key))
+                .or_default()
+                .push(shard);
+        }
+    }
+
+    let mut moves = Vec::new();
+    for (_, group) in groups
+        .into_iter()
+        .sorted_by_key(|((collection_name, _), group)| (*collection_name, group[0].shard_id))
+    {
+        let replication_factor = group.iter().map(|shard| shard.peers.len()).max();
+        let Some(replication_factor) = replication_factor else {
+            continue;
+        };
+
+        let mut replica_counts: BTreeMap<PeerId, usize> =
+            peers.iter().map(|&peer_id| (peer_id, 0)).collect();
+        for shard in &group {
+            for peer_id in &shard.peers {
+                if let Some(count) = replica_counts.get_mut(peer_id) {
+                    *count += 1;
+                }
+            }
+        }
+        let preferred = replica_counts
+            .into_iter()
+            .sorted_by_key(|&(peer_id, count)| (Reverse(count), peer_id))
+            .take(replication_factor)
+            .map(|(peer_id, _)| peer_id)
+            .collect_vec();
+
+        for shard in group.iter().filter(|shard| shard.movable) {
+            let from = shard
+                .peers
+                .iter()
+                .find(|peer_id| !preferred.contains(peer_id));
+            let to = preferred
+                .iter()
+                .find(|&&peer_id| !shard.peers.contains(&peer_id) && shard.allows(peer_id));
+            if let (Some(&from), Some(&to)) = (from, to) {
+                moves.push(ShardMove {
+                    collection_name: shard.collection_name.clone(),
+                    shard_id: shard.shard_id,
+                    from_peer_id: from,
+                    to_peer_id: to,
+                });
+            }
+        }
+    }
+
+    moves
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shard(shard_id: ShardId, peers: &[PeerId], weight: u64) -> ShardReplicas {
+        ShardReplicas {
+            collection_name: "test".to_string(),
+            shard_id,
+            shard_key: None,
+            peers: peers.to_vec(),
+            weight,
+            movable: true,
+            allowed_peers: None,
+        }
+    }
+
+    fn peer_loads(shards: &[ShardReplicas], moves: &[ShardMove]) -> BTreeMap<PeerId, u64> {
+        let mut loads = BTreeMap::new();
+        for shard in shards {
+            for &peer_id in &shard.peers {
+                let peer_id = moves
+                    .iter()
+                    .find(|m| m.shard_id == shard.shard_id && m.from_peer_id == peer_id)
+                    .map_or(peer_id, |m| m.to_peer_id);
+                *loads.entry(peer_id).or_default() += shard.weight;
+            }
+        }
+        loads
+    }
+
+    #[test]
+    fn test_plan_balanced_by_count() {
+        let shards = (0..6).map(|id| shard(id, &[1, 2], 1)).collect_vec();
+        let moves = plan_balanced(&shards, &[1, 2, 3], &HashMap::new());
+
+        assert_eq!(moves.len(), 4);
+        assert!(peer_loads(&shards, &moves).values().all(|&load| load == 4));
+
+        // Already balanced
+        let moves = plan_balanced(&shards, &[1, 2], &HashMap::new());
+        assert!(moves.is_empty());
+
+        // Peer 3 shares a zone with peer 2, replicas of peer 1 can't move there
+        let peer_zones = HashMap::from([(2, "a".to_string()), (3, "a".to_string())]);
+        let moves = plan_balanced(&shards, &[1, 2, 3], &peer_zones);
+        assert!(!moves.is_empty());
+        assert!(moves.iter().all(|m| m.from_peer_id == 2));
+    }
+
+    #[test]
+    fn test_plan_balanced_by_weight() {
+        let mut shards = vec![shard(0, &[1], 10), shard(1, &[1], 30), shard(2, &[1], 60)];
+        shards[0].movable = false;
+
+        let moves = plan_balanced(&shards, &[1, 2], &HashMap::new());
+        assert_eq!(
+            moves,
+            [ShardMove {
+                collection_name: "test".to_string(),
+                shard_id: 2,
+                from_peer_id: 1,
+                to_peer_id: 2,
+            }],
+        );
+        assert_eq!(
+            peer_loads(&shards, &moves),
+            BTreeMap::from([(1, 40), (2, 60)])
+        );
+    }
+
+    #[test]
+    fn test_plan_tenant_locality() {
+        let mut shards = vec![shard(0, &[1], 1), shard(1, &[1], 1), shard(2, &[2], 1)];
+        for shard in &mut shards {
+            shard.shard_key = Some(ShardKey::from("tenant"));
+        }
+        shards.push(shard(3, &[3], 1));
+
+        let moves = plan_tenant_locality(&shards, &[1, 2, 3]);
+        assert_eq!(
+            moves,
+            [ShardMove {
+                collection_name: "test".to_string(),
+                shard_id: 2,
+                from_peer_id: 2,
+                to_peer_id: 1,
+            }],
+        );
+    }
+}
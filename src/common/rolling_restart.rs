@@ -0,0 +1,155 @@
+use schemars::JsonSchema;
+use serde::Serialize;
+use storage::content_manager::errors::StorageError;
+use storage::dispatcher::Dispatcher;
+use storage::rbac::{Access, AccessRequirements};
+
+/// Stage of a rolling restart of this node
+#[derive(Debug, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RestartStage {
+    /// Node is not cordoned, all its replicas are active and no transfers are running.
+    /// Restart of the next node may proceed.
+    Serving,
+    /// Node is cordoned, but some operations or shard transfers are not finished yet
+    Draining,
+    /// Node is cordoned and drained, it can be restarted
+    Ready,
+    /// Node is not cordoned, but some of its replicas are still recovering
+    Recovering,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct RestartStatus {
+    pub stage: RestartStage,
+    /// Whether the node is cordoned, it rejects writes and incoming shard transfers
+    pub cordoned: bool,
+    /// Number of update operations currently processed by this node
+    pub in_flight_operations: usize,
+    /// Number of shard transfers from or to this node
+    pub shard_transfers: usize,
+    /// Number of replicas on this node, which are not active
+    pub unhealthy_replicas: usize,
+}
+
+impl RestartStatus {
+    fn new(
+        cordoned: bool,
+        in_flight_operations: usize,
+        shard_transfers: usize,
+        unhealthy_replicas: usize,
+    ) -> Self {
+        let stage = match cordoned {
+            true if in_flight_operations == 0 && shard_transfers == 0 => RestartStage::Ready,
+            true => RestartStage::Draining,
+            false if shard_transfers == 0 && unhealthy_replicas == 0 => RestartStage::Serving,
+            false => RestartStage::Recovering,
+        };
+
+        Self {
+            stage,
+            cordoned,
+            in_flight_operations,
+            shard_transfers,
+            unhealthy_replicas,
+        }
+    }
+}
+
+/// Current stage of the rolling restart of this node
+pub async fn do_get_restart_status(
+    dispatcher: &Dispatcher,
+    access: Access,
+) -> Result<RestartStatus, StorageError> {
+    access.check_global_access(AccessRequirements::new())?;
+
+    let toc = dispatcher.toc(&access);
+    let this_peer_id = toc.this_peer_id;
+
+    let mut shard_transfers = 0;
+    let mut unhealthy_replicas = 0;
+    for collection_pass in toc.all_collections(&access).await {
+        let state = match toc.get_collection(&collection_pass).await {
+            Ok(collection) => collection.state().await,
+            // Collection was removed in the meantime
+            Err(_) => continue,
+        };
+
+        shard_transfers += state
+            .transfers
+            .iter()
+            .filter(|transfer| transfer.from == this_peer_id || transfer.to == this_peer_id)
+            .count();
+        unhealthy_replicas += state
+            .shards
+            .values()
+            .filter_map(|info| info.replicas.get(&this_peer_id))
+            .filter(|state| !state.is_active_or_listener())
+            .count();
+    }
+
+    Ok(RestartStatus::new(
+        toc.is_cordoned(),
+        toc.in_flight_updates(),
+        shard_transfers,
+        unhealthy_replicas,
+    ))
+}
+
+/// Cordon this node before a restart, or uncordon it afterwards
+///
+/// Returns the status of the rolling restart after the change.
+pub async fn do_set_cordon(
+    dispatcher: &Dispatcher,
+    cordon: bool,
+    access: Access,
+) -> Result<RestartStatus, StorageError> {
+    access.check_global_access(AccessRequirements::new().manage())?;
+
+    let toc = dispatcher.toc(&access);
+    let changed = if cordon { toc.cordon() } else { toc.uncordon() };
+
+    if changed {
+        log::info!(
+            "Node {} for restart",
+            if cordon { "cordoned" } else { "uncordoned" },
+        );
+        // Let other peers know about the read-only mode as soon as possible
+        if let Some(consensus_state) = dispatcher.consensus_state() {
+            consensus_state.request_peer_metadata_update();
+        }
+    }
+
+    do_get_restart_status(dispatcher, access).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_restart_stage() {
+        assert_eq!(
+            RestartStatus::new(false, 3, 0, 0).stage,
+            RestartStage::Serving
+        );
+        assert_eq!(
+            RestartStatus::new(false, 0, 1, 0).stage,
+            RestartStage::Recovering
+        );
+        assert_eq!(
+            RestartStatus::new(false, 0, 0, 2).stage,
+            RestartStage::Recovering
+        );
+        assert_eq!(
+            RestartStatus::new(true, 1, 0, 0).stage,
+            RestartStage::Draining
+        );
+        assert_eq!(
+            RestartStatus::new(true, 0, 1, 0).stage,
+            RestartStage::Draining
+        );
+        // Replicas of a cordoned node may become dead, they are recovered after the restart
+        assert_eq!(RestartStatus::new(true, 0, 0, 2).stage, RestartStage::Ready);
+    }
+}
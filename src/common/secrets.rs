@@ -0,0 +1,323 @@
+//! Secrets referenced in the config as `secret:<name>`, fetched from a secret provider.
+//!
+//! API keys, encryption keys of storage, WAL and snapshots, and TLS certificates can be
+//! references. TLS certificates and keys are written to files, which are reloaded as usual.
+//! Secrets are fetched again periodically: rotated API keys apply immediately, rotated TLS files
+//! are picked up within `cert_ttl`, and rotated encryption keys apply after a restart.
+
+use std::fs;
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Context as _};
+
+use super::auth::AuthKeys;
+use crate::settings::{ServiceConfig, Settings, VaultConfig};
+
+/// Prefix of config values, which reference a secret
+pub const SECRET_PREFIX: &str = "secret:";
+
+/// Field of a Vault secret, which is used if the name doesn't specify one
+const DEFAULT_VAULT_FIELD: &str = "value";
+
+const VAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+trait SecretProvider: Send + Sync {
+    fn fetch(&self, name: &str) -> anyhow::Result<String>;
+}
+
+/// Reads secrets from KV v2 secrets engine of HashiCorp Vault.
+///
+/// Names are `<path>#<field>`, field defaults to `value`.
+struct VaultProvider {
+    config: VaultConfig,
+    client: reqwest::blocking::Client,
+}
+
+impl VaultProvider {
+    fn new(config: VaultConfig) -> anyhow::Result<Self> {
+        let client = reqwest::blocking::Client::builder()
+            .timeout(VAULT_TIMEOUT)
+            .build()?;
+        Ok(Self { config, client })
+    }
+
+    fn token(&self) -> anyhow::Result<String> {
+        if let Some(token) = &self.config.token {
+            return Ok(token.clone());
+        }
+        if let Some(token_file) = &self.config.token_file {
+            let token = fs::read_to_string(token_file)
+                .with_context(|| format!("Failed to read Vault token file {token_file}"))?;
+            return Ok(token.trim().to_string());
+        }
+        std::env::var("VAULT_TOKEN").context("Vault token is not configured")
+    }
+}
+
+impl SecretProvider for VaultProvider {
+    fn fetch(&self, name: &str) -> anyhow::Result<String> {
+        let (path, field) = name.split_once('#').unwrap_or((name, DEFAULT_VAULT_FIELD));
+        let url = format!(
+            "{}/v1/{}/data/{}",
+            self.config.address.trim_end_matches('/'),
+            self.config.mount.trim_matches('/'),
+            path.trim_start_matches('/'),
+        );
+
+        let mut request = self.client.get(url).header("X-Vault-Token", self.token()?);
+        if let Some(namespace) = &self.config.namespace {
+            request = request.header("X-Vault-Namespace", namespace);
+        }
+        let body = request.send()?.error_for_status()?.text()?;
+
+        let response: serde_json::Value = serde_json::from_str(&body)?;
+        response["data"]["data"][field]
+            .as_str()
+            .map(ToString::to_string)
+            .ok_or_else(|| anyhow!("Vault secret {path} has no string field {field}"))
+    }
+}
+
+/// Reads secrets from stdout of a command, e.g. CLI of a cloud KMS or secret manager
+struct CommandProvider {
+    command: Vec<String>,
+}
+
+impl SecretProvider for CommandProvider {
+    fn fetch(&self, name: &str) -> anyhow::Result<String> {
+        let (program, args) = self
+            .command
+            .split_first()
+            .ok_or_else(|| anyhow!("Secrets command is empty"))?;
+
+        let output = Command::new(program)
+            .args(args.iter().map(|arg| arg.replace("{name}", name)))
+            .output()
+            .with_context(|| format!("Failed to run secrets command {program}"))?;
+        if !output.status.success() {
+            bail!(
+                "Secrets command {program} failed with {}: {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim(),
+            );
+        }
+
+        let value = String::from_utf8(output.stdout).context("Secret is not valid UTF-8")?;
+        Ok(value.trim().to_string())
+    }
+}
+
+/// Resolves references to secrets in the settings
+pub struct Secrets {
+    provider: Box<dyn SecretProvider>,
+    /// Settings as configured, with references to secrets
+    unresolved: Settings,
+    files_dir: PathBuf,
+    refresh_interval: Option<Duration>,
+}
+
+impl Secrets {
+    /// Replace references to secrets in the settings with their values.
+    ///
+    /// Returns None if no secret provider is configured.
+    pub fn resolve(settings: &mut Settings) -> anyhow::Result<Option<Self>> {
+        let Some(config) = settings.secrets.clone() else {
+            return Ok(None);
+        };
+
+        let provider: Box<dyn SecretProvider> = match (config.vault, config.command) {
+            (Some(vault), None) => Box::new(VaultProvider::new(vault)?),
+            (None, Some(command)) => Box::new(CommandProvider { command }),
+            (Some(_), Some(_)) => bail!("Only one secret provider can be configured"),
+            (None, None) => bail!("Secret provider is not configured"),
+        };
+
+        let secrets = Self {
+            provider,
+            unresolved: settings.clone(),
+            files_dir: PathBuf::from(config.files_dir),
+            refresh_interval: config.refresh_interval_sec.map(Duration::from_secs),
+        };
+        *settings = secrets.resolve_settings()?;
+        Ok(Some(secrets))
+    }
+
+    fn resolve_settings(&self) -> anyhow::Result<Settings> {
+        let mut settings = self.unresolved.clone();
+
+        let service = &mut settings.service;
+        self.resolve_option(&mut service.api_key)?;
+        self.resolve_option(&mut service.read_only_api_key)?;
+        for api_key in &mut service.api_keys {
+            self.resolve_value(&mut api_key.key)?;
+        }
+
+        let storage = &mut settings.storage;
+        self.resolve_option(&mut storage.wal_encryption.key)?;
+        for key in storage.wal_encryption.collection_keys.values_mut() {
+            self.resolve_value(key)?;
+        }
+        self.resolve_option(&mut storage.storage_encryption.key)?;
+        for key in storage.storage_encryption.collection_keys.values_mut() {
+            self.resolve_value(key)?;
+        }
+        self.resolve_option(&mut storage.snapshots_config.encryption.key)?;
+
+        if let Some(tls) = &mut settings.tls {
+            self.resolve_file(&mut tls.cert, "cert.pem")?;
+            self.resolve_file(&mut tls.key, "key.pem")?;
+            self.resolve_file(&mut tls.ca_cert, "ca_cert.pem")?;
+        }
+
+        Ok(settings)
+    }
+
+    fn fetch(&self, value: &str) -> anyhow::Result<Option<String>> {
+        let Some(name) = value.strip_prefix(SECRET_PREFIX) else {
+            return Ok(None);
+        };
+        self.provider
+            .fetch(name)
+            .map(Some)
+            .with_context(|| format!("Failed to fetch secret {name}"))
+    }
+
+    fn resolve_value(&self, value: &mut String) -> anyhow::Result<()> {
+        if let Some(secret) = self.fetch(value)? {
+            *value = secret;
+        }
+        Ok(())
+    }
+
+    fn resolve_option(&self, value: &mut Option<String>) -> anyhow::Result<()> {
+        match value {
+            Some(value) => self.resolve_value(value),
+            None => Ok(()),
+        }
+    }
+
+    /// Write the secret to a file, and replace the reference with its path
+    fn resolve_file(&self, value: &mut String, file_name: &str) -> anyhow::Result<()> {
+        let Some(secret) = self.fetch(value)? else {
+            return Ok(());
+        };
+        let path = self.files_dir.join(file_name);
+        // Keep the file untouched if the secret didn't change
+        if fs::read_to_string(&path).ok().as_deref() != Some(secret.as_str()) {
+            write_private_file(&path, &secret)
+                .with_context(|| format!("Failed to write secret to {}", path.display()))?;
+        }
+        *value = path.to_string_lossy().into_owned();
+        Ok(())
+    }
+
+    /// Fetch secrets periodically, and apply rotated values.
+    ///
+    /// Never resolves if no refresh interval is configured.
+    pub async fn refresh_forever(self, auth_keys: Option<AuthKeys>, mut current: Settings) {
+        let Some(interval) = self.refresh_interval else {
+            return futures::future::pending().await;
+        };
+        let secrets = Arc::new(self);
+
+        loop {
+            tokio::time::sleep(interval).await;
+
+            let secrets = secrets.clone();
+            let refreshed =
+                match tokio::task::spawn_blocking(move || secrets.resolve_settings()).await {
+                    Ok(Ok(refreshed)) => refreshed,
+                    Ok(Err(err)) => {
+                        log::warn!("Failed to refresh secrets: {err:#}");
+                        continue;
+                    }
+                    Err(err) => {
+                        log::warn!("Failed to refresh secrets: {err}");
+                        continue;
+                    }
+                };
+
+            if api_keys(&refreshed.service) != api_keys(&current.service) {
+                if let Some(auth_keys) = &auth_keys {
+                    auth_keys.update(&refreshed.service);
+                    log::info!("Applied rotated API keys");
+                }
+            }
+
+            if encryption_keys(&refreshed) != encryption_keys(&current) {
+                log::warn!("Encryption keys were rotated, restart is required to apply them");
+            }
+
+            current = refreshed;
+        }
+    }
+}
+
+fn api_keys(service: &ServiceConfig) -> (Option<&String>, Option<&String>, Vec<&String>) {
+    (
+        service.api_key.as_ref(),
+        service.read_only_api_key.as_ref(),
+        service
+            .api_keys
+            .iter()
+            .map(|api_key| &api_key.key)
+            .collect(),
+    )
+}
+
+fn encryption_keys(settings: &Settings) -> Vec<(&str, Option<&String>)> {
+    let storage = &settings.storage;
+    let mut keys = vec![
+        ("wal", storage.wal_encryption.key.as_ref()),
+        ("storage", storage.storage_encryption.key.as_ref()),
+        (
+            "snapshots",
+            storage.snapshots_config.encryption.key.as_ref(),
+        ),
+    ];
+    keys.extend(
+        storage
+            .wal_encryption
+            .collection_keys
+            .iter()
+            .chain(&storage.storage_encryption.collection_keys)
+            .map(|(collection, key)| (collection.as_str(), Some(key))),
+    );
+    keys.sort();
+    keys
+}
+
+/// Atomically write a file, which is readable by the owner only
+fn write_private_file(path: &Path, content: &str) -> anyhow::Result<()> {
+    let dir = path.parent().unwrap_or(Path::new("."));
+    fs::create_dir_all(dir)?;
+
+    // Temporary files are created with permissions for the owner only
+    let mut file = tempfile::NamedTempFile::new_in(dir)?;
+    file.write_all(content.as_bytes())?;
+    file.as_file().sync_all()?;
+    file.persist(path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_provider() {
+        let provider = CommandProvider {
+            command: vec!["echo".to_string(), "value-of-{name}".to_string()],
+        };
+        assert_eq!(provider.fetch("api-key").unwrap(), "value-of-api-key");
+
+        let provider = CommandProvider {
+            command: vec!["false".to_string()],
+        };
+        assert!(provider.fetch("api-key").is_err());
+    }
+}
@@ -0,0 +1,80 @@
+//! Log of searches, scrolls and updates, which take longer than a configured threshold.
+//!
+//! Records contain the collection, filters and parameters of the operation, timings of each
+//! shard and search strategies chosen by local segments.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write as _};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use collection::common::slow_log::SLOW_OPERATION_THRESHOLD;
+use collection::events::SlowOperationEvent;
+use issues::broker::Subscriber;
+use parking_lot::Mutex;
+use serde::Serialize;
+
+use crate::settings::SlowLogConfig;
+
+#[derive(Serialize)]
+struct SlowLogRecord<'a> {
+    timestamp: DateTime<Utc>,
+    #[serde(flatten)]
+    event: &'a SlowOperationEvent,
+}
+
+pub struct SlowLog {
+    /// Dedicated file of the slow log, records are written to the regular log otherwise
+    file: Option<Mutex<File>>,
+}
+
+impl SlowLog {
+    /// Start reporting slow operations, if enabled
+    pub fn setup(config: &SlowLogConfig) -> io::Result<()> {
+        if !config.enabled {
+            return Ok(());
+        }
+
+        let file = match &config.path {
+            Some(path) => {
+                if let Some(dir) = Path::new(path).parent() {
+                    fs::create_dir_all(dir)?;
+                }
+                let file = OpenOptions::new().create(true).append(true).open(path)?;
+                Some(Mutex::new(file))
+            }
+            None => None,
+        };
+
+        let _ = SLOW_OPERATION_THRESHOLD.set(Duration::from_millis(config.threshold_ms));
+        issues::broker::add_subscriber::<SlowOperationEvent>(Box::new(Self { file }));
+        Ok(())
+    }
+}
+
+impl Subscriber<SlowOperationEvent> for SlowLog {
+    fn notify(&self, event: Arc<SlowOperationEvent>) {
+        let record = SlowLogRecord {
+            timestamp: Utc::now(),
+            event: &event,
+        };
+        let line = match serde_json::to_string(&record) {
+            Ok(line) => line,
+            Err(err) => {
+                log::error!("Failed to serialize slow log record {event:?}: {err}");
+                return;
+            }
+        };
+
+        match &self.file {
+            Some(file) => {
+                if let Err(err) = writeln!(file.lock(), "{line}") {
+                    log::error!("Failed to write slow log record {line}: {err}");
+                }
+            }
+            None => log::warn!(target: "slow_log", "{line}"),
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::NaiveDateTime;
+use collection::operations::snapshot_ops::SnapshotSchedule;
+use storage::content_manager::errors::StorageError;
+use storage::content_manager::toc::TableOfContent;
+use storage::rbac::{Access, CollectionPass};
+
+/// Schedules have a minute precision, so it is enough to check them a few times per minute
+const CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+const FULL_ACCESS: Access = Access::full("Snapshot scheduler");
+
+/// Background task, which creates scheduled snapshots of collections and applies retention policies
+pub struct SnapshotScheduler {
+    toc: Arc<TableOfContent>,
+    /// Schedule of each collection along with the time of its next run
+    next_runs: HashMap<String, (SnapshotSchedule, Option<NaiveDateTime>)>,
+}
+
+impl SnapshotScheduler {
+    fn new(toc: Arc<TableOfContent>) -> Self {
+        Self {
+            toc,
+            next_runs: HashMap::new(),
+        }
+    }
+
+    async fn check(&mut self) {
+        let now = chrono::Utc::now().naive_utc();
+        let collections = self.toc.all_collections(&FULL_ACCESS).await;
+
+        let mut next_runs = HashMap::with_capacity(self.next_runs.len());
+        for collection_pass in collections {
+            let collection_name = collection_pass.name().to_string();
+
+            let schedule = match self.toc.get_collection(&collection_pass).await {
+                Ok(collection) => collection.snapshot_schedule(),
+                // Collection was removed in the meantime
+                Err(_) => None,
+            };
+            let Some(schedule) = schedule else {
+                continue;
+            };
+
+            let next_run = match self.next_runs.remove(&collection_name) {
+                Some((previous, next_run)) if previous == schedule => next_run,
+                // Schedule is new or was changed, start counting from now
+                _ => schedule.next_run_after(now),
+            };
+
+            let next_run = match next_run {
+                Some(next_run) if next_run <= now => {
+                    if let Err(err) = self.run(&collection_pass, &schedule).await {
+                        log::error!(
+                            "Failed to create scheduled snapshot of collection {collection_name}: {err}",
+                        );
+                    }
+                    schedule.next_run_after(chrono::Utc::now().naive_utc())
+                }
+                next_run => next_run,
+            };
+
+            next_runs.insert(collection_name, (schedule, next_run));
+        }
+
+        self.next_runs = next_runs;
+    }
+
+    async fn run(
+        &self,
+        collection_pass: &CollectionPass<'_>,
+        schedule: &SnapshotSchedule,
+    ) -> Result<(), StorageError> {
+        let snapshot = self.toc.create_snapshot(collection_pass, None).await?;
+        log::info!(
+            "Created scheduled snapshot {} of collection {}",
+            snapshot.name,
+            collection_pass.name(),
+        );
+
+        let collection = self.toc.get_collection(collection_pass).await?;
+        collection
+            .apply_snapshot_retention(&schedule.retention)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn run_forever(toc: Arc<TableOfContent>) {
+        let mut scheduler = Self::new(toc);
+        loop {
+            scheduler.check().await;
+            tokio::time::sleep(CHECK_INTERVAL).await;
+        }
+    }
+}
@@ -4,6 +4,7 @@ use std::sync::Arc;
 
 use collection::collection::Collection;
 use collection::common::sha_256::hash_file;
+use collection::common::snapshot_encryption::decrypt_snapshot;
 use collection::operations::snapshot_ops::{
     ShardSnapshotLocation, SnapshotDescription, SnapshotPriority,
 };
@@ -12,7 +13,7 @@ use collection::shards::shard::ShardId;
 use storage::content_manager::errors::StorageError;
 use storage::content_manager::snapshots;
 use storage::content_manager::toc::TableOfContent;
-use storage::rbac::{Access, AccessRequirements};
+use storage::rbac::{Access, AccessRequirements, SnapshotAction};
 
 use super::http_client::HttpClient;
 
@@ -25,8 +26,12 @@ pub async fn create_shard_snapshot(
     collection_name: String,
     shard_id: ShardId,
 ) -> Result<SnapshotDescription, StorageError> {
-    let collection_pass = access
-        .check_collection_access(&collection_name, AccessRequirements::new().write().whole())?;
+    let collection_pass = access.check_collection_access(
+        &collection_name,
+        AccessRequirements::new()
+            .whole()
+            .snapshot(SnapshotAction::Create),
+    )?;
     let collection = toc.get_collection(&collection_pass).await?;
 
     let snapshot = collection
@@ -45,8 +50,12 @@ pub async fn list_shard_snapshots(
     collection_name: String,
     shard_id: ShardId,
 ) -> Result<Vec<SnapshotDescription>, StorageError> {
-    let collection_pass =
-        access.check_collection_access(&collection_name, AccessRequirements::new().whole())?;
+    let collection_pass = access.check_collection_access(
+        &collection_name,
+        AccessRequirements::new()
+            .whole()
+            .snapshot(SnapshotAction::List),
+    )?;
     let collection = toc.get_collection(&collection_pass).await?;
     let snapshots = collection.list_shard_snapshots(shard_id).await?;
     Ok(snapshots)
@@ -62,8 +71,12 @@ pub async fn delete_shard_snapshot(
     shard_id: ShardId,
     snapshot_name: String,
 ) -> Result<(), StorageError> {
-    let collection_pass = access
-        .check_collection_access(&collection_name, AccessRequirements::new().write().whole())?;
+    let collection_pass = access.check_collection_access(
+        &collection_name,
+        AccessRequirements::new()
+            .whole()
+            .snapshot(SnapshotAction::Create),
+    )?;
     let collection = toc.get_collection(&collection_pass).await?;
     let snapshot_manager = collection.get_snapshots_storage_manager()?;
     let snapshot_path = snapshot_manager
@@ -98,7 +111,11 @@ pub async fn recover_shard_snapshot(
     api_key: Option<String>,
 ) -> Result<(), StorageError> {
     let collection_pass = access
-        .check_global_access(AccessRequirements::new().manage())?
+        .check_global_access(
+            AccessRequirements::new()
+                .manage()
+                .snapshot(SnapshotAction::Restore),
+        )?
         .issue_pass(&collection_name)
         .into_static();
 
@@ -116,7 +133,7 @@ pub async fn recover_shard_snapshot(
 
             let (snapshot_path, snapshot_temp_path) = match snapshot_location {
                 ShardSnapshotLocation::Url(url) => {
-                    if !matches!(url.scheme(), "http" | "https") {
+                    if !matches!(url.scheme(), "http" | "https" | "s3" | "gs") {
                         let description = format!(
                             "Invalid snapshot URL {url}: URLs with {} scheme are not supported",
                             url.scheme(),
@@ -128,8 +145,13 @@ pub async fn recover_shard_snapshot(
                     let client = client.client(api_key.as_deref())?;
 
                     let (snapshot_path, snapshot_temp_path) =
-                        snapshots::download::download_snapshot(&client, url, download_dir.path())
-                            .await?;
+                        snapshots::download::download_snapshot(
+                            &client,
+                            toc.snapshots_config(),
+                            url,
+                            download_dir.path(),
+                        )
+                        .await?;
 
                     (snapshot_path, snapshot_temp_path)
                 }
@@ -141,9 +163,23 @@ pub async fn recover_shard_snapshot(
                             collection.shards_holder(),
                             shard_id,
                             collection.snapshots_path(),
-                            snapshot_file_name,
+                            &snapshot_file_name,
                         )
                         .await?;
+
+                    // Shard can also be recovered from a snapshot of the whole collection
+                    let snapshot_path = if snapshot_path.exists() {
+                        snapshot_path
+                    } else {
+                        snapshot_storage_manager
+                            .get_snapshot_path(
+                                collection.snapshots_path(),
+                                &snapshot_file_name.to_string_lossy(),
+                            )
+                            .await
+                            .unwrap_or(snapshot_path)
+                    };
+
                     check_shard_snapshot_file_exists(&snapshot_path)?;
                     (snapshot_path, None)
                 }
@@ -158,15 +194,26 @@ pub async fn recover_shard_snapshot(
                 }
             }
 
+            // Collection snapshots might be encrypted, those are decrypted with the configured key
+            let cipher = toc.snapshots_config().encryption.cipher(None)?;
+            let (snapshot_path, decrypted_snapshot) = {
+                let download_dir = download_dir.path().to_path_buf();
+                tokio::task::spawn_blocking(move || {
+                    decrypt_snapshot(&snapshot_path, cipher.as_deref(), &download_dir)
+                })
+                .await??
+            };
+
             Result::<_, StorageError>::Ok((
                 collection,
                 download_dir,
                 snapshot_path,
                 snapshot_temp_path,
+                decrypted_snapshot,
             ))
         };
 
-        let (collection, _download_dir, snapshot_path, snapshot_temp_path) =
+        let (collection, _download_dir, snapshot_path, snapshot_temp_path, _decrypted_snapshot) =
             cancel::future::cancel_on_token(cancel.clone(), future).await??;
 
         // `recover_shard_snapshot_impl` is *not* cancel safe
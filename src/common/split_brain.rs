@@ -0,0 +1,107 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use storage::content_manager::consensus_manager::ConsensusStateRef;
+use storage::content_manager::toc::TableOfContent;
+use storage::types::QuorumLossFencing;
+
+use crate::settings::SplitBrainProtectionConfig;
+
+const CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Tracks for how long this peer has no known leader
+///
+/// A peer in a minority partition can't elect a leader, and a leader in a minority partition
+/// steps down once it loses the quorum. So the absence of a leader for longer than a usual
+/// election indicates that the peer is cut off from the quorum.
+struct QuorumTracker {
+    fence: QuorumLossFencing,
+    timeout: Duration,
+    leader_lost_at: Option<Instant>,
+}
+
+impl QuorumTracker {
+    fn new(config: &SplitBrainProtectionConfig) -> Self {
+        Self {
+            fence: config.fence,
+            timeout: Duration::from_millis(config.timeout_ms),
+            leader_lost_at: None,
+        }
+    }
+
+    /// Operations to fence, given whether the leader is known at the moment
+    fn update(&mut self, has_leader: bool, now: Instant) -> QuorumLossFencing {
+        if has_leader {
+            self.leader_lost_at = None;
+            return QuorumLossFencing::None;
+        }
+
+        let leader_lost_at = *self.leader_lost_at.get_or_insert(now);
+        if now.duration_since(leader_lost_at) >= self.timeout {
+            self.fence
+        } else {
+            QuorumLossFencing::None
+        }
+    }
+}
+
+/// Background task, which fences operations of this peer while it is cut off from the quorum
+pub async fn run_forever(
+    toc: Arc<TableOfContent>,
+    consensus_state: ConsensusStateRef,
+    config: SplitBrainProtectionConfig,
+) {
+    let mut tracker = QuorumTracker::new(&config);
+    loop {
+        let has_leader = consensus_state.is_leader_established.check_ready();
+        let fencing = tracker.update(has_leader, Instant::now());
+
+        let previous = toc.set_quorum_loss_fencing(fencing);
+        if previous != fencing {
+            match fencing {
+                QuorumLossFencing::None => {
+                    log::info!("Connection to the consensus quorum restored, accepting operations")
+                }
+                fencing => log::warn!(
+                    "No consensus leader for {}ms, peer is likely cut off from the quorum, \
+                     rejecting operations: {fencing:?}",
+                    config.timeout_ms,
+                ),
+            }
+        }
+
+        tokio::time::sleep(CHECK_INTERVAL).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fencing_after_timeout() {
+        let mut tracker = QuorumTracker::new(&SplitBrainProtectionConfig {
+            fence: QuorumLossFencing::Writes,
+            timeout_ms: 1000,
+        });
+        let start = Instant::now();
+        let after = |ms| start + Duration::from_millis(ms);
+
+        assert_eq!(tracker.update(true, start), QuorumLossFencing::None);
+
+        // Short election is tolerated
+        assert_eq!(tracker.update(false, after(100)), QuorumLossFencing::None);
+        assert_eq!(tracker.update(true, after(500)), QuorumLossFencing::None);
+
+        // Leader is unknown for longer than the timeout
+        assert_eq!(tracker.update(false, after(600)), QuorumLossFencing::None);
+        assert_eq!(tracker.update(false, after(1500)), QuorumLossFencing::None);
+        assert_eq!(
+            tracker.update(false, after(1600)),
+            QuorumLossFencing::Writes
+        );
+
+        // Fence is lifted as soon as the leader is known again
+        assert_eq!(tracker.update(true, after(1700)), QuorumLossFencing::None);
+    }
+}
@@ -34,6 +34,7 @@ use crate::common::helpers;
 use crate::common::telemetry_ops::requests_telemetry::TonicTelemetryCollector;
 use crate::settings::{ConsensusConfig, Settings};
 use crate::tonic::init_internal;
+use crate::tonic::tls::ReloadableTlsAcceptor;
 
 type Node = RawNode<ConsensusStateRef>;
 
@@ -145,7 +146,7 @@ impl Consensus {
                 .clone()
                 .ok_or_else(Settings::tls_config_is_undefined_error)?;
 
-            Some(helpers::load_tls_internal_server_config(&tls_config)?)
+            Some(ReloadableTlsAcceptor::load(tls_config)?)
         } else {
             None
         };
@@ -188,6 +189,8 @@ impl Consensus {
         let raft_config = Config {
             id: state_ref.this_peer_id(),
             applied: last_applied,
+            // Leader steps down once it loses the quorum, so the loss can be detected on its side
+            check_quorum: config.split_brain_protection.fence.fences_writes(),
             ..Default::default()
         };
         raft_config.validate()?;
@@ -250,7 +253,6 @@ impl Consensus {
         let broker = RaftMessageBroker::new(
             runtime.clone(),
             bootstrap_peer,
-            tls_config,
             config.clone(),
             node.store().clone(),
             channel_service.channel_pool,
@@ -329,6 +331,7 @@ impl Consensus {
                     uri: current_uri,
                     port: Some(p2p_port as u32),
                     id: this_peer_id,
+                    learner: Some(config.learner),
                 },
             ))
             .await
@@ -696,7 +699,8 @@ impl Consensus {
     }
 
     fn find_learner_to_promote(&self) -> Option<u64> {
-        let commit = self.node.store().hard_state().commit;
+        let store = self.node.store();
+        let commit = store.hard_state().commit;
         let learners: HashSet<_> = self
             .node
             .store()
@@ -708,7 +712,9 @@ impl Consensus {
         status
             .progress?
             .iter()
-            .find(|(id, progress)| learners.contains(id) && progress.matched == commit)
+            .find(|(id, progress)| {
+                learners.contains(id) && progress.matched == commit && !store.is_learner_peer(**id)
+            })
             .map(|(id, _)| *id)
     }
 
@@ -881,7 +887,6 @@ struct RaftMessageBroker {
     senders: HashMap<PeerId, RaftMessageSenderHandle>,
     runtime: Handle,
     bootstrap_uri: Option<Uri>,
-    tls_config: Option<ClientTlsConfig>,
     consensus_config: Arc<ConsensusConfig>,
     consensus_state: ConsensusStateRef,
     transport_channel_pool: Arc<TransportChannelPool>,
@@ -891,7 +896,6 @@ impl RaftMessageBroker {
     pub fn new(
         runtime: Handle,
         bootstrap_uri: Option<Uri>,
-        tls_config: Option<ClientTlsConfig>,
         consensus_config: ConsensusConfig,
         consensus_state: ConsensusStateRef,
         transport_channel_pool: Arc<TransportChannelPool>,
@@ -900,7 +904,6 @@ impl RaftMessageBroker {
             senders: HashMap::new(),
             runtime,
             bootstrap_uri,
-            tls_config,
             consensus_config: consensus_config.into(),
             consensus_state,
             transport_channel_pool,
@@ -976,7 +979,6 @@ impl RaftMessageBroker {
             messages: messages_rx,
             heartbeat: heartbeat_rx,
             bootstrap_uri: self.bootstrap_uri.clone(),
-            tls_config: self.tls_config.clone(),
             consensus_config: self.consensus_config.clone(),
             consensus_state: self.consensus_state.clone(),
             transport_channel_pool: self.transport_channel_pool.clone(),
@@ -1025,7 +1027,6 @@ struct RaftMessageSender {
     messages: Receiver<(usize, RaftMessage)>,
     heartbeat: watch::Receiver<(usize, RaftMessage)>,
     bootstrap_uri: Option<Uri>,
-    tls_config: Option<ClientTlsConfig>,
     consensus_config: Arc<ConsensusConfig>,
     consensus_state: ConsensusStateRef,
     transport_channel_pool: Arc<TransportChannelPool>,
@@ -1199,12 +1200,13 @@ impl RaftMessageSender {
 
         let bootstrap_timeout = Duration::from_secs(self.consensus_config.bootstrap_timeout_sec);
 
-        // Use dedicated transport channel for who_is because of specific timeout
+        // Use dedicated transport channel for who_is because of specific timeout.
+        // TLS config is taken from the pool, as it is updated on certificate rotation
         let channel = make_grpc_channel(
             bootstrap_timeout,
             bootstrap_timeout,
             bootstrap_uri,
-            self.tls_config.clone(),
+            self.transport_channel_pool.tls_config(),
         )
         .await
         .map_err(|err| anyhow::format_err!("Failed to create who-is channel: {}", err))?;
@@ -1342,11 +1344,21 @@ mod tests {
                             optimizers_config: None,
                             shard_number: Some(2),
                             on_disk_payload: None,
+                            storage_engine: None,
                             replication_factor: None,
                             write_consistency_factor: None,
                             init_from: None,
                             quantization_config: None,
                             sharding_method: None,
+                            tiering_config: None,
+                            ttl_config: None,
+                            async_replication_config: None,
+                            inference_config: None,
+                            quota_config: None,
+                            multitenancy_config: None,
+                            payload_columns: None,
+                            placement_labels: None,
+                            shard_pinning: None,
                         },
                     )),
                     Access::full("For test"),
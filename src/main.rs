@@ -20,9 +20,11 @@ use std::thread::JoinHandle;
 use std::time::Duration;
 
 use ::common::cpu::{get_cpu_budget, CpuBudget};
+use ::common::io_throttle;
 use ::tonic::transport::Uri;
 use api::grpc::transport_channel_pool::TransportChannelPool;
 use clap::Parser;
+use collection::common::latency_histograms::LatencyHistograms;
 use collection::shards::channel_service::ChannelService;
 use consensus::Consensus;
 use slog::Drain;
@@ -34,16 +36,25 @@ use storage::content_manager::toc::transfer::ShardTransferDispatcher;
 use storage::content_manager::toc::TableOfContent;
 use storage::dispatcher::Dispatcher;
 use storage::rbac::Access;
+use storage::types::QuorumLossFencing;
 #[cfg(all(
     not(target_env = "msvc"),
     any(target_arch = "x86_64", target_arch = "aarch64")
 ))]
 use tikv_jemallocator::Jemalloc;
 
+use crate::common::async_replication::AsyncReplicator;
+use crate::common::audit::AuditLog;
+use crate::common::auth::AuthKeys;
 use crate::common::helpers::{
     create_general_purpose_runtime, create_search_runtime, create_update_runtime,
-    load_tls_client_config,
+    load_tls_client_config, reload_tls_client_config_forever,
 };
+use crate::common::memory_budget::MemoryBudget;
+use crate::common::points_expiry::PointsExpiry;
+use crate::common::secrets::Secrets;
+use crate::common::slow_log::SlowLog;
+use crate::common::snapshot_scheduler::SnapshotScheduler;
 use crate::common::telemetry::TelemetryCollector;
 use crate::common::telemetry_reporting::TelemetryReporter;
 use crate::greeting::welcome;
@@ -136,7 +147,8 @@ fn main() -> anyhow::Result<()> {
 
     remove_started_file_indicator();
 
-    let settings = Settings::new(args.config_path)?;
+    let mut settings = Settings::new(args.config_path)?;
+    let secrets = Secrets::resolve(&mut settings)?;
 
     let reporting_enabled = !settings.telemetry_disabled && !args.disable_telemetry;
 
@@ -151,7 +163,15 @@ fn main() -> anyhow::Result<()> {
     setup_panic_hook(reporting_enabled, reporting_id.to_string());
 
     memory::madvise::set_global(settings.storage.mmap_advice);
+    if let Some(buckets) = &settings.metrics.latency_buckets_sec {
+        LatencyHistograms::setup(buckets.clone());
+    }
+    io_throttle::setup(settings.storage.performance.optimizer_io);
     segment::vector_storage::common::set_async_scorer(settings.storage.async_scorer);
+    segment::vector_storage::common::set_async_payload_reads(settings.storage.async_payload_reads);
+    if let Some(parallelism) = settings.storage.async_scorer_parallelism {
+        segment::vector_storage::common::set_async_scorer_parallelism(parallelism);
+    }
 
     welcome(&settings);
 
@@ -170,9 +190,15 @@ fn main() -> anyhow::Result<()> {
         Persistent::load_or_init(&settings.storage.storage_path, args.bootstrap.is_none())?;
 
     let is_distributed_deployment = settings.cluster.enabled;
+    if is_distributed_deployment {
+        tracing::set_peer_id(persistent_consensus_state.this_peer_id());
+    }
 
     let temp_path = settings.storage.temp_path.as_deref();
 
+    // Encrypted snapshots are recovered with the configured key
+    let snapshot_cipher = settings.storage.snapshots_config.encryption.cipher(None)?;
+
     let restored_collections = if let Some(full_snapshot) = args.storage_snapshot {
         recover_full_snapshot(
             temp_path,
@@ -181,6 +207,7 @@ fn main() -> anyhow::Result<()> {
             args.force_snapshot,
             persistent_consensus_state.this_peer_id(),
             is_distributed_deployment,
+            snapshot_cipher.as_deref(),
         )
     } else if let Some(snapshots) = args.snapshot {
         // recover from snapshots
@@ -191,6 +218,7 @@ fn main() -> anyhow::Result<()> {
             &settings.storage.storage_path,
             persistent_consensus_state.this_peer_id(),
             is_distributed_deployment,
+            snapshot_cipher.as_deref(),
         )
     } else {
         vec![]
@@ -244,6 +272,12 @@ fn main() -> anyhow::Result<()> {
             settings.cluster.p2p.connection_pool_size,
             tls_config,
         ));
+        if let (true, Some(tls)) = (settings.cluster.p2p.enable_tls, &settings.tls) {
+            runtime_handle.spawn(reload_tls_client_config_forever(
+                tls.clone(),
+                channel_service.channel_pool.clone(),
+            ));
+        }
         channel_service.id_to_address = persistent_consensus_state.peer_address_by_id.clone();
         channel_service.id_to_metadata = persistent_consensus_state.peer_metadata_by_id.clone();
     }
@@ -287,6 +321,8 @@ fn main() -> anyhow::Result<()> {
             propose_operation_sender.unwrap(),
             storage_path,
         )
+        .with_zone(settings.cluster.zone.clone())
+        .with_labels(settings.cluster.labels.clone())
         .into();
         let is_new_deployment = consensus_state.is_new_deployment();
 
@@ -317,6 +353,15 @@ fn main() -> anyhow::Result<()> {
             consensus_state.is_new_deployment() && args.bootstrap.is_some(),
         ));
 
+        if settings.cluster.peer_health.enabled {
+            let detector = common::peer_health::PeerHealthDetector::new(
+                toc_arc.clone(),
+                channel_service.clone(),
+                &settings.cluster.peer_health,
+            );
+            runtime_handle.spawn(detector.run_forever());
+        }
+
         let handle = Consensus::run(
             &slog_logger,
             consensus_state.clone(),
@@ -333,6 +378,22 @@ fn main() -> anyhow::Result<()> {
 
         handles.push(handle);
 
+        let split_brain_protection = settings.cluster.consensus.split_brain_protection.clone();
+        if split_brain_protection.fence != QuorumLossFencing::None {
+            runtime_handle.spawn(common::split_brain::run_forever(
+                toc_arc.clone(),
+                consensus_state.clone(),
+                split_brain_protection,
+            ));
+        }
+
+        if settings.cluster.dead_peer_removal.enabled {
+            runtime_handle.spawn(common::dead_peers::run_forever(
+                dispatcher_arc.clone(),
+                settings.cluster.dead_peer_removal.clone(),
+            ));
+        }
+
         let toc_arc_clone = toc_arc.clone();
         let consensus_state_clone = consensus_state.clone();
         let _cancel_transfer_handle = runtime_handle.spawn(async move {
@@ -399,9 +460,69 @@ fn main() -> anyhow::Result<()> {
         log::info!("Telemetry reporting disabled");
     }
 
+    //
+    // Scheduled snapshots
+    //
+
+    runtime_handle.spawn(SnapshotScheduler::run_forever(toc_arc.clone()));
+
+    //
+    // Memory budget of vectors and indexes
+    //
+
+    if let Some(budget_mb) = settings.storage.performance.memory_budget_mb {
+        runtime_handle.spawn(MemoryBudget::run_forever(toc_arc.clone(), budget_mb));
+    }
+
+    //
+    // Deletion of expired points
+    //
+
+    runtime_handle.spawn(PointsExpiry::run_forever(toc_arc.clone()));
+
+    //
+    // Usage of tenants, limited by quotas
+    //
+
+    runtime_handle.spawn(common::quota_usage::run_forever(toc_arc.clone()));
+
+    //
+    // Asynchronous replication to remote clusters
+    //
+
+    runtime_handle.spawn(AsyncReplicator::run_forever(
+        toc_arc.clone(),
+        settings.storage.async_replication.clone(),
+    ));
+
     // Setup subscribers to listen for issue-able events
     issues_setup::setup_subscribers(&settings);
 
+    // Log of slow searches, scrolls and updates
+    SlowLog::setup(&settings.slow_log)?;
+    if settings.slow_log.enabled {
+        log::info!(
+            "Slow log enabled, threshold {}ms",
+            settings.slow_log.threshold_ms,
+        );
+    }
+
+    //
+    // Audit log of write and administrative requests
+    //
+
+    let audit_log = AuditLog::new(&settings.audit)?;
+    if audit_log.is_some() {
+        log::info!("Audit log enabled");
+    }
+
+    // Shared by REST and gRPC APIs, so that rate limits of API keys apply to both
+    let auth_keys = AuthKeys::try_create(&settings.service, toc_arc.clone());
+
+    if let Some(secrets) = secrets {
+        runtime_handle.spawn(secrets.refresh_forever(auth_keys.clone(), settings.clone()));
+    }
+
     // Helper to better log start errors
     let log_err_if_any = |server_name, result| match result {
         Err(err) => {
@@ -418,6 +539,8 @@ fn main() -> anyhow::Result<()> {
     #[cfg(feature = "web")]
     {
         let dispatcher_arc = dispatcher_arc.clone();
+        let auth_keys = auth_keys.clone();
+        let audit_log = audit_log.clone();
         let settings = settings.clone();
         let handle = thread::Builder::new()
             .name("web".to_string())
@@ -428,6 +551,8 @@ fn main() -> anyhow::Result<()> {
                         dispatcher_arc.clone(),
                         telemetry_collector,
                         health_checker,
+                        auth_keys,
+                        audit_log,
                         settings,
                         logger_handle,
                     ),
@@ -451,6 +576,8 @@ fn main() -> anyhow::Result<()> {
                     tonic::init(
                         dispatcher_arc,
                         tonic_telemetry_collector,
+                        auth_keys,
+                        audit_log,
                         settings,
                         grpc_port,
                         runtime_handle,
@@ -58,11 +58,21 @@ pub async fn handle_existing_collections(
                         .get(),
                 ),
                 on_disk_payload: Some(collection_state.config.params.on_disk_payload),
+                storage_engine: collection_state.config.params.storage_engine,
+                payload_columns: collection_state.config.params.payload_columns.clone(),
+                placement_labels: collection_state.config.params.placement_labels.clone(),
+                shard_pinning: collection_state.config.params.shard_pinning.clone(),
                 hnsw_config: Some(collection_state.config.hnsw_config.into()),
                 wal_config: Some(collection_state.config.wal_config.into()),
                 optimizers_config: Some(collection_state.config.optimizer_config.into()),
                 init_from: None,
                 quantization_config: collection_state.config.quantization_config,
+                tiering_config: collection_state.config.tiering_config,
+                ttl_config: collection_state.config.ttl_config,
+                async_replication_config: collection_state.config.async_replication_config,
+                inference_config: collection_state.config.inference_config,
+                quota_config: collection_state.config.quota_config,
+                multitenancy_config: collection_state.config.multitenancy_config,
             },
         );
 
@@ -1,30 +1,48 @@
 use api::grpc::models::{CollectionsResponse, VersionInfo};
 use api::rest::{Record, ScoredPoint};
+use collection::collection::async_replication::AsyncReplicationStatus;
+use collection::collection::event_log::CollectionEvents;
 use collection::operations::cluster_ops::ClusterOperations;
 use collection::operations::consistency_params::ReadConsistency;
-use collection::operations::payload_ops::{DeletePayload, SetPayload};
+use collection::operations::payload_ops::{DeletePayload, PatchPayload, SetPayload};
 use collection::operations::point_ops::{PointInsertOperations, PointsSelector, WriteOrdering};
 use collection::operations::snapshot_ops::{
-    ShardSnapshotRecover, SnapshotDescription, SnapshotRecover,
+    CreateSnapshot, ShardSnapshotRecover, SnapshotDescription, SnapshotRecover,
+    SnapshotRecoveryStatus, SnapshotSchedule, SnapshotScheduleInfo,
 };
 use collection::operations::types::{
-    AliasDescription, CollectionClusterInfo, CollectionExistence, CollectionInfo,
+    AliasDescription, CollectionClusterInfo, CollectionDiskUsage, CollectionExistence,
+    CollectionIndexInfo, CollectionInfo, CollectionPointsBreakdown, CollectionQuotaUsage,
     CollectionsAliasesResponse, CountRequest, CountResult, DiscoverRequest, DiscoverRequestBatch,
-    GroupsResult, PointGroup, PointRequest, RecommendGroupsRequest, RecommendRequest,
-    RecommendRequestBatch, ScrollRequest, ScrollResult, SearchGroupsRequest, SearchRequest,
-    SearchRequestBatch, UpdateResult,
+    DryRunResult, ForceOptimizationProgress, GroupsResult, PointGroup, PointRequest,
+    RecommendGroupsRequest, RecommendRequest, RecommendRequestBatch, ScrollRequest, ScrollResult,
+    SearchGroupsRequest, SearchRequest, SearchRequestBatch, UpdateResult, VacuumRequest,
+    VacuumResult,
 };
 use collection::operations::vector_ops::{DeleteVectors, UpdateVectors};
 use schemars::gen::SchemaSettings;
 use schemars::JsonSchema;
+use segment::common::request_usage::RequestUsageReport;
 use serde::Serialize;
+use storage::content_manager::cluster_events::ClusterEvent;
 use storage::content_manager::collection_meta_ops::{
-    ChangeAliasesOperation, CreateCollection, UpdateCollection,
+    ChangeAliasesOperation, CloneCollection, CreateCollection, RenameCollection, UpdateCollection,
 };
-use storage::types::ClusterStatus;
+use storage::content_manager::export::ExportPoints;
+use storage::content_manager::import::{ImportPoints, ImportResult};
+use storage::rbac::revocations::CredentialRevocation;
+use storage::types::{ClusterMetadataEntry, ClusterStatus};
 
-use crate::common::helpers::LocksOption;
-use crate::common::points::{CreateFieldIndex, UpdateOperations};
+use crate::common::async_operations::{AsyncOperationAccepted, AsyncOperationInfo};
+use crate::common::auth::revocations::RevokeCredential;
+use crate::common::cluster_metadata::SetClusterMetadata;
+use crate::common::effective_config::EffectiveConfig;
+use crate::common::helpers::{LocksOption, ReadOnlyOption};
+use crate::common::points::{
+    CreateFieldIndex, ReadOperationResult, ReadOperations, UpdateOperations,
+};
+use crate::common::rebalance::{RebalancePlan, RebalanceRequest};
+use crate::common::rolling_restart::RestartStatus;
 use crate::common::telemetry::TelemetryData;
 
 mod actix;
@@ -81,6 +99,41 @@ struct AllDefinitions {
     bb: DiscoverRequestBatch,
     bc: VersionInfo,
     bd: CollectionExistence,
+    be: CreateSnapshot,
+    bf: SnapshotSchedule,
+    bg: SnapshotScheduleInfo,
+    bh: SnapshotRecoveryStatus,
+    bi: RebalanceRequest,
+    bj: RebalancePlan,
+    bk: ReadOnlyOption,
+    bl: ClusterEvent,
+    bm: AsyncReplicationStatus,
+    bn: RestartStatus,
+    bo: ClusterMetadataEntry,
+    bp: SetClusterMetadata,
+    bq: ImportPoints,
+    br: ImportResult,
+    bs: ReadOperations,
+    bt: ReadOperationResult,
+    bu: ExportPoints,
+    bv: PatchPayload,
+    bw: CloneCollection,
+    bx: RenameCollection,
+    by: DryRunResult,
+    bz: AsyncOperationInfo,
+    ca: AsyncOperationAccepted,
+    cb: CollectionPointsBreakdown,
+    cc: CollectionQuotaUsage,
+    cd: CredentialRevocation,
+    ce: RevokeCredential,
+    cf: RequestUsageReport,
+    cg: CollectionDiskUsage,
+    ch: CollectionIndexInfo,
+    ci: CollectionEvents,
+    cj: EffectiveConfig,
+    ck: ForceOptimizationProgress,
+    cl: VacuumRequest,
+    cm: VacuumResult,
 }
 
 fn save_schema<T: JsonSchema>() {
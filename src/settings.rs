@@ -1,4 +1,6 @@
-use std::{env, io};
+use std::collections::{BTreeMap, HashMap};
+use std::num::NonZeroU32;
+use std::{env, fmt, io};
 
 use api::grpc::transport_channel_pool::{
     DEFAULT_CONNECT_TIMEOUT, DEFAULT_GRPC_TIMEOUT, DEFAULT_POOL_SIZE,
@@ -6,9 +8,11 @@ use api::grpc::transport_channel_pool::{
 use collection::operations::validation;
 use config::{Config, ConfigError, Environment, File, FileFormat, Source};
 use serde::Deserialize;
-use storage::types::StorageConfig;
+use storage::rbac::Access;
+use storage::types::{QuorumLossFencing, StorageConfig};
 use validator::Validate;
 
+use crate::common::ip_filter::IpNetwork;
 use crate::tracing;
 
 const DEFAULT_CONFIG: &str = include_str!("../config/config.yaml");
@@ -32,6 +36,27 @@ pub struct ServiceConfig {
     #[serde(default)]
     pub jwt_rbac: Option<bool>,
 
+    /// Additional API keys, each granting the access of a role from `roles`.
+    #[serde(default)]
+    #[validate]
+    pub api_keys: Vec<ApiKeyConfig>,
+
+    /// Roles of `api_keys` by name.
+    /// A role is either global access (`r` or `m`), or a list of collections or collection name
+    /// patterns with their access mode (`r`, `rw` or `m`).
+    #[serde(default)]
+    pub roles: HashMap<String, Access>,
+
+    /// Rate limits of `roles` by name, applied to each key of the role separately.
+    /// Rate limit of the key itself takes precedence.
+    #[serde(default)]
+    pub rate_limits: HashMap<String, RateLimitConfig>,
+
+    /// Networks allowed to connect to each of the listeners.
+    /// Connections from other clients are closed before any request is processed.
+    #[serde(default)]
+    pub ip_filter: IpFiltersConfig,
+
     /// Directory where static files are served from.
     /// For example, the Web-UI should be placed here.
     #[serde(default)]
@@ -44,6 +69,64 @@ pub struct ServiceConfig {
 
     /// How much time is considered too long for a query to execute.
     pub slow_query_secs: Option<f32>,
+
+    /// Compress REST responses with a codec negotiated by `Accept-Encoding` header.
+    #[serde(default = "default_enable_compression")]
+    pub enable_compression: bool,
+
+    /// Responses smaller than this are not compressed.
+    #[serde(default = "default_compression_min_size_bytes")]
+    pub compression_min_size_bytes: usize,
+}
+
+#[derive(Debug, Deserialize, Clone, Validate)]
+pub struct ApiKeyConfig {
+    #[validate(length(min = 1))]
+    pub key: String,
+    /// Name of the role from `roles`
+    pub role: String,
+    /// Rate limit of the key, overrides rate limit of its role
+    #[serde(default)]
+    pub rate_limit: Option<RateLimitConfig>,
+    /// Networks the key can be used from, in addition to the filter of the listener
+    #[serde(default)]
+    pub ip_filter: Option<IpFilterConfig>,
+    /// Expiration time of the key, seconds since UNIX epoch
+    #[serde(default)]
+    pub expires_at: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy)]
+pub struct RateLimitConfig {
+    /// Maximal number of requests per second
+    pub requests_per_second: NonZeroU32,
+    /// Maximal number of upserted points per second
+    #[serde(default)]
+    pub vectors_per_second: Option<NonZeroU32>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct IpFiltersConfig {
+    /// Filter of the REST API
+    #[serde(default)]
+    pub rest: Option<IpFilterConfig>,
+    /// Filter of the public gRPC API
+    #[serde(default)]
+    pub grpc: Option<IpFilterConfig>,
+    /// Filter of the internal gRPC API, used by peers of the cluster
+    #[serde(default)]
+    pub internal: Option<IpFilterConfig>,
+}
+
+/// CIDR lists of networks, e.g. `10.0.0.0/8`, see [`IpFilterConfig::is_allowed`]
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct IpFilterConfig {
+    /// Only clients from these networks are allowed, if not empty
+    #[serde(default)]
+    pub allow: Vec<IpNetwork>,
+    /// Clients from these networks are rejected, even if allowed
+    #[serde(default)]
+    pub deny: Vec<IpNetwork>,
 }
 
 #[derive(Debug, Deserialize, Clone, Default, Validate)]
@@ -61,6 +144,20 @@ pub struct ClusterConfig {
     #[serde(default)]
     #[validate]
     pub consensus: ConsensusConfig,
+    /// Availability zone or rack of this node.
+    /// Replicas of a shard are placed in different zones, when possible.
+    #[serde(default)]
+    pub zone: Option<String>,
+    /// Arbitrary labels of this node, e.g. `disk: nvme`.
+    /// Placement constraints of collections may restrict their shards to nodes with given labels.
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+    #[serde(default)]
+    #[validate]
+    pub peer_health: PeerHealthConfig,
+    #[serde(default)]
+    #[validate]
+    pub dead_peer_removal: DeadPeerRemovalConfig,
 }
 
 #[derive(Debug, Deserialize, Clone, Validate)]
@@ -97,6 +194,14 @@ pub struct ConsensusConfig {
     #[validate(range(min = 1))]
     #[serde(default = "default_message_timeout_tics")]
     pub message_timeout_ticks: u64,
+    /// Join the cluster as a learner: replicate consensus state and host shard replicas,
+    /// but never vote. Only takes effect when the peer is bootstrapped.
+    #[serde(default)]
+    pub learner: bool,
+    /// Reject operations on this peer, while it is cut off from the consensus quorum
+    #[serde(default)]
+    #[validate]
+    pub split_brain_protection: SplitBrainProtectionConfig,
 }
 
 impl Default for ConsensusConfig {
@@ -106,6 +211,79 @@ impl Default for ConsensusConfig {
             tick_period_ms: default_tick_period_ms(),
             bootstrap_timeout_sec: default_bootstrap_timeout_sec(),
             message_timeout_ticks: default_message_timeout_tics(),
+            learner: false,
+            split_brain_protection: SplitBrainProtectionConfig::default(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, Validate)]
+pub struct SplitBrainProtectionConfig {
+    /// Operations to reject, while there is no known leader: `none`, `writes` or `all`
+    #[serde(default)]
+    pub fence: QuorumLossFencing,
+    /// For how long the leader must be unknown before operations are rejected.
+    /// Bridges short leader elections.
+    #[serde(default = "default_split_brain_timeout_ms")]
+    #[validate(range(min = 1))]
+    pub timeout_ms: u64,
+}
+
+impl Default for SplitBrainProtectionConfig {
+    fn default() -> Self {
+        SplitBrainProtectionConfig {
+            fence: QuorumLossFencing::default(),
+            timeout_ms: default_split_brain_timeout_ms(),
+        }
+    }
+}
+
+/// Direct health checks between peers, which detect failed replicas faster than consensus
+#[derive(Debug, Deserialize, Clone, Validate)]
+pub struct PeerHealthConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// How often each peer is pinged
+    #[serde(default = "default_peer_health_interval_ms")]
+    #[validate(range(min = 1))]
+    pub interval_ms: u64,
+    /// Ping without response in this time is considered failed
+    #[serde(default = "default_peer_health_timeout_ms")]
+    #[validate(range(min = 1))]
+    pub timeout_ms: u64,
+    /// Number of consecutive failed pings, after which replicas of the peer are marked dead
+    #[serde(default = "default_peer_health_failure_threshold")]
+    #[validate(range(min = 1))]
+    pub failure_threshold: usize,
+}
+
+impl Default for PeerHealthConfig {
+    fn default() -> Self {
+        PeerHealthConfig {
+            enabled: false,
+            interval_ms: default_peer_health_interval_ms(),
+            timeout_ms: default_peer_health_timeout_ms(),
+            failure_threshold: default_peer_health_failure_threshold(),
+        }
+    }
+}
+
+/// Automatic removal of peers, which are unreachable for a long time
+#[derive(Debug, Deserialize, Clone, Validate)]
+pub struct DeadPeerRemovalConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Peer unreachable for this long is drained and removed from the cluster
+    #[serde(default = "default_dead_peer_timeout_sec")]
+    #[validate(range(min = 1))]
+    pub timeout_sec: u64,
+}
+
+impl Default for DeadPeerRemovalConfig {
+    fn default() -> Self {
+        DeadPeerRemovalConfig {
+            enabled: false,
+            timeout_sec: default_dead_peer_timeout_sec(),
         }
     }
 }
@@ -120,6 +298,94 @@ pub struct TlsConfig {
     pub cert_ttl: Option<u64>,
 }
 
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct AuditConfig {
+    /// Record write and administrative requests to REST and gRPC APIs
+    #[serde(default)]
+    pub enabled: bool,
+    /// Where the records are written
+    #[serde(default = "default_audit_sinks")]
+    pub sinks: Vec<AuditSinkConfig>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AuditSinkConfig {
+    /// Append records as JSON lines to a dedicated file
+    File { path: String },
+    /// Write records to the regular log, with `audit` target
+    Log,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct SlowLogConfig {
+    /// Record searches, scrolls and updates, which take longer than `threshold_ms`
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_slow_log_threshold_ms")]
+    pub threshold_ms: u64,
+    /// Append records as JSON lines to this file.
+    /// Records are written to the regular log, with `slow_log` target, if not set.
+    #[serde(default)]
+    pub path: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct MetricsConfig {
+    /// Upper boundaries of buckets of latency histograms by operation type, in seconds
+    #[serde(default)]
+    pub latency_buckets_sec: Option<Vec<f64>>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct SecretsConfig {
+    /// Fetch secrets from KV v2 secrets engine of HashiCorp Vault
+    #[serde(default)]
+    pub vault: Option<VaultConfig>,
+    /// Fetch secrets by running a command, e.g. CLI of a cloud KMS or secret manager.
+    /// `{name}` in arguments is replaced with the name of the secret, stdout is the value.
+    #[serde(default)]
+    pub command: Option<Vec<String>>,
+    /// How often secrets are fetched again to pick up rotated values, never if not set
+    #[serde(default)]
+    pub refresh_interval_sec: Option<u64>,
+    /// Directory where TLS certificates and keys from the provider are written to
+    #[serde(default = "default_secrets_files_dir")]
+    pub files_dir: String,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct VaultConfig {
+    /// Address of Vault, e.g. `https://vault:8200`
+    pub address: String,
+    /// Token to authenticate with
+    #[serde(default)]
+    pub token: Option<String>,
+    /// File with the token, read on every fetch, e.g. maintained by Vault agent.
+    /// `VAULT_TOKEN` env variable is used, if neither token nor file is set.
+    #[serde(default)]
+    pub token_file: Option<String>,
+    /// Mount path of the KV v2 secrets engine
+    #[serde(default = "default_vault_mount")]
+    pub mount: String,
+    /// Vault Enterprise namespace
+    #[serde(default)]
+    pub namespace: Option<String>,
+}
+
+impl fmt::Debug for VaultConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Never expose the token in logs
+        f.debug_struct("VaultConfig")
+            .field("address", &self.address)
+            .field("token", &self.token.as_ref().map(|_| "<redacted>"))
+            .field("token_file", &self.token_file)
+            .field("mount", &self.mount)
+            .field("namespace", &self.namespace)
+            .finish()
+    }
+}
+
 #[derive(Debug, Deserialize, Clone, Validate)]
 pub struct Settings {
     #[serde(default)]
@@ -137,11 +403,24 @@ pub struct Settings {
     pub telemetry_disabled: bool,
     #[validate]
     pub tls: Option<TlsConfig>,
+    #[serde(default)]
+    pub audit: AuditConfig,
+    #[serde(default)]
+    pub slow_log: SlowLogConfig,
+    #[serde(default)]
+    pub metrics: MetricsConfig,
+    /// Provider of values referenced as `secret:<name>` in the config
+    #[serde(default)]
+    pub secrets: Option<SecretsConfig>,
     /// A list of messages for errors that happened during loading the configuration. We collect
     /// them and store them here while loading because then our logger is not configured yet.
     /// We therefore need to log these messages later, after the logger is ready.
     #[serde(default, skip)]
     pub load_errors: Vec<LogMsg>,
+    /// All configuration sources merged together, before secrets are resolved.
+    /// Reported as the effective configuration of the node.
+    #[serde(default, skip)]
+    pub merged_config: serde_json::Value,
 }
 
 impl Settings {
@@ -193,8 +472,10 @@ impl Settings {
         config = config.add_source(Environment::with_prefix("QDRANT").separator("__"));
 
         // Build and merge config and deserialize into Settings, attach any load errors we had
-        let mut settings: Settings = config.build()?.try_deserialize()?;
+        let config = config.build()?;
+        let mut settings: Settings = config.clone().try_deserialize()?;
         settings.load_errors.extend(load_errors);
+        settings.merged_config = config.try_deserialize()?;
         Ok(settings)
     }
 
@@ -257,6 +538,14 @@ const fn default_cors() -> bool {
     true
 }
 
+const fn default_enable_compression() -> bool {
+    true
+}
+
+const fn default_compression_min_size_bytes() -> usize {
+    1024
+}
+
 const fn default_timeout_ms() -> u64 {
     DEFAULT_GRPC_TIMEOUT.as_millis() as u64
 }
@@ -282,10 +571,48 @@ const fn default_connection_pool_size() -> usize {
     DEFAULT_POOL_SIZE
 }
 
+const fn default_split_brain_timeout_ms() -> u64 {
+    5_000
+}
+
+const fn default_peer_health_interval_ms() -> u64 {
+    1_000
+}
+
+const fn default_peer_health_timeout_ms() -> u64 {
+    1_000
+}
+
+const fn default_peer_health_failure_threshold() -> usize {
+    3
+}
+
+const fn default_dead_peer_timeout_sec() -> u64 {
+    3_600
+}
+
 const fn default_message_timeout_tics() -> u64 {
     10
 }
 
+fn default_secrets_files_dir() -> String {
+    "./secrets".to_string()
+}
+
+fn default_vault_mount() -> String {
+    "secret".to_string()
+}
+
+fn default_audit_sinks() -> Vec<AuditSinkConfig> {
+    vec![AuditSinkConfig::File {
+        path: "./audit/audit.log".to_string(),
+    }]
+}
+
+const fn default_slow_log_threshold_ms() -> u64 {
+    1000
+}
+
 const fn default_tls_cert_ttl() -> Option<u64> {
     // Default one hour
     Some(3600)
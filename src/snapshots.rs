@@ -2,6 +2,7 @@ use std::fs::{self, remove_dir_all, rename};
 use std::path::{Path, PathBuf};
 
 use collection::collection::Collection;
+use collection::common::snapshot_encryption::{decrypt_snapshot, SnapshotCipher};
 use collection::common::validate_snapshot_archive::validate_open_snapshot_archive;
 use collection::shards::shard::PeerId;
 use log::info;
@@ -15,6 +16,7 @@ use storage::content_manager::toc::{ALIASES_PATH, COLLECTIONS_DIR};
 ///
 /// * `mapping` - `[ <path>:<collection_name> ]`
 /// * `force` - if true, allow to overwrite collections from snapshots
+/// * `snapshot_cipher` - cipher to decrypt encrypted snapshots with
 ///
 /// # Returns
 ///
@@ -26,6 +28,7 @@ pub fn recover_snapshots(
     storage_dir: &str,
     this_peer_id: PeerId,
     is_distributed: bool,
+    snapshot_cipher: Option<&SnapshotCipher>,
 ) -> Vec<String> {
     let collection_dir_path = Path::new(storage_dir).join(COLLECTIONS_DIR);
     let mut recovered_collections: Vec<String> = vec![];
@@ -62,8 +65,13 @@ pub fn recover_snapshots(
         let collection_temp_path = temp_dir
             .map(PathBuf::from)
             .unwrap_or_else(|| collection_path.with_extension("tmp"));
+        let (snapshot_path, _decrypted_snapshot) =
+            decrypt_snapshot(snapshot_path, snapshot_cipher, Path::new(storage_dir))
+                .unwrap_or_else(|err| {
+                    panic!("Failed to decrypt snapshot {collection_name}: {err}")
+                });
         if let Err(err) = Collection::restore_snapshot(
-            snapshot_path,
+            &snapshot_path,
             &collection_temp_path,
             this_peer_id,
             is_distributed,
@@ -88,6 +96,7 @@ pub fn recover_full_snapshot(
     force: bool,
     this_peer_id: PeerId,
     is_distributed: bool,
+    snapshot_cipher: Option<&SnapshotCipher>,
 ) -> Vec<String> {
     let snapshot_temp_path = temp_dir
         .map(PathBuf::from)
@@ -123,6 +132,7 @@ pub fn recover_full_snapshot(
         storage_dir,
         this_peer_id,
         is_distributed,
+        snapshot_cipher,
     );
 
     let alias_path = Path::new(storage_dir).join(ALIASES_PATH);
@@ -1,32 +1,33 @@
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use api::grpc::qdrant::points_server::Points;
 use api::grpc::qdrant::{
-    ClearPayloadPoints, CountPoints, CountResponse, CreateFieldIndexCollection,
+    ClearPayloadPoints, CountPoints, CountResponse, CreateFieldIndexCollection, DeleteBlobPoint,
     DeleteFieldIndexCollection, DeletePayloadPoints, DeletePointVectors, DeletePoints,
-    DiscoverBatchPoints, DiscoverBatchResponse, DiscoverPoints, DiscoverResponse, GetPoints,
-    GetResponse, PointsOperationResponse, RecommendBatchPoints, RecommendBatchResponse,
-    RecommendGroupsResponse, RecommendPointGroups, RecommendPoints, RecommendResponse,
-    ScrollPoints, ScrollResponse, SearchBatchPoints, SearchBatchResponse, SearchGroupsResponse,
-    SearchPointGroups, SearchPoints, SearchResponse, SetPayloadPoints, UpdateBatchPoints,
-    UpdateBatchResponse, UpdatePointVectors, UpsertPoints,
+    DiscoverBatchPoints, DiscoverBatchResponse, DiscoverPoints, DiscoverResponse, GetBlobPoint,
+    GetBlobResponse, GetPoints, GetResponse, PatchPayloadPoints, PointsOperationResponse,
+    RecommendBatchPoints, RecommendBatchResponse, RecommendGroupsResponse, RecommendPointGroups,
+    RecommendPoints, RecommendResponse, ScrollPoints, ScrollResponse, SearchBatchPoints,
+    SearchBatchResponse, SearchGroupsResponse, SearchPointGroups, SearchPoints, SearchResponse,
+    SetBlobPoint, SetPayloadPoints, UpdateBatchPoints, UpdateBatchResponse, UpdatePointVectors,
+    UpsertPoints, UpsertStreamResponse,
 };
 use collection::operations::types::CoreSearchRequest;
 use storage::dispatcher::Dispatcher;
-use tonic::{Request, Response, Status};
+use tonic::{Request, Response, Status, Streaming};
 
 use super::points_common::{
-    delete_vectors, discover, discover_batch, recommend_groups, search_groups, update_batch,
-    update_vectors,
+    delete_blob, delete_vectors, discover, discover_batch, get_blob, recommend_groups,
+    search_groups, set_blob, update_batch, update_vectors,
 };
 use super::validate;
 use crate::tonic::api::points_common::{
     clear_payload, convert_shard_selector_for_read, core_search_batch, count, create_field_index,
-    delete, delete_field_index, delete_payload, get, overwrite_payload, recommend, recommend_batch,
-    scroll, search, set_payload, upsert,
+    delete, delete_field_index, delete_payload, get, overwrite_payload, patch_payload, recommend,
+    recommend_batch, scroll, search, set_payload, upsert,
 };
-use crate::tonic::auth::extract_access;
+use crate::tonic::auth::{check_vectors_rate_limit, extract_access};
 
 pub struct PointsService {
     dispatcher: Arc<Dispatcher>,
@@ -45,6 +46,7 @@ impl Points for PointsService {
         mut request: Request<UpsertPoints>,
     ) -> Result<Response<PointsOperationResponse>, Status> {
         validate(request.get_ref())?;
+        check_vectors_rate_limit(&request, request.get_ref().points.len())?;
 
         let access = extract_access(&mut request);
 
@@ -168,6 +170,80 @@ impl Points for PointsService {
         .map(|resp| resp.map(Into::into))
     }
 
+    async fn patch_payload(
+        &self,
+        mut request: Request<PatchPayloadPoints>,
+    ) -> Result<Response<PointsOperationResponse>, Status> {
+        validate(request.get_ref())?;
+
+        let access = extract_access(&mut request);
+
+        patch_payload(
+            self.dispatcher.toc(&access).clone(),
+            request.into_inner(),
+            None,
+            None,
+            access,
+        )
+        .await
+        .map(|resp| resp.map(Into::into))
+    }
+
+    async fn set_blob(
+        &self,
+        mut request: Request<SetBlobPoint>,
+    ) -> Result<Response<PointsOperationResponse>, Status> {
+        validate(request.get_ref())?;
+
+        let access = extract_access(&mut request);
+
+        set_blob(
+            self.dispatcher.toc(&access).clone(),
+            request.into_inner(),
+            None,
+            None,
+            access,
+        )
+        .await
+        .map(|resp| resp.map(Into::into))
+    }
+
+    async fn delete_blob(
+        &self,
+        mut request: Request<DeleteBlobPoint>,
+    ) -> Result<Response<PointsOperationResponse>, Status> {
+        validate(request.get_ref())?;
+
+        let access = extract_access(&mut request);
+
+        delete_blob(
+            self.dispatcher.toc(&access).clone(),
+            request.into_inner(),
+            None,
+            None,
+            access,
+        )
+        .await
+        .map(|resp| resp.map(Into::into))
+    }
+
+    async fn get_blob(
+        &self,
+        mut request: Request<GetBlobPoint>,
+    ) -> Result<Response<GetBlobResponse>, Status> {
+        validate(request.get_ref())?;
+
+        let access = extract_access(&mut request);
+
+        get_blob(
+            self.dispatcher.toc(&access),
+            request.into_inner(),
+            None,
+            access,
+        )
+        .await
+    }
+
     async fn delete_payload(
         &self,
         mut request: Request<DeletePayloadPoints>,
@@ -224,6 +300,40 @@ impl Points for PointsService {
         .await
     }
 
+    async fn upsert_stream(
+        &self,
+        mut request: Request<Streaming<UpsertPoints>>,
+    ) -> Result<Response<UpsertStreamResponse>, Status> {
+        let access = extract_access(&mut request);
+
+        let timing = Instant::now();
+        let mut stream = request.into_inner();
+        let mut response = UpsertStreamResponse::default();
+
+        // Next batch is only received once the previous one is applied,
+        // so HTTP/2 flow control slows down clients sending faster than we can apply
+        while let Some(upsert_points) = stream.message().await? {
+            validate(&upsert_points)?;
+
+            let points_count = upsert_points.points.len() as u64;
+            let result = upsert(
+                self.dispatcher.toc(&access).clone(),
+                upsert_points,
+                None,
+                None,
+                access.clone(),
+            )
+            .await?;
+
+            response.batches_count += 1;
+            response.points_count += points_count;
+            response.result = PointsOperationResponse::from(result.into_inner()).result;
+        }
+
+        response.time = timing.elapsed().as_secs_f64();
+        Ok(Response::new(response))
+    }
+
     async fn create_field_index(
         &self,
         mut request: Request<CreateFieldIndexCollection>,
@@ -5,20 +5,23 @@ use api::grpc::conversions::{json_path_from_proto, proto_to_payloads};
 use api::grpc::qdrant::payload_index_params::IndexParams;
 use api::grpc::qdrant::points_update_operation::{ClearPayload, Operation, PointStructList};
 use api::grpc::qdrant::{
-    points_update_operation, BatchResult, ClearPayloadPoints, CoreSearchPoints, CountPoints,
-    CountResponse, CreateFieldIndexCollection, DeleteFieldIndexCollection, DeletePayloadPoints,
-    DeletePointVectors, DeletePoints, DiscoverBatchResponse, DiscoverPoints, DiscoverResponse,
-    FieldType, GetPoints, GetResponse, PayloadIndexParams, PointsOperationResponseInternal,
-    PointsSelector, ReadConsistency as ReadConsistencyGrpc, RecommendBatchResponse,
-    RecommendGroupsResponse, RecommendPointGroups, RecommendPoints, RecommendResponse,
-    ScrollPoints, ScrollResponse, SearchBatchResponse, SearchGroupsResponse, SearchPointGroups,
-    SearchPoints, SearchResponse, SetPayloadPoints, SyncPoints, UpdateBatchPoints,
-    UpdateBatchResponse, UpdatePointVectors, UpsertPoints,
+    points_update_operation, BatchResult, BlobChunk, ClearPayloadPoints, CoreSearchPoints,
+    CountPoints, CountResponse, CreateFieldIndexCollection, DeleteBlobPoint,
+    DeleteFieldIndexCollection, DeletePayloadPoints, DeletePointVectors, DeletePoints,
+    DiscoverBatchResponse, DiscoverPoints, DiscoverResponse, FieldType, GetBlobPoint,
+    GetBlobResponse, GetPoints, GetResponse, PatchPayloadPoints, PayloadIndexParams,
+    PointsOperationResponseInternal, PointsSelector, ReadConsistency as ReadConsistencyGrpc,
+    RecommendBatchResponse, RecommendGroupsResponse, RecommendPointGroups, RecommendPoints,
+    RecommendResponse, ScrollPoints, ScrollResponse, SearchBatchResponse, SearchGroupsResponse,
+    SearchPointGroups, SearchPoints, SearchResponse, SetBlobPoint, SetPayloadPoints, SyncPoints,
+    UpdateBatchPoints, UpdateBatchResponse, UpdatePointVectors, UpsertPoints,
 };
 use api::rest::{OrderByInterface, ShardKeySelector};
+use collection::operations::blob_ops::{DeleteBlobOp, SetBlobOp};
 use collection::operations::consistency_params::ReadConsistency;
 use collection::operations::conversions::{
-    try_discover_request_from_grpc, try_points_selector_from_grpc, write_ordering_from_proto,
+    try_discover_request_from_grpc, try_points_selector_from_grpc, update_mode_from_proto,
+    write_ordering_from_proto,
 };
 use collection::operations::payload_ops::DeletePayload;
 use collection::operations::point_ops::{
@@ -47,9 +50,10 @@ use tonic::{Response, Status};
 
 use crate::common::points::{
     do_clear_payload, do_core_search_points, do_count_points, do_create_index,
-    do_create_index_internal, do_delete_index, do_delete_index_internal, do_delete_payload,
-    do_delete_points, do_delete_vectors, do_get_points, do_overwrite_payload, do_scroll_points,
-    do_search_batch_points, do_set_payload, do_update_vectors, do_upsert_points, CreateFieldIndex,
+    do_create_index_internal, do_delete_blob, do_delete_index, do_delete_index_internal,
+    do_delete_payload, do_delete_points, do_delete_vectors, do_get_points, do_overwrite_payload,
+    do_patch_payload, do_scroll_points, do_search_batch_points, do_set_blob, do_set_payload,
+    do_update_vectors, do_upsert_points, CreateFieldIndex,
 };
 
 fn extract_points_selector(
@@ -108,6 +112,10 @@ pub async fn upsert(
         points,
         ordering,
         shard_key_selector,
+        update_mode,
+        update_filter,
+        if_version,
+        dedup_key,
     } = upsert_points;
     let points = points
         .into_iter()
@@ -116,12 +124,18 @@ pub async fn upsert(
     let operation = PointInsertOperations::PointsList(PointsList {
         points,
         shard_key: shard_key_selector.map(ShardKeySelector::from),
+        update_mode: update_mode_from_proto(update_mode)?,
+        update_filter: update_filter.map(|f| f.try_into()).transpose()?,
+        dedup_key: dedup_key
+            .map(|key| json_path_from_proto(&key))
+            .transpose()?,
     });
     let timing = Instant::now();
     let result = do_upsert_points(
         toc,
         collection_name,
         operation,
+        if_version,
         clock_tag,
         shard_selection,
         wait.unwrap_or(false),
@@ -202,6 +216,7 @@ pub async fn delete(
         points,
         ordering,
         shard_key_selector,
+        if_version,
     } = delete_points;
 
     let points_selector = match points {
@@ -214,6 +229,7 @@ pub async fn delete(
         toc,
         collection_name,
         points_selector,
+        if_version,
         clock_tag,
         shard_selection,
         wait.unwrap_or(false),
@@ -374,6 +390,133 @@ pub async fn set_payload(
     Ok(Response::new(response))
 }
 
+pub async fn patch_payload(
+    toc: Arc<TableOfContent>,
+    patch_payload_points: PatchPayloadPoints,
+    clock_tag: Option<ClockTag>,
+    shard_selection: Option<ShardId>,
+    access: Access,
+) -> Result<Response<PointsOperationResponseInternal>, Status> {
+    let PatchPayloadPoints {
+        collection_name,
+        wait,
+        patch,
+        points_selector,
+        ordering,
+        shard_key_selector,
+    } = patch_payload_points;
+
+    let (points, filter) = extract_points_selector(points_selector)?;
+    let operation = collection::operations::payload_ops::PatchPayload {
+        patch: patch
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<_, _>>()?,
+        points,
+        filter,
+        shard_key: shard_key_selector.map(ShardKeySelector::from),
+    };
+
+    let timing = Instant::now();
+    let result = do_patch_payload(
+        toc,
+        collection_name,
+        operation,
+        clock_tag,
+        shard_selection,
+        wait.unwrap_or(false),
+        write_ordering_from_proto(ordering)?,
+        access,
+    )
+    .await
+    .map_err(error_to_status)?;
+
+    let response = points_operation_response_internal(timing, result);
+    Ok(Response::new(response))
+}
+
+pub async fn set_blob(
+    toc: Arc<TableOfContent>,
+    set_blob_point: SetBlobPoint,
+    clock_tag: Option<ClockTag>,
+    shard_selection: Option<ShardId>,
+    access: Access,
+) -> Result<Response<PointsOperationResponseInternal>, Status> {
+    let SetBlobPoint {
+        collection_name,
+        wait,
+        id,
+        blob,
+        ordering,
+        shard_key_selector,
+    } = set_blob_point;
+
+    let operation = SetBlobOp {
+        point_id: id
+            .ok_or_else(|| Status::invalid_argument("id is expected"))?
+            .try_into()?,
+        blob,
+    };
+
+    let timing = Instant::now();
+    let result = do_set_blob(
+        toc,
+        collection_name,
+        operation,
+        shard_key_selector.map(ShardKeySelector::from),
+        clock_tag,
+        shard_selection,
+        wait.unwrap_or(false),
+        write_ordering_from_proto(ordering)?,
+        access,
+    )
+    .await
+    .map_err(error_to_status)?;
+
+    let response = points_operation_response_internal(timing, result);
+    Ok(Response::new(response))
+}
+
+pub async fn delete_blob(
+    toc: Arc<TableOfContent>,
+    delete_blob_point: DeleteBlobPoint,
+    clock_tag: Option<ClockTag>,
+    shard_selection: Option<ShardId>,
+    access: Access,
+) -> Result<Response<PointsOperationResponseInternal>, Status> {
+    let DeleteBlobPoint {
+        collection_name,
+        wait,
+        id,
+        ordering,
+        shard_key_selector,
+    } = delete_blob_point;
+
+    let operation = DeleteBlobOp {
+        point_id: id
+            .ok_or_else(|| Status::invalid_argument("id is expected"))?
+            .try_into()?,
+    };
+
+    let timing = Instant::now();
+    let result = do_delete_blob(
+        toc,
+        collection_name,
+        operation,
+        shard_key_selector.map(ShardKeySelector::from),
+        clock_tag,
+        shard_selection,
+        wait.unwrap_or(false),
+        write_ordering_from_proto(ordering)?,
+        access,
+    )
+    .await
+    .map_err(error_to_status)?;
+
+    let response = points_operation_response_internal(timing, result);
+    Ok(Response::new(response))
+}
+
 pub async fn overwrite_payload(
     toc: Arc<TableOfContent>,
     set_payload_points: SetPayloadPoints,
@@ -526,6 +669,9 @@ pub async fn update_batch(
             points_update_operation::Operation::Upsert(PointStructList {
                 points,
                 shard_key_selector,
+                update_mode,
+                update_filter,
+                dedup_key,
             }) => {
                 upsert(
                     toc.clone(),
@@ -535,6 +681,10 @@ pub async fn update_batch(
                         wait,
                         ordering,
                         shard_key_selector,
+                        update_mode,
+                        update_filter,
+                        if_version: None,
+                        dedup_key,
                     },
                     clock_tag,
                     shard_selection,
@@ -551,6 +701,7 @@ pub async fn update_batch(
                         points: Some(points),
                         ordering,
                         shard_key_selector: None,
+                        if_version: None,
                     },
                     clock_tag,
                     shard_selection,
@@ -723,6 +874,7 @@ pub async fn update_batch(
                         points,
                         ordering,
                         shard_key_selector,
+                        if_version: None,
                     },
                     clock_tag,
                     shard_selection,
@@ -1135,7 +1287,7 @@ pub async fn search_groups(
     .map_err(error_to_status)?;
 
     let response = SearchGroupsResponse {
-        result: Some(groups_result.into()),
+        result: Some(groups_result.try_into()?),
         time: timing.elapsed().as_secs_f64(),
     };
 
@@ -1316,7 +1468,7 @@ pub async fn recommend_groups(
     .map_err(error_to_status)?;
 
     let response = RecommendGroupsResponse {
-        result: Some(groups_result.into()),
+        result: Some(groups_result.try_into()?),
         time: timing.elapsed().as_secs_f64(),
     };
 
@@ -1556,3 +1708,50 @@ pub async fn get(
 
     Ok(Response::new(response))
 }
+
+pub async fn get_blob(
+    toc: &TableOfContent,
+    get_blob_point: GetBlobPoint,
+    shard_selection: Option<ShardId>,
+    access: Access,
+) -> Result<Response<GetBlobResponse>, Status> {
+    let GetBlobPoint {
+        collection_name,
+        id,
+        offset,
+        limit,
+        shard_key_selector,
+    } = get_blob_point;
+
+    let point_id = id
+        .ok_or_else(|| Status::invalid_argument("id is expected"))?
+        .try_into()?;
+    let offset = offset.map_or(0, |offset| offset as usize);
+    let limit = limit.map_or(usize::MAX, |limit| limit as usize);
+
+    let shard_selector = convert_shard_selector_for_read(shard_selection, shard_key_selector);
+
+    let timing = Instant::now();
+
+    let blob = toc
+        .read_blob(
+            &collection_name,
+            point_id,
+            offset,
+            limit,
+            shard_selector,
+            access,
+        )
+        .await
+        .map_err(error_to_status)?;
+
+    let response = GetBlobResponse {
+        result: blob.map(|blob| BlobChunk {
+            data: blob.data,
+            total_size: blob.total_len as u64,
+        }),
+        time: timing.elapsed().as_secs_f64(),
+    };
+
+    Ok(Response::new(response))
+}
@@ -4,12 +4,14 @@ use std::time::{Duration, Instant};
 use api::grpc::qdrant::points_internal_server::PointsInternal;
 use api::grpc::qdrant::{
     ClearPayloadPointsInternal, CoreSearchBatchPointsInternal, CountPointsInternal, CountResponse,
-    CreateFieldIndexCollectionInternal, DeleteFieldIndexCollectionInternal,
-    DeletePayloadPointsInternal, DeletePointsInternal, DeleteVectorsInternal, GetPointsInternal,
-    GetResponse, IntermediateResult, PointsOperationResponseInternal, QueryPointsInternal,
-    QueryResponse, QueryShardPoints, RecommendPointsInternal, RecommendResponse,
-    ScrollPointsInternal, ScrollResponse, SearchBatchResponse, SetPayloadPointsInternal,
-    SyncPointsInternal, UpdateVectorsInternal, UpsertPointsInternal,
+    CreateFieldIndexCollectionInternal, DeleteBlobPointInternal,
+    DeleteFieldIndexCollectionInternal, DeletePayloadPointsInternal, DeletePointsInternal,
+    DeleteVectorsInternal, GetBlobPointInternal, GetBlobResponse, GetPointsInternal, GetResponse,
+    IntermediateResult, PatchPayloadPointsInternal, PointsOperationResponseInternal,
+    QueryPointsInternal, QueryResponse, QueryShardPoints, RecommendPointsInternal,
+    RecommendResponse, ScrollPointsInternal, ScrollResponse, SearchBatchResponse,
+    SetBlobPointInternal, SetPayloadPointsInternal, SyncPointsInternal, UpdateVectorsInternal,
+    UpsertPointsInternal,
 };
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::universal_query::shard_query::ShardQueryRequest;
@@ -22,9 +24,9 @@ use tonic::{Request, Response, Status};
 use super::points_common::core_search_list;
 use super::validate_and_log;
 use crate::tonic::api::points_common::{
-    clear_payload, count, create_field_index_internal, delete, delete_field_index_internal,
-    delete_payload, delete_vectors, get, overwrite_payload, recommend, scroll, set_payload, sync,
-    update_vectors, upsert,
+    clear_payload, count, create_field_index_internal, delete, delete_blob,
+    delete_field_index_internal, delete_payload, delete_vectors, get, get_blob, overwrite_payload,
+    patch_payload, recommend, scroll, set_blob, set_payload, sync, update_vectors, upsert,
 };
 
 const FULL_ACCESS: Access = Access::full("Internal API");
@@ -230,6 +232,81 @@ impl PointsInternal for PointsInternalService {
         .await
     }
 
+    async fn patch_payload(
+        &self,
+        request: Request<PatchPayloadPointsInternal>,
+    ) -> Result<Response<PointsOperationResponseInternal>, Status> {
+        validate_and_log(request.get_ref());
+
+        let PatchPayloadPointsInternal {
+            patch_payload_points,
+            shard_id,
+            clock_tag,
+        } = request.into_inner();
+
+        let patch_payload_points = patch_payload_points
+            .ok_or_else(|| Status::invalid_argument("PatchPayloadPoints is missing"))?;
+
+        patch_payload(
+            self.toc.clone(),
+            patch_payload_points,
+            clock_tag.map(Into::into),
+            shard_id,
+            FULL_ACCESS.clone(),
+        )
+        .await
+    }
+
+    async fn set_blob(
+        &self,
+        request: Request<SetBlobPointInternal>,
+    ) -> Result<Response<PointsOperationResponseInternal>, Status> {
+        validate_and_log(request.get_ref());
+
+        let SetBlobPointInternal {
+            set_blob_point,
+            shard_id,
+            clock_tag,
+        } = request.into_inner();
+
+        let set_blob_point =
+            set_blob_point.ok_or_else(|| Status::invalid_argument("SetBlobPoint is missing"))?;
+
+        set_blob(
+            self.toc.clone(),
+            set_blob_point,
+            clock_tag.map(Into::into),
+            shard_id,
+            FULL_ACCESS.clone(),
+        )
+        .await
+    }
+
+    async fn delete_blob(
+        &self,
+        request: Request<DeleteBlobPointInternal>,
+    ) -> Result<Response<PointsOperationResponseInternal>, Status> {
+        validate_and_log(request.get_ref());
+
+        let DeleteBlobPointInternal {
+            delete_blob_point,
+            shard_id,
+            clock_tag,
+        } = request.into_inner();
+
+        let delete_blob_point = delete_blob_point
+            .ok_or_else(|| Status::invalid_argument("DeleteBlobPoint is missing"))?;
+
+        delete_blob(
+            self.toc.clone(),
+            delete_blob_point,
+            clock_tag.map(Into::into),
+            shard_id,
+            FULL_ACCESS.clone(),
+        )
+        .await
+    }
+
     async fn delete_payload(
         &self,
         request: Request<DeletePayloadPointsInternal>,
@@ -425,6 +502,29 @@ impl PointsInternal for PointsInternalService {
         get(self.toc.as_ref(), get_points, shard_id, FULL_ACCESS.clone()).await
     }
 
+    async fn get_blob(
+        &self,
+        request: Request<GetBlobPointInternal>,
+    ) -> Result<Response<GetBlobResponse>, Status> {
+        validate_and_log(request.get_ref());
+
+        let GetBlobPointInternal {
+            get_blob_point,
+            shard_id,
+        } = request.into_inner();
+
+        let get_blob_point =
+            get_blob_point.ok_or_else(|| Status::invalid_argument("GetBlobPoint is missing"))?;
+
+        get_blob(
+            self.toc.as_ref(),
+            get_blob_point,
+            shard_id,
+            FULL_ACCESS.clone(),
+        )
+        .await
+    }
+
     async fn count(
         &self,
         request: Request<CountPointsInternal>,
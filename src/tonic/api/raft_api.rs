@@ -79,6 +79,21 @@ impl Raft for RaftService {
             .map_err(|err| Status::internal(format!("Failed to parse uri: {err}")))?;
         let peer = request.into_inner();
 
+        // Learner flag must be committed before the peer is added,
+        // so that leader never promotes it to a voter
+        if peer.learner.unwrap_or(false) {
+            self.consensus_state
+                .propose_consensus_op_with_await(
+                    ConsensusOperations::SetPeerLearner {
+                        peer_id: peer.id,
+                        learner: true,
+                    },
+                    None,
+                )
+                .await
+                .map_err(|err| Status::internal(format!("Failed to add learner peer: {err}")))?;
+        }
+
         // the consensus operation can take up to DEFAULT_META_OP_WAIT
         self.consensus_state
             .propose_consensus_op_with_await(
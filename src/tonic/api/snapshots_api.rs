@@ -43,7 +43,10 @@ impl Snapshots for SnapshotsService {
     ) -> Result<Response<CreateSnapshotResponse>, Status> {
         validate(request.get_ref())?;
         let access = extract_access(&mut request);
-        let collection_name = request.into_inner().collection_name;
+        let CreateSnapshotRequest {
+            collection_name,
+            encryption_key,
+        } = request.into_inner();
         let timing = Instant::now();
         let dispatcher = self.dispatcher.clone();
         let response = async move {
@@ -51,6 +54,7 @@ impl Snapshots for SnapshotsService {
                 Arc::clone(dispatcher.toc(&access)),
                 access,
                 &collection_name,
+                encryption_key,
             )?
             .await?
         }
@@ -0,0 +1,104 @@
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use chrono::Utc;
+use futures::future::BoxFuture;
+use tonic::body::BoxBody;
+use tonic::transport::server::{TcpConnectInfo, TlsConnectInfo};
+use tower::{Layer, Service};
+
+use crate::common::audit::{is_audited_grpc_request, AuditLog, AuditRecord, ANONYMOUS_SUBJECT};
+use crate::common::auth::AuthSubject;
+
+type Request = tonic::codegen::http::Request<tonic::transport::Body>;
+type Response = tonic::codegen::http::Response<BoxBody>;
+
+#[derive(Clone)]
+pub struct AuditMiddleware<S> {
+    audit_log: Arc<AuditLog>,
+    service: S,
+}
+
+/// Address of the client, which sent the request
+pub(super) fn remote_addr(request: &Request) -> Option<SocketAddr> {
+    let extensions = request.extensions();
+    extensions
+        .get::<TcpConnectInfo>()
+        .or_else(|| {
+            extensions
+                .get::<TlsConnectInfo<TcpConnectInfo>>()
+                .map(|info| info.get_ref())
+        })
+        .and_then(|info| info.remote_addr())
+}
+
+impl<S> Service<Request> for AuditMiddleware<S>
+where
+    S: Service<Request, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        let method = request.uri().path().to_string();
+        if !is_audited_grpc_request(&method) {
+            return Box::pin(self.service.call(request));
+        }
+
+        let subject = request.extensions().get::<AuthSubject>().map_or_else(
+            || ANONYMOUS_SUBJECT.to_string(),
+            |subject| subject.0.clone(),
+        );
+        let remote_addr = remote_addr(&request).map(|addr| addr.to_string());
+
+        let audit_log = self.audit_log.clone();
+        let future = self.service.call(request);
+        Box::pin(async move {
+            let response = future.await?;
+
+            // Status is sent in headers, if the request failed before the response stream started
+            let status = tonic::Status::from_header_map(response.headers())
+                .map_or(tonic::Code::Ok, |status| status.code());
+            audit_log.record(&AuditRecord {
+                timestamp: Utc::now(),
+                subject,
+                remote_addr,
+                api: "grpc",
+                operation: method,
+                collection: None,
+                status: format!("{status:?}"),
+            });
+
+            Ok(response)
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct AuditLayer {
+    audit_log: Arc<AuditLog>,
+}
+
+impl AuditLayer {
+    pub fn new(audit_log: Arc<AuditLog>) -> Self {
+        Self { audit_log }
+    }
+}
+
+impl<S> Layer<S> for AuditLayer {
+    type Service = AuditMiddleware<S>;
+
+    fn layer(&self, service: S) -> Self::Service {
+        Self::Service {
+            audit_log: self.audit_log.clone(),
+            service,
+        }
+    }
+}
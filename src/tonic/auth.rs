@@ -8,25 +8,44 @@ use tonic::body::BoxBody;
 use tonic::Status;
 use tower::{Layer, Service};
 
-use crate::common::auth::{AuthError, AuthKeys};
+use super::audit::remote_addr;
+use crate::common::auth::{AuthError, AuthKeys, AuthSubject};
+use crate::common::rate_limiting::{RateLimited, RateLimiter};
 
 type Request = tonic::codegen::http::Request<tonic::transport::Body>;
 type Response = tonic::codegen::http::Response<BoxBody>;
 
+/// Health checks are available without authentication, same as `/healthz` in REST API,
+/// so that load balancers and probes don't need an API key
+const WHITELISTED_PREFIX: &str = "/grpc.health.v1.Health/";
+
 #[derive(Clone)]
 pub struct AuthMiddleware<S> {
     auth_keys: Arc<AuthKeys>,
     service: S,
 }
 
+fn rate_limited_to_status(err: RateLimited) -> Status {
+    let mut status = Status::resource_exhausted(err.to_string());
+    status
+        .metadata_mut()
+        .insert("retry-after", err.retry_after_secs().into());
+    status
+}
+
 async fn check(auth_keys: Arc<AuthKeys>, mut req: Request) -> Result<Request, Status> {
-    let access = auth_keys
-        .validate_request(|key| req.headers().get(key).and_then(|val| val.to_str().ok()))
+    let remote_ip = remote_addr(&req).map(|addr| addr.ip());
+    let (access, subject, rate_limiter) = auth_keys
+        .validate_request(
+            |key| req.headers().get(key).and_then(|val| val.to_str().ok()),
+            remote_ip,
+        )
         .await
         .map_err(|e| match e {
             AuthError::Unauthorized(e) => Status::unauthenticated(e),
             AuthError::Forbidden(e) => Status::permission_denied(e),
             AuthError::StorageError(e) => error_to_status(e),
+            AuthError::RateLimited(e) => rate_limited_to_status(e),
         })?;
 
     let _previous = req.extensions_mut().insert::<Access>(access);
@@ -34,6 +53,11 @@ async fn check(auth_keys: Arc<AuthKeys>, mut req: Request) -> Result<Request, St
         _previous.is_none(),
         "Previous access object should not exist in the request"
     );
+    req.extensions_mut().insert::<AuthSubject>(subject);
+    if let Some(rate_limiter) = rate_limiter {
+        req.extensions_mut()
+            .insert::<Arc<RateLimiter>>(rate_limiter);
+    }
 
     Ok(req)
 }
@@ -52,6 +76,10 @@ where
     }
 
     fn call(&mut self, request: Request) -> Self::Future {
+        if request.uri().path().starts_with(WHITELISTED_PREFIX) {
+            return Box::pin(self.service.call(request));
+        }
+
         let auth_keys = self.auth_keys.clone();
         let mut service = self.service.clone();
         Box::pin(async move {
@@ -92,3 +120,13 @@ pub fn extract_access<R>(req: &mut tonic::Request<R>) -> Access {
         Access::full("All requests have full by default access when API key is not configured")
     })
 }
+
+/// Account upserted vectors in the rate limit of the API key of the request
+pub fn check_vectors_rate_limit<R>(req: &tonic::Request<R>, count: usize) -> Result<(), Status> {
+    match req.extensions().get::<Arc<RateLimiter>>() {
+        Some(rate_limiter) => rate_limiter
+            .check_vectors(count)
+            .map_err(rate_limited_to_status),
+        None => Ok(()),
+    }
+}
@@ -1,6 +1,9 @@
 mod api;
+mod audit;
 mod auth;
 mod logging;
+mod request_id;
+pub mod tls;
 mod tonic_telemetry;
 
 use std::io;
@@ -28,26 +31,30 @@ use ::api::grpc::qdrant::{
     WaitOnConsensusCommitRequest, WaitOnConsensusCommitResponse,
 };
 use ::api::grpc::QDRANT_DESCRIPTOR_SET;
+use futures::stream::{self, BoxStream};
+use futures::{future, Stream, StreamExt};
 use storage::content_manager::consensus_manager::ConsensusStateRef;
 use storage::content_manager::toc::TableOfContent;
 use storage::dispatcher::Dispatcher;
-use storage::rbac::Access;
 use tokio::runtime::Handle;
 use tokio::signal;
 use tonic::codec::CompressionEncoding;
-use tonic::transport::{Server, ServerTlsConfig};
+use tonic::transport::server::{Connected, TcpConnectInfo, TcpIncoming};
+use tonic::transport::Server;
 use tonic::{Request, Response, Status};
 
+use crate::common::audit::AuditLog;
 use crate::common::auth::AuthKeys;
 use crate::common::helpers;
 use crate::common::http_client::HttpClient;
 use crate::common::telemetry_ops::requests_telemetry::TonicTelemetryCollector;
-use crate::settings::Settings;
+use crate::settings::{IpFilterConfig, Settings};
 use crate::tonic::api::collections_api::CollectionsService;
 use crate::tonic::api::collections_internal_api::CollectionsInternalService;
 use crate::tonic::api::points_api::PointsService;
 use crate::tonic::api::points_internal_api::PointsInternalService;
 use crate::tonic::api::snapshots_api::{ShardSnapshotsService, SnapshotsService};
+use crate::tonic::tls::ReloadableTlsAcceptor;
 
 #[derive(Default)]
 pub struct QdrantService {}
@@ -62,22 +69,62 @@ impl Qdrant for QdrantService {
     }
 }
 
+/// Public gRPC services, advertised with reflection and reported by the health service
+const PUBLIC_SERVICES: &[&str] = &[
+    "qdrant.Collections",
+    "qdrant.Points",
+    "qdrant.Snapshots",
+    "qdrant.Qdrant",
+    "grpc.health.v1.Health",
+];
+
 // Additional health check service that follows gRPC health check protocol as described in #2614
 #[derive(Default)]
 pub struct HealthService {}
 
+impl HealthService {
+    /// Status of the service, empty name stands for the server as a whole
+    fn serving_status(service: &str) -> Option<ServingStatus> {
+        (service.is_empty() || PUBLIC_SERVICES.contains(&service)).then_some(ServingStatus::Serving)
+    }
+}
+
 #[tonic::async_trait]
 impl Health for HealthService {
     async fn check(
         &self,
-        _request: Request<ProtocolHealthCheckRequest>,
+        request: Request<ProtocolHealthCheckRequest>,
     ) -> Result<Response<ProtocolHealthCheckResponse>, Status> {
+        let service = request.into_inner().service;
+        let status = Self::serving_status(&service)
+            .ok_or_else(|| Status::not_found(format!("Unknown service {service}")))?;
+
         let response = ProtocolHealthCheckResponse {
-            status: ServingStatus::Serving as i32,
+            status: status as i32,
         };
 
         Ok(Response::new(response))
     }
+
+    type WatchStream = BoxStream<'static, Result<ProtocolHealthCheckResponse, Status>>;
+
+    async fn watch(
+        &self,
+        request: Request<ProtocolHealthCheckRequest>,
+    ) -> Result<Response<Self::WatchStream>, Status> {
+        let service = request.into_inner().service;
+        let status = Self::serving_status(&service).unwrap_or(ServingStatus::ServiceUnknown);
+
+        let response = ProtocolHealthCheckResponse {
+            status: status as i32,
+        };
+
+        // Status doesn't change while the server is running,
+        // so send it once and keep the stream open until the client disconnects
+        let stream = stream::once(async move { Ok(response) }).chain(stream::pending());
+
+        Ok(Response::new(stream.boxed()))
+    }
 }
 
 pub struct QdrantInternalService {
@@ -143,9 +190,36 @@ async fn wait_stop_signal(for_what: &str) {
     }
 }
 
+/// Close connections of clients, which are not allowed by the IP filter of the listener,
+/// right after they are accepted
+fn filter_incoming<IO>(
+    incoming: impl Stream<Item = io::Result<IO>>,
+    ip_filter: Option<IpFilterConfig>,
+    listener: &'static str,
+) -> impl Stream<Item = io::Result<IO>>
+where
+    IO: Connected<ConnectInfo = TcpConnectInfo>,
+{
+    incoming.filter(move |connection| {
+        let is_allowed = match (connection, &ip_filter) {
+            (Ok(connection), Some(ip_filter)) => match connection.connect_info().remote_addr() {
+                Some(addr) if ip_filter.is_allowed(addr.ip()) => true,
+                addr => {
+                    log::debug!("Rejected {listener} connection from {addr:?}");
+                    false
+                }
+            },
+            _ => true,
+        };
+        future::ready(is_allowed)
+    })
+}
+
 pub fn init(
     dispatcher: Arc<Dispatcher>,
     telemetry_collector: Arc<parking_lot::Mutex<TonicTelemetryCollector>>,
+    auth_keys: Option<AuthKeys>,
+    audit_log: Option<Arc<AuditLog>>,
     settings: Settings,
     grpc_port: u16,
     runtime: Handle,
@@ -162,16 +236,23 @@ pub fn init(
 
         // Only advertise the public services. By default, all services in QDRANT_DESCRIPTOR_SET
         // will be advertised, so explicitly list the services to be included.
-        let reflection_service = tonic_reflection::server::Builder::configure()
-            .register_encoded_file_descriptor_set(QDRANT_DESCRIPTOR_SET)
-            .with_service_name("qdrant.Collections")
-            .with_service_name("qdrant.Points")
-            .with_service_name("qdrant.Snapshots")
-            .with_service_name("qdrant.Qdrant")
-            .with_service_name("grpc.health.v1.Health")
+        let reflection_service = PUBLIC_SERVICES
+            .iter()
+            .fold(
+                tonic_reflection::server::Builder::configure()
+                    .register_encoded_file_descriptor_set(QDRANT_DESCRIPTOR_SET),
+                |builder, service| builder.with_service_name(*service),
+            )
             .build()
             .unwrap();
 
+        let incoming = filter_incoming(
+            TcpIncoming::new(socket, false, None)
+                .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?,
+            settings.service.ip_filter.grpc.clone(),
+            "gRPC",
+        );
+
         log::info!("Qdrant gRPC listening on {}", grpc_port);
 
         let mut server = Server::builder();
@@ -190,19 +271,13 @@ pub fn init(
 
         // The stack of middleware that our service will be wrapped in
         let middleware_layer = tower::ServiceBuilder::new()
+            .layer(request_id::RequestIdLayer::new())
             .layer(logging::LoggingMiddlewareLayer::new())
             .layer(tonic_telemetry::TonicTelemetryLayer::new(
                 telemetry_collector,
             ))
-            .option_layer({
-                AuthKeys::try_create(
-                    &settings.service,
-                    dispatcher
-                        .toc(&Access::full("For tonic auth middleware"))
-                        .clone(),
-                )
-                .map(auth::AuthLayer::new)
-            })
+            .option_layer(auth_keys.map(auth::AuthLayer::new))
+            .option_layer(audit_log.map(audit::AuditLayer::new))
             .into_inner();
 
         server
@@ -238,7 +313,7 @@ pub fn init(
                     .accept_compressed(CompressionEncoding::Gzip)
                     .max_decoding_message_size(usize::MAX),
             )
-            .serve_with_shutdown(socket, async {
+            .serve_with_incoming_shutdown(incoming, async {
                 wait_stop_signal("gRPC service").await;
             })
             .await
@@ -256,7 +331,7 @@ pub fn init_internal(
     settings: Settings,
     host: String,
     internal_grpc_port: u16,
-    tls_config: Option<ServerTlsConfig>,
+    tls_acceptor: Option<ReloadableTlsAcceptor>,
     to_consensus: tokio::sync::mpsc::Sender<crate::consensus::Message>,
     runtime: Handle,
 ) -> std::io::Result<()> {
@@ -266,89 +341,102 @@ pub fn init_internal(
 
     let http_client = HttpClient::from_settings(&settings)?;
 
-    runtime
-        .block_on(async {
-            let socket = SocketAddr::from((host.parse::<IpAddr>().unwrap(), internal_grpc_port));
-
-            let qdrant_service = QdrantService::default();
-            let qdrant_internal_service =
-                QdrantInternalService::new(settings, consensus_state.clone());
-            let collections_internal_service = CollectionsInternalService::new(toc.clone());
-            let points_internal_service = PointsInternalService::new(toc.clone());
-            let shard_snapshots_service = ShardSnapshotsService::new(toc.clone(), http_client);
-            let raft_service = RaftService::new(to_consensus, consensus_state);
-
-            log::debug!("Qdrant internal gRPC listening on {}", internal_grpc_port);
-
-            let mut server = Server::builder()
-                // Internally use a high limit for pending accept streams.
-                // We can have a huge number of reset/dropped HTTP2 streams in our internal
-                // communication when there are a lot of clients dropping connections. This
-                // internally causes an GOAWAY/ENHANCE_YOUR_CALM error breaking cluster consensus.
-                // We prefer to keep more pending reset streams even though this may be expensive,
-                // versus an internal error that is very hard to handle.
-                // More info: <https://github.com/qdrant/qdrant/issues/1907>
-                .http2_max_pending_accept_reset_streams(Some(1024));
-
-            if let Some(config) = tls_config {
-                log::info!("TLS enabled for internal gRPC API (TTL not supported)");
-
-                server = server.tls_config(config)?;
-            } else {
-                log::info!("TLS disabled for internal gRPC API");
-            };
-
-            // The stack of middleware that our service will be wrapped in
-            let middleware_layer = tower::ServiceBuilder::new()
-                .layer(logging::LoggingMiddlewareLayer::new())
-                .layer(tonic_telemetry::TonicTelemetryLayer::new(
-                    telemetry_collector,
-                ))
-                .into_inner();
-
-            server
-                .layer(middleware_layer)
-                .add_service(
-                    QdrantServer::new(qdrant_service)
-                        .send_compressed(CompressionEncoding::Gzip)
-                        .accept_compressed(CompressionEncoding::Gzip)
-                        .max_decoding_message_size(usize::MAX),
-                )
-                .add_service(
-                    QdrantInternalServer::new(qdrant_internal_service)
-                        .send_compressed(CompressionEncoding::Gzip)
-                        .accept_compressed(CompressionEncoding::Gzip)
-                        .max_decoding_message_size(usize::MAX),
-                )
-                .add_service(
-                    CollectionsInternalServer::new(collections_internal_service)
-                        .send_compressed(CompressionEncoding::Gzip)
-                        .accept_compressed(CompressionEncoding::Gzip)
-                        .max_decoding_message_size(usize::MAX),
-                )
-                .add_service(
-                    PointsInternalServer::new(points_internal_service)
-                        .send_compressed(CompressionEncoding::Gzip)
-                        .accept_compressed(CompressionEncoding::Gzip)
-                        .max_decoding_message_size(usize::MAX),
-                )
-                .add_service(
-                    ShardSnapshotsServer::new(shard_snapshots_service)
-                        .send_compressed(CompressionEncoding::Gzip)
-                        .accept_compressed(CompressionEncoding::Gzip)
-                        .max_decoding_message_size(usize::MAX),
-                )
-                .add_service(
-                    RaftServer::new(raft_service)
-                        .send_compressed(CompressionEncoding::Gzip)
-                        .accept_compressed(CompressionEncoding::Gzip)
-                        .max_decoding_message_size(usize::MAX),
-                )
-                .serve_with_shutdown(socket, async {
-                    wait_stop_signal("internal gRPC").await;
-                })
+    runtime.block_on(async {
+        let socket = SocketAddr::from((host.parse::<IpAddr>().unwrap(), internal_grpc_port));
+
+        let qdrant_service = QdrantService::default();
+        let qdrant_internal_service =
+            QdrantInternalService::new(settings.clone(), consensus_state.clone());
+        let collections_internal_service = CollectionsInternalService::new(toc.clone());
+        let points_internal_service = PointsInternalService::new(toc.clone());
+        let shard_snapshots_service = ShardSnapshotsService::new(toc.clone(), http_client);
+        let raft_service = RaftService::new(to_consensus, consensus_state);
+
+        let incoming = filter_incoming(
+            TcpIncoming::new(socket, false, None)
+                .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?,
+            settings.service.ip_filter.internal.clone(),
+            "internal gRPC",
+        );
+
+        log::debug!("Qdrant internal gRPC listening on {}", internal_grpc_port);
+
+        let server = Server::builder()
+            // Internally use a high limit for pending accept streams.
+            // We can have a huge number of reset/dropped HTTP2 streams in our internal
+            // communication when there are a lot of clients dropping connections. This
+            // internally causes an GOAWAY/ENHANCE_YOUR_CALM error breaking cluster consensus.
+            // We prefer to keep more pending reset streams even though this may be expensive,
+            // versus an internal error that is very hard to handle.
+            // More info: <https://github.com/qdrant/qdrant/issues/1907>
+            .http2_max_pending_accept_reset_streams(Some(1024));
+
+        // The stack of middleware that our service will be wrapped in
+        let middleware_layer = tower::ServiceBuilder::new()
+            .layer(request_id::RequestIdLayer::new())
+            .layer(logging::LoggingMiddlewareLayer::new())
+            .layer(tonic_telemetry::TonicTelemetryLayer::new(
+                telemetry_collector,
+            ))
+            .into_inner();
+
+        let router = server
+            .layer(middleware_layer)
+            .add_service(
+                QdrantServer::new(qdrant_service)
+                    .send_compressed(CompressionEncoding::Gzip)
+                    .accept_compressed(CompressionEncoding::Gzip)
+                    .max_decoding_message_size(usize::MAX),
+            )
+            .add_service(
+                QdrantInternalServer::new(qdrant_internal_service)
+                    .send_compressed(CompressionEncoding::Gzip)
+                    .accept_compressed(CompressionEncoding::Gzip)
+                    .max_decoding_message_size(usize::MAX),
+            )
+            .add_service(
+                CollectionsInternalServer::new(collections_internal_service)
+                    .send_compressed(CompressionEncoding::Gzip)
+                    .accept_compressed(CompressionEncoding::Gzip)
+                    .max_decoding_message_size(usize::MAX),
+            )
+            .add_service(
+                PointsInternalServer::new(points_internal_service)
+                    .send_compressed(CompressionEncoding::Gzip)
+                    .accept_compressed(CompressionEncoding::Gzip)
+                    .max_decoding_message_size(usize::MAX),
+            )
+            .add_service(
+                ShardSnapshotsServer::new(shard_snapshots_service)
+                    .send_compressed(CompressionEncoding::Gzip)
+                    .accept_compressed(CompressionEncoding::Gzip)
+                    .max_decoding_message_size(usize::MAX),
+            )
+            .add_service(
+                RaftServer::new(raft_service)
+                    .send_compressed(CompressionEncoding::Gzip)
+                    .accept_compressed(CompressionEncoding::Gzip)
+                    .max_decoding_message_size(usize::MAX),
+            );
+
+        let shutdown_signal = wait_stop_signal("internal gRPC");
+
+        // Certificates are rotated by the acceptor without restarting the server
+        let served = if let Some(tls_acceptor) = tls_acceptor {
+            log::info!("TLS enabled for internal gRPC API");
+
+            tokio::spawn(tls_acceptor.clone().reload_forever());
+            router
+                .serve_with_incoming_shutdown(tls_acceptor.accept(incoming), shutdown_signal)
                 .await
-        })
-        .unwrap();
-    Ok(())
+        } else {
+            log::info!("TLS disabled for internal gRPC API");
+
+            router
+                .serve_with_incoming_shutdown(incoming, shutdown_signal)
+                .await
+        };
+
+        served.map_err(helpers::tonic_error_to_io_error)
+    })
 }
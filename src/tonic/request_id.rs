@@ -0,0 +1,81 @@
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use common::request_id::{self, REQUEST_ID_HEADER};
+use futures_util::future::BoxFuture;
+use tonic::body::BoxBody;
+use tonic::codegen::http::header::{HeaderName, HeaderValue};
+use tonic::codegen::http::Response;
+use tower::Service;
+use tower_layer::Layer;
+use tracing::Instrument as _;
+
+/// Accepts the request ID from the `x-request-id` header or generates one, attaches it to logs
+/// and internal gRPC calls of the request, and returns it in the response.
+#[derive(Clone)]
+pub struct RequestIdMiddleware<T> {
+    inner: T,
+}
+
+#[derive(Clone)]
+pub struct RequestIdLayer;
+
+impl RequestIdLayer {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl<S> Service<tonic::codegen::http::Request<tonic::transport::Body>> for RequestIdMiddleware<S>
+where
+    S: Service<tonic::codegen::http::Request<tonic::transport::Body>, Response = Response<BoxBody>>
+        + Clone,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<S::Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(
+        &mut self,
+        request: tonic::codegen::http::Request<tonic::transport::Body>,
+    ) -> Self::Future {
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+
+        let request_id: Arc<str> = request
+            .headers()
+            .get(REQUEST_ID_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .filter(|value| request_id::is_valid(value))
+            .map_or_else(|| uuid::Uuid::new_v4().to_string().into(), Into::into);
+
+        let span = tracing::info_span!("request", request_id = %request_id);
+        let future = span.in_scope(|| inner.call(request));
+
+        Box::pin(
+            async move {
+                let mut response = request_id::scope(Some(request_id.clone()), future).await?;
+                if let Ok(value) = HeaderValue::from_str(&request_id) {
+                    response
+                        .headers_mut()
+                        .insert(HeaderName::from_static(REQUEST_ID_HEADER), value);
+                }
+                Ok(response)
+            }
+            .instrument(span),
+        )
+    }
+}
+
+impl<S> Layer<S> for RequestIdLayer {
+    type Service = RequestIdMiddleware<S>;
+
+    fn layer(&self, service: S) -> Self::Service {
+        RequestIdMiddleware { inner: service }
+    }
+}
@@ -0,0 +1,152 @@
+use std::io;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use futures::{future, Stream, StreamExt};
+use parking_lot::RwLock;
+use rustls::ServerConfig;
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio_rustls::server::TlsStream;
+use tokio_rustls::TlsAcceptor;
+use tonic::transport::server::Connected;
+
+use crate::common::certificate_helpers::internal_tls_server_config;
+use crate::common::helpers::wait_tls_files_changed;
+use crate::settings::TlsConfig;
+
+/// Maximum number of TLS handshakes performed concurrently
+const MAX_CONCURRENT_HANDSHAKES: usize = 128;
+
+/// Time for a client to complete the TLS handshake
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// TLS acceptor for internal gRPC, which server configuration is replaced on certificate rotation
+///
+/// New connections are accepted with the current configuration, so the listener keeps running
+/// and open connections are not interrupted when certificates change on disk.
+#[derive(Clone)]
+pub struct ReloadableTlsAcceptor {
+    tls_config: TlsConfig,
+    server_config: Arc<RwLock<Arc<ServerConfig>>>,
+}
+
+impl ReloadableTlsAcceptor {
+    pub fn load(tls_config: TlsConfig) -> io::Result<Self> {
+        let server_config = load_server_config(&tls_config)?;
+        Ok(Self {
+            tls_config,
+            server_config: Arc::new(RwLock::new(server_config)),
+        })
+    }
+
+    /// Replace server configuration, when the certificates are rotated on disk
+    ///
+    /// If rotated certificates can't be loaded, an error is logged and the current ones are kept.
+    pub async fn reload_forever(self) {
+        loop {
+            wait_tls_files_changed(&self.tls_config).await;
+            match load_server_config(&self.tls_config) {
+                Ok(server_config) => {
+                    log::info!("TLS certificates are changed, accepting connections with new ones");
+                    *self.server_config.write() = server_config;
+                }
+                Err(err) => {
+                    log::error!("Failed to load rotated TLS certificates, keeping current: {err}");
+                }
+            }
+        }
+    }
+
+    /// Perform TLS handshake on incoming connections
+    ///
+    /// Connections, which fail the handshake, are dropped.
+    pub fn accept<IO>(
+        &self,
+        incoming: impl Stream<Item = io::Result<IO>>,
+    ) -> impl Stream<Item = io::Result<TlsIo<IO>>>
+    where
+        IO: AsyncRead + AsyncWrite + Unpin,
+    {
+        let server_config = self.server_config.clone();
+        incoming
+            .map(move |connection| {
+                let acceptor = TlsAcceptor::from(server_config.read().clone());
+                async move {
+                    let stream =
+                        tokio::time::timeout(HANDSHAKE_TIMEOUT, acceptor.accept(connection?))
+                            .await
+                            .map_err(|_| {
+                                io::Error::new(io::ErrorKind::TimedOut, "TLS handshake timed out")
+                            })??;
+                    Ok(TlsIo(stream))
+                }
+            })
+            .buffer_unordered(MAX_CONCURRENT_HANDSHAKES)
+            .filter(|connection| {
+                if let Err(err) = connection {
+                    log::debug!("Failed to accept internal gRPC connection: {err}");
+                }
+                future::ready(connection.is_ok())
+            })
+    }
+}
+
+fn load_server_config(tls_config: &TlsConfig) -> io::Result<Arc<ServerConfig>> {
+    internal_tls_server_config(tls_config)
+        .map(Arc::new)
+        .map_err(|err| io::Error::new(io::ErrorKind::Other, err))
+}
+
+/// TLS connection, which provides connection info of the underlying transport
+pub struct TlsIo<IO>(TlsStream<IO>);
+
+impl<IO: Connected> Connected for TlsIo<IO> {
+    type ConnectInfo = IO::ConnectInfo;
+
+    fn connect_info(&self) -> Self::ConnectInfo {
+        let (io, _) = self.0.get_ref();
+        io.connect_info()
+    }
+}
+
+impl<IO: AsyncRead + AsyncWrite + Unpin> AsyncRead for TlsIo<IO> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_read(cx, buf)
+    }
+}
+
+impl<IO: AsyncRead + AsyncWrite + Unpin> AsyncWrite for TlsIo<IO> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.0).poll_write(cx, buf)
+    }
+
+    fn poll_write_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.0).poll_write_vectored(cx, bufs)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.0.is_write_vectored()
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_shutdown(cx)
+    }
+}
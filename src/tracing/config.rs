@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 
 use serde::{Deserialize, Serialize};
 use tracing_subscriber::fmt;
@@ -12,6 +12,10 @@ pub struct LoggerConfig {
     pub default: default::Config,
     #[serde(default)]
     pub on_disk: on_disk::Config,
+    /// Log levels of modules, e.g. `collection::shards: debug`.
+    /// Take priority over log levels of both loggers.
+    #[serde(default)]
+    pub module_levels: BTreeMap<String, String>,
 }
 
 impl LoggerConfig {
@@ -32,9 +36,35 @@ impl LoggerConfig {
     pub fn merge(&mut self, other: Self) {
         self.default.merge(other.default);
         self.on_disk.merge(other.on_disk);
+        self.module_levels.extend(other.module_levels);
+    }
+
+    pub fn validate_module_levels(&self) -> anyhow::Result<()> {
+        for (module, level) in &self.module_levels {
+            let is_valid_module = !module.is_empty()
+                && module
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == ':' || c == '-');
+            if !is_valid_module {
+                anyhow::bail!("invalid module name {module:?}");
+            }
+            if level.parse::<log::LevelFilter>().is_err() {
+                anyhow::bail!("invalid log level {level:?} of module {module}");
+            }
+        }
+        Ok(())
     }
 }
 
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    #[default]
+    Text,
+    /// One JSON object per line, with collection, shard, peer and request ID as top-level fields
+    Json,
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize, Serialize, Hash)]
 #[serde(rename_all = "lowercase")]
 pub enum SpanEvent {
@@ -12,6 +12,7 @@ pub struct Config {
     pub log_level: Option<String>,
     pub span_events: Option<HashSet<config::SpanEvent>>,
     pub color: Option<config::Color>,
+    pub format: Option<config::LogFormat>,
 }
 
 impl Config {
@@ -19,36 +20,48 @@ impl Config {
         self.log_level = other.log_level.or(self.log_level.take());
         self.span_events = other.span_events.or(self.span_events.take());
         self.color = other.color.or(self.color.take());
+        self.format = other.format.or(self.format.take());
     }
 }
 
 #[rustfmt::skip] // `rustfmt` formats this into unreadable single line
 pub type Logger<S> = filter::Filtered<
-    Option<fmt::Layer<S>>,
+    Option<Layer<S>>,
     filter::EnvFilter,
     S,
 >;
 
-pub fn new_logger<S>(config: &Config) -> Logger<S>
+pub type Layer<S> = Box<dyn tracing_subscriber::Layer<S> + Send + Sync>;
+
+pub fn new_logger<S>(config: &Config, module_levels: &ModuleLevels) -> Logger<S>
 where
-    S: tracing::Subscriber + for<'span> registry::LookupSpan<'span>,
+    S: tracing::Subscriber + for<'span> registry::LookupSpan<'span> + 'static,
 {
     let layer = new_layer(config);
-    let filter = new_filter(config);
+    let filter = new_filter(config, module_levels);
     Some(layer).with_filter(filter)
 }
 
-pub fn new_layer<S>(config: &Config) -> fmt::Layer<S>
+pub fn new_layer<S>(config: &Config) -> Layer<S>
 where
-    S: tracing::Subscriber + for<'span> registry::LookupSpan<'span>,
+    S: tracing::Subscriber + for<'span> registry::LookupSpan<'span> + 'static,
 {
-    fmt::Layer::default()
-        .with_span_events(config::SpanEvent::unwrap_or_default_config(
-            &config.span_events,
-        ))
-        .with_ansi(config.color.unwrap_or_default().to_bool())
+    let layer = fmt::Layer::default().with_span_events(
+        config::SpanEvent::unwrap_or_default_config(&config.span_events),
+    );
+
+    match config.format.unwrap_or_default() {
+        config::LogFormat::Text => layer
+            .with_ansi(config.color.unwrap_or_default().to_bool())
+            .boxed(),
+        config::LogFormat::Json => layer
+            .with_ansi(false)
+            .fmt_fields(json::JsonFields)
+            .event_format(json::JsonFormat)
+            .boxed(),
+    }
 }
 
-pub fn new_filter(config: &Config) -> filter::EnvFilter {
-    filter(config.log_level.as_deref().unwrap_or(""))
+pub fn new_filter(config: &Config, module_levels: &ModuleLevels) -> filter::EnvFilter {
+    filter(config.log_level.as_deref().unwrap_or(""), module_levels)
 }
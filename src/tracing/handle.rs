@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use tokio::sync::RwLock;
@@ -50,11 +51,38 @@ impl LoggerHandle {
     pub async fn update_config(&self, new_config: config::LoggerConfig) -> anyhow::Result<()> {
         let mut config = self.config.write().await;
 
+        let mut merged_config = config.clone();
+        merged_config.merge(new_config);
+        self.apply(&mut config, merged_config)
+    }
+
+    /// Set log levels of modules, or reset them to log levels of loggers if `None`
+    pub async fn update_module_levels(
+        &self,
+        module_levels: BTreeMap<String, Option<String>>,
+    ) -> anyhow::Result<()> {
+        let mut config = self.config.write().await;
+
+        let mut merged_config = config.clone();
+        for (module, level) in module_levels {
+            match level {
+                Some(level) => merged_config.module_levels.insert(module, level),
+                None => merged_config.module_levels.remove(&module),
+            };
+        }
+        self.apply(&mut config, merged_config)
+    }
+
+    fn apply(
+        &self,
+        current: &mut config::LoggerConfig,
+        merged_config: config::LoggerConfig,
+    ) -> anyhow::Result<()> {
         // `tracing-subscriber` does not support `reload`ing `Filtered` layers, so we *have to* use
         // `modify`. However, `modify` would *deadlock* if provided closure logs anything or produce
         // any `tracing` event.
         //
-        // So, we structure `update_config` to only do an absolute minimum of changes and only use
+        // So, we structure `apply` to only do an absolute minimum of changes and only use
         // the most trivial operations during `modify`, to guarantee we won't deadlock.
         //
         // See:
@@ -62,33 +90,46 @@ impl LoggerHandle {
         // - https://github.com/tokio-rs/tracing/issues/1629
         // - https://github.com/tokio-rs/tracing/pull/2657
 
-        let mut merged_config = config.clone();
-        merged_config.merge(new_config);
+        merged_config.validate_module_levels()?;
+        let module_levels_changed = merged_config.module_levels != current.module_levels;
 
-        if merged_config.on_disk != config.on_disk {
+        if merged_config.on_disk != current.on_disk {
             let new_layer = on_disk::new_layer(&merged_config.on_disk)?;
-            let new_filter = on_disk::new_filter(&merged_config.on_disk);
+            let new_filter =
+                on_disk::new_filter(&merged_config.on_disk, &merged_config.module_levels);
 
             self.on_disk.modify(move |logger| {
                 *logger.inner_mut() = new_layer;
                 *logger.filter_mut() = new_filter;
             })?;
+        } else if module_levels_changed {
+            let new_filter =
+                on_disk::new_filter(&merged_config.on_disk, &merged_config.module_levels);
 
-            config.on_disk = merged_config.on_disk;
+            self.on_disk.modify(move |logger| {
+                *logger.filter_mut() = new_filter;
+            })?;
         }
 
-        if merged_config.default != config.default {
+        if merged_config.default != current.default {
             let new_layer = default::new_layer(&merged_config.default);
-            let new_filter = default::new_filter(&merged_config.default);
+            let new_filter =
+                default::new_filter(&merged_config.default, &merged_config.module_levels);
 
             self.default.modify(move |logger| {
                 *logger.inner_mut() = Some(new_layer);
                 *logger.filter_mut() = new_filter;
             })?;
+        } else if module_levels_changed {
+            let new_filter =
+                default::new_filter(&merged_config.default, &merged_config.module_levels);
 
-            config.default = merged_config.default;
+            self.default.modify(move |logger| {
+                *logger.filter_mut() = new_filter;
+            })?;
         }
 
+        *current = merged_config;
         Ok(())
     }
 }
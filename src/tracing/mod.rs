@@ -3,11 +3,13 @@
 pub mod config;
 pub mod default;
 pub mod handle;
+pub mod json;
 pub mod on_disk;
 
 #[cfg(test)]
 mod test;
 
+use std::collections::BTreeMap;
 use std::fmt::Write as _;
 use std::str::FromStr as _;
 
@@ -16,6 +18,10 @@ use tracing_subscriber::{filter, reload};
 
 pub use self::config::LoggerConfig;
 pub use self::handle::LoggerHandle;
+pub use self::json::set_peer_id;
+
+/// Log levels by module, see [`LoggerConfig::module_levels`]
+pub type ModuleLevels = BTreeMap<String, String>;
 
 const DEFAULT_LOG_LEVEL: log::LevelFilter = log::LevelFilter::Info;
 
@@ -42,11 +48,13 @@ pub fn setup(mut config: config::LoggerConfig) -> anyhow::Result<LoggerHandle> {
     // And default logger outputs colored log-lines, which on-disk logger reuse even if colors are
     // disabled for the on-disk logger. :/
 
-    let on_disk_logger = on_disk::new_logger(&mut config.on_disk);
+    config.validate_module_levels()?;
+
+    let on_disk_logger = on_disk::new_logger(&mut config.on_disk, &config.module_levels);
     let (on_disk_logger, on_disk_logger_handle) = reload::Layer::new(on_disk_logger);
     let reg = tracing_subscriber::registry().with(on_disk_logger);
 
-    let default_logger = default::new_logger(&config.default);
+    let default_logger = default::new_logger(&config.default, &config.module_levels);
     let (default_logger, default_logger_handle) = reload::Layer::new(default_logger);
     let reg = reg.with(default_logger);
 
@@ -83,7 +91,7 @@ pub fn setup(mut config: config::LoggerConfig) -> anyhow::Result<LoggerHandle> {
     Ok(logger_handle)
 }
 
-fn filter(user_filters: &str) -> filter::EnvFilter {
+fn filter(user_filters: &str, module_levels: &ModuleLevels) -> filter::EnvFilter {
     let mut filter = String::new();
 
     let user_log_level = user_filters
@@ -104,6 +112,11 @@ fn filter(user_filters: &str) -> filter::EnvFilter {
     let comma = if filter.is_empty() { "" } else { "," };
     write!(&mut filter, "{comma}{user_filters}").unwrap(); // Writing into `String` never fails
 
+    // Later directives for the same module take priority
+    for (module, log_level) in module_levels {
+        write!(&mut filter, ",{module}={log_level}").unwrap(); // Writing into `String` never fails
+    }
+
     filter::EnvFilter::builder()
         .with_regex(false)
         .parse_lossy(filter)
@@ -16,6 +16,7 @@ pub struct Config {
     pub log_file: Option<String>,
     pub log_level: Option<String>,
     pub span_events: Option<HashSet<config::SpanEvent>>,
+    pub format: Option<config::LogFormat>,
 }
 
 impl Config {
@@ -24,6 +25,7 @@ impl Config {
         self.log_file = other.log_file.or(self.log_file.take());
         self.log_level = other.log_level.or(self.log_level.take());
         self.span_events = other.span_events.or(self.span_events.take());
+        self.format = other.format.or(self.format.take());
     }
 }
 
@@ -34,19 +36,13 @@ pub type Logger<S> = filter::Filtered<
     S,
 >;
 
-#[rustfmt::skip] // `rustfmt` formats this into unreadable single line :/
-pub type Layer<S> = fmt::Layer<
-    S,
-    fmt::format::DefaultFields,
-    fmt::format::Format,
-    MakeWriter,
->;
+pub type Layer<S> = Box<dyn tracing_subscriber::Layer<S> + Send + Sync>;
 
 pub type MakeWriter = Mutex<io::BufWriter<fs::File>>;
 
-pub fn new_logger<S>(config: &mut Config) -> Logger<S>
+pub fn new_logger<S>(config: &mut Config, module_levels: &ModuleLevels) -> Logger<S>
 where
-    S: tracing::Subscriber + for<'span> registry::LookupSpan<'span>,
+    S: tracing::Subscriber + for<'span> registry::LookupSpan<'span> + 'static,
 {
     let layer = match new_layer(config) {
         Ok(layer) => layer,
@@ -61,13 +57,13 @@ where
         }
     };
 
-    let filter = new_filter(config);
+    let filter = new_filter(config, module_levels);
     layer.with_filter(filter)
 }
 
 pub fn new_layer<S>(config: &Config) -> anyhow::Result<Option<Layer<S>>>
 where
-    S: tracing::Subscriber + for<'span> registry::LookupSpan<'span>,
+    S: tracing::Subscriber + for<'span> registry::LookupSpan<'span> + 'static,
 {
     if !config.enabled.unwrap_or_default() {
         return Ok(None);
@@ -90,9 +86,17 @@ where
         ))
         .with_ansi(false);
 
+    let layer = match config.format.unwrap_or_default() {
+        config::LogFormat::Text => layer.boxed(),
+        config::LogFormat::Json => layer
+            .fmt_fields(json::JsonFields)
+            .event_format(json::JsonFormat)
+            .boxed(),
+    };
+
     Ok(Some(layer))
 }
 
-pub fn new_filter(config: &Config) -> filter::EnvFilter {
-    filter(config.log_level.as_deref().unwrap_or(""))
+pub fn new_filter(config: &Config, module_levels: &ModuleLevels) -> filter::EnvFilter {
+    filter(config.log_level.as_deref().unwrap_or(""), module_levels)
 }
@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 
 use serde_json::json;
 
@@ -10,12 +10,18 @@ fn deseriailze_logger_config() {
         "log_level": "debug",
         "span_events": ["new", "close"],
         "color": true,
+        "format": "json",
 
         "on_disk": {
             "enabled": true,
             "log_file": "/logs/qdrant",
             "log_level": "tracing",
             "span_events": ["new", "close"],
+            "format": "text",
+        },
+
+        "module_levels": {
+            "collection::shards": "trace",
         }
     });
 
@@ -29,6 +35,7 @@ fn deseriailze_logger_config() {
                 config::SpanEvent::Close,
             ])),
             color: Some(config::Color::Explicit(true)),
+            format: Some(config::LogFormat::Json),
         },
 
         on_disk: on_disk::Config {
@@ -39,7 +46,10 @@ fn deseriailze_logger_config() {
                 config::SpanEvent::New,
                 config::SpanEvent::Close,
             ])),
+            format: Some(config::LogFormat::Text),
         },
+
+        module_levels: BTreeMap::from([("collection::shards".into(), "trace".into())]),
     };
 
     assert_eq!(config, expected);
@@ -63,12 +73,14 @@ fn deseriailze_config_with_explicit_nulls() {
         "log_level": null,
         "span_events": null,
         "color": null,
+        "format": null,
 
         "on_disk": {
             "enabled": null,
             "log_file": null,
             "log_level": null,
             "span_events": null,
+            "format": null,
         }
     });
 
@@ -76,6 +88,23 @@ fn deseriailze_config_with_explicit_nulls() {
     assert_eq!(config, LoggerConfig::default());
 }
 
+#[test]
+fn validate_module_levels() {
+    let mut config = LoggerConfig::default();
+    config
+        .module_levels
+        .insert("collection::shards".into(), "debug".into());
+    assert!(config.validate_module_levels().is_ok());
+
+    config
+        .module_levels
+        .insert("collection::shards".into(), "verbose".into());
+    assert!(config.validate_module_levels().is_err());
+
+    config.module_levels = BTreeMap::from([("raft=debug,wal".into(), "debug".into())]);
+    assert!(config.validate_module_levels().is_err());
+}
+
 fn deserialize_config(json: serde_json::Value) -> LoggerConfig {
     serde_json::from_value(json).unwrap()
 }
@@ -1,6 +1,7 @@
 use std::env;
 use std::path::Path;
 
+use collection::common::wal_encryption::WalCipher;
 use collection::operations::OperationWithClockTag;
 use collection::wal::SerdeWal;
 use storage::content_manager::consensus::consensus_wal::ConsensusOpWal;
@@ -11,6 +12,8 @@ use wal::WalOptions;
 /// e.g:
 /// `cargo run --bin wal_inspector storage/collections/test-collection/0/wal/ collection`
 /// `cargo run --bin wal_inspector -- storage/node4/wal/ consensus` (expects `collections_meta_wal` folder as first child)
+///
+/// Encrypted collection WAL can be read by providing the hex-encoded key in `WAL_ENCRYPTION_KEY` env variable.
 fn main() {
     let args: Vec<String> = env::args().collect();
     let wal_path = Path::new(&args[1]);
@@ -53,8 +56,18 @@ fn print_consensus_wal(wal_path: &Path) {
 }
 
 fn print_collection_wal(wal_path: &Path) {
+    let cipher = match env::var("WAL_ENCRYPTION_KEY").map(|key| WalCipher::from_hex(&key)) {
+        Err(_) => None,
+        Ok(Ok(cipher)) => Some(cipher.into()),
+        Ok(Err(error)) => {
+            eprintln!("Invalid WAL encryption key: {error}");
+            return;
+        }
+    };
+
     let wal: Result<SerdeWal<OperationWithClockTag>, _> =
-        SerdeWal::new(wal_path.to_str().unwrap(), WalOptions::default());
+        SerdeWal::new(wal_path.to_str().unwrap(), WalOptions::default())
+            .map(|wal| wal.with_cipher(cipher));
 
     match wal {
         Err(error) => {
@@ -63,7 +76,14 @@ fn print_collection_wal(wal_path: &Path) {
         Ok(wal) => {
             // print all entries
             let mut count = 0;
-            for (idx, op) in wal.read_all(false) {
+            for record in wal.read_all(false) {
+                let (idx, op) = match record {
+                    Ok(record) => record,
+                    Err(error) => {
+                        eprintln!("Unable to read write ahead log entry: {error}");
+                        break;
+                    }
+                };
                 println!("==========================");
                 println!("Entry: {idx}");
                 println!("Operation: {:?}", op.operation);
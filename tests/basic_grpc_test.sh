@@ -50,6 +50,12 @@ $docker_grpcurl -d '{
   ]
 }' $QDRANT_HOST qdrant.Points/Upsert
 
+# Stream of two batches, applied one after another
+$docker_grpcurl -d '
+{"collection_name": "test_collection", "wait": true, "points": [{"id": { "num": 10 }, "vectors": {"vector": {"data": [0.1, 0.2, 0.3, 0.4]}}}]}
+{"collection_name": "test_collection", "wait": true, "points": [{"id": { "num": 11 }, "vectors": {"vector": {"data": [0.4, 0.3, 0.2, 0.1]}}}]}
+' $QDRANT_HOST qdrant.Points/UpsertStream
+
 $docker_grpcurl -d '{ "collection_name": "test_collection" }' $QDRANT_HOST qdrant.Collections/Get
 
 $docker_grpcurl -d '{
@@ -201,6 +207,18 @@ $docker_grpcurl $QDRANT_HOST describe qdrant.UpsertPoints
 
 # grpc protocol compliant health check
 $docker_grpcurl $QDRANT_HOST grpc.health.v1.Health/Check
+$docker_grpcurl -d '{"service": "qdrant.Points"}' $QDRANT_HOST grpc.health.v1.Health/Check
+
+# health check of an unknown service is an error
+set +e
+if $docker_grpcurl -d '{"service": "unknown.Service"}' $QDRANT_HOST grpc.health.v1.Health/Check; then
+    echo Unexpected response, expected unknown service to be not found
+    exit 1
+fi
+set -e
+
+# watch sends current status immediately and keeps the stream open
+$docker_grpcurl -max-time 1 $QDRANT_HOST grpc.health.v1.Health/Watch | grep -q SERVING
 
 #SAVED_POINTS_COUNT=$(curl --fail -s "http://$QDRANT_HOST/collections/test_collection" | jq '.result.points_count')
 #[[ "$SAVED_POINTS_COUNT" == "6" ]] || {
@@ -266,34 +266,31 @@ ACTION_ACCESS = {
     ### Collection Snapshots ###
     "list_collection_snapshots": EndpointAccess(
         True,
-        True,
+        False,
         True,
         "GET /collections/{collection_name}/snapshots",
         "qdrant.Snapshots/List",
-        coll_rw_payload=False,
+        coll_r=False,
     ),
     "create_collection_snapshot": EndpointAccess(
         False,
-        True,
+        False,
         True,
         "POST /collections/{collection_name}/snapshots",
         "qdrant.Snapshots/Create",
-        coll_rw_payload=False,
     ),
     "delete_collection_snapshot": EndpointAccess(
         False,
-        True,
+        False,
         True,
         "DELETE /collections/{collection_name}/snapshots/{snapshot_name}",
         "qdrant.Snapshots/Delete",
-        coll_rw_payload=False,
     ),
     "download_collection_snapshot": EndpointAccess(
-        True,
-        True,
+        False,
+        False,
         True,
         "GET /collections/{collection_name}/snapshots/{snapshot_name}",
-        coll_rw_payload=False,
     ),
     "upload_collection_snapshot": EndpointAccess(
         False, False, True, "POST /collections/{collection_name}/snapshots/upload"
@@ -319,31 +316,28 @@ ACTION_ACCESS = {
     ),
     "create_shard_snapshot": EndpointAccess(
         False,
-        True,
+        False,
         True,
         "POST /collections/{collection_name}/shards/{shard_id}/snapshots",
-        coll_rw_payload=False,
     ),
     "list_shard_snapshots": EndpointAccess(
         True,
-        True,
+        False,
         True,
         "GET /collections/{collection_name}/shards/{shard_id}/snapshots",
-        coll_rw_payload=False,
+        coll_r=False,
     ),
     "delete_shard_snapshot": EndpointAccess(
         False,
-        True,
+        False,
         True,
         "DELETE /collections/{collection_name}/shards/{shard_id}/snapshots/{snapshot_name}",
-        coll_rw_payload=False,
     ),
     "download_shard_snapshot": EndpointAccess(
-        True,
-        True,
+        False,
+        False,
         True,
         "GET /collections/{collection_name}/shards/{shard_id}/snapshots/{snapshot_name}",
-        coll_rw_payload=False,
     ),
     ### Full Snapshots ###
     "list_full_snapshots": EndpointAccess(
@@ -361,7 +355,7 @@ ACTION_ACCESS = {
         False, False, True, "DELETE /snapshots/{snapshot_name}", "qdrant.Snapshots/DeleteFull"
     ),
     "download_full_snapshot": EndpointAccess(
-        True, False, True, "GET /snapshots/{snapshot_name}", coll_r=False
+        False, False, True, "GET /snapshots/{snapshot_name}"
     ),
     ### Cluster ###
     "get_cluster": EndpointAccess(True, False, True, "GET /cluster", coll_r=False),
@@ -399,6 +393,20 @@ ACTION_ACCESS = {
         "qdrant.Points/UpdateBatch",
         coll_rw_payload=False,
     ),
+    "upsert_points_stream": EndpointAccess(
+        False,
+        True,
+        True,
+        "PUT /collections/{collection_name}/points",
+        "qdrant.Points/UpsertStream",
+        coll_rw_payload=False,
+    ),
+    "import_points": EndpointAccess(
+        False,
+        False,
+        True,
+        "POST /collections/{collection_name}/points/import",
+    ),
     "delete_points": EndpointAccess(
         False,
         True,
@@ -524,6 +532,12 @@ ACTION_ACCESS = {
     "count_points": EndpointAccess(
         True, True, True, "POST /collections/{collection_name}/points/count", "qdrant.Points/Count"
     ),
+    "read_batch_points": EndpointAccess(
+        True, True, True, "POST /collections/{collection_name}/points/read/batch"
+    ),
+    "export_points": EndpointAccess(
+        True, True, True, "POST /collections/{collection_name}/points/export"
+    ),
     ### Service ###
     "root": EndpointAccess(True, True, True, "GET /", "qdrant.Qdrant/HealthCheck"),
     "readyz": EndpointAccess(True, True, True, "GET /readyz", "grpc.health.v1.Health/Check"),
@@ -1402,6 +1416,29 @@ def test_upsert_points():
     )
 
 
+def test_upsert_points_stream():
+    check_access(
+        "upsert_points_stream",
+        rest_request={"points": [{"id": 1, "vector": [1, 2, 3, 4]}], "shard_key": SHARD_KEY},
+        path_params={"collection_name": COLL_NAME},
+        grpc_request=[
+            {
+                "collection_name": COLL_NAME,
+                "points": [{"id": {"num": 1}, "vectors": {"vector": {"data": [1, 2, 3, 4]}}}],
+                "shard_key_selector": {"shard_keys": [{"keyword": SHARD_KEY}]},
+            }
+        ],
+    )
+
+
+def test_import_points():
+    check_access(
+        "import_points",
+        rest_request={"location": "file:///nonexistent.parquet"},
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
 def test_update_points_batch():
     rest_operations = [
         {"upsert": {"shard_key": SHARD_KEY, "points": [{"id": 1, "vector": [1, 2, 3, 4]}]}},
@@ -1706,6 +1743,28 @@ def test_count_points():
     )
 
 
+def test_read_batch_points():
+    check_access(
+        "read_batch_points",
+        rest_request={
+            "operations": [
+                {"search": {"vector": [1, 2, 3, 4], "limit": 10}},
+                {"recommend": {"positive": [1], "limit": 10}},
+                {"count": {}},
+            ]
+        },
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
+def test_export_points():
+    check_access(
+        "export_points",
+        rest_request={"format": "jsonl"},
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
 def test_root():
     check_access("root")
 
@@ -0,0 +1,74 @@
+import pathlib
+
+from .fixtures import count_counts, create_collection, upsert_random_points
+from .utils import *
+
+N_PEERS = 2
+N_SHARDS = 2
+N_POINTS = 100
+COLLECTION_NAME = "test_collection"
+
+
+def start_remote_cluster(tmp_path: pathlib.Path):
+    remote_path = tmp_path / "remote"
+    remote_path.mkdir()
+    peer_dir = make_peer_folder(remote_path, 0)
+    remote_api_uri, _ = start_first_peer(peer_dir, "remote_peer_0.log")
+    wait_peer_added(remote_api_uri)
+    return remote_api_uri, processes[-1].grpc_port
+
+
+def get_async_replication_status(peer_url):
+    r = requests.get(f"{peer_url}/collections/{COLLECTION_NAME}/async_replication")
+    assert_http_ok(r)
+    return r.json()["result"]
+
+
+def all_shards_replicated(peer_api_uris):
+    shards = [
+        shard
+        for peer_url in peer_api_uris
+        for shard in get_async_replication_status(peer_url)["shards"]
+    ]
+    # Every shard is replicated by exactly one of its replicas
+    assert sorted(shard["shard_id"] for shard in shards) == list(range(N_SHARDS))
+    return all(shard["lag"] == 0 for shard in shards)
+
+
+def test_async_replication(tmp_path: pathlib.Path):
+    assert_project_root()
+
+    remote_api_uri, remote_grpc_port = start_remote_cluster(tmp_path)
+    create_collection(remote_api_uri)
+
+    primary_path = tmp_path / "primary"
+    primary_path.mkdir()
+    peer_api_uris, _, _ = start_cluster(primary_path, N_PEERS)
+    create_collection(peer_api_uris[0], shard_number=N_SHARDS, replication_factor=N_PEERS)
+    wait_collection_exists_and_active_on_all_peers(collection_name=COLLECTION_NAME, peer_api_uris=peer_api_uris)
+
+    # Not configured yet
+    r = requests.get(f"{peer_api_uris[0]}/collections/{COLLECTION_NAME}/async_replication")
+    assert r.status_code == 404
+
+    # Points written before replication is enabled are copied as well
+    upsert_random_points(peer_api_uris[0], N_POINTS // 2)
+
+    r = requests.patch(
+        f"{peer_api_uris[0]}/collections/{COLLECTION_NAME}",
+        json={"async_replication_config": {"uri": f"http://127.0.0.1:{remote_grpc_port}"}},
+    )
+    assert_http_ok(r)
+
+    upsert_random_points(peer_api_uris[1], N_POINTS // 2, offset=N_POINTS // 2)
+
+    wait_for(lambda: count_counts(remote_api_uri) == N_POINTS)
+    wait_for(all_shards_replicated, peer_api_uris)
+
+    status = get_async_replication_status(peer_api_uris[0])
+    assert status["enabled"]
+    assert status["collection_name"] == COLLECTION_NAME
+    for peer_url in peer_api_uris:
+        for shard in get_async_replication_status(peer_url)["shards"]:
+            assert not shard["resync_pending"]
+            assert shard["failed_operations"] == 0
@@ -0,0 +1,54 @@
+import json
+import pathlib
+import threading
+
+from .fixtures import create_collection, drop_collection
+from .utils import *
+
+N_PEERS = 3
+N_SHARDS = 2
+COLLECTION_NAME = "test_collection"
+
+
+def collect_events(peer_url, events, stop):
+    with requests.get(f"{peer_url}/cluster/events", stream=True, timeout=30) as res:
+        assert res.ok
+        assert res.headers["content-type"].startswith("text/event-stream")
+        for line in res.iter_lines(decode_unicode=True):
+            if line.startswith("data: "):
+                events.append(json.loads(line[len("data: "):]))
+            if stop.is_set():
+                return
+
+
+def test_cluster_events(tmp_path: pathlib.Path):
+    assert_project_root()
+
+    peer_api_uris, peer_dirs, bootstrap_uri = start_cluster(tmp_path, N_PEERS)
+
+    events = []
+    stop = threading.Event()
+    subscriber = threading.Thread(
+        target=collect_events, args=(peer_api_uris[1], events, stop), daemon=True,
+    )
+    subscriber.start()
+    # Give the subscriber some time to connect
+    time.sleep(1)
+
+    create_collection(peer_api_uris[0], shard_number=N_SHARDS, replication_factor=1)
+    wait_collection_exists_and_active_on_all_peers(collection_name=COLLECTION_NAME, peer_api_uris=peer_api_uris)
+    drop_collection(peer_api_uris[0])
+
+    wait_for(lambda: any(event["type"] == "collection_deleted" for event in events))
+    stop.set()
+
+    types = [event["type"] for event in events]
+    assert types.index("collection_created") < types.index("collection_deleted")
+
+    # Every shard replica was activated after creation
+    activated = [
+        event for event in events
+        if event["type"] == "replica_state_changed" and event["state"] == "Active"
+    ]
+    assert len(activated) == N_SHARDS
+    assert all(event["collection_name"] == COLLECTION_NAME for event in activated)
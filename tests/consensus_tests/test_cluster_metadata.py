@@ -0,0 +1,65 @@
+import pathlib
+
+from .utils import *
+
+N_PEERS = 3
+
+
+def get_metadata(peer_url):
+    r = requests.get(f"{peer_url}/cluster/metadata")
+    assert_http_ok(r)
+    return r.json()["result"]
+
+
+def put_metadata(peer_url, key, value, expected_version=None):
+    return requests.put(
+        f"{peer_url}/cluster/metadata/{key}",
+        json={"value": value, "expected_version": expected_version},
+    )
+
+
+def test_cluster_metadata(tmp_path: pathlib.Path):
+    assert_project_root()
+
+    peer_api_uris, _, _ = start_cluster(tmp_path, N_PEERS)
+
+    r = requests.get(f"{peer_api_uris[0]}/cluster/metadata/schema_version")
+    assert r.status_code == 404
+
+    # Version 0 creates the key only if it does not exist
+    r = put_metadata(peer_api_uris[0], "schema_version", "1", expected_version=0)
+    assert_http_ok(r)
+    r = put_metadata(peer_api_uris[1], "schema_version", "1", expected_version=0)
+    assert r.status_code == 409
+
+    # Value is replicated to all peers
+    wait_for(lambda: all("schema_version" in get_metadata(uri) for uri in peer_api_uris))
+    r = requests.get(f"{peer_api_uris[2]}/cluster/metadata/schema_version")
+    assert_http_ok(r)
+    entry = r.json()["result"]
+    assert entry["value"] == "1"
+
+    # Compare-and-set with the current version succeeds, with an outdated one fails
+    r = put_metadata(peer_api_uris[1], "schema_version", "2", expected_version=entry["version"])
+    assert_http_ok(r)
+    r = put_metadata(peer_api_uris[2], "schema_version", "3", expected_version=entry["version"])
+    assert r.status_code == 409
+
+    r = requests.get(f"{peer_api_uris[1]}/cluster/metadata/schema_version")
+    assert_http_ok(r)
+    entry = r.json()["result"]
+    assert entry["value"] == "2"
+
+    # Unconditional write
+    r = put_metadata(peer_api_uris[0], "embedding_model", "all-MiniLM-L6-v2")
+    assert_http_ok(r)
+
+    r = requests.delete(
+        f"{peer_api_uris[2]}/cluster/metadata/schema_version",
+        params={"expected_version": entry["version"]},
+    )
+    assert_http_ok(r)
+
+    wait_for(lambda: all(
+        set(get_metadata(uri)) == {"embedding_model"} for uri in peer_api_uris
+    ))
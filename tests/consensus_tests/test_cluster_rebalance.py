@@ -0,0 +1,65 @@
+import pathlib
+
+from .assertions import assert_http_ok
+from .test_custom_sharding import create_collection_with_custom_sharding, create_shard
+from .utils import *
+
+N_PEERS = 3
+N_SHARDS = 3
+
+COLLECTION_NAME = "test_collection"
+
+
+def rebalance(peer_url, strategy, apply):
+    res = requests.post(f"{peer_url}/cluster/rebalance", json={
+        "strategy": strategy,
+        "collections": [COLLECTION_NAME],
+        "apply": apply,
+    })
+    assert_http_ok(res)
+    return res.json()["result"]
+
+
+def replicas_per_peer(peer_url):
+    info = get_collection_cluster_info(peer_url, COLLECTION_NAME)
+    counts = {}
+    for replica in info["local_shards"]:
+        counts[info["peer_id"]] = counts.get(info["peer_id"], 0) + 1
+    for replica in info["remote_shards"]:
+        counts[replica["peer_id"]] = counts.get(replica["peer_id"], 0) + 1
+    return counts
+
+
+def test_rebalance_by_shard_count(tmp_path: pathlib.Path):
+    assert_project_root()
+
+    peer_api_uris, peer_dirs, bootstrap_uri = start_cluster(tmp_path, N_PEERS)
+
+    create_collection_with_custom_sharding(peer_api_uris[0], shard_number=N_SHARDS)
+    wait_collection_exists_and_active_on_all_peers(collection_name=COLLECTION_NAME, peer_api_uris=peer_api_uris)
+
+    # Place all shards of the key on a single peer
+    first_peer_id = get_cluster_info(peer_api_uris[0])["peer_id"]
+    create_shard(peer_api_uris[0], COLLECTION_NAME, shard_key="tenant", shard_number=N_SHARDS,
+                 placement=[first_peer_id])
+    wait_for_all_replicas_active(peer_api_uris[0], COLLECTION_NAME)
+    assert replicas_per_peer(peer_api_uris[0]) == {first_peer_id: N_SHARDS}
+
+    # Preview does not move anything
+    plan = rebalance(peer_api_uris[0], "shard_count", apply=False)
+    assert not plan["applied"]
+    assert len(plan["moves"]) == N_PEERS - 1
+    assert all(move["from_peer_id"] == first_peer_id for move in plan["moves"])
+    assert replicas_per_peer(peer_api_uris[0]) == {first_peer_id: N_SHARDS}
+
+    # Shards of the same key are already co-located
+    plan = rebalance(peer_api_uris[0], "tenant_locality", apply=False)
+    assert plan["moves"] == []
+
+    plan = rebalance(peer_api_uris[0], "shard_count", apply=True)
+    assert plan["applied"]
+
+    wait_for_collection_shard_transfers_count(peer_api_uris[0], COLLECTION_NAME, 0)
+    wait_for_all_replicas_active(peer_api_uris[0], COLLECTION_NAME)
+
+    assert sorted(replicas_per_peer(peer_api_uris[0]).values()) == [1] * N_PEERS
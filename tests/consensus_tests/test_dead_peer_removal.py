@@ -0,0 +1,47 @@
+import pathlib
+
+from .fixtures import create_collection, upsert_random_points
+from .utils import *
+
+N_PEERS = 3
+N_SHARDS = 3
+N_REPLICAS = 2
+COLLECTION_NAME = "test_collection"
+
+DEAD_PEER_REMOVAL_ENV = {
+    "QDRANT__CLUSTER__DEAD_PEER_REMOVAL__ENABLED": "true",
+    "QDRANT__CLUSTER__DEAD_PEER_REMOVAL__TIMEOUT_SEC": "5",
+}
+
+
+def dead_peer_removed(peer_api_uri: str, dead_peer_id: int) -> bool:
+    cluster_info = get_cluster_info(peer_api_uri)
+    if str(dead_peer_id) in cluster_info["peers"]:
+        return False
+
+    collection_cluster_info = get_collection_cluster_info(peer_api_uri, COLLECTION_NAME)
+    replicas = collection_cluster_info["local_shards"] + collection_cluster_info["remote_shards"]
+    return (
+        all(replica["state"] == "Active" for replica in replicas)
+        and len(replicas) == N_SHARDS * N_REPLICAS
+    )
+
+
+def test_dead_peer_removal(tmp_path: pathlib.Path):
+    assert_project_root()
+
+    peer_api_uris, _, _ = start_cluster(tmp_path, N_PEERS, extra_env=DEAD_PEER_REMOVAL_ENV)
+    create_collection(peer_api_uris[0], shard_number=N_SHARDS, replication_factor=N_REPLICAS)
+    wait_collection_exists_and_active_on_all_peers(collection_name=COLLECTION_NAME, peer_api_uris=peer_api_uris)
+    upsert_random_points(peer_api_uris[0], 100)
+
+    # Kill a follower, the leader notices it can't reach it
+    leader_id = get_leader(peer_api_uris[0])
+    dead_peer_uri = next(uri for uri in peer_api_uris if get_cluster_info(uri)["peer_id"] != leader_id)
+    dead_peer_id = get_cluster_info(dead_peer_uri)["peer_id"]
+    dead_peer_index = peer_api_uris.index(dead_peer_uri)
+    processes.pop(dead_peer_index).kill()
+    peer_api_uris.pop(dead_peer_index)
+
+    # Replicas of the dead peer are re-created on the remaining peers, then it is removed
+    wait_for(dead_peer_removed, peer_api_uris[0], dead_peer_id)
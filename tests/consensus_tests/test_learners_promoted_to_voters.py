@@ -35,3 +35,32 @@ def test_collection_after_peers_added(tmp_path: pathlib.Path):
             peer_dirs[i], f"peer_0_{i}.log", bootstrap_uri))
 
     wait_for(all_peers_are_voters, peer_api_uris)
+
+
+def test_learner_peer_is_not_promoted(tmp_path: pathlib.Path):
+    assert_project_root()
+    peer_dirs = make_peer_folders(tmp_path, 3)
+
+    peer_api_uris = []
+
+    (bootstrap_api_uri, bootstrap_uri) = start_first_peer(
+        peer_dirs[0], "peer_0_0.log")
+    peer_api_uris.append(bootstrap_api_uri)
+
+    wait_peer_added(bootstrap_api_uri)
+
+    peer_api_uris.append(start_peer(
+        peer_dirs[1], "peer_0_1.log", bootstrap_uri))
+    learner_api_uri = start_peer(
+        peer_dirs[2], "peer_0_2.log", bootstrap_uri,
+        extra_env={"QDRANT__CLUSTER__CONSENSUS__LEARNER": "true"})
+
+    wait_for(all_peers_are_voters, peer_api_uris)
+    wait_for(all_nodes_respond, [learner_api_uri])
+    wait_for_same_commit(peer_api_uris + [learner_api_uri])
+
+    assert not get_cluster_info(learner_api_uri)["raft_info"]["is_voter"]
+
+    peers = get_cluster_info(bootstrap_api_uri)["peers"]
+    assert len(peers) == 3
+    assert sorted(peer["is_voter"] for peer in peers.values()) == [False, True, True]
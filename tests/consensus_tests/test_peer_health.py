@@ -0,0 +1,28 @@
+import pathlib
+
+from .fixtures import create_collection, upsert_random_points
+from .utils import *
+
+N_PEERS = 3
+N_SHARDS = 2
+COLLECTION_NAME = "test_collection"
+
+PEER_HEALTH_ENV = {
+    "QDRANT__CLUSTER__PEER_HEALTH__ENABLED": "true",
+    "QDRANT__CLUSTER__PEER_HEALTH__INTERVAL_MS": "500",
+    "QDRANT__CLUSTER__PEER_HEALTH__FAILURE_THRESHOLD": "2",
+}
+
+
+def test_dead_peer_detected_without_updates(tmp_path: pathlib.Path):
+    assert_project_root()
+
+    peer_api_uris, _, _ = start_cluster(tmp_path, N_PEERS, extra_env=PEER_HEALTH_ENV)
+    create_collection(peer_api_uris[0], shard_number=N_SHARDS, replication_factor=N_PEERS)
+    wait_collection_exists_and_active_on_all_peers(collection_name=COLLECTION_NAME, peer_api_uris=peer_api_uris)
+    upsert_random_points(peer_api_uris[0], 10)
+
+    processes.pop().kill()
+
+    # No updates are sent, replicas of the killed peer are marked dead by health checks alone
+    wait_for_some_replicas_not_active(peer_api_uris[0], COLLECTION_NAME)
@@ -0,0 +1,68 @@
+import pathlib
+
+from .fixtures import create_collection, upsert_random_points
+from .utils import *
+from .assertions import assert_http_ok
+
+N_PEERS = 3
+N_SHARDS = 1
+N_REPLICA = 3
+
+COLLECTION_NAME = "test_collection"
+
+
+def get_point(peer_url, point_id):
+    r = requests.get(f"{peer_url}/collections/{COLLECTION_NAME}/points/{point_id}")
+    assert_http_ok(r)
+    return r.json()["result"]
+
+
+def guarded_upsert(peer_url, point_id, city, if_version):
+    return requests.put(
+        f"{peer_url}/collections/{COLLECTION_NAME}/points?wait=true&if_version={if_version}", json={
+            "points": [
+                {
+                    "id": point_id,
+                    "vector": [0.05, 0.61, 0.76, 0.74],
+                    "payload": {"city": city},
+                }
+            ]
+        })
+
+
+# Version guard is resolved once by the update leader, and the update is applied on all replicas
+def test_version_guard_resolved_by_leader(tmp_path: pathlib.Path):
+    assert_project_root()
+
+    peer_api_uris, peer_dirs, bootstrap_uri = start_cluster(tmp_path, N_PEERS)
+
+    create_collection(peer_api_uris[0], shard_number=N_SHARDS, replication_factor=N_REPLICA)
+    wait_collection_exists_and_active_on_all_peers(
+        collection_name=COLLECTION_NAME,
+        peer_api_uris=peer_api_uris
+    )
+    upsert_random_points(peer_api_uris[0], 10)
+
+    leader_url = fetch_highest_peer_id(peer_api_uris)
+    follower_urls = [url for url in peer_api_uris if url != leader_url]
+
+    version = get_point(leader_url, 1)["version"]
+
+    # Stale version is rejected, no matter which peer receives the request
+    for peer_url in peer_api_uris:
+        r = guarded_upsert(peer_url, 1, "Paris", version + 1000)
+        assert r.status_code == 409
+
+    # Update through a follower is checked against the version of the leader
+    r = guarded_upsert(follower_urls[0], 1, "Paris", version)
+    assert_http_ok(r)
+
+    for peer_url in peer_api_uris:
+        assert get_point(peer_url, 1)["payload"] == {"city": "Paris"}
+
+    # Guard can't pass twice with the same version
+    r = guarded_upsert(follower_urls[1], 1, "Rome", version)
+    assert r.status_code == 409
+
+    for peer_url in peer_api_uris:
+        assert get_point(peer_url, 1)["payload"] == {"city": "Paris"}
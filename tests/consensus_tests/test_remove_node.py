@@ -113,3 +113,32 @@ def test_remove_node(tmp_path: pathlib.Path):
     search_result = search(peer_api_uris[0], "Berlin")
     assert len(search_result) > 0
 
+
+
+def test_decommission_node(tmp_path: pathlib.Path):
+    assert_project_root()
+
+    peer_api_uris, peer_dirs, bootstrap_uri = start_cluster(tmp_path, N_PEERS + 1)
+
+    create_collection(peer_api_uris[0])
+    wait_collection_exists_and_active_on_all_peers(collection_name="test_collection", peer_api_uris=peer_api_uris)
+
+    upsert_points(peer_api_uris[0], "Paris")
+
+    decommissioned_peer = get_cluster_info(peer_api_uris[-1])["peer_id"]
+
+    res = requests.post(f"{peer_api_uris[0]}/cluster/peer/{decommissioned_peer}/decommission?timeout=60")
+    assert_http_ok(res)
+
+    # Peer is removed from the cluster
+    assert str(decommissioned_peer) not in get_cluster_info(peer_api_uris[0])["peers"]
+
+    # Replication factor is preserved on the remaining peers
+    collection_cluster_info = get_collection_cluster_info(peer_api_uris[0], "test_collection")
+    replicas = collection_cluster_info["local_shards"] + collection_cluster_info["remote_shards"]
+    assert len(replicas) == N_SHARDS * N_REPLICA
+    assert all(replica["state"] == "Active" for replica in replicas)
+    assert all(replica.get("peer_id") != decommissioned_peer for replica in replicas)
+
+    search_result = search(peer_api_uris[0], "Paris")
+    assert len(search_result) > 0
@@ -0,0 +1,74 @@
+import pathlib
+
+from .utils import *
+
+N_PEERS = 3
+COLLECTION_NAME = "test_collection"
+
+
+def get_peer_id(peer_url):
+    r = requests.get(f"{peer_url}/cluster")
+    assert_http_ok(r)
+    return r.json()["result"]["peer_id"]
+
+
+def move_shard(peer_url, shard_id, from_peer_id, to_peer_id):
+    return requests.post(
+        f"{peer_url}/collections/{COLLECTION_NAME}/cluster",
+        json={
+            "move_shard": {
+                "shard_id": shard_id,
+                "from_peer_id": from_peer_id,
+                "to_peer_id": to_peer_id,
+            }
+        },
+    )
+
+
+def test_shard_pinning(tmp_path: pathlib.Path):
+    assert_project_root()
+
+    peer_api_uris, _, _ = start_cluster(tmp_path, N_PEERS)
+    peer_ids = [get_peer_id(uri) for uri in peer_api_uris]
+
+    # Exactly one of `shard_id` and `shard_key` must be set
+    r = requests.put(
+        f"{peer_api_uris[0]}/collections/{COLLECTION_NAME}", json={
+            "vectors": {"size": 4, "distance": "Dot"},
+            "shard_pinning": [{"peers": [peer_ids[2]]}],
+        })
+    assert r.status_code == 422
+
+    r = requests.put(
+        f"{peer_api_uris[0]}/collections/{COLLECTION_NAME}", json={
+            "vectors": {"size": 4, "distance": "Dot"},
+            "shard_number": 3,
+            "replication_factor": 1,
+            "shard_pinning": [{"shard_id": 0, "peers": [peer_ids[2]]}],
+        })
+    assert_http_ok(r)
+    wait_collection_exists_and_active_on_all_peers(
+        collection_name=COLLECTION_NAME, peer_api_uris=peer_api_uris)
+
+    # Pinned shard is placed on the pinned peer
+    cluster_info = get_collection_cluster_info(peer_api_uris[2], COLLECTION_NAME)
+    assert 0 in [shard["shard_id"] for shard in cluster_info["local_shards"]]
+
+    # Pinned shard can't be moved to other peers
+    r = move_shard(peer_api_uris[0], 0, peer_ids[2], peer_ids[0])
+    assert r.status_code == 400
+    assert "pinned" in r.json()["status"]["error"]
+
+    # Removing the pin allows the move
+    r = requests.patch(
+        f"{peer_api_uris[0]}/collections/{COLLECTION_NAME}", json={
+            "shard_pinning": [],
+        })
+    assert_http_ok(r)
+
+    r = move_shard(peer_api_uris[0], 0, peer_ids[2], peer_ids[0])
+    assert_http_ok(r)
+    wait_for_collection_shard_transfers_count(peer_api_uris[0], COLLECTION_NAME, 0)
+
+    cluster_info = get_collection_cluster_info(peer_api_uris[0], COLLECTION_NAME)
+    assert 0 in [shard["shard_id"] for shard in cluster_info["local_shards"]]
@@ -0,0 +1,43 @@
+import pathlib
+
+from .fixtures import create_collection, random_dense_vector, upsert_random_points
+from .utils import *
+
+N_PEERS = 3
+COLLECTION_NAME = "test_collection"
+
+FENCING_ENV = {
+    "QDRANT__CLUSTER__CONSENSUS__SPLIT_BRAIN_PROTECTION__FENCE": "writes",
+    "QDRANT__CLUSTER__CONSENSUS__SPLIT_BRAIN_PROTECTION__TIMEOUT_MS": "2000",
+}
+
+
+def upsert_point(peer_url):
+    return requests.put(
+        f"{peer_url}/collections/{COLLECTION_NAME}/points?wait=true",
+        json={"points": [{"id": 1, "vector": {"": random_dense_vector()}}]},
+    )
+
+
+def writes_fenced(peer_url):
+    r = upsert_point(peer_url)
+    return r.status_code == 403 and "consensus quorum" in r.json()["status"]["error"]
+
+
+def test_split_brain_fencing(tmp_path: pathlib.Path):
+    assert_project_root()
+
+    peer_api_uris, _, _ = start_cluster(tmp_path, N_PEERS, extra_env=FENCING_ENV)
+    create_collection(peer_api_uris[0], replication_factor=N_PEERS)
+    wait_collection_exists_and_active_on_all_peers(collection_name=COLLECTION_NAME, peer_api_uris=peer_api_uris)
+    upsert_random_points(peer_api_uris[0], 10)
+
+    # Leave the first peer alone, without the quorum
+    for _ in range(N_PEERS - 1):
+        processes.pop().kill()
+
+    wait_for(writes_fenced, peer_api_uris[0])
+
+    # Reads are still allowed with `fence: writes`
+    r = requests.post(f"{peer_api_uris[0]}/collections/{COLLECTION_NAME}/points/count", json={"exact": True})
+    assert_http_ok(r)
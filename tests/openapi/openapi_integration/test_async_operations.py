@@ -0,0 +1,98 @@
+import time
+
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_async_operations'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def wait_for_operation(operation_id, timeout=10):
+    start = time.time()
+    while True:
+        response = request_with_validation(
+            api='/operations/{operation_id}',
+            method="GET",
+            path_params={'operation_id': operation_id},
+        )
+        assert response.ok
+        operation = response.json()['result']
+        if operation['status'] != 'running':
+            return operation
+        assert time.time() - start < timeout
+        time.sleep(0.1)
+
+
+def test_async_delete_points():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/delete',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'async': 'true'},
+        body={"points": [1, 2]},
+    )
+    assert response.ok
+    operation_id = response.json()['result']['operation_id']
+
+    operation = wait_for_operation(operation_id)
+    assert operation['status'] == 'completed'
+    assert operation['operation'] == 'delete_points'
+    assert operation['collection_name'] == collection_name
+    assert operation['result']['status'] == 'completed'
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/{id}',
+        method="GET",
+        path_params={'collection_name': collection_name, 'id': 1},
+    )
+    assert response.status_code == 404
+
+    response = request_with_validation(api='/operations', method="GET")
+    assert response.ok
+    assert operation_id in [operation['id'] for operation in response.json()['result']]
+
+
+def test_async_create_index():
+    response = request_with_validation(
+        api='/collections/{collection_name}/index',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'async': 'true'},
+        body={"field_name": "city", "field_schema": "keyword"},
+    )
+    assert response.ok
+
+    operation = wait_for_operation(response.json()['result']['operation_id'])
+    assert operation['status'] == 'completed'
+
+
+def test_async_operation_failed():
+    response = request_with_validation(
+        api='/collections/{collection_name}/index',
+        method="PUT",
+        path_params={'collection_name': 'i-do-not-exist'},
+        query_params={'async': 'true'},
+        body={"field_name": "city", "field_schema": "keyword"},
+    )
+    assert response.ok
+
+    operation = wait_for_operation(response.json()['result']['operation_id'])
+    assert operation['status'] == 'failed'
+    assert 'error' in operation
+
+
+def test_missing_operation():
+    response = request_with_validation(
+        api='/operations/{operation_id}',
+        method="GET",
+        path_params={'operation_id': '00000000-0000-0000-0000-000000000000'},
+    )
+    assert response.status_code == 404
@@ -141,6 +141,34 @@ def test_retrieve_invalid_vector():
     assert error == "Wrong input: Not existing vector name error: i_do_no_exist"
 
 
+def test_retrieve_exclude_vector():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/scroll',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={"offset": 2, "limit": 2, "with_vector": {"exclude": ["text"]}}
+    )
+    assert response.ok
+    assert len(response.json()['result']['points']) == 2
+    for point in response.json()['result']['points']:
+        assert 'text' not in point['vector']
+        assert len(point['vector']['image']) == 4
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "ids": [1, 2],
+            "with_vectors": {"exclude": ["image"]},
+        }
+    )
+    assert response.ok
+    for point in response.json()['result']:
+        assert 'image' not in point['vector']
+        assert len(point['vector']['text']) == 8
+
+
 def test_exclude_payload():
     response = request_with_validation(
         api='/collections/{collection_name}/points/search',
@@ -0,0 +1,88 @@
+import pytest
+import requests
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+from .helpers.settings import QDRANT_HOST
+
+collection_name = 'test_collection_blobs'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def set_blob(point_id, blob):
+    return requests.put(
+        f"{QDRANT_HOST}/collections/{collection_name}/points/{point_id}/blob",
+        params={'wait': 'true'},
+        data=blob,
+        headers={'Content-Type': 'application/octet-stream'},
+    )
+
+
+def get_blob(point_id):
+    return request_with_validation(
+        api='/collections/{collection_name}/points/{id}/blob',
+        method="GET",
+        path_params={'collection_name': collection_name, 'id': point_id},
+    )
+
+
+def test_point_blob():
+    response = get_blob(1)
+    assert response.status_code == 404
+
+    thumbnail = bytes(range(256)) * 10
+    response = set_blob(1, thumbnail)
+    assert response.ok
+    assert response.json()['result']['status'] == 'completed'
+
+    response = get_blob(1)
+    assert response.ok
+    assert response.headers['Content-Type'] == 'application/octet-stream'
+    assert response.content == thumbnail
+
+    # Blob is replaced, larger than a single read chunk
+    features = b'\x01\x02\x03' * 60_000
+    response = set_blob(1, features)
+    assert response.ok
+
+    response = get_blob(1)
+    assert response.ok
+    assert response.content == features
+
+    # Blob is kept alongside the point
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/{id}',
+        method="GET",
+        path_params={'collection_name': collection_name, 'id': 1},
+    )
+    assert response.ok
+    assert response.json()['result']['id'] == 1
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/{id}/blob',
+        method="DELETE",
+        path_params={'collection_name': collection_name, 'id': 1},
+        query_params={'wait': 'true'},
+    )
+    assert response.ok
+
+    response = get_blob(1)
+    assert response.status_code == 404
+
+
+def test_point_blob_validation():
+    # Point does not exist
+    response = set_blob(1000, b'blob')
+    assert response.status_code == 404
+
+    response = set_blob(1, b'')
+    assert response.status_code == 400
+
+    response = set_blob(1, b'\x00' * (256 * 1024 + 1))
+    assert response.status_code == 413
@@ -0,0 +1,89 @@
+import time
+
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_clone_source'
+clone_collection_name = 'test_collection_clone_target'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    drop_collection(collection_name=clone_collection_name)
+    yield
+    drop_collection(collection_name=clone_collection_name)
+    drop_collection(collection_name=collection_name)
+
+
+def clone_collection(body):
+    return request_with_validation(
+        api='/collections/{collection_name}/clone',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body=body,
+    )
+
+
+def count_points(name):
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/count',
+        method="POST",
+        path_params={'collection_name': name},
+        body={"exact": True},
+    )
+    assert response.ok
+    return response.json()['result']['count']
+
+
+def wait_for_count(name, expected, timeout=10):
+    # Points are copied in background after the collection is created
+    start = time.time()
+    while count_points(name) != expected:
+        assert time.time() - start < timeout, f"collection {name} has {count_points(name)} points"
+        time.sleep(0.1)
+
+
+def test_clone_collection():
+    response = clone_collection({"name": clone_collection_name})
+    assert response.ok
+
+    wait_for_count(clone_collection_name, count_points(collection_name))
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="GET",
+        path_params={'collection_name': clone_collection_name},
+    )
+    assert response.ok
+    assert response.json()['result']['config']['params']['vectors']['size'] == 4
+
+
+def test_clone_collection_with_filter():
+    response = clone_collection({
+        "name": clone_collection_name,
+        "filter": {"must": [{"has_id": [1, 2, 3]}]},
+    })
+    assert response.ok
+
+    wait_for_count(clone_collection_name, 3)
+
+
+def test_clone_collection_incompatible_vectors():
+    response = clone_collection({
+        "name": clone_collection_name,
+        "vectors": {"size": 8, "distance": "Dot"},
+    })
+    assert not response.ok
+
+
+def test_clone_missing_collection():
+    response = request_with_validation(
+        api='/collections/{collection_name}/clone',
+        method="POST",
+        path_params={'collection_name': 'i-do-not-exist'},
+        body={"name": clone_collection_name},
+    )
+    assert response.status_code == 404
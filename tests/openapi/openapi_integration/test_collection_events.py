@@ -0,0 +1,69 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_events'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def get_events(limit=None):
+    response = request_with_validation(
+        api='/collections/{collection_name}/events',
+        method="GET",
+        path_params={'collection_name': collection_name},
+        query_params={'limit': limit} if limit is not None else {},
+    )
+    assert response.ok
+    return response.json()['result']['events']
+
+
+def test_collection_events():
+    events = get_events()
+    assert events[-1]['event'] == {'type': 'created'}
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PATCH",
+        path_params={'collection_name': collection_name},
+        body={
+            "optimizers_config": {
+                "default_segment_number": 3,
+            },
+        },
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/index',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "field_name": "city",
+            "field_schema": "keyword",
+        },
+    )
+    assert response.ok
+
+    # Optimizers may report events in between
+    events = [
+        event['event'] for event in get_events()
+        if event['event']['type'] in ('config_changed', 'payload_index_created')
+    ]
+    assert events[0] == {'type': 'payload_index_created', 'field_name': 'city'}
+    assert events[1]['type'] == 'config_changed'
+    assert events[1]['update']['optimizers_config']['default_segment_number'] == 3
+
+    events = get_events()
+
+    times = [event['time'] for event in events]
+    assert times == sorted(times, reverse=True)
+
+    assert len(get_events(limit=1)) == 1
@@ -0,0 +1,68 @@
+import time
+
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_optimize'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def wait_for_operation(operation_id, timeout=30):
+    start = time.time()
+    while True:
+        response = request_with_validation(
+            api='/operations/{operation_id}',
+            method="GET",
+            path_params={'operation_id': operation_id},
+        )
+        assert response.ok
+        operation = response.json()['result']
+        if operation['status'] != 'running':
+            return operation
+        assert time.time() - start < timeout
+        time.sleep(0.1)
+
+
+def test_force_optimize():
+    response = request_with_validation(
+        api='/collections/{collection_name}/optimize',
+        method="POST",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+
+    operation = wait_for_operation(response.json()['result']['operation_id'])
+    assert operation['status'] == 'completed'
+    assert operation['operation'] == 'optimize'
+
+    segments = operation['progress']['segments']
+    assert len(segments) > 0
+    assert sum(segment['points_count'] for segment in segments) == 10
+    for segment in segments:
+        assert segment['status'] in ('done', 'skipped')
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+    assert response.json()['result']['points_count'] == 10
+
+
+def test_force_optimize_missing_shard():
+    response = request_with_validation(
+        api='/collections/{collection_name}/optimize',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'shard_id': 100},
+    )
+    assert response.status_code == 404
@@ -191,3 +191,70 @@ def test_edit_collection_params(on_disk_vectors, on_disk_payload):
     assert config["quantization_config"]["scalar"]["type"] == "int8"
     assert config["quantization_config"]["scalar"]["quantile"] == 0.99
     assert config["quantization_config"]["scalar"]["always_ram"]
+
+
+def test_optimizer_cpu_budget_update():
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PATCH",
+        path_params={'collection_name': collection_name},
+        body={
+            "optimizers_config": {
+                "cpu_budget": 1,
+            },
+        }
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+    assert response.json()['result']['config']['optimizer_config']['cpu_budget'] == 1
+
+
+def test_storage_lifecycle_configs():
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PATCH",
+        path_params={'collection_name': collection_name},
+        body={
+            "optimizers_config": {
+                "merge_policy": "leveled",
+            },
+            "tiering_config": {
+                "enabled": False,
+                "cold_after_sec": 600,
+            },
+            "ttl_config": {
+                "field": "created_at",
+                "ttl_sec": 3600,
+            },
+        }
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+    config = response.json()['result']['config']
+    assert config['optimizer_config']['merge_policy'] == 'leveled'
+    assert config['wal_config']['wal_compression'] == 'none'
+    assert config['wal_config']['wal_durability'] == 'interval'
+    assert config['tiering_config'] == {
+        'enabled': False,
+        'cold_after_sec': 600,
+        'min_segment_points': 0,
+    }
+    assert config['ttl_config'] == {
+        'enabled': True,
+        'field': 'created_at',
+        'ttl_sec': 3600,
+        'point_expiry': False,
+        'check_interval_sec': 60,
+    }
@@ -0,0 +1,58 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_vacuum'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def vacuum(body):
+    response = request_with_validation(
+        api='/collections/{collection_name}/vacuum',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body=body,
+    )
+    assert response.ok
+    return response.json()['result']
+
+
+def test_vacuum_collection():
+    # Nothing is deleted yet
+    assert vacuum({}) == {
+        'segments': 0,
+        'deleted_points': 0,
+        'reclaimed_bytes': 0,
+    }
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/delete',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={"points": [1, 2, 3]},
+    )
+    assert response.ok
+
+    result = vacuum({"deleted_threshold": 0.0})
+    assert result['segments'] > 0
+    assert result['deleted_points'] == 3
+
+    # Deleted points are removed from segments
+    assert vacuum({"deleted_threshold": 0.0})['segments'] == 0
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+    assert response.json()['result']['points_count'] == 7
+
@@ -0,0 +1,56 @@
+import pytest
+import requests
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+from .helpers.settings import QDRANT_HOST
+
+collection_name = 'test_collection_compression'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "points": [
+                {"id": i, "vector": [0.1, 0.2, 0.3, 0.4], "payload": {"text": "large payload " * 10}}
+                for i in range(100, 200)
+            ]
+        },
+    )
+    assert response.ok
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def scroll(limit, encoding):
+    return requests.post(
+        f"{QDRANT_HOST}/collections/{collection_name}/points/scroll",
+        json={"limit": limit, "with_payload": True, "with_vector": True},
+        headers={"Accept-Encoding": encoding},
+    )
+
+
+@pytest.mark.parametrize("encoding", ["gzip", "zstd"])
+def test_large_response_is_compressed(encoding):
+    response = scroll(100, encoding)
+    assert response.ok
+    assert response.headers.get('content-encoding') == encoding
+
+
+def test_small_response_is_not_compressed():
+    response = scroll(1, "gzip")
+    assert response.ok
+    assert response.headers.get('content-encoding') in (None, 'identity')
+    assert len(response.json()['result']['points']) == 1
+
+
+def test_no_compression_without_accept_encoding():
+    response = scroll(100, "identity")
+    assert response.ok
+    assert response.headers.get('content-encoding') in (None, 'identity')
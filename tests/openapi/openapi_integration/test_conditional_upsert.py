@@ -0,0 +1,103 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_conditional_upsert'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def upsert(body):
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body=body,
+    )
+    assert response.ok
+
+
+def get_payloads(ids):
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={"ids": ids, "with_payload": True},
+    )
+    assert response.ok
+    return {point['id']: point['payload'] for point in response.json()['result']}
+
+
+def test_insert_only():
+    upsert({
+        "points": [
+            {"id": 1, "vector": [0.1, 0.1, 0.1, 0.1], "payload": {"city": "Paris"}},
+            {"id": 100, "vector": [0.1, 0.1, 0.1, 0.1], "payload": {"city": "Paris"}},
+        ],
+        "update_mode": "insert_only",
+    })
+
+    payloads = get_payloads([1, 100])
+    assert payloads[1] == {"city": "Berlin"}
+    assert payloads[100] == {"city": "Paris"}
+
+
+def test_update_only():
+    upsert({
+        "batch": {
+            "ids": [1, 100],
+            "vectors": [[0.1, 0.1, 0.1, 0.1], [0.1, 0.1, 0.1, 0.1]],
+            "payloads": [{"city": "Paris"}, {"city": "Paris"}],
+        },
+        "update_mode": "update_only",
+    })
+
+    payloads = get_payloads([1, 100])
+    assert payloads == {1: {"city": "Paris"}}
+
+
+def test_update_filter():
+    upsert({
+        "points": [
+            {"id": 1, "vector": [0.1, 0.1, 0.1, 0.1], "payload": {"city": "Paris"}},
+            {"id": 2, "vector": [0.1, 0.1, 0.1, 0.1], "payload": {"city": "Paris"}},
+            {"id": 100, "vector": [0.1, 0.1, 0.1, 0.1], "payload": {"city": "Paris"}},
+        ],
+        "update_filter": {
+            "must_not": [{"key": "city", "match": {"value": "London"}}]
+        },
+    })
+
+    # Point 2 is in London and stays untouched, new point 100 is inserted
+    payloads = get_payloads([1, 2, 100])
+    assert payloads[1] == {"city": "Paris"}
+    assert payloads[2] == {"city": ["Berlin", "London"]}
+    assert payloads[100] == {"city": "Paris"}
+
+
+def test_dedup_key():
+    upsert({
+        "points": [
+            {"id": 100, "vector": [0.1, 0.1, 0.1, 0.1], "payload": {"sku": "abc", "price": 10}},
+        ],
+    })
+
+    upsert({
+        "points": [
+            {"id": 200, "vector": [0.2, 0.2, 0.2, 0.2], "payload": {"sku": "abc", "price": 12}},
+            {"id": 201, "vector": [0.2, 0.2, 0.2, 0.2], "payload": {"sku": "def", "price": 5}},
+        ],
+        "dedup_key": "sku",
+    })
+
+    payloads = get_payloads([100, 200, 201])
+    assert payloads[100] == {"sku": "abc", "price": 12}
+    assert 200 not in payloads
+    assert payloads[201] == {"sku": "def", "price": 5}
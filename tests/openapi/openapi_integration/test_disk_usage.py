@@ -0,0 +1,34 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_disk_usage'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def test_disk_usage():
+    response = request_with_validation(
+        api='/collections/{collection_name}/disk_usage',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+    usage = response.json()['result']
+
+    assert len(usage['shards']) == 1
+    shard = usage['shards'][0]
+    assert shard['shard_id'] == 0
+    assert shard['wal_bytes'] > 0
+    assert len(shard['segments']) > 0
+    assert shard['segments_data']['vector_storage_bytes'] + shard['segments_data']['other_bytes'] > 0
+    assert shard['total_bytes'] >= shard['wal_bytes']
+
+    assert usage['wal_bytes'] == shard['wal_bytes']
+    assert usage['total_bytes'] >= shard['total_bytes'] + usage['snapshots_bytes']
@@ -0,0 +1,79 @@
+import pytest
+
+from .helpers.collection_setup import drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_distance_override'
+
+
+@pytest.fixture(autouse=True)
+def setup():
+    drop_collection(collection_name=collection_name)
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        body={
+            "vectors": {
+                "size": 2,
+                "distance": "Dot",
+            },
+        }
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "points": [
+                {"id": 1, "vector": [1.0, 0.0]},
+                {"id": 2, "vector": [10.0, 10.0]},
+                {"id": 3, "vector": [0.0, 3.0]},
+            ]
+        },
+    )
+    assert response.ok
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def search(params=None):
+    body = {
+        "vector": [1.0, 0.0],
+        "limit": 3,
+    }
+    if params is not None:
+        body["params"] = params
+    return request_with_validation(
+        api='/collections/{collection_name}/points/search',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body=body,
+    )
+
+
+def test_distance_override():
+    response = search()
+    assert response.ok
+    assert [point['id'] for point in response.json()['result']] == [2, 1, 3]
+
+    response = search({"distance": "Euclid"})
+    assert response.ok
+    result = response.json()['result']
+    assert [point['id'] for point in result] == [1, 3, 2]
+    assert result[0]['score'] == 0.0
+
+    response = search({"distance": "Manhattan"})
+    assert response.ok
+    result = response.json()['result']
+    assert [point['id'] for point in result] == [1, 3, 2]
+    assert result[1]['score'] == 4.0
+
+
+def test_invalid_distance_override():
+    # Vectors are stored as is, cosine requires normalized vectors
+    response = search({"distance": "Cosine"})
+    assert response.status_code == 400
@@ -0,0 +1,68 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_dry_run'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def count_points():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/count',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={"exact": True},
+    )
+    assert response.ok
+    return response.json()['result']['count']
+
+
+def test_dry_run_delete_by_filter():
+    total = count_points()
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/delete',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true', 'dry_run': 'true', 'dry_run_samples': 2},
+        body={"filter": {"must": [{"has_id": [1, 2, 3]}]}},
+    )
+    assert response.ok
+    result = response.json()['result']
+    assert result['count'] == 3
+    assert len(result['sample_ids']) == 2
+    assert set(result['sample_ids']) <= {1, 2, 3}
+
+    assert count_points() == total
+
+
+def test_dry_run_set_payload():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/payload',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true', 'dry_run': 'true'},
+        body={
+            "payload": {"dry_run": True},
+            "filter": {"must": [{"has_id": [1, 2, 100]}]},
+        },
+    )
+    assert response.ok
+    result = response.json()['result']
+    assert result['count'] == 2
+    assert 'sample_ids' not in result
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/{id}',
+        method="GET",
+        path_params={'collection_name': collection_name, 'id': 1},
+    )
+    assert response.ok
+    assert 'dry_run' not in response.json()['result']['payload']
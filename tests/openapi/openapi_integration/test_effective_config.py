@@ -0,0 +1,30 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_effective_config'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def test_effective_config():
+    response = request_with_validation(
+        api='/config',
+        method="GET",
+    )
+    assert response.ok
+    config = response.json()['result']
+
+    node = config['node']
+    assert node['service']['http_port'] is not None
+    assert 'storage_path' in node['storage']
+
+    collection = config['collections'][collection_name]
+    assert collection['params']['vectors']['size'] == 4
+    assert collection['params']['vectors']['distance'] == 'Dot'
@@ -0,0 +1,62 @@
+import json
+
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_export'
+
+
+@pytest.fixture(autouse=True, scope="module")
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def export(body):
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/export',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body=body,
+    )
+    assert response.ok
+    return response
+
+
+def test_export_jsonl_matches_scroll():
+    response = export({"format": "jsonl", "batch_size": 3})
+    assert response.headers['content-type'].startswith('application/x-ndjson')
+    exported = [json.loads(line) for line in response.text.splitlines() if line]
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/scroll',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={"limit": 100, "with_payload": True, "with_vector": True},
+    )
+    assert response.ok
+    scrolled = response.json()['result']['points']
+
+    assert exported == scrolled
+
+
+def test_export_jsonl_filtered():
+    response = export({
+        "filter": {"must": [{"key": "city", "match": {"value": "London"}}]},
+        "with_vector": False,
+    })
+    exported = [json.loads(line) for line in response.text.splitlines() if line]
+    assert len(exported) > 0
+    for point in exported:
+        assert "London" in point['payload']['city']
+        assert point.get('vector') is None
+
+
+def test_export_parquet():
+    response = export({"format": "parquet"})
+    assert response.headers['content-type'].startswith('application/vnd.apache.parquet')
+    assert response.content[:4] == b'PAR1'
+    assert response.content[-4:] == b'PAR1'
@@ -0,0 +1,120 @@
+import pytest
+
+from .helpers.collection_setup import drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_float64'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    drop_collection(collection_name=collection_name)
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        body={
+            "vectors": {
+                "size": 4,
+                "distance": "Dot",
+                "on_disk": on_disk_vectors,
+                "datatype": "float64",
+            },
+        }
+    )
+    assert response.ok
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def test_float64_vectors():
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+    assert response.json()['result']['config']['params']['vectors']['datatype'] == "float64"
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "points": [
+                {"id": 1, "vector": [0.05, 0.61, 0.76, 0.74]},
+                {"id": 2, "vector": [0.19, 0.81, 0.75, 0.11]},
+                {"id": 3, "vector": [0.36, 0.55, 0.47, 0.94]},
+            ]
+        },
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "vector": [0.2, 0.1, 0.9, 0.7],
+            "limit": 3,
+        },
+    )
+    assert response.ok
+    result = response.json()['result']
+    assert [point['id'] for point in result] == [1, 3, 2]
+    assert result[0]['score'] == pytest.approx(1.273)
+
+
+def test_float64_vectors_keep_precision():
+    # None of these values can be represented in single precision
+    vector = [0.1234567890123, 1e-50, 16777217.0, -0.987654321098765]
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "points": [
+                {"id": 1, "vector": {"": {"float64": vector}}},
+            ]
+        },
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/{id}',
+        method="GET",
+        path_params={'collection_name': collection_name, 'id': 1},
+    )
+    assert response.ok
+    assert response.json()['result']['vector'] == {"": {"float64": vector}}
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/scroll',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={"with_vector": True},
+    )
+    assert response.ok
+    assert response.json()['result']['points'][0]['vector'] == {"": {"float64": vector}}
+
+
+def test_float64_multivectors_not_supported():
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PUT",
+        path_params={'collection_name': collection_name + '_multi'},
+        body={
+            "vectors": {
+                "size": 4,
+                "distance": "Dot",
+                "datatype": "float64",
+                "multivec_config": {
+                    "comparator": "max_sim"
+                }
+            },
+        }
+    )
+    assert response.status_code == 422
@@ -0,0 +1,51 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_id_range'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def scroll_ids(filter=None):
+    body = {"limit": 100}
+    if filter is not None:
+        body["filter"] = filter
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/scroll',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body=body,
+    )
+    assert response.ok
+    return [point['id'] for point in response.json()['result']['points']]
+
+
+def test_scroll_by_id_range():
+    ids = scroll_ids({"must": [{"has_id_range": {"gte": 2, "lt": 5}}]})
+    assert ids == [2, 3, 4]
+
+    ids = scroll_ids({"must": [{"has_id_range": {"gte": 8}}]})
+    assert ids == [8, 9, 10]
+
+    ids = scroll_ids({"must_not": [{"has_id_range": {"lt": 8}}]})
+    assert ids == [8, 9, 10]
+
+
+def test_delete_by_id_range():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/delete',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={"filter": {"must": [{"has_id_range": {"gte": 1, "lt": 4}}]}},
+    )
+    assert response.ok
+
+    assert scroll_ids() == [4, 5, 6, 7, 8, 9, 10]
@@ -0,0 +1,37 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_index_info'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def test_index_info():
+    response = request_with_validation(
+        api='/collections/{collection_name}/index_info',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+    info = response.json()['result']
+
+    assert len(info['shards']) == 1
+    shard = info['shards'][0]
+    assert shard['shard_id'] == 0
+    assert sum(segment['points_count'] for segment in shard['segments']) == 10
+
+    for segment in shard['segments']:
+        dense, sparse = segment['vectors']
+        assert dense['vector_name'] == ''
+        assert sparse['vector_name'] == 'sparse-text'
+        # Collection is too small to build HNSW graphs
+        assert dense['index_type'] == 'plain'
+        assert 'hnsw' not in dense
+        assert sparse['index_type'] == 'sparse'
@@ -0,0 +1,81 @@
+import pytest
+
+from .helpers.collection_setup import drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_multitenancy'
+
+
+@pytest.fixture(autouse=True)
+def setup():
+    drop_collection(collection_name=collection_name)
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        body={
+            "vectors": {
+                "size": 4,
+                "distance": "Dot",
+            },
+            "multitenancy_config": {
+                "tenant_key": "tenant",
+            },
+        }
+    )
+    assert response.ok
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def upsert_point(point_id, payload):
+    return request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "points": [
+                {"id": point_id, "vector": [0.1, 0.2, 0.3, 0.4], "payload": payload},
+            ]
+        },
+    )
+
+
+def test_multitenancy():
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+    result = response.json()['result']
+    assert result['config']['multitenancy_config'] == {"tenant_key": "tenant", "defragment": False}
+    assert result['payload_schema']['tenant']['data_type'] == 'keyword'
+
+    response = upsert_point(1, {"tenant": "acme", "city": "Berlin"})
+    assert response.ok
+
+    response = upsert_point(2, {"city": "Berlin"})
+    assert response.status_code == 400
+
+    response = upsert_point(3, {"tenant": ["acme", "other"]})
+    assert response.status_code == 400
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/payload/delete',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={"points": [1], "keys": ["tenant"]},
+    )
+    assert response.status_code == 400
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/payload/delete',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={"points": [1], "keys": ["city"]},
+    )
+    assert response.ok
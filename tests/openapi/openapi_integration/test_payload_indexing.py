@@ -230,6 +230,57 @@ def test_datetime_indexing():
         assert not response.ok
 
 
+def test_datetime_nanosecond_indexing():
+    datetime_key = "event_time"
+    # events within the same microsecond
+    set_payload({datetime_key: "2024-05-01T12:00:00.000000300Z"}, [1])
+    set_payload({datetime_key: "2024-05-01T12:00:00.000000100Z"}, [2])
+    set_payload({datetime_key: "2024-05-01T12:00:00.000000200Z"}, [3])
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/index',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "field_name": datetime_key,
+            "field_schema": "datetime"
+        }
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/scroll",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "with_vector": False,
+            "filter": {"must": [{"key": datetime_key, "range": {
+                "gt": "2024-05-01T12:00:00.000000100Z",
+                "lte": "2024-05-01T12:00:00.000000300Z",
+            }}]},
+        },
+    )
+    assert response.ok, response.json()
+    point_ids = sorted(p["id"] for p in response.json()["result"]["points"])
+    assert point_ids == [1, 3]
+
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/scroll",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "with_vector": False,
+            "order_by": {"key": datetime_key, "start_from": "2024-05-01T12:00:00.000000150Z"},
+        },
+    )
+    assert response.ok, response.json()
+    points = response.json()["result"]["points"]
+    assert [p["id"] for p in points] == [3, 1]
+    # payload keeps the original precision
+    assert points[0]["payload"][datetime_key] == "2024-05-01T12:00:00.000000200Z"
+
+
 def test_update_payload_on_indexed_field():
     keyword_field = "city"
 
@@ -0,0 +1,79 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_payload_patch'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def patch_payload(body):
+    return request_with_validation(
+        api='/collections/{collection_name}/points/payload/patch',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body=body,
+    )
+
+
+def get_payload(point_id):
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/{id}',
+        method="GET",
+        path_params={'collection_name': collection_name, 'id': point_id},
+    )
+    assert response.ok
+    return response.json()['result']['payload']
+
+
+def test_patch_payload():
+    response = patch_payload({
+        "points": [1],
+        "patch": [
+            {"op": "add", "path": "/tags", "value": ["a"]},
+            {"op": "add", "path": "/tags/-", "value": "b"},
+            {"op": "replace", "path": "/location/lat", "value": 10.0},
+            {"op": "move", "from": "/value", "path": "/count"},
+        ],
+    })
+    assert response.ok
+
+    payload = get_payload(1)
+    assert payload['tags'] == ["a", "b"]
+    assert payload['location']['lat'] == 10.0
+    assert payload['count'] == 1
+    assert 'value' not in payload
+
+
+def test_patch_payload_by_filter():
+    response = patch_payload({
+        "filter": {"must": [{"has_id": [2, 3]}]},
+        "patch": [{"op": "remove", "path": "/location"}],
+    })
+    assert response.ok
+
+    assert 'location' not in get_payload(2)
+    assert 'location' not in get_payload(3)
+    assert 'location' in get_payload(4)
+
+
+def test_failed_patch_is_rejected():
+    before = get_payload(1)
+
+    response = patch_payload({
+        "points": [1],
+        "patch": [
+            {"op": "replace", "path": "/value", "value": 100},
+            {"op": "test", "path": "/value", "value": 1},
+        ],
+    })
+    assert response.status_code == 400
+
+    assert get_payload(1) == before
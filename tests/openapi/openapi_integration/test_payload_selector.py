@@ -315,3 +315,79 @@ def test_payload_selectors():
             ],
         }
     }
+
+
+def test_payload_selector_patterns():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/scroll',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "filter": {
+                "must": [
+                    {
+                        "key": "country.name",
+                        "match": {
+                            "value": "Germany",
+                        }
+                    }
+                ]
+            },
+            "limit": 3,
+            "with_payload": {
+                "include": ["country.*"],
+                "exclude": ["country.cities.location", "country.cities.sight*"],
+            },
+        }
+    )
+    assert response.ok
+    assert response.json()['result']['points'][0]['payload'] == {
+        "country": {
+            "name": "Germany",
+            "capital": "Berlin",
+            "cities": [
+                {
+                    "name": "Berlin",
+                    "population": 3.7,
+                },
+                {
+                    "name": "Munich",
+                    "population": 1.5,
+                },
+                {
+                    "name": "Hamburg",
+                    "population": 1.8,
+                }
+            ],
+        }
+    }
+
+    # Exclude only, with wildcard in the middle of the path
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/scroll',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "filter": {
+                "must": [
+                    {
+                        "key": "country.name",
+                        "match": {
+                            "value": "Germany",
+                        }
+                    }
+                ]
+            },
+            "limit": 3,
+            "with_payload": {
+                "exclude": ["*.cities"],
+            },
+        }
+    )
+    assert response.ok
+    assert response.json()['result']['points'][0]['payload'] == {
+        "country": {
+            "name": "Germany",
+            "capital": "Berlin",
+        }
+    }
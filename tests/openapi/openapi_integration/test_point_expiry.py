@@ -0,0 +1,88 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_point_expiry'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def upsert(points):
+    return request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={"points": points},
+    )
+
+
+def test_point_expiry_opt_in():
+    expired_point = {
+        "id": 100,
+        "vector": [0.1, 0.2, 0.3, 0.4],
+        "expires_at": "2000-01-01T00:00:00Z",
+    }
+
+    # Points may not have own expiration moment, unless enabled for the collection
+    response = upsert([expired_point])
+    assert response.status_code == 400
+
+    # Reserved payload key can't be written directly
+    response = upsert([{
+        "id": 101,
+        "vector": [0.1, 0.2, 0.3, 0.4],
+        "payload": {"__qdrant_expires_at": "2000-01-01T00:00:00Z"},
+    }])
+    assert response.status_code == 400
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/payload',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "payload": {"__qdrant_expires_at": "2000-01-01T00:00:00Z"},
+            "points": [1],
+        },
+    )
+    assert response.status_code == 400
+
+    # User payload key with the same name as the point attribute is a plain payload
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/payload',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "payload": {"expires_at": "2000-01-01T00:00:00Z"},
+            "points": [1],
+        },
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PATCH",
+        path_params={'collection_name': collection_name},
+        body={"ttl_config": {"point_expiry": True}},
+    )
+    assert response.ok
+
+    response = upsert([expired_point])
+    assert response.ok
+
+    # Expired point is hidden immediately, point with the user payload key is not
+    for point_id, exists in [(100, False), (1, True)]:
+        response = request_with_validation(
+            api='/collections/{collection_name}/points/{id}',
+            method="GET",
+            path_params={'collection_name': collection_name, 'id': point_id},
+        )
+        assert response.ok == exists
@@ -0,0 +1,81 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_point_versions'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def get_version(point_id):
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/{id}',
+        method="GET",
+        path_params={'collection_name': collection_name, 'id': point_id},
+    )
+    assert response.ok
+    return response.json()['result']['version']
+
+
+def upsert(point_id, city, if_version):
+    return request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true', 'if_version': if_version},
+        body={
+            "points": [
+                {"id": point_id, "vector": [0.1, 0.1, 0.1, 0.1], "payload": {"city": city}},
+            ]
+        },
+    )
+
+
+def test_upsert_if_version():
+    version = get_version(1)
+
+    response = upsert(1, "Paris", version + 1000)
+    assert response.status_code == 409
+
+    response = upsert(1, "Paris", version)
+    assert response.ok
+    assert get_version(1) > version
+
+    # Stale version is rejected after the update
+    response = upsert(1, "Rome", version)
+    assert response.status_code == 409
+
+
+def test_delete_if_version():
+    version = get_version(2)
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/delete',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true', 'if_version': version + 1000},
+        body={"points": [2]},
+    )
+    assert response.status_code == 409
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/delete',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true', 'if_version': version},
+        body={"points": [2]},
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/{id}',
+        method="GET",
+        path_params={'collection_name': collection_name, 'id': 2},
+    )
+    assert response.status_code == 404
@@ -0,0 +1,48 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_points_breakdown'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def get_breakdown():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points_breakdown',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+    return response.json()['result']
+
+
+def test_points_breakdown():
+    breakdown = get_breakdown()
+    assert len(breakdown['shards']) == 1
+
+    shard = breakdown['shards'][0]
+    assert shard['shard_id'] == 0
+    assert shard['state'] == 'Active'
+    assert shard['points_count'] == 10
+    assert shard['indexed_points_count'] + shard['plain_points_count'] == 10
+    assert sum(segment['points_count'] for segment in shard['segments']) == 10
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/delete',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={"points": [1, 2, 3]},
+    )
+    assert response.ok
+
+    shard = get_breakdown()['shards'][0]
+    assert shard['points_count'] == 7
+    assert sum(segment['points_count'] for segment in shard['segments']) == 7
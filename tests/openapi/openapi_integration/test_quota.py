@@ -0,0 +1,71 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_quota'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def get_quota_usage():
+    response = request_with_validation(
+        api='/collections/{collection_name}/quota',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+    return response.json()['result']
+
+
+def upsert_point(point_id):
+    return request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "points": [
+                {"id": point_id, "vector": [0.1, 0.2, 0.3, 0.4], "payload": {"city": "Berlin"}},
+            ]
+        },
+    )
+
+
+def test_quota():
+    usage = get_quota_usage()
+    assert 'quota' not in usage
+    assert len(usage['tenants']) == 1
+    assert usage['tenants'][0]['points_count'] == 10
+    assert usage['tenants'][0]['payload_bytes'] > 0
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PATCH",
+        path_params={'collection_name': collection_name},
+        body={"quota_config": {"max_points": 11}},
+    )
+    assert response.ok
+
+    usage = get_quota_usage()
+    assert usage['quota']['max_points'] == 11
+
+    response = upsert_point(100)
+    assert response.ok
+
+    response = upsert_point(101)
+    assert response.status_code == 429
+    assert 'Quota exceeded' in response.json()['status']['error']
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+    assert response.json()['result']['points_count'] == 11
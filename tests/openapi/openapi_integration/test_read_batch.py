@@ -0,0 +1,67 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_read_batch'
+
+city_filter = {
+    "should": [
+        {
+            "key": "city",
+            "match": {
+                "value": "London"
+            }
+        },
+        {
+            "key": "city",
+            "match": {
+                "value": "Berlin"
+            }
+        }
+    ]
+}
+
+
+@pytest.fixture(autouse=True, scope="module")
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def read(api, body):
+    response = request_with_validation(
+        api=api,
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body=body,
+    )
+    assert response.ok
+    return response.json()['result']
+
+
+def test_read_batch_matches_single_operations():
+    search = {"vector": [0.2, 0.1, 0.9, 0.7], "filter": city_filter, "limit": 3}
+    recommend = {"positive": [1], "filter": city_filter, "limit": 3}
+    count = {"filter": city_filter}
+
+    results = read('/collections/{collection_name}/points/read/batch', {
+        "operations": [
+            {"search": search},
+            {"count": count},
+            {"recommend": recommend},
+            {"search": {**search, "limit": 1}},
+        ]
+    })
+
+    assert len(results) == 4
+    assert results[0] == read('/collections/{collection_name}/points/search', search)
+    assert results[1] == read('/collections/{collection_name}/points/count', count)
+    assert results[2] == read('/collections/{collection_name}/points/recommend', recommend)
+    assert results[3] == results[0][:1]
+
+
+def test_read_batch_empty():
+    assert read('/collections/{collection_name}/points/read/batch', {"operations": []}) == []
+
@@ -0,0 +1,81 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    request_with_validation(
+        api='/read_only',
+        method="POST",
+        body={"read_only": False}
+    )
+    drop_collection(collection_name=collection_name)
+
+
+def upsert_point():
+    return request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "points": [
+                {
+                    "id": 10,
+                    "vector": [0.05, -0.61, -0.76, 0.74],
+                    "payload": {"city": "Gdansk"}
+                }
+            ]
+        }
+    )
+
+
+def test_read_only_mode():
+    response = request_with_validation(
+        api='/read_only',
+        method="POST",
+        body={"read_only": True}
+    )
+    assert response.ok
+    assert response.json()['result']['read_only'] is False
+
+    response = request_with_validation(
+        api='/read_only',
+        method="GET",
+    )
+    assert response.ok
+    assert response.json()['result']['read_only'] is True
+
+    response = upsert_point()
+    assert not response.ok
+    assert "read-only" in response.text
+
+    # Reads are still served
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "vector": [0.2, 0.1, 0.9, 0.7],
+            "limit": 3
+        }
+    )
+    assert response.ok
+    assert len(response.json()['result']) == 3
+
+    response = request_with_validation(
+        api='/read_only',
+        method="POST",
+        body={"read_only": False}
+    )
+    assert response.ok
+    assert response.json()['result']['read_only'] is True
+
+    response = upsert_point()
+    assert response.ok
@@ -0,0 +1,84 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_rename'
+new_collection_name = 'test_collection_renamed'
+alias_name = 'test_collection_rename_alias'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    drop_collection(collection_name=new_collection_name)
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+    drop_collection(collection_name=new_collection_name)
+
+
+def rename_collection(name, new_name):
+    return request_with_validation(
+        api='/collections/{collection_name}/rename',
+        method="POST",
+        path_params={'collection_name': name},
+        body={"new_collection_name": new_name},
+    )
+
+
+def get_point(name, point_id):
+    return request_with_validation(
+        api='/collections/{collection_name}/points/{id}',
+        method="GET",
+        path_params={'collection_name': name, 'id': point_id},
+    )
+
+
+def test_rename_collection():
+    response = request_with_validation(
+        api='/collections/aliases',
+        method="POST",
+        body={
+            "actions": [
+                {"create_alias": {"collection_name": collection_name, "alias_name": alias_name}},
+            ]
+        },
+    )
+    assert response.ok
+
+    response = rename_collection(collection_name, new_collection_name)
+    assert response.ok
+
+    response = get_point(new_collection_name, 1)
+    assert response.ok
+    assert response.json()['result']['id'] == 1
+
+    response = get_point(collection_name, 1)
+    assert response.status_code == 404
+
+    # Alias follows the collection
+    response = get_point(alias_name, 1)
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/aliases',
+        method="GET",
+        path_params={'collection_name': new_collection_name},
+    )
+    assert response.ok
+    assert [alias['alias_name'] for alias in response.json()['result']['aliases']] == [alias_name]
+
+
+def test_rename_to_existing_collection():
+    basic_collection_setup(collection_name=new_collection_name)
+
+    response = rename_collection(collection_name, new_collection_name)
+    assert response.status_code == 409
+
+    response = get_point(collection_name, 1)
+    assert response.ok
+
+
+def test_rename_missing_collection():
+    response = rename_collection('i-do-not-exist', new_collection_name)
+    assert response.status_code == 404
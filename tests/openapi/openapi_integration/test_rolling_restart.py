@@ -0,0 +1,88 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    request_with_validation(api='/restart/uncordon', method="POST")
+    request_with_validation(
+        api='/read_only',
+        method="POST",
+        body={"read_only": False}
+    )
+    drop_collection(collection_name=collection_name)
+
+
+def upsert_point():
+    return request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "points": [
+                {
+                    "id": 10,
+                    "vector": [0.05, -0.61, -0.76, 0.74],
+                    "payload": {"city": "Gdansk"}
+                }
+            ]
+        }
+    )
+
+
+def get_restart_status():
+    response = request_with_validation(api='/restart', method="GET")
+    assert response.ok
+    return response.json()['result']
+
+
+def test_rolling_restart():
+    status = get_restart_status()
+    assert status['stage'] == 'serving'
+    assert status['cordoned'] is False
+
+    response = request_with_validation(api='/restart/cordon', method="POST")
+    assert response.ok
+    status = response.json()['result']
+    assert status['cordoned'] is True
+    assert status['stage'] == 'ready'
+    assert status['in_flight_operations'] == 0
+    assert status['shard_transfers'] == 0
+
+    response = upsert_point()
+    assert not response.ok
+    assert "read-only" in response.text
+
+    response = request_with_validation(api='/restart/uncordon', method="POST")
+    assert response.ok
+    status = response.json()['result']
+    assert status['cordoned'] is False
+    assert status['stage'] == 'serving'
+
+    response = upsert_point()
+    assert response.ok
+
+
+def test_uncordon_keeps_read_only_mode():
+    response = request_with_validation(
+        api='/read_only',
+        method="POST",
+        body={"read_only": True}
+    )
+    assert response.ok
+
+    response = request_with_validation(api='/restart/cordon', method="POST")
+    assert response.ok
+    response = request_with_validation(api='/restart/uncordon', method="POST")
+    assert response.ok
+
+    response = request_with_validation(api='/read_only', method="GET")
+    assert response.ok
+    assert response.json()['result']['read_only'] is True
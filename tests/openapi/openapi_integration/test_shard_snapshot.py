@@ -111,6 +111,64 @@ def test_shard_snapshot_operations(http_server):
     assert response.ok
 
 
+def test_shard_recovery_from_collection_snapshot():
+    # create snapshot of the whole collection
+    response = request_with_validation(
+        api='/collections/{collection_name}/snapshots',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+    )
+    assert response.ok
+    snapshot_name = response.json()['result']['name']
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+    points_count = response.json()['result']['points_count']
+
+    # lose data of the shard
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/delete',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={"filter": {}},
+    )
+    assert response.ok
+
+    # recover only the shard from the collection snapshot, stored on this node
+    response = request_with_validation(
+        api='/collections/{collection_name}/shards/{shard_id}/snapshots/recover',
+        method="PUT",
+        path_params={'shard_id': 0, 'collection_name': collection_name},
+        body={
+            "location": snapshot_name,
+            "wait": "true",
+        },
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+    assert response.json()['result']['points_count'] == points_count
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/snapshots/{snapshot_name}',
+        method="DELETE",
+        path_params={'collection_name': collection_name, 'snapshot_name': snapshot_name},
+        query_params={'wait': 'true'},
+    )
+    assert response.ok
+
+
 @pytest.mark.timeout(20)
 def test_shard_snapshot_operations_non_wait():
     # there no snapshot on collection
@@ -166,6 +166,241 @@ def test_collection_snapshot_operations(http_server):
     assert len(response.json()['result']['points']) == 10
 
 
+def test_collection_snapshot_streaming(http_server):
+    (srv_dir, srv_url) = http_server
+
+    # stream snapshot without storing it
+    response = request_with_validation(
+        api='/collections/{collection_name}/snapshot',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+    assert 'attachment' in response.headers['Content-Disposition']
+    with open(srv_dir / "streamed_snapshot.tar", 'wb') as f:
+        f.write(response.content)
+
+    # streamed snapshot is not stored
+    response = request_with_validation(
+        api='/collections/{collection_name}/snapshots',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+    assert len(response.json()['result']) == 0
+
+    # delete collection
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="DELETE",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+
+    # recover collection from streamed snapshot
+    response = request_with_validation(
+        api='/collections/{collection_name}/snapshots/recover',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        body={
+            "location": f"{srv_url}/streamed_snapshot.tar",
+            "wait": "true",
+        },
+    )
+    assert response.ok
+
+    # validate that the collection is recovered
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/scroll',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={},
+    )
+    assert response.ok
+    assert len(response.json()['result']['points']) == 10
+
+
+def test_collection_snapshot_encryption(http_server):
+    (srv_dir, srv_url) = http_server
+    encryption_key = "00112233445566778899aabbccddeeff00112233445566778899aabbccddeeff"
+
+    # create encrypted snapshot
+    response = request_with_validation(
+        api='/collections/{collection_name}/snapshots',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={"encryption_key": encryption_key},
+    )
+    assert response.ok
+    snapshot_name = response.json()['result']['name']
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/snapshots/{snapshot_name}',
+        method="GET",
+        path_params={'collection_name': collection_name, 'snapshot_name': snapshot_name},
+    )
+    assert response.ok
+    # encrypted snapshot is not a tar archive
+    assert response.content[257:262] != b"ustar"
+    with open(srv_dir / "encrypted_snapshot.tar", 'wb') as f:
+        f.write(response.content)
+
+    # recovery requires the key
+    response = request_with_validation(
+        api='/collections/{collection_name}/snapshots/recover',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        body={
+            "location": f"{srv_url}/encrypted_snapshot.tar",
+            "wait": "true",
+        },
+    )
+    assert response.status_code == 400
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/snapshots/recover',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        body={
+            "location": f"{srv_url}/encrypted_snapshot.tar",
+            "encryption_key": encryption_key,
+            "wait": "true",
+        },
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/scroll',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={},
+    )
+    assert response.ok
+    assert len(response.json()['result']['points']) == 10
+
+
+def test_collection_snapshot_point_in_time(http_server):
+    (srv_dir, srv_url) = http_server
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/snapshot',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+    with open(srv_dir / "pitr_snapshot.tar", 'wb') as f:
+        f.write(response.content)
+
+    # exactly one of timestamp and operation id is required
+    response = request_with_validation(
+        api='/collections/{collection_name}/snapshots/recover',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        body={
+            "location": f"{srv_url}/pitr_snapshot.tar",
+            "point_in_time": {"timestamp": "2024-05-01T00:00:00Z", "operation_id": 10},
+        },
+    )
+    assert response.status_code == 422
+
+    # WAL of the collection is not archived
+    response = request_with_validation(
+        api='/collections/{collection_name}/snapshots/recover',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        body={
+            "location": f"{srv_url}/pitr_snapshot.tar",
+            "point_in_time": {"operation_id": 1000},
+        },
+    )
+    assert response.status_code == 404
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/snapshots/recover',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+    status = response.json()['result']
+    assert status['stage'] == 'failed'
+    assert status['error']
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/snapshots/recover',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        body={"location": f"{srv_url}/pitr_snapshot.tar"},
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/snapshots/recover',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+    status = response.json()['result']
+    assert status['stage'] == 'finished'
+    assert status['shards_recovered'] == status['shards_total']
+    assert 'error' not in status
+
+
+def test_collection_snapshot_schedule():
+    # no schedule on collection
+    response = request_with_validation(
+        api='/collections/{collection_name}/snapshots/schedule',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.status_code == 404
+
+    # invalid cron expression
+    response = request_with_validation(
+        api='/collections/{collection_name}/snapshots/schedule',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        body={"cron": "61 * * * *"},
+    )
+    assert response.status_code == 422
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/snapshots/schedule',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        body={
+            "cron": "0 3 * * *",
+            "retention": {"keep_last": 3, "keep_days": 7},
+        },
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/snapshots/schedule',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+    schedule = response.json()['result']
+    assert schedule['cron'] == "0 3 * * *"
+    assert schedule['retention'] == {"keep_last": 3, "keep_days": 7}
+    assert schedule['next_run'].endswith("03:00:00")
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/snapshots/schedule',
+        method="DELETE",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/snapshots/schedule',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.status_code == 404
+
+
 def test_full_snapshot_operations():
     # no full snapshot
     response = request_with_validation(
@@ -0,0 +1,80 @@
+import pytest
+
+from .helpers.collection_setup import drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_sparse_text'
+
+
+@pytest.fixture(autouse=True)
+def setup():
+    drop_collection(collection_name=collection_name)
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        body={
+            "sparse_vectors": {
+                "text": {
+                    "modifier": "idf",
+                    "text": {"tokenizer": "word", "lowercase": True},
+                },
+            },
+        }
+    )
+    assert response.ok
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def test_sparse_vectors_from_text():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "points": [
+                {"id": 1, "vector": {"text": {"text": "The cat sat on the mat"}}},
+                {"id": 2, "vector": {"text": {"text": "The dog ate the bone"}}},
+            ]
+        },
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/{id}',
+        method="GET",
+        path_params={'collection_name': collection_name, 'id': 1},
+    )
+    assert response.ok
+    vector = response.json()['result']['vector']['text']
+    assert len(vector['indices']) == 5
+    assert sorted(vector['values']) == [1.0, 1.0, 1.0, 1.0, 2.0]
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "vector": {"name": "text", "vector": {"text": "Dog"}},
+            "limit": 10,
+        },
+    )
+    assert response.ok
+    assert [point['id'] for point in response.json()['result']] == [2]
+
+
+def test_sparse_text_from_image():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "points": [
+                {"id": 1, "vector": {"text": {"image": "https://example.com/cat.png"}}},
+            ]
+        },
+    )
+    assert response.status_code == 400
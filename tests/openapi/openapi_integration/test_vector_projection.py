@@ -0,0 +1,164 @@
+import pytest
+
+from .helpers.collection_setup import drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_vector_projection'
+
+
+@pytest.fixture(autouse=True)
+def setup():
+    drop_collection(collection_name=collection_name)
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        body={
+            "vectors": {
+                "reduced": {
+                    "size": 2,
+                    "distance": "Dot",
+                    "projection": {
+                        "input_size": 4,
+                        "method": {"matrix": {"matrix": [[1, 0, 0, 0], [0, 1, 0, 0]]}},
+                        "original_vector": "full",
+                    },
+                },
+                "full": {
+                    "size": 4,
+                    "distance": "Dot",
+                    "on_disk": True,
+                },
+            },
+        }
+    )
+    assert response.ok
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def test_vector_projection():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "points": [
+                {"id": 1, "vector": {"reduced": [1.0, 0.0, 0.0, 10.0]}},
+                {"id": 2, "vector": {"reduced": [2.0, 0.0, 0.0, 0.0]}},
+            ]
+        },
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/{id}',
+        method="GET",
+        path_params={'collection_name': collection_name, 'id': 1},
+    )
+    assert response.ok
+    vectors = response.json()['result']['vector']
+    assert vectors['reduced'] == [1.0, 0.0]
+    assert vectors['full'] == [1.0, 0.0, 0.0, 10.0]
+
+    # Projected vectors prefer point 2, original vectors prefer point 1
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "vector": {"name": "reduced", "vector": [1.0, 0.0, 0.0, 1.0]},
+            "limit": 1,
+        },
+    )
+    assert response.ok
+    result = response.json()['result']
+    assert [point['id'] for point in result] == [1]
+    assert result[0]['score'] == 11.0
+
+    # Vectors of a wrong size are rejected
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={"points": [{"id": 3, "vector": {"reduced": [1.0, 0.0]}}]},
+    )
+    assert response.status_code == 400
+
+
+def test_invalid_projection():
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PUT",
+        path_params={'collection_name': collection_name + '_invalid'},
+        body={
+            "vectors": {
+                "size": 2,
+                "distance": "Dot",
+                "projection": {
+                    "input_size": 4,
+                    "method": {"matrix": {"matrix": [[1, 0, 0, 0]]}},
+                },
+            },
+        }
+    )
+    assert response.status_code == 400
+
+
+def test_matryoshka_prefix():
+    name = collection_name + '_prefix'
+    drop_collection(collection_name=name)
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PUT",
+        path_params={'collection_name': name},
+        body={
+            "vectors": {
+                "prefix": {
+                    "size": 2,
+                    "distance": "Dot",
+                    "projection": {
+                        "input_size": 4,
+                        "method": "prefix",
+                        "original_vector": "full",
+                    },
+                },
+                "full": {"size": 4, "distance": "Dot", "on_disk": True},
+            },
+        }
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': name},
+        query_params={'wait': 'true'},
+        body={
+            "points": [
+                {"id": 1, "vector": {"prefix": [1.0, 0.0, 5.0, 5.0]}},
+                {"id": 2, "vector": {"prefix": [2.0, 0.0, 0.0, 0.0]}},
+            ]
+        },
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search',
+        method="POST",
+        path_params={'collection_name': name},
+        body={
+            "vector": {"name": "prefix", "vector": [1.0, 0.0, 1.0, 1.0]},
+            "limit": 2,
+            "with_vector": ["prefix"],
+        },
+    )
+    assert response.ok
+    result = response.json()['result']
+    assert [point['id'] for point in result] == [1, 2]
+    assert [point['score'] for point in result] == [11.0, 2.0]
+    assert result[0]['vector']['prefix'] == [1.0, 0.0]
+
+    drop_collection(collection_name=name)